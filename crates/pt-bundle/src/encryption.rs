@@ -1,7 +1,9 @@
 //! Bundle encryption helpers (optional).
 //!
-//! Bundles can be encrypted at rest with a passphrase-derived key.
-//! This is an outer envelope over the ZIP payload.
+//! Bundles can be encrypted at rest with a passphrase-derived key, or to one
+//! or more age X25519 recipients (`age1...`) for automated pipelines that
+//! cannot share a passphrase. Either way this is an outer envelope over the
+//! ZIP payload.
 
 use crate::{BundleError, Result};
 use chacha20poly1305::aead::{Aead, KeyInit};
@@ -9,6 +11,8 @@ use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use pbkdf2::pbkdf2_hmac;
 use rand_core::{OsRng, RngCore};
 use sha2::Sha256;
+use std::io::{Read, Write};
+use std::path::Path;
 
 const MAGIC: &[u8; 8] = b"PTBENC01";
 const SALT_LEN: usize = 16;
@@ -18,6 +22,11 @@ const KDF_ITERS: u32 = 100_000;
 /// Maximum iterations accepted during decryption to prevent DoS via crafted bundles.
 const MAX_KDF_ITERS: u32 = 10_000_000;
 const HEADER_LEN: usize = 8 + 4 + SALT_LEN + NONCE_LEN;
+/// Header line of the binary age format.
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1";
+
+/// Environment variable naming an age identity file for decrypting bundles.
+pub const IDENTITY_ENV: &str = "PT_BUNDLE_IDENTITY";
 
 fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; KEY_LEN] {
     let mut key = [0u8; KEY_LEN];
@@ -64,6 +73,95 @@ pub fn is_encrypted_prefix(prefix: &[u8]) -> bool {
     prefix.len() == MAGIC.len() && prefix == MAGIC
 }
 
+/// Return true if the buffer is an age-encrypted bundle.
+pub fn is_age_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(AGE_MAGIC)
+}
+
+/// Return true if an 8-byte prefix starts the age header.
+pub fn is_age_encrypted_prefix(prefix: &[u8]) -> bool {
+    prefix.len() == MAGIC.len() && AGE_MAGIC.starts_with(prefix)
+}
+
+/// Parse age X25519 recipients (`age1...`).
+pub fn parse_recipients<S: AsRef<str>>(recipients: &[S]) -> Result<Vec<age::x25519::Recipient>> {
+    recipients
+        .iter()
+        .map(|r| {
+            let r = r.as_ref().trim();
+            r.parse::<age::x25519::Recipient>()
+                .map_err(|_| BundleError::InvalidRecipient(r.to_string()))
+        })
+        .collect()
+}
+
+/// Load X25519 identities from an age identity file (as written by `age-keygen`).
+///
+/// Blank lines and `#` comments are skipped.
+pub fn load_identities(path: &Path) -> Result<Vec<age::x25519::Identity>> {
+    let invalid = |reason: String| BundleError::InvalidIdentity {
+        path: path.display().to_string(),
+        reason,
+    };
+    let content = std::fs::read_to_string(path)?;
+    let mut identities = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let identity = line
+            .parse::<age::x25519::Identity>()
+            .map_err(|_| invalid(format!("line {} is not an X25519 secret key", index + 1)))?;
+        identities.push(identity);
+    }
+    if identities.is_empty() {
+        return Err(invalid("no identities found".to_string()));
+    }
+    Ok(identities)
+}
+
+/// Identity file from `PT_BUNDLE_IDENTITY`, if set.
+pub fn identity_path_from_env() -> Option<std::path::PathBuf> {
+    std::env::var_os(IDENTITY_ENV)
+        .filter(|value| !value.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// Encrypt bundle bytes to age X25519 recipients.
+pub fn encrypt_to_recipients(
+    plaintext: &[u8],
+    recipients: &[age::x25519::Recipient],
+) -> Result<Vec<u8>> {
+    let encryptor =
+        age::Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))
+            .map_err(|_| BundleError::EncryptionFailed)?;
+    let mut output = Vec::with_capacity(plaintext.len() + 256);
+    let mut writer = encryptor.wrap_output(&mut output)?;
+    writer.write_all(plaintext)?;
+    writer.finish()?;
+    Ok(output)
+}
+
+/// Decrypt an age-encrypted bundle with any of the given identities.
+pub fn decrypt_with_identities(
+    bytes: &[u8],
+    identities: &[age::x25519::Identity],
+) -> Result<Vec<u8>> {
+    if !is_age_encrypted(bytes) {
+        return Err(BundleError::NotEncrypted);
+    }
+    let decryptor = age::Decryptor::new(bytes).map_err(|_| BundleError::InvalidEncryptionHeader)?;
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|i| i as &dyn age::Identity))
+        .map_err(|_| BundleError::DecryptionFailed)?;
+    let mut plaintext = Vec::new();
+    reader
+        .read_to_end(&mut plaintext)
+        .map_err(|_| BundleError::DecryptionFailed)?;
+    Ok(plaintext)
+}
+
 /// Encrypt bundle bytes using a passphrase-derived key.
 pub fn encrypt_bytes(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
     if passphrase.is_empty() {
@@ -133,6 +231,66 @@ mod tests {
         assert!(matches!(result, Err(BundleError::DecryptionFailed)));
     }
 
+    #[test]
+    fn test_age_roundtrip_any_recipient_decrypts() {
+        let alice = age::x25519::Identity::generate();
+        let bob = age::x25519::Identity::generate();
+        let recipients =
+            parse_recipients(&[alice.to_public().to_string(), bob.to_public().to_string()])
+                .unwrap();
+
+        let encrypted = encrypt_to_recipients(b"pt-bundle age", &recipients).unwrap();
+        assert!(is_age_encrypted(&encrypted));
+        assert!(!is_encrypted(&encrypted));
+        assert!(is_age_encrypted_prefix(&encrypted[..8]));
+
+        assert_eq!(
+            decrypt_with_identities(&encrypted, &[bob]).unwrap(),
+            b"pt-bundle age"
+        );
+        let mallory = age::x25519::Identity::generate();
+        assert!(matches!(
+            decrypt_with_identities(&encrypted, &[mallory]),
+            Err(BundleError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_parse_recipients_rejects_garbage() {
+        let result = parse_recipients(&["age1notakey"]);
+        assert!(matches!(result, Err(BundleError::InvalidRecipient(_))));
+    }
+
+    #[test]
+    fn test_load_identities_skips_comments() {
+        use age::secrecy::ExposeSecret;
+
+        let identity = age::x25519::Identity::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.txt");
+        std::fs::write(
+            &path,
+            format!(
+                "# created: 2026-01-01\n# public key: {}\n{}\n",
+                identity.to_public(),
+                identity.to_string().expose_secret()
+            ),
+        )
+        .unwrap();
+        let loaded = load_identities(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(
+            loaded[0].to_public().to_string(),
+            identity.to_public().to_string()
+        );
+
+        std::fs::write(&path, "# nothing here\n").unwrap();
+        assert!(matches!(
+            load_identities(&path),
+            Err(BundleError::InvalidIdentity { .. })
+        ));
+    }
+
     #[test]
     fn test_parse_header_rejects_short_input() {
         let result = decrypt_bytes(b"short", "secret");
@@ -84,6 +84,30 @@ pub enum BundleError {
     /// Decryption failed (bad key or tampered data)
     #[error("bundle decryption failed")]
     DecryptionFailed,
+
+    /// Bundle is encrypted to age recipients; identity required
+    #[error("bundle is encrypted to age recipients; identity required (use an identity file or PT_BUNDLE_IDENTITY)")]
+    EncryptedBundleRequiresIdentity,
+
+    /// Recipient string is not an age X25519 public key
+    #[error("invalid age recipient: {0}")]
+    InvalidRecipient(String),
+
+    /// Identity file contains no usable age X25519 secret key
+    #[error("invalid age identity file {path}: {reason}")]
+    InvalidIdentity { path: String, reason: String },
+
+    /// Delta bundle file lives in the base bundle, which has not been resolved
+    #[error("'{0}' is carried over from the base bundle; resolve the base first")]
+    BaseRequired(String),
+
+    /// Base bundle does not match the delta's base reference
+    #[error("base bundle mismatch: delta expects session {expected}, got {actual}")]
+    BaseMismatch { expected: String, actual: String },
+
+    /// Bundle is not a delta bundle
+    #[error("bundle is not a delta bundle")]
+    NotDelta,
 }
 
 /// Result type alias for bundle operations.
@@ -13,6 +13,19 @@
 //! - `logs/`: JSONL logs (optional)
 //! - `report.html`: Generated report (optional)
 //!
+//! # Delta Bundles
+//!
+//! A delta bundle (`BundleWriter::with_base`) omits artifacts whose checksum
+//! matches the base session's and lists them under `manifest.base` instead.
+//! `BundleReader::resolve_base` reads those files from the base bundle.
+//!
+//! # Encryption
+//!
+//! Bundles can be sealed with a passphrase (`BundleWriter::write_encrypted`)
+//! or to age X25519 recipients (`BundleWriter::write_to_recipients`).
+//! `BundleReader::open_with_keys` decrypts either; age identities come from
+//! an identity file or the `PT_BUNDLE_IDENTITY` environment variable.
+//!
 //! # Export Profiles
 //!
 //! Three profiles control inclusion and redaction:
@@ -47,9 +60,12 @@ pub mod manifest;
 pub mod reader;
 pub mod writer;
 
-pub use encryption::{decrypt_bytes, encrypt_bytes, is_encrypted};
+pub use encryption::{
+    decrypt_bytes, decrypt_with_identities, encrypt_bytes, encrypt_to_recipients, is_age_encrypted,
+    is_encrypted, IDENTITY_ENV,
+};
 pub use error::{BundleError, Result};
-pub use manifest::{BundleManifest, FileEntry, BUNDLE_SCHEMA_VERSION};
+pub use manifest::{BaseReference, BundleManifest, FileEntry, BUNDLE_SCHEMA_VERSION};
 pub use pt_redact::ExportProfile;
 pub use reader::BundleReader;
 pub use writer::{BundleWriter, FileType};
@@ -5,9 +5,12 @@
 //! - File listing with SHA-256 checksums
 //! - Redaction policy version used
 //! - Export profile applied
+//! - Raw-capture allowlist declared for forensic bundles
+//! - Base reference for delta bundles (files carried over from an earlier session)
 
 use chrono::{DateTime, Utc};
-use pt_redact::ExportProfile;
+use pt_redact::{CaptureAllowlist, ExportProfile};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
@@ -18,7 +21,7 @@ pub const BUNDLE_SCHEMA_VERSION: &str = "1.0.0";
 pub const MANIFEST_FILE_NAME: &str = "manifest.json";
 
 /// Bundle manifest containing metadata and file checksums.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BundleManifest {
     /// Bundle format version.
     pub bundle_version: String,
@@ -44,9 +47,23 @@ pub struct BundleManifest {
     /// SHA-256 hash of the redaction policy file.
     pub redaction_policy_hash: String,
 
+    /// Values that may appear unredacted (forensic profile only).
+    ///
+    /// Declared when the bundle is created; anything not listed here was
+    /// passed through the redaction policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_allowlist: Option<CaptureAllowlist>,
+
     /// Files included in the bundle with checksums.
     pub files: Vec<FileEntry>,
 
+    /// Base reference when this is a delta bundle.
+    ///
+    /// Delta bundles only carry artifacts that changed since the base
+    /// session; unchanged ones are listed here and read from the base bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<BaseReference>,
+
     /// Optional description or notes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -72,7 +89,9 @@ impl BundleManifest {
             export_profile,
             redaction_policy_version: "1.0.0".to_string(),
             redaction_policy_hash: String::new(),
+            capture_allowlist: None,
             files: Vec::new(),
+            base: None,
             description: None,
             pt_version: None,
         }
@@ -89,6 +108,12 @@ impl BundleManifest {
         self
     }
 
+    /// Declare the raw-capture allowlist (empty allowlists are not recorded).
+    pub fn with_capture_allowlist(mut self, allowlist: CaptureAllowlist) -> Self {
+        self.capture_allowlist = (!allowlist.is_empty()).then_some(allowlist);
+        self
+    }
+
     /// Set the pt version.
     pub fn with_pt_version(mut self, version: impl Into<String>) -> Self {
         self.pt_version = Some(version.into());
@@ -121,10 +146,22 @@ impl BundleManifest {
         self.files.iter().find(|f| f.path == path)
     }
 
+    /// Whether this is a delta bundle.
+    pub fn is_delta(&self) -> bool {
+        self.base.is_some()
+    }
+
+    /// Find a file carried over unchanged from the base bundle.
+    pub fn find_base_file(&self, path: &str) -> Option<&FileEntry> {
+        self.base
+            .as_ref()
+            .and_then(|base| base.unchanged.iter().find(|f| f.path == path))
+    }
+
     /// Compute the checksum of the manifest content itself (excluding this field).
     pub fn compute_self_checksum(&self) -> String {
         // Create a version without files for checksum (to avoid chicken-egg)
-        let canonical = serde_json::json!({
+        let mut canonical = serde_json::json!({
             "bundle_version": self.bundle_version,
             "schema_version": self.schema_version,
             "created_at": self.created_at.to_rfc3339(),
@@ -134,6 +171,12 @@ impl BundleManifest {
             "redaction_policy_version": self.redaction_policy_version,
             "redaction_policy_hash": self.redaction_policy_hash,
         });
+        if let Some(ref allowlist) = self.capture_allowlist {
+            canonical["capture_allowlist"] = serde_json::json!(allowlist);
+        }
+        if let Some(ref base) = self.base {
+            canonical["base_session_id"] = serde_json::json!(base.session_id);
+        }
 
         let json = serde_json::to_string(&canonical).unwrap_or_default();
         let mut hasher = Sha256::new();
@@ -162,8 +205,33 @@ impl BundleManifest {
             ));
         }
 
+        if let Some(ref allowlist) = self.capture_allowlist {
+            allowlist
+                .validate(self.export_profile)
+                .map_err(|e| crate::BundleError::CorruptedManifest(e.to_string()))?;
+        }
+
+        let unchanged = self.base.iter().flat_map(|base| base.unchanged.iter());
+        if let Some(ref base) = self.base {
+            if base.session_id.is_empty() {
+                return Err(crate::BundleError::CorruptedManifest(
+                    "base session_id is empty".to_string(),
+                ));
+            }
+            if let Some(dup) = base
+                .unchanged
+                .iter()
+                .find(|f| self.find_file(&f.path).is_some())
+            {
+                return Err(crate::BundleError::CorruptedManifest(format!(
+                    "file '{}' is both included and carried over from the base",
+                    dup.path
+                )));
+            }
+        }
+
         // Validate file entries
-        for file in &self.files {
+        for file in self.files.iter().chain(unchanged) {
             if file.path.is_empty() {
                 return Err(crate::BundleError::CorruptedManifest(
                     "file entry has empty path".to_string(),
@@ -200,6 +268,9 @@ impl BundleManifest {
     /// Sort files for deterministic ordering.
     pub fn sort_files(&mut self) {
         self.files.sort_by(|a, b| a.path.cmp(&b.path));
+        if let Some(ref mut base) = self.base {
+            base.unchanged.sort_by(|a, b| a.path.cmp(&b.path));
+        }
     }
 
     /// Serialize to JSON with consistent formatting.
@@ -213,8 +284,28 @@ impl BundleManifest {
     }
 }
 
+/// Reference from a delta bundle to the bundle it was built against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BaseReference {
+    /// Session ID of the base bundle.
+    pub session_id: String,
+
+    /// Files identical to the base, with the checksums they must match there.
+    pub unchanged: Vec<FileEntry>,
+}
+
+impl BaseReference {
+    /// Create a base reference with no unchanged files yet.
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            unchanged: Vec::new(),
+        }
+    }
+}
+
 /// File entry in the manifest with checksum.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FileEntry {
     /// Path within the bundle (relative).
     pub path: String,
@@ -341,6 +432,68 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_manifest_capture_allowlist() {
+        let allowlist = CaptureAllowlist::new().with_env_names(["RUST_LOG"]);
+        let mut manifest = BundleManifest::new("session-123", "host-abc", ExportProfile::Forensic)
+            .with_capture_allowlist(allowlist.clone());
+        manifest.add_file(FileEntry::new("test.json", "a".repeat(64), 100));
+        assert!(manifest.validate().is_ok());
+
+        // The allowlist is part of the manifest's own checksum.
+        let plain = BundleManifest {
+            capture_allowlist: None,
+            ..manifest.clone()
+        };
+        assert_ne!(
+            manifest.compute_self_checksum(),
+            plain.compute_self_checksum()
+        );
+
+        let parsed = BundleManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.capture_allowlist, Some(allowlist));
+
+        // Empty allowlists are not recorded.
+        let manifest = BundleManifest::new("session-123", "host-abc", ExportProfile::Forensic)
+            .with_capture_allowlist(CaptureAllowlist::new());
+        assert!(manifest.capture_allowlist.is_none());
+    }
+
+    #[test]
+    fn test_manifest_validate_allowlist_requires_forensic() {
+        let mut manifest = BundleManifest::new("session-123", "host-abc", ExportProfile::Safe)
+            .with_capture_allowlist(CaptureAllowlist::new().with_env_names(["RUST_LOG"]));
+        manifest.add_file(FileEntry::new("test.json", "a".repeat(64), 100));
+        assert!(manifest.validate().is_err());
+    }
+
+    #[test]
+    fn test_manifest_delta_base() {
+        let mut base = BaseReference::new("session-100");
+        base.unchanged
+            .push(FileEntry::new("snapshot.json", "b".repeat(64), 500));
+        let mut manifest = BundleManifest::new("session-123", "host-abc", ExportProfile::Safe);
+        manifest.add_file(FileEntry::new("plan.json", "a".repeat(64), 100));
+        manifest.base = Some(base);
+
+        assert!(manifest.is_delta());
+        assert!(manifest.validate().is_ok());
+        assert!(manifest.find_base_file("snapshot.json").is_some());
+        assert!(manifest.find_file("snapshot.json").is_none());
+
+        let parsed = BundleManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.base, manifest.base);
+
+        // A path cannot be both included and carried over.
+        manifest
+            .base
+            .as_mut()
+            .unwrap()
+            .unchanged
+            .push(FileEntry::new("plan.json", "c".repeat(64), 100));
+        assert!(manifest.validate().is_err());
+    }
+
     #[test]
     fn test_manifest_json_roundtrip() {
         let mut manifest = BundleManifest::new("session-123", "host-abc", ExportProfile::Safe)
@@ -1,6 +1,7 @@
 //! Bundle reader for opening and verifying .ptb files.
 //!
-//! Reads ZIP archives with integrity verification.
+//! Reads ZIP archives with integrity verification. Delta bundles read files
+//! carried over from their base once the base has been resolved.
 
 use crate::encryption;
 use crate::{BundleError, BundleManifest, FileEntry, Result, BUNDLE_SCHEMA_VERSION};
@@ -19,6 +20,8 @@ pub struct BundleReader<R: Read + std::io::Seek> {
     manifest: BundleManifest,
     archive: ZipArchive<R>,
     verified: HashMap<String, bool>,
+    /// Contents of delta files carried over from the base bundle.
+    resolved: HashMap<String, Vec<u8>>,
 }
 
 impl BundleReader<File> {
@@ -31,6 +34,9 @@ impl BundleReader<File> {
         if bytes_read == magic.len() && encryption::is_encrypted_prefix(&magic) {
             return Err(BundleError::EncryptedBundleRequiresPassphrase);
         }
+        if bytes_read == magic.len() && encryption::is_age_encrypted_prefix(&magic) {
+            return Err(BundleError::EncryptedBundleRequiresIdentity);
+        }
 
         file.seek(SeekFrom::Start(0))?;
         Self::from_reader(file)
@@ -45,21 +51,38 @@ impl BundleReader<Cursor<Vec<u8>>> {
     }
 
     /// Open a bundle from a file path, optionally using a passphrase.
+    ///
+    /// Age-encrypted bundles use the identity file named by `PT_BUNDLE_IDENTITY`.
     pub fn open_with_passphrase(path: &Path, passphrase: Option<&str>) -> Result<Self> {
-        let mut file = File::open(path)?;
-        let mut magic = [0u8; 8];
-        let bytes_read = file.read(&mut magic)?;
+        Self::open_with_keys(path, passphrase, None)
+    }
 
-        file.seek(SeekFrom::Start(0))?;
-        let mut data = Vec::new();
-        file.read_to_end(&mut data)?;
+    /// Open a bundle from a file path, decrypting with whichever key matches
+    /// its envelope: a passphrase, or an age identity file (falling back to
+    /// `PT_BUNDLE_IDENTITY`).
+    pub fn open_with_keys(
+        path: &Path,
+        passphrase: Option<&str>,
+        identity: Option<&Path>,
+    ) -> Result<Self> {
+        let data = std::fs::read(path)?;
 
-        if bytes_read == magic.len() && encryption::is_encrypted_prefix(&magic) {
+        if encryption::is_encrypted(&data) {
             let passphrase = passphrase.ok_or(BundleError::EncryptedBundleRequiresPassphrase)?;
             let decrypted = encryption::decrypt_bytes(&data, passphrase)?;
             return Self::from_bytes(decrypted);
         }
 
+        if encryption::is_age_encrypted(&data) {
+            let identity_path = identity
+                .map(Path::to_path_buf)
+                .or_else(encryption::identity_path_from_env)
+                .ok_or(BundleError::EncryptedBundleRequiresIdentity)?;
+            let identities = encryption::load_identities(&identity_path)?;
+            let decrypted = encryption::decrypt_with_identities(&data, &identities)?;
+            return Self::from_bytes(decrypted);
+        }
+
         Self::from_bytes(data)
     }
 }
@@ -86,6 +109,7 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
             manifest,
             archive,
             verified: HashMap::new(),
+            resolved: HashMap::new(),
         })
     }
 
@@ -138,15 +162,89 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
         &self.manifest.files
     }
 
-    /// Check if a file exists in the bundle.
+    /// Check if a file exists in the bundle (including delta files carried over from the base).
     pub fn has_file(&self, path: &str) -> bool {
-        self.manifest.find_file(path).is_some()
+        self.find_entry(path).is_some()
+    }
+
+    /// Whether this is a delta bundle.
+    pub fn is_delta(&self) -> bool {
+        self.manifest.is_delta()
+    }
+
+    /// Whether every file carried over from the base has been resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.manifest.base.as_ref().is_none_or(|base| {
+            base.unchanged
+                .iter()
+                .all(|f| self.resolved.contains_key(&f.path))
+        })
+    }
+
+    /// Manifest entries for every file, including those carried over from the base.
+    pub fn all_files(&self) -> Vec<&FileEntry> {
+        let unchanged = self
+            .manifest
+            .base
+            .iter()
+            .flat_map(|base| base.unchanged.iter());
+        self.manifest.files.iter().chain(unchanged).collect()
+    }
+
+    fn find_entry(&self, path: &str) -> Option<&FileEntry> {
+        self.manifest
+            .find_file(path)
+            .or_else(|| self.manifest.find_base_file(path))
+    }
+
+    /// Resolve a delta bundle against its base bundle.
+    ///
+    /// Each carried-over file is read (verified) from `base` and must match
+    /// the checksum recorded in the delta. Returns the number of files resolved.
+    pub fn resolve_base<B: Read + std::io::Seek>(
+        &mut self,
+        base: &mut BundleReader<B>,
+    ) -> Result<usize> {
+        let reference = self.manifest.base.clone().ok_or(BundleError::NotDelta)?;
+        if base.session_id() != reference.session_id {
+            return Err(BundleError::BaseMismatch {
+                expected: reference.session_id,
+                actual: base.session_id().to_string(),
+            });
+        }
+
+        for entry in &reference.unchanged {
+            let data = base.read_verified(&entry.path)?;
+            if !entry.verify(&data) {
+                return Err(BundleError::ChecksumMismatch {
+                    path: entry.path.clone(),
+                    expected: entry.sha256.clone(),
+                    actual: FileEntry::compute_checksum(&data),
+                });
+            }
+            self.resolved.insert(entry.path.clone(), data);
+        }
+
+        info!(
+            base_session = %reference.session_id,
+            files = reference.unchanged.len(),
+            "Delta bundle resolved against base"
+        );
+        Ok(reference.unchanged.len())
     }
 
     /// Read a file from the bundle without verification.
     ///
     /// Use `read_verified` for integrity-checked reads.
     pub fn read_raw(&mut self, path: &str) -> Result<Vec<u8>> {
+        if self.manifest.find_file(path).is_none() && self.manifest.find_base_file(path).is_some() {
+            return self
+                .resolved
+                .get(path)
+                .cloned()
+                .ok_or_else(|| BundleError::BaseRequired(path.to_string()));
+        }
+
         let mut file = self
             .archive
             .by_name(path)
@@ -178,8 +276,7 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
     pub fn read_verified(&mut self, path: &str) -> Result<Vec<u8>> {
         // Check if we have a manifest entry
         let entry = self
-            .manifest
-            .find_file(path)
+            .find_entry(path)
             .ok_or_else(|| BundleError::FileNotFound(path.to_string()))?
             .clone();
 
@@ -211,11 +308,19 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
 
     /// Verify all files in the bundle.
     ///
+    /// Delta files carried over from the base are included once resolved.
     /// Returns list of paths that failed verification.
     pub fn verify_all(&mut self) -> Vec<String> {
         let mut failures = Vec::new();
 
-        let paths: Vec<String> = self.manifest.files.iter().map(|f| f.path.clone()).collect();
+        let paths: Vec<String> = self
+            .all_files()
+            .into_iter()
+            .filter(|f| {
+                self.manifest.find_file(&f.path).is_some() || self.resolved.contains_key(&f.path)
+            })
+            .map(|f| f.path.clone())
+            .collect();
 
         for path in paths {
             if let Err(e) = self.read_verified(&path) {
@@ -266,9 +371,8 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
 
     /// List telemetry files in the bundle.
     pub fn telemetry_files(&self) -> Vec<&FileEntry> {
-        self.manifest
-            .files
-            .iter()
+        self.all_files()
+            .into_iter()
             .filter(|f| f.path.starts_with("telemetry/") && f.path.ends_with(".parquet"))
             .collect()
     }
@@ -281,9 +385,8 @@ impl<R: Read + std::io::Seek> BundleReader<R> {
 
     /// List log files in the bundle.
     pub fn log_files(&self) -> Vec<&FileEntry> {
-        self.manifest
-            .files
-            .iter()
+        self.all_files()
+            .into_iter()
             .filter(|f| f.path.starts_with("logs/") && f.path.ends_with(".jsonl"))
             .collect()
     }
@@ -492,6 +595,76 @@ mod tests {
         assert!(failures.is_empty(), "Verification failed: {:?}", failures);
     }
 
+    fn session_bundle(session: &str, plan: &str, snapshot: &str) -> BundleWriter {
+        let mut writer = BundleWriter::new(session, "host-abc", ExportProfile::Safe);
+        writer
+            .add_plan(&serde_json::json!({ "plan": plan }))
+            .unwrap();
+        writer.add_file("snapshot.json", snapshot.as_bytes().to_vec(), None);
+        writer
+    }
+
+    #[test]
+    fn test_delta_bundle_resolves_against_base() {
+        let base_writer = session_bundle("session-100", "old", "{\"procs\":1}");
+        let (base_bytes, base_manifest) = base_writer.write_to_vec().unwrap();
+
+        let delta_writer = session_bundle("session-200", "new", "{\"procs\":1}")
+            .with_base("session-100", base_manifest.files.clone());
+        let (delta_bytes, delta_manifest) = delta_writer.write_to_vec().unwrap();
+        assert_eq!(delta_manifest.file_count(), 1);
+        assert!(delta_manifest.find_file("plan.json").is_some());
+        assert!(delta_manifest.find_base_file("snapshot.json").is_some());
+
+        let mut delta = BundleReader::from_bytes(delta_bytes).unwrap();
+        assert!(delta.is_delta());
+        assert!(!delta.is_resolved());
+        assert!(delta.has_file("snapshot.json"));
+        assert!(matches!(
+            delta.read_verified("snapshot.json"),
+            Err(BundleError::BaseRequired(_))
+        ));
+
+        let mut base = BundleReader::from_bytes(base_bytes).unwrap();
+        assert_eq!(delta.resolve_base(&mut base).unwrap(), 1);
+        assert!(delta.is_resolved());
+        assert_eq!(
+            delta.read_verified("snapshot.json").unwrap(),
+            b"{\"procs\":1}"
+        );
+        let plan: Option<serde_json::Value> = delta.read_plan().unwrap();
+        assert_eq!(plan.unwrap()["plan"], "new");
+        assert!(delta.verify_all().is_empty());
+    }
+
+    #[test]
+    fn test_delta_bundle_rejects_wrong_base() {
+        let (base_bytes, base_manifest) = session_bundle("session-100", "old", "{}")
+            .write_to_vec()
+            .unwrap();
+        let (other_bytes, _) = session_bundle("session-150", "old", "{}")
+            .write_to_vec()
+            .unwrap();
+        let (delta_bytes, _) = session_bundle("session-200", "new", "{}")
+            .with_base("session-100", base_manifest.files)
+            .write_to_vec()
+            .unwrap();
+
+        let mut delta = BundleReader::from_bytes(delta_bytes).unwrap();
+        let mut other = BundleReader::from_bytes(other_bytes).unwrap();
+        assert!(matches!(
+            delta.resolve_base(&mut other),
+            Err(BundleError::BaseMismatch { .. })
+        ));
+
+        let mut full = BundleReader::from_bytes(base_bytes).unwrap();
+        let mut base = BundleReader::from_bytes(create_test_bundle()).unwrap();
+        assert!(matches!(
+            full.resolve_base(&mut base),
+            Err(BundleError::NotDelta)
+        ));
+    }
+
     #[test]
     fn test_bundle_reader_open_encrypted() {
         let bytes = create_test_bundle();
@@ -521,6 +694,32 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_bundle_reader_open_age_encrypted() {
+        let bytes = create_test_bundle();
+        let identity = age::x25519::Identity::generate();
+        let recipients =
+            crate::encryption::parse_recipients(&[identity.to_public().to_string()]).unwrap();
+        let encrypted = crate::encryption::encrypt_to_recipients(&bytes, &recipients).unwrap();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let bundle_path = temp_dir.path().join("age.ptb");
+        std::fs::write(&bundle_path, encrypted).unwrap();
+        let identity_path = temp_dir.path().join("key.txt");
+        {
+            use age::secrecy::ExposeSecret;
+            std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+        }
+
+        assert!(matches!(
+            BundleReader::open(&bundle_path),
+            Err(BundleError::EncryptedBundleRequiresIdentity)
+        ));
+        let reader =
+            BundleReader::open_with_keys(&bundle_path, None, Some(&identity_path)).unwrap();
+        assert_eq!(reader.session_id(), "session-123");
+    }
+
     #[test]
     fn test_bundle_reader_open_encrypted_wrong_passphrase() {
         let bytes = create_test_bundle();
@@ -3,8 +3,8 @@
 //! Creates ZIP archives with manifest and checksums.
 
 use crate::encryption;
-use crate::{BundleError, BundleManifest, FileEntry, Result};
-use pt_redact::ExportProfile;
+use crate::{BaseReference, BundleError, BundleManifest, FileEntry, Result};
+use pt_redact::{CaptureAllowlist, ExportProfile};
 use std::fs::File;
 use std::io::{Cursor, Write};
 use std::path::Path;
@@ -54,6 +54,7 @@ impl FileType {
 pub struct BundleWriter {
     manifest: BundleManifest,
     files: Vec<(String, Vec<u8>)>,
+    base_files: Vec<FileEntry>,
 }
 
 impl BundleWriter {
@@ -67,6 +68,7 @@ impl BundleWriter {
         Self {
             manifest,
             files: Vec::new(),
+            base_files: Vec::new(),
         }
     }
 
@@ -80,6 +82,17 @@ impl BundleWriter {
         self
     }
 
+    /// Declare the raw-capture allowlist recorded in the manifest.
+    ///
+    /// Fails unless the bundle uses the forensic profile and every pattern compiles.
+    pub fn with_capture_allowlist(mut self, allowlist: CaptureAllowlist) -> Result<Self> {
+        allowlist
+            .validate(self.manifest.export_profile)
+            .map_err(|e| BundleError::InvalidProfile(e.to_string()))?;
+        self.manifest = self.manifest.with_capture_allowlist(allowlist);
+        Ok(self)
+    }
+
     /// Set the pt version.
     pub fn with_pt_version(mut self, version: impl Into<String>) -> Self {
         self.manifest = self.manifest.with_pt_version(version);
@@ -92,6 +105,20 @@ impl BundleWriter {
         self
     }
 
+    /// Write a delta bundle against the artifacts of a base session.
+    ///
+    /// Files whose path and checksum match an entry in `base_files` are left
+    /// out of the archive and recorded under `manifest.base` instead.
+    pub fn with_base(
+        mut self,
+        base_session_id: impl Into<String>,
+        base_files: Vec<FileEntry>,
+    ) -> Self {
+        self.manifest.base = Some(BaseReference::new(base_session_id));
+        self.base_files = base_files;
+        self
+    }
+
     /// Add a file to the bundle with automatic checksum.
     pub fn add_file(
         &mut self,
@@ -177,11 +204,40 @@ impl BundleWriter {
         self.files.len()
     }
 
+    /// Move files unchanged since the base out of the archive.
+    fn apply_base(&mut self) {
+        let Some(ref mut base) = self.manifest.base else {
+            return;
+        };
+        let is_unchanged = |entry: &FileEntry| {
+            self.base_files
+                .iter()
+                .any(|b| b.path == entry.path && b.sha256 == entry.sha256)
+        };
+        let (unchanged, changed): (Vec<FileEntry>, Vec<FileEntry>) = self
+            .manifest
+            .files
+            .drain(..)
+            .partition(|entry| is_unchanged(entry));
+        self.files
+            .retain(|(path, _)| !unchanged.iter().any(|entry| &entry.path == path));
+        self.manifest.files = changed;
+        base.unchanged = unchanged;
+
+        debug!(
+            base_session = %base.session_id,
+            unchanged = base.unchanged.len(),
+            changed = self.files.len(),
+            "Applied delta base"
+        );
+    }
+
     /// Write the bundle to a file.
     pub fn write(mut self, path: &Path) -> Result<BundleManifest> {
         if self.files.is_empty() {
             return Err(BundleError::EmptyBundle);
         }
+        self.apply_base();
 
         // Sort files for deterministic ordering
         self.manifest.sort_files();
@@ -231,11 +287,31 @@ impl BundleWriter {
         Ok(manifest)
     }
 
+    /// Write the bundle to a file, encrypted to age X25519 recipients
+    /// (`age1...`). Any matching identity can decrypt it.
+    pub fn write_to_recipients<S: AsRef<str>>(
+        self,
+        path: &Path,
+        recipients: &[S],
+    ) -> Result<BundleManifest> {
+        let recipients = encryption::parse_recipients(recipients)?;
+        if recipients.is_empty() {
+            return Err(BundleError::InvalidRecipient(
+                "no recipients given".to_string(),
+            ));
+        }
+        let (bytes, manifest) = self.write_to_vec()?;
+        let encrypted = encryption::encrypt_to_recipients(&bytes, &recipients)?;
+        std::fs::write(path, encrypted)?;
+        Ok(manifest)
+    }
+
     /// Write the bundle to a byte vector (for in-memory use).
     pub fn write_to_vec(mut self) -> Result<(Vec<u8>, BundleManifest)> {
         if self.files.is_empty() {
             return Err(BundleError::EmptyBundle);
         }
+        self.apply_base();
 
         // Sort files for deterministic ordering
         self.manifest.sort_files();
@@ -427,6 +503,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bundle_writer_delta_skips_unchanged() {
+        let base_files = vec![
+            FileEntry::new("a.txt", FileEntry::compute_checksum(b"a"), 1),
+            FileEntry::new("b.txt", FileEntry::compute_checksum(b"old"), 3),
+        ];
+        let mut writer = BundleWriter::new("session-200", "host", ExportProfile::Safe)
+            .with_base("session-100", base_files);
+        writer.add_file("a.txt", b"a".to_vec(), None);
+        writer.add_file("b.txt", b"new".to_vec(), None);
+        writer.add_file("c.txt", b"c".to_vec(), None);
+
+        let (_, manifest) = writer.write_to_vec().unwrap();
+        let paths: Vec<_> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["b.txt", "c.txt"]);
+        let base = manifest.base.expect("delta base");
+        assert_eq!(base.session_id, "session-100");
+        assert_eq!(base.unchanged.len(), 1);
+        assert_eq!(base.unchanged[0].path, "a.txt");
+    }
+
     #[test]
     fn test_file_type_from_path() {
         assert!(matches!(FileType::from_path("test.json"), FileType::Json));
@@ -452,4 +549,20 @@ mod tests {
         assert_eq!(manifest.pt_version, Some("0.1.0".to_string()));
         assert_eq!(manifest.description, Some("Test bundle".to_string()));
     }
+
+    #[test]
+    fn test_bundle_writer_capture_allowlist() {
+        let allowlist = CaptureAllowlist::new()
+            .with_env_names(["RUST_LOG"])
+            .with_cmdline_patterns(["^--port=\\d+$"]);
+
+        let writer = BundleWriter::new("session-123", "host-abc", ExportProfile::Forensic)
+            .with_capture_allowlist(allowlist.clone())
+            .unwrap();
+        assert_eq!(writer.manifest().capture_allowlist, Some(allowlist.clone()));
+
+        let result = BundleWriter::new("session-123", "host-abc", ExportProfile::Safe)
+            .with_capture_allowlist(allowlist);
+        assert!(matches!(result, Err(BundleError::InvalidProfile(_))));
+    }
 }
@@ -45,6 +45,13 @@ impl StartId {
         StartId(format!("{}:{}:{}", boot_id, start_time, pid))
     }
 
+    /// Create a new StartId from components (Windows).
+    ///
+    /// `creation_time` is the process creation FILETIME (100ns intervals since 1601).
+    pub fn from_windows(boot_id: &str, creation_time: u64, pid: u32) -> Self {
+        StartId(format!("{}:{}:{}", boot_id, creation_time, pid))
+    }
+
     /// Parse and validate a StartId string.
     pub fn parse(s: &str) -> Option<Self> {
         let mut parts = s.split(':');
@@ -288,6 +295,20 @@ mod tests {
         assert_eq!(sid.0, "9d2d4e20-8c2b-4a3a-a8a2-90bcb7a1d86f:987654321:1234");
     }
 
+    #[test]
+    fn test_start_id_windows() {
+        let sid = StartId::from_windows(
+            "00000000-0000-0000-01da-3c1f2e5a7b00",
+            133_500_000_000_000_000,
+            5678,
+        );
+        assert_eq!(
+            sid.0,
+            "00000000-0000-0000-01da-3c1f2e5a7b00:133500000000000000:5678"
+        );
+        assert!(StartId::parse(&sid.0).is_some());
+    }
+
     #[test]
     fn test_identity_quality_automatable() {
         assert!(IdentityQuality::Full.is_automatable());
@@ -9,6 +9,7 @@
 //! - Capabilities detection and caching
 //! - Command and CWD category taxonomies
 //! - Galaxy-brain math transparency types
+//! - Duration and size parsing and humanization
 
 pub mod capabilities;
 pub mod categories;
@@ -18,6 +19,7 @@ pub mod galaxy_brain;
 pub mod id;
 pub mod output;
 pub mod schema;
+pub mod units;
 
 pub use capabilities::{
     Capabilities, CapabilitiesError, CgroupInfo, CgroupVersion, ContainerInfo, CpuArch,
@@ -42,3 +44,7 @@ pub use galaxy_brain::{
 pub use id::{IdentityQuality, ProcessId, ProcessIdentity, SessionId, StartId};
 pub use output::OutputFormat;
 pub use schema::SCHEMA_VERSION;
+pub use units::{
+    format_duration, format_size, parse_duration, parse_size, DurationUnit, NumberLocale, SizeBase,
+    SizeUnit, UnitError, UnitParser,
+};
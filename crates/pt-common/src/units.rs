@@ -0,0 +1,582 @@
+//! Duration and size parsing and humanization.
+//!
+//! Every command that takes a duration or a size goes through this module, so
+//! they all accept the same units:
+//!
+//! - durations: `ms`, `s`, `m`, `h`, `d`, `w` (and long forms such as `min`,
+//!   `hours`, `days`), with fractions (`1.5h`) and compounds (`1h30m`, `2d 4h`)
+//! - sizes: `B`, `K`/`KB`/`KiB` up to `T`/`TB`/`TiB`, with fractions (`1.5G`)
+//!
+//! A bare number is read in the unit the caller expects, so flags that used to
+//! take raw seconds or bytes keep accepting them.
+//!
+//! `KiB`..`TiB` are always binary. `KB`..`TB` are binary too by default, as in
+//! `ps` and `top`; [`SizeBase::Decimal`] reads them as powers of 1000.
+//!
+//! Numbers follow the numeric locale (`LC_ALL`, `LC_NUMERIC`, then `LANG`):
+//! `1,5G` is 1.5 GiB under `de_DE`, while `1,024MB` is 1024 MiB under `en_US`.
+//! `.` is accepted as a decimal point in any locale unless it forms a valid
+//! thousands group, and `_` or spaces may group digits anywhere.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors from parsing a duration or size.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UnitError {
+    #[error("empty value")]
+    Empty,
+
+    #[error("invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("unknown {kind} unit '{unit}'")]
+    UnknownUnit { kind: &'static str, unit: String },
+
+    #[error("value '{0}' is out of range")]
+    OutOfRange(String),
+}
+
+/// Unit of a duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationUnit {
+    Millis,
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+}
+
+impl DurationUnit {
+    /// Length of one unit, in milliseconds.
+    pub fn millis(self) -> f64 {
+        match self {
+            DurationUnit::Millis => 1.0,
+            DurationUnit::Seconds => 1_000.0,
+            DurationUnit::Minutes => 60_000.0,
+            DurationUnit::Hours => 3_600_000.0,
+            DurationUnit::Days => 86_400_000.0,
+            DurationUnit::Weeks => 604_800_000.0,
+        }
+    }
+
+    fn parse(unit: &str) -> Option<Self> {
+        Some(match unit.to_ascii_lowercase().as_str() {
+            "ms" | "msec" | "msecs" | "millis" | "millisecond" | "milliseconds" => Self::Millis,
+            "s" | "sec" | "secs" | "second" | "seconds" => Self::Seconds,
+            "m" | "min" | "mins" | "minute" | "minutes" => Self::Minutes,
+            "h" | "hr" | "hrs" | "hour" | "hours" => Self::Hours,
+            "d" | "day" | "days" => Self::Days,
+            "w" | "wk" | "wks" | "week" | "weeks" => Self::Weeks,
+            _ => return None,
+        })
+    }
+}
+
+/// Unit of a size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    Bytes,
+    Kilo,
+    Mega,
+    Giga,
+    Tera,
+}
+
+impl SizeUnit {
+    fn exponent(self) -> i32 {
+        match self {
+            SizeUnit::Bytes => 0,
+            SizeUnit::Kilo => 1,
+            SizeUnit::Mega => 2,
+            SizeUnit::Giga => 3,
+            SizeUnit::Tera => 4,
+        }
+    }
+
+    /// Bytes in one unit under `base`.
+    pub fn bytes(self, base: SizeBase) -> f64 {
+        base.radix().powi(self.exponent())
+    }
+}
+
+/// Whether `K`/`KB`-style units are powers of 1024 or 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeBase {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+impl SizeBase {
+    fn radix(self) -> f64 {
+        match self {
+            SizeBase::Binary => 1024.0,
+            SizeBase::Decimal => 1000.0,
+        }
+    }
+}
+
+/// Decimal and digit-group separators of a numeric locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberLocale {
+    pub decimal_separator: char,
+    pub group_separator: char,
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::C
+    }
+}
+
+impl NumberLocale {
+    /// The C/POSIX locale: `1,234.5`.
+    pub const C: Self = Self {
+        decimal_separator: '.',
+        group_separator: ',',
+    };
+
+    /// Separators for a locale name such as `de_DE.UTF-8`.
+    ///
+    /// Unknown languages fall back to [`NumberLocale::C`].
+    pub fn from_name(name: &str) -> Self {
+        let lang = name
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let (decimal_separator, group_separator) = match lang.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "da" | "tr" | "el" | "ro" | "hr" | "sr"
+            | "sl" => (',', '.'),
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "uk" | "hu"
+            | "bg" | "lt" | "lv" | "et" => (',', '\u{a0}'),
+            _ => return Self::C,
+        };
+        Self {
+            decimal_separator,
+            group_separator,
+        }
+    }
+
+    /// Locale from `LC_ALL`, `LC_NUMERIC` or `LANG`, first one set.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+            .map(|name| Self::from_name(&name))
+            .unwrap_or(Self::C)
+    }
+
+    /// Process-wide locale, read from the environment once.
+    pub fn current() -> Self {
+        static CURRENT: OnceLock<NumberLocale> = OnceLock::new();
+        *CURRENT.get_or_init(Self::from_env)
+    }
+
+    /// Parse a non-negative number written in this locale.
+    pub fn parse_number(&self, raw: &str) -> Result<f64, UnitError> {
+        let invalid = || UnitError::InvalidNumber(raw.to_string());
+        let mut s: String = raw
+            .chars()
+            .filter(|c| !matches!(c, '_' | ' ' | '\u{a0}' | '\u{202f}'))
+            .collect();
+        if s.is_empty() {
+            return Err(invalid());
+        }
+
+        let group = self.group_separator;
+        if s.contains(group) {
+            let integer = s.split(self.decimal_separator).next().unwrap_or("");
+            if is_grouped(integer, group) {
+                s = s.replace(group, "");
+            } else if group == '.' && s.matches('.').count() == 1 && !s.contains(',') {
+                // "1.5" in a decimal-comma locale: read the dot as a decimal point.
+            } else {
+                return Err(invalid());
+            }
+        }
+        if self.decimal_separator != '.' {
+            s = s.replace(self.decimal_separator, ".");
+        }
+        if !s.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Err(invalid());
+        }
+        let value: f64 = s.parse().map_err(|_| invalid())?;
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(invalid())
+        }
+    }
+
+    /// Format `value` with `precision` decimals and this locale's separators.
+    pub fn format_number(&self, value: f64, precision: usize) -> String {
+        let formatted = format!("{:.*}", precision, value);
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (formatted.as_str(), None),
+        };
+        let (sign, digits) = match integer.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", integer),
+        };
+        let mut out = String::from(sign);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(self.group_separator);
+            }
+            out.push(c);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+}
+
+/// Whether `integer` is digits grouped in threes by `group`, e.g. `12,345`.
+fn is_grouped(integer: &str, group: char) -> bool {
+    let mut parts = integer.split(group);
+    let head = parts.next().unwrap_or("");
+    (1..=3).contains(&head.len())
+        && head.chars().all(|c| c.is_ascii_digit())
+        && parts.all(|p| p.len() == 3 && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Split `s` into `(number, unit)` pairs, e.g. `1h30m` → `[("1", "h"), ("30", "m")]`.
+fn tokens(s: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| c.is_alphabetic()).unwrap_or(rest.len());
+        let after = &rest[number_end..];
+        let unit_end = after
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(after.len());
+        out.push((rest[..number_end].trim(), &after[..unit_end]));
+        rest = after[unit_end..].trim_start();
+    }
+    out
+}
+
+/// Parser for durations and sizes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnitParser {
+    pub locale: NumberLocale,
+    pub size_base: SizeBase,
+}
+
+impl UnitParser {
+    /// Parser using the process locale and binary sizes.
+    pub fn new() -> Self {
+        Self {
+            locale: NumberLocale::current(),
+            size_base: SizeBase::Binary,
+        }
+    }
+
+    pub fn with_locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn with_size_base(mut self, size_base: SizeBase) -> Self {
+        self.size_base = size_base;
+        self
+    }
+
+    /// Parse a duration; a bare number is read in `bare` units.
+    pub fn duration(&self, raw: &str, bare: DurationUnit) -> Result<Duration, UnitError> {
+        let tokens = tokens(raw);
+        if tokens.is_empty() {
+            return Err(UnitError::Empty);
+        }
+        let mut millis = 0.0;
+        for (number, unit) in &tokens {
+            let unit = match *unit {
+                "" if tokens.len() == 1 => bare,
+                "" => return Err(UnitError::InvalidNumber(raw.trim().to_string())),
+                unit => DurationUnit::parse(unit).ok_or_else(|| UnitError::UnknownUnit {
+                    kind: "duration",
+                    unit: unit.to_string(),
+                })?,
+            };
+            if number.is_empty() {
+                return Err(UnitError::InvalidNumber(raw.trim().to_string()));
+            }
+            millis += self.locale.parse_number(number)? * unit.millis();
+        }
+        Duration::try_from_secs_f64(millis / 1000.0)
+            .map_err(|_| UnitError::OutOfRange(raw.trim().to_string()))
+    }
+
+    /// Parse a size in bytes; a bare number is read in `bare` units.
+    pub fn size(&self, raw: &str, bare: SizeUnit) -> Result<u64, UnitError> {
+        let tokens = tokens(raw);
+        let [(number, unit)] = tokens.as_slice() else {
+            return Err(if tokens.is_empty() {
+                UnitError::Empty
+            } else {
+                UnitError::InvalidNumber(raw.trim().to_string())
+            });
+        };
+        let value = self.locale.parse_number(number)?;
+        let bytes = value * self.size_multiplier(unit, bare)?;
+        if bytes > u64::MAX as f64 {
+            return Err(UnitError::OutOfRange(raw.trim().to_string()));
+        }
+        Ok(bytes.round() as u64)
+    }
+
+    fn size_multiplier(&self, unit: &str, bare: SizeUnit) -> Result<f64, UnitError> {
+        if unit.is_empty() {
+            return Ok(bare.bytes(self.size_base));
+        }
+        let lower = unit.to_ascii_lowercase();
+        let (prefix, base) = if let Some(prefix) = lower.strip_suffix("ib") {
+            (prefix, SizeBase::Binary)
+        } else {
+            let prefix = lower.strip_suffix('b').unwrap_or(&lower);
+            (prefix, self.size_base)
+        };
+        let unit = match prefix {
+            "" | "byte" | "bytes" if !lower.ends_with("ib") => SizeUnit::Bytes,
+            "k" => SizeUnit::Kilo,
+            "m" => SizeUnit::Mega,
+            "g" => SizeUnit::Giga,
+            "t" => SizeUnit::Tera,
+            _ => {
+                return Err(UnitError::UnknownUnit {
+                    kind: "size",
+                    unit: unit.to_string(),
+                })
+            }
+        };
+        Ok(unit.bytes(base))
+    }
+}
+
+/// Parse a duration with the process locale; a bare number is read in `bare` units.
+pub fn parse_duration(raw: &str, bare: DurationUnit) -> Result<Duration, UnitError> {
+    UnitParser::new().duration(raw, bare)
+}
+
+/// Parse a size in bytes with the process locale; a bare number is read in `bare` units.
+pub fn parse_size(raw: &str, bare: SizeUnit) -> Result<u64, UnitError> {
+    UnitParser::new().size(raw, bare)
+}
+
+/// Humanize a duration with up to three units, e.g. `3d 4h 0m`, `2m 5s`, `250ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs == 0 {
+        return format!("{}ms", duration.subsec_millis());
+    }
+    let parts = [
+        (secs / 86_400, "d"),
+        ((secs % 86_400) / 3_600, "h"),
+        ((secs % 3_600) / 60, "m"),
+        (secs % 60, "s"),
+    ];
+    let first = parts.iter().position(|(n, _)| *n > 0).unwrap_or(3);
+    parts[first..]
+        .iter()
+        .take(3)
+        .map(|(n, unit)| format!("{}{}", n, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Humanize a byte count in binary units, e.g. `1.5 GiB`.
+pub fn format_size(bytes: u64) -> String {
+    format_size_with(bytes, SizeBase::Binary, &NumberLocale::C)
+}
+
+/// Humanize a byte count in the given base and locale.
+///
+/// Binary sizes use `KiB`..`TiB`, decimal sizes `kB`..`TB`.
+pub fn format_size_with(bytes: u64, base: SizeBase, locale: &NumberLocale) -> String {
+    const BINARY: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    const DECIMAL: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+    let labels = match base {
+        SizeBase::Binary => BINARY,
+        SizeBase::Decimal => DECIMAL,
+    };
+    let mut value = bytes as f64;
+    let mut index = 0;
+    while value >= base.radix() && index < labels.len() - 1 {
+        value /= base.radix();
+        index += 1;
+    }
+    let precision = if index == 0 { 0 } else { 1 };
+    format!(
+        "{} {}",
+        locale.format_number(value, precision),
+        labels[index]
+    )
+}
+
+/// `clap` value parsers for flags that take a duration or size.
+///
+/// Each returns the value in the unit the flag historically took, so the
+/// field type does not change.
+pub mod cli {
+    use super::*;
+
+    fn duration_in(raw: &str, unit: DurationUnit) -> Result<f64, String> {
+        parse_duration(raw, unit)
+            .map(|d| d.as_secs_f64() * 1000.0 / unit.millis())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Whole seconds; a bare number is seconds.
+    pub fn seconds(raw: &str) -> Result<u64, String> {
+        duration_in(raw, DurationUnit::Seconds).map(|s| s.round() as u64)
+    }
+
+    /// Whole milliseconds; a bare number is milliseconds.
+    pub fn millis(raw: &str) -> Result<u64, String> {
+        duration_in(raw, DurationUnit::Millis).map(|ms| ms.round() as u64)
+    }
+
+    /// Megabytes (MiB); a bare number is MiB.
+    pub fn megabytes(raw: &str) -> Result<f64, String> {
+        parse_size(raw, SizeUnit::Mega)
+            .map(|b| b as f64 / SizeUnit::Mega.bytes(SizeBase::Binary))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Gigabytes (GiB); a bare number is GiB.
+    pub fn gigabytes(raw: &str) -> Result<f64, String> {
+        parse_size(raw, SizeUnit::Giga)
+            .map(|b| b as f64 / SizeUnit::Giga.bytes(SizeBase::Binary))
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c() -> UnitParser {
+        UnitParser::default()
+    }
+
+    #[test]
+    fn parses_durations() {
+        let p = c();
+        let secs = |s: &str| p.duration(s, DurationUnit::Seconds).unwrap().as_secs_f64();
+        assert_eq!(secs("90"), 90.0);
+        assert_eq!(secs("250ms"), 0.25);
+        assert_eq!(secs("7d"), 7.0 * 86_400.0);
+        assert_eq!(secs("2w"), 14.0 * 86_400.0);
+        assert_eq!(secs("1.5h"), 5_400.0);
+        assert_eq!(secs("1h30m"), 5_400.0);
+        assert_eq!(secs("2 days 4 hours"), 52.0 * 3_600.0);
+        assert_eq!(secs("10 MIN"), 600.0);
+        assert_eq!(
+            p.duration("3", DurationUnit::Days).unwrap(),
+            Duration::from_secs(3 * 86_400)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_durations() {
+        let p = c();
+        assert_eq!(
+            p.duration("  ", DurationUnit::Seconds),
+            Err(UnitError::Empty)
+        );
+        assert!(matches!(
+            p.duration("5y", DurationUnit::Seconds),
+            Err(UnitError::UnknownUnit { .. })
+        ));
+        assert!(p.duration("h", DurationUnit::Seconds).is_err());
+        assert!(p.duration("-5m", DurationUnit::Seconds).is_err());
+        // A bare number inside a compound is ambiguous.
+        assert!(p.duration("1h 30", DurationUnit::Seconds).is_err());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        let p = c();
+        assert_eq!(p.size("512", SizeUnit::Bytes).unwrap(), 512);
+        assert_eq!(p.size("4k", SizeUnit::Bytes).unwrap(), 4096);
+        assert_eq!(p.size("1.5G", SizeUnit::Bytes).unwrap(), 3 << 29);
+        assert_eq!(p.size("2 GiB", SizeUnit::Bytes).unwrap(), 2 << 30);
+        assert_eq!(p.size("1TB", SizeUnit::Bytes).unwrap(), 1 << 40);
+        assert_eq!(p.size("100", SizeUnit::Mega).unwrap(), 100 << 20);
+        assert_eq!(p.size("10 bytes", SizeUnit::Mega).unwrap(), 10);
+        let decimal = c().with_size_base(SizeBase::Decimal);
+        assert_eq!(decimal.size("2GB", SizeUnit::Bytes).unwrap(), 2_000_000_000);
+        assert_eq!(decimal.size("2GiB", SizeUnit::Bytes).unwrap(), 2 << 30);
+        assert!(matches!(
+            p.size("3 PB", SizeUnit::Bytes),
+            Err(UnitError::UnknownUnit { .. })
+        ));
+        assert!(p.size("1G 2M", SizeUnit::Bytes).is_err());
+    }
+
+    #[test]
+    fn numbers_follow_locale() {
+        let en = c();
+        let de = c().with_locale(NumberLocale::from_name("de_DE.UTF-8"));
+        let fr = c().with_locale(NumberLocale::from_name("fr_FR"));
+        assert_eq!(en.size("1,024MB", SizeUnit::Bytes).unwrap(), 1 << 30);
+        assert!(en.size("1,5GB", SizeUnit::Bytes).is_err());
+        assert_eq!(de.size("1,5GB", SizeUnit::Bytes).unwrap(), 3 << 29);
+        assert_eq!(de.size("1.024MB", SizeUnit::Bytes).unwrap(), 1 << 30);
+        // A lone dot that is not a thousands group is still a decimal point.
+        assert_eq!(de.size("1.5GB", SizeUnit::Bytes).unwrap(), 3 << 29);
+        assert_eq!(fr.size("1 024 MB", SizeUnit::Bytes).unwrap(), 1 << 30);
+        assert_eq!(
+            fr.duration("1,5h", DurationUnit::Seconds).unwrap(),
+            Duration::from_secs(5_400)
+        );
+        assert_eq!(en.size("1_000_000", SizeUnit::Bytes).unwrap(), 1_000_000);
+        assert_eq!(NumberLocale::from_name("C"), NumberLocale::C);
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 3600 + 7)),
+            "3h 0m 7s"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs(11 * 86_400 + 2 * 3600 + 30 * 60 + 9)),
+            "11d 2h 30m"
+        );
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 << 29), "1.5 GiB");
+        let de = NumberLocale::from_name("de_DE");
+        assert_eq!(
+            format_size_with(1_500_000_000, SizeBase::Decimal, &de),
+            "1,5 GB"
+        );
+        assert_eq!(de.format_number(1234567.891, 2), "1.234.567,89");
+    }
+
+    #[test]
+    fn cli_parsers_keep_field_units() {
+        assert_eq!(cli::seconds("300").unwrap(), 300);
+        assert_eq!(cli::seconds("2h").unwrap(), 7_200);
+        assert_eq!(cli::millis("1.5s").unwrap(), 1_500);
+        assert_eq!(cli::megabytes("2G").unwrap(), 2048.0);
+        assert_eq!(cli::megabytes("512").unwrap(), 512.0);
+        assert_eq!(cli::gigabytes("512M").unwrap(), 0.5);
+        assert!(cli::seconds("soon").is_err());
+    }
+}
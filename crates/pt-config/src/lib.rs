@@ -12,6 +12,8 @@ pub mod policy_bundle;
 pub mod preset;
 pub mod priors;
 pub mod resolve;
+pub mod rules;
+pub mod schedule;
 pub mod snapshot;
 pub mod validate;
 
@@ -20,6 +22,7 @@ pub use policy_bundle::{PolicyBundle, PolicyBundleError, PolicyMode};
 pub use preset::{get_preset, list_presets, PresetError, PresetInfo, PresetName};
 pub use priors::Priors;
 pub use resolve::{resolve_config, ConfigPaths};
+pub use schedule::ActionSchedule;
 pub use snapshot::ConfigSnapshot;
 pub use validate::{ValidationError, ValidationResult};
 
@@ -4,6 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::rules::CustomRule;
+use crate::schedule::ActionSchedule;
+
 /// Complete policy configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Policy {
@@ -35,6 +38,22 @@ pub struct Policy {
     pub load_aware: LoadAwareDecision,
     #[serde(default)]
     pub decision_time_bound: DecisionTimeBound,
+    #[serde(default)]
+    pub apply_health_gate: ApplyHealthGate,
+    #[serde(default)]
+    pub throttle_limits: ThrottleLimits,
+    #[serde(default)]
+    pub cascade: CascadePolicy,
+    #[serde(default)]
+    pub container_actions: ContainerActions,
+    #[serde(default)]
+    pub thread_hog: ThreadHogPolicy,
+    /// Maintenance windows and business hours for destructive actions.
+    #[serde(default)]
+    pub schedule: ActionSchedule,
+    /// Organization-specific rules that block or force review of actions.
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRule>,
 
     #[serde(default)]
     pub notes: Option<String>,
@@ -184,6 +203,18 @@ pub struct Guardrails {
 
     #[serde(default)]
     pub require_confirmation: Option<bool>,
+
+    /// Hold kills of processes whose tmux/screen session or SSH login has a
+    /// client attached that sent input within `attached_session_idle_seconds`.
+    #[serde(default = "default_true")]
+    pub protect_attached_sessions: bool,
+
+    #[serde(default = "default_attached_session_idle_seconds")]
+    pub attached_session_idle_seconds: u64,
+}
+
+fn default_attached_session_idle_seconds() -> u64 {
+    900
 }
 
 impl Default for Guardrails {
@@ -215,6 +246,8 @@ impl Default for Guardrails {
             max_kills_per_day: Some(100),
             min_process_age_seconds: 300,
             require_confirmation: Some(true),
+            protect_attached_sessions: true,
+            attached_session_idle_seconds: 900,
         }
     }
 }
@@ -384,6 +417,14 @@ pub struct DataLossGates {
 
     #[serde(default)]
     pub block_if_recent_io_seconds: Option<u64>,
+
+    /// Block when clients are connected to the process's listening ports.
+    #[serde(default)]
+    pub block_if_serving_clients: Option<bool>,
+
+    /// Block when the cumulative blast radius risk score exceeds this.
+    #[serde(default)]
+    pub max_blast_radius_score: Option<f64>,
 }
 
 /// Load-aware decision configuration for adaptive thresholds.
@@ -404,6 +445,252 @@ pub struct LoadAwareDecision {
     pub multipliers: LoadMultipliers,
 }
 
+/// Host health gate checked before applying actions.
+///
+/// Under extreme load, sending signals and verifying outcomes can itself make
+/// things worse or time out misleadingly, so apply is deferred until the host
+/// is within these limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyHealthGate {
+    pub enabled: bool,
+    /// Defer when the 1-minute load average per core exceeds this.
+    #[serde(default = "default_gate_max_load_per_core")]
+    pub max_load_per_core: f64,
+    /// Defer when any PSI "some" avg10 (cpu, memory, io) exceeds this percentage.
+    #[serde(default = "default_gate_max_psi_avg10")]
+    pub max_psi_avg10: f64,
+    /// Defer when available memory drops below this many MiB.
+    #[serde(default = "default_gate_min_available_memory_mb")]
+    pub min_available_memory_mb: u64,
+    /// Maximum time to wait for the host to recover when waiting is requested.
+    #[serde(default = "default_gate_wait_timeout_seconds")]
+    pub wait_timeout_seconds: u64,
+    /// Interval between health samples while waiting.
+    #[serde(default = "default_gate_poll_interval_seconds")]
+    pub poll_interval_seconds: u64,
+}
+
+fn default_gate_max_load_per_core() -> f64 {
+    4.0
+}
+
+fn default_gate_max_psi_avg10() -> f64 {
+    60.0
+}
+
+fn default_gate_min_available_memory_mb() -> u64 {
+    64
+}
+
+fn default_gate_wait_timeout_seconds() -> u64 {
+    120
+}
+
+fn default_gate_poll_interval_seconds() -> u64 {
+    5
+}
+
+impl Default for ApplyHealthGate {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_load_per_core: default_gate_max_load_per_core(),
+            max_psi_avg10: default_gate_max_psi_avg10(),
+            min_available_memory_mb: default_gate_min_available_memory_mb(),
+            wait_timeout_seconds: default_gate_wait_timeout_seconds(),
+            poll_interval_seconds: default_gate_poll_interval_seconds(),
+        }
+    }
+}
+
+/// Default limits applied by the throttle action.
+///
+/// On cgroup v2 the runaway process is moved into its own child cgroup
+/// (`pt-throttle-<pid>`) next to its current one, so siblings sharing that
+/// cgroup are not throttled along with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThrottleLimits {
+    /// CPU cap as a fraction of one core (0.25 = 25%, 2.0 = two cores).
+    #[serde(default = "default_throttle_cpu_fraction")]
+    pub cpu_fraction: f64,
+    /// Scheduler period for the CPU quota, in microseconds.
+    #[serde(default = "default_throttle_period_us")]
+    pub period_us: u64,
+    /// Soft memory limit (`memory.high` / `MemoryHigh=`) in MiB; unset leaves memory alone.
+    #[serde(default)]
+    pub memory_high_mb: Option<u64>,
+    /// Move the process into a dedicated child cgroup before applying limits.
+    #[serde(default = "default_true")]
+    pub isolate: bool,
+    /// Fall back to `systemctl set-property --runtime` on the owning unit when
+    /// cgroup files are not writable.
+    #[serde(default = "default_true")]
+    pub systemd_fallback: bool,
+}
+
+fn default_throttle_cpu_fraction() -> f64 {
+    0.25
+}
+
+fn default_throttle_period_us() -> u64 {
+    100_000
+}
+
+impl Default for ThrottleLimits {
+    fn default() -> Self {
+        Self {
+            cpu_fraction: default_throttle_cpu_fraction(),
+            period_us: default_throttle_period_us(),
+            memory_high_mb: None,
+            isolate: true,
+            systemd_fallback: true,
+        }
+    }
+}
+
+/// How kills are planned across a process tree.
+///
+/// Killing a parent orphans its children, and killing a worker of a live
+/// supervisor usually just gets it respawned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CascadePolicy {
+    #[serde(default)]
+    pub mode: CascadeMode,
+    /// Order kills children first, so a dying parent cannot respawn them.
+    #[serde(default = "default_true")]
+    pub bottom_up: bool,
+    /// Most processes one tree-level action (group or subtree) may cover.
+    #[serde(default = "default_cascade_max_tree_size")]
+    pub max_tree_size: u32,
+}
+
+/// Scope of a kill in the process tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CascadeMode {
+    /// Kill only the selected processes.
+    #[default]
+    LeafOnly,
+    /// Signal the whole process group when every member is selected.
+    ProcessGroup,
+    /// Kill the selected processes together with their descendants.
+    Tree,
+}
+
+impl CascadeMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LeafOnly => "leaf_only",
+            Self::ProcessGroup => "process_group",
+            Self::Tree => "tree",
+        }
+    }
+}
+
+fn default_cascade_max_tree_size() -> u32 {
+    32
+}
+
+impl Default for CascadePolicy {
+    fn default() -> Self {
+        Self {
+            mode: CascadeMode::default(),
+            bottom_up: true,
+            max_tree_size: default_cascade_max_tree_size(),
+        }
+    }
+}
+
+/// How actions on a container's init process are carried out.
+///
+/// Signalling PID 1 of a container namespace races the runtime's restart
+/// policy and leaves the runtime's view of the container stale, so kills and
+/// pauses of an init are sent through the runtime instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerActions {
+    /// Runtimes pt may stop or pause containers through. Container inits
+    /// under any other runtime are refused rather than signalled.
+    #[serde(default = "default_container_runtimes")]
+    pub allowed_runtimes: Vec<ContainerRuntimeKind>,
+    /// Grace period the runtime gives the container before killing it.
+    #[serde(default = "default_container_stop_timeout_secs")]
+    pub stop_timeout_secs: u32,
+}
+
+/// Container runtime that pt can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntimeKind {
+    Docker,
+    Podman,
+    Containerd,
+}
+
+impl ContainerRuntimeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+            Self::Containerd => "containerd",
+        }
+    }
+}
+
+fn default_container_runtimes() -> Vec<ContainerRuntimeKind> {
+    vec![ContainerRuntimeKind::Docker, ContainerRuntimeKind::Podman]
+}
+
+fn default_container_stop_timeout_secs() -> u32 {
+    10
+}
+
+impl Default for ContainerActions {
+    fn default() -> Self {
+        Self {
+            allowed_runtimes: default_container_runtimes(),
+            stop_timeout_secs: default_container_stop_timeout_secs(),
+        }
+    }
+}
+
+/// Handling of CPU hogs whose load comes from a single thread.
+///
+/// One runaway thread (a spinning render loop, a stuck worker) in an otherwise
+/// healthy interactive app is better reported than killed: the user loses the
+/// whole session with the kill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadHogPolicy {
+    /// Downgrade kills of interactive processes (with a controlling TTY) to
+    /// review when one thread does nearly all the work.
+    #[serde(default = "default_true")]
+    pub review_interactive: bool,
+    /// Share of the process's CPU the busiest thread must account for.
+    #[serde(default = "default_thread_hog_min_share")]
+    pub min_dominant_share: f64,
+    /// Process CPU use, in percent of one core, below which no thread counts
+    /// as a hog.
+    #[serde(default = "default_thread_hog_min_cpu_percent")]
+    pub min_cpu_percent: f64,
+}
+
+fn default_thread_hog_min_share() -> f64 {
+    0.8
+}
+
+fn default_thread_hog_min_cpu_percent() -> f64 {
+    50.0
+}
+
+impl Default for ThreadHogPolicy {
+    fn default() -> Self {
+        Self {
+            review_interactive: true,
+            min_dominant_share: default_thread_hog_min_share(),
+            min_cpu_percent: default_thread_hog_min_cpu_percent(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoadWeights {
     pub queue: f64,
@@ -519,6 +806,8 @@ impl Default for DataLossGates {
             block_if_deleted_cwd: None,
             block_if_active_tty: true,
             block_if_recent_io_seconds: None,
+            block_if_serving_clients: None,
+            max_blast_radius_score: None,
         }
     }
 }
@@ -540,6 +829,13 @@ impl Default for Policy {
             data_loss_gates: DataLossGates::default(),
             load_aware: LoadAwareDecision::default(),
             decision_time_bound: DecisionTimeBound::default(),
+            apply_health_gate: ApplyHealthGate::default(),
+            throttle_limits: ThrottleLimits::default(),
+            cascade: CascadePolicy::default(),
+            container_actions: ContainerActions::default(),
+            thread_hog: ThreadHogPolicy::default(),
+            schedule: ActionSchedule::default(),
+            custom_rules: Vec::new(),
             notes: None,
         }
     }
@@ -1098,6 +1394,8 @@ mod tests {
             block_if_deleted_cwd: Some(true),
             block_if_active_tty: false,
             block_if_recent_io_seconds: Some(30),
+            block_if_serving_clients: Some(true),
+            max_blast_radius_score: Some(4.5),
         };
         let json = serde_json::to_string(&dlg).unwrap();
         let back: DataLossGates = serde_json::from_str(&json).unwrap();
@@ -1105,6 +1403,8 @@ mod tests {
         assert_eq!(back.max_open_write_fds, Some(10));
         assert_eq!(back.block_if_deleted_cwd, Some(true));
         assert_eq!(back.block_if_recent_io_seconds, Some(30));
+        assert_eq!(back.block_if_serving_clients, Some(true));
+        assert_eq!(back.max_blast_radius_score, Some(4.5));
     }
 
     #[test]
@@ -7,10 +7,12 @@
 //! - Paranoid: Maximum safety, extra confirmation, detailed logging
 
 use crate::policy::{
-    AlphaInvesting, ConfidenceLevel, DataLossGates, DecisionTimeBound, FdrControl, FdrMethod,
-    Guardrails, LoadAwareDecision, LossMatrix, LossRow, PatternEntry, PatternKind, Policy,
-    RobotMode, SignatureFastPath,
+    AlphaInvesting, ApplyHealthGate, CascadePolicy, ConfidenceLevel, ContainerActions,
+    DataLossGates, DecisionTimeBound, FdrControl, FdrMethod, Guardrails, LoadAwareDecision,
+    LossMatrix, LossRow, PatternEntry, PatternKind, Policy, RobotMode, SignatureFastPath,
+    ThreadHogPolicy, ThrottleLimits,
 };
+use crate::schedule::ActionSchedule;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -215,6 +217,8 @@ fn developer_preset() -> Policy {
             max_kills_per_day: Some(200),
             min_process_age_seconds: 1800, // 30 minutes (shorter than default)
             require_confirmation: Some(true), // Still interactive by default
+            protect_attached_sessions: true,
+            attached_session_idle_seconds: 900,
         },
 
         robot_mode: RobotMode {
@@ -251,10 +255,19 @@ fn developer_preset() -> Policy {
             block_if_deleted_cwd: None,
             block_if_active_tty: false, // Don't block on TTY - devs often have multiple terminals
             block_if_recent_io_seconds: Some(30), // Only block if very recent I/O
+            block_if_serving_clients: None,
+            max_blast_radius_score: None,
         },
 
         load_aware: LoadAwareDecision::default(),
         decision_time_bound: DecisionTimeBound::default(),
+        apply_health_gate: ApplyHealthGate::default(),
+        throttle_limits: ThrottleLimits::default(),
+        cascade: CascadePolicy::default(),
+        container_actions: ContainerActions::default(),
+        thread_hog: ThreadHogPolicy::default(),
+        schedule: ActionSchedule::default(),
+        custom_rules: Vec::new(),
     }
 }
 
@@ -411,6 +424,8 @@ fn server_preset() -> Policy {
             max_kills_per_day: Some(30),
             min_process_age_seconds: 14400, // 4 hours
             require_confirmation: Some(true),
+            protect_attached_sessions: true,
+            attached_session_idle_seconds: 1800, // Operators step away from consoles
         },
 
         robot_mode: RobotMode {
@@ -451,6 +466,8 @@ fn server_preset() -> Policy {
             block_if_deleted_cwd: Some(true),
             block_if_active_tty: true,
             block_if_recent_io_seconds: Some(300), // Block if any I/O in last 5 minutes
+            block_if_serving_clients: Some(true),  // Don't drop clients of a live service
+            max_blast_radius_score: None,
         },
 
         load_aware: LoadAwareDecision {
@@ -472,6 +489,13 @@ fn server_preset() -> Policy {
             overhead_budget_seconds: 600,
             fallback_action: "keep".to_string(), // Default to keeping on timeout
         },
+        apply_health_gate: ApplyHealthGate::default(),
+        throttle_limits: ThrottleLimits::default(),
+        cascade: CascadePolicy::default(),
+        container_actions: ContainerActions::default(),
+        thread_hog: ThreadHogPolicy::default(),
+        schedule: ActionSchedule::default(),
+        custom_rules: Vec::new(),
     }
 }
 
@@ -576,6 +600,8 @@ fn ci_preset() -> Policy {
             max_kills_per_day: Some(100),
             min_process_age_seconds: 3600, // 1 hour (long enough for most CI jobs)
             require_confirmation: Some(false), // NO interactive prompts
+            protect_attached_sessions: true,
+            attached_session_idle_seconds: 900,
         },
 
         robot_mode: RobotMode {
@@ -608,6 +634,8 @@ fn ci_preset() -> Policy {
             block_if_deleted_cwd: None,
             block_if_active_tty: false, // No TTY in CI
             block_if_recent_io_seconds: Some(60),
+            block_if_serving_clients: None,
+            max_blast_radius_score: None,
         },
 
         load_aware: LoadAwareDecision::default(),
@@ -620,6 +648,13 @@ fn ci_preset() -> Policy {
             overhead_budget_seconds: 120,
             fallback_action: "keep".to_string(),
         },
+        apply_health_gate: ApplyHealthGate::default(),
+        throttle_limits: ThrottleLimits::default(),
+        cascade: CascadePolicy::default(),
+        container_actions: ContainerActions::default(),
+        thread_hog: ThreadHogPolicy::default(),
+        schedule: ActionSchedule::default(),
+        custom_rules: Vec::new(),
     }
 }
 
@@ -831,6 +866,8 @@ fn paranoid_preset() -> Policy {
             max_kills_per_day: Some(10),
             min_process_age_seconds: 86400, // 24 hours
             require_confirmation: Some(true),
+            protect_attached_sessions: true,
+            attached_session_idle_seconds: 3600, // Any attached client in the last hour
         },
 
         robot_mode: RobotMode {
@@ -872,6 +909,8 @@ fn paranoid_preset() -> Policy {
             block_if_deleted_cwd: Some(true),
             block_if_active_tty: true,
             block_if_recent_io_seconds: Some(3600), // Block if any I/O in last hour
+            block_if_serving_clients: Some(true),
+            max_blast_radius_score: Some(2.0), // Only low-risk kills
         },
 
         load_aware: LoadAwareDecision {
@@ -897,6 +936,34 @@ fn paranoid_preset() -> Policy {
             overhead_budget_seconds: 1200,
             fallback_action: "keep".to_string(), // Always default to keeping
         },
+
+        apply_health_gate: ApplyHealthGate {
+            enabled: true,
+            max_load_per_core: 2.0, // Back off well before the host is saturated
+            max_psi_avg10: 30.0,
+            min_available_memory_mb: 256,
+            wait_timeout_seconds: 300,
+            poll_interval_seconds: 10,
+        },
+
+        throttle_limits: ThrottleLimits {
+            cpu_fraction: 0.5, // Gentler cap; throttling is the preferred reversible action
+            systemd_fallback: false, // Never modify unit properties
+            ..ThrottleLimits::default()
+        },
+
+        cascade: CascadePolicy {
+            max_tree_size: 8, // Keep tree-level actions small
+            ..CascadePolicy::default()
+        },
+
+        container_actions: ContainerActions {
+            allowed_runtimes: vec![], // Never stop containers; leave them for review
+            ..ContainerActions::default()
+        },
+        thread_hog: ThreadHogPolicy::default(),
+        schedule: ActionSchedule::default(),
+        custom_rules: Vec::new(),
     }
 }
 
@@ -1303,6 +1370,22 @@ mod tests {
 
     // ── Paranoid preset specifics ─────────────────────────────────────
 
+    #[test]
+    fn paranoid_health_gate_stricter_than_default() {
+        let p = get_preset(PresetName::Paranoid);
+        let default = ApplyHealthGate::default();
+        assert!(p.apply_health_gate.enabled);
+        assert!(p.apply_health_gate.max_load_per_core < default.max_load_per_core);
+        assert!(p.apply_health_gate.min_available_memory_mb > default.min_available_memory_mb);
+    }
+
+    #[test]
+    fn paranoid_throttle_skips_systemd_fallback() {
+        let p = get_preset(PresetName::Paranoid);
+        assert!(p.throttle_limits.isolate);
+        assert!(!p.throttle_limits.systemd_fallback);
+    }
+
     #[test]
     fn paranoid_loss_matrix_extreme() {
         let p = get_preset(PresetName::Paranoid);
@@ -3,6 +3,7 @@
 //! These types match the priors.schema.json specification.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Complete priors configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +42,24 @@ pub struct Priors {
     #[serde(default)]
     pub state_flags: Option<StateFlags>,
 
+    #[serde(default)]
+    pub net_recency: Option<NetRecency>,
+
+    #[serde(default)]
+    pub behavior_change: Option<BehaviorChange>,
+
+    #[serde(default)]
+    pub swap_pressure: Option<SwapPressure>,
+
+    #[serde(default)]
+    pub io_rate: Option<IoRate>,
+
+    #[serde(default)]
+    pub fd_leak: Option<FdLeak>,
+
+    #[serde(default)]
+    pub session_attachment: Option<SessionAttachment>,
+
     #[serde(default)]
     pub hierarchical: Option<HierarchicalParams>,
 
@@ -52,6 +71,9 @@ pub struct Priors {
 
     #[serde(default)]
     pub bocpd: Option<BocpdParams>,
+
+    #[serde(default)]
+    pub evidence_weights: Option<EvidenceWeights>,
 }
 
 /// Per-class Bayesian hyperparameters.
@@ -72,6 +94,9 @@ pub struct ClassParams {
     #[serde(default)]
     pub runtime_gamma: Option<GammaParams>,
 
+    #[serde(default)]
+    pub runtime_surprise_gamma: Option<GammaParams>,
+
     pub orphan_beta: BetaParams,
     pub tty_beta: BetaParams,
     pub net_beta: BetaParams,
@@ -79,6 +104,12 @@ pub struct ClassParams {
     #[serde(default)]
     pub io_active_beta: Option<BetaParams>,
 
+    #[serde(default)]
+    pub gpu_beta: Option<BetaParams>,
+
+    #[serde(default)]
+    pub gpu_util_beta: Option<BetaParams>,
+
     #[serde(default)]
     pub hazard_gamma: Option<GammaParams>,
 
@@ -312,6 +343,403 @@ pub struct StateFlags {
     pub comment: Option<String>,
 }
 
+/// Socket-activity recency Dirichlet priors.
+///
+/// The time since a process last sent or received data on a TCP socket is
+/// split at `thresholds_secs` into `thresholds_secs.len() + 1` buckets
+/// (by default active / recent / stale / idle). Each class alpha vector has
+/// one entry per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetRecency {
+    #[serde(default = "default_net_recency_thresholds")]
+    pub thresholds_secs: Vec<u64>,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_net_recency_thresholds() -> Vec<u64> {
+    vec![60, 3600, 86400]
+}
+
+impl NetRecency {
+    /// Bucket index for a socket idle time in seconds.
+    pub fn bucket(&self, idle_secs: f64) -> usize {
+        self.thresholds_secs
+            .iter()
+            .take_while(|&&threshold| idle_secs > threshold as f64)
+            .count()
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// Behavior-change Dirichlet priors.
+///
+/// How far a process deviates from its own fingerprint's history, as the
+/// largest per-metric z-score (with per-metric variance floors), is split at
+/// `thresholds_sigma` into `thresholds_sigma.len() + 1` buckets (by default
+/// typical / unusual / anomalous). Each class alpha vector has one entry per
+/// bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorChange {
+    #[serde(default = "default_behavior_change_thresholds")]
+    pub thresholds_sigma: Vec<f64>,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_behavior_change_thresholds() -> Vec<f64> {
+    vec![3.0, 6.0]
+}
+
+impl BehaviorChange {
+    /// Bucket index for a deviation in standard deviations.
+    pub fn bucket(&self, deviation_sigma: f64) -> usize {
+        self.thresholds_sigma
+            .iter()
+            .take_while(|&&threshold| deviation_sigma > threshold)
+            .count()
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// Swap-pressure Dirichlet priors.
+///
+/// Only applies while the host is under memory pressure: PSI `memory some`
+/// avg10 of at least `min_psi_some_avg10` percent or, on hosts without PSI,
+/// swap (including zram) at least `min_swap_used_fraction` full. A process's
+/// swapped-out memory (`VmSwap`) is then split at `thresholds_mb` into
+/// `thresholds_mb.len() + 1` buckets (by default none / moderate / heavy).
+/// Each class alpha vector has one entry per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapPressure {
+    #[serde(default = "default_swap_pressure_thresholds")]
+    pub thresholds_mb: Vec<f64>,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default = "default_swap_pressure_min_psi")]
+    pub min_psi_some_avg10: f64,
+
+    #[serde(default = "default_swap_pressure_min_swap_used")]
+    pub min_swap_used_fraction: f64,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_swap_pressure_thresholds() -> Vec<f64> {
+    vec![64.0, 512.0]
+}
+
+fn default_swap_pressure_min_psi() -> f64 {
+    10.0
+}
+
+fn default_swap_pressure_min_swap_used() -> f64 {
+    0.5
+}
+
+impl SwapPressure {
+    /// Whether the host signals call for the swap term at all.
+    pub fn applies(&self, psi_some_avg10: Option<f64>, swap_used_fraction: Option<f64>) -> bool {
+        match psi_some_avg10 {
+            Some(psi) => psi >= self.min_psi_some_avg10,
+            None => swap_used_fraction.is_some_and(|used| used >= self.min_swap_used_fraction),
+        }
+    }
+
+    /// Bucket index for a process's swapped-out memory in MB.
+    pub fn bucket(&self, swap_mb: f64) -> usize {
+        self.thresholds_mb
+            .iter()
+            .take_while(|&&threshold| swap_mb > threshold)
+            .count()
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// I/O-rate Dirichlet priors.
+///
+/// A process's storage I/O rate (read plus write bytes per second between
+/// two samples of its I/O counters during the scan) is split at
+/// `thresholds_kib_per_sec` into `thresholds_kib_per_sec.len() + 1` buckets
+/// (by default idle / light / heavy). Each class alpha vector has one entry
+/// per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IoRate {
+    #[serde(default = "default_io_rate_thresholds")]
+    pub thresholds_kib_per_sec: Vec<f64>,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_io_rate_thresholds() -> Vec<f64> {
+    vec![4.0, 1024.0]
+}
+
+impl IoRate {
+    /// Bucket index for a rate in bytes per second.
+    pub fn bucket(&self, bytes_per_sec: f64) -> usize {
+        let kib_per_sec = bytes_per_sec / 1024.0;
+        self.thresholds_kib_per_sec
+            .iter()
+            .take_while(|&&threshold| kib_per_sec > threshold)
+            .count()
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// File-descriptor leak Dirichlet priors.
+///
+/// The leak confidence of a process's fd-count trend across shadow
+/// observations (in `[0, 1]`) is split at `confidence_thresholds` into
+/// `confidence_thresholds.len() + 1` buckets (by default none / possible /
+/// likely). Each class alpha vector has one entry per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FdLeak {
+    #[serde(default = "default_fd_leak_thresholds")]
+    pub confidence_thresholds: Vec<f64>,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_fd_leak_thresholds() -> Vec<f64> {
+    vec![0.3, 0.6]
+}
+
+impl FdLeak {
+    /// Bucket index for a leak confidence.
+    pub fn bucket(&self, confidence: f64) -> usize {
+        self.confidence_thresholds
+            .iter()
+            .take_while(|&&threshold| confidence >= threshold)
+            .count()
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// Terminal-session attachment Dirichlet priors.
+///
+/// Processes attributed to a tmux/screen session or an SSH login fall in one
+/// of three buckets: detached (nobody connected), attached but idle (no client
+/// input within `active_idle_secs`, or idle time unknown), and attached with
+/// recent input. Each class alpha vector has one entry per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionAttachment {
+    #[serde(default = "default_session_active_idle_secs")]
+    pub active_idle_secs: u64,
+
+    #[serde(default)]
+    pub bucket_names: Vec<String>,
+
+    #[serde(default)]
+    pub useful: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub useful_bad: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub abandoned: Option<DirichletParams>,
+
+    #[serde(default)]
+    pub zombie: Option<DirichletParams>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+fn default_session_active_idle_secs() -> u64 {
+    900
+}
+
+impl SessionAttachment {
+    /// Number of buckets (detached, attached_idle, attached_active).
+    pub const BUCKETS: usize = 3;
+
+    /// Bucket index for a session's attachment and client idle time.
+    pub fn bucket(&self, attached: bool, client_idle_secs: Option<u64>) -> usize {
+        if !attached {
+            0
+        } else if client_idle_secs.is_some_and(|idle| idle <= self.active_idle_secs) {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Name of a bucket, falling back to its index.
+    pub fn bucket_name(&self, index: usize) -> String {
+        self.bucket_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| index.to_string())
+    }
+}
+
+/// Smallest allowed evidence weight (0 switches a term off).
+pub const EVIDENCE_WEIGHT_MIN: f64 = 0.0;
+
+/// Largest allowed evidence weight.
+pub const EVIDENCE_WEIGHT_MAX: f64 = 2.0;
+
+/// Evidence terms that accept a site weight (everything except the class prior).
+pub const WEIGHTABLE_EVIDENCE: &[&str] = &[
+    "cpu",
+    "runtime",
+    "runtime_surprise",
+    "orphan",
+    "tty",
+    "net",
+    "io_active",
+    "state_flag",
+    "command_category",
+    "net_recent_activity",
+    "behavior_change",
+    "swap_pressure",
+    "io_rate",
+    "fd_leak",
+    "session_attachment",
+    "gpu",
+];
+
+/// Site-specific evidence weighting.
+///
+/// Each weight `w` scales an evidence term's log-likelihoods, so its
+/// likelihood ratio between any two classes becomes `LR^w`. Weights below 1
+/// discount evidence a site knows to be unreliable (e.g. `tty` inside
+/// containers); 0 ignores it. Weights are bounded to
+/// [`EVIDENCE_WEIGHT_MIN`, `EVIDENCE_WEIGHT_MAX`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvidenceWeights {
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+
+    #[serde(rename = "_comment", default)]
+    pub comment: Option<String>,
+}
+
+impl EvidenceWeights {
+    /// Weight for an evidence term, clamped to the allowed range (1 if unset).
+    pub fn weight(&self, feature: &str) -> f64 {
+        match self.weights.get(feature) {
+            Some(w) if w.is_finite() => w.clamp(EVIDENCE_WEIGHT_MIN, EVIDENCE_WEIGHT_MAX),
+            _ => 1.0,
+        }
+    }
+}
+
 /// Hierarchical/empirical Bayes settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HierarchicalParams {
@@ -1030,4 +1458,111 @@ mod tests {
         assert!(back.zombie.is_some());
         assert_eq!(back.comment.as_deref(), Some("test"));
     }
+
+    #[test]
+    fn behavior_change_buckets() {
+        let bc: BehaviorChange = serde_json::from_str(r#"{"bucket_names": ["typical"]}"#).unwrap();
+        assert_eq!(bc.thresholds_sigma, vec![3.0, 6.0]);
+        assert_eq!(bc.bucket(0.5), 0);
+        assert_eq!(bc.bucket(3.0), 0);
+        assert_eq!(bc.bucket(4.2), 1);
+        assert_eq!(bc.bucket(12.0), 2);
+        assert_eq!(bc.bucket_name(0), "typical");
+        assert_eq!(bc.bucket_name(2), "2");
+        assert!(Priors::default().behavior_change.is_some());
+    }
+
+    #[test]
+    fn evidence_weights_default_and_clamp() {
+        let ew: EvidenceWeights =
+            serde_json::from_str(r#"{"weights": {"tty": 0.0, "orphan": 0.5, "cpu": 9.0}}"#)
+                .unwrap();
+        assert_eq!(ew.weight("tty"), 0.0);
+        assert_eq!(ew.weight("orphan"), 0.5);
+        assert_eq!(ew.weight("cpu"), EVIDENCE_WEIGHT_MAX);
+        assert_eq!(ew.weight("runtime"), 1.0);
+        assert!(Priors::default().evidence_weights.is_none());
+    }
+
+    #[test]
+    fn swap_pressure_buckets_and_gate() {
+        let sp: SwapPressure = serde_json::from_str(r#"{"bucket_names": ["none"]}"#).unwrap();
+        assert_eq!(sp.thresholds_mb, vec![64.0, 512.0]);
+        assert_eq!(sp.bucket(0.0), 0);
+        assert_eq!(sp.bucket(64.0), 0);
+        assert_eq!(sp.bucket(300.0), 1);
+        assert_eq!(sp.bucket(2048.0), 2);
+        assert_eq!(sp.bucket_name(0), "none");
+        assert_eq!(sp.bucket_name(2), "2");
+
+        assert!(sp.applies(Some(25.0), None));
+        assert!(!sp.applies(Some(2.0), Some(0.9)));
+        // Without PSI, a mostly full swap stands in for pressure.
+        assert!(sp.applies(None, Some(0.9)));
+        assert!(!sp.applies(None, Some(0.1)));
+        assert!(!sp.applies(None, None));
+        assert!(Priors::default().swap_pressure.is_some());
+    }
+
+    #[test]
+    fn io_rate_buckets() {
+        let ir: IoRate = serde_json::from_str(r#"{"bucket_names": ["idle"]}"#).unwrap();
+        assert_eq!(ir.thresholds_kib_per_sec, vec![4.0, 1024.0]);
+        assert_eq!(ir.bucket(0.0), 0);
+        assert_eq!(ir.bucket(4096.0), 0);
+        assert_eq!(ir.bucket(64.0 * 1024.0), 1);
+        assert_eq!(ir.bucket(50.0 * 1024.0 * 1024.0), 2);
+        assert_eq!(ir.bucket_name(0), "idle");
+        assert_eq!(ir.bucket_name(2), "2");
+        assert!(Priors::default().io_rate.is_some());
+    }
+
+    #[test]
+    fn fd_leak_buckets() {
+        let fl: FdLeak = serde_json::from_str(r#"{"bucket_names": ["none"]}"#).unwrap();
+        assert_eq!(fl.confidence_thresholds, vec![0.3, 0.6]);
+        assert_eq!(fl.bucket(0.0), 0);
+        assert_eq!(fl.bucket(0.3), 1);
+        assert_eq!(fl.bucket(0.59), 1);
+        assert_eq!(fl.bucket(0.6), 2);
+        assert_eq!(fl.bucket_name(0), "none");
+        assert_eq!(fl.bucket_name(2), "2");
+        assert!(Priors::default().fd_leak.is_some());
+    }
+
+    #[test]
+    fn session_attachment_buckets() {
+        let sa: SessionAttachment =
+            serde_json::from_str(r#"{"bucket_names": ["detached"]}"#).unwrap();
+        assert_eq!(sa.active_idle_secs, 900);
+        assert_eq!(sa.bucket(false, Some(5)), 0);
+        assert_eq!(sa.bucket(true, None), 1);
+        assert_eq!(sa.bucket(true, Some(901)), 1);
+        assert_eq!(sa.bucket(true, Some(900)), 2);
+        assert_eq!(sa.bucket_name(0), "detached");
+        assert_eq!(sa.bucket_name(2), "2");
+        assert!(Priors::default().session_attachment.is_some());
+    }
+
+    #[test]
+    fn net_recency_buckets() {
+        let nr: NetRecency = serde_json::from_str(r#"{"bucket_names": ["active"]}"#).unwrap();
+        assert_eq!(nr.thresholds_secs, vec![60, 3600, 86400]);
+        assert_eq!(nr.bucket(5.0), 0);
+        assert_eq!(nr.bucket(60.0), 0);
+        assert_eq!(nr.bucket(61.0), 1);
+        assert_eq!(nr.bucket(7200.0), 2);
+        assert_eq!(nr.bucket(30.0 * 86400.0), 3);
+        assert_eq!(nr.bucket_name(0), "active");
+        assert_eq!(nr.bucket_name(3), "3");
+    }
+
+    #[test]
+    fn default_priors_include_net_recency() {
+        let priors = Priors::default();
+        let nr = priors.net_recency.expect("default net_recency");
+        let buckets = nr.thresholds_secs.len() + 1;
+        assert_eq!(nr.bucket_names.len(), buckets);
+        assert_eq!(nr.abandoned.unwrap().alpha.len(), buckets);
+    }
 }
@@ -0,0 +1,676 @@
+//! Custom organizational rules (`custom_rules` in policy.json).
+//!
+//! Each rule is a boolean expression over candidate fields in a small
+//! CEL-like language. A rule that evaluates to `true` either blocks the
+//! action or forces it to human review:
+//!
+//! ```json
+//! { "name": "no-prod-db", "effect": "block",
+//!   "expr": "user == 'postgres' && action in ['kill', 'restart']" }
+//! ```
+//!
+//! Supported syntax: string (`'..'` or `".."`), number, `true`/`false`/`null`
+//! and list literals; `!`, `-`, `&&`, `||`, `==`, `!=`, `<`, `<=`, `>`, `>=`,
+//! `in` (list membership or substring); methods `startsWith`, `endsWith`,
+//! `contains` and `lowerAscii`; parentheses. Fields are listed in
+//! [`RULE_FIELDS`]. Comparisons against a missing (`null`) field are false.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Fields available to rule expressions, with their types.
+pub const RULE_FIELDS: &[(&str, &str)] = &[
+    ("action", "string"),
+    ("pid", "number"),
+    ("ppid", "number"),
+    ("user", "string?"),
+    ("group", "string?"),
+    ("category", "string?"),
+    ("cmdline", "string"),
+    ("cmdline_hash", "string"),
+    ("age_seconds", "number"),
+    ("posterior", "number?"),
+    ("memory_mb", "number?"),
+    ("has_known_signature", "bool"),
+    ("robot_mode", "bool"),
+];
+
+const METHODS: &[(&str, usize)] = &[
+    ("startsWith", 1),
+    ("endsWith", 1),
+    ("contains", 1),
+    ("lowerAscii", 0),
+];
+
+/// A custom rule in policy.json.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomRule {
+    pub name: String,
+    /// Boolean expression; the rule applies when it is true.
+    pub expr: String,
+    #[serde(default)]
+    pub effect: RuleEffect,
+    /// Shown to the user when the rule applies.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// What a matching rule does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    /// Block the action.
+    #[default]
+    Block,
+    /// Block in robot mode; warn in interactive mode.
+    ForceReview,
+}
+
+impl RuleEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::ForceReview => "force_review",
+        }
+    }
+}
+
+/// Errors compiling a rule.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RuleError {
+    #[error("rule '{rule}': {message}")]
+    Invalid { rule: String, message: String },
+
+    #[error("duplicate rule name '{0}'")]
+    DuplicateName(String),
+}
+
+/// Candidate fields a rule is evaluated against.
+#[derive(Debug, Clone, Default)]
+pub struct RuleInput {
+    pub action: String,
+    pub pid: i64,
+    pub ppid: i64,
+    pub user: Option<String>,
+    pub group: Option<String>,
+    pub category: Option<String>,
+    pub cmdline: String,
+    pub age_seconds: u64,
+    pub posterior: Option<f64>,
+    pub memory_mb: Option<f64>,
+    pub has_known_signature: bool,
+    pub robot_mode: bool,
+}
+
+impl RuleInput {
+    fn field(&self, name: &str) -> Value {
+        let opt_str = |v: &Option<String>| v.clone().map_or(Value::Null, Value::String);
+        let opt_num = |v: Option<f64>| v.map_or(Value::Null, Value::Number);
+        match name {
+            "action" => Value::String(self.action.clone()),
+            "pid" => Value::Number(self.pid as f64),
+            "ppid" => Value::Number(self.ppid as f64),
+            "user" => opt_str(&self.user),
+            "group" => opt_str(&self.group),
+            "category" => opt_str(&self.category),
+            "cmdline" => Value::String(self.cmdline.clone()),
+            "cmdline_hash" => Value::String(cmdline_hash(&self.cmdline)),
+            "age_seconds" => Value::Number(self.age_seconds as f64),
+            "posterior" => opt_num(self.posterior),
+            "memory_mb" => opt_num(self.memory_mb),
+            "has_known_signature" => Value::Bool(self.has_known_signature),
+            "robot_mode" => Value::Bool(self.robot_mode),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Hex SHA-256 of the trimmed command line, as exposed to rules as
+/// `cmdline_hash` (same as `printf '%s' "$cmd" | sha256sum`).
+pub fn cmdline_hash(cmdline: &str) -> String {
+    hex::encode(Sha256::digest(cmdline.trim().as_bytes()))
+}
+
+/// A parsed rule, ready to evaluate.
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub name: String,
+    pub effect: RuleEffect,
+    pub message: Option<String>,
+    expr: Expr,
+}
+
+impl CompiledRule {
+    /// Parse and type-check the rule's expression.
+    pub fn compile(rule: &CustomRule) -> Result<Self, RuleError> {
+        let invalid = |message: String| RuleError::Invalid {
+            rule: rule.name.clone(),
+            message,
+        };
+        if rule.name.trim().is_empty() {
+            return Err(invalid("name must not be empty".to_string()));
+        }
+        let expr = Parser::new(&rule.expr)
+            .map_err(invalid)?
+            .parse()
+            .map_err(invalid)?;
+        Ok(Self {
+            name: rule.name.clone(),
+            effect: rule.effect,
+            message: rule.message.clone(),
+            expr,
+        })
+    }
+
+    /// Whether the rule applies to `input`.
+    ///
+    /// Errors are type mismatches found at evaluation time (e.g. comparing a
+    /// number to a string); callers should treat them as a match.
+    pub fn matches(&self, input: &RuleInput) -> Result<bool, String> {
+        match self.expr.eval(input)? {
+            Value::Bool(b) => Ok(b),
+            Value::Null => Ok(false),
+            other => Err(format!("expression is {}, not bool", other.type_name())),
+        }
+    }
+}
+
+/// Compile every rule, rejecting duplicate names.
+pub fn compile_rules(rules: &[CustomRule]) -> Result<Vec<CompiledRule>, RuleError> {
+    let mut seen = HashSet::new();
+    rules
+        .iter()
+        .map(|rule| {
+            if !seen.insert(rule.name.as_str()) {
+                return Err(RuleError::DuplicateName(rule.name.clone()));
+            }
+            CompiledRule::compile(rule)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    List(Vec<Value>),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "bool",
+            Self::Number(_) => "number",
+            Self::String(_) => "string",
+            Self::List(_) => "list",
+        }
+    }
+
+    fn truthy(&self, op: &str) -> Result<bool, String> {
+        match self {
+            Self::Bool(b) => Ok(*b),
+            Self::Null => Ok(false),
+            other => Err(format!("'{}' needs bool, got {}", op, other.type_name())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Lit(Value),
+    Field(String),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Cmp(CmpOp, Box<Expr>, Box<Expr>),
+    Method(Box<Expr>, String, Vec<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, input: &RuleInput) -> Result<Value, String> {
+        Ok(match self {
+            Self::Lit(v) => v.clone(),
+            Self::Field(name) => input.field(name),
+            Self::List(items) => Value::List(
+                items
+                    .iter()
+                    .map(|e| e.eval(input))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Self::Not(e) => Value::Bool(!e.eval(input)?.truthy("!")?),
+            Self::Neg(e) => match e.eval(input)? {
+                Value::Number(n) => Value::Number(-n),
+                Value::Null => Value::Null,
+                other => return Err(format!("'-' needs number, got {}", other.type_name())),
+            },
+            Self::And(a, b) => {
+                Value::Bool(a.eval(input)?.truthy("&&")? && b.eval(input)?.truthy("&&")?)
+            }
+            Self::Or(a, b) => {
+                Value::Bool(a.eval(input)?.truthy("||")? || b.eval(input)?.truthy("||")?)
+            }
+            Self::Cmp(op, a, b) => Value::Bool(compare(*op, &a.eval(input)?, &b.eval(input)?)?),
+            Self::Method(target, name, args) => {
+                let target = target.eval(input)?;
+                let args: Vec<Value> = args
+                    .iter()
+                    .map(|e| e.eval(input))
+                    .collect::<Result<_, _>>()?;
+                call_method(&target, name, &args)?
+            }
+        })
+    }
+}
+
+fn compare(op: CmpOp, a: &Value, b: &Value) -> Result<bool, String> {
+    use std::cmp::Ordering;
+    match op {
+        CmpOp::Eq => return Ok(a == b),
+        CmpOp::Ne => return Ok(a != b),
+        CmpOp::In => {
+            return match (a, b) {
+                (Value::Null, _) | (_, Value::Null) => Ok(false),
+                (_, Value::List(items)) => Ok(items.contains(a)),
+                (Value::String(needle), Value::String(hay)) => Ok(hay.contains(needle.as_str())),
+                _ => Err(format!(
+                    "'in' needs a list or string on the right, got {}",
+                    b.type_name()
+                )),
+            }
+        }
+        _ => {}
+    }
+    let ordering = match (a, b) {
+        (Value::Null, _) | (_, Value::Null) => return Ok(false),
+        (Value::Number(x), Value::Number(y)) => x.partial_cmp(y),
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        _ => {
+            return Err(format!(
+                "cannot order {} and {}",
+                a.type_name(),
+                b.type_name()
+            ))
+        }
+    };
+    let Some(ordering) = ordering else {
+        return Ok(false);
+    };
+    Ok(match op {
+        CmpOp::Lt => ordering == Ordering::Less,
+        CmpOp::Le => ordering != Ordering::Greater,
+        CmpOp::Gt => ordering == Ordering::Greater,
+        CmpOp::Ge => ordering != Ordering::Less,
+        CmpOp::Eq | CmpOp::Ne | CmpOp::In => unreachable!(),
+    })
+}
+
+fn call_method(target: &Value, name: &str, args: &[Value]) -> Result<Value, String> {
+    let arg_str = || match args.first() {
+        Some(Value::String(s)) => Ok(s.as_str()),
+        Some(other) => Err(format!(
+            "{}() needs a string, got {}",
+            name,
+            other.type_name()
+        )),
+        None => Err(format!("{}() needs an argument", name)),
+    };
+    Ok(match (target, name) {
+        (Value::Null, _) => Value::Null,
+        (Value::String(s), "startsWith") => Value::Bool(s.starts_with(arg_str()?)),
+        (Value::String(s), "endsWith") => Value::Bool(s.ends_with(arg_str()?)),
+        (Value::String(s), "contains") => Value::Bool(s.contains(arg_str()?)),
+        (Value::String(s), "lowerAscii") => Value::String(s.to_ascii_lowercase()),
+        (Value::List(items), "contains") => Value::Bool(items.contains(&args[0])),
+        (other, _) => {
+            return Err(format!(
+                "{}() is not defined on {}",
+                name,
+                other.type_name()
+            ))
+        }
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Op(&'static str),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    const OPS: [&str; 16] = [
+        "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "-", "(", ")", "[", "]", ",", ".",
+    ];
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    'outer: while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    s.push(match chars[i] {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                } else {
+                    s.push(chars[i]);
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err("unterminated string literal".to_string());
+            }
+            tokens.push(Token::Str(s));
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let raw: String = chars[start..i].iter().collect();
+            let n = raw
+                .parse()
+                .map_err(|_| format!("invalid number '{}'", raw))?;
+            tokens.push(Token::Num(n));
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            continue;
+        }
+        for op in OPS {
+            let len = op.len();
+            if i + len <= chars.len() && chars[i..i + len].iter().copied().eq(op.chars()) {
+                tokens.push(Token::Op(op));
+                i += len;
+                continue 'outer;
+            }
+        }
+        return Err(format!("unexpected character '{}'", c));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(src: &str) -> Result<Self, String> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+            pos: 0,
+        })
+    }
+
+    fn parse(mut self) -> Result<Expr, String> {
+        if self.tokens.is_empty() {
+            return Err("expression is empty".to_string());
+        }
+        let expr = self.or()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(expr),
+            Some(tok) => Err(format!("unexpected {:?}", tok)),
+        }
+    }
+
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Op(o)) if *o == op)
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        let hit = self.peek_op(op);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_op(&mut self, op: &str) -> Result<(), String> {
+        if self.eat_op(op) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}'", op))
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat_op("||") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.relation()?;
+        while self.eat_op("&&") {
+            left = Expr::And(Box::new(left), Box::new(self.relation()?));
+        }
+        Ok(left)
+    }
+
+    fn relation(&mut self) -> Result<Expr, String> {
+        let left = self.unary()?;
+        let op = match self.tokens.get(self.pos) {
+            Some(Token::Op("==")) => CmpOp::Eq,
+            Some(Token::Op("!=")) => CmpOp::Ne,
+            Some(Token::Op("<")) => CmpOp::Lt,
+            Some(Token::Op("<=")) => CmpOp::Le,
+            Some(Token::Op(">")) => CmpOp::Gt,
+            Some(Token::Op(">=")) => CmpOp::Ge,
+            Some(Token::Ident(kw)) if kw == "in" => CmpOp::In,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.unary()?;
+        Ok(Expr::Cmp(op, Box::new(left), Box::new(right)))
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat_op("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat_op("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        while self.eat_op(".") {
+            let name = match self.tokens.get(self.pos) {
+                Some(Token::Ident(name)) => name.clone(),
+                _ => return Err("expected method name after '.'".to_string()),
+            };
+            self.pos += 1;
+            let arity = METHODS
+                .iter()
+                .find(|(m, _)| *m == name)
+                .map(|(_, arity)| *arity)
+                .ok_or_else(|| format!("unknown method '{}'", name))?;
+            self.expect_op("(")?;
+            let args = self.list_items(")")?;
+            if args.len() != arity {
+                return Err(format!(
+                    "{}() takes {} argument(s), got {}",
+                    name,
+                    arity,
+                    args.len()
+                ));
+            }
+            expr = Expr::Method(Box::new(expr), name, args);
+        }
+        Ok(expr)
+    }
+
+    fn list_items(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut items = Vec::new();
+        if self.eat_op(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.or()?);
+            if self.eat_op(close) {
+                return Ok(items);
+            }
+            self.expect_op(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let tok = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        match tok {
+            Token::Str(s) => Ok(Expr::Lit(Value::String(s))),
+            Token::Num(n) => Ok(Expr::Lit(Value::Number(n))),
+            Token::Ident(id) => match id.as_str() {
+                "true" => Ok(Expr::Lit(Value::Bool(true))),
+                "false" => Ok(Expr::Lit(Value::Bool(false))),
+                "null" => Ok(Expr::Lit(Value::Null)),
+                _ if RULE_FIELDS.iter().any(|(f, _)| *f == id) => Ok(Expr::Field(id)),
+                _ => Err(format!("unknown field '{}'", id)),
+            },
+            Token::Op("(") => {
+                let expr = self.or()?;
+                self.expect_op(")")?;
+                Ok(expr)
+            }
+            Token::Op("[") => Ok(Expr::List(self.list_items("]")?)),
+            Token::Op(op) => Err(format!("unexpected '{}'", op)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(expr: &str) -> CustomRule {
+        CustomRule {
+            name: "test".to_string(),
+            expr: expr.to_string(),
+            effect: RuleEffect::Block,
+            message: None,
+        }
+    }
+
+    fn input() -> RuleInput {
+        RuleInput {
+            action: "kill".to_string(),
+            pid: 4242,
+            ppid: 1,
+            user: Some("postgres".to_string()),
+            category: Some("daemon".to_string()),
+            cmdline: "postgres -D /var/lib/pg".to_string(),
+            age_seconds: 7200,
+            posterior: Some(0.97),
+            ..Default::default()
+        }
+    }
+
+    fn eval(expr: &str) -> Result<bool, String> {
+        CompiledRule::compile(&rule(expr))
+            .unwrap()
+            .matches(&input())
+    }
+
+    #[test]
+    fn evaluates_fields_and_operators() {
+        assert_eq!(
+            eval("user == 'postgres' && action in ['kill', 'restart']"),
+            Ok(true)
+        );
+        assert_eq!(eval("posterior >= 0.99 || age_seconds < 3600"), Ok(false));
+        assert_eq!(
+            eval("cmdline.startsWith(\"postgres\") && !has_known_signature"),
+            Ok(true)
+        );
+        assert_eq!(
+            eval("'/var/lib' in cmdline && category.lowerAscii() == 'daemon'"),
+            Ok(true)
+        );
+        assert_eq!(eval("(ppid == 1) && -pid < 0"), Ok(true));
+        assert_eq!(
+            eval(&format!(
+                "cmdline_hash == '{}'",
+                cmdline_hash("postgres -D /var/lib/pg")
+            )),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn null_fields_do_not_match() {
+        assert_eq!(eval("group == 'wheel'"), Ok(false));
+        assert_eq!(eval("memory_mb > 100"), Ok(false));
+        assert_eq!(eval("group.startsWith('w')"), Ok(false));
+        assert_eq!(eval("group == null"), Ok(true));
+    }
+
+    #[test]
+    fn type_errors_surface_at_evaluation() {
+        assert!(eval("pid > 'a'").is_err());
+        assert!(eval("cmdline").is_err());
+    }
+
+    #[test]
+    fn compile_rejects_bad_expressions() {
+        for bad in [
+            "",
+            "usr == 'x'",
+            "user ==",
+            "cmdline.matches('x')",
+            "user.startsWith()",
+            "'unterminated",
+            "pid == 1 pid",
+        ] {
+            assert!(CompiledRule::compile(&rule(bad)).is_err(), "{}", bad);
+        }
+        assert_eq!(
+            compile_rules(&[rule("true"), rule("false")]).unwrap_err(),
+            RuleError::DuplicateName("test".to_string())
+        );
+    }
+}
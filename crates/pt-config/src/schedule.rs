@@ -0,0 +1,495 @@
+//! Time-of-day constraints on destructive actions.
+//!
+//! A policy can restrict when robot mode may kill:
+//!
+//! - **Maintenance windows**: cron expressions (`minute hour day-of-month
+//!   month day-of-week`) marking when a window opens, plus how long it stays
+//!   open. When any window is configured, destructive actions run only while
+//!   one is open.
+//! - **Business hours**: per-category daily spans during which destructive
+//!   actions on matching processes are held back (e.g. no killing dev servers
+//!   between 09:00 and 18:00 on weekdays).
+//!
+//! Schedules are evaluated on wall-clock minutes in the configured timezone.
+
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Longest maintenance window (one week).
+pub const MAX_WINDOW_MINUTES: u32 = 7 * 24 * 60;
+
+/// How far ahead to look for the next allowed minute.
+const LOOKAHEAD_MINUTES: i64 = 366 * 24 * 60;
+
+/// Schedule constraints in policy.json (`schedule`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionSchedule {
+    /// Clock the windows are expressed in.
+    #[serde(default)]
+    pub timezone: ScheduleTimezone,
+    /// When non-empty, destructive actions run only inside one of these.
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// Spans during which destructive actions on some categories are held.
+    #[serde(default)]
+    pub business_hours: Vec<BusinessHours>,
+}
+
+/// Clock used to evaluate a schedule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleTimezone {
+    #[default]
+    Local,
+    Utc,
+}
+
+/// A recurring window during which destructive actions are allowed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub name: String,
+    /// Five-field cron expression for the window's opening minute.
+    pub cron: String,
+    /// How long the window stays open after each opening.
+    pub duration_minutes: u32,
+}
+
+/// A daily span during which destructive actions on `categories` are held.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BusinessHours {
+    /// Process categories covered (case-insensitive, `*` for all).
+    pub categories: Vec<String>,
+    /// Cron day-of-week field (default `1-5`, Monday to Friday).
+    #[serde(default = "default_business_days")]
+    pub days: String,
+    /// Start of the span, `HH:MM`.
+    pub start: String,
+    /// End of the span (exclusive), `HH:MM`, later than `start`.
+    pub end: String,
+}
+
+fn default_business_days() -> String {
+    "1-5".to_string()
+}
+
+impl ActionSchedule {
+    /// Whether the schedule places no constraints.
+    pub fn is_empty(&self) -> bool {
+        self.maintenance_windows.is_empty() && self.business_hours.is_empty()
+    }
+
+    /// Current wall-clock time in the schedule's timezone.
+    pub fn now(&self) -> NaiveDateTime {
+        match self.timezone {
+            ScheduleTimezone::Local => chrono::Local::now().naive_local(),
+            ScheduleTimezone::Utc => chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// Errors parsing a schedule.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[error("maintenance window '{window}': {message}")]
+    InvalidWindow { window: String, message: String },
+
+    #[error("business hours for [{categories}]: {message}")]
+    InvalidBusinessHours { categories: String, message: String },
+}
+
+/// Why a destructive action is held back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleBlock {
+    /// Maintenance windows are configured and none is open.
+    OutsideMaintenanceWindow,
+    /// A business-hours span covers the process category.
+    BusinessHours { category: String, span: String },
+}
+
+/// A parsed five-field cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronExpr {
+    /// Parse `minute hour day-of-month month day-of-week`.
+    ///
+    /// Fields accept `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+    /// and comma lists. Day-of-week is 0-7 (0 and 7 are Sunday) or `sun`..`sat`.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "expected 5 cron fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        }
+        let days_of_week = parse_days_of_week(fields[4])?;
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, "minute")?,
+            hours: parse_field(fields[1], 0, 23, "hour")?,
+            days_of_month: parse_field(fields[2], 1, 31, "day of month")?,
+            months: parse_field(fields[3], 1, 12, "month")?,
+            days_of_week,
+            dom_restricted: fields[2] != "*",
+            dow_restricted: fields[4] != "*",
+        })
+    }
+
+    /// Whether the expression fires at `at` (seconds ignored).
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        if !bit(self.minutes, at.minute())
+            || !bit(self.hours, at.hour())
+            || !bit(self.months, at.month())
+        {
+            return false;
+        }
+        let dom = bit(self.days_of_month, at.day());
+        let dow = bit(self.days_of_week, at.weekday().num_days_from_sunday());
+        // Standard cron: when both day fields are restricted, either may match.
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            _ => dom && dow,
+        }
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1u64 << value) != 0
+}
+
+fn parse_days_of_week(field: &str) -> Result<u64, String> {
+    const NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+    let mut normalized = field.to_ascii_lowercase();
+    for (i, name) in NAMES.iter().enumerate() {
+        normalized = normalized.replace(name, &i.to_string());
+    }
+    let mut mask = parse_field(&normalized, 0, 7, "day of week")?;
+    if bit(mask, 7) {
+        mask = (mask & !(1 << 7)) | 1;
+    }
+    Ok(mask)
+}
+
+fn parse_field(field: &str, min: u32, max: u32, what: &str) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid {} step '{}'", what, step))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (parse_value(lo, what)?, parse_value(hi, what)?)
+        } else {
+            let value = parse_value(range, what)?;
+            // `5/15` means "from 5, every 15".
+            (value, if step > 1 { max } else { value })
+        };
+        if lo < min || hi > max || lo > hi {
+            return Err(format!(
+                "{} range '{}' outside {}-{}",
+                what, range, min, max
+            ));
+        }
+        for value in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(raw: &str, what: &str) -> Result<u32, String> {
+    raw.parse()
+        .map_err(|_| format!("invalid {} value '{}'", what, raw))
+}
+
+fn parse_hhmm(raw: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(raw, "%H:%M")
+        .map_err(|_| format!("invalid time '{}' (expected HH:MM)", raw))
+}
+
+#[derive(Debug, Clone)]
+struct CompiledWindow {
+    cron: CronExpr,
+    duration_minutes: u32,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledHours {
+    categories: Vec<String>,
+    days: u64,
+    start: NaiveTime,
+    end: NaiveTime,
+    span: String,
+}
+
+impl CompiledHours {
+    fn covers(&self, category: Option<&str>) -> Option<String> {
+        self.categories.iter().find_map(|c| {
+            if c == "*" {
+                Some(category.unwrap_or("*").to_string())
+            } else {
+                category
+                    .filter(|cat| cat.eq_ignore_ascii_case(c))
+                    .map(str::to_string)
+            }
+        })
+    }
+
+    fn active(&self, at: NaiveDateTime) -> bool {
+        let time = at.time();
+        bit(self.days, at.weekday().num_days_from_sunday()) && time >= self.start && time < self.end
+    }
+}
+
+/// A validated schedule, ready to evaluate.
+#[derive(Debug, Clone)]
+pub struct CompiledSchedule {
+    windows: Vec<CompiledWindow>,
+    hours: Vec<CompiledHours>,
+}
+
+impl CompiledSchedule {
+    /// Parse every window and span in `schedule`.
+    pub fn compile(schedule: &ActionSchedule) -> Result<Self, ScheduleError> {
+        let windows = schedule
+            .maintenance_windows
+            .iter()
+            .map(|w| {
+                let invalid = |message: String| ScheduleError::InvalidWindow {
+                    window: w.name.clone(),
+                    message,
+                };
+                if w.duration_minutes == 0 || w.duration_minutes > MAX_WINDOW_MINUTES {
+                    return Err(invalid(format!(
+                        "duration_minutes must be in 1..={}",
+                        MAX_WINDOW_MINUTES
+                    )));
+                }
+                Ok(CompiledWindow {
+                    cron: CronExpr::parse(&w.cron).map_err(invalid)?,
+                    duration_minutes: w.duration_minutes,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let hours = schedule
+            .business_hours
+            .iter()
+            .map(|h| {
+                let invalid = |message: String| ScheduleError::InvalidBusinessHours {
+                    categories: h.categories.join(", "),
+                    message,
+                };
+                if h.categories.is_empty() {
+                    return Err(invalid("categories must not be empty".to_string()));
+                }
+                let start = parse_hhmm(&h.start).map_err(invalid)?;
+                let end = parse_hhmm(&h.end).map_err(invalid)?;
+                if end <= start {
+                    return Err(invalid(format!(
+                        "end {} must be later than start {}",
+                        h.end, h.start
+                    )));
+                }
+                Ok(CompiledHours {
+                    categories: h.categories.clone(),
+                    days: parse_days_of_week(&h.days).map_err(invalid)?,
+                    start,
+                    end,
+                    span: format!("{} {}-{}", h.days, h.start, h.end),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { windows, hours })
+    }
+
+    /// Whether any maintenance window is open at `at`.
+    pub fn in_maintenance_window(&self, at: NaiveDateTime) -> bool {
+        self.window_open_until(at).is_some_and(|until| at < until)
+    }
+
+    /// End of the latest window opened at or before `at`, if it is still open.
+    fn window_open_until(&self, at: NaiveDateTime) -> Option<NaiveDateTime> {
+        let at = truncate_to_minute(at);
+        self.windows
+            .iter()
+            .filter_map(|w| {
+                (0..w.duration_minutes as i64)
+                    .map(|back| at - Duration::minutes(back))
+                    .find(|opened| w.cron.matches(*opened))
+                    .map(|opened| opened + Duration::minutes(w.duration_minutes as i64))
+            })
+            .max()
+    }
+
+    fn held_by_hours(&self, at: NaiveDateTime, category: Option<&str>) -> Option<ScheduleBlock> {
+        self.hours.iter().find_map(|h| {
+            let category = h.covers(category)?;
+            h.active(at).then(|| ScheduleBlock::BusinessHours {
+                category,
+                span: h.span.clone(),
+            })
+        })
+    }
+
+    /// Why a destructive action on a process of `category` is held at
+    /// `at`, or `None` when it may run.
+    pub fn block_at(&self, at: NaiveDateTime, category: Option<&str>) -> Option<ScheduleBlock> {
+        if !self.windows.is_empty() && !self.in_maintenance_window(at) {
+            return Some(ScheduleBlock::OutsideMaintenanceWindow);
+        }
+        self.held_by_hours(at, category)
+    }
+
+    /// First minute at or after `at` when a destructive action on
+    /// `category` may run, within a year.
+    pub fn next_allowed(&self, at: NaiveDateTime, category: Option<&str>) -> Option<NaiveDateTime> {
+        let start = truncate_to_minute(at);
+        // Track window state incrementally rather than re-scanning each
+        // window's full duration for every minute.
+        let mut open_until = self.window_open_until(start);
+        (0..LOOKAHEAD_MINUTES)
+            .map(|m| start + Duration::minutes(m))
+            .find(|t| {
+                for w in &self.windows {
+                    if w.cron.matches(*t) {
+                        let until = *t + Duration::minutes(w.duration_minutes as i64);
+                        open_until = open_until.max(Some(until));
+                    }
+                }
+                let in_window = self.windows.is_empty() || open_until.is_some_and(|u| *t < u);
+                in_window && self.held_by_hours(*t, category).is_none()
+            })
+    }
+}
+
+fn truncate_to_minute(at: NaiveDateTime) -> NaiveDateTime {
+    at.with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, mo, d)
+            .unwrap()
+            .and_hms_opt(h, mi, 0)
+            .unwrap()
+    }
+
+    fn nightly_window() -> MaintenanceWindow {
+        MaintenanceWindow {
+            name: "nightly".to_string(),
+            cron: "0 2 * * *".to_string(),
+            duration_minutes: 120,
+        }
+    }
+
+    #[test]
+    fn cron_fields_parse() {
+        let cron = CronExpr::parse("*/15 9-17 * * mon-fri").unwrap();
+        // 2026-01-14 is a Wednesday.
+        assert!(cron.matches(at(2026, 1, 14, 9, 45)));
+        assert!(!cron.matches(at(2026, 1, 14, 9, 50)));
+        assert!(!cron.matches(at(2026, 1, 17, 9, 45)));
+        assert!(CronExpr::parse("0 2 * *").is_err());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("0 0 * * 7")
+            .unwrap()
+            .matches(at(2026, 1, 18, 0, 0)));
+    }
+
+    #[test]
+    fn maintenance_window_gates_destructive_actions() {
+        let schedule = CompiledSchedule::compile(&ActionSchedule {
+            maintenance_windows: vec![nightly_window()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(schedule.block_at(at(2026, 1, 14, 3, 30), None), None);
+        assert_eq!(
+            schedule.block_at(at(2026, 1, 14, 4, 0), None),
+            Some(ScheduleBlock::OutsideMaintenanceWindow)
+        );
+        assert_eq!(
+            schedule.next_allowed(at(2026, 1, 14, 12, 10), None),
+            Some(at(2026, 1, 15, 2, 0))
+        );
+    }
+
+    #[test]
+    fn business_hours_hold_matching_categories() {
+        let schedule = CompiledSchedule::compile(&ActionSchedule {
+            business_hours: vec![BusinessHours {
+                categories: vec!["dev_server".to_string()],
+                days: default_business_days(),
+                start: "09:00".to_string(),
+                end: "18:00".to_string(),
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let wednesday_noon = at(2026, 1, 14, 12, 0);
+        assert!(matches!(
+            schedule.block_at(wednesday_noon, Some("DEV_SERVER")),
+            Some(ScheduleBlock::BusinessHours { .. })
+        ));
+        assert_eq!(schedule.block_at(wednesday_noon, Some("test")), None);
+        assert_eq!(schedule.block_at(wednesday_noon, None), None);
+        assert_eq!(
+            schedule.block_at(at(2026, 1, 17, 12, 0), Some("dev_server")),
+            None
+        );
+        assert_eq!(
+            schedule.next_allowed(wednesday_noon, Some("dev_server")),
+            Some(at(2026, 1, 14, 18, 0))
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_rejected() {
+        let mut window = nightly_window();
+        window.duration_minutes = 0;
+        assert!(matches!(
+            CompiledSchedule::compile(&ActionSchedule {
+                maintenance_windows: vec![window],
+                ..Default::default()
+            }),
+            Err(ScheduleError::InvalidWindow { .. })
+        ));
+        assert!(matches!(
+            CompiledSchedule::compile(&ActionSchedule {
+                business_hours: vec![BusinessHours {
+                    categories: vec!["*".to_string()],
+                    days: default_business_days(),
+                    start: "18:00".to_string(),
+                    end: "09:00".to_string(),
+                }],
+                ..Default::default()
+            }),
+            Err(ScheduleError::InvalidBusinessHours { .. })
+        ));
+    }
+}
@@ -86,6 +86,268 @@ pub fn validate_priors(priors: &crate::priors::Priors) -> ValidationResult<()> {
         )?;
     }
 
+    if let Some(ref net_recency) = priors.net_recency {
+        validate_net_recency(net_recency)?;
+    }
+
+    if let Some(ref behavior_change) = priors.behavior_change {
+        validate_behavior_change(behavior_change)?;
+    }
+
+    if let Some(ref swap_pressure) = priors.swap_pressure {
+        validate_swap_pressure(swap_pressure)?;
+    }
+
+    if let Some(ref io_rate) = priors.io_rate {
+        validate_io_rate(io_rate)?;
+    }
+
+    if let Some(ref fd_leak) = priors.fd_leak {
+        validate_fd_leak(fd_leak)?;
+    }
+
+    if let Some(ref session_attachment) = priors.session_attachment {
+        validate_session_attachment(session_attachment)?;
+    }
+
+    if let Some(ref evidence_weights) = priors.evidence_weights {
+        validate_evidence_weights(evidence_weights)?;
+    }
+
+    Ok(())
+}
+
+/// Validate socket-activity recency buckets and their per-class Dirichlets.
+fn validate_net_recency(params: &crate::priors::NetRecency) -> ValidationResult<()> {
+    let thresholds = &params.thresholds_secs;
+    if thresholds.is_empty() || thresholds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(ValidationError::InvalidValue {
+            field: "net_recency.thresholds_secs".to_string(),
+            message: format!(
+                "Must be non-empty and strictly ascending, got {:?}",
+                thresholds
+            ),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "net_recency",
+        thresholds.len() + 1,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate behavior-change deviation buckets and their per-class Dirichlets.
+fn validate_behavior_change(params: &crate::priors::BehaviorChange) -> ValidationResult<()> {
+    let thresholds = &params.thresholds_sigma;
+    if thresholds.is_empty()
+        || thresholds.iter().any(|t| !t.is_finite() || *t < 0.0)
+        || thresholds.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(ValidationError::InvalidValue {
+            field: "behavior_change.thresholds_sigma".to_string(),
+            message: format!(
+                "Must be non-empty, non-negative and strictly ascending, got {:?}",
+                thresholds
+            ),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "behavior_change",
+        thresholds.len() + 1,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate I/O-rate buckets and their per-class Dirichlets.
+fn validate_io_rate(params: &crate::priors::IoRate) -> ValidationResult<()> {
+    let thresholds = &params.thresholds_kib_per_sec;
+    if thresholds.is_empty()
+        || thresholds.iter().any(|t| !t.is_finite() || *t < 0.0)
+        || thresholds.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(ValidationError::InvalidValue {
+            field: "io_rate.thresholds_kib_per_sec".to_string(),
+            message: format!(
+                "Must be non-empty, non-negative and strictly ascending, got {:?}",
+                thresholds
+            ),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "io_rate",
+        thresholds.len() + 1,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate fd-leak confidence buckets and their per-class Dirichlets.
+fn validate_fd_leak(params: &crate::priors::FdLeak) -> ValidationResult<()> {
+    let thresholds = &params.confidence_thresholds;
+    if thresholds.is_empty()
+        || thresholds.iter().any(|t| !(0.0..=1.0).contains(t))
+        || thresholds.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(ValidationError::InvalidValue {
+            field: "fd_leak.confidence_thresholds".to_string(),
+            message: format!(
+                "Must be non-empty, within [0, 1] and strictly ascending, got {:?}",
+                thresholds
+            ),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "fd_leak",
+        thresholds.len() + 1,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate the session-attachment idle cutoff and per-class Dirichlets.
+fn validate_session_attachment(params: &crate::priors::SessionAttachment) -> ValidationResult<()> {
+    if params.active_idle_secs == 0 {
+        return Err(ValidationError::InvalidValue {
+            field: "session_attachment.active_idle_secs".to_string(),
+            message: "Must be > 0".to_string(),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "session_attachment",
+        crate::priors::SessionAttachment::BUCKETS,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate swap-pressure buckets, host gates and per-class Dirichlets.
+fn validate_swap_pressure(params: &crate::priors::SwapPressure) -> ValidationResult<()> {
+    let thresholds = &params.thresholds_mb;
+    if thresholds.is_empty()
+        || thresholds.iter().any(|t| !t.is_finite() || *t < 0.0)
+        || thresholds.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(ValidationError::InvalidValue {
+            field: "swap_pressure.thresholds_mb".to_string(),
+            message: format!(
+                "Must be non-empty, non-negative and strictly ascending, got {:?}",
+                thresholds
+            ),
+        });
+    }
+    if !(0.0..=100.0).contains(&params.min_psi_some_avg10) {
+        return Err(ValidationError::InvalidValue {
+            field: "swap_pressure.min_psi_some_avg10".to_string(),
+            message: "must be in [0, 100]".to_string(),
+        });
+    }
+    if !(0.0..=1.0).contains(&params.min_swap_used_fraction) {
+        return Err(ValidationError::InvalidValue {
+            field: "swap_pressure.min_swap_used_fraction".to_string(),
+            message: "must be in [0, 1]".to_string(),
+        });
+    }
+
+    validate_bucket_dirichlets(
+        "swap_pressure",
+        thresholds.len() + 1,
+        [
+            &params.useful,
+            &params.useful_bad,
+            &params.abandoned,
+            &params.zombie,
+        ],
+    )
+}
+
+/// Validate site evidence weights: known terms, within the guard bounds.
+fn validate_evidence_weights(params: &crate::priors::EvidenceWeights) -> ValidationResult<()> {
+    use crate::priors::{EVIDENCE_WEIGHT_MAX, EVIDENCE_WEIGHT_MIN, WEIGHTABLE_EVIDENCE};
+
+    for (feature, weight) in &params.weights {
+        let field = format!("evidence_weights.weights.{}", feature);
+        if !WEIGHTABLE_EVIDENCE.contains(&feature.as_str()) {
+            return Err(ValidationError::InvalidValue {
+                field,
+                message: format!(
+                    "Unknown evidence term; expected one of {}",
+                    WEIGHTABLE_EVIDENCE.join(", ")
+                ),
+            });
+        }
+        if !(EVIDENCE_WEIGHT_MIN..=EVIDENCE_WEIGHT_MAX).contains(weight) {
+            return Err(ValidationError::InvalidValue {
+                field,
+                message: format!(
+                    "Must be in [{}, {}], got {}",
+                    EVIDENCE_WEIGHT_MIN, EVIDENCE_WEIGHT_MAX, weight
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that each present class Dirichlet has one positive alpha per bucket.
+fn validate_bucket_dirichlets(
+    section: &str,
+    buckets: usize,
+    classes: [&Option<crate::priors::DirichletParams>; 4],
+) -> ValidationResult<()> {
+    for (class, dirichlet) in ["useful", "useful_bad", "abandoned", "zombie"]
+        .into_iter()
+        .zip(classes)
+    {
+        let Some(dirichlet) = dirichlet else {
+            continue;
+        };
+        let field = format!("{}.{}.alpha", section, class);
+        if dirichlet.alpha.len() != buckets {
+            return Err(ValidationError::InvalidValue {
+                field,
+                message: format!(
+                    "Must have {} entries (one per bucket), got {}",
+                    buckets,
+                    dirichlet.alpha.len()
+                ),
+            });
+        }
+        if let Some(alpha) = dirichlet.alpha.iter().find(|a| **a <= 0.0) {
+            return Err(ValidationError::InvalidValue {
+                field,
+                message: format!("Must be positive, got {}", alpha),
+            });
+        }
+    }
+
     Ok(())
 }
 
@@ -112,11 +374,23 @@ fn validate_class_params(name: &str, params: &crate::priors::ClassParams) -> Val
         validate_beta_params(&format!("classes.{}.io_active_beta", name), beta)?;
     }
 
+    if let Some(ref beta) = params.gpu_beta {
+        validate_beta_params(&format!("classes.{}.gpu_beta", name), beta)?;
+    }
+
+    if let Some(ref beta) = params.gpu_util_beta {
+        validate_beta_params(&format!("classes.{}.gpu_util_beta", name), beta)?;
+    }
+
     // Validate Gamma parameters
     if let Some(ref gamma) = params.runtime_gamma {
         validate_gamma_params(&format!("classes.{}.runtime_gamma", name), gamma)?;
     }
 
+    if let Some(ref gamma) = params.runtime_surprise_gamma {
+        validate_gamma_params(&format!("classes.{}.runtime_surprise_gamma", name), gamma)?;
+    }
+
     if let Some(ref gamma) = params.hazard_gamma {
         validate_gamma_params(&format!("classes.{}.hazard_gamma", name), gamma)?;
     }
@@ -191,6 +465,15 @@ pub fn validate_policy(policy: &crate::policy::Policy) -> ValidationResult<()> {
         });
     }
 
+    if let Some(score) = policy.data_loss_gates.max_blast_radius_score {
+        if !score.is_finite() || score < 0.0 {
+            return Err(ValidationError::InvalidValue {
+                field: "data_loss_gates.max_blast_radius_score".to_string(),
+                message: format!("Must be >= 0, got {}", score),
+            });
+        }
+    }
+
     // Validate guardrails
     if policy.guardrails.never_kill_ppid.is_empty() {
         return Err(ValidationError::SemanticError(
@@ -204,7 +487,123 @@ pub fn validate_policy(policy: &crate::policy::Policy) -> ValidationResult<()> {
         ));
     }
 
+    if policy.guardrails.protect_attached_sessions
+        && policy.guardrails.attached_session_idle_seconds == 0
+    {
+        return Err(ValidationError::InvalidValue {
+            field: "guardrails.attached_session_idle_seconds".to_string(),
+            message: "Must be > 0 when protect_attached_sessions is set".to_string(),
+        });
+    }
+
     validate_load_aware(&policy.load_aware)?;
+    validate_apply_health_gate(&policy.apply_health_gate)?;
+    validate_throttle_limits(&policy.throttle_limits)?;
+    validate_cascade(&policy.cascade)?;
+    validate_thread_hog(&policy.thread_hog)?;
+    validate_schedule(&policy.schedule)?;
+    validate_custom_rules(&policy.custom_rules)?;
+
+    Ok(())
+}
+
+fn validate_apply_health_gate(gate: &crate::policy::ApplyHealthGate) -> ValidationResult<()> {
+    if !gate.enabled {
+        return Ok(());
+    }
+
+    if gate.max_load_per_core <= 0.0 {
+        return Err(ValidationError::InvalidValue {
+            field: "apply_health_gate.max_load_per_core".to_string(),
+            message: "must be > 0".to_string(),
+        });
+    }
+    if gate.max_psi_avg10 <= 0.0 || gate.max_psi_avg10 > 100.0 {
+        return Err(ValidationError::InvalidValue {
+            field: "apply_health_gate.max_psi_avg10".to_string(),
+            message: "must be in (0, 100]".to_string(),
+        });
+    }
+    if gate.poll_interval_seconds == 0 {
+        return Err(ValidationError::InvalidValue {
+            field: "apply_health_gate.poll_interval_seconds".to_string(),
+            message: "must be > 0".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_cascade(cascade: &crate::policy::CascadePolicy) -> ValidationResult<()> {
+    if cascade.max_tree_size == 0 {
+        return Err(ValidationError::InvalidValue {
+            field: "cascade.max_tree_size".to_string(),
+            message: "must be > 0".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_thread_hog(thread_hog: &crate::policy::ThreadHogPolicy) -> ValidationResult<()> {
+    let share = thread_hog.min_dominant_share;
+    if !(share > 0.0 && share <= 1.0) {
+        return Err(ValidationError::InvalidValue {
+            field: "thread_hog.min_dominant_share".to_string(),
+            message: format!("Must be in (0, 1], got {}", share),
+        });
+    }
+    let cpu = thread_hog.min_cpu_percent;
+    if !cpu.is_finite() || cpu < 0.0 {
+        return Err(ValidationError::InvalidValue {
+            field: "thread_hog.min_cpu_percent".to_string(),
+            message: format!("Must be >= 0, got {}", cpu),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_schedule(schedule: &crate::schedule::ActionSchedule) -> ValidationResult<()> {
+    crate::schedule::CompiledSchedule::compile(schedule).map_err(|e| {
+        ValidationError::InvalidValue {
+            field: "schedule".to_string(),
+            message: e.to_string(),
+        }
+    })?;
+
+    Ok(())
+}
+
+fn validate_custom_rules(rules: &[crate::rules::CustomRule]) -> ValidationResult<()> {
+    crate::rules::compile_rules(rules).map_err(|e| ValidationError::InvalidValue {
+        field: "custom_rules".to_string(),
+        message: e.to_string(),
+    })?;
+
+    Ok(())
+}
+
+fn validate_throttle_limits(limits: &crate::policy::ThrottleLimits) -> ValidationResult<()> {
+    if !limits.cpu_fraction.is_finite() || limits.cpu_fraction <= 0.0 {
+        return Err(ValidationError::InvalidValue {
+            field: "throttle_limits.cpu_fraction".to_string(),
+            message: "must be > 0".to_string(),
+        });
+    }
+    // Kernel bounds for cpu.max / cpu.cfs_period_us.
+    if !(1_000..=1_000_000).contains(&limits.period_us) {
+        return Err(ValidationError::InvalidValue {
+            field: "throttle_limits.period_us".to_string(),
+            message: "must be in [1000, 1000000]".to_string(),
+        });
+    }
+    if limits.memory_high_mb == Some(0) {
+        return Err(ValidationError::InvalidValue {
+            field: "throttle_limits.memory_high_mb".to_string(),
+            message: "must be > 0 when set".to_string(),
+        });
+    }
 
     Ok(())
 }
@@ -551,6 +950,159 @@ mod tests {
         );
     }
 
+    #[test]
+    fn priors_bad_net_recency() {
+        let mut priors = crate::priors::Priors::default();
+        let nr = priors.net_recency.as_mut().expect("default net_recency");
+        nr.abandoned.as_mut().unwrap().alpha.pop();
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "net_recency.abandoned.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.net_recency.as_mut().unwrap().thresholds_secs = vec![3600, 60];
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "net_recency.thresholds_secs")
+        );
+    }
+
+    #[test]
+    fn priors_bad_io_rate() {
+        let mut priors = crate::priors::Priors::default();
+        let ir = priors.io_rate.as_mut().expect("default io_rate");
+        ir.useful.as_mut().unwrap().alpha.push(1.0);
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "io_rate.useful.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.io_rate.as_mut().unwrap().thresholds_kib_per_sec = vec![];
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "io_rate.thresholds_kib_per_sec")
+        );
+    }
+
+    #[test]
+    fn priors_bad_fd_leak() {
+        let mut priors = crate::priors::Priors::default();
+        let fl = priors.fd_leak.as_mut().expect("default fd_leak");
+        fl.abandoned.as_mut().unwrap().alpha[0] = 0.0;
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "fd_leak.abandoned.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.fd_leak.as_mut().unwrap().confidence_thresholds = vec![0.6, 1.5];
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "fd_leak.confidence_thresholds")
+        );
+    }
+
+    #[test]
+    fn priors_bad_session_attachment() {
+        let mut priors = crate::priors::Priors::default();
+        let sa = priors
+            .session_attachment
+            .as_mut()
+            .expect("default session_attachment");
+        sa.useful.as_mut().unwrap().alpha.push(1.0);
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "session_attachment.useful.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.session_attachment.as_mut().unwrap().active_idle_secs = 0;
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "session_attachment.active_idle_secs")
+        );
+    }
+
+    #[test]
+    fn priors_bad_swap_pressure() {
+        let mut priors = crate::priors::Priors::default();
+        let sp = priors
+            .swap_pressure
+            .as_mut()
+            .expect("default swap_pressure");
+        sp.zombie.as_mut().unwrap().alpha.pop();
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "swap_pressure.zombie.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.swap_pressure.as_mut().unwrap().min_psi_some_avg10 = 150.0;
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "swap_pressure.min_psi_some_avg10")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.swap_pressure.as_mut().unwrap().thresholds_mb = vec![512.0, 64.0];
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "swap_pressure.thresholds_mb")
+        );
+    }
+
+    #[test]
+    fn priors_bad_behavior_change() {
+        let mut priors = crate::priors::Priors::default();
+        let bc = priors
+            .behavior_change
+            .as_mut()
+            .expect("default behavior_change");
+        bc.useful.as_mut().unwrap().alpha[0] = 0.0;
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "behavior_change.useful.alpha")
+        );
+
+        let mut priors = crate::priors::Priors::default();
+        priors.behavior_change.as_mut().unwrap().thresholds_sigma = vec![6.0, 3.0];
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "behavior_change.thresholds_sigma")
+        );
+    }
+
+    #[test]
+    fn priors_bad_evidence_weights() {
+        let mut priors = crate::priors::Priors::default();
+        let mut weights = crate::priors::EvidenceWeights::default();
+        weights.weights.insert("tty".into(), 0.0);
+        weights.weights.insert("orphan".into(), 1.5);
+        priors.evidence_weights = Some(weights.clone());
+        assert!(validate_priors(&priors).is_ok());
+
+        weights.weights.insert("orphan".into(), 2.5);
+        priors.evidence_weights = Some(weights.clone());
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "evidence_weights.weights.orphan")
+        );
+
+        weights.weights.insert("orphan".into(), f64::NAN);
+        priors.evidence_weights = Some(weights.clone());
+        assert!(validate_priors(&priors).is_err());
+
+        weights.weights.remove("orphan");
+        weights.weights.insert("prior".into(), 0.5);
+        priors.evidence_weights = Some(weights);
+        let err = validate_priors(&priors).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "evidence_weights.weights.prior")
+        );
+    }
+
     #[test]
     fn priors_wrong_schema_version() {
         let priors = crate::priors::Priors {
@@ -617,6 +1169,112 @@ mod tests {
         assert!(validate_policy(&policy).is_err());
     }
 
+    #[test]
+    fn policy_guardrails_zero_session_idle() {
+        let mut policy = crate::policy::Policy::default();
+        policy.guardrails.attached_session_idle_seconds = 0;
+        assert!(validate_policy(&policy).is_err());
+        policy.guardrails.protect_attached_sessions = false;
+        assert!(validate_policy(&policy).is_ok());
+    }
+
+    #[test]
+    fn policy_health_gate_bad_psi() {
+        let mut policy = crate::policy::Policy::default();
+        policy.apply_health_gate.max_psi_avg10 = 150.0;
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field.contains("apply_health_gate"))
+        );
+    }
+
+    #[test]
+    fn policy_health_gate_disabled_skips_checks() {
+        let mut policy = crate::policy::Policy::default();
+        policy.apply_health_gate.enabled = false;
+        policy.apply_health_gate.max_load_per_core = 0.0;
+        assert!(validate_policy(&policy).is_ok());
+    }
+
+    #[test]
+    fn policy_throttle_limits_bad_period() {
+        let mut policy = crate::policy::Policy::default();
+        policy.throttle_limits.period_us = 500;
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "throttle_limits.period_us")
+        );
+    }
+
+    #[test]
+    fn policy_throttle_limits_zero_memory_high() {
+        let mut policy = crate::policy::Policy::default();
+        policy.throttle_limits.memory_high_mb = Some(0);
+        assert!(validate_policy(&policy).is_err());
+        policy.throttle_limits.memory_high_mb = Some(512);
+        assert!(validate_policy(&policy).is_ok());
+    }
+
+    #[test]
+    fn policy_cascade_zero_tree_size() {
+        let mut policy = crate::policy::Policy::default();
+        policy.cascade.max_tree_size = 0;
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "cascade.max_tree_size")
+        );
+    }
+
+    #[test]
+    fn policy_thread_hog_bad_share() {
+        let mut policy = crate::policy::Policy::default();
+        policy.thread_hog.min_dominant_share = 1.5;
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "thread_hog.min_dominant_share")
+        );
+    }
+
+    #[test]
+    fn policy_schedule_bad_cron() {
+        let mut policy = crate::policy::Policy::default();
+        policy
+            .schedule
+            .maintenance_windows
+            .push(crate::schedule::MaintenanceWindow {
+                name: "nightly".to_string(),
+                cron: "0 25 * * *".to_string(),
+                duration_minutes: 60,
+            });
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "schedule")
+        );
+    }
+
+    #[test]
+    fn policy_custom_rule_unknown_field() {
+        let mut policy = crate::policy::Policy::default();
+        policy.custom_rules.push(crate::rules::CustomRule {
+            name: "prod-db".to_string(),
+            expr: "usr == 'postgres'".to_string(),
+            effect: crate::rules::RuleEffect::Block,
+            message: None,
+        });
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(err.to_string().contains("unknown field 'usr'"));
+    }
+
+    #[test]
+    fn policy_negative_blast_radius_score() {
+        let mut policy = crate::policy::Policy::default();
+        policy.data_loss_gates.max_blast_radius_score = Some(-1.0);
+        let err = validate_policy(&policy).unwrap_err();
+        assert!(
+            matches!(err, ValidationError::InvalidValue { ref field, .. } if field == "data_loss_gates.max_blast_radius_score")
+        );
+    }
+
     #[test]
     fn policy_wrong_schema_version() {
         let policy = crate::policy::Policy {
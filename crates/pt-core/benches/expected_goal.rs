@@ -71,8 +71,11 @@ fn test_priors() -> Priors {
         tty_beta: BetaParams::new(1.0, 1.0),
         net_beta: BetaParams::new(1.0, 1.0),
         io_active_beta: None,
+        gpu_beta: None,
+        gpu_util_beta: None,
         hazard_gamma: None,
         competing_hazards: None,
+        runtime_surprise_gamma: None,
     };
 
     Priors {
@@ -122,6 +125,13 @@ fn test_priors() -> Priors {
         robust_bayes: None,
         error_rate: None,
         bocpd: None,
+        evidence_weights: None,
+        net_recency: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -0,0 +1,84 @@
+//! Criterion benchmarks comparing blocking and async fleet SSH scanning.
+//!
+//! A stub `ssh` script stands in for the network: each host "scans" in 20ms,
+//! except every tenth host, which straggles for 200ms. The blocking scanner
+//! runs fixed batches of `--parallel` threads and waits for each batch's
+//! slowest host; the async scanner keeps a sliding window of `--parallel`
+//! sessions, so stragglers overlap with the rest of the fleet. The stub's
+//! output is not a real scan, so every host reports a parse failure; only
+//! wall time is measured (`continue_on_error` keeps the fleet going).
+//!
+//! Run with `cargo bench -p pt-core --features async --bench fleet_scan_async`.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use pt_core::fleet::ssh_scan::{ssh_scan_fleet, ssh_scan_fleet_async, SshScanConfig};
+use pt_core::runtime::{AsyncRuntime, CancellationToken};
+
+// ── Helpers ──────────────────────────────────────────────────────────
+
+/// Write a fake ssh client whose latency depends on the host index.
+fn write_stub_ssh(dir: &tempfile::TempDir) -> PathBuf {
+    let path = dir.path().join("ssh");
+    let script = r#"#!/bin/sh
+# The host is the second-to-last argument; the remote command is last.
+for arg in "$@"; do host="$prev"; prev="$arg"; done
+case "$host" in
+  *0) sleep 0.2 ;;
+  *) sleep 0.02 ;;
+esac
+echo '{}'
+"#;
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn make_hosts(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("host-{}", i)).collect()
+}
+
+fn make_config(ssh: &std::path::Path, parallel: usize) -> SshScanConfig {
+    SshScanConfig {
+        ssh_binary: ssh.display().to_string(),
+        parallel,
+        command_timeout: 10,
+        ..SshScanConfig::default()
+    }
+}
+
+// ── Benchmarks ───────────────────────────────────────────────────────
+
+fn bench_fleet_scan(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let ssh = write_stub_ssh(&dir);
+    let hosts = make_hosts(200);
+    let runtime = AsyncRuntime::new().unwrap();
+
+    let mut group = c.benchmark_group("fleet_scan");
+    group.sample_size(10);
+
+    for parallel in [50usize, 100] {
+        let config = make_config(&ssh, parallel);
+
+        group.bench_with_input(
+            BenchmarkId::new("blocking", parallel),
+            &config,
+            |b, config| b.iter(|| black_box(ssh_scan_fleet(&hosts, config))),
+        );
+
+        group.bench_with_input(BenchmarkId::new("async", parallel), &config, |b, config| {
+            b.iter(|| {
+                let token = CancellationToken::new();
+                black_box(runtime.block_on(ssh_scan_fleet_async(&hosts, config, &token)))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_fleet_scan);
+criterion_main!(benches);
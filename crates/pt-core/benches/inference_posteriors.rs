@@ -17,6 +17,14 @@ fn example_evidence_idle_orphan() -> Evidence {
         io_active: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -30,6 +38,14 @@ fn example_evidence_active_tty_net() -> Evidence {
         io_active: Some(true),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -27,6 +27,8 @@ fn simple_candidate(pid: i32) -> ProcessCandidate {
         process_state: None,
         wchan: None,
         critical_files: vec![],
+        privileges: None,
+        blast_radius: None,
     }
 }
 
@@ -2,18 +2,30 @@
 //!
 //! Implements CPU throttling via cgroup v2 cpu.max with:
 //! - Automatic cgroup path discovery for target process
+//! - Isolation into a dedicated `pt-throttle-<pid>` cgroup so siblings are
+//!   not throttled along with the target
+//! - Optional soft memory limit via memory.high
 //! - Reversal metadata capture for undo operations
-//! - Verification via read-back of cpu.max
+//! - Verification via read-back of cpu.max and memory.high
 //! - Fallback to cgroup v1 (cpu.cfs_quota_us/cpu.cfs_period_us)
+//! - Fallback to `systemctl set-property --runtime` when the process is the
+//!   sole occupant of a systemd unit whose cgroup files are not writable
 //! - Graceful degradation to renice if cgroup unavailable
 
 use super::executor::{ActionError, ActionRunner};
+#[cfg(target_os = "linux")]
+use super::freeze::cgroup_collateral;
 use crate::collect::cgroup::{collect_cgroup_details, CgroupVersion, CpuLimitSource};
+#[cfg(target_os = "linux")]
+use crate::collect::systemd::user_manager_uid;
+use crate::config::policy::ThrottleLimits;
 use crate::decision::Action;
 use crate::plan::PlanAction;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::process::Command;
 use tracing::{debug, info, warn};
 
 /// Default CPU throttle fraction (25% of current allocation or one core).
@@ -25,6 +37,9 @@ pub const DEFAULT_PERIOD_US: u64 = 100_000;
 /// Minimum quota in microseconds (1ms - prevent starvation).
 pub const MIN_QUOTA_US: i64 = 1_000;
 
+/// Name prefix of the per-process cgroups created for isolation.
+pub const ISOLATION_CGROUP_PREFIX: &str = "pt-throttle-";
+
 /// CPU throttle action configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuThrottleConfig {
@@ -40,6 +55,18 @@ pub struct CpuThrottleConfig {
 
     /// Whether to record previous settings for reversal.
     pub capture_reversal: bool,
+
+    /// Soft memory limit (memory.high) in bytes; None leaves memory untouched.
+    #[serde(default)]
+    pub memory_high_bytes: Option<u64>,
+
+    /// Whether to move the process into its own child cgroup before limiting (v2).
+    #[serde(default)]
+    pub isolate: bool,
+
+    /// Whether to fall back to systemd unit properties when cgroup writes fail.
+    #[serde(default)]
+    pub systemd_fallback: bool,
 }
 
 impl Default for CpuThrottleConfig {
@@ -49,6 +76,22 @@ impl Default for CpuThrottleConfig {
             period_us: DEFAULT_PERIOD_US,
             fallback_to_v1: true,
             capture_reversal: true,
+            memory_high_bytes: None,
+            isolate: true,
+            systemd_fallback: true,
+        }
+    }
+}
+
+impl From<&ThrottleLimits> for CpuThrottleConfig {
+    fn from(limits: &ThrottleLimits) -> Self {
+        Self {
+            target_fraction: limits.cpu_fraction,
+            period_us: limits.period_us,
+            memory_high_bytes: limits.memory_high_mb.map(|mb| mb * 1024 * 1024),
+            isolate: limits.isolate,
+            systemd_fallback: limits.systemd_fallback,
+            ..Default::default()
         }
     }
 }
@@ -67,6 +110,21 @@ impl CpuThrottleConfig {
         let quota = (self.target_fraction * self.period_us as f64) as i64;
         quota.max(MIN_QUOTA_US)
     }
+
+    /// Unit properties for `systemctl set-property`.
+    ///
+    /// systemd takes the CPU quota as a whole percentage of one core.
+    pub fn systemd_properties(&self) -> Vec<String> {
+        let percent = (self.quota_us() as f64 * 100.0 / self.period_us as f64).round() as u64;
+        let mut properties = vec![format!("CPUQuota={}%", percent.max(1))];
+        if self.period_us != DEFAULT_PERIOD_US {
+            properties.push(format!("CPUQuotaPeriodSec={}us", self.period_us));
+        }
+        if let Some(bytes) = self.memory_high_bytes {
+            properties.push(format!("MemoryHigh={}", bytes));
+        }
+        properties
+    }
 }
 
 /// Captured state for reversal of throttle action.
@@ -139,34 +197,106 @@ impl CpuThrottleActionRunner {
         let cgroup_details = collect_cgroup_details(pid)
             .ok_or_else(|| ActionError::Failed(format!("failed to read cgroup for pid {}", pid)))?;
 
+        let mut result = Err(ActionError::Failed(format!(
+            "no writable cgroup CPU controller found for pid {}",
+            pid
+        )));
+
         // Try cgroup v2 first
         if cgroup_details.version == CgroupVersion::V2
             || cgroup_details.version == CgroupVersion::Hybrid
         {
             if let Some(ref unified_path) = cgroup_details.unified_path {
-                let result = self.apply_throttle_v2(pid, unified_path);
+                let target_path = if self.config.isolate {
+                    self.isolate_v2(pid, unified_path).unwrap_or_else(|e| {
+                        warn!(pid, error = %e, "cgroup isolation failed, throttling in place");
+                        unified_path.clone()
+                    })
+                } else {
+                    unified_path.clone()
+                };
+                result = self.apply_throttle_v2(pid, &target_path);
                 if result.is_ok() {
                     return result;
                 }
-                // Fall through to v1 if v2 failed and fallback enabled
-                if !self.config.fallback_to_v1 {
-                    return result;
-                }
-                warn!(pid, "cgroup v2 throttle failed, trying v1 fallback");
+                warn!(pid, "cgroup v2 throttle failed, trying fallbacks");
             }
         }
 
         // Try cgroup v1 if available
         if self.config.fallback_to_v1 {
             if let Some(cpu_path) = cgroup_details.v1_paths.get("cpu") {
-                return self.apply_throttle_v1(pid, cpu_path);
+                result = self.apply_throttle_v1(pid, cpu_path);
+                if result.is_ok() {
+                    return result;
+                }
             }
         }
 
-        Err(ActionError::Failed(format!(
-            "no writable cgroup CPU controller found for pid {}",
-            pid
-        )))
+        // Ask systemd to apply the limits to the owning unit
+        if self.config.systemd_fallback {
+            if let (Some(unit), Some(unified_path)) = (
+                cgroup_details.systemd_unit.as_deref(),
+                cgroup_details.unified_path.as_deref(),
+            ) {
+                match self.apply_throttle_systemd(pid, unit, unified_path) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => warn!(pid, unit, error = %e, "systemd throttle fallback failed"),
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Move `pid` into its own `pt-throttle-<pid>` cgroup next to its current
+    /// one and return the new cgroup path.
+    ///
+    /// The parent already delegates the CPU controller to the process's
+    /// current cgroup, so a new sibling inherits it. Nothing else is moved:
+    /// children stay where they are and siblings keep their limits.
+    #[cfg(target_os = "linux")]
+    fn isolate_v2(&self, pid: u32, unified_path: &str) -> Result<String, ActionError> {
+        let name = format!("{}{}", ISOLATION_CGROUP_PREFIX, pid);
+        if unified_path.rsplit('/').next() == Some(name.as_str()) {
+            return Ok(unified_path.to_string());
+        }
+
+        let Some((parent, _)) = unified_path
+            .rsplit_once('/')
+            .filter(|(_, leaf)| !leaf.is_empty())
+        else {
+            return Err(ActionError::Failed(
+                "refusing to isolate a process in the root cgroup".to_string(),
+            ));
+        };
+        let isolated_path = format!("{}/{}", parent, name);
+        let cgroup_dir = format!("/sys/fs/cgroup{}", isolated_path);
+
+        match fs::create_dir(&cgroup_dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(map_write_error(e, "create isolation cgroup")),
+        }
+
+        let needs_memory = self.config.memory_high_bytes.is_some();
+        if !Path::new(&format!("{}/cpu.max", cgroup_dir)).exists()
+            || (needs_memory && !Path::new(&format!("{}/memory.high", cgroup_dir)).exists())
+        {
+            let _ = fs::remove_dir(&cgroup_dir);
+            return Err(ActionError::Failed(format!(
+                "controllers not delegated to {}",
+                isolated_path
+            )));
+        }
+
+        if let Err(e) = fs::write(format!("{}/cgroup.procs", cgroup_dir), pid.to_string()) {
+            let _ = fs::remove_dir(&cgroup_dir);
+            return Err(map_write_error(e, "move process into isolation cgroup"));
+        }
+
+        info!(pid, from = unified_path, to = %isolated_path, "isolated process for throttle");
+        Ok(isolated_path)
     }
 
     /// Apply CPU throttle using cgroup v2 cpu.max.
@@ -204,17 +334,81 @@ impl CpuThrottleActionRunner {
             }
         })?;
 
+        if let Some(bytes) = self.config.memory_high_bytes {
+            let memory_high_path = format!("{}{}/memory.high", cgroup_root, unified_path);
+            fs::write(&memory_high_path, bytes.to_string())
+                .map_err(|e| map_write_error(e, "write memory.high"))?;
+        }
+
         info!(
             pid,
             cgroup = unified_path,
             quota_us = quota,
             period_us = period,
+            memory_high = ?self.config.memory_high_bytes,
             "CPU throttle applied via cgroup v2"
         );
 
         Ok(())
     }
 
+    /// Apply limits as runtime properties of the owning systemd unit.
+    ///
+    /// Only used when the unit's cgroup is the process's own cgroup and holds
+    /// nothing but the process and its descendants; otherwise the unit limit
+    /// would throttle unrelated work.
+    #[cfg(target_os = "linux")]
+    fn apply_throttle_systemd(
+        &self,
+        pid: u32,
+        unit: &str,
+        unified_path: &str,
+    ) -> Result<(), ActionError> {
+        if unified_path.rsplit('/').next() != Some(unit) {
+            return Err(ActionError::Failed(format!(
+                "process is not directly in {}",
+                unit
+            )));
+        }
+        let procs_path = format!("/sys/fs/cgroup{}/cgroup.procs", unified_path);
+        let collateral = cgroup_collateral(pid, &procs_path)?;
+        if !collateral.is_empty() {
+            return Err(ActionError::Failed(format!(
+                "{} also holds {} unrelated process(es)",
+                unit,
+                collateral.len()
+            )));
+        }
+
+        let mut cmd = Command::new("systemctl");
+        if let Some(uid) = user_manager_uid(unified_path) {
+            if uid != unsafe { libc::geteuid() } {
+                return Err(ActionError::PermissionDenied);
+            }
+            cmd.arg("--user");
+        }
+        let properties = self.config.systemd_properties();
+        let output = cmd
+            .args(["set-property", "--runtime", unit])
+            .args(&properties)
+            .output()
+            .map_err(|e| ActionError::Failed(format!("failed to run systemctl: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("Access denied") || stderr.contains("authentication required") {
+                return Err(ActionError::PermissionDenied);
+            }
+            return Err(ActionError::Failed(format!(
+                "systemctl set-property failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        info!(pid, unit, properties = ?properties, "throttle applied via systemd unit properties");
+        Ok(())
+    }
+
     /// Apply CPU throttle using cgroup v1 cpu.cfs_quota_us.
     #[cfg(target_os = "linux")]
     fn apply_throttle_v1(&self, pid: u32, cpu_path: &str) -> Result<(), ActionError> {
@@ -294,7 +488,7 @@ impl CpuThrottleActionRunner {
                     let actual_quota = limits.quota_us.unwrap_or(-1);
                     let actual_period = limits.period_us.unwrap_or(0);
 
-                    if actual_quota != expected_quota {
+                    if !quota_matches(actual_quota, expected_quota, expected_period) {
                         return Err(ActionError::Failed(format!(
                             "quota mismatch: expected {}, got {}",
                             expected_quota, actual_quota
@@ -306,6 +500,18 @@ impl CpuThrottleActionRunner {
                             expected_period, actual_period
                         )));
                     }
+                    if let Some(expected_high) = self.config.memory_high_bytes {
+                        let actual_high = cgroup_details
+                            .memory_limits
+                            .as_ref()
+                            .and_then(|m| m.high_bytes);
+                        if !memory_high_matches(actual_high, expected_high) {
+                            return Err(ActionError::Failed(format!(
+                                "memory.high mismatch: expected {}, got {:?}",
+                                expected_high, actual_high
+                            )));
+                        }
+                    }
                     debug!(pid, "throttle verification passed (v2)");
                     return Ok(());
                 }
@@ -378,6 +584,32 @@ impl CpuThrottleActionRunner {
         &self,
         metadata: &ThrottleReversalMetadata,
     ) -> Result<(), ActionError> {
+        // An isolated process is restored by moving it back; its original
+        // cgroup was never modified.
+        if let Some(current) =
+            collect_cgroup_details(metadata.pid).and_then(|details| details.unified_path)
+        {
+            let isolated = format!("{}{}", ISOLATION_CGROUP_PREFIX, metadata.pid);
+            if current != metadata.cgroup_path
+                && current.rsplit('/').next() == Some(isolated.as_str())
+            {
+                fs::write(
+                    format!("/sys/fs/cgroup{}/cgroup.procs", metadata.cgroup_path),
+                    metadata.pid.to_string(),
+                )
+                .map_err(|e| map_write_error(e, "move process back to original cgroup"))?;
+                if let Err(e) = fs::remove_dir(format!("/sys/fs/cgroup{}", current)) {
+                    warn!(path = %current, error = %e, "failed to remove isolation cgroup");
+                }
+                info!(
+                    pid = metadata.pid,
+                    cgroup = %metadata.cgroup_path,
+                    "moved process out of isolation cgroup"
+                );
+                return Ok(());
+            }
+        }
+
         match metadata.source {
             CpuLimitSource::CgroupV2CpuMax => {
                 let cpu_max_path = format!("/sys/fs/cgroup{}/cpu.max", metadata.cgroup_path);
@@ -504,6 +736,32 @@ impl ActionRunner for CpuThrottleActionRunner {
     }
 }
 
+/// Whether a read-back quota matches the requested one.
+///
+/// systemd rounds the quota to a whole percent of the period, so a 1%
+/// difference is accepted.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn quota_matches(actual: i64, expected: i64, period_us: u64) -> bool {
+    (actual - expected).abs() <= (period_us / 100) as i64
+}
+
+/// Whether a read-back memory.high matches the requested bytes (the kernel
+/// rounds down to whole pages).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn memory_high_matches(actual: Option<u64>, expected: u64) -> bool {
+    const PAGE_ROUNDING: u64 = 64 * 1024;
+    actual.is_some_and(|actual| actual <= expected && expected - actual < PAGE_ROUNDING)
+}
+
+#[cfg(target_os = "linux")]
+fn map_write_error(e: std::io::Error, what: &str) -> ActionError {
+    if e.kind() == std::io::ErrorKind::PermissionDenied {
+        ActionError::PermissionDenied
+    } else {
+        ActionError::Failed(format!("failed to {}: {}", what, e))
+    }
+}
+
 /// Check if cgroup CPU throttle is available for a process.
 #[cfg(target_os = "linux")]
 pub fn can_throttle_process(pid: u32) -> bool {
@@ -551,6 +809,61 @@ mod tests {
         assert_eq!(config.period_us, DEFAULT_PERIOD_US);
         assert!(config.fallback_to_v1);
         assert!(config.capture_reversal);
+        assert!(config.isolate);
+        assert!(config.memory_high_bytes.is_none());
+    }
+
+    #[test]
+    fn config_from_policy_limits() {
+        let limits = ThrottleLimits {
+            cpu_fraction: 0.5,
+            memory_high_mb: Some(512),
+            isolate: false,
+            ..ThrottleLimits::default()
+        };
+        let config = CpuThrottleConfig::from(&limits);
+        assert_eq!(config.quota_us(), 50_000);
+        assert_eq!(config.memory_high_bytes, Some(512 * 1024 * 1024));
+        assert!(!config.isolate);
+        assert!(config.systemd_fallback);
+    }
+
+    #[test]
+    fn systemd_properties_format() {
+        let config = CpuThrottleConfig {
+            memory_high_bytes: Some(1 << 30),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.systemd_properties(),
+            vec![
+                "CPUQuota=25%".to_string(),
+                "MemoryHigh=1073741824".to_string()
+            ]
+        );
+
+        let config = CpuThrottleConfig {
+            target_fraction: 0.001,
+            period_us: 50_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.systemd_properties(),
+            vec![
+                "CPUQuota=2%".to_string(),
+                "CPUQuotaPeriodSec=50000us".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn verification_tolerances() {
+        assert!(quota_matches(25_000, 25_000, 100_000));
+        assert!(quota_matches(25_500, 25_000, 100_000));
+        assert!(!quota_matches(50_000, 25_000, 100_000));
+        assert!(memory_high_matches(Some(512 << 20), 512 << 20));
+        assert!(!memory_high_matches(None, 512 << 20));
+        assert!(!memory_high_matches(Some(1 << 30), 512 << 20));
     }
 
     #[test]
@@ -0,0 +1,255 @@
+//! Command-based termination of plan actions.
+//!
+//! Kills that carry a [`CommandAction`] (see [`crate::plan::command`]) first
+//! run the signature's shutdown command, such as `gradle --stop` or
+//! `sccache --stop-server`, and wait for the target to exit. The command is
+//! run as an argv with no shell, a minimal environment and a hard timeout.
+//! When it fails, times out, or leaves the target running, the kill falls
+//! back to the signal runner (SIGTERM → SIGKILL).
+//!
+//! The command runs with the caller's identity, so it is only attempted for
+//! targets owned by the caller; anything else goes straight to signals.
+
+use super::executor::{ActionError, ActionRunner};
+use super::signal::{SignalActionRunner, SignalConfig};
+use crate::collect::tool_runner::{ToolConfig, ToolError, ToolRunner, ToolSpec};
+use crate::plan::{CommandAction, PlanAction};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Environment passed through to the command, so tools find their per-user
+/// state (daemon registries, output bases, sockets).
+const PASSTHROUGH_ENV: &[&str] = &["HOME", "USER", "LOGNAME", "XDG_RUNTIME_DIR", "TMPDIR"];
+
+/// Command action runner configuration.
+#[derive(Debug, Clone)]
+pub struct CommandActionConfig {
+    /// Time allowed for the target to exit once the command returned.
+    pub exit_timeout_ms: u64,
+    /// Polling interval while waiting for the target to exit.
+    pub poll_interval_ms: u64,
+    /// Signal the target when the command does not stop it.
+    pub fallback_to_signal: bool,
+}
+
+impl Default for CommandActionConfig {
+    fn default() -> Self {
+        Self {
+            exit_timeout_ms: 10_000,
+            poll_interval_ms: 200,
+            fallback_to_signal: true,
+        }
+    }
+}
+
+/// Runs signature shutdown commands for plan actions with a `command_action`.
+#[derive(Debug)]
+pub struct CommandActionRunner {
+    config: CommandActionConfig,
+    signal: SignalActionRunner,
+}
+
+impl CommandActionRunner {
+    pub fn new(config: CommandActionConfig) -> Self {
+        let signal = SignalActionRunner::new(SignalConfig {
+            poll_interval_ms: config.poll_interval_ms,
+            verify_timeout_ms: config.exit_timeout_ms,
+            ..SignalConfig::default()
+        });
+        Self { config, signal }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(CommandActionConfig::default())
+    }
+
+    fn command_action(action: &PlanAction) -> Result<&CommandAction, ActionError> {
+        action.command_action.as_ref().ok_or_else(|| {
+            ActionError::Failed(format!(
+                "action {} has no shutdown command to run",
+                action.action_id
+            ))
+        })
+    }
+
+    /// Run the command and wait for the target to exit.
+    fn run_command(&self, action: &PlanAction, command: &CommandAction) -> Result<(), ActionError> {
+        if action.target.uid != unsafe { libc::geteuid() } {
+            return Err(ActionError::Failed(format!(
+                "{} runs as uid {}; not running its shutdown command as another user",
+                action.target.pid.0, action.target.uid
+            )));
+        }
+        let (program, args) = command
+            .argv
+            .split_first()
+            .ok_or_else(|| ActionError::Failed("empty shutdown command".to_string()))?;
+        debug!(
+            pid = action.target.pid.0,
+            signature = %command.signature,
+            "command action: {}",
+            command.display_command()
+        );
+
+        let timeout = Duration::from_millis(command.timeout_ms);
+        let runner = ToolRunner::new(ToolConfig {
+            default_timeout: timeout,
+            budget_ms: command.timeout_ms,
+            use_nice: false,
+            #[cfg(target_os = "linux")]
+            use_ionice: false,
+            ..ToolConfig::default()
+        });
+        let mut spec = ToolSpec::new(program.clone(), args.to_vec()).with_timeout(timeout);
+        if let Some(cwd) = &command.cwd {
+            spec = spec.with_cwd(cwd);
+        }
+        for key in PASSTHROUGH_ENV {
+            if let Ok(value) = std::env::var(key) {
+                spec = spec.with_env(*key, value);
+            }
+        }
+        let output = runner.run(&spec).map_err(|e| match e {
+            ToolError::Timeout(_) => ActionError::Timeout,
+            other => ActionError::Failed(format!("{}: {}", program, other)),
+        })?;
+        if output.timed_out {
+            return Err(ActionError::Timeout);
+        }
+        if !output.success() {
+            return Err(ActionError::Failed(format!(
+                "{} failed: {}",
+                command.display_command(),
+                output.stderr_str().trim()
+            )));
+        }
+        // The command returning is not the target exiting.
+        self.signal.verify(action)
+    }
+}
+
+impl ActionRunner for CommandActionRunner {
+    fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let command = Self::command_action(action)?;
+        match self.run_command(action, command) {
+            Ok(()) => Ok(()),
+            Err(e) if self.config.fallback_to_signal => {
+                warn!(
+                    pid = action.target.pid.0,
+                    signature = %command.signature,
+                    error = %e,
+                    "shutdown command did not stop the process; falling back to signals"
+                );
+                self.signal.execute(action)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn verify(&self, action: &PlanAction) -> Result<(), ActionError> {
+        self.signal.verify(action)
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::decision::Action;
+    use crate::plan::{ActionConfidence, ActionRationale, ActionRouting, ActionTimeouts};
+    use pt_common::{IdentityQuality, ProcessId, ProcessIdentity, StartId};
+    use std::process::{Child, Command};
+
+    fn spawn_sleeper() -> Child {
+        Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep")
+    }
+
+    fn kill_action(pid: u32, argv: &[&str]) -> PlanAction {
+        PlanAction {
+            action_id: format!("kill-{}", pid),
+            target: ProcessIdentity {
+                pid: ProcessId(pid),
+                start_id: StartId(format!("boot:1:{}", pid)),
+                uid: unsafe { libc::geteuid() },
+                pgid: None,
+                sid: None,
+                quality: IdentityQuality::Full,
+            },
+            action: Action::Kill,
+            order: 0,
+            stage: 0,
+            timeouts: ActionTimeouts::default(),
+            pre_checks: vec![],
+            rationale: ActionRationale {
+                expected_loss: None,
+                expected_recovery: None,
+                expected_recovery_stddev: None,
+                posterior_odds_abandoned_vs_useful: None,
+                sprt_boundary: None,
+                posterior: None,
+                memory_mb: None,
+                has_known_signature: Some(true),
+                category: None,
+            },
+            on_success: vec![],
+            on_failure: vec![],
+            blocked: false,
+            routing: ActionRouting::GracefulCommand,
+            confidence: ActionConfidence::Normal,
+            original_zombie_target: None,
+            d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: Some(CommandAction {
+                signature: "test".to_string(),
+                argv: argv.iter().map(|s| s.to_string()).collect(),
+                cwd: None,
+                timeout_ms: 5_000,
+            }),
+        }
+    }
+
+    fn runner(fallback_to_signal: bool) -> CommandActionRunner {
+        CommandActionRunner::new(CommandActionConfig {
+            exit_timeout_ms: 1_000,
+            poll_interval_ms: 20,
+            fallback_to_signal,
+        })
+    }
+
+    #[test]
+    fn command_stops_target() {
+        let mut child = spawn_sleeper();
+        let pid = child.id().to_string();
+        let action = kill_action(child.id(), &["kill", "-TERM", &pid]);
+        let result = runner(false).execute(&action);
+        let _ = child.wait();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn failed_command_falls_back_to_signals() {
+        let mut child = spawn_sleeper();
+        let action = kill_action(child.id(), &["false"]);
+        assert!(runner(false).execute(&action).is_err());
+
+        let result = runner(true).execute(&action);
+        let _ = child.wait();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn command_that_leaves_target_running_falls_back() {
+        let mut child = spawn_sleeper();
+        let action = kill_action(child.id(), &["true"]);
+        assert!(matches!(
+            runner(false).execute(&action),
+            Err(ActionError::Timeout)
+        ));
+        let result = runner(true).execute(&action);
+        let _ = child.wait();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+}
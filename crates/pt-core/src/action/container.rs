@@ -0,0 +1,538 @@
+//! Container-aware action execution.
+//!
+//! When a candidate is PID 1 of a container's PID namespace, signalling it
+//! is the wrong tool: the runtime's restart policy brings the container
+//! straight back, and the runtime's own view of the container goes stale.
+//! Kill and pause actions on such a process are carried out through the
+//! runtime instead:
+//!
+//! - kill → `docker stop` / `podman stop` (graceful, with the policy's stop
+//!   timeout) or `ctr task kill` followed by SIGKILL after the timeout
+//! - pause/resume → `pause`/`unpause` (`ctr task pause`/`resume`)
+//!
+//! Which runtimes pt may drive is a policy decision
+//! (`container_actions.allowed_runtimes`). Container inits under any other
+//! runtime, and containers managed by Kubernetes (where the kubelet would
+//! recreate them), are refused rather than signalled.
+
+use super::executor::{ActionError, ActionRunner};
+use crate::collect::tool_runner::{ToolConfig, ToolError, ToolRunner};
+use crate::collect::ContainerRuntime;
+use crate::config::policy::{ContainerActions, ContainerRuntimeKind};
+use crate::decision::Action;
+use crate::plan::PlanAction;
+use crate::supervision::ContainerSupervisionAnalyzer;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Container action runner configuration.
+#[derive(Debug, Clone)]
+pub struct ContainerActionConfig {
+    /// Runtimes the runner may stop or pause containers through.
+    pub allowed_runtimes: Vec<ContainerRuntimeKind>,
+    /// Grace period the runtime gives the container before killing it.
+    pub stop_timeout_secs: u32,
+    /// Extra time allowed for a runtime command beyond the stop timeout.
+    pub command_grace_ms: u64,
+    /// Maximum time to wait for the container to reach the expected state.
+    pub verify_timeout_ms: u64,
+    /// Polling interval while waiting for a state change.
+    pub poll_interval_ms: u64,
+}
+
+impl Default for ContainerActionConfig {
+    fn default() -> Self {
+        Self::from(&ContainerActions::default())
+    }
+}
+
+impl From<&ContainerActions> for ContainerActionConfig {
+    fn from(policy: &ContainerActions) -> Self {
+        Self {
+            allowed_runtimes: policy.allowed_runtimes.clone(),
+            stop_timeout_secs: policy.stop_timeout_secs,
+            command_grace_ms: 15_000,
+            verify_timeout_ms: 10_000,
+            poll_interval_ms: 200,
+        }
+    }
+}
+
+/// A container whose init process is the target of an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContainerTarget {
+    /// Host PID of the container's init process.
+    pub pid: u32,
+    pub runtime: ContainerRuntime,
+    pub container_id: String,
+    /// The container belongs to a Kubernetes pod.
+    pub kubernetes: bool,
+}
+
+/// How an action on a container init will be carried out.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerActionPlan {
+    #[serde(flatten)]
+    pub target: ContainerTarget,
+    /// Runtime command that replaces the signal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Whether policy lets pt run the command.
+    pub allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+}
+
+/// Container state as reported by the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerState {
+    Running,
+    Paused,
+    Stopped,
+    Unknown,
+}
+
+/// Parse the `NSpid` line of `/proc/<pid>/status`: the PID in each nested
+/// PID namespace, outermost first.
+pub fn parse_nspid(status: &str) -> Option<Vec<u32>> {
+    let line = status.lines().find(|line| line.starts_with("NSpid:"))?;
+    line["NSpid:".len()..]
+        .split_whitespace()
+        .map(|pid| pid.parse().ok())
+        .collect()
+}
+
+/// Whether the process is PID 1 of a nested PID namespace.
+pub fn is_namespace_init(status: &str) -> bool {
+    matches!(parse_nspid(status), Some(pids) if pids.len() > 1 && pids.last() == Some(&1))
+}
+
+/// Detect whether `pid` is the init process of a runtime-managed container.
+pub fn detect_container_init(pid: u32) -> Option<ContainerTarget> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    if !is_namespace_init(&status) {
+        return None;
+    }
+    let result = ContainerSupervisionAnalyzer::new().analyze(pid).ok()?;
+    if !result.in_container {
+        return None;
+    }
+    Some(ContainerTarget {
+        pid,
+        runtime: result.runtime,
+        container_id: result.container_id?,
+        kubernetes: result.kubernetes.is_some(),
+    })
+}
+
+fn runtime_kind(runtime: ContainerRuntime) -> Option<ContainerRuntimeKind> {
+    match runtime {
+        ContainerRuntime::Docker => Some(ContainerRuntimeKind::Docker),
+        ContainerRuntime::Podman => Some(ContainerRuntimeKind::Podman),
+        ContainerRuntime::Containerd => Some(ContainerRuntimeKind::Containerd),
+        _ => None,
+    }
+}
+
+/// Build the runtime command carrying out `action` on a container.
+pub fn runtime_command(
+    runtime: ContainerRuntime,
+    action: Action,
+    container_id: &str,
+    stop_timeout_secs: u32,
+) -> Result<(String, Vec<String>), ActionError> {
+    let kind = runtime_kind(runtime).ok_or_else(|| {
+        ActionError::Failed(format!("no container actions for {:?} runtime", runtime))
+    })?;
+    let id = container_id.to_string();
+    let args = match (kind, action) {
+        (ContainerRuntimeKind::Containerd, Action::Kill) => {
+            vec!["task".to_string(), "kill".to_string(), id]
+        }
+        (ContainerRuntimeKind::Containerd, Action::Pause) => {
+            vec!["task".to_string(), "pause".to_string(), id]
+        }
+        (ContainerRuntimeKind::Containerd, Action::Resume) => {
+            vec!["task".to_string(), "resume".to_string(), id]
+        }
+        (_, Action::Kill) => vec![
+            "stop".to_string(),
+            "-t".to_string(),
+            stop_timeout_secs.to_string(),
+            id,
+        ],
+        (_, Action::Pause) => vec!["pause".to_string(), id],
+        (_, Action::Resume) => vec!["unpause".to_string(), id],
+        (_, other) => {
+            return Err(ActionError::Failed(format!(
+                "{:?} is not a container action",
+                other
+            )))
+        }
+    };
+    let program = match kind {
+        ContainerRuntimeKind::Docker => "docker",
+        ContainerRuntimeKind::Podman => "podman",
+        ContainerRuntimeKind::Containerd => "ctr",
+    };
+    Ok((program.to_string(), args))
+}
+
+/// Parse `docker inspect -f '{{.State.Status}}'` output.
+pub fn parse_inspect_status(output: &str) -> ContainerState {
+    match output.trim() {
+        "running" | "restarting" => ContainerState::Running,
+        "paused" => ContainerState::Paused,
+        "exited" | "dead" | "created" | "stopped" => ContainerState::Stopped,
+        _ => ContainerState::Unknown,
+    }
+}
+
+/// Parse `ctr task ls` output (`TASK PID STATUS`) for one container.
+///
+/// A container without a task has stopped.
+pub fn parse_ctr_task_status(output: &str, container_id: &str) -> ContainerState {
+    let Some(line) = output
+        .lines()
+        .skip(1)
+        .find(|line| line.split_whitespace().next() == Some(container_id))
+    else {
+        return ContainerState::Stopped;
+    };
+    match line.split_whitespace().nth(2) {
+        Some("RUNNING") => ContainerState::Running,
+        Some("PAUSED") | Some("PAUSING") => ContainerState::Paused,
+        Some("STOPPED") => ContainerState::Stopped,
+        _ => ContainerState::Unknown,
+    }
+}
+
+/// Runs kill/pause/resume of container inits through the container runtime.
+#[derive(Debug)]
+pub struct ContainerActionRunner {
+    config: ContainerActionConfig,
+}
+
+impl ContainerActionRunner {
+    pub fn new(config: ContainerActionConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(ContainerActionConfig::default())
+    }
+
+    /// The container whose init `action` targets, if the action should go
+    /// through a runtime instead of a signal.
+    pub fn target(&self, action: &PlanAction) -> Option<ContainerTarget> {
+        match action.action {
+            Action::Kill | Action::Pause | Action::Resume => {
+                detect_container_init(action.target.pid.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Why policy refuses to act on this container, if it does.
+    pub fn blocked_reason(&self, target: &ContainerTarget) -> Option<String> {
+        if target.kubernetes {
+            return Some(format!(
+                "container {} is managed by Kubernetes; scale or delete the owning workload",
+                short_id(&target.container_id)
+            ));
+        }
+        match runtime_kind(target.runtime) {
+            Some(kind) if self.config.allowed_runtimes.contains(&kind) => None,
+            Some(kind) => Some(format!(
+                "container_actions.allowed_runtimes does not include {}",
+                kind.as_str()
+            )),
+            None => Some(format!(
+                "container runtime {:?} is not supported for container actions",
+                target.runtime
+            )),
+        }
+    }
+
+    /// Describe how `action` on `pid` would be carried out, when `pid` is a
+    /// container init.
+    pub fn plan_for(&self, pid: u32, action: Action) -> Option<ContainerActionPlan> {
+        if !matches!(action, Action::Kill | Action::Pause | Action::Resume) {
+            return None;
+        }
+        let target = detect_container_init(pid)?;
+        Some(self.plan_for_target(target, action))
+    }
+
+    fn plan_for_target(&self, target: ContainerTarget, action: Action) -> ContainerActionPlan {
+        let command = runtime_command(
+            target.runtime,
+            action,
+            &target.container_id,
+            self.config.stop_timeout_secs,
+        )
+        .ok()
+        .map(|(program, args)| format!("{} {}", program, args.join(" ")));
+        let blocked_reason = self.blocked_reason(&target);
+        ContainerActionPlan {
+            target,
+            command,
+            allowed: blocked_reason.is_none(),
+            blocked_reason,
+        }
+    }
+
+    fn run(&self, program: &str, args: &[String], timeout: Duration) -> Result<(), ActionError> {
+        let runner = ToolRunner::new(ToolConfig {
+            default_timeout: timeout,
+            budget_ms: timeout.as_millis() as u64,
+            use_nice: false,
+            use_ionice: false,
+            ..ToolConfig::default()
+        });
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = runner.run_tool(program, &args, None).map_err(|e| match e {
+            ToolError::Timeout(_) => ActionError::Timeout,
+            other => ActionError::Failed(format!("{}: {}", program, other)),
+        })?;
+        if output.timed_out {
+            return Err(ActionError::Timeout);
+        }
+        if output.success() {
+            return Ok(());
+        }
+        let stderr = output.stderr_str();
+        if stderr.to_ascii_lowercase().contains("permission denied") {
+            return Err(ActionError::PermissionDenied);
+        }
+        Err(ActionError::Failed(format!(
+            "{} {} failed: {}",
+            program,
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        )))
+    }
+
+    fn state(&self, target: &ContainerTarget) -> ContainerState {
+        let (program, args): (&str, Vec<&str>) = match target.runtime {
+            ContainerRuntime::Containerd => ("ctr", vec!["task", "ls"]),
+            ContainerRuntime::Podman => (
+                "podman",
+                vec!["inspect", "-f", "{{.State.Status}}", &target.container_id],
+            ),
+            _ => (
+                "docker",
+                vec!["inspect", "-f", "{{.State.Status}}", &target.container_id],
+            ),
+        };
+        let runner = ToolRunner::new(ToolConfig {
+            use_nice: false,
+            use_ionice: false,
+            ..ToolConfig::default()
+        });
+        match runner.run_tool(program, &args, None) {
+            Ok(output) if output.success() => match target.runtime {
+                ContainerRuntime::Containerd => {
+                    parse_ctr_task_status(&output.stdout_str(), &target.container_id)
+                }
+                _ => parse_inspect_status(&output.stdout_str()),
+            },
+            _ => ContainerState::Unknown,
+        }
+    }
+
+    fn wait_for_state(
+        &self,
+        target: &ContainerTarget,
+        expected: ContainerState,
+        timeout: Duration,
+    ) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let stopped = expected == ContainerState::Stopped
+                && !Path::new(&format!("/proc/{}", target.pid)).exists();
+            if stopped || self.state(target) == expected {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(self.config.poll_interval_ms));
+        }
+    }
+}
+
+impl ActionRunner for ContainerActionRunner {
+    fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let target = self.target(action).ok_or_else(|| {
+            ActionError::Failed(format!(
+                "PID {} is not a container init",
+                action.target.pid.0
+            ))
+        })?;
+        if let Some(reason) = self.blocked_reason(&target) {
+            return Err(ActionError::Failed(reason));
+        }
+
+        let (program, args) = runtime_command(
+            target.runtime,
+            action.action,
+            &target.container_id,
+            self.config.stop_timeout_secs,
+        )?;
+        debug!(
+            pid = target.pid,
+            container = %short_id(&target.container_id),
+            "container action: {} {}",
+            program,
+            args.join(" ")
+        );
+
+        let stop_timeout = Duration::from_secs(u64::from(self.config.stop_timeout_secs));
+        let timeout = stop_timeout + Duration::from_millis(self.config.command_grace_ms);
+        self.run(&program, &args, timeout)?;
+
+        // ctr has no graceful stop: escalate once the grace period is up.
+        if target.runtime == ContainerRuntime::Containerd
+            && action.action == Action::Kill
+            && !self.wait_for_state(&target, ContainerState::Stopped, stop_timeout)
+        {
+            let args = [
+                "task".to_string(),
+                "kill".to_string(),
+                "--signal".to_string(),
+                "SIGKILL".to_string(),
+                target.container_id.clone(),
+            ];
+            self.run(&program, &args, timeout)?;
+        }
+        Ok(())
+    }
+
+    fn verify(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let expected = match action.action {
+            Action::Kill => ContainerState::Stopped,
+            Action::Pause => ContainerState::Paused,
+            Action::Resume => ContainerState::Running,
+            _ => return Ok(()),
+        };
+        // A stopped container's init is gone, so it can no longer be detected.
+        let Some(target) = self.target(action) else {
+            return if expected == ContainerState::Stopped {
+                Ok(())
+            } else {
+                Err(ActionError::Failed(format!(
+                    "container init {} disappeared",
+                    action.target.pid.0
+                )))
+            };
+        };
+        let timeout = Duration::from_millis(self.config.verify_timeout_ms);
+        if self.wait_for_state(&target, expected, timeout) {
+            Ok(())
+        } else {
+            Err(ActionError::Failed(format!(
+                "container {} did not reach {:?} state",
+                short_id(&target.container_id),
+                expected
+            )))
+        }
+    }
+}
+
+fn short_id(container_id: &str) -> &str {
+    &container_id[..container_id.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(runtime: ContainerRuntime) -> ContainerTarget {
+        ContainerTarget {
+            pid: 4242,
+            runtime,
+            container_id: "0123456789abcdef0123".to_string(),
+            kubernetes: false,
+        }
+    }
+
+    #[test]
+    fn nspid_identifies_namespace_init() {
+        let status = "Name:\tnginx\nPid:\t4242\nNSpid:\t4242\t1\nNSpgid:\t4242\t1\n";
+        assert_eq!(parse_nspid(status), Some(vec![4242, 1]));
+        assert!(is_namespace_init(status));
+
+        // Host init and processes without a nested namespace are not.
+        assert!(!is_namespace_init("NSpid:\t1\n"));
+        assert!(!is_namespace_init("NSpid:\t4243\t7\n"));
+        assert!(!is_namespace_init("Name:\told-kernel\n"));
+    }
+
+    #[test]
+    fn kill_maps_to_runtime_stop() {
+        let (program, args) =
+            runtime_command(ContainerRuntime::Docker, Action::Kill, "abc", 10).unwrap();
+        assert_eq!(program, "docker");
+        assert_eq!(args, vec!["stop", "-t", "10", "abc"]);
+
+        let (program, args) =
+            runtime_command(ContainerRuntime::Podman, Action::Pause, "abc", 10).unwrap();
+        assert_eq!(program, "podman");
+        assert_eq!(args, vec!["pause", "abc"]);
+
+        let (program, args) =
+            runtime_command(ContainerRuntime::Containerd, Action::Resume, "abc", 10).unwrap();
+        assert_eq!(program, "ctr");
+        assert_eq!(args, vec!["task", "resume", "abc"]);
+
+        assert!(runtime_command(ContainerRuntime::Lxc, Action::Kill, "abc", 10).is_err());
+        assert!(runtime_command(ContainerRuntime::Docker, Action::Renice, "abc", 10).is_err());
+    }
+
+    #[test]
+    fn policy_limits_runtimes() {
+        let runner = ContainerActionRunner::with_defaults();
+        assert!(runner
+            .blocked_reason(&target(ContainerRuntime::Docker))
+            .is_none());
+        let reason = runner
+            .blocked_reason(&target(ContainerRuntime::Containerd))
+            .unwrap();
+        assert!(reason.contains("containerd"));
+
+        let mut k8s = target(ContainerRuntime::Docker);
+        k8s.kubernetes = true;
+        assert!(runner.blocked_reason(&k8s).unwrap().contains("Kubernetes"));
+
+        let plan = runner.plan_for_target(target(ContainerRuntime::Podman), Action::Kill);
+        assert!(plan.allowed);
+        assert_eq!(
+            plan.command.as_deref(),
+            Some("podman stop -t 10 0123456789abcdef0123")
+        );
+
+        let locked = ContainerActionRunner::new(ContainerActionConfig::from(&ContainerActions {
+            allowed_runtimes: vec![],
+            stop_timeout_secs: 10,
+        }));
+        let plan = locked.plan_for_target(target(ContainerRuntime::Docker), Action::Kill);
+        assert!(!plan.allowed);
+        assert!(plan.blocked_reason.is_some());
+    }
+
+    #[test]
+    fn parses_runtime_states() {
+        assert_eq!(parse_inspect_status("running\n"), ContainerState::Running);
+        assert_eq!(parse_inspect_status("paused\n"), ContainerState::Paused);
+        assert_eq!(parse_inspect_status("exited\n"), ContainerState::Stopped);
+
+        let ls = "TASK    PID     STATUS\nabc     4242    PAUSED\ndef     4300    RUNNING\n";
+        assert_eq!(parse_ctr_task_status(ls, "abc"), ContainerState::Paused);
+        assert_eq!(parse_ctr_task_status(ls, "def"), ContainerState::Running);
+        assert_eq!(parse_ctr_task_status(ls, "zzz"), ContainerState::Stopped);
+    }
+}
@@ -4,20 +4,38 @@ use super::executor::{ActionError, ActionRunner};
 use crate::decision::Action;
 use crate::plan::PlanAction;
 
+#[cfg(unix)]
+use super::command::CommandActionRunner;
 use super::renice::ReniceActionRunner;
+#[cfg(unix)]
 use super::signal::SignalActionRunner;
+#[cfg(target_os = "windows")]
+use super::terminate::TerminateActionRunner as SignalActionRunner;
 
 #[cfg(target_os = "linux")]
-use super::cgroup_throttle::CpuThrottleActionRunner;
+use super::cgroup_throttle::{CpuThrottleActionRunner, CpuThrottleConfig};
+#[cfg(target_os = "linux")]
+use super::container::{ContainerActionConfig, ContainerActionRunner};
 #[cfg(target_os = "linux")]
 use super::cpuset_quarantine::CpusetQuarantineActionRunner;
 #[cfg(target_os = "linux")]
 use super::freeze::FreezeActionRunner;
+#[cfg(target_os = "linux")]
+use super::systemd_unit::SystemdUnitActionRunner;
 
 /// Dispatches actions to the appropriate runner implementation.
+///
+/// Pause/resume/kill go to [`SignalActionRunner`] on Unix and to the
+/// `TerminateProcess`-based runner on Windows. On Linux, when the target is
+/// PID 1 of a container they go to [`ContainerActionRunner`] instead, and
+/// kills/restarts planned as unit operations go to [`SystemdUnitActionRunner`].
+/// Kills with a signature shutdown command go to [`CommandActionRunner`] on
+/// Unix. Restarts routed to a zombie's parent are SIGCHLD on Unix.
 #[derive(Debug)]
 pub struct CompositeActionRunner {
     signal: SignalActionRunner,
+    #[cfg(unix)]
+    command: CommandActionRunner,
     renice: ReniceActionRunner,
     #[cfg(target_os = "linux")]
     freeze: FreezeActionRunner,
@@ -25,6 +43,10 @@ pub struct CompositeActionRunner {
     throttle: CpuThrottleActionRunner,
     #[cfg(target_os = "linux")]
     quarantine: CpusetQuarantineActionRunner,
+    #[cfg(target_os = "linux")]
+    container: ContainerActionRunner,
+    #[cfg(target_os = "linux")]
+    unit: SystemdUnitActionRunner,
 }
 
 impl CompositeActionRunner {
@@ -32,6 +54,8 @@ impl CompositeActionRunner {
     pub fn with_defaults() -> Self {
         Self {
             signal: SignalActionRunner::with_defaults(),
+            #[cfg(unix)]
+            command: CommandActionRunner::with_defaults(),
             renice: ReniceActionRunner::with_defaults(),
             #[cfg(target_os = "linux")]
             freeze: FreezeActionRunner::with_defaults(),
@@ -39,8 +63,26 @@ impl CompositeActionRunner {
             throttle: CpuThrottleActionRunner::with_defaults(),
             #[cfg(target_os = "linux")]
             quarantine: CpusetQuarantineActionRunner::with_defaults(),
+            #[cfg(target_os = "linux")]
+            container: ContainerActionRunner::with_defaults(),
+            #[cfg(target_os = "linux")]
+            unit: SystemdUnitActionRunner::with_defaults(),
         }
     }
+
+    /// Use policy throttle limits instead of the built-in defaults.
+    #[cfg(target_os = "linux")]
+    pub fn with_throttle_config(mut self, config: CpuThrottleConfig) -> Self {
+        self.throttle = CpuThrottleActionRunner::new(config);
+        self
+    }
+
+    /// Use policy container-action controls instead of the built-in defaults.
+    #[cfg(target_os = "linux")]
+    pub fn with_container_config(mut self, config: ContainerActionConfig) -> Self {
+        self.container = ContainerActionRunner::new(config);
+        self
+    }
 }
 
 impl Default for CompositeActionRunner {
@@ -53,7 +95,23 @@ impl ActionRunner for CompositeActionRunner {
     fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
         match action.action {
             Action::Keep => Ok(()),
+            #[cfg(target_os = "linux")]
+            Action::Kill | Action::Restart if action.unit_action.is_some() => {
+                self.unit.execute(action)
+            }
+            #[cfg(target_os = "linux")]
+            Action::Pause | Action::Resume | Action::Kill
+                if self.container.target(action).is_some() =>
+            {
+                self.container.execute(action)
+            }
+            #[cfg(unix)]
+            Action::Kill if action.command_action.is_some() => self.command.execute(action),
             Action::Pause | Action::Resume | Action::Kill => self.signal.execute(action),
+            #[cfg(unix)]
+            Action::Restart if action.routing == crate::plan::ActionRouting::ZombieToParent => {
+                self.signal.execute(action)
+            }
             Action::Renice => self.renice.execute(action),
             #[cfg(target_os = "linux")]
             Action::Freeze | Action::Unfreeze => self.freeze.execute(action),
@@ -78,7 +136,23 @@ impl ActionRunner for CompositeActionRunner {
     fn verify(&self, action: &PlanAction) -> Result<(), ActionError> {
         match action.action {
             Action::Keep => Ok(()),
+            #[cfg(target_os = "linux")]
+            Action::Kill | Action::Restart if action.unit_action.is_some() => {
+                self.unit.verify(action)
+            }
+            #[cfg(target_os = "linux")]
+            Action::Pause | Action::Resume | Action::Kill
+                if self.container.target(action).is_some() =>
+            {
+                self.container.verify(action)
+            }
+            #[cfg(unix)]
+            Action::Kill if action.command_action.is_some() => self.command.verify(action),
             Action::Pause | Action::Resume | Action::Kill => self.signal.verify(action),
+            #[cfg(unix)]
+            Action::Restart if action.routing == crate::plan::ActionRouting::ZombieToParent => {
+                self.signal.verify(action)
+            }
             Action::Renice => self.renice.verify(action),
             #[cfg(target_os = "linux")]
             Action::Freeze | Action::Unfreeze => self.freeze.verify(action),
@@ -148,6 +222,10 @@ mod tests {
                 process_state: None,
                 parent_identity: None,
                 d_state_diagnostics: None,
+                impact: None,
+                systemd_unit: None,
+                zombie_reap: None,
+                graceful_stop: None,
             }],
             generated_at: Some("2026-01-15T12:00:00Z".to_string()),
         };
@@ -173,6 +251,22 @@ mod tests {
         assert!(format!("{:?}", err).contains("restart requires supervisor support"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn composite_runner_zombie_parent_restart_sends_sigchld() {
+        let mut action = make_action();
+        action.action = Action::Restart;
+        action.routing = crate::plan::ActionRouting::ZombieToParent;
+        // SIGCHLD to ourselves is ignored; the "zombie" is long gone.
+        action.target.pid = ProcessId(std::process::id());
+        let mut zombie = action.target.clone();
+        zombie.pid = ProcessId(i32::MAX as u32 - 1);
+        action.original_zombie_target = Some(zombie);
+        let runner = CompositeActionRunner::with_defaults();
+        assert!(runner.execute(&action).is_ok());
+        assert!(runner.verify(&action).is_ok());
+    }
+
     #[test]
     fn composite_runner_default_trait() {
         let runner = CompositeActionRunner::default();
@@ -1,16 +1,25 @@
 //! Staged action execution protocol.
+//!
+//! Plans run stage by stage (see [`crate::plan::PlanStage`]). After each stage
+//! its barriers are evaluated; a failed barrier halts every later stage. When a
+//! checkpoint path is configured, progress is persisted after each action so an
+//! interrupted run can be resumed without repeating completed actions.
 
 use crate::action::prechecks::PreCheckProvider;
-use crate::plan::{Plan, PlanAction, PreCheck};
+use crate::plan::{Plan, PlanAction, PreCheck, StageBarrier};
 use pt_common::ProcessIdentity;
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// Default time to wait for a load barrier to clear.
+pub const DEFAULT_BARRIER_TIMEOUT: Duration = Duration::from_secs(30);
+const BARRIER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Errors during plan execution.
 #[derive(Debug, Error)]
 pub enum ExecutionError {
@@ -18,6 +27,8 @@ pub enum ExecutionError {
     LockUnavailable,
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("checkpoint error: {0}")]
+    Checkpoint(String),
 }
 
 /// Errors during action execution.
@@ -48,6 +59,8 @@ pub enum ActionStatus {
         check: PreCheck,
         reason: String,
     },
+    /// Not attempted because an earlier stage's barrier failed.
+    StageHalted,
 }
 
 /// Per-action result with timing and details.
@@ -68,13 +81,105 @@ pub struct ExecutionSummary {
     pub actions_failed: usize,
 }
 
+/// Per-stage execution summary.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StageSummary {
+    pub stage: u8,
+    pub name: String,
+    pub actions_attempted: usize,
+    pub actions_succeeded: usize,
+    pub actions_failed: usize,
+    pub actions_skipped: usize,
+    /// True when the stage never ran because an earlier barrier failed.
+    pub halted: bool,
+    /// Barrier that failed after this stage, halting the rest of the plan.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub barrier_failure: Option<String>,
+}
+
 /// Full execution result with per-action outcomes.
 #[derive(Debug, Clone, Serialize)]
 pub struct ExecutionResult {
     pub summary: ExecutionSummary,
     pub outcomes: Vec<ActionResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<StageSummary>,
+}
+
+/// Persisted execution progress, used to resume an interrupted plan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutionCheckpoint {
+    pub plan_id: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub completed_stages: Vec<u8>,
+    #[serde(default)]
+    pub succeeded_action_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub halted_at_stage: Option<u8>,
 }
 
+impl ExecutionCheckpoint {
+    pub fn new(plan_id: impl Into<String>) -> Self {
+        Self {
+            plan_id: plan_id.into(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            completed_stages: Vec::new(),
+            succeeded_action_ids: Vec::new(),
+            halted_at_stage: None,
+        }
+    }
+
+    /// Load a checkpoint, returning `None` if the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, ExecutionError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ExecutionError::Io(e)),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| ExecutionError::Checkpoint(format!("parse {}: {}", path.display(), e)))
+    }
+
+    /// Atomically write the checkpoint (write to temp file, then rename).
+    pub fn save(&self, path: &Path) -> Result<(), ExecutionError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| ExecutionError::Checkpoint(format!("serialize: {}", e)))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+/// Source of system measurements for stage barriers.
+pub trait BarrierProbe {
+    /// 1-minute load average divided by the number of CPUs, if available.
+    fn load_per_core(&self) -> Option<f64>;
+}
+
+/// Barrier probe backed by `/proc/loadavg`.
+#[derive(Debug, Default)]
+pub struct LiveBarrierProbe;
+
+impl BarrierProbe for LiveBarrierProbe {
+    fn load_per_core(&self) -> Option<f64> {
+        let content = std::fs::read_to_string("/proc/loadavg").ok()?;
+        let load1: f64 = content.split_whitespace().next()?.parse().ok()?;
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Some(load1 / cores as f64)
+    }
+}
+
+static LIVE_BARRIER_PROBE: LiveBarrierProbe = LiveBarrierProbe;
+
 /// Trait for executing actions (signals, cgroup ops, etc.).
 pub trait ActionRunner {
     fn execute(&self, action: &PlanAction) -> Result<(), ActionError>;
@@ -127,6 +232,10 @@ pub struct ActionExecutor<'a> {
     runner: &'a dyn ActionRunner,
     identity_provider: &'a dyn IdentityProvider,
     pre_check_provider: Option<&'a dyn PreCheckProvider>,
+    barrier_probe: &'a dyn BarrierProbe,
+    barrier_timeout: Duration,
+    checkpoint_path: Option<PathBuf>,
+    resume: bool,
     lock_path: PathBuf,
 }
 
@@ -140,6 +249,10 @@ impl<'a> ActionExecutor<'a> {
             runner,
             identity_provider,
             pre_check_provider: None,
+            barrier_probe: &LIVE_BARRIER_PROBE,
+            barrier_timeout: DEFAULT_BARRIER_TIMEOUT,
+            checkpoint_path: None,
+            resume: false,
             lock_path: lock_path.into(),
         }
     }
@@ -150,29 +263,120 @@ impl<'a> ActionExecutor<'a> {
         self
     }
 
+    /// Set the probe used to evaluate load barriers between stages.
+    pub fn with_barrier_probe(mut self, probe: &'a dyn BarrierProbe) -> Self {
+        self.barrier_probe = probe;
+        self
+    }
+
+    /// Set how long a load barrier may wait before halting the plan.
+    pub fn with_barrier_timeout(mut self, timeout: Duration) -> Self {
+        self.barrier_timeout = timeout;
+        self
+    }
+
+    /// Persist execution progress to `path` after every action and stage.
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint_path = Some(path.into());
+        self
+    }
+
+    /// Resume from the checkpoint (if it belongs to the same plan), skipping
+    /// actions that already succeeded.
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
     pub fn execute_plan(&self, plan: &Plan) -> Result<ExecutionResult, ExecutionError> {
         let _lock = ActionLock::acquire(&self.lock_path)?;
 
+        let mut checkpoint = self.initial_checkpoint(plan)?;
+        let resumed: HashSet<String> = checkpoint.succeeded_action_ids.iter().cloned().collect();
+        checkpoint.halted_at_stage = None;
+
         let mut outcomes = Vec::new();
+        let mut stages = Vec::new();
         let mut succeeded = 0;
         let mut failed = 0;
+        let mut halted = false;
+
+        for stage in stage_order(plan) {
+            let spec = plan.stages.iter().find(|s| s.stage == stage);
+            let mut summary = StageSummary {
+                stage,
+                name: spec
+                    .map(|s| s.name.clone())
+                    .unwrap_or_else(|| format!("stage_{}", stage)),
+                actions_attempted: 0,
+                actions_succeeded: 0,
+                actions_failed: 0,
+                actions_skipped: 0,
+                halted,
+                barrier_failure: None,
+            };
+            let mut all_verified = true;
+
+            for action in plan.actions.iter().filter(|a| a.stage == stage) {
+                summary.actions_attempted += 1;
+                let start = Instant::now();
+                let (status, details) = if halted {
+                    (ActionStatus::StageHalted, None)
+                } else if resumed.contains(&action.action_id) {
+                    (
+                        ActionStatus::Skipped,
+                        Some("already completed (resumed)".to_string()),
+                    )
+                } else {
+                    (self.execute_action(action), None)
+                };
+                let time_ms = start.elapsed().as_millis();
+
+                match &status {
+                    ActionStatus::Success => {
+                        succeeded += 1;
+                        summary.actions_succeeded += 1;
+                        checkpoint
+                            .succeeded_action_ids
+                            .push(action.action_id.clone());
+                        self.save_checkpoint(&mut checkpoint)?;
+                    }
+                    ActionStatus::Skipped | ActionStatus::StageHalted => {
+                        summary.actions_skipped += 1;
+                    }
+                    _ => {
+                        failed += 1;
+                        summary.actions_failed += 1;
+                        all_verified = false;
+                    }
+                }
+
+                outcomes.push(ActionResult {
+                    action_id: action.action_id.clone(),
+                    status,
+                    time_ms,
+                    details,
+                });
+            }
 
-        for action in &plan.actions {
-            let start = Instant::now();
-            let result = self.execute_action(action);
-            let time_ms = start.elapsed().as_millis();
-            match &result {
-                ActionStatus::Success => succeeded += 1,
-                ActionStatus::Skipped => {}
-                _ => failed += 1,
+            if !halted {
+                let barriers = spec.map(|s| s.barriers.as_slice()).unwrap_or(&[]);
+                match self.check_barriers(barriers, all_verified) {
+                    Ok(()) => {
+                        if !checkpoint.completed_stages.contains(&stage) {
+                            checkpoint.completed_stages.push(stage);
+                        }
+                    }
+                    Err(reason) => {
+                        summary.barrier_failure = Some(reason);
+                        checkpoint.halted_at_stage = Some(stage);
+                        halted = true;
+                    }
+                }
+                self.save_checkpoint(&mut checkpoint)?;
             }
 
-            outcomes.push(ActionResult {
-                action_id: action.action_id.clone(),
-                status: result,
-                time_ms,
-                details: None,
-            });
+            stages.push(summary);
         }
 
         Ok(ExecutionResult {
@@ -182,9 +386,72 @@ impl<'a> ActionExecutor<'a> {
                 actions_failed: failed,
             },
             outcomes,
+            stages,
         })
     }
 
+    fn initial_checkpoint(&self, plan: &Plan) -> Result<ExecutionCheckpoint, ExecutionError> {
+        if self.resume {
+            if let Some(path) = &self.checkpoint_path {
+                if let Some(existing) = ExecutionCheckpoint::load(path)? {
+                    if existing.plan_id == plan.plan_id {
+                        return Ok(existing);
+                    }
+                }
+            }
+        }
+        Ok(ExecutionCheckpoint::new(plan.plan_id.clone()))
+    }
+
+    fn save_checkpoint(&self, checkpoint: &mut ExecutionCheckpoint) -> Result<(), ExecutionError> {
+        if let Some(path) = &self.checkpoint_path {
+            checkpoint.touch();
+            checkpoint.save(path)?;
+        }
+        Ok(())
+    }
+
+    /// Evaluate a stage's barriers, returning a description of the first failure.
+    fn check_barriers(&self, barriers: &[StageBarrier], all_verified: bool) -> Result<(), String> {
+        for barrier in barriers {
+            match barrier {
+                StageBarrier::AllVerified => {
+                    if !all_verified {
+                        return Err("not all stage actions verified".to_string());
+                    }
+                }
+                StageBarrier::LoadPerCoreBelow { max } => self.wait_for_load(*max)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Poll load until it drops to `max` or the barrier timeout expires.
+    ///
+    /// An unavailable measurement passes: the barrier throttles, it does not gate safety.
+    fn wait_for_load(&self, max: f64) -> Result<(), String> {
+        let deadline = Instant::now() + self.barrier_timeout;
+        loop {
+            let load = match self.barrier_probe.load_per_core() {
+                Some(load) => load,
+                None => return Ok(()),
+            };
+            if load <= max {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(format!(
+                    "load per core {:.2} above {:.2} after {}s",
+                    load,
+                    max,
+                    self.barrier_timeout.as_secs()
+                ));
+            }
+            std::thread::sleep(BARRIER_POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
     fn execute_action(&self, action: &PlanAction) -> ActionStatus {
         if action.blocked {
             return ActionStatus::Skipped;
@@ -212,6 +479,16 @@ impl<'a> ActionExecutor<'a> {
                     return ActionStatus::PreCheckBlocked { check, reason };
                 }
             }
+
+            if let Some(unit) = action.unit_action.as_ref() {
+                if action.pre_checks.contains(&PreCheck::VerifyUnitMapping) {
+                    if let crate::action::prechecks::PreCheckResult::Blocked { check, reason } =
+                        provider.check_unit_mapping(pid, unit)
+                    {
+                        return ActionStatus::PreCheckBlocked { check, reason };
+                    }
+                }
+            }
         }
 
         if let Err(err) = self.runner.execute(action) {
@@ -226,6 +503,14 @@ impl<'a> ActionExecutor<'a> {
     }
 }
 
+/// Stage numbers present in the plan's actions, in ascending order.
+fn stage_order(plan: &Plan) -> Vec<u8> {
+    let mut stages: Vec<u8> = plan.actions.iter().map(|a| a.stage).collect();
+    stages.sort_unstable();
+    stages.dedup();
+    stages
+}
+
 fn status_from_error(err: ActionError) -> ActionStatus {
     match err {
         ActionError::IdentityMismatch => ActionStatus::IdentityMismatch,
@@ -347,6 +632,10 @@ mod tests {
                 process_state: None,
                 parent_identity: None,
                 d_state_diagnostics: None,
+                impact: None,
+                systemd_unit: None,
+                zombie_reap: None,
+                graceful_stop: None,
             }],
             generated_at: Some("2026-01-15T12:00:00Z".to_string()),
         };
@@ -627,6 +916,7 @@ mod tests {
                 actions_failed: 1,
             },
             outcomes: vec![],
+            stages: vec![],
         };
         let json = serde_json::to_string(&r).unwrap();
         assert!(json.contains("\"actions_attempted\":3"));
@@ -688,4 +978,174 @@ mod tests {
         // time_ms should be a small non-negative number (noop is fast)
         assert!(result.outcomes[0].time_ms < 1000);
     }
+
+    // ── Stages, barriers, checkpoints ───────────────────────────────
+
+    struct FailIdsRunner(HashSet<String>);
+
+    impl ActionRunner for FailIdsRunner {
+        fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
+            if self.0.contains(&action.action_id) {
+                return Err(ActionError::Failed("boom".into()));
+            }
+            Ok(())
+        }
+
+        fn verify(&self, _action: &PlanAction) -> Result<(), ActionError> {
+            Ok(())
+        }
+    }
+
+    struct FixedLoad(Option<f64>);
+
+    impl BarrierProbe for FixedLoad {
+        fn load_per_core(&self) -> Option<f64> {
+            self.0
+        }
+    }
+
+    /// Plan with one action in stage 0 and one in stage 1, separated by `barriers`.
+    fn make_staged_plan(barriers: Vec<StageBarrier>) -> Plan {
+        let mut plan = make_plan();
+        let mut second = plan.actions[0].clone();
+        second.action_id = "act-stage-1".to_string();
+        second.stage = 1;
+        plan.actions.push(second);
+        plan.stages = vec![
+            crate::plan::PlanStage {
+                stage: 0,
+                name: "primary".to_string(),
+                barriers,
+            },
+            crate::plan::PlanStage {
+                stage: 1,
+                name: "kill_after_pause".to_string(),
+                barriers: vec![],
+            },
+        ];
+        plan
+    }
+
+    #[test]
+    fn executor_reports_stage_summaries() {
+        let plan = make_staged_plan(vec![StageBarrier::AllVerified]);
+        let dir = tempdir().unwrap();
+        let runner = NoopActionRunner;
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+        let executor = ActionExecutor::new(&runner, &ids, dir.path().join("lock"));
+        let result = executor.execute_plan(&plan).unwrap();
+        assert_eq!(result.stages.len(), 2);
+        assert_eq!(result.stages[0].name, "primary");
+        assert_eq!(result.stages[0].actions_succeeded, 1);
+        assert_eq!(result.stages[1].actions_succeeded, 1);
+        assert!(result.stages.iter().all(|s| !s.halted));
+        assert!(result.stages[0].barrier_failure.is_none());
+    }
+
+    #[test]
+    fn failed_all_verified_barrier_halts_later_stages() {
+        let plan = make_staged_plan(vec![StageBarrier::AllVerified]);
+        let dir = tempdir().unwrap();
+        let runner = FailIdsRunner(HashSet::from([plan.actions[0].action_id.clone()]));
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+        let executor = ActionExecutor::new(&runner, &ids, dir.path().join("lock"));
+        let result = executor.execute_plan(&plan).unwrap();
+        assert_eq!(result.outcomes[0].status, ActionStatus::Failed);
+        assert_eq!(result.outcomes[1].status, ActionStatus::StageHalted);
+        assert!(result.stages[0].barrier_failure.is_some());
+        assert!(result.stages[1].halted);
+        assert_eq!(result.summary.actions_failed, 1);
+    }
+
+    #[test]
+    fn load_barrier_halts_when_load_stays_high() {
+        let plan = make_staged_plan(vec![StageBarrier::LoadPerCoreBelow { max: 1.0 }]);
+        let dir = tempdir().unwrap();
+        let runner = NoopActionRunner;
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+        let probe = FixedLoad(Some(4.0));
+        let executor = ActionExecutor::new(&runner, &ids, dir.path().join("lock"))
+            .with_barrier_probe(&probe)
+            .with_barrier_timeout(Duration::ZERO);
+        let result = executor.execute_plan(&plan).unwrap();
+        let failure = result.stages[0].barrier_failure.as_deref().unwrap();
+        assert!(failure.contains("load per core"));
+        assert_eq!(result.outcomes[1].status, ActionStatus::StageHalted);
+    }
+
+    #[test]
+    fn load_barrier_passes_when_unavailable() {
+        let plan = make_staged_plan(vec![StageBarrier::LoadPerCoreBelow { max: 1.0 }]);
+        let dir = tempdir().unwrap();
+        let runner = NoopActionRunner;
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+        let probe = FixedLoad(None);
+        let executor = ActionExecutor::new(&runner, &ids, dir.path().join("lock"))
+            .with_barrier_probe(&probe)
+            .with_barrier_timeout(Duration::ZERO);
+        let result = executor.execute_plan(&plan).unwrap();
+        assert_eq!(result.summary.actions_succeeded, 2);
+    }
+
+    #[test]
+    fn resume_skips_actions_completed_in_checkpoint() {
+        let plan = make_staged_plan(vec![]);
+        let dir = tempdir().unwrap();
+        let checkpoint_path = dir.path().join("checkpoint.json");
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+
+        let failing = FailIdsRunner(HashSet::from(["act-stage-1".to_string()]));
+        ActionExecutor::new(&failing, &ids, dir.path().join("lock"))
+            .with_checkpoint(&checkpoint_path)
+            .execute_plan(&plan)
+            .unwrap();
+        let saved = ExecutionCheckpoint::load(&checkpoint_path)
+            .unwrap()
+            .expect("checkpoint written");
+        assert_eq!(saved.plan_id, plan.plan_id);
+        assert_eq!(
+            saved.succeeded_action_ids,
+            vec![plan.actions[0].action_id.clone()]
+        );
+
+        let runner = NoopActionRunner;
+        let result = ActionExecutor::new(&runner, &ids, dir.path().join("lock"))
+            .with_checkpoint(&checkpoint_path)
+            .with_resume(true)
+            .execute_plan(&plan)
+            .unwrap();
+        assert_eq!(result.outcomes[0].status, ActionStatus::Skipped);
+        assert_eq!(
+            result.outcomes[0].details.as_deref(),
+            Some("already completed (resumed)")
+        );
+        assert_eq!(result.outcomes[1].status, ActionStatus::Success);
+    }
+
+    #[test]
+    fn resume_ignores_checkpoint_for_other_plan() {
+        let plan = make_staged_plan(vec![]);
+        let dir = tempdir().unwrap();
+        let checkpoint_path = dir.path().join("checkpoint.json");
+        let mut stale = ExecutionCheckpoint::new("other-plan");
+        stale.succeeded_action_ids = plan.actions.iter().map(|a| a.action_id.clone()).collect();
+        stale.save(&checkpoint_path).unwrap();
+
+        let runner = NoopActionRunner;
+        let ids = StaticIdentityProvider::default().with_identity(plan.actions[0].target.clone());
+        let result = ActionExecutor::new(&runner, &ids, dir.path().join("lock"))
+            .with_checkpoint(&checkpoint_path)
+            .with_resume(true)
+            .execute_plan(&plan)
+            .unwrap();
+        assert_eq!(result.summary.actions_succeeded, 2);
+    }
+
+    #[test]
+    fn checkpoint_load_missing_is_none() {
+        let dir = tempdir().unwrap();
+        assert!(ExecutionCheckpoint::load(&dir.path().join("missing.json"))
+            .unwrap()
+            .is_none());
+    }
 }
@@ -2,8 +2,10 @@
 //!
 //! Implements process freezing using cgroup v2 freezer with:
 //! - TOCTOU safety via identity revalidation
-//! - Verification via cgroup.freeze state readback
-//! - Support for same-cgroup blast radius control
+//! - Verification via the `frozen` key in cgroup.events (kernel-confirmed state)
+//! - Same-cgroup blast radius control: freezing is cgroup-wide, so the
+//!   `CheckFreezeScope` pre-check refuses cgroups that hold processes outside
+//!   the target's own tree (see [`freeze_collateral`])
 //!
 //! The cgroup freezer is more robust than SIGSTOP because:
 //! - Works at cgroup level (entire process tree)
@@ -23,15 +25,18 @@ use tracing::{debug, trace};
 pub struct FreezeConfig {
     /// Whether to verify cgroup v2 availability before executing.
     pub verify_capability: bool,
-    /// Timeout in milliseconds for freeze state to propagate.
+    /// Maximum time in milliseconds to wait for the freeze state to propagate.
     pub propagation_timeout_ms: u64,
 }
 
+/// Poll interval while waiting for cgroup.events to report the new state.
+const FREEZE_POLL_INTERVAL_MS: u64 = 10;
+
 impl Default for FreezeConfig {
     fn default() -> Self {
         Self {
             verify_capability: true,
-            propagation_timeout_ms: 100,
+            propagation_timeout_ms: 1000,
         }
     }
 }
@@ -101,6 +106,35 @@ impl FreezeActionRunner {
         }
     }
 
+    /// Read the kernel-confirmed freeze state from cgroup.events.
+    ///
+    /// cgroup.freeze only echoes the requested state; the `frozen` key in
+    /// cgroup.events flips once every task in the cgroup has actually stopped.
+    /// Falls back to cgroup.freeze on kernels without the key.
+    fn read_frozen_state(&self, freeze_path: &str) -> Result<bool, ActionError> {
+        let events_path = freeze_path.replace("cgroup.freeze", "cgroup.events");
+        match fs::read_to_string(&events_path)
+            .ok()
+            .and_then(|content| parse_events_frozen(&content))
+        {
+            Some(frozen) => Ok(frozen),
+            None => self.read_freeze_state(freeze_path),
+        }
+    }
+
+    /// Wait until the cgroup reports `frozen`, up to the propagation timeout.
+    fn wait_for_frozen_state(&self, freeze_path: &str, frozen: bool) -> Result<bool, ActionError> {
+        let deadline = std::time::Instant::now()
+            + std::time::Duration::from_millis(self.config.propagation_timeout_ms);
+        loop {
+            let current = self.read_frozen_state(freeze_path)?;
+            if current == frozen || std::time::Instant::now() >= deadline {
+                return Ok(current);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(FREEZE_POLL_INTERVAL_MS));
+        }
+    }
+
     /// Write freeze state to cgroup.freeze.
     ///
     /// `frozen=true` freezes the cgroup, `frozen=false` unfreezes it.
@@ -140,13 +174,8 @@ impl FreezeActionRunner {
     fn verify_freeze(&self, action: &PlanAction) -> Result<(), ActionError> {
         let pid = action.target.pid.0;
 
-        // Give it a moment for the freeze state to propagate
-        std::thread::sleep(std::time::Duration::from_millis(
-            self.config.propagation_timeout_ms,
-        ));
-
         let freeze_path = self.get_freeze_path(pid)?;
-        let is_frozen = self.read_freeze_state(&freeze_path)?;
+        let is_frozen = self.wait_for_frozen_state(&freeze_path, true)?;
 
         if is_frozen {
             debug!(pid = pid, "freeze verified");
@@ -164,13 +193,8 @@ impl FreezeActionRunner {
     fn verify_unfreeze(&self, action: &PlanAction) -> Result<(), ActionError> {
         let pid = action.target.pid.0;
 
-        // Give it a moment for the unfreeze state to propagate
-        std::thread::sleep(std::time::Duration::from_millis(
-            self.config.propagation_timeout_ms,
-        ));
-
         let freeze_path = self.get_freeze_path(pid)?;
-        let is_frozen = self.read_freeze_state(&freeze_path)?;
+        let is_frozen = self.wait_for_frozen_state(&freeze_path, false)?;
 
         if !is_frozen {
             debug!(pid = pid, "unfreeze verified");
@@ -266,6 +290,72 @@ pub fn is_freeze_available(_pid: u32) -> bool {
     false
 }
 
+/// Processes that freezing `pid` would freeze as collateral.
+///
+/// The freezer acts on the whole cgroup, so every member that is neither the
+/// target nor one of its descendants is returned.
+#[cfg(target_os = "linux")]
+pub fn freeze_collateral(pid: u32) -> Result<Vec<u32>, ActionError> {
+    let freeze_path = FreezeActionRunner::with_defaults().get_freeze_path(pid)?;
+    cgroup_collateral(pid, &freeze_path.replace("cgroup.freeze", "cgroup.procs"))
+}
+
+/// Members of the cgroup whose `cgroup.procs` is at `procs_path` that are
+/// neither `pid` nor one of its descendants.
+#[cfg(target_os = "linux")]
+pub(super) fn cgroup_collateral(pid: u32, procs_path: &str) -> Result<Vec<u32>, ActionError> {
+    let content = fs::read_to_string(procs_path)
+        .map_err(|e| ActionError::Failed(format!("cannot read {}: {}", procs_path, e)))?;
+    let members: Vec<u32> = content
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect();
+    Ok(outside_tree(pid, &members, read_ppid))
+}
+
+#[cfg(target_os = "linux")]
+fn read_ppid(pid: u32) -> Option<u32> {
+    let content = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let after_comm = content.get(content.rfind(')')? + 2..)?;
+    after_comm.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Members not equal to `root` and without `root` among their ancestors.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn outside_tree(root: u32, members: &[u32], parent_of: impl Fn(u32) -> Option<u32>) -> Vec<u32> {
+    members
+        .iter()
+        .copied()
+        .filter(|&member| {
+            let mut current = member;
+            // Bounded walk guards against ppid cycles from racing reads.
+            for _ in 0..64 {
+                if current == root {
+                    return false;
+                }
+                match parent_of(current) {
+                    Some(parent) if parent > 1 && parent != current => current = parent,
+                    _ => return true,
+                }
+            }
+            true
+        })
+        .collect()
+}
+
+/// Parse the `frozen` key from cgroup.events content.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_events_frozen(content: &str) -> Option<bool> {
+    content.lines().find_map(|line| {
+        let value = line.strip_prefix("frozen ")?;
+        match value.trim() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,7 +364,23 @@ mod tests {
     fn freeze_config_defaults() {
         let config = FreezeConfig::default();
         assert!(config.verify_capability);
-        assert_eq!(config.propagation_timeout_ms, 100);
+        assert_eq!(config.propagation_timeout_ms, 1000);
+    }
+
+    #[test]
+    fn parses_frozen_key_from_events() {
+        assert_eq!(parse_events_frozen("populated 1\nfrozen 1\n"), Some(true));
+        assert_eq!(parse_events_frozen("populated 1\nfrozen 0\n"), Some(false));
+        assert_eq!(parse_events_frozen("populated 1\n"), None);
+    }
+
+    #[test]
+    fn collateral_excludes_target_tree() {
+        // 10 -> 11 -> 12 is the target tree; 20 is a sibling under PID 1.
+        let parents = [(10, 1), (11, 10), (12, 11), (20, 1)];
+        let parent_of = |pid| parents.iter().find(|(p, _)| *p == pid).map(|(_, pp)| *pp);
+        assert_eq!(outside_tree(10, &[10, 11, 12, 20], parent_of), vec![20]);
+        assert!(outside_tree(10, &[10, 12], parent_of).is_empty());
     }
 
     #[test]
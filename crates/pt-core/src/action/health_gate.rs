@@ -0,0 +1,339 @@
+//! Host health gate checked before applying actions.
+//!
+//! When the host is under extreme load, sending signals and verifying their
+//! effect can make things worse or time out misleadingly. The gate samples
+//! load, PSI, and available memory, compares them against
+//! [`ApplyHealthGate`] thresholds from policy, and either lets apply proceed
+//! or defers it with a structured reason. Callers may optionally wait for the
+//! host to recover, bounded by a timeout.
+
+use crate::config::policy::ApplyHealthGate;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Point-in-time host health measurements. `None` means unavailable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HostHealthSample {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_per_core: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psi_cpu_avg10: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psi_memory_avg10: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psi_io_avg10: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_memory_mb: Option<u64>,
+}
+
+/// A threshold the host currently exceeds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HealthViolation {
+    LoadPerCore {
+        value: f64,
+        max: f64,
+    },
+    Psi {
+        resource: String,
+        value: f64,
+        max: f64,
+    },
+    AvailableMemory {
+        value_mb: u64,
+        min_mb: u64,
+    },
+}
+
+impl HealthViolation {
+    /// Human-readable description of the violation.
+    pub fn describe(&self) -> String {
+        match self {
+            HealthViolation::LoadPerCore { value, max } => {
+                format!("load per core {:.2} > {:.2}", value, max)
+            }
+            HealthViolation::Psi {
+                resource,
+                value,
+                max,
+            } => format!("{} pressure avg10 {:.1}% > {:.1}%", resource, value, max),
+            HealthViolation::AvailableMemory { value_mb, min_mb } => {
+                format!("available memory {} MiB < {} MiB", value_mb, min_mb)
+            }
+        }
+    }
+}
+
+/// Outcome of the health gate.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthGateDecision {
+    /// True when apply may proceed.
+    pub healthy: bool,
+    /// Most recent sample.
+    pub sample: HostHealthSample,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<HealthViolation>,
+    /// Whether the caller waited for the host to recover.
+    pub waited: bool,
+    pub waited_ms: u64,
+    /// Number of samples taken.
+    pub samples: u32,
+}
+
+impl HealthGateDecision {
+    /// One-line reason suitable for error output.
+    pub fn reason(&self) -> String {
+        self.violations
+            .iter()
+            .map(HealthViolation::describe)
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Source of host health samples.
+pub trait HostHealthProbe {
+    fn sample(&self) -> HostHealthSample;
+}
+
+/// Host health probe backed by `/proc` (loadavg, pressure, meminfo).
+///
+/// On hosts without these files every field is `None`, which the gate treats
+/// as healthy.
+#[derive(Debug, Default)]
+pub struct LiveHostHealthProbe;
+
+impl HostHealthProbe for LiveHostHealthProbe {
+    fn sample(&self) -> HostHealthSample {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1) as f64;
+        let load_per_core = std::fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|content| parse_load1(&content))
+            .map(|load| load / cores);
+        let psi = |resource: &str| {
+            std::fs::read_to_string(format!("/proc/pressure/{}", resource))
+                .ok()
+                .and_then(|content| parse_psi_some_avg10(&content))
+        };
+        let available_memory_mb = std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|content| parse_mem_available_mb(&content));
+
+        HostHealthSample {
+            load_per_core,
+            psi_cpu_avg10: psi("cpu"),
+            psi_memory_avg10: psi("memory"),
+            psi_io_avg10: psi("io"),
+            available_memory_mb,
+        }
+    }
+}
+
+/// Compare a sample against the gate thresholds.
+pub fn evaluate(gate: &ApplyHealthGate, sample: &HostHealthSample) -> Vec<HealthViolation> {
+    let mut violations = Vec::new();
+    if let Some(value) = sample.load_per_core {
+        if value > gate.max_load_per_core {
+            violations.push(HealthViolation::LoadPerCore {
+                value,
+                max: gate.max_load_per_core,
+            });
+        }
+    }
+    for (resource, value) in [
+        ("cpu", sample.psi_cpu_avg10),
+        ("memory", sample.psi_memory_avg10),
+        ("io", sample.psi_io_avg10),
+    ] {
+        if let Some(value) = value {
+            if value > gate.max_psi_avg10 {
+                violations.push(HealthViolation::Psi {
+                    resource: resource.to_string(),
+                    value,
+                    max: gate.max_psi_avg10,
+                });
+            }
+        }
+    }
+    if let Some(value_mb) = sample.available_memory_mb {
+        if value_mb < gate.min_available_memory_mb {
+            violations.push(HealthViolation::AvailableMemory {
+                value_mb,
+                min_mb: gate.min_available_memory_mb,
+            });
+        }
+    }
+    violations
+}
+
+/// Run the gate once, or poll until healthy when `wait` is set.
+///
+/// Waiting is bounded by `timeout` (policy `wait_timeout_seconds` when `None`)
+/// and samples every `poll_interval_seconds`. A disabled gate is always healthy.
+pub fn check_host_health(
+    gate: &ApplyHealthGate,
+    probe: &dyn HostHealthProbe,
+    wait: bool,
+    timeout: Option<Duration>,
+) -> HealthGateDecision {
+    let start = Instant::now();
+    let timeout = timeout.unwrap_or(Duration::from_secs(gate.wait_timeout_seconds));
+    let poll = Duration::from_secs(gate.poll_interval_seconds.max(1));
+    let mut samples = 0u32;
+
+    loop {
+        let sample = probe.sample();
+        samples += 1;
+        let violations = if gate.enabled {
+            evaluate(gate, &sample)
+        } else {
+            Vec::new()
+        };
+
+        let elapsed = start.elapsed();
+        if violations.is_empty() || !wait || elapsed >= timeout {
+            return HealthGateDecision {
+                healthy: violations.is_empty(),
+                sample,
+                violations,
+                waited: wait && samples > 1,
+                waited_ms: elapsed.as_millis() as u64,
+                samples,
+            };
+        }
+        std::thread::sleep(poll.min(timeout - elapsed));
+    }
+}
+
+fn parse_load1(content: &str) -> Option<f64> {
+    content.split_whitespace().next()?.parse().ok()
+}
+
+fn parse_psi_some_avg10(content: &str) -> Option<f64> {
+    let line = content.lines().find(|l| l.starts_with("some"))?;
+    line.split_whitespace()
+        .find_map(|part| part.strip_prefix("avg10="))
+        .and_then(|v| v.parse().ok())
+}
+
+fn parse_mem_available_mb(content: &str) -> Option<u64> {
+    let line = content.lines().find(|l| l.starts_with("MemAvailable:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct SeqProbe(RefCell<Vec<HostHealthSample>>);
+
+    impl HostHealthProbe for SeqProbe {
+        fn sample(&self) -> HostHealthSample {
+            let mut seq = self.0.borrow_mut();
+            if seq.len() > 1 {
+                seq.remove(0)
+            } else {
+                seq[0].clone()
+            }
+        }
+    }
+
+    fn overloaded() -> HostHealthSample {
+        HostHealthSample {
+            load_per_core: Some(9.0),
+            psi_memory_avg10: Some(80.0),
+            available_memory_mb: Some(10),
+            ..Default::default()
+        }
+    }
+
+    fn calm() -> HostHealthSample {
+        HostHealthSample {
+            load_per_core: Some(0.2),
+            psi_cpu_avg10: Some(1.0),
+            available_memory_mb: Some(8192),
+            ..Default::default()
+        }
+    }
+
+    fn fast_gate() -> ApplyHealthGate {
+        ApplyHealthGate {
+            wait_timeout_seconds: 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluate_reports_each_violation() {
+        let violations = evaluate(&ApplyHealthGate::default(), &overloaded());
+        assert_eq!(violations.len(), 3);
+        assert!(matches!(violations[0], HealthViolation::LoadPerCore { .. }));
+        assert!(
+            matches!(&violations[1], HealthViolation::Psi { resource, .. } if resource == "memory")
+        );
+        assert!(matches!(
+            violations[2],
+            HealthViolation::AvailableMemory { .. }
+        ));
+    }
+
+    #[test]
+    fn missing_measurements_are_healthy() {
+        let violations = evaluate(&ApplyHealthGate::default(), &HostHealthSample::default());
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn unhealthy_without_wait_defers_immediately() {
+        let probe = SeqProbe(RefCell::new(vec![overloaded()]));
+        let decision = check_host_health(&fast_gate(), &probe, false, None);
+        assert!(!decision.healthy);
+        assert_eq!(decision.samples, 1);
+        assert!(!decision.waited);
+        assert!(decision.reason().contains("load per core"));
+    }
+
+    #[test]
+    fn wait_returns_once_host_recovers() {
+        let probe = SeqProbe(RefCell::new(vec![overloaded(), calm()]));
+        let gate = ApplyHealthGate {
+            poll_interval_seconds: 1,
+            ..Default::default()
+        };
+        let decision = check_host_health(&gate, &probe, true, Some(Duration::from_secs(5)));
+        assert!(decision.healthy);
+        assert!(decision.waited);
+        assert_eq!(decision.samples, 2);
+    }
+
+    #[test]
+    fn wait_times_out_when_host_stays_unhealthy() {
+        let probe = SeqProbe(RefCell::new(vec![overloaded()]));
+        let decision = check_host_health(&fast_gate(), &probe, true, None);
+        assert!(!decision.healthy);
+        assert!(!decision.violations.is_empty());
+    }
+
+    #[test]
+    fn disabled_gate_is_always_healthy() {
+        let gate = ApplyHealthGate {
+            enabled: false,
+            ..Default::default()
+        };
+        let probe = SeqProbe(RefCell::new(vec![overloaded()]));
+        assert!(check_host_health(&gate, &probe, false, None).healthy);
+    }
+
+    #[test]
+    fn parses_proc_formats() {
+        assert_eq!(parse_load1("3.50 2.00 1.00 2/300 12345\n"), Some(3.5));
+        let psi = "some avg10=12.34 avg60=5.00 avg300=1.00 total=100\nfull avg10=1.00 avg60=0.00 avg300=0.00 total=5\n";
+        assert_eq!(parse_psi_some_avg10(psi), Some(12.34));
+        let meminfo = "MemTotal:       16384000 kB\nMemAvailable:    2048000 kB\n";
+        assert_eq!(parse_mem_available_mb(meminfo), Some(2000));
+    }
+}
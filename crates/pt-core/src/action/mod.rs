@@ -2,11 +2,16 @@
 
 #[cfg(target_os = "linux")]
 pub mod cgroup_throttle;
+#[cfg(unix)]
+pub mod command;
+#[cfg(target_os = "linux")]
+pub mod container;
 #[cfg(target_os = "linux")]
 pub mod cpuset_quarantine;
 pub mod executor;
 #[cfg(target_os = "linux")]
 pub mod freeze;
+pub mod health_gate;
 
 #[cfg(test)]
 mod repro_cpuset;
@@ -19,12 +24,23 @@ pub mod renice;
 #[cfg(unix)]
 pub mod signal;
 pub mod supervisor;
+#[cfg(target_os = "linux")]
+pub mod systemd_unit;
+#[cfg(target_os = "windows")]
+pub mod terminate;
 
 #[cfg(target_os = "linux")]
 pub use cgroup_throttle::{
     can_throttle_process, CpuThrottleActionRunner, CpuThrottleConfig, ThrottleResult,
     ThrottleReversalMetadata, DEFAULT_PERIOD_US, DEFAULT_THROTTLE_FRACTION, MIN_QUOTA_US,
 };
+#[cfg(unix)]
+pub use command::{CommandActionConfig, CommandActionRunner};
+#[cfg(target_os = "linux")]
+pub use container::{
+    detect_container_init, ContainerActionConfig, ContainerActionPlan, ContainerActionRunner,
+    ContainerTarget,
+};
 #[cfg(target_os = "linux")]
 pub use cpuset_quarantine::{
     can_quarantine_cpuset, CpusetQuarantineActionRunner, CpusetQuarantineConfig, QuarantineResult,
@@ -32,11 +48,17 @@ pub use cpuset_quarantine::{
 };
 pub use dispatch::CompositeActionRunner;
 pub use executor::{
-    ActionError, ActionExecutor, ActionResult, ActionRunner, ActionStatus, ExecutionError,
-    ExecutionResult, ExecutionSummary, IdentityProvider, NoopActionRunner, StaticIdentityProvider,
+    ActionError, ActionExecutor, ActionResult, ActionRunner, ActionStatus, BarrierProbe,
+    ExecutionCheckpoint, ExecutionError, ExecutionResult, ExecutionSummary, IdentityProvider,
+    LiveBarrierProbe, NoopActionRunner, StageSummary, StaticIdentityProvider,
+    DEFAULT_BARRIER_TIMEOUT,
 };
 #[cfg(target_os = "linux")]
 pub use freeze::{is_freeze_available, FreezeActionRunner, FreezeConfig};
+pub use health_gate::{
+    check_host_health, HealthGateDecision, HealthViolation, HostHealthProbe, HostHealthSample,
+    LiveHostHealthProbe,
+};
 pub use recovery::{plan_recovery, ActionFailure, FailureKind, RecoveryDecision, RetryPolicy};
 pub use renice::{
     ReniceActionRunner, ReniceConfig, ReniceResult, ReniceReversalMetadata, DEFAULT_NICE_VALUE,
@@ -53,6 +75,10 @@ pub use supervisor::{
     SupervisorActionError, SupervisorActionResult, SupervisorActionRunner, SupervisorCommand,
     SupervisorParameters, SupervisorPlanAction, SupervisorType,
 };
+#[cfg(target_os = "linux")]
+pub use systemd_unit::{SystemdUnitActionConfig, SystemdUnitActionRunner};
+#[cfg(target_os = "windows")]
+pub use terminate::{TerminateActionRunner, TerminateConfig, WindowsIdentityProvider};
 
 #[cfg(target_os = "linux")]
 pub use prechecks::LivePreCheckProvider;
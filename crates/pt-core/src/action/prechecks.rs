@@ -7,14 +7,25 @@
 //! - `CheckDataLossGate`: Check for open write file descriptors
 //! - `CheckSupervisor`: Check for supervisor/systemd management
 //! - `CheckSessionSafety`: Verify session safety (not session leader, etc.)
+//! - `CheckFreezeScope`: Verify a cgroup freeze would not stop unrelated processes
+//! - `VerifyUnitMapping`: Verify the target is still the main process of the
+//!   systemd unit the action will stop or restart
+//! - `CheckForegroundWork`: Verify the target is not the foreground job of a
+//!   terminal the user typed into recently
 
+#[cfg(target_os = "linux")]
+use super::freeze::freeze_collateral;
+#[cfg(target_os = "linux")]
+use crate::collect::foreground::foreground_job;
 #[cfg(target_os = "linux")]
 use crate::collect::parse_io;
 use crate::collect::protected::ProtectedFilter;
 use crate::collect::systemd::{collect_systemd_unit, SystemdUnit, SystemdUnitType};
+#[cfg(target_os = "linux")]
+use crate::collect::systemd::{collect_systemd_unit_in, unit_of_pid};
 use crate::collect::ProcessState;
 use crate::config::policy::{DataLossGates, Guardrails};
-use crate::plan::PreCheck;
+use crate::plan::{PreCheck, UnitAction};
 use crate::supervision::session::{SessionAnalyzer, SessionConfig, SessionProtectionType};
 #[cfg(target_os = "linux")]
 use crate::supervision::{detect_supervision, is_human_supervised};
@@ -221,6 +232,26 @@ pub trait PreCheckProvider {
         PreCheckResult::Passed
     }
 
+    /// Check that freezing the process's cgroup is possible and contained.
+    ///
+    /// The cgroup freezer stops every task in the cgroup, so a cgroup shared
+    /// with processes outside the target's tree must not be frozen.
+    fn check_freeze_scope(&self, _pid: u32) -> PreCheckResult {
+        PreCheckResult::Passed
+    }
+
+    /// Check that the process is still the main process of `unit`'s unit,
+    /// under the same manager, so stopping the unit stops this process.
+    fn check_unit_mapping(&self, _pid: u32, _unit: &UnitAction) -> PreCheckResult {
+        PreCheckResult::Passed
+    }
+
+    /// Check that the process is not the foreground job of a terminal in
+    /// active use. Blocks regardless of the posterior: the user is watching it.
+    fn check_foreground_work(&self, _pid: u32) -> PreCheckResult {
+        PreCheckResult::Passed
+    }
+
     /// Run all applicable pre-checks for an action.
     fn run_checks(&self, checks: &[PreCheck], pid: u32, sid: Option<u32>) -> Vec<PreCheckResult> {
         checks
@@ -233,6 +264,10 @@ pub trait PreCheckProvider {
                 PreCheck::CheckAgentSupervision => Some(self.check_agent_supervision(pid)),
                 PreCheck::CheckSessionSafety => Some(self.check_session_safety(pid, sid)),
                 PreCheck::VerifyProcessState => Some(self.check_process_state(pid)),
+                PreCheck::CheckFreezeScope => Some(self.check_freeze_scope(pid)),
+                PreCheck::CheckForegroundWork => Some(self.check_foreground_work(pid)),
+                // Needs the planned unit; see check_unit_mapping
+                PreCheck::VerifyUnitMapping => None,
             })
             .collect()
     }
@@ -263,6 +298,10 @@ pub struct LivePreCheckConfig {
     pub protect_multiplexers: bool,
     /// Protect parent shells of pt.
     pub protect_parent_shells: bool,
+    /// Block kills of the foreground job of a terminal that read input recently.
+    pub protect_foreground_work: bool,
+    /// Keystroke recency, in seconds, for a terminal to count as in use.
+    pub foreground_idle_seconds: u64,
 }
 
 impl Default for LivePreCheckConfig {
@@ -279,6 +318,8 @@ impl Default for LivePreCheckConfig {
             protect_ssh_chains: true,
             protect_multiplexers: true,
             protect_parent_shells: true,
+            protect_foreground_work: true,
+            foreground_idle_seconds: 300,
         }
     }
 }
@@ -298,6 +339,8 @@ impl From<&DataLossGates> for LivePreCheckConfig {
             protect_ssh_chains: true,
             protect_multiplexers: true,
             protect_parent_shells: true,
+            protect_foreground_work: true,
+            foreground_idle_seconds: 300,
         }
     }
 }
@@ -944,6 +987,93 @@ impl PreCheckProvider for LivePreCheckProvider {
 
         PreCheckResult::Passed
     }
+
+    fn check_freeze_scope(&self, pid: u32) -> PreCheckResult {
+        trace!(pid, "checking cgroup freeze scope");
+
+        match freeze_collateral(pid) {
+            Ok(collateral) if collateral.is_empty() => PreCheckResult::Passed,
+            Ok(collateral) => {
+                debug!(
+                    pid,
+                    count = collateral.len(),
+                    "cgroup shared with other processes"
+                );
+                PreCheckResult::Blocked {
+                    check: PreCheck::CheckFreezeScope,
+                    reason: format!(
+                        "cgroup is shared with {} process(es) outside the target's tree \
+                         (e.g. PID {}); freezing it would stop them too",
+                        collateral.len(),
+                        collateral[0]
+                    ),
+                }
+            }
+            Err(e) => PreCheckResult::Blocked {
+                check: PreCheck::CheckFreezeScope,
+                reason: format!("cgroup freezer unavailable: {}", e),
+            },
+        }
+    }
+
+    fn check_foreground_work(&self, pid: u32) -> PreCheckResult {
+        if !self.config.protect_foreground_work {
+            return PreCheckResult::Passed;
+        }
+        trace!(pid, "checking foreground terminal work");
+
+        match foreground_job(pid) {
+            Some(job) if job.is_active(self.config.foreground_idle_seconds) => {
+                debug!(
+                    pid,
+                    tty = %job.tty,
+                    idle_secs = ?job.idle_secs,
+                    "foreground job of an active terminal"
+                );
+                PreCheckResult::Blocked {
+                    check: PreCheck::CheckForegroundWork,
+                    reason: format!(
+                        "process is the {}: killing it would interrupt interactive work",
+                        job.summary()
+                    ),
+                }
+            }
+            _ => PreCheckResult::Passed,
+        }
+    }
+
+    fn check_unit_mapping(&self, pid: u32, unit: &UnitAction) -> PreCheckResult {
+        trace!(pid, unit = %unit.unit, "checking systemd unit mapping");
+
+        let blocked = |reason: String| PreCheckResult::Blocked {
+            check: PreCheck::VerifyUnitMapping,
+            reason,
+        };
+        match unit_of_pid(pid) {
+            Some((name, manager)) if name == unit.unit && manager == unit.manager => {}
+            Some((name, _)) => {
+                return blocked(format!(
+                    "process now belongs to {} instead of {}",
+                    name, unit.unit
+                ))
+            }
+            None => return blocked(format!("process no longer belongs to {}", unit.unit)),
+        }
+
+        match collect_systemd_unit_in(pid, &unit.unit, unit.manager) {
+            Some(info) if info.main_pid == Some(pid) => PreCheckResult::Passed,
+            Some(info) => blocked(format!(
+                "{} main PID is now {}; refusing to {} it for PID {}",
+                unit.unit,
+                info.main_pid
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "unset".to_string()),
+                unit.command.as_str(),
+                pid
+            )),
+            None => blocked(format!("could not query {} from its manager", unit.unit)),
+        }
+    }
 }
 
 /// No-op pre-check provider (all checks pass).
@@ -1390,6 +1520,8 @@ mod tests {
             block_if_deleted_cwd: None,
             block_if_active_tty: false,
             block_if_recent_io_seconds: None,
+            block_if_serving_clients: None,
+            max_blast_radius_score: None,
         };
         let config = LivePreCheckConfig::from(&gates);
         assert!(config.block_if_open_write_fds);
@@ -1411,6 +1543,8 @@ mod tests {
             block_if_deleted_cwd: Some(false),
             block_if_active_tty: true,
             block_if_recent_io_seconds: Some(120),
+            block_if_serving_clients: None,
+            max_blast_radius_score: None,
         };
         let config = LivePreCheckConfig::from(&gates);
         assert!(!config.block_if_open_write_fds);
@@ -1702,6 +1836,13 @@ mod tests {
             }
         }
 
+        #[test]
+        fn live_provider_foreground_work_skips_detached_process() {
+            let provider = LivePreCheckProvider::with_defaults();
+            // PID 1 never has a controlling terminal.
+            assert!(provider.check_foreground_work(1).is_passed());
+        }
+
         #[test]
         fn live_provider_data_loss_disabled_config() {
             let config = LivePreCheckConfig {
@@ -1716,6 +1857,8 @@ mod tests {
                 protect_ssh_chains: false,
                 protect_multiplexers: false,
                 protect_parent_shells: false,
+                protect_foreground_work: false,
+                foreground_idle_seconds: 0,
             };
             let provider = LivePreCheckProvider::new(None, config).unwrap();
             let pid = std::process::id();
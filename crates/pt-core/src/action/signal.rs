@@ -1,6 +1,7 @@
 //! Signal-based action execution.
 //!
-//! Implements the actual signal delivery for pause/resume/kill actions with:
+//! Implements the actual signal delivery for pause/resume/kill actions, and
+//! SIGCHLD to the parent for zombie reaping, with:
 //! - TOCTOU safety via identity revalidation
 //! - Staged escalation (SIGTERM → SIGKILL)
 //! - Process group awareness
@@ -8,7 +9,7 @@
 
 use super::executor::{ActionError, ActionRunner};
 use crate::decision::Action;
-use crate::plan::PlanAction;
+use crate::plan::{ActionRouting, PlanAction};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -51,9 +52,13 @@ impl SignalActionRunner {
         Self::new(SignalConfig::default())
     }
 
-    fn resolve_group_target(&self, pid: u32, pgid: Option<u32>) -> (u32, bool) {
-        let pgid = pgid.filter(|pgid| *pgid > 0);
-        let use_group = self.config.use_process_groups && pgid.is_some();
+    /// Signal the process group when configured, or when the plan routed the
+    /// action to the whole group.
+    fn resolve_group_target(&self, action: &PlanAction) -> (u32, bool) {
+        let pid = action.target.pid.0;
+        let pgid = action.target.pgid.filter(|pgid| *pgid > 0);
+        let group_routed = action.routing == ActionRouting::ProcessGroup;
+        let use_group = (self.config.use_process_groups || group_routed) && pgid.is_some();
         let target = if use_group { pgid.unwrap() } else { pid };
         (target, use_group)
     }
@@ -171,8 +176,7 @@ impl SignalActionRunner {
     /// Execute a pause action (SIGSTOP).
     #[cfg(unix)]
     fn execute_pause(&self, action: &PlanAction) -> Result<(), ActionError> {
-        let pid = action.target.pid.0;
-        let (target, use_group) = self.resolve_group_target(pid, action.target.pgid);
+        let (target, use_group) = self.resolve_group_target(action);
 
         self.send_signal(target, libc::SIGSTOP, use_group)?;
         Ok(())
@@ -182,10 +186,15 @@ impl SignalActionRunner {
     #[cfg(unix)]
     fn execute_kill(&self, action: &PlanAction) -> Result<(), ActionError> {
         let pid = action.target.pid.0;
-        let (target, use_group) = self.resolve_group_target(pid, action.target.pgid);
+        let (target, use_group) = self.resolve_group_target(action);
 
         // Stage 1: SIGTERM
         self.send_signal(target, libc::SIGTERM, use_group)?;
+        // A stopped target (paused stage, staged kill) only handles SIGTERM
+        // once continued; without this it would always escalate to SIGKILL.
+        if self.get_process_state(pid).is_none_or(|state| state == 'T') {
+            let _ = self.send_signal(target, libc::SIGCONT, use_group);
+        }
 
         // Wait for graceful termination
         let grace = Duration::from_millis(self.config.term_grace_ms);
@@ -238,6 +247,55 @@ impl SignalActionRunner {
         self.wait_for_state_change(pid, true, None, timeout)
     }
 
+    /// Execute a zombie reap action: SIGCHLD to the zombie's parent.
+    ///
+    /// SIGCHLD is ignored by default, so a parent without a handler is left
+    /// untouched; one with a handler gets another chance to `wait()`.
+    #[cfg(unix)]
+    fn execute_reap(&self, action: &PlanAction) -> Result<(), ActionError> {
+        self.send_signal(action.target.pid.0, libc::SIGCHLD, false)
+    }
+
+    /// Verify a zombie reap action succeeded: the zombie is gone (or its PID
+    /// now belongs to a live process).
+    #[cfg(unix)]
+    fn verify_reap(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let Some(zombie) = action.original_zombie_target.as_ref() else {
+            return Ok(());
+        };
+        let pid = zombie.pid.0;
+        let start = Instant::now();
+        let timeout = Duration::from_millis(self.config.verify_timeout_ms);
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+
+        while start.elapsed() < timeout {
+            if !self.process_exists(pid) {
+                return Ok(());
+            }
+            if self
+                .get_process_state(pid)
+                .is_some_and(|state| state != 'Z')
+            {
+                return Ok(());
+            }
+            thread::sleep(poll_interval);
+        }
+
+        Err(ActionError::Failed(format!(
+            "zombie PID {} was not reaped; the parent does not wait() for its children",
+            pid
+        )))
+    }
+
+    /// Execute a pause action (SIGSTOP) - raw version.
+    #[cfg(unix)]
+    pub fn pause(&self, pid: u32, use_group: bool, pgid: Option<u32>) -> Result<(), ActionError> {
+        let pgid = pgid.filter(|pgid| *pgid > 0);
+        let use_group = use_group && pgid.is_some();
+        let target = if use_group { pgid.unwrap() } else { pid };
+        self.send_signal(target, libc::SIGSTOP, use_group)
+    }
+
     /// Execute a resume action (SIGCONT) - raw version.
     #[cfg(unix)]
     pub fn resume(&self, pid: u32, use_group: bool, pgid: Option<u32>) -> Result<(), ActionError> {
@@ -257,8 +315,7 @@ impl SignalActionRunner {
     /// Execute a resume action (SIGCONT) from PlanAction.
     #[cfg(unix)]
     fn execute_resume(&self, action: &PlanAction) -> Result<(), ActionError> {
-        let pid = action.target.pid.0;
-        let (target, use_group) = self.resolve_group_target(pid, action.target.pgid);
+        let (target, use_group) = self.resolve_group_target(action);
 
         self.send_signal(target, libc::SIGCONT, use_group)?;
         Ok(())
@@ -282,6 +339,9 @@ impl ActionRunner for SignalActionRunner {
             Action::Resume => self.execute_resume(action),
             Action::Kill => self.execute_kill(action),
             Action::Keep => Ok(()),
+            Action::Restart if action.routing == ActionRouting::ZombieToParent => {
+                self.execute_reap(action)
+            }
             Action::Throttle => {
                 // Throttle requires cgroup operations, not signals
                 Err(ActionError::Failed(
@@ -321,6 +381,9 @@ impl ActionRunner for SignalActionRunner {
             Action::Resume => self.verify_resume(action),
             Action::Kill => self.verify_kill(action),
             Action::Keep => Ok(()),
+            Action::Restart if action.routing == ActionRouting::ZombieToParent => {
+                self.verify_reap(action)
+            }
             Action::Throttle
             | Action::Restart
             | Action::Renice
@@ -0,0 +1,184 @@
+//! systemd unit execution of plan actions.
+//!
+//! Plan actions that carry a [`UnitAction`] (kills and restarts of a
+//! service's main process, see [`crate::plan::unit`]) are carried out with
+//! `systemctl stop` / `systemctl restart` on the unit instead of a signal,
+//! so the unit's restart policy and state stay consistent.
+//!
+//! Units of a user's manager go through `systemctl --user`; another user's
+//! manager is reached with `--machine=<uid>@.host`, which requires root.
+
+use super::executor::{ActionError, ActionRunner};
+use crate::collect::systemd::{collect_systemd_unit_in, SystemdActiveState};
+use crate::collect::tool_runner::{ToolConfig, ToolError, ToolRunner};
+use crate::plan::{PlanAction, UnitAction, UnitCommand};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// systemd unit action runner configuration.
+#[derive(Debug, Clone)]
+pub struct SystemdUnitActionConfig {
+    /// Time allowed for `systemctl` to return. `stop` waits for the unit's
+    /// own stop timeout (90s unless the unit sets `TimeoutStopSec=`).
+    pub command_timeout_ms: u64,
+    /// Maximum time to wait for the unit to reach the expected state.
+    pub verify_timeout_ms: u64,
+    /// Polling interval while waiting for a state change.
+    pub poll_interval_ms: u64,
+}
+
+impl Default for SystemdUnitActionConfig {
+    fn default() -> Self {
+        Self {
+            command_timeout_ms: 100_000,
+            verify_timeout_ms: 10_000,
+            poll_interval_ms: 200,
+        }
+    }
+}
+
+/// `systemctl` arguments for `unit` when run as `caller_uid`.
+pub fn systemctl_args(unit: &UnitAction, caller_uid: u32) -> Vec<String> {
+    let mut args = unit.manager.systemctl_args(caller_uid);
+    args.push(unit.command.as_str().to_string());
+    args.push(unit.unit.clone());
+    args
+}
+
+/// Runs stop/restart of systemd units for plan actions with a `unit_action`.
+#[derive(Debug)]
+pub struct SystemdUnitActionRunner {
+    config: SystemdUnitActionConfig,
+}
+
+impl SystemdUnitActionRunner {
+    pub fn new(config: SystemdUnitActionConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(SystemdUnitActionConfig::default())
+    }
+
+    fn unit_action(action: &PlanAction) -> Result<&UnitAction, ActionError> {
+        action.unit_action.as_ref().ok_or_else(|| {
+            ActionError::Failed(format!(
+                "action {} has no systemd unit to act on",
+                action.action_id
+            ))
+        })
+    }
+
+    /// Whether the unit reached the state `command` leads to.
+    fn reached(&self, unit: &UnitAction, old_pid: u32) -> bool {
+        let info = collect_systemd_unit_in(old_pid, &unit.unit, unit.manager);
+        match unit.command {
+            // Transient units disappear once stopped
+            UnitCommand::Stop => info.is_none_or(|info| {
+                matches!(
+                    info.active_state,
+                    SystemdActiveState::Inactive | SystemdActiveState::Failed
+                )
+            }),
+            UnitCommand::Restart => info.is_some_and(|info| {
+                info.active_state.is_running() && info.main_pid.is_some_and(|pid| pid != old_pid)
+            }),
+        }
+    }
+}
+
+impl ActionRunner for SystemdUnitActionRunner {
+    fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let unit = Self::unit_action(action)?;
+        let args = systemctl_args(unit, unsafe { libc::geteuid() });
+        debug!(
+            pid = action.target.pid.0,
+            unit = %unit.unit,
+            "unit action: systemctl {}",
+            args.join(" ")
+        );
+
+        let timeout = Duration::from_millis(self.config.command_timeout_ms);
+        let runner = ToolRunner::new(ToolConfig {
+            default_timeout: timeout,
+            budget_ms: self.config.command_timeout_ms,
+            use_nice: false,
+            use_ionice: false,
+            ..ToolConfig::default()
+        });
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = runner
+            .run_tool("systemctl", &arg_refs, None)
+            .map_err(|e| match e {
+                ToolError::Timeout(_) => ActionError::Timeout,
+                other => ActionError::Failed(format!("systemctl: {}", other)),
+            })?;
+        if output.timed_out {
+            return Err(ActionError::Timeout);
+        }
+        if output.success() {
+            return Ok(());
+        }
+        let stderr = output.stderr_str();
+        let lower = stderr.to_ascii_lowercase();
+        if lower.contains("access denied") || lower.contains("authentication required") {
+            return Err(ActionError::PermissionDenied);
+        }
+        Err(ActionError::Failed(format!(
+            "systemctl {} {} failed: {}",
+            unit.command.as_str(),
+            unit.unit,
+            stderr.trim()
+        )))
+    }
+
+    fn verify(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let unit = Self::unit_action(action)?;
+        let deadline = Instant::now() + Duration::from_millis(self.config.verify_timeout_ms);
+        loop {
+            if self.reached(unit, action.target.pid.0) {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(ActionError::Failed(format!(
+                    "{} did not {} in time",
+                    unit.unit,
+                    unit.command.as_str()
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(self.config.poll_interval_ms));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::SystemdManager;
+
+    #[test]
+    fn systemctl_args_address_the_owning_manager() {
+        let mut unit = UnitAction {
+            unit: "indexer.service".to_string(),
+            manager: SystemdManager::System,
+            command: UnitCommand::Stop,
+        };
+        assert_eq!(systemctl_args(&unit, 0), vec!["stop", "indexer.service"]);
+
+        unit.manager = SystemdManager::User { uid: 1000 };
+        unit.command = UnitCommand::Restart;
+        assert_eq!(
+            systemctl_args(&unit, 1000),
+            vec!["--user", "restart", "indexer.service"]
+        );
+        assert_eq!(
+            systemctl_args(&unit, 0),
+            vec![
+                "--user",
+                "--machine=1000@.host",
+                "restart",
+                "indexer.service"
+            ]
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Windows action execution via `TerminateProcess` and NT suspend/resume.
+//!
+//! Windows has no signals. The signal-equivalent actions map to:
+//! - kill → `TerminateProcess`
+//! - pause → `NtSuspendProcess`
+//! - resume → `NtResumeProcess`
+//!
+//! Identity is verified with the same semantics as [`SignalActionRunner`] on
+//! Linux: the process's current start time must match the plan's `start_id`
+//! before it is touched. The check is made through the handle that is then
+//! used for the action, and an open handle pins the process object, so the
+//! PID cannot be recycled between the check and the call.
+//!
+//! [`SignalActionRunner`]: super::signal::SignalActionRunner
+
+use super::executor::{ActionError, ActionRunner, IdentityProvider};
+use crate::collect::windows::{
+    native_start_id, Handle, ProcessHandle, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SUSPEND_RESUME, PROCESS_TERMINATE, SYNCHRONIZE,
+};
+use crate::decision::Action;
+use crate::plan::PlanAction;
+use pt_common::ProcessIdentity;
+
+const WAIT_OBJECT_0: u32 = 0;
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn TerminateProcess(handle: Handle, exit_code: u32) -> i32;
+    fn WaitForSingleObject(handle: Handle, timeout_ms: u32) -> u32;
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtSuspendProcess(handle: Handle) -> i32;
+    fn NtResumeProcess(handle: Handle) -> i32;
+}
+
+/// Terminate action runner configuration.
+#[derive(Debug, Clone)]
+pub struct TerminateConfig {
+    /// Exit code reported for terminated processes.
+    pub exit_code: u32,
+    /// Maximum time to wait for the process to exit after termination.
+    pub verify_timeout_ms: u64,
+}
+
+impl Default for TerminateConfig {
+    fn default() -> Self {
+        Self {
+            exit_code: 1,
+            verify_timeout_ms: 10_000,
+        }
+    }
+}
+
+/// `TerminateProcess`-based action runner for Windows.
+#[derive(Debug)]
+pub struct TerminateActionRunner {
+    config: TerminateConfig,
+}
+
+impl TerminateActionRunner {
+    pub fn new(config: TerminateConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self::new(TerminateConfig::default())
+    }
+
+    /// Open the target with `access` and confirm it is the planned process.
+    fn open_verified(
+        &self,
+        action: &PlanAction,
+        access: u32,
+    ) -> Result<ProcessHandle, ActionError> {
+        let pid = action.target.pid.0;
+        let handle = ProcessHandle::open(pid, access | PROCESS_QUERY_LIMITED_INFORMATION)
+            .map_err(map_open_error)?;
+        let expected = creation_time_from_start_id(&action.target.start_id.0);
+        match (expected, handle.creation_time()) {
+            (Some(expected), Some(current)) if expected == current => Ok(handle),
+            _ => Err(ActionError::IdentityMismatch),
+        }
+    }
+
+    fn execute_kill(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let handle = self.open_verified(action, PROCESS_TERMINATE)?;
+        if unsafe { TerminateProcess(handle.raw(), self.config.exit_code) } == 0 {
+            return Err(map_os_error(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    fn execute_pause(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let handle = self.open_verified(action, PROCESS_SUSPEND_RESUME)?;
+        nt_result(
+            unsafe { NtSuspendProcess(handle.raw()) },
+            "NtSuspendProcess",
+        )
+    }
+
+    fn execute_resume(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let handle = self.open_verified(action, PROCESS_SUSPEND_RESUME)?;
+        nt_result(unsafe { NtResumeProcess(handle.raw()) }, "NtResumeProcess")
+    }
+
+    /// Verify a kill: the planned process must have exited.
+    ///
+    /// A missing PID, or one now owned by a different process, means the
+    /// original has exited.
+    fn verify_kill(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let handle = match self.open_verified(action, SYNCHRONIZE) {
+            Ok(handle) => handle,
+            Err(ActionError::IdentityMismatch) => return Ok(()),
+            Err(ActionError::Failed(msg)) if msg == PROCESS_NOT_FOUND => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let timeout = self.config.verify_timeout_ms.min(u32::MAX as u64 - 1) as u32;
+        match unsafe { WaitForSingleObject(handle.raw(), timeout) } {
+            WAIT_OBJECT_0 => Ok(()),
+            _ => Err(ActionError::Timeout),
+        }
+    }
+
+    /// Verify a pause or resume: the planned process must still be alive.
+    ///
+    /// Windows exposes no cheap "suspended" state for a process, so the NT
+    /// call succeeding is taken as the state change.
+    fn verify_alive(&self, action: &PlanAction) -> Result<(), ActionError> {
+        let handle = self.open_verified(action, 0)?;
+        if handle.is_running() {
+            Ok(())
+        } else {
+            Err(ActionError::Failed("process exited".to_string()))
+        }
+    }
+}
+
+impl ActionRunner for TerminateActionRunner {
+    fn execute(&self, action: &PlanAction) -> Result<(), ActionError> {
+        match action.action {
+            Action::Pause => self.execute_pause(action),
+            Action::Resume => self.execute_resume(action),
+            Action::Kill => self.execute_kill(action),
+            Action::Keep => Ok(()),
+            _ => Err(ActionError::Failed(
+                "action not supported on this platform".to_string(),
+            )),
+        }
+    }
+
+    fn verify(&self, action: &PlanAction) -> Result<(), ActionError> {
+        match action.action {
+            Action::Pause | Action::Resume => self.verify_alive(action),
+            Action::Kill => self.verify_kill(action),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Live identity provider backed by process handles.
+///
+/// Windows has no numeric UIDs, so only the `start_id` is revalidated.
+#[derive(Debug, Default)]
+pub struct WindowsIdentityProvider;
+
+impl WindowsIdentityProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl IdentityProvider for WindowsIdentityProvider {
+    fn revalidate(&self, target: &ProcessIdentity) -> Result<bool, ActionError> {
+        let Some((current, _)) = native_start_id(target.pid.0) else {
+            return Ok(false); // Process gone or not inspectable
+        };
+        Ok(matches!(
+            (
+                creation_time_from_start_id(&target.start_id.0),
+                creation_time_from_start_id(&current.0),
+            ),
+            (Some(expected), Some(current)) if expected == current
+        ))
+    }
+}
+
+const PROCESS_NOT_FOUND: &str = "process not found";
+
+/// Extract the creation time from a `boot:creation:pid` start_id.
+///
+/// Unlike Linux start ticks, creation time is exact, so no tolerance is
+/// applied. A zero creation time (process could not be opened at scan time)
+/// never matches.
+fn creation_time_from_start_id(start_id: &str) -> Option<u64> {
+    let parts: Vec<&str> = start_id.split(':').collect();
+    let creation = match parts.len() {
+        1 => parts[0],
+        3 => parts[1],
+        _ => return None,
+    };
+    creation.parse::<u64>().ok().filter(|&t| t > 0)
+}
+
+fn map_open_error(err: std::io::Error) -> ActionError {
+    match err.raw_os_error() {
+        Some(ERROR_INVALID_PARAMETER) => ActionError::Failed(PROCESS_NOT_FOUND.to_string()),
+        _ => map_os_error(err),
+    }
+}
+
+fn map_os_error(err: std::io::Error) -> ActionError {
+    match err.raw_os_error() {
+        Some(ERROR_ACCESS_DENIED) => ActionError::PermissionDenied,
+        _ => ActionError::Failed(err.to_string()),
+    }
+}
+
+fn nt_result(status: i32, call: &str) -> Result<(), ActionError> {
+    const STATUS_ACCESS_DENIED: i32 = 0xC000_0022u32 as i32;
+    match status {
+        s if s >= 0 => Ok(()),
+        STATUS_ACCESS_DENIED => Err(ActionError::PermissionDenied),
+        s => Err(ActionError::Failed(format!(
+            "{} failed: NTSTATUS {:#010x}",
+            call, s as u32
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creation_time_parsed_from_start_id() {
+        assert_eq!(
+            creation_time_from_start_id(
+                "00000000-0000-0000-0000-0000659a1c00:133485408000000000:42"
+            ),
+            Some(133_485_408_000_000_000)
+        );
+        assert_eq!(creation_time_from_start_id("boot:0:42"), None);
+        assert_eq!(creation_time_from_start_id("a:b"), None);
+    }
+
+    #[test]
+    fn nt_status_mapping() {
+        assert!(nt_result(0, "NtSuspendProcess").is_ok());
+        assert!(matches!(
+            nt_result(0xC000_0022u32 as i32, "NtSuspendProcess"),
+            Err(ActionError::PermissionDenied)
+        ));
+        assert!(matches!(
+            nt_result(0xC000_0001u32 as i32, "NtSuspendProcess"),
+            Err(ActionError::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn identity_provider_accepts_self_and_rejects_wrong_start() {
+        let pid = std::process::id();
+        let (start_id, quality) = native_start_id(pid).expect("start_id");
+        let provider = WindowsIdentityProvider::new();
+
+        let mut target = ProcessIdentity::new(pid, start_id.clone(), 0);
+        target.quality = quality;
+        assert!(provider.revalidate(&target).unwrap());
+
+        target.start_id = pt_common::StartId(format!("boot:1:{}", pid));
+        assert!(!provider.revalidate(&target).unwrap());
+    }
+}
@@ -33,6 +33,8 @@ pub enum AuditEventType {
     Session,
     /// Log rotation checkpoint.
     Checkpoint,
+    /// Operator overrode a policy block for a single action.
+    Override,
 }
 
 impl std::fmt::Display for AuditEventType {
@@ -45,6 +47,7 @@ impl std::fmt::Display for AuditEventType {
             AuditEventType::Error => "error",
             AuditEventType::Session => "session",
             AuditEventType::Checkpoint => "checkpoint",
+            AuditEventType::Override => "override",
         };
         write!(f, "{}", s)
     }
@@ -297,6 +300,24 @@ pub struct PolicyCheckDetails {
     pub guardrail: Option<String>,
 }
 
+/// Details for operator override events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverrideDetails {
+    /// Identity of the operator who approved the override.
+    pub operator: String,
+    /// Free-text justification entered by the operator.
+    pub justification: String,
+    /// Action that was approved.
+    pub action: String,
+    /// Target process ID.
+    pub pid: u32,
+    /// Target process start ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_id: Option<String>,
+    /// Constraints that had blocked the action.
+    pub overridden: Vec<String>,
+}
+
 /// Details for error events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorDetails {
@@ -428,5 +449,6 @@ mod tests {
         assert_eq!(AuditEventType::Action.to_string(), "action");
         assert_eq!(AuditEventType::PolicyCheck.to_string(), "policy_check");
         assert_eq!(AuditEventType::Checkpoint.to_string(), "checkpoint");
+        assert_eq!(AuditEventType::Override.to_string(), "override");
     }
 }
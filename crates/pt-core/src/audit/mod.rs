@@ -46,7 +46,7 @@ mod writer;
 
 pub use entry::{
     ActionDetails, AuditContext, AuditEntry, AuditEventType, CheckpointDetails, ErrorDetails,
-    PolicyCheckDetails, RecommendDetails, ScanDetails, AUDIT_SCHEMA_VERSION,
+    OverrideDetails, PolicyCheckDetails, RecommendDetails, ScanDetails, AUDIT_SCHEMA_VERSION,
 };
 pub use verify::{
     verify_log, verify_log_chain, BreakType, BrokenLink, SchemaWarning, TamperedEntry,
@@ -5,7 +5,7 @@
 
 use super::entry::{
     ActionDetails, AuditContext, AuditEntry, AuditEventType, CheckpointDetails, ErrorDetails,
-    PolicyCheckDetails, RecommendDetails, ScanDetails,
+    OverrideDetails, PolicyCheckDetails, RecommendDetails, ScanDetails,
 };
 use super::{resolve_audit_dir, AuditError, AUDIT_LOG_FILENAME};
 use chrono::Utc;
@@ -309,6 +309,27 @@ impl AuditLog {
         self.write_entry(entry)
     }
 
+    /// Log an operator override of a policy block.
+    pub fn log_override(
+        &mut self,
+        ctx: &AuditContext,
+        details: &OverrideDetails,
+    ) -> Result<(), AuditError> {
+        let message = format!(
+            "Operator {} overrode {} for {} on PID {}: {}",
+            details.operator,
+            details.overridden.join(", "),
+            details.action,
+            details.pid,
+            details.justification
+        );
+
+        let entry = AuditEntry::new(ctx, AuditEventType::Override, message, &self.last_hash)
+            .with_details(details);
+
+        self.write_entry(entry)
+    }
+
     /// Log an error.
     pub fn log_error(
         &mut self,
@@ -688,6 +709,31 @@ mod tests {
         assert!(content.contains("systemd is protected"));
     }
 
+    #[test]
+    fn test_audit_log_override() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path());
+
+        let mut log = AuditLog::open_or_create_with_config(config).unwrap();
+        let ctx = AuditContext::new("run-test", "host-test");
+
+        let details = OverrideDetails {
+            operator: "alice (uid 1000)".to_string(),
+            justification: "stuck build, confirmed with owner".to_string(),
+            action: "kill".to_string(),
+            pid: 4242,
+            start_id: Some("boot:123:4242".to_string()),
+            overridden: vec!["max_kills".to_string()],
+        };
+        log.log_override(&ctx, &details).unwrap();
+
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        assert!(content.contains(r#""event_type":"override""#));
+        assert!(content.contains(r#""operator":"alice (uid 1000)""#));
+        assert!(content.contains("stuck build, confirmed with owner"));
+        assert!(crate::audit::verify_log(log.path()).unwrap().is_valid);
+    }
+
     #[test]
     fn test_audit_log_checkpoint() {
         let tmp = TempDir::new().unwrap();
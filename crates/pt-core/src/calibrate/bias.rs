@@ -7,11 +7,12 @@
 //! - Host-specific effects
 
 use super::{CalibrationData, CalibrationError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Detected bias in a specific stratum.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BiasResult {
     /// Name of the stratum (e.g., "test_runner", "high_confidence").
     pub stratum: String,
@@ -30,7 +31,7 @@ pub struct BiasResult {
 }
 
 /// Summary of bias analysis across all strata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BiasAnalysis {
     /// Overall bias (mean predicted - actual rate).
     pub overall_bias: f64,
@@ -3,10 +3,11 @@
 //! Generates reliability diagrams and calibration curves for visualization.
 
 use super::CalibrationData;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A single point on a calibration curve.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CalibrationBin {
     /// Bin lower bound (inclusive).
     pub lower: f64,
@@ -23,7 +24,7 @@ pub struct CalibrationBin {
 }
 
 /// A calibration curve (reliability diagram data).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CalibrationCurve {
     /// Number of bins used.
     pub num_bins: usize,
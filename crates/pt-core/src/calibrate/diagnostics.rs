@@ -0,0 +1,427 @@
+//! Calibration diagnostics for shadow-mode reports.
+//!
+//! Bundles the reliability diagram, ECE/MCE, ROC and precision-recall curves,
+//! and the Murphy decomposition of the Brier score:
+//!
+//! `brier ≈ reliability − resolution + uncertainty`
+//!
+//! The decomposition uses the reliability-diagram bins, so it is exact only
+//! when predictions are constant within each bin; the gap is the within-bin
+//! variance of the predictions.
+
+use super::curve::CalibrationCurve;
+use super::CalibrationData;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Maximum points kept per ROC/PR curve; areas use every threshold.
+const MAX_CURVE_POINTS: usize = 200;
+
+/// A point on the ROC curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RocPoint {
+    /// Predictions `>= threshold` count as positive.
+    pub threshold: f64,
+    /// False positive rate.
+    pub fpr: f64,
+    /// True positive rate (recall).
+    pub tpr: f64,
+}
+
+/// A point on the precision-recall curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PrPoint {
+    /// Predictions `>= threshold` count as positive.
+    pub threshold: f64,
+    pub recall: f64,
+    pub precision: f64,
+}
+
+/// Murphy decomposition of the Brier score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BrierDecomposition {
+    /// Brier score computed directly from the predictions.
+    pub brier: f64,
+    /// Squared gap between predicted and observed rates (lower is better).
+    pub reliability: f64,
+    /// Spread of observed bin rates around the base rate (higher is better).
+    pub resolution: f64,
+    /// Base-rate variance `ō(1 − ō)`; fixed by the data.
+    pub uncertainty: f64,
+}
+
+impl BrierDecomposition {
+    /// Decompose the Brier score over the bins of `curve`.
+    pub fn from_data(data: &[CalibrationData], curve: &CalibrationCurve) -> Self {
+        if data.is_empty() {
+            return Self {
+                brier: 0.0,
+                reliability: 0.0,
+                resolution: 0.0,
+                uncertainty: 0.0,
+            };
+        }
+        let n = data.len() as f64;
+        let base_rate = data.iter().filter(|d| d.actual).count() as f64 / n;
+        let brier = data
+            .iter()
+            .map(|d| {
+                let outcome = if d.actual { 1.0 } else { 0.0 };
+                (d.predicted - outcome).powi(2)
+            })
+            .sum::<f64>()
+            / n;
+
+        let mut reliability = 0.0;
+        let mut resolution = 0.0;
+        for bin in curve.bins.iter().filter(|b| b.count > 0) {
+            let weight = bin.count as f64 / n;
+            reliability += weight * (bin.mean_predicted - bin.actual_rate).powi(2);
+            resolution += weight * (bin.actual_rate - base_rate).powi(2);
+        }
+
+        Self {
+            brier,
+            reliability,
+            resolution,
+            uncertainty: base_rate * (1.0 - base_rate),
+        }
+    }
+}
+
+/// Full calibration diagnostics for a set of resolved predictions.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalibrationDiagnostics {
+    pub sample_count: usize,
+    pub positive_count: usize,
+    /// Reliability diagram bins, with ECE and MCE.
+    pub reliability: CalibrationCurve,
+    pub brier: BrierDecomposition,
+    /// ROC curve, by decreasing threshold.
+    pub roc: Vec<RocPoint>,
+    pub auc_roc: f64,
+    /// Precision-recall curve, by decreasing threshold.
+    pub pr: Vec<PrPoint>,
+    /// Average precision (step-wise area under the PR curve).
+    pub average_precision: f64,
+}
+
+impl CalibrationDiagnostics {
+    /// Compute diagnostics with `num_bins` reliability bins.
+    pub fn from_data(data: &[CalibrationData], num_bins: usize) -> Self {
+        let reliability = CalibrationCurve::from_data(data, num_bins);
+        let brier = BrierDecomposition::from_data(data, &reliability);
+        let (roc, pr) = threshold_sweep(data);
+        let auc_roc = roc_area(&roc);
+        let average_precision = average_precision(&pr);
+        Self {
+            sample_count: data.len(),
+            positive_count: data.iter().filter(|d| d.actual).count(),
+            reliability,
+            brier,
+            roc: thin(roc),
+            auc_roc,
+            pr: thin(pr),
+            average_precision,
+        }
+    }
+
+    /// Render as a self-contained HTML `<section>` with inline SVG plots.
+    pub fn html_section(&self) -> String {
+        let mut html = String::new();
+        html.push_str("<section class=\"calibration-diagnostics\">\n");
+        html.push_str("<h2>Calibration diagnostics</h2>\n");
+
+        html.push_str("<table class=\"summary\">\n");
+        let rows = [
+            ("Samples", self.sample_count.to_string()),
+            ("Positives", self.positive_count.to_string()),
+            ("ECE", format!("{:.4}", self.reliability.ece)),
+            ("MCE", format!("{:.4}", self.reliability.mce)),
+            ("Brier score", format!("{:.4}", self.brier.brier)),
+            ("Reliability", format!("{:.4}", self.brier.reliability)),
+            ("Resolution", format!("{:.4}", self.brier.resolution)),
+            ("Uncertainty", format!("{:.4}", self.brier.uncertainty)),
+            ("AUC-ROC", format!("{:.4}", self.auc_roc)),
+            (
+                "Average precision",
+                format!("{:.4}", self.average_precision),
+            ),
+        ];
+        for (label, value) in rows {
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", label, value);
+        }
+        html.push_str("</table>\n");
+
+        let reliability: Vec<(f64, f64)> = self
+            .reliability
+            .bins
+            .iter()
+            .filter(|b| b.count > 0)
+            .map(|b| (b.mean_predicted, b.actual_rate))
+            .collect();
+        let mut roc = vec![(0.0, 0.0)];
+        roc.extend(self.roc.iter().map(|p| (p.fpr, p.tpr)));
+        let pr: Vec<(f64, f64)> = self.pr.iter().map(|p| (p.recall, p.precision)).collect();
+
+        html.push_str("<div class=\"plots\">\n");
+        html.push_str(&svg_plot(
+            "Reliability diagram",
+            "Predicted",
+            "Observed",
+            true,
+            &reliability,
+        ));
+        html.push_str(&svg_plot("ROC curve", "FPR", "TPR", true, &roc));
+        html.push_str(&svg_plot(
+            "Precision-recall curve",
+            "Recall",
+            "Precision",
+            false,
+            &pr,
+        ));
+        html.push_str("</div>\n");
+
+        html.push_str("<table class=\"bins\">\n");
+        html.push_str(
+            "<tr><th>Bin</th><th>Count</th><th>Mean predicted</th><th>Observed rate</th><th>Error</th></tr>\n",
+        );
+        for bin in &self.reliability.bins {
+            let _ = writeln!(
+                html,
+                "<tr><td>{:.2}–{:.2}</td><td>{}</td><td>{:.3}</td><td>{:.3}</td><td>{:.3}</td></tr>",
+                bin.lower, bin.upper, bin.count, bin.mean_predicted, bin.actual_rate, bin.error
+            );
+        }
+        html.push_str("</table>\n");
+        html.push_str("</section>\n");
+        html
+    }
+}
+
+/// ROC and PR points at every distinct prediction, by decreasing threshold.
+fn threshold_sweep(data: &[CalibrationData]) -> (Vec<RocPoint>, Vec<PrPoint>) {
+    let positives = data.iter().filter(|d| d.actual).count() as f64;
+    let negatives = data.len() as f64 - positives;
+    if positives == 0.0 || negatives == 0.0 {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut sorted: Vec<&CalibrationData> = data.iter().collect();
+    sorted.sort_by(|a, b| b.predicted.total_cmp(&a.predicted));
+
+    let mut roc = Vec::new();
+    let mut pr = Vec::new();
+    let (mut tp, mut fp) = (0.0, 0.0);
+    for (i, d) in sorted.iter().enumerate() {
+        if d.actual {
+            tp += 1.0;
+        } else {
+            fp += 1.0;
+        }
+        // Emit once per distinct threshold, after all ties are counted.
+        if sorted.get(i + 1).map(|next| next.predicted) == Some(d.predicted) {
+            continue;
+        }
+        roc.push(RocPoint {
+            threshold: d.predicted,
+            fpr: fp / negatives,
+            tpr: tp / positives,
+        });
+        pr.push(PrPoint {
+            threshold: d.predicted,
+            recall: tp / positives,
+            precision: tp / (tp + fp),
+        });
+    }
+    (roc, pr)
+}
+
+/// Trapezoidal area under the ROC curve, starting from the origin.
+fn roc_area(roc: &[RocPoint]) -> f64 {
+    if roc.is_empty() {
+        return 0.5;
+    }
+    let mut area = 0.0;
+    let (mut prev_fpr, mut prev_tpr) = (0.0, 0.0);
+    for p in roc {
+        area += (p.fpr - prev_fpr) * (p.tpr + prev_tpr) / 2.0;
+        prev_fpr = p.fpr;
+        prev_tpr = p.tpr;
+    }
+    area
+}
+
+/// Average precision: Σ (R_k − R_{k−1}) · P_k.
+fn average_precision(pr: &[PrPoint]) -> f64 {
+    let mut ap = 0.0;
+    let mut prev_recall = 0.0;
+    for p in pr {
+        ap += (p.recall - prev_recall) * p.precision;
+        prev_recall = p.recall;
+    }
+    ap
+}
+
+/// Keep at most [`MAX_CURVE_POINTS`] evenly spaced points, always the last.
+fn thin<T: Copy>(points: Vec<T>) -> Vec<T> {
+    if points.len() <= MAX_CURVE_POINTS {
+        return points;
+    }
+    let step = points.len() as f64 / MAX_CURVE_POINTS as f64;
+    let last = points.len() - 1;
+    let mut out: Vec<T> = (0..MAX_CURVE_POINTS - 1)
+        .map(|i| points[(i as f64 * step) as usize])
+        .collect();
+    out.push(points[last]);
+    out
+}
+
+/// A unit-square line plot as inline SVG.
+fn svg_plot(
+    title: &str,
+    x_label: &str,
+    y_label: &str,
+    diagonal: bool,
+    points: &[(f64, f64)],
+) -> String {
+    const SIZE: f64 = 240.0;
+    const PAD: f64 = 36.0;
+    let x = |v: f64| PAD + v.clamp(0.0, 1.0) * SIZE;
+    let y = |v: f64| PAD + (1.0 - v.clamp(0.0, 1.0)) * SIZE;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<figure><figcaption>{}</figcaption><svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{w}\" viewBox=\"0 0 {w} {w}\">",
+        title,
+        w = SIZE + 2.0 * PAD
+    );
+    let _ = writeln!(
+        svg,
+        "<rect x=\"{p}\" y=\"{p}\" width=\"{s}\" height=\"{s}\" fill=\"none\" stroke=\"#999\"/>",
+        p = PAD,
+        s = SIZE
+    );
+    if diagonal {
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#bbb\" stroke-dasharray=\"4 4\"/>",
+            x(0.0),
+            y(0.0),
+            x(1.0),
+            y(1.0)
+        );
+    }
+    if !points.is_empty() {
+        let path: Vec<String> = points
+            .iter()
+            .map(|&(px, py)| format!("{:.1},{:.1}", x(px), y(py)))
+            .collect();
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#1f77b4\" stroke-width=\"2\"/>",
+            path.join(" ")
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\">{}</text>",
+        PAD + SIZE / 2.0,
+        SIZE + 2.0 * PAD - 8.0,
+        x_label
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"12\" y=\"{c}\" text-anchor=\"middle\" font-size=\"12\" transform=\"rotate(-90 12 {c})\">{}</text>",
+        y_label,
+        c = PAD + SIZE / 2.0
+    );
+    svg.push_str("</svg></figure>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(predicted: f64, actual: bool) -> CalibrationData {
+        CalibrationData {
+            predicted,
+            actual,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn perfect_ranking_has_unit_areas() {
+        let data: Vec<_> = (0..20).map(|i| sample(i as f64 / 20.0, i >= 10)).collect();
+        let diag = CalibrationDiagnostics::from_data(&data, 10);
+        assert!((diag.auc_roc - 1.0).abs() < 1e-12);
+        assert!((diag.average_precision - 1.0).abs() < 1e-12);
+        let last = diag.roc.last().unwrap();
+        assert_eq!((last.fpr, last.tpr), (1.0, 1.0));
+    }
+
+    #[test]
+    fn ties_collapse_to_one_point() {
+        let data = vec![
+            sample(0.8, true),
+            sample(0.8, false),
+            sample(0.2, true),
+            sample(0.2, false),
+        ];
+        let diag = CalibrationDiagnostics::from_data(&data, 10);
+        assert_eq!(diag.roc.len(), 2);
+        // Uninformative ranking.
+        assert!((diag.auc_roc - 0.5).abs() < 1e-12);
+        assert!((diag.pr[0].precision - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn brier_decomposition_is_exact_for_binned_predictions() {
+        // Predictions sit at bin centres, so the within-bin term vanishes.
+        let mut data = Vec::new();
+        for (p, positives) in [(0.15, 1), (0.45, 2), (0.75, 4), (0.95, 5)] {
+            for i in 0..5 {
+                data.push(sample(p, i < positives));
+            }
+        }
+        let diag = CalibrationDiagnostics::from_data(&data, 10);
+        let b = diag.brier;
+        assert!((b.brier - (b.reliability - b.resolution + b.uncertainty)).abs() < 1e-12);
+        assert!(b.resolution > 0.0);
+        assert!((b.uncertainty - 0.6 * 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn single_class_has_no_curves() {
+        let data: Vec<_> = (0..10).map(|i| sample(i as f64 / 10.0, false)).collect();
+        let diag = CalibrationDiagnostics::from_data(&data, 10);
+        assert!(diag.roc.is_empty() && diag.pr.is_empty());
+        assert_eq!(diag.auc_roc, 0.5);
+        assert_eq!(diag.brier.uncertainty, 0.0);
+    }
+
+    #[test]
+    fn curves_are_thinned() {
+        let data: Vec<_> = (0..1000)
+            .map(|i| sample(i as f64 / 1000.0, i % 3 == 0))
+            .collect();
+        let diag = CalibrationDiagnostics::from_data(&data, 10);
+        assert_eq!(diag.roc.len(), MAX_CURVE_POINTS);
+        assert_eq!(diag.roc.last().unwrap().tpr, 1.0);
+    }
+
+    #[test]
+    fn html_section_contains_plots() {
+        let data: Vec<_> = (0..20)
+            .map(|i| sample(i as f64 / 20.0, i % 2 == 0))
+            .collect();
+        let html = CalibrationDiagnostics::from_data(&data, 10).html_section();
+        assert!(html.starts_with("<section"));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.contains("Average precision"));
+    }
+}
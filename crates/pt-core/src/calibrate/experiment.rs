@@ -0,0 +1,262 @@
+//! Baseline vs experiment decision comparison.
+//!
+//! Sessions planned with `agent plan --experiment` keep the evidence of every
+//! candidate (`experiment/replay.json`). Replaying that evidence through the
+//! posterior and expected-loss decision under both configurations shows how
+//! the overlay changes decisions on identical data.
+//!
+//! The same replay re-evaluates a bundle from another host: its evidence
+//! ledger (`inference/evidence.json`) records each candidate's decision, which
+//! becomes the baseline for a replay under the local configuration.
+//!
+//! Replay uses the global priors only; signature-specific priors and the
+//! signature fast path are not part of the comparison.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::config::{Policy, Priors};
+use crate::decision::{decide_action, Action, ActionFeasibility};
+use crate::inference::{compute_posterior, ClassScores};
+use crate::session::snapshot_persist::{EvidenceRecord, ExperimentRecord};
+
+/// One candidate whose decision differs between baseline and experiment.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionChange {
+    pub session_id: String,
+    pub pid: u32,
+    pub start_id: String,
+    pub comm: String,
+    pub baseline_action: Action,
+    pub experiment_action: Action,
+    pub baseline_posterior: ClassScores,
+    pub experiment_posterior: ClassScores,
+}
+
+/// Aggregate comparison of an experiment against the baseline configuration.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExperimentComparison {
+    pub sessions: usize,
+    pub records: usize,
+    /// Records that failed posterior or decision under either configuration.
+    pub skipped: usize,
+    pub agreed: usize,
+    pub baseline_actions: BTreeMap<String, usize>,
+    pub experiment_actions: BTreeMap<String, usize>,
+    pub changes: Vec<DecisionChange>,
+}
+
+impl ExperimentComparison {
+    /// Fraction of compared records whose action is unchanged.
+    pub fn agreement_rate(&self) -> Option<f64> {
+        let compared = self.agreed + self.changes.len();
+        (compared > 0).then(|| self.agreed as f64 / compared as f64)
+    }
+
+    /// Replay one session's records under both configurations.
+    pub fn add_session(
+        &mut self,
+        session_id: &str,
+        records: &[ExperimentRecord],
+        baseline: (&Priors, &Policy),
+        experiment: (&Priors, &Policy),
+    ) {
+        self.sessions += 1;
+        for record in records {
+            self.records += 1;
+            let feasibility =
+                ActionFeasibility::from_process_state(record.zombie, record.disksleep, None);
+            let (Some(baseline), Some(experiment)) = (
+                replay(record, baseline, &feasibility),
+                replay(record, experiment, &feasibility),
+            ) else {
+                self.skipped += 1;
+                continue;
+            };
+            self.tally(session_id, record, baseline, experiment);
+        }
+    }
+
+    /// Replay recorded decisions under a local configuration.
+    ///
+    /// The baseline is the decision each record was made with on its original
+    /// host; the experiment is the replay under `local`.
+    pub fn add_recorded(
+        &mut self,
+        session_id: &str,
+        records: &[EvidenceRecord],
+        local: (&Priors, &Policy),
+    ) {
+        self.sessions += 1;
+        for record in records {
+            self.records += 1;
+            let feasibility = ActionFeasibility::from_process_state(
+                record.replay.zombie,
+                record.replay.disksleep,
+                None,
+            );
+            let Some(replayed) = replay(&record.replay, local, &feasibility) else {
+                self.skipped += 1;
+                continue;
+            };
+            self.tally(
+                session_id,
+                &record.replay,
+                (record.action, record.posterior),
+                replayed,
+            );
+        }
+    }
+
+    fn tally(
+        &mut self,
+        session_id: &str,
+        record: &ExperimentRecord,
+        (baseline_action, baseline_posterior): (Action, ClassScores),
+        (experiment_action, experiment_posterior): (Action, ClassScores),
+    ) {
+        *self
+            .baseline_actions
+            .entry(action_label(baseline_action))
+            .or_default() += 1;
+        *self
+            .experiment_actions
+            .entry(action_label(experiment_action))
+            .or_default() += 1;
+        if baseline_action == experiment_action {
+            self.agreed += 1;
+        } else {
+            self.changes.push(DecisionChange {
+                session_id: session_id.to_string(),
+                pid: record.pid,
+                start_id: record.start_id.clone(),
+                comm: record.comm.clone(),
+                baseline_action,
+                experiment_action,
+                baseline_posterior,
+                experiment_posterior,
+            });
+        }
+    }
+}
+
+fn replay(
+    record: &ExperimentRecord,
+    (priors, policy): (&Priors, &Policy),
+    feasibility: &ActionFeasibility,
+) -> Option<(Action, ClassScores)> {
+    let posterior = compute_posterior(priors, &record.evidence).ok()?.posterior;
+    let outcome = decide_action(&posterior, policy, feasibility).ok()?;
+    Some((outcome.optimal_action, posterior))
+}
+
+fn action_label(action: Action) -> String {
+    format!("{:?}", action).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::{CpuEvidence, Evidence};
+
+    fn record(pid: u32, occupancy: f64, runtime: f64) -> ExperimentRecord {
+        ExperimentRecord {
+            pid,
+            start_id: format!("boot:1:{}", pid),
+            comm: "worker".to_string(),
+            evidence: Evidence {
+                cpu: Some(CpuEvidence::Fraction { occupancy }),
+                runtime_seconds: Some(runtime),
+                orphan: Some(true),
+                tty: Some(false),
+                ..Default::default()
+            },
+            zombie: false,
+            disksleep: false,
+        }
+    }
+
+    #[test]
+    fn identical_configs_agree() {
+        let priors = Priors::default();
+        let policy = Policy::default();
+        let records = vec![record(10, 0.0, 86_400.0), record(11, 0.9, 60.0)];
+        let mut comparison = ExperimentComparison::default();
+        comparison.add_session("s1", &records, (&priors, &policy), (&priors, &policy));
+        assert_eq!(comparison.sessions, 1);
+        assert_eq!(comparison.records, 2);
+        assert_eq!(comparison.agreed + comparison.skipped, 2);
+        assert!(comparison.changes.is_empty());
+        assert_eq!(comparison.baseline_actions, comparison.experiment_actions);
+    }
+
+    #[test]
+    fn zero_keep_loss_turns_every_change_into_keep() {
+        let priors = Priors::default();
+        let policy = Policy::default();
+        let mut keep_all = policy.clone();
+        for row in [
+            &mut keep_all.loss_matrix.useful,
+            &mut keep_all.loss_matrix.useful_bad,
+            &mut keep_all.loss_matrix.abandoned,
+            &mut keep_all.loss_matrix.zombie,
+        ] {
+            row.keep = 0.0;
+        }
+
+        let records = vec![record(10, 0.0, 30.0 * 86_400.0), record(11, 0.9, 60.0)];
+        let mut comparison = ExperimentComparison::default();
+        comparison.add_session("s1", &records, (&priors, &policy), (&priors, &keep_all));
+        let baseline_non_keep = comparison
+            .baseline_actions
+            .iter()
+            .filter(|(action, _)| action.as_str() != "keep")
+            .map(|(_, count)| count)
+            .sum::<usize>();
+        assert_eq!(comparison.changes.len(), baseline_non_keep);
+        assert!(comparison
+            .changes
+            .iter()
+            .all(|change| change.experiment_action == Action::Keep));
+    }
+
+    #[test]
+    fn recorded_decisions_are_the_baseline() {
+        let priors = Priors::default();
+        let policy = Policy::default();
+        let feasibility = ActionFeasibility::allow_all();
+        let records: Vec<EvidenceRecord> =
+            [record(10, 0.0, 30.0 * 86_400.0), record(11, 0.9, 60.0)]
+                .into_iter()
+                .map(|replay_record| {
+                    let (action, posterior) =
+                        replay(&replay_record, (&priors, &policy), &feasibility).unwrap();
+                    EvidenceRecord {
+                        replay: replay_record,
+                        posterior,
+                        action,
+                    }
+                })
+                .collect();
+
+        let mut comparison = ExperimentComparison::default();
+        comparison.add_recorded("bundle", &records, (&priors, &policy));
+        assert_eq!(comparison.agreed, 2);
+
+        // A decision recorded under another policy shows up as a change.
+        let mut other_host = records.clone();
+        let recorded = if records[1].action == Action::Kill {
+            Action::Keep
+        } else {
+            Action::Kill
+        };
+        other_host[1].action = recorded;
+        let mut comparison = ExperimentComparison::default();
+        comparison.add_recorded("bundle", &other_host, (&priors, &policy));
+        assert_eq!(comparison.changes.len(), 1);
+        assert_eq!(comparison.changes[0].pid, 11);
+        assert_eq!(comparison.changes[0].baseline_action, recorded);
+        assert_eq!(comparison.changes[0].experiment_action, records[1].action);
+    }
+}
@@ -8,10 +8,11 @@
 //! - Precision/Recall/F1
 
 use super::{CalibrationData, CalibrationError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Computed calibration metrics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CalibrationMetrics {
     /// Brier score (mean squared error of probabilities).
     /// Range: 0 (perfect) to 1 (worst).
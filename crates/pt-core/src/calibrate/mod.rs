@@ -31,7 +31,9 @@ pub mod bias;
 pub mod bounds;
 pub mod cpu_trend;
 pub mod curve;
+pub mod diagnostics;
 pub mod empirical_bayes;
+pub mod experiment;
 pub mod hierarchical;
 pub mod kalman;
 pub mod mem_growth;
@@ -41,6 +43,7 @@ pub mod pac_bayes;
 pub mod ppc;
 pub mod queries;
 pub mod report;
+pub mod shadow_fit;
 pub mod threshold;
 pub mod trend;
 pub mod tuning;
@@ -49,6 +52,7 @@ pub mod validation;
 pub use bias::*;
 pub use bounds::*;
 pub use curve::*;
+pub use diagnostics::*;
 pub use metrics::*;
 pub use pac_bayes::*;
 pub use queries::*;
@@ -60,6 +64,7 @@ pub use monitor::{
     WindowHealth,
 };
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A single calibration observation pairing a prediction with ground truth.
@@ -97,7 +102,7 @@ impl Default for CalibrationData {
 }
 
 /// Calibration quality level based on metrics.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum CalibrationQuality {
     /// Excellent calibration (ECE < 0.05, Brier < 0.1)
     Excellent,
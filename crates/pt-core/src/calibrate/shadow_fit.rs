@@ -0,0 +1,519 @@
+//! Empirical-Bayes refit of likelihood parameters from shadow history.
+//!
+//! Shadow observations keep the evidence inputs each prediction was made on
+//! (CPU occupancy, runtime, orphan, TTY) next to the posterior it produced.
+//! Each process identity contributes one sample, its last observation, split
+//! across the classes by its responsibilities: the recorded posterior, or the
+//! resolved exit outcome where one was observed. Per class, the likelihood
+//! parameters are refit from the weighted samples:
+//!
+//! - `cpu_beta`: Beta by method of moments on occupancy
+//! - `runtime_gamma`: Gamma by method of moments on runtime (seconds)
+//! - `orphan_beta`: conjugate Beta update on the orphan indicator
+//!
+//! This is one EM step from the current priors. As in
+//! [`super::empirical_bayes`], a parameter moves `learning_rate` of the way
+//! to its fit and by at most `max_change_fraction` per refit. Calibration
+//! (Brier, ECE) before and after is measured on the samples whose outcome is
+//! resolved.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use pt_telemetry::shadow::{BeliefState, Observation};
+use serde::Serialize;
+
+use super::empirical_bayes::{EmpiricalBayesConfig, ParamChange, ParamValue};
+use super::metrics::{compute_metrics, CalibrationMetrics};
+use super::validation::ValidationEngine;
+use super::CalibrationData;
+use crate::config::priors::ClassParams;
+use crate::config::Priors;
+use crate::inference::{compute_posterior, ClassScores, CpuEvidence, Evidence};
+use crate::shadow::ShadowInputs;
+
+/// Class names in prior order.
+const CLASSES: [&str; 4] = ["useful", "useful_bad", "abandoned", "zombie"];
+
+/// Responsibility a class needs before its parameters are refit.
+const MIN_CLASS_WEIGHT: f64 = 5.0;
+
+/// Occupancy is kept inside (0, 1) so Beta moments stay finite.
+const OCCUPANCY_EPS: f64 = 1e-3;
+
+/// One process identity's inputs and class responsibilities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowSample {
+    pub identity_hash: String,
+    pub inputs: ShadowInputs,
+    /// Weight of each class in this sample (sums to 1).
+    pub responsibilities: ClassScores,
+    /// Resolved outcome: whether the process turned out abandoned.
+    pub abandoned: Option<bool>,
+}
+
+impl ShadowSample {
+    /// Evidence the sample's inputs give to the posterior.
+    pub fn evidence(&self) -> Evidence {
+        Evidence {
+            cpu: Some(CpuEvidence::Fraction {
+                occupancy: self.inputs.cpu_occupancy,
+            }),
+            runtime_seconds: Some(self.inputs.runtime_seconds),
+            orphan: Some(self.inputs.orphan),
+            tty: Some(self.inputs.tty),
+            ..Default::default()
+        }
+    }
+}
+
+/// Result of a refit.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowFit {
+    pub samples: usize,
+    /// Samples with a resolved outcome.
+    pub labeled: usize,
+    /// Total responsibility per class.
+    pub class_weights: ClassScores,
+    pub changes: Vec<ParamChange>,
+    /// Calibration of the current priors on the labeled samples.
+    pub before: Option<CalibrationMetrics>,
+    /// Calibration of the proposed priors on the labeled samples.
+    pub after: Option<CalibrationMetrics>,
+    /// Proposed priors.
+    #[serde(skip)]
+    pub priors: Priors,
+}
+
+/// One sample per identity: its last observation that recorded inputs.
+///
+/// Observations from before inputs were recorded are skipped.
+pub fn shadow_samples(observations: &[Observation]) -> Vec<ShadowSample> {
+    let engine = ValidationEngine::from_shadow_observations(observations, 0.5);
+    let outcomes: HashMap<&str, bool> = engine
+        .records()
+        .iter()
+        .filter_map(|record| {
+            let truth = record.ground_truth.filter(|gt| gt.is_resolved())?;
+            Some((record.identity_hash.as_str(), truth.is_abandoned()))
+        })
+        .collect();
+
+    let mut latest: BTreeMap<&str, (DateTime<Utc>, ShadowInputs, &BeliefState)> = BTreeMap::new();
+    for obs in observations {
+        let Some(inputs) = ShadowInputs::from_events(&obs.events) else {
+            continue;
+        };
+        let newer = latest
+            .get(obs.identity_hash.as_str())
+            .is_none_or(|(seen, _, _)| obs.timestamp >= *seen);
+        if newer {
+            latest.insert(&obs.identity_hash, (obs.timestamp, inputs, &obs.belief));
+        }
+    }
+
+    latest
+        .into_iter()
+        .filter_map(|(identity_hash, (_, inputs, belief))| {
+            let abandoned = outcomes.get(identity_hash).copied();
+            Some(ShadowSample {
+                identity_hash: identity_hash.to_string(),
+                inputs,
+                responsibilities: responsibilities(belief, abandoned)?,
+                abandoned,
+            })
+        })
+        .collect()
+}
+
+/// Refit the likelihood parameters of `current` from shadow samples.
+pub fn fit_from_shadow(
+    samples: &[ShadowSample],
+    current: &Priors,
+    config: &EmpiricalBayesConfig,
+) -> ShadowFit {
+    let mut priors = current.clone();
+    let mut changes = Vec::new();
+    let mut class_weights = ClassScores::default();
+
+    for class in CLASSES {
+        let weighted: Vec<(f64, &ShadowInputs)> = samples
+            .iter()
+            .map(|s| (class_score(&s.responsibilities, class), &s.inputs))
+            .filter(|(w, _)| *w > 0.0)
+            .collect();
+        let weight: f64 = weighted.iter().map(|(w, _)| w).sum();
+        *class_score_mut(&mut class_weights, class) = weight;
+        if samples.len() < config.min_observations || weight < MIN_CLASS_WEIGHT {
+            continue;
+        }
+        let params = class_params(&mut priors, class);
+        changes.extend(refit_class(class, params, &weighted, config));
+    }
+
+    if !changes.is_empty() {
+        priors.updated_at = Some(Utc::now().to_rfc3339());
+    }
+    ShadowFit {
+        samples: samples.len(),
+        labeled: samples.iter().filter(|s| s.abandoned.is_some()).count(),
+        class_weights,
+        changes,
+        before: calibration(samples, current),
+        after: calibration(samples, &priors),
+        priors,
+    }
+}
+
+fn refit_class(
+    class: &str,
+    params: &mut ClassParams,
+    weighted: &[(f64, &ShadowInputs)],
+    config: &EmpiricalBayesConfig,
+) -> Vec<ParamChange> {
+    let mut changes = Vec::new();
+
+    let occupancy = weighted.iter().map(|(w, inputs)| {
+        let x = inputs
+            .cpu_occupancy
+            .clamp(OCCUPANCY_EPS, 1.0 - OCCUPANCY_EPS);
+        (*w, x)
+    });
+    if let Some((mean, var)) = weighted_moments(occupancy) {
+        let common = mean * (1.0 - mean) / var - 1.0;
+        if common > 0.0 {
+            let before = (params.cpu_beta.alpha, params.cpu_beta.beta);
+            let (alpha, ca) = step(before.0, mean * common, config);
+            let (beta, cb) = step(before.1, (1.0 - mean) * common, config);
+            params.cpu_beta.alpha = alpha;
+            params.cpu_beta.beta = beta;
+            changes.extend(beta_change(
+                class,
+                "cpu_beta",
+                before,
+                (alpha, beta),
+                ca || cb,
+            ));
+        }
+    }
+
+    if let Some(gamma) = params.runtime_gamma.as_mut() {
+        let runtime = weighted
+            .iter()
+            .filter(|(_, inputs)| inputs.runtime_seconds > 0.0)
+            .map(|(w, inputs)| (*w, inputs.runtime_seconds));
+        if let Some((mean, var)) = weighted_moments(runtime) {
+            let before = (gamma.shape, gamma.rate);
+            let (shape, cs) = step(before.0, mean * mean / var, config);
+            let (rate, cr) = step(before.1, mean / var, config);
+            gamma.shape = shape;
+            gamma.rate = rate;
+            let clamped = cs || cr;
+            if (shape - before.0).abs() > 1e-6 || (rate - before.1).abs() > 1e-6 {
+                changes.push(ParamChange {
+                    path: format!("classes.{}.runtime_gamma", class),
+                    before: ParamValue::Gamma {
+                        shape: before.0,
+                        rate: before.1,
+                    },
+                    after: ParamValue::Gamma { shape, rate },
+                    clamped,
+                });
+            }
+        }
+    }
+
+    let orphans: f64 = weighted
+        .iter()
+        .filter(|(_, inputs)| inputs.orphan)
+        .map(|(w, _)| w)
+        .sum();
+    let total: f64 = weighted.iter().map(|(w, _)| w).sum();
+    let before = (params.orphan_beta.alpha, params.orphan_beta.beta);
+    let lr = config.learning_rate;
+    let (alpha, ca) = bounded(before.0, before.0 + lr * orphans, config);
+    let (beta, cb) = bounded(before.1, before.1 + lr * (total - orphans), config);
+    params.orphan_beta.alpha = alpha;
+    params.orphan_beta.beta = beta;
+    changes.extend(beta_change(
+        class,
+        "orphan_beta",
+        before,
+        (alpha, beta),
+        ca || cb,
+    ));
+
+    changes
+}
+
+/// Move `learning_rate` of the way from `current` to `fitted`, bounded.
+fn step(current: f64, fitted: f64, config: &EmpiricalBayesConfig) -> (f64, bool) {
+    let proposed = current + config.learning_rate * (fitted - current);
+    bounded(current, proposed, config)
+}
+
+/// Clamp `proposed` to within `max_change_fraction` of `current`.
+///
+/// Relative on both sides, unlike the absolute floor of the empirical-Bayes
+/// clamp: Gamma rates in 1/seconds are far below it.
+fn bounded(current: f64, proposed: f64, config: &EmpiricalBayesConfig) -> (f64, bool) {
+    let max_delta = current * config.max_change_fraction;
+    let clamped = proposed.clamp(current - max_delta, current + max_delta);
+    (clamped, clamped != proposed)
+}
+
+fn beta_change(
+    class: &str,
+    param: &str,
+    before: (f64, f64),
+    after: (f64, f64),
+    clamped: bool,
+) -> Option<ParamChange> {
+    let changed = (after.0 - before.0).abs() > 1e-6 || (after.1 - before.1).abs() > 1e-6;
+    changed.then(|| ParamChange {
+        path: format!("classes.{}.{}", class, param),
+        before: ParamValue::Beta {
+            alpha: before.0,
+            beta: before.1,
+        },
+        after: ParamValue::Beta {
+            alpha: after.0,
+            beta: after.1,
+        },
+        clamped,
+    })
+}
+
+/// Weighted mean and variance; None without positive weight and spread.
+fn weighted_moments(values: impl Iterator<Item = (f64, f64)>) -> Option<(f64, f64)> {
+    let (mut w_sum, mut x_sum, mut xx_sum) = (0.0, 0.0, 0.0);
+    for (w, x) in values {
+        if !x.is_finite() {
+            continue;
+        }
+        w_sum += w;
+        x_sum += w * x;
+        xx_sum += w * x * x;
+    }
+    if w_sum < MIN_CLASS_WEIGHT {
+        return None;
+    }
+    let mean = x_sum / w_sum;
+    let var = xx_sum / w_sum - mean * mean;
+    (var > 0.0 && mean > 0.0).then_some((mean, var))
+}
+
+fn responsibilities(belief: &BeliefState, abandoned: Option<bool>) -> Option<ClassScores> {
+    let mut r = match abandoned {
+        Some(true) => ClassScores {
+            abandoned: 1.0,
+            ..Default::default()
+        },
+        _ => ClassScores {
+            useful: belief.p_legitimate as f64,
+            useful_bad: belief.p_useful_but_bad as f64,
+            abandoned: belief.p_abandoned as f64,
+            zombie: belief.p_zombie as f64,
+        },
+    };
+    if abandoned == Some(false) {
+        r.abandoned = 0.0;
+    }
+    let total = r.useful + r.useful_bad + r.abandoned + r.zombie;
+    if !(total > 0.0 && total.is_finite()) {
+        return None;
+    }
+    for class in CLASSES {
+        *class_score_mut(&mut r, class) /= total;
+    }
+    Some(r)
+}
+
+/// Calibration of `P(abandoned)` under `priors` on the labeled samples.
+fn calibration(samples: &[ShadowSample], priors: &Priors) -> Option<CalibrationMetrics> {
+    let data: Vec<CalibrationData> = samples
+        .iter()
+        .filter_map(|sample| {
+            let actual = sample.abandoned?;
+            let posterior = compute_posterior(priors, &sample.evidence()).ok()?;
+            Some(CalibrationData {
+                predicted: posterior.posterior.abandoned.clamp(0.0, 1.0),
+                actual,
+                ..Default::default()
+            })
+        })
+        .collect();
+    compute_metrics(&data, 0.5).ok()
+}
+
+fn class_score(scores: &ClassScores, class: &str) -> f64 {
+    match class {
+        "useful" => scores.useful,
+        "useful_bad" => scores.useful_bad,
+        "abandoned" => scores.abandoned,
+        _ => scores.zombie,
+    }
+}
+
+fn class_score_mut<'a>(scores: &'a mut ClassScores, class: &str) -> &'a mut f64 {
+    match class {
+        "useful" => &mut scores.useful,
+        "useful_bad" => &mut scores.useful_bad,
+        "abandoned" => &mut scores.abandoned,
+        _ => &mut scores.zombie,
+    }
+}
+
+fn class_params<'a>(priors: &'a mut Priors, class: &str) -> &'a mut ClassParams {
+    match class {
+        "useful" => &mut priors.classes.useful,
+        "useful_bad" => &mut priors.classes.useful_bad,
+        "abandoned" => &mut priors.classes.abandoned,
+        _ => &mut priors.classes.zombie,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(occupancy: f64, runtime: f64, orphan: bool, abandoned: f64) -> ShadowSample {
+        ShadowSample {
+            identity_hash: format!("{}-{}", occupancy, runtime),
+            inputs: ShadowInputs {
+                cpu_occupancy: occupancy,
+                runtime_seconds: runtime,
+                orphan,
+                tty: false,
+            },
+            responsibilities: ClassScores {
+                useful: 1.0 - abandoned,
+                useful_bad: 0.0,
+                abandoned,
+                zombie: 0.0,
+            },
+            abandoned: None,
+        }
+    }
+
+    /// Idle, long-running orphans that are abandoned; busy short ones that are useful.
+    fn population() -> Vec<ShadowSample> {
+        (0..30)
+            .flat_map(|i| {
+                let jitter = i as f64 / 30.0;
+                [
+                    sample(0.01 + 0.02 * jitter, 86_400.0 * (1.0 + jitter), true, 1.0),
+                    sample(0.5 + 0.3 * jitter, 600.0 * (1.0 + jitter), false, 0.0),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn too_few_samples_change_nothing() {
+        let priors = Priors::default();
+        let fit = fit_from_shadow(
+            &population()[..10],
+            &priors,
+            &EmpiricalBayesConfig::default(),
+        );
+        assert!(fit.changes.is_empty());
+        assert!(fit.priors.updated_at.is_none());
+    }
+
+    #[test]
+    fn unbounded_refit_matches_moments() {
+        let priors = Priors::default();
+        let config = EmpiricalBayesConfig {
+            max_change_fraction: 1e6,
+            min_observations: 20,
+            learning_rate: 1.0,
+        };
+        let fit = fit_from_shadow(&population(), &priors, &config);
+        assert_eq!(fit.samples, 60);
+        assert!((fit.class_weights.abandoned - 30.0).abs() < 1e-9);
+        assert!(fit.priors.updated_at.is_some());
+
+        // Abandoned occupancy averages ~2%.
+        let cpu = &fit.priors.classes.abandoned.cpu_beta;
+        let mean = cpu.alpha / (cpu.alpha + cpu.beta);
+        assert!((mean - 0.0197).abs() < 1e-3, "mean = {}", mean);
+        // Every abandoned sample was an orphan, no useful one was.
+        let orphan = &fit.priors.classes.abandoned.orphan_beta;
+        assert_eq!(
+            orphan.alpha,
+            priors.classes.abandoned.orphan_beta.alpha + 30.0
+        );
+        assert_eq!(orphan.beta, priors.classes.abandoned.orphan_beta.beta);
+        let orphan = &fit.priors.classes.useful.orphan_beta;
+        assert_eq!(orphan.alpha, priors.classes.useful.orphan_beta.alpha);
+    }
+
+    #[test]
+    fn refit_is_bounded_per_round() {
+        let priors = Priors::default();
+        let config = EmpiricalBayesConfig::default();
+        let fit = fit_from_shadow(&population(), &priors, &config);
+        assert!(fit
+            .changes
+            .iter()
+            .any(|c| c.path == "classes.abandoned.cpu_beta" && c.clamped));
+        for change in &fit.changes {
+            let pairs = match (&change.before, &change.after) {
+                (
+                    ParamValue::Beta {
+                        alpha: a0,
+                        beta: b0,
+                    },
+                    ParamValue::Beta {
+                        alpha: a1,
+                        beta: b1,
+                    },
+                ) => [(a0, a1), (b0, b1)],
+                (
+                    ParamValue::Gamma {
+                        shape: s0,
+                        rate: r0,
+                    },
+                    ParamValue::Gamma {
+                        shape: s1,
+                        rate: r1,
+                    },
+                ) => [(s0, s1), (r0, r1)],
+                _ => continue,
+            };
+            for (before, after) in pairs {
+                assert!((after - before).abs() <= before * config.max_change_fraction + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn resolved_outcome_overrides_posterior() {
+        let belief = BeliefState {
+            p_abandoned: 0.2,
+            p_legitimate: 0.6,
+            p_zombie: 0.0,
+            p_useful_but_bad: 0.2,
+            confidence: 0.8,
+            score: 60.0,
+            recommendation: "keep".to_string(),
+        };
+        let r = responsibilities(&belief, Some(true)).unwrap();
+        assert_eq!(r.abandoned, 1.0);
+        let r = responsibilities(&belief, Some(false)).unwrap();
+        assert_eq!(r.abandoned, 0.0);
+        assert!((r.useful - 0.75).abs() < 1e-6);
+        let r = responsibilities(&belief, None).unwrap();
+        assert!((r.abandoned - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn moments_need_weight_and_spread() {
+        assert!(weighted_moments([(10.0, 0.5)].into_iter()).is_none());
+        assert!(weighted_moments([(1.0, 0.2), (1.0, 0.4)].into_iter()).is_none());
+        let (mean, var) = weighted_moments([(3.0, 0.2), (3.0, 0.4)].into_iter()).unwrap();
+        assert!((mean - 0.3).abs() < 1e-12);
+        assert!((var - 0.01).abs() < 1e-12);
+    }
+}
@@ -10,6 +10,8 @@
 //! - **Natural exit**: Normal completion after expected lifetime
 //! - **Crash**: Non-zero exit code or signal
 //! - **Timeout**: Process exceeded maximum tracking window without resolution
+//! - **Feedback**: A user or agent labelled a decision as a false positive or
+//!   false negative (`pt-core feedback`)
 //!
 //! # Feedback Loop
 //!
@@ -20,13 +22,16 @@
 //! - Signature review flagging
 
 use chrono::{DateTime, Utc};
+use pt_telemetry::feedback::{FeedbackKind, FeedbackRecord};
 use pt_telemetry::shadow::{EventType, Observation, ProcessEvent};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
 use super::{
     bias::{analyze_bias, BiasAnalysis},
+    diagnostics::CalibrationDiagnostics,
     metrics::{compute_metrics, CalibrationMetrics},
     report::CalibrationReport,
     CalibrationData, CalibrationError, CalibrationQuality,
@@ -125,7 +130,7 @@ impl ValidationRecord {
 }
 
 /// Per-category validation summary.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CategoryValidation {
     /// Category name (e.g., "test_runner").
     pub category: String,
@@ -150,7 +155,7 @@ pub struct CategoryValidation {
 }
 
 /// Aggregated validation report.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationReport {
     /// Time range of analyzed data.
     pub from: DateTime<Utc>,
@@ -169,6 +174,9 @@ pub struct ValidationReport {
     pub by_category: Vec<CategoryValidation>,
     /// Bias analysis.
     pub bias: Option<BiasAnalysis>,
+    /// Reliability bins, ROC/PR curves and Brier decomposition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<CalibrationDiagnostics>,
     /// Most common false positives (predicted kill, actually useful).
     pub top_false_positives: Vec<FalseOutcome>,
     /// Most common false negatives (predicted useful, actually abandoned).
@@ -178,7 +186,7 @@ pub struct ValidationReport {
 }
 
 /// A false prediction for reporting.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FalseOutcome {
     /// Process command pattern.
     pub pattern: String,
@@ -191,7 +199,7 @@ pub struct FalseOutcome {
 }
 
 /// A recommended prior adjustment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PriorAdjustment {
     /// What to adjust (e.g., category prior, evidence weight).
     pub target: String,
@@ -418,6 +426,48 @@ impl ValidationEngine {
         engine
     }
 
+    /// Add feedback labels as resolved predictions.
+    ///
+    /// A false positive resolves the plan-time prediction as spared, a false
+    /// negative as killed. Labels without a recorded posterior are skipped;
+    /// they say nothing about calibration.
+    pub fn add_feedback(&mut self, feedback: &[FeedbackRecord]) {
+        for label in feedback {
+            let Some(p_abandoned) = label.p_abandoned else {
+                continue;
+            };
+            let identity_hash = format!("feedback:{}:{}", label.session_id, label.start_id);
+            let comm = label
+                .cmd
+                .split_whitespace()
+                .next()
+                .and_then(|argv0| argv0.rsplit('/').next())
+                .unwrap_or("unknown")
+                .to_string();
+            self.upsert_prediction(
+                identity_hash.clone(),
+                label.pid,
+                p_abandoned,
+                label.recommendation.clone(),
+                Some(label.proc_type.clone()),
+                comm,
+                None,
+                label.recorded_at,
+            );
+            let ground_truth = match label.kind {
+                FeedbackKind::FalsePositive => GroundTruth::UserSpared,
+                FeedbackKind::FalseNegative => GroundTruth::UserKilled,
+            };
+            self.record_outcome_with_source(
+                &identity_hash,
+                ground_truth,
+                None,
+                None,
+                Some(format!("feedback:{}", label.actor)),
+            );
+        }
+    }
+
     /// Convert resolved records to calibration data.
     fn to_calibration_data(&self) -> Vec<CalibrationData> {
         self.records
@@ -508,6 +558,9 @@ impl ValidationEngine {
             None
         };
 
+        let diagnostics =
+            (cal_data.len() >= 10).then(|| CalibrationDiagnostics::from_data(&cal_data, 10));
+
         let by_category = self.compute_category_validation(&resolved);
         let (top_false_positives, top_false_negatives) = self.compute_false_outcomes(&resolved);
         let recommendations = self.compute_prior_adjustments(&bias, &by_category);
@@ -522,6 +575,7 @@ impl ValidationEngine {
             quality,
             by_category,
             bias,
+            diagnostics,
             top_false_positives,
             top_false_negatives,
             recommendations,
@@ -1620,4 +1674,42 @@ mod tests {
         assert_eq!(cal.host_id.as_deref(), Some("node-3"));
         assert!(cal.actual); // UserKilled is abandoned
     }
+
+    fn feedback(pid: u32, kind: FeedbackKind, p_abandoned: Option<f64>) -> FeedbackRecord {
+        FeedbackRecord {
+            session_id: "pt-20260115-143022-abcd".to_string(),
+            pid,
+            start_id: format!("{}:1700000000", pid),
+            kind,
+            actor: "user".to_string(),
+            note: None,
+            recorded_at: Utc::now(),
+            cmd: "/usr/bin/node server.js".to_string(),
+            recommendation: "kill".to_string(),
+            p_abandoned,
+            score: 90.0,
+            proc_type: "dev_server".to_string(),
+        }
+    }
+
+    #[test]
+    fn add_feedback_resolves_labelled_predictions() {
+        let mut engine = ValidationEngine::new(0.5);
+        engine.add_feedback(&[
+            feedback(10, FeedbackKind::FalsePositive, Some(0.9)),
+            feedback(11, FeedbackKind::FalseNegative, Some(0.2)),
+            feedback(12, FeedbackKind::FalseNegative, None),
+        ]);
+
+        let resolved = engine.resolved_records();
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].ground_truth, Some(GroundTruth::UserSpared));
+        assert_eq!(resolved[0].comm, "node");
+        assert_eq!(resolved[0].outcome_source.as_deref(), Some("feedback:user"));
+        assert_eq!(resolved[1].ground_truth, Some(GroundTruth::UserKilled));
+
+        let data = engine.to_calibration_data();
+        assert!(!data[0].actual);
+        assert!(data[1].actual);
+    }
 }
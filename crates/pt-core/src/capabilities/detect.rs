@@ -1,6 +1,7 @@
 //! Capability detection implementation.
 
 use crate::collect::tool_runner::run_tool;
+use crate::collect::ProcVisibility;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -311,6 +312,10 @@ pub struct PermissionCapabilities {
 
     /// Whether we can send signals to other users' processes.
     pub can_signal_others: bool,
+
+    /// Which processes are visible under the /proc hidepid mount option.
+    #[serde(default)]
+    pub proc_visibility: ProcVisibility,
 }
 
 /// Supervisor system capabilities.
@@ -336,6 +341,21 @@ pub struct SupervisorCapabilities {
 
     /// Kubernetes environment detected.
     pub kubernetes: bool,
+
+    /// systemd-oomd running (may kill cgroups under memory pressure).
+    #[serde(default)]
+    pub systemd_oomd: bool,
+
+    /// earlyoom running (may kill processes under memory pressure).
+    #[serde(default)]
+    pub earlyoom: bool,
+}
+
+impl SupervisorCapabilities {
+    /// Whether a userspace memory-pressure killer is running alongside pt.
+    pub fn memory_pressure_killer_active(&self) -> bool {
+        self.systemd_oomd || self.earlyoom
+    }
 }
 
 /// Action capabilities.
@@ -744,6 +764,9 @@ fn detect_permissions() -> PermissionCapabilities {
     // Check if we can signal other users' processes
     let can_signal_others = is_root || linux_capabilities.contains(&"CAP_KILL".to_string());
 
+    // hidepid on /proc silently limits what a scan can see
+    let proc_visibility = ProcVisibility::detect();
+
     PermissionCapabilities {
         effective_uid,
         effective_gid,
@@ -752,6 +775,7 @@ fn detect_permissions() -> PermissionCapabilities {
         linux_capabilities,
         can_read_others_procs,
         can_signal_others,
+        proc_visibility,
     }
 }
 
@@ -888,6 +912,9 @@ fn detect_supervisors(tools: &ToolCapabilities) -> SupervisorCapabilities {
     let kubernetes = Path::new("/var/run/secrets/kubernetes.io").exists()
         || std::env::var("KUBERNETES_SERVICE_HOST").is_ok();
 
+    // Check userspace OOM killers
+    let oom_killers = crate::collect::detect_oom_killers();
+
     SupervisorCapabilities {
         systemd,
         launchd,
@@ -896,6 +923,8 @@ fn detect_supervisors(tools: &ToolCapabilities) -> SupervisorCapabilities {
         docker_daemon,
         podman_available,
         kubernetes,
+        systemd_oomd: oom_killers.systemd_oomd,
+        earlyoom: oom_killers.earlyoom,
     }
 }
 
@@ -1034,6 +1063,30 @@ mod tests {
         assert!(caps.tools.ps.available);
     }
 
+    #[test]
+    fn test_memory_pressure_killer_active() {
+        let mut sups = SupervisorCapabilities {
+            systemd: true,
+            launchd: false,
+            pm2: false,
+            supervisord: false,
+            docker_daemon: false,
+            podman_available: false,
+            kubernetes: false,
+            systemd_oomd: false,
+            earlyoom: false,
+        };
+        assert!(!sups.memory_pressure_killer_active());
+        sups.earlyoom = true;
+        assert!(sups.memory_pressure_killer_active());
+
+        // Caches written before the fields existed still deserialize.
+        let legacy = r#"{"systemd":true,"launchd":false,"pm2":false,"supervisord":false,
+            "docker_daemon":false,"podman_available":false,"kubernetes":false}"#;
+        let parsed: SupervisorCapabilities = serde_json::from_str(legacy).unwrap();
+        assert!(!parsed.memory_pressure_killer_active());
+    }
+
     #[test]
     fn test_tool_capability_unavailable() {
         let cap = ToolCapability::unavailable();
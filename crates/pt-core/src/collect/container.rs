@@ -10,11 +10,16 @@
 //! - Cgroup path patterns
 //! - Environment variables (for K8s)
 //! - Container-specific files
+//! - Kubelet pod log directories (pod UID → namespace/name)
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
+
+/// Directory where the kubelet keeps one `<namespace>_<pod>_<uid>` entry per pod.
+pub const KUBELET_POD_LOG_DIR: &str = "/var/log/pods";
 
 /// Container information for a process.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -84,6 +89,11 @@ pub struct KubernetesInfo {
     /// QoS class (Guaranteed, Burstable, BestEffort).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub qos_class: Option<String>,
+
+    /// Owning workload (Deployment, StatefulSet, DaemonSet, ...), derived
+    /// from the pod name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
 }
 
 /// Provenance tracking for container detection.
@@ -249,6 +259,7 @@ pub fn detect_kubernetes_from_env(env: &HashMap<String, String>) -> Option<Kuber
 
     if is_k8s {
         Some(KubernetesInfo {
+            workload: pod_name.as_deref().and_then(workload_from_pod_name),
             pod_name,
             namespace,
             pod_uid: env.get("POD_UID").cloned(),
@@ -260,6 +271,175 @@ pub fn detect_kubernetes_from_env(env: &HashMap<String, String>) -> Option<Kuber
     }
 }
 
+/// Pod namespace and name by pod UID.
+///
+/// Cgroup paths only carry the pod UID. The kubelet names each pod's log
+/// directory `<namespace>_<pod>_<uid>`, which maps the UID back to the pod
+/// without talking to the API server.
+#[derive(Debug, Clone, Default)]
+pub struct PodIndex {
+    pods: HashMap<String, (String, String)>,
+}
+
+impl PodIndex {
+    /// Index the pods on this node from [`KUBELET_POD_LOG_DIR`].
+    pub fn load() -> Self {
+        Self::from_dir(Path::new(KUBELET_POD_LOG_DIR))
+    }
+
+    /// Index pods from a kubelet pod log directory. Missing or unreadable
+    /// directories yield an empty index.
+    pub fn from_dir(dir: &Path) -> Self {
+        let mut index = Self::default();
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if let Some(name) = entry.file_name().to_str() {
+                    index.insert_log_dir(name);
+                }
+            }
+        }
+        index
+    }
+
+    /// Add a `<namespace>_<pod>_<uid>` directory name. Namespaces and pod
+    /// names cannot contain `_`, so the split is unambiguous.
+    fn insert_log_dir(&mut self, name: &str) -> bool {
+        let mut parts = name.splitn(3, '_');
+        let (Some(namespace), Some(pod), Some(uid)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return false;
+        };
+        if namespace.is_empty() || pod.is_empty() || uid.is_empty() {
+            return false;
+        }
+        self.pods.insert(
+            normalize_pod_uid(uid),
+            (namespace.to_string(), pod.to_string()),
+        );
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.pods.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pods.is_empty()
+    }
+
+    /// `(namespace, pod name)` of a pod UID.
+    pub fn lookup(&self, pod_uid: &str) -> Option<(&str, &str)> {
+        self.pods
+            .get(&normalize_pod_uid(pod_uid))
+            .map(|(namespace, pod)| (namespace.as_str(), pod.as_str()))
+    }
+
+    /// Fill in namespace, pod name and workload from the pod UID. Values
+    /// already present are kept.
+    pub fn annotate(&self, k8s: &mut KubernetesInfo) {
+        let Some((namespace, pod)) = k8s.pod_uid.as_deref().and_then(|uid| self.lookup(uid)) else {
+            return;
+        };
+        if k8s.namespace.is_none() {
+            k8s.namespace = Some(namespace.to_string());
+        }
+        if k8s.pod_name.is_none() {
+            k8s.pod_name = Some(pod.to_string());
+        }
+        if k8s.workload.is_none() {
+            k8s.workload = k8s.pod_name.as_deref().and_then(workload_from_pod_name);
+        }
+    }
+}
+
+/// Systemd cgroup drivers spell pod UIDs with `_` instead of `-`.
+fn normalize_pod_uid(uid: &str) -> String {
+    uid.replace('_', "-")
+}
+
+/// Characters Kubernetes uses for generated name suffixes (no vowels, no
+/// look-alike digits).
+const K8S_SUFFIX_ALPHABET: &str = "bcdfghjklmnpqrstvwxz2456789";
+
+fn is_generated_suffix(segment: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&segment.len()) && segment.chars().all(|c| K8S_SUFFIX_ALPHABET.contains(c))
+}
+
+/// Best-effort owning workload of a pod, from its generated name.
+///
+/// - `web-7d4b9c8f6-x2x9k` (Deployment → ReplicaSet → pod) → `web`
+/// - `fluentd-x7k2p` (DaemonSet, Job) → `fluentd`
+/// - `db-0` (StatefulSet) → `db`
+///
+/// Names without a generated suffix (static or bare pods) are their own
+/// workload.
+pub fn workload_from_pod_name(pod_name: &str) -> Option<String> {
+    let pod_name = pod_name.trim();
+    if pod_name.is_empty() {
+        return None;
+    }
+    let Some((base, last)) = pod_name.rsplit_once('-') else {
+        return Some(pod_name.to_string());
+    };
+    if base.is_empty() {
+        return Some(pod_name.to_string());
+    }
+    if !last.is_empty() && last.chars().all(|c| c.is_ascii_digit()) {
+        return Some(base.to_string());
+    }
+    if !is_generated_suffix(last, 5, 5) {
+        return Some(pod_name.to_string());
+    }
+    match base.rsplit_once('-') {
+        Some((owner, hash)) if !owner.is_empty() && is_generated_suffix(hash, 6, 10) => {
+            Some(owner.to_string())
+        }
+        _ => Some(base.to_string()),
+    }
+}
+
+/// Attach container information to processes, from their cgroup paths.
+///
+/// Kubernetes pods are resolved to namespace, pod and workload through
+/// `pods`. Processes outside containers are left untouched, as is everything
+/// on platforms without cgroups.
+pub fn annotate_processes(processes: &mut [crate::collect::ProcessRecord], pods: &PodIndex) {
+    for proc in processes.iter_mut() {
+        if proc.container_info.is_some() {
+            continue;
+        }
+        let Some(path) = process_cgroup_path(proc.pid.0) else {
+            continue;
+        };
+        let mut info = detect_container_from_cgroup(&path);
+        if !info.in_container {
+            continue;
+        }
+        if let Some(k8s) = info.kubernetes.as_mut() {
+            pods.annotate(k8s);
+        }
+        proc.container_info = Some(info);
+    }
+}
+
+/// Cgroup v2 path of a process, or a v1 path when no unified hierarchy is
+/// mounted.
+#[cfg(target_os = "linux")]
+fn process_cgroup_path(pid: u32) -> Option<String> {
+    crate::collect::parse_cgroup(pid).and_then(|info| {
+        info.unified.or_else(|| {
+            ["pids", "memory", "cpu"]
+                .iter()
+                .find_map(|controller| info.v1_paths.get(*controller).cloned())
+        })
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cgroup_path(_pid: u32) -> Option<String> {
+    None
+}
+
 /// Extract Docker container ID from cgroup path.
 fn extract_docker_id(path: &str) -> Option<String> {
     // Patterns:
@@ -625,6 +805,49 @@ mod tests {
         assert!(k8s.is_none());
     }
 
+    #[test]
+    fn test_workload_from_pod_name() {
+        let cases = [
+            ("web-7d4b9c8f6-x2x9k", "web"),
+            ("api-gateway-5f6c7b8d9-h4jkl", "api-gateway"),
+            ("fluentd-x7k2p", "fluentd"),
+            ("db-0", "db"),
+            ("kafka-broker-12", "kafka-broker"),
+            ("kube-apiserver-node1", "kube-apiserver-node1"),
+            ("standalone", "standalone"),
+        ];
+        for (pod, workload) in cases {
+            assert_eq!(
+                workload_from_pod_name(pod).as_deref(),
+                Some(workload),
+                "{pod}"
+            );
+        }
+        assert_eq!(workload_from_pod_name(""), None);
+    }
+
+    #[test]
+    fn test_pod_index_annotates_systemd_cgroup() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "shop_web-7d4b9c8f6-x2x9k_12345678-1234-1234-1234-123456789012",
+            "not-a-pod-dir",
+        ] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        let pods = PodIndex::from_dir(dir.path());
+        assert_eq!(pods.len(), 1);
+
+        let path = "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod12345678_1234_1234_1234_123456789012.slice/cri-containerd-abc123def456789012345678901234567890123456789012345678901234.scope";
+        let mut k8s = detect_container_from_cgroup(path).kubernetes.unwrap();
+        pods.annotate(&mut k8s);
+        assert_eq!(k8s.namespace.as_deref(), Some("shop"));
+        assert_eq!(k8s.pod_name.as_deref(), Some("web-7d4b9c8f6-x2x9k"));
+        assert_eq!(k8s.workload.as_deref(), Some("web"));
+
+        assert!(PodIndex::from_dir(&dir.path().join("missing")).is_empty());
+    }
+
     #[test]
     fn test_container_id_short() {
         let path = "/docker/abc123def456789012345678901234567890123456789012345678901234";
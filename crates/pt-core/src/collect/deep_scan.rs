@@ -10,16 +10,23 @@
 //! - File descriptor analysis
 //! - Cgroup membership detection
 //! - Container detection heuristics
+//! - Per-process GPU memory and utilization
+//! - Per-thread CPU split over a short sample window
 //!
 //! # Performance
 //! - Target: <5s for 1000 processes
 //! - Graceful degradation for permission-denied paths
+//! - Optional time budget: expensive probes are dropped first as the budget
+//!   is consumed, and remaining PIDs are left unscanned once it is exhausted
 
+use super::gpu::{collect_gpu_snapshot, process_gpu_summary, ProcessGpuSummary};
 use super::network::{NetworkInfo, NetworkSnapshot};
 use super::proc_parsers::{
-    parse_cgroup, parse_environ, parse_fd, parse_io, parse_sched, parse_schedstat, parse_statm,
-    parse_wchan, CgroupInfo, FdInfo, IoStats, MemStats, SchedInfo, SchedStats,
+    exe_mode, parse_cgroup, parse_environ, parse_fd, parse_io, parse_privileges_content,
+    parse_sched, parse_schedstat, parse_smaps_rollup, parse_statm, parse_wchan, CgroupInfo, FdInfo,
+    IoStats, MemStats, PrivilegeInfo, SchedInfo, SchedStats, SmapsRollup,
 };
+use super::thread_cpu::{ThreadCpuSampler, ThreadCpuSummary, DEFAULT_THREAD_CPU_WINDOW};
 use crate::events::{event_names, Phase, ProgressEmitter, ProgressEvent};
 use pt_common::{IdentityQuality, ProcessId, ProcessIdentity, StartId};
 use serde::{Deserialize, Serialize};
@@ -29,7 +36,7 @@ use std::sync::{
     Arc,
 };
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Options for deep scan operation.
@@ -46,6 +53,9 @@ pub struct DeepScanOptions {
 
     /// Optional progress event emitter.
     pub progress: Option<Arc<dyn ProgressEmitter>>,
+
+    /// Optional wall-clock budget for the whole scan (None = unbounded).
+    pub budget: Option<Duration>,
 }
 
 impl std::fmt::Debug for DeepScanOptions {
@@ -55,6 +65,7 @@ impl std::fmt::Debug for DeepScanOptions {
             .field("skip_inaccessible", &self.skip_inaccessible)
             .field("include_environ", &self.include_environ)
             .field("progress", &self.progress.as_ref().map(|_| "..."))
+            .field("budget", &self.budget)
             .finish()
     }
 }
@@ -75,6 +86,212 @@ pub enum DeepScanError {
     ProcessVanished(u32),
 }
 
+/// Individual /proc probes run for each process beyond the core identity reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeepProbe {
+    Io,
+    Schedstat,
+    Sched,
+    Mem,
+    Smaps,
+    Wchan,
+    Cgroup,
+    Network,
+    Fd,
+    Threads,
+    Environ,
+}
+
+impl DeepProbe {
+    /// Probes in the order they are run for each process.
+    pub const ALL: [DeepProbe; 11] = [
+        DeepProbe::Io,
+        DeepProbe::Schedstat,
+        DeepProbe::Sched,
+        DeepProbe::Mem,
+        DeepProbe::Smaps,
+        DeepProbe::Wchan,
+        DeepProbe::Cgroup,
+        DeepProbe::Network,
+        DeepProbe::Fd,
+        DeepProbe::Threads,
+        DeepProbe::Environ,
+    ];
+
+    /// Stable name used in output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeepProbe::Io => "io",
+            DeepProbe::Schedstat => "schedstat",
+            DeepProbe::Sched => "sched",
+            DeepProbe::Mem => "mem",
+            DeepProbe::Smaps => "smaps",
+            DeepProbe::Wchan => "wchan",
+            DeepProbe::Cgroup => "cgroup",
+            DeepProbe::Network => "network",
+            DeepProbe::Fd => "fd",
+            DeepProbe::Threads => "threads",
+            DeepProbe::Environ => "environ",
+        }
+    }
+
+    /// Relative cost of the probe, used to decide what to drop under budget pressure.
+    pub fn cost(self) -> ProbeCost {
+        match self {
+            DeepProbe::Io
+            | DeepProbe::Schedstat
+            | DeepProbe::Sched
+            | DeepProbe::Mem
+            | DeepProbe::Wchan => ProbeCost::Cheap,
+            // smaps_rollup walks the page tables of every mapping; threads
+            // reads a stat file per task.
+            DeepProbe::Smaps | DeepProbe::Cgroup | DeepProbe::Network | DeepProbe::Threads => {
+                ProbeCost::Moderate
+            }
+            // fd walks every /proc/[pid]/fd entry; environ can be large.
+            DeepProbe::Fd | DeepProbe::Environ => ProbeCost::Expensive,
+        }
+    }
+
+    /// Whether a missing result is normal rather than a failed read
+    /// (e.g. a process without sockets has no network info).
+    fn absence_is_normal(self) -> bool {
+        matches!(self, DeepProbe::Network)
+    }
+}
+
+/// Cost tier of a probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeCost {
+    Cheap,
+    Moderate,
+    Expensive,
+}
+
+/// Why a probe produced no data for a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeSkipReason {
+    /// The probe was not requested (e.g. environ without `include_environ`).
+    NotRequested,
+    /// The budget was under pressure, so the probe's cost tier was dropped.
+    BudgetDegraded,
+    /// The budget ran out before the process was reached.
+    BudgetExhausted,
+    /// The probe ran but its /proc source was unreadable (permissions, races).
+    Unavailable,
+}
+
+impl ProbeSkipReason {
+    /// Stable name used in output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProbeSkipReason::NotRequested => "not_requested",
+            ProbeSkipReason::BudgetDegraded => "budget_degraded",
+            ProbeSkipReason::BudgetExhausted => "budget_exhausted",
+            ProbeSkipReason::Unavailable => "unavailable",
+        }
+    }
+}
+
+/// A probe that was skipped for a single process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedProbe {
+    pub probe: DeepProbe,
+    pub reason: ProbeSkipReason,
+}
+
+/// Aggregate count of skipped probes across the scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeSkipSummary {
+    pub probe: DeepProbe,
+    pub reason: ProbeSkipReason,
+    pub count: usize,
+}
+
+/// How much of the probe set the budget currently allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetStage {
+    /// All probes run.
+    Full,
+    /// Expensive probes are dropped.
+    Reduced,
+    /// Only cheap probes run.
+    Minimal,
+    /// Budget exhausted; no further processes are scanned.
+    Exhausted,
+}
+
+impl BudgetStage {
+    /// Fraction of the budget after which expensive probes are dropped.
+    pub const REDUCED_AT: f64 = 0.5;
+    /// Fraction of the budget after which only cheap probes run.
+    pub const MINIMAL_AT: f64 = 0.8;
+
+    /// Stage for the given elapsed time against an optional budget.
+    pub fn for_elapsed(elapsed: Duration, budget: Option<Duration>) -> Self {
+        let Some(budget) = budget else {
+            return BudgetStage::Full;
+        };
+        if budget.is_zero() || elapsed >= budget {
+            return BudgetStage::Exhausted;
+        }
+        let used = elapsed.as_secs_f64() / budget.as_secs_f64();
+        if used >= Self::MINIMAL_AT {
+            BudgetStage::Minimal
+        } else if used >= Self::REDUCED_AT {
+            BudgetStage::Reduced
+        } else {
+            BudgetStage::Full
+        }
+    }
+
+    /// Whether a probe of the given cost may run at this stage.
+    pub fn allows(self, cost: ProbeCost) -> bool {
+        match self {
+            BudgetStage::Full => true,
+            BudgetStage::Reduced => cost <= ProbeCost::Moderate,
+            BudgetStage::Minimal => cost == ProbeCost::Cheap,
+            BudgetStage::Exhausted => false,
+        }
+    }
+}
+
+/// Runs probes for one process under a budget stage, recording skips.
+struct ProbeRunner {
+    stage: BudgetStage,
+    skipped: Vec<SkippedProbe>,
+}
+
+impl ProbeRunner {
+    fn new(stage: BudgetStage) -> Self {
+        Self {
+            stage,
+            skipped: Vec::new(),
+        }
+    }
+
+    fn run<T>(&mut self, probe: DeepProbe, f: impl FnOnce() -> Option<T>) -> Option<T> {
+        if !self.stage.allows(probe.cost()) {
+            self.skipped.push(SkippedProbe {
+                probe,
+                reason: ProbeSkipReason::BudgetDegraded,
+            });
+            return None;
+        }
+        let value = f();
+        if value.is_none() && !probe.absence_is_normal() {
+            self.skipped.push(SkippedProbe {
+                probe,
+                reason: ProbeSkipReason::Unavailable,
+            });
+        }
+        value
+    }
+}
+
 /// Extended process record from deep scan.
 ///
 /// Contains all information from quick scan plus detailed /proc data.
@@ -137,6 +354,10 @@ pub struct DeepScanRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mem: Option<MemStats>,
 
+    /// PSS/USS breakdown from smaps_rollup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smaps: Option<SmapsRollup>,
+
     /// File descriptor information.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fd: Option<FdInfo>,
@@ -157,6 +378,22 @@ pub struct DeepScanRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub environ: Option<std::collections::HashMap<String, String>>,
 
+    /// Capabilities, setuid/setgid exe bits and related privilege state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privileges: Option<PrivilegeInfo>,
+
+    /// Per-thread CPU split over the scan's sample window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<ThreadCpuSummary>,
+
+    /// GPU memory and utilization (None when the host has no visible GPU).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<ProcessGpuSummary>,
+
+    /// Probes that produced no data for this process, and why.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_probes: Vec<SkippedProbe>,
+
     // === Timing ===
     /// Process start time (clock ticks since boot).
     pub starttime: u64,
@@ -221,6 +458,22 @@ pub struct DeepScanMetadata {
     /// Any warnings encountered during scan.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+
+    /// Time budget for the scan in milliseconds, if one was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_ms: Option<u64>,
+
+    /// Whether the budget ran out before every process was scanned.
+    #[serde(default)]
+    pub budget_exceeded: bool,
+
+    /// Number of processes not reached because the budget ran out.
+    #[serde(default)]
+    pub unscanned_count: usize,
+
+    /// Skipped probes aggregated by probe and reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub probe_skips: Vec<ProbeSkipSummary>,
 }
 
 /// Perform a deep scan of running processes.
@@ -246,6 +499,9 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
     // Initialize network snapshot once for O(1) lookups per process
     let network_snapshot = NetworkSnapshot::collect();
 
+    // GPU usage comes from vendor tools or DRM fdinfo; query it once as well
+    let gpu_snapshot = collect_gpu_snapshot();
+
     // Read boot_id once
     let boot_id = fs::read_to_string("/proc/sys/kernel/random/boot_id")
         .ok()
@@ -259,17 +515,27 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
     };
     let total_pids = pids.len() as u64;
 
+    // First per-thread CPU sample; each process's second sample is read when
+    // it is scanned, after at least the default window. Sampling is skipped
+    // when waiting out the window would leave no budget for the probe.
+    let sample_threads =
+        BudgetStage::for_elapsed(start.elapsed() + DEFAULT_THREAD_CPU_WINDOW, options.budget)
+            .allows(DeepProbe::Threads.cost());
+    let thread_sampler = ThreadCpuSampler::start(if sample_threads { &pids } else { &[] });
+
     if let Some(emitter) = options.progress.as_ref() {
         emitter.emit(
             ProgressEvent::new(event_names::DEEP_SCAN_STARTED, Phase::DeepScan)
                 .with_progress(0, Some(total_pids))
                 .with_detail("include_environ", options.include_environ)
-                .with_detail("skip_inaccessible", options.skip_inaccessible),
+                .with_detail("skip_inaccessible", options.skip_inaccessible)
+                .with_detail("budget_ms", options.budget.map(|b| b.as_millis() as u64)),
         );
     }
 
     const PROGRESS_STEP: usize = 50;
     let scanned_counter = AtomicUsize::new(0);
+    let unscanned_counter = AtomicUsize::new(0);
 
     // Determine parallelism
     let num_threads = std::thread::available_parallelism()
@@ -278,16 +544,22 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
         .min(16); // Cap threads
     let chunk_size = (pids.len() + num_threads - 1) / num_threads.max(1);
     let chunks: Vec<_> = pids.chunks(chunk_size).collect();
+    if sample_threads {
+        thread_sampler.wait(DEFAULT_THREAD_CPU_WINDOW);
+    }
 
-    let (processes, warnings, skipped_count) = thread::scope(|s| {
+    let (processes, mut warnings, skipped_count) = thread::scope(|s| {
         let mut handles = Vec::new();
 
         for chunk in chunks {
             let user_cache_ref = &user_cache;
             let network_snapshot_ref = &network_snapshot;
+            let gpu_snapshot_ref = &gpu_snapshot;
             let boot_id_ref = &boot_id;
+            let thread_sampler_ref = &thread_sampler;
             let progress_ref = options.progress.as_ref();
             let counter_ref = &scanned_counter;
+            let unscanned_ref = &unscanned_counter;
 
             handles.push(s.spawn(move || {
                 let mut local_processes = Vec::new();
@@ -295,14 +567,25 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
                 let mut local_skipped = 0;
 
                 for &pid in chunk {
+                    let stage = BudgetStage::for_elapsed(start.elapsed(), options.budget);
+                    if stage == BudgetStage::Exhausted {
+                        unscanned_ref.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    }
+
                     match scan_process(
                         pid,
                         options.include_environ,
+                        stage,
                         user_cache_ref,
                         boot_id_ref,
                         network_snapshot_ref,
+                        thread_sampler_ref,
                     ) {
-                        Ok(record) => local_processes.push(record),
+                        Ok(mut record) => {
+                            record.gpu = process_gpu_summary(gpu_snapshot_ref, pid);
+                            local_processes.push(record);
+                        }
                         Err(DeepScanError::ProcessVanished(_)) => {
                             // Always skip vanished processes without warning
                             local_skipped += 1;
@@ -352,6 +635,16 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
     let duration = start.elapsed();
     let process_count = processes.len();
     let scanned_total = scanned_counter.load(Ordering::Relaxed);
+    let unscanned_count = unscanned_counter.load(Ordering::Relaxed);
+    let budget_exceeded = unscanned_count > 0;
+    if budget_exceeded {
+        warnings.push(format!(
+            "Deep scan budget of {}ms exhausted; {} processes not scanned",
+            options.budget.map(|b| b.as_millis()).unwrap_or(0),
+            unscanned_count
+        ));
+    }
+    let probe_skips = summarize_probe_skips(&processes, options.include_environ, unscanned_count);
 
     if let Some(emitter) = options.progress.as_ref() {
         emitter.emit(
@@ -360,7 +653,8 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
                 .with_elapsed_ms(duration.as_millis() as u64)
                 .with_detail("process_count", process_count)
                 .with_detail("skipped", skipped_count)
-                .with_detail("warnings", warnings.len()),
+                .with_detail("warnings", warnings.len())
+                .with_detail("unscanned", unscanned_count),
         );
     }
 
@@ -372,10 +666,56 @@ pub fn deep_scan(options: &DeepScanOptions) -> Result<DeepScanResult, DeepScanEr
             process_count,
             skipped_count,
             warnings,
+            budget_ms: options.budget.map(|b| b.as_millis() as u64),
+            budget_exceeded,
+            unscanned_count,
+            probe_skips,
         },
     })
 }
 
+/// Aggregate per-record probe skips into counts by probe and reason.
+///
+/// Environ is reported as not requested once per scanned process when it is
+/// disabled, and every probe is reported as budget-exhausted for each process
+/// the scan never reached.
+fn summarize_probe_skips(
+    processes: &[DeepScanRecord],
+    include_environ: bool,
+    unscanned_count: usize,
+) -> Vec<ProbeSkipSummary> {
+    let mut counts: std::collections::BTreeMap<(DeepProbe, ProbeSkipReason), usize> =
+        std::collections::BTreeMap::new();
+    for record in processes {
+        for skip in &record.skipped_probes {
+            *counts.entry((skip.probe, skip.reason)).or_default() += 1;
+        }
+    }
+    if !include_environ && !processes.is_empty() {
+        *counts
+            .entry((DeepProbe::Environ, ProbeSkipReason::NotRequested))
+            .or_default() += processes.len();
+    }
+    if unscanned_count > 0 {
+        for probe in DeepProbe::ALL {
+            if probe == DeepProbe::Environ && !include_environ {
+                continue;
+            }
+            *counts
+                .entry((probe, ProbeSkipReason::BudgetExhausted))
+                .or_default() += unscanned_count;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((probe, reason), count)| ProbeSkipSummary {
+            probe,
+            reason,
+            count,
+        })
+        .collect()
+}
+
 /// List all PIDs from /proc.
 fn list_all_pids() -> Result<Vec<u32>, DeepScanError> {
     let mut pids = Vec::new();
@@ -430,9 +770,11 @@ impl UserCache {
 fn scan_process(
     pid: u32,
     include_environ: bool,
+    stage: BudgetStage,
     user_cache: &UserCache,
     boot_id: &Option<String>,
     network_snapshot: &NetworkSnapshot,
+    thread_sampler: &ThreadCpuSampler,
 ) -> Result<DeepScanRecord, DeepScanError> {
     let proc_path = format!("/proc/{}", pid);
 
@@ -474,6 +816,12 @@ fn scan_process(
         .ok()
         .map(|p| p.to_string_lossy().to_string());
 
+    // Privilege state comes from the status file already read above plus
+    // a single stat of the exe; it is cheap enough to skip the budget.
+    let privileges = status_content
+        .as_deref()
+        .map(|c| parse_privileges_content(c, exe_mode(pid)));
+
     // Compute identity quality based on available data
     let identity_quality = match (boot_id, stat_info.starttime, uid_known) {
         (_, _, false) => IdentityQuality::PidOnly,
@@ -484,19 +832,25 @@ fn scan_process(
 
     let start_id = compute_start_id(boot_id, stat_info.starttime, pid);
 
-    // Collect optional detailed stats (may fail due to permissions)
-    let io = parse_io(pid);
-    let schedstat = parse_schedstat(pid);
-    let sched = parse_sched(pid);
-    let mem = parse_statm(pid);
-    let fd = parse_fd(pid);
-    let cgroup = parse_cgroup(pid);
-    let wchan = parse_wchan(pid);
-    let network = network_snapshot.get_process_info(pid);
+    // Collect optional detailed stats (may fail due to permissions, or be
+    // dropped when the budget is under pressure)
+    let mut probes = ProbeRunner::new(stage);
+    let io = probes.run(DeepProbe::Io, || parse_io(pid));
+    let schedstat = probes.run(DeepProbe::Schedstat, || parse_schedstat(pid));
+    let sched = probes.run(DeepProbe::Sched, || parse_sched(pid));
+    let mem = probes.run(DeepProbe::Mem, || parse_statm(pid));
+    let smaps = probes.run(DeepProbe::Smaps, || parse_smaps_rollup(pid));
+    let wchan = probes.run(DeepProbe::Wchan, || parse_wchan(pid));
+    let cgroup = probes.run(DeepProbe::Cgroup, || parse_cgroup(pid));
+    let network = probes.run(DeepProbe::Network, || {
+        network_snapshot.get_process_info(pid)
+    });
+    let fd = probes.run(DeepProbe::Fd, || parse_fd(pid));
+    let threads = probes.run(DeepProbe::Threads, || thread_sampler.summary(pid));
 
     // Collect environment variables if requested (may contain sensitive data)
     let environ = if include_environ {
-        parse_environ(pid)
+        probes.run(DeepProbe::Environ, || parse_environ(pid))
     } else {
         None
     };
@@ -517,11 +871,16 @@ fn scan_process(
         schedstat,
         sched,
         mem,
+        smaps,
         fd,
         cgroup,
         wchan,
         network,
         environ,
+        privileges,
+        threads,
+        gpu: None,
+        skipped_probes: probes.skipped,
         starttime: stat_info.starttime,
         source: "deep_scan".to_string(),
         identity_quality,
@@ -715,6 +1074,107 @@ Gid:	1000	1000	1000	1000
         assert!(start_id.0.contains("1234"));
     }
 
+    #[test]
+    fn test_budget_stage_thresholds() {
+        let budget = Some(Duration::from_secs(10));
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::from_secs(100), None),
+            BudgetStage::Full
+        );
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::from_secs(1), budget),
+            BudgetStage::Full
+        );
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::from_secs(6), budget),
+            BudgetStage::Reduced
+        );
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::from_secs(9), budget),
+            BudgetStage::Minimal
+        );
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::from_secs(10), budget),
+            BudgetStage::Exhausted
+        );
+        assert_eq!(
+            BudgetStage::for_elapsed(Duration::ZERO, Some(Duration::ZERO)),
+            BudgetStage::Exhausted
+        );
+    }
+
+    #[test]
+    fn test_budget_stage_drops_expensive_probes_first() {
+        assert!(BudgetStage::Full.allows(ProbeCost::Expensive));
+        assert!(!BudgetStage::Reduced.allows(ProbeCost::Expensive));
+        assert!(BudgetStage::Reduced.allows(ProbeCost::Moderate));
+        assert!(!BudgetStage::Minimal.allows(ProbeCost::Moderate));
+        assert!(BudgetStage::Minimal.allows(ProbeCost::Cheap));
+        assert!(!BudgetStage::Exhausted.allows(ProbeCost::Cheap));
+        assert_eq!(DeepProbe::Fd.cost(), ProbeCost::Expensive);
+        assert_eq!(DeepProbe::Network.cost(), ProbeCost::Moderate);
+        assert_eq!(DeepProbe::Threads.cost(), ProbeCost::Moderate);
+        assert_eq!(DeepProbe::Io.cost(), ProbeCost::Cheap);
+    }
+
+    #[test]
+    fn test_probe_runner_records_skips() {
+        let mut runner = ProbeRunner::new(BudgetStage::Reduced);
+        assert_eq!(runner.run(DeepProbe::Io, || Some(1)), Some(1));
+        assert_eq!(runner.run(DeepProbe::Fd, || Some(2)), None);
+        assert_eq!(runner.run(DeepProbe::Wchan, || None::<u32>), None);
+        assert_eq!(runner.run(DeepProbe::Network, || None::<u32>), None);
+
+        assert_eq!(
+            runner.skipped,
+            vec![
+                SkippedProbe {
+                    probe: DeepProbe::Fd,
+                    reason: ProbeSkipReason::BudgetDegraded,
+                },
+                SkippedProbe {
+                    probe: DeepProbe::Wchan,
+                    reason: ProbeSkipReason::Unavailable,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_probe_skips_counts_unscanned_and_environ() {
+        let summary = summarize_probe_skips(&[], false, 2);
+        assert_eq!(summary.len(), DeepProbe::ALL.len() - 1);
+        assert!(summary
+            .iter()
+            .all(|s| s.reason == ProbeSkipReason::BudgetExhausted && s.count == 2));
+        assert!(!summary.iter().any(|s| s.probe == DeepProbe::Environ));
+
+        let summary = summarize_probe_skips(&[], true, 0);
+        assert!(summary.is_empty());
+    }
+
+    #[test]
+    fn test_deep_scan_zero_budget_scans_nothing() {
+        let options = DeepScanOptions {
+            pids: vec![std::process::id()],
+            skip_inaccessible: true,
+            include_environ: false,
+            progress: None,
+            budget: Some(Duration::ZERO),
+        };
+
+        let result = deep_scan(&options).expect("deep_scan");
+        assert!(result.processes.is_empty());
+        assert!(result.metadata.budget_exceeded);
+        assert_eq!(result.metadata.unscanned_count, 1);
+        assert_eq!(result.metadata.budget_ms, Some(0));
+        assert!(result
+            .metadata
+            .probe_skips
+            .iter()
+            .any(|s| s.probe == DeepProbe::Fd && s.reason == ProbeSkipReason::BudgetExhausted));
+    }
+
     // Integration test - only run when /proc is available
     #[test]
     #[ignore] // Run with: cargo test -- --ignored
@@ -724,6 +1184,7 @@ Gid:	1000	1000	1000	1000
             skip_inaccessible: true,
             include_environ: false,
             progress: None,
+            budget: None,
         };
 
         let result = deep_scan(&options);
@@ -750,7 +1211,16 @@ Gid:	1000	1000	1000	1000
         let user_cache = UserCache::new();
         let boot_id = None;
         let network_snapshot = NetworkSnapshot::collect();
-        let record = scan_process(pid, false, &user_cache, &boot_id, &network_snapshot).unwrap();
+        let record = scan_process(
+            pid,
+            false,
+            BudgetStage::Full,
+            &user_cache,
+            &boot_id,
+            &network_snapshot,
+            &ThreadCpuSampler::start(&[pid]),
+        )
+        .unwrap();
 
         assert_eq!(record.pid.0, pid);
         assert!(record.ppid.0 > 0);
@@ -782,6 +1252,7 @@ Gid:	1000	1000	1000	1000
             skip_inaccessible: false,
             include_environ: false,
             progress: None,
+            budget: None,
         };
 
         let result = deep_scan(&options);
@@ -839,7 +1310,15 @@ Gid:	1000	1000	1000	1000
             .map(|s| s.trim().to_string());
         let network_snapshot = NetworkSnapshot::collect();
 
-        let record = scan_process(proc.pid(), true, &user_cache, &boot_id, &network_snapshot);
+        let record = scan_process(
+            proc.pid(),
+            true,
+            BudgetStage::Full,
+            &user_cache,
+            &boot_id,
+            &network_snapshot,
+            &ThreadCpuSampler::start(&[proc.pid()]),
+        );
         crate::test_log!(
             INFO,
             "scan_process result",
@@ -912,6 +1391,7 @@ Gid:	1000	1000	1000	1000
             skip_inaccessible: false,
             include_environ: false,
             progress: None,
+            budget: None,
         };
 
         let result = deep_scan(&options).expect("deep_scan should succeed");
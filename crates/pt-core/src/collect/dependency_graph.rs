@@ -0,0 +1,482 @@
+//! Process dependency graph export.
+//!
+//! Builds the subtree around a set of candidate processes together with the
+//! relationships that matter for blast-radius review:
+//!
+//! - parent → child (`ppid`)
+//! - process-group membership (member → group leader)
+//! - session membership (member → session leader)
+//! - unix-socket links between processes holding the same socket inode or
+//!   sockets bound to the same path
+//!
+//! `/proc/net/unix` does not expose peer inodes, so connected pairs created by
+//! `connect(2)` are not linked; shared (inherited or passed) sockets and
+//! listeners bound to one path are. The graph serializes to JSON for the
+//! session artifact and the HTML report, and renders to Graphviz DOT.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::Write as _;
+
+use super::types::ProcessRecord;
+
+/// Schema version for the dependency graph artifact.
+pub const DEPENDENCY_GRAPH_SCHEMA_VERSION: &str = "1.0.0";
+
+/// Maximum number of nodes kept in one graph; deeper descendants are dropped.
+pub const DEFAULT_MAX_NODES: usize = 500;
+
+/// Relationship between two processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// Parent → child.
+    Parent,
+    /// Member → process-group leader.
+    ProcessGroup,
+    /// Member → session leader.
+    Session,
+    /// Processes sharing a unix socket (by inode or bound path).
+    UnixSocket,
+}
+
+impl EdgeKind {
+    /// Stable snake_case name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::Parent => "parent",
+            EdgeKind::ProcessGroup => "process_group",
+            EdgeKind::Session => "session",
+            EdgeKind::UnixSocket => "unix_socket",
+        }
+    }
+
+    fn dot_style(&self) -> &'static str {
+        match self {
+            EdgeKind::Parent => "solid",
+            EdgeKind::ProcessGroup => "dashed",
+            EdgeKind::Session => "dotted",
+            EdgeKind::UnixSocket => "bold",
+        }
+    }
+}
+
+/// Process node in the graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub pid: u32,
+    pub ppid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pgid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sid: Option<u32>,
+    pub comm: String,
+    pub state: String,
+    pub rss_bytes: u64,
+    pub cpu_percent: f64,
+    /// True for the processes the graph was built around.
+    pub candidate: bool,
+}
+
+/// Directed edge between two nodes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: u32,
+    pub to: u32,
+    pub kind: EdgeKind,
+    /// Socket path or inode for unix-socket edges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Unix socket held by a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixSocketRef {
+    pub inode: u64,
+    pub path: Option<String>,
+}
+
+/// Dependency graph around a set of candidates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub schema_version: String,
+    /// Candidate PIDs the graph was built around.
+    pub roots: Vec<u32>,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// True if descendants were dropped to respect the node limit.
+    pub truncated: bool,
+}
+
+impl DependencyGraph {
+    /// Build the graph for `roots` from a process table.
+    ///
+    /// Includes each root, its parent (for context) and all descendants,
+    /// plus the process-group and session leaders of included processes
+    /// when they are present in the table. Roots missing from the table are
+    /// skipped.
+    pub fn build(processes: &[ProcessRecord], roots: &[u32], max_nodes: usize) -> Self {
+        let by_pid: HashMap<u32, &ProcessRecord> = processes.iter().map(|p| (p.pid.0, p)).collect();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for p in processes {
+            if p.ppid.0 != p.pid.0 {
+                children.entry(p.ppid.0).or_default().push(p.pid.0);
+            }
+        }
+        for kids in children.values_mut() {
+            kids.sort_unstable();
+        }
+
+        let roots: Vec<u32> = roots
+            .iter()
+            .copied()
+            .filter(|pid| by_pid.contains_key(pid))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        // Breadth-first so the closest descendants survive truncation.
+        let mut included: BTreeSet<u32> = BTreeSet::new();
+        let mut queue: VecDeque<u32> = roots.iter().copied().collect();
+        let mut truncated = false;
+        while let Some(pid) = queue.pop_front() {
+            if included.contains(&pid) {
+                continue;
+            }
+            if included.len() >= max_nodes {
+                truncated = true;
+                break;
+            }
+            included.insert(pid);
+            if let Some(kids) = children.get(&pid) {
+                queue.extend(kids.iter().copied());
+            }
+        }
+
+        // Context nodes: parents of roots, group and session leaders.
+        let mut context: BTreeSet<u32> = BTreeSet::new();
+        for pid in &roots {
+            context.insert(by_pid[pid].ppid.0);
+        }
+        for pid in &included {
+            let p = by_pid[pid];
+            context.extend(p.pgid);
+            context.extend(p.sid);
+        }
+        for pid in context {
+            if included.contains(&pid) || !by_pid.contains_key(&pid) {
+                continue;
+            }
+            if included.len() >= max_nodes {
+                truncated = true;
+                break;
+            }
+            included.insert(pid);
+        }
+
+        let root_set: BTreeSet<u32> = roots.iter().copied().collect();
+        let nodes: Vec<GraphNode> = included
+            .iter()
+            .map(|pid| {
+                let p = by_pid[pid];
+                GraphNode {
+                    pid: p.pid.0,
+                    ppid: p.ppid.0,
+                    pgid: p.pgid,
+                    sid: p.sid,
+                    comm: p.comm.clone(),
+                    state: p.state.to_string(),
+                    rss_bytes: p.rss_bytes,
+                    cpu_percent: p.cpu_percent,
+                    candidate: root_set.contains(pid),
+                }
+            })
+            .collect();
+
+        let mut edges: BTreeSet<GraphEdge> = BTreeSet::new();
+        for node in &nodes {
+            if node.ppid != node.pid && included.contains(&node.ppid) {
+                edges.insert(GraphEdge {
+                    from: node.ppid,
+                    to: node.pid,
+                    kind: EdgeKind::Parent,
+                    label: None,
+                });
+            }
+            for (leader, kind) in [
+                (node.pgid, EdgeKind::ProcessGroup),
+                (node.sid, EdgeKind::Session),
+            ] {
+                if let Some(leader) = leader {
+                    if leader != node.pid && included.contains(&leader) {
+                        edges.insert(GraphEdge {
+                            from: node.pid,
+                            to: leader,
+                            kind,
+                            label: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        Self {
+            schema_version: DEPENDENCY_GRAPH_SCHEMA_VERSION.to_string(),
+            roots,
+            nodes,
+            edges: edges.into_iter().collect(),
+            truncated,
+        }
+    }
+
+    /// Add unix-socket edges between nodes that share a socket.
+    ///
+    /// Nodes sharing an inode are linked with the inode as label; nodes
+    /// holding sockets bound to the same path are linked with the path.
+    /// Each linked group becomes a chain from the lowest PID.
+    pub fn add_unix_socket_links(&mut self, sockets: &HashMap<u32, Vec<UnixSocketRef>>) {
+        let mut by_key: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+        for node in &self.nodes {
+            for socket in sockets.get(&node.pid).into_iter().flatten() {
+                by_key
+                    .entry(format!("socket:[{}]", socket.inode))
+                    .or_default()
+                    .insert(node.pid);
+                if let Some(path) = socket.path.as_ref().filter(|p| !p.is_empty()) {
+                    by_key.entry(path.clone()).or_default().insert(node.pid);
+                }
+            }
+        }
+
+        let mut edges: BTreeSet<GraphEdge> = self.edges.drain(..).collect();
+        for (label, pids) in by_key {
+            let pids: Vec<u32> = pids.into_iter().collect();
+            for pair in pids.windows(2) {
+                edges.insert(GraphEdge {
+                    from: pair[0],
+                    to: pair[1],
+                    kind: EdgeKind::UnixSocket,
+                    label: Some(label.clone()),
+                });
+            }
+        }
+        self.edges = edges.into_iter().collect();
+    }
+
+    /// Render as a Graphviz DOT digraph.
+    ///
+    /// Candidates are filled red; edge style encodes the relationship.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph process_dependencies {\n");
+        out.push_str("  rankdir=TB;\n  node [shape=box, fontname=\"monospace\"];\n");
+        for node in &self.nodes {
+            let fill = if node.candidate {
+                ", style=filled, fillcolor=\"#fca5a5\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "  p{} [label=\"{}\\n{} ({})\"{}];",
+                node.pid,
+                dot_escape(&node.comm),
+                node.pid,
+                node.state,
+                fill
+            );
+        }
+        for edge in &self.edges {
+            let label = edge
+                .label
+                .as_deref()
+                .map(|l| format!(", label=\"{}\"", dot_escape(l)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "  p{} -> p{} [style={}, tooltip=\"{}\"{}];",
+                edge.from,
+                edge.to,
+                edge.kind.dot_style(),
+                edge.kind.as_str(),
+                label
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Collect unix sockets held by each PID from `/proc/<pid>/fd` and `/proc/net/unix`.
+///
+/// PIDs whose fd table cannot be read are omitted.
+#[cfg(target_os = "linux")]
+pub fn collect_unix_sockets(pids: &[u32]) -> HashMap<u32, Vec<UnixSocketRef>> {
+    use super::network::{get_process_socket_inodes, parse_proc_net_unix};
+
+    let unix: HashMap<u64, Option<String>> = parse_proc_net_unix("/proc/net/unix")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| (s.inode, s.path))
+        .collect();
+
+    let mut out = HashMap::new();
+    for &pid in pids {
+        let Some(inodes) = get_process_socket_inodes(pid) else {
+            continue;
+        };
+        let mut sockets: Vec<UnixSocketRef> = inodes
+            .into_iter()
+            .filter_map(|inode| {
+                unix.get(&inode).map(|path| UnixSocketRef {
+                    inode,
+                    path: path.clone(),
+                })
+            })
+            .collect();
+        if !sockets.is_empty() {
+            sockets.sort_by_key(|s| s.inode);
+            out.insert(pid, sockets);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::ProcessState;
+    use pt_common::{ProcessId, StartId};
+    use std::time::Duration;
+
+    fn record(pid: u32, ppid: u32, pgid: u32, sid: u32, comm: &str) -> ProcessRecord {
+        ProcessRecord {
+            pid: ProcessId(pid),
+            ppid: ProcessId(ppid),
+            uid: 1000,
+            user: "dev".to_string(),
+            pgid: Some(pgid),
+            sid: Some(sid),
+            start_id: StartId::from_linux("test-boot-id", 1_000, pid),
+            comm: comm.to_string(),
+            cmd: comm.to_string(),
+            state: ProcessState::Sleeping,
+            cpu_percent: 0.0,
+            rss_bytes: 1024,
+            vsz_bytes: 2048,
+            tty: None,
+            start_time_unix: 1_000,
+            elapsed: Duration::from_secs(60),
+            source: "test".to_string(),
+            container_info: None,
+            io_rate: None,
+        }
+    }
+
+    fn table() -> Vec<ProcessRecord> {
+        vec![
+            record(1, 0, 1, 1, "init"),
+            record(100, 1, 100, 100, "bash"),
+            record(200, 100, 200, 100, "make"),
+            record(201, 200, 200, 100, "cc"),
+            record(202, 200, 200, 100, "cc"),
+            record(300, 1, 300, 300, "unrelated"),
+        ]
+    }
+
+    #[test]
+    fn subtree_includes_descendants_parent_and_leaders() {
+        let graph = DependencyGraph::build(&table(), &[200], DEFAULT_MAX_NODES);
+        let pids: Vec<u32> = graph.nodes.iter().map(|n| n.pid).collect();
+        assert_eq!(pids, vec![100, 200, 201, 202]);
+        assert_eq!(graph.roots, vec![200]);
+        assert!(!graph.truncated);
+
+        let has = |from, to, kind| {
+            graph
+                .edges
+                .iter()
+                .any(|e| e.from == from && e.to == to && e.kind == kind)
+        };
+        assert!(has(100, 200, EdgeKind::Parent));
+        assert!(has(200, 201, EdgeKind::Parent));
+        assert!(has(201, 200, EdgeKind::ProcessGroup));
+        assert!(has(200, 100, EdgeKind::Session));
+        assert!(graph.nodes.iter().find(|n| n.pid == 200).unwrap().candidate);
+    }
+
+    #[test]
+    fn node_limit_truncates() {
+        let graph = DependencyGraph::build(&table(), &[200], 2);
+        assert_eq!(graph.nodes.len(), 2);
+        assert!(graph.truncated);
+    }
+
+    #[test]
+    fn unix_socket_links_by_inode_and_path() {
+        let mut graph = DependencyGraph::build(&table(), &[200], DEFAULT_MAX_NODES);
+        let mut sockets = HashMap::new();
+        sockets.insert(
+            201,
+            vec![UnixSocketRef {
+                inode: 42,
+                path: None,
+            }],
+        );
+        sockets.insert(
+            202,
+            vec![
+                UnixSocketRef {
+                    inode: 42,
+                    path: None,
+                },
+                UnixSocketRef {
+                    inode: 43,
+                    path: Some("/run/build.sock".to_string()),
+                },
+            ],
+        );
+        sockets.insert(
+            100,
+            vec![UnixSocketRef {
+                inode: 44,
+                path: Some("/run/build.sock".to_string()),
+            }],
+        );
+        graph.add_unix_socket_links(&sockets);
+
+        let unix: Vec<&GraphEdge> = graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::UnixSocket)
+            .collect();
+        assert_eq!(unix.len(), 2);
+        assert!(unix
+            .iter()
+            .any(|e| e.from == 201 && e.to == 202 && e.label.as_deref() == Some("socket:[42]")));
+        assert!(unix.iter().any(|e| e.from == 100
+            && e.to == 202
+            && e.label.as_deref() == Some("/run/build.sock")));
+    }
+
+    #[test]
+    fn dot_output_marks_candidates_and_styles() {
+        let graph = DependencyGraph::build(&table(), &[200], DEFAULT_MAX_NODES);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph process_dependencies {"));
+        assert!(dot.contains("p200 [label=\"make\\n200 (S)\", style=filled"));
+        assert!(dot.contains("p100 -> p200 [style=solid"));
+        assert!(dot.contains("p201 -> p200 [style=dashed"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn missing_roots_are_skipped() {
+        let graph = DependencyGraph::build(&table(), &[999], DEFAULT_MAX_NODES);
+        assert!(graph.roots.is_empty());
+        assert!(graph.nodes.is_empty());
+    }
+}
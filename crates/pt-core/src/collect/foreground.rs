@@ -0,0 +1,169 @@
+//! Foreground-job detection for terminals in active use (Linux).
+//!
+//! A process in the foreground process group of its controlling terminal is
+//! what the user is looking at: the build they just started, the REPL they are
+//! typing into. When that terminal also read a keystroke recently, killing the
+//! process interrupts live work no matter how abandoned it looks.
+//!
+//! The foreground group is `tpgid` in `/proc/[pid]/stat`. The kernel updates a
+//! terminal device's atime when input is read from it (the same signal `w`
+//! reports as idle time), so the device's atime dates the last keystroke.
+
+use super::proc_parsers::{parse_proc_stat, ProcessStat};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Default keystroke recency, in seconds, for a terminal to count as in use.
+pub const DEFAULT_FOREGROUND_IDLE_SECS: u64 = 300;
+
+/// A process that is the foreground job of its controlling terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForegroundJob {
+    /// Controlling terminal device (e.g. `/dev/pts/3`).
+    pub tty: String,
+    /// Process group, which is the terminal's foreground group.
+    pub pgid: u32,
+    /// Seconds since the terminal last read input; `None` when unreadable.
+    pub idle_secs: Option<u64>,
+}
+
+impl ForegroundJob {
+    /// Whether the terminal read input within `max_idle_secs`.
+    pub fn is_active(&self, max_idle_secs: u64) -> bool {
+        self.idle_secs.is_some_and(|idle| idle <= max_idle_secs)
+    }
+
+    /// One-line description for rationales and block reasons.
+    pub fn summary(&self) -> String {
+        match self.idle_secs {
+            Some(idle) => format!(
+                "foreground job of {} (last keystroke {}s ago)",
+                self.tty, idle
+            ),
+            None => format!("foreground job of {}", self.tty),
+        }
+    }
+}
+
+/// Foreground-job status of `pid`; `None` when it is not the foreground
+/// process group of a terminal (or has exited).
+pub fn foreground_job(pid: u32) -> Option<ForegroundJob> {
+    let stat = parse_proc_stat(pid)?;
+    foreground_job_from_stat(&stat, tty_idle_secs)
+}
+
+/// Foreground-job status from parsed stat fields, with `idle_secs` giving the
+/// input idle time of a terminal device.
+pub fn foreground_job_from_stat(
+    stat: &ProcessStat,
+    idle_secs: impl Fn(&Path) -> Option<u64>,
+) -> Option<ForegroundJob> {
+    if stat.tty_nr == 0 || stat.tpgid <= 0 || stat.pgrp != stat.tpgid {
+        return None;
+    }
+    let tty = tty_device_path(stat.tty_nr as u32)?;
+    Some(ForegroundJob {
+        idle_secs: idle_secs(&tty),
+        tty: tty.to_string_lossy().into_owned(),
+        pgid: stat.pgrp as u32,
+    })
+}
+
+/// Device path of a `tty_nr` from `/proc/[pid]/stat`.
+///
+/// Covers pseudo-terminals (majors 136-143), virtual consoles and serial
+/// ports (major 4); other terminal drivers yield `None`.
+pub fn tty_device_path(tty_nr: u32) -> Option<PathBuf> {
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    let path = match major {
+        136..=143 => format!("/dev/pts/{}", (major - 136) * 256 + minor),
+        4 if minor < 64 => format!("/dev/tty{}", minor),
+        4 => format!("/dev/ttyS{}", minor - 64),
+        _ => return None,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Seconds since the terminal device `tty` last read input.
+pub fn tty_idle_secs(tty: &Path) -> Option<u64> {
+    let accessed = fs::metadata(tty).ok()?.accessed().ok()?;
+    // An atime in the future (clock steps) counts as input just now.
+    Some(
+        SystemTime::now()
+            .duration_since(accessed)
+            .map_or(0, |age| age.as_secs()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::proc_parsers::parse_proc_stat_content;
+
+    fn stat(pgrp: i32, tty_nr: i32, tpgid: i32) -> ProcessStat {
+        parse_proc_stat_content(&format!(
+            "4242 (make) R 4000 {} 4000 {} {} 4194304 0 0 0 0 10 5 0 0 20 0 1 0 98765 1000 100",
+            pgrp, tty_nr, tpgid
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn maps_tty_numbers_to_devices() {
+        assert_eq!(
+            tty_device_path((136 << 8) | 3),
+            Some(PathBuf::from("/dev/pts/3"))
+        );
+        assert_eq!(
+            tty_device_path((137 << 8) | 44),
+            Some(PathBuf::from("/dev/pts/300"))
+        );
+        assert_eq!(
+            tty_device_path((4 << 8) | 1),
+            Some(PathBuf::from("/dev/tty1"))
+        );
+        assert_eq!(
+            tty_device_path((4 << 8) | 65),
+            Some(PathBuf::from("/dev/ttyS1"))
+        );
+        assert_eq!(tty_device_path((5 << 8) | 1), None);
+    }
+
+    #[test]
+    fn detects_foreground_job_with_recent_input() {
+        let pts3 = (136 << 8) | 3;
+        let job = foreground_job_from_stat(&stat(4242, pts3, 4242), |tty| {
+            assert_eq!(tty, Path::new("/dev/pts/3"));
+            Some(12)
+        })
+        .unwrap();
+        assert_eq!(job.pgid, 4242);
+        assert!(job.is_active(DEFAULT_FOREGROUND_IDLE_SECS));
+        assert!(!job.is_active(10));
+        assert_eq!(
+            job.summary(),
+            "foreground job of /dev/pts/3 (last keystroke 12s ago)"
+        );
+
+        let unreadable = foreground_job_from_stat(&stat(4242, pts3, 4242), |_| None).unwrap();
+        assert!(!unreadable.is_active(DEFAULT_FOREGROUND_IDLE_SECS));
+    }
+
+    #[test]
+    fn background_and_detached_processes_are_not_foreground() {
+        let pts3 = (136 << 8) | 3;
+        // Background job: the terminal's foreground group is another one.
+        assert_eq!(
+            foreground_job_from_stat(&stat(4242, pts3, 5000), |_| Some(0)),
+            None
+        );
+        // No controlling terminal.
+        assert_eq!(
+            foreground_job_from_stat(&stat(4242, 0, -1), |_| Some(0)),
+            None
+        );
+    }
+}
@@ -1,14 +1,17 @@
 //! GPU process detection and device information collection.
 //!
 //! Detects GPU presence and per-process GPU usage using:
-//! - `nvidia-smi` for NVIDIA GPUs (CUDA)
+//! - `nvidia-smi` for NVIDIA GPUs (CUDA), including per-process SM
+//!   utilization from `nvidia-smi pmon`
 //! - `rocm-smi` for AMD GPUs (ROCm)
+//! - `/sys/class/drm` plus per-client `fdinfo` as a vendor-neutral fallback
 //!
 //! # Graceful Degradation
 //! - All GPU tools are optional; missing tools are silently skipped
 //! - Parse failures produce warnings in provenance, never hard errors
 //! - Query results are cached to avoid hammering expensive GPU tools
 
+use crate::inference::GpuEvidence;
 use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -50,6 +53,8 @@ pub enum GpuType {
     Nvidia,
     /// AMD GPU (ROCm capable).
     Amd,
+    /// GPU seen only through DRM (e.g. Intel, or no vendor tool installed).
+    Other,
     /// No GPU detected.
     #[default]
     None,
@@ -63,6 +68,8 @@ pub enum GpuDetectionSource {
     NvidiaSmi,
     /// Data from rocm-smi.
     RocmSmi,
+    /// Data from /sys/class/drm and per-client /proc/\[pid\]/fdinfo.
+    DrmFdinfo,
     /// No GPU data source available.
     #[default]
     None,
@@ -118,6 +125,52 @@ pub struct ProcessGpuUsage {
     /// Process type as reported by nvidia-smi (C=Compute, G=Graphics, C+G).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gpu_process_type: Option<String>,
+    /// Share of time this process kept the GPU busy (0-100), if measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utilization_percent: Option<u32>,
+}
+
+/// Minimum device memory for a process to count as holding a GPU.
+///
+/// Desktop processes routinely open a DRM render node with a few MiB of
+/// buffers; a CUDA/ROCm context or a loaded model is far above this.
+pub const GPU_HELD_MIN_MIB: u64 = 64;
+
+/// GPU occupancy of a single process, summed across devices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProcessGpuSummary {
+    /// Device memory held by the process in MiB.
+    pub memory_mib: u64,
+    /// Highest per-device utilization (0-100), if measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utilization_percent: Option<u32>,
+    /// Device indices the process uses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub devices: Vec<u32>,
+    /// Source the usage came from.
+    pub source: GpuDetectionSource,
+}
+
+impl ProcessGpuSummary {
+    /// Whether the process holds enough device memory to count as a GPU user.
+    pub fn holds_gpu(&self) -> bool {
+        self.memory_mib >= GPU_HELD_MIN_MIB
+    }
+
+    /// Convert to posterior evidence.
+    ///
+    /// Utilization is only reported for holders; below the memory threshold
+    /// the process is treated as not holding a GPU at all.
+    pub fn to_evidence(&self) -> GpuEvidence {
+        let held = self.holds_gpu();
+        GpuEvidence {
+            held,
+            utilization: self
+                .utilization_percent
+                .filter(|_| held)
+                .map(|u| f64::from(u.min(100)) / 100.0),
+        }
+    }
 }
 
 /// System-wide GPU information snapshot.
@@ -236,7 +289,7 @@ fn parse_nvidia_device_fields(fields: &[&str]) -> Result<GpuDevice, GpuError> {
 }
 
 /// Query per-process GPU usage from nvidia-smi.
-fn query_nvidia_processes() -> Result<Vec<ProcessGpuUsage>, GpuError> {
+fn query_nvidia_processes(devices: &[GpuDevice]) -> Result<Vec<ProcessGpuUsage>, GpuError> {
     let output = Command::new("nvidia-smi")
         .args([
             "--query-compute-apps=pid,gpu_uuid,used_memory",
@@ -254,7 +307,60 @@ fn query_nvidia_processes() -> Result<Vec<ProcessGpuUsage>, GpuError> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_nvidia_process_csv(&stdout, &[])
+    parse_nvidia_process_csv(&stdout, devices)
+}
+
+/// Sample per-process SM utilization once via `nvidia-smi pmon`.
+fn query_nvidia_utilization() -> Result<HashMap<(u32, u32), u32>, GpuError> {
+    let output = Command::new("nvidia-smi")
+        .args(["pmon", "-c", "1", "-s", "u"])
+        .output()
+        .map_err(|e| GpuError::ExecutionFailed(format!("nvidia-smi pmon: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GpuError::ExecutionFailed(format!(
+            "nvidia-smi pmon exited {}: {}",
+            output.status, stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_nvidia_pmon(&stdout))
+}
+
+/// Parse `nvidia-smi pmon -s u` output into (pid, gpu index) -> SM %.
+///
+/// Format (header lines start with `#`):
+/// `gpu  pid  type  sm  mem  enc  dec  command`
+///
+/// A `-` in the SM column means the process was not sampled busy during the
+/// interval and is reported as 0.
+pub fn parse_nvidia_pmon(output: &str) -> HashMap<(u32, u32), u32> {
+    let mut util = HashMap::new();
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let (Ok(gpu), Ok(pid)) = (fields[0].parse::<u32>(), fields[1].parse::<u32>()) else {
+            continue;
+        };
+        let sm = if fields[3] == "-" {
+            0
+        } else {
+            match fields[3].parse::<u32>() {
+                Ok(v) => v.min(100),
+                Err(_) => continue,
+            }
+        };
+        util.insert((pid, gpu), sm);
+    }
+    util
 }
 
 /// Parse nvidia-smi per-process CSV output.
@@ -298,6 +404,7 @@ pub fn parse_nvidia_process_csv(
             gpu_index,
             used_gpu_memory_mib: used_mem,
             gpu_process_type: None,
+            utilization_percent: None,
         });
     }
     Ok(usages)
@@ -495,6 +602,7 @@ pub fn parse_rocm_process_json(json_str: &str) -> Result<Vec<ProcessGpuUsage>, G
                         gpu_index,
                         used_gpu_memory_mib: mem_mib,
                         gpu_process_type: Some("Compute".to_string()),
+                        utilization_percent: None,
                     });
                 }
             }
@@ -503,14 +611,297 @@ pub fn parse_rocm_process_json(json_str: &str) -> Result<Vec<ProcessGpuUsage>, G
     Ok(usages)
 }
 
+// ---------------------------------------------------------------------------
+// DRM fdinfo fallback
+// ---------------------------------------------------------------------------
+
+const DRM_CLASS_DIR: &str = "/sys/class/drm";
+
+/// Interval between the two fdinfo samples used to derive utilization.
+const DRM_SAMPLE_INTERVAL_MS: u64 = 200;
+
+static DRM_CARD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^card(\d+)$").unwrap());
+
+/// Usage of one DRM client, parsed from `/proc/[pid]/fdinfo/[fd]`.
+///
+/// See the kernel's `drm-usage-stats` documentation for the key format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DrmClientUsage {
+    /// Kernel driver name (`drm-driver`).
+    pub driver: String,
+    /// Client id, unique per open DRM file (`drm-client-id`).
+    pub client_id: Option<u64>,
+    /// PCI address of the device (`drm-pdev`).
+    pub pdev: Option<String>,
+    /// Device memory attributed to the client, in bytes.
+    pub memory_bytes: u64,
+    /// Cumulative busy time summed across engines, in nanoseconds.
+    pub engine_busy_ns: u64,
+}
+
+/// Parse a DRM fdinfo file. Returns `None` if it is not a DRM client.
+pub fn parse_drm_fdinfo(content: &str) -> Option<DrmClientUsage> {
+    let mut usage = DrmClientUsage::default();
+    let mut is_drm = false;
+    let (mut resident, mut memory, mut total) = (None::<u64>, None::<u64>, None::<u64>);
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        match key {
+            "drm-driver" => {
+                usage.driver = value.to_string();
+                is_drm = true;
+            }
+            "drm-pdev" => usage.pdev = non_empty(value),
+            "drm-client-id" => usage.client_id = parse_u64_opt(value),
+            _ if key.starts_with("drm-engine-") && !key.starts_with("drm-engine-capacity-") => {
+                if let Some(ns) = value.strip_suffix("ns").and_then(parse_u64_opt) {
+                    usage.engine_busy_ns = usage.engine_busy_ns.saturating_add(ns);
+                }
+            }
+            _ => {
+                let slot = if key.starts_with("drm-resident-") {
+                    &mut resident
+                } else if key.starts_with("drm-memory-") {
+                    &mut memory
+                } else if key.starts_with("drm-total-") {
+                    &mut total
+                } else {
+                    continue;
+                };
+                if let Some(bytes) = parse_drm_size(value) {
+                    *slot = Some(slot.unwrap_or(0).saturating_add(bytes));
+                }
+            }
+        }
+    }
+
+    if !is_drm {
+        return None;
+    }
+    usage.memory_bytes = resident.or(memory).or(total).unwrap_or(0);
+    Some(usage)
+}
+
+/// Parse an fdinfo memory value such as `1024 KiB`, `3 MiB` or `4096`.
+fn parse_drm_size(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let n = parts.next()?.parse::<u64>().ok()?;
+    let mult = match parts.next() {
+        None => 1,
+        Some("KiB") => 1024,
+        Some("MiB") => 1024 * 1024,
+        Some("GiB") => 1024 * 1024 * 1024,
+        Some(_) => return None,
+    };
+    Some(n.saturating_mul(mult))
+}
+
+/// Read all DRM clients held by a process, deduplicated by client id.
+fn read_drm_clients(pid: u32) -> Vec<DrmClientUsage> {
+    let Ok(entries) = std::fs::read_dir(format!("/proc/{pid}/fd")) else {
+        return Vec::new();
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut clients = Vec::new();
+    for entry in entries.flatten() {
+        let is_dri = std::fs::read_link(entry.path())
+            .map(|target| target.starts_with("/dev/dri"))
+            .unwrap_or(false);
+        if !is_dri {
+            continue;
+        }
+        let fd = entry.file_name();
+        let Ok(content) =
+            std::fs::read_to_string(format!("/proc/{pid}/fdinfo/{}", fd.to_string_lossy()))
+        else {
+            continue;
+        };
+        if let Some(client) = parse_drm_fdinfo(&content) {
+            if client.client_id.is_some_and(|id| !seen.insert(id)) {
+                continue;
+            }
+            clients.push(client);
+        }
+    }
+    clients
+}
+
+/// A GPU card found under `/sys/class/drm`.
+struct DrmCard {
+    device: GpuDevice,
+    pdev: Option<String>,
+}
+
+fn enumerate_drm_cards() -> Vec<DrmCard> {
+    let Ok(entries) = std::fs::read_dir(DRM_CLASS_DIR) else {
+        return Vec::new();
+    };
+    let mut cards = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(index) = DRM_CARD_RE
+            .captures(&name)
+            .and_then(|c| c[1].parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let device_dir = entry.path().join("device");
+        let pdev = std::fs::canonicalize(&device_dir)
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+        let driver = std::fs::read_link(device_dir.join("driver"))
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "drm".to_string());
+        let read_bytes = |file: &str| {
+            std::fs::read_to_string(device_dir.join(file))
+                .ok()
+                .and_then(|v| parse_u64_opt(&v))
+                .map(|b| b / (1024 * 1024))
+        };
+        cards.push(DrmCard {
+            device: GpuDevice {
+                index,
+                name: driver,
+                uuid: None,
+                memory_total_mib: read_bytes("mem_info_vram_total"),
+                memory_used_mib: read_bytes("mem_info_vram_used"),
+                utilization_percent: std::fs::read_to_string(device_dir.join("gpu_busy_percent"))
+                    .ok()
+                    .and_then(|v| parse_u32_opt(&v)),
+                temperature_c: None,
+                driver_version: None,
+            },
+            pdev,
+        });
+    }
+    cards.sort_by_key(|c| c.device.index);
+    cards
+}
+
+/// Sample DRM clients of every process, keyed by pid.
+fn sample_drm_clients() -> HashMap<u32, Vec<DrmClientUsage>> {
+    let mut by_pid = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return by_pid;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let clients = read_drm_clients(pid);
+        if !clients.is_empty() {
+            by_pid.insert(pid, clients);
+        }
+    }
+    by_pid
+}
+
+/// Fold two fdinfo samples into per-process usage.
+///
+/// Utilization is the engine busy time accumulated between the samples
+/// divided by the wall-clock interval. Clients present in only one sample
+/// are reported without utilization.
+pub fn drm_usage_from_samples(
+    first: &HashMap<u32, Vec<DrmClientUsage>>,
+    second: &HashMap<u32, Vec<DrmClientUsage>>,
+    interval_ns: u64,
+    card_for_pdev: &HashMap<String, u32>,
+) -> Vec<ProcessGpuUsage> {
+    let mut usages = Vec::new();
+    for (&pid, clients) in second {
+        // Aggregate per device index.
+        let mut per_gpu: HashMap<u32, (u64, Option<u64>)> = HashMap::new();
+        for client in clients {
+            let gpu_index = client
+                .pdev
+                .as_ref()
+                .and_then(|p| card_for_pdev.get(p).copied())
+                .unwrap_or(0);
+            let busy_delta = first.get(&pid).and_then(|prev| {
+                prev.iter()
+                    .find(|c| c.client_id.is_some() && c.client_id == client.client_id)
+                    .map(|c| client.engine_busy_ns.saturating_sub(c.engine_busy_ns))
+            });
+            let slot = per_gpu.entry(gpu_index).or_insert((0, None));
+            slot.0 = slot.0.saturating_add(client.memory_bytes);
+            if let Some(delta) = busy_delta {
+                slot.1 = Some(slot.1.unwrap_or(0).saturating_add(delta));
+            }
+        }
+        for (gpu_index, (memory_bytes, busy_ns)) in per_gpu {
+            let utilization_percent = match (busy_ns, interval_ns) {
+                (Some(busy), interval) if interval > 0 => {
+                    Some((busy.saturating_mul(100) / interval).min(100) as u32)
+                }
+                _ => None,
+            };
+            usages.push(ProcessGpuUsage {
+                pid,
+                gpu_index,
+                used_gpu_memory_mib: Some(memory_bytes / (1024 * 1024)),
+                gpu_process_type: None,
+                utilization_percent,
+            });
+        }
+    }
+    usages.sort_by_key(|u| (u.pid, u.gpu_index));
+    usages
+}
+
+fn collect_drm_snapshot() -> Result<GpuSnapshot, GpuError> {
+    let cards = enumerate_drm_cards();
+    if cards.is_empty() {
+        return Err(GpuError::ToolNotFound(DRM_CLASS_DIR.to_string()));
+    }
+    let card_for_pdev: HashMap<String, u32> = cards
+        .iter()
+        .filter_map(|c| c.pdev.clone().map(|p| (p, c.device.index)))
+        .collect();
+
+    let start = std::time::Instant::now();
+    let first = sample_drm_clients();
+    std::thread::sleep(std::time::Duration::from_millis(DRM_SAMPLE_INTERVAL_MS));
+    let second = sample_drm_clients();
+    let interval_ns = start.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+
+    let processes = drm_usage_from_samples(&first, &second, interval_ns, &card_for_pdev);
+    let mut process_usage: HashMap<u32, Vec<ProcessGpuUsage>> = HashMap::new();
+    for p in &processes {
+        process_usage.entry(p.pid).or_default().push(p.clone());
+    }
+    let gpu_process_count = process_usage.len();
+
+    Ok(GpuSnapshot {
+        has_gpu: true,
+        gpu_type: GpuType::Other,
+        devices: cards.into_iter().map(|c| c.device).collect(),
+        process_usage,
+        gpu_process_count,
+        provenance: GpuProvenance {
+            source: GpuDetectionSource::DrmFdinfo,
+            warnings: Vec::new(),
+        },
+    })
+}
+
 // ---------------------------------------------------------------------------
 // High-level API
 // ---------------------------------------------------------------------------
 
 /// Collect a system-wide GPU snapshot.
 ///
-/// Tries NVIDIA first, then AMD. Returns a default (no-GPU) snapshot if
-/// neither tool is available.
+/// Tries NVIDIA first, then AMD, then the DRM fdinfo interface. Returns a
+/// default (no-GPU) snapshot if no source is available.
 pub fn collect_gpu_snapshot() -> GpuSnapshot {
     // Try NVIDIA
     if is_nvidia_available() {
@@ -541,13 +932,28 @@ pub fn collect_gpu_snapshot() -> GpuSnapshot {
         }
     }
 
+    // Fall back to the kernel DRM interface
+    match collect_drm_snapshot() {
+        Ok(snap) => return snap,
+        Err(e) => trace!(error = %e, "no DRM GPU devices"),
+    }
+
     trace!("no GPU tools available");
     GpuSnapshot::default()
 }
 
 fn collect_nvidia_snapshot() -> Result<GpuSnapshot, GpuError> {
     let devices = query_nvidia_devices()?;
-    let processes = query_nvidia_processes().unwrap_or_default();
+    let mut processes = query_nvidia_processes(&devices).unwrap_or_default();
+    let mut warnings = Vec::new();
+    match query_nvidia_utilization() {
+        Ok(util) => {
+            for p in &mut processes {
+                p.utilization_percent = util.get(&(p.pid, p.gpu_index)).copied();
+            }
+        }
+        Err(e) => warnings.push(format!("per-process utilization unavailable: {e}")),
+    }
 
     let mut process_usage: HashMap<u32, Vec<ProcessGpuUsage>> = HashMap::new();
     for p in &processes {
@@ -563,7 +969,7 @@ fn collect_nvidia_snapshot() -> Result<GpuSnapshot, GpuError> {
         gpu_process_count,
         provenance: GpuProvenance {
             source: GpuDetectionSource::NvidiaSmi,
-            warnings: Vec::new(),
+            warnings,
         },
     })
 }
@@ -604,6 +1010,31 @@ pub fn total_vram_mib_for_pid(snapshot: &GpuSnapshot, pid: u32) -> Option<u64> {
         .map(|usages| usages.iter().filter_map(|u| u.used_gpu_memory_mib).sum())
 }
 
+/// Summarize GPU occupancy of a PID.
+///
+/// Returns `None` when the snapshot saw no GPU at all, so callers can tell
+/// "no GPU on this host" apart from "this process holds no GPU".
+pub fn process_gpu_summary(snapshot: &GpuSnapshot, pid: u32) -> Option<ProcessGpuSummary> {
+    if !snapshot.has_gpu {
+        return None;
+    }
+    let mut summary = ProcessGpuSummary {
+        source: snapshot.provenance.source,
+        ..Default::default()
+    };
+    for usage in snapshot.process_usage.get(&pid).into_iter().flatten() {
+        summary.memory_mib += usage.used_gpu_memory_mib.unwrap_or(0);
+        if let Some(util) = usage.utilization_percent {
+            summary.utilization_percent = Some(summary.utilization_percent.unwrap_or(0).max(util));
+        }
+        if !summary.devices.contains(&usage.gpu_index) {
+            summary.devices.push(usage.gpu_index);
+        }
+    }
+    summary.devices.sort_unstable();
+    Some(summary)
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -867,6 +1298,7 @@ GPU  Temp  AvgPwr  SCLK     MCLK     Fan  Perf    PwrCap  VRAM%  GPU%
                 gpu_index: 0,
                 used_gpu_memory_mib: Some(2048),
                 gpu_process_type: None,
+                utilization_percent: None,
             }],
         );
         let snap = GpuSnapshot {
@@ -891,12 +1323,14 @@ GPU  Temp  AvgPwr  SCLK     MCLK     Fan  Perf    PwrCap  VRAM%  GPU%
                     gpu_index: 0,
                     used_gpu_memory_mib: Some(1024),
                     gpu_process_type: None,
+                    utilization_percent: None,
                 },
                 ProcessGpuUsage {
                     pid: 42,
                     gpu_index: 1,
                     used_gpu_memory_mib: Some(2048),
                     gpu_process_type: None,
+                    utilization_percent: None,
                 },
             ],
         );
@@ -911,6 +1345,141 @@ GPU  Temp  AvgPwr  SCLK     MCLK     Fan  Perf    PwrCap  VRAM%  GPU%
         assert_eq!(total_vram_mib_for_pid(&snap, 999), None);
     }
 
+    // === nvidia-smi pmon parsing ===
+
+    #[test]
+    fn test_parse_nvidia_pmon() {
+        let out = "\
+# gpu        pid  type    sm   mem   enc   dec   command
+# Idx          #   C/G     %     %     %     %   name
+    0      12345     C    87    40     -     -   python
+    0      12346     C     -     -     -     -   python
+    1      22222     G     3     1     -     -   Xorg
+    0          -     -     -     -     -     -   -
+";
+        let util = parse_nvidia_pmon(out);
+        assert_eq!(util.len(), 3);
+        assert_eq!(util.get(&(12345, 0)), Some(&87));
+        assert_eq!(util.get(&(12346, 0)), Some(&0));
+        assert_eq!(util.get(&(22222, 1)), Some(&3));
+    }
+
+    // === DRM fdinfo parsing ===
+
+    #[test]
+    fn test_parse_drm_fdinfo_amdgpu() {
+        let content = "\
+pos:\t0
+flags:\t02100002
+drm-driver:\tamdgpu
+drm-pdev:\t0000:03:00.0
+drm-client-id:\t17
+drm-memory-vram:\t2048 MiB
+drm-memory-gtt:\t16 MiB
+drm-engine-gfx:\t1500000 ns
+drm-engine-compute:\t500000 ns
+";
+        let usage = parse_drm_fdinfo(content).unwrap();
+        assert_eq!(usage.driver, "amdgpu");
+        assert_eq!(usage.pdev.as_deref(), Some("0000:03:00.0"));
+        assert_eq!(usage.client_id, Some(17));
+        assert_eq!(usage.memory_bytes, 2064 * 1024 * 1024);
+        assert_eq!(usage.engine_busy_ns, 2_000_000);
+    }
+
+    #[test]
+    fn test_parse_drm_fdinfo_prefers_resident() {
+        let content = "\
+drm-driver:\ti915
+drm-client-id:\t3
+drm-total-system0:\t8192 KiB
+drm-resident-system0:\t4096 KiB
+drm-engine-render:\t100 ns
+drm-engine-capacity-render:\t2
+";
+        let usage = parse_drm_fdinfo(content).unwrap();
+        assert_eq!(usage.memory_bytes, 4096 * 1024);
+        assert_eq!(usage.engine_busy_ns, 100);
+    }
+
+    #[test]
+    fn test_parse_drm_fdinfo_not_drm() {
+        assert!(parse_drm_fdinfo("pos:\t0\nflags:\t02\n").is_none());
+    }
+
+    #[test]
+    fn test_drm_usage_from_samples() {
+        let client = |busy_ns: u64| DrmClientUsage {
+            driver: "amdgpu".into(),
+            client_id: Some(5),
+            pdev: Some("0000:03:00.0".into()),
+            memory_bytes: 512 * 1024 * 1024,
+            engine_busy_ns: busy_ns,
+        };
+        let first = HashMap::from([(7, vec![client(1_000)])]);
+        let second = HashMap::from([(7, vec![client(51_000)]), (8, vec![client(0)])]);
+        let cards = HashMap::from([("0000:03:00.0".to_string(), 1)]);
+
+        let usages = drm_usage_from_samples(&first, &second, 100_000, &cards);
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].pid, 7);
+        assert_eq!(usages[0].gpu_index, 1);
+        assert_eq!(usages[0].used_gpu_memory_mib, Some(512));
+        assert_eq!(usages[0].utilization_percent, Some(50));
+        // New client: no previous sample, no utilization.
+        assert_eq!(usages[1].pid, 8);
+        assert_eq!(usages[1].utilization_percent, None);
+    }
+
+    // === Per-process summary ===
+
+    #[test]
+    fn test_process_gpu_summary() {
+        let mut process_usage = HashMap::new();
+        process_usage.insert(
+            42,
+            vec![
+                ProcessGpuUsage {
+                    pid: 42,
+                    gpu_index: 1,
+                    used_gpu_memory_mib: Some(6000),
+                    gpu_process_type: Some("C".into()),
+                    utilization_percent: Some(2),
+                },
+                ProcessGpuUsage {
+                    pid: 42,
+                    gpu_index: 0,
+                    used_gpu_memory_mib: Some(100),
+                    gpu_process_type: Some("C".into()),
+                    utilization_percent: Some(9),
+                },
+            ],
+        );
+        let snap = GpuSnapshot {
+            has_gpu: true,
+            gpu_type: GpuType::Nvidia,
+            process_usage,
+            gpu_process_count: 1,
+            provenance: GpuProvenance {
+                source: GpuDetectionSource::NvidiaSmi,
+                warnings: Vec::new(),
+            },
+            ..Default::default()
+        };
+
+        let summary = process_gpu_summary(&snap, 42).unwrap();
+        assert_eq!(summary.memory_mib, 6100);
+        assert_eq!(summary.utilization_percent, Some(9));
+        assert_eq!(summary.devices, vec![0, 1]);
+        assert!(summary.holds_gpu());
+
+        let idle = process_gpu_summary(&snap, 7).unwrap();
+        assert_eq!(idle.memory_mib, 0);
+        assert!(!idle.holds_gpu());
+
+        assert!(process_gpu_summary(&GpuSnapshot::default(), 42).is_none());
+    }
+
     // === Default / serialization ===
 
     #[test]
@@ -933,6 +1502,7 @@ GPU  Temp  AvgPwr  SCLK     MCLK     Fan  Perf    PwrCap  VRAM%  GPU%
                 gpu_index: 0,
                 used_gpu_memory_mib: Some(512),
                 gpu_process_type: Some("C".into()),
+                utilization_percent: None,
             }],
         );
         let snap = GpuSnapshot {
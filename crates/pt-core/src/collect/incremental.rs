@@ -425,6 +425,7 @@ mod tests {
             elapsed: Duration::from_secs(3600),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         }
     }
 
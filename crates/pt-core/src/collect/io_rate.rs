@@ -0,0 +1,155 @@
+//! Storage I/O rates from two samples of per-process I/O counters.
+//!
+//! `read_bytes` / `write_bytes` count from process start, so every long-lived
+//! process that ever touched the disk looks I/O-active. Sampling them twice,
+//! a short window apart, gives the current rate instead. Quick scan takes the
+//! first sample before listing processes and the second afterwards, sleeping
+//! only for whatever is left of the window.
+//!
+//! Counters come from `/proc/[pid]/io` on Linux and `proc_pid_rusage` on
+//! macOS. Processes whose counters are unreadable (other users' processes
+//! without privileges) or that started during the window get no rate.
+
+use super::types::{IoRate, ProcessRecord};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default time between the two samples.
+pub const DEFAULT_IO_RATE_WINDOW: Duration = Duration::from_millis(250);
+
+/// First sample of the I/O counters, awaiting the second.
+#[derive(Debug)]
+pub struct IoSampler {
+    started: Instant,
+    counters: HashMap<u32, (u64, u64)>,
+}
+
+impl IoSampler {
+    /// Take the first sample for `pids`, or every visible process when empty.
+    pub fn start(pids: &[u32]) -> Self {
+        let started = Instant::now();
+        let pids = if pids.is_empty() {
+            list_pids()
+        } else {
+            pids.to_vec()
+        };
+        let counters = pids
+            .into_iter()
+            .filter_map(|pid| read_counters(pid).map(|c| (pid, c)))
+            .collect();
+        Self { started, counters }
+    }
+
+    /// Take the second sample once `window` has passed and set each record's
+    /// `io_rate`.
+    pub fn finish(self, window: Duration, processes: &mut [ProcessRecord]) {
+        if let Some(remaining) = window.checked_sub(self.started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        let elapsed = self.started.elapsed();
+        for proc in processes {
+            proc.io_rate = self.counters.get(&proc.pid.0).and_then(|&before| {
+                read_counters(proc.pid.0).and_then(|after| compute_io_rate(before, after, elapsed))
+            });
+        }
+    }
+}
+
+/// Rate between two `(read_bytes, write_bytes)` samples `elapsed` apart.
+///
+/// Counters that went backwards mean the PID was reused; that yields `None`.
+pub fn compute_io_rate(before: (u64, u64), after: (u64, u64), elapsed: Duration) -> Option<IoRate> {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return None;
+    }
+    let read = after.0.checked_sub(before.0)?;
+    let write = after.1.checked_sub(before.1)?;
+    Some(IoRate {
+        read_bytes_per_sec: read as f64 / secs,
+        write_bytes_per_sec: write as f64 / secs,
+        window_ms: elapsed.as_millis() as u64,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn list_pids() -> Vec<u32> {
+    std::fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn read_counters(pid: u32) -> Option<(u64, u64)> {
+    super::proc_parsers::parse_io(pid).map(|io| (io.read_bytes, io.write_bytes))
+}
+
+#[cfg(target_os = "macos")]
+fn list_pids() -> Vec<u32> {
+    super::macos::native_list_pids().unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn read_counters(pid: u32) -> Option<(u64, u64)> {
+    super::macos::native_disk_io(pid).map(|io| (io.read_bytes, io.write_bytes))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn list_pids() -> Vec<u32> {
+    Vec::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_counters(_pid: u32) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_from_counter_deltas() {
+        let rate =
+            compute_io_rate((1_000, 5_000), (3_000, 5_000), Duration::from_millis(500)).unwrap();
+        assert_eq!(rate.read_bytes_per_sec, 4_000.0);
+        assert_eq!(rate.write_bytes_per_sec, 0.0);
+        assert_eq!(rate.total_bytes_per_sec(), 4_000.0);
+        assert_eq!(rate.window_ms, 500);
+
+        // Cumulative totals alone no longer make a process look busy.
+        let idle = compute_io_rate(
+            (9_000_000_000, 1_000_000),
+            (9_000_000_000, 1_000_000),
+            Duration::from_millis(250),
+        )
+        .unwrap();
+        assert_eq!(idle.total_bytes_per_sec(), 0.0);
+
+        assert_eq!(
+            compute_io_rate((5_000, 0), (10, 0), Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(compute_io_rate((0, 0), (1, 1), Duration::ZERO), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn samples_own_process() {
+        let pid = std::process::id();
+        let sampler = IoSampler::start(&[pid]);
+        let mut records = vec![crate::mock_process::MockProcessBuilder::new()
+            .pid(pid)
+            .build()];
+        sampler.finish(Duration::from_millis(20), &mut records);
+        if let Some(rate) = records[0].io_rate {
+            assert!(rate.window_ms >= 20);
+            assert!(rate.total_bytes_per_sec() >= 0.0);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! launchd job detection and collection.
+//!
+//! This module provides launchd job information for process triage on macOS:
+//! - Job label and domain for a running process
+//! - KeepAlive / RunAtLoad properties, used to predict respawns
+//! - Current job PID, used to recognise launchd-managed restarts
+//!
+//! # Data Sources
+//! - `launchctl list` - PID to label mapping in the caller's domain
+//! - `launchctl print <domain>/<label>` - job properties and state
+//!
+//! Parsing is available on all platforms; collection functions return
+//! `None` off macOS.
+
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+/// launchd job information for a process.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchdJob {
+    /// Job label (e.g., "com.example.agent").
+    pub label: String,
+
+    /// Domain target (e.g., "system", "gui/501").
+    pub domain: String,
+
+    /// PID of the running job instance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+
+    /// launchd restarts the job when it exits (`KeepAlive`).
+    pub keep_alive: bool,
+
+    /// The job is started when loaded (`RunAtLoad`).
+    pub run_at_load: bool,
+
+    /// Property list the job was loaded from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plist_path: Option<String>,
+
+    /// Exit code of the previous instance, if it ever exited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_exit_code: Option<i32>,
+}
+
+impl LaunchdJob {
+    /// Service target as launchctl expects it (`<domain>/<label>`).
+    pub fn service_target(&self) -> String {
+        format!("{}/{}", self.domain, self.label)
+    }
+
+    /// Whether signalling the process would just have launchd start it again.
+    pub fn respawn_expected(&self) -> bool {
+        self.keep_alive
+    }
+
+    /// Command that stops the job and unloads it from its domain, so it
+    /// stays down until the next login or boot.
+    pub fn bootout_command(&self) -> String {
+        format!("launchctl bootout {}", self.service_target())
+    }
+}
+
+/// launchd domain that holds the jobs of `uid`.
+///
+/// Daemons run in the `system` domain; per-user agents in `gui/<uid>`.
+pub fn domain_for_uid(uid: u32) -> String {
+    if uid == 0 {
+        "system".to_string()
+    } else {
+        format!("gui/{}", uid)
+    }
+}
+
+/// Parse `launchctl print <domain>/<label>` output.
+///
+/// Only top-level job properties are read; nested blocks (environment,
+/// endpoints, ...) are skipped.
+pub fn parse_launchctl_print(output: &str, label: &str, domain: &str) -> LaunchdJob {
+    let mut job = LaunchdJob {
+        label: label.to_string(),
+        domain: domain.to_string(),
+        ..LaunchdJob::default()
+    };
+
+    let mut depth = 0usize;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed == "}" {
+            depth = depth.saturating_sub(1);
+            continue;
+        }
+        let opens_block = trimmed.ends_with('{');
+        if depth == 1 {
+            if let Some((key, value)) = trimmed.split_once(" = ") {
+                match key.trim() {
+                    "pid" => job.pid = value.trim().parse().ok(),
+                    "path" => job.plist_path = Some(value.trim().to_string()),
+                    "last exit code" => {
+                        // "0", "1: Operation not permitted" or "(never exited)"
+                        job.last_exit_code = value
+                            .split(':')
+                            .next()
+                            .and_then(|code| code.trim().parse().ok());
+                    }
+                    "properties" => {
+                        for property in value.split('|').map(str::trim) {
+                            match property {
+                                "keepalive" => job.keep_alive = true,
+                                "runatload" => job.run_at_load = true,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if opens_block {
+            depth += 1;
+        }
+    }
+
+    job
+}
+
+/// Detect the launchd job that runs `pid`.
+#[cfg(target_os = "macos")]
+pub fn detect_launchd_job(pid: u32) -> Option<LaunchdJob> {
+    let service = super::macos::detect_launchd_service(pid)?;
+    let uid = super::macos::native_proc_info(pid)?.uid;
+    let domain = domain_for_uid(uid);
+    let mut job = query_launchctl_print(&service.label, &domain).unwrap_or_else(|| LaunchdJob {
+        label: service.label.clone(),
+        domain,
+        last_exit_code: service.last_exit_status,
+        ..LaunchdJob::default()
+    });
+    job.pid = Some(pid);
+    Some(job)
+}
+
+/// Detect the launchd job that runs `pid` (not available on this platform).
+#[cfg(not(target_os = "macos"))]
+pub fn detect_launchd_job(_pid: u32) -> Option<LaunchdJob> {
+    None
+}
+
+/// PID of the job's running instance right now.
+///
+/// `None` when the job is not running, was booted out, or cannot be queried.
+#[cfg(target_os = "macos")]
+pub fn current_job_pid(label: &str, domain: &str) -> Option<u32> {
+    query_launchctl_print(label, domain)?.pid
+}
+
+/// PID of the job's running instance (not available on this platform).
+#[cfg(not(target_os = "macos"))]
+pub fn current_job_pid(_label: &str, _domain: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn query_launchctl_print(label: &str, domain: &str) -> Option<LaunchdJob> {
+    let output = Command::new("launchctl")
+        .args(["print", &format!("{}/{}", domain, label)])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(parse_launchctl_print(&stdout, label, domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRINT_OUTPUT: &str = "gui/501/com.example.indexer = {
+\tactive count = 1
+\tpath = /Users/dev/Library/LaunchAgents/com.example.indexer.plist
+\tstate = running
+
+\tprogram = /usr/local/bin/indexer
+\tenvironment = {
+\t\tPATH => /usr/bin:/bin
+\t\tpid = 7
+\t}
+
+\tpid = 4242
+\tlast exit code = 1: Operation not permitted
+
+\tproperties = partial import | keepalive | runatload | inferred program
+}
+";
+
+    #[test]
+    fn test_parse_launchctl_print() {
+        let job = parse_launchctl_print(PRINT_OUTPUT, "com.example.indexer", "gui/501");
+        assert_eq!(job.pid, Some(4242));
+        assert_eq!(job.last_exit_code, Some(1));
+        assert!(job.keep_alive);
+        assert!(job.run_at_load);
+        assert_eq!(
+            job.plist_path.as_deref(),
+            Some("/Users/dev/Library/LaunchAgents/com.example.indexer.plist")
+        );
+        assert!(job.respawn_expected());
+        assert_eq!(
+            job.bootout_command(),
+            "launchctl bootout gui/501/com.example.indexer"
+        );
+    }
+
+    #[test]
+    fn test_parse_launchctl_print_not_running() {
+        let output = "system/com.example.job = {
+\tstate = not running
+\tlast exit code = (never exited)
+\tproperties = runatload
+}
+";
+        let job = parse_launchctl_print(output, "com.example.job", "system");
+        assert_eq!(job.pid, None);
+        assert_eq!(job.last_exit_code, None);
+        assert!(!job.respawn_expected());
+        assert_eq!(domain_for_uid(0), "system");
+        assert_eq!(domain_for_uid(501), "gui/501");
+    }
+}
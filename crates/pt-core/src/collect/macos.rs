@@ -3,6 +3,14 @@
 //! This module provides detailed process inspection for macOS systems,
 //! using macOS-specific tools and APIs since /proc is not available.
 //!
+//! # Native APIs
+//! Where possible, process details come straight from the kernel:
+//! - `proc_listallpids` / `proc_pidinfo` (libproc) - PID list, BSD/task info,
+//!   open descriptor types (fds, sockets)
+//! - `proc_pidpath` - executable path
+//! - `proc_pid_rusage` - disk I/O counters
+//! - `sysctl kern.bootsessionuuid` - boot identifier for `start_id`
+//!
 //! # Tools Used
 //! - `ps` - BSD-style process listing (handled in quick_scan)
 //! - `lsof` - Open files and network connections
@@ -26,7 +34,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, trace, warn};
@@ -109,6 +117,10 @@ pub struct MacOsCapabilities {
     /// Whether launchctl can list services.
     pub launchctl_available: bool,
 
+    /// Whether libproc answers process queries (native collection).
+    #[serde(default)]
+    pub libproc_available: bool,
+
     /// Whether we have elevated privileges (root or TCC entitlements).
     pub elevated_privileges: bool,
 
@@ -138,6 +150,8 @@ impl MacOsCapabilities {
             .map(|o| o.status.success())
             .unwrap_or(false);
 
+        let libproc_available = native_list_pids().is_some();
+
         let elevated_privileges = unsafe { libc::geteuid() } == 0;
 
         let macos_version = Command::new("sw_vers")
@@ -152,6 +166,7 @@ impl MacOsCapabilities {
             lsof_available,
             netstat_available,
             launchctl_available,
+            libproc_available,
             elevated_privileges,
             macos_version,
         }
@@ -330,6 +345,14 @@ pub struct MacOsScanRecord {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub network_connections: Vec<MacOsNetworkConnection>,
 
+    /// Open descriptor counts by type (from libproc).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_summary: Option<MacOsFdSummary>,
+
+    /// Cumulative disk I/O (from proc_pid_rusage).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_io: Option<MacOsDiskIo>,
+
     /// launchd service info (if managed by launchd).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub launchd_service: Option<LaunchdService>,
@@ -606,6 +629,344 @@ pub fn collect_environ(pid: u32) -> Option<HashMap<String, String>> {
     }
 }
 
+// ============================================================================
+// Native libproc / sysctl
+// ============================================================================
+
+// libproc descriptor listing and rusage are not exposed by the libc crate;
+// layouts mirror <sys/proc_info.h> and <sys/resource.h>.
+const PROC_PIDLISTFDS: libc::c_int = 1;
+const PROX_FDTYPE_VNODE: u32 = 1;
+const PROX_FDTYPE_SOCKET: u32 = 2;
+const PROX_FDTYPE_KQUEUE: u32 = 5;
+const PROX_FDTYPE_PIPE: u32 = 6;
+const RUSAGE_INFO_V2: libc::c_int = 2;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct ProcFdInfo {
+    proc_fd: i32,
+    proc_fdtype: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+#[allow(dead_code)] // Filled in by the kernel; only the disk I/O fields are read.
+struct RusageInfoV2 {
+    ri_uuid: [u8; 16],
+    ri_user_time: u64,
+    ri_system_time: u64,
+    ri_pkg_idle_wkups: u64,
+    ri_interrupt_wkups: u64,
+    ri_pageins: u64,
+    ri_wired_size: u64,
+    ri_resident_size: u64,
+    ri_phys_footprint: u64,
+    ri_proc_start_abstime: u64,
+    ri_proc_exit_abstime: u64,
+    ri_child_user_time: u64,
+    ri_child_system_time: u64,
+    ri_child_pkg_idle_wkups: u64,
+    ri_child_interrupt_wkups: u64,
+    ri_child_pageins: u64,
+    ri_child_elapsed_abstime: u64,
+    ri_diskio_bytesread: u64,
+    ri_diskio_byteswritten: u64,
+}
+
+extern "C" {
+    fn proc_pid_rusage(
+        pid: libc::c_int,
+        flavor: libc::c_int,
+        buffer: *mut libc::c_void,
+    ) -> libc::c_int;
+}
+
+/// Process details read directly from the kernel via libproc.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeProcInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub uid: u32,
+    pub pgid: u32,
+    pub comm: String,
+    /// BSD state character (I, R, S, T, Z).
+    pub state: char,
+    /// Process start time (Unix seconds).
+    pub start_time_unix: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rss_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vsz_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+}
+
+/// Open descriptor counts by type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacOsFdSummary {
+    pub total: usize,
+    pub vnodes: usize,
+    pub sockets: usize,
+    pub pipes: usize,
+    pub kqueues: usize,
+    pub other: usize,
+}
+
+impl MacOsFdSummary {
+    fn from_fd_types(types: impl IntoIterator<Item = u32>) -> Self {
+        let mut summary = Self::default();
+        for fd_type in types {
+            summary.total += 1;
+            match fd_type {
+                PROX_FDTYPE_VNODE => summary.vnodes += 1,
+                PROX_FDTYPE_SOCKET => summary.sockets += 1,
+                PROX_FDTYPE_PIPE => summary.pipes += 1,
+                PROX_FDTYPE_KQUEUE => summary.kqueues += 1,
+                _ => summary.other += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Cumulative disk I/O for a process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacOsDiskIo {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+/// Boot session UUID (`kern.bootsessionuuid`), cached for the process lifetime.
+///
+/// Plays the role of Linux's `/proc/sys/kernel/random/boot_id` in `start_id`.
+pub fn boot_session_uuid() -> Option<String> {
+    static BOOT_UUID: OnceLock<Option<String>> = OnceLock::new();
+    BOOT_UUID
+        .get_or_init(|| {
+            let name = std::ffi::CString::new("kern.bootsessionuuid").ok()?;
+            let mut buf = [0u8; 64];
+            let mut len = buf.len();
+            let ret = unsafe {
+                libc::sysctlbyname(
+                    name.as_ptr(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    &mut len,
+                    std::ptr::null_mut(),
+                    0,
+                )
+            };
+            if ret != 0 {
+                return None;
+            }
+            parse_sysctl_uuid(&buf[..len.min(buf.len())])
+        })
+        .clone()
+}
+
+fn parse_sysctl_uuid(raw: &[u8]) -> Option<String> {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    let value = std::str::from_utf8(&raw[..end]).ok()?.trim();
+    uuid::Uuid::parse_str(value).ok()?;
+    Some(value.to_lowercase())
+}
+
+/// List all PIDs via `proc_listallpids`.
+pub fn native_list_pids() -> Option<Vec<u32>> {
+    let needed = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if needed <= 0 {
+        return None;
+    }
+    // Leave headroom for processes spawned between the two calls.
+    let capacity = needed as usize + 64;
+    let mut pids = vec![0 as libc::c_int; capacity];
+    let count = unsafe {
+        libc::proc_listallpids(
+            pids.as_mut_ptr() as *mut libc::c_void,
+            (capacity * std::mem::size_of::<libc::c_int>()) as libc::c_int,
+        )
+    };
+    if count <= 0 {
+        return None;
+    }
+    pids.truncate((count as usize).min(capacity));
+    Some(
+        pids.into_iter()
+            .filter(|&pid| pid > 0)
+            .map(|pid| pid as u32)
+            .collect(),
+    )
+}
+
+/// Read BSD and task info for a PID via `proc_pidinfo`.
+///
+/// Returns `None` if the process is gone or not inspectable. Task info
+/// (memory, threads) needs the same user or root and is optional.
+pub fn native_proc_info(pid: u32) -> Option<NativeProcInfo> {
+    let mut bsd: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let bsd_size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            &mut bsd as *mut _ as *mut libc::c_void,
+            bsd_size,
+        )
+    };
+    if ret != bsd_size {
+        return None;
+    }
+
+    let mut task: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let task_size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let task_ok = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTASKINFO,
+            0,
+            &mut task as *mut _ as *mut libc::c_void,
+            task_size,
+        )
+    } == task_size;
+
+    Some(NativeProcInfo {
+        pid: bsd.pbi_pid,
+        ppid: bsd.pbi_ppid,
+        uid: bsd.pbi_uid,
+        pgid: bsd.pbi_pgid,
+        comm: c_chars_to_string(&bsd.pbi_comm),
+        state: state_from_pbi_status(bsd.pbi_status),
+        start_time_unix: bsd.pbi_start_tvsec,
+        rss_bytes: task_ok.then_some(task.pti_resident_size),
+        vsz_bytes: task_ok.then_some(task.pti_virtual_size),
+        threads: task_ok.then_some(task.pti_threadnum as u32),
+    })
+}
+
+/// Count open descriptors by type via `proc_pidinfo(PROC_PIDLISTFDS)`.
+pub fn native_fd_summary(pid: u32) -> Option<MacOsFdSummary> {
+    let needed = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            PROC_PIDLISTFDS,
+            0,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if needed <= 0 {
+        return None;
+    }
+    let entry = std::mem::size_of::<ProcFdInfo>();
+    // Leave headroom for descriptors opened between the two calls.
+    let capacity = needed as usize / entry + 16;
+    let mut fds = vec![ProcFdInfo::default(); capacity];
+    let bytes = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            PROC_PIDLISTFDS,
+            0,
+            fds.as_mut_ptr() as *mut libc::c_void,
+            (capacity * entry) as libc::c_int,
+        )
+    };
+    if bytes <= 0 {
+        return None;
+    }
+    let count = (bytes as usize / entry).min(capacity);
+    Some(MacOsFdSummary::from_fd_types(
+        fds[..count].iter().map(|fd| fd.proc_fdtype),
+    ))
+}
+
+/// Read cumulative disk I/O via `proc_pid_rusage`.
+pub fn native_disk_io(pid: u32) -> Option<MacOsDiskIo> {
+    let mut info = RusageInfoV2::default();
+    let ret = unsafe {
+        proc_pid_rusage(
+            pid as libc::c_int,
+            RUSAGE_INFO_V2,
+            &mut info as *mut _ as *mut libc::c_void,
+        )
+    };
+    if ret != 0 {
+        return None;
+    }
+    Some(MacOsDiskIo {
+        read_bytes: info.ri_diskio_bytesread,
+        write_bytes: info.ri_diskio_byteswritten,
+    })
+}
+
+/// Executable path via `proc_pidpath`.
+pub fn native_exe_path(pid: u32) -> Option<String> {
+    let mut buf = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe {
+        libc::proc_pidpath(
+            pid as libc::c_int,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len() as u32,
+        )
+    };
+    if len <= 0 {
+        return None;
+    }
+    buf.truncate(len as usize);
+    String::from_utf8(buf).ok()
+}
+
+/// Kernel start time (Unix seconds) for a PID.
+pub fn native_start_time(pid: u32) -> Option<u64> {
+    native_proc_info(pid).map(|info| info.start_time_unix)
+}
+
+/// Build a `start_id` from the boot session UUID and kernel start time.
+///
+/// Unlike the ps-derived fallback (`now - etime`), this is stable across scans.
+pub fn native_start_id(pid: u32) -> Option<(StartId, IdentityQuality)> {
+    let start = native_start_time(pid)?;
+    Some(start_id_for(boot_session_uuid().as_deref(), start, pid))
+}
+
+fn start_id_for(
+    boot_id: Option<&str>,
+    start_time_unix: u64,
+    pid: u32,
+) -> (StartId, IdentityQuality) {
+    match boot_id {
+        Some(boot) => (
+            StartId::from_macos(boot, start_time_unix, pid),
+            IdentityQuality::Full,
+        ),
+        None => (
+            StartId::from_macos("unknown", start_time_unix, pid),
+            IdentityQuality::NoBootId,
+        ),
+    }
+}
+
+/// Map `pbi_status` (SIDL, SRUN, SSLEEP, SSTOP, SZOMB) to a ps state char.
+fn state_from_pbi_status(status: u32) -> char {
+    match status {
+        1 => 'I',
+        2 => 'R',
+        3 => 'S',
+        4 => 'T',
+        5 => 'Z',
+        _ => '?',
+    }
+}
+
+fn c_chars_to_string(chars: &[libc::c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
 // ============================================================================
 // Main Scan Function
 // ============================================================================
@@ -628,10 +989,14 @@ pub fn macos_scan(options: &MacOsScanOptions) -> Result<MacOsScanResult, MacOsSc
     );
 
     let tool_timeout = options.tool_timeout.unwrap_or(Duration::from_secs(5));
+    let boot_id = boot_session_uuid();
 
     // Get list of PIDs from ps output
     let pids = if options.pids.is_empty() {
-        list_all_pids_macos()?
+        match native_list_pids() {
+            Some(pids) => pids,
+            None => list_all_pids_macos()?,
+        }
     } else {
         options.pids.clone()
     };
@@ -695,6 +1060,34 @@ pub fn macos_scan(options: &MacOsScanOptions) -> Result<MacOsScanResult, MacOsSc
             None
         };
 
+        // Prefer kernel start time for identity; ps-derived times drift between scans.
+        let native = if capabilities.libproc_available {
+            native_proc_info(*pid)
+        } else {
+            None
+        };
+        let (start_id, identity_quality, start_time_unix) = match &native {
+            Some(info) => {
+                let (start_id, quality) =
+                    start_id_for(boot_id.as_deref(), info.start_time_unix, *pid);
+                (start_id, quality, info.start_time_unix as i64)
+            }
+            None => (
+                base.start_id.clone(),
+                IdentityQuality::NoBootId,
+                base.start_time_unix,
+            ),
+        };
+        let (exe, fd_summary, disk_io) = if native.is_some() {
+            (
+                native_exe_path(*pid),
+                native_fd_summary(*pid),
+                native_disk_io(*pid),
+            )
+        } else {
+            (base.exe.clone(), None, None)
+        };
+
         let record = MacOsScanRecord {
             pid: ProcessId(*pid),
             ppid: base.ppid,
@@ -702,22 +1095,24 @@ pub fn macos_scan(options: &MacOsScanOptions) -> Result<MacOsScanResult, MacOsSc
             user: base.user.clone(),
             pgid: base.pgid,
             sid: base.sid,
-            start_id: base.start_id.clone(),
+            start_id,
             comm: base.comm.clone(),
             cmdline: base.cmdline.clone(),
-            exe: base.exe.clone(),
+            exe,
             state: base.state,
             cpu_percent: base.cpu_percent,
             rss_bytes: base.rss_bytes,
             vsz_bytes: base.vsz_bytes,
             open_files,
             network_connections,
+            fd_summary,
+            disk_io,
             launchd_service,
             environ,
-            start_time_unix: base.start_time_unix,
+            start_time_unix,
             elapsed: base.elapsed,
             source: "macos_scan".to_string(),
-            identity_quality: IdentityQuality::NoBootId, // macOS lacks /proc/boot_id TOCTOU protection
+            identity_quality,
         };
 
         processes.push(record);
@@ -1033,6 +1428,63 @@ mod tests {
     // Mock-based tests for full scan would go here
     // These tests can run on any platform since they use mocked data
 
+    #[test]
+    fn test_fd_summary_counts_by_type() {
+        let summary = MacOsFdSummary::from_fd_types([
+            PROX_FDTYPE_VNODE,
+            PROX_FDTYPE_VNODE,
+            PROX_FDTYPE_SOCKET,
+            PROX_FDTYPE_PIPE,
+            PROX_FDTYPE_KQUEUE,
+            7, // fsevents
+        ]);
+        assert_eq!(summary.total, 6);
+        assert_eq!(summary.vnodes, 2);
+        assert_eq!(summary.sockets, 1);
+        assert_eq!(summary.pipes, 1);
+        assert_eq!(summary.kqueues, 1);
+        assert_eq!(summary.other, 1);
+    }
+
+    #[test]
+    fn test_state_from_pbi_status() {
+        assert_eq!(state_from_pbi_status(2), 'R');
+        assert_eq!(state_from_pbi_status(3), 'S');
+        assert_eq!(state_from_pbi_status(5), 'Z');
+        assert_eq!(state_from_pbi_status(99), '?');
+    }
+
+    #[test]
+    fn test_parse_sysctl_uuid() {
+        let raw = b"6B1E3C52-8F0A-4D7E-9C1B-2A3F4E5D6C7B\0\0";
+        assert_eq!(
+            parse_sysctl_uuid(raw).as_deref(),
+            Some("6b1e3c52-8f0a-4d7e-9c1b-2a3f4e5d6c7b")
+        );
+        assert!(parse_sysctl_uuid(b"not-a-uuid\0").is_none());
+    }
+
+    #[test]
+    fn test_start_id_quality_depends_on_boot_id() {
+        let boot = "6b1e3c52-8f0a-4d7e-9c1b-2a3f4e5d6c7b";
+        let (id, quality) = start_id_for(Some(boot), 1_700_000_000, 42);
+        assert_eq!(quality, IdentityQuality::Full);
+        assert!(StartId::parse(&id.0).is_some());
+
+        let (_, quality) = start_id_for(None, 1_700_000_000, 42);
+        assert_eq!(quality, IdentityQuality::NoBootId);
+    }
+
+    #[test]
+    fn test_native_info_for_self() {
+        let pid = std::process::id();
+        let info = native_proc_info(pid).expect("own process is inspectable");
+        assert_eq!(info.pid, pid);
+        assert!(info.start_time_unix > 0);
+        assert!(native_fd_summary(pid).map(|s| s.total > 0).unwrap_or(false));
+        assert!(native_list_pids().unwrap().contains(&pid));
+    }
+
     #[test]
     fn test_macos_capabilities_default() {
         let caps = MacOsCapabilities::default();
@@ -1,26 +1,40 @@
 //! Process collection and scanning.
 //!
 //! This module provides the evidence collection layer for process triage:
-//! - Quick scan via ps parsing (fast, universal)
+//! - Quick scan via ps parsing (fast, universal), with an incremental /proc cache
+//!   and optional I/O-rate sampling
 //! - Deep scan via /proc inspection (detailed, Linux-only)
-//! - macOS-specific collection via lsof/launchctl (macOS-only)
+//! - macOS-specific collection via libproc/sysctl, lsof, launchctl (macOS-only)
+//! - Windows collection via Toolhelp32 and the NT native API (Windows-only)
 //! - Network connection collection
+//! - Listening-port ownership across network namespaces (Linux-only)
 //! - Cgroup and resource limit collection
 //! - Systemd unit detection
+//! - launchd job detection and KeepAlive respawn prediction (macOS)
 //! - Container detection (Docker, K8s, etc.)
+//! - Userspace OOM killer coexistence (systemd-oomd, earlyoom)
 //! - GPU process detection (NVIDIA CUDA, AMD ROCm)
+//! - Swap, zram and PSI memory-pressure signals (Linux-only)
+//! - Per-thread CPU sampling from task directories (Linux-only)
+//! - Foreground terminal jobs and keystroke recency (Linux-only)
+//! - tmux/screen session and SSH login attribution with client idle time (Linux-only)
 //! - Tool runner for safe external command execution
+//! - Short-lived process capture via the netlink process connector (Linux-only)
+//! - Procfs visibility detection under `hidepid` hardening
+//! - Process dependency graph export (parent/child, pgid/sid, unix sockets)
 //!
 //! The collection layer produces structured records that feed into the
 //! inference engine for classification.
 //!
 //! # Platform Support
 //! - Linux: Full support via /proc filesystem
-//! - macOS: Collection via BSD tools (ps, lsof, launchctl)
+//! - macOS: Native collection via libproc/sysctl, plus BSD tools (ps, lsof, launchctl)
+//! - Windows: Toolhelp32 enumeration, creation-time based `start_id`
 //!
 //! ## Platform-specific modules
 //! - `deep_scan`: Linux-only, uses /proc
-//! - `macos`: macOS-only, uses BSD tools and SIP detection
+//! - `macos`: macOS-only, uses libproc/sysctl, BSD tools and SIP detection
+//! - `windows`: Windows-only, uses Toolhelp32, process handles and NT APIs
 
 pub mod cgroup;
 pub mod container;
@@ -28,32 +42,60 @@ pub mod container;
 pub mod cpu_capacity;
 #[cfg(target_os = "linux")]
 mod deep_scan;
+pub mod dependency_graph;
+#[cfg(target_os = "linux")]
+pub mod foreground;
 #[cfg(target_os = "linux")]
 pub mod gpu;
 pub mod incremental;
+pub mod io_rate;
+pub mod launchd;
 #[cfg(target_os = "linux")]
 pub mod network;
+pub mod oom_killers;
+#[cfg(target_os = "linux")]
+pub mod port_owners;
+#[cfg(target_os = "linux")]
+pub mod proc_connector;
 pub mod proc_parsers;
 pub mod protected;
 mod quick_scan;
+pub mod scan_cache;
+#[cfg(target_os = "linux")]
+pub mod socket_activity;
+#[cfg(target_os = "linux")]
+pub mod swap_pressure;
 pub mod systemd;
 #[cfg(target_os = "linux")]
+pub mod terminal_session;
+#[cfg(target_os = "linux")]
+pub mod thread_cpu;
+#[cfg(target_os = "linux")]
 pub mod tick_delta;
 pub mod tool_runner;
 mod types;
 #[cfg(target_os = "linux")]
 pub mod user_intent;
+pub mod visibility;
 
 #[cfg(target_os = "macos")]
 pub mod macos;
 
+#[cfg(target_os = "windows")]
+pub mod windows;
+
 #[cfg(test)]
 mod real_tests;
 
 #[cfg(target_os = "linux")]
 pub use deep_scan::{
-    deep_scan, DeepScanError, DeepScanMetadata, DeepScanOptions, DeepScanRecord, DeepScanResult,
+    deep_scan, BudgetStage, DeepProbe, DeepScanError, DeepScanMetadata, DeepScanOptions,
+    DeepScanRecord, DeepScanResult, ProbeCost, ProbeSkipReason, ProbeSkipSummary, SkippedProbe,
 };
+pub use dependency_graph::{DependencyGraph, EdgeKind, GraphEdge, GraphNode, UnixSocketRef};
+#[cfg(target_os = "linux")]
+pub use foreground::{foreground_job, ForegroundJob, DEFAULT_FOREGROUND_IDLE_SECS};
+pub use io_rate::{compute_io_rate, IoSampler, DEFAULT_IO_RATE_WINDOW};
 #[cfg(target_os = "linux")]
 pub use network::{
     collect_network_info, parse_proc_net_tcp, parse_proc_net_udp, parse_proc_net_unix, ListenPort,
@@ -61,25 +103,53 @@ pub use network::{
     UnixSocketState, UnixSocketType,
 };
 #[cfg(target_os = "linux")]
+pub use port_owners::{NamespaceListeners, PortOwner, PortOwnershipMap};
+#[cfg(target_os = "linux")]
+pub use proc_connector::{
+    capture_short_lived, ParentActivity, ProcConnectorError, ShortLivedCapture, ShortLivedProcess,
+};
+#[cfg(target_os = "linux")]
 pub use proc_parsers::{
-    parse_cgroup, parse_environ, parse_environ_content, parse_fd, parse_fd_dir, parse_io,
-    parse_proc_stat, parse_proc_stat_content, parse_sched, parse_schedstat, parse_statm,
-    parse_wchan, CgroupInfo, CriticalFile, CriticalFileCategory, DetectionStrength, FdInfo, FdType,
-    IoStats, MemStats, OpenFile, OpenMode, ProcessStat, SchedInfo, SchedStats,
+    capability_names, parse_cgroup, parse_environ, parse_environ_content, parse_fd, parse_fd_dir,
+    parse_io, parse_privileges, parse_privileges_content, parse_proc_stat, parse_proc_stat_content,
+    parse_sched, parse_schedstat, parse_smaps_rollup, parse_statm, parse_wchan, read_ptrace_scope,
+    CgroupInfo, CriticalFile, CriticalFileCategory, DetectionStrength, FdInfo, FdType, IoStats,
+    MemStats, OpenFile, OpenMode, PrivilegeInfo, PrivilegeLevel, ProcessStat, SchedInfo,
+    SchedStats, SmapsRollup,
 };
 #[cfg(not(target_os = "linux"))]
 pub use proc_parsers::{
-    parse_environ_content, CriticalFile, CriticalFileCategory, DetectionStrength,
+    capability_names, parse_environ_content, parse_privileges_content, CriticalFile,
+    CriticalFileCategory, DetectionStrength, PrivilegeInfo, PrivilegeLevel,
 };
 pub use quick_scan::{
     parse_ps_output_synthetic_linux, quick_scan, QuickScanError, QuickScanOptions,
 };
+pub(crate) use quick_scan::{is_header_line, parse_ps_line_synthetic};
+pub use scan_cache::{ScanCache, ScanCacheStats, SharedScanCache, SCAN_CACHE_ENV};
+#[cfg(target_os = "linux")]
+pub use socket_activity::{
+    ActivitySource, ProcessNetActivity, SocketActivityError, SocketActivitySnapshot, TcpActivity,
+    TcpTimer,
+};
+#[cfg(target_os = "linux")]
+pub use swap_pressure::{process_swap_bytes, SwapPressureSnapshot, ZramStats};
+#[cfg(target_os = "linux")]
+pub use terminal_session::{
+    attribute_environ, MultiplexerKind, MultiplexerSession, SessionAttribution, SshLogin,
+    TerminalSessionSnapshot, DEFAULT_SESSION_IDLE_SECS,
+};
+#[cfg(target_os = "linux")]
+pub use thread_cpu::{
+    summarize_thread_cpu, ThreadCpu, ThreadCpuSampler, ThreadCpuSummary, DEFAULT_THREAD_CPU_WINDOW,
+};
 pub use tool_runner::{
     run_tool, run_tools_parallel, ToolConfig, ToolError, ToolOutput, ToolRunner, ToolRunnerBuilder,
     ToolSpec, DEFAULT_BUDGET_MS, DEFAULT_MAX_OUTPUT_BYTES, DEFAULT_MAX_PARALLEL,
     DEFAULT_TIMEOUT_SECS,
 };
-pub use types::{ProcessRecord, ProcessState, ScanMetadata, ScanResult};
+pub use types::{IoRate, ProcessRecord, ProcessState, ScanMetadata, ScanResult};
+pub use visibility::{HidePid, ProcVisibility, VisibilityScope};
 
 // Re-export protected filter types
 pub use protected::{
@@ -96,14 +166,28 @@ pub use cgroup::{
 // Re-export systemd types (available on all platforms; collection functions
 // gracefully return None / false when systemctl is absent)
 pub use systemd::{
-    collect_systemd_unit, is_systemd_available, is_systemd_managed, parse_systemctl_output,
-    SystemdActiveState, SystemdDataSource, SystemdProvenance, SystemdUnit, SystemdUnitType,
+    collect_systemd_unit, collect_systemd_unit_in, is_systemd_available, is_systemd_managed,
+    parse_systemctl_output, unit_of_cgroup_path, unit_of_pid, user_manager_uid, SystemdActiveState,
+    SystemdDataSource, SystemdManager, SystemdProvenance, SystemdUnit, SystemdUnitType,
+};
+
+// Re-export launchd types (parsing available on all platforms; collection
+// functions return None off macOS)
+pub use launchd::{
+    current_job_pid, detect_launchd_job, domain_for_uid, parse_launchctl_print, LaunchdJob,
+};
+
+// Re-export OOM killer coexistence types
+pub use oom_killers::{
+    collect_recent_oom_kills, detect_oom_killers, OomKillEvent, OomKillerKind, OomKillerPresence,
+    RecentOomKills, DEFAULT_RECENT_KILL_WINDOW_SECS,
 };
 
 // Re-export container types
 pub use container::{
-    detect_container_from_cgroup, detect_container_from_markers, detect_kubernetes_from_env,
-    ContainerDetectionSource, ContainerInfo, ContainerProvenance, ContainerRuntime, KubernetesInfo,
+    annotate_processes, detect_container_from_cgroup, detect_container_from_markers,
+    detect_kubernetes_from_env, workload_from_pod_name, ContainerDetectionSource, ContainerInfo,
+    ContainerProvenance, ContainerRuntime, KubernetesInfo, PodIndex,
 };
 
 // Re-export CPU capacity types
@@ -134,8 +218,8 @@ pub use user_intent::{
 #[cfg(target_os = "linux")]
 pub use gpu::{
     collect_gpu_snapshot, gpu_usage_for_pid, is_nvidia_available, is_rocm_available,
-    total_vram_mib_for_pid, GpuDetectionSource, GpuDevice, GpuError, GpuProvenance, GpuSnapshot,
-    GpuType, ProcessGpuUsage,
+    process_gpu_summary, total_vram_mib_for_pid, GpuDetectionSource, GpuDevice, GpuError,
+    GpuProvenance, GpuSnapshot, GpuType, ProcessGpuSummary, ProcessGpuUsage,
 };
 
 // Re-export incremental scanning types
@@ -147,7 +231,10 @@ pub use incremental::{
 // Re-export macOS collection types
 #[cfg(target_os = "macos")]
 pub use macos::{
-    collect_environ, collect_lsof_info, detect_launchd_service, detect_sip_status, macos_scan,
-    LaunchdService, MacOsCapabilities, MacOsNetworkConnection, MacOsScanError, MacOsScanMetadata,
-    MacOsScanOptions, MacOsScanRecord, MacOsScanResult, OpenFile, SipStatus,
+    boot_session_uuid, collect_environ, collect_lsof_info, detect_launchd_service,
+    detect_sip_status, macos_scan, native_disk_io, native_exe_path, native_fd_summary,
+    native_list_pids, native_proc_info, native_start_id, native_start_time, LaunchdService,
+    MacOsCapabilities, MacOsDiskIo, MacOsFdSummary, MacOsNetworkConnection, MacOsScanError,
+    MacOsScanMetadata, MacOsScanOptions, MacOsScanRecord, MacOsScanResult, NativeProcInfo,
+    OpenFile, SipStatus,
 };
@@ -352,7 +352,7 @@ pub fn collect_network_info(pid: u32) -> Option<NetworkInfo> {
 }
 
 /// Get all socket inode numbers for a process from /proc/[pid]/fd.
-fn get_process_socket_inodes(pid: u32) -> Option<HashSet<u64>> {
+pub(crate) fn get_process_socket_inodes(pid: u32) -> Option<HashSet<u64>> {
     let fd_path = format!("/proc/{}/fd", pid);
     let mut inodes = HashSet::new();
 
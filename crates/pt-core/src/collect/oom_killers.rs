@@ -0,0 +1,474 @@
+//! Userspace OOM killer coexistence (systemd-oomd, earlyoom).
+//!
+//! Hosts running a userspace memory-pressure killer have a second actor
+//! terminating processes. This module lets triage account for it:
+//! - Detect whether systemd-oomd or earlyoom is running
+//! - Read their recent kill events from the journal
+//! - Match candidates against freshly killed identities so plans don't
+//!   recommend acting on something the other actor already handled
+//!
+//! # Data Sources
+//! - `/proc/[pid]/comm` - daemon detection (Linux)
+//! - `journalctl -o json -t systemd-oomd -t earlyoom` - kill events
+//!
+//! Collection is best-effort: when the daemons or journalctl are absent,
+//! detection reports nothing and no events are returned.
+
+use super::tool_runner::run_tool;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How far back kill events count as "fresh" (seconds).
+pub const DEFAULT_RECENT_KILL_WINDOW_SECS: u64 = 600;
+
+/// Timeout for the journalctl query.
+const JOURNAL_TIMEOUT_MS: u64 = 3000;
+
+/// Output cap for the journalctl query.
+const JOURNAL_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// A userspace memory-pressure killer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OomKillerKind {
+    /// systemd-oomd (kills whole cgroups under PSI pressure).
+    SystemdOomd,
+    /// earlyoom (kills single processes when available memory runs low).
+    Earlyoom,
+}
+
+impl OomKillerKind {
+    /// Process name / syslog identifier of the daemon.
+    pub fn comm(self) -> &'static str {
+        match self {
+            OomKillerKind::SystemdOomd => "systemd-oomd",
+            OomKillerKind::Earlyoom => "earlyoom",
+        }
+    }
+
+    /// Map a process name / syslog identifier back to a killer.
+    pub fn from_comm(comm: &str) -> Option<Self> {
+        match comm.trim() {
+            "systemd-oomd" => Some(OomKillerKind::SystemdOomd),
+            "earlyoom" => Some(OomKillerKind::Earlyoom),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for OomKillerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.comm())
+    }
+}
+
+/// Which memory-pressure killers are running on this host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OomKillerPresence {
+    /// systemd-oomd is running.
+    pub systemd_oomd: bool,
+    /// earlyoom is running.
+    pub earlyoom: bool,
+}
+
+impl OomKillerPresence {
+    /// Whether any memory-pressure killer is running.
+    pub fn any(&self) -> bool {
+        self.systemd_oomd || self.earlyoom
+    }
+
+    /// Running killers, in a stable order.
+    pub fn active(&self) -> Vec<OomKillerKind> {
+        let mut kinds = Vec::new();
+        if self.systemd_oomd {
+            kinds.push(OomKillerKind::SystemdOomd);
+        }
+        if self.earlyoom {
+            kinds.push(OomKillerKind::Earlyoom);
+        }
+        kinds
+    }
+
+    /// Build presence from a list of running process names.
+    pub fn from_comms<'a>(comms: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut presence = Self::default();
+        for comm in comms {
+            match OomKillerKind::from_comm(comm) {
+                Some(OomKillerKind::SystemdOomd) => presence.systemd_oomd = true,
+                Some(OomKillerKind::Earlyoom) => presence.earlyoom = true,
+                None => {}
+            }
+        }
+        presence
+    }
+}
+
+/// Detect running memory-pressure killers.
+///
+/// Walks `/proc/[pid]/comm`; returns an empty presence on non-Linux hosts.
+pub fn detect_oom_killers() -> OomKillerPresence {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return OomKillerPresence::default();
+        };
+        let comms: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().parse::<u32>().is_ok())
+            .filter_map(|e| std::fs::read_to_string(e.path().join("comm")).ok())
+            .collect();
+        OomKillerPresence::from_comms(comms.iter().map(|s| s.as_str()))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        OomKillerPresence::default()
+    }
+}
+
+/// A kill performed by a memory-pressure killer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OomKillEvent {
+    /// Which daemon performed the kill.
+    pub killer: OomKillerKind,
+
+    /// When the kill was logged (ISO-8601).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub killed_at: Option<String>,
+
+    /// Killed PID (earlyoom).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+
+    /// Owner UID of the killed process (earlyoom >= 1.7).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+
+    /// Process name of the killed process (earlyoom).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comm: Option<String>,
+
+    /// Command line of the killed process (earlyoom >= 1.7).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<String>,
+
+    /// Killed cgroup path (systemd-oomd).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+}
+
+impl OomKillEvent {
+    fn new(killer: OomKillerKind) -> Self {
+        Self {
+            killer,
+            killed_at: None,
+            pid: None,
+            uid: None,
+            comm: None,
+            cmdline: None,
+            cgroup: None,
+        }
+    }
+}
+
+/// Parse an earlyoom kill line.
+///
+/// Format: `sending SIGTERM to process 4229 uid 1000 "firefox": oom_score 617,
+/// VmRSS 3245 MiB, cmdline "/usr/lib/firefox/firefox"` (uid and cmdline are
+/// absent in older releases).
+pub fn parse_earlyoom_message(message: &str) -> Option<OomKillEvent> {
+    let rest = message.trim().strip_prefix("sending SIG")?;
+    let rest = &rest[rest.find(" to process ")? + " to process ".len()..];
+
+    let pid_end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let pid: u32 = rest[..pid_end].parse().ok()?;
+    let mut rest = rest[pid_end..].trim_start();
+
+    let mut event = OomKillEvent::new(OomKillerKind::Earlyoom);
+    event.pid = Some(pid);
+
+    if let Some(after_uid) = rest.strip_prefix("uid ") {
+        let uid_end = after_uid
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after_uid.len());
+        event.uid = after_uid[..uid_end].parse().ok();
+        rest = after_uid[uid_end..].trim_start();
+    }
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        if let Some(end) = quoted.find("\":") {
+            event.comm = Some(quoted[..end].to_string());
+        }
+    }
+
+    if let Some(idx) = rest.find("cmdline \"") {
+        let cmd = &rest[idx + "cmdline \"".len()..];
+        let cmd = cmd.strip_suffix('"').unwrap_or(cmd);
+        if !cmd.is_empty() {
+            event.cmdline = Some(cmd.to_string());
+        }
+    }
+
+    Some(event)
+}
+
+/// Parse a systemd-oomd kill line.
+///
+/// Format: `Killed /user.slice/.../app-foo.scope due to memory pressure for
+/// /user.slice/... being 62.45% > 50.00% for > 20s with reclaim activity`.
+pub fn parse_oomd_message(message: &str) -> Option<OomKillEvent> {
+    let rest = message.trim().strip_prefix("Killed ")?;
+    let cgroup = rest.split(" due to ").next()?.trim();
+    if !cgroup.starts_with('/') {
+        return None;
+    }
+    let mut event = OomKillEvent::new(OomKillerKind::SystemdOomd);
+    event.cgroup = Some(cgroup.to_string());
+    Some(event)
+}
+
+/// Parse `journalctl -o json` output into kill events.
+///
+/// Lines that are not kill events (startup banners, memory reports, escalation
+/// notices) are ignored.
+pub fn parse_journal_json(output: &str) -> Vec<OomKillEvent> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|entry| {
+            let identifier = entry.get("SYSLOG_IDENTIFIER")?.as_str()?;
+            let message = entry.get("MESSAGE")?.as_str()?;
+            let mut event = match OomKillerKind::from_comm(identifier)? {
+                OomKillerKind::SystemdOomd => parse_oomd_message(message)?,
+                OomKillerKind::Earlyoom => parse_earlyoom_message(message)?,
+            };
+            event.killed_at = entry
+                .get("__REALTIME_TIMESTAMP")
+                .and_then(|v| v.as_str())
+                .and_then(|us| us.parse::<i64>().ok())
+                .and_then(chrono::DateTime::from_timestamp_micros)
+                .map(|ts| ts.to_rfc3339());
+            Some(event)
+        })
+        .collect()
+}
+
+/// Read kill events logged by the running killers within `window`.
+///
+/// Returns an empty list when no killer is running or the journal is
+/// unavailable.
+pub fn collect_recent_oom_kills(
+    presence: &OomKillerPresence,
+    window: Duration,
+) -> Vec<OomKillEvent> {
+    if !presence.any() {
+        return Vec::new();
+    }
+
+    let since = format!("--since=-{}s", window.as_secs().max(1));
+    let mut args = vec!["--no-pager", "-q", "-o", "json", since.as_str()];
+    for kind in presence.active() {
+        args.push("-t");
+        args.push(kind.comm());
+    }
+
+    match run_tool(
+        "journalctl",
+        &args,
+        Some(Duration::from_millis(JOURNAL_TIMEOUT_MS)),
+        Some(JOURNAL_MAX_OUTPUT_BYTES),
+    ) {
+        Ok(output) if output.success() => parse_journal_json(&output.stdout_str()),
+        _ => Vec::new(),
+    }
+}
+
+/// Recent kills indexed for candidate matching.
+#[derive(Debug, Clone, Default)]
+pub struct RecentOomKills {
+    events: Vec<OomKillEvent>,
+}
+
+impl RecentOomKills {
+    pub fn new(events: Vec<OomKillEvent>) -> Self {
+        Self { events }
+    }
+
+    pub fn events(&self) -> &[OomKillEvent] {
+        &self.events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Whether any event is keyed by cgroup (callers can skip reading
+    /// candidate cgroups otherwise).
+    pub fn has_cgroup_kills(&self) -> bool {
+        self.events.iter().any(|e| e.cgroup.is_some())
+    }
+
+    /// Find the kill event matching a candidate, if any.
+    ///
+    /// earlyoom kills match on PID plus process name (guarding against PID
+    /// reuse); systemd-oomd kills match when the candidate lives in the killed
+    /// cgroup or below it.
+    pub fn matching(&self, pid: u32, comm: &str, cgroup: Option<&str>) -> Option<&OomKillEvent> {
+        self.events.iter().find(|event| {
+            if let (Some(killed_pid), Some(killed_comm)) = (event.pid, event.comm.as_deref()) {
+                if killed_pid == pid && comm_matches(killed_comm, comm) {
+                    return true;
+                }
+            } else if event.pid == Some(pid) {
+                return true;
+            }
+            match (event.cgroup.as_deref(), cgroup) {
+                (Some(killed), Some(candidate)) => {
+                    let killed = killed.trim_end_matches('/');
+                    candidate == killed
+                        || candidate
+                            .strip_prefix(killed)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                }
+                _ => false,
+            }
+        })
+    }
+}
+
+/// Compare process names, allowing for the kernel's 15-byte comm truncation.
+fn comm_matches(a: &str, b: &str) -> bool {
+    const TASK_COMM_LEN: usize = 15;
+    let a: String = a.chars().take(TASK_COMM_LEN).collect();
+    let b: String = b.chars().take(TASK_COMM_LEN).collect();
+    a == b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presence_from_comms() {
+        let presence = OomKillerPresence::from_comms(["bash", "earlyoom\n", "sshd"]);
+        assert!(presence.any());
+        assert!(presence.earlyoom);
+        assert!(!presence.systemd_oomd);
+        assert_eq!(presence.active(), vec![OomKillerKind::Earlyoom]);
+
+        let none = OomKillerPresence::from_comms(["init", "bash"]);
+        assert!(!none.any());
+        assert!(none.active().is_empty());
+    }
+
+    #[test]
+    fn test_parse_earlyoom_modern() {
+        let msg = "sending SIGTERM to process 4229 uid 1000 \"firefox\": oom_score 617, VmRSS 3245 MiB, cmdline \"/usr/lib/firefox/firefox -P default\"";
+        let event = parse_earlyoom_message(msg).unwrap();
+        assert_eq!(event.killer, OomKillerKind::Earlyoom);
+        assert_eq!(event.pid, Some(4229));
+        assert_eq!(event.uid, Some(1000));
+        assert_eq!(event.comm.as_deref(), Some("firefox"));
+        assert_eq!(
+            event.cmdline.as_deref(),
+            Some("/usr/lib/firefox/firefox -P default")
+        );
+    }
+
+    #[test]
+    fn test_parse_earlyoom_legacy() {
+        let msg = "sending SIGKILL to process 812 \"node\": badness 900, VmRSS 2048 MiB";
+        let event = parse_earlyoom_message(msg).unwrap();
+        assert_eq!(event.pid, Some(812));
+        assert_eq!(event.uid, None);
+        assert_eq!(event.comm.as_deref(), Some("node"));
+        assert_eq!(event.cmdline, None);
+    }
+
+    #[test]
+    fn test_parse_earlyoom_ignores_other_lines() {
+        assert!(parse_earlyoom_message("mem avail: 1024 of 15000 MiB (6.83%)").is_none());
+        assert!(parse_earlyoom_message("escalating to SIGKILL after 1.0 seconds").is_none());
+    }
+
+    #[test]
+    fn test_parse_oomd_message() {
+        let msg = "Killed /user.slice/user-1000.slice/user@1000.service/app.slice/app-foo.scope due to memory pressure for /user.slice/user-1000.slice/user@1000.service being 62.45% > 50.00% for > 20s with reclaim activity";
+        let event = parse_oomd_message(msg).unwrap();
+        assert_eq!(event.killer, OomKillerKind::SystemdOomd);
+        assert_eq!(
+            event.cgroup.as_deref(),
+            Some("/user.slice/user-1000.slice/user@1000.service/app.slice/app-foo.scope")
+        );
+        assert!(parse_oomd_message("Considered 3 cgroups for killing").is_none());
+    }
+
+    #[test]
+    fn test_parse_journal_json() {
+        let output = concat!(
+            r#"{"SYSLOG_IDENTIFIER":"earlyoom","MESSAGE":"sending SIGTERM to process 42 uid 1000 \"cargo\": oom_score 700, VmRSS 900 MiB","__REALTIME_TIMESTAMP":"1700000000000000"}"#,
+            "\n",
+            r#"{"SYSLOG_IDENTIFIER":"earlyoom","MESSAGE":"mem avail: 100 of 8000 MiB"}"#,
+            "\n",
+            r#"{"SYSLOG_IDENTIFIER":"systemd-oomd","MESSAGE":"Killed /system.slice/build.service due to memory used"}"#,
+            "\n",
+            "not json\n",
+        );
+        let events = parse_journal_json(output);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].pid, Some(42));
+        assert!(events[0]
+            .killed_at
+            .as_deref()
+            .unwrap()
+            .starts_with("2023-11-14"));
+        assert_eq!(
+            events[1].cgroup.as_deref(),
+            Some("/system.slice/build.service")
+        );
+    }
+
+    #[test]
+    fn test_collect_without_killers_is_empty() {
+        let events = collect_recent_oom_kills(
+            &OomKillerPresence::default(),
+            Duration::from_secs(DEFAULT_RECENT_KILL_WINDOW_SECS),
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_recent_kills_match_by_pid_and_comm() {
+        let mut event = OomKillEvent::new(OomKillerKind::Earlyoom);
+        event.pid = Some(42);
+        event.comm = Some("very-long-process-name".to_string());
+        let kills = RecentOomKills::new(vec![event]);
+
+        assert!(!kills.has_cgroup_kills());
+        // Kernel comm is truncated to 15 bytes.
+        assert!(kills.matching(42, "very-long-proce", None).is_some());
+        // Same PID, different process: PID was reused.
+        assert!(kills.matching(42, "bash", None).is_none());
+        assert!(kills.matching(43, "very-long-proce", None).is_none());
+    }
+
+    #[test]
+    fn test_recent_kills_match_by_cgroup_prefix() {
+        let mut event = OomKillEvent::new(OomKillerKind::SystemdOomd);
+        event.cgroup = Some("/user.slice/app-foo.scope".to_string());
+        let kills = RecentOomKills::new(vec![event]);
+
+        assert!(kills.has_cgroup_kills());
+        assert!(kills
+            .matching(1, "foo", Some("/user.slice/app-foo.scope"))
+            .is_some());
+        assert!(kills
+            .matching(1, "foo", Some("/user.slice/app-foo.scope/child"))
+            .is_some());
+        assert!(kills
+            .matching(1, "foo", Some("/user.slice/app-foo.scope-other"))
+            .is_none());
+        assert!(kills.matching(1, "foo", None).is_none());
+    }
+}
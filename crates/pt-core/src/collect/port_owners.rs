@@ -0,0 +1,276 @@
+//! Listening-port ownership across network namespaces.
+//!
+//! `/proc/net/*` only lists sockets of the reader's own network namespace, so
+//! a port bound inside a container (or any `unshare -n` sandbox) is invisible
+//! to [`NetworkSnapshot`](super::network::NetworkSnapshot). This collector
+//! groups processes by the namespace in `/proc/[pid]/ns/net`, reads the socket
+//! tables once per namespace through a member (`/proc/[pid]/net/tcp`, ...),
+//! and attributes each listening socket to every process holding it open.
+//!
+//! The same port number can be bound in several namespaces; each binding is a
+//! separate owner. A socket shared by forked workers lists all of them.
+//!
+//! # Data Sources
+//! - `/proc/[pid]/ns/net` - Network namespace of a process
+//! - `/proc/[pid]/net/{tcp,tcp6,udp,udp6}` - Socket tables of that namespace
+//! - `/proc/[pid]/fd/` - Socket inodes held by the process
+
+use super::network::{
+    get_process_socket_inodes, parse_proc_net_tcp, parse_proc_net_udp, ListenPort, TcpConnection,
+    UdpSocket,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+/// A process holding a listening socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    /// Protocol (tcp, tcp6, udp, udp6).
+    pub protocol: String,
+    /// Bind address.
+    pub address: String,
+    /// Network namespace inode (None if `/proc/[pid]/ns/net` was unreadable).
+    pub netns: Option<u64>,
+    /// Socket lives in the same namespace as pt itself.
+    pub host_netns: bool,
+}
+
+/// Listening sockets of one network namespace, keyed by socket inode.
+#[derive(Debug, Default)]
+pub struct NamespaceListeners {
+    by_inode: HashMap<u64, ListenPort>,
+}
+
+impl NamespaceListeners {
+    /// Read the socket tables of the namespace `pid` lives in.
+    pub fn read(pid: u32) -> Option<Self> {
+        let mut tcp = Vec::new();
+        let mut udp = Vec::new();
+        let mut any = false;
+        for (file, is_ipv6) in [("tcp", false), ("tcp6", true)] {
+            if let Some(entries) = parse_proc_net_tcp(&format!("/proc/{pid}/net/{file}"), is_ipv6) {
+                tcp.extend(entries);
+                any = true;
+            }
+        }
+        for (file, is_ipv6) in [("udp", false), ("udp6", true)] {
+            if let Some(entries) = parse_proc_net_udp(&format!("/proc/{pid}/net/{file}"), is_ipv6) {
+                udp.extend(entries);
+                any = true;
+            }
+        }
+        any.then(|| Self::from_tables(&tcp, &udp))
+    }
+
+    /// Index the listening entries of parsed socket tables.
+    ///
+    /// TCP sockets count in LISTEN state; UDP sockets when bound to a port
+    /// and not connected.
+    pub fn from_tables(tcp: &[TcpConnection], udp: &[UdpSocket]) -> Self {
+        let mut by_inode = HashMap::new();
+        for conn in tcp.iter().filter(|c| c.state.is_listen() && c.inode != 0) {
+            by_inode.insert(
+                conn.inode,
+                ListenPort {
+                    protocol: if conn.is_ipv6 { "tcp6" } else { "tcp" }.to_string(),
+                    port: conn.local_port,
+                    address: conn.local_addr.clone(),
+                    inode: conn.inode,
+                },
+            );
+        }
+        for sock in udp
+            .iter()
+            .filter(|s| s.local_port != 0 && s.remote_port == 0 && s.inode != 0)
+        {
+            by_inode.insert(
+                sock.inode,
+                ListenPort {
+                    protocol: if sock.is_ipv6 { "udp6" } else { "udp" }.to_string(),
+                    port: sock.local_port,
+                    address: sock.local_addr.clone(),
+                    inode: sock.inode,
+                },
+            );
+        }
+        Self { by_inode }
+    }
+
+    pub fn get(&self, inode: u64) -> Option<&ListenPort> {
+        self.by_inode.get(&inode)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_inode.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_inode.is_empty()
+    }
+}
+
+/// Listening ports mapped to the processes that own them.
+#[derive(Debug, Default, Serialize)]
+pub struct PortOwnershipMap {
+    by_port: BTreeMap<u16, Vec<PortOwner>>,
+    /// Network namespaces whose socket tables were read.
+    pub namespaces_scanned: usize,
+}
+
+impl PortOwnershipMap {
+    /// Map the listening sockets held by `pids`, across all their namespaces.
+    ///
+    /// Processes whose fds cannot be read (other users without privileges,
+    /// exited) are skipped.
+    pub fn collect(pids: &[u32]) -> Self {
+        let host_netns = read_netns("self");
+        let mut namespaces: HashMap<u64, Option<NamespaceListeners>> = HashMap::new();
+        let mut map = Self::default();
+
+        for &pid in pids {
+            let Some(inodes) = get_process_socket_inodes(pid) else {
+                continue;
+            };
+            if inodes.is_empty() {
+                continue;
+            }
+            let netns = read_netns(&pid.to_string());
+            // Without a namespace id the process reads its own tables, uncached.
+            let uncached;
+            let listeners = match netns {
+                Some(ns) => namespaces
+                    .entry(ns)
+                    .or_insert_with(|| NamespaceListeners::read(pid))
+                    .as_ref(),
+                None => {
+                    uncached = NamespaceListeners::read(pid);
+                    uncached.as_ref()
+                }
+            };
+            let Some(listeners) = listeners else {
+                continue;
+            };
+            for inode in inodes {
+                if let Some(listen) = listeners.get(inode) {
+                    map.record(pid, listen, netns, netns.is_some() && netns == host_netns);
+                }
+            }
+        }
+        map.namespaces_scanned = namespaces.values().filter(|l| l.is_some()).count();
+        map
+    }
+
+    fn record(&mut self, pid: u32, listen: &ListenPort, netns: Option<u64>, host_netns: bool) {
+        let owners = self.by_port.entry(listen.port).or_default();
+        let owner = PortOwner {
+            pid,
+            protocol: listen.protocol.clone(),
+            address: listen.address.clone(),
+            netns,
+            host_netns,
+        };
+        if !owners.contains(&owner) {
+            owners.push(owner);
+        }
+    }
+
+    /// Processes listening on `port`, in any namespace.
+    pub fn owners(&self, port: u16) -> &[PortOwner] {
+        self.by_port.get(&port).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Ports `pid` listens on, ascending.
+    pub fn ports_of(&self, pid: u32) -> Vec<u16> {
+        self.by_port
+            .iter()
+            .filter(|(_, owners)| owners.iter().any(|owner| owner.pid == pid))
+            .map(|(port, _)| *port)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_port.is_empty()
+    }
+}
+
+/// Namespace inode of `/proc/<pid>/ns/net` ("net:[4026531840]").
+fn read_netns(pid: &str) -> Option<u64> {
+    let link = fs::read_link(format!("/proc/{pid}/ns/net")).ok()?;
+    parse_netns_link(&link.to_string_lossy())
+}
+
+fn parse_netns_link(link: &str) -> Option<u64> {
+    link.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::network::{parse_proc_net_tcp_content, parse_proc_net_udp_content};
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 41001 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 41002 1 0000000000000000 20 4 30 10 -1
+";
+    const UDP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  10: 00000000:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 41003 2 0000000000000000 0
+  11: 0100007F:D431 0100007F:0035 01 00000000:00000000 00:00000000 00000000  1000        0 41004 2 0000000000000000 0
+";
+
+    #[test]
+    fn only_listening_sockets_are_indexed() {
+        let listeners = NamespaceListeners::from_tables(
+            &parse_proc_net_tcp_content(TCP, false),
+            &parse_proc_net_udp_content(UDP, false),
+        );
+        assert_eq!(listeners.len(), 2);
+        let tcp = listeners.get(41001).unwrap();
+        assert_eq!((tcp.protocol.as_str(), tcp.port), ("tcp", 8080));
+        let udp = listeners.get(41003).unwrap();
+        assert_eq!((udp.protocol.as_str(), udp.port), ("udp", 53));
+        // Established and connected sockets are not listeners.
+        assert!(listeners.get(41002).is_none());
+        assert!(listeners.get(41004).is_none());
+    }
+
+    #[test]
+    fn same_port_in_two_namespaces_has_two_owners() {
+        let listen = ListenPort {
+            protocol: "tcp".to_string(),
+            port: 8080,
+            address: "0.0.0.0".to_string(),
+            inode: 1,
+        };
+        let mut map = PortOwnershipMap::default();
+        map.record(100, &listen, Some(4026531840), true);
+        map.record(200, &listen, Some(4026532999), false);
+        map.record(200, &listen, Some(4026532999), false);
+
+        let owners = map.owners(8080);
+        assert_eq!(owners.len(), 2);
+        assert!(owners.iter().any(|o| o.pid == 200 && !o.host_netns));
+        assert_eq!(map.ports_of(200), vec![8080]);
+        assert!(map.owners(9090).is_empty());
+    }
+
+    #[test]
+    fn netns_link_parses() {
+        assert_eq!(parse_netns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_netns_link("mnt:[4026531840]"), None);
+    }
+
+    #[test]
+    fn own_listener_is_found() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let map = PortOwnershipMap::collect(&[std::process::id()]);
+        let owners = map.owners(port);
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].pid, std::process::id());
+        assert_eq!(owners[0].protocol, "tcp");
+        assert!(owners[0].host_netns);
+        assert_eq!(map.namespaces_scanned, 1);
+    }
+}
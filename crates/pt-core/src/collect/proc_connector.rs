@@ -0,0 +1,636 @@
+//! Short-lived process capture via the netlink process connector.
+//!
+//! Quick scan takes point-in-time samples, so processes that start and exit
+//! between samples are invisible. The kernel's process connector
+//! (`NETLINK_CONNECTOR` / `CN_IDX_PROC`) multicasts fork, exec and exit events;
+//! listening for the duration of the sampling window lets us record those
+//! processes and attribute their churn to the parent that spawned them.
+//!
+//! Subscribing requires `CAP_NET_ADMIN`. Without it, [`capture_short_lived`]
+//! returns [`ProcConnectorError::Subscribe`] and callers should degrade to
+//! plain sampling.
+//!
+//! # Wire format
+//! Each datagram is `nlmsghdr` + `cn_msg` + `proc_event` (see
+//! `include/uapi/linux/cn_proc.h`). All integers are native-endian.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Connector index/value for process events.
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+
+/// Multicast control operations.
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+const PROC_CN_MCAST_IGNORE: u32 = 2;
+
+/// `proc_event.what` values.
+const PROC_EVENT_FORK: u32 = 0x0000_0001;
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+
+const NLMSG_HDR_LEN: usize = 16;
+const CN_MSG_LEN: usize = 20;
+/// `what` + `cpu` + `timestamp_ns`.
+const PROC_EVENT_HDR_LEN: usize = 16;
+
+/// How long a single recv may block before the deadline is re-checked.
+const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Upper bound on recorded short-lived processes per capture.
+pub const MAX_RECORDED_PROCESSES: usize = 1024;
+
+/// Errors from the process connector.
+#[derive(Debug, Error)]
+pub enum ProcConnectorError {
+    #[error("failed to open netlink connector socket: {0}")]
+    Socket(#[source] io::Error),
+
+    #[error("failed to bind netlink connector socket: {0}")]
+    Bind(#[source] io::Error),
+
+    #[error("failed to subscribe to process events (requires CAP_NET_ADMIN): {0}")]
+    Subscribe(#[source] io::Error),
+
+    #[error("failed to receive process events: {0}")]
+    Recv(#[source] io::Error),
+}
+
+/// A decoded process connector event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcEvent {
+    Fork {
+        timestamp_ns: u64,
+        parent_tgid: u32,
+        child_pid: u32,
+        child_tgid: u32,
+    },
+    Exec {
+        timestamp_ns: u64,
+        pid: u32,
+        tgid: u32,
+    },
+    Exit {
+        timestamp_ns: u64,
+        pid: u32,
+        tgid: u32,
+        /// Raw wait status (`task->exit_code`).
+        exit_status: u32,
+    },
+}
+
+/// A process that started and exited inside the capture window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShortLivedProcess {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Command name, if it could be read before the process exited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comm: Option<String>,
+    /// Command line, if it could be read before the process exited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmdline: Option<String>,
+    /// Time from fork to exit.
+    pub lifetime_ms: f64,
+    /// Exit code when the process exited normally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Terminating signal when the process was killed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal: Option<i32>,
+}
+
+impl ShortLivedProcess {
+    /// Whether the process exited with a non-zero code or a signal.
+    pub fn failed(&self) -> bool {
+        self.signal.is_some() || self.exit_code.is_some_and(|c| c != 0)
+    }
+}
+
+/// Child churn attributed to one parent during the window.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParentActivity {
+    /// Children forked.
+    pub forks: u32,
+    /// Children that exec'd a new program.
+    pub execs: u32,
+    /// Children that exited inside the window.
+    pub exits: u32,
+    /// Children that exited non-zero or were killed by a signal.
+    pub failures: u32,
+}
+
+/// Result of a short-lived process capture.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortLivedCapture {
+    /// Requested capture window.
+    pub window_ms: u64,
+    /// Process events decoded (threads excluded).
+    pub events_seen: u64,
+    /// Whether the kernel reported dropped events (socket buffer overrun).
+    pub overrun: bool,
+    /// Processes that both started and exited inside the window.
+    pub processes: Vec<ShortLivedProcess>,
+    /// Short-lived processes not recorded because of [`MAX_RECORDED_PROCESSES`].
+    pub dropped: u64,
+    /// Child activity keyed by parent PID.
+    pub parents: BTreeMap<u32, ParentActivity>,
+}
+
+impl ShortLivedCapture {
+    /// Child activity for a parent PID, if any was seen.
+    pub fn activity_for(&self, ppid: u32) -> Option<&ParentActivity> {
+        self.parents.get(&ppid)
+    }
+}
+
+/// Decode one `nlmsghdr`-framed process connector message.
+///
+/// Returns None for messages that are not process events, are too short, or
+/// describe threads rather than thread-group leaders.
+pub fn parse_proc_event(msg: &[u8]) -> Option<ProcEvent> {
+    let base = NLMSG_HDR_LEN + CN_MSG_LEN;
+    if msg.len() < base + PROC_EVENT_HDR_LEN {
+        return None;
+    }
+    if read_u32(msg, NLMSG_HDR_LEN)? != CN_IDX_PROC
+        || read_u32(msg, NLMSG_HDR_LEN + 4)? != CN_VAL_PROC
+    {
+        return None;
+    }
+
+    let what = read_u32(msg, base)?;
+    let timestamp_ns = read_u64(msg, base + 8)?;
+    let data = base + PROC_EVENT_HDR_LEN;
+
+    match what {
+        PROC_EVENT_FORK => {
+            let child_pid = read_u32(msg, data + 8)?;
+            let child_tgid = read_u32(msg, data + 12)?;
+            (child_pid == child_tgid).then_some(ProcEvent::Fork {
+                timestamp_ns,
+                parent_tgid: read_u32(msg, data + 4)?,
+                child_pid,
+                child_tgid,
+            })
+        }
+        PROC_EVENT_EXEC => {
+            let pid = read_u32(msg, data)?;
+            let tgid = read_u32(msg, data + 4)?;
+            (pid == tgid).then_some(ProcEvent::Exec {
+                timestamp_ns,
+                pid,
+                tgid,
+            })
+        }
+        PROC_EVENT_EXIT => {
+            let pid = read_u32(msg, data)?;
+            let tgid = read_u32(msg, data + 4)?;
+            (pid == tgid).then_some(ProcEvent::Exit {
+                timestamp_ns,
+                pid,
+                tgid,
+                exit_status: read_u32(msg, data + 8)?,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buf.get(offset..offset + 4)?;
+    Some(u32::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> Option<u64> {
+    let bytes = buf.get(offset..offset + 8)?;
+    Some(u64::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+/// Process started inside the window, not yet exited.
+#[derive(Debug, Clone)]
+struct Tracked {
+    ppid: u32,
+    forked_ns: u64,
+    comm: Option<String>,
+    cmdline: Option<String>,
+}
+
+/// Folds process events into a [`ShortLivedCapture`].
+///
+/// Only processes whose fork was observed are reported: an exit without a
+/// matching fork belongs to a process that predates the window and was
+/// already visible to sampling.
+#[derive(Debug, Default)]
+pub struct ShortLivedTracker {
+    live: HashMap<u32, Tracked>,
+    capture: ShortLivedCapture,
+}
+
+impl ShortLivedTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one event. `identity` is the (comm, cmdline) read at exec time.
+    pub fn observe(&mut self, event: ProcEvent, identity: Option<(String, String)>) {
+        self.capture.events_seen += 1;
+        match event {
+            ProcEvent::Fork {
+                timestamp_ns,
+                parent_tgid,
+                child_tgid,
+                ..
+            } => {
+                self.capture.parents.entry(parent_tgid).or_default().forks += 1;
+                self.live.insert(
+                    child_tgid,
+                    Tracked {
+                        ppid: parent_tgid,
+                        forked_ns: timestamp_ns,
+                        comm: None,
+                        cmdline: None,
+                    },
+                );
+            }
+            ProcEvent::Exec { tgid, .. } => {
+                if let Some(tracked) = self.live.get_mut(&tgid) {
+                    self.capture.parents.entry(tracked.ppid).or_default().execs += 1;
+                    if let Some((comm, cmdline)) = identity {
+                        tracked.comm = Some(comm);
+                        tracked.cmdline = Some(cmdline);
+                    }
+                }
+            }
+            ProcEvent::Exit {
+                timestamp_ns,
+                tgid,
+                exit_status,
+                ..
+            } => {
+                let Some(tracked) = self.live.remove(&tgid) else {
+                    return;
+                };
+                let signal = (exit_status & 0x7f) as i32;
+                let process = ShortLivedProcess {
+                    pid: tgid,
+                    ppid: tracked.ppid,
+                    comm: tracked.comm,
+                    cmdline: tracked.cmdline,
+                    lifetime_ms: timestamp_ns.saturating_sub(tracked.forked_ns) as f64 / 1e6,
+                    exit_code: (signal == 0).then_some(((exit_status >> 8) & 0xff) as i32),
+                    signal: (signal != 0).then_some(signal),
+                };
+                let activity = self.capture.parents.entry(tracked.ppid).or_default();
+                activity.exits += 1;
+                if process.failed() {
+                    activity.failures += 1;
+                }
+                if self.capture.processes.len() < MAX_RECORDED_PROCESSES {
+                    self.capture.processes.push(process);
+                } else {
+                    self.capture.dropped += 1;
+                }
+            }
+        }
+    }
+
+    /// Finish the capture.
+    pub fn finish(mut self, window: Duration, overrun: bool) -> ShortLivedCapture {
+        self.capture.window_ms = window.as_millis() as u64;
+        self.capture.overrun = overrun;
+        self.capture
+    }
+}
+
+/// Netlink socket subscribed to process events. Unsubscribes on drop.
+pub struct ProcConnector {
+    fd: libc::c_int,
+}
+
+impl ProcConnector {
+    /// Open the connector socket and subscribe to process events.
+    pub fn open() -> Result<Self, ProcConnectorError> {
+        // SAFETY: plain socket(2) call; the fd is owned by the returned value.
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_CONNECTOR,
+            )
+        };
+        if fd < 0 {
+            return Err(ProcConnectorError::Socket(io::Error::last_os_error()));
+        }
+        let connector = Self { fd };
+
+        // SAFETY: sockaddr_nl is plain data; zeroed is a valid initial state.
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = CN_IDX_PROC;
+        // SAFETY: addr is a valid sockaddr_nl and the length matches.
+        let rc = unsafe {
+            libc::bind(
+                connector.fd,
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            return Err(ProcConnectorError::Bind(io::Error::last_os_error()));
+        }
+
+        let timeout = libc::timeval {
+            tv_sec: 0,
+            tv_usec: RECV_TIMEOUT.as_micros() as libc::suseconds_t,
+        };
+        // SAFETY: timeout is a valid timeval and the length matches.
+        unsafe {
+            libc::setsockopt(
+                connector.fd,
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeout as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            );
+        }
+
+        connector
+            .send_control(PROC_CN_MCAST_LISTEN)
+            .map_err(ProcConnectorError::Subscribe)?;
+        Ok(connector)
+    }
+
+    fn send_control(&self, op: u32) -> io::Result<()> {
+        let total = NLMSG_HDR_LEN + CN_MSG_LEN + 4;
+        let mut msg = Vec::with_capacity(total);
+        // nlmsghdr
+        msg.extend_from_slice(&(total as u32).to_ne_bytes());
+        msg.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        msg.extend_from_slice(&0u16.to_ne_bytes());
+        msg.extend_from_slice(&0u32.to_ne_bytes());
+        msg.extend_from_slice(&std::process::id().to_ne_bytes());
+        // cn_msg
+        msg.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        msg.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        msg.extend_from_slice(&0u32.to_ne_bytes());
+        msg.extend_from_slice(&0u32.to_ne_bytes());
+        msg.extend_from_slice(&4u16.to_ne_bytes());
+        msg.extend_from_slice(&0u16.to_ne_bytes());
+        // op
+        msg.extend_from_slice(&op.to_ne_bytes());
+
+        // SAFETY: msg is a live buffer of msg.len() bytes.
+        let rc = unsafe { libc::send(self.fd, msg.as_ptr() as *const libc::c_void, msg.len(), 0) };
+        if rc < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Receive one datagram. Returns Ok(None) on timeout.
+    fn recv(&self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        // SAFETY: buf is a live, writable buffer of buf.len() bytes.
+        let n = unsafe { libc::recv(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if n >= 0 {
+            return Ok(Some(n as usize));
+        }
+        let err = io::Error::last_os_error();
+        match err.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted => {
+                Ok(None)
+            }
+            _ => Err(err),
+        }
+    }
+}
+
+impl Drop for ProcConnector {
+    fn drop(&mut self) {
+        let _ = self.send_control(PROC_CN_MCAST_IGNORE);
+        // SAFETY: fd was opened by us and is closed exactly once.
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Listen for process events for `window` and report short-lived processes.
+pub fn capture_short_lived(window: Duration) -> Result<ShortLivedCapture, ProcConnectorError> {
+    let connector = ProcConnector::open()?;
+    let mut tracker = ShortLivedTracker::new();
+    let mut overrun = false;
+    let mut buf = vec![0u8; 8192];
+    let deadline = Instant::now() + window;
+
+    while Instant::now() < deadline {
+        let n = match connector.recv(&mut buf) {
+            Ok(Some(n)) => n,
+            Ok(None) => continue,
+            Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                overrun = true;
+                continue;
+            }
+            Err(e) => return Err(ProcConnectorError::Recv(e)),
+        };
+
+        let mut offset = 0;
+        while offset + NLMSG_HDR_LEN <= n {
+            let Some(len) = read_u32(&buf[..n], offset).map(|l| l as usize) else {
+                break;
+            };
+            if len < NLMSG_HDR_LEN || offset + len > n {
+                break;
+            }
+            if let Some(event) = parse_proc_event(&buf[offset..offset + len]) {
+                // Read identity right away: the process may be gone by the next event.
+                let identity = match event {
+                    ProcEvent::Exec { tgid, .. } => read_identity(tgid),
+                    _ => None,
+                };
+                tracker.observe(event, identity);
+            }
+            offset += (len + 3) & !3;
+        }
+    }
+
+    Ok(tracker.finish(window, overrun))
+}
+
+fn read_identity(pid: u32) -> Option<(String, String)> {
+    let comm = fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|a| !a.is_empty())
+                .map(|a| String::from_utf8_lossy(a).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    Some((comm.trim().to_string(), cmdline))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(what: u32, timestamp_ns: u64, data: &[u32]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        let total = NLMSG_HDR_LEN + CN_MSG_LEN + PROC_EVENT_HDR_LEN + data.len() * 4;
+        msg.extend_from_slice(&(total as u32).to_ne_bytes());
+        msg.extend_from_slice(&[0u8; 12]);
+        msg.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        msg.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        msg.extend_from_slice(&[0u8; 12]);
+        msg.extend_from_slice(&what.to_ne_bytes());
+        msg.extend_from_slice(&0u32.to_ne_bytes());
+        msg.extend_from_slice(&timestamp_ns.to_ne_bytes());
+        for value in data {
+            msg.extend_from_slice(&value.to_ne_bytes());
+        }
+        msg
+    }
+
+    #[test]
+    fn test_parse_fork_exec_exit() {
+        let fork = message(PROC_EVENT_FORK, 10, &[100, 100, 200, 200]);
+        assert_eq!(
+            parse_proc_event(&fork),
+            Some(ProcEvent::Fork {
+                timestamp_ns: 10,
+                parent_tgid: 100,
+                child_pid: 200,
+                child_tgid: 200,
+            })
+        );
+
+        let exec = message(PROC_EVENT_EXEC, 20, &[200, 200]);
+        assert_eq!(
+            parse_proc_event(&exec),
+            Some(ProcEvent::Exec {
+                timestamp_ns: 20,
+                pid: 200,
+                tgid: 200,
+            })
+        );
+
+        let exit = message(PROC_EVENT_EXIT, 30, &[200, 200, 1 << 8, 17, 100, 100]);
+        assert_eq!(
+            parse_proc_event(&exit),
+            Some(ProcEvent::Exit {
+                timestamp_ns: 30,
+                pid: 200,
+                tgid: 200,
+                exit_status: 1 << 8,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_threads_and_short_messages() {
+        // Thread creation: child pid differs from its tgid.
+        let thread = message(PROC_EVENT_FORK, 10, &[100, 100, 201, 200]);
+        assert_eq!(parse_proc_event(&thread), None);
+
+        let fork = message(PROC_EVENT_FORK, 10, &[100, 100, 200, 200]);
+        assert_eq!(parse_proc_event(&fork[..fork.len() - 4]), None);
+        assert_eq!(parse_proc_event(&[0u8; 8]), None);
+    }
+
+    #[test]
+    fn test_tracker_records_short_lived_children() {
+        let mut tracker = ShortLivedTracker::new();
+        tracker.observe(
+            ProcEvent::Fork {
+                timestamp_ns: 1_000_000,
+                parent_tgid: 100,
+                child_pid: 200,
+                child_tgid: 200,
+            },
+            None,
+        );
+        tracker.observe(
+            ProcEvent::Exec {
+                timestamp_ns: 2_000_000,
+                pid: 200,
+                tgid: 200,
+            },
+            Some(("curl".to_string(), "curl -s http://localhost".to_string())),
+        );
+        tracker.observe(
+            ProcEvent::Exit {
+                timestamp_ns: 6_000_000,
+                pid: 200,
+                tgid: 200,
+                exit_status: 7 << 8,
+            },
+            None,
+        );
+        // Exit of a process that predates the window is ignored.
+        tracker.observe(
+            ProcEvent::Exit {
+                timestamp_ns: 7_000_000,
+                pid: 300,
+                tgid: 300,
+                exit_status: 0,
+            },
+            None,
+        );
+
+        let capture = tracker.finish(Duration::from_secs(2), false);
+        assert_eq!(capture.window_ms, 2000);
+        assert_eq!(capture.events_seen, 4);
+        assert_eq!(capture.processes.len(), 1);
+        let process = &capture.processes[0];
+        assert_eq!(process.ppid, 100);
+        assert_eq!(process.comm.as_deref(), Some("curl"));
+        assert_eq!(process.lifetime_ms, 5.0);
+        assert_eq!(process.exit_code, Some(7));
+        assert!(process.failed());
+
+        let activity = capture.activity_for(100).unwrap();
+        assert_eq!(
+            activity,
+            &ParentActivity {
+                forks: 1,
+                execs: 1,
+                exits: 1,
+                failures: 1,
+            }
+        );
+        assert!(capture.activity_for(300).is_none());
+    }
+
+    #[test]
+    fn test_tracker_decodes_signal_exit() {
+        let mut tracker = ShortLivedTracker::new();
+        tracker.observe(
+            ProcEvent::Fork {
+                timestamp_ns: 0,
+                parent_tgid: 1,
+                child_pid: 2,
+                child_tgid: 2,
+            },
+            None,
+        );
+        tracker.observe(
+            ProcEvent::Exit {
+                timestamp_ns: 1,
+                pid: 2,
+                tgid: 2,
+                exit_status: 9,
+            },
+            None,
+        );
+        let capture = tracker.finish(Duration::from_millis(10), false);
+        assert_eq!(capture.processes[0].signal, Some(9));
+        assert_eq!(capture.processes[0].exit_code, None);
+    }
+}
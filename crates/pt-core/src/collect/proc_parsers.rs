@@ -12,6 +12,7 @@
 //! - `/proc/[pid]/cgroup` - Cgroup membership
 //! - `/proc/[pid]/wchan` - Wait channel
 //! - `/proc/[pid]/environ` - Environment variables
+//! - `/proc/[pid]/status` - Capabilities and privilege bits
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -84,6 +85,25 @@ pub struct MemStats {
     pub dt: u64,
 }
 
+/// Proportional and private memory from /proc/\[pid\]/smaps_rollup.
+///
+/// RSS counts every shared page in full for each process mapping it, so it
+/// over-counts forked workers. PSS splits shared pages between their
+/// mappers; USS (private pages) is what killing the process alone frees.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmapsRollup {
+    pub rss_bytes: u64,
+    /// Proportional set size.
+    pub pss_bytes: u64,
+    /// Unique set size: `Private_Clean + Private_Dirty`.
+    pub uss_bytes: u64,
+    /// `Shared_Clean + Shared_Dirty`.
+    pub shared_bytes: u64,
+    pub swap_bytes: u64,
+    /// Proportional share of `swap_bytes`.
+    pub swap_pss_bytes: u64,
+}
+
 /// File descriptor information.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FdInfo {
@@ -495,6 +515,51 @@ pub fn parse_statm_content(content: &str) -> Option<MemStats> {
     })
 }
 
+/// Parse /proc/\[pid\]/smaps_rollup (Linux 4.14+).
+///
+/// Needs the same access as ptrace-read, so it is usually unavailable for
+/// other users' processes.
+pub fn parse_smaps_rollup(pid: u32) -> Option<SmapsRollup> {
+    let path = format!("/proc/{}/smaps_rollup", pid);
+    let content = fs::read_to_string(&path).ok()?;
+    parse_smaps_rollup_content(&content)
+}
+
+/// Parse smaps_rollup content (for testing).
+pub fn parse_smaps_rollup_content(content: &str) -> Option<SmapsRollup> {
+    let mut rollup = SmapsRollup::default();
+    let mut has_pss = false;
+
+    for line in content.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // Values are reported in kB; the header line has no "kB" value.
+        let Some(kb) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let bytes = kb.saturating_mul(1024);
+        match key.trim() {
+            "Rss" => rollup.rss_bytes = bytes,
+            "Pss" => {
+                rollup.pss_bytes = bytes;
+                has_pss = true;
+            }
+            "Shared_Clean" | "Shared_Dirty" => rollup.shared_bytes += bytes,
+            "Private_Clean" | "Private_Dirty" => rollup.uss_bytes += bytes,
+            "Swap" => rollup.swap_bytes = bytes,
+            "SwapPss" => rollup.swap_pss_bytes = bytes,
+            _ => {}
+        }
+    }
+
+    has_pss.then_some(rollup)
+}
+
 /// Parse /proc/\[pid\]/fd/ directory.
 ///
 /// Counts and categorizes open file descriptors.
@@ -896,6 +961,242 @@ pub fn parse_environ_content(content: &[u8]) -> Option<HashMap<String, String>>
     Some(env)
 }
 
+/// Linux capability names indexed by bit number (see capabilities(7)).
+const CAPABILITY_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// Capabilities that effectively grant control over the whole host.
+///
+/// Holding any of these in the effective set makes a process "highly
+/// privileged" for guardrail purposes.
+const HIGH_RISK_CAPABILITIES: [&str; 7] = [
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_PTRACE",
+    "CAP_DAC_OVERRIDE",
+    "CAP_SETUID",
+    "CAP_BPF",
+];
+
+/// Coarse privilege classification derived from [`PrivilegeInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivilegeLevel {
+    /// No effective capabilities, no ambient capabilities, no setuid/setgid exe.
+    Unprivileged,
+    /// Some capabilities or a setuid/setgid executable, but nothing host-wide.
+    Elevated,
+    /// Holds at least one host-wide capability (e.g. CAP_SYS_ADMIN).
+    High,
+}
+
+impl PrivilegeLevel {
+    /// Stable label used in output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrivilegeLevel::Unprivileged => "unprivileged",
+            PrivilegeLevel::Elevated => "elevated",
+            PrivilegeLevel::High => "high",
+        }
+    }
+}
+
+/// Capability and privilege information for a process.
+///
+/// Parsed from the `Cap*`, `Uid`, `NoNewPrivs` and `Seccomp` lines of
+/// /proc/\[pid\]/status, plus the setuid/setgid bits of the executable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivilegeInfo {
+    /// Effective capability mask (CapEff).
+    pub cap_eff: u64,
+    /// Permitted capability mask (CapPrm).
+    pub cap_prm: u64,
+    /// Inheritable capability mask (CapInh).
+    pub cap_inh: u64,
+    /// Ambient capability mask (CapAmb).
+    pub cap_amb: u64,
+    /// Bounding capability mask (CapBnd).
+    pub cap_bnd: u64,
+    /// Real UID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ruid: Option<u32>,
+    /// Effective UID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub euid: Option<u32>,
+    /// Whether the no_new_privs bit is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_new_privs: Option<bool>,
+    /// Seccomp mode (0 = disabled, 1 = strict, 2 = filter).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seccomp: Option<u8>,
+    /// Executable has the setuid bit.
+    pub exe_setuid: bool,
+    /// Executable has the setgid bit.
+    pub exe_setgid: bool,
+}
+
+impl PrivilegeInfo {
+    /// Names of the capabilities in the effective set.
+    pub fn effective_capabilities(&self) -> Vec<&'static str> {
+        capability_names(self.cap_eff)
+    }
+
+    /// Names of the capabilities in the ambient set.
+    pub fn ambient_capabilities(&self) -> Vec<&'static str> {
+        capability_names(self.cap_amb)
+    }
+
+    /// Whether the process runs with an effective UID differing from its real UID.
+    pub fn uid_transition(&self) -> bool {
+        matches!((self.ruid, self.euid), (Some(r), Some(e)) if r != e)
+    }
+
+    /// Coarse privilege classification.
+    pub fn level(&self) -> PrivilegeLevel {
+        let effective = self.effective_capabilities();
+        if effective
+            .iter()
+            .any(|cap| HIGH_RISK_CAPABILITIES.contains(cap))
+        {
+            PrivilegeLevel::High
+        } else if self.cap_eff != 0
+            || self.cap_amb != 0
+            || self.exe_setuid
+            || self.exe_setgid
+            || self.uid_transition()
+        {
+            PrivilegeLevel::Elevated
+        } else {
+            PrivilegeLevel::Unprivileged
+        }
+    }
+}
+
+/// Decode a capability mask into capability names.
+///
+/// Bits beyond the known table are skipped, since newer kernels may define
+/// capabilities this build does not know about.
+pub fn capability_names(mask: u64) -> Vec<&'static str> {
+    CAPABILITY_NAMES
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| mask & (1u64 << bit) != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+/// Collect privilege information for a process.
+///
+/// Returns None if /proc/\[pid\]/status cannot be read. The exe mode bits are
+/// best-effort: an unreadable exe link leaves `exe_setuid`/`exe_setgid` false.
+pub fn parse_privileges(pid: u32) -> Option<PrivilegeInfo> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    Some(parse_privileges_content(&status, exe_mode(pid)))
+}
+
+/// Parse privilege information from status content and the exe mode bits (for testing).
+pub fn parse_privileges_content(status: &str, exe_mode: Option<u32>) -> PrivilegeInfo {
+    let mut info = PrivilegeInfo::default();
+
+    for line in status.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let mask = || u64::from_str_radix(value, 16).unwrap_or(0);
+        match key {
+            "CapEff" => info.cap_eff = mask(),
+            "CapPrm" => info.cap_prm = mask(),
+            "CapInh" => info.cap_inh = mask(),
+            "CapAmb" => info.cap_amb = mask(),
+            "CapBnd" => info.cap_bnd = mask(),
+            "Uid" => {
+                let mut ids = value.split_whitespace().map(|v| v.parse::<u32>().ok());
+                info.ruid = ids.next().flatten();
+                info.euid = ids.next().flatten();
+            }
+            "NoNewPrivs" => info.no_new_privs = value.parse::<u8>().ok().map(|v| v != 0),
+            "Seccomp" => info.seccomp = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    if let Some(mode) = exe_mode {
+        info.exe_setuid = mode & 0o4000 != 0;
+        info.exe_setgid = mode & 0o2000 != 0;
+    }
+
+    info
+}
+
+/// Read the mode bits of the executable behind /proc/\[pid\]/exe.
+pub(crate) fn exe_mode(pid: u32) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(format!("/proc/{}/exe", pid))
+            .ok()
+            .map(|m| m.permissions().mode())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Read the Yama ptrace scope (/proc/sys/kernel/yama/ptrace_scope).
+///
+/// 0 = classic, 1 = restricted to descendants, 2 = admin-only, 3 = disabled.
+/// Returns None when Yama is not enabled.
+pub fn read_ptrace_scope() -> Option<u8> {
+    fs::read_to_string("/proc/sys/kernel/yama/ptrace_scope")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -993,6 +1294,35 @@ nice                                         :                    0
         assert_eq!(stats.dt, 0);
     }
 
+    #[test]
+    fn test_parse_smaps_rollup_content() {
+        let content = "\
+55d0c0a00000-7ffc9a5fe000 ---p 00000000 00:00 0                          [rollup]
+Rss:              204800 kB
+Pss:               61440 kB
+Pss_Anon:          40960 kB
+Shared_Clean:     143360 kB
+Shared_Dirty:       4096 kB
+Private_Clean:      1024 kB
+Private_Dirty:     56320 kB
+Swap:               2048 kB
+SwapPss:            1024 kB
+Locked:                0 kB
+";
+        let rollup = parse_smaps_rollup_content(content).unwrap();
+        assert_eq!(rollup.rss_bytes, 204_800 * 1024);
+        assert_eq!(rollup.pss_bytes, 61_440 * 1024);
+        assert_eq!(rollup.uss_bytes, 57_344 * 1024);
+        assert_eq!(rollup.shared_bytes, 147_456 * 1024);
+        assert_eq!(rollup.swap_bytes, 2048 * 1024);
+        assert_eq!(rollup.swap_pss_bytes, 1024 * 1024);
+        // Pss_Anon must not be folded into Pss.
+        assert!(rollup.uss_bytes <= rollup.pss_bytes);
+
+        assert!(parse_smaps_rollup_content("").is_none());
+        assert!(parse_smaps_rollup_content("Rss: 10 kB\n").is_none());
+    }
+
     #[test]
     fn test_categorize_fd() {
         assert_eq!(categorize_fd("socket:[12345]"), "socket");
@@ -1657,4 +1987,56 @@ nice                                         :                    0
             "Should be a Soft block"
         );
     }
+
+    #[test]
+    fn test_parse_privileges_content_root_daemon() {
+        let status = "Name:\tsshd\nUid:\t0\t0\t0\t0\nGid:\t0\t0\t0\t0\n\
+CapInh:\t0000000000000000\nCapPrm:\t000001ffffffffff\nCapEff:\t000001ffffffffff\n\
+CapBnd:\t000001ffffffffff\nCapAmb:\t0000000000000000\nNoNewPrivs:\t0\nSeccomp:\t0\n";
+
+        let info = parse_privileges_content(status, Some(0o100755));
+        assert_eq!(info.cap_eff, 0x1ff_ffff_ffff);
+        assert_eq!(info.ruid, Some(0));
+        assert_eq!(info.euid, Some(0));
+        assert_eq!(info.no_new_privs, Some(false));
+        assert_eq!(info.seccomp, Some(0));
+        assert!(!info.exe_setuid);
+        assert!(info.effective_capabilities().contains(&"CAP_SYS_ADMIN"));
+        assert_eq!(info.level(), PrivilegeLevel::High);
+    }
+
+    #[test]
+    fn test_parse_privileges_content_unprivileged() {
+        let status = "Uid:\t1000\t1000\t1000\t1000\nCapEff:\t0000000000000000\n\
+CapAmb:\t0000000000000000\nNoNewPrivs:\t1\nSeccomp:\t2\n";
+
+        let info = parse_privileges_content(status, Some(0o100755));
+        assert_eq!(info.no_new_privs, Some(true));
+        assert_eq!(info.seccomp, Some(2));
+        assert_eq!(info.level(), PrivilegeLevel::Unprivileged);
+    }
+
+    #[test]
+    fn test_parse_privileges_setuid_and_ambient() {
+        let status = "Uid:\t1000\t0\t0\t0\nCapEff:\t0000000000000000\n";
+        let info = parse_privileges_content(status, Some(0o104755));
+        assert!(info.exe_setuid);
+        assert!(!info.exe_setgid);
+        assert!(info.uid_transition());
+        assert_eq!(info.level(), PrivilegeLevel::Elevated);
+
+        // CAP_NET_BIND_SERVICE (bit 10) in the ambient set only.
+        let status = "Uid:\t1000\t1000\t1000\t1000\nCapEff:\t0000000000000400\n\
+CapAmb:\t0000000000000400\n";
+        let info = parse_privileges_content(status, None);
+        assert_eq!(info.ambient_capabilities(), vec!["CAP_NET_BIND_SERVICE"]);
+        assert_eq!(info.level(), PrivilegeLevel::Elevated);
+    }
+
+    #[test]
+    fn test_capability_names_skips_unknown_bits() {
+        assert_eq!(capability_names(0), Vec::<&str>::new());
+        assert_eq!(capability_names(1 << 21), vec!["CAP_SYS_ADMIN"]);
+        assert_eq!(capability_names(1 << 63), Vec::<&str>::new());
+    }
 }
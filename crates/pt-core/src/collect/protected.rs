@@ -547,6 +547,7 @@ mod tests {
             elapsed: Duration::from_secs(3600),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         }
     }
 
@@ -710,6 +711,7 @@ mod tests {
                 duration_ms: 100,
                 process_count: 3,
                 warnings: vec![],
+                visibility: Default::default(),
             },
         };
 
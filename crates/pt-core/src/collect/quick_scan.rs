@@ -6,12 +6,20 @@
 //! # Platform Support
 //! - Linux: Uses procps-ng ps with extended format
 //! - macOS: Uses BSD ps with compatible format
+//! - Windows: No ps; enumerates via Toolhelp32 (see `collect::windows`)
 //!
 //! # Performance
 //! - Target: <1s for 1000 processes
 //! - Single ps invocation with custom format string
 
+// The ps parsing helpers are unused on Windows, where the scan goes through
+// Toolhelp32 instead.
+#![cfg_attr(target_os = "windows", allow(dead_code, unused_imports))]
+
+use super::io_rate::IoSampler;
+use super::scan_cache::SharedScanCache;
 use super::types::{ProcessRecord, ProcessState, ScanMetadata, ScanResult};
+use super::visibility::ProcVisibility;
 use crate::events::{event_names, Phase, ProgressEmitter, ProgressEvent};
 use pt_common::{ProcessId, StartId};
 use std::io::{BufRead, BufReader};
@@ -37,6 +45,16 @@ pub struct QuickScanOptions {
 
     /// Optional progress event emitter.
     pub progress: Option<Arc<dyn ProgressEmitter>>,
+
+    /// Warm cache for incremental scans (Linux only; ignored elsewhere).
+    ///
+    /// When set, `/proc` is read directly and only new or changed processes
+    /// are read in full. Long-running loops keep one cache across ticks.
+    pub incremental: Option<SharedScanCache>,
+
+    /// Sample I/O counters twice, this far apart, and set each record's
+    /// `io_rate` (see [`super::io_rate`]). `None` skips sampling.
+    pub io_rate_window: Option<Duration>,
 }
 
 impl std::fmt::Debug for QuickScanOptions {
@@ -46,6 +64,8 @@ impl std::fmt::Debug for QuickScanOptions {
             .field("include_kernel_threads", &self.include_kernel_threads)
             .field("timeout", &self.timeout)
             .field("progress", &self.progress.as_ref().map(|_| "..."))
+            .field("incremental", &self.incremental.is_some())
+            .field("io_rate_window", &self.io_rate_window)
             .finish()
     }
 }
@@ -72,7 +92,12 @@ pub enum QuickScanError {
 /// Perform a quick scan of running processes.
 ///
 /// Uses the ps command with a custom format string to collect process
-/// information efficiently in a single invocation.
+/// information efficiently in a single invocation. On Windows, processes are
+/// enumerated with a Toolhelp32 snapshot instead. On Linux, setting
+/// [`QuickScanOptions::incremental`] reads `/proc` through the warm
+/// [`ScanCache`](super::ScanCache) instead of running ps. Setting
+/// [`QuickScanOptions::io_rate_window`] brackets the scan with two I/O
+/// counter samples, waiting out the rest of the window afterwards.
 ///
 /// # Arguments
 /// * `options` - Scan configuration options
@@ -83,6 +108,33 @@ pub enum QuickScanError {
 /// # Errors
 /// * `QuickScanError` if ps fails or output cannot be parsed
 pub fn quick_scan(options: &QuickScanOptions) -> Result<ScanResult, QuickScanError> {
+    let Some(window) = options.io_rate_window else {
+        return scan_processes(options);
+    };
+    let sampler = IoSampler::start(&options.pids);
+    let mut result = scan_processes(options)?;
+    sampler.finish(window, &mut result.processes);
+    Ok(result)
+}
+
+fn scan_processes(options: &QuickScanOptions) -> Result<ScanResult, QuickScanError> {
+    #[cfg(target_os = "linux")]
+    if let Some(cache) = options.incremental.as_ref() {
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        return cache.scan(options);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        quick_scan_windows(options)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        quick_scan_ps(options)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn quick_scan_ps(options: &QuickScanOptions) -> Result<ScanResult, QuickScanError> {
     let _span = span!(Level::DEBUG, "quick_scan").entered();
     debug!("Starting quick scan via ps");
 
@@ -202,6 +254,12 @@ pub fn quick_scan(options: &QuickScanOptions) -> Result<ScanResult, QuickScanErr
     let duration = start.elapsed();
     let process_count = processes.len();
 
+    // Under hidepid the process list is silently partial; say so.
+    let visibility = ProcVisibility::detect();
+    if !visibility.is_full() {
+        warnings.push(format!("scan incomplete: {}", visibility.describe()));
+    }
+
     if timed_out.load(Ordering::Relaxed) {
         debug!(
             duration_ms = duration.as_millis(),
@@ -235,11 +293,68 @@ pub fn quick_scan(options: &QuickScanOptions) -> Result<ScanResult, QuickScanErr
             duration_ms: duration.as_millis() as u64,
             process_count,
             warnings,
+            visibility,
+        },
+    })
+}
+
+/// Quick scan via a Toolhelp32 snapshot (Windows has no ps).
+#[cfg(target_os = "windows")]
+fn quick_scan_windows(options: &QuickScanOptions) -> Result<ScanResult, QuickScanError> {
+    let _span = span!(Level::DEBUG, "quick_scan").entered();
+    debug!("Starting quick scan via toolhelp");
+
+    let start = Instant::now();
+    let platform = detect_platform();
+    let boot_id = read_boot_id();
+
+    if let Some(emitter) = options.progress.as_ref() {
+        emitter.emit(
+            ProgressEvent::new(event_names::QUICK_SCAN_STARTED, Phase::QuickScan)
+                .with_detail("platform", &platform)
+                .with_detail("boot_id", &boot_id),
+        );
+    }
+
+    let is_targeting_specific_pids = !options.pids.is_empty();
+    let processes: Vec<ProcessRecord> = super::windows::scan_processes()?
+        .into_iter()
+        .filter(|record| !is_targeting_specific_pids || options.pids.contains(&record.pid.0))
+        .filter(|record| {
+            options.include_kernel_threads
+                || is_targeting_specific_pids
+                || !is_kernel_thread(record)
+        })
+        .collect();
+
+    let duration = start.elapsed();
+    let process_count = processes.len();
+
+    if let Some(emitter) = options.progress.as_ref() {
+        emitter.emit(
+            ProgressEvent::new(event_names::QUICK_SCAN_COMPLETE, Phase::QuickScan)
+                .with_progress(process_count as u64, Some(process_count as u64))
+                .with_elapsed_ms(duration.as_millis() as u64)
+                .with_detail("warnings", 0),
+        );
+    }
+
+    Ok(ScanResult {
+        processes,
+        metadata: ScanMetadata {
+            scan_type: "quick".to_string(),
+            platform,
+            boot_id,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            duration_ms: duration.as_millis() as u64,
+            process_count,
+            warnings: Vec::new(),
+            visibility: Default::default(),
         },
     })
 }
 
-fn is_header_line(line: &str) -> bool {
+pub(crate) fn is_header_line(line: &str) -> bool {
     let mut parts = line.split_whitespace();
     matches!(
         (parts.next(), parts.next()),
@@ -307,7 +422,8 @@ fn detect_platform() -> String {
     }
 }
 
-/// Read boot ID from /proc/sys/kernel/random/boot_id (Linux only).
+/// Read boot ID from /proc/sys/kernel/random/boot_id (Linux), the boot
+/// session UUID (macOS), or the kernel boot time (Windows).
 fn read_boot_id() -> Option<String> {
     #[cfg(target_os = "linux")]
     {
@@ -315,7 +431,15 @@ fn read_boot_id() -> Option<String> {
             .ok()
             .map(|s| s.trim().to_string())
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(target_os = "macos")]
+    {
+        super::macos::boot_session_uuid()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        super::windows::boot_id()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
     {
         None
     }
@@ -442,12 +566,16 @@ fn parse_ps_line(
         elapsed,
         source: "quick_scan".to_string(),
         container_info: None, // Container detection done as post-processing step
+        io_rate: None,
     })
 }
 
 /// Parse a single line of ps output like `parse_ps_line`, but with deterministic
 /// timing + start_id derivation and no `/proc` reads (bench/test helper).
-fn parse_ps_line_synthetic(
+///
+/// Agentless fleet scans use it for `ps` output collected on another host,
+/// passing that host's platform, boot id and clock.
+pub(crate) fn parse_ps_line_synthetic(
     line: &str,
     platform: &str,
     boot_id: &Option<String>,
@@ -455,10 +583,12 @@ fn parse_ps_line_synthetic(
 ) -> Result<ProcessRecord, String> {
     let fields: Vec<&str> = line.split_whitespace().collect();
 
+    // Short command lines ("sshd", "[kthreadd]") leave only comm + one arg.
     let comm_idx = 17;
-    if fields.len() < 22 {
+    if fields.len() <= comm_idx {
         return Err(format!(
-            "Insufficient fields: expected at least 22, got {}",
+            "Insufficient fields: expected at least {}, got {}",
+            comm_idx + 1,
             fields.len()
         ));
     }
@@ -518,6 +648,7 @@ fn parse_ps_line_synthetic(
         elapsed,
         source: "quick_scan".to_string(),
         container_info: None,
+        io_rate: None,
     })
 }
 
@@ -648,9 +779,11 @@ fn compute_start_id(
             StartId::from_linux(boot, ticks, pid)
         }
         "macos" => {
-            // macOS: use start_time:pid (no boot_id available easily)
+            // macOS: prefer the kernel start time (libproc); the ps-derived
+            // `now - etime` value can drift by a second between scans.
             let boot = boot_id.as_deref().unwrap_or("unknown");
-            StartId::from_macos(boot, start_time_unix as u64, pid)
+            let start = macos_start_time(pid).unwrap_or(start_time_unix.max(0) as u64);
+            StartId::from_macos(boot, start, pid)
         }
         _ => {
             // Fallback: use start_time:pid
@@ -659,6 +792,16 @@ fn compute_start_id(
     }
 }
 
+#[cfg(target_os = "macos")]
+fn macos_start_time(pid: u32) -> Option<u64> {
+    super::macos::native_start_time(pid)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn macos_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
 #[cfg(target_os = "linux")]
 fn linux_start_ticks_from_uptime(elapsed: Duration) -> Option<u64> {
     let uptime = read_uptime_seconds()?;
@@ -739,7 +882,7 @@ fn clock_ticks_per_second() -> Option<u64> {
 ///
 /// Note: PID 0 (swapper) never appears in ps output.
 /// Note: PID 1 (init/systemd) has PPID 0 but is NOT a kernel thread.
-fn is_kernel_thread(record: &ProcessRecord) -> bool {
+pub(super) fn is_kernel_thread(record: &ProcessRecord) -> bool {
     let ppid = record.ppid.0;
 
     // Special case: PID 1 (init/systemd) has PPID 0 but is NOT a kernel thread
@@ -1066,6 +1209,7 @@ mod tests {
             elapsed: Duration::from_secs(0),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         }
     }
 
@@ -51,6 +51,7 @@ fn test_deep_scan_real_pid() {
         skip_inaccessible: true,
         include_environ: false,
         progress: None,
+        budget: None,
     };
     let result = deep_scan(&options).expect("deep_scan");
 
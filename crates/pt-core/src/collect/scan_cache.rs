@@ -0,0 +1,586 @@
+//! Warm process cache for incremental quick scans.
+//!
+//! A full quick scan re-reads every process each time, which is slow on hosts
+//! with tens of thousands of processes. With a [`ScanCache`] attached via
+//! [`QuickScanOptions::incremental`](super::QuickScanOptions::incremental),
+//! the scan reads `/proc` directly and keeps records keyed by
+//! `(pid, start_time_ticks)`:
+//!
+//! - Known processes only have `/proc/<pid>/stat` re-read to refresh state,
+//!   CPU, memory, parentage and TTY. Command line, UID and user are reused.
+//! - New processes, reused PIDs (different start time) and processes that
+//!   exec'd (different `comm`) are read in full.
+//! - Processes that disappeared are evicted.
+//!
+//! Records are built with the same semantics as the `ps`-based scan
+//! (lifetime-average `cpu_percent`, `[comm]` for empty command lines), but
+//! `start_id` comes from the exact kernel start time rather than being
+//! derived from elapsed time.
+//!
+//! The cache can be saved to disk so short-lived processes (e.g. shadow mode
+//! iterations) stay warm across runs; see [`SCAN_CACHE_ENV`].
+
+use super::quick_scan::{is_kernel_thread, QuickScanError, QuickScanOptions};
+use super::types::{ProcessRecord, ProcessState, ScanMetadata, ScanResult};
+use super::visibility::ProcVisibility;
+use crate::collect::proc_parsers::{parse_proc_stat_content, ProcessStat};
+use crate::events::{event_names, Phase, ProgressEvent};
+use pt_common::{ProcessId, StartId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Environment variable naming a file used to persist the scan cache between
+/// processes. Set by the shadow loop for its `agent plan` iterations.
+pub const SCAN_CACHE_ENV: &str = "PT_SCAN_CACHE";
+
+/// Scan cache shared between a long-running loop and its scans.
+pub type SharedScanCache = Arc<Mutex<ScanCache>>;
+
+/// Counters from the most recent incremental scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ScanCacheStats {
+    /// Processes whose cached record was refreshed from `stat` only.
+    pub reused: usize,
+    /// Processes read in full (new, PID reused, or exec'd).
+    pub refreshed: usize,
+    /// Cached processes that no longer exist.
+    pub evicted: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedProcess {
+    start_ticks: u64,
+    record: ProcessRecord,
+}
+
+/// Per-tick system facts needed to turn `stat` into a record.
+#[derive(Debug, Clone)]
+struct TickContext {
+    boot_id: Option<String>,
+    uptime_secs: f64,
+    boot_time_unix: i64,
+    clock_ticks: u64,
+    page_size: u64,
+}
+
+/// Warm cache of process records keyed by `(pid, start_time_ticks)`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    boot_id: Option<String>,
+    entries: HashMap<u32, CachedProcess>,
+    #[serde(skip)]
+    users: Option<HashMap<u32, String>>,
+    #[serde(skip)]
+    last_stats: ScanCacheStats,
+}
+
+impl ScanCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty cache ready to share with a scan loop.
+    pub fn shared() -> SharedScanCache {
+        Arc::new(Mutex::new(Self::new()))
+    }
+
+    /// Number of cached processes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Counters from the most recent scan.
+    pub fn last_stats(&self) -> ScanCacheStats {
+        self.last_stats
+    }
+
+    /// Load a persisted cache, starting empty if the file is missing or unreadable.
+    pub fn load_or_default(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the cache atomically.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(self).map_err(io::Error::other)?)?;
+        fs::rename(tmp, path)
+    }
+
+    /// Incremental scan of the live `/proc`.
+    ///
+    /// Fails with [`QuickScanError::UnsupportedPlatform`] where `/proc` is
+    /// unavailable; [`quick_scan`](super::quick_scan) only calls this on Linux.
+    pub fn scan(&mut self, options: &QuickScanOptions) -> Result<ScanResult, QuickScanError> {
+        let started = Instant::now();
+        let proc_root = Path::new("/proc");
+        let ctx = TickContext::read(proc_root).ok_or_else(|| {
+            QuickScanError::UnsupportedPlatform("incremental scan requires /proc".to_string())
+        })?;
+        if self.users.is_none() {
+            self.users = Some(read_passwd(Path::new("/etc/passwd")));
+        }
+        if let Some(emitter) = options.progress.as_ref() {
+            emitter.emit(
+                ProgressEvent::new(event_names::QUICK_SCAN_STARTED, Phase::QuickScan)
+                    .with_detail("platform", "linux")
+                    .with_detail("boot_id", &ctx.boot_id)
+                    .with_detail("cached", self.entries.len()),
+            );
+        }
+
+        let mut processes = self.scan_root(proc_root, &ctx, options)?;
+        let mut warnings = Vec::new();
+        let visibility = ProcVisibility::detect();
+        if !visibility.is_full() {
+            warnings.push(format!("scan incomplete: {}", visibility.describe()));
+        }
+        processes.sort_by_key(|p| p.pid.0);
+
+        let duration = started.elapsed();
+        debug!(
+            process_count = processes.len(),
+            reused = self.last_stats.reused,
+            refreshed = self.last_stats.refreshed,
+            evicted = self.last_stats.evicted,
+            duration_ms = duration.as_millis(),
+            "Incremental quick scan completed"
+        );
+        if let Some(emitter) = options.progress.as_ref() {
+            emitter.emit(
+                ProgressEvent::new(event_names::QUICK_SCAN_COMPLETE, Phase::QuickScan)
+                    .with_progress(processes.len() as u64, Some(processes.len() as u64))
+                    .with_elapsed_ms(duration.as_millis() as u64)
+                    .with_detail("warnings", warnings.len())
+                    .with_detail("reused", self.last_stats.reused),
+            );
+        }
+
+        Ok(ScanResult {
+            metadata: ScanMetadata {
+                scan_type: "quick".to_string(),
+                platform: "linux".to_string(),
+                boot_id: ctx.boot_id,
+                started_at: chrono::Utc::now().to_rfc3339(),
+                duration_ms: duration.as_millis() as u64,
+                process_count: processes.len(),
+                warnings,
+                visibility,
+            },
+            processes,
+        })
+    }
+
+    /// Scan a `/proc`-shaped directory, updating the cache.
+    fn scan_root(
+        &mut self,
+        root: &Path,
+        ctx: &TickContext,
+        options: &QuickScanOptions,
+    ) -> Result<Vec<ProcessRecord>, QuickScanError> {
+        // A cache from a previous boot is meaningless.
+        if self.boot_id != ctx.boot_id {
+            self.entries.clear();
+            self.boot_id = ctx.boot_id.clone();
+        }
+
+        let pids: Vec<u32> = if options.pids.is_empty() {
+            fs::read_dir(root)?
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        } else {
+            options.pids.clone()
+        };
+
+        let users = self.users.take().unwrap_or_default();
+        let mut stats = ScanCacheStats::default();
+        let mut seen = HashSet::with_capacity(pids.len());
+        let mut processes = Vec::with_capacity(pids.len());
+
+        for pid in pids {
+            let pid_dir = root.join(pid.to_string());
+            let Some(stat) = fs::read_to_string(pid_dir.join("stat"))
+                .ok()
+                .and_then(|content| parse_proc_stat_content(&content))
+            else {
+                continue; // exited between readdir and read
+            };
+
+            let cached = self
+                .entries
+                .get(&pid)
+                .filter(|c| c.start_ticks == stat.starttime && c.record.comm == stat.comm);
+            let record = match cached {
+                Some(cached) => {
+                    stats.reused += 1;
+                    let mut record = cached.record.clone();
+                    apply_stat(&mut record, &stat, ctx);
+                    record
+                }
+                None => {
+                    let Some(record) = read_full(&pid_dir, &stat, ctx, &users) else {
+                        continue;
+                    };
+                    stats.refreshed += 1;
+                    record
+                }
+            };
+
+            seen.insert(pid);
+            self.entries.insert(
+                pid,
+                CachedProcess {
+                    start_ticks: stat.starttime,
+                    record: record.clone(),
+                },
+            );
+
+            if !options.include_kernel_threads
+                && options.pids.is_empty()
+                && is_kernel_thread(&record)
+            {
+                continue;
+            }
+            processes.push(record);
+        }
+
+        // Targeted scans only evict the PIDs they were asked about.
+        let before = self.entries.len();
+        if options.pids.is_empty() {
+            self.entries.retain(|pid, _| seen.contains(pid));
+        } else {
+            for pid in &options.pids {
+                if !seen.contains(pid) {
+                    self.entries.remove(pid);
+                }
+            }
+        }
+        stats.evicted = before - self.entries.len();
+
+        self.users = Some(users);
+        self.last_stats = stats;
+        Ok(processes)
+    }
+}
+
+impl TickContext {
+    fn read(root: &Path) -> Option<Self> {
+        let uptime_secs = fs::read_to_string(root.join("uptime"))
+            .ok()?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()?;
+        let boot_time_unix = fs::read_to_string(root.join("stat"))
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("btime")?.trim().parse().ok())?;
+        let boot_id = fs::read_to_string(root.join("sys/kernel/random/boot_id"))
+            .ok()
+            .map(|s| s.trim().to_string());
+        Some(Self {
+            boot_id,
+            uptime_secs,
+            boot_time_unix,
+            #[cfg(unix)]
+            clock_ticks: sysconf(libc::_SC_CLK_TCK).unwrap_or(100),
+            #[cfg(unix)]
+            page_size: sysconf(libc::_SC_PAGESIZE).unwrap_or(4096),
+            #[cfg(not(unix))]
+            clock_ticks: 100,
+            #[cfg(not(unix))]
+            page_size: 4096,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn sysconf(name: libc::c_int) -> Option<u64> {
+    let value = unsafe { libc::sysconf(name) };
+    (value > 0).then_some(value as u64)
+}
+
+/// Refresh the fields of a record that can change without exec.
+fn apply_stat(record: &mut ProcessRecord, stat: &ProcessStat, ctx: &TickContext) {
+    let hz = ctx.clock_ticks.max(1) as f64;
+    let start_secs = stat.starttime as f64 / hz;
+    let elapsed_secs = (ctx.uptime_secs - start_secs).max(0.0);
+    let cpu_secs = (stat.utime + stat.stime) as f64 / hz;
+
+    record.ppid = ProcessId(stat.ppid);
+    record.pgid = u32::try_from(stat.pgrp).ok();
+    record.sid = u32::try_from(stat.session).ok();
+    record.state = ProcessState::from_char(stat.state);
+    // Lifetime average, matching ps %cpu.
+    record.cpu_percent = if elapsed_secs > 0.0 {
+        (cpu_secs / elapsed_secs * 100.0 * 10.0).round() / 10.0
+    } else {
+        0.0
+    };
+    record.rss_bytes = (stat.rss.max(0) as u64).saturating_mul(ctx.page_size);
+    record.vsz_bytes = stat.vsize;
+    record.tty = tty_name(stat.tty_nr);
+    record.elapsed = Duration::from_secs_f64(elapsed_secs);
+}
+
+/// Build a record from scratch for a process not (validly) in the cache.
+fn read_full(
+    pid_dir: &Path,
+    stat: &ProcessStat,
+    ctx: &TickContext,
+    users: &HashMap<u32, String>,
+) -> Option<ProcessRecord> {
+    let status = fs::read_to_string(pid_dir.join("status")).ok()?;
+    // Effective UID, matching `ps -o uid`.
+    let uid = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))
+        .and_then(|ids| ids.split_whitespace().nth(1))
+        .and_then(|id| id.parse().ok())?;
+    let cmdline = fs::read(pid_dir.join("cmdline")).unwrap_or_default();
+    let cmd = String::from_utf8_lossy(&cmdline)
+        .split('\0')
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let cmd = if cmd.is_empty() {
+        format!("[{}]", stat.comm)
+    } else {
+        cmd
+    };
+
+    let boot = ctx.boot_id.as_deref().unwrap_or("unknown");
+    let mut record = ProcessRecord {
+        pid: ProcessId(stat.pid),
+        ppid: ProcessId(stat.ppid),
+        uid,
+        user: users.get(&uid).cloned().unwrap_or_else(|| uid.to_string()),
+        pgid: None,
+        sid: None,
+        start_id: StartId::from_linux(boot, stat.starttime, stat.pid),
+        comm: stat.comm.clone(),
+        cmd,
+        state: ProcessState::from_char(stat.state),
+        cpu_percent: 0.0,
+        rss_bytes: 0,
+        vsz_bytes: 0,
+        tty: None,
+        start_time_unix: ctx.boot_time_unix + (stat.starttime / ctx.clock_ticks.max(1)) as i64,
+        elapsed: Duration::ZERO,
+        source: "quick_scan".to_string(),
+        container_info: None,
+        io_rate: None,
+    };
+    apply_stat(&mut record, stat, ctx);
+    Some(record)
+}
+
+/// Decode `tty_nr` into the name `ps` would print.
+fn tty_name(tty_nr: i32) -> Option<String> {
+    if tty_nr <= 0 {
+        return None;
+    }
+    let dev = tty_nr as u32;
+    let major = (dev >> 8) & 0xfff;
+    let minor = (dev & 0xff) | ((dev >> 12) & 0xfff00);
+    match major {
+        136..=143 => Some(format!("pts/{}", minor + (major - 136) * 256)),
+        4 if minor < 64 => Some(format!("tty{}", minor)),
+        4 => Some(format!("ttyS{}", minor - 64)),
+        _ => Some(format!("{}:{}", major, minor)),
+    }
+}
+
+fn read_passwd(path: &Path) -> HashMap<u32, String> {
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split(':');
+                    let name = fields.next()?;
+                    let uid = fields.nth(1)?.parse().ok()?;
+                    Some((uid, name.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the persisted cache named by [`SCAN_CACHE_ENV`], if set.
+///
+/// Returns the path so the caller can save the cache after scanning.
+pub fn persisted_from_env() -> Option<(PathBuf, SharedScanCache)> {
+    let path = PathBuf::from(std::env::var_os(SCAN_CACHE_ENV).filter(|v| !v.is_empty())?);
+    let cache = ScanCache::load_or_default(&path);
+    Some((path, Arc::new(Mutex::new(cache))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn ctx() -> TickContext {
+        TickContext {
+            boot_id: Some("boot-a".to_string()),
+            uptime_secs: 1000.0,
+            boot_time_unix: 1_700_000_000,
+            clock_ticks: 100,
+            page_size: 4096,
+        }
+    }
+
+    fn stat_line(pid: u32, comm: &str, ppid: u32, starttime: u64, utime: u64) -> String {
+        format!(
+            "{pid} ({comm}) S {ppid} {pid} {pid} 34816 0 0 0 0 0 0 {utime} 0 0 0 20 0 1 0 {starttime} 1048576 256 0"
+        )
+    }
+
+    fn write_proc(root: &Path, pid: u32, comm: &str, ppid: u32, starttime: u64, cmd: &str) {
+        let dir = root.join(pid.to_string());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("stat"), stat_line(pid, comm, ppid, starttime, 50)).unwrap();
+        fs::write(
+            dir.join("status"),
+            "Name:\tx\nUid:\t1000\t1000\t1000\t1000\n",
+        )
+        .unwrap();
+        fs::write(dir.join("cmdline"), cmd.replace(' ', "\0")).unwrap();
+    }
+
+    fn cache_with_users() -> ScanCache {
+        let mut cache = ScanCache::new();
+        cache.users = Some(HashMap::from([(1000, "alice".to_string())]));
+        cache
+    }
+
+    #[test]
+    fn full_read_then_stat_only_refresh() {
+        let tmp = TempDir::new().unwrap();
+        write_proc(tmp.path(), 42, "sleep", 1, 50_000, "sleep 600");
+        let mut cache = cache_with_users();
+        let options = QuickScanOptions::default();
+
+        let first = cache.scan_root(tmp.path(), &ctx(), &options).unwrap();
+        assert_eq!(first.len(), 1);
+        let proc = &first[0];
+        assert_eq!(proc.cmd, "sleep 600");
+        assert_eq!(proc.user, "alice");
+        assert_eq!(proc.start_id.0, "boot-a:50000:42");
+        assert_eq!(proc.elapsed, Duration::from_secs(500));
+        assert_eq!(proc.tty.as_deref(), Some("pts/0"));
+        assert_eq!(proc.rss_bytes, 256 * 4096);
+        assert_eq!(cache.last_stats().refreshed, 1);
+
+        // Cmdline changes are ignored for a known process: only stat is re-read.
+        let dir = tmp.path().join("42");
+        fs::write(dir.join("cmdline"), "changed").unwrap();
+        fs::write(dir.join("stat"), stat_line(42, "sleep", 7, 50_000, 100)).unwrap();
+        let second = cache.scan_root(tmp.path(), &ctx(), &options).unwrap();
+        assert_eq!(second[0].cmd, "sleep 600");
+        assert_eq!(second[0].ppid.0, 7);
+        assert_eq!(cache.last_stats().reused, 1);
+        assert_eq!(cache.last_stats().refreshed, 0);
+    }
+
+    #[test]
+    fn pid_reuse_exec_and_exit_are_detected() {
+        let tmp = TempDir::new().unwrap();
+        write_proc(tmp.path(), 10, "a", 1, 100, "a");
+        write_proc(tmp.path(), 11, "b", 1, 200, "b");
+        write_proc(tmp.path(), 12, "c", 1, 300, "c");
+        let mut cache = cache_with_users();
+        let options = QuickScanOptions::default();
+        cache.scan_root(tmp.path(), &ctx(), &options).unwrap();
+
+        write_proc(tmp.path(), 10, "a2", 1, 900, "a2"); // PID reused
+        write_proc(tmp.path(), 11, "b-exec", 1, 200, "b-exec"); // exec'd
+        fs::remove_dir_all(tmp.path().join("12")).unwrap(); // exited
+        let procs = cache.scan_root(tmp.path(), &ctx(), &options).unwrap();
+
+        assert_eq!(procs.len(), 2);
+        assert!(procs
+            .iter()
+            .any(|p| p.cmd == "a2" && p.start_id.0.contains(":900:")));
+        assert!(procs.iter().any(|p| p.cmd == "b-exec"));
+        assert_eq!(
+            cache.last_stats(),
+            ScanCacheStats {
+                reused: 0,
+                refreshed: 2,
+                evicted: 1
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kernel_threads_filtered_and_boot_change_clears() {
+        let tmp = TempDir::new().unwrap();
+        write_proc(tmp.path(), 2, "kthreadd", 0, 1, "");
+        write_proc(tmp.path(), 30, "kworker/0:1", 2, 5, "");
+        write_proc(tmp.path(), 31, "bash", 1, 5, "bash");
+        let mut cache = cache_with_users();
+
+        let procs = cache
+            .scan_root(tmp.path(), &ctx(), &QuickScanOptions::default())
+            .unwrap();
+        assert_eq!(procs.len(), 1);
+        assert_eq!(procs[0].comm, "bash");
+        assert_eq!(cache.len(), 3);
+
+        let mut rebooted = ctx();
+        rebooted.boot_id = Some("boot-b".to_string());
+        cache
+            .scan_root(tmp.path(), &rebooted, &QuickScanOptions::default())
+            .unwrap();
+        assert_eq!(cache.last_stats().refreshed, 3);
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        write_proc(tmp.path(), 42, "sleep", 1, 50_000, "sleep 600");
+        let mut cache = cache_with_users();
+        cache
+            .scan_root(tmp.path(), &ctx(), &QuickScanOptions::default())
+            .unwrap();
+
+        let path = tmp.path().join("cache/scan.json");
+        cache.save(&path).unwrap();
+        let mut loaded = ScanCache::load_or_default(&path);
+        assert_eq!(loaded.len(), 1);
+        loaded.users = Some(HashMap::new());
+        loaded
+            .scan_root(tmp.path(), &ctx(), &QuickScanOptions::default())
+            .unwrap();
+        assert_eq!(loaded.last_stats().reused, 1);
+
+        assert!(ScanCache::load_or_default(&tmp.path().join("missing.json")).is_empty());
+    }
+
+    #[test]
+    fn tty_names_match_ps() {
+        assert_eq!(tty_name(0), None);
+        assert_eq!(tty_name((136 << 8) | 3).as_deref(), Some("pts/3"));
+        assert_eq!(tty_name((4 << 8) | 1).as_deref(), Some("tty1"));
+        assert_eq!(tty_name((4 << 8) | 65).as_deref(), Some("ttyS1"));
+    }
+}
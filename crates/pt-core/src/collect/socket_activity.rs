@@ -0,0 +1,795 @@
+//! Per-socket TCP activity recency.
+//!
+//! The boolean "has network sockets" signal cannot tell a listener that
+//! served traffic seconds ago from one that has been idle for a month. This
+//! module pulls per-socket activity approximations so inference can bucket a
+//! process by how long its sockets have been quiet.
+//!
+//! # Data Sources
+//! Tried in order, falling back when a source is unavailable:
+//! 1. `NETLINK_SOCK_DIAG` dump with `INET_DIAG_INFO`: full `tcp_info`,
+//!    including `tcpi_last_data_sent` / `tcpi_last_data_recv`.
+//! 2. `ss -tinHe`: the same `tcp_info` fields rendered as `lastsnd:` /
+//!    `lastrcv:` (used when the netlink socket is blocked, e.g. by seccomp).
+//! 3. `/proc/net/tcp{,6}`: queue depths and timer state only, so recency is
+//!    known only for sockets with data in flight.
+//!
+//! `tcpi_last_ack_recv` is deliberately ignored: keepalive probes refresh it,
+//! so an idle connection with keepalive enabled would always look recent.
+//!
+//! # Wire format
+//! Request is `nlmsghdr` + `inet_diag_req_v2`; each reply is `nlmsghdr` +
+//! `inet_diag_msg` followed by `rtattr`s (see
+//! `include/uapi/linux/inet_diag.h`). All integers are native-endian.
+
+use super::network::{get_process_socket_inodes, TcpState};
+use super::tool_runner::run_tool;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// `NETLINK_SOCK_DIAG` protocol and request type.
+const NETLINK_SOCK_DIAG: libc::c_int = 4;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+
+/// `nlmsghdr.nlmsg_type` control values.
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+
+/// `NLM_F_REQUEST | NLM_F_DUMP`.
+const NLM_F_REQUEST_DUMP: u16 = 0x0001 | 0x0300;
+
+/// Attribute carrying `struct tcp_info`.
+const INET_DIAG_INFO: u16 = 2;
+
+const NLMSG_HDR_LEN: usize = 16;
+const INET_DIAG_REQ_V2_LEN: usize = 56;
+const INET_DIAG_MSG_LEN: usize = 72;
+const RTATTR_HDR_LEN: usize = 4;
+
+/// Offsets into `struct tcp_info`.
+const TCPI_LAST_DATA_SENT: usize = 44;
+const TCPI_LAST_DATA_RECV: usize = 52;
+
+/// Upper bound on the whole sock_diag dump.
+const DUMP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a single recv may block before the deadline is re-checked.
+const RECV_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Timeout and output cap for the `ss` fallback.
+const SS_TIMEOUT: Duration = Duration::from_secs(2);
+const SS_MAX_OUTPUT: usize = 4 * 1024 * 1024;
+
+/// Errors from socket activity collection.
+#[derive(Debug, Error)]
+pub enum SocketActivityError {
+    #[error("failed to open sock_diag socket: {0}")]
+    Socket(#[source] io::Error),
+
+    #[error("failed to send sock_diag request: {0}")]
+    Send(#[source] io::Error),
+
+    #[error("failed to receive sock_diag reply: {0}")]
+    Recv(#[source] io::Error),
+
+    #[error("sock_diag request rejected by kernel: {0}")]
+    Kernel(#[source] io::Error),
+
+    #[error("ss fallback failed: {0}")]
+    Tool(String),
+}
+
+/// Where socket activity came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivitySource {
+    SockDiag,
+    Ss,
+    ProcNet,
+}
+
+/// Pending TCP timer (`idiag_timer`, `/proc/net/tcp` `tr`, `ss` `timer:`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TcpTimer {
+    #[default]
+    None,
+    Retransmit,
+    Keepalive,
+    TimeWait,
+    /// Zero-window probe.
+    Probe,
+}
+
+impl TcpTimer {
+    /// Parse the numeric timer code used by sock_diag and /proc/net/tcp.
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => TcpTimer::Retransmit,
+            2 => TcpTimer::Keepalive,
+            3 => TcpTimer::TimeWait,
+            4 => TcpTimer::Probe,
+            _ => TcpTimer::None,
+        }
+    }
+
+    /// Parse the timer name from `ss` output (`timer:(on,...)`).
+    pub fn from_ss(name: &str) -> Self {
+        match name {
+            "on" => TcpTimer::Retransmit,
+            "keepalive" => TcpTimer::Keepalive,
+            "timewait" => TcpTimer::TimeWait,
+            "persist" => TcpTimer::Probe,
+            _ => TcpTimer::None,
+        }
+    }
+
+    /// Whether the timer implies unacknowledged data in flight.
+    pub fn data_in_flight(&self) -> bool {
+        matches!(self, TcpTimer::Retransmit | TcpTimer::Probe)
+    }
+}
+
+/// Activity approximation for one TCP socket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TcpActivity {
+    pub inode: u64,
+    pub state: TcpState,
+    pub timer: TcpTimer,
+    pub retransmits: u8,
+    /// Receive queue (accept backlog for listeners).
+    pub recv_queue: u32,
+    pub send_queue: u32,
+    /// Milliseconds since data was last sent, when `tcp_info` is available.
+    pub last_data_sent_ms: Option<u64>,
+    /// Milliseconds since data was last received, when `tcp_info` is available.
+    pub last_data_recv_ms: Option<u64>,
+}
+
+impl TcpActivity {
+    /// Milliseconds since the socket last carried data, if known.
+    ///
+    /// Queued data or a pending retransmit counts as active now. Listeners
+    /// are only known to be active when connections are waiting in the
+    /// accept backlog; their `tcp_info` timestamps are meaningless.
+    pub fn idle_ms(&self) -> Option<u64> {
+        if self.state.is_listen() {
+            return (self.recv_queue > 0).then_some(0);
+        }
+        if self.recv_queue > 0 || self.send_queue > 0 || self.timer.data_in_flight() {
+            return Some(0);
+        }
+        match (self.last_data_sent_ms, self.last_data_recv_ms) {
+            (Some(sent), Some(recv)) => Some(sent.min(recv)),
+            (sent, recv) => sent.or(recv),
+        }
+    }
+}
+
+/// Socket activity summary for one process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessNetActivity {
+    pub source: ActivitySource,
+    /// TCP sockets owned by the process (including listeners).
+    pub tcp_sockets: usize,
+    pub listeners: usize,
+    /// Sockets with queued data or a pending retransmit.
+    pub queued_sockets: usize,
+    /// Seconds since the most recently active socket carried data, if known.
+    pub idle_secs: Option<f64>,
+}
+
+/// A snapshot of TCP socket activity indexed by inode.
+#[derive(Debug, Default)]
+pub struct SocketActivitySnapshot {
+    source: Option<ActivitySource>,
+    by_inode: HashMap<u64, TcpActivity>,
+    degraded: Vec<String>,
+}
+
+impl SocketActivitySnapshot {
+    /// Collect socket activity, degrading through sock_diag, `ss` and /proc/net.
+    pub fn collect() -> Self {
+        let mut degraded = Vec::new();
+
+        match collect_sock_diag() {
+            Ok(entries) => return Self::from_entries(ActivitySource::SockDiag, entries),
+            Err(e) => degraded.push(format!("sock_diag: {}", e)),
+        }
+
+        match collect_ss() {
+            Ok(entries) => {
+                let mut snapshot = Self::from_entries(ActivitySource::Ss, entries);
+                snapshot.degraded = degraded;
+                return snapshot;
+            }
+            Err(e) => degraded.push(format!("ss: {}", e)),
+        }
+
+        let mut entries = Vec::new();
+        let mut any_read = false;
+        for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            if let Ok(content) = fs::read_to_string(path) {
+                any_read = true;
+                entries.extend(parse_proc_net_tcp_activity(&content));
+            }
+        }
+        if !any_read {
+            degraded.push("proc_net: /proc/net/tcp unreadable".to_string());
+            return Self {
+                degraded,
+                ..Self::default()
+            };
+        }
+        let mut snapshot = Self::from_entries(ActivitySource::ProcNet, entries);
+        snapshot.degraded = degraded;
+        snapshot
+    }
+
+    /// Build a snapshot from already-collected entries.
+    pub fn from_entries(source: ActivitySource, entries: Vec<TcpActivity>) -> Self {
+        let by_inode = entries
+            .into_iter()
+            .filter(|e| e.inode != 0)
+            .map(|e| (e.inode, e))
+            .collect();
+        Self {
+            source: Some(source),
+            by_inode,
+            degraded: Vec::new(),
+        }
+    }
+
+    /// Source that produced the snapshot, or `None` if nothing was readable.
+    pub fn source(&self) -> Option<ActivitySource> {
+        self.source
+    }
+
+    /// Reasons earlier sources were skipped.
+    pub fn degraded(&self) -> &[String] {
+        &self.degraded
+    }
+
+    /// Activity summary for a process's TCP sockets.
+    pub fn process_activity(&self, pid: u32) -> Option<ProcessNetActivity> {
+        if self.by_inode.is_empty() {
+            return None;
+        }
+        let inodes = get_process_socket_inodes(pid)?;
+        self.activity_for_inodes(&inodes)
+    }
+
+    /// Activity summary for a set of socket inodes. `None` if none are TCP.
+    pub fn activity_for_inodes(&self, inodes: &HashSet<u64>) -> Option<ProcessNetActivity> {
+        let source = self.source?;
+        let mut tcp_sockets = 0;
+        let mut listeners = 0;
+        let mut queued_sockets = 0;
+        let mut idle_ms: Option<u64> = None;
+
+        for activity in inodes.iter().filter_map(|inode| self.by_inode.get(inode)) {
+            tcp_sockets += 1;
+            if activity.state.is_listen() {
+                listeners += 1;
+            }
+            let idle = activity.idle_ms();
+            if idle == Some(0) {
+                queued_sockets += 1;
+            }
+            if let Some(ms) = idle {
+                idle_ms = Some(idle_ms.map_or(ms, |current| current.min(ms)));
+            }
+        }
+
+        if tcp_sockets == 0 {
+            return None;
+        }
+        Some(ProcessNetActivity {
+            source,
+            tcp_sockets,
+            listeners,
+            queued_sockets,
+            idle_secs: idle_ms.map(|ms| ms as f64 / 1000.0),
+        })
+    }
+}
+
+/// Dump all TCP sockets over `NETLINK_SOCK_DIAG`.
+pub fn collect_sock_diag() -> Result<Vec<TcpActivity>, SocketActivityError> {
+    let socket = SockDiagSocket::open()?;
+    let mut entries = Vec::new();
+    for (seq, family) in [(1u32, libc::AF_INET), (2u32, libc::AF_INET6)] {
+        socket.send_dump(family as u8, seq)?;
+        socket.recv_dump(&mut entries)?;
+    }
+    Ok(entries)
+}
+
+/// Collect TCP activity from `ss -tinHe`.
+pub fn collect_ss() -> Result<Vec<TcpActivity>, SocketActivityError> {
+    let output = run_tool("ss", &["-tinHe"], Some(SS_TIMEOUT), Some(SS_MAX_OUTPUT))
+        .map_err(|e| SocketActivityError::Tool(e.to_string()))?;
+    if !output.success() {
+        return Err(SocketActivityError::Tool(format!(
+            "ss exited with {:?}",
+            output.exit_code
+        )));
+    }
+    Ok(parse_ss_output(&output.stdout_str()))
+}
+
+/// Netlink socket for sock_diag dumps.
+struct SockDiagSocket {
+    fd: libc::c_int,
+}
+
+impl SockDiagSocket {
+    fn open() -> Result<Self, SocketActivityError> {
+        // SAFETY: plain socket(2) call; the fd is owned by the returned value.
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                NETLINK_SOCK_DIAG,
+            )
+        };
+        if fd < 0 {
+            return Err(SocketActivityError::Socket(io::Error::last_os_error()));
+        }
+        let socket = Self { fd };
+
+        let timeout = libc::timeval {
+            tv_sec: 0,
+            tv_usec: RECV_TIMEOUT.as_micros() as libc::suseconds_t,
+        };
+        // SAFETY: timeout is a valid timeval and the length matches.
+        unsafe {
+            libc::setsockopt(
+                socket.fd,
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeout as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            );
+        }
+        Ok(socket)
+    }
+
+    fn send_dump(&self, family: u8, seq: u32) -> Result<(), SocketActivityError> {
+        let msg = build_dump_request(family, seq);
+        // SAFETY: sockaddr_nl is plain data; zeroed addresses the kernel.
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        // SAFETY: msg is a live buffer of msg.len() bytes and addr is a valid sockaddr_nl.
+        let rc = unsafe {
+            libc::sendto(
+                self.fd,
+                msg.as_ptr() as *const libc::c_void,
+                msg.len(),
+                0,
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            return Err(SocketActivityError::Send(io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    fn recv_dump(&self, entries: &mut Vec<TcpActivity>) -> Result<(), SocketActivityError> {
+        let deadline = Instant::now() + DUMP_TIMEOUT;
+        let mut buf = vec![0u8; 32 * 1024];
+        loop {
+            if Instant::now() >= deadline {
+                return Err(SocketActivityError::Recv(io::Error::from(
+                    io::ErrorKind::TimedOut,
+                )));
+            }
+            // SAFETY: buf is a live, writable buffer of buf.len() bytes.
+            let n =
+                unsafe { libc::recv(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+            if n < 0 {
+                let err = io::Error::last_os_error();
+                match err.kind() {
+                    io::ErrorKind::WouldBlock
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::Interrupted => continue,
+                    _ => return Err(SocketActivityError::Recv(err)),
+                }
+            }
+            if parse_dump_datagram(&buf[..n as usize], entries)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl Drop for SockDiagSocket {
+    fn drop(&mut self) {
+        // SAFETY: fd was opened by us and is closed exactly once.
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Build an `inet_diag_req_v2` dump request for all TCP sockets of `family`.
+fn build_dump_request(family: u8, seq: u32) -> Vec<u8> {
+    let total = NLMSG_HDR_LEN + INET_DIAG_REQ_V2_LEN;
+    let mut msg = Vec::with_capacity(total);
+    // nlmsghdr
+    msg.extend_from_slice(&(total as u32).to_ne_bytes());
+    msg.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    msg.extend_from_slice(&NLM_F_REQUEST_DUMP.to_ne_bytes());
+    msg.extend_from_slice(&seq.to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    // inet_diag_req_v2: family, protocol, ext, pad, states
+    msg.push(family);
+    msg.push(libc::IPPROTO_TCP as u8);
+    msg.push(1u8 << (INET_DIAG_INFO - 1));
+    msg.push(0);
+    msg.extend_from_slice(&u32::MAX.to_ne_bytes());
+    // inet_diag_sockid: wildcard
+    msg.resize(total, 0);
+    msg
+}
+
+/// Parse one datagram of a dump reply. Returns `Ok(true)` at `NLMSG_DONE`.
+fn parse_dump_datagram(
+    buf: &[u8],
+    entries: &mut Vec<TcpActivity>,
+) -> Result<bool, SocketActivityError> {
+    let mut offset = 0;
+    while offset + NLMSG_HDR_LEN <= buf.len() {
+        let Some(len) = read_u32(buf, offset).map(|l| l as usize) else {
+            break;
+        };
+        let Some(msg_type) = read_u16(buf, offset + 4) else {
+            break;
+        };
+        if len < NLMSG_HDR_LEN || offset + len > buf.len() {
+            break;
+        }
+        let payload = &buf[offset + NLMSG_HDR_LEN..offset + len];
+        match msg_type {
+            NLMSG_DONE => return Ok(true),
+            NLMSG_ERROR => {
+                let errno = read_u32(payload, 0).map(|e| e as i32).unwrap_or(0);
+                if errno != 0 {
+                    return Err(SocketActivityError::Kernel(io::Error::from_raw_os_error(
+                        -errno,
+                    )));
+                }
+            }
+            SOCK_DIAG_BY_FAMILY => {
+                if let Some(entry) = parse_inet_diag_msg(payload) {
+                    entries.push(entry);
+                }
+            }
+            _ => {}
+        }
+        offset += align4(len);
+    }
+    Ok(false)
+}
+
+/// Decode `inet_diag_msg` and its `INET_DIAG_INFO` attribute.
+fn parse_inet_diag_msg(payload: &[u8]) -> Option<TcpActivity> {
+    if payload.len() < INET_DIAG_MSG_LEN {
+        return None;
+    }
+    let state = TcpState::from_hex(payload[1]);
+    let mut activity = TcpActivity {
+        inode: read_u32(payload, 68)? as u64,
+        state,
+        timer: TcpTimer::from_code(payload[2]),
+        retransmits: payload[3],
+        recv_queue: read_u32(payload, 56)?,
+        send_queue: read_u32(payload, 60)?,
+        last_data_sent_ms: None,
+        last_data_recv_ms: None,
+    };
+
+    let mut offset = INET_DIAG_MSG_LEN;
+    while offset + RTATTR_HDR_LEN <= payload.len() {
+        let rta_len = read_u16(payload, offset)? as usize;
+        let rta_type = read_u16(payload, offset + 2)?;
+        if rta_len < RTATTR_HDR_LEN || offset + rta_len > payload.len() {
+            break;
+        }
+        if rta_type == INET_DIAG_INFO && !state.is_listen() {
+            let info = &payload[offset + RTATTR_HDR_LEN..offset + rta_len];
+            activity.last_data_sent_ms = read_u32(info, TCPI_LAST_DATA_SENT).map(u64::from);
+            activity.last_data_recv_ms = read_u32(info, TCPI_LAST_DATA_RECV).map(u64::from);
+        }
+        offset += align4(rta_len);
+    }
+    Some(activity)
+}
+
+/// Parse `ss -tinHe` output.
+///
+/// Each socket is a header line (`State Recv-Q Send-Q Local Peer ...`
+/// with `timer:(...)` and `ino:` from `-e`) followed by an indented
+/// `tcp_info` line from `-i`.
+pub fn parse_ss_output(output: &str) -> Vec<TcpActivity> {
+    let mut entries: Vec<TcpActivity> = Vec::new();
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let Some(last) = entries.last_mut() else {
+                continue;
+            };
+            if last.state.is_listen() {
+                continue;
+            }
+            // `ss` omits lastsnd/lastrcv when zero.
+            last.last_data_sent_ms = Some(ss_field_ms(line, "lastsnd:").unwrap_or(0));
+            last.last_data_recv_ms = Some(ss_field_ms(line, "lastrcv:").unwrap_or(0));
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        let state = ss_state(parts[0]);
+        let timer = parts
+            .iter()
+            .find_map(|p| p.strip_prefix("timer:("))
+            .and_then(|t| t.split(',').next())
+            .map(TcpTimer::from_ss)
+            .unwrap_or_default();
+        let inode = parts
+            .iter()
+            .find_map(|p| p.strip_prefix("ino:"))
+            .and_then(|i| i.parse().ok())
+            .unwrap_or(0);
+        entries.push(TcpActivity {
+            inode,
+            state,
+            timer,
+            retransmits: 0,
+            recv_queue: parts[1].parse().unwrap_or(0),
+            send_queue: parts[2].parse().unwrap_or(0),
+            last_data_sent_ms: None,
+            last_data_recv_ms: None,
+        });
+    }
+    entries
+}
+
+/// Parse `/proc/net/tcp{,6}` for queue depths, timer and retransmits.
+pub fn parse_proc_net_tcp_activity(content: &str) -> Vec<TcpActivity> {
+    let mut entries = Vec::new();
+    for line in content.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 10 {
+            continue;
+        }
+        // Format: sl local rem st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+        let state = u8::from_str_radix(parts[3], 16)
+            .map(TcpState::from_hex)
+            .unwrap_or(TcpState::Unknown);
+        let (tx, rx) = parts[4].split_once(':').unwrap_or(("0", "0"));
+        let timer = parts[5]
+            .split_once(':')
+            .and_then(|(tr, _)| u8::from_str_radix(tr, 16).ok())
+            .map(TcpTimer::from_code)
+            .unwrap_or_default();
+        entries.push(TcpActivity {
+            inode: parts[9].parse().unwrap_or(0),
+            state,
+            timer,
+            retransmits: u8::from_str_radix(parts[6], 16).unwrap_or(0),
+            recv_queue: u32::from_str_radix(rx, 16).unwrap_or(0),
+            send_queue: u32::from_str_radix(tx, 16).unwrap_or(0),
+            last_data_sent_ms: None,
+            last_data_recv_ms: None,
+        });
+    }
+    entries
+}
+
+fn ss_state(name: &str) -> TcpState {
+    match name {
+        "ESTAB" => TcpState::Established,
+        "SYN-SENT" => TcpState::SynSent,
+        "SYN-RECV" => TcpState::SynRecv,
+        "FIN-WAIT-1" => TcpState::FinWait1,
+        "FIN-WAIT-2" => TcpState::FinWait2,
+        "TIME-WAIT" => TcpState::TimeWait,
+        "UNCONN" => TcpState::Close,
+        "CLOSE-WAIT" => TcpState::CloseWait,
+        "LAST-ACK" => TcpState::LastAck,
+        "LISTEN" => TcpState::Listen,
+        "CLOSING" => TcpState::Closing,
+        _ => TcpState::Unknown,
+    }
+}
+
+fn ss_field_ms(line: &str, key: &str) -> Option<u64> {
+    line.split_whitespace()
+        .find_map(|p| p.strip_prefix(key))
+        .and_then(|v| v.parse().ok())
+}
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    let bytes = buf.get(offset..offset + 2)?;
+    Some(u16::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buf.get(offset..offset + 4)?;
+    Some(u32::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag_reply(
+        state: u8,
+        timer: u8,
+        rq: u32,
+        wq: u32,
+        inode: u32,
+        sent: u32,
+        recv: u32,
+    ) -> Vec<u8> {
+        let mut msg = vec![0u8; INET_DIAG_MSG_LEN];
+        msg[0] = libc::AF_INET as u8;
+        msg[1] = state;
+        msg[2] = timer;
+        msg[56..60].copy_from_slice(&rq.to_ne_bytes());
+        msg[60..64].copy_from_slice(&wq.to_ne_bytes());
+        msg[68..72].copy_from_slice(&inode.to_ne_bytes());
+        let mut info = vec![0u8; 104];
+        info[TCPI_LAST_DATA_SENT..TCPI_LAST_DATA_SENT + 4].copy_from_slice(&sent.to_ne_bytes());
+        info[TCPI_LAST_DATA_RECV..TCPI_LAST_DATA_RECV + 4].copy_from_slice(&recv.to_ne_bytes());
+        msg.extend_from_slice(&((RTATTR_HDR_LEN + info.len()) as u16).to_ne_bytes());
+        msg.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        msg.extend_from_slice(&info);
+
+        let mut datagram = Vec::new();
+        datagram.extend_from_slice(&((NLMSG_HDR_LEN + msg.len()) as u32).to_ne_bytes());
+        datagram.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        datagram.extend_from_slice(&[0u8; 10]);
+        datagram.extend_from_slice(&msg);
+        datagram
+    }
+
+    #[test]
+    fn dump_request_layout() {
+        let req = build_dump_request(libc::AF_INET6 as u8, 7);
+        assert_eq!(req.len(), NLMSG_HDR_LEN + INET_DIAG_REQ_V2_LEN);
+        assert_eq!(read_u32(&req, 0), Some(req.len() as u32));
+        assert_eq!(read_u16(&req, 4), Some(SOCK_DIAG_BY_FAMILY));
+        assert_eq!(read_u32(&req, 8), Some(7));
+        assert_eq!(req[16], libc::AF_INET6 as u8);
+        assert_eq!(req[17], libc::IPPROTO_TCP as u8);
+        assert_eq!(req[18], 0b10);
+        assert_eq!(read_u32(&req, 20), Some(u32::MAX));
+    }
+
+    #[test]
+    fn parse_dump_datagram_reads_tcp_info() {
+        let mut buf = diag_reply(0x01, 2, 0, 0, 4242, 90_000, 120_000);
+        let mut done = Vec::new();
+        done.extend_from_slice(&(NLMSG_HDR_LEN as u32 + 4).to_ne_bytes());
+        done.extend_from_slice(&NLMSG_DONE.to_ne_bytes());
+        done.extend_from_slice(&[0u8; 14]);
+        buf.extend_from_slice(&done);
+
+        let mut entries = Vec::new();
+        assert!(parse_dump_datagram(&buf, &mut entries).unwrap());
+        assert_eq!(entries.len(), 1);
+        let e = &entries[0];
+        assert_eq!(e.inode, 4242);
+        assert_eq!(e.state, TcpState::Established);
+        assert_eq!(e.timer, TcpTimer::Keepalive);
+        assert_eq!(e.last_data_sent_ms, Some(90_000));
+        assert_eq!(e.idle_ms(), Some(90_000));
+    }
+
+    #[test]
+    fn parse_dump_datagram_surfaces_kernel_error() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(NLMSG_HDR_LEN as u32 + 4).to_ne_bytes());
+        buf.extend_from_slice(&NLMSG_ERROR.to_ne_bytes());
+        buf.extend_from_slice(&[0u8; 10]);
+        buf.extend_from_slice(&(-libc::EPERM).to_ne_bytes());
+        let err = parse_dump_datagram(&buf, &mut Vec::new()).unwrap_err();
+        assert!(matches!(err, SocketActivityError::Kernel(_)));
+    }
+
+    #[test]
+    fn listener_ignores_tcp_info_timestamps() {
+        let buf = diag_reply(0x0A, 0, 0, 128, 7, 5, 5);
+        let mut entries = Vec::new();
+        parse_dump_datagram(&buf, &mut entries).unwrap();
+        assert_eq!(entries[0].last_data_recv_ms, None);
+        assert_eq!(entries[0].idle_ms(), None);
+    }
+
+    #[test]
+    fn parse_ss_output_basic() {
+        let output = "\
+ESTAB 0      0      10.0.0.5:22    10.0.0.9:51234 timer:(keepalive,119min,0) ino:1001 sk:1 <->
+\t cubic wscale:7,7 rto:204 rtt:1.5/0.75 lastsnd:3600000 lastrcv:3500000 lastack:10
+LISTEN 3      128    0.0.0.0:8080   0.0.0.0:* ino:1002 sk:2 <->
+\t cubic rto:1000
+ESTAB 0      512    10.0.0.5:40000 10.0.0.10:443 timer:(on,200ms,0) ino:1003 sk:3 <->
+\t cubic lastrcv:42
+";
+        let entries = parse_ss_output(output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].idle_ms(), Some(3_500_000));
+        assert_eq!(entries[0].timer, TcpTimer::Keepalive);
+        assert!(entries[1].state.is_listen());
+        assert_eq!(entries[1].idle_ms(), Some(0));
+        assert_eq!(entries[2].timer, TcpTimer::Retransmit);
+        assert_eq!(entries[2].last_data_sent_ms, Some(0));
+        assert_eq!(entries[2].idle_ms(), Some(0));
+    }
+
+    #[test]
+    fn parse_proc_net_tcp_activity_queues_only() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 2001 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:C350 01 00000010:00000000 01:00000014 00000002  1000        0 2002 1 0000000000000000 20 4 30 10 -1
+   2: 0100007F:1F90 0100007F:C351 01 00000000:00000000 02:000AFC80 00000000  1000        0 2003 1 0000000000000000 20 4 30 10 -1
+";
+        let entries = parse_proc_net_tcp_activity(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].idle_ms(), None);
+        assert_eq!(entries[1].send_queue, 16);
+        assert_eq!(entries[1].retransmits, 2);
+        assert_eq!(entries[1].idle_ms(), Some(0));
+        assert_eq!(entries[2].timer, TcpTimer::Keepalive);
+        assert_eq!(entries[2].idle_ms(), None);
+    }
+
+    #[test]
+    fn activity_for_inodes_takes_most_recent_socket() {
+        let sock = |inode, state, recv_ms| TcpActivity {
+            inode,
+            state,
+            timer: TcpTimer::None,
+            retransmits: 0,
+            recv_queue: 0,
+            send_queue: 0,
+            last_data_sent_ms: recv_ms,
+            last_data_recv_ms: recv_ms,
+        };
+        let snapshot = SocketActivitySnapshot::from_entries(
+            ActivitySource::SockDiag,
+            vec![
+                sock(1, TcpState::Listen, None),
+                sock(2, TcpState::Established, Some(86_400_000)),
+                sock(3, TcpState::Established, Some(5_000)),
+                sock(4, TcpState::Established, Some(1)),
+            ],
+        );
+        let inodes: HashSet<u64> = [1, 2, 3, 99].into_iter().collect();
+        let activity = snapshot.activity_for_inodes(&inodes).expect("tcp sockets");
+        assert_eq!(activity.tcp_sockets, 3);
+        assert_eq!(activity.listeners, 1);
+        assert_eq!(activity.queued_sockets, 0);
+        assert_eq!(activity.idle_secs, Some(5.0));
+
+        let none: HashSet<u64> = [99].into_iter().collect();
+        assert!(snapshot.activity_for_inodes(&none).is_none());
+    }
+}
@@ -0,0 +1,219 @@
+//! Swap and memory-pressure signals (Linux).
+//!
+//! Large RSS alone says little about who is hurting the host: a big process
+//! whose working set fits is harmless, while a smaller one that keeps getting
+//! swapped out and faulted back in drives thrash. This module reads the host
+//! side of that picture once per run and each process's swapped-out memory:
+//!
+//! - `/proc/pressure/memory`: PSI `some` / `full` avg10 (percent of time
+//!   tasks stalled on memory)
+//! - `/proc/meminfo`: `SwapTotal` / `SwapFree`
+//! - `/sys/block/zram*/mm_stat`: compressed-in-RAM swap, whose pages still
+//!   cost memory at their compressed size
+//! - `/proc/[pid]/status`: `VmSwap`
+//!
+//! Missing files (no PSI, no swap, no zram) yield `None` fields rather than
+//! errors.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Aggregate statistics over all zram devices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZramStats {
+    /// Number of initialized zram devices.
+    pub devices: usize,
+    /// Uncompressed size of the data stored.
+    pub orig_data_bytes: u64,
+    /// Compressed size of the data stored.
+    pub compr_data_bytes: u64,
+    /// Memory consumed by the devices, including allocator overhead.
+    pub mem_used_bytes: u64,
+}
+
+impl ZramStats {
+    /// Uncompressed over compressed size; `None` while empty.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        if self.compr_data_bytes == 0 {
+            return None;
+        }
+        Some(self.orig_data_bytes as f64 / self.compr_data_bytes as f64)
+    }
+}
+
+/// Host memory-pressure snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SwapPressureSnapshot {
+    /// PSI memory `some` avg10, in percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psi_some_avg10: Option<f64>,
+    /// PSI memory `full` avg10, in percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psi_full_avg10: Option<f64>,
+    pub swap_total_bytes: u64,
+    pub swap_free_bytes: u64,
+    /// zram devices backing (part of) swap.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zram: Option<ZramStats>,
+}
+
+impl SwapPressureSnapshot {
+    /// Read the current host signals.
+    pub fn collect() -> Self {
+        let (psi_some_avg10, psi_full_avg10) = fs::read_to_string("/proc/pressure/memory")
+            .map(|content| parse_psi_memory(&content))
+            .unwrap_or((None, None));
+        let (swap_total_bytes, swap_free_bytes) = fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|content| parse_meminfo_swap(&content))
+            .unwrap_or((0, 0));
+        Self {
+            psi_some_avg10,
+            psi_full_avg10,
+            swap_total_bytes,
+            swap_free_bytes,
+            zram: collect_zram(Path::new("/sys/block")),
+        }
+    }
+
+    /// Swap in use, in bytes.
+    pub fn swap_used_bytes(&self) -> u64 {
+        self.swap_total_bytes.saturating_sub(self.swap_free_bytes)
+    }
+
+    /// Fraction of swap in use; `None` without swap.
+    pub fn swap_used_fraction(&self) -> Option<f64> {
+        if self.swap_total_bytes == 0 {
+            return None;
+        }
+        Some(self.swap_used_bytes() as f64 / self.swap_total_bytes as f64)
+    }
+}
+
+/// Swapped-out memory of a process in bytes (`VmSwap`).
+///
+/// Kernel threads and exited processes have no `VmSwap` line and return `None`.
+pub fn process_swap_bytes(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    parse_vm_swap_content(&status)
+}
+
+/// Parse `VmSwap` from `/proc/[pid]/status` content, in bytes.
+pub fn parse_vm_swap_content(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmSwap:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Parse `some` and `full` avg10 from `/proc/pressure/memory` content.
+pub fn parse_psi_memory(content: &str) -> (Option<f64>, Option<f64>) {
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find(|l| l.split_whitespace().next() == Some(kind))?
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix("avg10="))
+            .and_then(|v| v.parse().ok())
+    };
+    (avg10("some"), avg10("full"))
+}
+
+/// Parse `(SwapTotal, SwapFree)` in bytes from `/proc/meminfo` content.
+pub fn parse_meminfo_swap(content: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| -> Option<u64> {
+        let line = content.lines().find(|l| l.starts_with(name))?;
+        let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kb * 1024)
+    };
+    Some((field("SwapTotal:")?, field("SwapFree:")?))
+}
+
+/// Parse a zram device's `mm_stat` into a single-device `ZramStats`.
+///
+/// Columns: orig_data_size compr_data_size mem_used_total mem_limit
+/// mem_used_max same_pages pages_compacted [huge_pages ...].
+pub fn parse_zram_mm_stat(content: &str) -> Option<ZramStats> {
+    let mut fields = content.split_whitespace().map(|f| f.parse::<u64>().ok());
+    Some(ZramStats {
+        devices: 1,
+        orig_data_bytes: fields.next()??,
+        compr_data_bytes: fields.next()??,
+        mem_used_bytes: fields.next()??,
+    })
+}
+
+fn collect_zram(sys_block: &Path) -> Option<ZramStats> {
+    let mut total: Option<ZramStats> = None;
+    for entry in fs::read_dir(sys_block).ok()?.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with("zram") {
+            continue;
+        }
+        let Some(stats) = fs::read_to_string(entry.path().join("mm_stat"))
+            .ok()
+            .and_then(|content| parse_zram_mm_stat(&content))
+        else {
+            continue;
+        };
+        let acc = total.get_or_insert_with(ZramStats::default);
+        acc.devices += 1;
+        acc.orig_data_bytes += stats.orig_data_bytes;
+        acc.compr_data_bytes += stats.compr_data_bytes;
+        acc.mem_used_bytes += stats.mem_used_bytes;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_vm_swap() {
+        let status = "Name:\tnode\nVmRSS:\t  204800 kB\nVmSwap:\t  524288 kB\nThreads:\t12\n";
+        assert_eq!(parse_vm_swap_content(status), Some(512 * 1024 * 1024));
+        assert_eq!(parse_vm_swap_content("Name:\tkthreadd\n"), None);
+    }
+
+    #[test]
+    fn parses_psi_and_meminfo() {
+        let psi = "some avg10=31.50 avg60=12.00 avg300=3.00 total=123\nfull avg10=8.25 avg60=2.00 avg300=0.50 total=45\n";
+        assert_eq!(parse_psi_memory(psi), (Some(31.5), Some(8.25)));
+        assert_eq!(parse_psi_memory(""), (None, None));
+
+        let meminfo = "MemTotal:       16384000 kB\nSwapTotal:       8388608 kB\nSwapFree:        2097152 kB\n";
+        let (total, free) = parse_meminfo_swap(meminfo).unwrap();
+        assert_eq!(total, 8 * 1024 * 1024 * 1024);
+        assert_eq!(free, 2 * 1024 * 1024 * 1024);
+
+        let snapshot = SwapPressureSnapshot {
+            swap_total_bytes: total,
+            swap_free_bytes: free,
+            ..Default::default()
+        };
+        assert_eq!(snapshot.swap_used_fraction(), Some(0.75));
+        assert_eq!(SwapPressureSnapshot::default().swap_used_fraction(), None);
+    }
+
+    #[test]
+    fn sums_zram_devices() {
+        let dir = tempdir().unwrap();
+        for (name, stat) in [
+            ("zram0", "4096000 1024000 1100000 0 1200000 10 0 0\n"),
+            ("zram1", "2048000 1024000 1050000 0 1050000 0 0 0\n"),
+            ("sda", "garbage\n"),
+        ] {
+            std::fs::create_dir(dir.path().join(name)).unwrap();
+            std::fs::write(dir.path().join(name).join("mm_stat"), stat).unwrap();
+        }
+        let zram = collect_zram(dir.path()).unwrap();
+        assert_eq!(zram.devices, 2);
+        assert_eq!(zram.orig_data_bytes, 6_144_000);
+        assert_eq!(zram.mem_used_bytes, 2_150_000);
+        assert_eq!(zram.compression_ratio(), Some(3.0));
+
+        let empty = tempdir().unwrap();
+        assert_eq!(collect_zram(empty.path()), None);
+    }
+}
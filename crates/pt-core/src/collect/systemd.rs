@@ -9,6 +9,7 @@
 //! - `systemctl show --property=... <pid>` - structured unit info
 //! - Cgroup path parsing (fallback when systemctl unavailable)
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
@@ -194,8 +195,25 @@ pub fn collect_systemd_unit(pid: u32, cgroup_unit: Option<&str>) -> Option<Syste
     None
 }
 
+/// Collect unit information from the manager that owns the unit.
+///
+/// Units of a user's manager are invisible to the system instance, so
+/// `systemctl show` is pointed at that manager (see
+/// [`SystemdManager::systemctl_args`]).
+pub fn collect_systemd_unit_in(
+    pid: u32,
+    unit_name: &str,
+    manager: SystemdManager,
+) -> Option<SystemdUnit> {
+    query_systemctl_in(&manager.systemctl_args(caller_uid()), unit_name, pid)
+}
+
 /// Query systemctl for unit information.
 fn query_systemctl(unit_name: &str, pid: u32) -> Option<SystemdUnit> {
+    query_systemctl_in(&[], unit_name, pid)
+}
+
+fn query_systemctl_in(manager_args: &[String], unit_name: &str, pid: u32) -> Option<SystemdUnit> {
     // Properties to query
     let properties = [
         "Id",
@@ -209,6 +227,7 @@ fn query_systemctl(unit_name: &str, pid: u32) -> Option<SystemdUnit> {
 
     let property_arg = properties.join(",");
     let output = Command::new("systemctl")
+        .args(manager_args)
         .args(["show", "--property", &property_arg, unit_name])
         .output()
         .ok()?;
@@ -221,6 +240,100 @@ fn query_systemctl(unit_name: &str, pid: u32) -> Option<SystemdUnit> {
     parse_systemctl_output(&stdout, pid)
 }
 
+/// The systemd instance that manages a unit.
+///
+/// Units below `user@<uid>.service` belong to that user's manager and are
+/// only reachable with `systemctl --user`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "scope", rename_all = "snake_case")]
+pub enum SystemdManager {
+    /// The system instance (PID 1).
+    System,
+    /// The per-user instance of `uid`.
+    User { uid: u32 },
+}
+
+impl SystemdManager {
+    /// `systemctl` arguments that address this manager from a process
+    /// running as `caller_uid`.
+    ///
+    /// Another user's manager is reached with `--machine=<uid>@.host`, which
+    /// only root may do.
+    pub fn systemctl_args(&self, caller_uid: u32) -> Vec<String> {
+        match self {
+            SystemdManager::System => Vec::new(),
+            SystemdManager::User { uid } if *uid == caller_uid => vec!["--user".to_string()],
+            SystemdManager::User { uid } => {
+                vec!["--user".to_string(), format!("--machine={}@.host", uid)]
+            }
+        }
+    }
+}
+
+/// Innermost service or scope in a cgroup path, with the manager that owns it.
+///
+/// ```text
+/// /system.slice/nginx.service                        -> nginx.service (system)
+/// /user.slice/user-1000.slice/session-2.scope        -> session-2.scope (system)
+/// /user.slice/user-1000.slice/user@1000.service/
+///     app.slice/syncthing.service                    -> syncthing.service (user 1000)
+/// ```
+pub fn unit_of_cgroup_path(path: &str) -> Option<(String, SystemdManager)> {
+    let mut unit = None;
+    let mut user_manager = None;
+
+    for part in path.split('/').filter(|s| !s.is_empty()) {
+        if !(part.ends_with(".service") || part.ends_with(".scope")) {
+            continue;
+        }
+        let manager = match user_manager {
+            Some(uid) => SystemdManager::User { uid },
+            None => SystemdManager::System,
+        };
+        unit = Some((part.to_string(), manager));
+        // Units below user@<uid>.service are that user's
+        if user_manager.is_none() {
+            user_manager = user_manager_uid(part);
+        }
+    }
+
+    unit
+}
+
+/// Unit and manager of a live process, from `/proc/<pid>/cgroup`.
+#[cfg(target_os = "linux")]
+pub fn unit_of_pid(pid: u32) -> Option<(String, SystemdManager)> {
+    let content = std::fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+    content
+        .lines()
+        .find_map(|line| unit_of_cgroup_path(line.splitn(3, ':').nth(2)?))
+}
+
+/// Unit and manager of a live process (not available on this platform).
+#[cfg(not(target_os = "linux"))]
+pub fn unit_of_pid(_pid: u32) -> Option<(String, SystemdManager)> {
+    None
+}
+
+/// UID of the user manager owning a cgroup path (`.../user@1000.service/...`)
+/// or unit name (`user@1000.service`).
+pub fn user_manager_uid(cgroup_path: &str) -> Option<u32> {
+    cgroup_path
+        .split('/')
+        .find_map(|part| part.strip_prefix("user@")?.strip_suffix(".service"))
+        .and_then(|uid| uid.parse().ok())
+}
+
+#[cfg(unix)]
+fn caller_uid() -> u32 {
+    unsafe { libc::geteuid() }
+}
+
+#[cfg(not(unix))]
+fn caller_uid() -> u32 {
+    u32::MAX
+}
+
 /// Parse systemctl show output.
 pub fn parse_systemctl_output(output: &str, pid: u32) -> Option<SystemdUnit> {
     let props = parse_properties(output);
@@ -394,6 +507,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_user_manager_uid() {
+        assert_eq!(user_manager_uid("user@1000.service"), Some(1000));
+        assert_eq!(
+            user_manager_uid("/user.slice/user-1000.slice/user@1000.service/app.slice/x.scope"),
+            Some(1000)
+        );
+        assert_eq!(user_manager_uid("/system.slice/nginx.service"), None);
+    }
+
+    #[test]
+    fn test_unit_of_cgroup_path_manager() {
+        assert_eq!(
+            unit_of_cgroup_path("/system.slice/nginx.service"),
+            Some(("nginx.service".to_string(), SystemdManager::System))
+        );
+        assert_eq!(
+            unit_of_cgroup_path("/user.slice/user-1000.slice/session-2.scope"),
+            Some(("session-2.scope".to_string(), SystemdManager::System))
+        );
+        assert_eq!(
+            unit_of_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/syncthing.service"
+            ),
+            Some((
+                "syncthing.service".to_string(),
+                SystemdManager::User { uid: 1000 }
+            ))
+        );
+        // The user manager itself is a system unit
+        assert_eq!(
+            unit_of_cgroup_path("/user.slice/user-1000.slice/user@1000.service"),
+            Some(("user@1000.service".to_string(), SystemdManager::System))
+        );
+        assert_eq!(unit_of_cgroup_path("/user.slice/user-1000.slice"), None);
+
+        let user = SystemdManager::User { uid: 1000 };
+        assert!(SystemdManager::System.systemctl_args(0).is_empty());
+        assert_eq!(user.systemctl_args(1000), vec!["--user"]);
+        assert_eq!(
+            user.systemctl_args(0),
+            vec!["--user", "--machine=1000@.host"]
+        );
+    }
+
     #[test]
     fn test_active_state_is_running() {
         assert!(SystemdActiveState::Active.is_running());
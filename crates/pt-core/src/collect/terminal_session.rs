@@ -0,0 +1,495 @@
+//! Attribution of processes to tmux/screen sessions and SSH logins (Linux).
+//!
+//! A process in a tmux pane that someone still has attached, or in the shell
+//! of a live SSH login, belongs to a human who may be watching it. Whether the
+//! session is attached, and how long ago its client last sent input, says more
+//! about that than the process's own activity.
+//!
+//! Attribution starts from the process environment: `TMUX` (socket, server
+//! PID, session id), `STY` for screen, and `SSH_CONNECTION`/`SSH_TTY`. Each
+//! tmux server is asked for its clients (`list-clients`) once per snapshot;
+//! screen marks attached sessions with the owner-execute bit on the session
+//! socket, which is what `screen -ls` reads. Client idle time is tmux's
+//! `client_activity`, or else the atime of the SSH login's terminal device.
+//!
+//! A pane inherits the environment of the shell that started the session, so
+//! its SSH variables may name a login that has since ended. Such a login only
+//! counts while its terminal device still exists.
+
+use super::foreground::tty_idle_secs;
+use crate::supervision::read_environ;
+use crate::supervision::session::SshConnectionInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default client input recency, in seconds, for an attached session to
+/// count as in use.
+pub const DEFAULT_SESSION_IDLE_SECS: u64 = 900;
+
+/// `tmux list-clients` format parsed by [`parse_tmux_clients`].
+pub const TMUX_CLIENT_FORMAT: &str = "#{client_tty} #{client_activity} #{session_id}";
+
+/// Terminal multiplexer hosting a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiplexerKind {
+    Tmux,
+    Screen,
+}
+
+impl MultiplexerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tmux => "tmux",
+            Self::Screen => "screen",
+        }
+    }
+}
+
+/// tmux/screen session a process runs in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiplexerSession {
+    pub kind: MultiplexerKind,
+    /// tmux session id (e.g. `$3`) or screen `STY` (e.g. `4242.work`).
+    pub session: String,
+    /// Attached clients; screen only tells attached from detached, so it
+    /// reports 0 or 1. `None` when the server could not be queried.
+    pub attached_clients: Option<usize>,
+    /// Seconds since the most recently active attached client sent input.
+    pub client_idle_secs: Option<u64>,
+}
+
+/// SSH login a process was started from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshLogin {
+    pub client_ip: String,
+    pub client_port: u16,
+    /// Login terminal (`SSH_TTY`); `None` for commands run without a tty.
+    pub tty: Option<String>,
+    /// Seconds since the login terminal last read input; `None` when the
+    /// terminal is gone or unreadable.
+    pub idle_secs: Option<u64>,
+}
+
+/// Terminal sessions a process belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionAttribution {
+    pub multiplexer: Option<MultiplexerSession>,
+    pub ssh: Option<SshLogin>,
+}
+
+impl SessionAttribution {
+    /// Whether someone is connected: a client attached to the multiplexer
+    /// session or, outside one, a live SSH login terminal. `None` when unknown.
+    pub fn attached(&self) -> Option<bool> {
+        match &self.multiplexer {
+            Some(mux) => mux.attached_clients.map(|clients| clients > 0),
+            None => self
+                .ssh
+                .as_ref()
+                .and_then(|ssh| ssh.tty.as_ref().map(|_| ssh.idle_secs.is_some())),
+        }
+    }
+
+    /// Seconds since the attached client last sent input.
+    ///
+    /// Falls back to the SSH login terminal when the multiplexer does not
+    /// report client activity (screen).
+    pub fn client_idle_secs(&self) -> Option<u64> {
+        if self.attached() != Some(true) {
+            return None;
+        }
+        let ssh_idle = self.ssh.as_ref().and_then(|ssh| ssh.idle_secs);
+        self.multiplexer
+            .as_ref()
+            .and_then(|mux| mux.client_idle_secs)
+            .or(ssh_idle)
+    }
+
+    /// Whether an attached client sent input within `max_idle_secs`.
+    pub fn is_attached_active(&self, max_idle_secs: u64) -> bool {
+        self.client_idle_secs()
+            .is_some_and(|idle| idle <= max_idle_secs)
+    }
+
+    /// One-line description for explanations and rationales.
+    pub fn summary(&self) -> String {
+        let idle = self
+            .client_idle_secs()
+            .map(|idle| format!(" (client idle {}s)", idle))
+            .unwrap_or_default();
+        match (&self.multiplexer, &self.ssh) {
+            (Some(mux), _) => {
+                let state = match mux.attached_clients {
+                    Some(0) => "detached",
+                    Some(_) => "attached",
+                    None => "with unknown attachment",
+                };
+                format!(
+                    "{} session {} {}{}",
+                    mux.kind.as_str(),
+                    mux.session,
+                    state,
+                    idle
+                )
+            }
+            (None, Some(ssh)) => match &ssh.tty {
+                Some(tty) => format!("SSH login from {} on {}{}", ssh.client_ip, tty, idle),
+                None => format!("SSH command from {}", ssh.client_ip),
+            },
+            (None, None) => "no terminal session".to_string(),
+        }
+    }
+}
+
+/// A client attached to a tmux server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxClient {
+    pub tty: String,
+    /// Unix time of the client's last input.
+    pub activity: u64,
+    /// Session the client is attached to (e.g. `$3`).
+    pub session_id: String,
+}
+
+/// Lookups attribution needs beyond the process environment.
+pub trait SessionProbe {
+    /// Clients of the tmux server at `socket`; `None` when it cannot be queried.
+    fn tmux_clients(&mut self, socket: &str) -> Option<Vec<TmuxClient>>;
+
+    /// Whether the screen session socket at `socket` is attached.
+    fn screen_attached(&mut self, socket: &Path) -> Option<bool>;
+
+    /// Seconds since the terminal device `tty` last read input.
+    fn tty_idle_secs(&mut self, tty: &Path) -> Option<u64>;
+
+    /// Current Unix time in seconds.
+    fn now_unix(&self) -> u64;
+}
+
+/// Session attribution for a set of processes.
+#[derive(Debug, Default)]
+pub struct TerminalSessionSnapshot {
+    sessions: HashMap<u32, SessionAttribution>,
+}
+
+impl TerminalSessionSnapshot {
+    /// Attribute `pids` from their environments. Processes whose environment
+    /// is unreadable or names no session are left out.
+    pub fn collect(pids: &[u32]) -> Self {
+        let mut probe = LiveProbe::default();
+        let sessions = pids
+            .iter()
+            .filter_map(|&pid| {
+                let env = read_environ(pid).ok()?;
+                attribute_environ(&env, &mut probe).map(|session| (pid, session))
+            })
+            .collect();
+        Self { sessions }
+    }
+
+    /// Sessions of `pid`, if it was attributed.
+    pub fn get(&self, pid: u32) -> Option<&SessionAttribution> {
+        self.sessions.get(&pid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+/// Attribute a process from its environment.
+pub fn attribute_environ(
+    env: &HashMap<String, String>,
+    probe: &mut impl SessionProbe,
+) -> Option<SessionAttribution> {
+    let multiplexer = tmux_session(env, probe).or_else(|| screen_session(env, probe));
+    let ssh = ssh_login(env, probe);
+    (multiplexer.is_some() || ssh.is_some()).then_some(SessionAttribution { multiplexer, ssh })
+}
+
+/// Parse `tmux list-clients -F` output in [`TMUX_CLIENT_FORMAT`].
+pub fn parse_tmux_clients(output: &str) -> Vec<TmuxClient> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(TmuxClient {
+                tty: fields.next()?.to_string(),
+                activity: fields.next()?.parse().ok()?,
+                session_id: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn tmux_session(
+    env: &HashMap<String, String>,
+    probe: &mut impl SessionProbe,
+) -> Option<MultiplexerSession> {
+    // TMUX is "socket,server_pid,session_id".
+    let mut parts = env.get("TMUX")?.split(',');
+    let socket = parts.next().filter(|socket| !socket.is_empty())?;
+    let session = format!("${}", parts.nth(1)?);
+    let now = probe.now_unix();
+    let attached: Option<Vec<TmuxClient>> = probe.tmux_clients(socket).map(|clients| {
+        clients
+            .into_iter()
+            .filter(|client| client.session_id == session)
+            .collect()
+    });
+    Some(MultiplexerSession {
+        kind: MultiplexerKind::Tmux,
+        client_idle_secs: attached.as_ref().and_then(|clients| {
+            clients
+                .iter()
+                .map(|client| now.saturating_sub(client.activity))
+                .min()
+        }),
+        attached_clients: attached.map(|clients| clients.len()),
+        session,
+    })
+}
+
+fn screen_session(
+    env: &HashMap<String, String>,
+    probe: &mut impl SessionProbe,
+) -> Option<MultiplexerSession> {
+    let sty = env.get("STY").filter(|sty| !sty.is_empty())?;
+    let dir = env.get("SCREENDIR").map(PathBuf::from).or_else(|| {
+        env.get("USER")
+            .or_else(|| env.get("LOGNAME"))
+            .map(|user| PathBuf::from(format!("/run/screen/S-{}", user)))
+    });
+    let attached = dir.and_then(|dir| probe.screen_attached(&dir.join(sty)));
+    Some(MultiplexerSession {
+        kind: MultiplexerKind::Screen,
+        session: sty.clone(),
+        attached_clients: attached.map(usize::from),
+        client_idle_secs: None,
+    })
+}
+
+fn ssh_login(env: &HashMap<String, String>, probe: &mut impl SessionProbe) -> Option<SshLogin> {
+    let info = env
+        .get("SSH_CONNECTION")
+        .and_then(|value| SshConnectionInfo::from_ssh_connection(value))
+        .or_else(|| {
+            env.get("SSH_CLIENT")
+                .and_then(|value| SshConnectionInfo::from_ssh_client(value))
+        })?;
+    let tty = env.get("SSH_TTY").filter(|tty| !tty.is_empty()).cloned();
+    let idle_secs = tty
+        .as_ref()
+        .and_then(|tty| probe.tty_idle_secs(Path::new(tty)));
+    Some(SshLogin {
+        client_ip: info.client_ip,
+        client_port: info.client_port,
+        tty,
+        idle_secs,
+    })
+}
+
+/// Probe of the live system, querying each tmux server once.
+#[derive(Debug, Default)]
+struct LiveProbe {
+    tmux: HashMap<String, Option<Vec<TmuxClient>>>,
+}
+
+impl SessionProbe for LiveProbe {
+    fn tmux_clients(&mut self, socket: &str) -> Option<Vec<TmuxClient>> {
+        self.tmux
+            .entry(socket.to_string())
+            .or_insert_with(|| query_tmux_clients(socket))
+            .clone()
+    }
+
+    fn screen_attached(&mut self, socket: &Path) -> Option<bool> {
+        let mode = fs::metadata(socket).ok()?.permissions().mode();
+        Some(mode & 0o100 != 0)
+    }
+
+    fn tty_idle_secs(&mut self, tty: &Path) -> Option<u64> {
+        tty_idle_secs(tty)
+    }
+
+    fn now_unix(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs())
+    }
+}
+
+fn query_tmux_clients(socket: &str) -> Option<Vec<TmuxClient>> {
+    let output = Command::new("tmux")
+        .args(["-S", socket, "list-clients", "-F", TMUX_CLIENT_FORMAT])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| parse_tmux_clients(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_800_000_000;
+
+    #[derive(Default)]
+    struct FakeProbe {
+        tmux: Option<Vec<TmuxClient>>,
+        screen_attached: Option<bool>,
+        tty_idle: Option<u64>,
+        tmux_queries: usize,
+    }
+
+    impl SessionProbe for FakeProbe {
+        fn tmux_clients(&mut self, socket: &str) -> Option<Vec<TmuxClient>> {
+            assert_eq!(socket, "/tmp/tmux-1000/default");
+            self.tmux_queries += 1;
+            self.tmux.clone()
+        }
+
+        fn screen_attached(&mut self, socket: &Path) -> Option<bool> {
+            assert_eq!(socket, Path::new("/run/screen/S-alice/4242.work"));
+            self.screen_attached
+        }
+
+        fn tty_idle_secs(&mut self, tty: &Path) -> Option<u64> {
+            assert_eq!(tty, Path::new("/dev/pts/2"));
+            self.tty_idle
+        }
+
+        fn now_unix(&self) -> u64 {
+            NOW
+        }
+    }
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_tmux_clients() {
+        let clients =
+            parse_tmux_clients("/dev/pts/4 1799999990 $3\ngarbage\n/dev/pts/7 1799990000 $0\n");
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].tty, "/dev/pts/4");
+        assert_eq!(clients[0].activity, 1_799_999_990);
+        assert_eq!(clients[1].session_id, "$0");
+        assert!(parse_tmux_clients("").is_empty());
+    }
+
+    #[test]
+    fn attributes_tmux_panes() {
+        let pane = env(&[("TMUX", "/tmp/tmux-1000/default,1234,3")]);
+        let mut probe = FakeProbe {
+            tmux: Some(parse_tmux_clients(
+                "/dev/pts/4 1799999990 $3\n/dev/pts/5 1799999000 $3\n/dev/pts/7 1799999999 $0\n",
+            )),
+            ..Default::default()
+        };
+        let attached = attribute_environ(&pane, &mut probe).unwrap();
+        let mux = attached.multiplexer.as_ref().unwrap();
+        assert_eq!(mux.session, "$3");
+        assert_eq!(mux.attached_clients, Some(2));
+        assert_eq!(attached.client_idle_secs(), Some(10));
+        assert!(attached.is_attached_active(DEFAULT_SESSION_IDLE_SECS));
+        assert!(!attached.is_attached_active(5));
+        assert_eq!(
+            attached.summary(),
+            "tmux session $3 attached (client idle 10s)"
+        );
+
+        let mut probe = FakeProbe {
+            tmux: Some(parse_tmux_clients("/dev/pts/7 1799999999 $0\n")),
+            ..Default::default()
+        };
+        let detached = attribute_environ(&pane, &mut probe).unwrap();
+        assert_eq!(detached.attached(), Some(false));
+        assert_eq!(detached.client_idle_secs(), None);
+        assert_eq!(detached.summary(), "tmux session $3 detached");
+
+        let mut probe = FakeProbe::default();
+        let unknown = attribute_environ(&pane, &mut probe).unwrap();
+        assert_eq!(unknown.attached(), None);
+        assert_eq!(probe.tmux_queries, 1);
+    }
+
+    #[test]
+    fn attributes_screen_with_ssh_idle_fallback() {
+        let window = env(&[
+            ("STY", "4242.work"),
+            ("USER", "alice"),
+            ("SSH_CONNECTION", "10.0.0.5 50022 10.0.0.1 22"),
+            ("SSH_TTY", "/dev/pts/2"),
+        ]);
+        let mut probe = FakeProbe {
+            screen_attached: Some(true),
+            tty_idle: Some(40),
+            ..Default::default()
+        };
+        let session = attribute_environ(&window, &mut probe).unwrap();
+        assert_eq!(
+            session.multiplexer.as_ref().unwrap().attached_clients,
+            Some(1)
+        );
+        assert_eq!(session.ssh.as_ref().unwrap().client_ip, "10.0.0.5");
+        assert_eq!(session.client_idle_secs(), Some(40));
+        assert!(session.is_attached_active(DEFAULT_SESSION_IDLE_SECS));
+
+        probe.screen_attached = Some(false);
+        let detached = attribute_environ(&window, &mut probe).unwrap();
+        assert_eq!(detached.attached(), Some(false));
+        assert!(!detached.is_attached_active(DEFAULT_SESSION_IDLE_SECS));
+    }
+
+    #[test]
+    fn attributes_ssh_logins() {
+        let login = env(&[
+            ("SSH_CLIENT", "10.0.0.5 50022 22"),
+            ("SSH_TTY", "/dev/pts/2"),
+        ]);
+        let mut probe = FakeProbe {
+            tty_idle: Some(3_600),
+            ..Default::default()
+        };
+        let idle = attribute_environ(&login, &mut probe).unwrap();
+        assert_eq!(idle.attached(), Some(true));
+        assert!(!idle.is_attached_active(DEFAULT_SESSION_IDLE_SECS));
+        assert_eq!(
+            idle.summary(),
+            "SSH login from 10.0.0.5 on /dev/pts/2 (client idle 3600s)"
+        );
+
+        // The login terminal is gone: the session ended.
+        probe.tty_idle = None;
+        let ended = attribute_environ(&login, &mut probe).unwrap();
+        assert_eq!(ended.attached(), Some(false));
+
+        let command = env(&[("SSH_CLIENT", "10.0.0.5 50022 22")]);
+        let command = attribute_environ(&command, &mut probe).unwrap();
+        assert_eq!(command.attached(), None);
+        assert_eq!(command.summary(), "SSH command from 10.0.0.5");
+
+        assert_eq!(
+            attribute_environ(&env(&[("HOME", "/home/alice")]), &mut probe),
+            None
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Per-thread CPU sampling from `/proc/[pid]/task` (Linux).
+//!
+//! A process at 100% CPU may be a healthy multi-threaded app with one runaway
+//! thread (a stuck render loop, a spinning worker) rather than a process that
+//! should die. Sampling `utime + stime` of every task twice, a short window
+//! apart, shows how the process's CPU splits across its threads.
+//!
+//! The sampler takes the first sample up front; each process's second sample
+//! is read when its summary is requested, so callers can do other work while
+//! the window elapses.
+
+use super::tick_delta::clk_tck;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Default minimum time between the two samples.
+pub const DEFAULT_THREAD_CPU_WINDOW: Duration = Duration::from_millis(250);
+
+/// Threads kept in a summary, busiest first.
+pub const TOP_THREADS: usize = 5;
+
+/// CPU use of one thread over the sample window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadCpu {
+    /// Thread ID.
+    pub tid: u32,
+    /// Thread name (`comm` of the task).
+    pub name: String,
+    /// CPU use in percent of one core.
+    pub cpu_percent: f64,
+    /// Fraction of the process's CPU use over the window.
+    pub share: f64,
+}
+
+/// How a process's CPU use splits across its threads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadCpuSummary {
+    /// Threads seen in the second sample.
+    pub thread_count: usize,
+    /// Length of the sample window.
+    pub window_ms: u64,
+    /// CPU use of the whole process in percent of one core.
+    pub total_cpu_percent: f64,
+    /// Busiest threads, at most [`TOP_THREADS`], with non-zero CPU use.
+    pub top_threads: Vec<ThreadCpu>,
+}
+
+impl ThreadCpuSummary {
+    /// Share of the process's CPU taken by its busiest thread.
+    pub fn dominant_share(&self) -> Option<f64> {
+        self.top_threads.first().map(|t| t.share)
+    }
+
+    /// Whether one thread of a multi-threaded process does nearly all the work
+    /// while the process as a whole is busy.
+    pub fn is_single_thread_hog(&self, min_share: f64, min_cpu_percent: f64) -> bool {
+        self.thread_count > 1
+            && self.total_cpu_percent >= min_cpu_percent
+            && self
+                .dominant_share()
+                .is_some_and(|share| share >= min_share)
+    }
+
+    /// One-line description for explanations and rationales.
+    pub fn summary(&self) -> String {
+        match self.top_threads.first() {
+            Some(top) => format!(
+                "thread {} ({}) uses {:.0}% of {:.0}% CPU across {} threads",
+                top.tid,
+                top.name,
+                top.share * 100.0,
+                self.total_cpu_percent,
+                self.thread_count
+            ),
+            None => format!("idle across {} threads", self.thread_count),
+        }
+    }
+}
+
+/// `tid -> (name, utime + stime)` for one process.
+pub type ThreadTicks = HashMap<u32, (String, u64)>;
+
+/// First sample of per-thread CPU ticks, awaiting the second.
+#[derive(Debug)]
+pub struct ThreadCpuSampler {
+    started: Instant,
+    samples: HashMap<u32, ThreadTicks>,
+}
+
+impl ThreadCpuSampler {
+    /// Take the first sample for `pids`.
+    pub fn start(pids: &[u32]) -> Self {
+        let started = Instant::now();
+        let samples = pids
+            .iter()
+            .filter_map(|&pid| read_thread_ticks(pid).map(|ticks| (pid, ticks)))
+            .collect();
+        Self { started, samples }
+    }
+
+    /// Sleep for whatever is left of `window` since the first sample.
+    pub fn wait(&self, window: Duration) {
+        if let Some(remaining) = window.checked_sub(self.started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+
+    /// Take the second sample for `pid` and summarize it.
+    ///
+    /// `None` when the process was not sampled or its tasks are unreadable.
+    pub fn summary(&self, pid: u32) -> Option<ThreadCpuSummary> {
+        let before = self.samples.get(&pid)?;
+        let after = read_thread_ticks(pid)?;
+        summarize_thread_cpu(before, &after, self.started.elapsed(), clk_tck())
+    }
+}
+
+/// Summarize two per-thread tick samples `elapsed` apart.
+///
+/// Threads created during the window count from zero; threads whose ticks went
+/// backwards (TID reuse) are ignored.
+pub fn summarize_thread_cpu(
+    before: &ThreadTicks,
+    after: &ThreadTicks,
+    elapsed: Duration,
+    clk_tck: u64,
+) -> Option<ThreadCpuSummary> {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 || clk_tck == 0 || after.is_empty() {
+        return None;
+    }
+    let to_percent = |ticks: u64| ticks as f64 * 100.0 / (clk_tck as f64 * secs);
+
+    let mut deltas: Vec<(u32, &str, u64)> = after
+        .iter()
+        .filter_map(|(&tid, (name, ticks))| {
+            let prior = before.get(&tid).map_or(0, |(_, t)| *t);
+            Some((tid, name.as_str(), ticks.checked_sub(prior)?))
+        })
+        .collect();
+    let total: u64 = deltas.iter().map(|(_, _, d)| d).sum();
+    deltas.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+
+    let top_threads = deltas
+        .iter()
+        .filter(|(_, _, d)| *d > 0)
+        .take(TOP_THREADS)
+        .map(|&(tid, name, d)| ThreadCpu {
+            tid,
+            name: name.to_string(),
+            cpu_percent: to_percent(d),
+            share: d as f64 / total as f64,
+        })
+        .collect();
+    Some(ThreadCpuSummary {
+        thread_count: after.len(),
+        window_ms: elapsed.as_millis() as u64,
+        total_cpu_percent: to_percent(total),
+        top_threads,
+    })
+}
+
+/// Read `utime + stime` of every thread of `pid`.
+pub fn read_thread_ticks(pid: u32) -> Option<ThreadTicks> {
+    let entries = fs::read_dir(format!("/proc/{}/task", pid)).ok()?;
+    let ticks: ThreadTicks = entries
+        .flatten()
+        .filter_map(|entry| {
+            let tid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let content = fs::read_to_string(entry.path().join("stat")).ok()?;
+            parse_task_stat(&content).map(|sample| (tid, sample))
+        })
+        .collect();
+    (!ticks.is_empty()).then_some(ticks)
+}
+
+/// Parse `(comm, utime + stime)` from `/proc/[pid]/task/[tid]/stat` content.
+pub fn parse_task_stat(content: &str) -> Option<(String, u64)> {
+    let comm_start = content.find('(')?;
+    let comm_end = content.rfind(')')?;
+    let name = content.get(comm_start + 1..comm_end)?.to_string();
+    // Fields after comm start at state (field 3); utime and stime are 14 and 15.
+    let mut fields = content.get(comm_end + 2..)?.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some((name, utime + stime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticks(entries: &[(u32, &str, u64)]) -> ThreadTicks {
+        entries
+            .iter()
+            .map(|&(tid, name, t)| (tid, (name.to_string(), t)))
+            .collect()
+    }
+
+    #[test]
+    fn parses_task_stat() {
+        let stat = "4242 (Web Content) R 4200 4200 4200 0 -1 4194560 1200 0 3 0 1500 250 0 0 20 0 31 0 98765 0 0";
+        assert_eq!(
+            parse_task_stat(stat),
+            Some(("Web Content".to_string(), 1750))
+        );
+        assert_eq!(parse_task_stat("4242 (x) R 1 2"), None);
+    }
+
+    #[test]
+    fn finds_single_runaway_thread() {
+        let before = ticks(&[(100, "app", 500), (101, "render", 1_000), (102, "io", 40)]);
+        let after = ticks(&[
+            (100, "app", 501),
+            (101, "render", 1_095),
+            (102, "io", 40),
+            (103, "worker", 4),
+        ]);
+        let summary = summarize_thread_cpu(&before, &after, Duration::from_secs(1), 100).unwrap();
+        assert_eq!(summary.thread_count, 4);
+        assert_eq!(summary.total_cpu_percent, 100.0);
+        assert_eq!(summary.top_threads.len(), 3);
+        assert_eq!(summary.top_threads[0].tid, 101);
+        assert_eq!(summary.top_threads[0].cpu_percent, 95.0);
+        assert_eq!(summary.dominant_share(), Some(0.95));
+        assert!(summary.is_single_thread_hog(0.8, 50.0));
+        assert!(!summary.is_single_thread_hog(0.8, 150.0));
+        assert!(summary
+            .summary()
+            .starts_with("thread 101 (render) uses 95%"));
+    }
+
+    #[test]
+    fn spread_load_and_single_threads_are_not_hogs() {
+        let before = ticks(&[(1, "a", 0), (2, "b", 0), (3, "c", 0)]);
+        let after = ticks(&[(1, "a", 40), (2, "b", 30), (3, "c", 30)]);
+        let spread = summarize_thread_cpu(&before, &after, Duration::from_secs(1), 100).unwrap();
+        assert!(!spread.is_single_thread_hog(0.8, 50.0));
+
+        let single = summarize_thread_cpu(
+            &ticks(&[(1, "a", 0)]),
+            &ticks(&[(1, "a", 100)]),
+            Duration::from_secs(1),
+            100,
+        )
+        .unwrap();
+        assert_eq!(single.dominant_share(), Some(1.0));
+        assert!(!single.is_single_thread_hog(0.8, 50.0));
+
+        let idle = summarize_thread_cpu(&before, &before, Duration::from_secs(1), 100).unwrap();
+        assert!(idle.top_threads.is_empty());
+        assert_eq!(idle.summary(), "idle across 3 threads");
+        assert_eq!(
+            summarize_thread_cpu(&before, &after, Duration::ZERO, 100),
+            None
+        );
+    }
+
+    #[test]
+    fn samples_own_threads() {
+        let pid = std::process::id();
+        let sampler = ThreadCpuSampler::start(&[pid]);
+        sampler.wait(Duration::from_millis(20));
+        let summary = sampler.summary(pid).unwrap();
+        assert!(summary.thread_count >= 1);
+        assert!(summary.window_ms >= 20);
+        assert_eq!(ThreadCpuSampler::start(&[]).summary(pid), None);
+    }
+}
@@ -24,7 +24,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -193,6 +193,12 @@ pub struct ToolSpec {
 
     /// Override max output (None = use default).
     pub max_output: Option<usize>,
+
+    /// Working directory (None = inherit).
+    pub cwd: Option<PathBuf>,
+
+    /// Variables set on top of the minimal environment.
+    pub env: Vec<(String, String)>,
 }
 
 impl ToolSpec {
@@ -203,6 +209,8 @@ impl ToolSpec {
             args,
             timeout: None,
             max_output: None,
+            cwd: None,
+            env: Vec::new(),
         }
     }
 
@@ -217,6 +225,18 @@ impl ToolSpec {
         self.max_output = Some(max_output);
         self
     }
+
+    /// Run in `cwd`.
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Pass `key=value` to the command.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
 }
 
 /// Tool runner with shared budget tracking.
@@ -284,6 +304,8 @@ impl ToolRunner {
             args: args.iter().map(|s| s.to_string()).collect(),
             timeout,
             max_output: None,
+            cwd: None,
+            env: Vec::new(),
         };
         self.run(&spec)
     }
@@ -343,7 +365,13 @@ impl ToolRunner {
 
         // Build command
         let mut command = match self.build_command(&spec.command, &spec.args) {
-            Ok(cmd) => cmd,
+            Ok(mut cmd) => {
+                if let Some(cwd) = &spec.cwd {
+                    cmd.current_dir(cwd);
+                }
+                cmd.envs(spec.env.iter().map(|(k, v)| (k, v)));
+                cmd
+            }
             Err(e) => {
                 // Refund budget if build fails
                 self.used_ms.fetch_sub(allocated_ms, Ordering::SeqCst);
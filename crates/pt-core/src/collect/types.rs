@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use super::container::ContainerInfo;
+use super::visibility::ProcVisibility;
 
 /// Process state from ps output.
 ///
@@ -146,6 +147,10 @@ pub struct ProcessRecord {
     /// Virtual memory size in bytes.
     pub vsz_bytes: u64,
 
+    /// Storage I/O rate over the scan's sampling window (if sampled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_rate: Option<IoRate>,
+
     // === Terminal ===
     /// Controlling terminal (None if no TTY).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -185,6 +190,26 @@ impl ProcessRecord {
     }
 }
 
+/// Storage I/O rate between two samples of a process's I/O counters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct IoRate {
+    /// Bytes read from storage per second.
+    pub read_bytes_per_sec: f64,
+
+    /// Bytes written to storage per second.
+    pub write_bytes_per_sec: f64,
+
+    /// Time between the two samples in milliseconds.
+    pub window_ms: u64,
+}
+
+impl IoRate {
+    /// Combined read and write rate in bytes per second.
+    pub fn total_bytes_per_sec(&self) -> f64 {
+        self.read_bytes_per_sec + self.write_bytes_per_sec
+    }
+}
+
 /// Result of a scan operation.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScanResult {
@@ -220,6 +245,10 @@ pub struct ScanMetadata {
     /// Any warnings encountered during scan.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+
+    /// Which processes the scanning user could see (restricted under hidepid).
+    #[serde(default, skip_serializing_if = "ProcVisibility::is_full")]
+    pub visibility: ProcVisibility,
 }
 
 #[cfg(test)]
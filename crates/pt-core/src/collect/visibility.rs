@@ -0,0 +1,339 @@
+//! Process visibility under `/proc` `hidepid` hardening.
+//!
+//! When `/proc` is mounted with `hidepid=2` (or `invisible`), non-root users
+//! only see their own processes; with `hidepid=1` (`noaccess`) other users'
+//! PIDs are listed but their details are unreadable. A scan run in that
+//! setting is silently incomplete, so this module:
+//! - Parses the `/proc` mount options (`hidepid=`, `gid=`)
+//! - Decides whether the current user is exempt (root, `CAP_SYS_PTRACE`,
+//!   member of the `gid=` group)
+//! - Produces the visibility scope attached to scan metadata and plans
+//! - Suggests concrete remediation (exemption group, scoped sudo, polkit)
+//!
+//! # Data Sources
+//! - `/proc/self/mounts` - mount options of the procfs instance
+//! - `/proc/self/status` - effective capabilities
+//! - `/etc/group` - name of the exemption group
+//!
+//! On non-Linux platforms detection always reports full visibility.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Bit for `CAP_SYS_PTRACE` in `CapEff`.
+#[cfg(target_os = "linux")]
+const CAP_SYS_PTRACE_BIT: u64 = 1 << 19;
+
+/// `hidepid` mount option of `/proc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HidePid {
+    /// `hidepid=0` / `off`: everything visible.
+    #[default]
+    Off,
+    /// `hidepid=1` / `noaccess`: other users' PIDs listed, contents unreadable.
+    NoAccess,
+    /// `hidepid=2` / `invisible`: other users' PIDs not listed at all.
+    Invisible,
+    /// `hidepid=4` / `ptraceable`: only ptrace-able PIDs are listed.
+    Ptraceable,
+}
+
+impl HidePid {
+    /// Parse the value of a `hidepid=` mount option.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "0" | "off" => Some(HidePid::Off),
+            "1" | "noaccess" => Some(HidePid::NoAccess),
+            "2" | "invisible" => Some(HidePid::Invisible),
+            "4" | "ptraceable" => Some(HidePid::Ptraceable),
+            _ => None,
+        }
+    }
+
+    /// Canonical mount option value (as accepted by `mount -o`).
+    pub fn as_option(self) -> &'static str {
+        match self {
+            HidePid::Off => "off",
+            HidePid::NoAccess => "noaccess",
+            HidePid::Invisible => "invisible",
+            HidePid::Ptraceable => "ptraceable",
+        }
+    }
+}
+
+/// Which processes the current user can observe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VisibilityScope {
+    /// All processes on the host (within the PID namespace).
+    #[default]
+    All,
+    /// All PIDs are listed, but other users' details are unreadable.
+    OwnDetails,
+    /// Only the current user's processes are listed.
+    OwnOnly,
+}
+
+/// Procfs visibility for the current user.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProcVisibility {
+    /// `hidepid` option of the `/proc` mount.
+    pub hidepid: HidePid,
+    /// `gid=` exemption group of the `/proc` mount, if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exempt_gid: Option<u32>,
+    /// Whether the current user bypasses `hidepid` (root, `CAP_SYS_PTRACE`,
+    /// or member of the exemption group).
+    #[serde(default)]
+    pub exempt: bool,
+    /// Resulting visibility scope.
+    pub scope: VisibilityScope,
+}
+
+impl ProcVisibility {
+    /// Build the visibility for a user given the `/proc` mount options.
+    pub fn from_mount(
+        hidepid: HidePid,
+        exempt_gid: Option<u32>,
+        euid: u32,
+        groups: &[u32],
+        has_ptrace_cap: bool,
+    ) -> Self {
+        let exempt =
+            euid == 0 || has_ptrace_cap || exempt_gid.is_some_and(|gid| groups.contains(&gid));
+        let scope = match hidepid {
+            _ if exempt => VisibilityScope::All,
+            HidePid::Off => VisibilityScope::All,
+            HidePid::NoAccess => VisibilityScope::OwnDetails,
+            HidePid::Invisible | HidePid::Ptraceable => VisibilityScope::OwnOnly,
+        };
+        Self {
+            hidepid,
+            exempt_gid,
+            exempt,
+            scope,
+        }
+    }
+
+    /// Detect visibility for the current process.
+    pub fn detect() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            let Some((hidepid, gid)) = std::fs::read_to_string("/proc/self/mounts")
+                .ok()
+                .and_then(|m| parse_proc_mount_options(&m))
+            else {
+                return Self::default();
+            };
+            let euid = unsafe { libc::geteuid() };
+            let has_ptrace_cap = std::fs::read_to_string("/proc/self/status")
+                .ok()
+                .and_then(|s| parse_cap_eff(&s))
+                .is_some_and(|bits| bits & CAP_SYS_PTRACE_BIT != 0);
+            Self::from_mount(hidepid, gid, euid, &current_groups(), has_ptrace_cap)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Whether every process on the host is visible.
+    pub fn is_full(&self) -> bool {
+        self.scope == VisibilityScope::All
+    }
+
+    /// One-line description for warnings and summaries.
+    pub fn describe(&self) -> String {
+        match self.scope {
+            VisibilityScope::All => "all processes visible".to_string(),
+            VisibilityScope::OwnDetails => format!(
+                "/proc mounted with hidepid={}: other users' processes are listed but unreadable",
+                self.hidepid.as_option()
+            ),
+            VisibilityScope::OwnOnly => format!(
+                "/proc mounted with hidepid={}: only your own processes are visible",
+                self.hidepid.as_option()
+            ),
+        }
+    }
+
+    /// Concrete steps that would restore full visibility for `user`.
+    ///
+    /// `exe` is the path of the pt-core binary, used for the sudo and polkit
+    /// rules. `group_name` is the name of the exemption group if known.
+    /// Returns nothing when visibility is already full.
+    pub fn remediation(&self, user: &str, exe: &str, group_name: Option<&str>) -> Vec<String> {
+        if self.is_full() {
+            return Vec::new();
+        }
+        let mut steps = Vec::new();
+        match (self.exempt_gid, group_name) {
+            (Some(_), Some(group)) => steps.push(format!(
+                "Join the /proc exemption group (takes effect at next login): sudo usermod -aG {group} {user}"
+            )),
+            (Some(gid), None) => steps.push(format!(
+                "Join the /proc exemption group gid {gid} (takes effect at next login): sudo usermod -aG $(getent group {gid} | cut -d: -f1) {user}"
+            )),
+            (None, _) => steps.push(format!(
+                "Add an exemption group to the /proc mount: sudo groupadd -r proc && sudo mount -o remount,hidepid={mode},gid=proc /proc && sudo usermod -aG proc {user} (persist with 'proc /proc proc defaults,hidepid={mode},gid=proc 0 0' in /etc/fstab)",
+                mode = self.hidepid.as_option()
+            )),
+        }
+        steps.push(format!(
+            "Allow scoped sudo for scans only: echo '{user} ALL=(root) NOPASSWD: {exe} scan *, {exe} agent plan *' | sudo tee /etc/sudoers.d/pt-core && sudo chmod 0440 /etc/sudoers.d/pt-core"
+        ));
+        steps.push(format!(
+            "Or allow pkexec via polkit: add /etc/polkit-1/rules.d/50-pt-core.rules containing polkit.addRule(function(action, subject) {{ if (action.id == \"org.freedesktop.policykit.exec\" && action.lookup(\"program\") == \"{exe}\" && subject.user == \"{user}\") {{ return polkit.Result.YES; }} }}); then run pkexec {exe} agent plan"
+        ));
+        steps
+    }
+}
+
+/// Parse `/proc/self/mounts` and return the `hidepid`/`gid` options of the
+/// procfs mounted at `/proc`.
+///
+/// Returns `None` if no procfs is mounted at `/proc`. When `/proc` is
+/// mounted several times, the last (topmost) mount wins.
+pub fn parse_proc_mount_options(mounts: &str) -> Option<(HidePid, Option<u32>)> {
+    let mut result = None;
+    for line in mounts.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 || fields[1] != "/proc" || fields[2] != "proc" {
+            continue;
+        }
+        let mut hidepid = HidePid::Off;
+        let mut gid = None;
+        for option in fields[3].split(',') {
+            if let Some(value) = option.strip_prefix("hidepid=") {
+                hidepid = HidePid::parse(value).unwrap_or(HidePid::Off);
+            } else if let Some(value) = option.strip_prefix("gid=") {
+                gid = value.parse::<u32>().ok();
+            }
+        }
+        result = Some((hidepid, gid));
+    }
+    result
+}
+
+/// Resolve a group id to its name via `/etc/group`.
+pub fn group_name(gid: u32) -> Option<String> {
+    let content = std::fs::read_to_string("/etc/group").ok()?;
+    parse_group_name(&content, gid)
+}
+
+fn parse_group_name(content: &str, gid: u32) -> Option<String> {
+    content.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id = fields.nth(1)?.parse::<u32>().ok()?;
+        (id == gid).then(|| name.to_string())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_cap_eff(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())
+}
+
+/// Effective and supplementary group ids of the current process.
+#[cfg(target_os = "linux")]
+fn current_groups() -> Vec<u32> {
+    let mut groups = vec![unsafe { libc::getegid() }];
+    let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+    if count > 0 {
+        let mut buf = vec![0 as libc::gid_t; count as usize];
+        let n = unsafe { libc::getgroups(count, buf.as_mut_ptr()) };
+        if n > 0 {
+            buf.truncate(n as usize);
+            groups.extend(buf);
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_mount_options() {
+        let mounts = "\
+sysfs /sys sysfs rw,nosuid,nodev,noexec,relatime 0 0
+proc /proc proc rw,nosuid,nodev,noexec,relatime,gid=27,hidepid=invisible 0 0
+tmpfs /run tmpfs rw,nosuid,nodev 0 0
+";
+        assert_eq!(
+            parse_proc_mount_options(mounts),
+            Some((HidePid::Invisible, Some(27)))
+        );
+
+        let legacy = "proc /proc proc rw,relatime,hidepid=2 0 0\n";
+        assert_eq!(
+            parse_proc_mount_options(legacy),
+            Some((HidePid::Invisible, None))
+        );
+
+        let plain = "proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0\n";
+        assert_eq!(parse_proc_mount_options(plain), Some((HidePid::Off, None)));
+
+        // A procfs mounted elsewhere (e.g. inside a container root) is ignored.
+        let other = "proc /var/lib/ct/proc proc rw,hidepid=2 0 0\n";
+        assert_eq!(parse_proc_mount_options(other), None);
+    }
+
+    #[test]
+    fn test_visibility_scope() {
+        let own = ProcVisibility::from_mount(HidePid::Invisible, Some(27), 1000, &[1000], false);
+        assert_eq!(own.scope, VisibilityScope::OwnOnly);
+        assert!(!own.exempt);
+        assert!(!own.is_full());
+
+        let member = ProcVisibility::from_mount(HidePid::Invisible, Some(27), 1000, &[27], false);
+        assert!(member.exempt);
+        assert!(member.is_full());
+
+        let root = ProcVisibility::from_mount(HidePid::Invisible, None, 0, &[0], false);
+        assert!(root.is_full());
+
+        let ptrace = ProcVisibility::from_mount(HidePid::NoAccess, None, 1000, &[1000], true);
+        assert!(ptrace.is_full());
+
+        let noaccess = ProcVisibility::from_mount(HidePid::NoAccess, None, 1000, &[1000], false);
+        assert_eq!(noaccess.scope, VisibilityScope::OwnDetails);
+
+        assert!(ProcVisibility::default().is_full());
+    }
+
+    #[test]
+    fn test_remediation() {
+        let full = ProcVisibility::default();
+        assert!(full
+            .remediation("alice", "/usr/bin/pt-core", None)
+            .is_empty());
+
+        let with_group =
+            ProcVisibility::from_mount(HidePid::Invisible, Some(27), 1000, &[1000], false);
+        let steps = with_group.remediation("alice", "/usr/bin/pt-core", Some("sudo"));
+        assert_eq!(steps.len(), 3);
+        assert!(steps[0].contains("usermod -aG sudo alice"));
+        assert!(steps[1].contains("NOPASSWD: /usr/bin/pt-core scan *"));
+        assert!(steps[2].contains("org.freedesktop.policykit.exec"));
+
+        let no_group = ProcVisibility::from_mount(HidePid::Invisible, None, 1000, &[1000], false);
+        let steps = no_group.remediation("alice", "/usr/bin/pt-core", None);
+        assert!(steps[0].contains("remount,hidepid=invisible,gid=proc"));
+    }
+
+    #[test]
+    fn test_parse_group_name() {
+        let content = "root:x:0:\nsudo:x:27:alice\nproc:x:998:\n";
+        assert_eq!(parse_group_name(content, 27).as_deref(), Some("sudo"));
+        assert_eq!(parse_group_name(content, 998).as_deref(), Some("proc"));
+        assert_eq!(parse_group_name(content, 5), None);
+    }
+}
@@ -0,0 +1,604 @@
+//! Windows-specific process collection.
+//!
+//! Windows has neither /proc nor `ps`, so processes are enumerated through
+//! the Win32 and NT native APIs:
+//! - `CreateToolhelp32Snapshot` / `Process32FirstW` / `Process32NextW` - PID,
+//!   parent PID, thread count, image name
+//! - `OpenProcess` + `GetProcessTimes` - creation time (for `start_id`) and
+//!   CPU times
+//! - `K32GetProcessMemoryInfo` - working set and pagefile usage
+//! - `QueryFullProcessImageNameW` - full executable path
+//! - `OpenProcessToken` / `GetTokenInformation(TokenUser)` - owner SID
+//! - `NtQuerySystemInformation(SystemTimeOfDayInformation)` - kernel boot
+//!   time, used as the boot identifier
+//!
+//! # Identity
+//! `start_id` is `boot_id:creation_time:pid`, where `creation_time` is the
+//! process creation FILETIME (100ns ticks since 1601). It is fixed for the
+//! lifetime of the process, so a recycled PID always gets a different
+//! `start_id`.
+//!
+//! Windows has no numeric UIDs. `uid` is the relative identifier (last
+//! sub-authority) of the owner SID, e.g. 18 for `SYSTEM` or 1001 for a local
+//! user, and `user` is the resolved `DOMAIN\name`.
+//!
+//! # Platform Support
+//! This module only compiles on Windows (target_os = "windows").
+
+use super::types::{ProcessRecord, ProcessState};
+use pt_common::{IdentityQuality, ProcessId, StartId};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// ============================================================================
+// FFI
+// ============================================================================
+
+// Declared by hand rather than through a bindings crate; layouts mirror
+// <tlhelp32.h>, <psapi.h>, <winnt.h> and <winternl.h>.
+pub(crate) type Handle = *mut c_void;
+
+const INVALID_HANDLE_VALUE: Handle = -1isize as Handle;
+const TH32CS_SNAPPROCESS: u32 = 0x0000_0002;
+const MAX_PATH: usize = 260;
+const TOKEN_QUERY: u32 = 0x0008;
+const TOKEN_USER: u32 = 1;
+const STILL_ACTIVE: u32 = 259;
+const SYSTEM_TIME_OF_DAY_INFORMATION: u32 = 3;
+
+pub const PROCESS_TERMINATE: u32 = 0x0001;
+pub const PROCESS_SUSPEND_RESUME: u32 = 0x0800;
+pub const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+pub const SYNCHRONIZE: u32 = 0x0010_0000;
+pub const ERROR_ACCESS_DENIED: i32 = 5;
+pub const ERROR_INVALID_PARAMETER: i32 = 87;
+
+/// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch.
+const FILETIME_UNIX_EPOCH_OFFSET_SECS: u64 = 11_644_473_600;
+const FILETIME_TICKS_PER_SEC: u64 = 10_000_000;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct FileTime {
+    low: u32,
+    high: u32,
+}
+
+impl FileTime {
+    fn ticks(self) -> u64 {
+        ((self.high as u64) << 32) | self.low as u64
+    }
+}
+
+#[repr(C)]
+struct ProcessEntry32W {
+    dw_size: u32,
+    cnt_usage: u32,
+    th32_process_id: u32,
+    th32_default_heap_id: usize,
+    th32_module_id: u32,
+    cnt_threads: u32,
+    th32_parent_process_id: u32,
+    pc_pri_class_base: i32,
+    dw_flags: u32,
+    sz_exe_file: [u16; MAX_PATH],
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+#[allow(dead_code)] // Filled in by the kernel; only a few fields are read.
+struct ProcessMemoryCounters {
+    cb: u32,
+    page_fault_count: u32,
+    peak_working_set_size: usize,
+    working_set_size: usize,
+    quota_peak_paged_pool_usage: usize,
+    quota_paged_pool_usage: usize,
+    quota_peak_non_paged_pool_usage: usize,
+    quota_non_paged_pool_usage: usize,
+    pagefile_usage: usize,
+    peak_pagefile_usage: usize,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+#[allow(dead_code)] // Filled in by the kernel; only boot_time is read.
+struct SystemTimeOfDayInformation {
+    boot_time: i64,
+    current_time: i64,
+    time_zone_bias: i64,
+    time_zone_id: u32,
+    reserved: u32,
+    boot_time_bias: u64,
+    sleep_time_bias: u64,
+}
+
+#[repr(C)]
+struct SidAndAttributes {
+    sid: *mut c_void,
+    attributes: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn CreateToolhelp32Snapshot(flags: u32, pid: u32) -> Handle;
+    fn Process32FirstW(snapshot: Handle, entry: *mut ProcessEntry32W) -> i32;
+    fn Process32NextW(snapshot: Handle, entry: *mut ProcessEntry32W) -> i32;
+    fn OpenProcess(access: u32, inherit: i32, pid: u32) -> Handle;
+    fn CloseHandle(handle: Handle) -> i32;
+    fn GetProcessTimes(
+        handle: Handle,
+        creation: *mut FileTime,
+        exit: *mut FileTime,
+        kernel: *mut FileTime,
+        user: *mut FileTime,
+    ) -> i32;
+    fn GetExitCodeProcess(handle: Handle, code: *mut u32) -> i32;
+    fn GetSystemTimeAsFileTime(now: *mut FileTime);
+    fn K32GetProcessMemoryInfo(
+        handle: Handle,
+        counters: *mut ProcessMemoryCounters,
+        cb: u32,
+    ) -> i32;
+    fn QueryFullProcessImageNameW(handle: Handle, flags: u32, buf: *mut u16, size: *mut u32)
+        -> i32;
+    fn ProcessIdToSessionId(pid: u32, session: *mut u32) -> i32;
+}
+
+#[link(name = "advapi32")]
+extern "system" {
+    fn OpenProcessToken(process: Handle, access: u32, token: *mut Handle) -> i32;
+    fn GetTokenInformation(
+        token: Handle,
+        class: u32,
+        buf: *mut c_void,
+        len: u32,
+        ret_len: *mut u32,
+    ) -> i32;
+    fn GetSidSubAuthorityCount(sid: *mut c_void) -> *mut u8;
+    fn GetSidSubAuthority(sid: *mut c_void, index: u32) -> *mut u32;
+    fn LookupAccountSidW(
+        system: *const u16,
+        sid: *mut c_void,
+        name: *mut u16,
+        name_len: *mut u32,
+        domain: *mut u16,
+        domain_len: *mut u32,
+        sid_use: *mut u32,
+    ) -> i32;
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQuerySystemInformation(class: u32, buf: *mut c_void, len: u32, ret_len: *mut u32) -> i32;
+}
+
+/// Kernel handle closed on drop.
+#[derive(Debug)]
+struct OwnedHandle(Handle);
+
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+// ============================================================================
+// Process handles
+// ============================================================================
+
+/// CPU and lifetime timestamps for a process (FILETIME ticks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessTimes {
+    /// Creation time (100ns ticks since 1601).
+    pub creation: u64,
+    /// Time spent in kernel mode (100ns ticks).
+    pub kernel: u64,
+    /// Time spent in user mode (100ns ticks).
+    pub user: u64,
+}
+
+/// Open process handle, closed on drop.
+///
+/// While a handle is open the process object cannot be freed, so its PID
+/// cannot be recycled. Checks made through the handle (creation time) remain
+/// valid for later operations on the same handle.
+#[derive(Debug)]
+pub struct ProcessHandle(OwnedHandle);
+
+impl ProcessHandle {
+    /// Open `pid` with the given access mask.
+    pub fn open(pid: u32, access: u32) -> std::io::Result<Self> {
+        let handle = unsafe { OpenProcess(access, 0, pid) };
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self(OwnedHandle(handle)))
+    }
+
+    /// Raw handle for FFI calls made by action runners.
+    pub(crate) fn raw(&self) -> Handle {
+        self.0 .0
+    }
+
+    /// Creation and CPU times via `GetProcessTimes`.
+    pub fn times(&self) -> Option<ProcessTimes> {
+        let mut creation = FileTime::default();
+        let mut exit = FileTime::default();
+        let mut kernel = FileTime::default();
+        let mut user = FileTime::default();
+        let ok = unsafe {
+            GetProcessTimes(self.raw(), &mut creation, &mut exit, &mut kernel, &mut user)
+        };
+        if ok == 0 {
+            return None;
+        }
+        Some(ProcessTimes {
+            creation: creation.ticks(),
+            kernel: kernel.ticks(),
+            user: user.ticks(),
+        })
+    }
+
+    /// Creation time as a FILETIME tick count.
+    pub fn creation_time(&self) -> Option<u64> {
+        self.times().map(|t| t.creation)
+    }
+
+    /// True until the process exits.
+    ///
+    /// A process that exits with code 259 (`STILL_ACTIVE`) is misreported as
+    /// running; callers that need certainty wait on the handle instead.
+    pub fn is_running(&self) -> bool {
+        let mut code = 0u32;
+        unsafe { GetExitCodeProcess(self.raw(), &mut code) != 0 && code == STILL_ACTIVE }
+    }
+
+    /// Working set and pagefile usage in bytes.
+    fn memory(&self) -> Option<(u64, u64)> {
+        let mut counters = ProcessMemoryCounters::default();
+        let cb = std::mem::size_of::<ProcessMemoryCounters>() as u32;
+        counters.cb = cb;
+        if unsafe { K32GetProcessMemoryInfo(self.raw(), &mut counters, cb) } == 0 {
+            return None;
+        }
+        Some((
+            counters.working_set_size as u64,
+            counters.pagefile_usage as u64,
+        ))
+    }
+
+    /// Full executable path via `QueryFullProcessImageNameW`.
+    fn image_path(&self) -> Option<String> {
+        let mut buf = vec![0u16; 1024];
+        let mut len = buf.len() as u32;
+        if unsafe { QueryFullProcessImageNameW(self.raw(), 0, buf.as_mut_ptr(), &mut len) } == 0 {
+            return None;
+        }
+        Some(String::from_utf16_lossy(&buf[..len as usize]))
+    }
+
+    /// Owner RID and account name from the process token's user SID.
+    ///
+    /// Names are cached in `names` per RID since `LookupAccountSidW` can be
+    /// slow (it may consult a domain controller).
+    fn owner(&self, names: &mut HashMap<u32, String>) -> Option<(u32, String)> {
+        let mut token: Handle = std::ptr::null_mut();
+        if unsafe { OpenProcessToken(self.raw(), TOKEN_QUERY, &mut token) } == 0 {
+            return None;
+        }
+        let token = OwnedHandle(token);
+
+        // u64 backing keeps the TOKEN_USER header pointer-aligned.
+        let mut buf = vec![0u64; 64];
+        let mut len = 0u32;
+        let ok = unsafe {
+            GetTokenInformation(
+                token.0,
+                TOKEN_USER,
+                buf.as_mut_ptr() as *mut c_void,
+                (buf.len() * std::mem::size_of::<u64>()) as u32,
+                &mut len,
+            )
+        };
+        if ok == 0 {
+            return None;
+        }
+        let sid = unsafe { (*(buf.as_ptr() as *const SidAndAttributes)).sid };
+        let count = unsafe { *GetSidSubAuthorityCount(sid) };
+        let rid = if count == 0 {
+            0
+        } else {
+            unsafe { *GetSidSubAuthority(sid, count as u32 - 1) }
+        };
+        let name = names
+            .entry(rid)
+            .or_insert_with(|| lookup_account_name(sid).unwrap_or_else(|| rid.to_string()))
+            .clone();
+        Some((rid, name))
+    }
+}
+
+fn lookup_account_name(sid: *mut c_void) -> Option<String> {
+    let mut name = [0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain = [0u16; 256];
+    let mut domain_len = domain.len() as u32;
+    let mut sid_use = 0u32;
+    let ok = unsafe {
+        LookupAccountSidW(
+            std::ptr::null(),
+            sid,
+            name.as_mut_ptr(),
+            &mut name_len,
+            domain.as_mut_ptr(),
+            &mut domain_len,
+            &mut sid_use,
+        )
+    };
+    if ok == 0 {
+        return None;
+    }
+    let name = String::from_utf16_lossy(&name[..name_len as usize]);
+    let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+    Some(if domain.is_empty() {
+        name
+    } else {
+        format!("{}\\{}", domain, name)
+    })
+}
+
+// ============================================================================
+// Boot identity and start_id
+// ============================================================================
+
+/// Kernel boot time as a FILETIME tick count.
+fn boot_time_ticks() -> Option<u64> {
+    let mut info = SystemTimeOfDayInformation::default();
+    let status = unsafe {
+        NtQuerySystemInformation(
+            SYSTEM_TIME_OF_DAY_INFORMATION,
+            &mut info as *mut _ as *mut c_void,
+            std::mem::size_of::<SystemTimeOfDayInformation>() as u32,
+            std::ptr::null_mut(),
+        )
+    };
+    // NTSTATUS: negative values are errors.
+    if status < 0 || info.boot_time <= 0 {
+        return None;
+    }
+    Some(info.boot_time as u64)
+}
+
+/// Boot identifier derived from the kernel boot time, cached for the process
+/// lifetime.
+///
+/// Windows has no boot UUID. The boot time in whole seconds is packed into a
+/// UUID so `start_id` keeps the `boot:start:pid` shape that
+/// [`StartId::parse`] accepts.
+pub fn boot_id() -> Option<String> {
+    static BOOT_ID: OnceLock<Option<String>> = OnceLock::new();
+    BOOT_ID
+        .get_or_init(|| boot_time_ticks().map(boot_id_from_ticks))
+        .clone()
+}
+
+fn boot_id_from_ticks(ticks: u64) -> String {
+    uuid::Uuid::from_u128((ticks / FILETIME_TICKS_PER_SEC) as u128).to_string()
+}
+
+/// Current system time as a FILETIME tick count.
+fn now_ticks() -> u64 {
+    let mut now = FileTime::default();
+    unsafe { GetSystemTimeAsFileTime(&mut now) };
+    now.ticks()
+}
+
+/// Convert a FILETIME tick count to Unix seconds (0 for unknown).
+fn filetime_to_unix(ticks: u64) -> i64 {
+    if ticks == 0 {
+        return 0;
+    }
+    (ticks / FILETIME_TICKS_PER_SEC) as i64 - FILETIME_UNIX_EPOCH_OFFSET_SECS as i64
+}
+
+/// Build a `start_id` from the boot identifier and process creation time.
+pub fn native_start_id(pid: u32) -> Option<(StartId, IdentityQuality)> {
+    let handle = ProcessHandle::open(pid, PROCESS_QUERY_LIMITED_INFORMATION).ok()?;
+    let creation = handle.creation_time()?;
+    Some(start_id_for(boot_id().as_deref(), creation, pid))
+}
+
+fn start_id_for(boot_id: Option<&str>, creation_time: u64, pid: u32) -> (StartId, IdentityQuality) {
+    match boot_id {
+        Some(boot) => (
+            StartId::from_windows(boot, creation_time, pid),
+            IdentityQuality::Full,
+        ),
+        None => (
+            StartId::from_windows("unknown", creation_time, pid),
+            IdentityQuality::NoBootId,
+        ),
+    }
+}
+
+// ============================================================================
+// Enumeration
+// ============================================================================
+
+/// One entry from a Toolhelp32 process snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolhelpEntry {
+    pub pid: u32,
+    pub ppid: u32,
+    pub threads: u32,
+    /// Image name (e.g. `notepad.exe`).
+    pub exe_name: String,
+}
+
+/// Enumerate processes via a Toolhelp32 snapshot.
+pub fn toolhelp_processes() -> std::io::Result<Vec<ToolhelpEntry>> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return Err(std::io::Error::last_os_error());
+    }
+    let snapshot = OwnedHandle(snapshot);
+
+    let mut entry: ProcessEntry32W = unsafe { std::mem::zeroed() };
+    entry.dw_size = std::mem::size_of::<ProcessEntry32W>() as u32;
+
+    let mut entries = Vec::new();
+    let mut ok = unsafe { Process32FirstW(snapshot.0, &mut entry) } != 0;
+    while ok {
+        entries.push(ToolhelpEntry {
+            pid: entry.th32_process_id,
+            ppid: entry.th32_parent_process_id,
+            threads: entry.cnt_threads,
+            exe_name: wide_to_string(&entry.sz_exe_file),
+        });
+        ok = unsafe { Process32NextW(snapshot.0, &mut entry) } != 0;
+    }
+    Ok(entries)
+}
+
+/// Collect a [`ProcessRecord`] for every process in a Toolhelp32 snapshot.
+///
+/// Processes that cannot be opened (protected processes, or other users'
+/// processes without SeDebugPrivilege) are still listed with creation time 0.
+/// Such a `start_id` never revalidates, so actions against them fail the
+/// identity check rather than risk hitting a recycled PID.
+pub fn scan_processes() -> std::io::Result<Vec<ProcessRecord>> {
+    let entries = toolhelp_processes()?;
+    let boot = boot_id();
+    let boot = boot.as_deref().unwrap_or("unknown");
+    let now = now_ticks();
+    let mut names = HashMap::new();
+
+    Ok(entries
+        .iter()
+        .map(|entry| build_record(entry, boot, now, &mut names))
+        .collect())
+}
+
+fn build_record(
+    entry: &ToolhelpEntry,
+    boot_id: &str,
+    now: u64,
+    names: &mut HashMap<u32, String>,
+) -> ProcessRecord {
+    let handle = ProcessHandle::open(entry.pid, PROCESS_QUERY_LIMITED_INFORMATION).ok();
+    let times = handle.as_ref().and_then(ProcessHandle::times);
+    let (rss_bytes, vsz_bytes) = handle
+        .as_ref()
+        .and_then(ProcessHandle::memory)
+        .unwrap_or((0, 0));
+    let (uid, user) = handle
+        .as_ref()
+        .and_then(|h| h.owner(names))
+        .unwrap_or((u32::MAX, "unknown".to_string()));
+    let cmd = handle
+        .as_ref()
+        .and_then(ProcessHandle::image_path)
+        .unwrap_or_else(|| entry.exe_name.clone());
+    // An exited process still referenced by open handles is the closest
+    // Windows analogue of a zombie.
+    let state = match handle.as_ref().map(ProcessHandle::is_running) {
+        Some(false) => ProcessState::Zombie,
+        _ => ProcessState::Unknown,
+    };
+
+    let creation = times.map(|t| t.creation).unwrap_or(0);
+    let elapsed_ticks = if creation > 0 {
+        now.saturating_sub(creation)
+    } else {
+        0
+    };
+    // Lifetime average, matching ps's %cpu semantics.
+    let cpu_percent = match times {
+        Some(t) if elapsed_ticks > 0 => (t.kernel + t.user) as f64 / elapsed_ticks as f64 * 100.0,
+        _ => 0.0,
+    };
+
+    let mut session = 0u32;
+    let sid = (unsafe { ProcessIdToSessionId(entry.pid, &mut session) } != 0).then_some(session);
+
+    ProcessRecord {
+        pid: ProcessId(entry.pid),
+        ppid: ProcessId(entry.ppid),
+        uid,
+        user,
+        pgid: None,
+        sid,
+        start_id: StartId::from_windows(boot_id, creation, entry.pid),
+        comm: entry.exe_name.clone(),
+        cmd,
+        state,
+        cpu_percent,
+        rss_bytes,
+        vsz_bytes,
+        tty: None,
+        start_time_unix: filetime_to_unix(creation),
+        elapsed: Duration::from_nanos(elapsed_ticks.saturating_mul(100)),
+        source: "quick_scan".to_string(),
+        container_info: None,
+        io_rate: None,
+    }
+}
+
+fn wide_to_string(chars: &[u16]) -> String {
+    let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+    String::from_utf16_lossy(&chars[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boot_id_is_parseable_uuid() {
+        let id = boot_id_from_ticks(133_500_000_000_000_000);
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+        // Sub-second jitter in the reported boot time must not change the id.
+        assert_eq!(id, boot_id_from_ticks(133_500_000_009_999_999));
+    }
+
+    #[test]
+    fn filetime_converts_to_unix_seconds() {
+        // 2024-01-01T00:00:00Z
+        assert_eq!(filetime_to_unix(133_485_408_000_000_000), 1_704_067_200);
+        assert_eq!(filetime_to_unix(0), 0);
+    }
+
+    #[test]
+    fn start_id_quality_reflects_boot_id() {
+        let (id, quality) = start_id_for(Some("boot"), 42, 7);
+        assert_eq!(id.0, "boot:42:7");
+        assert_eq!(quality, IdentityQuality::Full);
+        let (_, quality) = start_id_for(None, 42, 7);
+        assert_eq!(quality, IdentityQuality::NoBootId);
+    }
+
+    #[test]
+    fn wide_to_string_stops_at_nul() {
+        let mut buf = [0u16; 16];
+        for (slot, c) in buf.iter_mut().zip("cmd.exe".encode_utf16()) {
+            *slot = c;
+        }
+        assert_eq!(wide_to_string(&buf), "cmd.exe");
+    }
+
+    #[test]
+    fn current_process_is_enumerated_with_stable_start_id() {
+        let pid = std::process::id();
+        let entries = toolhelp_processes().expect("toolhelp snapshot");
+        assert!(entries.iter().any(|e| e.pid == pid));
+
+        let (first, _) = native_start_id(pid).expect("start_id");
+        let (second, _) = native_start_id(pid).expect("start_id");
+        assert_eq!(first, second);
+    }
+}
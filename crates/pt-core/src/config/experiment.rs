@@ -0,0 +1,262 @@
+//! Session-scoped configuration experiments.
+//!
+//! An experiment is a JSON overlay applied on top of the resolved priors and
+//! policy for a single session (`agent plan --experiment my-tweak.json`):
+//!
+//! ```json
+//! {
+//!   "name": "my-tweak",
+//!   "description": "Trust idle sockets more",
+//!   "priors": { "classes": { "abandoned": { "prior_prob": 0.2 } } },
+//!   "policy": { "loss_matrix": { "useful": { "kill": 120.0 } } }
+//! }
+//! ```
+//!
+//! Overlays use JSON merge-patch semantics (RFC 7396): objects merge
+//! recursively, any other value replaces the baseline value, and `null`
+//! removes a key. The merged configuration must pass the same semantic
+//! validation as a regular priors/policy file.
+
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{validate_policy, validate_priors, Policy, Priors, ValidationError};
+
+/// Errors raised while loading or applying an experiment overlay.
+#[derive(Debug, Error)]
+pub enum ExperimentError {
+    #[error("I/O error reading experiment {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid JSON in experiment {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid experiment name '{0}' (use letters, digits, '.', '_' or '-')")]
+    InvalidName(String),
+
+    #[error("Experiment '{0}' overlays neither priors nor policy")]
+    Empty(String),
+
+    #[error("Experiment {target} overlay does not produce a valid {target} file: {source}")]
+    Overlay {
+        target: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Experiment configuration failed validation: {0}")]
+    Validation(#[from] ValidationError),
+}
+
+/// A named priors/policy overlay scoped to one session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Experiment {
+    /// Experiment name used to group sessions (`calibrate compare --experiment`).
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Merge-patch applied to the resolved priors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priors: Option<serde_json::Value>,
+    /// Merge-patch applied to the resolved policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<serde_json::Value>,
+}
+
+impl Experiment {
+    /// Load an experiment file. The name defaults to the file stem.
+    pub fn load(path: &Path) -> Result<Self, ExperimentError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ExperimentError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let mut experiment: Experiment =
+            serde_json::from_str(&content).map_err(|e| ExperimentError::Parse {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+        if experiment.name.is_empty() {
+            experiment.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        experiment.validate()?;
+        Ok(experiment)
+    }
+
+    /// Check the name and that the overlay touches something.
+    pub fn validate(&self) -> Result<(), ExperimentError> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+        if !valid_name {
+            return Err(ExperimentError::InvalidName(self.name.clone()));
+        }
+        if self.priors.is_none() && self.policy.is_none() {
+            return Err(ExperimentError::Empty(self.name.clone()));
+        }
+        Ok(())
+    }
+
+    /// Apply the overlay to a baseline configuration and validate the result.
+    pub fn apply(
+        &self,
+        priors: &Priors,
+        policy: &Policy,
+    ) -> Result<(Priors, Policy), ExperimentError> {
+        let priors = match &self.priors {
+            Some(patch) => {
+                let merged: Priors = overlay(priors, patch, "priors")?;
+                validate_priors(&merged)?;
+                merged
+            }
+            None => priors.clone(),
+        };
+        let policy = match &self.policy {
+            Some(patch) => {
+                let merged: Policy = overlay(policy, patch, "policy")?;
+                validate_policy(&merged)?;
+                merged
+            }
+            None => policy.clone(),
+        };
+        Ok((priors, policy))
+    }
+
+    /// SHA-256 of the overlay, so sessions can tell two same-named overlays apart.
+    pub fn overlay_hash(&self) -> String {
+        let body = serde_json::json!({ "priors": self.priors, "policy": self.policy });
+        hex::encode(Sha256::digest(body.to_string().as_bytes()))
+    }
+}
+
+fn overlay<T>(
+    base: &T,
+    patch: &serde_json::Value,
+    target: &'static str,
+) -> Result<T, ExperimentError>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    let mut value =
+        serde_json::to_value(base).map_err(|e| ExperimentError::Overlay { target, source: e })?;
+    merge_patch(&mut value, patch);
+    serde_json::from_value(value).map_err(|e| ExperimentError::Overlay { target, source: e })
+}
+
+/// RFC 7396 JSON merge-patch.
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch_map) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(target_map) = target {
+        for (key, value) in patch_map {
+            if value.is_null() {
+                target_map.remove(key);
+            } else {
+                merge_patch(
+                    target_map
+                        .entry(key.clone())
+                        .or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_patch_merges_objects_and_removes_nulls() {
+        let mut target = json!({"a": {"b": 1, "c": 2}, "d": 3});
+        merge_patch(&mut target, &json!({"a": {"b": 10, "c": null}, "e": [1]}));
+        assert_eq!(target, json!({"a": {"b": 10}, "d": 3, "e": [1]}));
+    }
+
+    #[test]
+    fn apply_overrides_only_patched_fields() {
+        let experiment = Experiment {
+            name: "tweak".to_string(),
+            description: None,
+            priors: Some(
+                json!({"classes": {"abandoned": {"prior_prob": 0.3}, "useful": {"prior_prob": 0.6}}}),
+            ),
+            policy: None,
+        };
+        let priors = Priors::default();
+        let policy = Policy::default();
+        // Keep the class priors summing to one after the tweak.
+        let mut baseline = priors.clone();
+        baseline.classes.useful_bad.prior_prob = 0.05;
+        baseline.classes.zombie.prior_prob = 0.05;
+        let (merged, merged_policy) = experiment.apply(&baseline, &policy).unwrap();
+        assert_eq!(merged.classes.abandoned.prior_prob, 0.3);
+        assert_eq!(merged.classes.useful.prior_prob, 0.6);
+        assert_eq!(
+            merged.classes.useful.cpu_beta.alpha,
+            baseline.classes.useful.cpu_beta.alpha
+        );
+        assert_eq!(
+            serde_json::to_value(&merged_policy).unwrap(),
+            serde_json::to_value(&policy).unwrap()
+        );
+    }
+
+    #[test]
+    fn apply_rejects_invalid_result() {
+        let experiment = Experiment {
+            name: "broken".to_string(),
+            description: None,
+            priors: Some(json!({"classes": {"abandoned": {"prior_prob": -1.0}}})),
+            policy: None,
+        };
+        let err = experiment
+            .apply(&Priors::default(), &Policy::default())
+            .unwrap_err();
+        assert!(matches!(err, ExperimentError::Validation(_)));
+    }
+
+    #[test]
+    fn validate_rejects_bad_names_and_empty_overlays() {
+        let mut experiment = Experiment {
+            name: "../escape".to_string(),
+            description: None,
+            priors: Some(json!({})),
+            policy: None,
+        };
+        assert!(matches!(
+            experiment.validate(),
+            Err(ExperimentError::InvalidName(_))
+        ));
+        experiment.name = "ok".to_string();
+        experiment.priors = None;
+        assert!(matches!(
+            experiment.validate(),
+            Err(ExperimentError::Empty(_))
+        ));
+    }
+}
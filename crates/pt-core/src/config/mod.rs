@@ -6,6 +6,12 @@
 //! - Schema validation (shape/type checking via serde)
 //! - Semantic validation (probability sums, positive params)
 //! - Config snapshot generation for session artifacts
+//! - Session-scoped experiment overlays (`experiment`)
+//! - Partial pooling toward host-profile group priors (`pooling`)
+//! - Per-group priors for fleet host groups (`groups/<group>.json`)
+
+pub mod experiment;
+pub mod pooling;
 
 // Re-export types from pt-config
 pub use pt_config::policy;
@@ -14,6 +20,8 @@ pub use pt_config::priors;
 pub use policy::Policy;
 pub use priors::Priors;
 
+pub use experiment::{Experiment, ExperimentError};
+
 pub use pt_config::validate::ValidationError;
 use pt_config::validate::{validate_policy, validate_priors};
 
@@ -31,6 +39,9 @@ pub const CONFIG_SCHEMA_VERSION: &str = "1.0.0";
 /// Default XDG config directory name.
 const CONFIG_DIR_NAME: &str = "process_triage";
 
+/// Subdirectory of the config dir holding fleet host-group priors.
+pub const GROUP_PRIORS_DIR: &str = "groups";
+
 /// Errors that can occur during config loading.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -151,6 +162,33 @@ pub fn load_config(options: &ConfigOptions) -> Result<ResolvedConfig, ConfigErro
     })
 }
 
+/// Load the priors of a fleet host group from `<config_dir>/groups/<group>.json`.
+///
+/// Returns `Ok(None)` when the group has no priors file, or when the group
+/// name is not a plain file name.
+pub fn load_group_priors(
+    config_dir: &std::path::Path,
+    group: &str,
+) -> Result<Option<(Priors, PathBuf)>, ConfigError> {
+    let plain = !group.is_empty()
+        && !group.starts_with('.')
+        && group
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !plain {
+        return Ok(None);
+    }
+    let path = config_dir
+        .join(GROUP_PRIORS_DIR)
+        .join(format!("{group}.json"));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let (priors, _hash) = load_priors_from_file(&path)?;
+    validate_priors(&priors)?;
+    Ok(Some((priors, path)))
+}
+
 /// Resolve the config directory using the standard resolution order.
 fn resolve_config_dir(options: &ConfigOptions) -> Result<PathBuf, ConfigError> {
     // 1. Explicit option
@@ -305,6 +343,27 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_group_priors_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let groups = dir.path().join(GROUP_PRIORS_DIR);
+        std::fs::create_dir_all(&groups).unwrap();
+        let mut priors = Priors::default();
+        priors.classes.abandoned.prior_prob = 0.3;
+        priors.classes.useful.prior_prob = 0.6;
+        std::fs::write(
+            groups.join("ci.json"),
+            serde_json::to_string(&priors).unwrap(),
+        )
+        .unwrap();
+
+        let (loaded, path) = load_group_priors(dir.path(), "ci").unwrap().unwrap();
+        assert_eq!(path, groups.join("ci.json"));
+        assert!((loaded.classes.abandoned.prior_prob - 0.3).abs() < 1e-12);
+        assert!(load_group_priors(dir.path(), "db").unwrap().is_none());
+        assert!(load_group_priors(dir.path(), "../ci").unwrap().is_none());
+    }
+
     #[test]
     fn test_config_snapshot_serializes() {
         let options = empty_config_options();
@@ -0,0 +1,229 @@
+//! Partial pooling of priors toward a host-profile group.
+//!
+//! `agent import-priors --host-profile <profile> --shrink` treats the imported
+//! file as the group-level estimate for that profile (e.g. priors exported
+//! from a fleet of CI runners) and shrinks the local priors toward it with
+//! [`pt_math::hierarchical`], instead of replacing or overwriting classes.
+//! Well-evidenced local parameters move little; weak ones land near the group.
+
+use pt_math::bernoulli::BetaParams as MathBeta;
+use pt_math::hierarchical::{pool_beta, pool_gamma, pool_probability, HostProfile};
+
+use super::priors::{BetaParams, ClassParams, GammaParams};
+use super::Priors;
+
+/// Pooling strength to use: explicit value, then the priors'
+/// `hierarchical.shrinkage_strength`, then the profile default.
+pub fn resolve_strength(explicit: Option<f64>, local: &Priors, profile: HostProfile) -> f64 {
+    explicit
+        .or_else(|| {
+            local
+                .hierarchical
+                .as_ref()
+                .filter(|h| h.shrinkage_enabled != Some(false))
+                .and_then(|h| h.shrinkage_strength)
+        })
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .unwrap_or_else(|| profile.default_strength())
+}
+
+/// Priors for hosts of a fleet host group.
+///
+/// With a host profile, `local` is shrunk toward the group priors at the
+/// resolved strength; without one the group priors are used as they are.
+pub fn group_priors(local: &Priors, group: &Priors, profile: Option<HostProfile>) -> Priors {
+    match profile {
+        Some(profile) => {
+            let strength = resolve_strength(None, local, profile);
+            shrink_toward_group(local, group, profile, strength)
+        }
+        None => group.clone(),
+    }
+}
+
+/// Shrink `local` toward the group priors `group` with strength `κ`.
+///
+/// Class probabilities are pooled using each class's `cpu_beta` pseudo-count
+/// as its effective sample size, then renormalised. Sections other than the
+/// class parameters are kept from `local`.
+pub fn shrink_toward_group(
+    local: &Priors,
+    group: &Priors,
+    profile: HostProfile,
+    strength: f64,
+) -> Priors {
+    let mut pooled = local.clone();
+    let classes = &mut pooled.classes;
+    for (local_class, group_class) in [
+        (&mut classes.useful, &group.classes.useful),
+        (&mut classes.useful_bad, &group.classes.useful_bad),
+        (&mut classes.abandoned, &group.classes.abandoned),
+        (&mut classes.zombie, &group.classes.zombie),
+    ] {
+        shrink_class(local_class, group_class, strength);
+    }
+
+    let total = classes.useful.prior_prob
+        + classes.useful_bad.prior_prob
+        + classes.abandoned.prior_prob
+        + classes.zombie.prior_prob;
+    if total > 0.0 {
+        classes.useful.prior_prob /= total;
+        classes.useful_bad.prior_prob /= total;
+        classes.abandoned.prior_prob /= total;
+        classes.zombie.prior_prob /= total;
+    }
+
+    pooled.host_profile = Some(profile.to_string());
+    pooled
+}
+
+fn shrink_class(local: &mut ClassParams, group: &ClassParams, strength: f64) {
+    let effective_n = local.cpu_beta.alpha + local.cpu_beta.beta;
+    local.prior_prob = pool_probability(local.prior_prob, group.prior_prob, effective_n, strength);
+
+    shrink_beta(&mut local.cpu_beta, &group.cpu_beta, strength);
+    shrink_beta(&mut local.orphan_beta, &group.orphan_beta, strength);
+    shrink_beta(&mut local.tty_beta, &group.tty_beta, strength);
+    shrink_beta(&mut local.net_beta, &group.net_beta, strength);
+    for (local_beta, group_beta) in [
+        (&mut local.io_active_beta, &group.io_active_beta),
+        (&mut local.gpu_beta, &group.gpu_beta),
+        (&mut local.gpu_util_beta, &group.gpu_util_beta),
+    ] {
+        shrink_optional_beta(local_beta, group_beta, strength);
+    }
+    for (local_gamma, group_gamma) in [
+        (&mut local.runtime_gamma, &group.runtime_gamma),
+        (
+            &mut local.runtime_surprise_gamma,
+            &group.runtime_surprise_gamma,
+        ),
+        (&mut local.hazard_gamma, &group.hazard_gamma),
+    ] {
+        shrink_optional_gamma(local_gamma, group_gamma, strength);
+    }
+}
+
+fn shrink_beta(local: &mut BetaParams, group: &BetaParams, strength: f64) {
+    let (Some(host), Some(group)) = (
+        MathBeta::new(local.alpha, local.beta),
+        MathBeta::new(group.alpha, group.beta),
+    ) else {
+        return;
+    };
+    if let Some(pooled) = pool_beta(&host, &group, strength) {
+        local.alpha = pooled.alpha;
+        local.beta = pooled.beta;
+    }
+}
+
+fn shrink_optional_beta(local: &mut Option<BetaParams>, group: &Option<BetaParams>, strength: f64) {
+    match (local.as_mut(), group) {
+        (Some(local), Some(group)) => shrink_beta(local, group, strength),
+        // The host has no estimate of its own: adopt the group's.
+        (None, Some(group)) => *local = Some(group.clone()),
+        _ => {}
+    }
+}
+
+fn shrink_optional_gamma(
+    local: &mut Option<GammaParams>,
+    group: &Option<GammaParams>,
+    strength: f64,
+) {
+    match (local.as_mut(), group) {
+        (Some(local), Some(group)) => {
+            if let Some((shape, rate)) =
+                pool_gamma(local.shape, local.rate, group.shape, group.rate, strength)
+            {
+                local.shape = shape;
+                local.rate = rate;
+            }
+        }
+        (None, Some(group)) => *local = Some(group.clone()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::priors::HierarchicalParams;
+
+    #[test]
+    fn shrink_moves_means_toward_group_and_keeps_sum() {
+        let local = Priors::default();
+        let mut group = Priors::default();
+        group.classes.abandoned.prior_prob = 0.4;
+        group.classes.useful.prior_prob = 0.4;
+        group.classes.abandoned.cpu_beta = BetaParams::new(1.0, 99.0);
+
+        let pooled = shrink_toward_group(&local, &group, HostProfile::CiRunner, 30.0);
+
+        let before = local.classes.abandoned.cpu_beta.mean();
+        let after = pooled.classes.abandoned.cpu_beta.mean();
+        assert!(after < before);
+        assert!(after > 0.01);
+        assert!(pooled.classes.abandoned.prior_prob > local.classes.abandoned.prior_prob);
+
+        let sum = pooled.classes.useful.prior_prob
+            + pooled.classes.useful_bad.prior_prob
+            + pooled.classes.abandoned.prior_prob
+            + pooled.classes.zombie.prior_prob;
+        assert!((sum - 1.0).abs() < 1e-9);
+        assert_eq!(pooled.host_profile.as_deref(), Some("ci_runner"));
+    }
+
+    #[test]
+    fn zero_strength_keeps_local_classes() {
+        let local = Priors::default();
+        let mut group = Priors::default();
+        group.classes.zombie.cpu_beta = BetaParams::new(50.0, 1.0);
+
+        let pooled = shrink_toward_group(&local, &group, HostProfile::Laptop, 0.0);
+        assert_eq!(
+            pooled.classes.zombie.cpu_beta,
+            local.classes.zombie.cpu_beta
+        );
+    }
+
+    #[test]
+    fn group_priors_replace_without_profile() {
+        let local = Priors::default();
+        let mut group = Priors::default();
+        group.classes.zombie.prior_prob = 0.2;
+        group.classes.useful.prior_prob = 0.55;
+
+        let replaced = group_priors(&local, &group, None);
+        assert_eq!(replaced.classes.zombie.prior_prob, 0.2);
+
+        let pooled = group_priors(&local, &group, Some(HostProfile::Server));
+        assert!(pooled.classes.zombie.prior_prob > local.classes.zombie.prior_prob);
+        assert!(pooled.classes.zombie.prior_prob < 0.2);
+    }
+
+    #[test]
+    fn strength_resolution_order() {
+        let mut local = Priors {
+            hierarchical: None,
+            ..Default::default()
+        };
+        assert_eq!(resolve_strength(None, &local, HostProfile::Laptop), 5.0);
+        assert_eq!(
+            resolve_strength(Some(2.0), &local, HostProfile::Laptop),
+            2.0
+        );
+
+        local.hierarchical = Some(HierarchicalParams {
+            shrinkage_enabled: Some(true),
+            shrinkage_strength: Some(12.0),
+            comment: None,
+        });
+        assert_eq!(resolve_strength(None, &local, HostProfile::Laptop), 12.0);
+        assert_eq!(
+            resolve_strength(Some(-1.0), &local, HostProfile::Laptop),
+            5.0
+        );
+    }
+}
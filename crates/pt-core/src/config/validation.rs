@@ -223,10 +223,7 @@ pub fn validate_priors(priors: &Priors) -> Result<(), ValidationError> {
 }
 
 /// Validate class priors.
-fn validate_class_priors(
-    class: &ClassParams,
-    name: &str,
-) -> Result<(), ValidationError> {
+fn validate_class_priors(class: &ClassParams, name: &str) -> Result<(), ValidationError> {
     // Validate prior probability range
     if !(0.0..=1.0).contains(&class.prior_prob) {
         return Err(ValidationError::PriorProbabilityRange {
@@ -244,11 +241,20 @@ fn validate_class_priors(
     if let Some(b) = &class.io_active_beta {
         validate_beta(b, &format!("{}.io_active_beta", name))?;
     }
+    if let Some(b) = &class.gpu_beta {
+        validate_beta(b, &format!("{}.gpu_beta", name))?;
+    }
+    if let Some(b) = &class.gpu_util_beta {
+        validate_beta(b, &format!("{}.gpu_util_beta", name))?;
+    }
 
     // Validate Gamma parameters
     if let Some(g) = &class.runtime_gamma {
         validate_gamma(g, &format!("{}.runtime_gamma", name))?;
     }
+    if let Some(g) = &class.runtime_surprise_gamma {
+        validate_gamma(g, &format!("{}.runtime_surprise_gamma", name))?;
+    }
     if let Some(g) = &class.hazard_gamma {
         validate_gamma(g, &format!("{}.hazard_gamma", name))?;
     }
@@ -385,8 +391,10 @@ fn validate_load_aware(
         return Ok(());
     }
 
-    let weight_sum =
-        load_aware.weights.queue + load_aware.weights.load + load_aware.weights.memory + load_aware.weights.psi;
+    let weight_sum = load_aware.weights.queue
+        + load_aware.weights.load
+        + load_aware.weights.memory
+        + load_aware.weights.psi;
     if weight_sum <= 0.0 {
         return Err(ValidationError::InvalidValue {
             field: "load_aware.weights".to_string(),
@@ -437,8 +445,7 @@ fn validate_load_aware(
             message: "must be >= 1.0".to_string(),
         });
     }
-    if load_aware.multipliers.reversible_min <= 0.0 || load_aware.multipliers.reversible_min > 1.0
-    {
+    if load_aware.multipliers.reversible_min <= 0.0 || load_aware.multipliers.reversible_min > 1.0 {
         return Err(ValidationError::InvalidValue {
             field: "load_aware.multipliers.reversible_min".to_string(),
             message: "must be in (0, 1]".to_string(),
@@ -1,18 +1,23 @@
 //! Prometheus metrics endpoint for the dormant daemon.
 //!
 //! Exposes operational metrics at `/metrics` (configurable) in Prometheus
-//! exposition format. Runs a lightweight HTTP server on a background thread.
+//! exposition format, or OpenMetrics text when the scraper asks for it via
+//! `Accept: application/openmetrics-text`. Runs a lightweight HTTP server on
+//! a background thread.
 //!
 //! ## Metrics
 //!
 //! **Counters:**
 //! - `pt_scans_total` — total scans by status (success/failed)
-//! - `pt_kills_total` — kills by outcome and classification
+//! - `pt_actions_total` — actions taken by outcome status
 //! - `pt_escalations_total` — escalation events by type
 //! - `pt_errors_total` — errors by type
 //!
 //! **Gauges:**
 //! - `pt_candidates_current` — current candidate count by classification
+//! - `pt_fdr_alpha_wealth` — remaining alpha-investing wealth (FDR budget)
+//! - `pt_fdr_budget_consumed_ratio` — fraction of the initial wealth spent
+//! - `pt_shadow_calibration_*` — shadow-mode calibration (ECE, Brier, AUC)
 //! - `pt_daemon_uptime_seconds` — daemon uptime
 //! - `pt_last_scan_timestamp` — unix timestamp of last scan
 //! - `pt_load_average` — system load averages (1m, 5m)
@@ -27,11 +32,15 @@
 //! **Info:**
 //! - `pt_build_info` — version, commit, build date
 
+use crate::calibrate::CalibrationMetrics;
+use crate::session::{SessionSummary, ACTION_DIR};
 use prometheus::{
-    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
-    Registry, TextEncoder,
+    Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry, TextEncoder,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Seek, SeekFrom};
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -39,8 +48,15 @@ use std::thread;
 use std::time::Instant;
 use tracing::{debug, error, info, warn};
 
+/// Content type for the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Content type for the OpenMetrics text format.
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
 /// Configuration for the metrics endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MetricsConfig {
     /// Enable the metrics server.
     pub enabled: bool,
@@ -52,6 +68,8 @@ pub struct MetricsConfig {
     pub path: String,
     /// Max scrapes per second from a single client.
     pub rate_limit_per_sec: u32,
+    /// Minimum seconds between shadow-mode calibration refreshes.
+    pub calibration_refresh_secs: u64,
 }
 
 impl Default for MetricsConfig {
@@ -62,6 +80,7 @@ impl Default for MetricsConfig {
             port: 9184,
             path: "/metrics".to_string(),
             rate_limit_per_sec: 10,
+            calibration_refresh_secs: 600,
         }
     }
 }
@@ -75,6 +94,7 @@ pub struct DaemonMetrics {
 
     // Counters
     pub scans_total: IntCounterVec,
+    pub actions_total: IntCounterVec,
     pub escalations_total: IntCounterVec,
     pub errors_total: IntCounterVec,
 
@@ -91,6 +111,14 @@ pub struct DaemonMetrics {
     pub process_count: IntGauge,
     pub orphan_count: IntGauge,
     pub candidates_current: IntGaugeVec,
+    pub fdr_alpha_wealth: Gauge,
+    pub fdr_alpha_wealth_initial: Gauge,
+    pub fdr_budget_consumed_ratio: Gauge,
+    pub shadow_observations: IntGauge,
+    pub shadow_resolved_observations: IntGauge,
+    pub shadow_calibration_ece: Gauge,
+    pub shadow_calibration_brier: Gauge,
+    pub shadow_calibration_auc_roc: Gauge,
 
     // Histograms
     pub scan_duration_seconds: HistogramVec,
@@ -112,6 +140,12 @@ impl DaemonMetrics {
         )?;
         registry.register(Box::new(scans_total.clone()))?;
 
+        let actions_total = IntCounterVec::new(
+            Opts::new("pt_actions_total", "Total actions taken by outcome status"),
+            &["status"],
+        )?;
+        registry.register(Box::new(actions_total.clone()))?;
+
         let escalations_total = IntCounterVec::new(
             Opts::new("pt_escalations_total", "Total escalation events"),
             &["type"],
@@ -175,6 +209,50 @@ impl DaemonMetrics {
         )?;
         registry.register(Box::new(candidates_current.clone()))?;
 
+        let fdr_alpha_wealth = Gauge::new(
+            "pt_fdr_alpha_wealth",
+            "Remaining alpha-investing wealth (FDR budget)",
+        )?;
+        registry.register(Box::new(fdr_alpha_wealth.clone()))?;
+
+        let fdr_alpha_wealth_initial = Gauge::new(
+            "pt_fdr_alpha_wealth_initial",
+            "Initial alpha-investing wealth (w0) from policy",
+        )?;
+        registry.register(Box::new(fdr_alpha_wealth_initial.clone()))?;
+
+        let fdr_budget_consumed_ratio = Gauge::new(
+            "pt_fdr_budget_consumed_ratio",
+            "Fraction of the initial alpha-investing wealth spent",
+        )?;
+        registry.register(Box::new(fdr_budget_consumed_ratio.clone()))?;
+
+        let shadow_observations =
+            IntGauge::new("pt_shadow_observations", "Shadow-mode observations on disk")?;
+        registry.register(Box::new(shadow_observations.clone()))?;
+
+        let shadow_resolved_observations = IntGauge::new(
+            "pt_shadow_resolved_observations",
+            "Shadow-mode predictions with a resolved outcome",
+        )?;
+        registry.register(Box::new(shadow_resolved_observations.clone()))?;
+
+        let shadow_calibration_ece = Gauge::new(
+            "pt_shadow_calibration_ece",
+            "Shadow-mode expected calibration error",
+        )?;
+        registry.register(Box::new(shadow_calibration_ece.clone()))?;
+
+        let shadow_calibration_brier =
+            Gauge::new("pt_shadow_calibration_brier", "Shadow-mode Brier score")?;
+        registry.register(Box::new(shadow_calibration_brier.clone()))?;
+
+        let shadow_calibration_auc_roc = Gauge::new(
+            "pt_shadow_calibration_auc_roc",
+            "Shadow-mode area under the ROC curve",
+        )?;
+        registry.register(Box::new(shadow_calibration_auc_roc.clone()))?;
+
         // -- Histograms --
         let scan_duration_seconds = HistogramVec::new(
             HistogramOpts::new("pt_scan_duration_seconds", "Scan duration in seconds")
@@ -205,6 +283,7 @@ impl DaemonMetrics {
         Ok(Self {
             registry,
             scans_total,
+            actions_total,
             escalations_total,
             errors_total,
             daemon_uptime_seconds,
@@ -219,6 +298,14 @@ impl DaemonMetrics {
             process_count,
             orphan_count,
             candidates_current,
+            fdr_alpha_wealth,
+            fdr_alpha_wealth_initial,
+            fdr_budget_consumed_ratio,
+            shadow_observations,
+            shadow_resolved_observations,
+            shadow_calibration_ece,
+            shadow_calibration_brier,
+            shadow_calibration_auc_roc,
             scan_duration_seconds,
             tick_duration_seconds,
             started_at: Instant::now(),
@@ -261,6 +348,57 @@ impl DaemonMetrics {
         }
     }
 
+    /// Record actions taken with the given outcome status.
+    pub fn record_actions(&self, status: &str, count: u64) {
+        self.actions_total
+            .with_label_values(&[status])
+            .inc_by(count);
+    }
+
+    /// Replace the current candidate counts by classification.
+    pub fn set_candidates<'a, I>(&self, counts: I)
+    where
+        I: IntoIterator<Item = (&'a str, u64)>,
+    {
+        self.candidates_current.reset();
+        for (classification, count) in counts {
+            self.candidates_current
+                .with_label_values(&[classification])
+                .set(count as i64);
+        }
+    }
+
+    /// Update the alpha-investing FDR budget gauges.
+    pub fn update_fdr_budget(&self, wealth: f64, initial_wealth: f64) {
+        self.fdr_alpha_wealth.set(wealth);
+        self.fdr_alpha_wealth_initial.set(initial_wealth);
+        let consumed = if initial_wealth > 0.0 {
+            (1.0 - wealth / initial_wealth).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.fdr_budget_consumed_ratio.set(consumed);
+    }
+
+    /// Update the shadow-mode calibration gauges.
+    ///
+    /// `calibration` is `None` while too few predictions have resolved to
+    /// compute calibration metrics; only the observation counts are updated.
+    pub fn update_shadow_calibration(
+        &self,
+        observations: usize,
+        calibration: Option<&CalibrationMetrics>,
+    ) {
+        self.shadow_observations.set(observations as i64);
+        if let Some(cal) = calibration {
+            self.shadow_resolved_observations
+                .set(cal.sample_count as i64);
+            self.shadow_calibration_ece.set(cal.ece);
+            self.shadow_calibration_brier.set(cal.brier_score);
+            self.shadow_calibration_auc_roc.set(cal.auc_roc);
+        }
+    }
+
     /// Record an escalation event.
     pub fn record_escalation(&self, escalation_type: &str) {
         self.escalations_total
@@ -292,6 +430,116 @@ impl DaemonMetrics {
         encoder.encode(&metric_families, &mut buffer)?;
         Ok(String::from_utf8_lossy(&buffer).into_owned())
     }
+
+    /// Render all metrics in OpenMetrics text format.
+    pub fn render_openmetrics(&self) -> Result<String, prometheus::Error> {
+        Ok(to_openmetrics(&self.render()?))
+    }
+}
+
+/// Convert Prometheus text exposition output to OpenMetrics text.
+///
+/// The two formats share sample syntax; OpenMetrics names counter families
+/// without the `_total` suffix in `HELP`/`TYPE` metadata and requires a
+/// trailing `# EOF`.
+fn to_openmetrics(text: &str) -> String {
+    let counters: Vec<&str> = text
+        .lines()
+        .filter_map(|line| line.strip_prefix("# TYPE "))
+        .filter_map(|rest| rest.strip_suffix(" counter"))
+        .collect();
+
+    let mut out = String::with_capacity(text.len() + 8);
+    for line in text.lines() {
+        let meta = line
+            .strip_prefix("# HELP ")
+            .map(|rest| ("# HELP ", rest))
+            .or_else(|| line.strip_prefix("# TYPE ").map(|rest| ("# TYPE ", rest)));
+        match meta {
+            Some((prefix, rest)) => {
+                let (name, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+                let family = if counters.contains(&name) {
+                    name.strip_suffix("_total").unwrap_or(name)
+                } else {
+                    name
+                };
+                out.push_str(prefix);
+                out.push_str(family);
+                out.push(' ');
+                out.push_str(tail);
+            }
+            None => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Counts `action/outcomes.jsonl` entries appended since the last poll.
+///
+/// Keeps a byte offset per session so each poll only reads new lines;
+/// sessions that drop out of the listing are forgotten.
+#[derive(Debug, Default)]
+pub struct ActionOutcomeTail {
+    offsets: HashMap<String, u64>,
+}
+
+impl ActionOutcomeTail {
+    /// Start tailing from the current end of every session's outcomes, so
+    /// actions taken before the daemon started are not counted.
+    pub fn seeded(sessions: &[SessionSummary]) -> Self {
+        let mut tail = Self::default();
+        tail.poll(sessions);
+        tail
+    }
+
+    /// Count new outcome entries by status.
+    pub fn poll(&mut self, sessions: &[SessionSummary]) -> BTreeMap<String, u64> {
+        let mut counts = BTreeMap::new();
+        self.offsets
+            .retain(|id, _| sessions.iter().any(|s| &s.session_id == id));
+
+        for summary in sessions {
+            let path = summary.path.join(ACTION_DIR).join("outcomes.jsonl");
+            let Ok(mut file) = std::fs::File::open(&path) else {
+                continue;
+            };
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            let offset = self
+                .offsets
+                .get(&summary.session_id)
+                .copied()
+                .filter(|&offset| offset <= len)
+                .unwrap_or(0);
+            if offset == len {
+                self.offsets.insert(summary.session_id.clone(), len);
+                continue;
+            }
+
+            let mut appended = String::new();
+            if file.seek(SeekFrom::Start(offset)).is_err()
+                || file.read_to_string(&mut appended).is_err()
+            {
+                continue;
+            }
+            // Leave a partially written trailing line for the next poll.
+            let complete = appended.rfind('\n').map(|i| i + 1).unwrap_or(0);
+            for line in appended[..complete]
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+            {
+                let status = serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|v| v.get("status").and_then(|s| s.as_str()).map(String::from))
+                    .unwrap_or_else(|| "unknown".to_string());
+                *counts.entry(status).or_insert(0) += 1;
+            }
+            self.offsets
+                .insert(summary.session_id.clone(), offset + complete as u64);
+        }
+        counts
+    }
 }
 
 impl Default for DaemonMetrics {
@@ -398,12 +646,22 @@ fn serve_loop(
         debug!(method = %request.method(), url = %url, "metrics scrape");
 
         if url == path || url == format!("{}/", path) {
-            match metrics.render() {
+            let openmetrics = request.headers().iter().any(|h| {
+                h.field.equiv("Accept") && h.value.as_str().contains("application/openmetrics-text")
+            });
+            let (rendered, content_type) = if openmetrics {
+                (metrics.render_openmetrics(), OPENMETRICS_CONTENT_TYPE)
+            } else {
+                (metrics.render(), PROMETHEUS_CONTENT_TYPE)
+            };
+            match rendered {
                 Ok(body) => {
                     let response = tiny_http::Response::from_string(body).with_header(
-                        "Content-Type: text/plain; version=0.0.4; charset=utf-8"
-                            .parse::<tiny_http::Header>()
-                            .unwrap(),
+                        tiny_http::Header::from_bytes(
+                            &b"Content-Type"[..],
+                            content_type.as_bytes(),
+                        )
+                        .unwrap(),
                     );
                     if let Err(e) = request.respond(response) {
                         warn!(error = %e, "failed to send metrics response");
@@ -452,6 +710,7 @@ mod tests {
             swap_used_mb: 100,
             process_count: 300,
             orphan_count: 5,
+            psi: None,
         };
         let state = super::super::DaemonState {
             started_at: "2026-01-01T00:00:00Z".to_string(),
@@ -461,6 +720,7 @@ mod tests {
             escalation_count: 3,
             deferred_count: 1,
             recent_events: std::collections::VecDeque::new(),
+            schedule: Default::default(),
         };
 
         metrics.update_from_tick(&tick, &state);
@@ -524,6 +784,91 @@ mod tests {
         assert!(output.contains("# TYPE pt_scan_duration_seconds histogram"));
     }
 
+    #[test]
+    fn test_openmetrics_counter_families_and_eof() {
+        let metrics = DaemonMetrics::new().unwrap();
+        metrics.record_scan("success", 1.0);
+        metrics.record_actions("success", 2);
+
+        let output = metrics.render_openmetrics().unwrap();
+        assert!(output.contains("# TYPE pt_scans counter"));
+        assert!(output.contains("# HELP pt_actions Total actions"));
+        assert!(output.contains("pt_actions_total{status=\"success\"} 2"));
+        assert!(output.contains("# TYPE pt_scan_duration_seconds histogram"));
+        assert!(output.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_fdr_and_calibration_gauges() {
+        let metrics = DaemonMetrics::new().unwrap();
+        metrics.update_fdr_budget(0.03, 0.05);
+        metrics.set_candidates([("abandoned", 4), ("zombie", 1)]);
+        let cal = CalibrationMetrics {
+            ece: 0.07,
+            brier_score: 0.12,
+            sample_count: 40,
+            ..Default::default()
+        };
+        metrics.update_shadow_calibration(120, Some(&cal));
+
+        let output = metrics.render().unwrap();
+        assert!(output.contains("pt_fdr_alpha_wealth 0.03"));
+        assert!(output.contains("pt_fdr_budget_consumed_ratio 0.4"));
+        assert!(output.contains("pt_candidates_current{classification=\"abandoned\"} 4"));
+        assert!(output.contains("pt_shadow_observations 120"));
+        assert!(output.contains("pt_shadow_resolved_observations 40"));
+        assert!(output.contains("pt_shadow_calibration_ece 0.07"));
+
+        metrics.set_candidates([("abandoned", 2)]);
+        let output = metrics.render().unwrap();
+        assert!(!output.contains("classification=\"zombie\""));
+    }
+
+    #[test]
+    fn test_action_outcome_tail_counts_new_lines() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let action_dir = dir.path().join(ACTION_DIR);
+        std::fs::create_dir_all(&action_dir).unwrap();
+        let outcomes = action_dir.join("outcomes.jsonl");
+        std::fs::write(&outcomes, "{\"pid\":1,\"status\":\"success\"}\n").unwrap();
+
+        let sessions = vec![SessionSummary {
+            session_id: "pt-20260101-000000-abcd".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            state: crate::session::SessionState::Executing,
+            mode: crate::session::SessionMode::RobotPlan,
+            label: None,
+            host_id: None,
+            candidates_count: None,
+            actions_count: None,
+            path: dir.path().to_path_buf(),
+            experiment: None,
+        }];
+
+        let mut tail = ActionOutcomeTail::seeded(&sessions);
+        assert!(tail.poll(&sessions).is_empty());
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&outcomes)
+            .unwrap();
+        write!(
+            file,
+            "{{\"pid\":2,\"status\":\"success\"}}\n{{\"pid\":3,\"status\":\"failed\"}}\n{{\"pid\":4"
+        )
+        .unwrap();
+        let counts = tail.poll(&sessions);
+        assert_eq!(counts.get("success"), Some(&1));
+        assert_eq!(counts.get("failed"), Some(&1));
+
+        writeln!(file, ",\"status\":\"timeout\"}}").unwrap();
+        let counts = tail.poll(&sessions);
+        assert_eq!(counts.get("timeout"), Some(&1));
+        assert_eq!(counts.len(), 1);
+    }
+
     #[test]
     fn test_build_info_has_version() {
         let metrics = DaemonMetrics::new().unwrap();
@@ -539,6 +884,7 @@ mod tests {
             port: 0, // Let OS pick a port
             path: "/metrics".to_string(),
             rate_limit_per_sec: 10,
+            calibration_refresh_secs: 600,
         };
 
         let metrics = DaemonMetrics::new().unwrap();
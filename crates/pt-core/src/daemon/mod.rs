@@ -7,6 +7,8 @@
 //! - **Escalation**: orchestrates scan → infer → plan pipeline, writes inbox
 //!   items, respects per-user lock contention.
 //! - **Core loop**: tick-based event loop with overhead budgeting.
+//! - **Schedule**: PSI-driven tick interval and trigger thresholds, tighter
+//!   under pressure and backing off when idle.
 //!
 //! This module is intentionally *library-only*. The actual daemon binary /
 //! systemd integration lives in CLI/service layer code.
@@ -14,6 +16,7 @@
 pub mod escalation;
 #[cfg(feature = "metrics")]
 pub mod metrics;
+pub mod schedule;
 pub mod triggers;
 
 use chrono::Utc;
@@ -37,12 +40,19 @@ pub struct DaemonConfig {
     pub triggers: triggers::TriggerConfig,
     /// Escalation configuration.
     pub escalation: escalation::EscalationConfig,
+    /// PSI-driven adaptive scheduling.
+    #[serde(default)]
+    pub adaptive: schedule::AdaptiveScheduleConfig,
     /// Notification escalation ladder configuration.
     #[serde(default)]
     pub notification_ladder: crate::decision::escalation::EscalationConfig,
     /// Notification delivery configuration.
     #[serde(default)]
     pub notifications: DaemonNotificationsConfig,
+    /// Prometheus/OpenMetrics endpoint; disabled when absent.
+    #[cfg(feature = "metrics")]
+    #[serde(default)]
+    pub metrics: Option<metrics::MetricsConfig>,
 }
 
 /// Notification delivery settings for the daemon.
@@ -82,8 +92,11 @@ impl Default for DaemonConfig {
             max_rss_mb: 64,
             triggers: triggers::TriggerConfig::default(),
             escalation: escalation::EscalationConfig::default(),
+            adaptive: schedule::AdaptiveScheduleConfig::default(),
             notification_ladder: crate::decision::escalation::EscalationConfig::default(),
             notifications: DaemonNotificationsConfig::default(),
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -103,6 +116,9 @@ pub struct TickMetrics {
     pub swap_used_mb: u64,
     pub process_count: u32,
     pub orphan_count: u32,
+    /// Pressure Stall Information; absent on hosts without PSI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi: Option<schedule::PsiSnapshot>,
 }
 
 /// A daemon event for telemetry / audit.
@@ -128,6 +144,7 @@ pub enum DaemonEventType {
     LockContention,
     OverheadBudgetExceeded,
     ConfigReloaded,
+    ScheduleAdjusted,
 }
 
 /// Running state of the daemon core loop.
@@ -141,6 +158,9 @@ pub struct DaemonState {
     pub deferred_count: u32,
     /// Recent events for audit.
     pub recent_events: VecDeque<DaemonEvent>,
+    /// Adaptive tick schedule.
+    #[serde(default)]
+    pub schedule: schedule::AdaptiveSchedule,
 }
 
 impl Default for DaemonState {
@@ -159,6 +179,7 @@ impl DaemonState {
             escalation_count: 0,
             deferred_count: 0,
             recent_events: VecDeque::with_capacity(100),
+            schedule: schedule::AdaptiveSchedule::default(),
         }
     }
 
@@ -186,12 +207,16 @@ pub struct TickOutcome {
     pub triggers_fired: Vec<triggers::FiredTrigger>,
     pub escalation: Option<escalation::EscalationOutcome>,
     pub events: Vec<DaemonEvent>,
+    /// Pressure level the tick was evaluated at.
+    pub pressure: schedule::PressureLevel,
+    /// Seconds until the next tick.
+    pub next_interval_secs: u64,
 }
 
 /// Process one daemon tick.
 ///
-/// This is the core testable unit — it takes metrics, evaluates triggers,
-/// and decides whether to escalate. The actual metric collection and
+/// This is the core testable unit — it takes metrics, adapts the schedule
+/// to PSI, evaluates triggers, and decides whether to escalate. The actual metric collection and
 /// escalation execution are injected via callbacks for testability.
 pub fn process_tick<E>(
     config: &DaemonConfig,
@@ -212,8 +237,29 @@ where
 
     let mut events = Vec::new();
 
-    // 1) Evaluate triggers.
-    let fired = triggers::evaluate_triggers(&config.triggers, trigger_state, metrics);
+    // 1) Adapt interval and thresholds to host pressure.
+    let previous = state.schedule.update(
+        &config.adaptive,
+        config.tick_interval_secs,
+        metrics.psi.as_ref(),
+        &metrics.timestamp,
+    );
+    if let Some(previous) = previous {
+        let detail = format!(
+            "pressure {} -> {}, next tick in {}s",
+            previous, state.schedule.level, state.schedule.interval_secs
+        );
+        state.record_event(DaemonEventType::ScheduleAdjusted, &detail);
+        events.push(DaemonEvent {
+            timestamp: metrics.timestamp.clone(),
+            event_type: DaemonEventType::ScheduleAdjusted,
+            detail,
+        });
+    }
+
+    // 2) Evaluate triggers.
+    let trigger_config = state.schedule.scaled_triggers(&config.triggers);
+    let fired = triggers::evaluate_triggers(&trigger_config, trigger_state, metrics);
 
     for t in &fired {
         state.record_event(DaemonEventType::TriggerFired, &t.description);
@@ -224,7 +270,7 @@ where
         });
     }
 
-    // 2) Escalate if any triggers fired.
+    // 3) Escalate if any triggers fired.
     let escalation = if !fired.is_empty() {
         let outcome = escalate_fn(&config.escalation, &fired);
 
@@ -273,6 +319,8 @@ where
         triggers_fired: fired,
         escalation,
         events,
+        pressure: state.schedule.level,
+        next_interval_secs: state.schedule.interval_secs,
     }
 }
 
@@ -294,6 +342,7 @@ mod tests {
             swap_used_mb: 0,
             process_count: 200,
             orphan_count: orphans,
+            psi: None,
         }
     }
 
@@ -404,6 +453,55 @@ mod tests {
             .any(|event| event.event_type == DaemonEventType::EscalationFailed));
     }
 
+    #[test]
+    fn test_tick_under_critical_pressure_fires_early() {
+        let config = DaemonConfig::default();
+        let mut state = DaemonState::new();
+        let mut trig_state = triggers::TriggerState::new(&config.triggers);
+
+        // Load below the configured threshold, but the host is stalling.
+        let mut metrics = test_metrics(3.5, 2000, 5);
+        metrics.psi = Some(schedule::PsiSnapshot {
+            memory: schedule::PsiResource {
+                some_avg10: Some(55.0),
+                full_avg10: Some(20.0),
+            },
+            ..Default::default()
+        });
+        let outcome = process_tick(
+            &config,
+            &mut state,
+            &mut trig_state,
+            &metrics,
+            &mut |_, _| escalation::EscalationOutcome {
+                status: escalation::EscalationStatus::Completed,
+                reason: String::new(),
+                session_id: None,
+            },
+        );
+
+        assert_eq!(outcome.pressure, schedule::PressureLevel::Critical);
+        assert_eq!(outcome.next_interval_secs, 10);
+        assert_eq!(outcome.triggers_fired.len(), 1);
+        assert_eq!(
+            outcome.triggers_fired[0].kind,
+            triggers::TriggerKind::SustainedLoad
+        );
+        assert!(outcome
+            .events
+            .iter()
+            .any(|event| event.event_type == DaemonEventType::ScheduleAdjusted));
+        assert_eq!(state.schedule.level, schedule::PressureLevel::Critical);
+    }
+
+    #[test]
+    fn test_state_without_schedule_deserializes() {
+        let json = r#"{"started_at":"2026-01-01T00:00:00Z","tick_count":3,"last_tick_at":null,"last_escalation_at":null,"escalation_count":0,"deferred_count":0,"recent_events":[]}"#;
+        let state: DaemonState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.schedule.level, schedule::PressureLevel::Normal);
+        assert_eq!(state.schedule.next_interval_secs(60), 60);
+    }
+
     #[test]
     fn test_state_event_ring() {
         let mut state = DaemonState::new();
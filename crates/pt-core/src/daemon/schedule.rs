@@ -0,0 +1,488 @@
+//! PSI-driven adaptive tick scheduling.
+//!
+//! A fixed tick interval samples a struggling host too slowly and spends
+//! cycles on an idle one. Each tick reads Pressure Stall Information
+//! (`/proc/pressure/{cpu,memory,io}`, `some` and `full` avg10) and classifies
+//! the host:
+//!
+//! - **Critical**: any `full` avg10 ≥ `critical_full_avg10` or any `some`
+//!   avg10 ≥ `critical_some_avg10`. Ticks at `min_interval_secs`; trigger
+//!   thresholds are scaled by `critical_threshold_scale` and fire after one
+//!   tick.
+//! - **Elevated**: any `some` avg10 ≥ `elevated_some_avg10`. Ticks at half
+//!   the base interval; thresholds are scaled by `elevated_threshold_scale`
+//!   and need one fewer sustained tick.
+//! - **Normal**: base interval and thresholds.
+//! - **Idle**: every `some` avg10 below `idle_some_avg10`. After
+//!   `idle_ticks_before_backoff` idle ticks the interval grows by
+//!   `backoff_factor` per tick, up to `max_interval_secs`.
+//!
+//! Hosts without PSI stay at Normal. The schedule is part of
+//! [`super::DaemonState`] so it survives restarts and shows in
+//! `daemon status`.
+
+use pt_common::PsiInfo;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+use super::triggers::TriggerConfig;
+
+const PSI_DIR: &str = "/proc/pressure";
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Adaptive scheduling configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveScheduleConfig {
+    /// Adapt the interval and thresholds to PSI (base values when false).
+    pub enabled: bool,
+    /// Shortest interval between ticks (seconds).
+    pub min_interval_secs: u64,
+    /// Longest interval between ticks when backing off (seconds).
+    pub max_interval_secs: u64,
+    /// `some` avg10 (percent) at or above which pressure is elevated.
+    pub elevated_some_avg10: f64,
+    /// `some` avg10 (percent) at or above which pressure is critical.
+    pub critical_some_avg10: f64,
+    /// `full` avg10 (percent) at or above which pressure is critical.
+    pub critical_full_avg10: f64,
+    /// `some` avg10 (percent) below which every resource counts as idle.
+    pub idle_some_avg10: f64,
+    /// Consecutive idle ticks before the interval starts growing.
+    pub idle_ticks_before_backoff: u32,
+    /// Interval multiplier per idle tick once backing off.
+    pub backoff_factor: f64,
+    /// Trigger threshold multiplier under elevated pressure.
+    pub elevated_threshold_scale: f64,
+    /// Trigger threshold multiplier under critical pressure.
+    pub critical_threshold_scale: f64,
+}
+
+impl Default for AdaptiveScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_interval_secs: 10,
+            max_interval_secs: 300,
+            elevated_some_avg10: 10.0,
+            critical_some_avg10: 40.0,
+            critical_full_avg10: 10.0,
+            idle_some_avg10: 1.0,
+            idle_ticks_before_backoff: 5,
+            backoff_factor: 2.0,
+            elevated_threshold_scale: 0.85,
+            critical_threshold_scale: 0.7,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PSI sampling
+// ---------------------------------------------------------------------------
+
+/// `some` / `full` avg10 of one PSI resource, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PsiResource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub some_avg10: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_avg10: Option<f64>,
+}
+
+impl PsiResource {
+    /// Parse the content of a `/proc/pressure/<resource>` file.
+    pub fn parse(content: &str) -> Self {
+        let avg10 = |kind: &str| {
+            content
+                .lines()
+                .find(|l| l.split_whitespace().next() == Some(kind))?
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix("avg10="))
+                .and_then(|v| v.parse().ok())
+        };
+        Self {
+            some_avg10: avg10("some"),
+            full_avg10: avg10("full"),
+        }
+    }
+}
+
+/// PSI readings for one tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PsiSnapshot {
+    pub cpu: PsiResource,
+    pub memory: PsiResource,
+    pub io: PsiResource,
+}
+
+impl PsiSnapshot {
+    /// Read the resources `info` reports as available; `None` without PSI.
+    pub fn collect(info: &PsiInfo) -> Option<Self> {
+        Self::collect_from(Path::new(PSI_DIR), info)
+    }
+
+    fn collect_from(dir: &Path, info: &PsiInfo) -> Option<Self> {
+        if !info.available {
+            return None;
+        }
+        let read = |name: &str, available: Option<bool>| {
+            if available == Some(false) {
+                return PsiResource::default();
+            }
+            std::fs::read_to_string(dir.join(name))
+                .map(|content| PsiResource::parse(&content))
+                .unwrap_or_default()
+        };
+        Some(Self {
+            cpu: read("cpu", info.cpu),
+            memory: read("memory", info.memory),
+            io: read("io", info.io),
+        })
+    }
+
+    fn resources(&self) -> [&PsiResource; 3] {
+        [&self.cpu, &self.memory, &self.io]
+    }
+
+    /// Highest `some` avg10 across resources.
+    pub fn max_some(&self) -> Option<f64> {
+        self.resources()
+            .iter()
+            .filter_map(|r| r.some_avg10)
+            .reduce(f64::max)
+    }
+
+    /// Highest `full` avg10 across resources.
+    pub fn max_full(&self) -> Option<f64> {
+        self.resources()
+            .iter()
+            .filter_map(|r| r.full_avg10)
+            .reduce(f64::max)
+    }
+}
+
+/// Probe which PSI resources this host exposes.
+pub fn detect_psi() -> PsiInfo {
+    detect_psi_in(Path::new(PSI_DIR))
+}
+
+fn detect_psi_in(dir: &Path) -> PsiInfo {
+    let readable = |name: &str| std::fs::read_to_string(dir.join(name)).is_ok();
+    let (cpu, memory, io) = (readable("cpu"), readable("memory"), readable("io"));
+    PsiInfo {
+        available: cpu || memory || io,
+        cpu: Some(cpu),
+        memory: Some(memory),
+        io: Some(io),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Schedule state
+// ---------------------------------------------------------------------------
+
+/// Host pressure as seen through PSI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureLevel {
+    Idle,
+    #[default]
+    Normal,
+    Elevated,
+    Critical,
+}
+
+impl PressureLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PressureLevel::Idle => "idle",
+            PressureLevel::Normal => "normal",
+            PressureLevel::Elevated => "elevated",
+            PressureLevel::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for PressureLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classify a PSI snapshot; `Normal` without PSI.
+pub fn classify(config: &AdaptiveScheduleConfig, psi: Option<&PsiSnapshot>) -> PressureLevel {
+    let Some(psi) = psi else {
+        return PressureLevel::Normal;
+    };
+    let Some(some) = psi.max_some() else {
+        return PressureLevel::Normal;
+    };
+    let full = psi.max_full().unwrap_or(0.0);
+    if full >= config.critical_full_avg10 || some >= config.critical_some_avg10 {
+        PressureLevel::Critical
+    } else if some >= config.elevated_some_avg10 {
+        PressureLevel::Elevated
+    } else if some < config.idle_some_avg10 {
+        PressureLevel::Idle
+    } else {
+        PressureLevel::Normal
+    }
+}
+
+/// The adaptive schedule, persisted in daemon state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveSchedule {
+    pub level: PressureLevel,
+    /// Seconds until the next tick; 0 before the first tick.
+    pub interval_secs: u64,
+    /// Multiplier applied to trigger thresholds (1.0 = as configured).
+    pub threshold_scale: f64,
+    /// Consecutive idle ticks.
+    pub idle_streak: u32,
+    /// PSI readings of the last tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_psi: Option<PsiSnapshot>,
+    /// When the level last changed.
+    #[serde(default)]
+    pub changed_at: Option<String>,
+    /// Number of level changes.
+    #[serde(default)]
+    pub transitions: u64,
+}
+
+impl Default for AdaptiveSchedule {
+    fn default() -> Self {
+        Self {
+            level: PressureLevel::Normal,
+            interval_secs: 0,
+            threshold_scale: 1.0,
+            idle_streak: 0,
+            last_psi: None,
+            changed_at: None,
+            transitions: 0,
+        }
+    }
+}
+
+impl AdaptiveSchedule {
+    /// Adapt to this tick's PSI readings.
+    ///
+    /// Returns the previous level when the level changed.
+    pub fn update(
+        &mut self,
+        config: &AdaptiveScheduleConfig,
+        base_interval_secs: u64,
+        psi: Option<&PsiSnapshot>,
+        timestamp: &str,
+    ) -> Option<PressureLevel> {
+        self.last_psi = psi.copied();
+        let level = if config.enabled {
+            classify(config, psi)
+        } else {
+            PressureLevel::Normal
+        };
+
+        let base = base_interval_secs.max(1);
+        let min = config.min_interval_secs.clamp(1, base);
+        let max = config.max_interval_secs.max(base);
+        self.idle_streak = if level == PressureLevel::Idle {
+            self.idle_streak.saturating_add(1)
+        } else {
+            0
+        };
+        let (interval_secs, threshold_scale) = match level {
+            PressureLevel::Critical => (min, config.critical_threshold_scale),
+            PressureLevel::Elevated => {
+                ((base / 2).clamp(min, base), config.elevated_threshold_scale)
+            }
+            PressureLevel::Normal => (base, 1.0),
+            PressureLevel::Idle if self.idle_streak > config.idle_ticks_before_backoff => {
+                let factor = config.backoff_factor.max(1.0);
+                let grown = (self.interval_secs.max(base) as f64 * factor).round() as u64;
+                (grown.min(max), 1.0)
+            }
+            PressureLevel::Idle => (base, 1.0),
+        };
+        self.interval_secs = interval_secs;
+        self.threshold_scale = threshold_scale;
+
+        if level == self.level {
+            return None;
+        }
+        let previous = std::mem::replace(&mut self.level, level);
+        self.changed_at = Some(timestamp.to_string());
+        self.transitions += 1;
+        Some(previous)
+    }
+
+    /// Seconds to sleep before the next tick.
+    pub fn next_interval_secs(&self, base_interval_secs: u64) -> u64 {
+        if self.interval_secs == 0 {
+            base_interval_secs
+        } else {
+            self.interval_secs
+        }
+    }
+
+    /// `base` adjusted for the current pressure level.
+    pub fn scaled_triggers(&self, base: &TriggerConfig) -> TriggerConfig {
+        let scale = if self.threshold_scale.is_finite() && self.threshold_scale > 0.0 {
+            self.threshold_scale
+        } else {
+            1.0
+        };
+        let sustained_ticks = match self.level {
+            PressureLevel::Critical => 1,
+            PressureLevel::Elevated => base.sustained_ticks.saturating_sub(1).max(1),
+            PressureLevel::Normal | PressureLevel::Idle => base.sustained_ticks,
+        };
+        TriggerConfig {
+            load_threshold: base.load_threshold * scale,
+            memory_threshold: base.memory_threshold * scale,
+            orphan_threshold: ((base.orphan_threshold as f64 * scale).round() as u32).max(1),
+            sustained_ticks,
+            ..base.clone()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn psi(some: f64, full: f64) -> PsiSnapshot {
+        let resource = PsiResource {
+            some_avg10: Some(some),
+            full_avg10: Some(full),
+        };
+        PsiSnapshot {
+            cpu: PsiResource {
+                some_avg10: Some(0.0),
+                full_avg10: None,
+            },
+            memory: resource,
+            io: PsiResource::default(),
+        }
+    }
+
+    #[test]
+    fn parses_and_collects_psi() {
+        let content = "some avg10=12.50 avg60=3.00 avg300=1.00 total=100\nfull avg10=2.25 avg60=0.50 avg300=0.10 total=20\n";
+        let parsed = PsiResource::parse(content);
+        assert_eq!(parsed.some_avg10, Some(12.5));
+        assert_eq!(parsed.full_avg10, Some(2.25));
+
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("memory"), content).unwrap();
+        let info = detect_psi_in(dir.path());
+        assert!(info.available);
+        assert_eq!(info.cpu, Some(false));
+
+        let snapshot = PsiSnapshot::collect_from(dir.path(), &info).unwrap();
+        assert_eq!(snapshot.memory, parsed);
+        assert_eq!(snapshot.max_some(), Some(12.5));
+        assert_eq!(snapshot.cpu, PsiResource::default());
+
+        let empty = tempdir().unwrap();
+        let none = detect_psi_in(empty.path());
+        assert!(!none.available);
+        assert_eq!(PsiSnapshot::collect_from(empty.path(), &none), None);
+    }
+
+    #[test]
+    fn classifies_pressure() {
+        let config = AdaptiveScheduleConfig::default();
+        assert_eq!(classify(&config, None), PressureLevel::Normal);
+        assert_eq!(classify(&config, Some(&psi(0.2, 0.0))), PressureLevel::Idle);
+        assert_eq!(
+            classify(&config, Some(&psi(5.0, 0.0))),
+            PressureLevel::Normal
+        );
+        assert_eq!(
+            classify(&config, Some(&psi(15.0, 1.0))),
+            PressureLevel::Elevated
+        );
+        assert_eq!(
+            classify(&config, Some(&psi(15.0, 12.0))),
+            PressureLevel::Critical
+        );
+        assert_eq!(
+            classify(&config, Some(&psi(45.0, 0.0))),
+            PressureLevel::Critical
+        );
+    }
+
+    #[test]
+    fn pressure_shortens_interval_and_tightens_triggers() {
+        let config = AdaptiveScheduleConfig::default();
+        let base = TriggerConfig::default();
+        let mut schedule = AdaptiveSchedule::default();
+        assert_eq!(schedule.next_interval_secs(60), 60);
+
+        let previous = schedule.update(&config, 60, Some(&psi(15.0, 0.0)), "t1");
+        assert_eq!(previous, Some(PressureLevel::Normal));
+        assert_eq!(schedule.interval_secs, 30);
+        let elevated = schedule.scaled_triggers(&base);
+        assert_eq!(elevated.sustained_ticks, 2);
+        assert!(elevated.load_threshold < base.load_threshold);
+
+        schedule.update(&config, 60, Some(&psi(50.0, 20.0)), "t2");
+        assert_eq!(schedule.level, PressureLevel::Critical);
+        assert_eq!(schedule.interval_secs, 10);
+        assert_eq!(schedule.scaled_triggers(&base).sustained_ticks, 1);
+        assert_eq!(schedule.transitions, 2);
+        assert_eq!(schedule.changed_at.as_deref(), Some("t2"));
+
+        assert_eq!(
+            schedule.update(&config, 60, None, "t3"),
+            Some(PressureLevel::Critical)
+        );
+        assert_eq!(schedule.interval_secs, 60);
+        assert_eq!(
+            schedule.scaled_triggers(&base).load_threshold,
+            base.load_threshold
+        );
+    }
+
+    #[test]
+    fn idle_backs_off_up_to_max() {
+        let config = AdaptiveScheduleConfig::default();
+        let mut schedule = AdaptiveSchedule::default();
+        let idle = psi(0.1, 0.0);
+        let mut intervals = Vec::new();
+        for _ in 0..10 {
+            schedule.update(&config, 60, Some(&idle), "t");
+            intervals.push(schedule.interval_secs);
+        }
+        assert_eq!(intervals, vec![60, 60, 60, 60, 60, 120, 240, 300, 300, 300]);
+
+        schedule.update(&config, 60, Some(&psi(5.0, 0.0)), "t");
+        assert_eq!(schedule.interval_secs, 60);
+        assert_eq!(schedule.idle_streak, 0);
+    }
+
+    #[test]
+    fn disabled_keeps_base_schedule() {
+        let config = AdaptiveScheduleConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let mut schedule = AdaptiveSchedule::default();
+        assert_eq!(
+            schedule.update(&config, 60, Some(&psi(80.0, 50.0)), "t"),
+            None
+        );
+        assert_eq!(schedule.interval_secs, 60);
+        assert_eq!(schedule.threshold_scale, 1.0);
+        assert!(schedule.last_psi.is_some());
+    }
+}
@@ -263,6 +263,7 @@ mod tests {
             swap_used_mb: 0,
             process_count: 200,
             orphan_count: orphans,
+            psi: None,
         }
     }
 
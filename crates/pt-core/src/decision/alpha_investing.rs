@@ -105,6 +105,15 @@ impl AlphaInvestingStore {
         }
     }
 
+    /// Read the persisted wealth state without initializing it.
+    pub fn load(&self) -> Result<Option<AlphaWealthState>, AlphaInvestingError> {
+        if !self.state_path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&self.state_path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
     pub fn load_or_init(
         &self,
         policy: &Policy,
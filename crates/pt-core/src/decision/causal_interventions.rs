@@ -4,10 +4,10 @@ use crate::config::priors::{BetaParams, CausalInterventions, InterventionPriors,
 use crate::decision::Action;
 use crate::inference::ClassScores;
 use schemars::JsonSchema;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Process class labels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ProcessClass {
     Useful,
@@ -383,6 +383,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         assert!(recovery_table(&priors, Action::Pause).is_none());
     }
@@ -437,6 +444,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         let posterior = ClassScores {
             useful: 0.5,
@@ -500,6 +514,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         let posterior = ClassScores {
             useful: 0.25,
@@ -574,6 +595,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         let outcomes = vec![
             // Pause
@@ -644,8 +672,11 @@ mod tests {
                 comment: None,
             },
             io_active_beta: None,
+            gpu_beta: None,
+            gpu_util_beta: None,
             hazard_gamma: None,
             competing_hazards: None,
+            runtime_surprise_gamma: None,
         }
     }
 }
@@ -22,11 +22,15 @@
 //! }
 //! ```
 
-use crate::collect::{CriticalFile, DetectionStrength, ProcessState};
+use crate::collect::{
+    CriticalFile, DetectionStrength, PrivilegeInfo, PrivilegeLevel, ProcessState,
+};
 use crate::config::policy::{DataLossGates, PatternEntry, Policy, RobotMode};
+use crate::supervision::blast_radius::BlastRadius;
+use pt_config::rules::{compile_rules, CompiledRule, RuleEffect, RuleInput};
 use regex::Regex;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -133,6 +137,10 @@ pub enum ViolationKind {
     ForceReview,
     /// Process state prevents action (zombie/D-state).
     ProcessStateInvalid,
+    /// Process holds elevated privileges that require human review.
+    PrivilegedProcess,
+    /// An organization's custom rule matched.
+    CustomRule,
 }
 
 /// Information about a process candidate for policy checking.
@@ -174,6 +182,10 @@ pub struct ProcessCandidate {
     pub wchan: Option<String>,
     /// Critical files detected (for data-loss safety gate).
     pub critical_files: Vec<CriticalFile>,
+    /// Capabilities and setuid/setgid state (for privilege guardrails).
+    pub privileges: Option<PrivilegeInfo>,
+    /// Write handles, listening ports and connected clients (for data-loss gates).
+    pub blast_radius: Option<BlastRadius>,
 }
 
 /// Compiled pattern for efficient matching.
@@ -329,6 +341,8 @@ pub struct PolicyEnforcer {
     protected_patterns: Vec<CompiledPattern>,
     /// Compiled force-review patterns.
     force_review_patterns: Vec<CompiledPattern>,
+    /// Compiled custom rules, in policy order.
+    custom_rules: Vec<CompiledRule>,
     /// Protected users (lowercase for case-insensitive matching).
     protected_users: HashSet<String>,
     /// Protected groups (lowercase).
@@ -381,6 +395,9 @@ impl PolicyEnforcer {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        let custom_rules = compile_rules(&policy.custom_rules)
+            .map_err(|e| EnforcerError::PolicyInvalid(format!("custom_rules: {}", e)))?;
+
         // Build lookup sets (lowercase for case-insensitive matching)
         let protected_users: HashSet<String> = policy
             .guardrails
@@ -424,6 +441,7 @@ impl PolicyEnforcer {
         Ok(Self {
             protected_patterns,
             force_review_patterns,
+            custom_rules,
             protected_users,
             protected_groups,
             protected_categories,
@@ -533,6 +551,19 @@ impl PolicyEnforcer {
             }
         }
 
+        // Check privilege guardrails (blocks destructive actions in robot mode)
+        if is_destructive {
+            if let Some(ref privileges) = candidate.privileges {
+                match self.check_privilege_guardrails(candidate, privileges) {
+                    Some(violation) if robot_mode => {
+                        return PolicyCheckResult::blocked(violation);
+                    }
+                    Some(violation) => warnings.push(violation.message),
+                    None => {}
+                }
+            }
+        }
+
         // Check force-review patterns (only blocks in robot mode)
         for pattern in &self.force_review_patterns {
             if pattern.matches(&candidate.cmdline) {
@@ -557,6 +588,13 @@ impl PolicyEnforcer {
             }
         }
 
+        // Check custom rules (block, or force review in robot mode)
+        if let Some(violation) =
+            self.check_custom_rules(candidate, action, robot_mode, &mut warnings)
+        {
+            return PolicyCheckResult::blocked(violation);
+        }
+
         // Check minimum age (only for destructive actions)
         if is_destructive && candidate.age_seconds < self.min_age_seconds {
             return PolicyCheckResult::blocked(PolicyViolation {
@@ -639,6 +677,121 @@ impl PolicyEnforcer {
         result
     }
 
+    /// Evaluate custom rules in order; the first rule that blocks wins.
+    ///
+    /// A rule whose expression fails to evaluate is treated as matching, so a
+    /// broken rule errs on the side of not acting.
+    fn check_custom_rules(
+        &self,
+        candidate: &ProcessCandidate,
+        action: Action,
+        robot_mode: bool,
+        warnings: &mut Vec<String>,
+    ) -> Option<PolicyViolation> {
+        if self.custom_rules.is_empty() {
+            return None;
+        }
+        let input = RuleInput {
+            action: format!("{:?}", action).to_lowercase(),
+            pid: candidate.pid as i64,
+            ppid: candidate.ppid as i64,
+            user: candidate.user.clone(),
+            group: candidate.group.clone(),
+            category: candidate.category.clone(),
+            cmdline: candidate.cmdline.clone(),
+            age_seconds: candidate.age_seconds,
+            posterior: candidate.posterior,
+            memory_mb: candidate.memory_mb,
+            has_known_signature: candidate.has_known_signature,
+            robot_mode,
+        };
+        for rule in &self.custom_rules {
+            let (matched, error) = match rule.matches(&input) {
+                Ok(matched) => (matched, None),
+                Err(e) => (true, Some(e)),
+            };
+            if !matched {
+                continue;
+            }
+            let message = match (&error, &rule.message) {
+                (Some(e), _) => format!("custom rule '{}' failed to evaluate: {}", rule.name, e),
+                (None, Some(message)) => format!("custom rule '{}': {}", rule.name, message),
+                (None, None) => format!("custom rule '{}' matched", rule.name),
+            };
+            if rule.effect == RuleEffect::ForceReview && !robot_mode {
+                warnings.push(message);
+                continue;
+            }
+            return Some(PolicyViolation {
+                kind: match rule.effect {
+                    RuleEffect::Block => ViolationKind::CustomRule,
+                    RuleEffect::ForceReview => ViolationKind::ForceReview,
+                },
+                message,
+                rule: format!("custom_rules.{}", rule.name),
+                context: Some(format!("effect={}", rule.effect.as_str())),
+            });
+        }
+        None
+    }
+
+    /// Check privilege-based guardrails.
+    ///
+    /// Privileged daemons (a service category or known signature plus any
+    /// elevated privilege) are protected: killing one can take down host-wide
+    /// functionality. Highly-privileged orphans that match no known daemon are
+    /// unexpected and are flagged for review rather than handled automatically.
+    fn check_privilege_guardrails(
+        &self,
+        candidate: &ProcessCandidate,
+        privileges: &PrivilegeInfo,
+    ) -> Option<PolicyViolation> {
+        let level = privileges.level();
+        if level == PrivilegeLevel::Unprivileged {
+            return None;
+        }
+
+        let caps = privileges.effective_capabilities();
+        let context = Some(format!(
+            "privilege={} caps=[{}] setuid={} setgid={}",
+            level.as_str(),
+            caps.join(","),
+            privileges.exe_setuid,
+            privileges.exe_setgid
+        ));
+        let is_daemon = candidate
+            .category
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("daemon"));
+
+        if is_daemon || candidate.has_known_signature {
+            return Some(PolicyViolation {
+                kind: ViolationKind::PrivilegedProcess,
+                message: format!(
+                    "PID {} is a privileged daemon ({}): requires manual review",
+                    candidate.pid,
+                    level.as_str()
+                ),
+                rule: "guardrails.privileged_daemon".to_string(),
+                context,
+            });
+        }
+
+        if level == PrivilegeLevel::High && candidate.ppid == 1 {
+            return Some(PolicyViolation {
+                kind: ViolationKind::PrivilegedProcess,
+                message: format!(
+                    "PID {} is an unexpected highly-privileged orphan: requires manual review",
+                    candidate.pid
+                ),
+                rule: "guardrails.privileged_orphan".to_string(),
+                context,
+            });
+        }
+
+        None
+    }
+
     /// Check robot mode specific gates.
     fn check_robot_mode_gates(
         &self,
@@ -794,9 +947,14 @@ impl PolicyEnforcer {
             }
         }
 
+        let blast_radius = candidate.blast_radius.as_ref();
+
         // Check open write FDs
         if self.data_loss_gates.block_if_open_write_fds {
-            if let Some(fds) = candidate.open_write_fds {
+            let open_write_fds = candidate
+                .open_write_fds
+                .or_else(|| blast_radius.map(|br| br.write_handles.len() as u32));
+            if let Some(fds) = open_write_fds {
                 let max_fds = self.data_loss_gates.max_open_write_fds.unwrap_or(0);
                 if fds > max_fds {
                     // If we have soft critical files, include them in the message
@@ -833,7 +991,10 @@ impl PolicyEnforcer {
         }
 
         // Check locked files
-        if self.data_loss_gates.block_if_locked_files && candidate.has_locked_files == Some(true) {
+        let has_locked_files = candidate
+            .has_locked_files
+            .or_else(|| blast_radius.map(BlastRadius::holds_locks));
+        if self.data_loss_gates.block_if_locked_files && has_locked_files == Some(true) {
             return Some(PolicyViolation {
                 kind: ViolationKind::DataLossGate,
                 message: "process has locked files".to_string(),
@@ -881,6 +1042,43 @@ impl PolicyEnforcer {
             }
         }
 
+        // Check downstream clients
+        if self.data_loss_gates.block_if_serving_clients == Some(true) {
+            if let Some(br) = blast_radius.filter(|br| !br.client_connections.is_empty()) {
+                let ports: BTreeSet<u16> =
+                    br.client_connections.iter().map(|c| c.local_port).collect();
+                return Some(PolicyViolation {
+                    kind: ViolationKind::DataLossGate,
+                    message: format!(
+                        "process serves {} connected client(s) on port(s) {}",
+                        br.client_connections.len(),
+                        ports
+                            .iter()
+                            .map(u16::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    rule: "data_loss_gates.block_if_serving_clients".to_string(),
+                    context: Some("killing interrupts downstream clients".to_string()),
+                });
+            }
+        }
+
+        // Check cumulative blast radius
+        if let Some(max_score) = self.data_loss_gates.max_blast_radius_score {
+            if let Some(br) = blast_radius.filter(|br| br.risk_score > max_score) {
+                return Some(PolicyViolation {
+                    kind: ViolationKind::DataLossGate,
+                    message: format!(
+                        "blast radius score {:.1} exceeds max_blast_radius_score {:.1}",
+                        br.risk_score, max_score
+                    ),
+                    rule: "data_loss_gates.max_blast_radius_score".to_string(),
+                    context: Some(br.summary.clone()),
+                });
+            }
+        }
+
         None
     }
 
@@ -1063,6 +1261,8 @@ mod tests {
             process_state: None, // Normal processes have no special state
             wchan: None,
             critical_files: Vec::new(),
+            privileges: None,
+            blast_radius: None,
         }
     }
 
@@ -1336,6 +1536,62 @@ mod tests {
         assert!(result.violation.as_ref().unwrap().message.contains("I/O"));
     }
 
+    #[test]
+    fn test_data_loss_gate_blast_radius() {
+        use crate::supervision::blast_radius::{
+            compute_blast_radius, BlastRadiusInput, ClientConnection,
+        };
+
+        let blast_radius = compute_blast_radius(&BlastRadiusInput {
+            target_pid: 12345,
+            client_connections: vec![ClientConnection {
+                local_port: 8080,
+                remote_addr: "10.0.0.7".to_string(),
+                remote_port: 51000,
+            }],
+            open_write_files: vec![(3, "/srv/app.log".to_string())],
+            ..Default::default()
+        });
+        let mut candidate = test_candidate();
+        candidate.blast_radius = Some(blast_radius);
+
+        // Measured write FDs take precedence over the blast radius.
+        let policy = test_policy();
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+        assert!(
+            enforcer
+                .check_action(&candidate, Action::Kill, false)
+                .allowed
+        );
+
+        candidate.open_write_fds = None;
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert_eq!(
+            result.violation.unwrap().rule,
+            "data_loss_gates.block_if_open_write_fds"
+        );
+
+        candidate.open_write_fds = Some(0);
+        let mut policy = test_policy();
+        policy.data_loss_gates.block_if_serving_clients = Some(true);
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+        let violation = enforcer
+            .check_action(&candidate, Action::Kill, false)
+            .violation
+            .unwrap();
+        assert_eq!(violation.rule, "data_loss_gates.block_if_serving_clients");
+        assert!(violation.message.contains("port(s) 8080"));
+
+        let mut policy = test_policy();
+        policy.data_loss_gates.max_blast_radius_score = Some(1.0);
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+        let violation = enforcer
+            .check_action(&candidate, Action::Kill, false)
+            .violation
+            .unwrap();
+        assert_eq!(violation.rule, "data_loss_gates.max_blast_radius_score");
+    }
+
     #[test]
     fn test_force_review_pattern_warning_in_interactive() {
         let mut policy = test_policy();
@@ -1548,6 +1804,80 @@ mod tests {
         );
     }
 
+    fn root_privileges() -> PrivilegeInfo {
+        PrivilegeInfo {
+            cap_eff: 1 << 21, // CAP_SYS_ADMIN
+            ruid: Some(0),
+            euid: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_privileged_daemon_blocked_in_robot_mode() {
+        let policy = test_policy();
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+
+        let mut candidate = test_candidate();
+        candidate.category = Some("daemon".to_string());
+        candidate.privileges = Some(root_privileges());
+
+        let result = enforcer.check_action(&candidate, Action::Kill, true);
+        assert!(!result.allowed);
+        let violation = result.violation.unwrap();
+        assert_eq!(violation.kind, ViolationKind::PrivilegedProcess);
+        assert_eq!(violation.rule, "guardrails.privileged_daemon");
+        assert!(violation.context.unwrap().contains("CAP_SYS_ADMIN"));
+
+        // Interactive mode surfaces the same finding as a warning.
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert!(result.allowed);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("privileged daemon")));
+    }
+
+    #[test]
+    fn test_privileged_orphan_flagged() {
+        let mut policy = test_policy();
+        policy.guardrails.never_kill_ppid = Vec::new();
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+
+        let mut candidate = test_candidate();
+        candidate.ppid = 1;
+        candidate.privileges = Some(root_privileges());
+
+        let result = enforcer.check_action(&candidate, Action::Kill, true);
+        assert!(!result.allowed);
+        assert_eq!(
+            result.violation.unwrap().rule,
+            "guardrails.privileged_orphan"
+        );
+
+        // Elevated-but-not-high orphans are not flagged.
+        candidate.privileges = Some(PrivilegeInfo {
+            cap_eff: 1 << 10, // CAP_NET_BIND_SERVICE
+            ..Default::default()
+        });
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert!(result.warnings.iter().all(|w| !w.contains("orphan")));
+    }
+
+    #[test]
+    fn test_unprivileged_process_ignores_privilege_guardrails() {
+        let policy = test_policy();
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+
+        let mut candidate = test_candidate();
+        candidate.category = Some("daemon".to_string());
+        candidate.privileges = Some(PrivilegeInfo::default());
+
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert!(result.allowed);
+        assert!(result.warnings.is_empty());
+    }
+
     #[test]
     fn test_zombie_process_kill_blocked() {
         let policy = test_policy();
@@ -2159,6 +2489,72 @@ mod tests {
         );
     }
 
+    // ── Custom rules ────────────────────────────────────────────────
+
+    #[test]
+    fn custom_rules_block_and_force_review() {
+        use pt_config::rules::{CustomRule, RuleEffect};
+
+        let mut policy = test_policy();
+        policy.robot_mode.enabled = true;
+        policy.robot_mode.min_posterior = 0.50;
+        policy.custom_rules = vec![
+            CustomRule {
+                name: "never-kill-build-agents".to_string(),
+                expr: "action == 'kill' && cmdline.contains('buildkite-agent')".to_string(),
+                effect: RuleEffect::Block,
+                message: Some("build agents are managed by CI".to_string()),
+            },
+            CustomRule {
+                name: "review-large".to_string(),
+                expr: "memory_mb != null && memory_mb > 4096".to_string(),
+                effect: RuleEffect::ForceReview,
+                message: None,
+            },
+        ];
+        let enforcer = PolicyEnforcer::new(&policy, None).unwrap();
+
+        let mut candidate = test_candidate();
+        candidate.posterior = Some(0.99);
+        candidate.cmdline = "/usr/bin/buildkite-agent start".to_string();
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert!(!result.allowed);
+        let violation = result.violation.unwrap();
+        assert_eq!(violation.kind, ViolationKind::CustomRule);
+        assert_eq!(violation.rule, "custom_rules.never-kill-build-agents");
+        assert!(
+            enforcer
+                .check_action(&candidate, Action::Pause, false)
+                .allowed
+        );
+
+        candidate.cmdline = "python train.py".to_string();
+        candidate.memory_mb = Some(8192.0);
+        let result = enforcer.check_action(&candidate, Action::Kill, true);
+        assert_eq!(
+            result.violation.as_ref().unwrap().kind,
+            ViolationKind::ForceReview
+        );
+        let result = enforcer.check_action(&candidate, Action::Kill, false);
+        assert!(result.allowed);
+        assert!(result.warnings.iter().any(|w| w.contains("review-large")));
+    }
+
+    #[test]
+    fn invalid_custom_rule_rejected_at_load() {
+        let mut policy = test_policy();
+        policy.custom_rules = vec![pt_config::rules::CustomRule {
+            name: "broken".to_string(),
+            expr: "cmdline.contains(".to_string(),
+            effect: Default::default(),
+            message: None,
+        }];
+        assert!(matches!(
+            PolicyEnforcer::new(&policy, None),
+            Err(EnforcerError::PolicyInvalid(_))
+        ));
+    }
+
     // ── Min age does not block non-destructive actions ──────────────
 
     #[test]
@@ -847,8 +847,11 @@ mod tests {
             tty_beta: BetaParams::new(1.0, 1.0),
             net_beta: BetaParams::new(1.0, 1.0),
             io_active_beta: None,
+            gpu_beta: None,
+            gpu_util_beta: None,
             hazard_gamma: None,
             competing_hazards: None,
+            runtime_surprise_gamma: None,
         };
 
         let priors = Priors {
@@ -888,6 +891,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
 
         let outcome = decide_action_with_recovery(
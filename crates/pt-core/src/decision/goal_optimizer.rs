@@ -8,6 +8,9 @@
 //!
 //! - **Greedy**: Sort by efficiency (contribution/loss), select greedily.
 //! - **DP-exact**: Dynamic programming for small candidate sets (N ≤ 30).
+//! - **ILP branch-and-bound**: Exact minimum-loss set for a single goal.
+//! - **Multi-objective**: Pareto frontier over loss and every goal, exact for
+//!   small sets and from a weighted-sum sweep otherwise.
 //! - **Local search**: Swap-based improvement on greedy solutions.
 //!
 //! When goals are infeasible, reports the shortfall and best-effort plan.
@@ -94,6 +97,9 @@ pub struct GoalAchievement {
 pub struct AlternativePlan {
     /// Description of the tradeoff.
     pub description: String,
+    /// Candidate IDs the plan acts on.
+    #[serde(default)]
+    pub ids: Vec<String>,
     /// Number of actions.
     pub action_count: usize,
     /// Total expected loss.
@@ -673,6 +679,235 @@ pub fn optimize_ilp(candidates: &[OptCandidate], goals: &[ResourceGoal]) -> Opti
     }
 }
 
+/// Candidate count up to which the multi-objective frontier is enumerated
+/// exactly; larger sets use the weighted-sum sweep.
+pub const PARETO_EXACT_LIMIT: usize = 12;
+
+/// Risk weights swept when the frontier cannot be enumerated exactly.
+const WEIGHT_SWEEP_STEPS: usize = 20;
+
+/// Alternatives reported next to the chosen multi-objective plan.
+const MAX_PARETO_ALTERNATIVES: usize = 6;
+
+/// Multi-objective optimization: Pareto frontier over loss and every goal.
+///
+/// The frontier is enumerated exactly for up to [`PARETO_EXACT_LIMIT`]
+/// eligible candidates. Beyond that it is approximated by a weighted-sum
+/// sweep: for risk weights from 0 to 1, candidates are ranked by weighted
+/// normalized goal progress minus weighted normalized loss, and every prefix
+/// of the ranking is a point.
+///
+/// The chosen plan is the least-loss frontier point meeting all goals; if no
+/// point does, the one with the highest weighted achievement. Other frontier
+/// points are reported as alternatives, from least to most loss.
+pub fn optimize_multi_objective(
+    candidates: &[OptCandidate],
+    goals: &[ResourceGoal],
+) -> OptimizationResult {
+    let mut log_events = Vec::new();
+    let mut start_event = OptimizationLogEvent::new("optimizer_start", "pareto");
+    start_event.note = Some(format!(
+        "candidates={} goals={}",
+        candidates.len(),
+        goals.len()
+    ));
+    log_events.push(start_event);
+
+    let eligible: Vec<&OptCandidate> = candidates
+        .iter()
+        .filter(|c| !c.blocked && c.expected_loss >= 0.0)
+        .collect();
+    if eligible.is_empty() || goals.is_empty() {
+        let mut greedy = optimize_greedy(candidates, goals);
+        greedy.algorithm = "pareto (no_candidates, greedy fallback)".to_string();
+        greedy.log_events.extend(log_events);
+        return greedy;
+    }
+    let blocked_count = candidates.len() - eligible.len();
+    if blocked_count > 0 {
+        let mut event = OptimizationLogEvent::new("constraint_violation", "pareto");
+        event.note = Some(format!("blocked_candidates={}", blocked_count));
+        log_events.push(event);
+    }
+
+    let goals_len = goals.len();
+    let (algorithm, sets) = if eligible.len() <= PARETO_EXACT_LIMIT {
+        ("pareto_exact", enumerate_subsets(&eligible, goals_len))
+    } else {
+        ("pareto_weighted_sum", weighted_sum_sweep(&eligible, goals))
+    };
+    let mut frontier = non_dominated(sets);
+    // Identical points (same members) can survive from different sweeps.
+    let mut seen = HashSet::new();
+    frontier.retain(|set| {
+        let mut ids: Vec<&str> = set.actions.iter().map(|a| a.id.as_str()).collect();
+        ids.sort_unstable();
+        seen.insert(ids.join(","))
+    });
+    frontier.sort_by(|a, b| {
+        a.total_loss
+            .partial_cmp(&b.total_loss)
+            .unwrap_or(Ordering::Equal)
+            .then(a.actions.len().cmp(&b.actions.len()))
+    });
+    for set in &frontier {
+        let mut event = OptimizationLogEvent::new("pareto_point", algorithm);
+        event.total_loss = Some(set.total_loss);
+        event.total_contributions = set.total_contributions.clone();
+        log_events.push(event);
+    }
+
+    let meets_all = |set: &ParetoSet| {
+        goals
+            .iter()
+            .zip(&set.total_contributions)
+            .all(|(goal, achieved)| *achieved >= goal.target)
+    };
+    // Sorted by loss, so the first feasible point is the cheapest one.
+    let chosen_idx = frontier.iter().position(meets_all).unwrap_or_else(|| {
+        let mut best = 0;
+        for (idx, set) in frontier.iter().enumerate() {
+            if weighted_achievement(set, goals)
+                > weighted_achievement(&frontier[best], goals) + 1e-12
+            {
+                best = idx;
+            }
+        }
+        best
+    });
+    let chosen = frontier.remove(chosen_idx);
+
+    let alternatives: Vec<AlternativePlan> = frontier
+        .iter()
+        .map(|set| {
+            let goal_achievement = compute_goal_achievements(goals, &set.total_contributions);
+            AlternativePlan {
+                description: describe_tradeoff(
+                    set.actions.len(),
+                    set.total_loss,
+                    &goal_achievement,
+                ),
+                ids: set.actions.iter().map(|a| a.id.clone()).collect(),
+                action_count: set.actions.len(),
+                total_loss: set.total_loss,
+                goal_achievement,
+            }
+        })
+        .collect();
+    let alternatives = thin_evenly(alternatives, MAX_PARETO_ALTERNATIVES);
+
+    let goal_achievement = compute_goal_achievements(goals, &chosen.total_contributions);
+    let feasible = goal_achievement.iter().all(|g| g.met);
+    let mut converged = OptimizationLogEvent::new("converged", algorithm);
+    converged.total_loss = Some(chosen.total_loss);
+    converged.total_contributions = chosen.total_contributions.clone();
+    converged.note = Some(format!("frontier_size={}", alternatives.len() + 1));
+    log_events.push(converged);
+
+    OptimizationResult {
+        selected: chosen.actions,
+        total_loss: chosen.total_loss,
+        total_contributions: chosen.total_contributions,
+        goal_achievement,
+        feasible,
+        algorithm: algorithm.to_string(),
+        alternatives,
+        log_events,
+    }
+}
+
+/// Frontier points from ranking candidates under a sweep of risk weights.
+fn weighted_sum_sweep(eligible: &[&OptCandidate], goals: &[ResourceGoal]) -> Vec<ParetoSet> {
+    let max_loss = eligible
+        .iter()
+        .map(|c| c.expected_loss)
+        .fold(0.0_f64, f64::max)
+        .max(1e-12);
+    let total_weight: f64 = goals.iter().map(|g| g.weight).sum::<f64>().max(1e-12);
+    let progress = |c: &OptCandidate| -> f64 {
+        goals
+            .iter()
+            .zip(&c.contributions)
+            .map(|(goal, contrib)| {
+                let share = if goal.target > 0.0 {
+                    (contrib / goal.target).min(1.0)
+                } else {
+                    0.0
+                };
+                goal.weight * share
+            })
+            .sum::<f64>()
+            / total_weight
+    };
+
+    let mut sets = Vec::new();
+    for step in 0..=WEIGHT_SWEEP_STEPS {
+        let risk_weight = step as f64 / WEIGHT_SWEEP_STEPS as f64;
+        let mut ranked: Vec<(f64, &OptCandidate)> = eligible
+            .iter()
+            .map(|c| {
+                let score =
+                    (1.0 - risk_weight) * progress(c) - risk_weight * (c.expected_loss / max_loss);
+                (score, *c)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        for len in 1..=ranked.len() {
+            let set = ParetoSet::from_members(ranked[..len].iter().map(|(_, c)| *c), goals.len());
+            let done = goals
+                .iter()
+                .zip(&set.total_contributions)
+                .all(|(goal, achieved)| *achieved >= goal.target);
+            sets.push(set);
+            // Longer prefixes only add loss once every goal is met.
+            if done {
+                break;
+            }
+        }
+    }
+    sets
+}
+
+/// Weighted share of the goals a set achieves, in [0, 1].
+fn weighted_achievement(set: &ParetoSet, goals: &[ResourceGoal]) -> f64 {
+    let total_weight: f64 = goals.iter().map(|g| g.weight).sum::<f64>().max(1e-12);
+    goals
+        .iter()
+        .zip(&set.total_contributions)
+        .map(|(goal, achieved)| {
+            let share = if goal.target > 0.0 {
+                (achieved / goal.target).min(1.0)
+            } else {
+                1.0
+            };
+            goal.weight * share
+        })
+        .sum::<f64>()
+        / total_weight
+}
+
+/// "Kill 3: memory_mb 4096.0/4096.0 (met), cpu_pct 12.0/20.0; loss 1.200".
+fn describe_tradeoff(action_count: usize, total_loss: f64, goals: &[GoalAchievement]) -> String {
+    let parts: Vec<String> = goals
+        .iter()
+        .map(|g| {
+            format!(
+                "{} {:.1}/{:.1}{}",
+                g.resource,
+                g.achieved,
+                g.target,
+                if g.met { " (met)" } else { "" }
+            )
+        })
+        .collect();
+    format!(
+        "Kill {}: {}; loss {:.3}",
+        action_count,
+        parts.join(", "),
+        total_loss
+    )
+}
+
 /// Re-optimize when the candidate set changes materially.
 ///
 /// Returns the previous plan if changes are minor; otherwise recomputes.
@@ -867,6 +1102,7 @@ fn generate_alternatives(
 
         push_unique(AlternativePlan {
             description: "Conservative: fewer actions, potentially under target".to_string(),
+            ids: fewer.iter().map(|s| s.id.clone()).collect(),
             action_count: fewer.len(),
             total_loss: loss,
             goal_achievement: achievements,
@@ -893,6 +1129,7 @@ fn generate_alternatives(
 
             push_unique(AlternativePlan {
                 description: "Aggressive: more headroom, higher total loss".to_string(),
+                ids: more.iter().map(|s| s.id.clone()).collect(),
                 action_count: more.len(),
                 total_loss: loss,
                 goal_achievement: achievements,
@@ -969,53 +1206,7 @@ fn compute_pareto_frontier(
         candidates.truncate(16);
     }
 
-    let n = candidates.len();
-    let mut sets: Vec<ParetoSet> = Vec::new();
-
-    for mask in 1..(1_u32 << n) {
-        let mut actions = Vec::new();
-        let mut total_loss = 0.0;
-        let mut totals = vec![0.0; goals_len];
-
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..n {
-            if (mask & (1_u32 << i)) != 0 {
-                let cand = candidates[i];
-                actions.push(SelectedAction {
-                    id: cand.id.clone(),
-                    expected_loss: cand.expected_loss,
-                    contributions: cand.contributions.clone(),
-                });
-                total_loss += cand.expected_loss;
-                for (g, contrib) in cand.contributions.iter().enumerate().take(goals_len) {
-                    totals[g] += *contrib;
-                }
-            }
-        }
-
-        sets.push(ParetoSet {
-            actions,
-            total_loss,
-            total_contributions: totals,
-        });
-    }
-
-    let mut frontier = Vec::new();
-    for i in 0..sets.len() {
-        let mut dominated = false;
-        for j in 0..sets.len() {
-            if i == j {
-                continue;
-            }
-            if pareto_dominates(&sets[j], &sets[i]) {
-                dominated = true;
-                break;
-            }
-        }
-        if !dominated {
-            frontier.push(sets[i].clone());
-        }
-    }
+    let mut frontier = non_dominated(enumerate_subsets(&candidates, goals_len));
 
     frontier.sort_by(|a, b| {
         let loss_cmp = a
@@ -1030,7 +1221,7 @@ fn compute_pareto_frontier(
         sum_b.partial_cmp(&sum_a).unwrap_or(Ordering::Equal)
     });
 
-    let mut alternatives: Vec<AlternativePlan> = frontier
+    let alternatives: Vec<AlternativePlan> = frontier
         .into_iter()
         .map(|set| {
             let sum_contrib: f64 = set.total_contributions.iter().sum();
@@ -1043,6 +1234,7 @@ fn compute_pareto_frontier(
                     "Pareto: loss {:.3}, contribution {:.3}",
                     set.total_loss, sum_contrib
                 ),
+                ids: set.actions.iter().map(|a| a.id.clone()).collect(),
                 action_count: set.actions.len(),
                 total_loss: set.total_loss,
                 goal_achievement: compute_goal_achievements(goals, &set.total_contributions),
@@ -1050,26 +1242,31 @@ fn compute_pareto_frontier(
         })
         .collect();
 
-    if alternatives.len() > max_sets {
-        let mut reduced = Vec::with_capacity(max_sets);
-        if max_sets == 1 {
-            reduced.push(alternatives[0].clone());
-            return reduced;
-        }
-        let step = (alternatives.len() - 1) as f64 / (max_sets - 1) as f64;
-        let mut last_idx = None;
-        for i in 0..max_sets {
-            let idx = (i as f64 * step).round() as usize;
-            if last_idx == Some(idx) {
-                continue;
-            }
-            reduced.push(alternatives[idx].clone());
-            last_idx = Some(idx);
+    thin_evenly(alternatives, max_sets)
+}
+
+/// Keep at most `max` items, spread evenly and always including the first
+/// and last.
+fn thin_evenly<T: Clone>(items: Vec<T>, max: usize) -> Vec<T> {
+    if items.len() <= max {
+        return items;
+    }
+    let mut reduced = Vec::with_capacity(max);
+    if max == 1 {
+        reduced.push(items[0].clone());
+        return reduced;
+    }
+    let step = (items.len() - 1) as f64 / (max - 1) as f64;
+    let mut last_idx = None;
+    for i in 0..max {
+        let idx = (i as f64 * step).round() as usize;
+        if last_idx == Some(idx) {
+            continue;
         }
-        alternatives = reduced;
+        reduced.push(items[idx].clone());
+        last_idx = Some(idx);
     }
-
-    alternatives
+    reduced
 }
 
 #[derive(Clone)]
@@ -1079,6 +1276,62 @@ struct ParetoSet {
     total_contributions: Vec<f64>,
 }
 
+impl ParetoSet {
+    fn from_members<'a>(
+        members: impl IntoIterator<Item = &'a OptCandidate>,
+        goals_len: usize,
+    ) -> Self {
+        let mut set = ParetoSet {
+            actions: Vec::new(),
+            total_loss: 0.0,
+            total_contributions: vec![0.0; goals_len],
+        };
+        for cand in members {
+            set.actions.push(SelectedAction {
+                id: cand.id.clone(),
+                expected_loss: cand.expected_loss,
+                contributions: cand.contributions.clone(),
+            });
+            set.total_loss += cand.expected_loss;
+            for (g, contrib) in cand.contributions.iter().enumerate().take(goals_len) {
+                set.total_contributions[g] += *contrib;
+            }
+        }
+        set
+    }
+}
+
+/// Every non-empty subset of `candidates` (at most 16 of them).
+fn enumerate_subsets(candidates: &[&OptCandidate], goals_len: usize) -> Vec<ParetoSet> {
+    let n = candidates.len().min(16);
+    (1..(1_u32 << n))
+        .map(|mask| {
+            ParetoSet::from_members(
+                (0..n)
+                    .filter(|&i| mask & (1_u32 << i) != 0)
+                    .map(|i| candidates[i]),
+                goals_len,
+            )
+        })
+        .collect()
+}
+
+/// Sets no other set dominates.
+fn non_dominated(sets: Vec<ParetoSet>) -> Vec<ParetoSet> {
+    let keep: Vec<bool> = (0..sets.len())
+        .map(|i| {
+            !sets
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && pareto_dominates(other, &sets[i]))
+        })
+        .collect();
+    sets.into_iter()
+        .zip(keep)
+        .filter_map(|(set, keep)| keep.then_some(set))
+        .collect()
+}
+
 fn pareto_efficiency(candidate: &OptCandidate, goals: &[ResourceGoal]) -> f64 {
     let weighted: f64 = candidate
         .contributions
@@ -1437,6 +1690,7 @@ mod tests {
     fn alternative_plan_serde_roundtrip() {
         let alt = AlternativePlan {
             description: "Conservative plan".to_string(),
+            ids: vec![],
             action_count: 2,
             total_loss: 0.5,
             goal_achievement: vec![GoalAchievement {
@@ -1529,6 +1783,7 @@ mod tests {
 
         let chosen = AlternativePlan {
             description: "aggressive".to_string(),
+            ids: vec![],
             action_count: 5,
             total_loss: 8.0, // high loss = aggressive
             goal_achievement: vec![],
@@ -1536,12 +1791,14 @@ mod tests {
         let alternatives = vec![
             AlternativePlan {
                 description: "safe".to_string(),
+                ids: vec![],
                 action_count: 1,
                 total_loss: 2.0,
                 goal_achievement: vec![],
             },
             AlternativePlan {
                 description: "aggressive".to_string(),
+                ids: vec![],
                 action_count: 5,
                 total_loss: 8.0,
                 goal_achievement: vec![],
@@ -1561,12 +1818,14 @@ mod tests {
         let mut pm = PreferenceModel::default();
         let chosen = AlternativePlan {
             description: "only".to_string(),
+            ids: vec![],
             action_count: 1,
             total_loss: 5.0,
             goal_achievement: vec![],
         };
         let alternatives = vec![AlternativePlan {
             description: "only".to_string(),
+            ids: vec![],
             action_count: 1,
             total_loss: 5.0,
             goal_achievement: vec![],
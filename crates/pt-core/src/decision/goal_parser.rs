@@ -3,6 +3,7 @@
 //! Parses human-readable goal strings like "free 4GB RAM" or "release port 3000"
 //! into structured goal ASTs for the goal-oriented optimizer.
 
+use pt_common::units::{parse_size, SizeUnit, UnitError};
 use serde::{Deserialize, Serialize};
 
 /// A parsed resource goal.
@@ -111,7 +112,7 @@ impl std::fmt::Display for GoalParseError {
             Self::UnrecognizedFormat(s) => {
                 write!(f, "Unrecognized goal format: \"{}\". Try: \"free 4GB RAM\", \"reduce CPU below 50%\", \"release port 3000\", \"free 100 FDs\"", s)
             }
-            Self::InvalidUnit(u) => write!(f, "Invalid unit: \"{}\". Use: B, KB, MB, GB, TB", u),
+            Self::InvalidUnit(u) => write!(f, "Invalid unit: \"{}\". Use: B, KB/KiB, MB/MiB, GB/GiB, TB/TiB", u),
             Self::InvalidNumber(n) => write!(f, "Invalid number: \"{}\"", n),
             Self::InvalidPort(p) => write!(f, "Invalid port: \"{}\" (must be 1-65535)", p),
             Self::Ambiguous(s) => write!(f, "Ambiguous goal: \"{}\"", s),
@@ -126,8 +127,9 @@ impl std::fmt::Display for GoalParseError {
 /// - "free 500MB memory"
 /// - "reduce CPU below 50%"
 /// - "free 20% CPU"
-/// - "release port 3000"
+/// - "release port 3000" or "free port 3000"
 /// - "free 100 FDs"
+/// - "reduce FDs below 100k"
 /// - "free 50 file descriptors"
 /// - Composition: "free 4GB RAM AND release port 3000"
 pub fn parse_goal(input: &str) -> Result<Goal, GoalParseError> {
@@ -184,8 +186,8 @@ fn parse_single_goal(input: &str) -> Result<ResourceTarget, GoalParseError> {
         return Err(GoalParseError::EmptyInput);
     }
 
-    // "release port <N>"
-    if tokens.len() >= 3 && tokens[0] == "release" && tokens[1] == "port" {
+    // "release port <N>" or "free port <N>"
+    if tokens.len() >= 3 && (tokens[0] == "release" || tokens[0] == "free") && tokens[1] == "port" {
         let port_str = tokens[2];
         let port: u16 = port_str
             .parse()
@@ -215,6 +217,23 @@ fn parse_single_goal(input: &str) -> Result<ResourceTarget, GoalParseError> {
         });
     }
 
+    // "reduce FDs below <N>" or "reduce file descriptors below <N>"
+    if tokens.len() >= 4 && tokens[0] == "reduce" {
+        let count_idx = match &tokens[1..] {
+            ["fds" | "fd", "below", ..] => Some(3),
+            ["file", "descriptors", "below", _, ..] => Some(4),
+            _ => None,
+        };
+        if let Some(idx) = count_idx {
+            return Ok(ResourceTarget {
+                metric: Metric::FileDescriptors,
+                value: parse_count(tokens[idx])?,
+                comparator: Comparator::ReduceBelow,
+                port: None,
+            });
+        }
+    }
+
     // "free <N><unit> RAM/memory" or "free <N>% CPU" or "free <N> FDs"
     if tokens.len() >= 3 && tokens[0] == "free" {
         let amount_str = tokens[1];
@@ -238,12 +257,9 @@ fn parse_single_goal(input: &str) -> Result<ResourceTarget, GoalParseError> {
             || tokens[2] == "fd"
             || (tokens.len() >= 4 && tokens[2] == "file" && tokens[3] == "descriptors")
         {
-            let n: f64 = amount_str
-                .parse()
-                .map_err(|_| GoalParseError::InvalidNumber(amount_str.to_string()))?;
             return Ok(ResourceTarget {
                 metric: Metric::FileDescriptors,
-                value: n,
+                value: parse_count(amount_str)?,
                 comparator: Comparator::FreeAtLeast,
                 port: None,
             });
@@ -275,34 +291,26 @@ fn parse_single_goal(input: &str) -> Result<ResourceTarget, GoalParseError> {
 
 /// Parse a memory amount string like "4GB", "500MB", "1024B" into bytes.
 fn parse_memory_amount(s: &str) -> Result<f64, GoalParseError> {
-    let s = s.to_lowercase();
-
-    // Find the boundary between number and unit.
-    let num_end = s
-        .find(|c: char| !c.is_ascii_digit() && c != '.')
-        .unwrap_or(s.len());
-
-    if num_end == 0 {
-        return Err(GoalParseError::InvalidNumber(s.to_string()));
+    match parse_size(s, SizeUnit::Bytes) {
+        Ok(bytes) => Ok(bytes as f64),
+        Err(UnitError::UnknownUnit { unit, .. }) => Err(GoalParseError::InvalidUnit(unit)),
+        Err(_) => Err(GoalParseError::InvalidNumber(s.to_string())),
     }
+}
 
-    let num_str = &s[..num_end];
-    let unit_str = s[num_end..].trim();
-
-    let num: f64 = num_str
-        .parse()
-        .map_err(|_| GoalParseError::InvalidNumber(num_str.to_string()))?;
-
-    let multiplier = match unit_str {
-        "" | "b" | "bytes" => 1.0,
-        "k" | "kb" | "kib" => 1024.0,
-        "m" | "mb" | "mib" => 1024.0 * 1024.0,
-        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
-        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
-        _ => return Err(GoalParseError::InvalidUnit(unit_str.to_string())),
+/// Parse a count like "500", "100k" or "2m".
+fn parse_count(s: &str) -> Result<f64, GoalParseError> {
+    let (digits, scale) = match s.chars().last() {
+        Some('k') => (&s[..s.len() - 1], 1_000.0),
+        Some('m') => (&s[..s.len() - 1], 1_000_000.0),
+        _ => (s, 1.0),
     };
-
-    Ok(num * multiplier)
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+        .map(|n| n * scale)
+        .ok_or_else(|| GoalParseError::InvalidNumber(s.to_string()))
 }
 
 #[cfg(test)]
@@ -389,6 +397,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_free_port_alias() {
+        let goal = parse_goal("free port 8080").unwrap();
+        if let Goal::Target(t) = goal {
+            assert_eq!(t.metric, Metric::Port);
+            assert_eq!(t.comparator, Comparator::Release);
+            assert_eq!(t.port, Some(8080));
+        } else {
+            panic!("Expected Target");
+        }
+    }
+
+    #[test]
+    fn test_reduce_fds_below() {
+        let goal = parse_goal("reduce FDs below 100k").unwrap();
+        if let Goal::Target(t) = goal {
+            assert_eq!(t.metric, Metric::FileDescriptors);
+            assert_eq!(t.comparator, Comparator::ReduceBelow);
+            assert!((t.value - 100_000.0).abs() < 0.01);
+        } else {
+            panic!("Expected Target");
+        }
+        let goal = parse_goal("reduce file descriptors below 5000").unwrap();
+        assert!(matches!(goal, Goal::Target(t) if (t.value - 5000.0).abs() < 0.01));
+        assert!(matches!(
+            parse_goal("reduce fds below lots"),
+            Err(GoalParseError::InvalidNumber(_))
+        ));
+    }
+
     #[test]
     fn test_and_composition() {
         let goal = parse_goal("free 4GB RAM AND release port 3000").unwrap();
@@ -3,9 +3,30 @@
 //! Detects repeated kill→respawn cycles for process identities across sessions
 //! and provides planning adjustments: down-weight kill contribution for processes
 //! that always respawn, and recommend supervisor-level actions instead.
+//!
+//! Identities are (signature, supervisor) pairs ([`respawn_identity_key`]).
+//! `agent verify` records respawns it observes into a history file in shadow
+//! storage ([`respawn_history_path`]); `agent plan` reads it and downgrades
+//! kills of identities that loop under a supervisor to review.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the respawn history inside the shadow storage directory.
+pub const RESPAWN_HISTORY_FILE: &str = "respawn_history.json";
+
+/// Errors loading or saving the respawn history.
+#[derive(Debug, Error)]
+pub enum RespawnHistoryError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
 
 /// A record of a kill→respawn event for a process identity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +92,17 @@ pub struct RespawnLoopDetection {
     pub kill_utility_multiplier: f64,
 }
 
+impl RespawnLoopDetection {
+    /// Whether the identity loops and a supervisor is bringing it back.
+    pub fn under_supervision(&self) -> bool {
+        self.is_loop
+            && matches!(
+                self.recommendation,
+                RespawnRecommendation::SupervisorStop | RespawnRecommendation::SupervisorDisable
+            )
+    }
+}
+
 /// Recommended action for a respawn loop.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RespawnRecommendation {
@@ -109,12 +141,42 @@ impl RespawnTracker {
         }
     }
 
-    /// Record a respawn event.
+    /// Load history from disk; a missing file yields an empty tracker.
+    pub fn load(path: &Path) -> Result<Self, RespawnHistoryError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drop events outside the window and write the history atomically.
+    pub fn save(
+        &mut self,
+        path: &Path,
+        config: &RespawnLoopConfig,
+        now: f64,
+    ) -> Result<(), RespawnHistoryError> {
+        self.prune(config, now);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Record a respawn event; an event already recorded (same kill and
+    /// respawn time) is ignored, so re-verifying a session is harmless.
     pub fn record_event(&mut self, event: RespawnEvent) {
-        self.events
-            .entry(event.identity_key.clone())
-            .or_default()
-            .push(event);
+        let events = self.events.entry(event.identity_key.clone()).or_default();
+        if !events
+            .iter()
+            .any(|e| e.kill_ts == event.kill_ts && e.respawn_ts == event.respawn_ts)
+        {
+            events.push(event);
+        }
     }
 
     /// Record a kill→respawn pair.
@@ -235,6 +297,30 @@ impl RespawnTracker {
     }
 }
 
+/// Identity of a (signature, supervisor) pair.
+///
+/// The matched signature name is preferred; unmatched processes fall back to
+/// their whitespace-normalized command line.
+pub fn respawn_identity_key(
+    signature: Option<&str>,
+    cmd: &str,
+    supervisor: Option<&str>,
+) -> String {
+    let what = match signature {
+        Some(name) => format!("sig:{}", name),
+        None => format!(
+            "cmd:{}",
+            cmd.split_whitespace().collect::<Vec<_>>().join(" ")
+        ),
+    };
+    format!("{}@{}", what, supervisor.unwrap_or("-"))
+}
+
+/// Default location of the respawn history for a shadow storage directory.
+pub fn respawn_history_path(shadow_dir: &Path) -> PathBuf {
+    shadow_dir.join(RESPAWN_HISTORY_FILE)
+}
+
 /// Apply respawn loop discount to a kill utility score.
 pub fn discount_kill_utility(base_utility: f64, detection: &RespawnLoopDetection) -> f64 {
     base_utility * detection.kill_utility_multiplier
@@ -415,6 +501,30 @@ mod tests {
         assert_eq!(loops[0].identity_key, "nginx");
     }
 
+    #[test]
+    fn test_identity_key_and_history_roundtrip() {
+        assert_eq!(
+            respawn_identity_key(Some("celery-worker"), "ignored", Some("celery.service")),
+            "sig:celery-worker@celery.service"
+        );
+        assert_eq!(
+            respawn_identity_key(None, "node   server.js", None),
+            "cmd:node server.js@-"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = respawn_history_path(dir.path());
+        assert_eq!(RespawnTracker::load(&path).unwrap().event_count(), 0);
+
+        let (mut tracker, now) = make_tracker_with_respawns(3, 2.0);
+        tracker
+            .save(&path, &RespawnLoopConfig::default(), now)
+            .unwrap();
+        let loaded = RespawnTracker::load(&path).unwrap();
+        let det = loaded.detect_loop("nginx", &RespawnLoopConfig::default(), now);
+        assert!(det.under_supervision());
+    }
+
     #[test]
     fn test_avg_delay() {
         let mut tracker = RespawnTracker::new();
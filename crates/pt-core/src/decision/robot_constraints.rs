@@ -11,6 +11,10 @@
 //! Candidate → ConstraintChecker → ConstraintCheckResult (allow/block + reasons)
 //! ```
 //!
+//! The policy `schedule` (maintenance windows, business hours) is checked here
+//! too; candidates held only by it are reported as deferred rather than blocked
+//! (see [`ConstraintCheckResult::deferred_by_schedule`]).
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -33,6 +37,8 @@
 //! }
 //! ```
 
+use chrono::NaiveDateTime;
+use pt_config::schedule::{ActionSchedule, CompiledSchedule, ScheduleBlock};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
@@ -77,6 +83,10 @@ pub struct RuntimeRobotConstraints {
     /// Require human confirmation for supervised processes.
     pub require_human_for_supervised: bool,
 
+    /// Maintenance windows and business hours for destructive actions.
+    #[serde(default, skip_serializing_if = "ActionSchedule::is_empty")]
+    pub schedule: ActionSchedule,
+
     /// Source of each constraint value for explainability.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sources: Option<ConstraintSources>,
@@ -94,6 +104,7 @@ pub struct ConstraintSources {
     pub allow_categories: ConstraintSource,
     pub exclude_categories: ConstraintSource,
     pub require_human_for_supervised: ConstraintSource,
+    pub schedule: ConstraintSource,
 }
 
 /// Source of a constraint value.
@@ -125,6 +136,7 @@ impl RuntimeRobotConstraints {
             allow_categories: robot_mode.allow_categories.clone(),
             exclude_categories: robot_mode.exclude_categories.clone(),
             require_human_for_supervised: robot_mode.require_human_for_supervised,
+            schedule: ActionSchedule::default(),
             sources: Some(ConstraintSources::default()),
         }
     }
@@ -142,6 +154,7 @@ impl RuntimeRobotConstraints {
             allow_categories: Vec::new(),
             exclude_categories: Vec::new(),
             require_human_for_supervised: false,
+            schedule: ActionSchedule::default(),
             sources: None,
         }
     }
@@ -245,6 +258,12 @@ impl RuntimeRobotConstraints {
         self
     }
 
+    /// Set the time-of-day schedule from policy.
+    pub fn with_schedule(mut self, schedule: ActionSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
     /// Get a summary of active constraints for logging/display.
     pub fn active_constraints_summary(&self) -> Vec<String> {
         let mut summary = Vec::new();
@@ -292,6 +311,26 @@ impl RuntimeRobotConstraints {
             summary.push("require_human_for_supervised: true".to_string());
         }
 
+        if !self.schedule.maintenance_windows.is_empty() {
+            let windows: Vec<String> = self
+                .schedule
+                .maintenance_windows
+                .iter()
+                .map(|w| format!("{} ({}, {}m)", w.name, w.cron, w.duration_minutes))
+                .collect();
+            summary.push(format!("maintenance_windows: [{}]", windows.join(", ")));
+        }
+
+        for hours in &self.schedule.business_hours {
+            summary.push(format!(
+                "business_hours: [{}] {} {}-{}",
+                hours.categories.join(", "),
+                hours.days,
+                hours.start,
+                hours.end
+            ));
+        }
+
         summary
     }
 }
@@ -323,6 +362,14 @@ impl ConstraintCheckResult {
         }
     }
 
+    /// Whether the candidate is held only by the schedule, so it can run
+    /// unchanged once an allowed window comes round.
+    pub fn deferred_by_schedule(&self) -> bool {
+        !self.allowed
+            && !self.violations.is_empty()
+            && self.violations.iter().all(|v| v.constraint.is_schedule())
+    }
+
     /// Create a blocked result.
     pub fn blocked(violations: Vec<ConstraintViolation>, metrics: ConstraintMetrics) -> Self {
         Self {
@@ -387,6 +434,17 @@ pub enum ConstraintKind {
     CategoryNotAllowed,
     /// Supervision detected and human confirmation required.
     RequireHumanForSupervised,
+    /// Maintenance windows configured and none is open.
+    OutsideMaintenanceWindow,
+    /// Business hours hold destructive actions for this category.
+    BusinessHours,
+}
+
+impl ConstraintKind {
+    /// Whether this constraint depends only on the time of day.
+    pub fn is_schedule(&self) -> bool {
+        matches!(self, Self::OutsideMaintenanceWindow | Self::BusinessHours)
+    }
 }
 
 /// Metrics about a constraint check.
@@ -414,15 +472,46 @@ pub struct ConstraintChecker {
 
     /// Accumulated blast radius in bytes (stored as integer for atomics).
     accumulated_blast_bytes: AtomicU64,
+
+    /// Parsed schedule, or the parse error (which holds every destructive
+    /// action rather than ignoring the schedule).
+    schedule: Option<Result<CompiledSchedule, String>>,
+
+    /// Wall-clock time the schedule is evaluated at.
+    now: NaiveDateTime,
 }
 
 impl ConstraintChecker {
     /// Create a new constraint checker.
     pub fn new(constraints: RuntimeRobotConstraints) -> Self {
+        let schedule = (!constraints.schedule.is_empty())
+            .then(|| CompiledSchedule::compile(&constraints.schedule).map_err(|e| e.to_string()));
+        let now = constraints.schedule.now();
         Self {
             constraints,
             kill_count: AtomicU32::new(0),
             accumulated_blast_bytes: AtomicU64::new(0),
+            schedule,
+            now,
+        }
+    }
+
+    /// Evaluate the schedule at `now` instead of the current time.
+    pub fn with_now(mut self, now: NaiveDateTime) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Earliest time the schedule lets a destructive action on `candidate`
+    /// run, if it is held now.
+    pub fn deferred_until(&self, candidate: &RobotCandidate) -> Option<NaiveDateTime> {
+        match self.schedule.as_ref()? {
+            Ok(schedule) => {
+                let category = candidate.category.as_deref();
+                schedule.block_at(self.now, category)?;
+                schedule.next_allowed(self.now, category)
+            }
+            Err(_) => None,
         }
     }
 
@@ -679,6 +768,13 @@ impl ConstraintChecker {
             }
         }
 
+        // Check time-of-day schedule (destructive actions only)
+        if candidate.is_kill_action || candidate.is_destructive {
+            if let Some(violation) = self.check_schedule(candidate) {
+                violations.push(violation);
+            }
+        }
+
         let metrics = self.current_metrics();
 
         if violations.is_empty() {
@@ -688,6 +784,65 @@ impl ConstraintChecker {
         }
     }
 
+    fn check_schedule(&self, candidate: &RobotCandidate) -> Option<ConstraintViolation> {
+        let source = self
+            .constraints
+            .sources
+            .as_ref()
+            .map(|s| s.schedule)
+            .unwrap_or_default();
+        let schedule = match self.schedule.as_ref()? {
+            Ok(schedule) => schedule,
+            Err(err) => {
+                return Some(ConstraintViolation {
+                    constraint: ConstraintKind::OutsideMaintenanceWindow,
+                    message: format!("Schedule is invalid: {}", err),
+                    threshold: "valid schedule".to_string(),
+                    actual: err.clone(),
+                    source,
+                    remediation: Some("Fix schedule in policy.json".to_string()),
+                })
+            }
+        };
+        let category = candidate.category.as_deref();
+        let block = schedule.block_at(self.now, category)?;
+        let now = self.now.format("%Y-%m-%d %H:%M");
+        let remediation = Some(match schedule.next_allowed(self.now, category) {
+            Some(at) => format!("Deferred until {}", at.format("%Y-%m-%d %H:%M")),
+            None => "No allowed window within a year; review schedule in policy.json".to_string(),
+        });
+        Some(match block {
+            ScheduleBlock::OutsideMaintenanceWindow => {
+                let windows: Vec<&str> = self
+                    .constraints
+                    .schedule
+                    .maintenance_windows
+                    .iter()
+                    .map(|w| w.name.as_str())
+                    .collect();
+                ConstraintViolation {
+                    constraint: ConstraintKind::OutsideMaintenanceWindow,
+                    message: format!("{} is outside every maintenance window", now),
+                    threshold: format!("maintenance_windows: [{}]", windows.join(", ")),
+                    actual: now.to_string(),
+                    source,
+                    remediation,
+                }
+            }
+            ScheduleBlock::BusinessHours { category, span } => ConstraintViolation {
+                constraint: ConstraintKind::BusinessHours,
+                message: format!(
+                    "Destructive actions on '{}' are held during business hours ({})",
+                    category, span
+                ),
+                threshold: format!("business_hours: {}", span),
+                actual: now.to_string(),
+                source,
+                remediation,
+            },
+        })
+    }
+
     /// Record that an action was successfully executed.
     ///
     /// Call this AFTER the action succeeds to update counters.
@@ -749,6 +904,10 @@ pub struct RobotCandidate {
     pub has_policy_snapshot: bool,
     /// Whether process is supervised by an agent/IDE/CI.
     pub is_supervised: bool,
+
+    /// Whether the proposed action is destructive other than a kill
+    /// (e.g. restart); schedules hold these as they do kills.
+    pub is_destructive: bool,
 }
 
 impl RobotCandidate {
@@ -798,6 +957,12 @@ impl RobotCandidate {
         self.is_supervised = supervised;
         self
     }
+
+    /// Set whether the action is destructive.
+    pub fn with_destructive(mut self, destructive: bool) -> Self {
+        self.is_destructive = destructive;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1172,4 +1337,88 @@ mod tests {
         assert!(result_json.contains("min_posterior"));
         assert!(result_json.contains("Too low"));
     }
+
+    fn scheduled_checker(schedule: ActionSchedule, hour: u32) -> ConstraintChecker {
+        let constraints =
+            RuntimeRobotConstraints::from_policy(&test_robot_mode()).with_schedule(schedule);
+        // 2026-01-14 is a Wednesday.
+        let now = chrono::NaiveDate::from_ymd_opt(2026, 1, 14)
+            .unwrap()
+            .and_hms_opt(hour, 30, 0)
+            .unwrap();
+        ConstraintChecker::new(constraints).with_now(now)
+    }
+
+    #[test]
+    fn test_maintenance_window_defers_kills() {
+        let schedule = ActionSchedule {
+            maintenance_windows: vec![pt_config::schedule::MaintenanceWindow {
+                name: "nightly".to_string(),
+                cron: "0 2 * * *".to_string(),
+                duration_minutes: 120,
+            }],
+            ..Default::default()
+        };
+        let kill = RobotCandidate::new()
+            .with_posterior(0.99)
+            .with_kill_action(true);
+
+        let checker = scheduled_checker(schedule.clone(), 12);
+        let result = checker.check_candidate(&kill);
+        assert!(result.deferred_by_schedule());
+        assert_eq!(
+            result.violations[0].constraint,
+            ConstraintKind::OutsideMaintenanceWindow
+        );
+        assert_eq!(
+            checker.deferred_until(&kill).unwrap().to_string(),
+            "2026-01-15 02:00:00"
+        );
+        // Non-destructive actions are not held.
+        assert!(
+            checker
+                .check_candidate(&RobotCandidate::new().with_posterior(0.99))
+                .allowed
+        );
+
+        assert!(
+            scheduled_checker(schedule, 3)
+                .check_candidate(&kill)
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_business_hours_hold_category() {
+        let schedule = ActionSchedule {
+            business_hours: vec![pt_config::schedule::BusinessHours {
+                categories: vec!["dev_server".to_string()],
+                days: "mon-fri".to_string(),
+                start: "09:00".to_string(),
+                end: "18:00".to_string(),
+            }],
+            ..Default::default()
+        };
+        let checker = scheduled_checker(schedule, 12);
+        let dev = RobotCandidate::new()
+            .with_posterior(0.99)
+            .with_category("dev_server")
+            .with_destructive(true);
+        let result = checker.check_candidate(&dev);
+        assert!(result.deferred_by_schedule());
+        assert_eq!(
+            result.violations[0].constraint,
+            ConstraintKind::BusinessHours
+        );
+        assert!(
+            checker
+                .check_candidate(&dev.clone().with_category("test"))
+                .allowed
+        );
+        assert!(checker
+            .constraints()
+            .active_constraints_summary()
+            .iter()
+            .any(|s| s.starts_with("business_hours")));
+    }
 }
@@ -0,0 +1,421 @@
+//! Candidate deduplication across detection paths.
+//!
+//! One process can be surfaced by a signature match, by Bayesian inference,
+//! by the watch threshold and by several watch rules at once. Emitting each
+//! of those separately produces duplicate inbox items, duplicate notify hooks
+//! and plans with conflicting recommendations.
+//!
+//! A [`CandidateLedger`] keys every [`Detection`] by [`CandidateIdentity`]
+//! (PID plus start id, so a recycled PID is a different candidate) and merges
+//! them into one [`MergedCandidate`] carrying the combined [`Provenance`].
+//!
+//! Recommendations are reconciled conservatively: when paths recommend
+//! different actions (say `kill` and `spare`), the merged candidate becomes
+//! `review` and is flagged `conflicting`. A detection without a
+//! recommendation (e.g. a signature match that only informed the prior) adds
+//! provenance but never conflicts.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Identity of a process incarnation.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+pub struct CandidateIdentity {
+    pub pid: u32,
+    pub start_id: String,
+}
+
+impl CandidateIdentity {
+    pub fn new(pid: u32, start_id: impl Into<String>) -> Self {
+        Self {
+            pid,
+            start_id: start_id.into(),
+        }
+    }
+
+    /// Identity of a plan candidate (`pid` and `start_id` fields).
+    pub fn from_candidate(candidate: &Value) -> Option<Self> {
+        let pid = candidate.get("pid")?.as_u64()? as u32;
+        let start_id = candidate.get("start_id")?.as_str()?;
+        Some(Self::new(pid, start_id))
+    }
+}
+
+/// Path that surfaced a candidate.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionSource {
+    /// A signature matched and informed the prior.
+    Signature,
+    /// A high-confidence signature decided without inference.
+    SignatureFastPath,
+    /// Posterior inference and expected-loss decision.
+    Bayesian,
+    /// `agent watch` severity threshold.
+    WatchThreshold,
+    /// An `agent watch` rules-file rule.
+    WatchRule,
+    /// A daemon escalation plan.
+    DormantEscalation,
+}
+
+impl DetectionSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DetectionSource::Signature => "signature",
+            DetectionSource::SignatureFastPath => "signature_fast_path",
+            DetectionSource::Bayesian => "bayesian",
+            DetectionSource::WatchThreshold => "watch_threshold",
+            DetectionSource::WatchRule => "watch_rule",
+            DetectionSource::DormantEscalation => "dormant_escalation",
+        }
+    }
+}
+
+/// One path's view of a candidate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    pub source: DetectionSource,
+    /// Action this path recommends, if it recommends one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommendation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Rule, signature or trigger name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Detection {
+    pub fn new(source: DetectionSource) -> Self {
+        Self {
+            source,
+            recommendation: None,
+            confidence: None,
+            detail: None,
+        }
+    }
+
+    pub fn with_recommendation(mut self, recommendation: impl Into<String>) -> Self {
+        self.recommendation = Some(recommendation.into());
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Combined provenance of a merged candidate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Distinct sources, in the order they were first seen.
+    pub sources: Vec<DetectionSource>,
+    pub detections: Vec<Detection>,
+    /// Reconciled recommendation across detections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommendation: Option<String>,
+    /// Detections disagreed on the action.
+    pub conflicting: bool,
+}
+
+impl Provenance {
+    /// Add a detection. A repeat from the same source and detail replaces the
+    /// earlier one. Returns true if the reconciled recommendation changed.
+    pub fn add(&mut self, detection: Detection) -> bool {
+        if !self.sources.contains(&detection.source) {
+            self.sources.push(detection.source);
+        }
+        match self
+            .detections
+            .iter_mut()
+            .find(|d| d.source == detection.source && d.detail == detection.detail)
+        {
+            Some(existing) => *existing = detection,
+            None => self.detections.push(detection),
+        }
+        let before = self.recommendation.clone();
+        let (recommendation, conflicting) = reconcile(&self.detections);
+        self.recommendation = recommendation;
+        self.conflicting = conflicting;
+        before != self.recommendation
+    }
+
+    pub fn has_source(&self, source: DetectionSource) -> bool {
+        self.sources.contains(&source)
+    }
+
+    /// Details (rule or signature names) recorded for `source`.
+    pub fn details(&self, source: DetectionSource) -> Vec<String> {
+        self.detections
+            .iter()
+            .filter(|d| d.source == source)
+            .filter_map(|d| d.detail.clone())
+            .collect()
+    }
+
+    /// Highest confidence reported by any detection.
+    pub fn confidence(&self) -> Option<f64> {
+        self.detections
+            .iter()
+            .filter_map(|d| d.confidence)
+            .reduce(f64::max)
+    }
+}
+
+/// Reconcile recommendations: agreement wins, any disagreement is `review`.
+pub fn reconcile(detections: &[Detection]) -> (Option<String>, bool) {
+    let mut recommendations = detections
+        .iter()
+        .filter_map(|d| d.recommendation.as_deref());
+    let Some(first) = recommendations.next() else {
+        return (None, false);
+    };
+    if recommendations.all(|r| r == first) {
+        (Some(first.to_string()), false)
+    } else {
+        (Some("review".to_string()), true)
+    }
+}
+
+/// A candidate after merging every detection of its identity.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergedCandidate {
+    pub identity: CandidateIdentity,
+    pub provenance: Provenance,
+}
+
+/// What [`CandidateLedger::record`] did with a detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordOutcome {
+    /// First detection of this identity.
+    New,
+    /// Folded into an existing candidate; nothing new to emit.
+    Merged,
+    /// Folded in and the reconciled recommendation changed.
+    Changed,
+}
+
+/// Detections keyed by identity, in first-seen order.
+#[derive(Debug, Clone, Default)]
+pub struct CandidateLedger {
+    candidates: Vec<MergedCandidate>,
+    index: HashMap<CandidateIdentity, usize>,
+}
+
+impl CandidateLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, identity: CandidateIdentity, detection: Detection) -> RecordOutcome {
+        if let Some(&idx) = self.index.get(&identity) {
+            return if self.candidates[idx].provenance.add(detection) {
+                RecordOutcome::Changed
+            } else {
+                RecordOutcome::Merged
+            };
+        }
+        let mut provenance = Provenance::default();
+        provenance.add(detection);
+        self.index.insert(identity.clone(), self.candidates.len());
+        self.candidates.push(MergedCandidate {
+            identity,
+            provenance,
+        });
+        RecordOutcome::New
+    }
+
+    pub fn get(&self, identity: &CandidateIdentity) -> Option<&MergedCandidate> {
+        self.index.get(identity).map(|&idx| &self.candidates[idx])
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MergedCandidate> {
+        self.candidates.iter()
+    }
+}
+
+/// Merge plan candidates that share an identity.
+///
+/// `candidate` picks the candidate JSON out of each entry. The first entry of
+/// each identity is kept (plans are sorted by posterior, so it is the
+/// strongest); later duplicates contribute their `provenance`. A conflicting
+/// merge downgrades a `kill` recommendation to `review`. Returns the number
+/// of entries merged away.
+pub fn dedupe_plan_candidates<T>(
+    entries: &mut Vec<T>,
+    candidate: impl Fn(&mut T) -> &mut Value,
+) -> usize {
+    let mut kept: Vec<T> = Vec::with_capacity(entries.len());
+    let mut index: HashMap<CandidateIdentity, usize> = HashMap::new();
+    let mut merged = 0;
+
+    for mut entry in entries.drain(..) {
+        let Some(identity) = CandidateIdentity::from_candidate(candidate(&mut entry)) else {
+            kept.push(entry);
+            continue;
+        };
+        let Some(&idx) = index.get(&identity) else {
+            index.insert(identity, kept.len());
+            kept.push(entry);
+            continue;
+        };
+
+        merged += 1;
+        let incoming: Provenance =
+            serde_json::from_value(candidate(&mut entry)["provenance"].take()).unwrap_or_default();
+        let target = candidate(&mut kept[idx]);
+        let mut provenance: Provenance =
+            serde_json::from_value(target["provenance"].clone()).unwrap_or_default();
+        for detection in incoming.detections {
+            provenance.add(detection);
+        }
+        if provenance.conflicting && target["recommended_action"] == "kill" {
+            target["recommended_action"] = Value::from("review");
+            if target.get("recommendation").is_some() {
+                target["recommendation"] = Value::from("REVIEW");
+            }
+        }
+        target["provenance"] = serde_json::to_value(&provenance).unwrap_or(Value::Null);
+    }
+
+    *entries = kept;
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn id(pid: u32) -> CandidateIdentity {
+        CandidateIdentity::new(pid, format!("boot:{}:100", pid))
+    }
+
+    #[test]
+    fn merges_sources_for_one_identity() {
+        let mut ledger = CandidateLedger::new();
+        let outcome = ledger.record(
+            id(42),
+            Detection::new(DetectionSource::WatchThreshold).with_recommendation("kill"),
+        );
+        assert_eq!(outcome, RecordOutcome::New);
+        let outcome = ledger.record(
+            id(42),
+            Detection::new(DetectionSource::WatchRule)
+                .with_recommendation("kill")
+                .with_detail("leaky"),
+        );
+        assert_eq!(outcome, RecordOutcome::Merged);
+        // Same rule again replaces rather than duplicates.
+        ledger.record(
+            id(42),
+            Detection::new(DetectionSource::WatchRule)
+                .with_recommendation("kill")
+                .with_detail("leaky")
+                .with_confidence(0.9),
+        );
+
+        assert_eq!(ledger.len(), 1);
+        let merged = ledger.get(&id(42)).unwrap();
+        assert_eq!(
+            merged.provenance.sources,
+            vec![DetectionSource::WatchThreshold, DetectionSource::WatchRule]
+        );
+        assert_eq!(merged.provenance.detections.len(), 2);
+        assert_eq!(
+            merged.provenance.details(DetectionSource::WatchRule),
+            ["leaky"]
+        );
+        assert_eq!(merged.provenance.confidence(), Some(0.9));
+        assert_eq!(merged.provenance.recommendation.as_deref(), Some("kill"));
+        assert!(!merged.provenance.conflicting);
+    }
+
+    #[test]
+    fn recycled_pid_is_a_new_candidate() {
+        let mut ledger = CandidateLedger::new();
+        ledger.record(id(7), Detection::new(DetectionSource::Bayesian));
+        let outcome = ledger.record(
+            CandidateIdentity::new(7, "boot:7:999"),
+            Detection::new(DetectionSource::Bayesian),
+        );
+        assert_eq!(outcome, RecordOutcome::New);
+        assert_eq!(ledger.len(), 2);
+    }
+
+    #[test]
+    fn conflicting_recommendations_reconcile_to_review() {
+        let mut ledger = CandidateLedger::new();
+        ledger.record(
+            id(1),
+            Detection::new(DetectionSource::Signature).with_detail("jest-worker"),
+        );
+        ledger.record(
+            id(1),
+            Detection::new(DetectionSource::Bayesian).with_recommendation("kill"),
+        );
+        assert!(!ledger.get(&id(1)).unwrap().provenance.conflicting);
+
+        let outcome = ledger.record(
+            id(1),
+            Detection::new(DetectionSource::WatchThreshold).with_recommendation("spare"),
+        );
+        assert_eq!(outcome, RecordOutcome::Changed);
+        let provenance = &ledger.get(&id(1)).unwrap().provenance;
+        assert_eq!(provenance.recommendation.as_deref(), Some("review"));
+        assert!(provenance.conflicting);
+    }
+
+    #[test]
+    fn dedupes_plan_candidates_by_identity() {
+        let kill = Provenance {
+            sources: vec![DetectionSource::Bayesian],
+            detections: vec![Detection::new(DetectionSource::Bayesian).with_recommendation("kill")],
+            recommendation: Some("kill".to_string()),
+            conflicting: false,
+        };
+        let keep = Provenance {
+            sources: vec![DetectionSource::DormantEscalation],
+            detections: vec![
+                Detection::new(DetectionSource::DormantEscalation).with_recommendation("keep")
+            ],
+            recommendation: Some("keep".to_string()),
+            conflicting: false,
+        };
+        let mut candidates = vec![
+            json!({"pid": 10, "start_id": "10:1", "recommended_action": "kill", "provenance": kill}),
+            json!({"pid": 11, "start_id": "11:1", "recommended_action": "kill"}),
+            json!({"pid": 10, "start_id": "10:1", "recommended_action": "keep", "provenance": keep}),
+        ];
+
+        assert_eq!(dedupe_plan_candidates(&mut candidates, |c| c), 1);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0]["recommended_action"], "review");
+        assert_eq!(candidates[0]["provenance"]["conflicting"], true);
+        assert_eq!(
+            candidates[0]["provenance"]["sources"],
+            json!(["bayesian", "dormant_escalation"])
+        );
+    }
+}
@@ -3,127 +3,15 @@
 //! Provides lightweight, structured progress events for TUI and agent CLI
 //! consumers. Events are dispatched through an in-process event bus that
 //! supports multiple subscribers and JSONL formatting.
+//!
+//! The event types live in the `pt-events` crate, which downstream tools use
+//! to consume the same stream.
+
+pub use pt_events::{event_names, Phase, Progress, ProgressEvent};
 
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
 use std::io::Write;
 use std::sync::{mpsc, Arc, Mutex};
 
-/// Standard progress event names.
-pub mod event_names {
-    pub const SESSION_STARTED: &str = "session_started";
-    pub const SESSION_ENDED: &str = "session_ended";
-
-    pub const QUICK_SCAN_STARTED: &str = "quick_scan_started";
-    pub const QUICK_SCAN_PROGRESS: &str = "quick_scan_progress";
-    pub const QUICK_SCAN_COMPLETE: &str = "quick_scan_complete";
-
-    pub const DEEP_SCAN_STARTED: &str = "deep_scan_started";
-    pub const DEEP_SCAN_PROGRESS: &str = "deep_scan_progress";
-    pub const DEEP_SCAN_COMPLETE: &str = "deep_scan_complete";
-
-    pub const INFERENCE_STARTED: &str = "inference_started";
-    pub const INFERENCE_PROGRESS: &str = "inference_progress";
-    pub const INFERENCE_COMPLETE: &str = "inference_complete";
-
-    pub const DECISION_STARTED: &str = "decision_started";
-    pub const DECISION_COMPLETE: &str = "decision_complete";
-
-    pub const ACTION_STARTED: &str = "action_started";
-    pub const ACTION_COMPLETE: &str = "action_complete";
-    pub const ACTION_FAILED: &str = "action_failed";
-
-    pub const PLAN_READY: &str = "plan_ready";
-}
-
-/// High-level pipeline phase for a progress event.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Phase {
-    Session,
-    QuickScan,
-    DeepScan,
-    Infer,
-    Decide,
-    Plan,
-    Apply,
-    Ui,
-    Verify,
-    Report,
-    Bundle,
-}
-
-/// Progress counters for a phase.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Progress {
-    pub current: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub total: Option<u64>,
-}
-
-/// Structured progress event for CLI/TUI consumers.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProgressEvent {
-    pub event: String,
-    pub timestamp: DateTime<Utc>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub session_id: Option<String>,
-    pub phase: Phase,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub progress: Option<Progress>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub elapsed_ms: Option<u64>,
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub details: HashMap<String, Value>,
-}
-
-impl ProgressEvent {
-    pub fn new(event: impl Into<String>, phase: Phase) -> Self {
-        Self {
-            event: event.into(),
-            timestamp: Utc::now(),
-            session_id: None,
-            phase,
-            progress: None,
-            elapsed_ms: None,
-            details: HashMap::new(),
-        }
-    }
-
-    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
-        self.session_id = Some(session_id.into());
-        self
-    }
-
-    pub fn with_progress(mut self, current: u64, total: Option<u64>) -> Self {
-        self.progress = Some(Progress { current, total });
-        self
-    }
-
-    pub fn with_elapsed_ms(mut self, elapsed_ms: u64) -> Self {
-        self.elapsed_ms = Some(elapsed_ms);
-        self
-    }
-
-    pub fn with_detail(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
-        if let Ok(v) = serde_json::to_value(value) {
-            self.details.insert(key.into(), v);
-        }
-        self
-    }
-
-    pub fn to_jsonl(&self) -> String {
-        serde_json::to_string(self).unwrap_or_else(|_| {
-            format!(
-                r#"{{"error":"serialization_failed","event":"{}"}}"#,
-                self.event
-            )
-        })
-    }
-}
-
 /// Trait for emitting progress events.
 pub trait ProgressEmitter: Send + Sync {
     fn emit(&self, event: ProgressEvent);
@@ -0,0 +1,222 @@
+//! Feedback on past decisions (`pt-core feedback`, MCP `pt_feedback`).
+//!
+//! A user or agent labels a (session, PID) pair as a false positive ("this
+//! kill was wrong") or a false negative ("you missed this hog"). The label is
+//! resolved against the session's artifacts so it carries what the model
+//! believed at plan time, then stored in the telemetry `outcomes` table (see
+//! [`pt_telemetry::feedback`]).
+//!
+//! Stored labels feed back in two places:
+//!
+//! - calibration: each label is a resolved prediction with known ground
+//!   truth ([`crate::calibrate::ValidationEngine::add_feedback`]);
+//! - online prior updates: `agent verify --update-priors` turns labels for
+//!   the verified session into observations ([`observed_class`]).
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::inference::ObservedClass;
+use crate::session::snapshot_persist::{load_inference_unchecked, load_inventory_unchecked};
+use crate::session::SessionHandle;
+
+pub use pt_telemetry::feedback::{
+    read_feedback, write_feedback, FeedbackError, FeedbackKind, FeedbackRecord,
+};
+
+/// Actors allowed to give feedback.
+pub const FEEDBACK_ACTORS: &[&str] = &["user", "agent"];
+
+/// Errors from resolving a feedback target.
+#[derive(Debug, Error)]
+pub enum FeedbackTargetError {
+    #[error("unknown actor '{0}' (expected user or agent)")]
+    InvalidActor(String),
+
+    #[error("PID {pid} does not appear in session {session_id}")]
+    PidNotInSession { session_id: String, pid: u32 },
+}
+
+/// Build a feedback label for `pid` from the session's artifacts.
+///
+/// Plan candidates are preferred because they carry the posterior the
+/// decision was based on; processes that were scanned but never became
+/// candidates are labelled with a `keep` recommendation and no posterior.
+pub fn resolve_feedback(
+    handle: &SessionHandle,
+    pid: u32,
+    kind: FeedbackKind,
+    actor: &str,
+    note: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<FeedbackRecord, FeedbackTargetError> {
+    if !FEEDBACK_ACTORS.contains(&actor) {
+        return Err(FeedbackTargetError::InvalidActor(actor.to_string()));
+    }
+    let session_id = handle.id.0.clone();
+    let mut record = FeedbackRecord {
+        session_id: session_id.clone(),
+        pid,
+        start_id: String::new(),
+        kind,
+        actor: actor.to_string(),
+        note,
+        recorded_at: now,
+        cmd: String::new(),
+        recommendation: "keep".to_string(),
+        p_abandoned: None,
+        score: 0.0,
+        proc_type: "unknown".to_string(),
+    };
+
+    let plan: Option<serde_json::Value> =
+        std::fs::read_to_string(handle.dir.join("decision").join("plan.json"))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok());
+    let candidate = plan.as_ref().and_then(|plan| {
+        plan["candidates"]
+            .as_array()?
+            .iter()
+            .find(|c| c["pid"].as_u64() == Some(pid as u64))
+    });
+    if let Some(c) = candidate {
+        record.start_id = c["start_id"].as_str().unwrap_or_default().to_string();
+        record.cmd = c["command"].as_str().unwrap_or_default().to_string();
+        if let Some(action) = c["recommended_action"].as_str() {
+            record.recommendation = action.to_string();
+        }
+        record.p_abandoned = c["posterior"]["abandoned"].as_f64();
+        record.score = c["score"].as_f64().unwrap_or_default();
+        if let Some(proc_type) = c["type"].as_str() {
+            record.proc_type = proc_type.to_string();
+        }
+        return Ok(record);
+    }
+
+    let mut found = false;
+    if let Ok(inference) = load_inference_unchecked(handle) {
+        if let Some(inf) = inference.payload.candidates.iter().find(|c| c.pid == pid) {
+            record.start_id = inf.start_id.clone();
+            record.recommendation = inf.recommended_action.clone();
+            record.p_abandoned = Some(inf.posterior_abandoned);
+            record.score = inf.score as f64;
+            record.proc_type = inf.classification.clone();
+            found = true;
+        }
+    }
+    if let Ok(inventory) = load_inventory_unchecked(handle) {
+        if let Some(proc) = inventory.payload.records.iter().find(|p| p.pid == pid) {
+            if record.start_id.is_empty() {
+                record.start_id = proc.start_id.clone();
+            }
+            record.cmd = proc.cmd.clone();
+            found = true;
+        }
+    }
+    if !found {
+        return Err(FeedbackTargetError::PidNotInSession { session_id, pid });
+    }
+    Ok(record)
+}
+
+/// Ground-truth class a label implies for online prior updates.
+pub fn observed_class(kind: FeedbackKind) -> ObservedClass {
+    match kind {
+        FeedbackKind::FalsePositive => ObservedClass::Useful,
+        FeedbackKind::FalseNegative => ObservedClass::Abandoned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pt_common::SessionId;
+    use tempfile::TempDir;
+
+    fn session_with_plan(dir: &TempDir, plan: serde_json::Value) -> SessionHandle {
+        let handle = SessionHandle {
+            id: SessionId("pt-20260115-143022-fbck".to_string()),
+            dir: dir.path().to_path_buf(),
+        };
+        std::fs::create_dir_all(handle.dir.join("decision")).unwrap();
+        std::fs::write(
+            handle.dir.join("decision").join("plan.json"),
+            serde_json::to_string(&plan).unwrap(),
+        )
+        .unwrap();
+        handle
+    }
+
+    #[test]
+    fn resolves_plan_candidate() {
+        let dir = TempDir::new().unwrap();
+        let handle = session_with_plan(
+            &dir,
+            serde_json::json!({
+                "candidates": [{
+                    "pid": 4242,
+                    "start_id": "4242:1700000000",
+                    "command": "node server.js",
+                    "recommended_action": "kill",
+                    "posterior": {"abandoned": 0.93},
+                    "score": 91,
+                    "type": "dev_server",
+                }]
+            }),
+        );
+        let record = resolve_feedback(
+            &handle,
+            4242,
+            FeedbackKind::FalsePositive,
+            "agent",
+            Some("still serving".to_string()),
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(record.start_id, "4242:1700000000");
+        assert_eq!(record.recommendation, "kill");
+        assert_eq!(record.p_abandoned, Some(0.93));
+        assert_eq!(record.proc_type, "dev_server");
+        assert_eq!(record.actor, "agent");
+    }
+
+    #[test]
+    fn rejects_unknown_pid_and_actor() {
+        let dir = TempDir::new().unwrap();
+        let handle = session_with_plan(&dir, serde_json::json!({"candidates": []}));
+        assert!(matches!(
+            resolve_feedback(
+                &handle,
+                1,
+                FeedbackKind::FalseNegative,
+                "user",
+                None,
+                Utc::now()
+            ),
+            Err(FeedbackTargetError::PidNotInSession { pid: 1, .. })
+        ));
+        assert!(matches!(
+            resolve_feedback(
+                &handle,
+                1,
+                FeedbackKind::FalseNegative,
+                "robot",
+                None,
+                Utc::now()
+            ),
+            Err(FeedbackTargetError::InvalidActor(_))
+        ));
+    }
+
+    #[test]
+    fn labels_map_to_observed_classes() {
+        assert_eq!(
+            observed_class(FeedbackKind::FalsePositive),
+            ObservedClass::Useful
+        );
+        assert_eq!(
+            observed_class(FeedbackKind::FalseNegative),
+            ObservedClass::Abandoned
+        );
+    }
+}
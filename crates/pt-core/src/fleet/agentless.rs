@@ -0,0 +1,385 @@
+//! Agentless remote collection for fleet mode.
+//!
+//! Instead of running `pt-core scan` on the remote host, ssh runs a small
+//! POSIX shell payload that prints `ps` output plus the few `/proc` facts pt
+//! needs for process identity. The local pt-core parses that text into
+//! [`ProcessRecord`]s and runs inference itself, so hosts need nothing but
+//! `sh` and `ps`.
+//!
+//! # Payload format
+//! The payload prints sections introduced by `@@<name>` marker lines:
+//! - `@@pt-agentless <version>` - Format header
+//! - `@@uname` - `uname -s` (Linux or Darwin)
+//! - `@@boot_id` - `/proc/sys/kernel/random/boot_id` (Linux only)
+//! - `@@now` - Remote clock, unix seconds
+//! - `@@ps` - `ps` in the same column layout as the local quick scan
+//! - `@@stat` - `/proc/[pid]/stat` lines, for start ticks (Linux only)
+//! - `@@end` - Absent when the output was cut off
+
+use super::ssh_scan::{scan_result_to_host_input_with, HostScanResult};
+use crate::collect::{
+    is_header_line, parse_ps_line_synthetic, ProcessRecord, ProcessState, ScanMetadata, ScanResult,
+};
+use crate::config::priors::Priors;
+use crate::config::Policy;
+use crate::decision::{decide_action, Action, ActionFeasibility};
+use crate::inference::{compute_posterior, ClassScores, CpuEvidence, Evidence};
+use crate::session::fleet::HostInput;
+use pt_common::StartId;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Payload format version printed in the `@@pt-agentless` header.
+pub const PAYLOAD_VERSION: u32 = 1;
+
+/// Shell payload run on the remote host.
+///
+/// One line of `;`-separated POSIX sh with no single quotes, so it survives
+/// being wrapped in `sh -c '...'` under any login shell.
+pub const REMOTE_PAYLOAD: &str = "echo @@pt-agentless 1; \
+     echo @@uname; uname -s; \
+     echo @@boot_id; cat /proc/sys/kernel/random/boot_id 2>/dev/null; \
+     echo @@now; date +%s; \
+     echo @@ps; \
+     if [ \"$(uname -s)\" = Linux ]; then \
+     LC_ALL=C ps -eo pid,ppid,uid,user,pgid,sid,state,%cpu,rss,vsz,tty,lstart,etimes,comm,args --no-headers -ww; \
+     else \
+     LC_ALL=C ps -eo pid,ppid,uid,user,pgid,sess,state,%cpu,rss,vsz,tty,lstart,etime,comm,args; \
+     fi; \
+     echo @@stat; \
+     if [ -r /proc/self/stat ]; then cat /proc/[0-9]*/stat 2>/dev/null; fi; \
+     echo @@end";
+
+/// Remote command line handed to ssh.
+pub fn remote_command() -> String {
+    format!("sh -c '{}'", REMOTE_PAYLOAD)
+}
+
+/// Errors from parsing agentless payload output.
+#[derive(Debug, Error)]
+pub enum AgentlessError {
+    #[error("output is not from the agentless payload (missing @@pt-agentless header)")]
+    MissingHeader,
+    #[error("unsupported payload version {0}")]
+    UnsupportedVersion(String),
+    #[error("payload output was truncated (missing @@end)")]
+    Truncated,
+    #[error("payload section @@{0} is missing or empty")]
+    MissingSection(&'static str),
+    #[error("unsupported remote platform: {0}")]
+    UnsupportedPlatform(String),
+    #[error("invalid remote clock value: {0}")]
+    InvalidClock(String),
+}
+
+/// Split payload output into its sections.
+fn split_sections(output: &str) -> Result<HashMap<&str, Vec<&str>>, AgentlessError> {
+    let mut lines = output.lines().skip_while(|line| line.trim().is_empty());
+    let version = lines
+        .next()
+        .and_then(|line| line.trim().strip_prefix("@@pt-agentless"))
+        .ok_or(AgentlessError::MissingHeader)?
+        .trim();
+    if version.parse::<u32>().ok() != Some(PAYLOAD_VERSION) {
+        return Err(AgentlessError::UnsupportedVersion(version.to_string()));
+    }
+
+    let mut sections: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut current = None;
+    let mut complete = false;
+    for line in lines {
+        if let Some(name) = line.strip_prefix("@@") {
+            let name = name.trim();
+            if name == "end" {
+                complete = true;
+                break;
+            }
+            sections.entry(name).or_default();
+            current = Some(name);
+        } else if let Some(name) = current {
+            if !line.trim().is_empty() {
+                sections.entry(name).or_default().push(line);
+            }
+        }
+    }
+    if !complete {
+        return Err(AgentlessError::Truncated);
+    }
+    Ok(sections)
+}
+
+fn first_line<'a>(sections: &HashMap<&str, Vec<&'a str>>, name: &str) -> Option<&'a str> {
+    sections
+        .get(name)
+        .and_then(|lines| lines.first())
+        .map(|line| line.trim())
+}
+
+/// Start time in clock ticks (field 22) of each `/proc/[pid]/stat` line.
+fn parse_stat_start_ticks(lines: &[&str]) -> HashMap<u32, u64> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let pid = line.split_whitespace().next()?.parse().ok()?;
+            // comm may contain spaces and parens; fields resume after the last ')'.
+            let rest = &line[line.rfind(')')? + 1..];
+            let ticks = rest.split_whitespace().nth(19)?.parse().ok()?;
+            Some((pid, ticks))
+        })
+        .collect()
+}
+
+/// Kernel threads on the remote platform (see `quick_scan::is_kernel_thread`).
+fn is_remote_kernel_thread(record: &ProcessRecord, platform: &str) -> bool {
+    record.pid.0 != 1 && (record.ppid.0 == 0 || (platform == "linux" && record.ppid.0 == 2))
+}
+
+/// Parse the output of [`REMOTE_PAYLOAD`] into a scan result.
+///
+/// Timing uses the remote clock. On Linux, start ids use the boot id and
+/// `/proc` start ticks like a local scan, so they stay stable between scans.
+/// Unparseable `ps` lines become warnings; kernel threads are dropped.
+pub fn parse_payload_output(output: &str) -> Result<ScanResult, AgentlessError> {
+    let sections = split_sections(output)?;
+
+    let uname = first_line(&sections, "uname").ok_or(AgentlessError::MissingSection("uname"))?;
+    let platform = match uname {
+        "Linux" => "linux",
+        "Darwin" => "macos",
+        other => return Err(AgentlessError::UnsupportedPlatform(other.to_string())),
+    };
+    let boot_id = first_line(&sections, "boot_id").map(str::to_string);
+    let now_raw = first_line(&sections, "now").ok_or(AgentlessError::MissingSection("now"))?;
+    let now_unix: i64 = now_raw
+        .parse()
+        .map_err(|_| AgentlessError::InvalidClock(now_raw.to_string()))?;
+    let ps_lines = sections
+        .get("ps")
+        .filter(|lines| !lines.is_empty())
+        .ok_or(AgentlessError::MissingSection("ps"))?;
+    let start_ticks = sections
+        .get("stat")
+        .map(|lines| parse_stat_start_ticks(lines))
+        .unwrap_or_default();
+
+    let mut processes = Vec::new();
+    let mut warnings = Vec::new();
+    for (line_num, line) in ps_lines.iter().enumerate() {
+        if line_num == 0 && is_header_line(line) {
+            continue;
+        }
+        let mut record = match parse_ps_line_synthetic(line, platform, &boot_id, now_unix) {
+            Ok(record) => record,
+            Err(e) => {
+                warnings.push(format!("Line {}: {}", line_num + 1, e));
+                continue;
+            }
+        };
+        if is_remote_kernel_thread(&record, platform) {
+            continue;
+        }
+        if let (Some(boot), Some(ticks)) = (&boot_id, start_ticks.get(&record.pid.0)) {
+            record.start_id = StartId::from_linux(boot, *ticks, record.pid.0);
+        }
+        record.source = "agentless".to_string();
+        processes.push(record);
+    }
+
+    let started_at = chrono::DateTime::from_timestamp(now_unix, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339();
+    Ok(ScanResult {
+        metadata: ScanMetadata {
+            scan_type: "agentless".to_string(),
+            platform: platform.to_string(),
+            boot_id,
+            started_at,
+            duration_ms: 0,
+            process_count: processes.len(),
+            warnings,
+            visibility: Default::default(),
+        },
+        processes,
+    })
+}
+
+/// Local inference over remotely collected records.
+#[derive(Debug, Clone)]
+pub struct LocalInference {
+    pub priors: Priors,
+    pub policy: Policy,
+}
+
+impl LocalInference {
+    pub fn new(priors: Priors, policy: Policy) -> Self {
+        Self { priors, policy }
+    }
+
+    /// Classify one remote process.
+    ///
+    /// Returns (classification, recommended_action, score), where the score
+    /// is the posterior mass off the "useful" class. Processes whose posterior
+    /// cannot be computed score 0.
+    pub fn classify(&self, process: &ProcessRecord) -> (String, String, f64) {
+        let evidence = Evidence {
+            cpu: Some(CpuEvidence::Fraction {
+                occupancy: (process.cpu_percent / 100.0).clamp(0.0, 1.0),
+            }),
+            runtime_seconds: Some(process.elapsed.as_secs_f64()),
+            orphan: Some(process.is_orphan()),
+            tty: Some(process.has_tty()),
+            state_flag: state_flag(process.state),
+            ..Evidence::default()
+        };
+        let Ok(result) = compute_posterior(&self.priors, &evidence) else {
+            return ("unknown".to_string(), "spare".to_string(), 0.0);
+        };
+        let action = match decide_action(
+            &result.posterior,
+            &self.policy,
+            &ActionFeasibility::allow_all(),
+        ) {
+            Ok(outcome) => match outcome.optimal_action {
+                Action::Kill => "kill",
+                Action::Keep => "spare",
+                _ => "review",
+            },
+            Err(_) => "review",
+        };
+        (
+            top_class(&result.posterior).to_string(),
+            action.to_string(),
+            (1.0 - result.posterior.useful).clamp(0.0, 1.0),
+        )
+    }
+
+    /// Build a fleet host input, classifying every process by inference.
+    pub fn host_input(&self, result: &HostScanResult) -> HostInput {
+        scan_result_to_host_input_with(result, |process| self.classify(process))
+    }
+}
+
+fn state_flag(state: ProcessState) -> Option<usize> {
+    match state {
+        ProcessState::Running => Some(0),
+        ProcessState::Sleeping => Some(1),
+        ProcessState::DiskSleep => Some(2),
+        ProcessState::Zombie => Some(3),
+        ProcessState::Stopped => Some(4),
+        ProcessState::Idle => Some(5),
+        ProcessState::Dead => Some(6),
+        ProcessState::Unknown => None,
+    }
+}
+
+fn top_class(posterior: &ClassScores) -> &'static str {
+    [
+        ("useful", posterior.useful),
+        ("useful_bad", posterior.useful_bad),
+        ("abandoned", posterior.abandoned),
+        ("zombie", posterior.zombie),
+    ]
+    .into_iter()
+    .max_by(|a, b| a.1.total_cmp(&b.1))
+    .map(|(name, _)| name)
+    .unwrap_or("useful")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINUX_OUTPUT: &str = "@@pt-agentless 1
+@@uname
+Linux
+@@boot_id
+8d1c7a52-4bb6-4a4e-9d33-5e1f0b6c2a10
+@@now
+1767225600
+@@ps
+    1     0     0 root         1     1 S  0.0 12000 170000 ?        Thu Jan  1 00:00:00 2026 86400 systemd /sbin/init
+    2     0     0 root         0     0 S  0.0     0      0 ?        Thu Jan  1 00:00:00 2026 86400 kthreadd [kthreadd]
+  812     1  1000 alice      812   812 T  0.0 204800 409600 ?       Thu Jan  1 00:00:00 2026 86400 node node /srv/app/server.js
+  913   812  1000 alice      812   812 Z  0.0     0      0 ?        Thu Jan  1 12:00:00 2026 43200 worker [worker] <defunct>
+garbage line
+@@stat
+1 (systemd) S 0 1 1 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 1 0 42 170000 3000
+812 (node (srv) x) T 1 812 812 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 11 0 93500 409600 51200
+@@end
+";
+
+    #[test]
+    fn payload_is_safe_inside_single_quotes() {
+        assert!(!REMOTE_PAYLOAD.contains('\''));
+        assert!(!REMOTE_PAYLOAD.contains('\n'));
+        assert!(remote_command().starts_with("sh -c 'echo @@pt-agentless 1;"));
+    }
+
+    #[test]
+    fn parses_linux_payload() {
+        let scan = parse_payload_output(LINUX_OUTPUT).unwrap();
+        assert_eq!(scan.metadata.scan_type, "agentless");
+        assert_eq!(scan.metadata.platform, "linux");
+        assert_eq!(scan.metadata.started_at, "2026-01-01T00:00:00+00:00");
+        // kthreadd is dropped; the garbage line becomes a warning.
+        let pids: Vec<u32> = scan.processes.iter().map(|p| p.pid.0).collect();
+        assert_eq!(pids, vec![1, 812, 913]);
+        assert_eq!(scan.metadata.warnings.len(), 1);
+
+        let node = &scan.processes[1];
+        assert_eq!(node.comm, "node");
+        assert_eq!(node.state, ProcessState::Stopped);
+        assert_eq!(node.rss_bytes, 204800 * 1024);
+        assert_eq!(node.elapsed.as_secs(), 86400);
+        assert_eq!(node.source, "agentless");
+        assert_eq!(
+            node.start_id.0,
+            "8d1c7a52-4bb6-4a4e-9d33-5e1f0b6c2a10:93500:812"
+        );
+    }
+
+    #[test]
+    fn truncated_or_foreign_output_is_rejected() {
+        let truncated = LINUX_OUTPUT.replace("@@end\n", "");
+        assert!(matches!(
+            parse_payload_output(&truncated),
+            Err(AgentlessError::Truncated)
+        ));
+        assert!(matches!(
+            parse_payload_output("{\"scan\": {}}"),
+            Err(AgentlessError::MissingHeader)
+        ));
+        let windows = LINUX_OUTPUT.replace("Linux", "MINGW64_NT");
+        assert!(matches!(
+            parse_payload_output(&windows),
+            Err(AgentlessError::UnsupportedPlatform(_))
+        ));
+    }
+
+    #[test]
+    fn stat_ticks_survive_parens_in_comm() {
+        let ticks = parse_stat_start_ticks(&[
+            "812 (node (srv) x) T 1 812 812 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 11 0 93500 409600",
+        ]);
+        assert_eq!(ticks.get(&812), Some(&93500));
+    }
+
+    #[test]
+    fn local_inference_flags_zombies() {
+        let scan = parse_payload_output(LINUX_OUTPUT).unwrap();
+        let inference = LocalInference::new(Priors::default(), Policy::default());
+        let (_, _, init_score) = inference.classify(&scan.processes[0]);
+        let (_, _, zombie_score) = inference.classify(&scan.processes[2]);
+        assert!(zombie_score > init_score);
+
+        let input = inference.host_input(&HostScanResult {
+            host: "web1".to_string(),
+            success: true,
+            scan: Some(scan),
+            error: None,
+            duration_ms: 120,
+        });
+        assert_eq!(input.total_processes, 3);
+        assert!(input.candidates.iter().any(|c| c.pid == 913));
+    }
+}
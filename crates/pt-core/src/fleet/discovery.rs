@@ -4,15 +4,19 @@
 //! - Provider trait + registry
 //! - Static inventory provider
 //! - DNS provider scaffold (feature-gated)
-//! - Config schema for future AWS/GCP/K8s providers
+//! - Kubernetes node provider (via `kubectl`)
+//! - Config schema for future AWS/GCP providers
 
+use crate::collect::tool_runner::ToolRunnerBuilder;
 use crate::fleet::inventory::{load_inventory_from_path, FleetInventory, InventoryError};
-use crate::fleet::inventory::{HostRecord, INVENTORY_SCHEMA_VERSION};
+use crate::fleet::inventory::{HostRecord, InventoryStatus, INVENTORY_SCHEMA_VERSION};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
 pub const DISCOVERY_SCHEMA_VERSION: &str = "1.0.0";
@@ -81,6 +85,9 @@ pub enum ProviderConfig {
         #[serde(default)]
         labels: HashMap<String, String>,
     },
+    /// Kubernetes nodes. With a namespace or label selector, only nodes
+    /// running matching pods are listed.
+    #[serde(alias = "kubernetes")]
     K8s {
         #[serde(default)]
         namespace: Option<String>,
@@ -207,10 +214,16 @@ impl ProviderRegistry {
                         "gcp provider not implemented".to_string(),
                     ));
                 }
-                ProviderConfig::K8s { .. } => {
-                    return Err(DiscoveryError::Other(
-                        "k8s provider not implemented".to_string(),
-                    ));
+                ProviderConfig::K8s {
+                    namespace,
+                    label_selector,
+                } => {
+                    registry
+                        .providers
+                        .push(Box::new(KubernetesInventoryProvider::new(
+                            namespace.as_deref(),
+                            label_selector.as_deref(),
+                        )));
                 }
             }
         }
@@ -313,10 +326,180 @@ impl InventoryProvider for DnsDiscoveryProvider {
             schema_version: INVENTORY_SCHEMA_VERSION.to_string(),
             generated_at: Utc::now().to_rfc3339(),
             hosts: vec![host],
+            groups: HashMap::new(),
+        })
+    }
+}
+
+const KUBECTL_TIMEOUT: Duration = Duration::from_secs(30);
+const KUBECTL_MAX_OUTPUT: usize = 32 * 1024 * 1024;
+
+/// Kubernetes node discovery through the API server.
+///
+/// Runs `kubectl get nodes -o json` against the current kubeconfig context
+/// (`KUBECONFIG` is honoured). When a namespace or pod label selector is set,
+/// running pods are listed too and only the nodes hosting them are kept.
+#[derive(Debug, Clone)]
+pub struct KubernetesInventoryProvider {
+    namespace: Option<String>,
+    label_selector: Option<String>,
+}
+
+impl KubernetesInventoryProvider {
+    pub fn new(namespace: Option<&str>, label_selector: Option<&str>) -> Self {
+        Self {
+            namespace: namespace.map(|s| s.to_string()),
+            label_selector: label_selector.map(|s| s.to_string()),
+        }
+    }
+
+    fn pod_args(&self) -> Vec<&str> {
+        let mut args = vec![
+            "get",
+            "pods",
+            "-o",
+            "json",
+            "--field-selector=status.phase=Running",
+        ];
+        match &self.namespace {
+            Some(namespace) => args.extend(["-n", namespace.as_str()]),
+            None => args.push("--all-namespaces"),
+        }
+        if let Some(selector) = &self.label_selector {
+            args.extend(["-l", selector.as_str()]);
+        }
+        args
+    }
+
+    fn kubectl(&self, args: &[&str]) -> Result<String, DiscoveryError> {
+        let runner = ToolRunnerBuilder::new()
+            .timeout(KUBECTL_TIMEOUT)
+            .budget_ms(2 * KUBECTL_TIMEOUT.as_millis() as u64)
+            .max_output(KUBECTL_MAX_OUTPUT)
+            .use_nice(false)
+            .build();
+        let output = runner
+            .run_tool("kubectl", args, None)
+            .map_err(|e| DiscoveryError::Other(format!("kubectl failed: {}", e)))?;
+        if !output.success() {
+            return Err(DiscoveryError::Other(format!(
+                "kubectl {} failed: {}",
+                args.join(" "),
+                output.stderr_str().trim()
+            )));
+        }
+        Ok(output.stdout_str())
+    }
+}
+
+impl InventoryProvider for KubernetesInventoryProvider {
+    fn name(&self) -> &str {
+        "kubernetes"
+    }
+
+    fn discover(&self) -> Result<FleetInventory, DiscoveryError> {
+        let mut hosts = parse_kubernetes_nodes(&self.kubectl(&["get", "nodes", "-o", "json"])?)?;
+        if self.namespace.is_some() || self.label_selector.is_some() {
+            let nodes = parse_kubernetes_pod_nodes(&self.kubectl(&self.pod_args())?)?;
+            hosts.retain(|host| nodes.contains(&host.hostname));
+        }
+
+        Ok(FleetInventory {
+            schema_version: INVENTORY_SCHEMA_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            hosts,
+            groups: HashMap::new(),
         })
     }
 }
 
+fn parse_kubectl_items(json: &str) -> Result<Vec<Value>, DiscoveryError> {
+    let mut list: Value = serde_json::from_str(json)
+        .map_err(|e| DiscoveryError::Other(format!("failed to parse kubectl output: {}", e)))?;
+    match list.get_mut("items").map(Value::take) {
+        Some(Value::Array(items)) => Ok(items),
+        _ => Err(DiscoveryError::Other(
+            "kubectl output has no items list".to_string(),
+        )),
+    }
+}
+
+/// Host records from `kubectl get nodes -o json`.
+///
+/// Nodes are tagged with `provider=kubernetes` and, when known, their zone,
+/// instance type, roles and internal IP. Nodes whose `Ready` condition is
+/// not `True` are marked unreachable.
+pub fn parse_kubernetes_nodes(json: &str) -> Result<Vec<HostRecord>, DiscoveryError> {
+    Ok(parse_kubectl_items(json)?
+        .iter()
+        .filter_map(node_to_host)
+        .collect())
+}
+
+fn node_to_host(node: &Value) -> Option<HostRecord> {
+    let hostname = node["metadata"]["name"].as_str()?.to_string();
+    let labels = node["metadata"]["labels"].as_object();
+    let label = |key: &str| labels.and_then(|l| l.get(key)).and_then(Value::as_str);
+
+    let mut tags = HashMap::new();
+    tags.insert("provider".to_string(), "kubernetes".to_string());
+    if let Some(zone) = label("topology.kubernetes.io/zone") {
+        tags.insert("zone".to_string(), zone.to_string());
+    }
+    if let Some(instance_type) = label("node.kubernetes.io/instance-type") {
+        tags.insert("instance_type".to_string(), instance_type.to_string());
+    }
+    let roles: BTreeSet<&str> = labels
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, _)| key.strip_prefix("node-role.kubernetes.io/"))
+        .filter(|role| !role.is_empty())
+        .collect();
+    if !roles.is_empty() {
+        tags.insert(
+            "roles".to_string(),
+            roles.into_iter().collect::<Vec<_>>().join(","),
+        );
+    }
+    if let Some(ip) = node["status"]["addresses"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|addr| addr["type"] == "InternalIP")
+        .and_then(|addr| addr["address"].as_str())
+    {
+        tags.insert("internal_ip".to_string(), ip.to_string());
+    }
+
+    let ready = node["status"]["conditions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|cond| cond["type"] == "Ready" && cond["status"] == "True");
+
+    Some(HostRecord {
+        hostname,
+        tags,
+        access_method: None,
+        credentials_ref: None,
+        last_seen: None,
+        status: Some(if ready {
+            InventoryStatus::Active
+        } else {
+            InventoryStatus::Unreachable
+        }),
+    })
+}
+
+/// Names of the nodes running the pods in `kubectl get pods -o json`.
+pub fn parse_kubernetes_pod_nodes(json: &str) -> Result<HashSet<String>, DiscoveryError> {
+    Ok(parse_kubectl_items(json)?
+        .iter()
+        .filter_map(|pod| pod["spec"]["nodeName"].as_str())
+        .map(|node| node.to_string())
+        .collect())
+}
+
 fn merge_inventories(inventories: &[FleetInventory]) -> FleetInventory {
     let mut by_host: HashMap<String, HostRecord> = HashMap::new();
     for inventory in inventories {
@@ -346,10 +529,19 @@ fn merge_inventories(inventories: &[FleetInventory]) -> FleetInventory {
     let mut hosts: Vec<HostRecord> = by_host.into_values().collect();
     hosts.sort_by(|a, b| a.hostname.cmp(&b.hostname));
 
+    // First provider to define a group wins, like host fields above.
+    let mut groups = HashMap::new();
+    for inventory in inventories {
+        for (name, group) in &inventory.groups {
+            groups.entry(name.clone()).or_insert_with(|| group.clone());
+        }
+    }
+
     FleetInventory {
         schema_version: INVENTORY_SCHEMA_VERSION.to_string(),
         generated_at: Utc::now().to_rfc3339(),
         hosts,
+        groups,
     }
 }
 
@@ -706,7 +898,7 @@ path = "fleet.toml"
     }
 
     #[test]
-    fn registry_from_config_k8s_not_implemented() {
+    fn registry_from_config_k8s() {
         let config = FleetDiscoveryConfig {
             schema_version: DISCOVERY_SCHEMA_VERSION.to_string(),
             generated_at: None,
@@ -718,8 +910,70 @@ path = "fleet.toml"
             refresh_interval_secs: None,
             stale_while_revalidate_secs: None,
         };
-        let err = ProviderRegistry::from_config(&config).err().unwrap();
-        assert!(err.to_string().contains("k8s"));
+        let registry = ProviderRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.providers.len(), 1);
+        assert_eq!(registry.providers[0].name(), "kubernetes");
+    }
+
+    // ── KubernetesInventoryProvider ─────────────────────────────────
+
+    #[test]
+    fn provider_config_kubernetes_alias() {
+        let config: FleetDiscoveryConfig =
+            serde_json::from_str(r#"{"providers":[{"type":"kubernetes","namespace":"shop"}]}"#)
+                .unwrap();
+        assert!(matches!(
+            &config.providers[0],
+            ProviderConfig::K8s { namespace: Some(ns), .. } if ns == "shop"
+        ));
+    }
+
+    #[test]
+    fn kubernetes_pod_args_scope_namespace_and_selector() {
+        let all = KubernetesInventoryProvider::new(None, Some("app=web"));
+        assert!(all.pod_args().contains(&"--all-namespaces"));
+        assert!(all.pod_args().ends_with(&["-l", "app=web"]));
+        let scoped = KubernetesInventoryProvider::new(Some("shop"), None);
+        assert!(scoped.pod_args().ends_with(&["-n", "shop"]));
+    }
+
+    #[test]
+    fn parse_kubernetes_nodes_tags_and_readiness() {
+        let json = r#"{"items":[
+            {"metadata":{"name":"node-a","labels":{
+                "topology.kubernetes.io/zone":"eu-west-1a",
+                "node.kubernetes.io/instance-type":"m5.large",
+                "node-role.kubernetes.io/worker":"",
+                "node-role.kubernetes.io/ingress":""}},
+             "status":{"addresses":[{"type":"InternalIP","address":"10.0.0.5"}],
+                       "conditions":[{"type":"Ready","status":"True"}]}},
+            {"metadata":{"name":"node-b"},
+             "status":{"conditions":[{"type":"Ready","status":"Unknown"}]}},
+            {"metadata":{}}
+        ]}"#;
+        let hosts = parse_kubernetes_nodes(json).unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].hostname, "node-a");
+        assert_eq!(hosts[0].tags["provider"], "kubernetes");
+        assert_eq!(hosts[0].tags["zone"], "eu-west-1a");
+        assert_eq!(hosts[0].tags["instance_type"], "m5.large");
+        assert_eq!(hosts[0].tags["roles"], "ingress,worker");
+        assert_eq!(hosts[0].tags["internal_ip"], "10.0.0.5");
+        assert_eq!(hosts[0].status, Some(InventoryStatus::Active));
+        assert_eq!(hosts[1].status, Some(InventoryStatus::Unreachable));
+
+        assert!(parse_kubernetes_nodes(r#"{"kind":"Status"}"#).is_err());
+    }
+
+    #[test]
+    fn parse_kubernetes_pod_nodes_skips_unscheduled() {
+        let json = r#"{"items":[
+            {"spec":{"nodeName":"node-a"}},
+            {"spec":{"nodeName":"node-a"}},
+            {"spec":{}}
+        ]}"#;
+        let nodes = parse_kubernetes_pod_nodes(json).unwrap();
+        assert_eq!(nodes, HashSet::from(["node-a".to_string()]));
     }
 
     // ── StaticInventoryProvider ──────────────────────────────────────
@@ -773,6 +1027,7 @@ path = "fleet.toml"
                 last_seen: None,
                 status: None,
             }],
+            groups: HashMap::new(),
         };
         let result = merge_inventories(&[inv]);
         assert_eq!(result.hosts.len(), 1);
@@ -792,6 +1047,7 @@ path = "fleet.toml"
                 last_seen: None,
                 status: None,
             }],
+            groups: HashMap::new(),
         };
         let inv2 = FleetInventory {
             schema_version: INVENTORY_SCHEMA_VERSION.to_string(),
@@ -804,6 +1060,7 @@ path = "fleet.toml"
                 last_seen: Some("2026-01-01".to_string()),
                 status: None,
             }],
+            groups: HashMap::new(),
         };
         let result = merge_inventories(&[inv1, inv2]);
         assert_eq!(result.hosts.len(), 1);
@@ -851,6 +1108,7 @@ path = "fleet.toml"
                     status: None,
                 },
             ],
+            groups: HashMap::new(),
         };
         let result = merge_inventories(&[inv]);
         assert_eq!(result.hosts[0].hostname, "alpha");
@@ -1,6 +1,10 @@
 //! Fleet inventory parsing and static discovery provider.
 //!
 //! Supports static configuration via TOML/YAML/JSON inventory files.
+//!
+//! Hosts join a group through a `group` key or a `group`/`role` tag; the
+//! optional top-level `groups` table sets per-group options such as the host
+//! profile used to pool group priors.
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -51,12 +55,34 @@ pub struct HostRecord {
     pub status: Option<InventoryStatus>,
 }
 
+impl HostRecord {
+    /// Host group: the `group` tag, else the `role` tag.
+    pub fn group(&self) -> Option<&str> {
+        self.tags
+            .get("group")
+            .or_else(|| self.tags.get("role"))
+            .map(String::as_str)
+    }
+}
+
+/// Options for a host group.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostGroup {
+    /// Host profile (laptop, ci_runner, k8s_node, server). When set, local
+    /// priors are shrunk toward the group priors instead of replaced by them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_profile: Option<String>,
+}
+
 /// Fleet inventory loaded from a static config.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FleetInventory {
     pub schema_version: String,
     pub generated_at: String,
     pub hosts: Vec<HostRecord>,
+    /// Per-group options, keyed by group name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub groups: HashMap<String, HostGroup>,
 }
 
 /// Supported inventory formats.
@@ -101,6 +127,8 @@ struct StaticInventoryConfig {
     generated_at: Option<String>,
     #[serde(default)]
     hosts: Vec<HostSpec>,
+    #[serde(default)]
+    groups: HashMap<String, HostGroup>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -115,6 +143,8 @@ struct HostRecordConfig {
     #[serde(alias = "host")]
     hostname: String,
     #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
     tags: HashMap<String, String>,
     #[serde(default)]
     access_method: Option<AccessMethod>,
@@ -128,9 +158,13 @@ struct HostRecordConfig {
 
 impl From<HostRecordConfig> for HostRecord {
     fn from(value: HostRecordConfig) -> Self {
+        let mut tags = value.tags;
+        if let Some(group) = value.group {
+            tags.insert("group".to_string(), group);
+        }
         Self {
             hostname: value.hostname,
-            tags: value.tags,
+            tags,
             access_method: value.access_method,
             credentials_ref: value.credentials_ref,
             last_seen: value.last_seen,
@@ -215,6 +249,7 @@ pub fn parse_inventory_str(
             .generated_at
             .unwrap_or_else(|| Utc::now().to_rfc3339()),
         hosts,
+        groups: config.groups,
     })
 }
 
@@ -250,6 +285,28 @@ hosts = [
         );
     }
 
+    #[test]
+    fn parse_host_groups() {
+        let input = r#"
+hosts = [
+  { host = "ci-1", group = "ci" },
+  { host = "db-1", tags = { role = "db" } },
+  "misc-1"
+]
+
+[groups.ci]
+host_profile = "ci_runner"
+"#;
+        let inventory = parse_inventory_str(input, InventoryFormat::Toml).unwrap();
+        let groups: Vec<Option<&str>> = inventory.hosts.iter().map(HostRecord::group).collect();
+        assert_eq!(groups, vec![Some("ci"), Some("db"), None]);
+        assert_eq!(
+            inventory.groups["ci"].host_profile.as_deref(),
+            Some("ci_runner")
+        );
+        assert!(!inventory.groups.contains_key("db"));
+    }
+
     #[test]
     fn parse_yaml_simple_hosts() {
         let input = r#"
@@ -1,6 +1,8 @@
 //! Fleet-mode support modules.
 
+pub mod agentless;
 pub mod discovery;
 pub mod inventory;
+pub mod scan_artifacts;
 pub mod ssh_scan;
 pub mod transfer;
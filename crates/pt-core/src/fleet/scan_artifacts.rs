@@ -0,0 +1,245 @@
+//! Per-host scan artifacts cached inside a fleet session.
+//!
+//! `agent fleet plan` writes every host's scan result under
+//! `<session_dir>/scans/` so a later `agent fleet plan --from-session` can
+//! re-run inference and the fleet FDR with other priors or thresholds
+//! without reconnecting to the hosts.
+//!
+//! Layout:
+//! - `scans/index.json` - host order, groups, and the scan mode
+//! - `scans/NNNN-<host>.json` - one [`HostScanResult`] per host
+
+use super::inventory::HostGroup;
+use super::ssh_scan::HostScanResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory inside a fleet session holding the cached scans.
+pub const SCANS_DIR: &str = "scans";
+
+/// Current scan artifact index schema version.
+pub const SCAN_ARTIFACTS_SCHEMA_VERSION: &str = "1.0.0";
+
+const INDEX_FILE: &str = "index.json";
+
+#[derive(Debug, Error)]
+pub enum ScanArtifactError {
+    #[error("fleet session has no cached scans ({path})")]
+    Missing { path: PathBuf },
+    #[error(
+        "unsupported scan artifact schema version: {0} (expected {SCAN_ARTIFACTS_SCHEMA_VERSION})"
+    )]
+    UnsupportedSchemaVersion(String),
+    #[error("failed to access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid scan artifact {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// One host's entry in the scan index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanArtifactEntry {
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// File name relative to the scans directory.
+    pub file: String,
+}
+
+/// Index of the cached scans of a fleet session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanArtifactIndex {
+    pub schema_version: String,
+    pub created_at: String,
+    /// Scans hold raw ps output parsed locally (no remote inference).
+    #[serde(default)]
+    pub agentless: bool,
+    pub hosts: Vec<ScanArtifactEntry>,
+    /// Group definitions from the inventory the scans were planned with.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub groups: HashMap<String, HostGroup>,
+}
+
+/// Cached scans loaded back from a fleet session.
+#[derive(Debug, Clone)]
+pub struct CachedScans {
+    pub index: ScanArtifactIndex,
+    /// Scan results in index order.
+    pub results: Vec<HostScanResult>,
+}
+
+impl CachedScans {
+    /// Host -> group assignments recorded at scan time.
+    pub fn host_groups(&self) -> HashMap<String, String> {
+        self.index
+            .hosts
+            .iter()
+            .filter_map(|e| Some((e.host.clone(), e.group.clone()?)))
+            .collect()
+    }
+}
+
+/// Write `results` under `<session_dir>/scans/`.
+pub fn write_host_scans(
+    session_dir: &Path,
+    results: &[HostScanResult],
+    host_groups: &HashMap<String, String>,
+    groups: &HashMap<String, HostGroup>,
+    agentless: bool,
+) -> Result<PathBuf, ScanArtifactError> {
+    let dir = session_dir.join(SCANS_DIR);
+    fs::create_dir_all(&dir).map_err(|source| ScanArtifactError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+
+    let mut hosts = Vec::with_capacity(results.len());
+    for (i, result) in results.iter().enumerate() {
+        let file = format!("{:04}-{}.json", i, sanitize_host(&result.host));
+        write_json(&dir.join(&file), result)?;
+        hosts.push(ScanArtifactEntry {
+            host: result.host.clone(),
+            group: host_groups.get(&result.host).cloned(),
+            file,
+        });
+    }
+
+    let index = ScanArtifactIndex {
+        schema_version: SCAN_ARTIFACTS_SCHEMA_VERSION.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        agentless,
+        hosts,
+        groups: groups.clone(),
+    };
+    write_json(&dir.join(INDEX_FILE), &index)?;
+    Ok(dir)
+}
+
+/// Load the scans cached by [`write_host_scans`].
+pub fn read_host_scans(session_dir: &Path) -> Result<CachedScans, ScanArtifactError> {
+    let dir = session_dir.join(SCANS_DIR);
+    let index_path = dir.join(INDEX_FILE);
+    if !index_path.exists() {
+        return Err(ScanArtifactError::Missing { path: dir });
+    }
+    let index: ScanArtifactIndex = read_json(&index_path)?;
+    if index.schema_version != SCAN_ARTIFACTS_SCHEMA_VERSION {
+        return Err(ScanArtifactError::UnsupportedSchemaVersion(
+            index.schema_version,
+        ));
+    }
+    let results = index
+        .hosts
+        .iter()
+        .map(|entry| read_json(&dir.join(&entry.file)))
+        .collect::<Result<Vec<HostScanResult>, _>>()?;
+    Ok(CachedScans { index, results })
+}
+
+/// File-name-safe form of a host spec (`user@host:22` -> `user_host_22`).
+fn sanitize_host(host: &str) -> String {
+    host.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), ScanArtifactError> {
+    let json = serde_json::to_string_pretty(value).map_err(|source| ScanArtifactError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    fs::write(path, json).map_err(|source| ScanArtifactError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, ScanArtifactError> {
+    let content = fs::read_to_string(path).map_err(|source| ScanArtifactError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&content).map_err(|source| ScanArtifactError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(host: &str) -> HostScanResult {
+        HostScanResult {
+            host: host.to_string(),
+            success: false,
+            scan: None,
+            error: Some("connection refused".to_string()),
+            duration_ms: 12,
+        }
+    }
+
+    #[test]
+    fn scans_round_trip_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = vec![failed("web-1"), failed("deploy@db-1:2222")];
+        let host_groups = HashMap::from([("web-1".to_string(), "web".to_string())]);
+        let groups = HashMap::from([(
+            "web".to_string(),
+            HostGroup {
+                host_profile: Some("server".to_string()),
+            },
+        )]);
+
+        write_host_scans(dir.path(), &results, &host_groups, &groups, true).unwrap();
+        assert!(dir.path().join("scans/0001-deploy_db-1_2222.json").exists());
+
+        let cached = read_host_scans(dir.path()).unwrap();
+        assert!(cached.index.agentless);
+        let hosts: Vec<&str> = cached.results.iter().map(|r| r.host.as_str()).collect();
+        assert_eq!(hosts, vec!["web-1", "deploy@db-1:2222"]);
+        assert_eq!(
+            cached.results[0].error.as_deref(),
+            Some("connection refused")
+        );
+        assert_eq!(cached.host_groups(), host_groups);
+        assert_eq!(cached.index.groups, groups);
+    }
+
+    #[test]
+    fn missing_scans_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = read_host_scans(dir.path()).unwrap_err();
+        assert!(matches!(err, ScanArtifactError::Missing { .. }));
+    }
+
+    #[test]
+    fn unknown_schema_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        write_host_scans(dir.path(), &[], &HashMap::new(), &HashMap::new(), false).unwrap();
+        let index_path = dir.path().join(SCANS_DIR).join(INDEX_FILE);
+        let content = fs::read_to_string(&index_path)
+            .unwrap()
+            .replace(SCAN_ARTIFACTS_SCHEMA_VERSION, "9.0.0");
+        fs::write(&index_path, content).unwrap();
+        let err = read_host_scans(dir.path()).unwrap_err();
+        assert!(matches!(err, ScanArtifactError::UnsupportedSchemaVersion(v) if v == "9.0.0"));
+    }
+}
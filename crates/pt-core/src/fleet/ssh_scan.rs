@@ -1,10 +1,19 @@
 //! SSH-based remote scanning for fleet mode.
 //!
 //! Executes `pt-core scan --format json` on remote hosts via the `ssh` command
-//! and parses the JSON output into `ScanResult` structures.
+//! and parses the JSON output into `ScanResult` structures. In agentless mode
+//! ([`SshScanConfig::agentless`]) it runs the shell payload from
+//! [`super::agentless`] instead and parses its `ps` output locally.
+//!
+//! With the `async` feature, [`ssh_scan_fleet_async`] runs the sessions on the
+//! tokio runtime layer: a sliding window of `parallel` hosts (rather than
+//! fixed batches), a hard per-host `command_timeout`, and cancellation from
+//! the command's global timeout token.
 
 use crate::collect::{ProcessRecord, ScanResult};
+use crate::supervision::signature::{ProcessMatchContext, SignatureDatabase};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
@@ -26,12 +35,16 @@ pub struct SshScanConfig {
     pub command_timeout: u64,
     /// Remote binary name/path (default: "pt-core").
     pub remote_binary: String,
+    /// Local ssh client binary (default: "ssh").
+    pub ssh_binary: String,
     /// Extra SSH options passed via -o.
     pub ssh_options: Vec<String>,
     /// Maximum concurrent SSH connections.
     pub parallel: usize,
     /// Continue scanning remaining hosts if one fails.
     pub continue_on_error: bool,
+    /// Collect with a shell payload instead of the remote pt-core binary.
+    pub agentless: bool,
 }
 
 impl Default for SshScanConfig {
@@ -43,12 +56,14 @@ impl Default for SshScanConfig {
             connect_timeout: 10,
             command_timeout: 30,
             remote_binary: "pt-core".to_string(),
+            ssh_binary: "ssh".to_string(),
             ssh_options: vec![
                 "StrictHostKeyChecking=accept-new".to_string(),
                 "BatchMode=yes".to_string(),
             ],
             parallel: 10,
             continue_on_error: true,
+            agentless: false,
         }
     }
 }
@@ -137,67 +152,70 @@ fn build_ssh_args(host: &str, config: &SshScanConfig) -> Vec<String> {
     args.push(target);
 
     // Remote command
-    args.push(format!("{} scan --format json", config.remote_binary));
+    if config.agentless {
+        args.push(super::agentless::remote_command());
+    } else {
+        args.push(format!("{} scan --format json", config.remote_binary));
+    }
 
     args
 }
 
-/// Scan a single host via SSH and parse the result.
-pub fn ssh_scan_host(host: &str, config: &SshScanConfig) -> HostScanResult {
-    let start = std::time::Instant::now();
-
-    let args = build_ssh_args(host, config);
-    let timeout = Duration::from_secs(config.command_timeout);
+fn failed_result(host: &str, error: String, duration_ms: u64) -> HostScanResult {
+    HostScanResult {
+        host: host.to_string(),
+        success: false,
+        scan: None,
+        error: Some(error),
+        duration_ms,
+    }
+}
 
-    let child = match Command::new("ssh").args(&args).output() {
-        Ok(output) => output,
-        Err(e) => {
-            if e.kind() == io::ErrorKind::NotFound {
-                return HostScanResult {
-                    host: host.to_string(),
-                    success: false,
-                    scan: None,
-                    error: Some(format!("ssh binary not found: {}", e)),
-                    duration_ms: start.elapsed().as_millis() as u64,
-                };
-            }
-            return HostScanResult {
-                host: host.to_string(),
-                success: false,
-                scan: None,
-                error: Some(format!("ssh failed: {}", e)),
-                duration_ms: start.elapsed().as_millis() as u64,
-            };
-        }
+fn spawn_error_result(host: &str, e: &io::Error, duration_ms: u64) -> HostScanResult {
+    let error = if e.kind() == io::ErrorKind::NotFound {
+        format!("ssh binary not found: {}", e)
+    } else {
+        format!("ssh failed: {}", e)
     };
+    failed_result(host, error, duration_ms)
+}
 
-    let duration_ms = start.elapsed().as_millis() as u64;
-
-    // Check for timeout (approximate — Command::output blocks)
-    if duration_ms > timeout.as_millis() as u64 {
-        return HostScanResult {
-            host: host.to_string(),
-            success: false,
-            scan: None,
-            error: Some(format!("timed out after {}s", config.command_timeout)),
+/// Interpret a finished ssh invocation.
+fn result_from_output(
+    host: &str,
+    output: &std::process::Output,
+    duration_ms: u64,
+    agentless: bool,
+) -> HostScanResult {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let code = output.status.code().unwrap_or(-1);
+        return failed_result(
+            host,
+            format!("exit code {}: {}", code, stderr.trim()),
             duration_ms,
-        };
+        );
     }
 
-    if !child.status.success() {
-        let stderr = String::from_utf8_lossy(&child.stderr);
-        let code = child.status.code().unwrap_or(-1);
-        return HostScanResult {
-            host: host.to_string(),
-            success: false,
-            scan: None,
-            error: Some(format!("exit code {}: {}", code, stderr.trim())),
-            duration_ms,
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if agentless {
+        return match super::agentless::parse_payload_output(&stdout) {
+            Ok(scan) => HostScanResult {
+                host: host.to_string(),
+                success: true,
+                scan: Some(scan),
+                error: None,
+                duration_ms,
+            },
+            Err(e) => failed_result(
+                host,
+                format!("failed to parse payload output: {}", e),
+                duration_ms,
+            ),
         };
     }
 
-    let stdout = String::from_utf8_lossy(&child.stdout);
-
     // Parse the JSON output
     match serde_json::from_str::<RemoteScanOutput>(&stdout) {
         Ok(output) => HostScanResult {
@@ -217,18 +235,80 @@ pub fn ssh_scan_host(host: &str, config: &SshScanConfig) -> HostScanResult {
                     error: None,
                     duration_ms,
                 },
-                Err(_) => HostScanResult {
-                    host: host.to_string(),
-                    success: false,
-                    scan: None,
-                    error: Some(format!("failed to parse scan output: {}", e)),
+                Err(_) => failed_result(
+                    host,
+                    format!("failed to parse scan output: {}", e),
                     duration_ms,
-                },
+                ),
             }
         }
     }
 }
 
+/// Scan a single host via SSH and parse the result.
+pub fn ssh_scan_host(host: &str, config: &SshScanConfig) -> HostScanResult {
+    let start = std::time::Instant::now();
+
+    let args = build_ssh_args(host, config);
+    let timeout = Duration::from_secs(config.command_timeout);
+
+    let output = match Command::new(&config.ssh_binary).args(&args).output() {
+        Ok(output) => output,
+        Err(e) => return spawn_error_result(host, &e, start.elapsed().as_millis() as u64),
+    };
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    // Check for timeout (approximate — Command::output blocks)
+    if duration_ms > timeout.as_millis() as u64 {
+        return failed_result(
+            host,
+            format!("timed out after {}s", config.command_timeout),
+            duration_ms,
+        );
+    }
+
+    result_from_output(host, &output, duration_ms, config.agentless)
+}
+
+/// Scan a single host via SSH on the async runtime.
+///
+/// The ssh child is killed when `command_timeout` elapses or `token` is
+/// cancelled.
+#[cfg(feature = "async")]
+pub async fn ssh_scan_host_async(
+    host: &str,
+    config: &SshScanConfig,
+    token: &crate::runtime::CancellationToken,
+) -> HostScanResult {
+    let start = std::time::Instant::now();
+
+    let mut command = tokio::process::Command::new(&config.ssh_binary);
+    command
+        .args(build_ssh_args(host, config))
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let timeout = Duration::from_secs(config.command_timeout);
+
+    let output = tokio::select! {
+        _ = token.cancelled() => {
+            return failed_result(host, "cancelled".to_string(), start.elapsed().as_millis() as u64);
+        }
+        output = tokio::time::timeout(timeout, command.output()) => output,
+    };
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match output {
+        Err(_) => failed_result(
+            host,
+            format!("timed out after {}s", config.command_timeout),
+            duration_ms,
+        ),
+        Ok(Err(e)) => spawn_error_result(host, &e, duration_ms),
+        Ok(Ok(output)) => result_from_output(host, &output, duration_ms, config.agentless),
+    }
+}
+
 /// Scan multiple hosts in parallel via SSH.
 ///
 /// Uses a thread pool with configurable concurrency. Results are collected
@@ -299,20 +379,104 @@ pub fn ssh_scan_fleet(hosts: &[String], config: &SshScanConfig) -> FleetScanResu
     }
 }
 
+/// Scan multiple hosts via SSH on the async runtime.
+///
+/// Keeps up to `parallel` sessions in flight, starting the next host as soon
+/// as any finishes. Every host gets a result in input order: hosts cut off by
+/// `token` (the global timeout) or skipped after a failure without
+/// `continue_on_error` are reported as failed.
+#[cfg(feature = "async")]
+pub async fn ssh_scan_fleet_async(
+    hosts: &[String],
+    config: &SshScanConfig,
+    token: &crate::runtime::CancellationToken,
+) -> FleetScanResult {
+    let start = std::time::Instant::now();
+    // Child token: a host failure can stop the fleet without cancelling the caller.
+    let fleet_token = token.child_token();
+    let shared = Arc::new(config.clone());
+
+    let outcomes = crate::runtime::bounded_map(
+        hosts.to_vec(),
+        config.parallel,
+        &fleet_token,
+        |host: String| {
+            let config = Arc::clone(&shared);
+            let fleet_token = fleet_token.clone();
+            async move {
+                let result = ssh_scan_host_async(&host, &config, &fleet_token).await;
+                if !result.success && !config.continue_on_error {
+                    fleet_token.cancel();
+                }
+                result
+            }
+        },
+    )
+    .await;
+
+    let results: Vec<HostScanResult> = outcomes
+        .into_iter()
+        .zip(hosts)
+        .map(|(outcome, host)| {
+            outcome.unwrap_or_else(|| {
+                let reason = if token.is_cancelled() {
+                    "cancelled: command timeout reached"
+                } else {
+                    "skipped: aborted after an earlier host failed"
+                };
+                failed_result(host, reason.to_string(), 0)
+            })
+        })
+        .collect();
+
+    let successful = results.iter().filter(|r| r.success).count();
+
+    FleetScanResult {
+        total_hosts: hosts.len(),
+        successful,
+        failed: results.len() - successful,
+        results,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+/// Kubernetes namespace and workload of a remote process, as annotated by the
+/// remote scan.
+fn candidate_workload(proc: &ProcessRecord) -> Option<crate::session::fleet::WorkloadRef> {
+    let k8s = proc.container_info.as_ref()?.kubernetes.as_ref()?;
+    Some(crate::session::fleet::WorkloadRef {
+        namespace: k8s.namespace.clone()?,
+        workload: k8s.workload.clone(),
+    })
+}
+
 /// Convert a HostScanResult into a HostInput for fleet session aggregation.
+///
+/// Processes are classified with state-based heuristics; agentless scans
+/// run real inference through [`super::agentless::LocalInference`].
 pub fn scan_result_to_host_input(result: &HostScanResult) -> crate::session::fleet::HostInput {
+    scan_result_to_host_input_with(result, classify_process)
+}
+
+/// Convert a HostScanResult into a HostInput, classifying each process with
+/// `classify` (returning classification, recommended action and score).
+/// Processes scoring above 0.3 become candidates.
+pub fn scan_result_to_host_input_with<F>(
+    result: &HostScanResult,
+    classify: F,
+) -> crate::session::fleet::HostInput
+where
+    F: Fn(&ProcessRecord) -> (String, String, f64),
+{
     use crate::session::fleet::{CandidateInfo, HostInput};
 
     match &result.scan {
         Some(scan) => {
-            // Build candidate info from processes.
-            // In a real fleet scan, this would go through inference to get
-            // classifications and scores. For now, we use state-based heuristics.
             let candidates: Vec<CandidateInfo> = scan
                 .processes
                 .iter()
                 .filter_map(|p| {
-                    let (classification, action, score) = classify_process(p);
+                    let (classification, action, score) = classify(p);
                     if score > 0.3 {
                         Some(CandidateInfo {
                             pid: p.pid.0,
@@ -321,6 +485,7 @@ pub fn scan_result_to_host_input(result: &HostScanResult) -> crate::session::fle
                             recommended_action: action,
                             score,
                             e_value: None,
+                            workload: candidate_workload(p),
                         })
                     } else {
                         None
@@ -330,22 +495,47 @@ pub fn scan_result_to_host_input(result: &HostScanResult) -> crate::session::fle
 
             HostInput {
                 host_id: result.host.clone(),
+                group: None,
                 session_id: format!("ssh-{}", result.host),
                 scanned_at: scan.metadata.started_at.clone(),
                 total_processes: scan.metadata.process_count as u32,
                 candidates,
+                signature_hits: HashMap::new(),
             }
         }
         None => HostInput {
             host_id: result.host.clone(),
+            group: None,
             session_id: format!("ssh-{}-failed", result.host),
             scanned_at: chrono::Utc::now().to_rfc3339(),
             total_processes: 0,
             candidates: Vec::new(),
+            signature_hits: HashMap::new(),
         },
     }
 }
 
+/// Count processes per best-matching supervisor signature.
+///
+/// Every process is matched, not just candidates: the counts say how often a
+/// signature would fire on the host.
+pub fn count_signature_hits(
+    db: &SignatureDatabase,
+    processes: &[ProcessRecord],
+) -> HashMap<String, u32> {
+    let mut hits: HashMap<String, u32> = HashMap::new();
+    for process in processes {
+        let mut ctx = ProcessMatchContext::with_comm(&process.comm);
+        if !process.cmd.is_empty() {
+            ctx = ctx.cmdline(&process.cmd);
+        }
+        if let Some(m) = db.best_match(&ctx) {
+            *hits.entry(m.signature.name.clone()).or_default() += 1;
+        }
+    }
+    hits
+}
+
 /// Simple state-based process classification for fleet scanning.
 ///
 /// Returns (classification, recommended_action, score).
@@ -452,6 +642,18 @@ mod tests {
             .any(|a| a.contains("/opt/pt/bin/pt-core scan --format json")));
     }
 
+    #[test]
+    fn build_ssh_args_agentless_sends_payload() {
+        let config = SshScanConfig {
+            agentless: true,
+            ..SshScanConfig::default()
+        };
+        let args = build_ssh_args("myhost", &config);
+        let remote = args.last().unwrap();
+        assert!(remote.starts_with("sh -c '"));
+        assert!(!remote.contains("pt-core"));
+    }
+
     #[test]
     fn classify_zombie_process() {
         let p = MockProcessBuilder::new()
@@ -529,6 +731,60 @@ mod tests {
         assert_eq!(input.candidates.len(), 1);
         assert_eq!(input.candidates[0].signature, "zombie_test");
         assert_eq!(input.candidates[0].classification, "zombie");
+        assert_eq!(input.candidates[0].workload, None);
+    }
+
+    #[test]
+    fn scan_result_to_host_input_carries_pod_workload() {
+        let mut zombie = MockProcessBuilder::new()
+            .pid(100)
+            .comm("zombie_test")
+            .state_zombie()
+            .elapsed_hours(1)
+            .build();
+        zombie.container_info = Some(crate::collect::ContainerInfo {
+            in_container: true,
+            kubernetes: Some(crate::collect::KubernetesInfo {
+                namespace: Some("shop".to_string()),
+                workload: Some("web".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let result = HostScanResult {
+            host: "node1".to_string(),
+            success: true,
+            scan: Some(MockScanBuilder::new().with_process(zombie).build()),
+            error: None,
+            duration_ms: 500,
+        };
+
+        let input = scan_result_to_host_input(&result);
+        let workload = input.candidates[0].workload.as_ref().unwrap();
+        assert_eq!(workload.namespace, "shop");
+        assert_eq!(workload.workload.as_deref(), Some("web"));
+    }
+
+    #[test]
+    fn count_signature_hits_matches_all_processes() {
+        let mut db = SignatureDatabase::new();
+        db.add(
+            crate::supervision::SupervisorSignature::new(
+                "test-worker",
+                crate::supervision::SupervisorCategory::Other,
+            )
+            .with_process_patterns(vec![r"^worker$"]),
+        )
+        .unwrap();
+        let processes = vec![
+            MockProcessBuilder::new().pid(1).comm("worker").build(),
+            MockProcessBuilder::new().pid(2).comm("worker").build(),
+            MockProcessBuilder::new().pid(3).comm("nginx").build(),
+        ];
+
+        let hits = count_signature_hits(&db, &processes);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits["test-worker"], 2);
     }
 
     #[test]
@@ -291,6 +291,21 @@ pub fn merge_beta_params(
     ))
 }
 
+/// Merge optional Beta parameters; a side that is missing defers to the other.
+fn merge_optional_beta(
+    local: &Option<BetaParams>,
+    incoming: &Option<BetaParams>,
+    wl: f64,
+    wi: f64,
+) -> Result<Option<BetaParams>, TransferError> {
+    Ok(match (local, incoming) {
+        (Some(l), Some(i)) => Some(merge_beta_params(l, i, wl, wi)?),
+        (Some(l), None) => Some(l.clone()),
+        (None, Some(i)) => Some(i.clone()),
+        (None, None) => None,
+    })
+}
+
 /// Merge class parameters with weighted averaging of all Beta-distributed fields.
 fn merge_class_params(
     local: &ClassParams,
@@ -318,14 +333,22 @@ fn merge_class_params(
                 orphan_beta: merge_beta_params(&local.orphan_beta, &incoming.orphan_beta, wl, wi)?,
                 tty_beta: merge_beta_params(&local.tty_beta, &incoming.tty_beta, wl, wi)?,
                 net_beta: merge_beta_params(&local.net_beta, &incoming.net_beta, wl, wi)?,
-                io_active_beta: match (&local.io_active_beta, &incoming.io_active_beta) {
-                    (Some(l), Some(i)) => Some(merge_beta_params(l, i, wl, wi)?),
-                    (Some(l), None) => Some(l.clone()),
-                    (None, Some(i)) => Some(i.clone()),
-                    (None, None) => None,
-                },
+                io_active_beta: merge_optional_beta(
+                    &local.io_active_beta,
+                    &incoming.io_active_beta,
+                    wl,
+                    wi,
+                )?,
+                gpu_beta: merge_optional_beta(&local.gpu_beta, &incoming.gpu_beta, wl, wi)?,
+                gpu_util_beta: merge_optional_beta(
+                    &local.gpu_util_beta,
+                    &incoming.gpu_util_beta,
+                    wl,
+                    wi,
+                )?,
                 hazard_gamma: local.hazard_gamma.clone(),
                 competing_hazards: local.competing_hazards.clone(),
+                runtime_surprise_gamma: local.runtime_surprise_gamma.clone(),
             })
         }
     }
@@ -439,8 +462,15 @@ pub fn normalize_baseline(
         scale_beta(&mut class.orphan_beta, scale);
         scale_beta(&mut class.tty_beta, scale);
         scale_beta(&mut class.net_beta, scale);
-        if let Some(ref mut io) = class.io_active_beta {
-            scale_beta(io, scale);
+        for beta in [
+            &mut class.io_active_beta,
+            &mut class.gpu_beta,
+            &mut class.gpu_util_beta,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            scale_beta(beta, scale);
         }
     }
 }
@@ -983,6 +1013,8 @@ mod tests {
             priors: Default::default(),
             expectations: Default::default(),
             priority: 100,
+            rollout: Default::default(),
+            graceful_stop: None,
         };
         let incoming_sigs = PersistedSchema {
             schema_version: 2,
@@ -0,0 +1,273 @@
+//! Golden-output snapshot testing.
+//!
+//! Renders command envelopes for canned inputs in every [`OutputFormat`] and
+//! compares them against committed snapshots under `tests/golden/`, in the
+//! spirit of `insta`:
+//!
+//! - A matching snapshot passes.
+//! - A mismatch writes `<name>.snap.new` next to the snapshot and fails with
+//!   the first differing line. Re-run with `PT_UPDATE_GOLDEN=1` to accept.
+//! - A missing snapshot is recorded and passes, except under `CI` where it
+//!   fails so unreviewed output never lands silently.
+//!
+//! Volatile values (timestamps, session IDs, UUIDs, durations, host IDs and
+//! the crate version) are normalized before comparison; see
+//! [`normalize_output`]. The stability policy these snapshots enforce is
+//! documented in `docs/AGENT_CLI_CONTRACT.md` ("Stability Enforcement").
+
+use clap::ValueEnum;
+use pt_common::OutputFormat;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that accepts changed or new snapshots.
+pub const UPDATE_ENV: &str = "PT_UPDATE_GOLDEN";
+
+/// A canned command invocation rendered in every output format.
+#[derive(Debug, Clone, Copy)]
+pub struct GoldenCase {
+    /// Snapshot name prefix; the format is appended (`<name>.<format>.snap`).
+    pub name: &'static str,
+    /// Arguments after `--format <fmt>`, relative to the pt-core crate root.
+    pub args: &'static [&'static str],
+}
+
+/// Commands whose output depends only on canned inputs, not on the host.
+pub fn canned_cases() -> &'static [GoldenCase] {
+    &[
+        GoldenCase {
+            name: "schema_list",
+            args: &["schema", "--list"],
+        },
+        GoldenCase {
+            name: "schema_process_identity",
+            args: &["schema", "ProcessIdentity"],
+        },
+        GoldenCase {
+            name: "config_list_presets",
+            args: &["config", "list-presets"],
+        },
+        GoldenCase {
+            name: "config_show_preset_developer",
+            args: &["config", "show-preset", "developer"],
+        },
+        GoldenCase {
+            name: "config_validate_policy",
+            args: &["config", "validate", "tests/fixtures/policy.json"],
+        },
+        GoldenCase {
+            name: "config_validate_invalid_priors",
+            args: &["config", "validate", "tests/fixtures/invalid_priors.json"],
+        },
+    ]
+}
+
+/// Every output format, in declaration order.
+pub fn all_formats() -> &'static [OutputFormat] {
+    OutputFormat::value_variants()
+}
+
+/// Directory holding committed snapshots.
+pub fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+/// Snapshot name for a case rendered in `format`.
+pub fn snapshot_name(case: &GoldenCase, format: OutputFormat) -> String {
+    format!("{}.{}", case.name, format)
+}
+
+fn normalizers() -> &'static [(Regex, &'static str)] {
+    static NORMALIZERS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    NORMALIZERS.get_or_init(|| {
+        [
+            (
+                r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:?\d{2})?",
+                "[TIMESTAMP]",
+            ),
+            (r"pt-\d{8}-\d{6}-[0-9a-z]{4}", "[SESSION_ID]"),
+            (
+                r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}",
+                "[UUID]",
+            ),
+            (r#"("[a-z_]*_(ms|us|secs)"\s*:\s*)\d+(\.\d+)?"#, "${1}0"),
+            (r"\b([a-z_]*_(ms|us|secs)=)\d+(\.\d+)?", "${1}0"),
+            (r#"("host_id"\s*:\s*)"[^"]*""#, "${1}\"[HOST_ID]\""),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| {
+            (
+                Regex::new(pattern).expect("golden normalizer regex"),
+                replacement,
+            )
+        })
+        .collect()
+    })
+}
+
+/// Replace volatile values with stable placeholders.
+pub fn normalize_output(output: &str) -> String {
+    let mut text = output.replace(env!("CARGO_PKG_VERSION"), "[VERSION]");
+    for (regex, replacement) in normalizers() {
+        text = regex.replace_all(&text, *replacement).into_owned();
+    }
+    // Trailing whitespace is not part of the contract.
+    let mut normalized: String = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.push('\n');
+    normalized
+}
+
+/// Result of comparing output with its snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoldenOutcome {
+    /// Output matched the committed snapshot.
+    Matched,
+    /// No snapshot existed; one was recorded.
+    Recorded,
+    /// Snapshot differed and was overwritten (update mode).
+    Updated,
+}
+
+/// Compare `actual` (after normalization) with snapshot `name` in `dir`.
+///
+/// Returns a human-readable failure on mismatch, or on a missing snapshot
+/// under CI.
+pub fn check_golden_in(dir: &Path, name: &str, actual: &str) -> Result<GoldenOutcome, String> {
+    let update = std::env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+    let in_ci = std::env::var_os("CI").is_some();
+    let actual = normalize_output(actual);
+    let snap_path = dir.join(format!("{}.snap", name));
+    let new_path = dir.join(format!("{}.snap.new", name));
+
+    let write = |path: &Path| -> Result<(), String> {
+        std::fs::create_dir_all(dir).map_err(|e| format!("create {}: {}", dir.display(), e))?;
+        std::fs::write(path, &actual).map_err(|e| format!("write {}: {}", path.display(), e))
+    };
+
+    let expected = match std::fs::read_to_string(&snap_path) {
+        Ok(expected) => expected,
+        Err(_) if in_ci && !update => {
+            write(&new_path)?;
+            return Err(format!(
+                "missing golden snapshot {} (candidate written to {})",
+                snap_path.display(),
+                new_path.display()
+            ));
+        }
+        Err(_) => {
+            write(&snap_path)?;
+            return Ok(GoldenOutcome::Recorded);
+        }
+    };
+
+    if expected == actual {
+        let _ = std::fs::remove_file(&new_path);
+        return Ok(GoldenOutcome::Matched);
+    }
+    if update {
+        write(&snap_path)?;
+        let _ = std::fs::remove_file(&new_path);
+        return Ok(GoldenOutcome::Updated);
+    }
+
+    write(&new_path)?;
+    Err(format!(
+        "golden snapshot {} changed\n{}\nreview {} and re-run with {}=1 to accept",
+        name,
+        first_difference(&expected, &actual),
+        new_path.display(),
+        UPDATE_ENV
+    ))
+}
+
+/// Assert that `actual` matches snapshot `name` in [`golden_dir`].
+pub fn assert_golden(name: &str, actual: &str) {
+    if let Err(msg) = check_golden_in(&golden_dir(), name, actual) {
+        panic!("{}", msg);
+    }
+}
+
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (e, a) => {
+                return format!(
+                    "first difference at line {}:\n- {}\n+ {}",
+                    line,
+                    e.unwrap_or("<end of snapshot>"),
+                    a.unwrap_or("<end of output>")
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn normalize_replaces_volatile_values() {
+        let raw = concat!(
+            r#"{"generated_at":"2026-01-15T12:00:00.123Z","session_id":"pt-20260115-120000-ab12","#,
+            r#""duration_ms":42,"host_id":"abc"}   "#
+        );
+        assert_eq!(
+            normalize_output(raw),
+            concat!(
+                r#"{"generated_at":"[TIMESTAMP]","session_id":"[SESSION_ID]","#,
+                r#""duration_ms":0,"host_id":"[HOST_ID]"}"#,
+                "\n"
+            )
+        );
+        assert_eq!(
+            normalize_output(&format!("pt {}", env!("CARGO_PKG_VERSION"))),
+            "pt [VERSION]\n"
+        );
+    }
+
+    #[test]
+    fn every_format_is_covered() {
+        assert_eq!(all_formats().len(), 9);
+        let case = canned_cases()[0];
+        assert_eq!(
+            snapshot_name(&case, OutputFormat::Md),
+            format!("{}.md", case.name)
+        );
+    }
+
+    #[test]
+    fn mismatch_writes_candidate() {
+        // Skip when the environment forces update or CI behaviour.
+        if std::env::var_os(UPDATE_ENV).is_some() || std::env::var_os("CI").is_some() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("case.snap"), "old\n").unwrap();
+
+        let err = check_golden_in(tmp.path(), "case", "new").unwrap_err();
+        assert!(err.contains("- old"));
+        assert!(err.contains("+ new"));
+        assert!(tmp.path().join("case.snap.new").exists());
+
+        assert_eq!(
+            check_golden_in(tmp.path(), "case", "old").unwrap(),
+            GoldenOutcome::Matched
+        );
+        assert!(!tmp.path().join("case.snap.new").exists());
+        assert_eq!(
+            check_golden_in(tmp.path(), "fresh", "x").unwrap(),
+            GoldenOutcome::Recorded
+        );
+    }
+}
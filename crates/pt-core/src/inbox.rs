@@ -5,7 +5,10 @@
 //! - Tracks lock contention events
 //! - Records respawn detection notifications
 //! - Provides acknowledgement mechanism
+//! - Merges repeat escalations about the same processes into one item
+//! - Offers signatures learned from repeated kill/spare decisions
 
+use crate::dedupe::CandidateIdentity;
 use chrono::Utc;
 use pt_common::schema::SCHEMA_VERSION;
 use serde::{Deserialize, Serialize};
@@ -53,6 +56,8 @@ pub enum InboxItemType {
     CalibrationDrift,
     /// Periodic cleanup suggested.
     MaintenanceReminder,
+    /// A signature learned from repeated user decisions awaits acceptance.
+    SignatureSuggestion,
     /// Manual notification.
     Manual,
 }
@@ -65,6 +70,7 @@ impl std::fmt::Display for InboxItemType {
             Self::RespawnDetected => write!(f, "respawn_detected"),
             Self::CalibrationDrift => write!(f, "calibration_drift"),
             Self::MaintenanceReminder => write!(f, "maintenance_reminder"),
+            Self::SignatureSuggestion => write!(f, "signature_suggestion"),
             Self::Manual => write!(f, "manual"),
         }
     }
@@ -105,6 +111,15 @@ pub struct InboxItem {
     /// Deferred session ID (for lock contention).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deferred_session_id: Option<String>,
+    /// Processes the item is about (for escalations).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<CandidateIdentity>,
+    /// Times this item was raised before being acknowledged, when more than once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurrences: Option<u32>,
+    /// When the item was last raised again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_at: Option<String>,
 }
 
 impl InboxItem {
@@ -129,9 +144,18 @@ impl InboxItem {
             review_command: None,
             message: None,
             deferred_session_id: None,
+            targets: Vec::new(),
+            occurrences: None,
+            last_seen_at: None,
         }
     }
 
+    /// Attach the processes this item is about.
+    pub fn with_targets(mut self, targets: Vec<CandidateIdentity>) -> Self {
+        self.targets = targets;
+        self
+    }
+
     /// Create a dormant escalation item.
     pub fn dormant_escalation(
         session_id: String,
@@ -167,6 +191,14 @@ impl InboxItem {
         item
     }
 
+    /// Create a signature suggestion item; `trigger` carries the signature name.
+    pub fn signature_suggestion(name: &str, prompt: String) -> Self {
+        let mut item = Self::new(InboxItemType::SignatureSuggestion, prompt);
+        item.trigger = Some(name.to_string());
+        item.review_command = Some(format!("pt signature accept {}", name));
+        item
+    }
+
     /// Mark this item as acknowledged.
     pub fn acknowledge(&mut self) {
         self.acknowledged = true;
@@ -200,6 +232,30 @@ impl InboxResponse {
     }
 }
 
+/// Result of [`InboxStore::add_or_merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboxMerge {
+    /// Stored as a new item.
+    Added,
+    /// Folded into an unacknowledged item about the same processes.
+    Merged {
+        /// ID of the existing item.
+        id: String,
+        /// Targets the existing item did not cover yet.
+        new_targets: usize,
+    },
+}
+
+impl InboxMerge {
+    /// Whether the item tells the user anything new.
+    pub fn is_news(&self) -> bool {
+        match self {
+            InboxMerge::Added => true,
+            InboxMerge::Merged { new_targets, .. } => *new_targets > 0,
+        }
+    }
+}
+
 /// Store for inbox items.
 #[derive(Debug, Clone)]
 pub struct InboxStore {
@@ -284,6 +340,54 @@ impl InboxStore {
         Ok(())
     }
 
+    /// Add an item, merging it into an unacknowledged item of the same type
+    /// that shares any target.
+    ///
+    /// The merged item keeps its ID, gains the new targets, and takes the
+    /// newer session, summary and review command. Items without targets are
+    /// always added.
+    pub fn add_or_merge(&self, item: &InboxItem) -> Result<InboxMerge, InboxError> {
+        if item.targets.is_empty() {
+            self.add(item)?;
+            return Ok(InboxMerge::Added);
+        }
+
+        let mut items = self.list()?;
+        let Some(existing) = items.iter_mut().find(|existing| {
+            !existing.acknowledged
+                && existing.item_type == item.item_type
+                && existing.targets.iter().any(|t| item.targets.contains(t))
+        }) else {
+            self.add(item)?;
+            return Ok(InboxMerge::Added);
+        };
+
+        let mut new_targets = 0;
+        for target in &item.targets {
+            if !existing.targets.contains(target) {
+                existing.targets.push(target.clone());
+                new_targets += 1;
+            }
+        }
+        existing.occurrences = Some(existing.occurrences.unwrap_or(1) + 1);
+        existing.last_seen_at = Some(item.created_at.clone());
+        existing.summary = item.summary.clone();
+        if item.session_id.is_some() {
+            existing.session_id = item.session_id.clone();
+            existing.review_command = item.review_command.clone();
+        }
+        if item.trigger.is_some() {
+            existing.trigger = item.trigger.clone();
+        }
+        if item.candidates.is_some() {
+            existing.candidates = item.candidates;
+        }
+        let id = existing.id.clone();
+
+        self.write_all(&items)?;
+        Ok(InboxMerge::Merged { id, new_targets })
+    }
+
     /// Acknowledge an item by ID.
     pub fn acknowledge(&self, item_id: &str) -> Result<InboxItem, InboxError> {
         let mut items = self.list()?;
@@ -459,6 +563,72 @@ mod tests {
         assert!(item.review_command.is_some());
     }
 
+    #[test]
+    fn test_signature_suggestion() {
+        let item = InboxItem::signature_suggestion(
+            "learned-node-1a2b3c4d",
+            "You spared `node --watch` 3 of 3 times".to_string(),
+        );
+        assert_eq!(item.item_type, InboxItemType::SignatureSuggestion);
+        assert_eq!(item.trigger.as_deref(), Some("learned-node-1a2b3c4d"));
+        assert_eq!(
+            item.review_command.as_deref(),
+            Some("pt signature accept learned-node-1a2b3c4d")
+        );
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["type"], "signature_suggestion");
+    }
+
+    #[test]
+    fn test_add_or_merge_same_targets() {
+        let (store, _tmp) = test_store();
+        let target = |pid: u32| CandidateIdentity::new(pid, format!("{}:100", pid));
+
+        let first = InboxItem::dormant_escalation(
+            "session-1".to_string(),
+            "sustained_load".to_string(),
+            "2 KILL candidates".to_string(),
+            2,
+        )
+        .with_targets(vec![target(10), target(11)]);
+        assert_eq!(store.add_or_merge(&first).unwrap(), InboxMerge::Added);
+
+        let repeat = InboxItem::dormant_escalation(
+            "session-2".to_string(),
+            "sustained_load".to_string(),
+            "2 KILL candidates".to_string(),
+            2,
+        )
+        .with_targets(vec![target(11), target(10)]);
+        let merge = store.add_or_merge(&repeat).unwrap();
+        assert!(!merge.is_news());
+
+        let grown = InboxItem::dormant_escalation(
+            "session-3".to_string(),
+            "sustained_load".to_string(),
+            "3 KILL candidates".to_string(),
+            3,
+        )
+        .with_targets(vec![target(10), target(12)]);
+        assert_eq!(
+            store.add_or_merge(&grown).unwrap(),
+            InboxMerge::Merged {
+                id: first.id.clone(),
+                new_targets: 1
+            }
+        );
+
+        let items = store.list().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].occurrences, Some(3));
+        assert_eq!(items[0].targets.len(), 3);
+        assert_eq!(items[0].session_id.as_deref(), Some("session-3"));
+
+        // Once acknowledged, the same processes raise a fresh item.
+        store.acknowledge(&first.id).unwrap();
+        assert_eq!(store.add_or_merge(&repeat).unwrap(), InboxMerge::Added);
+    }
+
     #[test]
     fn test_inbox_response() {
         let item1 = InboxItem::new(InboxItemType::Manual, "Test 1".to_string());
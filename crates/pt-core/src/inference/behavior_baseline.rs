@@ -0,0 +1,368 @@
+//! Per-fingerprint behavior baselines ("this process never used to do that").
+//!
+//! A well-behaved daemon has a characteristic footprint: nginx idles at a few
+//! percent CPU with a stable RSS, fd count and socket count. This module keeps
+//! exponentially weighted rolling statistics of those metrics per process
+//! fingerprint (owner + command line, see [`crate::snooze::process_fingerprint`])
+//! and scores a live process by how far it sits from its own history:
+//!
+//! ```text
+//! z_m = |x_m - mean_m| / max(sd_m, floor_m)      deviation = max_m z_m
+//! ```
+//!
+//! RSS is tracked as `ln(bytes)` so the deviation is scale-free. The
+//! per-metric floors stop a perfectly flat history from turning noise into
+//! huge z-scores. The deviation is bucketed by the priors' `behavior_change`
+//! section and fed to the posterior as `behavior_change` evidence, catching
+//! compromised or runaway instances of normally quiet daemons.
+//!
+//! Baselines live next to the shadow observations (they are updated by the
+//! shadow recorder) in a small JSON file.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the baseline table inside the shadow storage directory.
+pub const BEHAVIOR_BASELINES_FILE: &str = "fingerprint_baselines.json";
+
+/// Observations of a metric before its baseline is used.
+pub const MIN_BASELINE_SAMPLES: u64 = 10;
+
+/// Weight of the newest observation once the baseline is warm; older
+/// observations decay geometrically (an effective window of ~20 samples).
+const EWMA_ALPHA: f64 = 0.05;
+
+/// Baselines not refreshed for this many days are dropped on save.
+const STALE_AFTER_DAYS: i64 = 30;
+
+/// Errors from loading or saving the baseline table.
+#[derive(Debug, Error)]
+pub enum BehaviorBaselineError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Metrics tracked per fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorMetric {
+    /// CPU usage percentage.
+    Cpu,
+    /// Resident set size, as `ln(bytes)`.
+    Rss,
+    /// Open file descriptor count.
+    Fds,
+    /// Open socket count.
+    Sockets,
+}
+
+impl BehaviorMetric {
+    /// Stable snake_case name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BehaviorMetric::Cpu => "cpu",
+            BehaviorMetric::Rss => "rss",
+            BehaviorMetric::Fds => "fds",
+            BehaviorMetric::Sockets => "sockets",
+        }
+    }
+
+    /// Minimum standard deviation used when scoring.
+    fn sd_floor(&self) -> f64 {
+        match self {
+            // Percentage points
+            BehaviorMetric::Cpu => 2.0,
+            // ~10% in RSS
+            BehaviorMetric::Rss => 0.1,
+            BehaviorMetric::Fds => 2.0,
+            BehaviorMetric::Sockets => 1.0,
+        }
+    }
+}
+
+/// One observation of a process's footprint.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BehaviorSample {
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    pub fd_count: Option<u32>,
+    pub sockets: Option<u32>,
+}
+
+impl BehaviorSample {
+    fn values(&self) -> impl Iterator<Item = (BehaviorMetric, f64)> {
+        [
+            Some((BehaviorMetric::Cpu, self.cpu_percent)),
+            Some((BehaviorMetric::Rss, (self.rss_bytes.max(1) as f64).ln())),
+            self.fd_count.map(|n| (BehaviorMetric::Fds, n as f64)),
+            self.sockets.map(|n| (BehaviorMetric::Sockets, n as f64)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|(_, v)| v.is_finite())
+    }
+}
+
+/// Exponentially weighted mean and variance of one metric.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricBaseline {
+    pub count: u64,
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl MetricBaseline {
+    /// Fold one observation in.
+    ///
+    /// The first `1 / EWMA_ALPHA` observations are averaged uniformly so the
+    /// baseline warms up quickly; afterwards the weight is fixed.
+    pub fn record(&mut self, value: f64) {
+        self.count += 1;
+        let alpha = (1.0 / self.count as f64).max(EWMA_ALPHA);
+        let diff = value - self.mean;
+        let incr = alpha * diff;
+        self.mean += incr;
+        self.variance = (1.0 - alpha) * (self.variance + diff * incr);
+    }
+
+    /// Floored z-score of `value`, if the baseline is warm.
+    pub fn z_score(&self, value: f64, metric: BehaviorMetric) -> Option<f64> {
+        if self.count < MIN_BASELINE_SAMPLES {
+            return None;
+        }
+        let sd = self.variance.max(0.0).sqrt().max(metric.sd_floor());
+        Some((value - self.mean).abs() / sd)
+    }
+}
+
+/// Rolling baseline for one fingerprint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FingerprintBaseline {
+    pub comm: String,
+    pub last_seen: DateTime<Utc>,
+    pub metrics: BTreeMap<BehaviorMetric, MetricBaseline>,
+}
+
+/// Deviation of a live process from its fingerprint baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorDeviation {
+    /// Largest per-metric z-score.
+    pub max_z: f64,
+    /// Metric with the largest z-score.
+    pub metric: BehaviorMetric,
+    /// Per-metric z-scores for warm metrics.
+    pub z_scores: BTreeMap<BehaviorMetric, f64>,
+    /// Observations behind the baseline of `metric`.
+    pub samples: u64,
+}
+
+/// Persisted per-fingerprint baselines.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BehaviorBaselineStore {
+    #[serde(default)]
+    baselines: BTreeMap<String, FingerprintBaseline>,
+}
+
+impl BehaviorBaselineStore {
+    /// Load a table from disk; a missing file yields an empty table.
+    pub fn load(path: &Path) -> Result<Self, BehaviorBaselineError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drop stale fingerprints and write the table to disk atomically.
+    pub fn save(&mut self, path: &Path, now: DateTime<Utc>) -> Result<(), BehaviorBaselineError> {
+        let cutoff = now - Duration::days(STALE_AFTER_DAYS);
+        self.baselines.retain(|_, b| b.last_seen >= cutoff);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Number of fingerprints tracked.
+    pub fn len(&self) -> usize {
+        self.baselines.len()
+    }
+
+    /// Whether no fingerprint is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.baselines.is_empty()
+    }
+
+    /// Baseline for a fingerprint.
+    pub fn get(&self, fingerprint: &str) -> Option<&FingerprintBaseline> {
+        self.baselines.get(fingerprint)
+    }
+
+    /// Fold one observation into a fingerprint's baseline.
+    pub fn observe(
+        &mut self,
+        fingerprint: &str,
+        comm: &str,
+        sample: &BehaviorSample,
+        now: DateTime<Utc>,
+    ) {
+        let baseline = self
+            .baselines
+            .entry(fingerprint.to_string())
+            .or_insert_with(|| FingerprintBaseline {
+                comm: comm.to_string(),
+                last_seen: now,
+                metrics: BTreeMap::new(),
+            });
+        baseline.last_seen = now;
+        for (metric, value) in sample.values() {
+            baseline.metrics.entry(metric).or_default().record(value);
+        }
+    }
+
+    /// Deviation of `sample` from the fingerprint's baseline, if any metric is warm.
+    pub fn deviation(
+        &self,
+        fingerprint: &str,
+        sample: &BehaviorSample,
+    ) -> Option<BehaviorDeviation> {
+        let baseline = self.baselines.get(fingerprint)?;
+        let z_scores: BTreeMap<BehaviorMetric, f64> = sample
+            .values()
+            .filter_map(|(metric, value)| {
+                let z = baseline.metrics.get(&metric)?.z_score(value, metric)?;
+                Some((metric, z))
+            })
+            .collect();
+        let (&metric, &max_z) = z_scores.iter().max_by(|a, b| a.1.total_cmp(b.1))?;
+        Some(BehaviorDeviation {
+            max_z,
+            metric,
+            samples: baseline.metrics[&metric].count,
+            z_scores,
+        })
+    }
+}
+
+/// Default location of the baseline table for a shadow storage directory.
+pub fn behavior_baselines_path(shadow_dir: &Path) -> PathBuf {
+    shadow_dir.join(BEHAVIOR_BASELINES_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu: f64, rss_mb: u64, fds: u32, sockets: u32) -> BehaviorSample {
+        BehaviorSample {
+            cpu_percent: cpu,
+            rss_bytes: rss_mb * 1024 * 1024,
+            fd_count: Some(fds),
+            sockets: Some(sockets),
+        }
+    }
+
+    fn warm_store() -> BehaviorBaselineStore {
+        let mut store = BehaviorBaselineStore::default();
+        let now = Utc::now();
+        for i in 0..30 {
+            let jitter = (i % 3) as f64 * 0.5;
+            store.observe(
+                "fp",
+                "nginx",
+                &sample(1.0 + jitter, 50, 20 + (i % 2), 4),
+                now,
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn cold_baseline_yields_no_deviation() {
+        let mut store = BehaviorBaselineStore::default();
+        for _ in 0..(MIN_BASELINE_SAMPLES - 1) {
+            store.observe("fp", "nginx", &sample(1.0, 50, 20, 4), Utc::now());
+        }
+        assert!(store.deviation("fp", &sample(90.0, 50, 20, 4)).is_none());
+        assert!(store.deviation("other", &sample(1.0, 50, 20, 4)).is_none());
+    }
+
+    #[test]
+    fn typical_sample_scores_low() {
+        let store = warm_store();
+        let dev = store.deviation("fp", &sample(1.5, 51, 21, 4)).unwrap();
+        assert!(dev.max_z < 1.0, "max_z = {}", dev.max_z);
+    }
+
+    #[test]
+    fn runaway_cpu_and_sockets_score_high() {
+        let store = warm_store();
+        let dev = store.deviation("fp", &sample(95.0, 50, 20, 4)).unwrap();
+        assert_eq!(dev.metric, BehaviorMetric::Cpu);
+        assert!(dev.max_z > 20.0);
+
+        let dev = store.deviation("fp", &sample(1.0, 50, 20, 400)).unwrap();
+        assert_eq!(dev.metric, BehaviorMetric::Sockets);
+        assert!(dev.max_z > 100.0);
+    }
+
+    #[test]
+    fn missing_metrics_are_skipped() {
+        let store = warm_store();
+        let partial = BehaviorSample {
+            cpu_percent: 1.0,
+            rss_bytes: 50 * 1024 * 1024,
+            fd_count: None,
+            sockets: None,
+        };
+        let dev = store.deviation("fp", &partial).unwrap();
+        assert_eq!(dev.z_scores.len(), 2);
+    }
+
+    #[test]
+    fn ewma_tracks_level_shift() {
+        let mut m = MetricBaseline::default();
+        for _ in 0..50 {
+            m.record(10.0);
+        }
+        for _ in 0..200 {
+            m.record(20.0);
+        }
+        assert!((m.mean - 20.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn save_load_round_trip_and_prunes_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = behavior_baselines_path(dir.path());
+        let now = Utc::now();
+        let mut store = warm_store();
+        store.observe(
+            "old",
+            "cron",
+            &sample(0.0, 5, 3, 0),
+            now - Duration::days(STALE_AFTER_DAYS + 1),
+        );
+        assert_eq!(store.len(), 2);
+        store.save(&path, now).unwrap();
+
+        let loaded = BehaviorBaselineStore::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get("fp").unwrap().comm, "nginx");
+        assert!(
+            BehaviorBaselineStore::load(&dir.path().join("missing.json"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
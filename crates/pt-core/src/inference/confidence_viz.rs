@@ -304,6 +304,7 @@ mod tests {
                     delta_bits: 2.74,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "age_elapsed".to_string(),
@@ -312,6 +313,7 @@ mod tests {
                     delta_bits: 2.31,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
             ],
             top_evidence: vec![],
@@ -81,24 +81,60 @@ fn phrase_feature(entry: &BayesFactorEntry) -> String {
         } else {
             "active CPU usage"
         }
+    } else if name.contains("runtime_surprise") {
+        if entry.log_bf > 0.0 {
+            "running far longer than is typical for its category"
+        } else {
+            "runtime typical for its category"
+        }
     } else if name.contains("age") || name.contains("runtime") || name.contains("elapsed") {
         if entry.log_bf > 0.0 {
             "running for an unusually long time"
         } else {
             "relatively short runtime"
         }
+    } else if name.contains("session_attachment") {
+        if entry.log_bf > 0.0 {
+            "terminal session detached or left idle"
+        } else {
+            "terminal session attached with recent input"
+        }
+    } else if name.contains("swap") {
+        if entry.log_bf > 0.0 {
+            "heavily swapped out while memory is scarce"
+        } else {
+            "working set resident despite memory pressure"
+        }
     } else if name.contains("memory") || name.contains("rss") || name.contains("vsz") {
         if entry.log_bf > 0.0 {
             "memory held without active use"
         } else {
             "normal memory usage pattern"
         }
+    } else if name.contains("fd_leak") {
+        if entry.log_bf > 0.0 {
+            "open file descriptors growing steadily (likely fd leak)"
+        } else {
+            "stable file descriptor count"
+        }
+    } else if name.starts_with("io") {
+        if entry.log_bf > 0.0 {
+            "no recent disk I/O"
+        } else {
+            "active disk I/O"
+        }
     } else if name.contains("fd") || name.contains("file") {
         if entry.log_bf > 0.0 {
             "no recent file activity"
         } else {
             "active file I/O"
         }
+    } else if name.contains("net_recent") {
+        if entry.log_bf > 0.0 {
+            "network sockets idle for a long time"
+        } else {
+            "recent network traffic"
+        }
     } else if name.contains("net") || name.contains("socket") || name.contains("port") {
         if entry.log_bf > 0.0 {
             "no network connections"
@@ -334,6 +370,7 @@ mod tests {
             } else {
                 "weak".to_string()
             },
+            weight: None,
         }
     }
 
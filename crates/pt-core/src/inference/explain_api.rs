@@ -257,6 +257,7 @@ mod tests {
                 "supports useful".to_string()
             },
             strength: "strong".to_string(),
+            weight: None,
         }
     }
 
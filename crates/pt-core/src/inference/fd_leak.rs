@@ -0,0 +1,366 @@
+//! File-descriptor leak detection from fd-count trends.
+//!
+//! A process that leaks descriptors shows an open-fd count that only ever
+//! goes up. The shadow recorder appends each observed count to a per-identity
+//! trend (identity = PID + start time, see
+//! [`crate::shadow::compute_identity_hash`]), and the detector fits a
+//! least-squares line through the trend:
+//!
+//! ```text
+//! slope      = Σ(t - t̄)(n - n̄) / Σ(t - t̄)²          (fds per hour)
+//! confidence = R² · monotone · (1 - 2/k)
+//! ```
+//!
+//! where `monotone` is the fraction of steps that did not decrease and `k` is
+//! the number of samples. A flat or shrinking trend, or one that grew by fewer
+//! than [`MIN_FD_GROWTH`] descriptors, has confidence 0. The confidence is
+//! bucketed by the priors' `fd_leak` section and fed to the posterior as
+//! `fd_leak` evidence.
+//!
+//! Trends live next to the shadow observations in a small JSON file.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the trend table inside the shadow storage directory.
+pub const FD_TRENDS_FILE: &str = "fd_trends.json";
+
+/// Samples needed before a trend is assessed.
+pub const MIN_TREND_SAMPLES: usize = 4;
+
+/// Samples kept per identity; older ones are dropped first.
+pub const MAX_TREND_SAMPLES: usize = 48;
+
+/// Net growth (last minus first count) below which no leak is reported.
+pub const MIN_FD_GROWTH: u32 = 8;
+
+/// Confidence from which a trend is reported as a likely leak.
+pub const LIKELY_LEAK_CONFIDENCE: f64 = 0.6;
+
+/// Shortest time span a trend must cover before it is assessed.
+const MIN_TREND_SPAN_SECS: i64 = 600;
+
+/// Trends not extended for this many days are dropped on save.
+const STALE_AFTER_DAYS: i64 = 7;
+
+/// Errors from loading or saving the trend table.
+#[derive(Debug, Error)]
+pub enum FdTrendError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// One observed open-fd count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FdSample {
+    pub at: DateTime<Utc>,
+    pub fd_count: u32,
+}
+
+/// Observed fd counts of one process identity, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FdTrend {
+    pub pid: u32,
+    pub comm: String,
+    pub samples: Vec<FdSample>,
+}
+
+/// Result of fitting a trend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FdLeakAssessment {
+    /// Samples in the fit.
+    pub samples: usize,
+    /// Time between the first and last sample, in hours.
+    pub span_hours: f64,
+    pub first_fd_count: u32,
+    pub last_fd_count: u32,
+    /// Fitted growth in descriptors per hour.
+    pub slope_per_hour: f64,
+    /// Coefficient of determination of the linear fit.
+    pub r_squared: f64,
+    /// Fraction of consecutive steps that did not decrease.
+    pub monotonic_fraction: f64,
+    /// Leak confidence in `[0, 1]`.
+    pub confidence: f64,
+    /// Whether `confidence` reaches [`LIKELY_LEAK_CONFIDENCE`].
+    pub likely_leak: bool,
+}
+
+impl FdLeakAssessment {
+    /// One-line description for reports.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} fd leak: {} -> {} fds over {:.1}h ({:+.1}/h, R² {:.2}, confidence {:.2})",
+            if self.likely_leak {
+                "likely"
+            } else if self.confidence > 0.0 {
+                "possible"
+            } else {
+                "no"
+            },
+            self.first_fd_count,
+            self.last_fd_count,
+            self.span_hours,
+            self.slope_per_hour,
+            self.r_squared,
+            self.confidence
+        )
+    }
+}
+
+/// Fit a line through `samples` (oldest first) and score it as a leak.
+///
+/// Returns `None` for fewer than [`MIN_TREND_SAMPLES`] samples or a span
+/// shorter than ten minutes.
+pub fn detect_fd_leak(samples: &[FdSample]) -> Option<FdLeakAssessment> {
+    let n = samples.len();
+    if n < MIN_TREND_SAMPLES {
+        return None;
+    }
+    let first = samples.first()?;
+    let last = samples.last()?;
+    if (last.at - first.at).num_seconds() < MIN_TREND_SPAN_SECS {
+        return None;
+    }
+
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| {
+            let hours = (s.at - first.at).num_milliseconds() as f64 / 3_600_000.0;
+            (hours, s.fd_count as f64)
+        })
+        .collect();
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n as f64;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n as f64;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in &points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx <= 0.0 {
+        return None;
+    }
+    let slope_per_hour = sxy / sxx;
+    let r_squared = if syy > 0.0 {
+        (sxy * sxy / (sxx * syy)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let steps_up = samples
+        .windows(2)
+        .filter(|w| w[1].fd_count >= w[0].fd_count)
+        .count();
+    let monotonic_fraction = steps_up as f64 / (n - 1) as f64;
+
+    let growth = last.fd_count.saturating_sub(first.fd_count);
+    let confidence = if slope_per_hour > 0.0 && growth >= MIN_FD_GROWTH {
+        r_squared * monotonic_fraction * (1.0 - 2.0 / n as f64)
+    } else {
+        0.0
+    };
+
+    Some(FdLeakAssessment {
+        samples: n,
+        span_hours: points[n - 1].0,
+        first_fd_count: first.fd_count,
+        last_fd_count: last.fd_count,
+        slope_per_hour,
+        r_squared,
+        monotonic_fraction,
+        confidence,
+        likely_leak: confidence >= LIKELY_LEAK_CONFIDENCE,
+    })
+}
+
+/// Persisted per-identity fd-count trends.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FdTrendStore {
+    #[serde(default)]
+    trends: BTreeMap<String, FdTrend>,
+}
+
+impl FdTrendStore {
+    /// Load a table from disk; a missing file yields an empty table.
+    pub fn load(path: &Path) -> Result<Self, FdTrendError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drop stale trends and write the table to disk atomically.
+    pub fn save(&mut self, path: &Path, now: DateTime<Utc>) -> Result<(), FdTrendError> {
+        let cutoff = now - Duration::days(STALE_AFTER_DAYS);
+        self.trends
+            .retain(|_, t| t.samples.last().is_some_and(|s| s.at >= cutoff));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Number of identities tracked.
+    pub fn len(&self) -> usize {
+        self.trends.len()
+    }
+
+    /// Whether no identity is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.trends.is_empty()
+    }
+
+    /// Trend of an identity.
+    pub fn get(&self, identity_hash: &str) -> Option<&FdTrend> {
+        self.trends.get(identity_hash)
+    }
+
+    /// Append one observation to an identity's trend.
+    pub fn observe(
+        &mut self,
+        identity_hash: &str,
+        pid: u32,
+        comm: &str,
+        fd_count: u32,
+        now: DateTime<Utc>,
+    ) {
+        let trend = self
+            .trends
+            .entry(identity_hash.to_string())
+            .or_insert_with(|| FdTrend {
+                pid,
+                comm: comm.to_string(),
+                samples: Vec::new(),
+            });
+        trend.samples.push(FdSample { at: now, fd_count });
+        if trend.samples.len() > MAX_TREND_SAMPLES {
+            let excess = trend.samples.len() - MAX_TREND_SAMPLES;
+            trend.samples.drain(..excess);
+        }
+    }
+
+    /// Assess an identity's trend extended by the current count.
+    pub fn assess(
+        &self,
+        identity_hash: &str,
+        fd_count: u32,
+        now: DateTime<Utc>,
+    ) -> Option<FdLeakAssessment> {
+        let trend = self.trends.get(identity_hash)?;
+        let mut samples: Vec<FdSample> = trend
+            .samples
+            .iter()
+            .copied()
+            .filter(|s| s.at < now)
+            .collect();
+        samples.push(FdSample { at: now, fd_count });
+        detect_fd_leak(&samples)
+    }
+}
+
+/// Default location of the trend table for a shadow storage directory.
+pub fn fd_trends_path(shadow_dir: &Path) -> PathBuf {
+    shadow_dir.join(FD_TRENDS_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(counts: &[u32], step_minutes: i64) -> Vec<FdSample> {
+        let start = Utc::now() - Duration::minutes(step_minutes * counts.len() as i64);
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, &fd_count)| FdSample {
+                at: start + Duration::minutes(step_minutes * i as i64),
+                fd_count,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn steady_growth_is_a_likely_leak() {
+        let leak = detect_fd_leak(&series(&[40, 52, 61, 75, 88, 97, 110, 124], 30)).unwrap();
+        assert!(leak.likely_leak, "{:?}", leak);
+        assert!(leak.r_squared > 0.95);
+        assert_eq!(leak.monotonic_fraction, 1.0);
+        assert!((leak.slope_per_hour - 24.0).abs() < 2.0);
+        assert!(leak.summary().starts_with("likely fd leak: 40 -> 124"));
+    }
+
+    #[test]
+    fn flat_noisy_or_shrinking_trends_are_not_leaks() {
+        let flat = detect_fd_leak(&series(&[30, 30, 30, 30, 30], 30)).unwrap();
+        assert_eq!(flat.confidence, 0.0);
+
+        let noisy = detect_fd_leak(&series(&[30, 80, 25, 90, 20, 85, 40], 30)).unwrap();
+        assert!(!noisy.likely_leak, "{:?}", noisy);
+
+        let shrinking = detect_fd_leak(&series(&[120, 100, 90, 70, 50], 30)).unwrap();
+        assert_eq!(shrinking.confidence, 0.0);
+
+        // Steady but tiny growth is normal churn.
+        let small = detect_fd_leak(&series(&[10, 11, 12, 13, 14], 30)).unwrap();
+        assert_eq!(small.confidence, 0.0);
+    }
+
+    #[test]
+    fn short_trends_are_not_assessed() {
+        assert!(detect_fd_leak(&series(&[10, 40, 80], 30)).is_none());
+        assert!(detect_fd_leak(&series(&[10, 40, 80, 120, 160], 1)).is_none());
+    }
+
+    #[test]
+    fn store_assesses_history_plus_current_sample() {
+        let mut store = FdTrendStore::default();
+        for sample in series(&[40, 60, 80, 100, 120], 30) {
+            store.observe("id", 4242, "leaky", sample.fd_count, sample.at);
+        }
+        let leak = store.assess("id", 140, Utc::now()).unwrap();
+        assert_eq!(leak.samples, 6);
+        assert_eq!(leak.last_fd_count, 140);
+        assert!(leak.likely_leak);
+        assert!(store.assess("other", 140, Utc::now()).is_none());
+
+        for _ in 0..MAX_TREND_SAMPLES {
+            store.observe("id", 4242, "leaky", 140, Utc::now());
+        }
+        assert_eq!(store.get("id").unwrap().samples.len(), MAX_TREND_SAMPLES);
+    }
+
+    #[test]
+    fn save_load_round_trip_and_prunes_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fd_trends_path(dir.path());
+        let now = Utc::now();
+        let mut store = FdTrendStore::default();
+        store.observe("live", 1, "server", 10, now);
+        store.observe(
+            "gone",
+            2,
+            "worker",
+            10,
+            now - Duration::days(STALE_AFTER_DAYS + 1),
+        );
+        store.save(&path, now).unwrap();
+
+        let loaded = FdTrendStore::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get("live").unwrap().comm, "server");
+        assert!(FdTrendStore::load(&dir.path().join("missing.json"))
+            .unwrap()
+            .is_empty());
+    }
+}
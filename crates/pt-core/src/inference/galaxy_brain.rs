@@ -17,6 +17,8 @@ use super::ledger::{BayesFactorEntry, EvidenceLedger};
 #[cfg(test)]
 use super::ledger::{Classification, Confidence};
 use super::posterior::{ClassScores, PosteriorResult};
+use super::runtime_prior::RuntimeSurprise;
+use super::survival_eta::SurvivalEta;
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -227,10 +229,102 @@ fn render_full(
     lines.join("\n")
 }
 
+/// Render the category-relative runtime prior behind a `runtime_surprise` term.
+///
+/// Shows the fitted log-normal parameters for the process's signature
+/// category and where the process's age falls in that distribution.
+pub fn render_runtime_prior(surprise: &RuntimeSurprise, config: &GalaxyBrainConfig) -> String {
+    let fit = &surprise.fit;
+    let mu = sym(config.math_mode, "μ", "mu");
+    let sigma = sym(config.math_mode, "σ", "sigma");
+    let ge = sym(config.math_mode, "≥", ">=");
+    let arrow = sym(config.math_mode, "→", "->");
+    let mut lines = vec![section_header(
+        &format!("Runtime Prior (category: {})", surprise.category),
+        config,
+    )];
+    lines.push(format!(
+        "  ln T | {} ~ Normal({}={:.3}, {}={:.3})  n={}",
+        surprise.category, mu, fit.mu, sigma, fit.sigma, fit.samples,
+    ));
+    lines.push(format!(
+        "  median = {}  p90 = {}",
+        format_secs(fit.median_secs()),
+        format_secs(fit.runtime_at_z(Z_P90)),
+    ));
+    lines.push(format!(
+        "  t = {}  {}  z = {:.2}  P(T {} t) = {:.3e}  s = -ln P = {:.2} nats",
+        format_secs(surprise.runtime_secs),
+        arrow,
+        surprise.z_score,
+        ge,
+        surprise.survival,
+        surprise.surprisal,
+    ));
+    lines.join("\n")
+}
+
+/// Render the Weibull survival model behind an `eta_abandoned` estimate.
+///
+/// Shows the fitted shape and scale, the conjugate rate posterior, and the
+/// residual-time quantiles given the time already observed.
+pub fn render_survival_eta(eta: &SurvivalEta, config: &GalaxyBrainConfig) -> String {
+    let theta = sym(config.math_mode, "θ", "theta");
+    let lambda = sym(config.math_mode, "λ", "lambda");
+    let alpha = sym(config.math_mode, "α", "alpha");
+    let beta = sym(config.math_mode, "β", "beta");
+    let ge = sym(config.math_mode, "≥", ">=");
+    let mut lines = vec![section_header(
+        "Time to Abandonment (Weibull survival)",
+        config,
+    )];
+    lines.push(format!(
+        "  S(t) = exp(-{} t^k)  k={:.3}  {}={}  (events={}, censored={})",
+        theta,
+        eta.shape,
+        lambda,
+        format_secs(eta.scale_secs),
+        eta.events,
+        eta.censored,
+    ));
+    lines.push(format!(
+        "  P(T > t0+s | T > t0) = ({b} / ({b} + (t0+s)^k - t0^k))^{a}  t0 = {} observed",
+        format_secs(eta.observed_secs),
+        a = alpha,
+        b = beta,
+    ));
+    lines.push(format!(
+        "  ETA median = {}  {:.0}% CI [{}, {}]  confidence = {:.2}",
+        format_secs(eta.eta_secs),
+        eta.interval_mass * 100.0,
+        format_secs(eta.lower_secs),
+        format_secs(eta.upper_secs),
+        eta.confidence,
+    ));
+    lines.push(format!(
+        "  event: first shadow observation with P(abandoned) {} threshold",
+        ge
+    ));
+    lines.join("\n")
+}
+
+/// Standard-normal 90th percentile.
+const Z_P90: f64 = 1.281_551_6;
+
 // ---------------------------------------------------------------------------
 // Formatting helpers
 // ---------------------------------------------------------------------------
 
+fn format_secs(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    match secs {
+        s if s >= 86_400 => format!("{}d {}h", s / 86_400, (s % 86_400) / 3_600),
+        s if s >= 3_600 => format!("{}h {}m", s / 3_600, (s % 3_600) / 60),
+        s if s >= 60 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
 fn section_header(title: &str, config: &GalaxyBrainConfig) -> String {
     let bullet = sym(config.math_mode, "▸", ">");
     format!("{} {}", bullet, title)
@@ -350,6 +444,7 @@ mod tests {
                         abandoned: -1.386,
                         zombie: -1.386,
                     },
+                    weight: None,
                 },
                 EvidenceTerm {
                     feature: "cpu_occupancy".to_string(),
@@ -359,6 +454,7 @@ mod tests {
                         abandoned: -0.1,
                         zombie: -0.5,
                     },
+                    weight: None,
                 },
                 EvidenceTerm {
                     feature: "age_elapsed".to_string(),
@@ -368,6 +464,7 @@ mod tests {
                         abandoned: -0.2,
                         zombie: -0.8,
                     },
+                    weight: None,
                 },
             ],
         }
@@ -389,6 +486,7 @@ mod tests {
                     delta_bits: 2.74,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "age_elapsed".to_string(),
@@ -397,6 +495,7 @@ mod tests {
                     delta_bits: 2.31,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
             ],
             top_evidence: vec![],
@@ -510,6 +609,61 @@ mod tests {
         assert!(output.contains("Posterior Distribution"));
     }
 
+    #[test]
+    fn test_render_runtime_prior_ascii() {
+        use crate::inference::runtime_prior::LogNormalFit;
+        let surprise = RuntimeSurprise {
+            category: "TestRunner".to_string(),
+            fit: LogNormalFit {
+                mu: 600f64.ln(),
+                sigma: 0.7,
+                samples: 42,
+            },
+            runtime_secs: 14.0 * 86_400.0,
+            z_score: 12.08,
+            survival: 1e-22,
+            surprisal: 50.0,
+        };
+        let config = GalaxyBrainConfig {
+            math_mode: MathMode::Ascii,
+            ..Default::default()
+        };
+        let output = render_runtime_prior(&surprise, &config);
+        assert!(output.contains("Runtime Prior (category: TestRunner)"));
+        assert!(output.contains("mu=6.397"));
+        assert!(output.contains("n=42"));
+        assert!(output.contains("median = 10m 0s"));
+        assert!(output.contains("t = 14d 0h"));
+        assert!(output.is_ascii());
+    }
+
+    #[test]
+    fn test_render_survival_eta_ascii() {
+        let eta = SurvivalEta {
+            observed_secs: 7_200.0,
+            eta_secs: 5_400.0,
+            lower_secs: 600.0,
+            upper_secs: 21_600.0,
+            interval_mass: 0.9,
+            confidence: 0.95,
+            shape: 1.8,
+            scale_secs: 18_000.0,
+            events: 20,
+            censored: 7,
+        };
+        let config = GalaxyBrainConfig {
+            math_mode: MathMode::Ascii,
+            ..Default::default()
+        };
+        let output = render_survival_eta(&eta, &config);
+        assert!(output.contains("k=1.800"));
+        assert!(output.contains("lambda=5h 0m"));
+        assert!(output.contains("events=20, censored=7"));
+        assert!(output.contains("t0 = 2h 0m observed"));
+        assert!(output.contains("90% CI [10m 0s, 6h 0m]"));
+        assert!(output.is_ascii());
+    }
+
     #[test]
     fn test_config_serialization() {
         let v = Verbosity::Full;
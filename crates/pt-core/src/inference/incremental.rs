@@ -234,8 +234,19 @@ fn evidence_hash(evidence: &Evidence) -> u64 {
     evidence.tty.hash(&mut hasher);
     evidence.net.hash(&mut hasher);
     evidence.io_active.hash(&mut hasher);
+    evidence.io_rate.hash(&mut hasher);
+    evidence.fd_leak.hash(&mut hasher);
+    evidence.session_attachment.hash(&mut hasher);
     evidence.state_flag.hash(&mut hasher);
     evidence.command_category.hash(&mut hasher);
+    if let Some(gpu) = &evidence.gpu {
+        gpu.held.hash(&mut hasher);
+        gpu.utilization.map(|u| u.to_bits()).hash(&mut hasher);
+    }
+    evidence
+        .runtime_surprise
+        .map(|v| v.to_bits())
+        .hash(&mut hasher);
 
     hasher.finish()
 }
@@ -272,6 +283,14 @@ mod tests {
             io_active: Some(true),
             state_flag: None,
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         }
     }
 
@@ -17,6 +17,9 @@ pub struct BayesFactorEntry {
     pub delta_bits: f64,
     pub direction: String,
     pub strength: String,
+    /// Site evidence weight already applied to `log_bf`, if not 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -60,10 +63,13 @@ impl EvidenceLedger {
             Confidence::Low
         };
 
-        let summary = format!(
+        let mut summary = format!(
             "Classified as {:?} with {} confidence.",
             classification, confidence
         );
+        if let Some(note) = evidence_weights_note(result) {
+            summary = format!("{} {}", summary, note);
+        }
 
         // Calculate Bayes Factors for Abandoned vs Useful
         let mut bayes_factors = Vec::new();
@@ -106,6 +112,7 @@ impl EvidenceLedger {
                 delta_bits,
                 direction,
                 strength,
+                weight: term.weight,
             });
         }
 
@@ -202,17 +209,25 @@ pub struct FeatureGlyph {
 
 pub fn get_glyph(feature: &str) -> char {
     match feature {
-        "prior" => '\u{1F3B2}',            // dice - prior probability
-        "cpu" => '\u{1F4BB}',              // laptop - CPU activity
-        "runtime" => '\u{23F1}',           // stopwatch - process age
-        "orphan" => '\u{1F47B}',           // ghost - orphaned process
-        "tty" => '\u{1F5A5}',              // desktop computer - terminal
-        "net" => '\u{1F310}',              // globe - network activity
-        "io_active" => '\u{1F4BE}',        // floppy - I/O activity
-        "state_flag" => '\u{1F6A9}',       // flag - process state
-        "command_category" => '\u{1F3F7}', // label - command type
-        "signature_match" => '\u{1F50D}',  // magnifying glass
-        "fast_path" => '\u{26A1}',         // lightning bolt
+        "prior" => '\u{1F3B2}',               // dice - prior probability
+        "cpu" => '\u{1F4BB}',                 // laptop - CPU activity
+        "runtime" => '\u{23F1}',              // stopwatch - process age
+        "runtime_surprise" => '\u{231B}',     // hourglass - age vs. category norm
+        "orphan" => '\u{1F47B}',              // ghost - orphaned process
+        "tty" => '\u{1F5A5}',                 // desktop computer - terminal
+        "net" => '\u{1F310}',                 // globe - network activity
+        "net_recent_activity" => '\u{1F4E1}', // satellite - socket idle time
+        "behavior_change" => '\u{1F4C8}',     // chart - deviation from own baseline
+        "swap_pressure" => '\u{1F300}',       // cyclone - swap thrash under pressure
+        "io_active" => '\u{1F4BE}',           // floppy - I/O activity
+        "io_rate" => '\u{1F4CA}',             // bar chart - sampled I/O rate
+        "fd_leak" => '\u{1F4A7}',             // droplet - growing fd count
+        "session_attachment" => '\u{1F4CE}',  // paperclip - attached terminal session
+        "state_flag" => '\u{1F6A9}',          // flag - process state
+        "command_category" => '\u{1F3F7}',    // label - command type
+        "gpu" => '\u{1F3AE}',                 // video game - GPU occupancy
+        "signature_match" => '\u{1F50D}',     // magnifying glass
+        "fast_path" => '\u{26A1}',            // lightning bolt
         _ => '?',
     }
 }
@@ -222,12 +237,20 @@ pub fn default_glyph_map() -> std::collections::HashMap<String, char> {
         "prior",
         "cpu",
         "runtime",
+        "runtime_surprise",
         "orphan",
         "tty",
         "net",
+        "net_recent_activity",
+        "behavior_change",
+        "swap_pressure",
         "io_active",
+        "io_rate",
+        "fd_leak",
+        "session_attachment",
         "state_flag",
         "command_category",
+        "gpu",
         "signature_match",
         "fast_path",
     ];
@@ -260,6 +283,14 @@ pub fn build_process_explanation(proc: &ProcessRecord, priors: &Priors) -> serde
         io_active: None,
         state_flag,
         command_category: None, // Needs category mapping
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     // 2. Compute posterior
@@ -321,6 +352,7 @@ pub fn build_process_explanation(proc: &ProcessRecord, priors: &Priors) -> serde
             delta_bits,
             direction,
             strength,
+            weight: term.weight,
         });
     }
     bfs.sort_by(|a, b| {
@@ -346,9 +378,26 @@ pub fn build_process_explanation(proc: &ProcessRecord, priors: &Priors) -> serde
         "bayes_factors": bfs,
         "why_summary": summary,
         "evidence": evidence_to_json(&evidence),
+        "evidence_weights": result.applied_evidence_weights(),
     })
 }
 
+/// Sentence naming the site evidence weights applied to a posterior, if any.
+fn evidence_weights_note(result: &PosteriorResult) -> Option<String> {
+    let weights = result.applied_evidence_weights();
+    if weights.is_empty() {
+        return None;
+    }
+    let parts: Vec<String> = weights
+        .iter()
+        .map(|(feature, weight)| format!("{}×{:.2}", feature, weight))
+        .collect();
+    Some(format!(
+        "Site evidence weights applied: {}.",
+        parts.join(", ")
+    ))
+}
+
 fn evidence_to_json(evidence: &crate::inference::Evidence) -> serde_json::Value {
     let cpu = match &evidence.cpu {
         Some(crate::inference::CpuEvidence::Fraction { occupancy }) => {
@@ -369,6 +418,17 @@ fn evidence_to_json(evidence: &crate::inference::Evidence) -> serde_json::Value
         "io_active": evidence.io_active,
         "state_flag": evidence.state_flag,
         "command_category": evidence.command_category,
+        "gpu": evidence.gpu.map(|g| serde_json::json!({
+            "held": g.held,
+            "utilization": g.utilization,
+        })),
+        "runtime_surprise": evidence.runtime_surprise,
+        "net_recent_activity": evidence.net_recent_activity,
+        "behavior_change": evidence.behavior_change,
+        "swap_pressure": evidence.swap_pressure,
+        "io_rate": evidence.io_rate,
+        "fd_leak": evidence.fd_leak,
+        "session_attachment": evidence.session_attachment,
     })
 }
 
@@ -444,6 +504,7 @@ mod tests {
                 abandoned: abandoned_ll,
                 zombie: 0.0,
             },
+            weight: None,
         }
     }
 
@@ -852,6 +913,28 @@ mod tests {
         assert_eq!(ledger.posterior, result);
     }
 
+    #[test]
+    fn ledger_records_site_evidence_weights() {
+        let mut tty = make_term("tty", 0.0, 0.0);
+        tty.weight = Some(0.0);
+        let mut orphan = make_term("orphan", -0.5, -1.5);
+        orphan.weight = Some(0.5);
+        let result = make_posterior_with_terms(0.2, 0.8, vec![tty, orphan]);
+        let ledger = EvidenceLedger::from_posterior_result(&result, None, None);
+
+        assert_eq!(ledger.bayes_factors.len(), 1);
+        assert_eq!(ledger.bayes_factors[0].weight, Some(0.5));
+        assert!(ledger
+            .why_summary
+            .contains("Site evidence weights applied: orphan×0.50, tty×0.00."));
+
+        let unweighted = make_posterior_with_terms(0.2, 0.8, vec![make_term("cpu", 0.0, -1.0)]);
+        let ledger = EvidenceLedger::from_posterior_result(&unweighted, None, None);
+        assert!(!ledger.why_summary.contains("Site evidence weights"));
+        let json = serde_json::to_value(&ledger.bayes_factors[0]).unwrap();
+        assert!(json.get("weight").is_none());
+    }
+
     // ── BayesFactorEntry serde ──────────────────────────────────────
 
     #[test]
@@ -863,6 +946,7 @@ mod tests {
             delta_bits: 2.885,
             direction: "supports abandoned".to_string(),
             strength: "strong".to_string(),
+            weight: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         let back: BayesFactorEntry = serde_json::from_str(&json).unwrap();
@@ -386,6 +386,7 @@ mod tests {
                     delta_bits: 2.74,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "age_elapsed".to_string(),
@@ -394,6 +395,7 @@ mod tests {
                     delta_bits: 2.31,
                     direction: "supports abandoned".to_string(),
                     strength: "strong".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "net_sockets".to_string(),
@@ -402,6 +404,7 @@ mod tests {
                     delta_bits: -1.73,
                     direction: "supports useful".to_string(),
                     strength: "substantial".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "orphan_ppid".to_string(),
@@ -410,6 +413,7 @@ mod tests {
                     delta_bits: 1.0,
                     direction: "supports abandoned".to_string(),
                     strength: "weak".to_string(),
+                    weight: None,
                 },
                 BayesFactorEntry {
                     feature: "fd_count".to_string(),
@@ -418,6 +422,7 @@ mod tests {
                     delta_bits: 0.58,
                     direction: "supports abandoned".to_string(),
                     strength: "weak".to_string(),
+                    weight: None,
                 },
             ],
             top_evidence: vec![],
@@ -590,6 +595,7 @@ mod tests {
             delta_bits: 1.0,
             direction: "supports abandoned".to_string(),
             strength: "weak".to_string(),
+            weight: None,
         });
 
         let config = LedgerDisplayConfig {
@@ -1,5 +1,6 @@
 //! Inference engine modules.
 
+pub mod behavior_baseline;
 pub mod belief_prop;
 pub mod belief_state;
 pub mod beta_stacy;
@@ -14,6 +15,7 @@ pub mod drift_membrane;
 pub mod evt;
 pub mod explain;
 pub mod explain_api;
+pub mod fd_leak;
 pub mod flip_conditions;
 pub mod galaxy_brain;
 pub mod graph_smoothing;
@@ -30,15 +32,24 @@ pub mod ledger;
 pub mod ledger_display;
 pub mod martingale;
 pub mod mpp;
+pub mod online_update;
+pub mod parallel;
 pub mod posterior;
 pub mod ppc;
 pub mod prior_override;
 pub mod robust;
 pub mod robust_stats;
+pub mod runtime_prior;
+pub mod shadow_sprt;
 pub mod signature_fast_path;
 pub mod sketches;
+pub mod survival_eta;
 pub mod wasserstein;
 
+pub use behavior_baseline::{
+    behavior_baselines_path, BehaviorBaselineError, BehaviorBaselineStore, BehaviorDeviation,
+    BehaviorMetric, BehaviorSample,
+};
 pub use belief_prop::{
     propagate_beliefs, BeliefPropConfig, BeliefPropError, BeliefPropEvidence, BeliefPropResult,
     BeliefPropagator, ProcessNode, ProcessTree, State, TreeSummary,
@@ -80,6 +91,9 @@ pub use evt::{
     BatchEvtAnalyzer, EstimationMethod, EvtError, EvtEvidence, GpdConfig, GpdFitter, GpdResult,
     TailType, ThresholdMethod,
 };
+pub use fd_leak::{
+    detect_fd_leak, fd_trends_path, FdLeakAssessment, FdSample, FdTrend, FdTrendError, FdTrendStore,
+};
 pub use graph_smoothing::{
     build_neighbors, edges_from_clusters, smooth_values, GraphSmoothingConfig, GraphSmoothingError,
     GraphSmoothingResult,
@@ -128,9 +142,14 @@ pub use mpp::{
     BatchMppAnalyzer, BurstinessLevel, InterArrivalStats, MarkDistribution, MarkedEvent,
     MarkedPointProcess, MppConfig, MppEvidence, MppSummary,
 };
+pub use online_update::{
+    append_audit as append_prior_update_audit, apply_observations, prior_updates_path,
+    ObservedClass, OnlineUpdateConfig, OnlineUpdateError, PriorUpdateRecord, VerifiedObservation,
+};
+pub use parallel::{par_map_ordered, resolve_jobs, PARALLEL_MIN_ITEMS};
 pub use posterior::{
-    compute_posterior, ClassScores, CpuEvidence, Evidence, EvidenceTerm, PosteriorError,
-    PosteriorResult,
+    compute_posterior, ClassScores, CpuEvidence, Evidence, EvidenceTerm, GpuEvidence,
+    PosteriorError, PosteriorResult,
 };
 pub use ppc::{
     AggregatedPpcEvidence, BatchPpcChecker, FallbackAction, PpcChecker, PpcConfig, PpcError,
@@ -150,6 +169,14 @@ pub use robust::{
 pub use robust_stats::{
     summarize as summarize_robust_stats, RobustStatsConfig, RobustStatsError, RobustSummary,
 };
+pub use runtime_prior::{
+    runtime_priors_path, LogNormalFit, RuntimePriorError, RuntimePriorTable, RuntimeStats,
+    RuntimeSurprise,
+};
+pub use shadow_sprt::{
+    per_sample_log_lr, shadow_sprt_path, ProcessSprt, ShadowSprtConfig, ShadowSprtError,
+    ShadowSprtTracker, SprtVerdict, PER_SAMPLE_FEATURES,
+};
 pub use signature_fast_path::{
     fast_path_potentially_applicable, try_signature_fast_path, FastPathConfig, FastPathResult,
     FastPathSkipReason,
@@ -159,6 +186,7 @@ pub use sketches::{
     SketchManager, SketchManagerConfig, SketchResult, SketchSummary, SpaceSaving,
     SpaceSavingConfig, TDigest, TDigestConfig,
 };
+pub use survival_eta::{SurvivalEta, SurvivalEtaConfig, SurvivalEtaModel};
 pub use wasserstein::{
     wasserstein_1d, wasserstein_2_squared, AggregatedDriftEvidence, DriftAction, DriftMonitor,
     DriftResult, DriftSeverity, WassersteinConfig, WassersteinDetector, WassersteinError,
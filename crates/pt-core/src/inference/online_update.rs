@@ -0,0 +1,393 @@
+//! Online prior updating from verified action outcomes.
+//!
+//! `agent verify --update-priors` closes the loop between decisions and
+//! priors. Each verified kill becomes a labelled observation:
+//!
+//! - killed, stayed dead, no complaint filed → the process was `abandoned`
+//! - respawned by a supervisor, or a complaint was filed → it was `useful`
+//! - anything else (still running, PID reused, timeout) is inconclusive
+//!
+//! Labelled observations update two conjugate pieces of the priors:
+//!
+//! - Class prior `π ~ Dirichlet(N·π_1, …, N·π_4)`: the observed class gains
+//!   `w` pseudo-counts (`N` is [`OnlineUpdateConfig::class_concentration`]).
+//! - The observed class's `cpu_beta` (occupancy fraction `u`) and
+//!   `orphan_beta` (reparented to init): `α += w·x`, `β += w·(1 - x)`.
+//!
+//! Before each batch, existing pseudo-counts are multiplied by the forgetting
+//! factor `λ` ([`OnlineUpdateConfig::decay`]) so old evidence fades and the
+//! priors track drift, floored at [`OnlineUpdateConfig::min_param`] so a long
+//! run of updates cannot collapse a Beta. Every parameter change is returned
+//! as a [`PriorUpdateRecord`] and appended to `prior_updates.jsonl` for audit.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::config::priors::{BetaParams, ClassParams};
+use crate::config::Priors;
+use crate::verify::VerifyOutcome;
+
+/// File name of the prior update audit log inside the telemetry directory.
+pub const PRIOR_UPDATES_FILE: &str = "prior_updates.jsonl";
+
+/// Errors from writing the prior update audit log.
+#[derive(Debug, Error)]
+pub enum OnlineUpdateError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Tuning for online updates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnlineUpdateConfig {
+    /// Forgetting factor `λ ∈ (0, 1]` applied to pseudo-counts per batch.
+    pub decay: f64,
+    /// Pseudo-count weight `w` of one verified outcome.
+    pub weight: f64,
+    /// Dirichlet concentration `N` the class probabilities stand for.
+    pub class_concentration: f64,
+    /// Floor on any Beta/Dirichlet parameter after decay.
+    pub min_param: f64,
+}
+
+impl Default for OnlineUpdateConfig {
+    fn default() -> Self {
+        Self {
+            decay: 0.98,
+            weight: 1.0,
+            class_concentration: 100.0,
+            min_param: 0.5,
+        }
+    }
+}
+
+/// Ground-truth class inferred from a verified outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObservedClass {
+    Useful,
+    Abandoned,
+}
+
+impl ObservedClass {
+    /// Label for a verified outcome, or None if it says nothing about the class.
+    pub fn from_outcome(outcome: &VerifyOutcome, complaint: bool) -> Option<Self> {
+        if complaint {
+            return Some(ObservedClass::Useful);
+        }
+        match outcome {
+            VerifyOutcome::ConfirmedDead | VerifyOutcome::Cascaded => {
+                Some(ObservedClass::Abandoned)
+            }
+            VerifyOutcome::Respawned => Some(ObservedClass::Useful),
+            VerifyOutcome::ConfirmedStopped
+            | VerifyOutcome::StillRunning
+            | VerifyOutcome::PidReused
+            | VerifyOutcome::Timeout => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ObservedClass::Useful => "useful",
+            ObservedClass::Abandoned => "abandoned",
+        }
+    }
+}
+
+/// One labelled observation from `agent verify`.
+#[derive(Debug, Clone)]
+pub struct VerifiedObservation {
+    pub session_id: String,
+    pub pid: u32,
+    pub observed: ObservedClass,
+    /// CPU occupancy fraction at plan time, if recorded.
+    pub cpu_occupancy: Option<f64>,
+    /// Whether the process had been reparented to init at plan time.
+    pub orphan: Option<bool>,
+}
+
+/// Audit record of one prior parameter change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorUpdateRecord {
+    pub timestamp: String,
+    pub session_id: String,
+    /// Dotted path of the changed parameter, e.g. `classes.abandoned.cpu_beta`.
+    pub parameter: String,
+    /// Parameter values before the batch (`[α, β]` or the four class probabilities).
+    pub before: Vec<f64>,
+    pub after: Vec<f64>,
+    /// PIDs whose outcomes contributed to this change.
+    pub pids: Vec<u32>,
+    pub decay: f64,
+    pub weight: f64,
+}
+
+/// Fold a batch of verified observations into `priors`.
+///
+/// Returns one audit record per changed parameter; an empty batch changes
+/// nothing.
+pub fn apply_observations(
+    priors: &mut Priors,
+    observations: &[VerifiedObservation],
+    config: &OnlineUpdateConfig,
+    now: DateTime<Utc>,
+) -> Vec<PriorUpdateRecord> {
+    let Some(first) = observations.first() else {
+        return Vec::new();
+    };
+    let record =
+        |parameter: String, before: Vec<f64>, after: Vec<f64>, pids: Vec<u32>| PriorUpdateRecord {
+            timestamp: now.to_rfc3339(),
+            session_id: first.session_id.clone(),
+            parameter,
+            before,
+            after,
+            pids,
+            decay: config.decay,
+            weight: config.weight,
+        };
+    let mut records = Vec::new();
+
+    // Class prior: Dirichlet pseudo-counts N·π, decayed, plus w per outcome.
+    let classes = &mut priors.classes;
+    let before = vec![
+        classes.useful.prior_prob,
+        classes.useful_bad.prior_prob,
+        classes.abandoned.prior_prob,
+        classes.zombie.prior_prob,
+    ];
+    let mut alpha: Vec<f64> = before
+        .iter()
+        .map(|p| decayed(p * config.class_concentration, config))
+        .collect();
+    for obs in observations {
+        let index = match obs.observed {
+            ObservedClass::Useful => 0,
+            ObservedClass::Abandoned => 2,
+        };
+        alpha[index] += config.weight;
+    }
+    let total: f64 = alpha.iter().sum();
+    let after: Vec<f64> = alpha.iter().map(|a| a / total).collect();
+    classes.useful.prior_prob = after[0];
+    classes.useful_bad.prior_prob = after[1];
+    classes.abandoned.prior_prob = after[2];
+    classes.zombie.prior_prob = after[3];
+    records.push(record(
+        "classes.prior_prob".to_string(),
+        before,
+        after,
+        observations.iter().map(|o| o.pid).collect(),
+    ));
+
+    // Per-class evidence Betas for the classes that were observed.
+    for class in [ObservedClass::Useful, ObservedClass::Abandoned] {
+        let batch: Vec<&VerifiedObservation> = observations
+            .iter()
+            .filter(|o| o.observed == class)
+            .collect();
+        if batch.is_empty() {
+            continue;
+        }
+        let params: &mut ClassParams = match class {
+            ObservedClass::Useful => &mut classes.useful,
+            ObservedClass::Abandoned => &mut classes.abandoned,
+        };
+
+        let cpu: Vec<(u32, f64)> = batch
+            .iter()
+            .filter_map(|o| o.cpu_occupancy.map(|u| (o.pid, u.clamp(0.0, 1.0))))
+            .collect();
+        if let Some(change) = update_beta(&mut params.cpu_beta, &cpu, config) {
+            records.push(record(
+                format!("classes.{}.cpu_beta", class.as_str()),
+                change.0,
+                change.1,
+                cpu.iter().map(|(pid, _)| *pid).collect(),
+            ));
+        }
+
+        let orphan: Vec<(u32, f64)> = batch
+            .iter()
+            .filter_map(|o| o.orphan.map(|x| (o.pid, if x { 1.0 } else { 0.0 })))
+            .collect();
+        if let Some(change) = update_beta(&mut params.orphan_beta, &orphan, config) {
+            records.push(record(
+                format!("classes.{}.orphan_beta", class.as_str()),
+                change.0,
+                change.1,
+                orphan.iter().map(|(pid, _)| *pid).collect(),
+            ));
+        }
+    }
+
+    records
+}
+
+/// Decay then update a Beta with fractional observations `x ∈ [0, 1]`.
+///
+/// Returns `(before, after)` as `[α, β]`, or None when there is nothing to fold in.
+fn update_beta(
+    beta: &mut BetaParams,
+    observations: &[(u32, f64)],
+    config: &OnlineUpdateConfig,
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    if observations.is_empty() {
+        return None;
+    }
+    let before = vec![beta.alpha, beta.beta];
+    beta.alpha = decayed(beta.alpha, config);
+    beta.beta = decayed(beta.beta, config);
+    for (_, x) in observations {
+        beta.alpha += config.weight * x;
+        beta.beta += config.weight * (1.0 - x);
+    }
+    Some((before, vec![beta.alpha, beta.beta]))
+}
+
+fn decayed(value: f64, config: &OnlineUpdateConfig) -> f64 {
+    (value * config.decay).max(config.min_param)
+}
+
+/// Append audit records to the prior update log (JSON Lines).
+pub fn append_audit(path: &Path, records: &[PriorUpdateRecord]) -> Result<(), OnlineUpdateError> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Default location of the prior update log for a telemetry directory.
+pub fn prior_updates_path(telemetry_dir: &Path) -> PathBuf {
+    telemetry_dir.join(PRIOR_UPDATES_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn observation(pid: u32, observed: ObservedClass, cpu: f64) -> VerifiedObservation {
+        VerifiedObservation {
+            session_id: "pt-20260101-000000-abcd".to_string(),
+            pid,
+            observed,
+            cpu_occupancy: Some(cpu),
+            orphan: Some(true),
+        }
+    }
+
+    #[test]
+    fn outcomes_map_to_labels() {
+        use VerifyOutcome::*;
+        assert_eq!(
+            ObservedClass::from_outcome(&ConfirmedDead, false),
+            Some(ObservedClass::Abandoned)
+        );
+        assert_eq!(
+            ObservedClass::from_outcome(&ConfirmedDead, true),
+            Some(ObservedClass::Useful)
+        );
+        assert_eq!(
+            ObservedClass::from_outcome(&Respawned, false),
+            Some(ObservedClass::Useful)
+        );
+        assert_eq!(ObservedClass::from_outcome(&StillRunning, false), None);
+        assert_eq!(ObservedClass::from_outcome(&PidReused, false), None);
+    }
+
+    #[test]
+    fn abandoned_outcomes_raise_abandoned_prior() {
+        let mut priors = Priors::default();
+        let before = priors.classes.abandoned.prior_prob;
+        let batch: Vec<_> = (0..5)
+            .map(|pid| observation(pid, ObservedClass::Abandoned, 0.0))
+            .collect();
+
+        let records = apply_observations(
+            &mut priors,
+            &batch,
+            &OnlineUpdateConfig::default(),
+            Utc::now(),
+        );
+
+        assert!(priors.classes.abandoned.prior_prob > before);
+        let sum = priors.classes.useful.prior_prob
+            + priors.classes.useful_bad.prior_prob
+            + priors.classes.abandoned.prior_prob
+            + priors.classes.zombie.prior_prob;
+        assert!((sum - 1.0).abs() < 1e-9);
+        let params: Vec<&str> = records.iter().map(|r| r.parameter.as_str()).collect();
+        assert_eq!(
+            params,
+            [
+                "classes.prior_prob",
+                "classes.abandoned.cpu_beta",
+                "classes.abandoned.orphan_beta"
+            ]
+        );
+        assert_eq!(records[1].pids, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn decay_shrinks_concentration_but_respects_floor() {
+        let config = OnlineUpdateConfig {
+            decay: 0.5,
+            weight: 0.0,
+            ..Default::default()
+        };
+        let mut beta = BetaParams::new(4.0, 0.6);
+        update_beta(&mut beta, &[(1, 0.5)], &config).unwrap();
+        assert!((beta.alpha - 2.0).abs() < 1e-12);
+        assert!((beta.beta - config.min_param).abs() < 1e-12);
+        assert!(update_beta(&mut beta, &[], &config).is_none());
+    }
+
+    #[test]
+    fn empty_batch_is_a_no_op() {
+        let mut priors = Priors::default();
+        let records =
+            apply_observations(&mut priors, &[], &OnlineUpdateConfig::default(), Utc::now());
+        assert!(records.is_empty());
+        assert_eq!(
+            priors.classes.abandoned.prior_prob,
+            Priors::default().classes.abandoned.prior_prob
+        );
+    }
+
+    #[test]
+    fn audit_log_appends_jsonl() {
+        let dir = tempdir().unwrap();
+        let path = prior_updates_path(dir.path());
+        let mut priors = Priors::default();
+        let records = apply_observations(
+            &mut priors,
+            &[observation(7, ObservedClass::Useful, 0.4)],
+            &OnlineUpdateConfig::default(),
+            Utc::now(),
+        );
+        append_audit(&path, &records).unwrap();
+        append_audit(&path, &records).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), records.len() * 2);
+        let parsed: PriorUpdateRecord =
+            serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(parsed, records[0]);
+    }
+}
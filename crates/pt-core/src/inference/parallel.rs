@@ -0,0 +1,104 @@
+//! Parallel per-process inference.
+//!
+//! Posterior computation and evidence ledger construction are independent
+//! per process, so on hosts with tens of thousands of processes they run on a
+//! rayon work-stealing pool. Results keep input order, so plans, ledgers and
+//! anything folded over them afterwards are the same as with a serial loop.
+
+use rayon::prelude::*;
+
+/// Below this many items the pool setup costs more than it saves.
+pub const PARALLEL_MIN_ITEMS: usize = 256;
+
+/// Resolve a `--jobs` value: `0` means one job per available CPU.
+pub fn resolve_jobs(jobs: usize) -> usize {
+    if jobs > 0 {
+        return jobs;
+    }
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Map `f` over `items` on `jobs` threads, returning results in input order.
+///
+/// Runs serially for `jobs <= 1`, for small inputs, or when the pool cannot
+/// be created.
+pub fn par_map_ordered<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    if jobs <= 1 || items.len() < PARALLEL_MIN_ITEMS {
+        return items.iter().map(f).collect();
+    }
+    match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool.install(|| items.par_iter().map(&f).collect()),
+        Err(err) => {
+            tracing::warn!("inference pool unavailable ({}), running serially", err);
+            items.iter().map(f).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::{compute_posterior, CpuEvidence, Evidence, EvidenceLedger};
+    use pt_config::priors::Priors;
+
+    #[test]
+    fn results_keep_input_order() {
+        let items: Vec<u64> = (0..10_000).collect();
+        let squares = par_map_ordered(&items, 4, |x| x * x);
+        assert_eq!(squares.len(), items.len());
+        assert!(squares
+            .iter()
+            .enumerate()
+            .all(|(i, s)| *s == (i * i) as u64));
+    }
+
+    #[test]
+    fn small_inputs_and_single_job_run_serially() {
+        let main = std::thread::current().id();
+        let small: Vec<u32> = (0..8).collect();
+        let threads = par_map_ordered(&small, 8, |_| std::thread::current().id());
+        assert!(threads.iter().all(|id| *id == main));
+
+        let large: Vec<u32> = (0..1_000).collect();
+        let threads = par_map_ordered(&large, 1, |_| std::thread::current().id());
+        assert!(threads.iter().all(|id| *id == main));
+    }
+
+    #[test]
+    fn jobs_zero_means_all_cpus() {
+        assert_eq!(resolve_jobs(3), 3);
+        assert!(resolve_jobs(0) >= 1);
+    }
+
+    #[test]
+    fn parallel_posteriors_match_serial() {
+        let priors = Priors::default();
+        let evidence: Vec<Evidence> = (0..1_000)
+            .map(|i| Evidence {
+                cpu: Some(CpuEvidence::Fraction {
+                    occupancy: (i % 100) as f64 / 100.0,
+                }),
+                runtime_seconds: Some(60.0 * i as f64),
+                orphan: Some(i % 3 == 0),
+                tty: Some(i % 5 == 0),
+                ..Evidence::default()
+            })
+            .collect();
+        let infer = |e: &Evidence| {
+            let result = compute_posterior(&priors, e).unwrap();
+            let ledger = EvidenceLedger::from_posterior_result(&result, None, None);
+            (result.posterior.abandoned, ledger.classification)
+        };
+
+        let serial = par_map_ordered(&evidence, 1, infer);
+        let parallel = par_map_ordered(&evidence, 4, infer);
+        assert_eq!(serial, parallel);
+    }
+}
@@ -3,14 +3,19 @@
 //! Combines class priors with per-feature likelihoods in log-domain and
 //! returns normalized posteriors plus log-odds.
 
-use crate::config::priors::{ClassParams, CommandCategories, DirichletParams, Priors, StateFlags};
+use crate::config::priors::{
+    BehaviorChange, ClassParams, CommandCategories, DirichletParams, FdLeak, GammaParams, IoRate,
+    NetRecency, Priors, SessionAttachment, StateFlags, SwapPressure,
+};
 use pt_math::{log_beta, log_beta_pdf, log_gamma, normalize_log_probs};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Evidence for CPU activity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CpuEvidence {
     /// Use a fraction in \[0,1\] and a Beta likelihood.
     Fraction { occupancy: f64 },
@@ -18,8 +23,18 @@ pub enum CpuEvidence {
     Binomial { k: f64, n: f64, eta: Option<f64> },
 }
 
+/// Evidence for GPU occupancy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GpuEvidence {
+    /// Whether the process holds a GPU context (device memory or a DRM client).
+    pub held: bool,
+    /// Fraction of time the process kept the GPU busy, in \[0,1\], if measured.
+    pub utilization: Option<f64>,
+}
+
 /// Evidence inputs for posterior computation.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Evidence {
     pub cpu: Option<CpuEvidence>,
     pub runtime_seconds: Option<f64>,
@@ -29,6 +44,26 @@ pub struct Evidence {
     pub io_active: Option<bool>,
     pub state_flag: Option<usize>,
     pub command_category: Option<usize>,
+    pub gpu: Option<GpuEvidence>,
+    /// Category-relative runtime surprisal `-ln P(T >= t | category)` in nats.
+    pub runtime_surprise: Option<f64>,
+    /// Socket idle-time bucket (see `NetRecency::bucket`), for processes with TCP sockets.
+    pub net_recent_activity: Option<usize>,
+    /// Deviation bucket from the process fingerprint's own baseline (see
+    /// `BehaviorChange::bucket`), once the fingerprint has enough history.
+    pub behavior_change: Option<usize>,
+    /// Swapped-out memory bucket (see `SwapPressure::bucket`), only while the
+    /// host is under memory pressure (`SwapPressure::applies`).
+    pub swap_pressure: Option<usize>,
+    /// Storage I/O rate bucket (see `IoRate::bucket`), from two samples of
+    /// the I/O counters during the scan.
+    pub io_rate: Option<usize>,
+    /// Leak-confidence bucket of the process's fd-count trend (see
+    /// `FdLeak::bucket`), once shadow mode has enough observations.
+    pub fd_leak: Option<usize>,
+    /// Attachment bucket of the process's tmux/screen session or SSH login
+    /// (see `SessionAttachment::bucket`).
+    pub session_attachment: Option<usize>,
 }
 
 /// Per-class scores for the 4-state model.
@@ -60,6 +95,9 @@ impl ClassScores {
 pub struct EvidenceTerm {
     pub feature: String,
     pub log_likelihood: ClassScores,
+    /// Site weight applied to `log_likelihood` (from `evidence_weights`), if not 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
 }
 
 /// Posterior computation result.
@@ -71,6 +109,16 @@ pub struct PosteriorResult {
     pub evidence_terms: Vec<EvidenceTerm>,
 }
 
+impl PosteriorResult {
+    /// Site evidence weights applied to the terms, by feature.
+    pub fn applied_evidence_weights(&self) -> BTreeMap<String, f64> {
+        self.evidence_terms
+            .iter()
+            .filter_map(|term| term.weight.map(|w| (term.feature.clone(), w)))
+            .collect()
+    }
+}
+
 /// Errors raised during posterior computation.
 #[derive(Debug, Error)]
 pub enum PosteriorError {
@@ -103,6 +151,7 @@ pub fn compute_posterior(
     evidence_terms.push(EvidenceTerm {
         feature: "prior".to_string(),
         log_likelihood: prior_scores,
+        weight: None,
     });
 
     if let Some(cpu) = &evidence.cpu {
@@ -116,6 +165,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "cpu".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -130,6 +180,22 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "runtime".to_string(),
             log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(surprise) = evidence.runtime_surprise {
+        let term = ClassScores {
+            useful: log_lik_runtime_surprise(surprise, &priors.classes.useful)?,
+            useful_bad: log_lik_runtime_surprise(surprise, &priors.classes.useful_bad)?,
+            abandoned: log_lik_runtime_surprise(surprise, &priors.classes.abandoned)?,
+            zombie: log_lik_runtime_surprise(surprise, &priors.classes.zombie)?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "runtime_surprise".to_string(),
+            log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -152,6 +218,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "orphan".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -166,6 +233,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "tty".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -180,6 +248,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "net".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -210,6 +279,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "io_active".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -244,6 +314,7 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "state_flag".to_string(),
             log_likelihood: term,
+            weight: None,
         });
     }
 
@@ -278,9 +349,153 @@ pub fn compute_posterior(
         evidence_terms.push(EvidenceTerm {
             feature: "command_category".to_string(),
             log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.net_recent_activity {
+        let term = ClassScores {
+            useful: log_lik_dirichlet(
+                bucket,
+                priors.net_recency.as_ref(),
+                "net_recency",
+                "useful",
+            )?,
+            useful_bad: log_lik_dirichlet(
+                bucket,
+                priors.net_recency.as_ref(),
+                "net_recency",
+                "useful_bad",
+            )?,
+            abandoned: log_lik_dirichlet(
+                bucket,
+                priors.net_recency.as_ref(),
+                "net_recency",
+                "abandoned",
+            )?,
+            zombie: log_lik_dirichlet(
+                bucket,
+                priors.net_recency.as_ref(),
+                "net_recency",
+                "zombie",
+            )?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "net_recent_activity".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.behavior_change {
+        let params = priors.behavior_change.as_ref();
+        let term = ClassScores {
+            useful: log_lik_dirichlet(bucket, params, "behavior_change", "useful")?,
+            useful_bad: log_lik_dirichlet(bucket, params, "behavior_change", "useful_bad")?,
+            abandoned: log_lik_dirichlet(bucket, params, "behavior_change", "abandoned")?,
+            zombie: log_lik_dirichlet(bucket, params, "behavior_change", "zombie")?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "behavior_change".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.swap_pressure {
+        let params = priors.swap_pressure.as_ref();
+        let term = ClassScores {
+            useful: log_lik_dirichlet(bucket, params, "swap_pressure", "useful")?,
+            useful_bad: log_lik_dirichlet(bucket, params, "swap_pressure", "useful_bad")?,
+            abandoned: log_lik_dirichlet(bucket, params, "swap_pressure", "abandoned")?,
+            zombie: log_lik_dirichlet(bucket, params, "swap_pressure", "zombie")?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "swap_pressure".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.io_rate {
+        let params = priors.io_rate.as_ref();
+        let term = ClassScores {
+            useful: log_lik_dirichlet(bucket, params, "io_rate", "useful")?,
+            useful_bad: log_lik_dirichlet(bucket, params, "io_rate", "useful_bad")?,
+            abandoned: log_lik_dirichlet(bucket, params, "io_rate", "abandoned")?,
+            zombie: log_lik_dirichlet(bucket, params, "io_rate", "zombie")?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "io_rate".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.fd_leak {
+        let params = priors.fd_leak.as_ref();
+        let term = ClassScores {
+            useful: log_lik_dirichlet(bucket, params, "fd_leak", "useful")?,
+            useful_bad: log_lik_dirichlet(bucket, params, "fd_leak", "useful_bad")?,
+            abandoned: log_lik_dirichlet(bucket, params, "fd_leak", "abandoned")?,
+            zombie: log_lik_dirichlet(bucket, params, "fd_leak", "zombie")?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "fd_leak".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(bucket) = evidence.session_attachment {
+        let params = priors.session_attachment.as_ref();
+        let term = ClassScores {
+            useful: log_lik_dirichlet(bucket, params, "session_attachment", "useful")?,
+            useful_bad: log_lik_dirichlet(bucket, params, "session_attachment", "useful_bad")?,
+            abandoned: log_lik_dirichlet(bucket, params, "session_attachment", "abandoned")?,
+            zombie: log_lik_dirichlet(bucket, params, "session_attachment", "zombie")?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "session_attachment".to_string(),
+            log_likelihood: term,
+            weight: None,
+        });
+    }
+
+    if let Some(gpu) = &evidence.gpu {
+        let term = ClassScores {
+            useful: log_lik_gpu(gpu, &priors.classes.useful)?,
+            useful_bad: log_lik_gpu(gpu, &priors.classes.useful_bad)?,
+            abandoned: log_lik_gpu(gpu, &priors.classes.abandoned)?,
+            zombie: log_lik_gpu(gpu, &priors.classes.zombie)?,
+        };
+        log_unnormalized = add_scores(log_unnormalized, term);
+        evidence_terms.push(EvidenceTerm {
+            feature: "gpu".to_string(),
+            log_likelihood: term,
+            weight: None,
         });
     }
 
+    // Site evidence weights temper each term's likelihood ratios to LR^w.
+    if let Some(weights) = priors.evidence_weights.as_ref() {
+        log_unnormalized = prior_scores;
+        for term in evidence_terms.iter_mut().skip(1) {
+            let weight = weights.weight(&term.feature);
+            if weight != 1.0 {
+                term.log_likelihood = scale_scores(term.log_likelihood, weight);
+                term.weight = Some(weight);
+            }
+            log_unnormalized = add_scores(log_unnormalized, term.log_likelihood);
+        }
+    }
+
     let log_vec = log_unnormalized.as_vec();
     let log_post_vec = normalize_log_probs(&log_vec);
     if log_post_vec.iter().any(|v| v.is_nan()) {
@@ -314,6 +529,15 @@ fn add_scores(a: ClassScores, b: ClassScores) -> ClassScores {
     }
 }
 
+fn scale_scores(scores: ClassScores, factor: f64) -> ClassScores {
+    ClassScores {
+        useful: scores.useful * factor,
+        useful_bad: scores.useful_bad * factor,
+        abandoned: scores.abandoned * factor,
+        zombie: scores.zombie * factor,
+    }
+}
+
 fn ln_checked(value: f64, field: &'static str) -> Result<f64, PosteriorError> {
     if value <= 0.0 || value.is_nan() {
         return Err(PosteriorError::InvalidPriors {
@@ -389,17 +613,37 @@ fn log_lik_runtime(runtime: f64, priors: &ClassParams) -> Result<f64, PosteriorE
             message: format!("expected > 0, got {runtime}"),
         });
     }
+    log_gamma_pdf(runtime, gamma, "runtime_gamma")
+}
+
+fn log_lik_runtime_surprise(surprise: f64, priors: &ClassParams) -> Result<f64, PosteriorError> {
+    let gamma = match &priors.runtime_surprise_gamma {
+        Some(g) => g,
+        None => return Ok(0.0),
+    };
+    if surprise < 0.0 || !surprise.is_finite() {
+        return Err(PosteriorError::InvalidEvidence {
+            field: "runtime_surprise",
+            message: format!("expected finite value >= 0, got {surprise}"),
+        });
+    }
+    // A surprisal of exactly zero sits on the support boundary; shapes below
+    // one would diverge there.
+    log_gamma_pdf(surprise.max(1e-6), gamma, "runtime_surprise_gamma")
+}
+
+fn log_gamma_pdf(x: f64, gamma: &GammaParams, field: &'static str) -> Result<f64, PosteriorError> {
     if gamma.shape <= 0.0 || gamma.rate <= 0.0 {
         return Err(PosteriorError::InvalidPriors {
-            field: "runtime_gamma",
+            field,
             message: format!(
                 "shape and rate must be > 0 (shape={}, rate={})",
                 gamma.shape, gamma.rate
             ),
         });
     }
-    let log_pdf = gamma.shape * gamma.rate.ln() + (gamma.shape - 1.0) * runtime.ln()
-        - gamma.rate * runtime
+    let log_pdf = gamma.shape * gamma.rate.ln() + (gamma.shape - 1.0) * x.ln()
+        - gamma.rate * x
         - log_gamma(gamma.shape);
     Ok(log_pdf)
 }
@@ -438,6 +682,47 @@ fn log_lik_optional_beta_bernoulli(
     }
 }
 
+fn log_lik_gpu(gpu: &GpuEvidence, priors: &ClassParams) -> Result<f64, PosteriorError> {
+    let gate = match &priors.gpu_beta {
+        Some(g) => to_math_beta(g, "gpu_beta")?,
+        None => return Ok(0.0),
+    };
+    let level = priors
+        .gpu_util_beta
+        .as_ref()
+        .map(|b| to_math_beta(b, "gpu_util_beta"))
+        .transpose()?;
+    if let Some(u) = gpu.utilization {
+        if !(0.0..=1.0).contains(&u) {
+            return Err(PosteriorError::InvalidEvidence {
+                field: "gpu.utilization",
+                message: format!("expected value in [0,1], got {u}"),
+            });
+        }
+    }
+    Ok(pt_math::hurdle::log_likelihood(
+        &gate,
+        level.as_ref(),
+        gpu.held,
+        gpu.utilization,
+    ))
+}
+
+fn to_math_beta(
+    params: &crate::config::priors::BetaParams,
+    field: &'static str,
+) -> Result<pt_math::bernoulli::BetaParams, PosteriorError> {
+    pt_math::bernoulli::BetaParams::new(params.alpha, params.beta).ok_or_else(|| {
+        PosteriorError::InvalidPriors {
+            field,
+            message: format!(
+                "alpha and beta must be > 0 (alpha={}, beta={})",
+                params.alpha, params.beta
+            ),
+        }
+    })
+}
+
 fn log_lik_dirichlet(
     index: usize,
     params: Option<&impl DirichletAccess>,
@@ -512,6 +797,78 @@ impl DirichletAccess for StateFlags {
     }
 }
 
+impl DirichletAccess for NetRecency {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl DirichletAccess for BehaviorChange {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl DirichletAccess for SwapPressure {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl DirichletAccess for IoRate {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl DirichletAccess for FdLeak {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl DirichletAccess for SessionAttachment {
+    fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
+        match class {
+            "useful" => self.useful.as_ref(),
+            "useful_bad" => self.useful_bad.as_ref(),
+            "abandoned" => self.abandoned.as_ref(),
+            "zombie" => self.zombie.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 impl DirichletAccess for CommandCategories {
     fn get_class_dirichlet(&self, class: &'static str) -> Option<&DirichletParams> {
         match class {
@@ -542,8 +899,11 @@ mod tests {
             tty_beta: BetaParams::new(1.0, 1.0),
             net_beta: BetaParams::new(1.0, 1.0),
             io_active_beta: Some(BetaParams::new(1.0, 1.0)),
+            gpu_beta: None,
+            gpu_util_beta: None,
             hazard_gamma: None,
             competing_hazards: None,
+            runtime_surprise_gamma: None,
         };
         Priors {
             schema_version: "1.0.0".to_string(),
@@ -567,6 +927,13 @@ mod tests {
             robust_bayes: None,
             error_rate: None,
             bocpd: None,
+            evidence_weights: None,
+            net_recency: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         }
     }
 
@@ -630,6 +997,61 @@ mod tests {
         assert!(result.posterior.useful.is_finite());
     }
 
+    #[test]
+    fn idle_gpu_holder_shifts_toward_abandoned() {
+        let mut priors = base_priors();
+        for class in [&mut priors.classes.useful, &mut priors.classes.useful_bad] {
+            class.gpu_beta = Some(BetaParams::new(1.0, 20.0));
+            class.gpu_util_beta = Some(BetaParams::new(3.0, 2.0));
+        }
+        for class in [&mut priors.classes.abandoned, &mut priors.classes.zombie] {
+            class.gpu_beta = Some(BetaParams::new(1.5, 10.0));
+            class.gpu_util_beta = Some(BetaParams::new(1.0, 8.0));
+        }
+
+        let idle = Evidence {
+            gpu: Some(GpuEvidence {
+                held: true,
+                utilization: Some(0.0),
+            }),
+            ..Evidence::default()
+        };
+        let result = compute_posterior(&priors, &idle).expect("posterior");
+        assert!(result.posterior.abandoned > result.posterior.useful);
+        assert!(result.evidence_terms.iter().any(|t| t.feature == "gpu"));
+
+        let busy = Evidence {
+            gpu: Some(GpuEvidence {
+                held: true,
+                utilization: Some(0.9),
+            }),
+            ..Evidence::default()
+        };
+        let result = compute_posterior(&priors, &busy).expect("posterior");
+        assert!(result.posterior.useful > result.posterior.abandoned);
+
+        // Without GPU priors the evidence is ignored.
+        let result = compute_posterior(&base_priors(), &idle).expect("posterior");
+        assert!(approx_eq(result.posterior.useful, 0.25, 1e-12));
+    }
+
+    #[test]
+    fn gpu_utilization_out_of_range_errors() {
+        let mut priors = base_priors();
+        priors.classes.useful.gpu_beta = Some(BetaParams::new(1.0, 1.0));
+        let evidence = Evidence {
+            gpu: Some(GpuEvidence {
+                held: true,
+                utilization: Some(1.5),
+            }),
+            ..Evidence::default()
+        };
+        assert!(matches!(
+            compute_posterior(&priors, &evidence),
+            Err(PosteriorError::InvalidEvidence { .. })
+        ));
+    }
+
     // ── ClassScores ─────────────────────────────────────────────────
 
     #[test]
@@ -687,6 +1109,7 @@ mod tests {
                 abandoned: -0.5,
                 zombie: -3.0,
             },
+            weight: None,
         };
         let json = serde_json::to_string(&term).unwrap();
         let deser: EvidenceTerm = serde_json::from_str(&json).unwrap();
@@ -712,6 +1135,7 @@ mod tests {
             evidence_terms: vec![EvidenceTerm {
                 feature: "prior".to_string(),
                 log_likelihood: ClassScores::default(),
+                weight: None,
             }],
         };
         let json = serde_json::to_string(&result).unwrap();
@@ -756,6 +1180,8 @@ mod tests {
         assert!(e.io_active.is_none());
         assert!(e.state_flag.is_none());
         assert!(e.command_category.is_none());
+        assert!(e.gpu.is_none());
+        assert!(e.net_recent_activity.is_none());
     }
 
     // ── add_scores ──────────────────────────────────────────────────
@@ -964,8 +1390,11 @@ mod tests {
             tty_beta: BetaParams::new(1.0, 1.0),
             net_beta: BetaParams::new(1.0, 1.0),
             io_active_beta: None,
+            gpu_beta: None,
+            gpu_util_beta: None,
             hazard_gamma: None,
             competing_hazards: None,
+            runtime_surprise_gamma: None,
         };
         assert_eq!(log_lik_runtime(100.0, &class).unwrap(), 0.0);
     }
@@ -1166,6 +1595,14 @@ mod tests {
             io_active: Some(false),
             state_flag: None,
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         let result = compute_posterior(&priors, &evidence).expect("posterior");
         // 7 evidence terms: prior + cpu + runtime + orphan + tty + net + io_active
@@ -1177,6 +1614,173 @@ mod tests {
         assert!(approx_eq(sum, 1.0, 1e-10));
     }
 
+    #[test]
+    fn posterior_net_recency_idle_sockets_favor_abandoned() {
+        let mut priors = base_priors();
+        priors.net_recency = Priors::default().net_recency;
+        let nr = priors.net_recency.clone().expect("default net_recency");
+
+        let idle = Evidence {
+            net_recent_activity: Some(nr.bucket(7.0 * 86400.0)),
+            ..Evidence::default()
+        };
+        let active = Evidence {
+            net_recent_activity: Some(nr.bucket(5.0)),
+            ..Evidence::default()
+        };
+        let idle_post = compute_posterior(&priors, &idle).expect("posterior");
+        let active_post = compute_posterior(&priors, &active).expect("posterior");
+        assert!(idle_post.posterior.abandoned > active_post.posterior.abandoned);
+        assert!(idle_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "net_recent_activity"));
+
+        let out_of_range = Evidence {
+            net_recent_activity: Some(nr.thresholds_secs.len() + 1),
+            ..Evidence::default()
+        };
+        assert!(compute_posterior(&priors, &out_of_range).is_err());
+    }
+
+    #[test]
+    fn posterior_heavy_swap_under_pressure_favors_useful_bad() {
+        let mut priors = base_priors();
+        priors.swap_pressure = Priors::default().swap_pressure;
+        let sp = priors.swap_pressure.clone().expect("default swap_pressure");
+
+        let heavy = Evidence {
+            swap_pressure: Some(sp.bucket(2048.0)),
+            ..Evidence::default()
+        };
+        let none = Evidence {
+            swap_pressure: Some(sp.bucket(0.0)),
+            ..Evidence::default()
+        };
+        let heavy_post = compute_posterior(&priors, &heavy).expect("posterior");
+        let none_post = compute_posterior(&priors, &none).expect("posterior");
+        assert!(heavy_post.posterior.useful_bad > none_post.posterior.useful_bad);
+        assert!(heavy_post.posterior.useful < none_post.posterior.useful);
+        assert!(heavy_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "swap_pressure"));
+
+        let out_of_range = Evidence {
+            swap_pressure: Some(9),
+            ..Evidence::default()
+        };
+        assert!(compute_posterior(&priors, &out_of_range).is_err());
+    }
+
+    #[test]
+    fn posterior_idle_io_rate_favors_abandoned() {
+        let mut priors = base_priors();
+        priors.io_rate = Priors::default().io_rate;
+        let ir = priors.io_rate.clone().expect("default io_rate");
+
+        let idle = Evidence {
+            io_rate: Some(ir.bucket(0.0)),
+            ..Evidence::default()
+        };
+        let heavy = Evidence {
+            io_rate: Some(ir.bucket(8.0 * 1024.0 * 1024.0)),
+            ..Evidence::default()
+        };
+        let idle_post = compute_posterior(&priors, &idle).expect("posterior");
+        let heavy_post = compute_posterior(&priors, &heavy).expect("posterior");
+        assert!(idle_post.posterior.abandoned > heavy_post.posterior.abandoned);
+        assert!(heavy_post.posterior.useful_bad > idle_post.posterior.useful_bad);
+        assert!(idle_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "io_rate"));
+
+        let out_of_range = Evidence {
+            io_rate: Some(ir.bucket_names.len()),
+            ..Evidence::default()
+        };
+        assert!(compute_posterior(&priors, &out_of_range).is_err());
+    }
+
+    #[test]
+    fn posterior_likely_fd_leak_favors_useful_bad() {
+        let mut priors = base_priors();
+        priors.fd_leak = Priors::default().fd_leak;
+        let fl = priors.fd_leak.clone().expect("default fd_leak");
+
+        let leaking = Evidence {
+            fd_leak: Some(fl.bucket(0.9)),
+            ..Evidence::default()
+        };
+        let steady = Evidence {
+            fd_leak: Some(fl.bucket(0.0)),
+            ..Evidence::default()
+        };
+        let leaking_post = compute_posterior(&priors, &leaking).expect("posterior");
+        let steady_post = compute_posterior(&priors, &steady).expect("posterior");
+        assert!(leaking_post.posterior.useful_bad > steady_post.posterior.useful_bad);
+        assert!(leaking_post.posterior.useful < steady_post.posterior.useful);
+        assert!(leaking_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "fd_leak"));
+    }
+
+    #[test]
+    fn posterior_attached_active_session_favors_useful() {
+        let mut priors = base_priors();
+        priors.session_attachment = Priors::default().session_attachment;
+        let sa = priors
+            .session_attachment
+            .clone()
+            .expect("default session_attachment");
+
+        let active = Evidence {
+            session_attachment: Some(sa.bucket(true, Some(30))),
+            ..Evidence::default()
+        };
+        let detached = Evidence {
+            session_attachment: Some(sa.bucket(false, None)),
+            ..Evidence::default()
+        };
+        let active_post = compute_posterior(&priors, &active).expect("posterior");
+        let detached_post = compute_posterior(&priors, &detached).expect("posterior");
+        assert!(active_post.posterior.useful > detached_post.posterior.useful);
+        assert!(active_post.posterior.abandoned < detached_post.posterior.abandoned);
+        assert!(active_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "session_attachment"));
+    }
+
+    #[test]
+    fn posterior_behavior_change_anomaly_favors_useful_bad() {
+        let mut priors = base_priors();
+        priors.behavior_change = Priors::default().behavior_change;
+        let bc = priors
+            .behavior_change
+            .clone()
+            .expect("default behavior_change");
+
+        let anomalous = Evidence {
+            behavior_change: Some(bc.bucket(10.0)),
+            ..Evidence::default()
+        };
+        let typical = Evidence {
+            behavior_change: Some(bc.bucket(0.5)),
+            ..Evidence::default()
+        };
+        let anomalous_post = compute_posterior(&priors, &anomalous).expect("posterior");
+        let typical_post = compute_posterior(&priors, &typical).expect("posterior");
+        assert!(anomalous_post.posterior.useful_bad > typical_post.posterior.useful_bad);
+        assert!(anomalous_post.posterior.useful < typical_post.posterior.useful);
+        assert!(anomalous_post
+            .evidence_terms
+            .iter()
+            .any(|t| t.feature == "behavior_change"));
+    }
+
     #[test]
     fn posterior_asymmetric_priors_shift_result() {
         let mut priors = base_priors();
@@ -313,14 +313,19 @@ fn match_level_to_string(level: &MatchLevel) -> String {
 /// 2. Signature-specific priors
 /// 3. Category defaults
 /// 4. Global priors
+///
+/// Signatures in the observe-only rollout stage contribute nothing.
 pub fn resolve_priors(context: &PriorContext<'_>) -> ResolvedPriors {
     // Start with a clone of global priors
     let mut priors = context.global_priors.clone();
     let mut source_info = PriorSourceInfo::default();
+    let signature_match = context
+        .signature_match
+        .filter(|m| m.signature.rollout.applies_priors());
 
     // Apply category defaults if a signature match provides category info
     // but the signature itself doesn't have specific priors.
-    if let Some(sig_match) = context.signature_match {
+    if let Some(sig_match) = signature_match {
         if let Some(cat_defaults) = context.category_defaults {
             let sig_priors = &sig_match.signature.priors;
             // Only apply category defaults when the signature lacks its own priors
@@ -345,7 +350,7 @@ pub fn resolve_priors(context: &PriorContext<'_>) -> ResolvedPriors {
     }
 
     // Apply signature priors if available (check if priors are not empty)
-    if let Some(sig_match) = context.signature_match {
+    if let Some(sig_match) = signature_match {
         let sig_priors = &sig_match.signature.priors;
         if !sig_priors.is_empty() {
             let overrides = apply_signature_priors(&mut priors, sig_priors);
@@ -426,6 +431,8 @@ mod tests {
             priors,
             expectations: ProcessExpectations::default(),
             priority: 0,
+            rollout: Default::default(),
+            graceful_stop: None,
         }
     }
 
@@ -566,6 +573,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_observe_only_signature_not_applied() {
+        let global = default_priors();
+        let mut signature = make_test_signature(
+            "staged-process",
+            SupervisorCategory::Ci,
+            SignaturePriors {
+                abandoned: Some(BetaParams::new(9.0, 1.0)),
+                ..Default::default()
+            },
+        );
+        signature.rollout = crate::supervision::signature::RolloutStage::Observe;
+        let sig_match =
+            SignatureMatch::new(&signature, MatchLevel::CommandOnly, MatchDetails::default());
+
+        let context = PriorContext {
+            global_priors: &global,
+            signature_match: Some(&sig_match),
+            category_defaults: None,
+            user_overrides: None,
+        };
+
+        let resolved = resolve_priors(&context);
+        assert_eq!(resolved.source_info.source, PriorSource::Global);
+        assert_eq!(
+            resolved.priors.classes.abandoned.prior_prob,
+            global.classes.abandoned.prior_prob
+        );
+    }
+
     #[test]
     fn test_empty_signature_priors_not_applied() {
         let global = default_priors();
@@ -0,0 +1,336 @@
+//! Per-category expected-runtime priors learned from telemetry.
+//!
+//! "Has been running 14 days" means different things for nginx and pytest.
+//! This module keeps, per signature category, the sufficient statistics of
+//! `ln(age)` for every process seen in past session snapshots and fits a
+//! log-normal distribution from them. A live process in a fitted category is
+//! then scored by its category-relative surprisal
+//!
+//! ```text
+//! s = -ln P(T >= t | category) = -ln(1 - Φ((ln t - μ) / σ))
+//! ```
+//!
+//! which is fed to the posterior as `runtime_surprise` evidence. A process
+//! that is typical for its category has `s ~ Exponential(1)`; one that has
+//! overstayed it has a large `s`.
+//!
+//! The table is a small JSON file in the telemetry directory. Session
+//! snapshots are folded in once (tracked by session id), so the table
+//! outlives session retention.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the runtime prior table inside the telemetry directory.
+pub const RUNTIME_PRIORS_FILE: &str = "runtime_priors.json";
+
+/// Minimum number of observations before a category fit is used.
+pub const MIN_FIT_SAMPLES: u64 = 20;
+
+/// Floor on the fitted log-scale spread; keeps near-constant categories
+/// from producing unbounded surprisal.
+const MIN_SIGMA: f64 = 0.25;
+
+/// Ages below one second carry no category information.
+const MIN_RUNTIME_SECS: f64 = 1.0;
+
+/// Cap on reported surprisal (nats).
+const MAX_SURPRISAL: f64 = 50.0;
+
+/// Errors from loading or saving the runtime prior table.
+#[derive(Debug, Error)]
+pub enum RuntimePriorError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Sufficient statistics of `ln(runtime)` for one category.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeStats {
+    pub count: u64,
+    pub sum_log: f64,
+    pub sum_log_sq: f64,
+}
+
+impl RuntimeStats {
+    /// Record one observed runtime (seconds).
+    pub fn record(&mut self, runtime_secs: f64) {
+        if !runtime_secs.is_finite() {
+            return;
+        }
+        let x = runtime_secs.max(MIN_RUNTIME_SECS).ln();
+        self.count += 1;
+        self.sum_log += x;
+        self.sum_log_sq += x * x;
+    }
+
+    /// Maximum-likelihood log-normal fit, if there are enough observations.
+    pub fn fit(&self) -> Option<LogNormalFit> {
+        if self.count < MIN_FIT_SAMPLES {
+            return None;
+        }
+        let n = self.count as f64;
+        let mu = self.sum_log / n;
+        let var = (self.sum_log_sq / n - mu * mu).max(0.0);
+        Some(LogNormalFit {
+            mu,
+            sigma: var.sqrt().max(MIN_SIGMA),
+            samples: self.count,
+        })
+    }
+}
+
+/// Log-normal runtime distribution: `ln T ~ Normal(mu, sigma^2)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LogNormalFit {
+    pub mu: f64,
+    pub sigma: f64,
+    pub samples: u64,
+}
+
+impl LogNormalFit {
+    /// Median runtime in seconds (`e^mu`).
+    pub fn median_secs(&self) -> f64 {
+        self.mu.exp()
+    }
+
+    /// Runtime in seconds at the given standard-normal quantile `z`.
+    pub fn runtime_at_z(&self, z: f64) -> f64 {
+        (self.mu + self.sigma * z).exp()
+    }
+
+    /// Standardized log-runtime `(ln t - mu) / sigma`.
+    pub fn z_score(&self, runtime_secs: f64) -> f64 {
+        (runtime_secs.max(MIN_RUNTIME_SECS).ln() - self.mu) / self.sigma
+    }
+
+    /// Surprisal `-ln P(T >= t)` in nats, capped at [`MAX_SURPRISAL`].
+    pub fn surprisal(&self, runtime_secs: f64) -> f64 {
+        let z = self.z_score(runtime_secs);
+        (-ln_upper_tail(z)).clamp(0.0, MAX_SURPRISAL)
+    }
+}
+
+/// Category-relative runtime evidence for one process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSurprise {
+    pub category: String,
+    pub fit: LogNormalFit,
+    pub runtime_secs: f64,
+    pub z_score: f64,
+    /// `P(T >= t | category)`.
+    pub survival: f64,
+    /// `-ln survival`, the value used as evidence.
+    pub surprisal: f64,
+}
+
+/// Persisted per-category runtime statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuntimePriorTable {
+    #[serde(default)]
+    ingested_sessions: BTreeSet<String>,
+    #[serde(default)]
+    categories: BTreeMap<String, RuntimeStats>,
+}
+
+impl RuntimePriorTable {
+    /// Load a table from disk; a missing file yields an empty table.
+    pub fn load(path: &Path) -> Result<Self, RuntimePriorError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the table to disk atomically.
+    pub fn save(&self, path: &Path) -> Result<(), RuntimePriorError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Whether a session's snapshot has already been folded in.
+    pub fn is_ingested(&self, session_id: &str) -> bool {
+        self.ingested_sessions.contains(session_id)
+    }
+
+    /// Number of sessions folded into the table.
+    pub fn ingested_count(&self) -> usize {
+        self.ingested_sessions.len()
+    }
+
+    /// Fold one session's `(category, runtime_secs)` observations in.
+    ///
+    /// Returns `false` (and changes nothing) if the session was already
+    /// ingested.
+    pub fn ingest<I>(&mut self, session_id: &str, samples: I) -> bool
+    where
+        I: IntoIterator<Item = (String, f64)>,
+    {
+        if !self.ingested_sessions.insert(session_id.to_string()) {
+            return false;
+        }
+        for (category, runtime_secs) in samples {
+            self.categories
+                .entry(category)
+                .or_default()
+                .record(runtime_secs);
+        }
+        true
+    }
+
+    /// Raw statistics for a category.
+    pub fn stats(&self, category: &str) -> Option<&RuntimeStats> {
+        self.categories.get(category)
+    }
+
+    /// Fitted distribution for a category, if it has enough observations.
+    pub fn fit(&self, category: &str) -> Option<LogNormalFit> {
+        self.categories.get(category).and_then(RuntimeStats::fit)
+    }
+
+    /// All categories with a usable fit.
+    pub fn fits(&self) -> BTreeMap<&str, LogNormalFit> {
+        self.categories
+            .iter()
+            .filter_map(|(name, stats)| stats.fit().map(|fit| (name.as_str(), fit)))
+            .collect()
+    }
+
+    /// Category-relative surprise for a process of `category` aged `runtime_secs`.
+    pub fn surprise(&self, category: &str, runtime_secs: f64) -> Option<RuntimeSurprise> {
+        if !runtime_secs.is_finite() || runtime_secs <= 0.0 {
+            return None;
+        }
+        let fit = self.fit(category)?;
+        let surprisal = fit.surprisal(runtime_secs);
+        Some(RuntimeSurprise {
+            category: category.to_string(),
+            fit,
+            runtime_secs,
+            z_score: fit.z_score(runtime_secs),
+            survival: (-surprisal).exp(),
+            surprisal,
+        })
+    }
+}
+
+/// Default location of the runtime prior table for a telemetry directory.
+pub fn runtime_priors_path(telemetry_dir: &Path) -> PathBuf {
+    telemetry_dir.join(RUNTIME_PRIORS_FILE)
+}
+
+/// `ln P(Z >= z)` for a standard normal, stable far into the upper tail.
+///
+/// Uses the Chebyshev fit for `erfc` from Numerical Recipes (fractional
+/// error below 1.2e-7), evaluated in log space.
+fn ln_upper_tail(z: f64) -> f64 {
+    let x = z / std::f64::consts::SQRT_2;
+    if x < 0.0 {
+        // P(Z >= z) = 1 - P(Z >= -z); no cancellation since it is >= 0.5.
+        return (1.0 - ln_upper_tail(-z).exp()).ln();
+    }
+    let t = 1.0 / (1.0 + 0.5 * x);
+    let poly = -x * x - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    // erfc(x) = t * exp(poly); P(Z >= z) = erfc(x) / 2.
+    t.ln() + poly - std::f64::consts::LN_2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn table_with(category: &str, runtimes: &[f64]) -> RuntimePriorTable {
+        let mut table = RuntimePriorTable::default();
+        table.ingest(
+            "pt-20260101-000000-abcd",
+            runtimes.iter().map(|&r| (category.to_string(), r)),
+        );
+        table
+    }
+
+    #[test]
+    fn upper_tail_matches_known_values() {
+        assert!((ln_upper_tail(0.0).exp() - 0.5).abs() < 1e-6);
+        assert!((ln_upper_tail(1.959_964).exp() - 0.025).abs() < 1e-5);
+        assert!((ln_upper_tail(-1.959_964).exp() - 0.975).abs() < 1e-5);
+        // Deep tail stays finite: ln P(Z >= 10) ~ -53.23.
+        assert!((ln_upper_tail(10.0) + 53.23).abs() < 0.05);
+    }
+
+    #[test]
+    fn fit_requires_minimum_samples() {
+        let table = table_with("TestRunner", &[60.0; 5]);
+        assert!(table.fit("TestRunner").is_none());
+        assert!(table.surprise("TestRunner", 60.0).is_none());
+    }
+
+    #[test]
+    fn fit_recovers_log_normal_parameters() {
+        // Symmetric spread around 600s in log space.
+        let runtimes: Vec<f64> = (0..40)
+            .map(|i| 600.0 * if i % 2 == 0 { 2.0 } else { 0.5 })
+            .collect();
+        let fit = table_with("TestRunner", &runtimes)
+            .fit("TestRunner")
+            .unwrap();
+        assert!((fit.median_secs() - 600.0).abs() < 1.0);
+        assert!((fit.sigma - 2f64.ln()).abs() < 1e-9);
+        assert_eq!(fit.samples, 40);
+    }
+
+    #[test]
+    fn surprise_is_category_relative() {
+        let mut table = RuntimePriorTable::default();
+        let day = 86_400.0;
+        let servers: Vec<(String, f64)> = (0..30)
+            .map(|i| ("WebServer".to_string(), day * (5.0 + (i % 10) as f64)))
+            .collect();
+        let tests: Vec<(String, f64)> = (0..30)
+            .map(|i| ("TestRunner".to_string(), 60.0 * (1.0 + (i % 10) as f64)))
+            .collect();
+        assert!(table.ingest("s1", servers));
+        assert!(table.ingest("s2", tests));
+        assert!(!table.ingest("s1", Vec::new()), "re-ingest is a no-op");
+
+        let two_weeks = 14.0 * day;
+        let server = table.surprise("WebServer", two_weeks).unwrap();
+        let test = table.surprise("TestRunner", two_weeks).unwrap();
+        assert!(server.surprisal < 3.0, "server: {server:?}");
+        assert!(test.surprisal > 20.0, "test: {test:?}");
+        assert!((server.survival - (-server.surprisal).exp()).abs() < 1e-12);
+        assert_eq!(table.fits().len(), 2);
+    }
+
+    #[test]
+    fn table_round_trips_through_disk() {
+        let dir = tempdir().unwrap();
+        let path = runtime_priors_path(dir.path());
+        assert_eq!(RuntimePriorTable::load(&path).unwrap().ingested_count(), 0);
+
+        let table = table_with("Build", &[120.0; 25]);
+        table.save(&path).unwrap();
+        let loaded = RuntimePriorTable::load(&path).unwrap();
+        assert!(loaded.is_ingested("pt-20260101-000000-abcd"));
+        assert_eq!(loaded.stats("Build"), table.stats("Build"));
+    }
+}
@@ -0,0 +1,350 @@
+//! Sequential (SPRT) evidence accumulation across shadow observations.
+//!
+//! A single `agent plan` sees one snapshot of a process, so its kill
+//! recommendation rests on one draw of noisy, time-varying evidence (a CPU
+//! sample that happened to be idle, a socket that happened to be quiet).
+//! Shadow mode observes the same process run after run; this module turns
+//! those repeated looks into a Wald SPRT of
+//!
+//! - H1: the process is abandoned
+//! - H0: the process is useful
+//!
+//! Each observation contributes the abandoned-vs-useful log-likelihood ratio
+//! of its *per-sample* evidence terms only ([`PER_SAMPLE_FEATURES`]). Static
+//! features (prior, runtime, orphan, TTY, command category) are the same in
+//! every look and would be double counted if summed, so they stay in the
+//! single-shot posterior. A kill recommendation is only promoted once the
+//! accumulated ratio crosses the upper boundary.
+//!
+//! State is keyed by process identity hash (PID + start id + uid), so a
+//! recycled PID starts a fresh test.
+
+use crate::inference::PosteriorResult;
+use chrono::{DateTime, Duration, Utc};
+use pt_math::sprt::{SprtDecision, SprtState, WaldBoundaries};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the SPRT state table inside the shadow storage directory.
+pub const SHADOW_SPRT_FILE: &str = "sprt_state.json";
+
+/// Evidence terms that vary between observations of the same process.
+pub const PER_SAMPLE_FEATURES: &[&str] = &[
+    "cpu",
+    "io_active",
+    "io_rate",
+    "net_recent_activity",
+    "behavior_change",
+    "swap_pressure",
+    "state_flag",
+    "gpu",
+];
+
+/// Errors from loading or saving SPRT state.
+#[derive(Debug, Error)]
+pub enum ShadowSprtError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid SPRT error rates: alpha={alpha}, beta={beta}")]
+    InvalidErrorRates { alpha: f64, beta: f64 },
+}
+
+/// SPRT configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShadowSprtConfig {
+    /// Target probability of promoting a kill for a useful process.
+    pub alpha: f64,
+    /// Target probability of never promoting a kill for an abandoned process.
+    pub beta: f64,
+    /// Observations required before a verdict is reported.
+    pub min_observations: u64,
+    /// Tests for identities unseen this long are dropped on save.
+    pub stale_after_hours: i64,
+}
+
+impl Default for ShadowSprtConfig {
+    fn default() -> Self {
+        Self {
+            alpha: 0.01,
+            beta: 0.05,
+            min_observations: 3,
+            stale_after_hours: 24,
+        }
+    }
+}
+
+impl ShadowSprtConfig {
+    /// Wald boundaries for the configured error rates.
+    pub fn boundaries(&self) -> Result<WaldBoundaries, ShadowSprtError> {
+        WaldBoundaries::from_error_rates(self.alpha, self.beta).ok_or(
+            ShadowSprtError::InvalidErrorRates {
+                alpha: self.alpha,
+                beta: self.beta,
+            },
+        )
+    }
+}
+
+/// Verdict of a per-process test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SprtVerdict {
+    /// Keep observing.
+    Pending,
+    /// Upper boundary crossed: evidence supports abandoned.
+    Abandoned,
+    /// Lower boundary crossed: evidence supports useful.
+    Useful,
+}
+
+impl SprtVerdict {
+    /// Stable snake_case name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SprtVerdict::Pending => "pending",
+            SprtVerdict::Abandoned => "abandoned",
+            SprtVerdict::Useful => "useful",
+        }
+    }
+}
+
+/// Running test for one process identity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessSprt {
+    pub pid: u32,
+    pub comm: String,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub state: SprtState,
+    pub verdict: SprtVerdict,
+}
+
+/// Persisted per-identity SPRT state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShadowSprtTracker {
+    #[serde(default)]
+    tests: BTreeMap<String, ProcessSprt>,
+}
+
+impl ShadowSprtTracker {
+    /// Load state from disk; a missing file yields an empty tracker.
+    pub fn load(path: &Path) -> Result<Self, ShadowSprtError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drop stale tests and write the state to disk atomically.
+    pub fn save(
+        &mut self,
+        path: &Path,
+        config: &ShadowSprtConfig,
+        now: DateTime<Utc>,
+    ) -> Result<(), ShadowSprtError> {
+        let cutoff = now - Duration::hours(config.stale_after_hours);
+        self.tests.retain(|_, t| t.last_seen >= cutoff);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Number of identities under test.
+    pub fn len(&self) -> usize {
+        self.tests.len()
+    }
+
+    /// Whether no identity is under test.
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty()
+    }
+
+    /// Current test for an identity.
+    pub fn get(&self, identity_hash: &str) -> Option<&ProcessSprt> {
+        self.tests.get(identity_hash)
+    }
+
+    /// Fold one observation into an identity's test.
+    ///
+    /// Once a boundary is crossed the verdict is final and further
+    /// observations only refresh `last_seen`.
+    pub fn observe(
+        &mut self,
+        identity_hash: &str,
+        pid: u32,
+        comm: &str,
+        log_lr: f64,
+        config: &ShadowSprtConfig,
+        now: DateTime<Utc>,
+    ) -> Result<&ProcessSprt, ShadowSprtError> {
+        let boundaries = config.boundaries()?;
+        let test = self
+            .tests
+            .entry(identity_hash.to_string())
+            .or_insert_with(|| ProcessSprt {
+                pid,
+                comm: comm.to_string(),
+                first_seen: now,
+                last_seen: now,
+                state: SprtState::default(),
+                verdict: SprtVerdict::Pending,
+            });
+        test.last_seen = now;
+        if test.verdict == SprtVerdict::Pending {
+            let decision = test.state.update(log_lr, &boundaries);
+            if test.state.observations >= config.min_observations {
+                test.verdict = match decision {
+                    SprtDecision::Continue => SprtVerdict::Pending,
+                    SprtDecision::AcceptH1 => SprtVerdict::Abandoned,
+                    SprtDecision::AcceptH0 => SprtVerdict::Useful,
+                };
+            }
+        }
+        Ok(test)
+    }
+}
+
+/// Abandoned-vs-useful log-likelihood ratio of one observation's per-sample
+/// evidence.
+pub fn per_sample_log_lr(result: &PosteriorResult) -> f64 {
+    result
+        .evidence_terms
+        .iter()
+        .filter(|term| PER_SAMPLE_FEATURES.contains(&term.feature.as_str()))
+        .map(|term| term.log_likelihood.abandoned - term.log_likelihood.useful)
+        .filter(|lr| lr.is_finite())
+        .sum()
+}
+
+/// Default location of the SPRT state for a shadow storage directory.
+pub fn shadow_sprt_path(shadow_dir: &Path) -> PathBuf {
+    shadow_dir.join(SHADOW_SPRT_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::{ClassScores, EvidenceTerm};
+
+    fn term(feature: &str, abandoned: f64, useful: f64) -> EvidenceTerm {
+        EvidenceTerm {
+            feature: feature.to_string(),
+            log_likelihood: ClassScores {
+                useful,
+                useful_bad: 0.0,
+                abandoned,
+                zombie: 0.0,
+            },
+            weight: None,
+        }
+    }
+
+    #[test]
+    fn per_sample_lr_ignores_static_terms() {
+        let result = PosteriorResult {
+            posterior: ClassScores::default(),
+            log_posterior: ClassScores::default(),
+            log_odds_abandoned_useful: 0.0,
+            evidence_terms: vec![
+                term("prior", 5.0, 0.0),
+                term("runtime", 3.0, 0.0),
+                term("cpu", -0.5, -1.5),
+                term("io_active", -2.0, -1.5),
+            ],
+        };
+        assert!((per_sample_log_lr(&result) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn verdict_waits_for_min_observations() {
+        let config = ShadowSprtConfig::default();
+        let mut tracker = ShadowSprtTracker::default();
+        let now = Utc::now();
+        // One huge increment crosses the boundary but cannot decide alone.
+        let t = tracker
+            .observe("h", 42, "sleep", 20.0, &config, now)
+            .unwrap();
+        assert_eq!(t.verdict, SprtVerdict::Pending);
+        tracker
+            .observe("h", 42, "sleep", 0.1, &config, now)
+            .unwrap();
+        let t = tracker
+            .observe("h", 42, "sleep", 0.1, &config, now)
+            .unwrap();
+        assert_eq!(t.verdict, SprtVerdict::Abandoned);
+        assert_eq!(t.state.observations, 3);
+    }
+
+    #[test]
+    fn verdict_is_final_once_reached() {
+        let config = ShadowSprtConfig::default();
+        let mut tracker = ShadowSprtTracker::default();
+        let now = Utc::now();
+        for _ in 0..3 {
+            tracker
+                .observe("h", 42, "node", -2.0, &config, now)
+                .unwrap();
+        }
+        assert_eq!(tracker.get("h").unwrap().verdict, SprtVerdict::Useful);
+        let t = tracker
+            .observe("h", 42, "node", 50.0, &config, now)
+            .unwrap();
+        assert_eq!(t.verdict, SprtVerdict::Useful);
+        assert_eq!(t.state.observations, 3);
+    }
+
+    #[test]
+    fn weak_evidence_stays_pending() {
+        let config = ShadowSprtConfig::default();
+        let mut tracker = ShadowSprtTracker::default();
+        let now = Utc::now();
+        for _ in 0..10 {
+            tracker.observe("h", 7, "vim", 0.2, &config, now).unwrap();
+        }
+        // 10 * 0.2 = 2.0 < ln(0.95 / 0.01) ≈ 4.55
+        assert_eq!(tracker.get("h").unwrap().verdict, SprtVerdict::Pending);
+    }
+
+    #[test]
+    fn invalid_rates_error() {
+        let config = ShadowSprtConfig {
+            alpha: 0.0,
+            ..Default::default()
+        };
+        let mut tracker = ShadowSprtTracker::default();
+        assert!(matches!(
+            tracker.observe("h", 1, "x", 1.0, &config, Utc::now()),
+            Err(ShadowSprtError::InvalidErrorRates { .. })
+        ));
+    }
+
+    #[test]
+    fn save_load_round_trip_and_prunes_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = shadow_sprt_path(dir.path());
+        let config = ShadowSprtConfig::default();
+        let now = Utc::now();
+        let mut tracker = ShadowSprtTracker::default();
+        tracker.observe("fresh", 1, "a", 1.0, &config, now).unwrap();
+        tracker
+            .observe("stale", 2, "b", 1.0, &config, now - Duration::hours(48))
+            .unwrap();
+        tracker.save(&path, &config, now).unwrap();
+
+        let loaded = ShadowSprtTracker::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get("fresh").unwrap().state.observations, 1);
+    }
+}
@@ -120,6 +120,8 @@ pub enum FastPathSkipReason {
     ScoreBelowThreshold,
     /// Signature has no explicit priors.
     NoPriors,
+    /// Signature is not rolled out to automatic decisions.
+    RolloutStage,
 }
 
 /// Attempt to use signature fast-path for classification.
@@ -153,6 +155,11 @@ pub fn try_signature_fast_path(
         None => return Err(FastPathSkipReason::NoMatch),
     };
 
+    // Only fully rolled-out signatures may bypass inference
+    if !sig_match.signature.rollout.is_auto() {
+        return Err(FastPathSkipReason::RolloutStage);
+    }
+
     // Check match score against threshold
     if sig_match.score < config.min_confidence_threshold {
         return Err(FastPathSkipReason::ScoreBelowThreshold);
@@ -302,6 +309,7 @@ fn build_fast_path_ledger(
             delta_bits: 0.0,
             direction: "fast_path".to_string(),
             strength: format!("Matched '{}' (score={:.2})", signature_name, match_score),
+            weight: None,
         }],
         top_evidence: vec![
             format!(
@@ -344,6 +352,8 @@ mod tests {
             priors,
             expectations: Default::default(),
             priority: 100,
+            rollout: Default::default(),
+            graceful_stop: None,
         }
     }
 
@@ -394,6 +404,24 @@ mod tests {
         assert!(matches!(result, Err(FastPathSkipReason::NoPriors)));
     }
 
+    #[test]
+    fn test_fast_path_requires_auto_rollout() {
+        let config = FastPathConfig::default();
+        let mut sig = make_test_signature(
+            "staged-sig",
+            SignaturePriors {
+                abandoned: Some(BetaParams::new(8.0, 2.0)),
+                ..Default::default()
+            },
+        );
+        sig.rollout = crate::supervision::signature::RolloutStage::Review;
+        let sig_match =
+            SignatureMatch::new(&sig, MatchLevel::MultiPattern, MatchDetails::default());
+
+        let result = try_signature_fast_path(&config, Some(&sig_match), 1234);
+        assert!(matches!(result, Err(FastPathSkipReason::RolloutStage)));
+    }
+
     #[test]
     fn test_fast_path_success() {
         let config = FastPathConfig::default();
@@ -0,0 +1,289 @@
+//! Time-to-abandonment (ETA) estimates from historical shadow observations.
+//!
+//! Shadow mode records every candidate's posterior run after run. Read as a
+//! survival study, each process identity is a subject followed from its first
+//! shadow observation:
+//!
+//! - **event**: the first observation with `P(abandoned) ≥ threshold`
+//! - **censored**: the identity never crossed the threshold (still running,
+//!   or exited while useful); follow-up ends at its last observation
+//!
+//! Identities already abandoned when first observed are left out, since the
+//! event time is unknown. The times are fitted with a Weibull model whose
+//! rate has a Gamma prior (see [`pt_math::weibull`]); for a live process the
+//! posterior predictive of its remaining time, given how long it has been
+//! observed without being abandoned, yields `eta_abandoned` with a credible
+//! interval.
+//!
+//! Time is measured from the first shadow observation, not process start:
+//! observations do not carry the process age.
+
+use chrono::{DateTime, Utc};
+use pt_math::weibull::{fit_weibull_shape, SurvivalTime, WeibullGammaPosterior};
+use pt_telemetry::shadow::Observation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Survival ETA configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalEtaConfig {
+    /// `P(abandoned)` at which an observation counts as the event.
+    pub abandoned_threshold: f64,
+    /// Events required before the model is fitted.
+    pub min_events: usize,
+    /// Prior weight, in pseudo-events.
+    pub prior_events: f64,
+    /// Prior characteristic time to abandonment (seconds).
+    pub prior_scale_secs: f64,
+    /// Mass of the reported credible interval.
+    pub interval_mass: f64,
+}
+
+impl Default for SurvivalEtaConfig {
+    fn default() -> Self {
+        Self {
+            abandoned_threshold: 0.5,
+            min_events: 5,
+            prior_events: 1.0,
+            prior_scale_secs: 3600.0,
+            interval_mass: 0.9,
+        }
+    }
+}
+
+/// Follow-up of one identity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FollowUp {
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    abandoned_at: Option<DateTime<Utc>>,
+}
+
+/// Remaining-time estimate for one process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalEta {
+    /// Time already observed without abandonment (seconds).
+    pub observed_secs: f64,
+    /// Posterior predictive median of the remaining time (seconds).
+    pub eta_secs: f64,
+    /// Lower end of the credible interval (seconds).
+    pub lower_secs: f64,
+    /// Upper end of the credible interval (seconds).
+    pub upper_secs: f64,
+    /// Mass of the credible interval.
+    pub interval_mass: f64,
+    /// Share of the rate posterior carried by data rather than the prior (0..1).
+    pub confidence: f64,
+    /// Fitted Weibull shape (>1: hazard grows with time observed).
+    pub shape: f64,
+    /// Posterior-mean Weibull scale (seconds).
+    pub scale_secs: f64,
+    /// Abandonment events in the history.
+    pub events: usize,
+    /// Censored identities in the history.
+    pub censored: usize,
+}
+
+/// Weibull time-to-abandonment model fitted to shadow history.
+#[derive(Debug, Clone)]
+pub struct SurvivalEtaModel {
+    posterior: WeibullGammaPosterior,
+    config: SurvivalEtaConfig,
+    follow_ups: BTreeMap<String, FollowUp>,
+    events: usize,
+    censored: usize,
+}
+
+impl SurvivalEtaModel {
+    /// Fit the model; None until the history holds enough events.
+    pub fn from_observations(
+        observations: &[Observation],
+        config: &SurvivalEtaConfig,
+    ) -> Option<Self> {
+        let follow_ups = follow_ups(observations, config.abandoned_threshold);
+        let data: Vec<SurvivalTime> = follow_ups.values().filter_map(survival_time).collect();
+        let events = data.iter().filter(|s| s.event).count();
+        if events < config.min_events.max(2) {
+            return None;
+        }
+        let shape = fit_weibull_shape(&data)?;
+        let prior_beta = config.prior_events * config.prior_scale_secs.powf(shape);
+        let posterior = WeibullGammaPosterior::fit(&data, shape, config.prior_events, prior_beta);
+        Some(Self {
+            posterior,
+            config: *config,
+            follow_ups,
+            events,
+            censored: data.len() - events,
+        })
+    }
+
+    /// Estimate for a process identity observed at `now`.
+    ///
+    /// Identities absent from the history are treated as first observed now.
+    /// None once the identity has been observed abandoned.
+    pub fn eta_for(&self, identity_hash: &str, now: DateTime<Utc>) -> Option<SurvivalEta> {
+        let observed_secs = match self.follow_ups.get(identity_hash) {
+            Some(f) if f.abandoned_at.is_some() => return None,
+            Some(f) => secs_between(f.first_seen, now),
+            None => 0.0,
+        };
+        let tail = (1.0 - self.config.interval_mass) / 2.0;
+        Some(SurvivalEta {
+            observed_secs,
+            eta_secs: self.posterior.residual_quantile(observed_secs, 0.5),
+            lower_secs: self.posterior.residual_quantile(observed_secs, tail),
+            upper_secs: self.posterior.residual_quantile(observed_secs, 1.0 - tail),
+            interval_mass: self.config.interval_mass,
+            confidence: 1.0 - self.config.prior_events / self.posterior.alpha,
+            shape: self.posterior.shape,
+            scale_secs: self.posterior.scale(),
+            events: self.events,
+            censored: self.censored,
+        })
+    }
+}
+
+fn follow_ups(observations: &[Observation], threshold: f64) -> BTreeMap<String, FollowUp> {
+    let mut ordered: Vec<&Observation> = observations.iter().collect();
+    ordered.sort_by_key(|obs| obs.timestamp);
+    let mut follow_ups: BTreeMap<String, FollowUp> = BTreeMap::new();
+    for obs in ordered {
+        let abandoned = obs.belief.p_abandoned as f64 >= threshold;
+        let entry = follow_ups
+            .entry(obs.identity_hash.clone())
+            .or_insert(FollowUp {
+                first_seen: obs.timestamp,
+                last_seen: obs.timestamp,
+                abandoned_at: None,
+            });
+        entry.last_seen = obs.timestamp;
+        if abandoned && entry.abandoned_at.is_none() {
+            entry.abandoned_at = Some(obs.timestamp);
+        }
+    }
+    follow_ups
+}
+
+fn survival_time(f: &FollowUp) -> Option<SurvivalTime> {
+    match f.abandoned_at {
+        // Abandoned on first sight: the event time is unknown.
+        Some(at) if at == f.first_seen => None,
+        Some(at) => Some(SurvivalTime::event(secs_between(f.first_seen, at))),
+        None => {
+            let time = secs_between(f.first_seen, f.last_seen);
+            (time > 0.0).then(|| SurvivalTime::censored(time))
+        }
+    }
+}
+
+fn secs_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    ((to - from).num_milliseconds() as f64 / 1000.0).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pt_telemetry::shadow::{BeliefState, StateSnapshot};
+
+    fn obs(identity: &str, at: DateTime<Utc>, p_abandoned: f32) -> Observation {
+        Observation {
+            timestamp: at,
+            pid: 1,
+            identity_hash: identity.to_string(),
+            state: StateSnapshot {
+                cpu_percent: 0.0,
+                memory_bytes: 0,
+                rss_bytes: 0,
+                fd_count: 0,
+                thread_count: 0,
+                state_char: 'S',
+                io_read_bytes: 0,
+                io_write_bytes: 0,
+                has_tty: false,
+                child_count: 0,
+            },
+            events: Vec::new(),
+            belief: BeliefState {
+                p_abandoned,
+                p_legitimate: 1.0 - p_abandoned,
+                p_zombie: 0.0,
+                p_useful_but_bad: 0.0,
+                confidence: 0.8,
+                score: 0.0,
+                recommendation: "keep".to_string(),
+            },
+        }
+    }
+
+    /// Identities abandoned after `hours[i]` hours of observation.
+    fn history(t0: DateTime<Utc>, hours: &[i64]) -> Vec<Observation> {
+        let mut out = Vec::new();
+        for (i, &h) in hours.iter().enumerate() {
+            let id = format!("abandoned-{}", i);
+            out.push(obs(&id, t0, 0.1));
+            out.push(obs(&id, t0 + Duration::hours(h), 0.9));
+        }
+        out
+    }
+
+    #[test]
+    fn needs_min_events() {
+        let t0 = Utc::now();
+        let config = SurvivalEtaConfig::default();
+        assert!(SurvivalEtaModel::from_observations(&history(t0, &[1, 2, 3]), &config).is_none());
+        assert!(
+            SurvivalEtaModel::from_observations(&history(t0, &[1, 2, 3, 4, 5]), &config).is_some()
+        );
+    }
+
+    #[test]
+    fn abandoned_on_first_sight_is_excluded() {
+        let t0 = Utc::now();
+        let mut observations = history(t0, &[1, 2, 3, 4, 5]);
+        observations.push(obs("born-abandoned", t0, 0.95));
+        observations.push(obs("born-abandoned", t0 + Duration::hours(1), 0.95));
+        let model =
+            SurvivalEtaModel::from_observations(&observations, &SurvivalEtaConfig::default())
+                .unwrap();
+        assert_eq!(model.events, 5);
+        assert_eq!(model.censored, 0);
+        assert!(model.eta_for("born-abandoned", t0).is_none());
+    }
+
+    #[test]
+    fn eta_interval_brackets_median() {
+        let t0 = Utc::now();
+        let model = SurvivalEtaModel::from_observations(
+            &history(t0, &[2, 3, 4, 5, 6, 7, 8, 9]),
+            &SurvivalEtaConfig::default(),
+        )
+        .unwrap();
+        let eta = model.eta_for("new-process", t0).unwrap();
+        assert_eq!(eta.observed_secs, 0.0);
+        assert!(eta.lower_secs < eta.eta_secs && eta.eta_secs < eta.upper_secs);
+        // Median abandonment time in the history is ~5.5h.
+        assert!(eta.eta_secs > 3.0 * 3600.0 && eta.eta_secs < 8.0 * 3600.0);
+        assert!(eta.confidence > 0.85);
+    }
+
+    #[test]
+    fn censored_identity_uses_observed_time() {
+        let t0 = Utc::now();
+        let mut observations = history(t0, &[2, 3, 4, 5, 6, 7, 8, 9]);
+        observations.push(obs("still-running", t0, 0.1));
+        observations.push(obs("still-running", t0 + Duration::hours(4), 0.2));
+        let model =
+            SurvivalEtaModel::from_observations(&observations, &SurvivalEtaConfig::default())
+                .unwrap();
+        assert_eq!(model.censored, 1);
+        let now = t0 + Duration::hours(4);
+        let eta = model.eta_for("still-running", now).unwrap();
+        assert_eq!(eta.observed_secs, 4.0 * 3600.0);
+        // Increasing hazard: a process 4h in has less time left than a new one.
+        let fresh = model.eta_for("unknown", now).unwrap();
+        assert!(eta.shape > 1.0);
+        assert!(eta.eta_secs < fresh.eta_secs);
+    }
+}
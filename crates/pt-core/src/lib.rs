@@ -6,6 +6,7 @@
 //! - CLI utilities and helpers
 //! - Process collection and scanning
 //! - Capability detection and caching
+//! - Programmatic plan construction ([`PlanBuilder`])
 //!
 //! The binary entry point is in `main.rs`.
 
@@ -19,25 +20,42 @@ pub mod collect;
 pub mod config;
 pub mod daemon;
 pub mod decision;
+pub mod dedupe;
 pub mod events;
 pub mod exit_codes;
+pub mod feedback;
 pub mod fleet;
 pub mod inbox;
 pub mod inference;
 pub mod install;
 pub mod learn;
+pub mod lock;
 pub mod logging;
 pub mod mcp;
 pub mod output;
 pub mod plan;
 pub mod plugin;
+pub mod redact;
 pub mod replay;
 pub mod schema;
 pub mod session;
 pub mod shadow;
 pub mod signature_cli;
+pub mod snooze;
 pub mod supervision;
+pub mod top;
 pub mod verify;
+pub mod watch;
+
+pub use plan::{validate_plan, ActionSpec, PlanBuildError, PlanBuilder};
+
+// Async runtime layer (optional, behind "async" feature)
+#[cfg(feature = "async")]
+pub mod runtime;
+
+// Read-only REST API (optional, behind "serve" feature)
+#[cfg(feature = "serve")]
+pub mod serve;
 
 // TUI module (optional, behind "ui" feature)
 #[cfg(feature = "ui")]
@@ -45,6 +63,8 @@ pub mod tui;
 
 // Re-export test utilities for integration tests
 #[cfg(any(test, feature = "test-utils"))]
+pub mod golden;
+#[cfg(any(test, feature = "test-utils"))]
 pub mod mock_process;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_log;
@@ -0,0 +1,548 @@
+//! Cooperative global lock shared by daemon, manual and agent runs.
+//!
+//! The lock is an advisory `flock` on `<data_dir>/.pt-lock`. While held, the
+//! file records who holds it ([`LockHolder`]: pid, command, session, start
+//! time) so contending agents can report the owner instead of failing blind.
+//! The record is cleared on release.
+//!
+//! Callers that prefer to wait use [`GlobalLock::acquire_timeout`], which
+//! queues them in `<data_dir>/.pt-lock.queue/`. Waiters are served in arrival
+//! order; entries of waiters that died are pruned. Non-waiting callers
+//! ([`GlobalLock::try_acquire`]) do not queue.
+//!
+//! [`inspect`] reports the holder and queue, and [`break_stale`] recovers from
+//! a holder that died without releasing the lock.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Lock file name inside the data directory.
+pub const GLOBAL_LOCK_FILE: &str = ".pt-lock";
+
+/// Interval between acquisition attempts while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Who holds the lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    /// Command that took the lock (e.g. `agent apply`).
+    pub command: String,
+    /// Session the holder is working on, once known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// RFC 3339 acquisition time (empty for holders that did not record one).
+    #[serde(default)]
+    pub started_at: String,
+}
+
+impl LockHolder {
+    fn current(command: &str) -> Self {
+        Self {
+            pid: std::process::id(),
+            command: command.to_string(),
+            session_id: None,
+            started_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Seconds since the lock was acquired, if known.
+    pub fn age_secs(&self) -> Option<u64> {
+        let started = DateTime::parse_from_rfc3339(&self.started_at).ok()?;
+        u64::try_from((Utc::now() - started.with_timezone(&Utc)).num_seconds()).ok()
+    }
+
+    pub fn is_alive(&self) -> bool {
+        pid_alive(self.pid)
+    }
+}
+
+/// A queued waiter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockWaiter {
+    pub pid: u32,
+    pub command: String,
+    pub enqueued_at: String,
+}
+
+/// Snapshot of the lock for `agent sessions --locks`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LockStatus {
+    pub path: PathBuf,
+    /// Whether some process currently holds the `flock`.
+    pub held: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<LockHolder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder_alive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_secs: Option<u64>,
+    /// A holder is recorded but its process is gone.
+    pub stale: bool,
+    /// Waiters in queue order.
+    pub waiters: Vec<LockWaiter>,
+}
+
+/// What [`break_stale`] did to the lock itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleLockAction {
+    /// Nothing to break: no holder recorded, or the holder is alive.
+    None,
+    /// The lock was free; the dead holder's record was cleared.
+    Cleared,
+    /// The lock was still held by a descriptor the dead holder leaked to a
+    /// child; the lock file was removed so new callers use a fresh one.
+    Removed,
+}
+
+/// Result of [`break_stale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BrokenLock {
+    pub action: StaleLockAction,
+    /// Queue entries of dead waiters that were removed.
+    pub pruned_waiters: usize,
+}
+
+/// Held global lock. Released (and its holder record cleared) on drop.
+#[derive(Debug)]
+pub struct GlobalLock {
+    file: File,
+    holder: LockHolder,
+}
+
+impl GlobalLock {
+    /// Take the lock if it is free; `Ok(None)` if another process holds it.
+    pub fn try_acquire(path: &Path, command: &str) -> io::Result<Option<Self>> {
+        let file = open_lock_file(path)?;
+        if !try_flock(&file)? {
+            return Ok(None);
+        }
+        let lock = Self {
+            file,
+            holder: LockHolder::current(command),
+        };
+        lock.write_holder()?;
+        Ok(Some(lock))
+    }
+
+    /// Wait up to `timeout` for the lock, queued behind earlier waiters.
+    ///
+    /// A zero timeout behaves like [`try_acquire`](Self::try_acquire).
+    pub fn acquire_timeout(
+        path: &Path,
+        command: &str,
+        timeout: Duration,
+    ) -> io::Result<Option<Self>> {
+        if timeout.is_zero() {
+            return Self::try_acquire(path, command);
+        }
+        let ticket = QueueTicket::enqueue(path, command)?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            if ticket.is_next()? {
+                if let Some(lock) = Self::try_acquire(path, command)? {
+                    return Ok(Some(lock));
+                }
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    pub fn holder(&self) -> &LockHolder {
+        &self.holder
+    }
+
+    /// Record the session this holder is working on.
+    pub fn set_session(&mut self, session_id: &str) -> io::Result<()> {
+        self.holder.session_id = Some(session_id.to_string());
+        self.write_holder()
+    }
+
+    fn write_holder(&self) -> io::Result<()> {
+        let json = serde_json::to_string(&self.holder).map_err(io::Error::other)?;
+        self.file.set_len(0)?;
+        let mut writer = &self.file;
+        writer.seek(SeekFrom::Start(0))?;
+        writer.write_all(json.as_bytes())?;
+        writer.flush()
+    }
+}
+
+impl Drop for GlobalLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        unlock(&self.file);
+    }
+}
+
+/// Read the recorded holder. Accepts the legacy format (a bare pid).
+pub fn read_holder(path: &Path) -> Option<LockHolder> {
+    let content = fs::read_to_string(path).ok()?;
+    let content = content.trim();
+    if content.is_empty() {
+        return None;
+    }
+    serde_json::from_str(content).ok().or_else(|| {
+        Some(LockHolder {
+            pid: content.parse().ok()?,
+            command: "unknown".to_string(),
+            session_id: None,
+            started_at: String::new(),
+        })
+    })
+}
+
+/// Inspect the lock without taking it.
+///
+/// `held` probes the `flock` with a shared, non-blocking lock that is released
+/// immediately.
+pub fn inspect(path: &Path) -> LockStatus {
+    let holder = read_holder(path);
+    let holder_alive = holder.as_ref().map(LockHolder::is_alive);
+    let held = match File::open(path) {
+        Ok(file) => is_flocked(&file),
+        Err(_) => false,
+    };
+    LockStatus {
+        path: path.to_path_buf(),
+        held,
+        age_secs: holder.as_ref().and_then(LockHolder::age_secs),
+        stale: holder_alive == Some(false),
+        holder,
+        holder_alive,
+        waiters: read_queue(path)
+            .into_iter()
+            .map(|(_, waiter)| waiter)
+            .collect(),
+    }
+}
+
+/// Break a stale lock: one whose recorded holder process no longer exists.
+///
+/// Live holders are never touched. Dead waiters are pruned from the queue
+/// either way.
+pub fn break_stale(path: &Path) -> io::Result<BrokenLock> {
+    let mut pruned_waiters = 0;
+    for (entry, waiter) in read_queue(path) {
+        if !pid_alive(waiter.pid) && fs::remove_file(entry).is_ok() {
+            pruned_waiters += 1;
+        }
+    }
+
+    let action = match read_holder(path) {
+        Some(holder) if !holder.is_alive() => {
+            let file = open_lock_file(path)?;
+            if try_flock(&file)? {
+                file.set_len(0)?;
+                unlock(&file);
+                StaleLockAction::Cleared
+            } else {
+                fs::remove_file(path)?;
+                StaleLockAction::Removed
+            }
+        }
+        _ => StaleLockAction::None,
+    };
+    Ok(BrokenLock {
+        action,
+        pruned_waiters,
+    })
+}
+
+fn queue_dir(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".queue");
+    path.with_file_name(name)
+}
+
+/// Queue entries in arrival order.
+fn read_queue(path: &Path) -> Vec<(PathBuf, LockWaiter)> {
+    let Ok(entries) = fs::read_dir(queue_dir(path)) else {
+        return Vec::new();
+    };
+    let mut queue: Vec<(PathBuf, LockWaiter)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let waiter = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+            Some((path, waiter))
+        })
+        .collect();
+    // Entry names start with a zero-padded enqueue timestamp.
+    queue.sort_by(|a, b| a.0.cmp(&b.0));
+    queue
+}
+
+/// A place in the wait queue, removed on drop.
+struct QueueTicket {
+    lock_path: PathBuf,
+    entry: PathBuf,
+}
+
+impl QueueTicket {
+    fn enqueue(lock_path: &Path, command: &str) -> io::Result<Self> {
+        let dir = queue_dir(lock_path);
+        fs::create_dir_all(&dir)?;
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let pid = std::process::id();
+        let entry = dir.join(format!("{:024}-{}.json", nanos, pid));
+        let waiter = LockWaiter {
+            pid,
+            command: command.to_string(),
+            enqueued_at: Utc::now().to_rfc3339(),
+        };
+        fs::write(
+            &entry,
+            serde_json::to_vec(&waiter).map_err(io::Error::other)?,
+        )?;
+        Ok(Self {
+            lock_path: lock_path.to_path_buf(),
+            entry,
+        })
+    }
+
+    /// Whether this ticket is first among live waiters. Prunes dead ones.
+    fn is_next(&self) -> io::Result<bool> {
+        for (entry, waiter) in read_queue(&self.lock_path) {
+            if entry == self.entry {
+                return Ok(true);
+            }
+            if pid_alive(waiter.pid) {
+                return Ok(false);
+            }
+            let _ = fs::remove_file(entry);
+        }
+        // Our entry vanished (e.g. pruned by `break_stale`); re-queueing would
+        // only lose our place, so compete directly.
+        Ok(true)
+    }
+}
+
+impl Drop for QueueTicket {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.entry);
+    }
+}
+
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Take an exclusive non-blocking `flock`; `Ok(false)` if held elsewhere.
+#[cfg(unix)]
+fn try_flock(file: &File) -> io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+    let result = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+    if result == 0 {
+        return Ok(true);
+    }
+    let err = io::Error::last_os_error();
+    if err.kind() == io::ErrorKind::WouldBlock {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+#[cfg(not(unix))]
+fn try_flock(_file: &File) -> io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(unix)]
+fn unlock(file: &File) {
+    use std::os::unix::io::AsRawFd;
+    unsafe {
+        libc::flock(file.as_raw_fd(), libc::LOCK_UN);
+    }
+}
+
+#[cfg(not(unix))]
+fn unlock(_file: &File) {}
+
+#[cfg(unix)]
+fn is_flocked(file: &File) -> bool {
+    use std::os::unix::io::AsRawFd;
+    let fd = file.as_raw_fd();
+    if unsafe { libc::flock(fd, libc::LOCK_SH | libc::LOCK_NB) } == 0 {
+        unlock(file);
+        false
+    } else {
+        true
+    }
+}
+
+#[cfg(not(unix))]
+fn is_flocked(_file: &File) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    // Guard against 0 and values that wrap to negative (process groups).
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || io::Error::last_os_error().kind() == io::ErrorKind::PermissionDenied
+}
+
+#[cfg(not(unix))]
+fn pid_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Far above any kernel's pid_max, so never alive.
+    const DEAD_PID: u32 = 999_999_999;
+
+    #[test]
+    fn holder_recorded_while_held_and_cleared_on_release() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+
+        let mut lock = GlobalLock::try_acquire(&path, "agent apply")
+            .unwrap()
+            .unwrap();
+        lock.set_session("pt-20260115-120000-ab12").unwrap();
+        let holder = read_holder(&path).unwrap();
+        assert_eq!(holder.pid, std::process::id());
+        assert_eq!(holder.command, "agent apply");
+        assert_eq!(
+            holder.session_id.as_deref(),
+            Some("pt-20260115-120000-ab12")
+        );
+        assert!(holder.age_secs().is_some());
+
+        drop(lock);
+        assert!(read_holder(&path).is_none());
+    }
+
+    #[test]
+    fn legacy_pid_only_holder_is_read() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+        fs::write(&path, "4242").unwrap();
+        let holder = read_holder(&path).unwrap();
+        assert_eq!(holder.pid, 4242);
+        assert_eq!(holder.age_secs(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn contended_lock_times_out_and_reports_holder() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+        let _held = GlobalLock::try_acquire(&path, "run").unwrap().unwrap();
+
+        assert!(GlobalLock::try_acquire(&path, "agent apply")
+            .unwrap()
+            .is_none());
+        let waited =
+            GlobalLock::acquire_timeout(&path, "agent apply", Duration::from_millis(250)).unwrap();
+        assert!(waited.is_none());
+
+        let status = inspect(&path);
+        assert!(status.held);
+        assert!(!status.stale);
+        assert_eq!(status.holder.unwrap().command, "run");
+        // The timed-out waiter left the queue.
+        assert!(status.waiters.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn waiter_acquires_after_release() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+        let held = GlobalLock::try_acquire(&path, "run").unwrap().unwrap();
+
+        let waiter_path = path.clone();
+        let waiter = thread::spawn(move || {
+            GlobalLock::acquire_timeout(&waiter_path, "agent apply", Duration::from_secs(10))
+                .unwrap()
+                .map(|lock| lock.holder().command.clone())
+        });
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(inspect(&path).waiters.len(), 1);
+        drop(held);
+
+        assert_eq!(waiter.join().unwrap().as_deref(), Some("agent apply"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dead_waiters_do_not_block_the_queue() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+        let dir = queue_dir(&path);
+        fs::create_dir_all(&dir).unwrap();
+        let dead = LockWaiter {
+            pid: DEAD_PID,
+            command: "run".to_string(),
+            enqueued_at: String::new(),
+        };
+        fs::write(
+            dir.join(format!("{:024}-{}.json", 1, DEAD_PID)),
+            serde_json::to_vec(&dead).unwrap(),
+        )
+        .unwrap();
+
+        let lock = GlobalLock::acquire_timeout(&path, "run", Duration::from_secs(5)).unwrap();
+        assert!(lock.is_some());
+        assert!(read_queue(&path).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_dead_holders_are_broken() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(GLOBAL_LOCK_FILE);
+        let record = |pid: u32| {
+            let holder = LockHolder {
+                pid,
+                command: "agent apply".to_string(),
+                session_id: None,
+                started_at: Utc::now().to_rfc3339(),
+            };
+            fs::write(&path, serde_json::to_string(&holder).unwrap()).unwrap();
+        };
+
+        record(std::process::id());
+        assert_eq!(break_stale(&path).unwrap().action, StaleLockAction::None);
+        assert!(read_holder(&path).is_some());
+
+        record(DEAD_PID);
+        assert!(inspect(&path).stale);
+        assert_eq!(break_stale(&path).unwrap().action, StaleLockAction::Cleared);
+        assert!(read_holder(&path).is_none());
+    }
+}
@@ -10,6 +10,7 @@
 use clap::parser::ValueSource;
 use clap::FromArgMatches;
 use clap::{Args, CommandFactory, Parser, Subcommand};
+use pt_common::units::{format_duration, format_size, DurationUnit};
 #[cfg(feature = "ui")]
 use pt_common::{IdentityQuality, ProcessIdentity};
 use pt_common::{OutputFormat, SessionId, SCHEMA_VERSION};
@@ -19,19 +20,27 @@ use pt_core::collect::protected::ProtectedFilter;
 #[cfg(target_os = "linux")]
 use pt_core::collect::{systemd::collect_systemd_unit, ContainerRuntime};
 use pt_core::config::{
-    get_preset, list_presets, load_config, ConfigError, ConfigOptions, PresetName, Priors,
+    get_preset, list_presets, load_config, ConfigError, ConfigOptions, Policy, PresetName, Priors,
+    ResolvedConfig,
 };
+use pt_core::dedupe::{CandidateIdentity, CandidateLedger, Detection, DetectionSource, Provenance};
 use pt_core::events::{
     FanoutEmitter, JsonlWriter, Phase, ProgressEmitter, ProgressEvent, SessionEmitter,
 };
 use pt_core::exit_codes::ExitCode;
+use pt_core::fleet::agentless::LocalInference;
 use pt_core::fleet::discovery::{
     FleetDiscoveryConfig, InventoryProvider, ProviderRegistry, StaticInventoryProvider,
 };
-use pt_core::fleet::ssh_scan::{scan_result_to_host_input, ssh_scan_fleet, SshScanConfig};
+use pt_core::fleet::inventory::HostGroup;
+use pt_core::fleet::scan_artifacts::{read_host_scans, write_host_scans};
+use pt_core::fleet::ssh_scan::{
+    count_signature_hits, scan_result_to_host_input, ssh_scan_fleet, FleetScanResult, SshScanConfig,
+};
 #[cfg(feature = "ui")]
+use pt_core::inference::galaxy_brain::{render as render_galaxy_brain, Verbosity};
 use pt_core::inference::galaxy_brain::{
-    render as render_galaxy_brain, GalaxyBrainConfig, MathMode, Verbosity,
+    render_runtime_prior, render_survival_eta, GalaxyBrainConfig, MathMode,
 };
 use pt_core::learn::{
     clear_progress as clear_learn_progress, find_tutorial, load_progress as load_learn_progress,
@@ -40,37 +49,53 @@ use pt_core::learn::{
     verify_tutorial as verify_learn_tutorial,
 };
 
+use pt_core::output::pagination::{ContinuationToken, PaginationError, PaginationStore};
 use pt_core::output::predictions::{
-    apply_field_selection, CpuPrediction, MemoryPrediction, PredictionDiagnostics, PredictionField,
-    PredictionFieldSelector, Predictions, TrajectoryAssessment, TrajectoryLabel, Trend,
+    apply_field_selection, CpuPrediction, EtaPrediction, MemoryPrediction, PredictionDiagnostics,
+    PredictionField, PredictionFieldSelector, Predictions, TrajectoryAssessment, TrajectoryLabel,
+    Trend,
+};
+use pt_core::output::{
+    encode_toon_value, CompactConfig, FieldSelector, ProcessedOutput, TokenEfficientOutput,
+    TokenEstimator,
 };
-use pt_core::output::{encode_toon_value, CompactConfig, FieldSelector, TokenEfficientOutput};
 #[cfg(feature = "ui")]
 use pt_core::plan::{generate_plan, DecisionBundle, DecisionCandidate};
+use pt_core::redact::{OutputRedactionError, OutputRedactor};
 use pt_core::session::compare::generate_comparison_report;
 use pt_core::session::diff::{
     compute_diff, DeltaKind, DiffConfig, InferenceSummary, ProcessDelta, SessionDiff,
 };
-use pt_core::session::fleet::{create_fleet_session, HostInput};
+use pt_core::session::fleet::{create_fleet_session, load_fleet_session, HostInput};
+#[cfg(feature = "ui")]
+use pt_core::session::snapshot_persist::{
+    list_artifacts, load_inference, load_inventory, load_tui_state, persist_tui_state,
+    PersistedSelection, TuiStateArtifact,
+};
 use pt_core::session::snapshot_persist::{
-    load_inference_unchecked, load_inventory_unchecked, persist_inference, persist_inventory,
-    InferenceArtifact, InventoryArtifact, PersistedInference, PersistedProcess,
+    load_experiment, load_inference_unchecked, load_inventory_unchecked, persist_evidence,
+    persist_experiment, persist_inference, persist_inventory, ArtifactEnvelope, EvidenceArtifact,
+    EvidenceRecord, ExperimentArtifact, ExperimentRecord, InferenceArtifact, InventoryArtifact,
+    PersistedInference, PersistedProcess,
 };
 use pt_core::session::{
-    ListSessionsOptions, SessionContext, SessionHandle, SessionManifest, SessionMode, SessionState,
-    SessionStore, SessionSummary,
+    ListSessionsOptions, SessionContext, SessionEventKind, SessionHandle, SessionManifest,
+    SessionMode, SessionState, SessionStore, SessionSummary,
 };
 use pt_core::shadow::ShadowRecorder;
 #[cfg(target_os = "linux")]
 use pt_core::supervision::{
     detect_supervision, is_human_supervised, AppActionType, AppSupervisionAnalyzer,
-    AppSupervisorType, ContainerActionType, ContainerSupervisionAnalyzer,
+    AppSupervisorType, ContainerActionType, ContainerSupervisionAnalyzer, DecisionAction,
+    DecisionRecord,
 };
 #[cfg(feature = "ui")]
 use pt_core::tui::widgets::ProcessRow;
 #[cfg(feature = "ui")]
-use pt_core::tui::{run_ftui, App, ExecutionOutcome};
+use pt_core::tui::{run_ftui, App, ExecutionOutcome, StageSignal, TelemetrySample};
 use pt_core::verify::{parse_agent_plan, verify_plan, VerifyError};
+use pt_core::watch::{RuleAction, WatchEvent};
+use pt_telemetry::quota::{DiskQuotaGuard, QuotaDecision, TelemetryMode, WritePriority};
 use pt_telemetry::retention::{RetentionConfig, RetentionEnforcer, RetentionError};
 use pt_telemetry::shadow::{Observation, ShadowStorage, ShadowStorageConfig};
 use pt_telemetry::writer::default_telemetry_dir;
@@ -133,10 +158,14 @@ struct GlobalOpts {
     #[arg(long, global = true)]
     no_color: bool,
 
-    /// Abort if operation exceeds time limit (seconds)
-    #[arg(long, global = true)]
+    /// Abort if operation exceeds time limit (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, global = true, value_parser = pt_common::units::cli::seconds)]
     timeout: Option<u64>,
 
+    /// Wait up to N seconds (or a duration like 2m) for the global lock instead of failing immediately
+    #[arg(long, global = true, value_name = "SECONDS", env = "PT_LOCK_WAIT", value_parser = pt_common::units::cli::seconds)]
+    lock_wait: Option<u64>,
+
     /// Non-interactive mode; execute policy-approved actions automatically
     #[arg(long, global = true)]
     robot: bool,
@@ -153,6 +182,21 @@ struct GlobalOpts {
     #[arg(long, global = true)]
     standalone: bool,
 
+    /// Redaction profile for command lines and environments in output: minimal, safe, forensic
+    #[arg(
+        long,
+        global = true,
+        value_name = "PROFILE",
+        default_value = "safe",
+        env = "PT_REDACTION_PROFILE",
+        value_parser = parse_export_profile
+    )]
+    redaction_profile: pt_redact::ExportProfile,
+
+    /// Print and persist raw command lines and environments (requires --redaction-profile forensic)
+    #[arg(long, global = true)]
+    no_redact: bool,
+
     // Token-efficient output options
     /// Select specific output fields (comma-separated or preset: minimal, standard, full)
     #[arg(long, global = true, value_name = "FIELDS")]
@@ -169,6 +213,10 @@ struct GlobalOpts {
     /// Estimate token count without full response
     #[arg(long, global = true)]
     estimate_tokens: bool,
+
+    /// Fetch the next chunk of truncated output (token from `_meta.continuation_token`)
+    #[arg(long = "continue", global = true, value_name = "TOKEN")]
+    continue_token: Option<String>,
 }
 
 impl GlobalOpts {
@@ -196,6 +244,25 @@ impl GlobalOpts {
         processor
     }
 
+    /// Persist the untruncated output behind a truncated result and return a
+    /// token `--continue` can resume from.
+    fn resumable_token(&self, result: &ProcessedOutput) -> Option<String> {
+        let truncation_token = result.continuation_token.as_deref()?;
+        let (Some(untruncated), Some(max_tokens)) = (result.untruncated.clone(), self.max_tokens)
+        else {
+            return result.continuation_token.clone();
+        };
+        match PaginationStore::from_env().and_then(|store| {
+            store.persist_truncation(untruncated, truncation_token, max_tokens, self.compact)
+        }) {
+            Ok(token) => Some(token.to_string()),
+            Err(e) => {
+                eprintln!("warning: output is not resumable: {}", e);
+                None
+            }
+        }
+    }
+
     /// Process JSON value through token-efficient output pipeline.
     /// Returns the processed string and optional metadata.
     fn process_output(&self, value: serde_json::Value) -> String {
@@ -228,7 +295,7 @@ impl GlobalOpts {
                 "data": result.json,
                 "_meta": {
                     "truncated": true,
-                    "continuation_token": result.continuation_token,
+                    "continuation_token": self.resumable_token(&result),
                     "remaining_count": result.remaining_count,
                     "token_count": result.token_count,
                 }
@@ -274,7 +341,7 @@ impl GlobalOpts {
                 "data": result.json,
                 "_meta": {
                     "truncated": true,
-                    "continuation_token": result.continuation_token,
+                    "continuation_token": self.resumable_token(&result),
                     "remaining_count": result.remaining_count,
                     "token_count": result.token_count,
                 }
@@ -308,9 +375,15 @@ enum Commands {
     /// Full deep scan with all available probes
     DeepScan(DeepScanArgs),
 
+    /// Live view of the highest-posterior candidates, refreshed in place
+    Top(TopArgs),
+
     /// Compare two sessions and show differences
     Diff(DiffArgs),
 
+    /// Inspect a session after apply
+    Session(SessionArgs),
+
     /// Query telemetry and history
     Query(QueryArgs),
 
@@ -343,9 +416,18 @@ enum Commands {
     /// Shadow mode observation management
     Shadow(ShadowArgs),
 
+    /// Compare and promote session-scoped configuration experiments
+    Calibrate(CalibrateArgs),
+
+    /// Record or list false positive/negative feedback on past decisions
+    Feedback(FeedbackArgs),
+
     /// Signature management (list, add, remove user signatures)
     Signature(pt_core::signature_cli::SignatureArgs),
 
+    /// Secret redaction tools (policy audit, pseudonymization dictionary, key rotation)
+    Redact(RedactArgs),
+
     /// Generate JSON schemas for agent output types
     Schema(SchemaArgs),
 
@@ -355,6 +437,9 @@ enum Commands {
     /// MCP server for AI agent integration
     Mcp(McpArgs),
 
+    /// Read-only REST API for dashboards (requires the `serve` feature)
+    Serve(ServeArgs),
+
     /// Generate shell completion scripts
     Completions(CompletionsArgs),
 
@@ -387,8 +472,8 @@ struct RunArgs {
     #[arg(long)]
     community_signatures: bool,
 
-    /// Only consider processes older than threshold (seconds)
-    #[arg(long)]
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     min_age: Option<u64>,
 
     /// Resource recovery goal for goal-oriented optimization
@@ -412,6 +497,47 @@ struct RunArgs {
     /// Also activatable via PT_ACCESSIBLE env var.
     #[arg(long)]
     accessible: bool,
+
+    /// Resume an interrupted interactive session instead of starting a new one.
+    /// Candidates, selections and goal are restored from the session snapshot.
+    #[arg(long, value_name = "SESSION")]
+    resume: Option<String>,
+
+    /// Stage kills: confirmed targets are stopped (SIGSTOP) first and only
+    /// killed after the grace period. Press U in the TUI to undo (SIGCONT).
+    #[arg(long)]
+    staged: bool,
+
+    /// Grace period before a staged kill proceeds (seconds, or a duration like 30s, 2m)
+    #[arg(long, default_value = "10", value_parser = pt_common::units::cli::seconds)]
+    stage_grace: u64,
+}
+
+#[derive(Args, Debug)]
+struct TopArgs {
+    /// Refresh interval in seconds (or a duration like 5s)
+    #[arg(long, default_value = "2", value_parser = pt_common::units::cli::seconds)]
+    interval: u64,
+
+    /// Number of candidates shown
+    #[arg(long, default_value = "20")]
+    limit: usize,
+
+    /// Hide processes scoring below this posterior (0-1)
+    #[arg(long, default_value = "0.0")]
+    min_score: f64,
+
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
+    min_age: Option<u64>,
+
+    /// Draw on the alternate screen instead of redrawing in place
+    #[arg(long)]
+    alt_screen: bool,
+
+    /// Stop after this many refreshes
+    #[arg(long)]
+    iterations: Option<u64>,
 }
 
 #[derive(Args, Debug)]
@@ -424,8 +550,8 @@ struct ScanArgs {
     #[arg(long, default_value = "3")]
     samples: u32,
 
-    /// Interval between samples (milliseconds)
-    #[arg(long, default_value = "500")]
+    /// Interval between samples (milliseconds, or a duration like 500ms, 2s)
+    #[arg(long, default_value = "500", value_parser = pt_common::units::cli::millis)]
     interval: u64,
 
     /// Include kernel threads in scan output (default: exclude)
@@ -443,11 +569,114 @@ struct DeepScanArgs {
     #[arg(long, value_delimiter = ',')]
     pids: Vec<u32>,
 
-    /// Maximum time budget for deep scan (seconds)
-    #[arg(long)]
+    /// Maximum time budget for deep scan (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     budget: Option<u64>,
 }
 
+#[derive(Args, Debug)]
+struct SessionArgs {
+    #[command(subcommand)]
+    command: SessionCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionCommands {
+    /// Compare the plan with outcomes and verification; writes action/reconciliation.json
+    Reconcile(SessionReconcileArgs),
+}
+
+#[derive(Args, Debug)]
+struct SessionReconcileArgs {
+    /// Session ID to reconcile (default: latest)
+    #[arg(long)]
+    session: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct RedactArgs {
+    #[command(subcommand)]
+    command: RedactCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum RedactCommands {
+    /// Check a session's artifacts for secrets the redaction policy would let through
+    Audit(RedactAuditArgs),
+    /// Export or read a session's hash token → original value dictionary
+    Dictionary(RedactDictionaryArgs),
+    /// Replace the hashing key and re-hash open sessions under the new one
+    RotateKey(RedactRotateKeyArgs),
+}
+
+#[derive(Args, Debug)]
+struct RedactAuditArgs {
+    /// Session ID to audit
+    #[arg(long)]
+    session: String,
+
+    /// Redaction policy file to audit instead of the configured one
+    #[arg(long, value_name = "PATH")]
+    policy: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct RedactRotateKeyArgs {
+    /// Why the key is rotated (recorded in the rotation log and telemetry)
+    #[arg(long)]
+    reason: Option<String>,
+
+    /// Also re-hash this session, whatever its state (repeatable)
+    #[arg(long = "session", value_name = "ID")]
+    sessions: Vec<String>,
+
+    /// Only rotate the key; leave existing session artifacts untouched
+    #[arg(long)]
+    no_rehash: bool,
+
+    /// Override telemetry directory for the rotation marker
+    #[arg(long)]
+    telemetry_dir: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct RedactDictionaryArgs {
+    #[command(subcommand)]
+    command: RedactDictionaryCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum RedactDictionaryCommands {
+    /// Write a session's dictionary encrypted under a responder passphrase
+    Export {
+        /// Session ID to export
+        #[arg(long)]
+        session: String,
+
+        /// Passphrase to encrypt the export with (or PT_BUNDLE_PASSPHRASE)
+        #[arg(long)]
+        passphrase: Option<String>,
+
+        /// Output path (default: <session>.dictionary.enc)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+    /// Decrypt an exported dictionary and print token → original pairs
+    Show {
+        /// Exported dictionary file
+        #[arg(value_name = "FILE")]
+        path: PathBuf,
+
+        /// Passphrase the export was encrypted with (or PT_BUNDLE_PASSPHRASE)
+        #[arg(long)]
+        passphrase: Option<String>,
+
+        /// Only show these tokens (e.g. "[HASH:k1:…]")
+        #[arg(value_name = "TOKEN")]
+        tokens: Vec<String>,
+    },
+}
+
 #[derive(Args, Debug)]
 struct DiffArgs {
     /// Base session ID (older snapshot)
@@ -484,8 +713,12 @@ struct QueryArgs {
     #[command(subcommand)]
     command: Option<QueryCommands>,
 
-    /// Query expression
+    /// SQL over the telemetry tables (requires the `query-sql` feature)
     query: Option<String>,
+
+    /// Override telemetry directory for SQL queries
+    #[arg(long)]
+    telemetry_dir: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -504,9 +737,29 @@ enum QueryCommands {
     },
     /// Query telemetry data
     Telemetry {
+        /// SQL query; without one, lists the queryable tables and columns
+        sql: Option<String>,
         /// Time range (e.g., "1h", "24h", "7d")
         #[arg(long, default_value = "24h")]
         range: String,
+        /// Override telemetry directory
+        #[arg(long)]
+        telemetry_dir: Option<String>,
+    },
+    /// Query long-term daily trends (candidates, recoveries, false positives)
+    Trends {
+        /// Restrict to one category (signature category or "uncategorized")
+        #[arg(long)]
+        category: Option<String>,
+        /// Restrict to one host ID
+        #[arg(long)]
+        host: Option<String>,
+        /// Time range in days or weeks (e.g., "30d", "90d", "12w")
+        #[arg(long, default_value = "90d")]
+        range: String,
+        /// Override telemetry directory holding the trends table
+        #[arg(long)]
+        telemetry_dir: Option<String>,
     },
 }
 
@@ -540,6 +793,18 @@ enum BundleCommands {
         #[arg(long)]
         include_dumps: bool,
 
+        /// Write a delta bundle with only the artifacts that changed since this session
+        #[arg(long, value_name = "SESSION")]
+        since: Option<String>,
+
+        /// Environment variable whose value may be captured raw (forensic profile only, repeatable)
+        #[arg(long = "allow-env", value_name = "NAME")]
+        allow_env: Vec<String>,
+
+        /// Regex for command line arguments that may be captured raw (forensic profile only, repeatable)
+        #[arg(long = "allow-arg", value_name = "REGEX")]
+        allow_arg: Vec<String>,
+
         /// Encrypt the bundle with a passphrase (explicit opt-in)
         #[arg(long)]
         encrypt: bool,
@@ -547,6 +812,14 @@ enum BundleCommands {
         /// Passphrase for bundle encryption/decryption (or use PT_BUNDLE_PASSPHRASE)
         #[arg(long)]
         passphrase: Option<String>,
+
+        /// Encrypt to an age X25519 recipient (`age1...`, repeatable)
+        #[arg(
+            long = "recipient",
+            value_name = "AGE_PUBKEY",
+            conflicts_with = "encrypt"
+        )]
+        recipients: Vec<String>,
     },
     /// Inspect an existing bundle
     Inspect {
@@ -560,6 +833,10 @@ enum BundleCommands {
         /// Passphrase for encrypted bundles (or use PT_BUNDLE_PASSPHRASE)
         #[arg(long)]
         passphrase: Option<String>,
+
+        /// Age identity file for recipient-encrypted bundles (or use PT_BUNDLE_IDENTITY)
+        #[arg(long, value_name = "PATH")]
+        identity: Option<PathBuf>,
     },
     /// Extract bundle contents
     Extract {
@@ -570,6 +847,10 @@ enum BundleCommands {
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Base bundle to resolve a delta bundle against (extracts the full contents)
+        #[arg(long, value_name = "PATH")]
+        base: Option<String>,
+
         /// Verify file checksums before extraction
         #[arg(long)]
         verify: bool,
@@ -577,22 +858,34 @@ enum BundleCommands {
         /// Passphrase for encrypted bundles (or use PT_BUNDLE_PASSPHRASE)
         #[arg(long)]
         passphrase: Option<String>,
+
+        /// Age identity file for recipient-encrypted bundles (or use PT_BUNDLE_IDENTITY)
+        #[arg(long, value_name = "PATH")]
+        identity: Option<PathBuf>,
     },
 }
 
 #[derive(Args, Debug)]
 struct ReportArgs {
     /// Session ID to generate report for (default: latest)
-    #[arg(long)]
+    #[arg(long, conflicts_with = "bundle")]
     session: Option<String>,
 
-    /// Output path for the HTML report
+    /// Path to a .ptb bundle file (alternative to --session)
+    #[arg(long)]
+    bundle: Option<String>,
+
+    /// Output path for the HTML report (default: stdout)
     #[arg(short, long)]
     output: Option<String>,
 
     /// Include detailed math ledger
     #[arg(long)]
     include_ledger: bool,
+
+    /// Report theme: light, dark, auto (default)
+    #[arg(long, default_value = "auto")]
+    theme: String,
 }
 
 #[derive(Args, Debug)]
@@ -619,12 +912,12 @@ struct LearnArgs {
     #[command(subcommand)]
     command: Option<LearnCommands>,
 
-    /// Per-check verification budget in milliseconds
-    #[arg(long, default_value_t = 750)]
+    /// Per-check verification budget in milliseconds (or a duration like 2s)
+    #[arg(long, default_value_t = 750, value_parser = pt_common::units::cli::millis)]
     verify_budget_ms: u64,
 
-    /// Total verification budget in milliseconds
-    #[arg(long, default_value_t = 5000)]
+    /// Total verification budget in milliseconds (or a duration like 2s)
+    #[arg(long, default_value_t = 5000, value_parser = pt_common::units::cli::millis)]
     total_budget_ms: u64,
 }
 
@@ -695,6 +988,9 @@ enum AgentCommands {
     /// View pending plans and notifications
     Inbox(AgentInboxArgs),
 
+    /// Spare a candidate, optionally remembering it so it is never suggested again
+    Spare(AgentSpareArgs),
+
     /// Stream session progress events (JSONL)
     Tail(AgentTailArgs),
 
@@ -743,6 +1039,18 @@ struct AgentExportArgs {
     #[arg(long)]
     include_dumps: bool,
 
+    /// Write a delta bundle with only the artifacts that changed since this session
+    #[arg(long, value_name = "SESSION")]
+    since: Option<String>,
+
+    /// Environment variable whose value may be captured raw (forensic profile only, repeatable)
+    #[arg(long = "allow-env", value_name = "NAME")]
+    allow_env: Vec<String>,
+
+    /// Regex for command line arguments that may be captured raw (forensic profile only, repeatable)
+    #[arg(long = "allow-arg", value_name = "REGEX")]
+    allow_arg: Vec<String>,
+
     /// Encrypt the bundle with a passphrase
     #[arg(long)]
     encrypt: bool,
@@ -750,6 +1058,14 @@ struct AgentExportArgs {
     /// Passphrase for bundle encryption (or use PT_BUNDLE_PASSPHRASE)
     #[arg(long)]
     passphrase: Option<String>,
+
+    /// Encrypt to an age X25519 recipient (`age1...`, repeatable)
+    #[arg(
+        long = "recipient",
+        value_name = "AGE_PUBKEY",
+        conflicts_with = "encrypt"
+    )]
+    recipients: Vec<String>,
 }
 
 #[derive(Args, Debug)]
@@ -786,18 +1102,27 @@ struct AgentFleetPlanArgs {
     #[arg(long, conflicts_with_all = ["hosts", "inventory"])]
     discovery_config: Option<String>,
 
+    /// Re-plan from the scans cached in an earlier fleet session (no SSH)
+    #[arg(long, conflicts_with_all = ["hosts", "inventory", "discovery_config"])]
+    from_session: Option<String>,
+
     /// Max concurrent host connections
     #[arg(long, default_value = "10")]
     parallel: u32,
 
-    /// Per-host timeout (seconds)
-    #[arg(long, default_value = "30")]
+    /// Per-host timeout (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, default_value = "30", value_parser = pt_common::units::cli::seconds)]
     timeout: u64,
 
     /// Continue if a host fails
     #[arg(long)]
     continue_on_error: bool,
 
+    /// Collect ps output with a shell payload and run inference locally
+    /// (no pt-core needed on the hosts)
+    #[arg(long)]
+    agentless: bool,
+
     /// Apply host-group priors
     #[arg(long)]
     host_profile: Option<String>,
@@ -821,8 +1146,8 @@ struct AgentFleetApplyArgs {
     #[arg(long, default_value = "10")]
     parallel: u32,
 
-    /// Per-host timeout (seconds)
-    #[arg(long, default_value = "30")]
+    /// Per-host timeout (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, default_value = "30", value_parser = pt_common::units::cli::seconds)]
     timeout: u64,
 
     /// Continue if a host fails
@@ -981,25 +1306,29 @@ struct AgentWatchArgs {
     #[arg(long, default_value = "medium")]
     threshold: String,
 
-    /// Check interval in seconds
-    #[arg(long, default_value = "60")]
+    /// Check interval in seconds (or a duration like 5m)
+    #[arg(long, default_value = "60", value_parser = pt_common::units::cli::seconds)]
     interval: u64,
 
-    /// Only consider processes older than threshold (seconds)
-    #[arg(long)]
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     min_age: Option<u64>,
 
     /// Run a single iteration and exit
     #[arg(long)]
     once: bool,
 
-    /// Goal: minimum memory available (GB) before alerting
-    #[arg(long)]
+    /// Goal: minimum memory available (GB, or a size like 4GiB) before alerting
+    #[arg(long, value_parser = pt_common::units::cli::gigabytes)]
     goal_memory_available_gb: Option<f64>,
 
     /// Goal: maximum 1-minute load average before alerting
     #[arg(long)]
     goal_load_max: Option<f64>,
+
+    /// Structured rules file (TOML or JSON; see `pt schema WatchRules`)
+    #[arg(long, value_name = "PATH")]
+    rules: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -1040,14 +1369,23 @@ struct AgentPlanArgs {
     #[arg(long)]
     deep: bool,
 
-    /// Only consider processes older than threshold (seconds)
-    #[arg(long)]
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     min_age: Option<u64>,
 
     /// Limit inference to a random sample of N processes (for testing)
     #[arg(long)]
     sample_size: Option<usize>,
 
+    /// Worker threads for per-process inference (0 = one per CPU)
+    #[arg(long, default_value = "0")]
+    jobs: usize,
+
+    /// Watch exec/exit events for N seconds around the scan to catch short-lived
+    /// processes (Linux process connector; requires CAP_NET_ADMIN)
+    #[arg(long, value_name = "SECONDS", value_parser = pt_common::units::cli::seconds)]
+    short_lived_window: Option<u64>,
+
     /// Include trajectory prediction analysis in output
     #[arg(long)]
     include_predictions: bool,
@@ -1075,6 +1413,11 @@ struct AgentPlanArgs {
     #[arg(long, help = "Resource recovery goal, e.g. 'free 4GB RAM'")]
     goal: Option<String>,
 
+    /// Project free memory, swap pressure, load and cgroup headroom after the
+    /// kill set is applied
+    #[arg(long)]
+    simulate: bool,
+
     /// Minimal JSON output (PIDs, scores, and recommendations only)
     #[arg(long)]
     minimal: bool,
@@ -1090,13 +1433,49 @@ struct AgentPlanArgs {
     /// Narrative output: human-readable prose summary
     #[arg(long, conflicts_with = "brief")]
     narrative: bool,
+
+    /// Edit the candidate list in $EDITOR (pick/review/drop per line) before
+    /// the plan is written
+    #[arg(long)]
+    edit: bool,
+
+    /// Apply a priors/policy overlay for this session only and tag it as
+    /// experimental (kept out of learning until `calibrate promote`)
+    #[arg(long, value_name = "FILE")]
+    experiment: Option<PathBuf>,
+
+    /// With --shadow, accumulate per-sample evidence across runs (SPRT) and
+    /// only recommend kill once the abandoned boundary is crossed
+    #[arg(long)]
+    sprt: bool,
+
+    /// Re-evaluate the evidence ledger of a .ptb bundle under local priors and
+    /// policy instead of scanning this host
+    #[arg(long, value_name = "FILE")]
+    replay_bundle: Option<PathBuf>,
+
+    /// Policy file for --replay-bundle (default: the local config)
+    #[arg(long, value_name = "FILE", requires = "replay_bundle")]
+    policy: Option<PathBuf>,
+
+    /// Passphrase for an encrypted bundle (or use PT_BUNDLE_PASSPHRASE)
+    #[arg(long, requires = "replay_bundle")]
+    passphrase: Option<String>,
 }
 
 #[derive(Args, Debug)]
 struct AgentExplainArgs {
-    /// Session ID (required)
-    #[arg(long)]
-    session: String,
+    /// Session ID (required unless --bundle is given)
+    #[arg(long, required_unless_present = "bundle")]
+    session: Option<String>,
+
+    /// Explain from the evidence ledger of a .ptb bundle instead of a live scan
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["session", "show_dependencies"])]
+    bundle: Option<PathBuf>,
+
+    /// Passphrase for an encrypted bundle (or use PT_BUNDLE_PASSPHRASE)
+    #[arg(long, requires = "bundle")]
+    passphrase: Option<String>,
 
     /// PIDs to explain
     #[arg(long, value_delimiter = ',')]
@@ -1137,10 +1516,10 @@ use pt_core::action::{
 };
 use pt_core::decision::{
     goal_optimizer::{
-        optimize_greedy, optimize_ilp, OptCandidate, OptimizationResult, ResourceGoal,
+        optimize_ilp, optimize_multi_objective, OptCandidate, OptimizationResult, ResourceGoal,
     },
     goal_parser::{parse_goal, Comparator, Goal, Metric, ResourceTarget},
-    ConstraintChecker, RobotCandidate, RuntimeRobotConstraints,
+    ConstraintChecker, ConstraintViolation, RobotCandidate, RuntimeRobotConstraints,
 };
 use pt_core::plan::{Plan, PlanAction};
 
@@ -1158,6 +1537,10 @@ struct AgentApplyArgs {
     #[arg(long, value_delimiter = ',')]
     targets: Vec<String>,
 
+    /// Act only on candidates in a group: user:<name>, cgroup:<path> or container:<id> (repeatable)
+    #[arg(long = "group")]
+    groups: Vec<String>,
+
     /// Skip safety gate confirmations
     #[arg(long)]
     yes: bool,
@@ -1166,20 +1549,20 @@ struct AgentApplyArgs {
     #[arg(long)]
     recommended: bool,
 
-    /// Only consider processes older than threshold (seconds)
-    #[arg(long)]
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     min_age: Option<u64>,
 
     /// Minimum posterior probability required (e.g. 0.99)
     #[arg(long)]
     min_posterior: Option<f64>,
 
-    /// Max blast radius per action (MB)
-    #[arg(long)]
+    /// Max blast radius per action (MB, or a size like 512MB, 2GiB)
+    #[arg(long, value_parser = pt_common::units::cli::megabytes)]
     max_blast_radius: Option<f64>,
 
-    /// Max total blast radius for the run (MB)
-    #[arg(long)]
+    /// Max total blast radius for the run (MB, or a size like 512MB, 2GiB)
+    #[arg(long, value_parser = pt_common::units::cli::megabytes)]
     max_total_blast_radius: Option<f64>,
 
     /// Max kills per run
@@ -1205,6 +1588,18 @@ struct AgentApplyArgs {
     /// Resume interrupted apply (skip already completed actions)
     #[arg(long)]
     resume: bool,
+
+    /// If the host health gate fails, wait for the host to recover instead of deferring
+    #[arg(long)]
+    wait_healthy: bool,
+
+    /// Max time to wait (seconds or a duration like 2m) with --wait-healthy (default: policy apply_health_gate.wait_timeout_seconds)
+    #[arg(long, requires = "wait_healthy", value_parser = pt_common::units::cli::seconds)]
+    health_timeout: Option<u64>,
+
+    /// Interactively review actions blocked by robot constraints and approve overrides one by one
+    #[arg(long)]
+    review_blocked: bool,
 }
 
 fn config_options(global: &GlobalOpts) -> ConfigOptions {
@@ -1221,13 +1616,21 @@ struct AgentVerifyArgs {
     #[arg(long)]
     session: String,
 
-    /// Wait for process termination with timeout in seconds (default: 0 = no wait)
-    #[arg(long, default_value = "0")]
+    /// Wait for process termination with timeout in seconds or a duration like 30s (default: 0 = no wait)
+    #[arg(long, default_value = "0", value_parser = pt_common::units::cli::seconds)]
     wait: u64,
 
     /// Check if killed processes have respawned
     #[arg(long)]
     check_respawn: bool,
+
+    /// Fold verified kill outcomes into the configured priors
+    #[arg(long)]
+    update_priors: bool,
+
+    /// PID whose kill drew a complaint (counts as useful; repeatable)
+    #[arg(long = "complaint", value_name = "PID", requires = "update_priors")]
+    complaints: Vec<u32>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1251,6 +1654,15 @@ impl FocusMode {
     }
 }
 
+fn parse_export_profile(value: &str) -> Result<pt_redact::ExportProfile, String> {
+    pt_redact::ExportProfile::parse_str(value).ok_or_else(|| {
+        format!(
+            "Invalid redaction profile: \"{}\". Valid values: minimal, safe, forensic",
+            value
+        )
+    })
+}
+
 fn parse_focus_mode(value: &str) -> Result<FocusMode, String> {
     match value.to_lowercase().as_str() {
         "all" => Ok(FocusMode::All),
@@ -1339,6 +1751,14 @@ struct AgentSessionsArgs {
     /// Remove sessions older than duration (e.g., "7d", "30d")
     #[arg(long, default_value = "7d")]
     older_than: String,
+
+    /// Show the global lock holder and wait queue instead of sessions
+    #[arg(long, conflicts_with_all = ["session", "cleanup"])]
+    locks: bool,
+
+    /// With --locks: break the lock if its holder process is gone
+    #[arg(long, requires = "locks")]
+    force: bool,
 }
 
 #[derive(Args, Debug)]
@@ -1369,6 +1789,37 @@ struct AgentInboxArgs {
     /// Show only unread items
     #[arg(long)]
     unread: bool,
+
+    /// List snoozed and pinned candidates instead of inbox items
+    #[arg(long)]
+    snoozed: bool,
+
+    /// Snooze a candidate by PID so it stops resurfacing in plan/watch/TUI
+    #[arg(long, value_name = "PID")]
+    snooze: Option<u32>,
+
+    /// Snooze duration (e.g. 30m, 4h, 2d; default: PT_SNOOZE_DURATION or 4h)
+    #[arg(long, value_name = "DURATION", requires = "snooze")]
+    snooze_for: Option<String>,
+
+    /// Pin the candidate indefinitely instead of snoozing for a duration
+    #[arg(long, requires = "snooze", conflicts_with = "snooze_for")]
+    pin: bool,
+
+    /// Remove a snooze/pin by fingerprint
+    #[arg(long, value_name = "FINGERPRINT")]
+    unsnooze: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct AgentSpareArgs {
+    /// PID of the process to spare
+    #[arg(long)]
+    pid: u32,
+
+    /// Write a user signature so this command is never suggested for killing again
+    #[arg(long)]
+    remember: bool,
 }
 
 #[derive(Args, Debug)]
@@ -1389,14 +1840,25 @@ struct AgentImportPriorsArgs {
     from: String,
 
     /// Merge with existing priors (weighted average)
-    #[arg(long, conflicts_with = "replace")]
+    #[arg(long, conflicts_with_all = ["replace", "shrink"])]
     merge: bool,
 
     /// Replace existing priors entirely
-    #[arg(long, conflicts_with = "merge")]
+    #[arg(long, conflicts_with_all = ["merge", "shrink"])]
     replace: bool,
 
+    /// Shrink existing priors toward the imported group-level priors
+    /// (partial pooling; requires --host-profile)
+    #[arg(long, requires = "host_profile")]
+    shrink: bool,
+
+    /// Pooling strength for --shrink, in pseudo-observations
+    /// (default: priors hierarchical.shrinkage_strength, then the profile default)
+    #[arg(long, requires = "shrink")]
+    shrink_strength: Option<f64>,
+
     /// Apply only to specific host profile
+    /// (with --shrink: laptop, ci_runner, k8s_node, server)
     #[arg(long)]
     host_profile: Option<String>,
 
@@ -1544,13 +2006,21 @@ enum TelemetryCommands {
     Status,
     /// Export telemetry data
     Export {
-        /// Output path
+        /// Output directory (one file per table)
         #[arg(short, long)]
         output: String,
 
-        /// Export format (parquet, csv, json)
-        #[arg(long, default_value = "parquet")]
-        format: String,
+        /// Export format (parquet, csv, json, jsonl)
+        #[arg(long = "export-format", default_value = "parquet")]
+        export_format: String,
+
+        /// Redaction profile: minimal, safe (default), forensic
+        #[arg(long, default_value = "safe")]
+        profile: String,
+
+        /// Tables to export (repeatable; default all)
+        #[arg(long = "table")]
+        tables: Vec<String>,
     },
     /// Prune old telemetry data
     Prune {
@@ -1599,12 +2069,12 @@ enum ShadowCommands {
 
 #[derive(Args, Debug, Clone)]
 struct ShadowStartArgs {
-    /// Interval between scans (seconds)
-    #[arg(long, default_value = "300")]
+    /// Interval between scans (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, default_value = "300", value_parser = pt_common::units::cli::seconds)]
     interval: u64,
 
-    /// Interval between deep scans (seconds, 0 disables)
-    #[arg(long, default_value = "3600")]
+    /// Interval between deep scans (seconds or a duration like 10m; 0 disables)
+    #[arg(long, default_value = "3600", value_parser = pt_common::units::cli::seconds)]
     deep_interval: u64,
 
     /// Number of iterations before exiting (0 = run forever)
@@ -1635,8 +2105,8 @@ struct ShadowStartArgs {
     #[arg(long)]
     deep: bool,
 
-    /// Only consider processes older than threshold (seconds)
-    #[arg(long)]
+    /// Only consider processes older than threshold (seconds, or a duration like 30s, 5m, 2h)
+    #[arg(long, value_parser = pt_common::units::cli::seconds)]
     min_age: Option<u64>,
 
     /// Limit inference to a random sample of N processes
@@ -1672,63 +2142,204 @@ struct ShadowReportArgs {
     /// Max observations to analyze (most recent first)
     #[arg(long)]
     limit: Option<usize>,
+
+    /// Render calibration diagnostics as an HTML page instead (html)
+    #[arg(long = "report-format")]
+    report_format: Option<String>,
 }
 
 #[derive(Args, Debug)]
-struct SchemaArgs {
-    /// Type name to generate schema for (e.g., Plan, DecisionOutcome)
-    #[arg(value_name = "TYPE")]
-    type_name: Option<String>,
+struct FeedbackArgs {
+    #[command(subcommand)]
+    command: FeedbackCommands,
+}
 
-    /// List all available schema types
-    #[arg(long, short)]
-    list: bool,
+#[derive(Subcommand, Debug)]
+enum FeedbackCommands {
+    /// Label a session's decision about a PID as wrong
+    Record(FeedbackRecordArgs),
+    /// List recorded feedback labels
+    List(FeedbackListArgs),
+}
 
-    /// Generate schemas for all types
-    #[arg(long, short)]
-    all: bool,
+#[derive(Args, Debug)]
+struct FeedbackRecordArgs {
+    /// Session the decision was made in
+    #[arg(long)]
+    session: String,
 
-    /// Output compact JSON (no pretty-printing)
+    /// PID the feedback is about
     #[arg(long)]
-    compact: bool,
+    pid: u32,
+
+    /// false-positive (the kill was wrong) or false-negative (the process was missed)
+    #[arg(long)]
+    kind: String,
+
+    /// Who is giving the feedback: user or agent
+    #[arg(long, default_value = "user")]
+    actor: String,
+
+    /// Free-form note stored with the label
+    #[arg(long)]
+    note: Option<String>,
 }
 
 #[derive(Args, Debug)]
-struct McpArgs {
-    /// Transport: stdio (default) for standard MCP integration
-    #[arg(long, default_value = "stdio")]
-    transport: String,
+struct FeedbackListArgs {
+    /// Only list feedback for this session
+    #[arg(long)]
+    session: Option<String>,
 }
 
 #[derive(Args, Debug)]
-struct UpdateArgs {
+struct CalibrateArgs {
     #[command(subcommand)]
-    command: UpdateCommands,
+    command: CalibrateCommands,
 }
 
 #[derive(Subcommand, Debug)]
-enum UpdateCommands {
-    /// Rollback to a previous version
-    Rollback {
-        /// Target version to rollback to (default: most recent backup)
-        target: Option<String>,
+enum CalibrateCommands {
+    /// Replay an experiment's sessions under the baseline and experiment configs
+    Compare(CalibrateCompareArgs),
+    /// Let an experiment's sessions feed learning and calibration
+    Promote(CalibratePromoteArgs),
+    /// Fit likelihood parameters from shadow observations (empirical Bayes)
+    Fit(CalibrateFitArgs),
+}
 
-        /// Force rollback without confirmation
-        #[arg(long)]
-        force: bool,
-    },
-    /// List available backup versions
-    ListBackups,
-    /// Show backup details
-    ShowBackup {
-        /// Version to inspect
-        target: String,
-    },
-    /// Verify a backup's integrity
-    VerifyBackup {
-        /// Version to verify (default: most recent)
-        target: Option<String>,
-    },
+#[derive(Args, Debug)]
+struct CalibrateCompareArgs {
+    /// Experiment name (as tagged by `agent plan --experiment`)
+    #[arg(long)]
+    experiment: String,
+
+    /// Max changed decisions to list
+    #[arg(long, default_value = "50")]
+    max_changes: usize,
+}
+
+#[derive(Args, Debug)]
+struct CalibratePromoteArgs {
+    /// Experiment name (as tagged by `agent plan --experiment`)
+    #[arg(long)]
+    experiment: String,
+}
+
+#[derive(Args, Debug)]
+struct CalibrateFitArgs {
+    /// Fit from shadow-mode observations (the only source so far)
+    #[arg(long)]
+    from_shadow: bool,
+
+    /// Read observations from a `shadow export` file (JSON or JSONL)
+    /// instead of the shadow store
+    #[arg(long, requires = "from_shadow")]
+    input: Option<PathBuf>,
+
+    /// Only use the newest N observations
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Where to write the proposed priors (default: priors.proposed.json in
+    /// the config directory)
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Report the fit without writing the proposed priors
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+struct SchemaArgs {
+    /// Type name to generate schema for (e.g., Plan, DecisionOutcome)
+    #[arg(value_name = "TYPE")]
+    type_name: Option<String>,
+
+    /// List all available schema types
+    #[arg(long, short)]
+    list: bool,
+
+    /// Generate schemas for all types
+    #[arg(long, short)]
+    all: bool,
+
+    /// With --all: write one `<TYPE>.schema.json` per type plus `index.json`
+    /// into `<DIR>/v<schema_version>/` instead of printing
+    #[arg(long, value_name = "DIR", requires = "all")]
+    out_dir: Option<PathBuf>,
+
+    /// Output compact JSON (no pretty-printing)
+    #[arg(long)]
+    compact: bool,
+
+    /// Compare current schemas against a baseline saved from `schema --all`
+    /// and fail on breaking changes
+    #[arg(long, value_name = "BASELINE", conflicts_with_all = ["type_name", "list", "all"])]
+    compat: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct McpArgs {
+    /// Transport: stdio (default) for standard MCP integration, or http for
+    /// HTTP + SSE (requires the `mcp-http` feature)
+    #[arg(long, default_value = "stdio", value_parser = ["stdio", "http"])]
+    transport: String,
+
+    /// Address to listen on for the http transport
+    #[arg(long, default_value = "127.0.0.1:8631")]
+    bind: String,
+
+    /// Bearer token required by the http transport (generated if unset)
+    #[arg(long, env = "PT_MCP_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:7070")]
+    listen: String,
+
+    /// Bearer token required on /api endpoints (generated if unset)
+    #[arg(long, env = "PT_SERVE_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// Export profile applied to responses: minimal, safe, forensic
+    #[arg(long, default_value = "safe")]
+    profile: String,
+}
+
+#[derive(Args, Debug)]
+struct UpdateArgs {
+    #[command(subcommand)]
+    command: UpdateCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum UpdateCommands {
+    /// Rollback to a previous version
+    Rollback {
+        /// Target version to rollback to (default: most recent backup)
+        target: Option<String>,
+
+        /// Force rollback without confirmation
+        #[arg(long)]
+        force: bool,
+    },
+    /// List available backup versions
+    ListBackups,
+    /// Show backup details
+    ShowBackup {
+        /// Version to inspect
+        target: String,
+    },
+    /// Verify a backup's integrity
+    VerifyBackup {
+        /// Version to verify (default: most recent)
+        target: Option<String>,
+    },
     /// Remove old backups (keep most recent N)
     PruneBackups {
         /// Number of backups to keep
@@ -1744,6 +2355,7 @@ struct CompletionsArgs {
     shell: clap_complete::Shell,
 }
 
+use pt_core::lock::GlobalLock;
 use pt_core::log_event;
 use pt_core::logging::{
     event_names, init_logging, LogConfig, LogContext, LogFormat, LogLevel, Stage,
@@ -1792,7 +2404,16 @@ fn main() {
     };
     init_logging(&log_config);
 
+    if cli.global.no_redact && cli.global.redaction_profile != pt_redact::ExportProfile::Forensic {
+        eprintln!("pt-core: --no-redact requires --redaction-profile forensic");
+        std::process::exit(ExitCode::ArgsError.as_i32());
+    }
+
     let exit_code = match cli.command {
+        _ if cli.global.continue_token.is_some() => run_continue(
+            &cli.global,
+            cli.global.continue_token.as_deref().unwrap_or_default(),
+        ),
         None => {
             // Default: run interactive mode
             run_interactive(
@@ -1808,13 +2429,20 @@ fn main() {
                     high_contrast: false,
                     reduce_motion: false,
                     accessible: false,
+                    resume: None,
+                    staged: false,
+                    stage_grace: 10,
                 },
             )
         }
         Some(Commands::Run(args)) => run_interactive(&cli.global, &args),
         Some(Commands::Scan(args)) => run_scan(&cli.global, &args),
         Some(Commands::DeepScan(args)) => run_deep_scan(&cli.global, &args),
+        Some(Commands::Top(args)) => run_top(&cli.global, &args),
         Some(Commands::Diff(args)) => run_diff(&cli.global, &args),
+        Some(Commands::Session(args)) => match &args.command {
+            SessionCommands::Reconcile(args) => run_session_reconcile(&cli.global, args),
+        },
         Some(Commands::Query(args)) => run_query(&cli.global, &args),
         Some(Commands::Bundle(args)) => run_bundle(&cli.global, &args),
         Some(Commands::Report(args)) => run_report(&cli.global, &args),
@@ -1826,11 +2454,15 @@ fn main() {
         Some(Commands::Daemon(args)) => run_daemon(&cli.global, &args),
         Some(Commands::Telemetry(args)) => run_telemetry(&cli.global, &args),
         Some(Commands::Shadow(args)) => run_shadow(&cli.global, &args),
+        Some(Commands::Calibrate(args)) => run_calibrate(&cli.global, &args),
+        Some(Commands::Feedback(args)) => run_feedback(&cli.global, &args),
         Some(Commands::Signature(args)) => {
             pt_core::signature_cli::run_signature(&cli.global.format, &args)
         }
+        Some(Commands::Redact(args)) => run_redact(&cli.global, &args),
         Some(Commands::Schema(args)) => run_schema(&cli.global, &args),
-        Some(Commands::Mcp(args)) => run_mcp(&args),
+        Some(Commands::Mcp(args)) => run_mcp(&cli.global, &args),
+        Some(Commands::Serve(args)) => run_serve(&args),
         Some(Commands::Update(args)) => run_update(&cli.global, &args),
         Some(Commands::Completions(args)) => {
             clap_complete::generate(
@@ -1931,7 +2563,12 @@ mod output_format_tests {
 // ============================================================================
 
 fn run_interactive(global: &GlobalOpts, args: &RunArgs) -> ExitCode {
-    let _lock = match acquire_global_lock(global, "run") {
+    let access = if global.dry_run || global.shadow {
+        LockAccess::ReadOnly
+    } else {
+        LockAccess::Exclusive
+    };
+    let _lock = match acquire_global_lock(global, "run", access) {
         Ok(lock) => lock,
         Err(code) => return code,
     };
@@ -1961,21 +2598,56 @@ fn run_interactive(global: &GlobalOpts, args: &RunArgs) -> ExitCode {
 #[cfg(feature = "ui")]
 fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String> {
     let store = SessionStore::from_env().map_err(|e| format!("session store error: {}", e))?;
-    let session_id = SessionId::new();
-    let manifest = SessionManifest::new(&session_id, None, SessionMode::Interactive, None);
-    let handle = store
-        .create(&manifest)
-        .map_err(|e| format!("failed to create session: {}", e))?;
+    let (session_id, handle) = match args.resume.as_deref() {
+        Some(raw) => open_resumable_session(&store, raw)?,
+        None => {
+            let session_id = SessionId::new();
+            let manifest = SessionManifest::new(&session_id, None, SessionMode::Interactive, None);
+            let handle = store
+                .create(&manifest)
+                .map_err(|e| format!("failed to create session: {}", e))?;
 
-    let ctx = SessionContext::new(
-        &session_id,
-        pt_core::logging::get_host_id(),
-        pt_core::logging::generate_run_id(),
-        None,
-    );
-    handle
-        .write_context(&ctx)
-        .map_err(|e| format!("failed to write context.json: {}", e))?;
+            let ctx = SessionContext::new(
+                &session_id,
+                pt_core::logging::get_host_id(),
+                pt_core::logging::generate_run_id(),
+                None,
+            );
+            handle
+                .write_context(&ctx)
+                .map_err(|e| format!("failed to write context.json: {}", e))?;
+            (session_id, handle)
+        }
+    };
+
+    // When resuming, reload what the interrupted run had already persisted.
+    // Sessions interrupted before the snapshot was written fall back to a
+    // full scan within the same session.
+    let resumed = args.resume.is_some();
+    let artifacts = if resumed {
+        list_artifacts(&handle)
+    } else {
+        Vec::new()
+    };
+    let has_artifact = |name: &str| artifacts.iter().any(|a| a == name);
+    let resume_identities = if has_artifact("inventory") && has_artifact("inference") {
+        Some(load_resume_identities(&handle)?)
+    } else {
+        None
+    };
+    let tui_state = if has_artifact("tui_state") {
+        Some(
+            load_tui_state(&handle)
+                .map_err(|e| format!("failed to load TUI state: {}", e))?
+                .payload,
+        )
+    } else {
+        None
+    };
+    let goal = args
+        .goal
+        .clone()
+        .or_else(|| tui_state.as_ref().and_then(|state| state.goal.clone()));
 
     let _ = handle.update_state(SessionState::Scanning);
 
@@ -1992,7 +2664,47 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
         plan_candidates,
         goal_summary,
         goal_order,
-    } = build_tui_data_from_live_scan(global, args, &priors, &policy)?;
+        inventory,
+        inference,
+        total_scanned,
+        protected_filtered,
+    } = build_tui_data_from_live_scan(
+        global,
+        args,
+        &priors,
+        &policy,
+        goal.as_deref(),
+        resume_identities.as_ref(),
+    )?;
+
+    let redactor = output_redactor(global).map_err(|e| e.to_string())?;
+    let inventory: Vec<PersistedProcess> = inventory
+        .into_iter()
+        .map(|record| PersistedProcess {
+            cmd: redactor.cmdline(&record.cmd),
+            ..record
+        })
+        .collect();
+    let host_id = pt_core::logging::get_host_id();
+    let inv_artifact = InventoryArtifact {
+        total_system_processes: total_scanned as u64,
+        protected_filtered: protected_filtered as u64,
+        record_count: inventory.len(),
+        records: inventory,
+    };
+    if let Err(e) = persist_inventory(&handle, &session_id.0, &host_id, inv_artifact) {
+        eprintln!("run: warning: failed to persist inventory artifact: {}", e);
+    }
+    if let Err(e) = redactor.persist_dictionary(&handle.dir) {
+        eprintln!("run: warning: failed to save redaction dictionary: {}", e);
+    }
+    let inf_artifact = InferenceArtifact {
+        candidate_count: inference.len(),
+        candidates: inference,
+    };
+    if let Err(e) = persist_inference(&handle, &session_id.0, &host_id, inf_artifact) {
+        eprintln!("run: warning: failed to persist inference artifact: {}", e);
+    }
 
     let _ = handle.update_state(SessionState::Planned);
 
@@ -2034,9 +2746,29 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
     if let Some(lines) = goal_summary {
         app.set_goal_summary(lines);
     }
-    app.process_table.select_recommended();
+    match &tui_state {
+        Some(state) => {
+            // Restore only selections whose identity still matches a candidate.
+            for selection in &state.selected {
+                let still_valid = plan_candidates
+                    .get(&selection.pid)
+                    .is_some_and(|candidate| {
+                        candidate.identity.start_id.to_string() == selection.start_id
+                    });
+                if still_valid {
+                    app.process_table.selected.insert(selection.pid);
+                }
+            }
+        }
+        None => app.process_table.select_recommended(),
+    }
     app.set_status(format!(
-        "Session {} • {} candidates",
+        "{} {} • {} candidates",
+        if resumed {
+            "Resumed session"
+        } else {
+            "Session"
+        },
         session_id.0,
         app.process_table.rows.len()
     ));
@@ -2053,7 +2785,7 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
         let timeout_r = global.timeout;
         let deep_r = args.deep;
         let min_age_r = args.min_age;
-        let goal_r = args.goal.clone();
+        let goal_r = goal.clone();
         let policy_scan_r = policy.clone();
 
         let refresh_fn: Arc<dyn Fn() -> Result<Vec<ProcessRow>, String> + Send + Sync> =
@@ -2063,6 +2795,8 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
                     include_kernel_threads: false,
                     timeout: timeout_r.map(std::time::Duration::from_secs),
                     progress: None,
+                    incremental: None,
+                    io_rate_window: Some(pt_core::collect::DEFAULT_IO_RATE_WINDOW),
                 };
                 let scan_result =
                     quick_scan(&scan_options).map_err(|e| format!("scan failed: {}", e))?;
@@ -2102,10 +2836,16 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
                 let candidates = plan_cache_e
                     .lock()
                     .map_err(|_| "plan cache lock poisoned".to_string())?;
-                let plan =
+                let mut plan =
                     build_plan_from_selection(&session_id_e, &policy_e, &selected, &candidates)?;
                 drop(candidates); // release lock before I/O
 
+                // Plan tree-level kills against the current process table
+                if let Ok(scan) = quick_scan(&QuickScanOptions::default()) {
+                    let tree = pt_core::plan::tree::ProcessTree::from_records(&scan.processes);
+                    pt_core::plan::tree::apply_cascade(&mut plan, &tree, &policy_e.cascade);
+                }
+
                 if plan.actions.is_empty() {
                     return Err("no actions to apply for selected processes".to_string());
                 }
@@ -2149,8 +2889,132 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
                 }
             });
 
+        // Build snooze closure (keyed by owner + command fingerprint)
+        let plan_cache_s = Arc::clone(&plan_candidates);
+        let snooze_fn: Arc<dyn Fn(u32, String) -> Result<String, String> + Send + Sync> =
+            Arc::new(move |pid: u32, command: String| {
+                let uid = plan_cache_s
+                    .lock()
+                    .map_err(|_| "plan cache lock poisoned".to_string())?
+                    .get(&pid)
+                    .map(|candidate| candidate.identity.uid)
+                    .ok_or_else(|| format!("PID {} is no longer a candidate", pid))?;
+                let store = pt_core::snooze::SnoozeStore::from_env().map_err(|e| e.to_string())?;
+                let entry = pt_core::snooze::SnoozeEntry::snooze(
+                    uid,
+                    pid,
+                    &command,
+                    pt_core::snooze::default_snooze_duration(),
+                );
+                let until = entry.until.clone().unwrap_or_default();
+                store.upsert(entry).map_err(|e| e.to_string())?;
+                Ok(format!("Snoozed PID {} until {}", pid, until))
+            });
+
+        // Build remember closure (writes a protecting user signature)
+        let remember_timeout = global.timeout;
+        let remember_fn: Arc<dyn Fn(u32, String) -> Result<String, String> + Send + Sync> =
+            Arc::new(move |pid: u32, _command: String| {
+                let name = remember_spared_pid(pid, remember_timeout)?;
+                Ok(format!(
+                    "PID {} will not be suggested again ({})",
+                    pid, name
+                ))
+            });
+
+        // Build selection closure (persists TUI state so the run can be resumed)
+        let plan_cache_t = Arc::clone(&plan_candidates);
+        let handle_t = handle.clone();
+        let session_id_t = session_id.0.clone();
+        let goal_t = goal.clone();
+        let selection_fn: Arc<dyn Fn(Vec<u32>) + Send + Sync> =
+            Arc::new(move |selected: Vec<u32>| {
+                let Ok(candidates) = plan_cache_t.lock() else {
+                    return;
+                };
+                let selected = selected
+                    .iter()
+                    .filter_map(|pid| {
+                        candidates.get(pid).map(|candidate| PersistedSelection {
+                            pid: *pid,
+                            start_id: candidate.identity.start_id.to_string(),
+                        })
+                    })
+                    .collect();
+                drop(candidates);
+                let state = TuiStateArtifact {
+                    selected,
+                    goal: goal_t.clone(),
+                };
+                if let Err(err) = persist_tui_state(&handle_t, &session_id_t, &host_id, state) {
+                    tracing::warn!(error = %err, "failed to persist TUI state");
+                }
+            });
+
+        // Build telemetry closure (CPU/RSS for the listed PIDs only)
+        let sample_fn: Arc<dyn Fn(Vec<u32>) -> Vec<TelemetrySample> + Send + Sync> =
+            Arc::new(move |pids: Vec<u32>| {
+                if pids.is_empty() {
+                    return Vec::new();
+                }
+                let scan_options = QuickScanOptions {
+                    pids,
+                    include_kernel_threads: false,
+                    timeout: Some(std::time::Duration::from_secs(2)),
+                    progress: None,
+                    incremental: None,
+                    io_rate_window: None,
+                };
+                quick_scan(&scan_options)
+                    .map(|result| {
+                        result
+                            .processes
+                            .iter()
+                            .map(|p| TelemetrySample {
+                                pid: p.pid.0,
+                                cpu_percent: p.cpu_percent,
+                                rss_bytes: p.rss_bytes,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            });
+
+        let mut initial_selection = app.process_table.get_selected();
+        initial_selection.sort_unstable();
+        selection_fn(initial_selection);
+
         app.set_refresh_op(refresh_fn);
         app.set_execute_op(execute_fn);
+        app.set_snooze_op(snooze_fn);
+        app.set_remember_op(remember_fn);
+        app.set_selection_op(selection_fn);
+        app.set_sample_op(sample_fn);
+
+        // Staged kills stop real processes, so they stay off in dry-run/shadow
+        if args.staged && !(global.dry_run || global.shadow) {
+            #[cfg(target_os = "linux")]
+            {
+                let plan_cache_g = Arc::clone(&plan_candidates);
+                let stage_fn: Arc<
+                    dyn Fn(Vec<u32>, StageSignal) -> Result<Vec<u32>, String> + Send + Sync,
+                > = Arc::new(move |pids: Vec<u32>, signal: StageSignal| {
+                    let targets: Vec<ProcessIdentity> = {
+                        let candidates = plan_cache_g
+                            .lock()
+                            .map_err(|_| "plan cache lock poisoned".to_string())?;
+                        pids.iter()
+                            .filter_map(|pid| candidates.get(pid))
+                            .map(|candidate| candidate.identity.clone())
+                            .collect()
+                    };
+                    Ok(signal_staged_targets(&targets, signal))
+                });
+                app.set_stage_op(stage_fn, std::time::Duration::from_secs(args.stage_grace));
+            }
+            #[cfg(not(target_os = "linux"))]
+            eprintln!("run: --staged requires Linux; actions will run immediately");
+        }
 
         let program_config = if args.inline {
             ftui::ProgramConfig::inline(compute_inline_ui_height())
@@ -2170,6 +3034,64 @@ fn run_interactive_tui(global: &GlobalOpts, args: &RunArgs) -> Result<(), String
     Ok(())
 }
 
+/// Open an existing interactive session for `run --resume`.
+#[cfg(feature = "ui")]
+fn open_resumable_session(
+    store: &SessionStore,
+    raw: &str,
+) -> Result<(SessionId, SessionHandle), String> {
+    let session_id =
+        SessionId::parse(raw).ok_or_else(|| format!("invalid session ID '{}'", raw))?;
+    let handle = store
+        .open(&session_id)
+        .map_err(|e| format!("failed to open session {}: {}", raw, e))?;
+    let manifest = handle
+        .read_manifest()
+        .map_err(|e| format!("failed to read manifest for {}: {}", raw, e))?;
+    if manifest.mode != SessionMode::Interactive {
+        return Err(format!("session {} was not created by `run`", raw));
+    }
+    if matches!(
+        manifest.state,
+        SessionState::Completed
+            | SessionState::Cancelled
+            | SessionState::Failed
+            | SessionState::Archived
+    ) {
+        let state = format!("{:?}", manifest.state).to_lowercase();
+        return Err(format!(
+            "session {} is {} and cannot be resumed",
+            raw, state
+        ));
+    }
+    Ok((session_id, handle))
+}
+
+/// Load the persisted candidate identities (pid -> start_id) of a session.
+///
+/// Only candidates present in both the inventory and inference snapshots are
+/// returned; both artifacts are integrity-checked.
+#[cfg(feature = "ui")]
+fn load_resume_identities(handle: &SessionHandle) -> Result<HashMap<u32, String>, String> {
+    let inventory =
+        load_inventory(handle).map_err(|e| format!("failed to load inventory: {}", e))?;
+    let inference =
+        load_inference(handle).map_err(|e| format!("failed to load inference: {}", e))?;
+    let inventoried: HashMap<u32, String> = inventory
+        .payload
+        .records
+        .into_iter()
+        .map(|record| (record.pid, record.start_id))
+        .collect();
+    Ok(inference
+        .payload
+        .candidates
+        .into_iter()
+        .filter(|candidate| inventoried.get(&candidate.pid) == Some(&candidate.start_id))
+        .map(|candidate| (candidate.pid, candidate.start_id))
+        .collect())
+}
+
 #[cfg(feature = "ui")]
 fn compute_inline_ui_height() -> u16 {
     // Prefer a fixed bottom-anchored UI region, leaving some scrollback space above.
@@ -2199,6 +3121,12 @@ struct TuiBuildOutput {
     plan_candidates: HashMap<u32, PlanCandidateInput>,
     goal_summary: Option<Vec<String>>,
     goal_order: Option<HashMap<u32, usize>>,
+    /// Snapshot records for the candidates shown, persisted for resume.
+    inventory: Vec<PersistedProcess>,
+    inference: Vec<PersistedInference>,
+    /// Scan totals; filled in by the live-scan wrapper.
+    total_scanned: usize,
+    protected_filtered: usize,
 }
 
 #[cfg(feature = "ui")]
@@ -2207,14 +3135,26 @@ fn build_tui_data_from_live_scan(
     args: &RunArgs,
     priors: &Priors,
     policy: &pt_core::config::Policy,
+    goal: Option<&str>,
+    resume: Option<&HashMap<u32, String>>,
 ) -> Result<TuiBuildOutput, String> {
     let scan_options = QuickScanOptions {
-        pids: vec![],
+        pids: resume
+            .map(|identities| identities.keys().copied().collect())
+            .unwrap_or_default(),
         include_kernel_threads: false,
         timeout: global.timeout.map(std::time::Duration::from_secs),
         progress: None,
+        incremental: None,
+        io_rate_window: Some(pt_core::collect::DEFAULT_IO_RATE_WINDOW),
     };
-    let scan_result = quick_scan(&scan_options).map_err(|e| format!("scan failed: {}", e))?;
+    let mut scan_result = quick_scan(&scan_options).map_err(|e| format!("scan failed: {}", e))?;
+    if let Some(identities) = resume {
+        // Drop processes that exited or whose PID was reused since the snapshot.
+        scan_result
+            .processes
+            .retain(|proc| identities.get(&proc.pid.0) == Some(&proc.start_id.to_string()));
+    }
 
     let deep_signals = if args.deep {
         collect_deep_signals(&scan_result.processes)
@@ -2226,14 +3166,17 @@ fn build_tui_data_from_live_scan(
         .map_err(|e| format!("protected filter error: {}", e))?;
     let filter_result = protected_filter.filter_scan_result(&scan_result);
 
-    Ok(build_tui_rows(
+    let mut output = build_tui_rows(
         &filter_result.passed,
         args.min_age,
         deep_signals.as_ref(),
         priors,
         policy,
-        args.goal.as_deref(),
-    ))
+        goal,
+    );
+    output.total_scanned = filter_result.total_before;
+    output.protected_filtered = filter_result.filtered.len();
+    Ok(output)
 }
 
 #[cfg(feature = "ui")]
@@ -2257,6 +3200,10 @@ fn build_plan_from_selection(
             process_state: Some(candidate.process_state),
             parent_identity: None,
             d_state_diagnostics: None,
+            impact: None,
+            systemd_unit: pt_core::plan::ServiceMembership::detect(*pid),
+            zombie_reap: None,
+            graceful_stop: None,
         });
     }
 
@@ -2295,20 +3242,23 @@ fn execute_plan_actions(
     #[cfg(target_os = "linux")]
     {
         use pt_core::action::{
-            ActionExecutor, CompositeActionRunner, LiveIdentityProvider, LivePreCheckConfig,
-            LivePreCheckProvider,
+            ActionExecutor, CompositeActionRunner, ContainerActionConfig, CpuThrottleConfig,
+            LiveIdentityProvider, LivePreCheckConfig, LivePreCheckProvider,
         };
         let action_dir = handle.dir.join("action");
         std::fs::create_dir_all(&action_dir).map_err(|e| format!("create action dir: {}", e))?;
         let lock_path = action_dir.join("lock");
-        let runner = CompositeActionRunner::with_defaults();
+        let runner = CompositeActionRunner::with_defaults()
+            .with_throttle_config(CpuThrottleConfig::from(&policy.throttle_limits))
+            .with_container_config(ContainerActionConfig::from(&policy.container_actions));
         let identity_provider = LiveIdentityProvider::new();
         let pre_checks =
             LivePreCheckProvider::new(Some(&policy.guardrails), LivePreCheckConfig::default())
                 .unwrap_or_else(|_| LivePreCheckProvider::with_defaults());
 
         let executor = ActionExecutor::new(&runner, &identity_provider, lock_path)
-            .with_pre_check_provider(&pre_checks);
+            .with_pre_check_provider(&pre_checks)
+            .with_checkpoint(action_dir.join("checkpoint.json"));
         executor
             .execute_plan(plan)
             .map_err(|e| format!("execute plan: {}", e))
@@ -2411,6 +3361,7 @@ fn action_status_label(status: &pt_core::action::ActionStatus) -> &'static str {
         ActionStatus::Failed => "failed",
         ActionStatus::Skipped => "skipped",
         ActionStatus::PreCheckBlocked { .. } => "precheck_blocked",
+        ActionStatus::StageHalted => "stage_halted",
     }
 }
 
@@ -2425,6 +3376,9 @@ fn precheck_label(check: &pt_core::plan::PreCheck) -> &'static str {
         PreCheck::CheckSupervisor => "check_supervisor",
         PreCheck::CheckAgentSupervision => "check_agent_supervision",
         PreCheck::VerifyProcessState => "verify_process_state",
+        PreCheck::CheckFreezeScope => "check_freeze_scope",
+        PreCheck::VerifyUnitMapping => "verify_unit_mapping",
+        PreCheck::CheckForegroundWork => "check_foreground_work",
     }
 }
 
@@ -2433,6 +3387,8 @@ fn precheck_label(check: &pt_core::plan::PreCheck) -> &'static str {
 struct DeepSignals {
     net_active: Option<bool>,
     io_active: Option<bool>,
+    gpu: Option<pt_core::inference::GpuEvidence>,
+    fd_count: Option<u32>,
 }
 
 #[cfg(feature = "ui")]
@@ -2447,6 +3403,7 @@ fn collect_deep_signals(processes: &[ProcessRecord]) -> Option<HashMap<u32, Deep
             skip_inaccessible: true,
             include_environ: false,
             progress: None,
+            budget: None,
         };
         let result = match deep_scan(&options) {
             Ok(r) => r,
@@ -2478,13 +3435,42 @@ fn collect_deep_signals(processes: &[ProcessRecord]) -> Option<HashMap<u32, Deep
                 DeepSignals {
                     net_active,
                     io_active,
+                    gpu: record.gpu.as_ref().map(|g| g.to_evidence()),
+                    fd_count: record.fd.as_ref().map(|fd| fd.count as u32),
                 },
             );
         }
         Some(map)
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(target_os = "macos")]
     {
+        use pt_core::collect::{native_disk_io, native_fd_summary};
+
+        // libproc answers these per PID without spawning lsof.
+        let map = processes
+            .iter()
+            .map(|p| {
+                let pid = p.pid.0;
+                let fds = native_fd_summary(pid);
+                let net_active = fds.as_ref().map(|fds| fds.sockets > 0);
+                let io_active =
+                    native_disk_io(pid).map(|io| io.read_bytes > 0 || io.write_bytes > 0);
+                (
+                    pid,
+                    DeepSignals {
+                        net_active,
+                        io_active,
+                        gpu: None,
+                        fd_count: fds.map(|fds| fds.total as u32),
+                    },
+                )
+            })
+            .collect();
+        Some(map)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = processes;
         eprintln!("run: deep scan not supported on this platform; using quick scan");
         None
     }
@@ -2519,10 +3505,37 @@ fn build_tui_rows(
     };
 
     let feasibility = ActionFeasibility::allow_all();
+    let snoozes = load_active_snoozes();
+    // User signatures include commands remembered as spared
+    let mut signature_db = SignatureDatabase::with_defaults();
+    if let Some(pack) = pt_core::signature_cli::load_signature_pack() {
+        for signature in pack.signatures {
+            if let Err(err) = signature_db.add(signature) {
+                tracing::warn!("skipping invalid signature pack entry: {}", err);
+            }
+        }
+    }
+    if let Some(user_schema) = pt_core::signature_cli::load_user_signatures() {
+        for signature in user_schema.signatures {
+            if let Err(err) = signature_db.add(signature) {
+                tracing::warn!("skipping invalid user signature: {}", err);
+            }
+        }
+    }
     let mut rows = Vec::new();
     let mut plan_candidates = HashMap::new();
     let mut goal_candidates: HashMap<u32, serde_json::Value> = HashMap::new();
+    let mut persisted: HashMap<u32, (PersistedProcess, PersistedInference)> = HashMap::new();
     let mut cpu_total = 0.0;
+    // Blast radius from the process tree only; fd and socket scans would
+    // slow every refresh
+    let mut blast_input = pt_core::supervision::blast_radius::BlastRadiusInput {
+        process_table: processes
+            .iter()
+            .map(|p| (p.pid.0, (p.comm.clone(), p.ppid.0)))
+            .collect(),
+        ..Default::default()
+    };
 
     for proc in processes {
         if proc.pid.0 == 0 || proc.pid.0 == 1 {
@@ -2535,6 +3548,13 @@ fn build_tui_rows(
         }
 
         let deep = deep_signals.and_then(|m| m.get(&proc.pid.0).copied());
+        // A sampled rate supersedes the cumulative counters behind io_active.
+        let io_bucket = proc.io_rate.and_then(|rate| {
+            priors
+                .io_rate
+                .as_ref()
+                .map(|io| io.bucket(rate.total_bytes_per_sec()))
+        });
         let evidence = Evidence {
             cpu: Some(CpuEvidence::Fraction {
                 occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
@@ -2543,13 +3563,38 @@ fn build_tui_rows(
             orphan: Some(proc.is_orphan()),
             tty: Some(proc.has_tty()),
             net: deep.and_then(|d| d.net_active),
-            io_active: deep.and_then(|d| d.io_active),
+            io_active: if io_bucket.is_some() {
+                None
+            } else {
+                deep.and_then(|d| d.io_active)
+            },
             state_flag: state_to_flag(proc.state),
             command_category: None,
+            gpu: deep.and_then(|d| d.gpu),
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: io_bucket,
+            fd_leak: None,
+            session_attachment: None,
         };
 
-        let posterior_result = match compute_posterior(priors, &evidence) {
-            Ok(r) => r,
+        let mut match_ctx = ProcessMatchContext::with_comm(&proc.comm);
+        if !proc.cmd.is_empty() {
+            match_ctx = match_ctx.cmdline(&proc.cmd);
+        }
+        let signature_match = signature_db.best_match(&match_ctx);
+        let prior_context = PriorContext {
+            global_priors: priors,
+            signature_match: signature_match
+                .as_ref()
+                .filter(|m| m.signature.rollout.applies_priors()),
+            category_defaults: None,
+            user_overrides: None,
+        };
+        let posterior_result = match compute_posterior_with_overrides(&prior_context, &evidence) {
+            Ok((r, _)) => r,
             Err(_) => continue,
         };
         let decision_outcome =
@@ -2577,8 +3622,8 @@ fn build_tui_rows(
         };
 
         let score = (max_posterior * 100.0).round() as u32;
-        let runtime = format_duration_human(proc.elapsed.as_secs());
-        let memory = format_memory_human(proc.rss_bytes);
+        let runtime = format_duration(proc.elapsed);
+        let memory = format_size(proc.rss_bytes);
         let galaxy_brain = render_galaxy_brain(
             &posterior_result,
             &ledger,
@@ -2607,6 +3652,15 @@ fn build_tui_rows(
             },
         );
 
+        let snooze = snoozes.get(proc.uid, &proc.cmd);
+        let why_summary = match snooze.map(|entry| entry.until.as_deref()) {
+            Some(Some(until)) => format!("Snoozed until {} • {}", until, ledger.why_summary),
+            Some(None) => format!("Pinned • {}", ledger.why_summary),
+            None => ledger.why_summary.clone(),
+        };
+        blast_input.target_pid = proc.pid.0;
+        blast_input.target_comm = proc.comm.clone();
+        let blast_radius = pt_core::supervision::blast_radius::compute_blast_radius(&blast_input);
         rows.push(ProcessRow {
             pid: proc.pid.0,
             score,
@@ -2614,12 +3668,29 @@ fn build_tui_rows(
             runtime,
             memory,
             command: proc.cmd.clone(),
-            selected: classification == "KILL",
+            selected: classification == "KILL" && snooze.is_none(),
             galaxy_brain: Some(galaxy_brain),
-            why_summary: Some(ledger.why_summary.clone()),
+            why_summary: Some(why_summary),
             top_evidence: ledger.top_evidence.clone(),
             confidence: Some(ledger.confidence.label().to_string()),
             plan_preview: Vec::new(),
+            blast_radius: Some(blast_radius.summary),
+            io_rate: proc.io_rate.map(|rate| {
+                format!(
+                    "read {}/s, write {}/s",
+                    format_size(rate.read_bytes_per_sec as u64),
+                    format_size(rate.write_bytes_per_sec as u64)
+                )
+            }),
+            user: Some(proc.user.clone()),
+            category: Some(
+                pt_core::supervision::narrative::classify_role(&proc.comm, false, false)
+                    .label()
+                    .to_string(),
+            ),
+            runtime_secs: proc.elapsed.as_secs(),
+            memory_bytes: proc.rss_bytes,
+            snoozed: snooze.is_some(),
         });
 
         cpu_total += proc.cpu_percent;
@@ -2641,20 +3712,52 @@ fn build_tui_rows(
             _ => "review",
         };
 
-        let memory_mb = proc.rss_bytes / (1024 * 1024);
-        goal_candidates.insert(
+        persisted.insert(
+            proc.pid.0,
+            (
+                PersistedProcess {
+                    pid: proc.pid.0,
+                    ppid: proc.ppid.0,
+                    uid: proc.uid,
+                    start_id: proc.start_id.to_string(),
+                    comm: proc.comm.clone(),
+                    cmd: proc.cmd.clone(),
+                    state: proc.state.to_string(),
+                    start_time_unix: proc.start_time_unix,
+                    elapsed_secs: proc.elapsed.as_secs(),
+                    identity_quality: "QuickScan".to_string(),
+                },
+                PersistedInference {
+                    pid: proc.pid.0,
+                    start_id: proc.start_id.to_string(),
+                    classification: ledger.classification.label().to_string(),
+                    posterior_useful: posterior_result.posterior.useful,
+                    posterior_useful_bad: posterior_result.posterior.useful_bad,
+                    posterior_abandoned: posterior_result.posterior.abandoned,
+                    posterior_zombie: posterior_result.posterior.zombie,
+                    confidence: ledger.confidence.label().to_string(),
+                    recommended_action: recommended_action.to_string(),
+                    score,
+                },
+            ),
+        );
+
+        let memory_mb = proc.rss_bytes / (1024 * 1024);
+        goal_candidates.insert(
             proc.pid.0,
             serde_json::json!({
                 "pid": proc.pid.0,
                 "recommended_action": recommended_action,
                 "memory_mb": memory_mb,
                 "cpu_percent": proc.cpu_percent,
+                "fd_count": deep.and_then(|d| d.fd_count),
                 "expected_loss": expected_loss_entries,
             }),
         );
     }
 
-    rows.sort_by_key(|r| std::cmp::Reverse(r.score));
+    // Snoozed rows sink below everything else, then by score descending.
+    rows.sort_by_key(|r| (r.snoozed, std::cmp::Reverse(r.score)));
     rows.truncate(MAX_CANDIDATES);
 
     let mut goal_summary: Option<Vec<String>> = None;
@@ -2671,13 +3774,17 @@ fn build_tui_rows(
                 }
 
                 if !candidates_for_goal.is_empty() {
+                    let pids: Vec<u32> = processes.iter().map(|p| p.pid.0).collect();
+                    let (_, port_warnings) =
+                        resolve_goal_ports(&parsed, &mut candidates_for_goal, &pids);
                     match build_goal_plan_from_candidates(
                         goal_str,
                         &parsed,
-                        cpu_total,
+                        &GoalBaseline::with_cpu(cpu_total),
                         &candidates_for_goal,
                     ) {
-                        Ok(output) => {
+                        Ok(mut output) => {
+                            output.warnings.extend(port_warnings);
                             let mut lines = Vec::new();
                             lines.push(format!("Goal: {}", goal_str));
                             lines.push(format!(
@@ -2707,6 +3814,9 @@ fn build_tui_rows(
                                 output.result.total_loss,
                                 output.selected_pids.len()
                             ));
+                            for alt in output.result.alternatives.iter().take(3) {
+                                lines.push(format!("Alternative: {}", alt.description));
+                            }
                             if !output.warnings.is_empty() {
                                 lines.push(format!("Warnings: {}", output.warnings.join(", ")));
                             }
@@ -2753,16 +3863,27 @@ fn build_tui_rows(
         }
     }
 
+    let (inventory, inference) = rows
+        .iter()
+        .filter_map(|row| persisted.remove(&row.pid))
+        .unzip();
+
     TuiBuildOutput {
         rows,
         plan_candidates,
         goal_summary,
         goal_order,
+        inventory,
+        inference,
+        total_scanned: processes.len(),
+        protected_filtered: 0,
     }
 }
 
 #[cfg(target_os = "linux")]
-use pt_core::collect::{parse_fd, parse_proc_net_tcp, parse_proc_net_udp, NetworkSnapshot};
+use pt_core::collect::{
+    parse_fd, parse_proc_net_tcp, parse_proc_net_udp, parse_smaps_rollup, NetworkSnapshot,
+};
 use pt_core::collect::{quick_scan, ProcessRecord, QuickScanOptions, ScanResult};
 use pt_core::decision::goal_progress::{
     self, ActionOutcome as GoalActionOutcome, GoalMetric, GoalProgressReport, MetricSnapshot,
@@ -2773,10 +3894,15 @@ use pt_core::decision::{
     LoadSignals,
 };
 use pt_core::inference::{
-    compute_posterior, compute_posterior_with_overrides, try_signature_fast_path, CpuEvidence,
-    Evidence, EvidenceLedger, FastPathConfig, FastPathSkipReason, PriorContext,
+    compute_posterior, compute_posterior_with_overrides, try_signature_fast_path,
+    BehaviorBaselineStore, BehaviorDeviation, BehaviorSample, CpuEvidence, Evidence,
+    EvidenceLedger, FastPathConfig, FastPathSkipReason, FdLeakAssessment, FdTrendStore,
+    PosteriorResult, PriorContext, RuntimePriorTable, RuntimeSurprise, SprtVerdict, SurvivalEta,
+    SurvivalEtaConfig, SurvivalEtaModel,
+};
+use pt_core::supervision::signature::{
+    MatchLevel, ProcessMatchContext, SignatureDatabase, SignatureMatch,
 };
-use pt_core::supervision::signature::{MatchLevel, ProcessMatchContext, SignatureDatabase};
 
 fn progress_emitter(global: &GlobalOpts) -> Option<Arc<dyn ProgressEmitter>> {
     match global.format {
@@ -2912,11 +4038,17 @@ fn run_scan(global: &GlobalOpts, args: &ScanArgs) -> ExitCode {
         include_kernel_threads: args.include_kernel_threads,
         timeout: global.timeout.map(std::time::Duration::from_secs),
         progress,
+        incremental: None,
+        io_rate_window: None,
     };
 
     // Perform scan
     match quick_scan(&options) {
-        Ok(result) => {
+        Ok(mut result) => {
+            pt_core::collect::annotate_processes(
+                &mut result.processes,
+                &pt_core::collect::PodIndex::load(),
+            );
             log_event!(
                 ctx,
                 INFO,
@@ -2949,9 +4081,24 @@ fn run_scan(global: &GlobalOpts, args: &ScanArgs) -> ExitCode {
                         "generated_at": chrono::Utc::now().to_rfc3339(),
                         "scan": result
                     });
+                    let launch_attributor =
+                        pt_core::supervision::LaunchAttributor::new(&result.processes);
+                    if let Some(processes) = output["scan"]["processes"].as_array_mut() {
+                        for (value, proc) in processes.iter_mut().zip(&result.processes) {
+                            value["launched_by"] =
+                                serde_json::json!(launch_attributor.attribute(proc));
+                        }
+                    }
                     if let Some(goal_advisory) = goal_advisory {
                         output["goal_advisory"] = goal_advisory;
                     }
+                    match output_redactor(global) {
+                        Ok(redactor) => redactor.scrub_json(&mut output),
+                        Err(e) => {
+                            eprintln!("scan: {}", e);
+                            return ExitCode::InternalError;
+                        }
+                    }
                     // Apply token-efficient processing if options specified
                     println!("{}", format_structured_output(global, output));
                 }
@@ -2988,7 +4135,7 @@ fn run_scan(global: &GlobalOpts, args: &ScanArgs) -> ExitCode {
                             p.user.chars().take(10).collect::<String>(),
                             p.state,
                             p.cpu_percent,
-                            bytes_to_human(p.rss_bytes),
+                            format_size(p.rss_bytes),
                             p.comm
                         );
                     }
@@ -3019,18 +4166,6 @@ fn run_scan(global: &GlobalOpts, args: &ScanArgs) -> ExitCode {
     }
 }
 
-fn bytes_to_human(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{}B", bytes)
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1}K", bytes as f64 / 1024.0)
-    } else if bytes < 1024 * 1024 * 1024 {
-        format!("{:.1}M", bytes as f64 / (1024.0 * 1024.0))
-    } else {
-        format!("{:.1}G", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
-    }
-}
-
 struct GoalPlanOutput {
     goals: Vec<ResourceGoal>,
     result: OptimizationResult,
@@ -3039,9 +4174,32 @@ struct GoalPlanOutput {
     warnings: Vec<String>,
 }
 
+/// Current usage that "reduce ... below" goals are measured against.
+#[derive(Debug, Clone, Copy)]
+struct GoalBaseline {
+    cpu_pct: f64,
+    /// File handles allocated system-wide (None where unknown).
+    fd_count: Option<f64>,
+}
+
+impl GoalBaseline {
+    fn with_cpu(cpu_pct: f64) -> Self {
+        Self {
+            cpu_pct,
+            fd_count: system_fd_count(),
+        }
+    }
+}
+
+/// Allocated file handles system-wide, from `/proc/sys/fs/file-nr`.
+fn system_fd_count() -> Option<f64> {
+    let content = std::fs::read_to_string("/proc/sys/fs/file-nr").ok()?;
+    content.split_whitespace().next()?.parse().ok()
+}
+
 fn resource_goal_from_target(
     target: &ResourceTarget,
-    current_cpu_pct: f64,
+    baseline: &GoalBaseline,
 ) -> Result<(ResourceGoal, Vec<String>), String> {
     let mut warnings = Vec::new();
     let goal = match target.metric {
@@ -3054,7 +4212,7 @@ fn resource_goal_from_target(
             let target_pct = target.value * 100.0;
             let desired = match target.comparator {
                 Comparator::ReduceBelow => {
-                    let required = (current_cpu_pct - target_pct).max(0.0);
+                    let required = (baseline.cpu_pct - target_pct).max(0.0);
                     if required <= 0.0 {
                         warnings.push("cpu_target_already_met".to_string());
                     }
@@ -3069,19 +4227,28 @@ fn resource_goal_from_target(
                 weight: 1.0,
             }
         }
-        Metric::Port => {
-            warnings.push("port_goal_requires_socket_inspection".to_string());
-            ResourceGoal {
-                resource: format!("port_{}", target.port.unwrap_or(0)),
-                target: 1.0,
-                weight: 1.0,
-            }
-        }
+        Metric::Port => ResourceGoal {
+            resource: format!("port_{}", target.port.unwrap_or(0)),
+            target: 1.0,
+            weight: 1.0,
+        },
         Metric::FileDescriptors => {
-            warnings.push("fd_goal_requires_fd_counts".to_string());
+            let desired = match target.comparator {
+                Comparator::ReduceBelow => {
+                    let current = baseline
+                        .fd_count
+                        .ok_or_else(|| "system fd count unavailable".to_string())?;
+                    let required = (current - target.value).max(0.0);
+                    if required <= 0.0 {
+                        warnings.push("fd_target_already_met".to_string());
+                    }
+                    required
+                }
+                Comparator::FreeAtLeast | Comparator::Release => target.value,
+            };
             ResourceGoal {
                 resource: "fd_count".to_string(),
-                target: target.value,
+                target: desired,
                 weight: 1.0,
             }
         }
@@ -3091,13 +4258,13 @@ fn resource_goal_from_target(
 
 fn build_resource_goals(
     goal: &Goal,
-    current_cpu_pct: f64,
+    baseline: &GoalBaseline,
 ) -> Result<(Vec<ResourceGoal>, Vec<String>), String> {
     let mut warnings = Vec::new();
     let mut goals = Vec::new();
     match goal {
         Goal::Target(t) => {
-            let (g, mut w) = resource_goal_from_target(t, current_cpu_pct)?;
+            let (g, mut w) = resource_goal_from_target(t, baseline)?;
             warnings.append(&mut w);
             goals.push(g);
         }
@@ -3106,7 +4273,7 @@ fn build_resource_goals(
                 let Goal::Target(t) = sub else {
                     return Err("nested composite goals not supported".to_string());
                 };
-                let (g, mut w) = resource_goal_from_target(t, current_cpu_pct)?;
+                let (g, mut w) = resource_goal_from_target(t, baseline)?;
                 warnings.append(&mut w);
                 goals.push(g);
             }
@@ -3135,6 +4302,16 @@ fn parse_kill_loss(candidate: &serde_json::Value) -> f64 {
         .unwrap_or(0.0)
 }
 
+/// Memory a kill is expected to return, in MB: PSS when `--deep` read
+/// smaps_rollup, otherwise RSS.
+fn candidate_recoverable_memory_mb(candidate: &serde_json::Value) -> u64 {
+    candidate
+        .pointer("/memory/pss_mb")
+        .and_then(|v| v.as_u64())
+        .or_else(|| candidate.get("memory_mb").and_then(|v| v.as_u64()))
+        .unwrap_or(0)
+}
+
 fn build_opt_candidates_for_goals(
     candidates: &[serde_json::Value],
     goals: &[ResourceGoal],
@@ -3148,10 +4325,7 @@ fn build_opt_candidates_for_goals(
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
             let blocked = action.eq_ignore_ascii_case("keep");
-            let memory_mb = candidate
-                .get("memory_mb")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as f64;
+            let memory_mb = candidate_recoverable_memory_mb(candidate) as f64;
             let cpu_pct = candidate
                 .get("cpu_percent")
                 .and_then(|v| v.as_f64())
@@ -3162,8 +4336,22 @@ fn build_opt_candidates_for_goals(
                 .map(|goal| match goal.resource.as_str() {
                     "memory_mb" => memory_mb,
                     "cpu_pct" => cpu_pct,
-                    "fd_count" => 0.0,
-                    r if r.starts_with("port_") => 0.0,
+                    "fd_count" => candidate
+                        .get("fd_count")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0) as f64,
+                    r if r.starts_with("port_") => {
+                        let port = r["port_".len()..].parse::<u64>().ok();
+                        let listens = candidate
+                            .get("listen_ports")
+                            .and_then(|v| v.as_array())
+                            .is_some_and(|ports| ports.iter().any(|p| p.as_u64() == port));
+                        if listens {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    }
                     _ => 0.0,
                 })
                 .collect();
@@ -3179,13 +4367,109 @@ fn build_opt_candidates_for_goals(
         .collect()
 }
 
+/// Leaf targets of a goal.
+fn goal_targets(goal: &Goal) -> Vec<&ResourceTarget> {
+    match goal {
+        Goal::Target(t) => vec![t],
+        Goal::And(parts) | Goal::Or(parts) => parts.iter().flat_map(goal_targets).collect(),
+    }
+}
+
+/// Ports named by the goal's port targets.
+fn goal_ports(goal: &Goal) -> Vec<u16> {
+    goal_targets(goal)
+        .into_iter()
+        .filter(|t| t.metric == Metric::Port)
+        .filter_map(|t| t.port)
+        .collect()
+}
+
+/// Resolve the goal's port targets to the processes listening on them, in any
+/// network namespace, and record each candidate's ports as `listen_ports` for
+/// the optimizer.
+///
+/// Returns the owners per port and a warning for each port no candidate owns.
+fn resolve_goal_ports(
+    goal: &Goal,
+    candidates: &mut [serde_json::Value],
+    pids: &[u32],
+) -> (serde_json::Value, Vec<String>) {
+    let ports = goal_ports(goal);
+    let mut warnings = Vec::new();
+    if ports.is_empty() {
+        return (serde_json::json!({}), warnings);
+    }
+
+    #[cfg(target_os = "linux")]
+    let owners_by_port = {
+        let map = pt_core::collect::PortOwnershipMap::collect(pids);
+        let mut candidate_pids = HashSet::new();
+        for candidate in candidates.iter_mut() {
+            let Some(obj) = candidate.as_object_mut() else {
+                continue;
+            };
+            let Some(pid) = obj.get("pid").and_then(|v| v.as_u64()) else {
+                continue;
+            };
+            candidate_pids.insert(pid as u32);
+            // Keep ports already known from the host namespace.
+            let mut ports: BTreeSet<u64> = obj
+                .get("listen_ports")
+                .and_then(|v| v.as_array())
+                .map(|known| known.iter().filter_map(|p| p.as_u64()).collect())
+                .unwrap_or_default();
+            ports.extend(map.ports_of(pid as u32).into_iter().map(u64::from));
+            obj.insert("listen_ports".to_string(), serde_json::json!(ports));
+        }
+        let mut owners_by_port = serde_json::Map::new();
+        for port in ports {
+            let owners = map.owners(port);
+            if owners.is_empty() {
+                warnings.push(format!("port_{}_no_listener", port));
+            } else if !owners
+                .iter()
+                .any(|owner| candidate_pids.contains(&owner.pid))
+            {
+                warnings.push(format!("port_{}_owner_not_candidate", port));
+            }
+            owners_by_port.insert(
+                port.to_string(),
+                serde_json::to_value(owners).unwrap_or_else(|_| serde_json::json!([])),
+            );
+        }
+        owners_by_port
+    };
+    #[cfg(not(target_os = "linux"))]
+    let owners_by_port = {
+        let _ = (candidates, pids);
+        warnings.extend(
+            ports
+                .iter()
+                .map(|port| format!("port_{}_owner_unresolved", port)),
+        );
+        serde_json::Map::new()
+    };
+
+    (serde_json::Value::Object(owners_by_port), warnings)
+}
+
 fn build_goal_plan_from_candidates(
     _goal_str: &str,
     goal: &Goal,
-    current_cpu_pct: f64,
+    baseline: &GoalBaseline,
     candidates: &[serde_json::Value],
 ) -> Result<GoalPlanOutput, String> {
     let mut warnings = Vec::new();
+    let fd_goal = goal_targets(goal)
+        .iter()
+        .any(|t| t.metric == Metric::FileDescriptors);
+    if fd_goal
+        && !candidates
+            .iter()
+            .any(|c| c.get("fd_count").is_some_and(|v| v.is_u64()))
+    {
+        warnings.push("fd_counts_unavailable".to_string());
+    }
     let (goals, mut w) = match goal {
         Goal::Or(parts) => {
             let mut best: Option<(OptimizationResult, Vec<ResourceGoal>)> = None;
@@ -3194,7 +4478,7 @@ fn build_goal_plan_from_candidates(
                 let Goal::Target(t) = sub else {
                     continue;
                 };
-                let (g, mut w) = resource_goal_from_target(t, current_cpu_pct)?;
+                let (g, mut w) = resource_goal_from_target(t, baseline)?;
                 warnings.append(&mut w);
                 let goals = vec![g.clone()];
                 let opt_candidates = build_opt_candidates_for_goals(candidates, &goals);
@@ -3240,7 +4524,7 @@ fn build_goal_plan_from_candidates(
                 warnings,
             });
         }
-        _ => build_resource_goals(goal, current_cpu_pct)?,
+        _ => build_resource_goals(goal, baseline)?,
     };
     warnings.append(&mut w);
 
@@ -3248,7 +4532,7 @@ fn build_goal_plan_from_candidates(
     let result = if goals.len() == 1 {
         optimize_ilp(&opt_candidates, &goals)
     } else {
-        optimize_greedy(&opt_candidates, &goals)
+        optimize_multi_objective(&opt_candidates, &goals)
     };
 
     let selected_pids = result
@@ -3294,6 +4578,47 @@ fn goal_summary_json(goal_str: &str, goal: &Goal, output: &GoalPlanOutput) -> se
     })
 }
 
+/// Project the host state after the kill set of a plan is applied.
+fn simulate_plan(
+    candidates: &[serde_json::Value],
+    kill_set: &[u32],
+    goal_summary: Option<&serde_json::Value>,
+) -> pt_core::plan::simulate::Simulation {
+    use pt_core::plan::simulate::{
+        read_process_swap_mb, simulate, CgroupState, SimTarget, SystemState,
+    };
+
+    let targets: Vec<SimTarget> = candidates
+        .iter()
+        .filter_map(SimTarget::from_candidate)
+        .filter(|target| kill_set.contains(&target.pid))
+        .map(|mut target| {
+            target.swap_mb = read_process_swap_mb(target.pid).unwrap_or(0.0);
+            target
+        })
+        .collect();
+    let mut cgroups: Vec<CgroupState> = Vec::new();
+    for path in targets.iter().filter_map(|t| t.cgroup.as_deref()) {
+        if let Some(cgroup) = CgroupState::read_effective(path) {
+            if !cgroups.iter().any(|c| c.path == cgroup.path) {
+                cgroups.push(cgroup);
+            }
+        }
+    }
+
+    let mut simulation = simulate(&SystemState::read(), &cgroups, &targets);
+    simulation.goal = goal_summary
+        .filter(|goal| goal.get("error").is_none())
+        .map(|goal| {
+            serde_json::json!({
+                "goal": goal["goal"],
+                "achievable": goal["achievable"],
+                "goal_achievement": goal["goal_achievement"],
+            })
+        });
+    simulation
+}
+
 fn build_goal_advisory_from_scan(
     goal_str: &str,
     goal: &Goal,
@@ -3306,7 +4631,8 @@ fn build_goal_advisory_from_scan(
         .sum();
     let total_cpu_pct: f64 = result.processes.iter().map(|p| p.cpu_percent).sum();
 
-    let (goals, warnings) = match build_resource_goals(goal, total_cpu_pct) {
+    let baseline = GoalBaseline::with_cpu(total_cpu_pct);
+    let (goals, warnings) = match build_resource_goals(goal, &baseline) {
         Ok(v) => v,
         Err(err) => {
             return serde_json::json!({
@@ -3317,12 +4643,34 @@ fn build_goal_advisory_from_scan(
         }
     };
 
+    #[cfg(target_os = "linux")]
+    let port_owners = (!goal_ports(goal).is_empty()).then(|| {
+        let pids: Vec<u32> = result.processes.iter().map(|p| p.pid.0).collect();
+        pt_core::collect::PortOwnershipMap::collect(&pids)
+    });
+
     let achievements: Vec<serde_json::Value> = goals
         .iter()
         .map(|g| {
             let achieved = match g.resource.as_str() {
                 "memory_mb" => total_mem_mb,
                 "cpu_pct" => total_cpu_pct,
+                r if r.starts_with("port_") => {
+                    // A port can be freed when a scanned process listens on it.
+                    #[cfg(target_os = "linux")]
+                    let held = r["port_".len()..]
+                        .parse::<u16>()
+                        .ok()
+                        .zip(port_owners.as_ref())
+                        .is_some_and(|(port, map)| !map.owners(port).is_empty());
+                    #[cfg(not(target_os = "linux"))]
+                    let held = false;
+                    if held {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
                 _ => 0.0,
             };
             serde_json::json!({
@@ -3343,99 +4691,523 @@ fn build_goal_advisory_from_scan(
     })
 }
 
+#[cfg(test)]
+mod goal_plan_tests {
+    use super::*;
+
+    fn candidate(pid: u32, fd_count: u64, listen_ports: &[u16]) -> serde_json::Value {
+        serde_json::json!({
+            "pid": pid,
+            "recommended_action": "kill",
+            "memory_mb": 10,
+            "cpu_percent": 0.0,
+            "fd_count": fd_count,
+            "listen_ports": listen_ports,
+            "expected_loss": [{"action": "kill", "loss": 1.0}],
+        })
+    }
+
+    fn baseline(fd_count: f64) -> GoalBaseline {
+        GoalBaseline {
+            cpu_pct: 0.0,
+            fd_count: Some(fd_count),
+        }
+    }
+
+    #[test]
+    fn fd_goal_selects_by_fd_count() {
+        let goal = parse_goal("reduce FDs below 100k").unwrap();
+        let candidates = vec![
+            candidate(10, 60_000, &[]),
+            candidate(11, 30_000, &[]),
+            candidate(12, 10, &[]),
+        ];
+        let output =
+            build_goal_plan_from_candidates("", &goal, &baseline(150_000.0), &candidates).unwrap();
+        assert!(output.result.feasible);
+        assert_eq!(output.goals[0].target, 50_000.0);
+        assert_eq!(output.selected_pids, vec![10]);
+        assert!(output.warnings.is_empty());
+    }
+
+    #[test]
+    fn fd_goal_without_counts_warns() {
+        let goal = parse_goal("free 100 FDs").unwrap();
+        let mut bare = candidate(10, 0, &[]);
+        bare.as_object_mut().unwrap().remove("fd_count");
+        let output =
+            build_goal_plan_from_candidates("", &goal, &baseline(1_000.0), &[bare]).unwrap();
+        assert!(!output.result.feasible);
+        assert!(output
+            .warnings
+            .contains(&"fd_counts_unavailable".to_string()));
+    }
+
+    #[test]
+    fn port_goal_selects_listener() {
+        let goal = parse_goal("free port 3000").unwrap();
+        let candidates = vec![candidate(10, 5, &[8080]), candidate(11, 5, &[3000])];
+        let output =
+            build_goal_plan_from_candidates("", &goal, &baseline(0.0), &candidates).unwrap();
+        assert!(output.result.feasible);
+        assert_eq!(output.selected_pids, vec![11]);
+    }
+}
+
 fn resolve_bundle_passphrase(passphrase_arg: &Option<String>) -> Option<String> {
     passphrase_arg
         .clone()
         .or_else(|| std::env::var("PT_BUNDLE_PASSPHRASE").ok())
 }
 
-fn run_deep_scan(global: &GlobalOpts, _args: &DeepScanArgs) -> ExitCode {
-    output_stub(global, "deep-scan", "Deep scan mode not yet implemented");
-    ExitCode::Clean
-}
+#[cfg(target_os = "linux")]
+fn run_deep_scan(global: &GlobalOpts, args: &DeepScanArgs) -> ExitCode {
+    use pt_core::collect::{deep_scan, DeepScanOptions};
 
-fn run_query(global: &GlobalOpts, args: &QueryArgs) -> ExitCode {
-    match &args.command {
-        Some(QueryCommands::Sessions { limit }) => run_query_sessions(global, *limit),
-        Some(QueryCommands::Actions { .. }) => {
-            output_stub(
-                global,
-                "query actions",
-                "Query actions mode not yet implemented",
+    let ctx = LogContext::new(
+        pt_core::logging::generate_run_id(),
+        pt_core::logging::get_host_id(),
+    );
+
+    // An explicit --budget wins; otherwise fall back to the global timeout.
+    let budget = args
+        .budget
+        .or(global.timeout)
+        .map(std::time::Duration::from_secs);
+    let options = DeepScanOptions {
+        pids: args.pids.clone(),
+        // Inaccessible PIDs are only worth a warning when explicitly requested.
+        skip_inaccessible: args.pids.is_empty(),
+        include_environ: false,
+        progress: progress_emitter(global),
+        budget,
+    };
+
+    let result = match deep_scan(&options) {
+        Ok(result) => result,
+        Err(e) => {
+            log_event!(
+                ctx,
+                ERROR,
+                event_names::INTERNAL_ERROR,
+                Stage::Scan,
+                "Deep scan failed",
+                error = e.to_string()
             );
-            ExitCode::Clean
+            eprintln!("deep-scan: {}", e);
+            return ExitCode::InternalError;
         }
-        Some(QueryCommands::Telemetry { .. }) => {
-            output_stub(
-                global,
-                "query telemetry",
-                "Query telemetry mode not yet implemented",
+    };
+
+    log_event!(
+        ctx,
+        INFO,
+        event_names::SCAN_FINISHED,
+        Stage::Scan,
+        "Deep scan finished",
+        count = result.metadata.process_count,
+        duration_ms = result.metadata.duration_ms
+    );
+
+    let meta = &result.metadata;
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let session_id = SessionId::new();
+            let mut output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "deep_scan": result
+            });
+            match output_redactor(global) {
+                Ok(redactor) => redactor.scrub_json(&mut output),
+                Err(e) => {
+                    eprintln!("deep-scan: {}", e);
+                    return ExitCode::InternalError;
+                }
+            }
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            print!(
+                "Deep scanned {} processes in {}ms ({} skipped)",
+                meta.process_count, meta.duration_ms, meta.skipped_count
             );
-            ExitCode::Clean
+            if meta.budget_exceeded {
+                print!("; budget exceeded, {} not scanned", meta.unscanned_count);
+            }
+            println!();
         }
-        None => {
-            if let Some(expr) = &args.query {
-                output_stub(
-                    global,
-                    "query",
-                    &format!("Query expression '{}' is not yet implemented", expr),
-                );
-            } else {
-                output_stub(
-                    global,
-                    "query",
-                    "Use subcommands like `query sessions --limit 10`",
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# Deep Scan Results");
+            println!(
+                "Scanned {} processes in {}ms ({} skipped)",
+                meta.process_count, meta.duration_ms, meta.skipped_count
+            );
+            if let Some(budget_ms) = meta.budget_ms {
+                if meta.budget_exceeded {
+                    println!(
+                        "Budget: {}ms (exceeded, {} processes not scanned)",
+                        budget_ms, meta.unscanned_count
+                    );
+                } else {
+                    println!("Budget: {}ms", budget_ms);
+                }
+            }
+            println!();
+
+            println!(
+                "{:<8} {:<10} {:<6} {:<6} {:<6} {:<10} {:<10} COMMAND",
+                "PID", "USER", "STATE", "FDS", "SOCKS", "READ", "WRITE"
+            );
+            for p in result.processes.iter().take(20) {
+                let fds =
+                    p.fd.as_ref()
+                        .map(|fd| fd.count.to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                let socks = p
+                    .network
+                    .as_ref()
+                    .map(|n| {
+                        let c = &n.socket_counts;
+                        (c.tcp + c.tcp6 + c.udp + c.udp6 + c.unix + c.raw).to_string()
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                let (read, write) =
+                    p.io.as_ref()
+                        .map(|io| (format_size(io.read_bytes), format_size(io.write_bytes)))
+                        .unwrap_or_else(|| ("-".to_string(), "-".to_string()));
+                println!(
+                    "{:<8} {:<10} {:<6} {:<6} {:<6} {:<10} {:<10} {}",
+                    p.pid.0,
+                    p.user.chars().take(10).collect::<String>(),
+                    p.state,
+                    fds,
+                    socks,
+                    read,
+                    write,
+                    p.comm
                 );
             }
-            ExitCode::Clean
+            if result.processes.len() > 20 {
+                println!("... and {} more", result.processes.len() - 20);
+            }
+
+            if !meta.probe_skips.is_empty() {
+                println!();
+                println!("## Skipped Probes");
+                for skip in &meta.probe_skips {
+                    println!(
+                        "  {:<10} {:<16} {}",
+                        skip.probe.as_str(),
+                        skip.reason.as_str(),
+                        skip.count
+                    );
+                }
+            }
+            for warning in &meta.warnings {
+                eprintln!("deep-scan: warning: {}", warning);
+            }
         }
     }
+
+    ExitCode::Clean
 }
 
-fn run_query_sessions(global: &GlobalOpts, limit: u32) -> ExitCode {
-    let store = match SessionStore::from_env() {
-        Ok(store) => store,
-        Err(e) => {
-            eprintln!("query sessions: session store error: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
+#[cfg(target_os = "macos")]
+fn run_deep_scan(global: &GlobalOpts, args: &DeepScanArgs) -> ExitCode {
+    use pt_core::collect::{macos_scan, MacOsScanOptions};
 
-    let host_id = pt_core::logging::get_host_id();
-    let options = ListSessionsOptions {
-        limit: Some(limit),
-        state: None,
-        older_than: None,
+    let options = MacOsScanOptions {
+        pids: args.pids.clone(),
+        skip_inaccessible: args.pids.is_empty(),
+        include_environ: false,
+        tool_timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: progress_emitter(global),
     };
 
-    let sessions = match store.list_sessions(&options) {
-        Ok(s) => s,
+    let result = match macos_scan(&options) {
+        Ok(result) => result,
         Err(e) => {
-            eprintln!("query sessions: failed to list sessions: {}", e);
+            eprintln!("deep-scan: {}", e);
             return ExitCode::InternalError;
         }
     };
 
+    let meta = &result.metadata;
     match global.format {
         OutputFormat::Json | OutputFormat::Toon => {
-            let output = serde_json::json!({
+            let session_id = SessionId::new();
+            let mut output = serde_json::json!({
                 "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
                 "generated_at": chrono::Utc::now().to_rfc3339(),
-                "host_id": host_id,
-                "query": "sessions",
-                "limit": limit,
-                "sessions": sessions.iter().map(|s| serde_json::json!({
-                    "session_id": s.session_id,
-                    "host": s.host_id,
-                    "state": s.state,
-                    "mode": s.mode,
-                    "created_at": s.created_at,
-                    "label": s.label,
-                    "candidates": s.candidates_count,
-                    "actions_taken": s.actions_count,
-                })).collect::<Vec<_>>(),
-                "total_count": sessions.len(),
-                "status": "ok",
+                "deep_scan": result
+            });
+            match output_redactor(global) {
+                Ok(redactor) => redactor.scrub_json(&mut output),
+                Err(e) => {
+                    eprintln!("deep-scan: {}", e);
+                    return ExitCode::InternalError;
+                }
+            }
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "Deep scanned {} processes in {}ms ({} skipped)",
+                meta.process_count, meta.duration_ms, meta.skipped_count
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# Deep Scan Results");
+            println!(
+                "Scanned {} processes in {}ms ({} skipped)",
+                meta.process_count, meta.duration_ms, meta.skipped_count
+            );
+            println!();
+
+            println!(
+                "{:<8} {:<10} {:<6} {:<6} {:<6} {:<10} {:<10} COMMAND",
+                "PID", "USER", "STATE", "FDS", "SOCKS", "READ", "WRITE"
+            );
+            for p in result.processes.iter().take(20) {
+                let (fds, socks) = p
+                    .fd_summary
+                    .as_ref()
+                    .map(|s| (s.total.to_string(), s.sockets.to_string()))
+                    .unwrap_or_else(|| ("-".to_string(), "-".to_string()));
+                let (read, write) = p
+                    .disk_io
+                    .map(|io| (format_size(io.read_bytes), format_size(io.write_bytes)))
+                    .unwrap_or_else(|| ("-".to_string(), "-".to_string()));
+                println!(
+                    "{:<8} {:<10} {:<6} {:<6} {:<6} {:<10} {:<10} {}",
+                    p.pid.0,
+                    p.user.chars().take(10).collect::<String>(),
+                    p.state,
+                    fds,
+                    socks,
+                    read,
+                    write,
+                    p.comm
+                );
+            }
+            if result.processes.len() > 20 {
+                println!("... and {} more", result.processes.len() - 20);
+            }
+            for warning in &meta.warnings {
+                eprintln!("deep-scan: warning: {}", warning);
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn run_deep_scan(_global: &GlobalOpts, _args: &DeepScanArgs) -> ExitCode {
+    eprintln!("deep-scan: deep scan is only supported on Linux and macOS");
+    ExitCode::CapabilityError
+}
+
+fn run_query(global: &GlobalOpts, args: &QueryArgs) -> ExitCode {
+    match &args.command {
+        Some(QueryCommands::Sessions { limit }) => run_query_sessions(global, *limit),
+        Some(QueryCommands::Actions { .. }) => {
+            output_stub(
+                global,
+                "query actions",
+                "Query actions mode not yet implemented",
+            );
+            ExitCode::Clean
+        }
+        Some(QueryCommands::Telemetry {
+            sql, telemetry_dir, ..
+        }) => run_query_sql(
+            global,
+            "query telemetry",
+            sql.as_deref(),
+            telemetry_dir.as_deref(),
+        ),
+        Some(QueryCommands::Trends {
+            category,
+            host,
+            range,
+            telemetry_dir,
+        }) => run_query_trends(
+            global,
+            category.as_deref(),
+            host.as_deref(),
+            range,
+            telemetry_dir.as_deref(),
+        ),
+        None => {
+            if let Some(expr) = &args.query {
+                return run_query_sql(global, "query", Some(expr), args.telemetry_dir.as_deref());
+            }
+            output_stub(
+                global,
+                "query",
+                "Use subcommands like `query sessions --limit 10`, or pass a SQL query",
+            );
+            ExitCode::Clean
+        }
+    }
+}
+
+/// Run SQL over the telemetry Parquet tables, or list the tables when `sql` is `None`.
+#[cfg(feature = "query-sql")]
+fn run_query_sql(
+    global: &GlobalOpts,
+    command: &str,
+    sql: Option<&str>,
+    telemetry_dir: Option<&str>,
+) -> ExitCode {
+    use pt_telemetry::sql::{run_query, SqlError};
+
+    let telemetry_dir = telemetry_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(default_telemetry_dir);
+    // Without a query, describe what can be queried.
+    let listing = sql.is_none();
+    let sql = sql.unwrap_or(
+        "SELECT table_name, column_name, data_type FROM information_schema.columns \
+         WHERE table_schema = 'public' ORDER BY table_name, ordinal_position",
+    );
+    let (tables, result) = match run_query(&telemetry_dir, sql) {
+        Ok(ok) => ok,
+        Err(SqlError::NoTables(dir)) => {
+            return output_agent_error(
+                global,
+                command,
+                &format!("no telemetry tables found under {}", dir.display()),
+            );
+        }
+        Err(e) => return output_agent_error(global, command, &e.to_string()),
+    };
+    let rows = match result.to_json_rows() {
+        Ok(rows) => rows,
+        Err(e) => return output_agent_error(global, command, &e.to_string()),
+    };
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": command,
+                "status": "ok",
+                "telemetry_dir": telemetry_dir.display().to_string(),
+                "tables": tables,
+                "sql": if listing { None } else { Some(sql) },
+                "columns": result.columns(),
+                "row_count": rows.len(),
+                "rows": rows,
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Jsonl => {
+            for row in &rows {
+                println!("{}", row);
+            }
+        }
+        OutputFormat::Summary => {
+            println!(
+                "[{}] {} rows, {} columns from {} tables",
+                command,
+                rows.len(),
+                result.columns().len(),
+                tables.len()
+            );
+        }
+        OutputFormat::Metrics => {
+            println!("query_rows={}", rows.len());
+            println!("query_columns={}", result.columns().len());
+            println!("query_tables={}", tables.len());
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            let table = match result.to_table() {
+                Ok(table) => table,
+                Err(e) => return output_agent_error(global, command, &e.to_string()),
+            };
+            println!("# pt-core {}", command);
+            println!();
+            println!("Tables: {}", tables.join(", "));
+            println!();
+            println!("```");
+            println!("{}", table);
+            println!("```");
+            println!();
+            println!("{} rows", rows.len());
+        }
+    }
+
+    ExitCode::Clean
+}
+
+#[cfg(not(feature = "query-sql"))]
+fn run_query_sql(
+    global: &GlobalOpts,
+    command: &str,
+    _sql: Option<&str>,
+    _telemetry_dir: Option<&str>,
+) -> ExitCode {
+    output_stub(
+        global,
+        command,
+        "SQL queries require the `query-sql` feature (build with --features query-sql)",
+    );
+    ExitCode::CapabilityError
+}
+
+fn run_query_sessions(global: &GlobalOpts, limit: u32) -> ExitCode {
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("query sessions: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    let host_id = pt_core::logging::get_host_id();
+    let options = ListSessionsOptions {
+        limit: Some(limit),
+        state: None,
+        older_than: None,
+    };
+
+    let sessions = match store.list_sessions(&options) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("query sessions: failed to list sessions: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "host_id": host_id,
+                "query": "sessions",
+                "limit": limit,
+                "sessions": sessions.iter().map(|s| serde_json::json!({
+                    "session_id": s.session_id,
+                    "host": s.host_id,
+                    "state": s.state,
+                    "mode": s.mode,
+                    "created_at": s.created_at,
+                    "label": s.label,
+                    "candidates": s.candidates_count,
+                    "actions_taken": s.actions_count,
+                })).collect::<Vec<_>>(),
+                "total_count": sessions.len(),
+                "status": "ok",
                 "command": format!("pt query sessions --limit {}", limit),
             });
             println!("{}", format_structured_output(global, output));
@@ -3492,6 +5264,166 @@ fn run_query_sessions(global: &GlobalOpts, limit: u32) -> ExitCode {
     ExitCode::Clean
 }
 
+fn run_query_trends(
+    global: &GlobalOpts,
+    category: Option<&str>,
+    host: Option<&str>,
+    range: &str,
+    telemetry_dir: Option<&str>,
+) -> ExitCode {
+    use pt_telemetry::trends::{parse_range_days, trends_path, TrendQuery, TrendsTable};
+
+    let days = match parse_range_days(range) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("query trends: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+
+    let telemetry_dir = telemetry_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(default_telemetry_dir);
+    let table_path = trends_path(&telemetry_dir);
+    let mut table = match TrendsTable::load(&table_path) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!(
+                "query trends: failed to load {}: {}",
+                table_path.display(),
+                e
+            );
+            return ExitCode::InternalError;
+        }
+    };
+
+    // Fold in settled sessions that have not been ingested yet. The table
+    // outlives session retention, so a missing store only means no new data.
+    let now = chrono::Utc::now();
+    let mut newly_ingested = 0usize;
+    match SessionStore::from_env()
+        .and_then(|store| store.list_sessions(&ListSessionsOptions::default()))
+    {
+        Ok(sessions) => {
+            for summary in sessions
+                .iter()
+                .filter(|s| pt_core::session::trends::is_settled(s, now))
+            {
+                if table.is_ingested(&summary.session_id) {
+                    continue;
+                }
+                if let Some(rollup) = pt_core::session::trends::session_rollup(summary) {
+                    if table.ingest(&rollup) {
+                        newly_ingested += 1;
+                    }
+                }
+            }
+        }
+        Err(e) => eprintln!("query trends: session store unavailable: {}", e),
+    }
+    if newly_ingested > 0 {
+        if let Err(e) = table.save(&table_path) {
+            eprintln!(
+                "query trends: failed to write {}: {}",
+                table_path.display(),
+                e
+            );
+            return ExitCode::IoError;
+        }
+    }
+
+    let mut query = TrendQuery::last_days(now.date_naive(), days);
+    if let Some(category) = category {
+        query = query.with_category(category);
+    }
+    if let Some(host) = host {
+        query = query.with_host(host);
+    }
+    let series = table.series(&query);
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": now.to_rfc3339(),
+                "host_id": pt_core::logging::get_host_id(),
+                "query": "trends",
+                "filters": {
+                    "category": category,
+                    "host": host,
+                    "range": range,
+                    "days": days,
+                },
+                "start": series.start,
+                "end": series.end,
+                "series": series.points,
+                "totals": series.totals,
+                "categories": table.categories(),
+                "sessions_ingested": table.ingested_count(),
+                "newly_ingested": newly_ingested,
+                "status": "ok",
+                "command": format!("pt query trends --range {}", range),
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            let t = &series.totals;
+            println!(
+                "{} → {}: {} candidates, {} actions, {} recoveries ({:.0} MB), {} false positives",
+                series.start,
+                series.end,
+                t.candidates,
+                t.actions,
+                t.recoveries,
+                t.memory_recovered_mb,
+                t.false_positives
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# Query Trends");
+            println!();
+            println!(
+                "Range: {} → {} ({} days), category: {}, host: {}",
+                series.start,
+                series.end,
+                days,
+                category.unwrap_or("all"),
+                host.unwrap_or("all")
+            );
+            println!();
+            println!(
+                "{:<12} {:>10} {:>8} {:>10} {:>8} {:>10}",
+                "DAY", "CANDS", "ACTIONS", "RECOVERED", "FP", "FREED_MB"
+            );
+            for point in series.points.iter().filter(|p| !p.counts.is_empty()) {
+                let c = &point.counts;
+                println!(
+                    "{:<12} {:>10} {:>8} {:>10} {:>8} {:>10.0}",
+                    point.day,
+                    c.candidates,
+                    c.actions,
+                    c.recoveries,
+                    c.false_positives,
+                    c.memory_recovered_mb
+                );
+            }
+            let t = &series.totals;
+            println!(
+                "{:<12} {:>10} {:>8} {:>10} {:>8} {:>10.0}",
+                "TOTAL",
+                t.candidates,
+                t.actions,
+                t.recoveries,
+                t.false_positives,
+                t.memory_recovered_mb
+            );
+        }
+    }
+
+    ExitCode::Clean
+}
+
 fn run_bundle(global: &GlobalOpts, args: &BundleArgs) -> ExitCode {
     match &args.command {
         BundleCommands::Create {
@@ -3500,8 +5432,12 @@ fn run_bundle(global: &GlobalOpts, args: &BundleArgs) -> ExitCode {
             profile,
             include_telemetry,
             include_dumps,
+            since,
+            allow_env,
+            allow_arg,
             encrypt,
             passphrase,
+            recipients,
         } => run_bundle_create(
             global,
             session,
@@ -3509,20 +5445,36 @@ fn run_bundle(global: &GlobalOpts, args: &BundleArgs) -> ExitCode {
             profile,
             *include_telemetry,
             *include_dumps,
+            since,
+            pt_redact::CaptureAllowlist::new()
+                .with_env_names(allow_env.iter().cloned())
+                .with_cmdline_patterns(allow_arg.iter().cloned()),
             *encrypt,
             passphrase,
+            recipients,
         ),
         BundleCommands::Inspect {
             path,
             verify,
             passphrase,
-        } => run_bundle_inspect(global, path, *verify, passphrase),
+            identity,
+        } => run_bundle_inspect(global, path, *verify, passphrase, identity.as_deref()),
         BundleCommands::Extract {
             path,
             output,
+            base,
             verify,
             passphrase,
-        } => run_bundle_extract(global, path, output, *verify, passphrase),
+            identity,
+        } => run_bundle_extract(
+            global,
+            path,
+            output,
+            base,
+            *verify,
+            passphrase,
+            identity.as_deref(),
+        ),
     }
 }
 
@@ -3534,11 +5486,14 @@ fn run_bundle_create(
     profile_str: &str,
     include_telemetry: bool,
     _include_dumps: bool,
+    since_arg: &Option<String>,
+    capture_allowlist: pt_redact::CaptureAllowlist,
     encrypt: bool,
     passphrase_arg: &Option<String>,
+    recipients: &[String],
 ) -> ExitCode {
     use pt_bundle::{BundleWriter, FileType};
-    use pt_redact::ExportProfile;
+    use pt_redact::{ExportProfile, RedactionEngine, RedactionPolicy};
 
     let session_id = SessionId::new();
     let host_id = pt_core::logging::get_host_id();
@@ -3563,6 +5518,23 @@ fn run_bundle_create(
         return ExitCode::ArgsError;
     }
 
+    if let Err(e) = pt_bundle::encryption::parse_recipients(recipients) {
+        let error_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "session_id": session_id.0,
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+            "command": "bundle create",
+            "status": "error",
+            "error": e.to_string(),
+        });
+        match global.format {
+            OutputFormat::Md => eprintln!("Error: {}", e),
+            OutputFormat::Jsonl => println!("{}", serde_json::to_string(&error_output).unwrap()),
+            _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
+        }
+        return ExitCode::ArgsError;
+    }
+
     // Parse export profile
     let export_profile = match ExportProfile::parse_str(profile_str) {
         Some(p) => p,
@@ -3635,84 +5607,84 @@ fn run_bundle_create(
         }
     };
 
-    // Create bundle writer
-    let mut writer = BundleWriter::new(&target_session.0, &host_id, export_profile)
+    // Create bundle writer; the raw-capture allowlist is validated against the profile here
+    let writer = BundleWriter::new(&target_session.0, &host_id, export_profile)
         .with_pt_version(env!("CARGO_PKG_VERSION"))
-        .with_description(format!("Export of session {}", target_session.0));
-
-    // Add manifest.json from session
-    let manifest_path = handle.manifest_path();
-    if let Ok(content) = std::fs::read(&manifest_path) {
-        writer.add_file("session/manifest.json", content, Some(FileType::Json));
-    }
-
-    // Add context.json from session
-    let context_path = handle.context_path();
-    if let Ok(content) = std::fs::read(&context_path) {
-        writer.add_file("session/context.json", content, Some(FileType::Json));
-    }
-
-    // Add plan.json if present
-    let plan_path = handle.dir.join("decision/plan.json");
-    if plan_path.exists() {
-        if let Ok(content) = std::fs::read(&plan_path) {
-            writer.add_file("plan.json", content, Some(FileType::Json));
-        }
-    }
-
-    // Add snapshot.json if present
-    let snapshot_path = handle.dir.join("scan/snapshot.json");
-    if snapshot_path.exists() {
-        if let Ok(content) = std::fs::read(&snapshot_path) {
-            writer.add_file("snapshot.json", content, Some(FileType::Json));
-        }
-    }
-
-    // Add inference results if present
-    let posteriors_path = handle.dir.join("inference/posteriors.json");
-    if posteriors_path.exists() {
-        if let Ok(content) = std::fs::read(&posteriors_path) {
-            writer.add_file("inference/posteriors.json", content, Some(FileType::Json));
-        }
-    }
-
-    // Add audit trail if present
-    let audit_path = handle.dir.join("action/outcomes.jsonl");
-    if audit_path.exists() {
-        if let Ok(content) = std::fs::read(&audit_path) {
-            writer.add_file("logs/outcomes.jsonl", content, Some(FileType::Log));
+        .with_description(format!("Export of session {}", target_session.0))
+        .with_capture_allowlist(capture_allowlist.clone());
+    let mut writer = match writer {
+        Ok(w) => w,
+        Err(e) => {
+            let error_output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "bundle create",
+                "status": "error",
+                "error": e.to_string(),
+            });
+            match global.format {
+                OutputFormat::Md => eprintln!("Error: {}", e),
+                OutputFormat::Jsonl => {
+                    println!("{}", serde_json::to_string(&error_output).unwrap())
+                }
+                _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
+            }
+            return ExitCode::ArgsError;
+        }
+    };
+
+    // Artifacts of the base session, for a delta bundle
+    let base_files = match since_arg {
+        Some(raw) => {
+            let base_handle = match SessionId::parse(raw).map(|sid| store.open(&sid)) {
+                Some(Ok(h)) => h,
+                Some(Err(e)) => {
+                    eprintln!("bundle create: --since: {}", e);
+                    return ExitCode::ArgsError;
+                }
+                None => {
+                    eprintln!("bundle create: invalid --since session ID '{}'", raw);
+                    return ExitCode::ArgsError;
+                }
+            };
+            let files: Vec<pt_bundle::FileEntry> =
+                session_bundle_artifacts(&base_handle, include_telemetry)
+                    .into_iter()
+                    .map(|(path, content, _)| {
+                        let checksum = pt_bundle::FileEntry::compute_checksum(&content);
+                        pt_bundle::FileEntry::new(path, checksum, content.len() as u64)
+                    })
+                    .collect();
+            Some((base_handle.id.0.clone(), files))
         }
+        None => None,
+    };
+    if let Some((base_session, files)) = base_files {
+        writer = writer.with_base(base_session, files);
     }
 
-    // Optionally include telemetry data
-    if include_telemetry {
-        let telemetry_dir = handle.dir.join("telemetry");
-        if telemetry_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&telemetry_dir) {
-                for entry in entries.flatten() {
-                    let entry_path = entry.path();
-                    if entry_path.is_file() {
-                        if let Some(name) = entry_path.file_name().and_then(|n| n.to_str()) {
-                            if let Ok(content) = std::fs::read(&entry_path) {
-                                let file_type = if name.ends_with(".parquet") {
-                                    FileType::Parquet
-                                } else if name.ends_with(".jsonl") {
-                                    FileType::Log
-                                } else if name.ends_with(".json") {
-                                    FileType::Json
-                                } else {
-                                    FileType::Binary
-                                };
-                                writer.add_file(
-                                    format!("telemetry/{}", name),
-                                    content,
-                                    Some(file_type),
-                                );
-                            }
-                        }
-                    }
+    for (path, content, file_type) in session_bundle_artifacts(&handle, include_telemetry) {
+        writer.add_file(path, content, Some(file_type));
+    }
+
+    // Forensic captures for plan candidates: only allowlisted values stay raw
+    let plan_path = handle.dir.join("decision/plan.json");
+    if !capture_allowlist.is_empty() {
+        let engine = RedactionEngine::new(RedactionPolicy::default())
+            .and_then(|engine| engine.with_capture_allowlist(&capture_allowlist));
+        let plan = std::fs::read_to_string(&plan_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        match (engine, plan) {
+            (Ok(engine), Some(plan)) => {
+                let captures = forensic_captures(&plan, &engine, export_profile);
+                if let Err(e) = writer.add_json("forensic/captures.json", &captures) {
+                    eprintln!("bundle create: failed to add forensic captures: {}", e);
                 }
             }
+            (Err(e), _) => eprintln!("bundle create: redaction engine error: {}", e),
+            (_, None) => eprintln!("bundle create: no plan found; skipping forensic captures"),
         }
     }
 
@@ -3763,9 +5735,18 @@ fn run_bundle_create(
             }
         };
         writer.write_encrypted(&output_path, passphrase)
+    } else if !recipients.is_empty() {
+        writer.write_to_recipients(&output_path, recipients)
     } else {
         writer.write(&output_path)
     };
+    let encryption = if encrypt {
+        Some("passphrase")
+    } else if !recipients.is_empty() {
+        Some("age")
+    } else {
+        None
+    };
 
     match result {
         Ok(manifest) => {
@@ -3781,16 +5762,36 @@ fn run_bundle_create(
                     "profile": format!("{}", export_profile),
                     "files": manifest.file_count(),
                     "total_bytes": manifest.total_bytes(),
-                    "encrypted": encrypt,
+                    "encrypted": encryption.is_some(),
+                    "encryption": encryption,
+                    "recipients": recipients.len(),
+                    "capture_allowlist": manifest.capture_allowlist,
+                    "base": manifest.base.as_ref().map(|base| serde_json::json!({
+                        "session_id": base.session_id,
+                        "unchanged_files": base.unchanged.len(),
+                    })),
                 },
             });
             match global.format {
                 OutputFormat::Md => println!(
-                    "Bundle created: {} ({} files, {} bytes{})",
+                    "Bundle created: {} ({} files, {} bytes{}{})",
                     output_path.display(),
                     manifest.file_count(),
                     manifest.total_bytes(),
-                    if encrypt { ", encrypted" } else { "" }
+                    manifest
+                        .base
+                        .as_ref()
+                        .map(|base| format!(
+                            ", delta since {} ({} unchanged)",
+                            base.session_id,
+                            base.unchanged.len()
+                        ))
+                        .unwrap_or_default(),
+                    match encryption {
+                        Some("age") => format!(", encrypted to {} recipient(s)", recipients.len()),
+                        Some(_) => ", encrypted".to_string(),
+                        None => String::new(),
+                    }
                 ),
                 OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
                 _ => println!("{}", serde_json::to_string_pretty(&output).unwrap()),
@@ -3818,11 +5819,169 @@ fn run_bundle_create(
     }
 }
 
+/// Session artifacts exported by `bundle create`, as (bundle path, content, type).
+fn session_bundle_artifacts(
+    handle: &SessionHandle,
+    include_telemetry: bool,
+) -> Vec<(String, Vec<u8>, pt_bundle::FileType)> {
+    use pt_bundle::FileType;
+
+    let mut artifacts = Vec::new();
+    let sources = [
+        (
+            handle.manifest_path(),
+            "session/manifest.json",
+            FileType::Json,
+        ),
+        (
+            handle.context_path(),
+            "session/context.json",
+            FileType::Json,
+        ),
+        (
+            handle.dir.join("decision/plan.json"),
+            "plan.json",
+            FileType::Json,
+        ),
+        (
+            handle.dir.join("scan/snapshot.json"),
+            "snapshot.json",
+            FileType::Json,
+        ),
+        (
+            handle.dir.join("inference/posteriors.json"),
+            "inference/posteriors.json",
+            FileType::Json,
+        ),
+        (
+            handle.dir.join("inference/evidence.json"),
+            "inference/evidence.json",
+            FileType::Json,
+        ),
+        (
+            handle.dir.join("action/outcomes.jsonl"),
+            "logs/outcomes.jsonl",
+            FileType::Log,
+        ),
+    ];
+    for (source, path, file_type) in sources {
+        if let Ok(content) = std::fs::read(&source) {
+            artifacts.push((path.to_string(), content, file_type));
+        }
+    }
+
+    // Optionally include telemetry data
+    if include_telemetry {
+        let telemetry_dir = handle.dir.join("telemetry");
+        if let Ok(entries) = std::fs::read_dir(&telemetry_dir) {
+            for entry in entries.flatten() {
+                let entry_path = entry.path();
+                if !entry_path.is_file() {
+                    continue;
+                }
+                let Some(name) = entry_path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if let Ok(content) = std::fs::read(&entry_path) {
+                    let file_type = if name.ends_with(".parquet") {
+                        FileType::Parquet
+                    } else if name.ends_with(".jsonl") {
+                        FileType::Log
+                    } else if name.ends_with(".json") {
+                        FileType::Json
+                    } else {
+                        FileType::Binary
+                    };
+                    artifacts.push((format!("telemetry/{}", name), content, file_type));
+                }
+            }
+        }
+    }
+
+    artifacts
+}
+
+/// Capture env and cmdline args for plan candidates, redacting all but allowlisted values.
+fn forensic_captures(
+    plan: &serde_json::Value,
+    engine: &pt_redact::RedactionEngine,
+    profile: pt_redact::ExportProfile,
+) -> serde_json::Value {
+    let candidates = plan
+        .get("candidates")
+        .and_then(|c| c.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let processes: Vec<serde_json::Value> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let pid = candidate.get("pid").and_then(|p| p.as_u64())?;
+            let args: Vec<String> = std::fs::read(format!("/proc/{}/cmdline", pid))
+                .ok()
+                .map(|raw| {
+                    raw.split(|&b| b == 0)
+                        .filter(|a| !a.is_empty())
+                        .map(|a| String::from_utf8_lossy(a).into_owned())
+                        .collect()
+                })
+                .unwrap_or_else(|| {
+                    candidate
+                        .get("command")
+                        .and_then(|c| c.as_str())
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect()
+                });
+            let mut prev: Option<&str> = None;
+            let cmdline_args: Vec<String> = args
+                .iter()
+                .map(|arg| {
+                    let redacted = engine.redact_arg_with_profile(arg, prev, profile);
+                    prev = Some(arg.as_str());
+                    redacted.output
+                })
+                .collect();
+
+            let env: serde_json::Map<String, serde_json::Value> =
+                std::fs::read(format!("/proc/{}/environ", pid))
+                    .ok()
+                    .and_then(|raw| pt_core::collect::parse_environ_content(&raw))
+                    .map(|vars| {
+                        let mut vars: Vec<_> = vars.into_iter().collect();
+                        vars.sort();
+                        vars.into_iter()
+                            .map(|(name, value)| {
+                                let (name, value) =
+                                    engine.redact_env_with_profile(&name, &value, profile);
+                                (name.output, serde_json::Value::String(value.output))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+            Some(serde_json::json!({
+                "pid": pid,
+                "cmdline_args": cmdline_args,
+                "env": env,
+            }))
+        })
+        .collect();
+
+    serde_json::json!({
+        "captured_at": chrono::Utc::now().to_rfc3339(),
+        "profile": profile.to_string(),
+        "processes": processes,
+    })
+}
+
 fn run_bundle_inspect(
     global: &GlobalOpts,
     path: &str,
     verify: bool,
     passphrase_arg: &Option<String>,
+    identity: Option<&std::path::Path>,
 ) -> ExitCode {
     use pt_bundle::BundleReader;
 
@@ -3847,36 +6006,39 @@ fn run_bundle_inspect(
     }
 
     let passphrase = resolve_bundle_passphrase(passphrase_arg);
-    let mut reader = match BundleReader::open_with_passphrase(bundle_path, passphrase.as_deref()) {
-        Ok(r) => r,
-        Err(e) => {
-            let error_output = serde_json::json!({
-                "schema_version": SCHEMA_VERSION,
-                "session_id": session_id.0,
-                "generated_at": chrono::Utc::now().to_rfc3339(),
-                "command": "bundle inspect",
-                "status": "error",
-                "error": format!("Failed to open bundle: {}", e),
-            });
-            match global.format {
-                OutputFormat::Md => eprintln!("Error: Failed to open bundle: {}", e),
-                OutputFormat::Jsonl => {
-                    println!("{}", serde_json::to_string(&error_output).unwrap())
+    let mut reader =
+        match BundleReader::open_with_keys(bundle_path, passphrase.as_deref(), identity) {
+            Ok(r) => r,
+            Err(e) => {
+                let error_output = serde_json::json!({
+                    "schema_version": SCHEMA_VERSION,
+                    "session_id": session_id.0,
+                    "generated_at": chrono::Utc::now().to_rfc3339(),
+                    "command": "bundle inspect",
+                    "status": "error",
+                    "error": format!("Failed to open bundle: {}", e),
+                });
+                match global.format {
+                    OutputFormat::Md => eprintln!("Error: Failed to open bundle: {}", e),
+                    OutputFormat::Jsonl => {
+                        println!("{}", serde_json::to_string(&error_output).unwrap())
+                    }
+                    _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
                 }
-                _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
+                return if matches!(
+                    e,
+                    pt_bundle::BundleError::EncryptedBundleRequiresPassphrase
+                        | pt_bundle::BundleError::EncryptedBundleRequiresIdentity
+                        | pt_bundle::BundleError::InvalidIdentity { .. }
+                        | pt_bundle::BundleError::MissingPassphrase
+                        | pt_bundle::BundleError::DecryptionFailed
+                ) {
+                    ExitCode::ArgsError
+                } else {
+                    ExitCode::InternalError
+                };
             }
-            return if matches!(
-                e,
-                pt_bundle::BundleError::EncryptedBundleRequiresPassphrase
-                    | pt_bundle::BundleError::MissingPassphrase
-                    | pt_bundle::BundleError::DecryptionFailed
-            ) {
-                ExitCode::ArgsError
-            } else {
-                ExitCode::InternalError
-            };
-        }
-    };
+        };
 
     // Clone manifest data we need to avoid borrow issues with verify_all
     let bundle_version = reader.manifest().bundle_version.clone();
@@ -3886,6 +6048,8 @@ fn run_bundle_inspect(
     let export_profile = reader.manifest().export_profile;
     let pt_version = reader.manifest().pt_version.clone();
     let description = reader.manifest().description.clone();
+    let capture_allowlist = reader.manifest().capture_allowlist.clone();
+    let base = reader.manifest().base.clone();
     let file_count = reader.manifest().file_count();
     let total_bytes = reader.manifest().total_bytes();
     let files: Vec<_> = reader
@@ -3928,6 +6092,8 @@ fn run_bundle_inspect(
             "export_profile": format!("{}", export_profile),
             "pt_version": pt_version,
             "description": description,
+            "capture_allowlist": capture_allowlist,
+            "base": base,
             "file_count": file_count,
             "total_bytes": total_bytes,
         },
@@ -3941,7 +6107,21 @@ fn run_bundle_inspect(
             println!("  Session: {}", source_session);
             println!("  Created: {}", created_at);
             println!("  Profile: {}", export_profile);
+            if let Some(ref allowlist) = capture_allowlist {
+                println!(
+                    "  Raw capture allowlist: env [{}], args [{}]",
+                    allowlist.env_names.join(", "),
+                    allowlist.cmdline_patterns.join(", ")
+                );
+            }
             println!("  Files: {} ({} bytes)", file_count, total_bytes);
+            if let Some(ref base) = base {
+                println!(
+                    "  Delta since: {} ({} unchanged files in base)",
+                    base.session_id,
+                    base.unchanged.len()
+                );
+            }
             if let Some(ref v) = verification {
                 if v["verified"].as_bool() == Some(true) {
                     println!("  Verification: PASSED");
@@ -3962,8 +6142,10 @@ fn run_bundle_extract(
     global: &GlobalOpts,
     path: &str,
     output_arg: &Option<String>,
+    base_arg: &Option<String>,
     verify: bool,
     passphrase_arg: &Option<String>,
+    identity: Option<&std::path::Path>,
 ) -> ExitCode {
     use pt_bundle::BundleReader;
 
@@ -3988,36 +6170,67 @@ fn run_bundle_extract(
     }
 
     let passphrase = resolve_bundle_passphrase(passphrase_arg);
-    let mut reader = match BundleReader::open_with_passphrase(bundle_path, passphrase.as_deref()) {
-        Ok(r) => r,
-        Err(e) => {
+    let mut reader =
+        match BundleReader::open_with_keys(bundle_path, passphrase.as_deref(), identity) {
+            Ok(r) => r,
+            Err(e) => {
+                let error_output = serde_json::json!({
+                    "schema_version": SCHEMA_VERSION,
+                    "session_id": session_id.0,
+                    "generated_at": chrono::Utc::now().to_rfc3339(),
+                    "command": "bundle extract",
+                    "status": "error",
+                    "error": format!("Failed to open bundle: {}", e),
+                });
+                match global.format {
+                    OutputFormat::Md => eprintln!("Error: Failed to open bundle: {}", e),
+                    OutputFormat::Jsonl => {
+                        println!("{}", serde_json::to_string(&error_output).unwrap())
+                    }
+                    _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
+                }
+                return if matches!(
+                    e,
+                    pt_bundle::BundleError::EncryptedBundleRequiresPassphrase
+                        | pt_bundle::BundleError::EncryptedBundleRequiresIdentity
+                        | pt_bundle::BundleError::InvalidIdentity { .. }
+                        | pt_bundle::BundleError::MissingPassphrase
+                        | pt_bundle::BundleError::DecryptionFailed
+                ) {
+                    ExitCode::ArgsError
+                } else {
+                    ExitCode::InternalError
+                };
+            }
+        };
+
+    // Resolve a delta bundle against its base so unchanged files are extracted too
+    if let Some(base_path) = base_arg {
+        let resolved = BundleReader::open_with_keys(
+            std::path::Path::new(base_path),
+            passphrase.as_deref(),
+            identity,
+        )
+        .and_then(|mut base| reader.resolve_base(&mut base));
+        if let Err(e) = resolved {
             let error_output = serde_json::json!({
                 "schema_version": SCHEMA_VERSION,
                 "session_id": session_id.0,
                 "generated_at": chrono::Utc::now().to_rfc3339(),
                 "command": "bundle extract",
                 "status": "error",
-                "error": format!("Failed to open bundle: {}", e),
+                "error": format!("Failed to resolve base bundle: {}", e),
             });
             match global.format {
-                OutputFormat::Md => eprintln!("Error: Failed to open bundle: {}", e),
+                OutputFormat::Md => eprintln!("Error: Failed to resolve base bundle: {}", e),
                 OutputFormat::Jsonl => {
                     println!("{}", serde_json::to_string(&error_output).unwrap())
                 }
                 _ => println!("{}", serde_json::to_string_pretty(&error_output).unwrap()),
             }
-            return if matches!(
-                e,
-                pt_bundle::BundleError::EncryptedBundleRequiresPassphrase
-                    | pt_bundle::BundleError::MissingPassphrase
-                    | pt_bundle::BundleError::DecryptionFailed
-            ) {
-                ExitCode::ArgsError
-            } else {
-                ExitCode::InternalError
-            };
+            return ExitCode::ArgsError;
         }
-    };
+    }
 
     // Determine output directory
     let output_dir = match output_arg {
@@ -4048,8 +6261,12 @@ fn run_bundle_extract(
         return ExitCode::InternalError;
     }
 
-    // Get list of files to extract
-    let file_paths: Vec<String> = reader.files().iter().map(|f| f.path.clone()).collect();
+    // Get list of files to extract (a resolved delta includes its base files)
+    let file_paths: Vec<String> = if reader.is_resolved() {
+        reader.all_files().iter().map(|f| f.path.clone()).collect()
+    } else {
+        reader.files().iter().map(|f| f.path.clone()).collect()
+    };
     let mut extracted = 0;
     let mut errors = Vec::new();
 
@@ -4094,6 +6311,8 @@ fn run_bundle_extract(
         "output_dir": output_dir.display().to_string(),
         "extracted": extracted,
         "total": file_paths.len(),
+        "delta": reader.is_delta(),
+        "base_resolved": reader.is_delta() && reader.is_resolved(),
         "errors": errors,
     });
 
@@ -4123,64 +6342,212 @@ fn run_bundle_extract(
     }
 }
 
-fn run_report(global: &GlobalOpts, _args: &ReportArgs) -> ExitCode {
-    output_stub(global, "report", "Report generation not yet implemented");
-    ExitCode::Clean
-}
-
-fn run_check(global: &GlobalOpts, args: &CheckArgs) -> ExitCode {
-    let session_id = SessionId::new();
-    let check_all = args.all || (!args.priors && !args.policy && !args.check_capabilities);
-
-    let mut results: Vec<serde_json::Value> = Vec::new();
-    let mut all_ok = true;
+#[cfg(feature = "report")]
+fn run_report(global: &GlobalOpts, args: &ReportArgs) -> ExitCode {
+    use pt_report::{ReportConfig, ReportGenerator};
 
-    // Build config options from global opts
-    let options = ConfigOptions {
-        config_dir: global.config.as_ref().map(PathBuf::from),
-        priors_path: None,
-        policy_path: None,
+    let theme = match parse_report_theme(&args.theme) {
+        Some(theme) => theme,
+        None => {
+            eprintln!(
+                "report: invalid theme '{}', use: light, dark, auto",
+                args.theme
+            );
+            return ExitCode::ArgsError;
+        }
     };
 
-    // Check priors
-    if check_all || args.priors {
-        match load_config(&options) {
-            Ok(config) => {
-                let snapshot = config.snapshot();
-                results.push(serde_json::json!({
-                    "check": "priors",
-                    "status": "ok",
-                    "source": snapshot.priors_path.as_ref().map(|p| p.display().to_string()),
-                    "using_defaults": snapshot.priors_path.is_none(),
-                    "schema_version": snapshot.priors_schema_version,
-                }));
+    let config = ReportConfig::new()
+        .with_theme(theme)
+        .with_galaxy_brain(args.include_ledger);
+    let generator = ReportGenerator::new(config);
+
+    let (source, html_result) = if let Some(ref bundle_path) = args.bundle {
+        let path = std::path::Path::new(bundle_path);
+        if !path.exists() {
+            eprintln!("report: bundle file not found: {}", bundle_path);
+            return ExitCode::ArgsError;
+        }
+        let mut reader = match pt_bundle::BundleReader::open(path) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("report: failed to read bundle: {}", e);
+                return ExitCode::InternalError;
             }
+        };
+        (
+            serde_json::json!({ "bundle": bundle_path }),
+            generator.generate_from_bundle(&mut reader),
+        )
+    } else {
+        let store = match SessionStore::from_env() {
+            Ok(s) => s,
             Err(e) => {
-                all_ok = false;
-                results.push(serde_json::json!({
-                    "check": "priors",
-                    "status": "error",
-                    "error": e.to_string(),
-                }));
+                eprintln!("report: session store error: {}", e);
+                return ExitCode::InternalError;
             }
-        }
-    }
+        };
 
-    // Check policy (using same config load - already validated)
-    if (check_all || args.policy) && all_ok {
-        // Already loaded above if priors was checked
-        match load_config(&options) {
-            Ok(config) => {
-                let snapshot = config.snapshot();
-                results.push(serde_json::json!({
-                    "check": "policy",
-                    "status": "ok",
-                    "source": snapshot.policy_path.as_ref().map(|p| p.display().to_string()),
-                    "using_defaults": snapshot.policy_path.is_none(),
-                    "schema_version": snapshot.policy_schema_version,
-                }));
-            }
-            Err(e) => {
+        let session_id = if let Some(ref raw) = args.session {
+            match SessionId::parse(raw) {
+                Some(sid) => sid,
+                None => {
+                    eprintln!("report: invalid session ID '{}'", raw);
+                    return ExitCode::ArgsError;
+                }
+            }
+        } else {
+            // Default to the latest session
+            let options = ListSessionsOptions {
+                limit: Some(1),
+                ..Default::default()
+            };
+            match store.list_sessions(&options) {
+                Ok(sessions) if !sessions.is_empty() => SessionId(sessions[0].session_id.clone()),
+                Ok(_) => {
+                    eprintln!("report: no sessions found");
+                    return ExitCode::ArgsError;
+                }
+                Err(e) => {
+                    eprintln!("report: failed to list sessions: {}", e);
+                    return ExitCode::InternalError;
+                }
+            }
+        };
+
+        let handle = match store.open(&session_id) {
+            Ok(h) => h,
+            Err(e) => {
+                eprintln!("report: session not found: {}", e);
+                return ExitCode::ArgsError;
+            }
+        };
+        (
+            serde_json::json!({ "session_id": session_id.0 }),
+            generate_report_from_session(&generator, &handle),
+        )
+    };
+
+    let html = match html_result {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("report: failed to generate report: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    let Some(ref out_path) = args.output else {
+        print!("{}", html);
+        return ExitCode::Clean;
+    };
+
+    if let Err(e) = std::fs::write(out_path, &html) {
+        eprintln!("report: failed to write {}: {}", out_path, e);
+        return ExitCode::IoError;
+    }
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let mut response = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "report",
+                "status": "success",
+                "output_path": out_path,
+                "size_bytes": html.len(),
+                "format": "html",
+                "include_ledger": args.include_ledger,
+            });
+            if let (Some(dst), Some(src)) = (response.as_object_mut(), source.as_object()) {
+                dst.extend(src.clone());
+            }
+            println!("{}", format_structured_output(global, response));
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("Report written to: {}", out_path);
+        }
+    }
+
+    ExitCode::Clean
+}
+
+#[cfg(not(feature = "report"))]
+fn run_report(global: &GlobalOpts, _args: &ReportArgs) -> ExitCode {
+    output_stub(
+        global,
+        "report",
+        "Report generation requires the `report` feature (build with --features report)",
+    );
+    ExitCode::CapabilityError
+}
+
+/// Parse a report theme name (light, dark, auto).
+#[cfg(feature = "report")]
+fn parse_report_theme(raw: &str) -> Option<pt_report::ReportTheme> {
+    use pt_report::ReportTheme;
+
+    match raw.to_lowercase().as_str() {
+        "light" => Some(ReportTheme::Light),
+        "dark" => Some(ReportTheme::Dark),
+        "auto" | "" => Some(ReportTheme::Auto),
+        _ => None,
+    }
+}
+
+fn run_check(global: &GlobalOpts, args: &CheckArgs) -> ExitCode {
+    let session_id = SessionId::new();
+    let check_all = args.all || (!args.priors && !args.policy && !args.check_capabilities);
+
+    let mut results: Vec<serde_json::Value> = Vec::new();
+    let mut all_ok = true;
+
+    // Build config options from global opts
+    let options = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        priors_path: None,
+        policy_path: None,
+    };
+
+    // Check priors
+    if check_all || args.priors {
+        match load_config(&options) {
+            Ok(config) => {
+                let snapshot = config.snapshot();
+                results.push(serde_json::json!({
+                    "check": "priors",
+                    "status": "ok",
+                    "source": snapshot.priors_path.as_ref().map(|p| p.display().to_string()),
+                    "using_defaults": snapshot.priors_path.is_none(),
+                    "schema_version": snapshot.priors_schema_version,
+                }));
+            }
+            Err(e) => {
+                all_ok = false;
+                results.push(serde_json::json!({
+                    "check": "priors",
+                    "status": "error",
+                    "error": e.to_string(),
+                }));
+            }
+        }
+    }
+
+    // Check policy (using same config load - already validated)
+    if (check_all || args.policy) && all_ok {
+        // Already loaded above if priors was checked
+        match load_config(&options) {
+            Ok(config) => {
+                let snapshot = config.snapshot();
+                results.push(serde_json::json!({
+                    "check": "policy",
+                    "status": "ok",
+                    "source": snapshot.policy_path.as_ref().map(|p| p.display().to_string()),
+                    "using_defaults": snapshot.policy_path.is_none(),
+                    "schema_version": snapshot.policy_schema_version,
+                }));
+            }
+            Err(e) => {
                 all_ok = false;
                 results.push(serde_json::json!({
                     "check": "policy",
@@ -4195,7 +6562,7 @@ fn run_check(global: &GlobalOpts, args: &CheckArgs) -> ExitCode {
     if check_all || args.check_capabilities {
         // Check if we have a capabilities manifest
         let has_capabilities = global.capabilities.is_some();
-        results.push(serde_json::json!({
+        let mut check = serde_json::json!({
             "check": "capabilities",
             "status": if has_capabilities { "ok" } else { "info" },
             "manifest": global.capabilities.as_ref(),
@@ -4204,7 +6571,16 @@ fn run_check(global: &GlobalOpts, args: &CheckArgs) -> ExitCode {
             } else {
                 "No capabilities manifest provided (will use auto-detection)"
             },
-        }));
+        });
+        // hidepid makes scans silently incomplete
+        let visibility = pt_core::collect::ProcVisibility::detect();
+        if !visibility.is_full() {
+            check["status"] = serde_json::json!("warn");
+            check["note"] = serde_json::json!(visibility.describe());
+            check["remediation"] = serde_json::json!(visibility_remediation(&visibility));
+        }
+        check["proc_visibility"] = visibility_json(&visibility);
+        results.push(check);
     }
 
     let response = serde_json::json!({
@@ -4233,12 +6609,18 @@ fn run_check(global: &GlobalOpts, args: &CheckArgs) -> ExitCode {
                 let symbol = match status {
                     "ok" => "✓",
                     "info" => "ℹ",
+                    "warn" => "⚠",
                     _ => "✗",
                 };
                 println!("{} {}: {}", symbol, check, status);
                 if let Some(note) = result.get("note").and_then(|v| v.as_str()) {
                     println!("  {}", note);
                 }
+                if let Some(steps) = result.get("remediation").and_then(|v| v.as_array()) {
+                    for step in steps.iter().filter_map(|s| s.as_str()) {
+                        println!("  → {}", step);
+                    }
+                }
                 if let Some(error) = result.get("error").and_then(|v| v.as_str()) {
                     println!("  Error: {}", error);
                 }
@@ -4690,6 +7072,7 @@ fn run_agent(global: &GlobalOpts, args: &AgentArgs) -> ExitCode {
         AgentCommands::Sessions(args) => run_agent_sessions(global, args),
         AgentCommands::ListPriors(args) => run_agent_list_priors(global, args),
         AgentCommands::Inbox(args) => run_agent_inbox(global, args),
+        AgentCommands::Spare(args) => run_agent_spare(global, args),
         AgentCommands::Tail(args) => run_agent_tail(global, args),
         AgentCommands::Watch(args) => run_agent_watch(global, args),
         AgentCommands::ExportPriors(args) => run_agent_export_priors(global, args),
@@ -4752,103 +7135,303 @@ fn parse_fleet_hosts(spec: &str) -> Result<Vec<String>, String> {
     Ok(vec![trimmed.to_string()])
 }
 
+/// Scan fleet hosts over SSH, on the tokio runtime when built with `async`.
+#[cfg(feature = "async")]
+fn run_ssh_scan_fleet(
+    hosts: &[String],
+    config: &SshScanConfig,
+    global_timeout: Option<u64>,
+) -> pt_core::fleet::ssh_scan::FleetScanResult {
+    use pt_core::runtime::AsyncRuntime;
+
+    match AsyncRuntime::new() {
+        Ok(runtime) => {
+            let token = runtime.timeout_token(global_timeout.map(std::time::Duration::from_secs));
+            runtime.block_on(pt_core::fleet::ssh_scan::ssh_scan_fleet_async(
+                hosts, config, &token,
+            ))
+        }
+        Err(e) => {
+            eprintln!("[fleet] async runtime unavailable ({}), using threads", e);
+            ssh_scan_fleet(hosts, config)
+        }
+    }
+}
+
+/// Scan fleet hosts over SSH with the blocking thread pool.
+#[cfg(not(feature = "async"))]
+fn run_ssh_scan_fleet(
+    hosts: &[String],
+    config: &SshScanConfig,
+    _global_timeout: Option<u64>,
+) -> pt_core::fleet::ssh_scan::FleetScanResult {
+    ssh_scan_fleet(hosts, config)
+}
+
 fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitCode {
-    let (hosts, inventory, source_label) =
-        match (&args.hosts, &args.inventory, &args.discovery_config) {
-            (Some(hosts_spec), None, None) => {
-                let hosts = match parse_fleet_hosts(hosts_spec) {
-                    Ok(h) => h,
-                    Err(err) => {
-                        return output_agent_error(global, "fleet plan", &err);
-                    }
-                };
-                (hosts, None, Some("hosts"))
+    // Re-planning reads the scans cached in the source session instead of
+    // reconnecting to the hosts.
+    let cached_scans = match &args.from_session {
+        Some(source) => {
+            let (_, source_dir) = match load_fleet_session(source) {
+                Ok(f) => f,
+                Err(e) => return output_agent_error(global, "fleet plan", &e),
+            };
+            match read_host_scans(&source_dir) {
+                Ok(cached) => Some(cached),
+                Err(e) => {
+                    return output_agent_error(
+                        global,
+                        "fleet plan",
+                        &format!("cannot re-plan fleet session '{}': {}", source, e),
+                    );
+                }
             }
-            (None, Some(path), None) => {
-                let provider = StaticInventoryProvider::from_path(Path::new(path));
-                let inventory = match provider.discover() {
-                    Ok(inv) => inv,
-                    Err(err) => {
-                        return output_agent_error(global, "fleet plan", &err.to_string());
-                    }
-                };
-                let hosts: Vec<String> =
-                    inventory.hosts.iter().map(|h| h.hostname.clone()).collect();
-                if hosts.is_empty() {
-                    return output_agent_error(global, "fleet plan", "inventory contains no hosts");
+        }
+        None => None,
+    };
+
+    let (hosts, inventory, source_label) = match (
+        &cached_scans,
+        &args.hosts,
+        &args.inventory,
+        &args.discovery_config,
+    ) {
+        (Some(cached), None, None, None) => {
+            let hosts: Vec<String> = cached.results.iter().map(|r| r.host.clone()).collect();
+            (hosts, None, Some("fleet_session"))
+        }
+        (None, Some(hosts_spec), None, None) => {
+            let hosts = match parse_fleet_hosts(hosts_spec) {
+                Ok(h) => h,
+                Err(err) => {
+                    return output_agent_error(global, "fleet plan", &err);
+                }
+            };
+            (hosts, None, Some("hosts"))
+        }
+        (None, None, Some(path), None) => {
+            let provider = StaticInventoryProvider::from_path(Path::new(path));
+            let inventory = match provider.discover() {
+                Ok(inv) => inv,
+                Err(err) => {
+                    return output_agent_error(global, "fleet plan", &err.to_string());
                 }
-                (hosts, Some(inventory), Some("inventory"))
+            };
+            let hosts: Vec<String> = inventory.hosts.iter().map(|h| h.hostname.clone()).collect();
+            if hosts.is_empty() {
+                return output_agent_error(global, "fleet plan", "inventory contains no hosts");
             }
-            (None, None, Some(path)) => {
-                let discovery = match FleetDiscoveryConfig::load_from_path(Path::new(path)) {
-                    Ok(cfg) => cfg,
-                    Err(err) => {
-                        return output_agent_error(global, "fleet plan", &err.to_string());
-                    }
-                };
-                let registry = match ProviderRegistry::from_config(&discovery) {
-                    Ok(registry) => registry,
-                    Err(err) => {
-                        return output_agent_error(global, "fleet plan", &err.to_string());
-                    }
-                };
-                let inventory = match registry.discover_all() {
-                    Ok(inv) => inv,
-                    Err(err) => {
-                        return output_agent_error(global, "fleet plan", &err.to_string());
-                    }
-                };
-                let hosts: Vec<String> =
-                    inventory.hosts.iter().map(|h| h.hostname.clone()).collect();
-                if hosts.is_empty() {
-                    return output_agent_error(global, "fleet plan", "discovery found no hosts");
+            (hosts, Some(inventory), Some("inventory"))
+        }
+        (None, None, None, Some(path)) => {
+            let discovery = match FleetDiscoveryConfig::load_from_path(Path::new(path)) {
+                Ok(cfg) => cfg,
+                Err(err) => {
+                    return output_agent_error(global, "fleet plan", &err.to_string());
+                }
+            };
+            let registry = match ProviderRegistry::from_config(&discovery) {
+                Ok(registry) => registry,
+                Err(err) => {
+                    return output_agent_error(global, "fleet plan", &err.to_string());
                 }
-                (hosts, Some(inventory), Some("discovery_config"))
+            };
+            let inventory = match registry.discover_all() {
+                Ok(inv) => inv,
+                Err(err) => {
+                    return output_agent_error(global, "fleet plan", &err.to_string());
+                }
+            };
+            let hosts: Vec<String> = inventory.hosts.iter().map(|h| h.hostname.clone()).collect();
+            if hosts.is_empty() {
+                return output_agent_error(global, "fleet plan", "discovery found no hosts");
             }
-            (None, None, None) => {
-                return output_agent_error(
+            (hosts, Some(inventory), Some("discovery_config"))
+        }
+        (None, None, None, None) => {
+            return output_agent_error(
+                global,
+                "fleet plan",
+                "one of --hosts, --inventory, --discovery-config, or --from-session is required",
+            );
+        }
+        _ => {
+            return output_agent_error(
                     global,
                     "fleet plan",
-                    "either --hosts, --inventory, or --discovery-config is required",
+                    "--hosts, --inventory, --discovery-config, and --from-session are mutually exclusive",
                 );
-            }
-            _ => {
-                return output_agent_error(
-                    global,
-                    "fleet plan",
-                    "--hosts, --inventory, and --discovery-config are mutually exclusive",
+        }
+    };
+
+    // Inventory host groups (`group` key, or a `group`/`role` tag), or the
+    // groups recorded with the cached scans.
+    let (host_groups, group_defs): (HashMap<String, String>, HashMap<String, HostGroup>) =
+        match (&cached_scans, &inventory) {
+            (Some(cached), _) => (cached.host_groups(), cached.index.groups.clone()),
+            (None, Some(inv)) => (
+                inv.hosts
+                    .iter()
+                    .filter_map(|h| Some((h.hostname.clone(), h.group()?.to_string())))
+                    .collect(),
+                inv.groups.clone(),
+            ),
+            (None, None) => (HashMap::new(), HashMap::new()),
+        };
+    // Cached agentless scans hold only ps output and always need local inference.
+    let agentless = args.agentless || cached_scans.as_ref().is_some_and(|c| c.index.agentless);
+
+    // Agentless hosts only return ps output; classify it with local priors,
+    // or with their group's priors from `<config_dir>/groups/<group>.json`.
+    let mut warnings: Vec<String> = Vec::new();
+    let mut local_inference: Option<LocalInference> = None;
+    let mut group_inference: HashMap<String, LocalInference> = HashMap::new();
+    let mut group_priors_used = serde_json::Map::new();
+    if agentless || !host_groups.is_empty() {
+        use pt_core::config::load_group_priors;
+        use pt_core::config::pooling::group_priors;
+        use pt_math::hierarchical::HostProfile;
+
+        let config = match load_config(&config_options(global)) {
+            Ok(config) => Some(config),
+            Err(e) if agentless => {
+                return output_agent_error(global, "fleet plan", &format!("config error: {}", e));
+            }
+            Err(_) => None,
+        };
+        if let Some(config) = config {
+            let groups: BTreeSet<&String> = host_groups.values().collect();
+            for group in groups {
+                let (priors, path) = match load_group_priors(&config.config_dir, group) {
+                    Ok(Some(loaded)) => loaded,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warnings.push(format!("group '{}' priors ignored: {}", group, e));
+                        continue;
+                    }
+                };
+                if !agentless {
+                    warnings.push(format!(
+                        "group '{}' priors ({}) apply only with --agentless",
+                        group,
+                        path.display()
+                    ));
+                    continue;
+                }
+                // The group's own host profile, else --host-profile.
+                let profile_name = group_defs
+                    .get(group)
+                    .and_then(|g| g.host_profile.as_deref())
+                    .or(args.host_profile.as_deref());
+                let profile = profile_name.and_then(|name| {
+                    let profile = HostProfile::parse(name);
+                    if profile.is_none() {
+                        warnings.push(format!(
+                            "group '{}': unknown host profile '{}', using group priors as-is",
+                            group, name
+                        ));
+                    }
+                    profile
+                });
+                group_priors_used.insert(
+                    group.clone(),
+                    serde_json::json!({
+                        "path": path.display().to_string(),
+                        "host_profile": profile.map(|p| p.to_string()),
+                        "mode": if profile.is_some() { "shrink" } else { "replace" },
+                    }),
+                );
+                group_inference.insert(
+                    group.clone(),
+                    LocalInference::new(
+                        group_priors(&config.priors, &priors, profile),
+                        config.policy.clone(),
+                    ),
                 );
             }
-        };
+            if agentless {
+                local_inference = Some(LocalInference::new(config.priors, config.policy));
+            }
+        }
+    }
 
-    // Perform SSH scanning of remote hosts
-    let ssh_config = SshScanConfig {
-        connect_timeout: args.timeout.min(30),
-        command_timeout: args.timeout,
-        parallel: args.parallel as usize,
-        continue_on_error: args.continue_on_error,
-        ..SshScanConfig::default()
-    };
+    let scan_result = match cached_scans {
+        Some(cached) => {
+            eprintln!(
+                "[fleet] Re-planning {} hosts from cached scans (agentless={})...",
+                cached.results.len(),
+                cached.index.agentless,
+            );
+            let successful = cached.results.iter().filter(|r| r.success).count();
+            FleetScanResult {
+                total_hosts: cached.results.len(),
+                successful,
+                failed: cached.results.len() - successful,
+                results: cached.results,
+                duration_ms: 0,
+            }
+        }
+        None => {
+            // Perform SSH scanning of remote hosts
+            let ssh_config = SshScanConfig {
+                connect_timeout: args.timeout.min(30),
+                command_timeout: args.timeout,
+                parallel: args.parallel as usize,
+                continue_on_error: args.continue_on_error,
+                agentless: args.agentless,
+                ..SshScanConfig::default()
+            };
 
-    eprintln!(
-        "[fleet] Scanning {} hosts (parallel={}, timeout={}s)...",
-        hosts.len(),
-        ssh_config.parallel,
-        ssh_config.command_timeout,
-    );
+            eprintln!(
+                "[fleet] Scanning {} hosts (parallel={}, timeout={}s, agentless={})...",
+                hosts.len(),
+                ssh_config.parallel,
+                ssh_config.command_timeout,
+                ssh_config.agentless,
+            );
 
-    let scan_result = ssh_scan_fleet(&hosts, &ssh_config);
+            let scan_result = run_ssh_scan_fleet(&hosts, &ssh_config, global.timeout);
 
-    eprintln!(
-        "[fleet] Scan complete: {}/{} succeeded in {}ms",
-        scan_result.successful, scan_result.total_hosts, scan_result.duration_ms,
-    );
+            eprintln!(
+                "[fleet] Scan complete: {}/{} succeeded in {}ms",
+                scan_result.successful, scan_result.total_hosts, scan_result.duration_ms,
+            );
+            scan_result
+        }
+    };
 
-    // Convert scan results to fleet session inputs
+    // Convert scan results to fleet session inputs, counting signature hits
+    // on every scanned process.
+    let mut signature_db = SignatureDatabase::with_defaults();
+    if let Some(pack) = pt_core::signature_cli::load_signature_pack() {
+        for signature in pack.signatures {
+            let _ = signature_db.add(signature);
+        }
+    }
+    if let Some(user_schema) = pt_core::signature_cli::load_user_signatures() {
+        for signature in user_schema.signatures {
+            let _ = signature_db.add(signature);
+        }
+    }
     let host_inputs: Vec<HostInput> = scan_result
         .results
         .iter()
-        .map(scan_result_to_host_input)
+        .map(|result| {
+            let group = host_groups.get(&result.host);
+            let inference = group
+                .and_then(|g| group_inference.get(g))
+                .or(local_inference.as_ref());
+            let mut input = match inference {
+                Some(inference) => inference.host_input(result),
+                None => scan_result_to_host_input(result),
+            };
+            input.group = group.cloned();
+            if let Some(scan) = &result.scan {
+                input.signature_hits = count_signature_hits(&signature_db, &scan.processes);
+            }
+            input
+        })
         .collect();
 
     let fleet_session_id = SessionId::new();
@@ -4859,7 +7442,6 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
         args.max_fdr,
     );
 
-    let mut warnings: Vec<String> = Vec::new();
     for r in &scan_result.results {
         if !r.success {
             warnings.push(format!(
@@ -4890,7 +7472,19 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
     })();
 
     let session_dir = match &persist_result {
-        Ok(dir) => Some(dir.display().to_string()),
+        Ok(dir) => {
+            // Keep the scans so the session can be re-planned with --from-session.
+            if let Err(e) = write_host_scans(
+                dir,
+                &scan_result.results,
+                &host_groups,
+                &group_defs,
+                agentless,
+            ) {
+                warnings.push(format!("failed to cache host scans: {}", e));
+            }
+            Some(dir.display().to_string())
+        }
         Err(e) => {
             warnings.push(format!("failed to persist fleet session: {}", e));
             None
@@ -4915,10 +7509,12 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
             "hosts_spec": args.hosts,
             "inventory_path": args.inventory,
             "discovery_config": args.discovery_config,
+            "from_session": args.from_session,
             "hosts": hosts,
             "parallel": args.parallel,
             "timeout_secs": args.timeout,
             "continue_on_error": args.continue_on_error,
+            "agentless": args.agentless,
             "host_profile": args.host_profile,
             "label": args.label,
             "max_fdr": args.max_fdr,
@@ -4931,6 +7527,7 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
             })
         }),
         "inventory_source": source_label,
+        "group_priors": group_priors_used,
         "fleet_session": fleet_session,
     });
 
@@ -4950,6 +7547,12 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
                 scan_result.duration_ms,
             );
             println!("Fleet session: {}", fleet_session_id.0);
+            for group in &fleet_session.aggregate.groups {
+                println!(
+                    "  Group {}: {} hosts, {} candidates",
+                    group.group, group.host_count, group.total_candidates
+                );
+            }
             if !warnings.is_empty() {
                 println!();
                 println!("Warnings:");
@@ -4963,27 +7566,6 @@ fn run_agent_fleet_plan(global: &GlobalOpts, args: &AgentFleetPlanArgs) -> ExitC
     ExitCode::Clean
 }
 
-fn load_fleet_session(
-    fleet_session_id: &str,
-) -> Result<(pt_core::session::fleet::FleetSession, PathBuf), String> {
-    let store = SessionStore::from_env().map_err(|e| format!("session store error: {}", e))?;
-    let sid = SessionId(fleet_session_id.to_string());
-    let handle = store
-        .open(&sid)
-        .map_err(|e| format!("cannot open fleet session '{}': {}", fleet_session_id, e))?;
-    let fleet_path = handle.dir.join("fleet.json");
-    let content = std::fs::read_to_string(&fleet_path).map_err(|e| {
-        format!(
-            "cannot read fleet session '{}': {}",
-            fleet_path.display(),
-            e
-        )
-    })?;
-    let fleet: pt_core::session::fleet::FleetSession =
-        serde_json::from_str(&content).map_err(|e| format!("parse error: {}", e))?;
-    Ok((fleet, handle.dir))
-}
-
 fn run_agent_fleet_apply(global: &GlobalOpts, args: &AgentFleetApplyArgs) -> ExitCode {
     let (fleet, session_dir) = match load_fleet_session(&args.fleet_session) {
         Ok(f) => f,
@@ -5216,34 +7798,98 @@ fn build_fleet_top_offenders(
         .collect()
 }
 
-fn build_host_comparison(
+fn build_fleet_workload_offenders(
     fleet: &pt_core::session::fleet::FleetSession,
     profile: FleetReportProfile,
 ) -> Vec<serde_json::Value> {
-    let mut rows: Vec<serde_json::Value> = fleet
-        .hosts
+    let redact = |name: &str, prefix: &str| match profile {
+        FleetReportProfile::Forensic | FleetReportProfile::Safe => name.to_string(),
+        FleetReportProfile::Minimal => deterministic_token(prefix, name),
+    };
+
+    fleet
+        .aggregate
+        .workload_offenders
         .iter()
-        .map(|h| {
-            let process_count = h.process_count.max(1);
+        .enumerate()
+        .map(|(idx, w)| {
+            let mut hosts: Vec<String> = w
+                .hosts
+                .iter()
+                .map(|h| redact_host_id_for_profile(h, profile))
+                .collect();
+            hosts.sort();
+            hosts.dedup();
+            let signatures: Vec<String> = w
+                .signatures
+                .iter()
+                .map(|s| redact_signature_for_profile(s, profile))
+                .collect();
+            serde_json::json!({
+                "rank": idx + 1,
+                "namespace": redact(&w.namespace, "ns_"),
+                "workload": w.workload.as_deref().map(|name| redact(name, "workload_")),
+                "host_count": w.host_count,
+                "total_instances": w.total_instances,
+                "kill_count": w.kill_count,
+                "hosts": hosts,
+                "signatures": signatures,
+            })
+        })
+        .collect()
+}
+
+fn redact_group_for_profile(group: &str, profile: FleetReportProfile) -> String {
+    match profile {
+        FleetReportProfile::Forensic | FleetReportProfile::Safe => group.to_string(),
+        FleetReportProfile::Minimal => deterministic_token("group_", group),
+    }
+}
+
+/// Candidate density, kill rate, risk index and risk tier of a host (or a
+/// group of hosts).
+fn fleet_risk(
+    process_count: u32,
+    candidate_count: u32,
+    kill_count: u32,
+    mean_candidate_score: f64,
+) -> (f64, f64, f64, &'static str) {
+    let candidate_density = candidate_count as f64 / process_count.max(1) as f64;
+    let kill_rate = if candidate_count == 0 {
+        0.0
+    } else {
+        kill_count as f64 / candidate_count as f64
+    };
+    let risk_index = candidate_density * 100.0 + mean_candidate_score * 10.0 + kill_rate * 5.0;
+    let risk_tier = if risk_index >= 35.0 {
+        "high"
+    } else if risk_index >= 15.0 {
+        "medium"
+    } else {
+        "low"
+    };
+    (candidate_density, kill_rate, risk_index, risk_tier)
+}
+
+fn build_host_comparison(
+    fleet: &pt_core::session::fleet::FleetSession,
+    profile: FleetReportProfile,
+) -> Vec<serde_json::Value> {
+    let mut rows: Vec<serde_json::Value> = fleet
+        .hosts
+        .iter()
+        .map(|h| {
             let candidate_count = h.candidate_count;
             let kill_count = *h.summary.action_counts.get("kill").unwrap_or(&0);
-            let candidate_density = candidate_count as f64 / process_count as f64;
-            let kill_rate = if candidate_count == 0 {
-                0.0
-            } else {
-                kill_count as f64 / candidate_count as f64
-            };
-            let risk_index =
-                candidate_density * 100.0 + h.summary.mean_candidate_score * 10.0 + kill_rate * 5.0;
-            let risk_tier = if risk_index >= 35.0 {
-                "high"
-            } else if risk_index >= 15.0 {
-                "medium"
-            } else {
-                "low"
-            };
+            let (candidate_density, kill_rate, risk_index, risk_tier) = fleet_risk(
+                h.process_count,
+                candidate_count,
+                kill_count,
+                h.summary.mean_candidate_score,
+            );
             serde_json::json!({
                 "host_id": redact_host_id_for_profile(&h.host_id, profile),
+                "group": h.group.as_deref().map(|g| redact_group_for_profile(g, profile)),
                 "process_count": h.process_count,
                 "candidate_count": h.candidate_count,
                 "candidate_density": candidate_density,
@@ -5284,6 +7930,79 @@ fn build_host_comparison(
     rows
 }
 
+/// Risk and top offenders per inventory host group, riskiest first.
+fn build_group_report(
+    fleet: &pt_core::session::fleet::FleetSession,
+    profile: FleetReportProfile,
+) -> Vec<serde_json::Value> {
+    let mut rows: Vec<serde_json::Value> = fleet
+        .aggregate
+        .groups
+        .iter()
+        .map(|g| {
+            let kill_count = *g.action_counts.get("kill").unwrap_or(&0);
+            let (candidate_density, kill_rate, risk_index, risk_tier) = fleet_risk(
+                g.total_processes,
+                g.total_candidates,
+                kill_count,
+                g.mean_candidate_score,
+            );
+            let mut hosts: Vec<String> = g
+                .hosts
+                .iter()
+                .map(|h| redact_host_id_for_profile(h, profile))
+                .collect();
+            hosts.sort();
+            let top_offenders: Vec<serde_json::Value> = g
+                .top_offenders
+                .iter()
+                .map(|o| {
+                    serde_json::json!({
+                        "signature": redact_signature_for_profile(&o.signature, profile),
+                        "host_count": o.host_count,
+                        "total_instances": o.total_instances,
+                        "kill_count": o.kill_count,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "group": redact_group_for_profile(&g.group, profile),
+                "host_count": g.host_count,
+                "hosts": hosts,
+                "process_count": g.total_processes,
+                "candidate_count": g.total_candidates,
+                "candidate_density": candidate_density,
+                "mean_candidate_score": g.mean_candidate_score,
+                "max_candidate_score": g.max_candidate_score,
+                "kill_count": kill_count,
+                "kill_rate": kill_rate,
+                "risk_index": risk_index,
+                "risk_tier": risk_tier,
+                "class_counts": ordered_u32_map(&g.class_counts),
+                "action_counts": ordered_u32_map(&g.action_counts),
+                "top_offenders": top_offenders,
+            })
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        b["risk_index"]
+            .as_f64()
+            .partial_cmp(&a["risk_index"].as_f64())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| {
+                a["group"]
+                    .as_str()
+                    .unwrap_or("")
+                    .cmp(b["group"].as_str().unwrap_or(""))
+            })
+    });
+    for (idx, row) in rows.iter_mut().enumerate() {
+        row["rank"] = serde_json::json!(idx + 1);
+    }
+    rows
+}
+
 fn build_cross_host_anomalies(
     fleet: &pt_core::session::fleet::FleetSession,
     profile: FleetReportProfile,
@@ -5478,7 +8197,9 @@ fn run_agent_fleet_report(global: &GlobalOpts, args: &AgentFleetReportArgs) -> E
     };
 
     let top_offenders = build_fleet_top_offenders(&fleet, profile);
+    let workload_offenders = build_fleet_workload_offenders(&fleet, profile);
     let host_comparison = build_host_comparison(&fleet, profile);
+    let groups = build_group_report(&fleet, profile);
     let cross_host_anomalies = build_cross_host_anomalies(&fleet, profile);
     let safety_budget = build_safety_budget_report(&fleet.safety_budget, profile);
 
@@ -5505,7 +8226,9 @@ fn run_agent_fleet_report(global: &GlobalOpts, args: &AgentFleetReportArgs) -> E
             "safety_budget": safety_budget,
             "hosts": host_comparison.clone(),
             "top_offenders": top_offenders,
+            "workload_offenders": workload_offenders,
             "host_comparison": host_comparison,
+            "groups": groups,
             "cross_host_anomalies": cross_host_anomalies,
         },
     });
@@ -5548,6 +8271,49 @@ fn run_agent_fleet_report(global: &GlobalOpts, args: &AgentFleetReportArgs) -> E
                     offender["dominant_action"].as_str().unwrap_or("?"),
                 );
             }
+            if !workload_offenders.is_empty() {
+                println!();
+                println!("## Workload Offenders");
+                for workload in workload_offenders.iter().take(8) {
+                    let name = match workload["workload"].as_str() {
+                        Some(name) => {
+                            format!("{}/{}", workload["namespace"].as_str().unwrap_or("?"), name)
+                        }
+                        None => workload["namespace"].as_str().unwrap_or("?").to_string(),
+                    };
+                    println!(
+                        "  #{} {} — {} hosts, {} candidates ({} kill)",
+                        workload["rank"].as_u64().unwrap_or(0),
+                        name,
+                        workload["host_count"].as_u64().unwrap_or(0),
+                        workload["total_instances"].as_u64().unwrap_or(0),
+                        workload["kill_count"].as_u64().unwrap_or(0),
+                    );
+                }
+            }
+            if !groups.is_empty() {
+                println!();
+                println!("## Host Groups");
+                for group in &groups {
+                    println!(
+                        "  #{} {} — {} hosts, {} candidates (risk: {}, index {:.2})",
+                        group["rank"].as_u64().unwrap_or(0),
+                        group["group"].as_str().unwrap_or("?"),
+                        group["host_count"].as_u64().unwrap_or(0),
+                        group["candidate_count"].as_u64().unwrap_or(0),
+                        group["risk_tier"].as_str().unwrap_or("?"),
+                        group["risk_index"].as_f64().unwrap_or(0.0),
+                    );
+                    for offender in group["top_offenders"].as_array().into_iter().flatten() {
+                        println!(
+                            "      {} — {} instances on {} hosts",
+                            offender["signature"].as_str().unwrap_or("?"),
+                            offender["total_instances"].as_u64().unwrap_or(0),
+                            offender["host_count"].as_u64().unwrap_or(0),
+                        );
+                    }
+                }
+            }
             println!();
             println!("## Per-Host Comparison");
             for host in response["report"]["host_comparison"]
@@ -6419,6 +9185,7 @@ fn run_config_validate(global: &GlobalOpts, path: Option<&String>) -> ExitCode {
                     "path": snapshot.policy_path.as_ref().map(|p| p.display().to_string()),
                     "using_defaults": snapshot.policy_path.is_none(),
                     "schema_version": snapshot.policy_schema_version,
+                    "custom_rules": config.policy.custom_rules.len(),
                 }
             });
 
@@ -6952,6 +9719,7 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
 
     install_daemon_signal_handlers();
     apply_daemon_nice();
+    let io = DaemonIo::new();
 
     let _pid_lock = match try_acquire_daemon_pid_lock() {
         Ok(Some(lock)) => lock,
@@ -6994,11 +9762,23 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
         config.notification_ladder.clone(),
         state_bundle.notifications.clone(),
     );
+    // Warm across ticks and handed to escalation plans via PT_SCAN_CACHE.
+    let scan_cache = pt_core::collect::ScanCache::shared();
+    let scan_cache_path = daemon_scan_cache_path();
+    let psi_info = pt_core::daemon::schedule::detect_psi();
+    #[cfg(feature = "metrics")]
+    let mut daemon_metrics = config
+        .metrics
+        .as_ref()
+        .filter(|m| m.enabled)
+        .and_then(|m| DaemonMetricsExporter::start(global, m));
 
     loop {
         if DAEMON_SIGNALS.should_stop() {
             break;
         }
+        #[cfg(feature = "metrics")]
+        let tick_started = std::time::Instant::now();
 
         if DAEMON_SIGNALS.take_reload() {
             let (reloaded, enabled) = load_daemon_config(global);
@@ -7016,11 +9796,11 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
             }
         }
 
-        let metrics = collect_daemon_metrics();
+        let metrics = collect_daemon_metrics(&scan_cache, &psi_info);
         let now_secs = daemon_now_secs();
 
         if let Some(store) = inbox.as_ref() {
-            daemon_refresh_inbox_notifications(&config, &mut notify_mgr, store, now_secs);
+            daemon_refresh_inbox_notifications(&config, &mut notify_mgr, store, now_secs, &io);
         }
 
         let mut budget_exceeded = false;
@@ -7077,7 +9857,7 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
                 &metrics,
                 &mut |esc_config, fired| {
                     let lock_path = global_lock_path().unwrap_or_else(daemon_lock_path);
-                    let lock = match GlobalLock::try_acquire(&lock_path) {
+                    let lock = match GlobalLock::try_acquire(&lock_path, "daemon") {
                         Ok(lock) => lock,
                         Err(err) => {
                             return pt_core::daemon::escalation::EscalationOutcome {
@@ -7114,7 +9894,27 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
                         pt_core::daemon::escalation::EscalationStatus::Completed
                     ) {
                         let summary = pt_core::daemon::escalation::build_inbox_summary(fired);
-                        match run_daemon_escalation(global, fired, esc_config) {
+                        if let Err(err) = scan_cache
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .save(&scan_cache_path)
+                        {
+                            tracing::warn!("daemon: failed to save scan cache: {}", err);
+                        }
+                        #[cfg(feature = "metrics")]
+                        let scan_started = std::time::Instant::now();
+                        let escalation =
+                            run_daemon_escalation(global, fired, esc_config, &scan_cache_path, &io);
+                        #[cfg(feature = "metrics")]
+                        {
+                            if let Some(exporter) = daemon_metrics.as_ref() {
+                                exporter.observe_escalation(
+                                    escalation.as_ref().ok(),
+                                    scan_started.elapsed().as_secs_f64(),
+                                );
+                            }
+                        }
+                        match escalation {
                             Ok(result) => {
                                 outcome.session_id = Some(result.session_id.clone());
                                 if let Some(store) = escalation_inbox.as_mut() {
@@ -7123,10 +9923,16 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
                                         summary.clone(),
                                         summary,
                                         result.candidates_found,
-                                    );
-                                    let _ = store.add(&item);
+                                    )
+                                    .with_targets(result.targets);
+                                    // Repeat escalations about the same processes fold
+                                    // into the open item and do not notify again.
+                                    let is_news = store
+                                        .add_or_merge(&item)
+                                        .map(|merge| merge.is_news())
+                                        .unwrap_or(true);
                                     // Emit L1 notification immediately for new inbox item.
-                                    if config.notifications.enabled {
+                                    if config.notifications.enabled && is_news {
                                         daemon_submit_inbox_item_trigger(
                                             &config,
                                             &mut notify_mgr,
@@ -7135,7 +9941,7 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
                                         );
                                         let notifs = notify_mgr.flush(now_secs);
                                         for n in notifs {
-                                            daemon_deliver_notification(&config, &n);
+                                            daemon_deliver_notification(&config, &n, &io);
                                         }
                                     }
                                 }
@@ -7152,12 +9958,39 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
                     outcome
                 },
             );
+            #[cfg(feature = "metrics")]
+            {
+                if let (Some(exporter), Some(escalation)) =
+                    (daemon_metrics.as_ref(), outcome.escalation.as_ref())
+                {
+                    exporter
+                        .metrics
+                        .record_escalation(daemon_escalation_status_label(escalation.status));
+                }
+            }
             let _ = outcome;
             state_bundle
                 .daemon
                 .record_event(pt_core::daemon::DaemonEventType::TickCompleted, "tick");
         }
 
+        #[cfg(feature = "metrics")]
+        {
+            if let Some(exporter) = daemon_metrics.as_mut() {
+                let outcome = if budget_exceeded {
+                    "budget_exceeded"
+                } else {
+                    "normal"
+                };
+                exporter.observe_tick(
+                    &metrics,
+                    &state_bundle.daemon,
+                    outcome,
+                    tick_started.elapsed().as_secs_f64(),
+                );
+            }
+        }
+
         // Persist notification escalation state.
         state_bundle.notifications = notify_mgr.persisted_state();
         let _ = save_daemon_state(&state_path, &state_bundle);
@@ -7166,7 +9999,15 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
             break;
         }
 
-        if daemon_sleep_with_interrupt(config.tick_interval_secs) {
+        let mut interval = state_bundle
+            .daemon
+            .schedule
+            .next_interval_secs(config.tick_interval_secs);
+        if budget_exceeded {
+            // Never tick faster while over the overhead budget.
+            interval = interval.max(config.tick_interval_secs);
+        }
+        if io.sleep(interval) {
             continue;
         }
     }
@@ -7194,6 +10035,140 @@ fn run_daemon_foreground(global: &GlobalOpts, config: &pt_core::daemon::DaemonCo
     ExitCode::Clean
 }
 
+/// Metrics endpoint state owned by the daemon loop.
+#[cfg(feature = "metrics")]
+struct DaemonMetricsExporter {
+    metrics: pt_core::daemon::metrics::DaemonMetrics,
+    alpha_policy: Option<pt_core::decision::AlphaInvestingPolicy>,
+    alpha_store: pt_core::decision::AlphaInvestingStore,
+    refresh_calibration_every: std::time::Duration,
+    last_calibration: Option<std::time::Instant>,
+    outcomes: pt_core::daemon::metrics::ActionOutcomeTail,
+    _server: pt_core::daemon::metrics::MetricsServer,
+}
+
+#[cfg(feature = "metrics")]
+impl DaemonMetricsExporter {
+    fn start(
+        global: &GlobalOpts,
+        config: &pt_core::daemon::metrics::MetricsConfig,
+    ) -> Option<Self> {
+        let metrics = match pt_core::daemon::metrics::DaemonMetrics::new() {
+            Ok(metrics) => metrics,
+            Err(err) => {
+                eprintln!("daemon: failed to create metrics: {}", err);
+                return None;
+            }
+        };
+        let server = match pt_core::daemon::metrics::MetricsServer::start(config, metrics.clone()) {
+            Ok(server) => server,
+            Err(err) => {
+                eprintln!("daemon: {}", err);
+                return None;
+            }
+        };
+        let alpha_policy = load_config(&config_options(global))
+            .ok()
+            .and_then(|c| pt_core::decision::AlphaInvestingPolicy::from_policy(&c.policy).ok());
+        Some(Self {
+            metrics,
+            alpha_policy,
+            alpha_store: pt_core::decision::AlphaInvestingStore::new(&resolve_config_dir(global)),
+            refresh_calibration_every: std::time::Duration::from_secs(
+                config.calibration_refresh_secs,
+            ),
+            last_calibration: None,
+            outcomes: pt_core::daemon::metrics::ActionOutcomeTail::seeded(
+                &daemon_metrics_sessions(),
+            ),
+            _server: server,
+        })
+    }
+
+    /// Record the plan run behind a completed escalation.
+    fn observe_escalation(&self, result: Option<&DaemonEscalationResult>, duration_secs: f64) {
+        match result {
+            Some(result) => {
+                self.metrics.record_scan("success", duration_secs);
+                self.metrics.set_candidates(
+                    result
+                        .classifications
+                        .iter()
+                        .map(|(class, count)| (class.as_str(), *count)),
+                );
+            }
+            None => {
+                self.metrics.record_scan("failed", duration_secs);
+                self.metrics.record_error("escalation_plan");
+            }
+        }
+    }
+
+    /// Refresh tick gauges, new action outcomes, the FDR budget and (at most
+    /// every `calibration_refresh_secs`) shadow-mode calibration.
+    fn observe_tick(
+        &mut self,
+        tick: &pt_core::daemon::TickMetrics,
+        state: &pt_core::daemon::DaemonState,
+        outcome: &str,
+        duration_secs: f64,
+    ) {
+        self.metrics.update_from_tick(tick, state);
+        self.metrics.record_tick_duration(outcome, duration_secs);
+
+        for (status, count) in self.outcomes.poll(&daemon_metrics_sessions()) {
+            self.metrics.record_actions(&status, count);
+        }
+
+        if let Some(policy) = &self.alpha_policy {
+            let wealth = self.alpha_store.load().ok().flatten().map(|s| s.wealth);
+            self.metrics
+                .update_fdr_budget(wealth.unwrap_or(policy.w0), policy.w0);
+        }
+
+        let calibration_due = !matches!(
+            self.last_calibration,
+            Some(at) if at.elapsed() < self.refresh_calibration_every
+        );
+        if calibration_due {
+            self.last_calibration = Some(std::time::Instant::now());
+            match collect_shadow_observations(&shadow_base_dir(), None) {
+                Ok(observations) => {
+                    let mut engine = ValidationEngine::from_shadow_observations(&observations, 0.5);
+                    engine.add_feedback(
+                        &pt_core::feedback::read_feedback(&default_telemetry_dir())
+                            .unwrap_or_default(),
+                    );
+                    let report = engine.calibration_report().ok();
+                    self.metrics.update_shadow_calibration(
+                        observations.len(),
+                        report.as_ref().map(|r| &r.metrics),
+                    );
+                }
+                Err(_) => self.metrics.record_error("shadow_read"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn daemon_metrics_sessions() -> Vec<pt_core::session::SessionSummary> {
+    SessionStore::from_env()
+        .and_then(|store| store.list_sessions(&ListSessionsOptions::default()))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "metrics")]
+fn daemon_escalation_status_label(
+    status: pt_core::daemon::escalation::EscalationStatus,
+) -> &'static str {
+    match status {
+        pt_core::daemon::escalation::EscalationStatus::Completed => "completed",
+        pt_core::daemon::escalation::EscalationStatus::Deferred => "deferred",
+        pt_core::daemon::escalation::EscalationStatus::Failed => "failed",
+    }
+}
+
 #[cfg(feature = "daemon")]
 fn daemon_now_secs() -> f64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -7210,9 +10185,10 @@ fn parse_rfc3339_secs(s: &str) -> Option<f64> {
         .map(|dt| dt.timestamp_millis() as f64 / 1000.0)
 }
 
+/// Item IDs stay stable when repeat escalations merge into an item.
 #[cfg(feature = "daemon")]
 fn inbox_item_dedupe_key(item: &pt_core::inbox::InboxItem) -> String {
-    item.session_id.clone().unwrap_or_else(|| item.id.clone())
+    item.id.clone()
 }
 
 #[cfg(feature = "daemon")]
@@ -7283,6 +10259,7 @@ fn daemon_refresh_inbox_notifications(
     notify_mgr: &mut pt_core::decision::escalation::EscalationManager,
     store: &pt_core::inbox::InboxStore,
     now_secs: f64,
+    io: &DaemonIo,
 ) {
     if !config.notifications.enabled {
         return;
@@ -7306,7 +10283,7 @@ fn daemon_refresh_inbox_notifications(
 
     let notifs = notify_mgr.flush(now_secs);
     for n in notifs {
-        daemon_deliver_notification(config, &n);
+        daemon_deliver_notification(config, &n, io);
     }
 }
 
@@ -7314,11 +10291,13 @@ fn daemon_refresh_inbox_notifications(
 fn daemon_deliver_notification(
     config: &pt_core::daemon::DaemonConfig,
     notif: &pt_core::decision::escalation::Notification,
+    io: &DaemonIo,
 ) {
     if !config.notifications.enabled {
         return;
     }
 
+    let mut commands = Vec::new();
     if config.notifications.desktop
         && notif.channels.iter().any(|c| {
             matches!(
@@ -7327,12 +10306,17 @@ fn daemon_deliver_notification(
             )
         })
     {
-        let _ = daemon_notify_desktop(notif);
+        commands.extend(daemon_notify_desktop(notif));
     }
 
     if let Some(cmd) = config.notifications.notify_cmd.as_deref() {
-        let _ = daemon_notify_cmd(cmd, &config.notifications.notify_arg, notif);
+        commands.push(daemon_notify_cmd(
+            cmd,
+            &config.notifications.notify_arg,
+            notif,
+        ));
     }
+    io.notify(commands);
 }
 
 #[cfg(feature = "daemon")]
@@ -7340,7 +10324,7 @@ fn daemon_notify_cmd(
     cmd: &str,
     args: &[String],
     notif: &pt_core::decision::escalation::Notification,
-) -> std::io::Result<()> {
+) -> std::process::Command {
     use std::process::Command;
 
     let mut c = Command::new(cmd);
@@ -7357,15 +10341,13 @@ fn daemon_notify_cmd(
     if let Some(session_id) = &notif.session_id {
         c.env("PT_NOTIFY_SESSION_ID", session_id.clone());
     }
-
-    let _ = c.status();
-    Ok(())
+    c
 }
 
 #[cfg(feature = "daemon")]
 fn daemon_notify_desktop(
     notif: &pt_core::decision::escalation::Notification,
-) -> std::io::Result<()> {
+) -> Option<std::process::Command> {
     use std::process::Command;
 
     #[cfg(target_os = "linux")]
@@ -7375,10 +10357,9 @@ fn daemon_notify_desktop(
             pt_core::decision::escalation::Severity::Warning => "normal",
             pt_core::decision::escalation::Severity::Info => "low",
         };
-        let _ = Command::new("notify-send")
-            .args(["-u", urgency, "-a", "pt", &notif.title, &notif.body])
-            .status();
-        Ok(())
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["-u", urgency, "-a", "pt", &notif.title, &notif.body]);
+        Some(cmd)
     }
 
     #[cfg(target_os = "macos")]
@@ -7387,14 +10368,15 @@ fn daemon_notify_desktop(
         let body = notif.body.replace('"', "\\\"");
         let title = notif.title.replace('"', "\\\"");
         let script = format!("display notification \"{}\" with title \"{}\"", body, title);
-        let _ = Command::new("osascript").args(["-e", &script]).status();
-        Ok(())
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        Some(cmd)
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
         let _ = notif;
-        Ok(())
+        None
     }
 }
 
@@ -7484,6 +10466,14 @@ fn run_daemon_status(global: &GlobalOpts) -> ExitCode {
             } else {
                 println!("Daemon not running.");
             }
+            if let Some(schedule) = state.as_ref().map(|s| &s.daemon.schedule) {
+                if schedule.interval_secs > 0 {
+                    println!(
+                        "Pressure: {}, tick every {}s.",
+                        schedule.level, schedule.interval_secs
+                    );
+                }
+            }
         }
     }
 
@@ -7498,10 +10488,12 @@ fn run_telemetry(global: &GlobalOpts, _args: &TelemetryArgs) -> ExitCode {
             dry_run,
             keep_everything,
         } => run_telemetry_prune(global, _args, keep, *dry_run, *keep_everything),
-        TelemetryCommands::Export { .. } => {
-            output_stub(global, "telemetry export", "Export not yet implemented");
-            ExitCode::Clean
-        }
+        TelemetryCommands::Export {
+            output,
+            export_format,
+            profile,
+            tables,
+        } => run_telemetry_export(global, _args, output, export_format, profile, tables),
         TelemetryCommands::Redact { .. } => {
             output_stub(global, "telemetry redact", "Redaction not yet implemented");
             ExitCode::Clean
@@ -7516,6 +10508,46 @@ fn resolve_telemetry_dir(args: &TelemetryArgs) -> PathBuf {
         .unwrap_or_else(default_telemetry_dir)
 }
 
+/// Room a plan or apply run needs in its session directory up front.
+const SESSION_WRITE_ESTIMATE_BYTES: u64 = 1024 * 1024;
+
+/// Disk-pressure guard for session and telemetry writes, using the reserve
+/// from `telemetry_retention.json` when present.
+fn telemetry_write_guard(global: &GlobalOpts) -> DiskQuotaGuard {
+    let telemetry_dir = default_telemetry_dir();
+    let mut config =
+        std::fs::read_to_string(resolve_config_dir(global).join("telemetry_retention.json"))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .and_then(|value| parse_retention_config_value(value).ok())
+            .unwrap_or_default();
+    if config.event_log_dir.is_none() {
+        config.event_log_dir = Some(telemetry_dir.join("retention_logs"));
+    }
+    DiskQuotaGuard::new(telemetry_dir, config)
+}
+
+/// Telemetry mode and disk-pressure events, when anything happened.
+fn telemetry_quota_json(guard: &DiskQuotaGuard) -> Option<serde_json::Value> {
+    if guard.mode() == TelemetryMode::Full && guard.events().is_empty() {
+        return None;
+    }
+    Some(serde_json::json!({
+        "mode": guard.mode(),
+        "skipped_writes": guard.skipped_writes(),
+        "events": guard.events(),
+    }))
+}
+
+/// Scrubber for command lines and environments in this run's output.
+fn output_redactor(global: &GlobalOpts) -> Result<OutputRedactor, OutputRedactionError> {
+    OutputRedactor::load(
+        &resolve_config_dir(global),
+        global.redaction_profile,
+        global.no_redact,
+    )
+}
+
 fn resolve_config_dir(global: &GlobalOpts) -> PathBuf {
     if let Some(dir) = &global.config {
         return PathBuf::from(dir);
@@ -7606,6 +10638,17 @@ fn parse_retention_config_value(
             config.keep_everything = keep;
         }
 
+        if let Some(min_free_gb) = map.get("min_free_gb").and_then(|v| v.as_f64()) {
+            if min_free_gb >= 0.0 {
+                config.disk_reserve.min_free_bytes =
+                    (min_free_gb * 1024.0 * 1024.0 * 1024.0).round() as u64;
+            }
+        }
+
+        if let Some(min_free_pct) = map.get("min_free_pct").and_then(|v| v.as_f64()) {
+            config.disk_reserve.min_free_pct = min_free_pct;
+        }
+
         return Ok(config);
     }
 
@@ -7667,20 +10710,20 @@ fn run_telemetry_status(global: &GlobalOpts, args: &TelemetryArgs) -> ExitCode {
             println!("Telemetry directory: {}", status.root_dir);
             println!(
                 "Total usage: {} in {} files",
-                format_bytes(status.total_bytes),
+                format_size(status.total_bytes),
                 status.total_files
             );
             if status.disk_budget_bytes > 0 {
                 println!(
                     "Disk budget: {} ({:.1}% used)",
-                    format_bytes(status.disk_budget_bytes),
+                    format_size(status.disk_budget_bytes),
                     status.budget_used_pct
                 );
             }
             println!(
                 "TTL-eligible: {} files ({} bytes)",
                 status.ttl_eligible_files,
-                format_bytes(status.ttl_eligible_bytes)
+                format_size(status.ttl_eligible_bytes)
             );
             println!();
             println!("Per-table:");
@@ -7689,7 +10732,7 @@ fn run_telemetry_status(global: &GlobalOpts, args: &TelemetryArgs) -> ExitCode {
                     "  {:<16} files={:<4} size={:<8} ttl={}d over_ttl={}",
                     table,
                     table_status.file_count,
-                    format_bytes(table_status.total_bytes),
+                    format_size(table_status.total_bytes),
                     table_status.ttl_days,
                     table_status.over_ttl_count
                 );
@@ -7718,7 +10761,7 @@ fn run_telemetry_prune(
 
     if keep_everything {
         config.keep_everything = true;
-    } else if let Some(duration) = parse_duration(keep) {
+    } else if let Ok(duration) = parse_chrono_duration(keep, DurationUnit::Days) {
         let days = duration.num_days();
         if days <= 0 {
             eprintln!("telemetry prune: keep must be at least 1 day");
@@ -7784,13 +10827,13 @@ fn run_telemetry_prune(
             println!(
                 "Bytes {}: {}",
                 if dry_run { "eligible" } else { "freed" },
-                format_bytes(freed_bytes)
+                format_size(freed_bytes)
             );
             for event in &events {
                 println!(
                     "  {} ({}) [{:?}]",
                     event.file_path,
-                    format_bytes(event.size_bytes),
+                    format_size(event.size_bytes),
                     event.reason
                 );
             }
@@ -7800,35 +10843,167 @@ fn run_telemetry_prune(
     ExitCode::Clean
 }
 
-#[derive(Debug)]
-struct ShadowSignalState {
-    stop: AtomicBool,
-    reload: AtomicBool,
-    force_scan: AtomicBool,
-}
+fn run_telemetry_export(
+    global: &GlobalOpts,
+    args: &TelemetryArgs,
+    output: &str,
+    export_format: &str,
+    profile: &str,
+    tables: &[String],
+) -> ExitCode {
+    use pt_redact::{ExportProfile, RedactionEngine, RedactionPolicy};
+    use pt_telemetry::export::{export_tables, ExportFormat, ExportOptions};
+    use pt_telemetry::schema::TableName;
 
-impl ShadowSignalState {
-    const fn new() -> Self {
-        Self {
-            stop: AtomicBool::new(false),
-            reload: AtomicBool::new(false),
-            force_scan: AtomicBool::new(false),
+    let Some(format) = ExportFormat::parse_str(export_format) else {
+        eprintln!(
+            "telemetry export: invalid format '{}'. Valid options: parquet, csv, json, jsonl",
+            export_format
+        );
+        return ExitCode::ArgsError;
+    };
+    let Some(profile) = ExportProfile::parse_str(profile) else {
+        eprintln!(
+            "telemetry export: invalid profile '{}'. Valid options: minimal, safe, forensic",
+            profile
+        );
+        return ExitCode::ArgsError;
+    };
+    let mut selected = Vec::new();
+    for name in tables {
+        match TableName::parse_str(name) {
+            Some(table) => selected.push(table),
+            None => {
+                eprintln!("telemetry export: unknown table '{}'", name);
+                return ExitCode::ArgsError;
+            }
         }
     }
+    // Hash with the configured key, so exported tokens share the key epoch
+    // of session output; a fresh key only when none is configured.
+    let key_path = resolve_config_dir(global).join(pt_core::redact::KEY_FILE);
+    let engine = match pt_redact::KeyManager::load(&key_path) {
+        Ok(keys) => RedactionEngine::with_key_manager(RedactionPolicy::default(), &keys),
+        Err(_) => RedactionEngine::new(RedactionPolicy::default()),
+    };
+    let engine = match engine {
+        Ok(engine) => engine,
+        Err(err) => {
+            eprintln!("telemetry export: redaction engine error: {}", err);
+            return ExitCode::InternalError;
+        }
+    };
 
-    fn request_stop(&self) {
-        self.stop.store(true, Ordering::Relaxed);
+    let telemetry_dir = resolve_telemetry_dir(args);
+    let output_dir = PathBuf::from(output);
+    let options = ExportOptions {
+        format,
+        profile,
+        tables: selected,
+    };
+    let emitter = progress_emitter(global);
+    let started = std::time::Instant::now();
+    if let Some(ref e) = emitter {
+        e.emit(
+            ProgressEvent::new(pt_core::events::event_names::EXPORT_STARTED, Phase::Export)
+                .with_detail("format", format)
+                .with_detail("profile", profile.to_string()),
+        );
     }
 
-    fn should_stop(&self) -> bool {
-        self.stop.load(Ordering::Relaxed)
-    }
+    let summary = match export_tables(&telemetry_dir, &output_dir, &options, &engine, |p| {
+        if let Some(ref e) = emitter {
+            e.emit(
+                ProgressEvent::new(pt_core::events::event_names::EXPORT_PROGRESS, Phase::Export)
+                    .with_progress(p.files_done as u64, Some(p.files_total as u64))
+                    .with_elapsed_ms(started.elapsed().as_millis() as u64)
+                    .with_detail("table", p.table.as_str())
+                    .with_detail("rows_written", p.rows_written),
+            );
+        }
+    }) {
+        Ok(summary) => summary,
+        Err(err) => {
+            eprintln!("telemetry export: {}", err);
+            return ExitCode::IoError;
+        }
+    };
 
-    fn request_reload(&self) {
-        self.reload.store(true, Ordering::Relaxed);
+    if let Some(ref e) = emitter {
+        e.emit(
+            ProgressEvent::new(pt_core::events::event_names::EXPORT_COMPLETE, Phase::Export)
+                .with_elapsed_ms(started.elapsed().as_millis() as u64)
+                .with_detail("rows", summary.rows),
+        );
     }
 
-    fn take_reload(&self) -> bool {
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "command": "telemetry export",
+                "telemetry_dir": telemetry_dir.display().to_string(),
+                "export": summary,
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        _ => {
+            println!(
+                "Exported {} row(s) from {} table(s) to {} ({}, profile {}).",
+                summary.rows,
+                summary.tables.len(),
+                summary.output_dir.display(),
+                format.extension(),
+                summary.profile
+            );
+            println!(
+                "Hash tokens use redaction key {}.",
+                summary.redaction_key_id
+            );
+            for table in &summary.tables {
+                println!(
+                    "  {}: {} row(s), {} redacted value(s) -> {}",
+                    table.table,
+                    table.rows,
+                    table.redacted_values,
+                    table.path.display()
+                );
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+#[derive(Debug)]
+struct ShadowSignalState {
+    stop: AtomicBool,
+    reload: AtomicBool,
+    force_scan: AtomicBool,
+}
+
+impl ShadowSignalState {
+    const fn new() -> Self {
+        Self {
+            stop: AtomicBool::new(false),
+            reload: AtomicBool::new(false),
+            force_scan: AtomicBool::new(false),
+        }
+    }
+
+    fn request_stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn request_reload(&self) {
+        self.reload.store(true, Ordering::Relaxed);
+    }
+
+    fn take_reload(&self) -> bool {
         self.reload.swap(false, Ordering::Relaxed)
     }
 
@@ -8046,7 +11221,9 @@ fn run_shadow_iteration(
         .arg("--format")
         .arg("json")
         .arg("agent")
-        .arg("plan");
+        .arg("plan")
+        // Keep quick scans incremental across iterations.
+        .env(pt_core::collect::SCAN_CACHE_ENV, shadow_scan_cache_path());
     apply_shadow_plan_args(&mut cmd, args, force_deep);
 
     cmd.status()
@@ -8212,7 +11389,23 @@ fn run_shadow_report(global: &GlobalOpts, args: &ShadowReportArgs) -> ExitCode {
         return ExitCode::Clean;
     }
 
-    let engine = ValidationEngine::from_shadow_observations(&observations, args.threshold);
+    let mut engine = ValidationEngine::from_shadow_observations(&observations, args.threshold);
+    // User/agent feedback labels are ground truth; fold them in alongside
+    // shadow observations.
+    engine.add_feedback(
+        &pt_core::feedback::read_feedback(&default_telemetry_dir()).unwrap_or_default(),
+    );
+
+    if let Some(ref report_format) = args.report_format {
+        if !report_format.eq_ignore_ascii_case("html") {
+            eprintln!(
+                "shadow report: invalid report format '{}', use: html",
+                report_format
+            );
+            return ExitCode::ArgsError;
+        }
+        return write_shadow_report_html(global, args, &engine, observations.len());
+    }
 
     let is_structured = matches!(
         global.format,
@@ -8307,413 +11500,641 @@ fn run_shadow_report(global: &GlobalOpts, args: &ShadowReportArgs) -> ExitCode {
     ExitCode::Clean
 }
 
-fn apply_shadow_start_args(cmd: &mut std::process::Command, args: &ShadowStartArgs) {
-    if args.interval != 300 {
-        cmd.arg("--interval").arg(args.interval.to_string());
-    }
-    if args.deep_interval != 3600 {
-        cmd.arg("--deep-interval")
-            .arg(args.deep_interval.to_string());
-    }
-    if args.iterations != 0 {
-        cmd.arg("--iterations").arg(args.iterations.to_string());
-    }
-    if args.max_candidates != 20 {
-        cmd.arg("--max-candidates")
-            .arg(args.max_candidates.to_string());
-    }
-    if (args.min_posterior - 0.7).abs() > f64::EPSILON {
-        cmd.arg("--min-posterior")
-            .arg(args.min_posterior.to_string());
-    }
-    if args.only != "all" {
-        cmd.arg("--only").arg(&args.only);
-    }
-    if args.include_kernel_threads {
-        cmd.arg("--include-kernel-threads");
-    }
-    if args.deep {
-        cmd.arg("--deep");
-    }
-    if let Some(min_age) = args.min_age {
-        cmd.arg("--min-age").arg(min_age.to_string());
+fn write_shadow_report_html(
+    global: &GlobalOpts,
+    args: &ShadowReportArgs,
+    engine: &ValidationEngine,
+    count: usize,
+) -> ExitCode {
+    let report = match engine.compute_report() {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("shadow report: {}", err);
+            return ExitCode::InternalError;
+        }
+    };
+    let Some(diagnostics) = report.diagnostics else {
+        println!(
+            "Calibration diagnostics require at least 10 resolved observations (found {}).",
+            report.resolved_predictions
+        );
+        return ExitCode::Clean;
+    };
+
+    let html = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Shadow calibration report</title></head>\n<body>\n<h1>Shadow calibration report</h1>\n<p>{} observations, {} resolved, threshold {:.2}.</p>\n{}</body></html>\n",
+        count, report.resolved_predictions, args.threshold, diagnostics.html_section()
+    );
+
+    let Some(ref path) = args.output else {
+        print!("{}", html);
+        return ExitCode::Clean;
+    };
+    if let Err(err) = std::fs::write(path, &html) {
+        eprintln!("shadow report: failed to write {}: {}", path, err);
+        return ExitCode::IoError;
     }
-    if let Some(sample_size) = args.sample_size {
-        cmd.arg("--sample-size").arg(sample_size.to_string());
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let response = serde_json::json!({
+                "command": "shadow report",
+                "count": count,
+                "threshold": args.threshold,
+                "format": "html",
+                "output": path,
+                "size_bytes": html.len(),
+            });
+            println!("{}", format_structured_output(global, response));
+        }
+        _ => println!("Report written to: {}", path),
     }
+    ExitCode::Clean
 }
 
-fn apply_shadow_plan_args(
-    cmd: &mut std::process::Command,
-    args: &ShadowStartArgs,
-    force_deep: bool,
-) {
-    cmd.arg("--max-candidates")
-        .arg(args.max_candidates.to_string());
-    cmd.arg("--min-posterior")
-        .arg(args.min_posterior.to_string());
-    cmd.arg("--only").arg(&args.only);
-    if args.include_kernel_threads {
-        cmd.arg("--include-kernel-threads");
-    }
-    if args.deep || force_deep {
-        cmd.arg("--deep");
-    }
-    if let Some(min_age) = args.min_age {
-        cmd.arg("--min-age").arg(min_age.to_string());
-    }
-    if let Some(sample_size) = args.sample_size {
-        cmd.arg("--sample-size").arg(sample_size.to_string());
+fn run_feedback(global: &GlobalOpts, args: &FeedbackArgs) -> ExitCode {
+    match &args.command {
+        FeedbackCommands::Record(record) => run_feedback_record(global, record),
+        FeedbackCommands::List(list) => run_feedback_list(global, list),
     }
 }
 
-fn shadow_sleep_with_interrupt(seconds: u64) -> bool {
-    if seconds == 0 {
-        return false;
-    }
-    let mut remaining = seconds;
-    while remaining > 0 {
-        if SHADOW_SIGNALS.should_stop() {
-            return false;
+fn run_feedback_record(global: &GlobalOpts, args: &FeedbackRecordArgs) -> ExitCode {
+    use pt_core::feedback::{resolve_feedback, write_feedback, FeedbackKind};
+
+    let Some(kind) = FeedbackKind::parse(&args.kind) else {
+        eprintln!(
+            "feedback record: invalid kind '{}', use: false-positive, false-negative",
+            args.kind
+        );
+        return ExitCode::ArgsError;
+    };
+    let Some(sid) = SessionId::parse(&args.session) else {
+        eprintln!("feedback record: invalid session ID '{}'", args.session);
+        return ExitCode::ArgsError;
+    };
+    let handle = match SessionStore::from_env().and_then(|store| store.open(&sid)) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("feedback record: {}", e);
+            return ExitCode::ArgsError;
         }
-        if SHADOW_SIGNALS.take_force_scan() {
-            return true;
+    };
+    let record = match resolve_feedback(
+        &handle,
+        args.pid,
+        kind,
+        &args.actor,
+        args.note.clone(),
+        chrono::Utc::now(),
+    ) {
+        Ok(record) => record,
+        Err(e) => {
+            eprintln!("feedback record: {}", e);
+            return ExitCode::ArgsError;
         }
-        let step = remaining.min(1);
-        std::thread::sleep(std::time::Duration::from_secs(step));
-        remaining = remaining.saturating_sub(step);
+    };
+
+    let telemetry_dir = default_telemetry_dir();
+    let mut write_guard = telemetry_write_guard(global);
+    if let QuotaDecision::Skip { reason } =
+        write_guard.check(&telemetry_dir, 64 * 1024, WritePriority::Essential)
+    {
+        eprintln!("feedback record: not enough disk space: {}", reason);
+        return ExitCode::IoError;
     }
-    false
-}
+    let path = match write_feedback(&telemetry_dir, &pt_core::logging::get_host_id(), &record) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("feedback record: {}", e);
+            return ExitCode::IoError;
+        }
+    };
 
-fn cleanup_shadow_pid_if_owned(pid: u32) {
-    if let Ok(Some(current)) = read_shadow_pid() {
-        if current == pid {
-            let _ = remove_shadow_pid();
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let response = serde_json::json!({
+                "command": "feedback record",
+                "recorded": true,
+                "feedback": record,
+                "path": path.display().to_string(),
+            });
+            println!("{}", format_structured_output(global, response));
         }
+        _ => println!(
+            "Recorded {} for PID {} in {} ({})",
+            record.kind,
+            record.pid,
+            record.session_id,
+            path.display()
+        ),
     }
+    ExitCode::Clean
 }
 
-fn shadow_base_dir() -> PathBuf {
-    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
-        return PathBuf::from(dir).join("shadow");
-    }
-    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
-        return PathBuf::from(dir).join("process_triage").join("shadow");
-    }
-    ShadowStorageConfig::default().base_dir
-}
+fn run_feedback_list(global: &GlobalOpts, args: &FeedbackListArgs) -> ExitCode {
+    let labels = match pt_core::feedback::read_feedback(&default_telemetry_dir()) {
+        Ok(labels) => labels,
+        Err(e) => {
+            eprintln!("feedback list: {}", e);
+            return ExitCode::IoError;
+        }
+    };
+    let labels: Vec<_> = labels
+        .into_iter()
+        .filter(|l| args.session.as_ref().is_none_or(|s| &l.session_id == s))
+        .collect();
 
-fn shadow_pid_path() -> PathBuf {
-    shadow_base_dir().join("shadow.pid")
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let response = serde_json::json!({
+                "command": "feedback list",
+                "count": labels.len(),
+                "feedback": labels,
+            });
+            println!("{}", format_structured_output(global, response));
+        }
+        OutputFormat::Jsonl => {
+            for label in &labels {
+                println!("{}", serde_json::to_string(label).unwrap_or_default());
+            }
+        }
+        _ => {
+            if labels.is_empty() {
+                println!("No feedback recorded.");
+            }
+            for label in &labels {
+                println!(
+                    "{}  {}  pid {}  {}  by {}{}",
+                    label.recorded_at.format("%Y-%m-%d %H:%M"),
+                    label.session_id,
+                    label.pid,
+                    label.kind,
+                    label.actor,
+                    label
+                        .note
+                        .as_deref()
+                        .map(|n| format!("  \"{}\"", n))
+                        .unwrap_or_default()
+                );
+            }
+        }
+    }
+    ExitCode::Clean
 }
 
-fn write_shadow_pid(pid: u32) -> std::io::Result<()> {
-    let path = shadow_pid_path();
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+fn run_calibrate(global: &GlobalOpts, args: &CalibrateArgs) -> ExitCode {
+    match &args.command {
+        CalibrateCommands::Compare(compare) => run_calibrate_compare(global, compare),
+        CalibrateCommands::Promote(promote) => run_calibrate_promote(global, promote),
+        CalibrateCommands::Fit(fit) => run_calibrate_fit(global, fit),
     }
-    std::fs::write(path, pid.to_string())
 }
 
-fn read_shadow_pid() -> std::io::Result<Option<u32>> {
-    let path = shadow_pid_path();
-    if !path.exists() {
-        return Ok(None);
-    }
-    let content = std::fs::read_to_string(path)?;
-    Ok(content.trim().parse::<u32>().ok())
+/// Sessions tagged with the named experiment, newest first.
+fn experiment_sessions(
+    command: &str,
+    name: &str,
+) -> Result<(SessionStore, Vec<pt_core::session::SessionSummary>), ExitCode> {
+    let store = SessionStore::from_env().map_err(|e| {
+        eprintln!("{}: session store error: {}", command, e);
+        ExitCode::InternalError
+    })?;
+    let sessions = store
+        .list_sessions(&ListSessionsOptions::default())
+        .map_err(|e| {
+            eprintln!("{}: failed to list sessions: {}", command, e);
+            ExitCode::InternalError
+        })?
+        .into_iter()
+        .filter(|summary| {
+            summary
+                .experiment
+                .as_ref()
+                .is_some_and(|tag| tag.name == name)
+        })
+        .collect();
+    Ok((store, sessions))
 }
 
-fn remove_shadow_pid() -> std::io::Result<()> {
-    let path = shadow_pid_path();
-    if path.exists() {
-        std::fs::remove_file(path)?;
+fn run_calibrate_compare(global: &GlobalOpts, args: &CalibrateCompareArgs) -> ExitCode {
+    let (store, sessions) = match experiment_sessions("calibrate compare", &args.experiment) {
+        Ok(found) => found,
+        Err(code) => return code,
+    };
+    if sessions.is_empty() {
+        eprintln!(
+            "calibrate compare: no sessions found for experiment '{}'",
+            args.experiment
+        );
+        return ExitCode::Clean;
     }
-    Ok(())
-}
 
-// ============================================================================
-// Global run lock (daemon vs manual/agent coordination)
-// ============================================================================
+    let config_options = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        ..Default::default()
+    };
+    let config = match load_config(&config_options) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("calibrate compare: failed to load config: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
 
-/// Resolve the data directory for global lock placement.
-fn resolve_data_dir_for_lock() -> Option<PathBuf> {
-    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
-        return Some(PathBuf::from(dir));
-    }
-    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
-        return Some(PathBuf::from(dir).join("process_triage"));
+    let mut comparison = pt_core::calibrate::experiment::ExperimentComparison::default();
+    let mut unreadable: Vec<serde_json::Value> = Vec::new();
+    for summary in &sessions {
+        let replay = SessionId::parse(&summary.session_id)
+            .ok_or_else(|| "invalid session id".to_string())
+            .and_then(|sid| store.open(&sid).map_err(|e| e.to_string()))
+            .and_then(|handle| load_experiment(&handle).map_err(|e| e.to_string()))
+            .and_then(|envelope| {
+                let artifact = envelope.payload;
+                artifact
+                    .experiment
+                    .apply(&config.priors, &config.policy)
+                    .map(|overlaid| (artifact.records, overlaid))
+                    .map_err(|e| e.to_string())
+            });
+        match replay {
+            Ok((records, (priors, policy))) => comparison.add_session(
+                &summary.session_id,
+                &records,
+                (&config.priors, &config.policy),
+                (&priors, &policy),
+            ),
+            Err(error) => unreadable.push(serde_json::json!({
+                "session_id": summary.session_id,
+                "error": error,
+            })),
+        }
     }
-    dirs::data_dir().map(|dir| dir.join("process_triage"))
-}
 
-/// Global lock path shared by daemon + manual/agent runs.
-fn global_lock_path() -> Option<PathBuf> {
-    resolve_data_dir_for_lock().map(|dir| dir.join(".pt-lock"))
-}
-
-struct GlobalLock {
-    file: std::fs::File,
-}
-
-impl GlobalLock {
-    fn try_acquire(path: &Path) -> Result<Option<Self>, std::io::Error> {
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        let file = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(path)?;
+    let changes_total = comparison.changes.len();
+    let agreement_rate = comparison.agreement_rate();
+    comparison.changes.truncate(args.max_changes);
 
-        #[cfg(unix)]
-        {
-            use std::os::unix::io::AsRawFd;
-            let fd = file.as_raw_fd();
-            let result = unsafe { libc::flock(fd, libc::LOCK_EX | libc::LOCK_NB) };
-            if result != 0 {
-                let err = std::io::Error::last_os_error();
-                if err.kind() == std::io::ErrorKind::WouldBlock {
-                    return Ok(None);
-                }
-                return Err(err);
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "calibrate compare",
+                "experiment": args.experiment,
+                "baseline": config.snapshot(),
+                "agreement_rate": agreement_rate,
+                "changes_total": changes_total,
+                "comparison": comparison,
+                "unreadable_sessions": unreadable,
+            });
+            match global.format {
+                OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
+                _ => println!("{}", format_structured_output(global, output)),
             }
         }
-
-        file.set_len(0)?;
-        let mut writer = &file;
-        let _ = writer.write_all(format!("{}", std::process::id()).as_bytes());
-        let _ = writer.flush();
-
-        Ok(Some(Self { file }))
-    }
-}
-
-impl Drop for GlobalLock {
-    fn drop(&mut self) {
-        #[cfg(unix)]
-        {
-            use std::os::unix::io::AsRawFd;
-            unsafe {
-                libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        _ => {
+            println!(
+                "Experiment '{}': {} session(s), {} record(s)",
+                args.experiment, comparison.sessions, comparison.records
+            );
+            match agreement_rate {
+                Some(rate) => println!(
+                    "Agreement with baseline: {:.1}% ({} changed, {} skipped)",
+                    rate * 100.0,
+                    changes_total,
+                    comparison.skipped
+                ),
+                None => println!("No comparable records."),
+            }
+            for change in &comparison.changes {
+                println!(
+                    "  {} pid {} ({}): {:?} -> {:?}",
+                    change.session_id,
+                    change.pid,
+                    change.comm,
+                    change.baseline_action,
+                    change.experiment_action
+                );
+            }
+            if changes_total > comparison.changes.len() {
+                println!("  ... {} more", changes_total - comparison.changes.len());
+            }
+            for entry in &unreadable {
+                eprintln!(
+                    "calibrate compare: skipped session {}: {}",
+                    entry["session_id"].as_str().unwrap_or(""),
+                    entry["error"].as_str().unwrap_or("")
+                );
             }
         }
     }
+
+    ExitCode::Clean
 }
 
-fn acquire_global_lock(global: &GlobalOpts, command: &str) -> Result<Option<GlobalLock>, ExitCode> {
-    if std::env::var("PT_SKIP_GLOBAL_LOCK").is_ok() {
-        return Ok(None);
-    }
-    let path = match global_lock_path() {
-        Some(path) => path,
-        None => return Ok(None),
+fn run_calibrate_promote(global: &GlobalOpts, args: &CalibratePromoteArgs) -> ExitCode {
+    let (store, sessions) = match experiment_sessions("calibrate promote", &args.experiment) {
+        Ok(found) => found,
+        Err(code) => return code,
     };
+    if sessions.is_empty() {
+        eprintln!(
+            "calibrate promote: no sessions found for experiment '{}'",
+            args.experiment
+        );
+        return ExitCode::ArgsError;
+    }
 
-    match GlobalLock::try_acquire(&path) {
-        Ok(Some(lock)) => Ok(Some(lock)),
-        Ok(None) => {
-            let response = serde_json::json!({
-                "command": command,
-                "error": "lock contention",
-                "lock_path": path.display().to_string(),
-            });
-            match global.format {
-                OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
-                    println!("{}", format_structured_output(global, response));
-                }
-                _ => {
-                    eprintln!("{}: lock held at {}", command, path.display());
+    let mut promoted = Vec::new();
+    for summary in sessions.iter().filter(|summary| !summary.feeds_learning()) {
+        let result = SessionId::parse(&summary.session_id)
+            .ok_or_else(|| "invalid session id".to_string())
+            .and_then(|sid| store.open(&sid).map_err(|e| e.to_string()))
+            .and_then(|handle| {
+                let mut manifest = handle.read_manifest().map_err(|e| e.to_string())?;
+                if let Some(tag) = manifest.experiment.as_mut() {
+                    tag.promoted = true;
                 }
+                handle.write_manifest(&manifest).map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(()) => promoted.push(summary.session_id.clone()),
+            Err(e) => {
+                eprintln!(
+                    "calibrate promote: failed to promote {}: {}",
+                    summary.session_id, e
+                );
+                return ExitCode::IoError;
             }
-            Err(ExitCode::LockError)
         }
-        Err(err) => {
-            let response = serde_json::json!({
-                "command": command,
-                "error": format!("lock error: {}", err),
-                "lock_path": path.display().to_string(),
+    }
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "calibrate promote",
+                "experiment": args.experiment,
+                "sessions": sessions.len(),
+                "promoted": promoted,
             });
             match global.format {
-                OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
-                    eprintln!("{}", format_structured_output(global, response));
-                }
-                _ => {
-                    eprintln!("{}: lock error at {}: {}", command, path.display(), err);
-                }
+                OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
+                _ => println!("{}", format_structured_output(global, output)),
             }
-            Err(ExitCode::IoError)
         }
+        _ => println!(
+            "Promoted {} of {} session(s) for experiment '{}'.",
+            promoted.len(),
+            sessions.len(),
+            args.experiment
+        ),
     }
-}
-
-// ============================================================================
-// Daemon helpers
-// ============================================================================
 
-#[cfg(feature = "daemon")]
-#[derive(Debug)]
-struct DaemonSignalState {
-    stop: AtomicBool,
-    reload: AtomicBool,
-    force_tick: AtomicBool,
+    ExitCode::Clean
 }
 
-#[cfg(feature = "daemon")]
-impl DaemonSignalState {
-    const fn new() -> Self {
-        Self {
-            stop: AtomicBool::new(false),
-            reload: AtomicBool::new(false),
-            force_tick: AtomicBool::new(false),
-        }
-    }
-
-    fn request_stop(&self) {
-        self.stop.store(true, Ordering::Relaxed);
-    }
+fn run_calibrate_fit(global: &GlobalOpts, args: &CalibrateFitArgs) -> ExitCode {
+    use pt_core::calibrate::empirical_bayes::EmpiricalBayesConfig;
+    use pt_core::calibrate::shadow_fit::{fit_from_shadow, shadow_samples};
 
-    fn should_stop(&self) -> bool {
-        self.stop.load(Ordering::Relaxed)
+    if !args.from_shadow {
+        eprintln!("calibrate fit: no data source given (use --from-shadow)");
+        return ExitCode::ArgsError;
     }
 
-    fn request_reload(&self) {
-        self.reload.store(true, Ordering::Relaxed);
-    }
+    let config_options = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        ..Default::default()
+    };
+    let config = match load_config(&config_options) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("calibrate fit: failed to load config: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
 
-    fn take_reload(&self) -> bool {
-        self.reload.swap(false, Ordering::Relaxed)
-    }
+    let observations = match &args.input {
+        Some(path) => read_shadow_export(path, args.limit),
+        None => collect_shadow_observations(&shadow_base_dir(), args.limit),
+    };
+    let observations = match observations {
+        Ok(observations) => observations,
+        Err(e) => {
+            eprintln!("calibrate fit: failed to read shadow observations: {}", e);
+            return ExitCode::IoError;
+        }
+    };
 
-    fn request_force_tick(&self) {
-        self.force_tick.store(true, Ordering::Relaxed);
+    let samples = shadow_samples(&observations);
+    let fit = fit_from_shadow(&samples, &config.priors, &EmpiricalBayesConfig::default());
+    if let Err(e) = pt_config::validate::validate_priors(&fit.priors) {
+        eprintln!("calibrate fit: proposed priors are invalid: {}", e);
+        return ExitCode::InternalError;
     }
 
-    fn take_force_tick(&self) -> bool {
-        self.force_tick.swap(false, Ordering::Relaxed)
+    let output_path = args
+        .output
+        .clone()
+        .unwrap_or_else(|| config.config_dir.join("priors.proposed.json"));
+    if config.priors_path.as_deref() == Some(output_path.as_path()) {
+        eprintln!(
+            "calibrate fit: refusing to overwrite the active priors at {}",
+            output_path.display()
+        );
+        return ExitCode::ArgsError;
     }
-}
-
-#[cfg(feature = "daemon")]
-static DAEMON_SIGNALS: DaemonSignalState = DaemonSignalState::new();
-
-#[cfg(feature = "daemon")]
-#[cfg(unix)]
-fn install_daemon_signal_handlers() {
-    unsafe extern "C" fn handler(signal: i32) {
-        match signal {
-            libc::SIGTERM | libc::SIGINT => DAEMON_SIGNALS.request_stop(),
-            libc::SIGHUP => {
-                DAEMON_SIGNALS.request_reload();
-                DAEMON_SIGNALS.request_force_tick();
-            }
-            libc::SIGUSR1 => DAEMON_SIGNALS.request_force_tick(),
-            _ => {}
+    let written = !args.dry_run && !fit.changes.is_empty();
+    if written {
+        let result = output_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string_pretty(&fit.priors)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                std::fs::write(&output_path, json)
+            });
+        if let Err(e) = result {
+            eprintln!(
+                "calibrate fit: failed to write {}: {}",
+                output_path.display(),
+                e
+            );
+            return ExitCode::IoError;
         }
     }
 
-    unsafe {
-        #[cfg(target_os = "linux")]
-        {
-            // Use sigaction for persistent handler semantics across repeated signals.
-            let mut action: libc::sigaction = std::mem::zeroed();
-            action.sa_sigaction = handler as *const () as usize;
-            action.sa_flags = libc::SA_RESTART;
-            libc::sigemptyset(&mut action.sa_mask);
-            for signal in [libc::SIGTERM, libc::SIGINT, libc::SIGHUP, libc::SIGUSR1] {
-                libc::sigaction(signal, &action, std::ptr::null_mut());
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "calibrate fit",
+                "source": args.input.as_ref().map_or_else(
+                    || shadow_base_dir().display().to_string(),
+                    |path| path.display().to_string(),
+                ),
+                "observations": observations.len(),
+                "fit": fit,
+                "output": written.then(|| output_path.display().to_string()),
+            });
+            match global.format {
+                OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
+                _ => println!("{}", format_structured_output(global, output)),
             }
         }
-
-        #[cfg(not(target_os = "linux"))]
-        {
-            let handler_ptr = handler as *const () as libc::sighandler_t;
-            libc::signal(libc::SIGTERM, handler_ptr);
-            libc::signal(libc::SIGINT, handler_ptr);
-            libc::signal(libc::SIGHUP, handler_ptr);
-            libc::signal(libc::SIGUSR1, handler_ptr);
+        _ => {
+            println!(
+                "Shadow fit: {} observation(s), {} sample(s), {} with resolved outcome",
+                observations.len(),
+                fit.samples,
+                fit.labeled
+            );
+            if fit.changes.is_empty() {
+                println!("No parameter changes (not enough samples per class).");
+            }
+            for change in &fit.changes {
+                println!(
+                    "  {}: {:?} -> {:?}{}",
+                    change.path,
+                    change.before,
+                    change.after,
+                    if change.clamped { " (clamped)" } else { "" }
+                );
+            }
+            match (&fit.before, &fit.after) {
+                (Some(before), Some(after)) => println!(
+                    "Calibration: Brier {:.4} -> {:.4}, ECE {:.4} -> {:.4}",
+                    before.brier_score, after.brier_score, before.ece, after.ece
+                ),
+                _ => println!("Calibration: not enough resolved outcomes to score."),
+            }
+            if written {
+                println!("Proposed priors written to {}", output_path.display());
+            }
         }
     }
-}
 
-#[cfg(feature = "daemon")]
-#[cfg(not(unix))]
-fn install_daemon_signal_handlers() {}
-
-#[cfg(feature = "daemon")]
-fn daemon_sleep_with_interrupt(seconds: u64) -> bool {
-    if seconds == 0 {
-        return false;
-    }
-    let mut remaining = seconds;
-    while remaining > 0 {
-        if DAEMON_SIGNALS.should_stop() {
-            return false;
-        }
-        if DAEMON_SIGNALS.take_force_tick() {
-            return true;
-        }
-        let step = remaining.min(1);
-        std::thread::sleep(std::time::Duration::from_secs(step));
-        remaining = remaining.saturating_sub(step);
-    }
-    false
+    ExitCode::Clean
 }
 
-#[cfg(feature = "daemon")]
-fn daemon_base_dir() -> PathBuf {
-    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
-        return PathBuf::from(dir).join("daemon");
+fn apply_shadow_start_args(cmd: &mut std::process::Command, args: &ShadowStartArgs) {
+    if args.interval != 300 {
+        cmd.arg("--interval").arg(args.interval.to_string());
     }
-    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
-        return PathBuf::from(dir).join("process_triage").join("daemon");
+    if args.deep_interval != 3600 {
+        cmd.arg("--deep-interval")
+            .arg(args.deep_interval.to_string());
+    }
+    if args.iterations != 0 {
+        cmd.arg("--iterations").arg(args.iterations.to_string());
+    }
+    if args.max_candidates != 20 {
+        cmd.arg("--max-candidates")
+            .arg(args.max_candidates.to_string());
+    }
+    if (args.min_posterior - 0.7).abs() > f64::EPSILON {
+        cmd.arg("--min-posterior")
+            .arg(args.min_posterior.to_string());
+    }
+    if args.only != "all" {
+        cmd.arg("--only").arg(&args.only);
+    }
+    if args.include_kernel_threads {
+        cmd.arg("--include-kernel-threads");
+    }
+    if args.deep {
+        cmd.arg("--deep");
+    }
+    if let Some(min_age) = args.min_age {
+        cmd.arg("--min-age").arg(min_age.to_string());
+    }
+    if let Some(sample_size) = args.sample_size {
+        cmd.arg("--sample-size").arg(sample_size.to_string());
     }
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("process_triage")
-        .join("daemon")
 }
 
-#[cfg(feature = "daemon")]
-fn daemon_pid_path() -> PathBuf {
-    daemon_base_dir().join("daemon.pid")
+fn apply_shadow_plan_args(
+    cmd: &mut std::process::Command,
+    args: &ShadowStartArgs,
+    force_deep: bool,
+) {
+    cmd.arg("--max-candidates")
+        .arg(args.max_candidates.to_string());
+    cmd.arg("--min-posterior")
+        .arg(args.min_posterior.to_string());
+    cmd.arg("--only").arg(&args.only);
+    if args.include_kernel_threads {
+        cmd.arg("--include-kernel-threads");
+    }
+    if args.deep || force_deep {
+        cmd.arg("--deep");
+    }
+    if let Some(min_age) = args.min_age {
+        cmd.arg("--min-age").arg(min_age.to_string());
+    }
+    if let Some(sample_size) = args.sample_size {
+        cmd.arg("--sample-size").arg(sample_size.to_string());
+    }
 }
 
-#[cfg(feature = "daemon")]
-fn daemon_pid_lock_path() -> PathBuf {
-    daemon_base_dir().join("daemon.pid.lock")
+fn shadow_sleep_with_interrupt(seconds: u64) -> bool {
+    if seconds == 0 {
+        return false;
+    }
+    let mut remaining = seconds;
+    while remaining > 0 {
+        if SHADOW_SIGNALS.should_stop() {
+            return false;
+        }
+        if SHADOW_SIGNALS.take_force_scan() {
+            return true;
+        }
+        let step = remaining.min(1);
+        std::thread::sleep(std::time::Duration::from_secs(step));
+        remaining = remaining.saturating_sub(step);
+    }
+    false
 }
 
-#[cfg(feature = "daemon")]
-fn try_acquire_daemon_pid_lock() -> std::io::Result<Option<GlobalLock>> {
-    GlobalLock::try_acquire(&daemon_pid_lock_path())
+fn cleanup_shadow_pid_if_owned(pid: u32) {
+    if let Ok(Some(current)) = read_shadow_pid() {
+        if current == pid {
+            let _ = remove_shadow_pid();
+        }
+    }
 }
 
-#[cfg(feature = "daemon")]
-fn daemon_state_path() -> PathBuf {
-    daemon_base_dir().join("state.json")
+fn shadow_base_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
+        return PathBuf::from(dir).join("shadow");
+    }
+    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join("process_triage").join("shadow");
+    }
+    ShadowStorageConfig::default().base_dir
 }
 
-#[cfg(feature = "daemon")]
-fn daemon_lock_path() -> PathBuf {
-    daemon_base_dir().join("pt.lock")
+fn shadow_pid_path() -> PathBuf {
+    shadow_base_dir().join("shadow.pid")
 }
 
-#[cfg(feature = "daemon")]
-fn write_daemon_pid(pid: u32) -> std::io::Result<()> {
-    let path = daemon_pid_path();
+fn shadow_scan_cache_path() -> PathBuf {
+    shadow_base_dir().join("scan_cache.json")
+}
+
+fn write_shadow_pid(pid: u32) -> std::io::Result<()> {
+    let path = shadow_pid_path();
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
     std::fs::write(path, pid.to_string())
 }
 
-#[cfg(feature = "daemon")]
-fn read_daemon_pid() -> std::io::Result<Option<u32>> {
-    let path = daemon_pid_path();
+fn read_shadow_pid() -> std::io::Result<Option<u32>> {
+    let path = shadow_pid_path();
     if !path.exists() {
         return Ok(None);
     }
@@ -8721,5231 +12142,9711 @@ fn read_daemon_pid() -> std::io::Result<Option<u32>> {
     Ok(content.trim().parse::<u32>().ok())
 }
 
-#[cfg(feature = "daemon")]
-fn remove_daemon_pid() -> std::io::Result<()> {
-    let path = daemon_pid_path();
+fn remove_shadow_pid() -> std::io::Result<()> {
+    let path = shadow_pid_path();
     if path.exists() {
         std::fs::remove_file(path)?;
     }
     Ok(())
 }
 
-#[cfg(feature = "daemon")]
-fn cleanup_daemon_pid_if_owned(pid: u32) {
-    if let Ok(Some(current)) = read_daemon_pid() {
-        if current == pid {
-            let _ = remove_daemon_pid();
-        }
-    }
-}
-
-#[cfg(feature = "daemon")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct DaemonStateBundle {
-    daemon: pt_core::daemon::DaemonState,
-    triggers: pt_core::daemon::triggers::TriggerState,
-    escalation: pt_core::daemon::escalation::EscalationState,
-    #[serde(default)]
-    notifications: pt_core::decision::escalation::PersistedEscalationState,
-}
+// ============================================================================
+// Global run lock (daemon vs manual/agent coordination)
+// ============================================================================
 
-#[cfg(feature = "daemon")]
-fn load_daemon_state(path: &Path, config: &pt_core::daemon::DaemonConfig) -> DaemonStateBundle {
-    if let Ok(content) = std::fs::read_to_string(path) {
-        if let Ok(state) = serde_json::from_str::<DaemonStateBundle>(&content) {
-            return state;
-        }
+/// Resolve the data directory for global lock placement.
+fn resolve_data_dir_for_lock() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
+        return Some(PathBuf::from(dir));
     }
-
-    DaemonStateBundle {
-        daemon: pt_core::daemon::DaemonState::new(),
-        triggers: pt_core::daemon::triggers::TriggerState::new(&config.triggers),
-        escalation: pt_core::daemon::escalation::EscalationState::new(),
-        notifications: pt_core::decision::escalation::PersistedEscalationState::default(),
+    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
+        return Some(PathBuf::from(dir).join("process_triage"));
     }
+    dirs::data_dir().map(|dir| dir.join("process_triage"))
 }
 
-#[cfg(feature = "daemon")]
-fn save_daemon_state(path: &Path, state: &DaemonStateBundle) -> std::io::Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let tmp = path.with_extension("json.tmp");
-    let content = serde_json::to_vec_pretty(state)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-    std::fs::write(&tmp, content)?;
-    std::fs::rename(tmp, path)?;
-    Ok(())
+/// Global lock path shared by daemon + manual/agent runs.
+fn global_lock_path() -> Option<PathBuf> {
+    resolve_data_dir_for_lock().map(|dir| dir.join(pt_core::lock::GLOBAL_LOCK_FILE))
 }
 
-#[cfg(feature = "daemon")]
-struct DaemonEscalationResult {
-    session_id: String,
-    candidates_found: u32,
+/// Whether a command changes process state and therefore needs the global lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockAccess {
+    /// Acts on processes; takes the lock (waiting up to `--lock-wait`).
+    Exclusive,
+    /// Only observes; proceeds without the lock so it never blocks or waits.
+    ReadOnly,
 }
 
-#[cfg(feature = "daemon")]
-fn run_daemon_escalation(
+fn acquire_global_lock(
     global: &GlobalOpts,
-    _triggers: &[pt_core::daemon::triggers::FiredTrigger],
-    esc_config: &pt_core::daemon::escalation::EscalationConfig,
-) -> Result<DaemonEscalationResult, String> {
-    let quick = run_daemon_plan(global, None, false, esc_config.max_deep_scan_targets)?;
-    if quick.candidates_found == 0 {
-        return Ok(quick);
+    command: &str,
+    access: LockAccess,
+) -> Result<Option<GlobalLock>, ExitCode> {
+    if access == LockAccess::ReadOnly || std::env::var("PT_SKIP_GLOBAL_LOCK").is_ok() {
+        return Ok(None);
     }
+    let path = match global_lock_path() {
+        Some(path) => path,
+        None => return Ok(None),
+    };
 
-    match run_daemon_plan(
-        global,
-        Some(&quick.session_id),
-        true,
-        esc_config.max_deep_scan_targets,
-    ) {
-        Ok(deep) => Ok(deep),
+    let wait = Duration::from_secs(global.lock_wait.unwrap_or(0));
+    match GlobalLock::acquire_timeout(&path, command, wait) {
+        Ok(Some(lock)) => Ok(Some(lock)),
+        Ok(None) => {
+            let status = pt_core::lock::inspect(&path);
+            let holder = status.holder.as_ref().map(|holder| {
+                serde_json::json!({
+                    "pid": holder.pid,
+                    "command": holder.command,
+                    "session_id": holder.session_id,
+                    "started_at": holder.started_at,
+                    "age_secs": holder.age_secs(),
+                    "alive": holder.is_alive(),
+                })
+            });
+            let response = serde_json::json!({
+                "command": command,
+                "error": "lock contention",
+                "lock_path": path.display().to_string(),
+                "holder": holder,
+                "waited_secs": wait.as_secs(),
+                "waiters": status.waiters.len(),
+                "stale": status.stale,
+                "hint": if status.stale {
+                    "lock holder is gone; run `pt-core agent sessions --locks --force` to break the stale lock"
+                } else {
+                    "retry with --lock-wait <seconds>, or inspect with `pt-core agent sessions --locks`"
+                },
+            });
+            match global.format {
+                OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+                    println!("{}", format_structured_output(global, response));
+                }
+                _ => match status.holder {
+                    Some(holder) => eprintln!(
+                        "{}: lock held at {} by pid {} ({}{}){}",
+                        command,
+                        path.display(),
+                        holder.pid,
+                        holder.command,
+                        holder
+                            .session_id
+                            .as_deref()
+                            .map(|sid| format!(", session {}", sid))
+                            .unwrap_or_default(),
+                        holder
+                            .age_secs()
+                            .map(|age| format!(" for {}s", age))
+                            .unwrap_or_default(),
+                    ),
+                    None => eprintln!("{}: lock held at {}", command, path.display()),
+                },
+            }
+            Err(ExitCode::LockError)
+        }
         Err(err) => {
-            eprintln!(
-                "daemon escalation: deep plan failed, using quick plan: {}",
-                err
-            );
-            Ok(quick)
+            let response = serde_json::json!({
+                "command": command,
+                "error": format!("lock error: {}", err),
+                "lock_path": path.display().to_string(),
+            });
+            match global.format {
+                OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+                    eprintln!("{}", format_structured_output(global, response));
+                }
+                _ => {
+                    eprintln!("{}: lock error at {}: {}", command, path.display(), err);
+                }
+            }
+            Err(ExitCode::IoError)
         }
     }
 }
 
+// ============================================================================
+// Daemon helpers
+// ============================================================================
+
 #[cfg(feature = "daemon")]
-fn run_daemon_plan(
-    global: &GlobalOpts,
-    session_id: Option<&str>,
-    deep: bool,
-    max_candidates: u32,
-) -> Result<DaemonEscalationResult, String> {
-    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
-    let mut cmd = std::process::Command::new(exe);
-    cmd.args(["--format", "json", "agent", "plan", "--max-candidates"])
-        .arg(max_candidates.to_string());
-    if deep {
-        cmd.arg("--deep");
-    }
-    if let Some(session) = session_id {
-        cmd.arg("--session").arg(session);
-    }
-    cmd.stdin(std::process::Stdio::null())
-        .stderr(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .env("PT_SKIP_GLOBAL_LOCK", "1");
+#[derive(Debug)]
+struct DaemonSignalState {
+    stop: AtomicBool,
+    reload: AtomicBool,
+    force_tick: AtomicBool,
+}
 
-    apply_daemon_global_args(&mut cmd, global);
+#[cfg(feature = "daemon")]
+impl DaemonSignalState {
+    const fn new() -> Self {
+        Self {
+            stop: AtomicBool::new(false),
+            reload: AtomicBool::new(false),
+            force_tick: AtomicBool::new(false),
+        }
+    }
 
-    let output = cmd.output().map_err(|e| e.to_string())?;
-    let stdout = output.stdout;
-    if stdout.is_empty() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!(
-            "daemon escalation: empty stdout (status {:?}): {}",
-            output.status.code(),
-            stderr.trim()
-        ));
+    fn request_stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
     }
 
-    let json: serde_json::Value =
-        serde_json::from_slice(&stdout).map_err(|e| format!("invalid JSON: {}", e))?;
-    let session_id = json
-        .get("session_id")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| "missing session_id in plan output".to_string())?
-        .to_string();
-    let candidates_found = json
-        .get("summary")
-        .and_then(|s| s.get("candidates_found"))
-        .and_then(|v| v.as_u64())
-        .or_else(|| {
-            json.get("candidates")
-                .and_then(|v| v.as_array())
-                .map(|a| a.len() as u64)
-        })
-        .unwrap_or(0) as u32;
+    fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
 
-    Ok(DaemonEscalationResult {
-        session_id,
-        candidates_found,
-    })
-}
+    fn request_reload(&self) {
+        self.reload.store(true, Ordering::Relaxed);
+    }
 
-#[cfg(feature = "daemon")]
-fn collect_daemon_metrics() -> pt_core::daemon::TickMetrics {
-    let load = collect_load_averages();
-    let load_avg_1 = load.first().copied().unwrap_or(0.0);
-    let load_avg_5 = load.get(1).copied().unwrap_or(load_avg_1);
+    fn take_reload(&self) -> bool {
+        self.reload.swap(false, Ordering::Relaxed)
+    }
 
-    let memory = collect_memory_info();
-    let total_gb = memory
-        .get("total_gb")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
-    let used_gb = memory
-        .get("used_gb")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
-    let memory_total_mb = (total_gb * 1024.0).round() as u64;
-    let memory_used_mb = (used_gb * 1024.0).round() as u64;
+    fn request_force_tick(&self) {
+        self.force_tick.store(true, Ordering::Relaxed);
+    }
 
-    pt_core::daemon::TickMetrics {
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        load_avg_1,
-        load_avg_5,
-        memory_used_mb,
-        memory_total_mb,
-        swap_used_mb: collect_swap_used_mb(),
-        process_count: collect_process_count(),
-        orphan_count: collect_orphan_count(),
+    fn take_force_tick(&self) -> bool {
+        self.force_tick.swap(false, Ordering::Relaxed)
     }
 }
 
 #[cfg(feature = "daemon")]
-fn collect_swap_used_mb() -> u64 {
-    let content = match std::fs::read_to_string("/proc/meminfo") {
-        Ok(c) => c,
-        Err(_) => return 0,
-    };
-    let mut total = 0u64;
-    let mut free = 0u64;
-    for line in content.lines() {
-        if let Some(rest) = line.strip_prefix("SwapTotal:") {
-            total = rest
-                .split_whitespace()
-                .next()
-                .and_then(|s| s.parse::<u64>().ok())
-                .unwrap_or(0);
-        } else if let Some(rest) = line.strip_prefix("SwapFree:") {
-            free = rest
-                .split_whitespace()
-                .next()
-                .and_then(|s| s.parse::<u64>().ok())
-                .unwrap_or(0);
+static DAEMON_SIGNALS: DaemonSignalState = DaemonSignalState::new();
+
+#[cfg(feature = "daemon")]
+#[cfg(unix)]
+fn install_daemon_signal_handlers() {
+    unsafe extern "C" fn handler(signal: i32) {
+        match signal {
+            libc::SIGTERM | libc::SIGINT => DAEMON_SIGNALS.request_stop(),
+            libc::SIGHUP => {
+                DAEMON_SIGNALS.request_reload();
+                DAEMON_SIGNALS.request_force_tick();
+            }
+            libc::SIGUSR1 => DAEMON_SIGNALS.request_force_tick(),
+            _ => {}
         }
     }
-    total.saturating_sub(free) / 1024
-}
 
-#[cfg(all(feature = "daemon", target_os = "linux"))]
-fn collect_orphan_count() -> u32 {
-    let mut count = 0u32;
-    let entries = match std::fs::read_dir("/proc") {
-        Ok(entries) => entries,
-        Err(_) => return 0,
-    };
+    unsafe {
+        #[cfg(target_os = "linux")]
+        {
+            // Use sigaction for persistent handler semantics across repeated signals.
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = handler as *const () as usize;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            for signal in [libc::SIGTERM, libc::SIGINT, libc::SIGHUP, libc::SIGUSR1] {
+                libc::sigaction(signal, &action, std::ptr::null_mut());
+            }
+        }
 
-    for entry in entries.flatten() {
-        let file_name = entry.file_name();
-        let pid_str = match file_name.to_str() {
-            Some(name) if name.chars().all(|c| c.is_ascii_digit()) => name,
-            _ => continue,
-        };
-        let stat_path = format!("/proc/{}/stat", pid_str);
-        let stat = match std::fs::read_to_string(stat_path) {
-            Ok(stat) => stat,
-            Err(_) => continue,
-        };
-        let end = match stat.rfind(')') {
-            Some(pos) => pos,
-            None => continue,
-        };
-        let rest = match stat.get(end + 2..) {
-            Some(rest) => rest,
-            None => continue,
-        };
-        let mut parts = rest.split_whitespace();
-        let _state = parts.next();
-        let ppid = match parts.next() {
-            Some(ppid) => ppid,
-            None => continue,
-        };
-        if ppid == "1" {
-            count = count.saturating_add(1);
+        #[cfg(not(target_os = "linux"))]
+        {
+            let handler_ptr = handler as *const () as libc::sighandler_t;
+            libc::signal(libc::SIGTERM, handler_ptr);
+            libc::signal(libc::SIGINT, handler_ptr);
+            libc::signal(libc::SIGHUP, handler_ptr);
+            libc::signal(libc::SIGUSR1, handler_ptr);
         }
     }
-
-    count
 }
 
-#[cfg(all(feature = "daemon", target_os = "linux"))]
-fn current_rss_mb() -> Option<u64> {
-    let stats = pt_core::collect::parse_statm(std::process::id())?;
-    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
-    if page_size <= 0 {
-        return None;
-    }
-    let rss_bytes = stats.resident.saturating_mul(page_size as u64);
-    Some(rss_bytes / 1024 / 1024)
-}
+#[cfg(feature = "daemon")]
+#[cfg(not(unix))]
+fn install_daemon_signal_handlers() {}
 
-#[cfg(all(feature = "daemon", not(target_os = "linux")))]
-fn collect_orphan_count() -> u32 {
-    0
+#[cfg(feature = "daemon")]
+fn daemon_sleep_with_interrupt(seconds: u64) -> bool {
+    if seconds == 0 {
+        return false;
+    }
+    let mut remaining = seconds;
+    while remaining > 0 {
+        if DAEMON_SIGNALS.should_stop() {
+            return false;
+        }
+        if DAEMON_SIGNALS.take_force_tick() {
+            return true;
+        }
+        let step = remaining.min(1);
+        std::thread::sleep(std::time::Duration::from_secs(step));
+        remaining = remaining.saturating_sub(step);
+    }
+    false
 }
 
-#[cfg(all(feature = "daemon", not(target_os = "linux")))]
-fn current_rss_mb() -> Option<u64> {
-    None
-}
+/// Longest a notification command may run before it is killed.
+#[cfg(all(feature = "daemon", feature = "async"))]
+const DAEMON_NOTIFY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
-#[cfg(all(feature = "daemon", unix))]
-fn current_cpu_seconds() -> Option<f64> {
-    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
-    let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
-    if result != 0 {
-        return None;
-    }
-    let usage = unsafe { usage.assume_init() };
-    let user = usage.ru_utime.tv_sec as f64 + (usage.ru_utime.tv_usec as f64 / 1_000_000.0);
-    let system = usage.ru_stime.tv_sec as f64 + (usage.ru_stime.tv_usec as f64 / 1_000_000.0);
-    Some(user + system)
-}
+/// Notification commands run at once.
+#[cfg(all(feature = "daemon", feature = "async"))]
+const DAEMON_NOTIFY_PARALLEL: usize = 4;
 
-#[cfg(all(feature = "daemon", not(unix)))]
-fn current_cpu_seconds() -> Option<f64> {
-    None
+/// Child processes and inter-tick waits of the daemon loop.
+///
+/// With the `async` feature they run on the tokio runtime: a stop signal
+/// kills an in-flight escalation plan instead of waiting it out, and
+/// notification commands run concurrently under a time limit so a hung
+/// notifier cannot stall the loop.
+#[cfg(feature = "daemon")]
+struct DaemonIo {
+    #[cfg(feature = "async")]
+    runtime: Option<(
+        pt_core::runtime::AsyncRuntime,
+        pt_core::runtime::CancellationToken,
+    )>,
 }
 
 #[cfg(feature = "daemon")]
-fn apply_daemon_global_args(cmd: &mut std::process::Command, global: &GlobalOpts) {
-    if let Some(dir) = &global.config {
-        cmd.arg("--config").arg(dir);
+impl DaemonIo {
+    #[cfg(feature = "async")]
+    fn new() -> Self {
+        use pt_core::runtime::AsyncRuntime;
+
+        let runtime = match AsyncRuntime::with_worker_threads(2) {
+            Ok(runtime) => {
+                let stop = runtime.flag_token(
+                    || DAEMON_SIGNALS.should_stop(),
+                    std::time::Duration::from_millis(100),
+                );
+                Some((runtime, stop))
+            }
+            Err(e) => {
+                eprintln!("[daemon] async runtime unavailable ({}), using threads", e);
+                None
+            }
+        };
+        Self { runtime }
     }
-}
 
-#[cfg(feature = "daemon")]
-fn apply_daemon_nice() {
-    #[cfg(unix)]
-    unsafe {
-        libc::setpriority(libc::PRIO_PROCESS, 0, 19);
+    #[cfg(not(feature = "async"))]
+    fn new() -> Self {
+        Self {}
     }
 
-    #[cfg(unix)]
-    {
-        let _ = std::process::Command::new("ionice")
-            .args(["-c3", "-p", &std::process::id().to_string()])
-            .status();
-    }
-}
-
-#[cfg(feature = "daemon")]
-fn load_daemon_config(global: &GlobalOpts) -> (pt_core::daemon::DaemonConfig, bool) {
-    let config_dir = resolve_config_dir(global);
-    let path = config_dir.join("daemon.json");
-    if !path.exists() {
-        return (pt_core::daemon::DaemonConfig::default(), true);
-    }
-
-    let content = match std::fs::read_to_string(&path) {
-        Ok(content) => content,
-        Err(_) => return (pt_core::daemon::DaemonConfig::default(), true),
-    };
-
-    if let Ok(config) = serde_json::from_str::<pt_core::daemon::DaemonConfig>(&content) {
-        return (config, true);
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonFileConfig {
-        enabled: Option<bool>,
-        collection_interval_seconds: Option<u64>,
-        overhead_budget: Option<DaemonBudget>,
-        triggers: Option<DaemonTriggerConfig>,
-        auto_mitigate: Option<DaemonAutoMitigate>,
-        cooldown: Option<DaemonCooldown>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonBudget {
-        max_cpu_percent: Option<f64>,
-        max_memory_mb: Option<u64>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonTriggerConfig {
-        sustained_load: Option<DaemonLoadTrigger>,
-        memory_pressure: Option<DaemonMemoryTrigger>,
-        orphan_spike: Option<DaemonOrphanTrigger>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonLoadTrigger {
-        threshold_multiplier: Option<f64>,
-        min_duration_seconds: Option<u64>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonMemoryTrigger {
-        threshold_percent: Option<f64>,
-        min_duration_seconds: Option<u64>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonOrphanTrigger {
-        threshold_delta: Option<u32>,
-        window_seconds: Option<u64>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonAutoMitigate {
-        enabled: Option<bool>,
-    }
-
-    #[derive(Deserialize)]
-    struct DaemonCooldown {
-        after_escalation_seconds: Option<u64>,
+    /// Wait before the next tick; true if a forced tick cut the wait short.
+    fn sleep(&self, seconds: u64) -> bool {
+        #[cfg(feature = "async")]
+        if let Some((runtime, stop)) = &self.runtime {
+            return seconds > 0
+                && runtime.block_on(pt_core::runtime::wait_for(
+                    std::time::Duration::from_secs(seconds),
+                    stop,
+                    || DAEMON_SIGNALS.take_force_tick(),
+                ));
+        }
+        daemon_sleep_with_interrupt(seconds)
     }
 
-    let mut config = pt_core::daemon::DaemonConfig::default();
-    let mut enabled = true;
-
-    if let Ok(file_cfg) = serde_json::from_str::<DaemonFileConfig>(&content) {
-        if let Some(value) = file_cfg.enabled {
-            enabled = value;
-        }
-        if let Some(interval) = file_cfg.collection_interval_seconds {
-            config.tick_interval_secs = interval;
-        }
-        if let Some(budget) = file_cfg.overhead_budget {
-            if let Some(cpu) = budget.max_cpu_percent {
-                config.max_cpu_percent = cpu;
-            }
-            if let Some(mem) = budget.max_memory_mb {
-                config.max_rss_mb = mem;
-            }
-        }
-        if let Some(triggers) = file_cfg.triggers {
-            if let Some(load) = triggers.sustained_load {
-                if let Some(multiplier) = load.threshold_multiplier {
-                    let cores = collect_cpu_count().max(1) as f64;
-                    config.triggers.load_threshold = cores * multiplier;
-                }
-                if let Some(seconds) = load.min_duration_seconds {
-                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
-                    config.triggers.sustained_ticks = ticks;
-                }
-            }
-            if let Some(mem) = triggers.memory_pressure {
-                if let Some(percent) = mem.threshold_percent {
-                    config.triggers.memory_threshold = (percent / 100.0).clamp(0.0, 1.0);
-                }
-                if let Some(seconds) = mem.min_duration_seconds {
-                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
-                    config.triggers.sustained_ticks = config.triggers.sustained_ticks.max(ticks);
-                }
-            }
-            if let Some(orphan) = triggers.orphan_spike {
-                if let Some(delta) = orphan.threshold_delta {
-                    config.triggers.orphan_threshold = delta;
-                }
-                if let Some(seconds) = orphan.window_seconds {
-                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
-                    config.triggers.sustained_ticks = config.triggers.sustained_ticks.max(ticks);
-                }
-            }
+    /// Run a child process to completion and collect its output.
+    fn output(&self, cmd: std::process::Command) -> Result<std::process::Output, String> {
+        #[cfg(feature = "async")]
+        if let Some((runtime, stop)) = &self.runtime {
+            return match runtime.block_on(pt_core::runtime::run_command(cmd, None, stop)) {
+                Ok(Some(output)) => Ok(output),
+                Ok(None) => Err("cancelled: daemon stopping".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
         }
-        if let Some(auto) = file_cfg.auto_mitigate {
-            if let Some(enabled) = auto.enabled {
-                config.escalation.allow_auto_mitigation = enabled;
-            }
+        let mut cmd = cmd;
+        cmd.output().map_err(|e| e.to_string())
+    }
+
+    /// Run notification commands, best-effort.
+    fn notify(&self, commands: Vec<std::process::Command>) {
+        #[cfg(feature = "async")]
+        if let Some((runtime, stop)) = &self.runtime {
+            runtime.block_on(pt_core::runtime::bounded_map(
+                commands,
+                DAEMON_NOTIFY_PARALLEL,
+                stop,
+                |cmd| {
+                    let stop = stop.clone();
+                    async move {
+                        let _ =
+                            pt_core::runtime::run_command(cmd, Some(DAEMON_NOTIFY_TIMEOUT), &stop)
+                                .await;
+                    }
+                },
+            ));
+            return;
         }
-        if let Some(cooldown) = file_cfg.cooldown {
-            if let Some(seconds) = cooldown.after_escalation_seconds {
-                config.escalation.min_interval_secs = seconds;
-            }
+        for mut cmd in commands {
+            let _ = cmd.status();
         }
     }
-
-    (config, enabled)
 }
 
-#[cfg(unix)]
-fn terminate_process(pid: u32) -> std::io::Result<()> {
-    let result = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
-    if result == 0 {
-        Ok(())
-    } else {
-        Err(std::io::Error::last_os_error())
+#[cfg(feature = "daemon")]
+fn daemon_base_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var("PROCESS_TRIAGE_DATA") {
+        return PathBuf::from(dir).join("daemon");
+    }
+    if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join("process_triage").join("daemon");
     }
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("process_triage")
+        .join("daemon")
 }
 
-#[cfg(not(unix))]
-fn terminate_process(_pid: u32) -> std::io::Result<()> {
-    Err(std::io::Error::new(
-        std::io::ErrorKind::Unsupported,
-        "terminate not supported on this platform",
-    ))
+#[cfg(feature = "daemon")]
+fn daemon_pid_path() -> PathBuf {
+    daemon_base_dir().join("daemon.pid")
 }
 
-#[cfg(unix)]
-fn is_process_running(pid: u32) -> bool {
-    let result = unsafe { libc::kill(pid as i32, 0) };
-    result == 0 || std::io::Error::last_os_error().kind() == std::io::ErrorKind::PermissionDenied
+#[cfg(feature = "daemon")]
+fn daemon_pid_lock_path() -> PathBuf {
+    daemon_base_dir().join("daemon.pid.lock")
 }
 
-#[cfg(not(unix))]
-fn is_process_running(_pid: u32) -> bool {
-    false
+#[cfg(feature = "daemon")]
+fn try_acquire_daemon_pid_lock() -> std::io::Result<Option<GlobalLock>> {
+    GlobalLock::try_acquire(&daemon_pid_lock_path(), "daemon")
 }
 
-#[derive(Debug)]
-enum ShadowExportError {
-    Io(std::io::Error),
-    Json(serde_json::Error),
+#[cfg(feature = "daemon")]
+fn daemon_state_path() -> PathBuf {
+    daemon_base_dir().join("state.json")
 }
 
-impl std::fmt::Display for ShadowExportError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ShadowExportError::Io(err) => write!(f, "I/O error: {}", err),
-            ShadowExportError::Json(err) => write!(f, "JSON error: {}", err),
-        }
-    }
+#[cfg(feature = "daemon")]
+fn daemon_scan_cache_path() -> PathBuf {
+    daemon_base_dir().join("scan_cache.json")
 }
 
-impl From<std::io::Error> for ShadowExportError {
-    fn from(err: std::io::Error) -> Self {
-        ShadowExportError::Io(err)
-    }
+#[cfg(feature = "daemon")]
+fn daemon_lock_path() -> PathBuf {
+    daemon_base_dir().join("pt.lock")
 }
 
-impl From<serde_json::Error> for ShadowExportError {
-    fn from(err: serde_json::Error) -> Self {
-        ShadowExportError::Json(err)
+#[cfg(feature = "daemon")]
+fn write_daemon_pid(pid: u32) -> std::io::Result<()> {
+    let path = daemon_pid_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
+    std::fs::write(path, pid.to_string())
 }
 
-fn collect_shadow_observations(
-    base_dir: &PathBuf,
-    limit: Option<usize>,
-) -> Result<Vec<Observation>, ShadowExportError> {
-    let mut files = Vec::new();
-    collect_shadow_files(base_dir, &mut files)?;
+#[cfg(feature = "daemon")]
+fn read_daemon_pid() -> std::io::Result<Option<u32>> {
+    let path = daemon_pid_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(content.trim().parse::<u32>().ok())
+}
 
-    let mut observations: Vec<Observation> = Vec::new();
-    for path in files {
-        let content = std::fs::read_to_string(&path)?;
-        let mut batch: Vec<Observation> = serde_json::from_str(&content)?;
-        observations.append(&mut batch);
+#[cfg(feature = "daemon")]
+fn remove_daemon_pid() -> std::io::Result<()> {
+    let path = daemon_pid_path();
+    if path.exists() {
+        std::fs::remove_file(path)?;
     }
+    Ok(())
+}
 
-    observations.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
-    if let Some(max) = limit {
-        observations.truncate(max);
+#[cfg(feature = "daemon")]
+fn cleanup_daemon_pid_if_owned(pid: u32) {
+    if let Ok(Some(current)) = read_daemon_pid() {
+        if current == pid {
+            let _ = remove_daemon_pid();
+        }
     }
-    observations.sort_by_key(|a| a.timestamp);
+}
 
-    Ok(observations)
+#[cfg(feature = "daemon")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DaemonStateBundle {
+    daemon: pt_core::daemon::DaemonState,
+    triggers: pt_core::daemon::triggers::TriggerState,
+    escalation: pt_core::daemon::escalation::EscalationState,
+    #[serde(default)]
+    notifications: pt_core::decision::escalation::PersistedEscalationState,
 }
 
-fn collect_shadow_files(dir: &PathBuf, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
-    if !dir.exists() {
-        return Ok(());
-    }
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            collect_shadow_files(&path, files)?;
-        } else if path.is_file() {
-            if path.file_name().and_then(|s| s.to_str()) == Some("stats.json") {
-                continue;
-            }
-            if path.file_name().and_then(|s| s.to_str()) == Some("pending.json") {
-                continue;
-            }
-            if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                files.push(path);
-            }
+#[cfg(feature = "daemon")]
+fn load_daemon_state(path: &Path, config: &pt_core::daemon::DaemonConfig) -> DaemonStateBundle {
+    if let Ok(content) = std::fs::read_to_string(path) {
+        if let Ok(state) = serde_json::from_str::<DaemonStateBundle>(&content) {
+            return state;
         }
     }
-    Ok(())
-}
 
-fn run_mcp(args: &McpArgs) -> ExitCode {
-    if args.transport != "stdio" {
-        eprintln!("Only 'stdio' transport is currently supported");
-        return ExitCode::ArgsError;
+    DaemonStateBundle {
+        daemon: pt_core::daemon::DaemonState::new(),
+        triggers: pt_core::daemon::triggers::TriggerState::new(&config.triggers),
+        escalation: pt_core::daemon::escalation::EscalationState::new(),
+        notifications: pt_core::decision::escalation::PersistedEscalationState::default(),
     }
+}
 
-    let mut server = pt_core::mcp::McpServer::new();
-    if let Err(e) = server.run_stdio() {
-        eprintln!("MCP server error: {}", e);
-        return ExitCode::IoError;
+#[cfg(feature = "daemon")]
+fn save_daemon_state(path: &Path, state: &DaemonStateBundle) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
-    ExitCode::Clean
+    let tmp = path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(state)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
 }
 
-fn run_schema(global: &GlobalOpts, args: &SchemaArgs) -> ExitCode {
-    use pt_core::schema::{
-        available_schemas, format_schema, generate_all_schemas, generate_schema, SchemaFormat,
-    };
-
-    let format = if args.compact {
-        SchemaFormat::JsonCompact
-    } else {
-        SchemaFormat::Json
-    };
+#[cfg(feature = "daemon")]
+struct DaemonEscalationResult {
+    session_id: String,
+    candidates_found: u32,
+    /// Returned candidates per classification.
+    classifications: BTreeMap<String, u64>,
+    /// Identities of the returned candidates.
+    targets: Vec<CandidateIdentity>,
+}
 
-    // List available types
-    if args.list {
-        match global.format {
-            OutputFormat::Json | OutputFormat::Toon => {
-                let types: Vec<_> = available_schemas()
-                    .into_iter()
-                    .map(|(name, desc)| serde_json::json!({"name": name, "description": desc}))
-                    .collect();
-                let types_value = serde_json::Value::Array(types);
-                println!("{}", format_structured_output(global, types_value));
-            }
-            OutputFormat::Jsonl => {
-                for (name, desc) in available_schemas() {
-                    let entry = serde_json::json!({"name": name, "description": desc});
-                    println!("{}", serde_json::to_string(&entry).unwrap());
-                }
-            }
-            _ => {
-                println!("Available schema types:\n");
-                for (name, desc) in available_schemas() {
-                    println!("  {:<25} {}", name, desc);
-                }
-                println!("\nUse 'pt schema <TYPE>' to generate a schema.");
-            }
-        }
-        return ExitCode::Clean;
+#[cfg(feature = "daemon")]
+fn run_daemon_escalation(
+    global: &GlobalOpts,
+    _triggers: &[pt_core::daemon::triggers::FiredTrigger],
+    esc_config: &pt_core::daemon::escalation::EscalationConfig,
+    scan_cache_path: &Path,
+    io: &DaemonIo,
+) -> Result<DaemonEscalationResult, String> {
+    let quick = run_daemon_plan(
+        global,
+        None,
+        false,
+        esc_config.max_deep_scan_targets,
+        scan_cache_path,
+        io,
+    )?;
+    if quick.candidates_found == 0 {
+        return Ok(quick);
     }
 
-    // Generate all schemas
-    if args.all {
-        let schemas = generate_all_schemas();
-        match global.format {
-            OutputFormat::Json | OutputFormat::Toon => {
-                let schemas_value = serde_json::to_value(&schemas).unwrap_or_default();
-                println!("{}", format_structured_output(global, schemas_value));
-            }
-            OutputFormat::Jsonl => {
-                for (name, schema) in schemas {
-                    let entry = serde_json::json!({"type": name, "schema": schema});
-                    println!("{}", serde_json::to_string(&entry).unwrap());
-                }
-            }
-            _ => {
-                // Human-readable: output each schema separately
-                for (name, schema) in schemas {
-                    println!("# {}\n", name);
-                    println!("{}\n", format_schema(&schema, format));
-                }
-            }
+    match run_daemon_plan(
+        global,
+        Some(&quick.session_id),
+        true,
+        esc_config.max_deep_scan_targets,
+        scan_cache_path,
+        io,
+    ) {
+        Ok(deep) => Ok(deep),
+        Err(err) => {
+            eprintln!(
+                "daemon escalation: deep plan failed, using quick plan: {}",
+                err
+            );
+            Ok(quick)
         }
-        return ExitCode::Clean;
     }
+}
 
-    // Generate schema for a specific type
-    if let Some(ref type_name) = args.type_name {
-        match generate_schema(type_name) {
-            Some(schema) => {
-                match global.format {
-                    OutputFormat::Jsonl => {
-                        println!("{}", serde_json::to_string(&schema).unwrap());
-                    }
-                    _ => {
-                        println!("{}", format_schema(&schema, format));
-                    }
-                }
-                ExitCode::Clean
-            }
-            None => {
-                eprintln!("Unknown type: {}", type_name);
-                eprintln!("\nUse 'pt schema --list' to see available types.");
-                ExitCode::PartialFail
-            }
-        }
-    } else {
-        eprintln!("Usage: pt schema <TYPE> | --list | --all");
-        eprintln!("\nUse 'pt schema --list' to see available types.");
-        ExitCode::PartialFail
+#[cfg(feature = "daemon")]
+fn run_daemon_plan(
+    global: &GlobalOpts,
+    session_id: Option<&str>,
+    deep: bool,
+    max_candidates: u32,
+    scan_cache_path: &Path,
+    io: &DaemonIo,
+) -> Result<DaemonEscalationResult, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(["--format", "json", "agent", "plan", "--max-candidates"])
+        .arg(max_candidates.to_string());
+    if deep {
+        cmd.arg("--deep");
     }
-}
+    if let Some(session) = session_id {
+        cmd.arg("--session").arg(session);
+    }
+    cmd.stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .env("PT_SKIP_GLOBAL_LOCK", "1")
+        .env(pt_core::collect::SCAN_CACHE_ENV, scan_cache_path);
 
-fn print_version(global: &GlobalOpts) {
-    let version_info = serde_json::json!({
-        "schema_version": SCHEMA_VERSION,
-        "pt_core_version": env!("CARGO_PKG_VERSION"),
-        "rust_version": env!("CARGO_PKG_RUST_VERSION"),
-    });
+    apply_daemon_global_args(&mut cmd, global);
 
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            println!("{}", format_structured_output(global, version_info));
-        }
-        OutputFormat::Exitcode => {}
-        _ => {
-            println!("pt-core {}", env!("CARGO_PKG_VERSION"));
-            println!("schema version: {}", SCHEMA_VERSION);
+    let output = io.output(cmd)?;
+    let stdout = output.stdout;
+    if stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "daemon escalation: empty stdout (status {:?}): {}",
+            output.status.code(),
+            stderr.trim()
+        ));
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&stdout).map_err(|e| format!("invalid JSON: {}", e))?;
+    let session_id = json
+        .get("session_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "missing session_id in plan output".to_string())?
+        .to_string();
+    let candidates_found = json
+        .get("summary")
+        .and_then(|s| s.get("candidates_found"))
+        .and_then(|v| v.as_u64())
+        .or_else(|| {
+            json.get("candidates")
+                .and_then(|v| v.as_array())
+                .map(|a| a.len() as u64)
+        })
+        .unwrap_or(0) as u32;
+    let mut classifications: BTreeMap<String, u64> = BTreeMap::new();
+    let mut targets = Vec::new();
+    for candidate in json
+        .get("candidates")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(class) = candidate.get("classification").and_then(|v| v.as_str()) {
+            *classifications.entry(class.to_string()).or_insert(0) += 1;
         }
+        targets.extend(CandidateIdentity::from_candidate(candidate));
     }
+
+    Ok(DaemonEscalationResult {
+        session_id,
+        candidates_found,
+        classifications,
+        targets,
+    })
 }
 
-fn output_stub(global: &GlobalOpts, command: &str, message: &str) {
-    let session_id = SessionId::new();
+#[cfg(feature = "daemon")]
+fn collect_daemon_metrics(
+    scan_cache: &pt_core::collect::SharedScanCache,
+    psi_info: &pt_common::PsiInfo,
+) -> pt_core::daemon::TickMetrics {
+    let load = collect_load_averages();
+    let load_avg_1 = load.first().copied().unwrap_or(0.0);
+    let load_avg_5 = load.get(1).copied().unwrap_or(load_avg_1);
 
-    output_stub_with_session(global, &session_id, command, message);
+    let memory = collect_memory_info();
+    let total_gb = memory
+        .get("total_gb")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+    let used_gb = memory
+        .get("used_gb")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+    let memory_total_mb = (total_gb * 1024.0).round() as u64;
+    let memory_used_mb = (used_gb * 1024.0).round() as u64;
+    let (process_count, orphan_count) = daemon_process_counts(scan_cache);
+
+    pt_core::daemon::TickMetrics {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        load_avg_1,
+        load_avg_5,
+        memory_used_mb,
+        memory_total_mb,
+        swap_used_mb: collect_swap_used_mb(),
+        process_count,
+        orphan_count,
+        psi: pt_core::daemon::schedule::PsiSnapshot::collect(psi_info),
+    }
 }
 
-fn output_stub_with_session(
-    global: &GlobalOpts,
-    session_id: &SessionId,
-    command: &str,
-    message: &str,
-) {
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            let output = serde_json::json!({
-                "schema_version": SCHEMA_VERSION,
-                "session_id": session_id.0,
-                "generated_at": chrono::Utc::now().to_rfc3339(),
-                "command": command,
-                "status": "stub",
-                "message": message
-            });
-            println!("{}", format_structured_output(global, output));
-        }
-        OutputFormat::Summary => {
-            println!("[{}] {}: {}", session_id, command, message);
+/// Process and orphan counts for a daemon tick.
+///
+/// On Linux these come from an incremental scan through the daemon's warm
+/// cache, which also keeps the cache current for escalation plans.
+#[cfg(feature = "daemon")]
+fn daemon_process_counts(scan_cache: &pt_core::collect::SharedScanCache) -> (u32, u32) {
+    #[cfg(target_os = "linux")]
+    {
+        let options = QuickScanOptions {
+            include_kernel_threads: true,
+            incremental: Some(scan_cache.clone()),
+            ..Default::default()
+        };
+        if let Ok(scan) = quick_scan(&options) {
+            let orphans = scan.processes.iter().filter(|p| p.ppid.0 == 1).count();
+            return (scan.processes.len() as u32, orphans as u32);
         }
-        OutputFormat::Exitcode => {}
-        _ => {
-            println!("# pt-core {}", command);
-            println!();
-            println!("{}", message);
-            println!();
-            println!("Session: {}", session_id);
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = scan_cache;
+    (collect_process_count(), collect_orphan_count())
+}
+
+#[cfg(feature = "daemon")]
+fn collect_swap_used_mb() -> u64 {
+    let content = match std::fs::read_to_string("/proc/meminfo") {
+        Ok(c) => c,
+        Err(_) => return 0,
+    };
+    let mut total = 0u64;
+    let mut free = 0u64;
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("SwapTotal:") {
+            total = rest
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(0);
+        } else if let Some(rest) = line.strip_prefix("SwapFree:") {
+            free = rest
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(0);
         }
     }
+    total.saturating_sub(free) / 1024
 }
 
-fn run_agent_capabilities(global: &GlobalOpts, args: &AgentCapabilitiesArgs) -> ExitCode {
-    // If --check-action is specified, just check that specific action
-    if let Some(action) = &args.check_action {
-        let caps = get_capabilities();
-        let (supported, reason) = match action.to_lowercase().as_str() {
-            "sigterm" | "terminate" | "term" => (true, "SIGTERM always available"),
-            "sigkill" | "kill" => (true, "SIGKILL always available"),
-            "sigstop" | "stop" => (true, "SIGSTOP always available"),
-            "sigcont" | "cont" | "continue" => (true, "SIGCONT always available"),
-            "strace" => (
-                caps.tools.strace.available && caps.tools.strace.works,
-                if caps.tools.strace.available {
-                    "strace available"
-                } else {
-                    "strace not installed"
-                },
-            ),
-            "perf" => (
-                caps.tools.perf.available && caps.tools.perf.works,
-                if caps.tools.perf.available {
-                    "perf available"
-                } else {
-                    "perf not installed"
-                },
-            ),
-            "lsof" => (
-                caps.tools.lsof.available && caps.tools.lsof.works,
-                if caps.tools.lsof.available {
-                    "lsof available"
-                } else {
-                    "lsof not installed"
-                },
-            ),
-            "nice" | "renice" => (
-                caps.tools.renice.available,
-                if caps.tools.renice.available {
-                    "renice available"
-                } else {
-                    "renice not installed"
-                },
-            ),
-            "ionice" => (
-                caps.tools.ionice.available,
-                if caps.tools.ionice.available {
-                    "ionice available"
-                } else {
-                    "ionice not installed"
-                },
-            ),
-            "cgroup" | "cgroups" => (
-                caps.data_sources.cgroup_v2,
-                if caps.data_sources.cgroup_v2 {
-                    "cgroups v2 available"
-                } else {
-                    "cgroups v2 not available"
-                },
-            ),
-            "docker" => (
-                caps.tools.docker.available,
-                if caps.tools.docker.available {
-                    "docker available"
-                } else {
-                    "docker not installed"
-                },
-            ),
-            "podman" => (
-                caps.tools.podman.available,
-                if caps.tools.podman.available {
-                    "podman available"
-                } else {
-                    "podman not installed"
-                },
-            ),
-            "sudo" => (
-                caps.permissions.can_sudo,
-                if caps.permissions.can_sudo {
-                    "sudo available"
-                } else {
-                    "cannot sudo"
-                },
-            ),
-            "root" => (
-                caps.permissions.is_root,
-                if caps.permissions.is_root {
-                    "running as root"
-                } else {
-                    "not running as root"
-                },
-            ),
-            _ => (false, "unknown action type"),
-        };
+#[cfg(all(feature = "daemon", target_os = "linux"))]
+fn collect_orphan_count() -> u32 {
+    let mut count = 0u32;
+    let entries = match std::fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
 
-        match global.format {
-            OutputFormat::Json | OutputFormat::Toon => {
-                let output = serde_json::json!({
-                    "action": action,
-                    "supported": supported,
-                    "reason": reason,
-                });
-                println!("{}", format_structured_output(global, output));
-            }
-            OutputFormat::Summary => {
-                if supported {
-                    println!("[capabilities] {}: supported", action);
-                } else {
-                    println!("[capabilities] {}: not supported ({})", action, reason);
-                }
-            }
-            OutputFormat::Exitcode => {}
-            _ => {
-                if supported {
-                    println!("Action '{}' is supported: {}", action, reason);
-                } else {
-                    println!("Action '{}' is NOT supported: {}", action, reason);
-                }
-            }
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let pid_str = match file_name.to_str() {
+            Some(name) if name.chars().all(|c| c.is_ascii_digit()) => name,
+            _ => continue,
+        };
+        let stat_path = format!("/proc/{}/stat", pid_str);
+        let stat = match std::fs::read_to_string(stat_path) {
+            Ok(stat) => stat,
+            Err(_) => continue,
+        };
+        let end = match stat.rfind(')') {
+            Some(pos) => pos,
+            None => continue,
+        };
+        let rest = match stat.get(end + 2..) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let mut parts = rest.split_whitespace();
+        let _state = parts.next();
+        let ppid = match parts.next() {
+            Some(ppid) => ppid,
+            None => continue,
+        };
+        if ppid == "1" {
+            count = count.saturating_add(1);
         }
+    }
 
-        return if supported {
-            ExitCode::Clean
-        } else {
-            ExitCode::CapabilityError
-        };
+    count
+}
+
+#[cfg(all(feature = "daemon", target_os = "linux"))]
+fn current_rss_mb() -> Option<u64> {
+    let stats = pt_core::collect::parse_statm(std::process::id())?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if page_size <= 0 {
+        return None;
     }
+    let rss_bytes = stats.resident.saturating_mul(page_size as u64);
+    Some(rss_bytes / 1024 / 1024)
+}
 
-    // Otherwise, output full capabilities
-    output_capabilities(global);
-    ExitCode::Clean
+#[cfg(all(feature = "daemon", not(target_os = "linux")))]
+fn collect_orphan_count() -> u32 {
+    0
 }
 
-fn output_capabilities(global: &GlobalOpts) {
-    let session_id = SessionId::new();
+#[cfg(all(feature = "daemon", not(target_os = "linux")))]
+fn current_rss_mb() -> Option<u64> {
+    None
+}
 
-    // Detect actual system capabilities (get_capabilities handles cache internally)
-    let caps = get_capabilities();
+#[cfg(all(feature = "daemon", unix))]
+fn current_cpu_seconds() -> Option<f64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+    let user = usage.ru_utime.tv_sec as f64 + (usage.ru_utime.tv_usec as f64 / 1_000_000.0);
+    let system = usage.ru_stime.tv_sec as f64 + (usage.ru_stime.tv_usec as f64 / 1_000_000.0);
+    Some(user + system)
+}
 
-    // Build tools map for output
-    let mut tools_output = serde_json::Map::new();
-    let tool_list: [(&str, &ToolCapability); 14] = [
-        ("ps", &caps.tools.ps),
-        ("lsof", &caps.tools.lsof),
-        ("ss", &caps.tools.ss),
-        ("netstat", &caps.tools.netstat),
-        ("perf", &caps.tools.perf),
-        ("strace", &caps.tools.strace),
-        ("dtrace", &caps.tools.dtrace),
-        ("bpftrace", &caps.tools.bpftrace),
-        ("systemctl", &caps.tools.systemctl),
-        ("docker", &caps.tools.docker),
-        ("podman", &caps.tools.podman),
-        ("nice", &caps.tools.nice),
-        ("renice", &caps.tools.renice),
-        ("ionice", &caps.tools.ionice),
-    ];
-    for (name, tool) in tool_list {
-        let mut tool_info = serde_json::Map::new();
-        tool_info.insert(
-            "available".to_string(),
-            serde_json::Value::Bool(tool.available),
-        );
-        if let Some(ref v) = tool.version {
-            tool_info.insert("version".to_string(), serde_json::Value::String(v.clone()));
-        }
-        if let Some(ref p) = tool.path {
-            tool_info.insert("path".to_string(), serde_json::Value::String(p.clone()));
-        }
-        tool_info.insert("works".to_string(), serde_json::Value::Bool(tool.works));
-        if !tool.available {
-            tool_info.insert(
-                "reason".to_string(),
-                serde_json::Value::String(
-                    tool.error.clone().unwrap_or_else(|| "not installed".into()),
-                ),
-            );
-        }
-        tools_output.insert(name.to_string(), serde_json::Value::Object(tool_info));
+#[cfg(all(feature = "daemon", not(unix)))]
+fn current_cpu_seconds() -> Option<f64> {
+    None
+}
+
+#[cfg(feature = "daemon")]
+fn apply_daemon_global_args(cmd: &mut std::process::Command, global: &GlobalOpts) {
+    if let Some(dir) = &global.config {
+        cmd.arg("--config").arg(dir);
     }
+}
 
-    let capabilities_json = serde_json::json!({
-        "schema_version": SCHEMA_VERSION,
-        "session_id": session_id.0,
-        "generated_at": chrono::Utc::now().to_rfc3339(),
-        "os": {
-            "family": caps.platform.os,
-            "arch": caps.platform.arch,
-            "kernel": caps.platform.kernel_release,
-            "in_container": caps.platform.in_container,
-            "container_runtime": caps.platform.container_runtime,
-        },
-        "tools": tools_output,
-        "permissions": {
-            "effective_uid": caps.permissions.effective_uid,
-            "is_root": caps.permissions.is_root,
-            "can_sudo": caps.permissions.can_sudo,
-            "can_read_others_procs": caps.permissions.can_read_others_procs,
-            "can_signal_others": caps.permissions.can_signal_others,
-            "linux_capabilities": caps.permissions.linux_capabilities,
-        },
-        "data_sources": {
-            "procfs": caps.data_sources.procfs,
-            "sysfs": caps.data_sources.sysfs,
-            "perf_events": caps.data_sources.perf_events,
-            "ebpf": caps.data_sources.ebpf,
-            "schedstat": caps.data_sources.schedstat,
-            "cgroup_v1": caps.data_sources.cgroup_v1,
-            "cgroup_v2": caps.data_sources.cgroup_v2,
-        },
-        "supervisors": {
-            "systemd": caps.supervisors.systemd,
-            "launchd": caps.supervisors.launchd,
-            "pm2": caps.supervisors.pm2,
-            "supervisord": caps.supervisors.supervisord,
-            "docker_daemon": caps.supervisors.docker_daemon,
-            "podman": caps.supervisors.podman_available,
-            "kubernetes": caps.supervisors.kubernetes,
-        },
-        "actions": {
-            "kill": caps.actions.kill,
-            "pause": caps.actions.pause,
-            "renice": caps.actions.renice,
-            "ionice": caps.actions.ionice,
-            "cgroup_freeze": caps.actions.cgroup_freeze,
-            "cgroup_throttle": caps.actions.cgroup_throttle,
-            "cpuset_quarantine": caps.actions.cpuset_quarantine,
-        },
-        "features": {
-            "deep_scan": caps.can_deep_scan(),
-            "maximal_scan": caps.can_maximal_scan(),
-        },
-        "detected_at": caps.detected_at,
-    });
+#[cfg(feature = "daemon")]
+fn apply_daemon_nice() {
+    #[cfg(unix)]
+    unsafe {
+        libc::setpriority(libc::PRIO_PROCESS, 0, 19);
+    }
 
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            println!("{}", format_structured_output(global, capabilities_json));
-        }
-        OutputFormat::Exitcode => {}
-        _ => {
-            println!("# Capabilities");
-            println!();
-            println!("## Platform");
-            println!(
-                "OS: {} ({}) kernel: {}",
-                caps.platform.os,
-                caps.platform.arch,
-                caps.platform.kernel_release.as_deref().unwrap_or("unknown")
-            );
-            if caps.platform.in_container {
-                println!(
-                    "Container: {} ({})",
-                    caps.platform.in_container,
-                    caps.platform
-                        .container_runtime
-                        .as_deref()
-                        .unwrap_or("unknown")
-                );
-            }
-            println!();
-            println!("## Permissions");
-            println!(
-                "UID: {} (root: {})",
-                caps.permissions.effective_uid, caps.permissions.is_root
-            );
-            println!("Sudo: {}", caps.permissions.can_sudo);
-            println!("Read others: {}", caps.permissions.can_read_others_procs);
-            println!("Signal others: {}", caps.permissions.can_signal_others);
-            println!();
-            println!(
-                "## Tools ({}/{} available)",
-                caps.tools.available_count(),
-                caps.tools.total_count()
-            );
-            for (name, tool) in [
-                ("ps", &caps.tools.ps),
-                ("lsof", &caps.tools.lsof),
-                ("perf", &caps.tools.perf),
-                ("strace", &caps.tools.strace),
-                ("bpftrace", &caps.tools.bpftrace),
-            ] {
-                let status = if tool.works {
-                    format!("ok ({})", tool.version.as_deref().unwrap_or("?"))
-                } else if tool.available {
-                    "broken".to_string()
-                } else {
-                    "not found".to_string()
-                };
-                println!("  {}: {}", name, status);
-            }
-            println!();
-            println!(
-                "## Actions ({}/{} available)",
-                caps.actions.available_count(),
-                caps.actions.total_count()
-            );
-            println!(
-                "  kill: {}, pause: {}, renice: {}",
-                caps.actions.kill, caps.actions.pause, caps.actions.renice
-            );
-            println!(
-                "  cgroup_freeze: {}, cgroup_throttle: {}",
-                caps.actions.cgroup_freeze, caps.actions.cgroup_throttle
-            );
-            println!();
-            println!("## Features");
-            println!("  deep_scan: {}", caps.can_deep_scan());
-            println!("  maximal_scan: {}", caps.can_maximal_scan());
-        }
+    #[cfg(unix)]
+    {
+        let _ = std::process::Command::new("ionice")
+            .args(["-c3", "-p", &std::process::id().to_string()])
+            .status();
     }
 }
 
-// ============================================================================
-// System State Collection
-// ============================================================================
+#[cfg(feature = "daemon")]
+fn load_daemon_config(global: &GlobalOpts) -> (pt_core::daemon::DaemonConfig, bool) {
+    let config_dir = resolve_config_dir(global);
+    let path = config_dir.join("daemon.json");
+    if !path.exists() {
+        return (pt_core::daemon::DaemonConfig::default(), true);
+    }
 
-/// Collect system state for snapshot output.
-fn collect_system_state() -> serde_json::Value {
-    let load = collect_load_averages();
-    let cores = collect_cpu_count();
-    let memory = collect_memory_info();
-    let process_count = collect_process_count();
-    let psi = collect_psi();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return (pt_core::daemon::DaemonConfig::default(), true),
+    };
 
-    serde_json::json!({
-        "load": load,
-        "cores": cores,
-        "memory": memory,
-        "process_count": process_count,
-        "psi": psi,
-    })
-}
+    if let Ok(config) = serde_json::from_str::<pt_core::daemon::DaemonConfig>(&content) {
+        return (config, true);
+    }
 
-/// Read /proc/loadavg and return [1min, 5min, 15min].
-fn collect_load_averages() -> Vec<f64> {
-    std::fs::read_to_string("/proc/loadavg")
-        .ok()
-        .and_then(|content| {
-            let parts: Vec<&str> = content.split_whitespace().collect();
-            if parts.len() >= 3 {
-                let load1 = parts[0].parse::<f64>().ok()?;
-                let load5 = parts[1].parse::<f64>().ok()?;
-                let load15 = parts[2].parse::<f64>().ok()?;
-                Some(vec![load1, load5, load15])
-            } else {
-                None
-            }
-        })
-        .unwrap_or_default()
-}
+    #[derive(Deserialize)]
+    struct DaemonFileConfig {
+        enabled: Option<bool>,
+        collection_interval_seconds: Option<u64>,
+        overhead_budget: Option<DaemonBudget>,
+        triggers: Option<DaemonTriggerConfig>,
+        auto_mitigate: Option<DaemonAutoMitigate>,
+        cooldown: Option<DaemonCooldown>,
+        adaptive: Option<pt_core::daemon::schedule::AdaptiveScheduleConfig>,
+        #[cfg(feature = "metrics")]
+        metrics: Option<pt_core::daemon::metrics::MetricsConfig>,
+    }
 
-/// Get CPU count from /proc/cpuinfo or nproc.
-fn collect_cpu_count() -> u32 {
-    // Try reading from /proc/cpuinfo
-    if let Ok(content) = std::fs::read_to_string("/proc/cpuinfo") {
-        let count = content
-            .lines()
-            .filter(|line| line.starts_with("processor"))
-            .count();
-        if count > 0 {
-            return count as u32;
-        }
+    #[derive(Deserialize)]
+    struct DaemonBudget {
+        max_cpu_percent: Option<f64>,
+        max_memory_mb: Option<u64>,
     }
-    // Fallback to nproc command
-    std::process::Command::new("nproc")
-        .output()
-        .ok()
-        .and_then(|output| String::from_utf8(output.stdout).ok()?.trim().parse().ok())
-        .unwrap_or(1)
-}
 
-/// Read /proc/meminfo and return memory stats in GB.
-fn collect_memory_info() -> serde_json::Value {
-    let (total_kb, available_kb) = std::fs::read_to_string("/proc/meminfo")
-        .ok()
-        .map(|content| {
-            let mut total: u64 = 0;
-            let mut available: u64 = 0;
-            for line in content.lines() {
-                if let Some(rest) = line.strip_prefix("MemTotal:") {
-                    total = rest
-                        .split_whitespace()
-                        .next()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(0);
-                } else if let Some(rest) = line.strip_prefix("MemAvailable:") {
-                    available = rest
-                        .split_whitespace()
-                        .next()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(0);
-                }
-            }
-            (total, available)
-        })
-        .unwrap_or((0, 0));
+    #[derive(Deserialize)]
+    struct DaemonTriggerConfig {
+        sustained_load: Option<DaemonLoadTrigger>,
+        memory_pressure: Option<DaemonMemoryTrigger>,
+        orphan_spike: Option<DaemonOrphanTrigger>,
+    }
 
-    let total_gb = (total_kb as f64) / 1024.0 / 1024.0;
-    let available_gb = (available_kb as f64) / 1024.0 / 1024.0;
-    let used_gb = total_gb - available_gb;
+    #[derive(Deserialize)]
+    struct DaemonLoadTrigger {
+        threshold_multiplier: Option<f64>,
+        min_duration_seconds: Option<u64>,
+    }
 
-    serde_json::json!({
-        "total_gb": (total_gb * 10.0).round() / 10.0,
-        "used_gb": (used_gb * 10.0).round() / 10.0,
-        "available_gb": (available_gb * 10.0).round() / 10.0,
-    })
-}
+    #[derive(Deserialize)]
+    struct DaemonMemoryTrigger {
+        threshold_percent: Option<f64>,
+        min_duration_seconds: Option<u64>,
+    }
 
-/// Count process directories in /proc.
-fn collect_process_count() -> u32 {
-    std::fs::read_dir("/proc")
-        .ok()
-        .map(|entries| {
-            entries
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.file_name()
-                        .to_str()
-                        .map(|s| s.chars().all(|c| c.is_ascii_digit()))
-                        .unwrap_or(false)
-                })
-                .count() as u32
-        })
-        .unwrap_or(0)
-}
+    #[derive(Deserialize)]
+    struct DaemonOrphanTrigger {
+        threshold_delta: Option<u32>,
+        window_seconds: Option<u64>,
+    }
 
-/// Read PSI (Pressure Stall Information) from /proc/pressure/.
-fn collect_psi() -> serde_json::Value {
-    fn read_psi_file(resource: &str) -> Option<f64> {
-        let path = format!("/proc/pressure/{}", resource);
-        std::fs::read_to_string(&path).ok().and_then(|content| {
-            // Parse "some avg10=X.XX avg60=Y.YY avg300=Z.ZZ total=N"
-            // We want avg10 for recent pressure
-            for line in content.lines() {
-                if line.starts_with("some") {
-                    for part in line.split_whitespace() {
-                        if let Some(val) = part.strip_prefix("avg10=") {
-                            return val.parse().ok();
-                        }
-                    }
+    #[derive(Deserialize)]
+    struct DaemonAutoMitigate {
+        enabled: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    struct DaemonCooldown {
+        after_escalation_seconds: Option<u64>,
+    }
+
+    let mut config = pt_core::daemon::DaemonConfig::default();
+    let mut enabled = true;
+
+    if let Ok(file_cfg) = serde_json::from_str::<DaemonFileConfig>(&content) {
+        if let Some(value) = file_cfg.enabled {
+            enabled = value;
+        }
+        if let Some(interval) = file_cfg.collection_interval_seconds {
+            config.tick_interval_secs = interval;
+        }
+        if let Some(budget) = file_cfg.overhead_budget {
+            if let Some(cpu) = budget.max_cpu_percent {
+                config.max_cpu_percent = cpu;
+            }
+            if let Some(mem) = budget.max_memory_mb {
+                config.max_rss_mb = mem;
+            }
+        }
+        if let Some(triggers) = file_cfg.triggers {
+            if let Some(load) = triggers.sustained_load {
+                if let Some(multiplier) = load.threshold_multiplier {
+                    let cores = collect_cpu_count().max(1) as f64;
+                    config.triggers.load_threshold = cores * multiplier;
+                }
+                if let Some(seconds) = load.min_duration_seconds {
+                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
+                    config.triggers.sustained_ticks = ticks;
                 }
             }
-            None
-        })
+            if let Some(mem) = triggers.memory_pressure {
+                if let Some(percent) = mem.threshold_percent {
+                    config.triggers.memory_threshold = (percent / 100.0).clamp(0.0, 1.0);
+                }
+                if let Some(seconds) = mem.min_duration_seconds {
+                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
+                    config.triggers.sustained_ticks = config.triggers.sustained_ticks.max(ticks);
+                }
+            }
+            if let Some(orphan) = triggers.orphan_spike {
+                if let Some(delta) = orphan.threshold_delta {
+                    config.triggers.orphan_threshold = delta;
+                }
+                if let Some(seconds) = orphan.window_seconds {
+                    let ticks = (seconds / config.tick_interval_secs.max(1)).max(1) as u32;
+                    config.triggers.sustained_ticks = config.triggers.sustained_ticks.max(ticks);
+                }
+            }
+        }
+        if let Some(auto) = file_cfg.auto_mitigate {
+            if let Some(enabled) = auto.enabled {
+                config.escalation.allow_auto_mitigation = enabled;
+            }
+        }
+        if let Some(cooldown) = file_cfg.cooldown {
+            if let Some(seconds) = cooldown.after_escalation_seconds {
+                config.escalation.min_interval_secs = seconds;
+            }
+        }
+        if let Some(adaptive) = file_cfg.adaptive {
+            config.adaptive = adaptive;
+        }
+        #[cfg(feature = "metrics")]
+        {
+            config.metrics = file_cfg.metrics;
+        }
     }
 
-    serde_json::json!({
-        "cpu": read_psi_file("cpu").unwrap_or(0.0),
-        "memory": read_psi_file("memory").unwrap_or(0.0),
-        "io": read_psi_file("io").unwrap_or(0.0),
-    })
+    (config, enabled)
 }
 
-/// Get the system hostname.
-fn collect_hostname() -> String {
-    // Try /etc/hostname first
-    if let Ok(hostname) = std::fs::read_to_string("/etc/hostname") {
-        let hostname = hostname.trim();
-        if !hostname.is_empty() {
-            return hostname.to_string();
-        }
-    }
-    // Fallback to HOSTNAME env var
-    if let Ok(hostname) = std::env::var("HOSTNAME") {
-        return hostname;
-    }
-    // Last resort: use gethostname
-    std::process::Command::new("hostname")
-        .output()
-        .ok()
-        .and_then(|output| String::from_utf8(output.stdout).ok())
-        .map(|s| s.trim().to_string())
-        .unwrap_or_else(|| "unknown".to_string())
+/// Stop or continue the targets of a staged kill.
+///
+/// Targets that exited or whose PID was reused are skipped. Returns the PIDs
+/// that were signalled.
+#[cfg(all(feature = "ui", target_os = "linux"))]
+fn signal_staged_targets(targets: &[ProcessIdentity], signal: StageSignal) -> Vec<u32> {
+    let identity_provider = LiveIdentityProvider::new();
+    let runner = SignalActionRunner::new(SignalConfig::default());
+    targets
+        .iter()
+        .filter(|target| identity_provider.revalidate(target).unwrap_or(false))
+        .filter(|target| {
+            let pid = target.pid.0;
+            let sent = match signal {
+                StageSignal::Stop => runner.pause(pid, false, None),
+                StageSignal::Continue => runner.resume(pid, false, None),
+            };
+            if let Err(err) = &sent {
+                tracing::warn!(pid, error = %err, ?signal, "staged signal failed");
+            }
+            sent.is_ok()
+        })
+        .map(|target| target.pid.0)
+        .collect()
 }
 
-/// Collect host information in the structured format for JSON output.
-fn collect_host_info() -> serde_json::Value {
-    let hostname = collect_hostname();
-    let cores = collect_cpu_count();
-    let memory = collect_memory_info();
-    let load = collect_load_averages();
+#[cfg(unix)]
+fn terminate_process(pid: u32) -> std::io::Result<()> {
+    let result = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
 
-    let total_gb = memory
-        .get("total_gb")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
-    let used_gb = memory
-        .get("used_gb")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
+#[cfg(not(unix))]
+fn terminate_process(_pid: u32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "terminate not supported on this platform",
+    ))
+}
 
-    serde_json::json!({
-        "hostname": hostname,
-        "cores": cores,
-        "memory_total_gb": total_gb,
-        "memory_used_gb": used_gb,
-        "load_avg": load,
-    })
+#[cfg(unix)]
+fn is_process_running(pid: u32) -> bool {
+    let result = unsafe { libc::kill(pid as i32, 0) };
+    result == 0 || std::io::Error::last_os_error().kind() == std::io::ErrorKind::PermissionDenied
 }
 
-/// Format duration in human-readable form (e.g., "11d 2h 30m").
-fn format_duration_human(seconds: u64) -> String {
-    let days = seconds / 86400;
-    let hours = (seconds % 86400) / 3600;
-    let minutes = (seconds % 3600) / 60;
+#[cfg(not(unix))]
+fn is_process_running(_pid: u32) -> bool {
+    false
+}
 
-    if days > 0 {
-        format!("{}d {}h {}m", days, hours, minutes)
-    } else if hours > 0 {
-        format!("{}h {}m", hours, minutes)
-    } else {
-        format!("{}m", minutes)
-    }
+#[derive(Debug)]
+enum ShadowExportError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
 }
 
-fn parse_prediction_fields(spec: &str) -> Result<PredictionFieldSelector, String> {
-    let spec = spec.trim();
-    if spec.is_empty() {
-        return Err("prediction fields cannot be empty".to_string());
+impl std::fmt::Display for ShadowExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShadowExportError::Io(err) => write!(f, "I/O error: {}", err),
+            ShadowExportError::Json(err) => write!(f, "JSON error: {}", err),
+        }
     }
+}
 
-    let mut include = Vec::new();
-    for raw in spec.split(',') {
-        let field = raw.trim().to_lowercase();
-        if field.is_empty() {
-            continue;
-        }
-        let parsed = match field.as_str() {
-            "memory" => PredictionField::Memory,
-            "cpu" => PredictionField::Cpu,
-            "eta_abandoned" => PredictionField::EtaAbandoned,
-            "eta_resource_limit" => PredictionField::EtaResourceLimit,
-            "trajectory" => PredictionField::Trajectory,
-            "diagnostics" => PredictionField::Diagnostics,
-            _ => return Err(format!("unknown prediction field: {}", field)),
-        };
-        if !include.contains(&parsed) {
-            include.push(parsed);
-        }
+impl From<std::io::Error> for ShadowExportError {
+    fn from(err: std::io::Error) -> Self {
+        ShadowExportError::Io(err)
     }
+}
 
-    if include.is_empty() {
-        return Err("prediction fields cannot be empty".to_string());
+impl From<serde_json::Error> for ShadowExportError {
+    fn from(err: serde_json::Error) -> Self {
+        ShadowExportError::Json(err)
     }
+}
 
-    Ok(PredictionFieldSelector { include })
+fn collect_shadow_observations(
+    base_dir: &PathBuf,
+    limit: Option<usize>,
+) -> Result<Vec<Observation>, ShadowExportError> {
+    let mut files = Vec::new();
+    collect_shadow_files(base_dir, &mut files)?;
+
+    let mut observations: Vec<Observation> = Vec::new();
+    for path in files {
+        let content = std::fs::read_to_string(&path)?;
+        let mut batch: Vec<Observation> = serde_json::from_str(&content)?;
+        observations.append(&mut batch);
+    }
+
+    observations.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
+    if let Some(max) = limit {
+        observations.truncate(max);
+    }
+    observations.sort_by_key(|a| a.timestamp);
+
+    Ok(observations)
 }
 
-fn build_stub_predictions(proc: &ProcessRecord) -> Predictions {
-    let window_secs = proc.elapsed.as_secs_f64().max(0.0);
-    Predictions {
-        memory: Some(MemoryPrediction {
-            rss_slope_bytes_per_sec: 0.0,
-            trend: Trend::Stable,
-            confidence: 0.0,
-            window_secs,
-        }),
-        cpu: Some(CpuPrediction {
-            usage_slope_pct_per_sec: 0.0,
-            trend: Trend::Stable,
-            confidence: 0.0,
-            window_secs,
-        }),
-        eta_abandoned: None,
-        eta_resource_limit: None,
-        trajectory: Some(TrajectoryAssessment {
-            label: TrajectoryLabel::Unknown,
-            confidence: 0.0,
-            summary: "insufficient history for trajectory prediction".to_string(),
-        }),
-        diagnostics: Some(PredictionDiagnostics {
-            n_observations: 1,
-            calibrated: false,
-            model: "snapshot".to_string(),
-            warnings: vec!["insufficient_history".to_string()],
-        }),
+/// Observations from a `shadow export` file, JSON array or JSONL.
+fn read_shadow_export(
+    path: &std::path::Path,
+    limit: Option<usize>,
+) -> Result<Vec<Observation>, ShadowExportError> {
+    let content = std::fs::read_to_string(path)?;
+    let mut observations: Vec<Observation> = match serde_json::from_str(&content) {
+        Ok(observations) => observations,
+        Err(_) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?,
+    };
+
+    observations.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
+    if let Some(max) = limit {
+        observations.truncate(max);
     }
+    observations.sort_by_key(|a| a.timestamp);
+
+    Ok(observations)
 }
 
-#[cfg(feature = "ui")]
-fn format_memory_human(bytes: u64) -> String {
-    let mb = bytes as f64 / 1024.0 / 1024.0;
-    if mb >= 1024.0 {
-        format!("{:.1}GB", mb / 1024.0)
-    } else if mb >= 10.0 {
-        format!("{:.0}MB", mb)
-    } else {
-        format!("{:.1}MB", mb)
+fn collect_shadow_files(dir: &PathBuf, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_shadow_files(&path, files)?;
+        } else if path.is_file() {
+            if path.file_name().and_then(|s| s.to_str()) == Some("stats.json") {
+                continue;
+            }
+            if path.file_name().and_then(|s| s.to_str()) == Some("pending.json") {
+                continue;
+            }
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                files.push(path);
+            }
+        }
     }
+    Ok(())
 }
 
-/// Generate a single-line rationale for a candidate process.
-/// Used by --brief mode to provide context without verbosity.
-fn generate_single_line_rationale(candidate: &serde_json::Value) -> String {
-    let classification = candidate
-        .get("classification")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-    let age_human = candidate
-        .get("age_human")
-        .and_then(|v| v.as_str())
-        .unwrap_or("?");
-    let score = candidate.get("score").and_then(|v| v.as_u64()).unwrap_or(0);
-    let memory_mb = candidate
-        .get("memory_mb")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
-    let cpu_pct = candidate
-        .get("cpu_percent")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
+fn run_mcp(global: &GlobalOpts, args: &McpArgs) -> ExitCode {
+    if args.transport == "http" {
+        return run_mcp_http(global, args);
+    }
 
-    // Extract top evidence factor if available
-    let top_factor = candidate
-        .get("evidence")
-        .and_then(|e| e.as_array())
-        .and_then(|arr| arr.first())
-        .and_then(|f| f.get("factor"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("inference");
+    if let Err(e) = run_mcp_stdio(global.timeout) {
+        eprintln!("MCP server error: {}", e);
+        return ExitCode::IoError;
+    }
+    ExitCode::Clean
+}
 
-    // Build concise rationale
-    if cpu_pct < 0.1 && memory_mb > 100 {
-        format!(
-            "{} for {} ({}% conf), idle+{}MB, {}",
-            classification, age_human, score, memory_mb, top_factor
-        )
-    } else if cpu_pct < 0.1 {
-        format!(
-            "{} for {} ({}% conf), idle, {}",
-            classification, age_human, score, top_factor
-        )
-    } else {
-        format!(
-            "{} for {} ({}% conf), {:.1}% CPU, {}",
-            classification, age_human, score, cpu_pct, top_factor
-        )
+/// Serve MCP over stdio, on the tokio runtime when built with `async`.
+#[cfg(feature = "async")]
+fn run_mcp_stdio(global_timeout: Option<u64>) -> std::io::Result<()> {
+    use pt_core::mcp::server::{serve_stdio_async, MAX_CONCURRENT_REQUESTS};
+    use pt_core::runtime::AsyncRuntime;
+
+    match AsyncRuntime::new() {
+        Ok(runtime) => {
+            let token = runtime.timeout_token(global_timeout.map(std::time::Duration::from_secs));
+            let server = std::sync::Arc::new(pt_core::mcp::McpServer::new());
+            runtime.block_on(serve_stdio_async(server, MAX_CONCURRENT_REQUESTS, &token))
+        }
+        Err(e) => {
+            eprintln!("[pt-mcp] async runtime unavailable ({}), using threads", e);
+            pt_core::mcp::McpServer::new().run_stdio()
+        }
     }
 }
 
-/// Generate a human-readable narrative summary of the plan.
-/// Used by --narrative mode for human consumption.
-fn generate_narrative_summary(
-    session_id: &pt_common::SessionId,
-    candidates: &[serde_json::Value],
-    kill_candidates: &[u32],
-    review_candidates: &[u32],
-    total_scanned: usize,
-    expected_memory_freed_gb: f64,
-) -> String {
-    let mut output = String::new();
+/// Serve MCP over stdio, one request at a time.
+#[cfg(not(feature = "async"))]
+fn run_mcp_stdio(_global_timeout: Option<u64>) -> std::io::Result<()> {
+    pt_core::mcp::McpServer::new().run_stdio()
+}
 
-    // Header
-    output.push_str(&format!(
-        "Process Triage Report (Session: {})\n",
-        session_id
-    ));
-    output.push_str(&"=".repeat(50));
-    output.push('\n');
-    output.push('\n');
+#[cfg(feature = "mcp-http")]
+fn run_mcp_http(global: &GlobalOpts, args: &McpArgs) -> ExitCode {
+    use pt_core::mcp::http::{generate_token, serve, HttpTransportConfig};
 
-    // Executive summary
-    if candidates.is_empty() {
-        output.push_str("No problematic processes detected. Your system looks healthy.\n");
-        return output;
+    let bind: std::net::SocketAddr = match args.bind.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("mcp: invalid --bind address '{}': {}", args.bind, e);
+            return ExitCode::ArgsError;
+        }
+    };
+    let token = match &args.token {
+        Some(token) if !token.is_empty() => token.clone(),
+        _ => {
+            let token = generate_token();
+            eprintln!("[pt-mcp] Generated bearer token: {}", token);
+            token
+        }
+    };
+
+    let config = HttpTransportConfig::new(bind, token);
+    #[cfg(feature = "async")]
+    let served = match pt_core::runtime::AsyncRuntime::new() {
+        Ok(runtime) => {
+            let token = runtime.timeout_token(global.timeout.map(std::time::Duration::from_secs));
+            runtime.block_on(pt_core::mcp::http::serve_async(
+                &config,
+                pt_core::mcp::server::MAX_CONCURRENT_REQUESTS,
+                &token,
+            ))
+        }
+        Err(e) => {
+            eprintln!("[pt-mcp] async runtime unavailable ({}), using threads", e);
+            serve(&config)
+        }
+    };
+    #[cfg(not(feature = "async"))]
+    let served = {
+        let _ = global;
+        serve(&config)
+    };
+    if let Err(e) = served {
+        eprintln!("MCP server error: {}", e);
+        return ExitCode::IoError;
     }
+    ExitCode::Clean
+}
 
-    output.push_str(&format!(
-        "Scanned {} processes and identified {} candidates for review.\n\n",
-        total_scanned,
-        candidates.len()
-    ));
+#[cfg(not(feature = "mcp-http"))]
+fn run_mcp_http(_global: &GlobalOpts, _args: &McpArgs) -> ExitCode {
+    eprintln!(
+        "mcp: the http transport requires the `mcp-http` feature (build with --features mcp-http)"
+    );
+    ExitCode::ArgsError
+}
 
-    // Recommendations summary
-    if !kill_candidates.is_empty() {
-        output.push_str(&format!(
-            "KILL RECOMMENDED: {} process{} ({:.2} GB memory recoverable)\n",
-            kill_candidates.len(),
-            if kill_candidates.len() == 1 { "" } else { "es" },
-            expected_memory_freed_gb
-        ));
-    }
-    if !review_candidates.is_empty() {
-        output.push_str(&format!(
-            "REVIEW SUGGESTED: {} process{}\n",
-            review_candidates.len(),
-            if review_candidates.len() == 1 {
-                ""
-            } else {
-                "es"
-            }
-        ));
-    }
-    output.push('\n');
+#[cfg(feature = "serve")]
+fn run_serve(args: &ServeArgs) -> ExitCode {
+    use pt_core::mcp::http::generate_token;
+    use pt_core::serve::{serve, ServeConfig};
 
-    // Detailed candidate breakdown
-    output.push_str("Candidate Details:\n");
-    output.push_str(&"-".repeat(40));
-    output.push('\n');
+    let listen: std::net::SocketAddr = match args.listen.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("serve: invalid --listen address '{}': {}", args.listen, e);
+            return ExitCode::ArgsError;
+        }
+    };
+    let Some(profile) = pt_redact::ExportProfile::parse_str(&args.profile) else {
+        eprintln!(
+            "serve: invalid profile '{}'. Valid options: minimal, safe, forensic",
+            args.profile
+        );
+        return ExitCode::ArgsError;
+    };
+    let token = match &args.token {
+        Some(token) if !token.is_empty() => token.clone(),
+        _ => {
+            let token = generate_token();
+            eprintln!("[pt-serve] Generated bearer token: {}", token);
+            token
+        }
+    };
 
-    for (i, candidate) in candidates.iter().enumerate() {
-        let pid = candidate.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
-        let cmd = candidate
-            .get("command_short")
-            .and_then(|v| v.as_str())
-            .unwrap_or("?");
-        let age_human = candidate
-            .get("age_human")
-            .and_then(|v| v.as_str())
-            .unwrap_or("?");
-        let classification = candidate
-            .get("classification")
-            .and_then(|v| v.as_str())
-            .unwrap_or("?");
-        let score = candidate.get("score").and_then(|v| v.as_u64()).unwrap_or(0);
-        let recommendation = candidate
-            .get("recommendation")
-            .and_then(|v| v.as_str())
-            .unwrap_or("?");
-        let memory_mb = candidate
-            .get("memory_mb")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-
-        output.push_str(&format!(
-            "\n{}. PID {} - {} ({})\n",
-            i + 1,
-            pid,
-            cmd,
-            classification
-        ));
-        output.push_str(&format!(
-            "   Age: {}, Memory: {} MB\n",
-            age_human, memory_mb
-        ));
-        output.push_str(&format!(
-            "   Confidence: {}%, Recommendation: {}\n",
-            score, recommendation
-        ));
-
-        // Top evidence factors
-        if let Some(evidence) = candidate.get("evidence").and_then(|e| e.as_array()) {
-            let top_factors: Vec<&str> = evidence
-                .iter()
-                .take(3)
-                .filter_map(|f| f.get("factor").and_then(|v| v.as_str()))
-                .collect();
-            if !top_factors.is_empty() {
-                output.push_str(&format!("   Key factors: {}\n", top_factors.join(", ")));
-            }
-        }
+    let config = ServeConfig {
+        listen,
+        token,
+        profile,
+        shadow_dir: shadow_base_dir(),
+    };
+    if let Err(e) = serve(&config) {
+        eprintln!("serve: {}", e);
+        return ExitCode::IoError;
     }
-
-    output.push('\n');
-    output.push_str(&"-".repeat(40));
-    output.push('\n');
-    output.push_str("Use 'pt agent apply --session <id>' to execute recommendations.\n");
-
-    output
+    ExitCode::Clean
 }
 
-fn run_agent_snapshot(global: &GlobalOpts, args: &AgentSnapshotArgs) -> ExitCode {
-    let session_id = SessionId::new();
+#[cfg(not(feature = "serve"))]
+fn run_serve(_args: &ServeArgs) -> ExitCode {
+    eprintln!("serve: the REST API requires the `serve` feature (build with --features serve)");
+    ExitCode::ArgsError
+}
 
-    let store = match SessionStore::from_env() {
-        Ok(store) => store,
+/// Print the next chunk of previously truncated output.
+///
+/// The chunk comes from the persisted cursor, not a re-run of the original
+/// command, so the same token always returns the same chunk.
+fn run_continue(global: &GlobalOpts, raw_token: &str) -> ExitCode {
+    let page = ContinuationToken::parse(raw_token).and_then(|token| {
+        let cursor = PaginationStore::from_env()?.load(&token)?;
+        let max_tokens = global.max_tokens.unwrap_or(cursor.max_tokens);
+        Ok((
+            cursor.page(token.offset, max_tokens, &TokenEstimator::new()),
+            cursor.compact,
+        ))
+    });
+    let (page, compact) = match page {
+        Ok(page) => page,
         Err(e) => {
-            eprintln!("agent snapshot: session store error: {}", e);
-            return ExitCode::InternalError;
+            eprintln!("continue: {}", e);
+            return match e {
+                PaginationError::InvalidToken { .. } | PaginationError::Mismatch { .. } => {
+                    ExitCode::ArgsError
+                }
+                PaginationError::NotFound { .. } | PaginationError::Session(_) => {
+                    ExitCode::SessionError
+                }
+                PaginationError::Io { .. } => ExitCode::IoError,
+                PaginationError::Json { .. } => ExitCode::InternalError,
+            };
         }
     };
 
-    let manifest = SessionManifest::new(
-        &session_id,
-        None,
-        SessionMode::RobotPlan,
-        args.label.clone(),
-    );
-    let handle = match store.create(&manifest) {
-        Ok(handle) => handle,
-        Err(e) => {
-            eprintln!("agent snapshot: failed to create session: {}", e);
-            return ExitCode::InternalError;
+    let output = page.to_output(&TokenEstimator::new());
+    match global.format {
+        OutputFormat::Toon => println!("{}", encode_toon_value(&output)),
+        _ if compact || global.compact => {
+            println!("{}", serde_json::to_string(&output).unwrap_or_default())
         }
+        _ => println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        ),
+    }
+    ExitCode::Clean
+}
+
+fn run_schema(global: &GlobalOpts, args: &SchemaArgs) -> ExitCode {
+    use pt_core::schema::{
+        available_schemas, format_schema, generate_all_schemas, generate_schema, SchemaFormat,
     };
 
-    let ctx = SessionContext::new(
-        &session_id,
-        pt_core::logging::get_host_id(),
-        pt_core::logging::generate_run_id(),
-        args.label.clone(),
-    );
-    if let Err(e) = handle.write_context(&ctx) {
-        eprintln!("agent snapshot: failed to write context.json: {}", e);
-        return ExitCode::InternalError;
+    let format = if args.compact {
+        SchemaFormat::JsonCompact
+    } else {
+        SchemaFormat::Json
+    };
+
+    if let Some(ref baseline_path) = args.compat {
+        return run_schema_compat(global, baseline_path);
     }
 
-    if let Some(path) = &global.capabilities {
-        match std::fs::read_to_string(path) {
-            Ok(content) => {
-                if let Err(e) = handle.write_capabilities_json(&content) {
-                    eprintln!("agent snapshot: failed to write capabilities.json: {}", e);
-                    return ExitCode::InternalError;
+    // List available types
+    if args.list {
+        match global.format {
+            OutputFormat::Json | OutputFormat::Toon => {
+                let types: Vec<_> = available_schemas()
+                    .into_iter()
+                    .map(|(name, desc)| serde_json::json!({"name": name, "description": desc}))
+                    .collect();
+                let types_value = serde_json::Value::Array(types);
+                println!("{}", format_structured_output(global, types_value));
+            }
+            OutputFormat::Jsonl => {
+                for (name, desc) in available_schemas() {
+                    let entry = serde_json::json!({"name": name, "description": desc});
+                    println!("{}", serde_json::to_string(&entry).unwrap());
                 }
             }
-            Err(e) => {
-                eprintln!(
-                    "agent snapshot: failed to read capabilities manifest {}: {}",
-                    path, e
-                );
-                return ExitCode::InternalError;
+            _ => {
+                println!("Available schema types:\n");
+                for (name, desc) in available_schemas() {
+                    println!("  {:<25} {}", name, desc);
+                }
+                println!("\nUse 'pt schema <TYPE>' to generate a schema.");
             }
         }
+        return ExitCode::Clean;
     }
 
-    // Collect system state and capabilities
-    let system_state = collect_system_state();
-    let caps = get_capabilities();
-    let host_id = pt_core::logging::get_host_id();
-    let timestamp = chrono::Utc::now();
+    // Generate all schemas
+    if let Some(ref out_dir) = args.out_dir {
+        return run_schema_write(global, out_dir);
+    }
+    if args.all {
+        let schemas = generate_all_schemas();
+        match global.format {
+            OutputFormat::Json | OutputFormat::Toon => {
+                let schemas_value = serde_json::to_value(&schemas).unwrap_or_default();
+                println!("{}", format_structured_output(global, schemas_value));
+            }
+            OutputFormat::Jsonl => {
+                for (name, schema) in schemas {
+                    let entry = serde_json::json!({"type": name, "schema": schema});
+                    println!("{}", serde_json::to_string(&entry).unwrap());
+                }
+            }
+            _ => {
+                // Human-readable: output each schema separately
+                for (name, schema) in schemas {
+                    println!("# {}\n", name);
+                    println!("{}\n", format_schema(&schema, format));
+                }
+            }
+        }
+        return ExitCode::Clean;
+    }
 
-    // Perform a quick scan once. We use it both for user-facing snapshot output (optional)
-    // and for persisting diff artifacts (inventory + inference) so `pt diff` can work.
-    let scan_options = QuickScanOptions {
-        pids: vec![],
-        include_kernel_threads: false,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
-        progress: None,
-    };
+    // Generate schema for a specific type
+    if let Some(ref type_name) = args.type_name {
+        match generate_schema(type_name) {
+            Some(schema) => {
+                match global.format {
+                    OutputFormat::Jsonl => {
+                        println!("{}", serde_json::to_string(&schema).unwrap());
+                    }
+                    _ => {
+                        println!("{}", format_schema(&schema, format));
+                    }
+                }
+                ExitCode::Clean
+            }
+            None => {
+                eprintln!("Unknown type: {}", type_name);
+                eprintln!("\nUse 'pt schema --list' to see available types.");
+                ExitCode::PartialFail
+            }
+        }
+    } else {
+        eprintln!("Usage: pt schema <TYPE> | --list | --all");
+        eprintln!("\nUse 'pt schema --list' to see available types.");
+        ExitCode::PartialFail
+    }
+}
 
-    let scan_result = match quick_scan(&scan_options) {
-        Ok(result) => Some(result),
+/// Write versioned schema files for every type (`schema --all --out-dir`).
+fn run_schema_write(global: &GlobalOpts, out_dir: &Path) -> ExitCode {
+    let bundle = match pt_core::schema::write_versioned_schemas(out_dir, SCHEMA_VERSION) {
+        Ok(bundle) => bundle,
         Err(e) => {
-            eprintln!("agent snapshot: warning: process scan failed: {}", e);
-            None
+            eprintln!("schema: failed to write {}: {}", out_dir.display(), e);
+            return ExitCode::IoError;
         }
     };
 
-    // Persist compact artifacts when we have a scan result.
-    if let Some(ref scan_result) = scan_result {
-        // Load config for protected filter + action policy.
-        let config_options = ConfigOptions {
-            config_dir: global.config.as_ref().map(PathBuf::from),
-            ..Default::default()
-        };
-        if let Ok(config) = load_config(&config_options) {
-            let priors = config.priors.clone();
-            let policy = config.policy.clone();
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "command": "schema",
+                "schema_version": bundle.version,
+                "dir": bundle.dir.display().to_string(),
+                "files": bundle.files.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+            });
+            match global.format {
+                OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
+                _ => println!("{}", format_structured_output(global, output)),
+            }
+        }
+        _ => {
+            println!(
+                "Wrote {} files to {}",
+                bundle.files.len(),
+                bundle.dir.display()
+            );
+        }
+    }
+    ExitCode::Clean
+}
 
-            if let Ok(protected_filter) = ProtectedFilter::from_guardrails(&policy.guardrails) {
-                let filter_result = protected_filter.filter_scan_result(scan_result);
+/// Check the current schemas against a baseline for breaking changes.
+fn run_schema_compat(global: &GlobalOpts, baseline_path: &str) -> ExitCode {
+    use pt_core::schema::{compare_schema_sets, generate_all_schemas, ChangeSeverity};
 
-                let mut persisted_inventory_records: Vec<PersistedProcess> = Vec::new();
-                let mut persisted_inference_records: Vec<PersistedInference> = Vec::new();
-                persisted_inventory_records.reserve(filter_result.passed.len());
-                persisted_inference_records.reserve(filter_result.passed.len());
+    let baseline: std::collections::BTreeMap<String, serde_json::Value> =
+        match std::fs::read_to_string(baseline_path)
+            .map_err(|e| e.to_string())
+            .and_then(|raw| serde_json::from_str(&raw).map_err(|e| e.to_string()))
+        {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("schema: failed to read baseline {}: {}", baseline_path, e);
+                return ExitCode::ArgsError;
+            }
+        };
 
-                let feasibility = ActionFeasibility::allow_all();
-                for proc in &filter_result.passed {
-                    let evidence = Evidence {
-                        cpu: Some(CpuEvidence::Fraction {
-                            occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
-                        }),
-                        runtime_seconds: Some(proc.elapsed.as_secs_f64()),
-                        orphan: Some(proc.is_orphan()),
-                        tty: Some(proc.has_tty()),
-                        net: None,
-                        io_active: None,
-                        state_flag: state_to_flag(proc.state),
-                        command_category: None,
-                    };
-
-                    let posterior_result = match compute_posterior(&priors, &evidence) {
-                        Ok(r) => r,
-                        Err(_) => continue,
-                    };
-
-                    let decision_outcome =
-                        match decide_action(&posterior_result.posterior, &policy, &feasibility) {
-                            Ok(d) => d,
-                            Err(_) => continue,
-                        };
-
-                    let ledger = EvidenceLedger::from_posterior_result(
-                        &posterior_result,
-                        Some(proc.pid.0),
-                        None,
-                    );
-
-                    let posterior = &posterior_result.posterior;
-                    let max_posterior = posterior
-                        .useful
-                        .max(posterior.useful_bad)
-                        .max(posterior.abandoned)
-                        .max(posterior.zombie);
-                    let score = (max_posterior * 100.0).round() as u32;
-
-                    let recommended_action = match decision_outcome.optimal_action {
-                        Action::Keep => "keep",
-                        Action::Renice => "renice",
-                        Action::Pause => "pause",
-                        Action::Resume => "resume",
-                        Action::Freeze => "freeze",
-                        Action::Unfreeze => "unfreeze",
-                        Action::Throttle => "throttle",
-                        Action::Quarantine => "quarantine",
-                        Action::Unquarantine => "unquarantine",
-                        Action::Restart => "restart",
-                        Action::Kill => "kill",
-                    };
-
-                    persisted_inventory_records.push(PersistedProcess {
-                        pid: proc.pid.0,
-                        ppid: proc.ppid.0,
-                        uid: proc.uid,
-                        start_id: proc.start_id.to_string(),
-                        comm: proc.comm.clone(),
-                        cmd: proc.cmd.clone(),
-                        state: proc.state.to_string(),
-                        start_time_unix: proc.start_time_unix,
-                        elapsed_secs: proc.elapsed.as_secs(),
-                        identity_quality: "QuickScan".to_string(),
-                    });
-
-                    persisted_inference_records.push(PersistedInference {
-                        pid: proc.pid.0,
-                        start_id: proc.start_id.to_string(),
-                        classification: ledger.classification.label().to_string(),
-                        posterior_useful: posterior.useful,
-                        posterior_useful_bad: posterior.useful_bad,
-                        posterior_abandoned: posterior.abandoned,
-                        posterior_zombie: posterior.zombie,
-                        confidence: ledger.confidence.label().to_string(),
-                        recommended_action: recommended_action.to_string(),
-                        score,
-                    });
-                }
-
-                let host_id = pt_core::logging::get_host_id();
-                let inv_artifact = InventoryArtifact {
-                    total_system_processes: filter_result.total_before as u64,
-                    protected_filtered: filter_result.filtered.len() as u64,
-                    record_count: persisted_inventory_records.len(),
-                    records: persisted_inventory_records,
-                };
-                if let Err(e) = persist_inventory(&handle, &session_id.0, &host_id, inv_artifact) {
-                    eprintln!(
-                        "agent snapshot: warning: failed to persist inventory artifact: {}",
-                        e
-                    );
-                }
+    let changes = compare_schema_sets(&baseline, &generate_all_schemas());
+    let breaking = changes
+        .iter()
+        .filter(|c| c.severity == ChangeSeverity::Breaking)
+        .count();
+    let additive = changes.len() - breaking;
 
-                let inf_artifact = InferenceArtifact {
-                    candidate_count: persisted_inference_records.len(),
-                    candidates: persisted_inference_records,
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "baseline": baseline_path,
+                "compatible": breaking == 0,
+                "breaking": breaking,
+                "additive": additive,
+                "changes": changes,
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Jsonl => {
+            for change in &changes {
+                println!("{}", serde_json::to_string(change).unwrap());
+            }
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!(
+                "Schema compatibility vs {}: {} breaking, {} additive",
+                baseline_path, breaking, additive
+            );
+            for change in &changes {
+                let marker = match change.severity {
+                    ChangeSeverity::Breaking => "BREAKING",
+                    ChangeSeverity::Additive => "additive",
                 };
-                if let Err(e) = persist_inference(&handle, &session_id.0, &host_id, inf_artifact) {
-                    eprintln!(
-                        "agent snapshot: warning: failed to persist inference artifact: {}",
-                        e
-                    );
-                }
+                println!(
+                    "  [{}] {}{}: {}",
+                    marker, change.schema, change.path, change.description
+                );
             }
         }
     }
 
-    // Collect process list if --top, --include-env, or --include-network is specified.
-    let process_snapshot = if args.top.is_some() || args.include_env || args.include_network {
-        if let Some(ref scan_result) = scan_result {
-            let mut processes: Vec<_> = scan_result.processes.iter().collect();
-
-            // Sort by resource usage (CPU + normalized memory)
-            processes.sort_by(|a, b| {
-                let a_score = a.cpu_percent + (a.rss_bytes as f64 / 1_073_741_824.0); // GB
-                let b_score = b.cpu_percent + (b.rss_bytes as f64 / 1_073_741_824.0);
-                b_score
-                    .partial_cmp(&a_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-
-            // Limit to top N if specified
-            if let Some(top_n) = args.top {
-                processes.truncate(top_n);
-            }
-
-            // Build process summaries for output
-            let summaries: Vec<serde_json::Value> = processes
-                .iter()
-                .map(|p| {
-                    let mut obj = serde_json::json!({
-                        "pid": p.pid.0,
-                        "ppid": p.ppid.0,
-                        "user": &p.user,
-                        "comm": &p.comm,
-                        "cmd": &p.cmd,
-                        "cpu_percent": p.cpu_percent,
-                        "rss_mb": p.rss_bytes / 1_048_576,
-                        "vsz_mb": p.vsz_bytes / 1_048_576,
-                        "state": format!("{:?}", p.state),
-                        "elapsed_secs": p.elapsed.as_secs(),
-                    });
-
-                    // Add environment info placeholder (redacted keys only)
-                    if args.include_env {
-                        // Read env from /proc/<pid>/environ and extract key names only
-                        let env_path = format!("/proc/{}/environ", p.pid.0);
-                        if let Ok(content) = std::fs::read(&env_path) {
-                            let keys: Vec<String> = content
-                                .split(|&b| b == 0)
-                                .filter_map(|entry| {
-                                    std::str::from_utf8(entry)
-                                        .ok()
-                                        .and_then(|s| s.split('=').next())
-                                        .map(|k| k.to_string())
-                                })
-                                .filter(|k| !k.is_empty())
-                                .collect();
-                            obj.as_object_mut()
-                                .unwrap()
-                                .insert("env_keys".to_string(), serde_json::json!(keys));
-                        }
-                    }
-
-                    // Add network connections placeholder
-                    if args.include_network {
-                        // Count file descriptors that look like sockets
-                        let fd_path = format!("/proc/{}/fd", p.pid.0);
-                        let socket_count = std::fs::read_dir(&fd_path)
-                            .map(|entries| {
-                                entries
-                                    .filter_map(|e| e.ok())
-                                    .filter(|e| {
-                                        e.path()
-                                            .read_link()
-                                            .map(|target| {
-                                                target.to_string_lossy().starts_with("socket:")
-                                            })
-                                            .unwrap_or(false)
-                                    })
-                                    .count()
-                            })
-                            .unwrap_or(0);
-                        obj.as_object_mut()
-                            .unwrap()
-                            .insert("socket_count".to_string(), serde_json::json!(socket_count));
-                    }
+    if breaking > 0 {
+        ExitCode::VersionError
+    } else {
+        ExitCode::Clean
+    }
+}
 
-                    obj
-                })
-                .collect();
+fn print_version(global: &GlobalOpts) {
+    let version_info = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "pt_core_version": env!("CARGO_PKG_VERSION"),
+        "rust_version": env!("CARGO_PKG_RUST_VERSION"),
+    });
 
-            Some(serde_json::json!({
-                "count": summaries.len(),
-                "top_n": args.top,
-                "include_env": args.include_env,
-                "include_network": args.include_network,
-                "processes": summaries,
-            }))
-        } else {
-            None
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, version_info));
         }
-    } else {
-        None
-    };
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("pt-core {}", env!("CARGO_PKG_VERSION"));
+            println!("schema version: {}", SCHEMA_VERSION);
+        }
+    }
+}
 
-    // Build capabilities summary for output
-    let capabilities_summary = serde_json::json!({
-        "tools": {
-            "perf": caps.tools.perf.available,
-            "bpftrace": caps.tools.bpftrace.available,
-            "strace": caps.tools.strace.available,
-            "lsof": caps.tools.lsof.available,
-            "ps": caps.tools.ps.available,
-            "systemctl": caps.tools.systemctl.available,
-        },
-        "permissions": {
-            "can_sudo": caps.permissions.can_sudo,
-            "can_ptrace": caps.permissions.can_read_others_procs,
-            "is_root": caps.permissions.is_root,
-        },
-    });
+fn output_stub(global: &GlobalOpts, command: &str, message: &str) {
+    let session_id = SessionId::new();
+
+    output_stub_with_session(global, &session_id, command, message);
+}
 
+fn output_stub_with_session(
+    global: &GlobalOpts,
+    session_id: &SessionId,
+    command: &str,
+    message: &str,
+) {
     match global.format {
         OutputFormat::Json | OutputFormat::Toon => {
-            let mut output = serde_json::json!({
+            let output = serde_json::json!({
                 "schema_version": SCHEMA_VERSION,
                 "session_id": session_id.0,
-                "host_id": host_id,
-                "timestamp": timestamp.to_rfc3339(),
-                "generated_at": timestamp.to_rfc3339(),
-                "label": args.label,
-                "session_dir": handle.dir.display().to_string(),
-                "context_path": handle.context_path().display().to_string(),
-                "system_state": system_state,
-                "capabilities": capabilities_summary,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": command,
+                "status": "stub",
+                "message": message
             });
-            if let Some(procs) = &process_snapshot {
-                output
-                    .as_object_mut()
-                    .unwrap()
-                    .insert("process_snapshot".to_string(), procs.clone());
-            }
             println!("{}", format_structured_output(global, output));
         }
         OutputFormat::Summary => {
-            let mem = system_state
-                .get("memory")
-                .and_then(|m| m.get("used_gb"))
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            let total = system_state
-                .get("memory")
-                .and_then(|m| m.get("total_gb"))
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            let procs = system_state
-                .get("process_count")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
-            println!(
-                "[{}] agent snapshot: created ({} procs, {:.0}/{:.0}GB mem)",
-                session_id, procs, mem, total
-            );
+            println!("[{}] {}: {}", session_id, command, message);
         }
         OutputFormat::Exitcode => {}
         _ => {
-            println!("# pt-core agent snapshot");
+            println!("# pt-core {}", command);
             println!();
-            println!("Session: {}", session_id);
-            println!("Host: {}", host_id);
-            println!("Dir: {}", handle.dir.display());
-            if let Some(label) = &args.label {
-                println!("Label: {}", label);
-            }
+            println!("{}", message);
             println!();
-            println!("## System State");
-            if let Some(load) = system_state.get("load").and_then(|v| v.as_array()) {
-                let load_strs: Vec<String> = load
-                    .iter()
-                    .filter_map(|v| v.as_f64().map(|f| format!("{:.2}", f)))
-                    .collect();
-                println!("  Load: {}", load_strs.join(", "));
-            }
-            if let Some(cores) = system_state.get("cores").and_then(|v| v.as_u64()) {
-                println!("  Cores: {}", cores);
-            }
-            if let Some(mem) = system_state.get("memory") {
-                let total = mem.get("total_gb").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let used = mem.get("used_gb").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let avail = mem
-                    .get("available_gb")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(0.0);
-                println!(
-                    "  Memory: {:.1}GB total, {:.1}GB used, {:.1}GB available",
-                    total, used, avail
-                );
-            }
-            if let Some(procs) = system_state.get("process_count").and_then(|v| v.as_u64()) {
-                println!("  Processes: {}", procs);
-            }
-            if let Some(psi) = system_state.get("psi") {
-                let cpu = psi.get("cpu").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let mem = psi.get("memory").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let io = psi.get("io").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                println!("  PSI: cpu={:.2}%, mem={:.2}%, io={:.2}%", cpu, mem, io);
-            }
+            println!("Session: {}", session_id);
+        }
+    }
+}
 
-            // Display process snapshot if collected
-            if let Some(snapshot) = &process_snapshot {
-                println!();
-                println!("## Process Snapshot");
-                let count = snapshot.get("count").and_then(|v| v.as_u64()).unwrap_or(0);
-                let top_n = snapshot.get("top_n").and_then(|v| v.as_u64());
-                if let Some(n) = top_n {
-                    println!("  Top {} processes by resource usage:", n);
+fn run_agent_capabilities(global: &GlobalOpts, args: &AgentCapabilitiesArgs) -> ExitCode {
+    // If --check-action is specified, just check that specific action
+    if let Some(action) = &args.check_action {
+        let caps = get_capabilities();
+        let (supported, reason) = match action.to_lowercase().as_str() {
+            "sigterm" | "terminate" | "term" => (true, "SIGTERM always available"),
+            "sigkill" | "kill" => (true, "SIGKILL always available"),
+            "sigstop" | "stop" => (true, "SIGSTOP always available"),
+            "sigcont" | "cont" | "continue" => (true, "SIGCONT always available"),
+            "strace" => (
+                caps.tools.strace.available && caps.tools.strace.works,
+                if caps.tools.strace.available {
+                    "strace available"
                 } else {
-                    println!("  {} processes:", count);
+                    "strace not installed"
+                },
+            ),
+            "perf" => (
+                caps.tools.perf.available && caps.tools.perf.works,
+                if caps.tools.perf.available {
+                    "perf available"
+                } else {
+                    "perf not installed"
+                },
+            ),
+            "lsof" => (
+                caps.tools.lsof.available && caps.tools.lsof.works,
+                if caps.tools.lsof.available {
+                    "lsof available"
+                } else {
+                    "lsof not installed"
+                },
+            ),
+            "nice" | "renice" => (
+                caps.tools.renice.available,
+                if caps.tools.renice.available {
+                    "renice available"
+                } else {
+                    "renice not installed"
+                },
+            ),
+            "ionice" => (
+                caps.tools.ionice.available,
+                if caps.tools.ionice.available {
+                    "ionice available"
+                } else {
+                    "ionice not installed"
+                },
+            ),
+            "cgroup" | "cgroups" => (
+                caps.data_sources.cgroup_v2,
+                if caps.data_sources.cgroup_v2 {
+                    "cgroups v2 available"
+                } else {
+                    "cgroups v2 not available"
+                },
+            ),
+            "docker" => (
+                caps.tools.docker.available,
+                if caps.tools.docker.available {
+                    "docker available"
+                } else {
+                    "docker not installed"
+                },
+            ),
+            "podman" => (
+                caps.tools.podman.available,
+                if caps.tools.podman.available {
+                    "podman available"
+                } else {
+                    "podman not installed"
+                },
+            ),
+            "sudo" => (
+                caps.permissions.can_sudo,
+                if caps.permissions.can_sudo {
+                    "sudo available"
+                } else {
+                    "cannot sudo"
+                },
+            ),
+            "root" => (
+                caps.permissions.is_root,
+                if caps.permissions.is_root {
+                    "running as root"
+                } else {
+                    "not running as root"
+                },
+            ),
+            _ => (false, "unknown action type"),
+        };
+
+        match global.format {
+            OutputFormat::Json | OutputFormat::Toon => {
+                let output = serde_json::json!({
+                    "action": action,
+                    "supported": supported,
+                    "reason": reason,
+                });
+                println!("{}", format_structured_output(global, output));
+            }
+            OutputFormat::Summary => {
+                if supported {
+                    println!("[capabilities] {}: supported", action);
+                } else {
+                    println!("[capabilities] {}: not supported ({})", action, reason);
                 }
-                if let Some(procs) = snapshot.get("processes").and_then(|v| v.as_array()) {
-                    for p in procs.iter().take(10) {
-                        let pid = p.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
-                        let comm = p.get("comm").and_then(|v| v.as_str()).unwrap_or("?");
-                        let cpu = p.get("cpu_percent").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                        let rss = p.get("rss_mb").and_then(|v| v.as_u64()).unwrap_or(0);
-                        println!("    {:>7} {:<20} {:>5.1}% CPU {:>6}MB", pid, comm, cpu, rss);
-                    }
-                    if procs.len() > 10 {
-                        println!(
-                            "    ... and {} more (use --format json for full list)",
-                            procs.len() - 10
-                        );
-                    }
+            }
+            OutputFormat::Exitcode => {}
+            _ => {
+                if supported {
+                    println!("Action '{}' is supported: {}", action, reason);
+                } else {
+                    println!("Action '{}' is NOT supported: {}", action, reason);
                 }
             }
         }
+
+        return if supported {
+            ExitCode::Clean
+        } else {
+            ExitCode::CapabilityError
+        };
     }
 
+    // Otherwise, output full capabilities
+    output_capabilities(global);
     ExitCode::Clean
 }
 
-fn match_level_label(level: MatchLevel) -> &'static str {
-    match level {
-        MatchLevel::None => "none",
-        MatchLevel::GenericCategory => "generic_category",
-        MatchLevel::CommandOnly => "command_only",
-        MatchLevel::CommandPlusArgs => "command_plus_args",
-        MatchLevel::ExactCommand => "exact_command",
-        MatchLevel::MultiPattern => "multi_pattern",
-    }
-}
-
-fn fast_path_skip_reason_label(reason: FastPathSkipReason) -> &'static str {
-    match reason {
-        FastPathSkipReason::Disabled => "disabled",
-        FastPathSkipReason::NoMatch => "no_match",
-        FastPathSkipReason::ScoreBelowThreshold => "score_below_threshold",
-        FastPathSkipReason::NoPriors => "no_priors",
-    }
-}
+fn output_capabilities(global: &GlobalOpts) {
+    let session_id = SessionId::new();
 
-fn run_agent_plan(global: &GlobalOpts, args: &AgentPlanArgs) -> ExitCode {
-    let _lock = match acquire_global_lock(global, "agent plan") {
-        Ok(lock) => lock,
-        Err(code) => return code,
-    };
-    let store = match SessionStore::from_env() {
-        Ok(store) => store,
-        Err(e) => {
-            eprintln!("agent plan: session store error: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
+    // Detect actual system capabilities (get_capabilities handles cache internally)
+    let caps = get_capabilities();
 
-    let (session_id, handle, created) = match args.session.as_ref() {
-        Some(raw) => {
-            let sid = match SessionId::parse(raw) {
-                Some(sid) => sid,
-                None => {
-                    eprintln!("agent plan: invalid --session {}", raw);
-                    return ExitCode::ArgsError;
-                }
-            };
-            let handle = match store.open(&sid) {
-                Ok(handle) => handle,
-                Err(e) => {
-                    eprintln!("agent plan: {}", e);
-                    return ExitCode::ArgsError;
-                }
-            };
-            (sid, handle, false)
+    // Build tools map for output
+    let mut tools_output = serde_json::Map::new();
+    let tool_list: [(&str, &ToolCapability); 14] = [
+        ("ps", &caps.tools.ps),
+        ("lsof", &caps.tools.lsof),
+        ("ss", &caps.tools.ss),
+        ("netstat", &caps.tools.netstat),
+        ("perf", &caps.tools.perf),
+        ("strace", &caps.tools.strace),
+        ("dtrace", &caps.tools.dtrace),
+        ("bpftrace", &caps.tools.bpftrace),
+        ("systemctl", &caps.tools.systemctl),
+        ("docker", &caps.tools.docker),
+        ("podman", &caps.tools.podman),
+        ("nice", &caps.tools.nice),
+        ("renice", &caps.tools.renice),
+        ("ionice", &caps.tools.ionice),
+    ];
+    for (name, tool) in tool_list {
+        let mut tool_info = serde_json::Map::new();
+        tool_info.insert(
+            "available".to_string(),
+            serde_json::Value::Bool(tool.available),
+        );
+        if let Some(ref v) = tool.version {
+            tool_info.insert("version".to_string(), serde_json::Value::String(v.clone()));
         }
-        None => {
-            let sid = SessionId::new();
-            let manifest =
-                SessionManifest::new(&sid, None, SessionMode::RobotPlan, args.label.clone());
-            let handle = match store.create(&manifest) {
-                Ok(handle) => handle,
-                Err(e) => {
-                    eprintln!("agent plan: failed to create session: {}", e);
-                    return ExitCode::InternalError;
-                }
-            };
-            let ctx = SessionContext::new(
-                &sid,
-                pt_core::logging::get_host_id(),
-                pt_core::logging::generate_run_id(),
-                None,
+        if let Some(ref p) = tool.path {
+            tool_info.insert("path".to_string(), serde_json::Value::String(p.clone()));
+        }
+        tool_info.insert("works".to_string(), serde_json::Value::Bool(tool.works));
+        if !tool.available {
+            tool_info.insert(
+                "reason".to_string(),
+                serde_json::Value::String(
+                    tool.error.clone().unwrap_or_else(|| "not installed".into()),
+                ),
             );
-            if let Err(e) = handle.write_context(&ctx) {
-                eprintln!("agent plan: failed to write context.json: {}", e);
-                return ExitCode::InternalError;
-            }
-            (sid, handle, true)
         }
-    };
+        tools_output.insert(name.to_string(), serde_json::Value::Object(tool_info));
+    }
 
-    // Load configuration and priors
-    let config_options = ConfigOptions {
-        config_dir: global.config.as_ref().map(PathBuf::from),
-        ..Default::default()
-    };
-    let config = match load_config(&config_options) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("agent plan: failed to load config: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
-    let priors = config.priors.clone();
-    let policy = config.policy.clone();
-    let fast_path_config = FastPathConfig {
-        enabled: policy.signature_fast_path.enabled,
-        min_confidence_threshold: policy.signature_fast_path.min_confidence_threshold,
-        require_explicit_priors: policy.signature_fast_path.require_explicit_priors,
-    };
+    let capabilities_json = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "session_id": session_id.0,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "os": {
+            "family": caps.platform.os,
+            "arch": caps.platform.arch,
+            "kernel": caps.platform.kernel_release,
+            "in_container": caps.platform.in_container,
+            "container_runtime": caps.platform.container_runtime,
+        },
+        "tools": tools_output,
+        "permissions": {
+            "effective_uid": caps.permissions.effective_uid,
+            "is_root": caps.permissions.is_root,
+            "can_sudo": caps.permissions.can_sudo,
+            "can_read_others_procs": caps.permissions.can_read_others_procs,
+            "can_signal_others": caps.permissions.can_signal_others,
+            "linux_capabilities": caps.permissions.linux_capabilities,
+            "proc_visibility": visibility_json(&caps.permissions.proc_visibility),
+        },
+        "data_sources": {
+            "procfs": caps.data_sources.procfs,
+            "sysfs": caps.data_sources.sysfs,
+            "perf_events": caps.data_sources.perf_events,
+            "ebpf": caps.data_sources.ebpf,
+            "schedstat": caps.data_sources.schedstat,
+            "cgroup_v1": caps.data_sources.cgroup_v1,
+            "cgroup_v2": caps.data_sources.cgroup_v2,
+        },
+        "supervisors": {
+            "systemd": caps.supervisors.systemd,
+            "launchd": caps.supervisors.launchd,
+            "pm2": caps.supervisors.pm2,
+            "supervisord": caps.supervisors.supervisord,
+            "docker_daemon": caps.supervisors.docker_daemon,
+            "podman": caps.supervisors.podman_available,
+            "kubernetes": caps.supervisors.kubernetes,
+            "systemd_oomd": caps.supervisors.systemd_oomd,
+            "earlyoom": caps.supervisors.earlyoom,
+        },
+        "actions": {
+            "kill": caps.actions.kill,
+            "pause": caps.actions.pause,
+            "renice": caps.actions.renice,
+            "ionice": caps.actions.ionice,
+            "cgroup_freeze": caps.actions.cgroup_freeze,
+            "cgroup_throttle": caps.actions.cgroup_throttle,
+            "cpuset_quarantine": caps.actions.cpuset_quarantine,
+        },
+        "features": {
+            "deep_scan": caps.can_deep_scan(),
+            "maximal_scan": caps.can_maximal_scan(),
+        },
+        "detected_at": caps.detected_at,
+    });
 
-    let mut signature_db = SignatureDatabase::with_defaults();
-    if let Some(user_schema) = pt_core::signature_cli::load_user_signatures() {
-        for signature in user_schema.signatures {
-            if let Err(err) = signature_db.add(signature) {
-                eprintln!(
-                    "agent plan: warning: skipping invalid user signature during load: {}",
-                    err
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, capabilities_json));
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# Capabilities");
+            println!();
+            println!("## Platform");
+            println!(
+                "OS: {} ({}) kernel: {}",
+                caps.platform.os,
+                caps.platform.arch,
+                caps.platform.kernel_release.as_deref().unwrap_or("unknown")
+            );
+            if caps.platform.in_container {
+                println!(
+                    "Container: {} ({})",
+                    caps.platform.in_container,
+                    caps.platform
+                        .container_runtime
+                        .as_deref()
+                        .unwrap_or("unknown")
+                );
+            }
+            println!();
+            println!("## Permissions");
+            println!(
+                "UID: {} (root: {})",
+                caps.permissions.effective_uid, caps.permissions.is_root
+            );
+            println!("Sudo: {}", caps.permissions.can_sudo);
+            println!("Read others: {}", caps.permissions.can_read_others_procs);
+            println!("Signal others: {}", caps.permissions.can_signal_others);
+            println!(
+                "Process visibility: {}",
+                caps.permissions.proc_visibility.describe()
+            );
+            if caps.supervisors.memory_pressure_killer_active() {
+                println!();
+                println!("## Memory Pressure Killers");
+                println!(
+                    "  systemd-oomd: {}, earlyoom: {}",
+                    caps.supervisors.systemd_oomd, caps.supervisors.earlyoom
                 );
             }
+            println!();
+            println!(
+                "## Tools ({}/{} available)",
+                caps.tools.available_count(),
+                caps.tools.total_count()
+            );
+            for (name, tool) in [
+                ("ps", &caps.tools.ps),
+                ("lsof", &caps.tools.lsof),
+                ("perf", &caps.tools.perf),
+                ("strace", &caps.tools.strace),
+                ("bpftrace", &caps.tools.bpftrace),
+            ] {
+                let status = if tool.works {
+                    format!("ok ({})", tool.version.as_deref().unwrap_or("?"))
+                } else if tool.available {
+                    "broken".to_string()
+                } else {
+                    "not found".to_string()
+                };
+                println!("  {}: {}", name, status);
+            }
+            println!();
+            println!(
+                "## Actions ({}/{} available)",
+                caps.actions.available_count(),
+                caps.actions.total_count()
+            );
+            println!(
+                "  kill: {}, pause: {}, renice: {}",
+                caps.actions.kill, caps.actions.pause, caps.actions.renice
+            );
+            println!(
+                "  cgroup_freeze: {}, cgroup_throttle: {}",
+                caps.actions.cgroup_freeze, caps.actions.cgroup_throttle
+            );
+            println!();
+            println!("## Features");
+            println!("  deep_scan: {}", caps.can_deep_scan());
+            println!("  maximal_scan: {}", caps.can_maximal_scan());
         }
     }
+}
 
-    let rate_limit_path = resolve_data_dir_for_lock().map(|dir| dir.join("rate_limit.json"));
-    let enforcer = match pt_core::decision::PolicyEnforcer::new(&policy, rate_limit_path.as_deref())
-    {
-        Ok(enforcer) => enforcer,
-        Err(e) => {
-            eprintln!("agent plan: failed to init policy enforcer: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
+// ============================================================================
+// System State Collection
+// ============================================================================
 
-    if args.prediction_fields.is_some() && !args.include_predictions {
-        eprintln!("agent plan: --prediction-fields requires --include-predictions");
-        return ExitCode::ArgsError;
-    }
+/// Collect system state for snapshot output.
+fn collect_system_state() -> serde_json::Value {
+    let load = collect_load_averages();
+    let cores = collect_cpu_count();
+    let memory = collect_memory_info();
+    let process_count = collect_process_count();
+    let psi = collect_psi();
 
-    let prediction_field_selector = if args.include_predictions {
-        match args.prediction_fields.as_deref() {
-            Some(spec) => match parse_prediction_fields(spec) {
-                Ok(selector) => Some(selector),
-                Err(err) => {
-                    eprintln!("agent plan: {}", err);
-                    return ExitCode::ArgsError;
-                }
-            },
-            None => None,
-        }
-    } else {
-        None
-    };
-
-    // Progress emitter for streaming updates + session log.
-    // Emits SESSION_STARTED immediately and guarantees SESSION_ENDED on all exits.
-    let session_lifecycle = SessionLifecycle::start(global, &handle, &session_id);
-    let emitter = session_lifecycle.emitter();
-
-    // Perform quick scan to enumerate processes (with timing)
-    let scan_start = std::time::Instant::now();
-    let scan_options = QuickScanOptions {
-        pids: vec![],
-        include_kernel_threads: args.include_kernel_threads,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
-        progress: emitter.clone(),
-    };
-
-    let scan_result = match quick_scan(&scan_options) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("agent plan: scan failed: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
-    let scan_duration_ms = scan_start.elapsed().as_millis() as u64;
+    serde_json::json!({
+        "load": load,
+        "cores": cores,
+        "memory": memory,
+        "process_count": process_count,
+        "psi": psi,
+    })
+}
 
-    // Quick scan emits its own progress events via the shared emitter.
+/// Read /proc/loadavg and return [1min, 5min, 15min].
+fn collect_load_averages() -> Vec<f64> {
+    std::fs::read_to_string("/proc/loadavg")
+        .ok()
+        .and_then(|content| {
+            let parts: Vec<&str> = content.split_whitespace().collect();
+            if parts.len() >= 3 {
+                let load1 = parts[0].parse::<f64>().ok()?;
+                let load5 = parts[1].parse::<f64>().ok()?;
+                let load15 = parts[2].parse::<f64>().ok()?;
+                Some(vec![load1, load5, load15])
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default()
+}
 
-    // Create protected filter from policy guardrails
-    let protected_filter = match ProtectedFilter::from_guardrails(&policy.guardrails) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("agent plan: failed to create protected filter: {}", e);
-            return ExitCode::InternalError;
+/// Get CPU count from /proc/cpuinfo or nproc.
+fn collect_cpu_count() -> u32 {
+    // Try reading from /proc/cpuinfo
+    if let Ok(content) = std::fs::read_to_string("/proc/cpuinfo") {
+        let count = content
+            .lines()
+            .filter(|line| line.starts_with("processor"))
+            .count();
+        if count > 0 {
+            return count as u32;
         }
-    };
-
-    // Apply filter BEFORE inference loop to remove protected processes
-    let filter_result = protected_filter.filter_scan_result(&scan_result);
-    let protected_filtered_count = filter_result.filtered.len();
-    let total_scanned = filter_result.total_before;
+    }
+    // Fallback to nproc command
+    std::process::Command::new("nproc")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok()?.trim().parse().ok())
+        .unwrap_or(1)
+}
 
-    tracing::info!(
-        total_scanned = total_scanned,
-        filtered_count = protected_filtered_count,
-        passed_count = filter_result.passed.len(),
-        "Protected filter applied"
-    );
+/// Read /proc/meminfo and return memory stats in GB.
+fn collect_memory_info() -> serde_json::Value {
+    let (total_kb, available_kb) = std::fs::read_to_string("/proc/meminfo")
+        .ok()
+        .map(|content| {
+            let mut total: u64 = 0;
+            let mut available: u64 = 0;
+            for line in content.lines() {
+                if let Some(rest) = line.strip_prefix("MemTotal:") {
+                    total = rest
+                        .split_whitespace()
+                        .next()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(0);
+                } else if let Some(rest) = line.strip_prefix("MemAvailable:") {
+                    available = rest
+                        .split_whitespace()
+                        .next()
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(0);
+                }
+            }
+            (total, available)
+        })
+        .unwrap_or((0, 0));
 
-    let system_state = collect_system_state();
-    let load_adjustment = if policy.load_aware.enabled {
-        let signals = LoadSignals::from_system_state(&system_state, filter_result.passed.len());
-        compute_load_adjustment(&policy.load_aware, &signals)
-    } else {
-        None
-    };
+    let total_gb = (total_kb as f64) / 1024.0 / 1024.0;
+    let available_gb = (available_kb as f64) / 1024.0 / 1024.0;
+    let used_gb = total_gb - available_gb;
 
-    let decision_policy = if let Some(adjustment) = &load_adjustment {
-        let mut adjusted = policy.clone();
-        adjusted.loss_matrix = apply_load_to_loss_matrix(&policy.loss_matrix, adjustment);
-        adjusted
-    } else {
-        policy.clone()
-    };
+    serde_json::json!({
+        "total_gb": (total_gb * 10.0).round() / 10.0,
+        "used_gb": (used_gb * 10.0).round() / 10.0,
+        "available_gb": (available_gb * 10.0).round() / 10.0,
+    })
+}
 
-    // Process each candidate: compute posterior, make decision, build candidate output.
-    //
-    // Collect all candidates above threshold with their max_posterior for sorting, plus
-    // a compact persisted snapshot (inventory + inference) so `diff` can compare sessions.
-    let mut all_candidates: Vec<(f64, serde_json::Value, PersistedProcess, PersistedInference)> =
-        Vec::new();
-    let mut policy_blocked_count = 0usize;
-    let mut signature_match_count = 0usize;
-    let mut signature_fast_path_used_count = 0usize;
+/// Count process directories in /proc.
+fn collect_process_count() -> u32 {
+    std::fs::read_dir("/proc")
+        .ok()
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    e.file_name()
+                        .to_str()
+                        .map(|s| s.chars().all(|c| c.is_ascii_digit()))
+                        .unwrap_or(false)
+                })
+                .count() as u32
+        })
+        .unwrap_or(0)
+}
 
-    let base_feasibility = ActionFeasibility::allow_all();
-    let mut shadow_recorder = if global.shadow {
-        match ShadowRecorder::new() {
-            Ok(recorder) => Some(recorder),
-            Err(err) => {
-                eprintln!("shadow mode: failed to initialize storage: {:?}", err);
-                None
+/// Read PSI (Pressure Stall Information) from /proc/pressure/.
+fn collect_psi() -> serde_json::Value {
+    fn read_psi_file(resource: &str) -> Option<f64> {
+        let path = format!("/proc/pressure/{}", resource);
+        std::fs::read_to_string(&path).ok().and_then(|content| {
+            // Parse "some avg10=X.XX avg60=Y.YY avg300=Z.ZZ total=N"
+            // We want avg10 for recent pressure
+            for line in content.lines() {
+                if line.starts_with("some") {
+                    for part in line.split_whitespace() {
+                        if let Some(val) = part.strip_prefix("avg10=") {
+                            return val.parse().ok();
+                        }
+                    }
+                }
             }
-        }
-    } else {
-        None
-    };
-    let mut shadow_recorded = 0u64;
+            None
+        })
+    }
 
-    // Apply min-age filter before sampling (if configured)
-    let eligible_processes: Vec<_> = if let Some(min_age) = args.min_age {
-        filter_result
-            .passed
-            .iter()
-            .filter(|proc| proc.elapsed.as_secs() >= min_age)
-            .collect()
-    } else {
-        filter_result.passed.iter().collect()
-    };
+    serde_json::json!({
+        "cpu": read_psi_file("cpu").unwrap_or(0.0),
+        "memory": read_psi_file("memory").unwrap_or(0.0),
+        "io": read_psi_file("io").unwrap_or(0.0),
+    })
+}
 
-    // Apply sampling if requested (for testing)
-    let processes_to_infer: Vec<_> = if let Some(sample_size) = args.sample_size {
-        use rand::seq::SliceRandom;
-        let mut rng = rand::rng();
-        let mut sampled: Vec<_> = eligible_processes;
-        sampled.shuffle(&mut rng);
-        sampled.truncate(sample_size);
-        sampled
-    } else {
-        eligible_processes
-    };
+/// Get the system hostname.
+fn collect_hostname() -> String {
+    // Try /etc/hostname first
+    if let Ok(hostname) = std::fs::read_to_string("/etc/hostname") {
+        let hostname = hostname.trim();
+        if !hostname.is_empty() {
+            return hostname.to_string();
+        }
+    }
+    // Fallback to HOSTNAME env var
+    if let Ok(hostname) = std::env::var("HOSTNAME") {
+        return hostname;
+    }
+    // Last resort: use gethostname
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
 
-    let _current_cpu_pct: f64 = processes_to_infer.iter().map(|p| p.cpu_percent).sum();
+/// Collect host information in the structured format for JSON output.
+fn collect_host_info() -> serde_json::Value {
+    let hostname = collect_hostname();
+    let cores = collect_cpu_count();
+    let memory = collect_memory_info();
+    let load = collect_load_averages();
 
-    let candidates_evaluated = processes_to_infer.len();
-    let total_processes = candidates_evaluated as u64;
-    let mut processed = 0u64;
+    let total_gb = memory
+        .get("total_gb")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+    let used_gb = memory
+        .get("used_gb")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
 
-    if let Some(ref e) = emitter {
-        e.emit(
-            ProgressEvent::new(
-                pt_core::events::event_names::INFERENCE_STARTED,
-                Phase::Infer,
-            )
-            .with_progress(0, Some(total_processes)),
-        );
-        e.emit(
-            ProgressEvent::new(
-                pt_core::events::event_names::DECISION_STARTED,
-                Phase::Decide,
-            )
-            .with_progress(0, Some(total_processes)),
-        );
+    serde_json::json!({
+        "hostname": hostname,
+        "cores": cores,
+        "memory_total_gb": total_gb,
+        "memory_used_gb": used_gb,
+        "load_avg": load,
+    })
+}
+
+fn parse_prediction_fields(spec: &str) -> Result<PredictionFieldSelector, String> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err("prediction fields cannot be empty".to_string());
     }
 
-    // Use filtered (and optionally sampled) processes for inference
-    for proc in processes_to_infer {
-        // Skip PID 0/1 (extra safety - should already be filtered)
-        if proc.pid.0 == 0 || proc.pid.0 == 1 {
+    let mut include = Vec::new();
+    for raw in spec.split(',') {
+        let field = raw.trim().to_lowercase();
+        if field.is_empty() {
             continue;
         }
-        processed = processed.saturating_add(1);
-
-        // Build evidence from process record
-        let evidence = Evidence {
-            cpu: Some(CpuEvidence::Fraction {
-                occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
-            }),
-            runtime_seconds: Some(proc.elapsed.as_secs_f64()),
-            orphan: Some(proc.is_orphan()),
-            tty: Some(proc.has_tty()),
-            net: None,
-            io_active: None,
-            state_flag: state_to_flag(proc.state),
-            command_category: None,
+        let parsed = match field.as_str() {
+            "memory" => PredictionField::Memory,
+            "cpu" => PredictionField::Cpu,
+            "eta_abandoned" => PredictionField::EtaAbandoned,
+            "eta_resource_limit" => PredictionField::EtaResourceLimit,
+            "trajectory" => PredictionField::Trajectory,
+            "diagnostics" => PredictionField::Diagnostics,
+            _ => return Err(format!("unknown prediction field: {}", field)),
         };
-
-        let mut match_ctx = ProcessMatchContext::with_comm(&proc.comm);
-        if !proc.cmd.is_empty() {
-            match_ctx = match_ctx.cmdline(&proc.cmd);
-        }
-        let signature_match = signature_db.best_match(&match_ctx);
-        if signature_match.is_some() {
-            signature_match_count = signature_match_count.saturating_add(1);
+        if !include.contains(&parsed) {
+            include.push(parsed);
         }
+    }
 
-        let mut fast_path_used = false;
-        let mut fast_path_skip_reason: Option<&'static str> = None;
-        let prior_source_label: String;
-        let prior_context = PriorContext {
-            global_priors: &priors,
-            signature_match: signature_match.as_ref(),
-            category_defaults: None,
-            user_overrides: None,
-        };
+    if include.is_empty() {
+        return Err("prediction fields cannot be empty".to_string());
+    }
 
-        let (posterior_result, mut ledger) = if let Some(sig_match) = signature_match.as_ref() {
-            match try_signature_fast_path(&fast_path_config, Some(sig_match), proc.pid.0) {
-                Ok(Some(fast_path)) => {
-                    fast_path_used = true;
-                    signature_fast_path_used_count =
-                        signature_fast_path_used_count.saturating_add(1);
-                    prior_source_label = "signature_fast_path".to_string();
-                    (fast_path.posterior, fast_path.ledger)
-                }
-                Ok(None) => match compute_posterior_with_overrides(&prior_context, &evidence) {
-                    Ok((result, source_info)) => {
-                        prior_source_label = source_info.source.to_string();
-                        let ledger =
-                            EvidenceLedger::from_posterior_result(&result, Some(proc.pid.0), None);
-                        (result, ledger)
-                    }
-                    Err(_) => continue,
-                },
-                Err(reason) => {
-                    fast_path_skip_reason = Some(fast_path_skip_reason_label(reason));
-                    match compute_posterior_with_overrides(&prior_context, &evidence) {
-                        Ok((result, source_info)) => {
-                            prior_source_label = source_info.source.to_string();
-                            let ledger = EvidenceLedger::from_posterior_result(
-                                &result,
-                                Some(proc.pid.0),
-                                None,
-                            );
-                            (result, ledger)
-                        }
-                        Err(_) => continue,
-                    }
-                }
-            }
-        } else {
-            match compute_posterior_with_overrides(&prior_context, &evidence) {
-                Ok((result, source_info)) => {
-                    prior_source_label = source_info.source.to_string();
-                    let ledger =
-                        EvidenceLedger::from_posterior_result(&result, Some(proc.pid.0), None);
-                    (result, ledger)
-                }
-                Err(_) => continue,
-            }
-        };
+    Ok(PredictionFieldSelector { include })
+}
 
-        let signature_name = signature_match.as_ref().map(|m| m.signature.name.clone());
-        let signature_level = signature_match
-            .as_ref()
-            .map(|m| match_level_label(m.level).to_string());
-        let signature_score = signature_match.as_ref().map(|m| m.score);
-        let signature_category = signature_match
-            .as_ref()
-            .map(|m| format!("{:?}", m.signature.category));
+/// Fit the time-to-abandonment model to the shadow history.
+///
+/// None until shadow mode has recorded enough abandonment events.
+fn load_survival_eta_model() -> Option<SurvivalEtaModel> {
+    let observations = collect_shadow_observations(&shadow_base_dir(), None).ok()?;
+    SurvivalEtaModel::from_observations(&observations, &SurvivalEtaConfig::default())
+}
 
-        if let Some(sig_match) = signature_match.as_ref() {
-            if !fast_path_used {
-                ledger.top_evidence.insert(
-                    0,
-                    format!(
-                        "Signature match: {} (score={:.2}, level={})",
-                        sig_match.signature.name,
-                        sig_match.score,
-                        match_level_label(sig_match.level)
-                    ),
-                );
-                ledger.why_summary = format!(
-                    "Matched signature '{}' (score {:.2}, level {}, prior source {}). {}",
-                    sig_match.signature.name,
-                    sig_match.score,
-                    match_level_label(sig_match.level),
-                    prior_source_label,
-                    ledger.why_summary
-                );
-            }
-        }
+fn build_stub_predictions(proc: &ProcessRecord, eta: Option<&SurvivalEta>) -> Predictions {
+    let window_secs = proc.elapsed.as_secs_f64().max(0.0);
+    Predictions {
+        memory: Some(MemoryPrediction {
+            rss_slope_bytes_per_sec: 0.0,
+            trend: Trend::Stable,
+            confidence: 0.0,
+            window_secs,
+            pss_bytes: None,
+            uss_bytes: None,
+        }),
+        cpu: Some(CpuPrediction {
+            usage_slope_pct_per_sec: 0.0,
+            trend: Trend::Stable,
+            confidence: 0.0,
+            window_secs,
+        }),
+        eta_abandoned: eta.map(|eta| EtaPrediction {
+            eta_secs: eta.eta_secs,
+            confidence: eta.confidence,
+            lower_bound_secs: Some(eta.lower_secs),
+            upper_bound_secs: Some(eta.upper_secs),
+        }),
+        eta_resource_limit: None,
+        trajectory: Some(TrajectoryAssessment {
+            label: TrajectoryLabel::Unknown,
+            confidence: 0.0,
+            summary: "insufficient history for trajectory prediction".to_string(),
+        }),
+        diagnostics: Some(PredictionDiagnostics {
+            n_observations: 1,
+            calibrated: false,
+            model: if eta.is_some() {
+                "snapshot+weibull_survival".to_string()
+            } else {
+                "snapshot".to_string()
+            },
+            warnings: vec!["insufficient_history".to_string()],
+        }),
+    }
+}
 
-        // Apply state-based feasibility constraints so decisioning does not
-        // recommend fundamentally invalid actions (e.g., kill for zombie/D-state).
-        let state_feasibility = ActionFeasibility::from_process_state(
-            proc.state.is_zombie(),
-            proc.state.is_disksleep(),
-            None,
-        );
-        let feasibility = base_feasibility.merge(&state_feasibility);
+/// Generate a single-line rationale for a candidate process.
+/// Used by --brief mode to provide context without verbosity.
+fn generate_single_line_rationale(candidate: &serde_json::Value) -> String {
+    let classification = candidate
+        .get("classification")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    let age_human = candidate
+        .get("age_human")
+        .and_then(|v| v.as_str())
+        .unwrap_or("?");
+    let score = candidate.get("score").and_then(|v| v.as_u64()).unwrap_or(0);
+    let memory_mb = candidate
+        .get("memory_mb")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    let cpu_pct = candidate
+        .get("cpu_percent")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
 
-        // Compute decision (optimal action based on expected loss)
-        let mut decision_outcome =
-            match decide_action(&posterior_result.posterior, &decision_policy, &feasibility) {
-                Ok(d) => d,
-                Err(_) => continue, // Skip processes that fail decision
-            };
-        decision_outcome.rationale.has_known_signature = Some(signature_match.is_some());
+    // Extract top evidence factor if available
+    let top_factor = candidate
+        .get("evidence")
+        .and_then(|e| e.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|f| f.get("factor"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("inference");
 
-        // Determine max posterior class for filtering
-        let posterior = &posterior_result.posterior;
-        let max_posterior = posterior
-            .useful
-            .max(posterior.useful_bad)
-            .max(posterior.abandoned)
-            .max(posterior.zombie);
+    // Build concise rationale
+    if cpu_pct < 0.1 && memory_mb > 100 {
+        format!(
+            "{} for {} ({}% conf), idle+{}MB, {}",
+            classification, age_human, score, memory_mb, top_factor
+        )
+    } else if cpu_pct < 0.1 {
+        format!(
+            "{} for {} ({}% conf), idle, {}",
+            classification, age_human, score, top_factor
+        )
+    } else {
+        format!(
+            "{} for {} ({}% conf), {:.1}% CPU, {}",
+            classification, age_human, score, cpu_pct, top_factor
+        )
+    }
+}
 
-        // Determine recommended action string (used for shadow recording and plan output)
-        let mut recommended_action = match decision_outcome.optimal_action {
-            Action::Keep => "keep",
-            Action::Renice => "renice",
-            Action::Pause => "pause",
-            Action::Resume => "resume",
-            Action::Freeze => "freeze",
-            Action::Unfreeze => "unfreeze",
-            Action::Throttle => "throttle",
-            Action::Quarantine => "quarantine",
-            Action::Unquarantine => "unquarantine",
-            Action::Restart => "restart",
-            Action::Kill => "kill",
-        };
+/// Generate a human-readable narrative summary of the plan.
+/// Used by --narrative mode for human consumption.
+fn generate_narrative_summary(
+    session_id: &pt_common::SessionId,
+    candidates: &[serde_json::Value],
+    kill_candidates: &[u32],
+    review_candidates: &[u32],
+    total_scanned: usize,
+    expected_memory_freed_gb: f64,
+) -> String {
+    let mut output = String::new();
 
-        if let Some(ref mut recorder) = shadow_recorder {
-            match recorder.record_candidate(proc, posterior, &ledger, &decision_outcome) {
-                Ok(()) => shadow_recorded = shadow_recorded.saturating_add(1),
-                Err(err) => {
-                    eprintln!(
-                        "shadow mode: failed to record observation for pid {}: {:?}",
-                        proc.pid.0, err
-                    );
-                }
-            }
-        }
+    // Header
+    output.push_str(&format!(
+        "Process Triage Report (Session: {})\n",
+        session_id
+    ));
+    output.push_str(&"=".repeat(50));
+    output.push('\n');
+    output.push('\n');
 
-        if let Some(ref e) = emitter {
-            if processed.is_multiple_of(50) || processed == total_processes {
-                e.emit(
-                    ProgressEvent::new(
-                        pt_core::events::event_names::INFERENCE_PROGRESS,
-                        Phase::Infer,
-                    )
-                    .with_progress(processed, Some(total_processes)),
-                );
+    // Executive summary
+    if candidates.is_empty() {
+        output.push_str("No problematic processes detected. Your system looks healthy.\n");
+        return output;
+    }
+
+    output.push_str(&format!(
+        "Scanned {} processes and identified {} candidates for review.\n\n",
+        total_scanned,
+        candidates.len()
+    ));
+
+    // Recommendations summary
+    if !kill_candidates.is_empty() {
+        output.push_str(&format!(
+            "KILL RECOMMENDED: {} process{} ({:.2} GB memory recoverable)\n",
+            kill_candidates.len(),
+            if kill_candidates.len() == 1 { "" } else { "es" },
+            expected_memory_freed_gb
+        ));
+    }
+    if !review_candidates.is_empty() {
+        output.push_str(&format!(
+            "REVIEW SUGGESTED: {} process{}\n",
+            review_candidates.len(),
+            if review_candidates.len() == 1 {
+                ""
+            } else {
+                "es"
             }
-        }
+        ));
+    }
+    output.push('\n');
 
-        // Apply threshold filter
-        if max_posterior < args.min_posterior {
-            continue;
-        }
+    // Detailed candidate breakdown
+    output.push_str("Candidate Details:\n");
+    output.push_str(&"-".repeat(40));
+    output.push('\n');
 
-        // Apply --only filter
-        let include = match args.only.as_str() {
-            "kill" => decision_outcome.optimal_action == Action::Kill,
-            "review" => decision_outcome.optimal_action != Action::Keep,
-            _ => true, // "all"
-        };
-        if !include {
-            continue;
-        }
+    for (i, candidate) in candidates.iter().enumerate() {
+        let pid = candidate.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+        let cmd = candidate
+            .get("command_short")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let age_human = candidate
+            .get("age_human")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let classification = candidate
+            .get("classification")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let score = candidate.get("score").and_then(|v| v.as_u64()).unwrap_or(0);
+        let recommendation = candidate
+            .get("recommendation")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let memory_mb = candidate
+            .get("memory_mb")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
 
-        let process_candidate = pt_core::decision::ProcessCandidate {
-            pid: proc.pid.0 as i32,
-            ppid: proc.ppid.0 as i32,
-            cmdline: proc.cmd.clone(),
-            user: Some(proc.user.clone()),
-            group: None,
-            category: decision_outcome.rationale.category.clone(),
-            age_seconds: proc.elapsed.as_secs(),
-            posterior: Some(max_posterior),
-            memory_mb: Some(proc.rss_bytes as f64 / (1024.0 * 1024.0)),
-            has_known_signature: decision_outcome
-                .rationale
-                .has_known_signature
-                .unwrap_or(false),
-            open_write_fds: None,
-            has_locked_files: None,
-            has_active_tty: Some(proc.has_tty()),
-            seconds_since_io: None,
-            cwd_deleted: None,
-            process_state: Some(proc.state),
-            wchan: None,
-            critical_files: Vec::new(),
-        };
-        let policy_result = enforcer.check_action(
-            &process_candidate,
-            decision_outcome.optimal_action,
-            global.robot,
-        );
-        let policy_blocked = !policy_result.allowed;
-        if policy_blocked {
-            policy_blocked_count += 1;
-            recommended_action = "review";
+        output.push_str(&format!(
+            "\n{}. PID {} - {} ({})\n",
+            i + 1,
+            pid,
+            cmd,
+            classification
+        ));
+        output.push_str(&format!(
+            "   Age: {}, Memory: {} MB\n",
+            age_human, memory_mb
+        ));
+        output.push_str(&format!(
+            "   Confidence: {}%, Recommendation: {}\n",
+            score, recommendation
+        ));
+
+        // Top evidence factors
+        if let Some(evidence) = candidate.get("evidence").and_then(|e| e.as_array()) {
+            let top_factors: Vec<&str> = evidence
+                .iter()
+                .take(3)
+                .filter_map(|f| f.get("factor").and_then(|v| v.as_str()))
+                .collect();
+            if !top_factors.is_empty() {
+                output.push_str(&format!("   Key factors: {}\n", top_factors.join(", ")));
+            }
         }
-        let policy_value = serde_json::to_value(&policy_result)
-            .unwrap_or_else(|_| serde_json::json!({ "allowed": policy_result.allowed }));
-        let action_rationale = if policy_blocked {
-            policy_result
-                .violation
-                .as_ref()
-                .map(|v| format!("Policy blocked: {}", v.message))
-                .unwrap_or_else(|| "Policy blocked".to_string())
-        } else {
-            format!(
-                "Action {:?} selected{}",
-                decision_outcome.rationale.chosen_action,
-                if decision_outcome.rationale.tie_break {
-                    " (tie-break)"
-                } else {
-                    ""
-                }
-            )
-        };
+    }
 
-        // Build evidence contributions from Bayes factors
-        let evidence_contributions: Vec<serde_json::Value> = ledger
-            .bayes_factors
-            .iter()
-            .map(|bf| {
-                serde_json::json!({
-                    "factor": bf.feature,
-                    "contribution": (bf.delta_bits * 10.0).round() as i32, // Scale to integer score
-                    "detail": format!("{:.1} bits {}", bf.delta_bits.abs(), bf.direction),
-                    "strength": bf.strength,
-                })
-            })
-            .collect();
+    output.push('\n');
+    output.push_str(&"-".repeat(40));
+    output.push('\n');
+    output.push_str("Use 'pt agent apply --session <id>' to execute recommendations.\n");
 
-        // Calculate age in seconds and human-readable form
-        let age_seconds = proc.elapsed.as_secs();
-        let age_human = format_duration_human(age_seconds);
+    output
+}
 
-        // Calculate a composite score (0-100) based on max posterior
-        let score = (max_posterior * 100.0).round() as u32;
+fn run_agent_snapshot(global: &GlobalOpts, args: &AgentSnapshotArgs) -> ExitCode {
+    let session_id = SessionId::new();
 
-        let predictions = if args.include_predictions {
-            let mut predictions = build_stub_predictions(proc);
-            if let Some(selector) = &prediction_field_selector {
-                predictions = apply_field_selection(&predictions, selector);
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent snapshot: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let redactor = match output_redactor(global) {
+        Ok(redactor) => redactor,
+        Err(e) => {
+            eprintln!("agent snapshot: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    let manifest = SessionManifest::new(
+        &session_id,
+        None,
+        SessionMode::RobotPlan,
+        args.label.clone(),
+    );
+    let handle = match store.create(&manifest) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("agent snapshot: failed to create session: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    let ctx = SessionContext::new(
+        &session_id,
+        pt_core::logging::get_host_id(),
+        pt_core::logging::generate_run_id(),
+        args.label.clone(),
+    );
+    if let Err(e) = handle.write_context(&ctx) {
+        eprintln!("agent snapshot: failed to write context.json: {}", e);
+        return ExitCode::InternalError;
+    }
+
+    if let Some(path) = &global.capabilities {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                if let Err(e) = handle.write_capabilities_json(&content) {
+                    eprintln!("agent snapshot: failed to write capabilities.json: {}", e);
+                    return ExitCode::InternalError;
+                }
             }
-            if predictions.is_empty() {
-                None
-            } else {
-                Some(predictions)
+            Err(e) => {
+                eprintln!(
+                    "agent snapshot: failed to read capabilities manifest {}: {}",
+                    path, e
+                );
+                return ExitCode::InternalError;
             }
-        } else {
-            None
-        };
+        }
+    }
 
-        // Build candidate JSON (action tracking moved to after sorting)
-        let mut candidate = serde_json::json!({
-            "pid": proc.pid.0,
-            "ppid": proc.ppid.0,
-            "state": proc.state.to_string(),
-            "start_id": format!("{}:{}", proc.pid.0, proc.start_time_unix),
-            "uid": proc.uid,
-            "user": &proc.user,
-            "command": &proc.cmd,
-            "command_short": &proc.comm,
-            "type": ledger.classification.label(), // Process type classification
-            "age_seconds": age_seconds,
-            "age_human": age_human,
-            "memory_mb": proc.rss_bytes / (1024 * 1024),
-            "cpu_percent": proc.cpu_percent,
-            "score": score,
-            "classification": ledger.classification.label(),
-            "posterior": {
-                "useful": posterior.useful,
-                "useful_bad": posterior.useful_bad,
-                "abandoned": posterior.abandoned,
-                "zombie": posterior.zombie,
-            },
-            "signature": {
-                "matched": signature_match.is_some(),
-                "name": signature_name,
-                "category": signature_category,
-                "score": signature_score,
-                "match_level": signature_level,
-            },
-            "inference": {
-                "mode": if fast_path_used { "signature_fast_path" } else { "bayesian" },
-                "prior_source": prior_source_label,
-                "fast_path": {
-                    "enabled": fast_path_config.enabled,
-                    "used": fast_path_used,
-                    "skip_reason": fast_path_skip_reason,
-                    "min_confidence_threshold": fast_path_config.min_confidence_threshold,
-                    "require_explicit_priors": fast_path_config.require_explicit_priors,
-                },
-            },
-            "confidence": ledger.confidence.label(),
-            "evidence": evidence_contributions,
-            "blast_radius": {
-                "memory_mb": proc.rss_bytes / (1024 * 1024),
-                "cpu_pct": proc.cpu_percent,
-                "child_count": 0, // Would need child enumeration
-                "risk_level": if proc.rss_bytes > 1024 * 1024 * 1024 { "medium" } else { "low" },
-            },
-            "reversibility": match decision_outcome.optimal_action {
-                Action::Kill | Action::Restart => "irreversible",
-                Action::Pause | Action::Freeze | Action::Throttle | Action::Quarantine => "reversible",
-                Action::Resume | Action::Unfreeze | Action::Unquarantine => "reversal",
-                Action::Keep | Action::Renice => "no_action",
-            },
-            "supervisor": supervisor_info_for_plan(proc.pid.0),
-            "uncertainty": {
-                "entropy": ledger.bayes_factors.len() as f64 * 0.1, // Simplified
-                "confidence_interval": [(max_posterior - 0.1).max(0.0), (max_posterior + 0.1).min(1.0)],
-            },
-            "recommendation": recommended_action.to_uppercase(),
-            "recommended_action": recommended_action,
-            "action_rationale": action_rationale,
-            "expected_loss": decision_outcome.expected_loss.iter()
-                .map(|el| serde_json::json!({
-                    "action": format!("{:?}", el.action),
-                    "loss": el.loss,
-                }))
-                .collect::<Vec<_>>(),
-            "policy_blocked": policy_blocked,
-            "policy": policy_value,
-        });
+    // Collect system state and capabilities
+    let system_state = collect_system_state();
+    let caps = get_capabilities();
+    let host_id = pt_core::logging::get_host_id();
+    let timestamp = chrono::Utc::now();
 
-        if let Some(predictions) = predictions {
-            if let Some(obj) = candidate.as_object_mut() {
-                obj.insert(
-                    "predictions".to_string(),
-                    serde_json::to_value(predictions).unwrap_or_else(|_| serde_json::json!({})),
-                );
-            }
+    // Perform a quick scan once. We use it both for user-facing snapshot output (optional)
+    // and for persisting diff artifacts (inventory + inference) so `pt diff` can work.
+    let scan_options = QuickScanOptions {
+        pids: vec![],
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+
+    let scan_result = match quick_scan(&scan_options) {
+        Ok(result) => Some(result),
+        Err(e) => {
+            eprintln!("agent snapshot: warning: process scan failed: {}", e);
+            None
         }
+    };
 
-        let persisted_proc = PersistedProcess {
-            pid: proc.pid.0,
-            ppid: proc.ppid.0,
-            uid: proc.uid,
-            start_id: proc.start_id.to_string(),
-            comm: proc.comm.clone(),
-            cmd: proc.cmd.clone(),
-            state: proc.state.to_string(),
-            start_time_unix: proc.start_time_unix,
-            elapsed_secs: proc.elapsed.as_secs(),
-            // Quick scan provides a solid start_id but lacks full TOCTOU coverage.
-            identity_quality: "QuickScan".to_string(),
+    // Persist compact artifacts when we have a scan result.
+    if let Some(ref scan_result) = scan_result {
+        // Load config for protected filter + action policy.
+        let config_options = ConfigOptions {
+            config_dir: global.config.as_ref().map(PathBuf::from),
+            ..Default::default()
         };
+        if let Ok(config) = load_config(&config_options) {
+            let priors = config.priors.clone();
+            let policy = config.policy.clone();
 
-        let persisted_inf = PersistedInference {
-            pid: proc.pid.0,
-            start_id: proc.start_id.to_string(),
-            classification: ledger.classification.label().to_string(),
-            posterior_useful: posterior.useful,
-            posterior_useful_bad: posterior.useful_bad,
-            posterior_abandoned: posterior.abandoned,
-            posterior_zombie: posterior.zombie,
-            confidence: ledger.confidence.label().to_string(),
-            recommended_action: recommended_action.to_string(),
-            score,
-        };
+            if let Ok(protected_filter) = ProtectedFilter::from_guardrails(&policy.guardrails) {
+                let filter_result = protected_filter.filter_scan_result(scan_result);
 
-        // Store candidate with max_posterior for sorting (no early break!)
-        all_candidates.push((max_posterior, candidate, persisted_proc, persisted_inf));
-    }
+                let mut persisted_inventory_records: Vec<PersistedProcess> = Vec::new();
+                let mut persisted_inference_records: Vec<PersistedInference> = Vec::new();
+                persisted_inventory_records.reserve(filter_result.passed.len());
+                persisted_inference_records.reserve(filter_result.passed.len());
 
-    if let Some(ref e) = emitter {
-        e.emit(
-            ProgressEvent::new(
-                pt_core::events::event_names::INFERENCE_COMPLETE,
-                Phase::Infer,
-            )
-            .with_progress(processed, Some(total_processes)),
-        );
-        e.emit(
-            ProgressEvent::new(
-                pt_core::events::event_names::DECISION_COMPLETE,
-                Phase::Decide,
-            )
-            .with_progress(processed, Some(total_processes)),
-        );
-    }
+                let feasibility = ActionFeasibility::allow_all();
+                for proc in &filter_result.passed {
+                    let evidence = Evidence {
+                        cpu: Some(CpuEvidence::Fraction {
+                            occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
+                        }),
+                        runtime_seconds: Some(proc.elapsed.as_secs_f64()),
+                        orphan: Some(proc.is_orphan()),
+                        tty: Some(proc.has_tty()),
+                        net: None,
+                        io_active: None,
+                        state_flag: state_to_flag(proc.state),
+                        command_category: None,
+                        gpu: None,
+                        runtime_surprise: None,
+                        net_recent_activity: None,
+                        behavior_change: None,
+                        swap_pressure: None,
+                        io_rate: None,
+                        fd_leak: None,
+                        session_attachment: None,
+                    };
 
-    if let Some(ref mut recorder) = shadow_recorder {
-        if let Err(err) = recorder.flush() {
-            eprintln!("shadow mode: failed to flush storage: {:?}", err);
-        }
-    }
+                    let posterior_result = match compute_posterior(&priors, &evidence) {
+                        Ok(r) => r,
+                        Err(_) => continue,
+                    };
 
-    // Sort candidates by max_posterior descending (highest confidence first)
-    all_candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+                    let decision_outcome =
+                        match decide_action(&posterior_result.posterior, &policy, &feasibility) {
+                            Ok(d) => d,
+                            Err(_) => continue,
+                        };
 
-    // Capture count before truncation for summary stats
-    let above_threshold_count = all_candidates.len();
+                    let ledger = EvidenceLedger::from_posterior_result(
+                        &posterior_result,
+                        Some(proc.pid.0),
+                        None,
+                    );
 
-    // Take top N candidates (sorted by max posterior, not scan order!)
-    let mut candidates: Vec<serde_json::Value> = Vec::new();
-    let mut persisted_inventory_records: Vec<PersistedProcess> = Vec::new();
-    let mut persisted_inference_records: Vec<PersistedInference> = Vec::new();
-    for (_, candidate_json, proc_rec, inf_rec) in all_candidates
-        .into_iter()
-        .take(args.max_candidates as usize)
-    {
-        candidates.push(candidate_json);
-        persisted_inventory_records.push(proc_rec);
-        persisted_inference_records.push(inf_rec);
-    }
+                    let posterior = &posterior_result.posterior;
+                    let max_posterior = posterior
+                        .useful
+                        .max(posterior.useful_bad)
+                        .max(posterior.abandoned)
+                        .max(posterior.zombie);
+                    let score = (max_posterior * 100.0).round() as u32;
 
-    let mut goal_summary: Option<serde_json::Value> = None;
-    let mut goal_selected: Option<HashSet<u32>> = None;
-    if let Some(goal_str) = args.goal.as_deref() {
-        match parse_goal(goal_str) {
-            Ok(goal) => {
-                let total_cpu_pct_for_goal: f64 = candidates
-                    .iter()
-                    .map(|candidate| {
-                        candidate
-                            .get("cpu_percent")
-                            .and_then(|value| value.as_f64())
-                            .unwrap_or(0.0)
-                    })
-                    .sum();
-                match build_goal_plan_from_candidates(
-                    goal_str,
-                    &goal,
-                    total_cpu_pct_for_goal,
-                    &candidates,
-                ) {
-                    Ok(goal_output) => {
-                        let goal_json = goal_summary_json(goal_str, &goal, &goal_output);
-                        let selected = goal_output.selected_pids.clone();
-                        let selected_set: HashSet<u32> = selected.iter().copied().collect();
-                        let mut selected_rank: HashMap<u32, usize> = HashMap::new();
-                        for (idx, pid) in selected.iter().enumerate() {
-                            selected_rank.insert(*pid, idx);
-                        }
+                    let recommended_action = match decision_outcome.optimal_action {
+                        Action::Keep => "keep",
+                        Action::Renice => "renice",
+                        Action::Pause => "pause",
+                        Action::Resume => "resume",
+                        Action::Freeze => "freeze",
+                        Action::Unfreeze => "unfreeze",
+                        Action::Throttle => "throttle",
+                        Action::Quarantine => "quarantine",
+                        Action::Unquarantine => "unquarantine",
+                        Action::Restart => "restart",
+                        Action::Kill => "kill",
+                    };
 
-                        for candidate in &mut candidates {
-                            if let Some(obj) = candidate.as_object_mut() {
-                                let pid =
-                                    obj.get("pid").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
-                                let selected_for_goal = selected_set.contains(&pid);
-                                obj.insert(
-                                    "goal_selected".to_string(),
-                                    serde_json::json!(selected_for_goal),
-                                );
-                                if selected_for_goal {
-                                    obj.insert(
-                                        "goal_rank".to_string(),
-                                        serde_json::json!(selected_rank
-                                            .get(&pid)
-                                            .copied()
-                                            .unwrap_or(usize::MAX)),
-                                    );
-                                }
-                            }
-                        }
+                    persisted_inventory_records.push(PersistedProcess {
+                        pid: proc.pid.0,
+                        ppid: proc.ppid.0,
+                        uid: proc.uid,
+                        start_id: proc.start_id.to_string(),
+                        comm: proc.comm.clone(),
+                        cmd: redactor.cmdline(&proc.cmd),
+                        state: proc.state.to_string(),
+                        start_time_unix: proc.start_time_unix,
+                        elapsed_secs: proc.elapsed.as_secs(),
+                        identity_quality: "QuickScan".to_string(),
+                    });
 
-                        candidates.sort_by_key(|candidate| {
-                            let pid = candidate
-                                .get("pid")
-                                .and_then(|value| value.as_u64())
-                                .unwrap_or(0) as u32;
-                            selected_rank.get(&pid).copied().unwrap_or(usize::MAX)
-                        });
+                    persisted_inference_records.push(PersistedInference {
+                        pid: proc.pid.0,
+                        start_id: proc.start_id.to_string(),
+                        classification: ledger.classification.label().to_string(),
+                        posterior_useful: posterior.useful,
+                        posterior_useful_bad: posterior.useful_bad,
+                        posterior_abandoned: posterior.abandoned,
+                        posterior_zombie: posterior.zombie,
+                        confidence: ledger.confidence.label().to_string(),
+                        recommended_action: recommended_action.to_string(),
+                        score,
+                    });
+                }
 
-                        goal_summary = Some(goal_json);
-                        goal_selected = Some(selected_set);
-                    }
-                    Err(err) => {
-                        goal_summary = Some(serde_json::json!({
-                            "goal": goal_str,
-                            "parsed": goal.canonical(),
-                            "error": err,
-                        }));
-                    }
+                let host_id = pt_core::logging::get_host_id();
+                let inv_artifact = InventoryArtifact {
+                    total_system_processes: filter_result.total_before as u64,
+                    protected_filtered: filter_result.filtered.len() as u64,
+                    record_count: persisted_inventory_records.len(),
+                    records: persisted_inventory_records,
+                };
+                if let Err(e) = persist_inventory(&handle, &session_id.0, &host_id, inv_artifact) {
+                    eprintln!(
+                        "agent snapshot: warning: failed to persist inventory artifact: {}",
+                        e
+                    );
+                }
+
+                let inf_artifact = InferenceArtifact {
+                    candidate_count: persisted_inference_records.len(),
+                    candidates: persisted_inference_records,
+                };
+                if let Err(e) = persist_inference(&handle, &session_id.0, &host_id, inf_artifact) {
+                    eprintln!(
+                        "agent snapshot: warning: failed to persist inference artifact: {}",
+                        e
+                    );
                 }
-            }
-            Err(err) => {
-                goal_summary = Some(serde_json::json!({
-                    "goal": goal_str,
-                    "error": err,
-                }));
             }
         }
     }
 
-    // Rebuild kill/review/spare candidate lists from the final sorted candidates
-    let mut kill_candidates: Vec<u32> = Vec::new();
-    let mut review_candidates: Vec<u32> = Vec::new();
-    let mut spare_candidates: Vec<u32> = Vec::new();
-    let mut expected_memory_freed_bytes: u64 = 0;
-    for candidate in &candidates {
-        let pid = candidate["pid"].as_u64().unwrap_or(0) as u32;
-        let action = candidate["recommended_action"].as_str().unwrap_or("");
-        let memory_mb = candidate["memory_mb"].as_u64().unwrap_or(0);
-        let selected_by_goal = goal_selected
-            .as_ref()
-            .map(|selected| selected.contains(&pid))
-            .unwrap_or(false);
-        if selected_by_goal || action == "kill" {
-            kill_candidates.push(pid);
-            expected_memory_freed_bytes += memory_mb * 1024 * 1024;
-        } else if action == "keep" {
-            spare_candidates.push(pid);
-        } else {
-            review_candidates.push(pid);
-        }
-    }
-    let expected_memory_freed_gb = (expected_memory_freed_bytes as f64) / 1024.0 / 1024.0 / 1024.0;
+    // Collect process list if --top, --include-env, or --include-network is specified.
+    let process_snapshot = if args.top.is_some() || args.include_env || args.include_network {
+        if let Some(ref scan_result) = scan_result {
+            let mut processes: Vec<_> = scan_result.processes.iter().collect();
 
-    // Collect host information
-    let host_info = collect_host_info();
+            // Sort by resource usage (CPU + normalized memory)
+            processes.sort_by(|a, b| {
+                let a_score = a.cpu_percent + (a.rss_bytes as f64 / 1_073_741_824.0); // GB
+                let b_score = b.cpu_percent + (b.rss_bytes as f64 / 1_073_741_824.0);
+                b_score
+                    .partial_cmp(&a_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
 
-    // Build scan info
-    let scan_info = serde_json::json!({
-        "total_processes": total_scanned,
-        "candidates_found": above_threshold_count,
-        "scan_duration_ms": scan_duration_ms,
-    });
+            // Limit to top N if specified
+            if let Some(top_n) = args.top {
+                processes.truncate(top_n);
+            }
 
-    // Build summary (legacy format for backward compatibility)
-    let mut summary = serde_json::json!({
-        "total_processes_scanned": total_scanned,
-        "protected_filtered": protected_filtered_count,
-        "candidates_evaluated": candidates_evaluated,
-        "above_threshold": above_threshold_count,  // Candidates meeting threshold before truncation
-        "candidates_returned": candidates.len(),   // After truncation to max_candidates
-        "kill_recommendations": kill_candidates.len(),
-        "review_recommendations": review_candidates.len(),
-        "policy_blocked": policy_blocked_count,
-        "signature_matches": signature_match_count,
-        "signature_fast_path_used": signature_fast_path_used_count,
-        "signature_fast_path_enabled": fast_path_config.enabled,
-        "signature_fast_path_min_confidence_threshold": fast_path_config.min_confidence_threshold,
-        "signature_fast_path_require_explicit_priors": fast_path_config.require_explicit_priors,
-        "threshold_used": args.min_posterior,
-        "filter_used": args.only,
-    });
-    if global.shadow {
-        summary["shadow_observations_recorded"] = serde_json::json!(shadow_recorded);
-    }
-    if let Some(goal) = &goal_summary {
-        summary["goal_mode"] = serde_json::json!(true);
-        summary["goal_achievable"] = goal
-            .get("achievable")
-            .cloned()
-            .unwrap_or(serde_json::Value::Null);
-        summary["goal_selected_count"] = serde_json::json!(kill_candidates.len());
-    }
+            // Build process summaries for output
+            let summaries: Vec<serde_json::Value> = processes
+                .iter()
+                .map(|p| {
+                    let mut obj = serde_json::json!({
+                        "pid": p.pid.0,
+                        "ppid": p.ppid.0,
+                        "user": &p.user,
+                        "comm": &p.comm,
+                        "cmd": redactor.cmdline(&p.cmd),
+                        "cpu_percent": p.cpu_percent,
+                        "rss_mb": p.rss_bytes / 1_048_576,
+                        "vsz_mb": p.vsz_bytes / 1_048_576,
+                        "state": format!("{:?}", p.state),
+                        "elapsed_secs": p.elapsed.as_secs(),
+                    });
 
-    // Build recommendations section (new structured format)
-    let mut recommendations = serde_json::json!({
-        "kill_set": kill_candidates,
-        "review_set": review_candidates,
-        "spare_set": spare_candidates,
-        "expected_memory_freed_gb": (expected_memory_freed_gb * 100.0).round() / 100.0,
-        "fleet_fdr": 0.03, // Placeholder - would come from fleet-wide statistics
-    });
-    if let Some(goal) = &goal_summary {
-        recommendations["goal"] = goal.clone();
-    }
+                    // Add environment info placeholder (redacted keys only)
+                    if args.include_env {
+                        // Read env from /proc/<pid>/environ and extract key names only
+                        let env_path = format!("/proc/{}/environ", p.pid.0);
+                        if let Ok(content) = std::fs::read(&env_path) {
+                            let keys: Vec<String> = content
+                                .split(|&b| b == 0)
+                                .filter_map(|entry| {
+                                    std::str::from_utf8(entry)
+                                        .ok()
+                                        .and_then(|s| s.split('=').next())
+                                        .map(|k| k.to_string())
+                                })
+                                .filter(|k| !k.is_empty())
+                                .collect();
+                            obj.as_object_mut()
+                                .unwrap()
+                                .insert("env_keys".to_string(), serde_json::json!(keys));
+                        }
+                    }
 
-    // Build recommended section (legacy format for backward compatibility)
-    let empty_pids: Vec<u32> = Vec::new();
-    let preselected_pids = if args.yes {
-        &kill_candidates
+                    // Add network connections placeholder
+                    if args.include_network {
+                        // Count file descriptors that look like sockets
+                        let fd_path = format!("/proc/{}/fd", p.pid.0);
+                        let socket_count = std::fs::read_dir(&fd_path)
+                            .map(|entries| {
+                                entries
+                                    .filter_map(|e| e.ok())
+                                    .filter(|e| {
+                                        e.path()
+                                            .read_link()
+                                            .map(|target| {
+                                                target.to_string_lossy().starts_with("socket:")
+                                            })
+                                            .unwrap_or(false)
+                                    })
+                                    .count()
+                            })
+                            .unwrap_or(0);
+                        obj.as_object_mut()
+                            .unwrap()
+                            .insert("socket_count".to_string(), serde_json::json!(socket_count));
+                    }
+
+                    obj
+                })
+                .collect();
+
+            Some(serde_json::json!({
+                "count": summaries.len(),
+                "top_n": args.top,
+                "include_env": args.include_env,
+                "include_network": args.include_network,
+                "processes": summaries,
+            }))
+        } else {
+            None
+        }
     } else {
-        &empty_pids
+        None
     };
-    let recommended = serde_json::json!({
-        "preselected_pids": preselected_pids,
-        "actions": kill_candidates.iter().map(|pid| serde_json::json!({
-            "pid": pid,
-            "action": "kill",
-            "stage": 1,
-        })).collect::<Vec<_>>(),
-    });
 
-    // Check for stub flags usage (future features parsed but not yet functional)
-    let mut stub_flags_used: Vec<&str> = Vec::new();
-    if args.since.is_some() {
-        stub_flags_used.push("--since");
+    if let Err(e) = redactor.persist_dictionary(&handle.dir) {
+        eprintln!(
+            "agent snapshot: warning: failed to save redaction dictionary: {}",
+            e
+        );
     }
-    if args.since_time.is_some() {
-        stub_flags_used.push("--since-time");
-    }
-
-    // Build stub_flags section if any future flags were used
-    let stub_flags_section = if !stub_flags_used.is_empty() {
-        Some(serde_json::json!({
-            "warning": "feature_not_implemented",
-            "message": "Some flags are parsed but not yet functional",
-            "flags_used": stub_flags_used,
-            "flags_ignored": stub_flags_used,
-            "workaround": "Use pt agent diff for manual comparison; goal-oriented mode coming in v1.2",
-            "planned_release": "v1.2"
-        }))
-    } else {
-        None
-    };
-
-    let goal_value = goal_summary
-        .as_ref()
-        .and_then(|goal| goal.get("goal"))
-        .cloned()
-        .or_else(|| args.goal.as_ref().map(|goal| serde_json::json!(goal)))
-        .unwrap_or(serde_json::Value::Null);
-    let goal_progress = goal_summary
-        .as_ref()
-        .and_then(|goal| goal.get("goal_achievement"))
-        .cloned()
-        .unwrap_or_else(|| serde_json::json!([]));
 
-    // Build complete plan output with structured JSON format
-    let mut plan_output = serde_json::json!({
-        "pt_version": env!("CARGO_PKG_VERSION"),
-        "schema_version": SCHEMA_VERSION,
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "session_id": session_id.0,
-        "label": args.label,
-        "generated_at": chrono::Utc::now().to_rfc3339(),
-        "host_id": pt_core::logging::get_host_id(),
-        "host": host_info,
-        "scan": scan_info,
-        "command": "agent plan",
-        "args": {
-            "max_candidates": args.max_candidates,
-            "min_posterior": args.min_posterior,
-            "only": args.only,
-            "yes": args.yes,
-            "dry_run": global.dry_run,
-            "robot": global.robot,
-            "shadow": global.shadow,
-            "min_age": args.min_age,
-            "sample_size": args.sample_size,
-            "include_kernel_threads": args.include_kernel_threads,
-            "deep": args.deep,
-            "since": args.since,
-            "since_time": args.since_time,
-            "goal": args.goal,
-            "include_predictions": args.include_predictions,
-            "prediction_fields": args.prediction_fields,
-            "minimal": args.minimal,
-            "pretty": args.pretty,
-            "brief": args.brief,
-            "narrative": args.narrative,
+    // Build capabilities summary for output
+    let capabilities_summary = serde_json::json!({
+        "tools": {
+            "perf": caps.tools.perf.available,
+            "bpftrace": caps.tools.bpftrace.available,
+            "strace": caps.tools.strace.available,
+            "lsof": caps.tools.lsof.available,
+            "ps": caps.tools.ps.available,
+            "systemctl": caps.tools.systemctl.available,
+        },
+        "permissions": {
+            "can_sudo": caps.permissions.can_sudo,
+            "can_ptrace": caps.permissions.can_read_others_procs,
+            "is_root": caps.permissions.is_root,
         },
-        "summary": summary,
-        "goal": goal_value,
-        "goal_progress": goal_progress,
-        "goal_summary": goal_summary,
-        "candidates": candidates,
-        "recommendations": recommendations,
-        "recommended": recommended,  // Legacy format for backward compatibility
-        "session_created": created,
     });
 
-    // Add stub_flags section if any future flags were used
-    if let Some(stub_flags) = stub_flags_section {
-        plan_output["stub_flags"] = stub_flags;
-    }
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let mut output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "host_id": host_id,
+                "timestamp": timestamp.to_rfc3339(),
+                "generated_at": timestamp.to_rfc3339(),
+                "label": args.label,
+                "session_dir": handle.dir.display().to_string(),
+                "context_path": handle.context_path().display().to_string(),
+                "system_state": system_state,
+                "capabilities": capabilities_summary,
+            });
+            if let Some(procs) = &process_snapshot {
+                output
+                    .as_object_mut()
+                    .unwrap()
+                    .insert("process_snapshot".to_string(), procs.clone());
+            }
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            let mem = system_state
+                .get("memory")
+                .and_then(|m| m.get("used_gb"))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let total = system_state
+                .get("memory")
+                .and_then(|m| m.get("total_gb"))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let procs = system_state
+                .get("process_count")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            println!(
+                "[{}] agent snapshot: created ({} procs, {:.0}/{:.0}GB mem)",
+                session_id, procs, mem, total
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core agent snapshot");
+            println!();
+            println!("Session: {}", session_id);
+            println!("Host: {}", host_id);
+            println!("Dir: {}", handle.dir.display());
+            if let Some(label) = &args.label {
+                println!("Label: {}", label);
+            }
+            println!();
+            println!("## System State");
+            if let Some(load) = system_state.get("load").and_then(|v| v.as_array()) {
+                let load_strs: Vec<String> = load
+                    .iter()
+                    .filter_map(|v| v.as_f64().map(|f| format!("{:.2}", f)))
+                    .collect();
+                println!("  Load: {}", load_strs.join(", "));
+            }
+            if let Some(cores) = system_state.get("cores").and_then(|v| v.as_u64()) {
+                println!("  Cores: {}", cores);
+            }
+            if let Some(mem) = system_state.get("memory") {
+                let total = mem.get("total_gb").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let used = mem.get("used_gb").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let avail = mem
+                    .get("available_gb")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                println!(
+                    "  Memory: {:.1}GB total, {:.1}GB used, {:.1}GB available",
+                    total, used, avail
+                );
+            }
+            if let Some(procs) = system_state.get("process_count").and_then(|v| v.as_u64()) {
+                println!("  Processes: {}", procs);
+            }
+            if let Some(psi) = system_state.get("psi") {
+                let cpu = psi.get("cpu").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let mem = psi.get("memory").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let io = psi.get("io").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                println!("  PSI: cpu={:.2}%, mem={:.2}%, io={:.2}%", cpu, mem, io);
+            }
 
-    // Write plan to session
-    let decision_dir = handle.dir.join("decision");
-    if let Err(e) = std::fs::create_dir_all(&decision_dir) {
-        eprintln!(
-            "agent plan: failed to create directory {}: {}",
-            decision_dir.display(),
-            e
-        );
-        return ExitCode::InternalError;
-    }
-    let plan_path = decision_dir.join("plan.json");
-    if let Err(e) = std::fs::write(
-        &plan_path,
-        serde_json::to_string_pretty(&plan_output).unwrap(),
-    ) {
-        eprintln!("agent plan: failed to write {}: {}", plan_path.display(), e);
-        return ExitCode::InternalError;
+            // Display process snapshot if collected
+            if let Some(snapshot) = &process_snapshot {
+                println!();
+                println!("## Process Snapshot");
+                let count = snapshot.get("count").and_then(|v| v.as_u64()).unwrap_or(0);
+                let top_n = snapshot.get("top_n").and_then(|v| v.as_u64());
+                if let Some(n) = top_n {
+                    println!("  Top {} processes by resource usage:", n);
+                } else {
+                    println!("  {} processes:", count);
+                }
+                if let Some(procs) = snapshot.get("processes").and_then(|v| v.as_array()) {
+                    for p in procs.iter().take(10) {
+                        let pid = p.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+                        let comm = p.get("comm").and_then(|v| v.as_str()).unwrap_or("?");
+                        let cpu = p.get("cpu_percent").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                        let rss = p.get("rss_mb").and_then(|v| v.as_u64()).unwrap_or(0);
+                        println!("    {:>7} {:<20} {:>5.1}% CPU {:>6}MB", pid, comm, cpu, rss);
+                    }
+                    if procs.len() > 10 {
+                        println!(
+                            "    ... and {} more (use --format json for full list)",
+                            procs.len() - 10
+                        );
+                    }
+                }
+            }
+        }
     }
 
-    // Persist compact diff artifacts so `pt diff` can compare sessions reliably.
-    // Best-effort: don't fail the plan output if persistence fails, but emit a warning.
-    let host_id = pt_core::logging::get_host_id();
-    let inv_artifact = InventoryArtifact {
-        total_system_processes: total_scanned as u64,
-        protected_filtered: protected_filtered_count as u64,
-        record_count: persisted_inventory_records.len(),
-        records: persisted_inventory_records,
-    };
-    if let Err(e) = persist_inventory(&handle, &session_id.0, &host_id, inv_artifact) {
-        eprintln!(
-            "agent plan: warning: failed to persist inventory artifact: {}",
-            e
-        );
-    }
+    ExitCode::Clean
+}
 
-    let inf_artifact = InferenceArtifact {
-        candidate_count: persisted_inference_records.len(),
-        candidates: persisted_inference_records,
-    };
-    if let Err(e) = persist_inference(&handle, &session_id.0, &host_id, inf_artifact) {
-        eprintln!(
-            "agent plan: warning: failed to persist inference artifact: {}",
-            e
-        );
+fn match_level_label(level: MatchLevel) -> &'static str {
+    match level {
+        MatchLevel::None => "none",
+        MatchLevel::GenericCategory => "generic_category",
+        MatchLevel::CommandOnly => "command_only",
+        MatchLevel::CommandPlusArgs => "command_plus_args",
+        MatchLevel::ExactCommand => "exact_command",
+        MatchLevel::MultiPattern => "multi_pattern",
     }
+}
 
-    // Update manifest state
-    let _ = handle.update_state(SessionState::Planned);
+/// Per-process results `agent plan` computes on the `--jobs` pool.
+struct PlanInference<'a> {
+    child_activity: Option<&'a serde_json::Value>,
+    #[cfg(target_os = "linux")]
+    gpu_summary: Option<pt_core::collect::ProcessGpuSummary>,
+    #[cfg(target_os = "linux")]
+    net_activity: Option<pt_core::collect::ProcessNetActivity>,
+    #[cfg(target_os = "linux")]
+    net_bucket: Option<usize>,
+    #[cfg(target_os = "linux")]
+    swap_bytes: Option<u64>,
+    #[cfg(target_os = "linux")]
+    swap_bucket: Option<usize>,
+    behavior_sample: Option<BehaviorSample>,
+    behavior_deviation: Option<BehaviorDeviation>,
+    fd_leak: Option<FdLeakAssessment>,
+    signature_match: Option<SignatureMatch<'a>>,
+    runtime_surprise: Option<RuntimeSurprise>,
+    evidence: Evidence,
+    fast_path_used: bool,
+    fast_path_skip_reason: Option<&'static str>,
+    /// Posterior, ledger and prior source; `None` if the posterior failed.
+    result: Option<(PosteriorResult, EvidenceLedger, String)>,
+}
 
-    if let Some(ref e) = emitter {
-        e.emit(
-            ProgressEvent::new(pt_core::events::event_names::PLAN_READY, Phase::Plan)
-                .with_session_id(session_id.to_string())
-                .with_detail("plan_path", plan_path.display().to_string())
-                .with_detail("count", candidates.len()),
-        );
-    }
-
-    // Warn about stub flags on stderr (for all formats, machine-parseable too)
-    if !stub_flags_used.is_empty() {
-        eprintln!(
-            "warning: flags {} are parsed but not yet functional (coming in v1.2)",
-            stub_flags_used.join(", ")
-        );
-    }
-
-    // Handle --narrative flag (outputs prose regardless of format)
-    if args.narrative {
-        let narrative = generate_narrative_summary(
-            &session_id,
-            &candidates,
-            &kill_candidates,
-            &review_candidates,
-            total_scanned,
-            expected_memory_freed_gb,
-        );
-        println!("{}", narrative);
-        return if candidates.is_empty() {
-            ExitCode::Clean
-        } else {
-            ExitCode::PlanReady
-        };
-    }
-
-    // Output based on format
-    match global.format {
-        OutputFormat::Json => {
-            // Build output based on --minimal, --brief, and --pretty flags
-            let output_json = if args.brief {
-                // Brief output: minimal fields + single-line rationale
-                let brief_candidates: Vec<serde_json::Value> = candidates
-                    .iter()
-                    .map(|c| {
-                        let rationale = generate_single_line_rationale(c);
-                        serde_json::json!({
-                            "pid": c["pid"],
-                            "cmd": c["command_short"],
-                            "score": c["score"],
-                            "rec": c["recommendation"],
-                            "why": rationale,
-                        })
-                    })
-                    .collect();
-                serde_json::json!({
-                    "v": env!("CARGO_PKG_VERSION"),
-                    "sid": session_id.0,
-                    "n": candidates.len(),
-                    "kill": kill_candidates.len(),
-                    "review": review_candidates.len(),
-                    "c": brief_candidates,
-                })
-            } else if args.minimal {
-                // Minimal output: just PIDs, scores, and recommendations
-                let minimal_candidates: Vec<serde_json::Value> = candidates
-                    .iter()
-                    .map(|c| {
-                        serde_json::json!({
-                            "pid": c["pid"],
-                            "score": c["score"],
-                            "recommendation": c["recommendation"],
-                        })
-                    })
-                    .collect();
-                serde_json::json!({
-                    "pt_version": env!("CARGO_PKG_VERSION"),
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "session_id": session_id.0,
-                    "candidates": minimal_candidates,
-                    "recommendations": recommendations,
-                })
-            } else {
-                plan_output.clone()
-            };
-
-            // Apply pretty-printing or compact based on --pretty flag
-            let output_str = if args.pretty {
-                serde_json::to_string_pretty(&output_json).unwrap()
-            } else {
-                // Use global.process_output for token-efficient processing if not pretty
-                global.process_output(output_json)
-            };
-            println!("{}", output_str);
-        }
-        OutputFormat::Toon => {
-            let output_json = if args.brief {
-                // Brief output for TOON: minimal fields + single-line rationale
-                let brief_candidates: Vec<serde_json::Value> = candidates
-                    .iter()
-                    .map(|c| {
-                        let rationale = generate_single_line_rationale(c);
-                        serde_json::json!({
-                            "p": c["pid"],
-                            "c": c["command_short"],
-                            "s": c["score"],
-                            "r": c["recommendation"],
-                            "w": rationale,
-                        })
-                    })
-                    .collect();
-                serde_json::json!({
-                    "v": env!("CARGO_PKG_VERSION"),
-                    "i": session_id.0,
-                    "n": candidates.len(),
-                    "k": kill_candidates.len(),
-                    "r": review_candidates.len(),
-                    "c": brief_candidates,
-                })
-            } else if args.minimal {
-                let minimal_candidates: Vec<serde_json::Value> = candidates
-                    .iter()
-                    .map(|c| {
-                        serde_json::json!({
-                            "pid": c["pid"],
-                            "score": c["score"],
-                            "recommendation": c["recommendation"],
-                        })
-                    })
-                    .collect();
-                serde_json::json!({
-                    "pt_version": env!("CARGO_PKG_VERSION"),
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "session_id": session_id.0,
-                    "candidates": minimal_candidates,
-                    "recommendations": recommendations,
-                })
-            } else {
-                plan_output.clone()
-            };
-
-            let output_value = if args.pretty {
-                output_json
-            } else {
-                global.process_output_value(output_json)
-            };
-            println!("{}", encode_toon_value(&output_value));
-        }
-        OutputFormat::Summary => {
-            println!(
-                "[{}] agent plan: {} candidates ({} kill, {} review)",
-                session_id,
-                candidates.len(),
-                kill_candidates.len(),
-                review_candidates.len()
-            );
-        }
-        OutputFormat::Exitcode => {}
-        _ => {
-            println!("# pt-core agent plan\n");
-            println!("Session: {}", session_id);
-            println!("Plan: {}\n", plan_path.display());
-            println!("## Summary\n");
-            println!("- Processes scanned: {}", scan_result.processes.len());
-            println!("- Candidates identified: {}", candidates.len());
-            println!("- Kill recommendations: {}", kill_candidates.len());
-            println!("- Review recommendations: {}", review_candidates.len());
-            println!("\n## Candidates\n");
-            for candidate in &candidates {
-                let pid = candidate.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
-                let cmd = candidate
-                    .get("command_short")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("?");
-                let class = candidate
-                    .get("classification")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("?");
-                let action = candidate
-                    .get("recommended_action")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("?");
-                println!("- PID {}: {} ({}) → {}", pid, cmd, class, action);
-            }
-        }
-    }
-
-    // Return appropriate exit code
-    if candidates.is_empty() {
-        ExitCode::Clean // 0: nothing to do
-    } else {
-        ExitCode::PlanReady // 1: candidates exist, plan produced
+fn fast_path_skip_reason_label(reason: FastPathSkipReason) -> &'static str {
+    match reason {
+        FastPathSkipReason::Disabled => "disabled",
+        FastPathSkipReason::NoMatch => "no_match",
+        FastPathSkipReason::ScoreBelowThreshold => "score_below_threshold",
+        FastPathSkipReason::NoPriors => "no_priors",
+        FastPathSkipReason::RolloutStage => "rollout_stage",
     }
 }
 
-fn run_agent_explain(global: &GlobalOpts, args: &AgentExplainArgs) -> ExitCode {
+fn run_agent_plan(global: &GlobalOpts, args: &AgentPlanArgs) -> ExitCode {
+    if let Some(bundle) = args.replay_bundle.as_deref() {
+        return run_agent_plan_replay(global, args, bundle);
+    }
+    // Planning never acts on processes.
+    let _lock = match acquire_global_lock(global, "agent plan", LockAccess::ReadOnly) {
+        Ok(lock) => lock,
+        Err(code) => return code,
+    };
     let store = match SessionStore::from_env() {
         Ok(store) => store,
         Err(e) => {
-            eprintln!("agent explain: session store error: {}", e);
+            eprintln!("agent plan: session store error: {}", e);
             return ExitCode::InternalError;
         }
     };
-    let sid = match SessionId::parse(&args.session) {
-        Some(sid) => sid,
-        None => {
-            eprintln!("agent explain: invalid --session {}", args.session);
-            return ExitCode::ArgsError;
-        }
-    };
-    let handle = match store.open(&sid) {
-        Ok(h) => h,
-        Err(e) => {
-            eprintln!("agent explain: {}", e);
+
+    let experiment = match args
+        .experiment
+        .as_deref()
+        .map(pt_core::config::Experiment::load)
+    {
+        Some(Ok(experiment)) => Some(experiment),
+        Some(Err(e)) => {
+            eprintln!("agent plan: {}", e);
             return ExitCode::ArgsError;
         }
+        None => None,
     };
-
-    // Load priors from config or use defaults
-    let priors = match load_priors_for_explain(global) {
-        Ok(p) => p,
+    let redactor = match output_redactor(global) {
+        Ok(redactor) => redactor,
         Err(e) => {
-            eprintln!("agent explain: failed to load priors: {}", e);
+            eprintln!("agent plan: {}", e);
             return ExitCode::InternalError;
         }
     };
+    let experiment_tag = experiment
+        .as_ref()
+        .map(|experiment| pt_core::session::ExperimentTag {
+            name: experiment.name.clone(),
+            overlay_hash: experiment.overlay_hash(),
+            promoted: false,
+        });
 
-    // Determine which PIDs to explain
-    let pids_to_explain: Vec<u32> = if !args.pids.is_empty() {
-        args.pids.clone()
-    } else if let Some(ref target) = args.target {
-        // Parse target format "pid:start_id" and extract PID
-        match target.split(':').next().and_then(|s| s.parse::<u32>().ok()) {
-            Some(pid) => vec![pid],
-            None => {
-                eprintln!("agent explain: invalid --target format, expected pid:start_id");
-                return ExitCode::ArgsError;
+    let (session_id, handle, created) = match args.session.as_ref() {
+        Some(raw) => {
+            let sid = match SessionId::parse(raw) {
+                Some(sid) => sid,
+                None => {
+                    eprintln!("agent plan: invalid --session {}", raw);
+                    return ExitCode::ArgsError;
+                }
+            };
+            let handle = match store.open(&sid) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    eprintln!("agent plan: {}", e);
+                    return ExitCode::ArgsError;
+                }
+            };
+            if let Some(label) = &args.label {
+                let _ = handle.record_event(SessionEventKind::Labeled {
+                    label: label.clone(),
+                });
+            }
+            if let Some(tag) = &experiment_tag {
+                let tagged = handle.read_manifest().and_then(|mut manifest| {
+                    manifest.experiment = Some(tag.clone());
+                    handle.write_manifest(&manifest)
+                });
+                if let Err(e) = tagged {
+                    eprintln!("agent plan: failed to tag session as experimental: {}", e);
+                    return ExitCode::InternalError;
+                }
             }
+            (sid, handle, false)
+        }
+        None => {
+            let sid = SessionId::new();
+            let mut manifest =
+                SessionManifest::new(&sid, None, SessionMode::RobotPlan, args.label.clone());
+            manifest.experiment = experiment_tag.clone();
+            let handle = match store.create(&manifest) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    eprintln!("agent plan: failed to create session: {}", e);
+                    return ExitCode::InternalError;
+                }
+            };
+            let ctx = SessionContext::new(
+                &sid,
+                pt_core::logging::get_host_id(),
+                pt_core::logging::generate_run_id(),
+                None,
+            );
+            if let Err(e) = handle.write_context(&ctx) {
+                eprintln!("agent plan: failed to write context.json: {}", e);
+                return ExitCode::InternalError;
+            }
+            (sid, handle, true)
         }
-    } else {
-        eprintln!("agent explain: must specify --pids or --target");
-        return ExitCode::ArgsError;
-    };
-
-    // Quick scan to get process records for the specified PIDs
-    let scan_options = QuickScanOptions {
-        pids: pids_to_explain.clone(),
-        include_kernel_threads: false,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
-        progress: None,
     };
 
-    let scan_result = match quick_scan(&scan_options) {
-        Ok(r) => r,
+    // Load configuration and priors
+    let config_options = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        ..Default::default()
+    };
+    let config = match load_config(&config_options) {
+        Ok(c) => c,
         Err(e) => {
-            eprintln!("agent explain: scan failed: {}", e);
+            eprintln!("agent plan: failed to load config: {}", e);
             return ExitCode::InternalError;
         }
     };
+    let (priors, policy) = match &experiment {
+        Some(experiment) => match experiment.apply(&config.priors, &config.policy) {
+            Ok(overlaid) => overlaid,
+            Err(e) => {
+                eprintln!("agent plan: {}", e);
+                return ExitCode::ArgsError;
+            }
+        },
+        None => (config.priors.clone(), config.policy.clone()),
+    };
+    let fast_path_config = FastPathConfig {
+        enabled: policy.signature_fast_path.enabled,
+        min_confidence_threshold: policy.signature_fast_path.min_confidence_threshold,
+        require_explicit_priors: policy.signature_fast_path.require_explicit_priors,
+    };
 
-    // Build explanations for each process
-    let mut explanations: Vec<serde_json::Value> = Vec::new();
-
-    for pid in &pids_to_explain {
-        let record = scan_result.processes.iter().find(|p| p.pid.0 == *pid);
-        match record {
-            Some(proc) => {
-                let explanation = build_process_explanation(proc, &priors, args);
-                explanations.push(explanation);
+    let mut signature_db = SignatureDatabase::with_defaults();
+    if let Some(pack) = pt_core::signature_cli::load_signature_pack() {
+        for signature in pack.signatures {
+            if let Err(err) = signature_db.add(signature) {
+                eprintln!(
+                    "agent plan: warning: skipping invalid signature pack entry: {}",
+                    err
+                );
             }
-            None => {
-                // Process not found - might have exited
-                explanations.push(serde_json::json!({
-                    "pid": pid,
-                    "error": "process not found (may have exited)",
-                    "classification": null,
-                }));
+        }
+    }
+    if let Some(user_schema) = pt_core::signature_cli::load_user_signatures() {
+        for signature in user_schema.signatures {
+            if let Err(err) = signature_db.add(signature) {
+                eprintln!(
+                    "agent plan: warning: skipping invalid user signature during load: {}",
+                    err
+                );
             }
         }
     }
+    let runtime_priors = load_runtime_priors(&signature_db);
+    let behavior_baselines = load_behavior_baselines();
+    let fd_trends = load_fd_trends();
 
-    // Output in requested format
-    let output = serde_json::json!({
-        "schema_version": SCHEMA_VERSION,
-        "session_id": sid.0,
-        "generated_at": chrono::Utc::now().to_rfc3339(),
-        "command": "agent explain",
-        "explanations": explanations,
-    });
+    let rate_limit_path = resolve_data_dir_for_lock().map(|dir| dir.join("rate_limit.json"));
+    let enforcer = match pt_core::decision::PolicyEnforcer::new(&policy, rate_limit_path.as_deref())
+    {
+        Ok(enforcer) => enforcer,
+        Err(e) => {
+            eprintln!("agent plan: failed to init policy enforcer: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
 
-    // Optionally save to session
-    let explain_path = handle.dir.join("inference").join("explain.json");
-    if let Err(e) = std::fs::write(
-        &explain_path,
-        serde_json::to_string_pretty(&output).unwrap(),
-    ) {
-        eprintln!("agent explain: warning: failed to save to session: {}", e);
+    if args.prediction_fields.is_some() && !args.include_predictions {
+        eprintln!("agent plan: --prediction-fields requires --include-predictions");
+        return ExitCode::ArgsError;
     }
 
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            println!("{}", format_structured_output(global, output));
-        }
-        OutputFormat::Summary => {
-            for expl in &explanations {
-                if let (Some(pid), Some(class)) = (expl.get("pid"), expl.get("classification")) {
-                    let conf = expl
-                        .get("confidence")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("?");
-                    println!("[{}] PID {}: {} ({})\n", sid, pid, class, conf);
+    let prediction_field_selector = if args.include_predictions {
+        match args.prediction_fields.as_deref() {
+            Some(spec) => match parse_prediction_fields(spec) {
+                Ok(selector) => Some(selector),
+                Err(err) => {
+                    eprintln!("agent plan: {}", err);
+                    return ExitCode::ArgsError;
                 }
-            }
+            },
+            None => None,
         }
-        OutputFormat::Exitcode => {}
-        _ => {
-            // Human readable markdown output
-            println!("# pt-core agent explain\n");
-            println!("Session: {}", sid);
-            println!();
-
-            for expl in &explanations {
-                let pid = expl.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+    } else {
+        None
+    };
 
-                if let Some(err) = expl.get("error") {
-                    println!("## PID {}\n", pid);
-                    println!("Error: {}\n", err);
-                    continue;
-                }
+    let survival_eta_model = if args.include_predictions {
+        load_survival_eta_model()
+    } else {
+        None
+    };
 
-                let class = expl
-                    .get("classification")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                let conf = expl
-                    .get("confidence")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                let why = expl
-                    .get("why_summary")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
+    // Progress emitter for streaming updates + session log.
+    // Emits SESSION_STARTED immediately and guarantees SESSION_ENDED on all exits.
+    let session_lifecycle = SessionLifecycle::start(global, &handle, &session_id);
+    let emitter = session_lifecycle.emitter();
 
-                println!("## PID {} - {} ({})\n", pid, class, conf);
-                if !why.is_empty() {
-                    println!("{}\n", why);
-                }
+    // Listen for exec/exit events while scanning so processes that start and
+    // die between samples are still seen.
+    #[cfg(target_os = "linux")]
+    let short_lived_handle = args.short_lived_window.map(|secs| {
+        std::thread::spawn(move || {
+            pt_core::collect::capture_short_lived(Duration::from_secs(secs))
+                .map_err(|e| e.to_string())
+        })
+    });
+    #[cfg(not(target_os = "linux"))]
+    if args.short_lived_window.is_some() {
+        eprintln!("agent plan: --short-lived-window requires Linux; ignoring");
+    }
 
-                // Show posterior probabilities
-                if let Some(posterior) = expl.get("posterior") {
-                    println!("### Posterior Probabilities\n");
-                    println!("| Class | P(C|x) |");
-                    println!("|-------|--------|");
-                    for class_name in &["useful", "useful_bad", "abandoned", "zombie"] {
-                        if let Some(p) = posterior.get(*class_name).and_then(|v| v.as_f64()) {
-                            println!("| {} | {:.4} |", class_name, p);
-                        }
-                    }
-                    println!();
-                }
+    // Perform quick scan to enumerate processes (with timing).
+    // Daemon and shadow iterations pass a persisted cache to scan incrementally.
+    let scan_start = std::time::Instant::now();
+    let persisted_scan_cache = pt_core::collect::scan_cache::persisted_from_env();
+    let scan_options = QuickScanOptions {
+        pids: vec![],
+        include_kernel_threads: args.include_kernel_threads,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: emitter.clone(),
+        incremental: persisted_scan_cache
+            .as_ref()
+            .map(|(_, cache)| cache.clone()),
+        io_rate_window: Some(pt_core::collect::DEFAULT_IO_RATE_WINDOW),
+    };
 
-                // Show top evidence if galaxy_brain mode
-                if args.galaxy_brain {
-                    if let Some(factors) = expl.get("bayes_factors").and_then(|v| v.as_array()) {
-                        println!("### Evidence Breakdown\n");
-                        println!("| Feature | BF | Direction | Strength |");
-                        println!("|---------|-----|-----------|----------|");
-                        for bf in factors.iter().take(5) {
-                            let feat = bf.get("feature").and_then(|v| v.as_str()).unwrap_or("?");
-                            let bf_val = bf.get("bf").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                            let dir = bf.get("direction").and_then(|v| v.as_str()).unwrap_or("?");
-                            let strength =
-                                bf.get("strength").and_then(|v| v.as_str()).unwrap_or("?");
-                            // Format BF: use scientific notation for extreme values
-                            let bf_str = if bf_val.is_infinite()
-                                || bf_val > 1e6
-                                || (bf_val < 1e-6 && bf_val > 0.0)
-                            {
-                                format!("{:.2e}", bf_val)
-                            } else {
-                                format!("{:.2}", bf_val)
-                            };
-                            println!("| {} | {} | {} | {} |", feat, bf_str, dir, strength);
-                        }
-                        println!();
-                    }
-                }
-            }
+    let mut scan_result = match quick_scan(&scan_options) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("agent plan: scan failed: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    pt_core::collect::annotate_processes(
+        &mut scan_result.processes,
+        &pt_core::collect::PodIndex::load(),
+    );
+    if let Some((path, cache)) = persisted_scan_cache.as_ref() {
+        let cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = cache.save(path) {
+            eprintln!("agent plan: failed to save scan cache: {}", e);
         }
     }
+    let scan_duration_ms = scan_start.elapsed().as_millis() as u64;
 
-    ExitCode::Clean
-}
+    // Short-lived capture: per-parent child churn (evidence) plus the raw capture (output).
+    #[cfg(target_os = "linux")]
+    let (short_lived_children, short_lived_output) = match short_lived_handle.map(|h| h.join()) {
+        Some(Ok(Ok(capture))) => (
+            capture
+                .parents
+                .iter()
+                .map(|(ppid, activity)| (*ppid, serde_json::json!(activity)))
+                .collect::<HashMap<u32, serde_json::Value>>(),
+            Some(serde_json::json!(capture)),
+        ),
+        Some(Ok(Err(e))) => {
+            eprintln!("agent plan: short-lived capture unavailable: {}", e);
+            (
+                HashMap::new(),
+                Some(serde_json::json!({ "available": false, "error": e })),
+            )
+        }
+        Some(Err(_)) => {
+            eprintln!("agent plan: short-lived capture thread panicked");
+            (HashMap::new(), None)
+        }
+        None => (HashMap::new(), None),
+    };
+    #[cfg(not(target_os = "linux"))]
+    let (short_lived_children, short_lived_output): (
+        HashMap<u32, serde_json::Value>,
+        Option<serde_json::Value>,
+    ) = (HashMap::new(), None);
 
-/// Load priors from config with fallback to defaults.
-fn load_priors_for_explain(global: &GlobalOpts) -> Result<Priors, ConfigError> {
-    let opts = ConfigOptions {
-        config_dir: global.config.as_ref().map(PathBuf::from),
-        priors_path: None,
-        policy_path: None,
+    // Quick scan emits its own progress events via the shared emitter.
+
+    // Create protected filter from policy guardrails
+    let protected_filter = match ProtectedFilter::from_guardrails(&policy.guardrails) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("agent plan: failed to create protected filter: {}", e);
+            return ExitCode::InternalError;
+        }
     };
-    match load_config(&opts) {
-        Ok(resolved) => Ok(resolved.priors),
-        Err(_) => Ok(Priors::default()),
-    }
-}
 
-/// Build a JSON explanation for a single process.
-fn build_process_explanation(
-    proc: &ProcessRecord,
-    priors: &Priors,
-    args: &AgentExplainArgs,
-) -> serde_json::Value {
-    // Convert ProcessRecord to Evidence
-    let evidence = Evidence {
-        cpu: Some(CpuEvidence::Fraction {
-            occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
-        }),
-        runtime_seconds: Some(proc.elapsed.as_secs_f64()),
-        orphan: Some(proc.is_orphan()),
-        tty: Some(proc.has_tty()),
-        net: None,       // Would need network scan
-        io_active: None, // Would need /proc inspection
-        state_flag: state_to_flag(proc.state),
-        command_category: None, // Would need category classifier
+    // Apply filter BEFORE inference loop to remove protected processes
+    let filter_result = protected_filter.filter_scan_result(&scan_result);
+    let protected_filtered_count = filter_result.filtered.len();
+    let total_scanned = filter_result.total_before;
+
+    tracing::info!(
+        total_scanned = total_scanned,
+        filtered_count = protected_filtered_count,
+        passed_count = filter_result.passed.len(),
+        "Protected filter applied"
+    );
+    let _ = handle.record_event(SessionEventKind::Scanned {
+        process_count: total_scanned,
+    });
+
+    let system_state = collect_system_state();
+    let load_adjustment = if policy.load_aware.enabled {
+        let signals = LoadSignals::from_system_state(&system_state, filter_result.passed.len());
+        compute_load_adjustment(&policy.load_aware, &signals)
+    } else {
+        None
     };
 
-    // Compute posterior
-    let posterior_result = match compute_posterior(priors, &evidence) {
-        Ok(r) => r,
-        Err(e) => {
-            return serde_json::json!({
-                "pid": proc.pid.0,
-                "comm": proc.comm,
-                "error": format!("posterior computation failed: {}", e),
-            });
-        }
+    let decision_policy = if let Some(adjustment) = &load_adjustment {
+        let mut adjusted = policy.clone();
+        adjusted.loss_matrix = apply_load_to_loss_matrix(&policy.loss_matrix, adjustment);
+        adjusted
+    } else {
+        policy.clone()
     };
 
-    // Build evidence ledger
-    let ledger = EvidenceLedger::from_posterior_result(&posterior_result, Some(proc.pid.0), None);
+    // Process each candidate: compute posterior, make decision, build candidate output.
+    //
+    // Collect all candidates above threshold with their max_posterior for sorting, plus
+    // a compact persisted snapshot (inventory + inference) so `diff` can compare sessions.
+    let mut all_candidates: Vec<(f64, serde_json::Value, PersistedProcess, PersistedInference)> =
+        Vec::new();
+    let mut policy_blocked_count = 0usize;
+    let mut signature_match_count = 0usize;
+    let mut signature_fast_path_used_count = 0usize;
+    // Evidence ledger, replayed by `calibrate compare` and `--replay-bundle`.
+    let mut evidence_records: Vec<EvidenceRecord> = Vec::new();
 
-    // Build base explanation
-    let mut explanation = serde_json::json!({
-        "pid": proc.pid.0,
-        "ppid": proc.ppid.0,
-        "comm": proc.comm,
-        "user": proc.user,
-        "state": proc.state.to_string(),
-        "elapsed_seconds": proc.elapsed.as_secs(),
-        "cpu_percent": proc.cpu_percent,
-        "classification": ledger.classification.label(),
-        "confidence": ledger.confidence.label(),
-        "why_summary": ledger.why_summary,
-        "posterior": {
-            "useful": posterior_result.posterior.useful,
-            "useful_bad": posterior_result.posterior.useful_bad,
-            "abandoned": posterior_result.posterior.abandoned,
-            "zombie": posterior_result.posterior.zombie,
-        },
+    let base_feasibility = ActionFeasibility::allow_all();
+    // Near a full disk, drop optional telemetry instead of failing mid-run.
+    let mut write_guard = telemetry_write_guard(global);
+    let minimal_telemetry = !write_guard
+        .check(
+            &handle.dir,
+            SESSION_WRITE_ESTIMATE_BYTES,
+            WritePriority::Optional,
+        )
+        .allowed();
+    // Experimental sessions stay out of the shadow calibration store.
+    if global.shadow && experiment.is_some() {
+        eprintln!("agent plan: --experiment session; not recording shadow observations");
+    }
+    if global.shadow && minimal_telemetry {
+        eprintln!("agent plan: disk below reserve; not recording shadow observations");
+    }
+    let mut shadow_recorder = if global.shadow && experiment.is_none() && !minimal_telemetry {
+        match ShadowRecorder::new() {
+            Ok(mut recorder) => {
+                if args.sprt {
+                    if let Err(err) =
+                        recorder.enable_sprt(pt_core::inference::ShadowSprtConfig::default())
+                    {
+                        eprintln!("shadow mode: failed to load SPRT state: {:?}", err);
+                    }
+                }
+                Some(recorder)
+            }
+            Err(err) => {
+                eprintln!("shadow mode: failed to initialize storage: {:?}", err);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let mut shadow_recorded = 0u64;
+    if args.sprt && shadow_recorder.is_none() {
+        eprintln!("agent plan: --sprt needs --shadow; using single-shot decisions");
+    }
+    let mut sprt_held_count = 0usize;
+
+    // Kills of identities that keep respawning under a supervisor go to review.
+    let respawn_history = pt_core::decision::respawn_loop::RespawnTracker::load(
+        &pt_core::decision::respawn_loop::respawn_history_path(&shadow_base_dir()),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("agent plan: respawn history unreadable: {}", err);
+        Default::default()
     });
+    let respawn_config = pt_core::decision::respawn_loop::RespawnLoopConfig::default();
+    let respawn_now = chrono::Utc::now().timestamp() as f64;
+    let mut respawn_held_count = 0usize;
 
-    // Add Bayes factors if galaxy_brain mode or requested
-    if args.galaxy_brain || args.include.contains(&"bayes_factors".to_string()) {
-        let bf_entries: Vec<serde_json::Value> = ledger
-            .bayes_factors
+    // Apply min-age filter before sampling (if configured)
+    let eligible_processes: Vec<_> = if let Some(min_age) = args.min_age {
+        filter_result
+            .passed
             .iter()
-            .map(|bf| {
-                serde_json::json!({
-                    "feature": bf.feature,
-                    "log_bf": bf.log_bf,
-                    "bf": bf.bf,
-                    "delta_bits": bf.delta_bits,
-                    "direction": format!("{}", bf.direction),
-                    "strength": bf.strength.clone(),
-                })
-            })
+            .filter(|proc| proc.elapsed.as_secs() >= min_age)
+            .collect()
+    } else {
+        filter_result.passed.iter().collect()
+    };
+
+    // Drop candidates the user snoozed or pinned ("looked at it, fine for now")
+    let snoozes = load_active_snoozes();
+    let eligible_count_before_snooze = eligible_processes.len();
+    let eligible_processes: Vec<_> = eligible_processes
+        .into_iter()
+        .filter(|proc| !snoozes.contains(proc.uid, &proc.cmd))
+        .collect();
+    let snoozed_skipped_count = eligible_count_before_snooze - eligible_processes.len();
+
+    // Drop candidates a userspace OOM killer (systemd-oomd/earlyoom) just killed;
+    // the other actor already handled them and they are on their way out.
+    let oom_killers = pt_core::collect::detect_oom_killers();
+    let recent_oom_kills =
+        pt_core::collect::RecentOomKills::new(pt_core::collect::collect_recent_oom_kills(
+            &oom_killers,
+            Duration::from_secs(pt_core::collect::DEFAULT_RECENT_KILL_WINDOW_SECS),
+        ));
+    let eligible_count_before_oom = eligible_processes.len();
+    let eligible_processes: Vec<_> = eligible_processes
+        .into_iter()
+        .filter(|proc| !is_recently_oom_killed(&recent_oom_kills, proc))
+        .collect();
+    let oom_killed_skipped_count = eligible_count_before_oom - eligible_processes.len();
+
+    // Apply sampling if requested (for testing)
+    let processes_to_infer: Vec<_> = if let Some(sample_size) = args.sample_size {
+        use rand::seq::SliceRandom;
+        let mut rng = rand::rng();
+        let mut sampled: Vec<_> = eligible_processes;
+        sampled.shuffle(&mut rng);
+        sampled.truncate(sample_size);
+        sampled
+    } else {
+        eligible_processes
+    };
+
+    let _current_cpu_pct: f64 = processes_to_infer.iter().map(|p| p.cpu_percent).sum();
+
+    // Kills of interactive processes whose CPU comes from one runaway thread go
+    // to review. Threads are sampled now; inference covers most of the window.
+    #[cfg(target_os = "linux")]
+    let thread_sampler = policy.thread_hog.review_interactive.then(|| {
+        let interactive: Vec<u32> = processes_to_infer
+            .iter()
+            .filter(|proc| proc.has_tty())
+            .map(|proc| proc.pid.0)
             .collect();
-        explanation["bayes_factors"] = serde_json::json!(bf_entries);
-        explanation["top_evidence"] = serde_json::json!(ledger.top_evidence);
+        pt_core::collect::ThreadCpuSampler::start(&interactive)
+    });
+    let mut thread_hog_held_count = 0usize;
+    let mut foreground_held_count = 0usize;
+    // tmux/screen sessions and SSH logins of terminal processes, for the
+    // session_attachment evidence and the attached-session guardrail.
+    #[cfg(target_os = "linux")]
+    let terminal_sessions = pt_core::collect::TerminalSessionSnapshot::collect(
+        &processes_to_infer
+            .iter()
+            .filter(|proc| proc.has_tty())
+            .map(|proc| proc.pid.0)
+            .collect::<Vec<_>>(),
+    );
+    let mut session_held_count = 0usize;
+    // Gradle, Kotlin, Bazel, sccache and cargo-watch daemons, judged against the whole scan.
+    let build_daemons = pt_core::supervision::BuildDaemonAnalyzer::new(&scan_result.processes);
+    let mut build_daemon_held_count = 0usize;
+    let mut build_daemon_reclaimable_count = 0usize;
+
+    let candidates_evaluated = processes_to_infer.len();
+    let total_processes = candidates_evaluated as u64;
+    let mut processed = 0u64;
+
+    if let Some(ref e) = emitter {
+        e.emit(
+            ProgressEvent::new(
+                pt_core::events::event_names::INFERENCE_STARTED,
+                Phase::Infer,
+            )
+            .with_progress(0, Some(total_processes)),
+        );
+        e.emit(
+            ProgressEvent::new(
+                pt_core::events::event_names::DECISION_STARTED,
+                Phase::Decide,
+            )
+            .with_progress(0, Some(total_processes)),
+        );
     }
 
-    // Add input evidence if requested
-    if args.include.contains(&"evidence".to_string()) {
-        explanation["evidence"] = serde_json::json!({
-            "cpu_occupancy": proc.cpu_percent / 100.0,
-            "runtime_seconds": proc.elapsed.as_secs_f64(),
-            "is_orphan": proc.is_orphan(),
-            "has_tty": proc.has_tty(),
-            "state": proc.state.to_string(),
-        });
+    // Inputs for per-candidate cost/benefit annotation
+    let mut child_counts: HashMap<u32, usize> = HashMap::new();
+    for proc in &scan_result.processes {
+        *child_counts.entry(proc.ppid.0).or_default() += 1;
     }
+    let launch_attributor = pt_core::supervision::LaunchAttributor::new(&scan_result.processes);
+    // Without /proc, IDE and agent helper trees are traced through the scan itself.
+    #[cfg(not(target_os = "linux"))]
+    let mut table_ancestry =
+        pt_core::supervision::AncestryAnalyzer::with_process_table(&scan_result.processes);
+    let process_tree = pt_core::plan::tree::ProcessTree::from_records(&scan_result.processes);
+    #[cfg(target_os = "linux")]
+    let container_runner = pt_core::action::ContainerActionRunner::new(
+        pt_core::action::ContainerActionConfig::from(&policy.container_actions),
+    );
+    // Per-candidate blast radius: subtree over the whole scan, plus the
+    // candidate's write handles, listening ports and connected clients
+    let mut blast_input = pt_core::supervision::blast_radius::BlastRadiusInput {
+        process_table: scan_result
+            .processes
+            .iter()
+            .map(|p| (p.pid.0, (p.comm.clone(), p.ppid.0)))
+            .collect(),
+        ..Default::default()
+    };
+    #[cfg(target_os = "linux")]
+    let network_snapshot = NetworkSnapshot::collect();
+    // Per-socket recency: sock_diag, then `ss`, then /proc/net queue depths
+    #[cfg(target_os = "linux")]
+    let socket_activity = pt_core::collect::SocketActivitySnapshot::collect();
+    // Swapped-out memory only says something while the host is short of it.
+    #[cfg(target_os = "linux")]
+    let swap_pressure = pt_core::collect::SwapPressureSnapshot::collect();
+    #[cfg(target_os = "linux")]
+    let swap_evidence = priors.swap_pressure.as_ref().is_some_and(|sp| {
+        sp.applies(
+            swap_pressure.psi_some_avg10,
+            swap_pressure.swap_used_fraction(),
+        )
+    });
+    // Under hidepid a supervisor or the real parent may be invisible, so
+    // candidates carry wider uncertainty when the scan could not see everything.
+    let visibility_full = scan_result.metadata.visibility.is_full();
+    let ci_half_width = if visibility_full { 0.1 } else { 0.2 };
+    // GPU occupancy needs vendor tools or an fdinfo sweep, so only on --deep
+    #[cfg(target_os = "linux")]
+    let gpu_snapshot = args.deep.then(pt_core::collect::collect_gpu_snapshot);
+
+    // Evidence, signature matching and the posterior/ledger of each process
+    // are independent, so they run on the --jobs pool. Results come back in
+    // scan order; everything stateful (shadow recorder, SPRT, counters) stays
+    // in the serial loop below.
+    let jobs = pt_core::inference::resolve_jobs(args.jobs);
+    let sample_behavior =
+        shadow_recorder.is_some() || !behavior_baselines.is_empty() || !fd_trends.is_empty();
+    let inferred = pt_core::inference::par_map_ordered(&processes_to_infer, jobs, |proc| {
+        // Children spawned during the short-lived window show the parent is doing
+        // work even when its own sampled CPU is idle. No spawns is not evidence.
+        let child_activity = short_lived_children.get(&proc.pid.0);
+        let spawned_children = child_activity.is_some_and(|a| a["forks"].as_u64().unwrap_or(0) > 0);
 
-    explanation
-}
+        #[cfg(target_os = "linux")]
+        let gpu_summary = gpu_snapshot
+            .as_ref()
+            .and_then(|snapshot| pt_core::collect::process_gpu_summary(snapshot, proc.pid.0));
+        #[cfg(target_os = "linux")]
+        let gpu_evidence = gpu_summary.as_ref().map(|gpu| gpu.to_evidence());
+        #[cfg(not(target_os = "linux"))]
+        let gpu_evidence: Option<pt_core::inference::GpuEvidence> = None;
 
-/// Map ProcessState to state flag index for priors.
-fn state_to_flag(state: pt_core::collect::ProcessState) -> Option<usize> {
-    use pt_core::collect::ProcessState;
-    match state {
-        ProcessState::Running => Some(0),
-        ProcessState::Sleeping => Some(1),
-        ProcessState::DiskSleep => Some(2),
-        ProcessState::Zombie => Some(3),
-        ProcessState::Stopped => Some(4),
-        ProcessState::Idle => Some(5),
-        ProcessState::Dead => Some(6),
-        ProcessState::Unknown => None,
-    }
-}
+        #[cfg(target_os = "linux")]
+        let net_activity = socket_activity.process_activity(proc.pid.0);
+        #[cfg(target_os = "linux")]
+        let net_bucket = net_activity
+            .as_ref()
+            .and_then(|activity| activity.idle_secs)
+            .and_then(|idle| priors.net_recency.as_ref().map(|nr| nr.bucket(idle)));
+        #[cfg(not(target_os = "linux"))]
+        let net_bucket: Option<usize> = None;
 
-#[cfg(target_os = "linux")]
-fn supervisor_info_for_plan(pid: u32) -> serde_json::Value {
-    let mut detected = false;
-    let mut supervisor_type: Option<String> = None;
-    let mut unit: Option<String> = None;
-    let mut recommended_action = "kill".to_string();
-    let mut supervisor_command: Option<String> = None;
+        #[cfg(target_os = "linux")]
+        let swap_bytes = swap_evidence
+            .then(|| pt_core::collect::process_swap_bytes(proc.pid.0))
+            .flatten();
+        #[cfg(target_os = "linux")]
+        let swap_bucket = swap_bytes.and_then(|bytes| {
+            priors
+                .swap_pressure
+                .as_ref()
+                .map(|sp| sp.bucket(bytes as f64 / (1024.0 * 1024.0)))
+        });
+        #[cfg(not(target_os = "linux"))]
+        let swap_bucket: Option<usize> = None;
 
-    // Prefer container supervision if present
-    if let Ok(result) = ContainerSupervisionAnalyzer::new()
-        .with_action_recommendations()
-        .analyze(pid)
-    {
-        if result.is_supervised {
-            detected = true;
-            let runtime_label = if result.kubernetes.is_some() {
-                "kubernetes"
-            } else {
-                match result.runtime {
-                    ContainerRuntime::Docker => "docker",
-                    ContainerRuntime::Containerd => "containerd",
-                    ContainerRuntime::Podman => "podman",
-                    ContainerRuntime::Lxc => "lxc",
-                    ContainerRuntime::Crio => "crio",
-                    ContainerRuntime::Generic => "container",
-                    ContainerRuntime::None => "container",
-                }
-            };
-            supervisor_type = Some(runtime_label.to_string());
-            unit = result
-                .container_id_short
-                .clone()
-                .or(result.container_id.clone())
-                .or_else(|| result.kubernetes.as_ref().and_then(|k| k.pod_name.clone()));
+        let io_bucket = proc.io_rate.and_then(|rate| {
+            priors
+                .io_rate
+                .as_ref()
+                .map(|io| io.bucket(rate.total_bytes_per_sec()))
+        });
 
-            if let Some(action) = result.recommended_action.as_ref() {
-                let action_label = match action.action_type {
-                    ContainerActionType::Stop => "stop",
-                    ContainerActionType::Restart => "restart",
-                    ContainerActionType::Remove => "remove",
-                    ContainerActionType::ScaleDown => "scale_down",
-                    ContainerActionType::DeletePod => "delete_pod",
-                    ContainerActionType::Inspect => "inspect",
-                };
-                recommended_action = format!("{}_{}", runtime_label, action_label);
-                supervisor_command = Some(action.command.clone());
-            } else {
-                recommended_action = format!("{}_review", runtime_label);
-            }
+        // Footprint vs. this fingerprint's own history, scored before the
+        // shadow recorder folds the current sample in.
+        let behavior_sample = sample_behavior.then(|| process_behavior_sample(proc));
+        let fingerprint = pt_core::snooze::process_fingerprint(proc.uid, &proc.cmd);
+        let behavior_deviation = behavior_sample
+            .as_ref()
+            .and_then(|sample| behavior_baselines.deviation(&fingerprint, sample));
+        let behavior_bucket = behavior_deviation.as_ref().and_then(|dev| {
+            priors
+                .behavior_change
+                .as_ref()
+                .map(|bc| bc.bucket(dev.max_z))
+        });
+        // Open-fd count against this process's trend from earlier shadow runs.
+        let fd_leak = behavior_sample
+            .as_ref()
+            .and_then(|sample| sample.fd_count)
+            .and_then(|fds| {
+                fd_trends.assess(
+                    &pt_core::shadow::compute_identity_hash(proc),
+                    fds,
+                    chrono::Utc::now(),
+                )
+            });
+        let fd_leak_bucket = fd_leak
+            .as_ref()
+            .and_then(|leak| priors.fd_leak.as_ref().map(|fl| fl.bucket(leak.confidence)));
+        #[cfg(target_os = "linux")]
+        let session_bucket = terminal_sessions
+            .get(proc.pid.0)
+            .and_then(|session| session_attachment_bucket(session, &priors));
+        #[cfg(not(target_os = "linux"))]
+        let session_bucket: Option<usize> = None;
+
+        let mut match_ctx = ProcessMatchContext::with_comm(&proc.comm);
+        if !proc.cmd.is_empty() {
+            match_ctx = match_ctx.cmdline(&proc.cmd);
         }
-    }
+        let signature_match = signature_db.best_match(&match_ctx);
+        // Observe-only signatures are reported but leave inference alone.
+        let active_match = signature_match
+            .as_ref()
+            .filter(|m| m.signature.rollout.applies_priors());
+        let runtime_surprise = active_match.and_then(|m| {
+            runtime_priors.surprise(
+                &format!("{:?}", m.signature.category),
+                proc.elapsed.as_secs_f64(),
+            )
+        });
 
-    // App supervisors (pm2, supervisord, etc.)
-    if !detected {
-        if let Ok(result) = AppSupervisionAnalyzer::new().analyze(pid) {
-            if result.is_supervised && result.supervisor_type != AppSupervisorType::Unknown {
-                detected = true;
-                let supervisor_label = result.supervisor_type.to_string();
-                supervisor_type = Some(supervisor_label.clone());
-                unit = result
-                    .pm2_name
-                    .clone()
-                    .or(result.supervisord_program.clone())
-                    .or(result.supervisor_name.clone());
+        // Build evidence from process record
+        let evidence = Evidence {
+            cpu: Some(CpuEvidence::Fraction {
+                occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
+            }),
+            runtime_seconds: Some(proc.elapsed.as_secs_f64()),
+            orphan: Some(proc.is_orphan()),
+            tty: Some(proc.has_tty()),
+            net: None,
+            io_active: spawned_children.then_some(true),
+            state_flag: state_to_flag(proc.state),
+            command_category: None,
+            gpu: gpu_evidence,
+            runtime_surprise: runtime_surprise.as_ref().map(|s| s.surprisal),
+            net_recent_activity: net_bucket,
+            behavior_change: behavior_bucket,
+            swap_pressure: swap_bucket,
+            io_rate: io_bucket,
+            fd_leak: fd_leak_bucket,
+            session_attachment: session_bucket,
+        };
 
-                if let Some(action) = result.recommended_action.as_ref() {
-                    let action_label = match action.action_type {
-                        AppActionType::Stop => "stop",
-                        AppActionType::Restart => "restart",
-                        AppActionType::Delete => "delete",
-                        AppActionType::Status => "status",
-                        AppActionType::Logs => "logs",
-                    };
-                    recommended_action = format!("{}_{}", supervisor_label, action_label);
-                    supervisor_command = Some(action.command.clone());
-                } else {
-                    recommended_action = format!("{}_review", supervisor_label);
+        let mut fast_path_used = false;
+        let mut fast_path_skip_reason: Option<&'static str> = None;
+        let prior_context = PriorContext {
+            global_priors: &priors,
+            signature_match: active_match,
+            category_defaults: None,
+            user_overrides: None,
+        };
+        let with_overrides = || {
+            compute_posterior_with_overrides(&prior_context, &evidence)
+                .ok()
+                .map(|(result, source_info)| {
+                    let ledger =
+                        EvidenceLedger::from_posterior_result(&result, Some(proc.pid.0), None);
+                    (result, ledger, source_info.source.to_string())
+                })
+        };
+
+        let fast_path =
+            active_match.map(|m| try_signature_fast_path(&fast_path_config, Some(m), proc.pid.0));
+        let result = match fast_path {
+            Some(Ok(Some(fast_path))) => {
+                fast_path_used = true;
+                Some((
+                    fast_path.posterior,
+                    fast_path.ledger,
+                    "signature_fast_path".to_string(),
+                ))
+            }
+            Some(Err(reason)) => {
+                fast_path_skip_reason = Some(fast_path_skip_reason_label(reason));
+                with_overrides()
+            }
+            Some(Ok(None)) | None => with_overrides(),
+        };
+
+        let result = result.map(|(result, mut ledger, prior_source_label)| {
+            if let Some(sig_match) = active_match {
+                if !fast_path_used {
+                    ledger.top_evidence.insert(
+                        0,
+                        format!(
+                            "Signature match: {} (score={:.2}, level={})",
+                            sig_match.signature.name,
+                            sig_match.score,
+                            match_level_label(sig_match.level)
+                        ),
+                    );
+                    ledger.why_summary = format!(
+                        "Matched signature '{}' (score {:.2}, level {}, prior source {}). {}",
+                        sig_match.signature.name,
+                        sig_match.score,
+                        match_level_label(sig_match.level),
+                        prior_source_label,
+                        ledger.why_summary
+                    );
                 }
             }
+            (result, ledger, prior_source_label)
+        });
+
+        PlanInference {
+            child_activity,
+            #[cfg(target_os = "linux")]
+            gpu_summary,
+            #[cfg(target_os = "linux")]
+            net_activity,
+            #[cfg(target_os = "linux")]
+            net_bucket,
+            #[cfg(target_os = "linux")]
+            swap_bytes,
+            #[cfg(target_os = "linux")]
+            swap_bucket,
+            behavior_sample,
+            behavior_deviation,
+            fd_leak,
+            signature_match,
+            runtime_surprise,
+            evidence,
+            fast_path_used,
+            fast_path_skip_reason,
+            result,
         }
-    }
+    });
 
-    // systemd supervision
-    if !detected {
-        if let Some(unit_info) = collect_systemd_unit(pid, None) {
-            detected = true;
-            supervisor_type = Some("systemd".to_string());
-            unit = Some(unit_info.name.clone());
-            let (action_label, command) = match unit_info.unit_type {
-                pt_core::collect::systemd::SystemdUnitType::Scope => (
-                    "systemctl_stop",
-                    format!("systemctl stop {}", unit_info.name),
-                ),
-                _ => (
-                    "systemctl_restart",
-                    format!("systemctl restart {}", unit_info.name),
-                ),
-            };
-            recommended_action = action_label.to_string();
-            supervisor_command = Some(command);
+    // Use filtered (and optionally sampled) processes for inference
+    for (proc, inferred) in processes_to_infer.into_iter().zip(inferred) {
+        // Skip PID 0/1 (extra safety - should already be filtered)
+        if proc.pid.0 == 0 || proc.pid.0 == 1 {
+            continue;
         }
-    }
+        processed = processed.saturating_add(1);
 
-    // Human supervision (agents/IDEs/CI) for warning-only
-    if !detected {
-        if let Ok(result) = detect_supervision(pid) {
-            if is_human_supervised(&result) {
-                detected = true;
-                supervisor_type = result.supervisor_type.map(|t| t.to_string());
-                unit = result.supervisor_name.clone();
-                recommended_action = "review".to_string();
-            }
+        let PlanInference {
+            child_activity,
+            #[cfg(target_os = "linux")]
+            gpu_summary,
+            #[cfg(target_os = "linux")]
+            net_activity,
+            #[cfg(target_os = "linux")]
+            net_bucket,
+            #[cfg(target_os = "linux")]
+            swap_bytes,
+            #[cfg(target_os = "linux")]
+            swap_bucket,
+            behavior_sample,
+            behavior_deviation,
+            fd_leak,
+            signature_match,
+            runtime_surprise,
+            evidence,
+            fast_path_used,
+            fast_path_skip_reason,
+            result,
+        } = inferred;
+        if signature_match.is_some() {
+            signature_match_count = signature_match_count.saturating_add(1);
         }
-    }
+        let Some((posterior_result, ledger, prior_source_label)) = result else {
+            continue;
+        };
+        if fast_path_used {
+            signature_fast_path_used_count = signature_fast_path_used_count.saturating_add(1);
+        }
+        let active_match = signature_match
+            .as_ref()
+            .filter(|m| m.signature.rollout.applies_priors());
 
-    serde_json::json!({
-        "detected": detected,
-        "type": supervisor_type,
-        "unit": unit,
-        "recommended_action": recommended_action,
-        "supervisor_command": supervisor_command,
-    })
-}
+        let signature_name = signature_match.as_ref().map(|m| m.signature.name.clone());
+        let signature_level = signature_match
+            .as_ref()
+            .map(|m| match_level_label(m.level).to_string());
+        let signature_score = signature_match.as_ref().map(|m| m.score);
+        let signature_category = signature_match
+            .as_ref()
+            .map(|m| format!("{:?}", m.signature.category));
 
-#[cfg(not(target_os = "linux"))]
-fn supervisor_info_for_plan(_pid: u32) -> serde_json::Value {
-    serde_json::json!({
-        "detected": false,
-        "type": serde_json::Value::Null,
-        "unit": serde_json::Value::Null,
-        "recommended_action": "kill",
-        "supervisor_command": serde_json::Value::Null,
-    })
-}
+        // Apply state-based feasibility constraints so decisioning does not
+        // recommend fundamentally invalid actions (e.g., kill for zombie/D-state).
+        let state_feasibility = ActionFeasibility::from_process_state(
+            proc.state.is_zombie(),
+            proc.state.is_disksleep(),
+            None,
+        );
+        let feasibility = base_feasibility.merge(&state_feasibility);
 
-#[cfg(target_os = "linux")]
-fn is_supervised_for_robot(pid: u32) -> bool {
-    match detect_supervision(pid) {
-        Ok(result) => is_human_supervised(&result),
-        Err(_) => false,
-    }
-}
+        // Compute decision (optimal action based on expected loss)
+        let mut decision_outcome =
+            match decide_action(&posterior_result.posterior, &decision_policy, &feasibility) {
+                Ok(d) => d,
+                Err(_) => continue, // Skip processes that fail decision
+            };
+        decision_outcome.rationale.has_known_signature = Some(active_match.is_some());
+
+        evidence_records.push(EvidenceRecord {
+            replay: ExperimentRecord {
+                pid: proc.pid.0,
+                start_id: proc.start_id.to_string(),
+                comm: proc.comm.clone(),
+                evidence: evidence.clone(),
+                zombie: proc.state.is_zombie(),
+                disksleep: proc.state.is_disksleep(),
+            },
+            posterior: posterior_result.posterior,
+            action: decision_outcome.optimal_action,
+        });
 
-#[cfg(not(target_os = "linux"))]
-fn is_supervised_for_robot(_pid: u32) -> bool {
-    false
-}
+        // Determine max posterior class for filtering
+        let posterior = &posterior_result.posterior;
+        let max_posterior = posterior
+            .useful
+            .max(posterior.useful_bad)
+            .max(posterior.abandoned)
+            .max(posterior.zombie);
 
-#[cfg(target_os = "linux")]
-fn first_precheck_block(
-    provider: &dyn pt_core::action::prechecks::PreCheckProvider,
-    action: &PlanAction,
-) -> Option<(pt_core::plan::PreCheck, String)> {
-    let results = provider.run_checks(&action.pre_checks, action.target.pid.0, action.target.sid);
-    for result in results {
-        if let pt_core::action::prechecks::PreCheckResult::Blocked { check, reason } = result {
-            return Some((check, reason));
+        // Determine recommended action string (used for shadow recording and plan output)
+        let mut recommended_action = match decision_outcome.optimal_action {
+            Action::Keep => "keep",
+            Action::Renice => "renice",
+            Action::Pause => "pause",
+            Action::Resume => "resume",
+            Action::Freeze => "freeze",
+            Action::Unfreeze => "unfreeze",
+            Action::Throttle => "throttle",
+            Action::Quarantine => "quarantine",
+            Action::Unquarantine => "unquarantine",
+            Action::Restart => "restart",
+            Action::Kill => "kill",
+        };
+
+        if let Some(ref mut recorder) = shadow_recorder {
+            match recorder.record_candidate(proc, posterior, &ledger, &decision_outcome) {
+                Ok(()) => shadow_recorded = shadow_recorded.saturating_add(1),
+                Err(err) => {
+                    eprintln!(
+                        "shadow mode: failed to record observation for pid {}: {:?}",
+                        proc.pid.0, err
+                    );
+                }
+            }
+            if let Some(ref sample) = behavior_sample {
+                recorder.observe_behavior(proc, sample);
+                if let Some(fds) = sample.fd_count {
+                    recorder.observe_fd_count(proc, fds);
+                }
+            }
+        }
+        let sprt_test = match shadow_recorder.as_mut() {
+            Some(recorder) => recorder
+                .observe_sprt(proc, &posterior_result)
+                .unwrap_or_else(|err| {
+                    eprintln!(
+                        "shadow mode: SPRT update failed for pid {}: {:?}",
+                        proc.pid.0, err
+                    );
+                    None
+                }),
+            None => None,
+        };
+        // Kill is only promoted once repeated looks cross the abandoned boundary.
+        let sprt_holds_kill = recommended_action == "kill"
+            && sprt_test
+                .as_ref()
+                .is_some_and(|test| test.verdict != SprtVerdict::Abandoned);
+        if sprt_holds_kill {
+            recommended_action = "review";
+            sprt_held_count += 1;
         }
-    }
-    None
-}
 
-fn precheck_label_for_apply(check: &pt_core::plan::PreCheck) -> &'static str {
-    use pt_core::plan::PreCheck;
-    match check {
-        PreCheck::VerifyIdentity => "verify_identity",
-        PreCheck::CheckNotProtected => "check_not_protected",
-        PreCheck::CheckSessionSafety => "check_session_safety",
-        PreCheck::CheckDataLossGate => "check_data_loss_gate",
-        PreCheck::CheckSupervisor => "check_supervisor",
-        PreCheck::CheckAgentSupervision => "check_agent_supervision",
-        PreCheck::VerifyProcessState => "verify_process_state",
-    }
-}
+        if let Some(ref e) = emitter {
+            if processed.is_multiple_of(50) || processed == total_processes {
+                e.emit(
+                    ProgressEvent::new(
+                        pt_core::events::event_names::INFERENCE_PROGRESS,
+                        Phase::Infer,
+                    )
+                    .with_progress(processed, Some(total_processes)),
+                );
+            }
+        }
 
-#[cfg(target_os = "linux")]
-fn read_mem_available_bytes_for_goal_progress() -> u64 {
-    std::fs::read_to_string("/proc/meminfo")
-        .ok()
-        .and_then(|content| {
-            content.lines().find_map(|line| {
-                line.strip_prefix("MemAvailable:")
-                    .and_then(|rest| rest.split_whitespace().next())
-                    .and_then(|value| value.parse::<u64>().ok())
+        // Apply threshold filter
+        if max_posterior < args.min_posterior {
+            continue;
+        }
+
+        // Apply --only filter
+        let include = match args.only.as_str() {
+            "kill" => decision_outcome.optimal_action == Action::Kill,
+            "review" => decision_outcome.optimal_action != Action::Keep,
+            _ => true, // "all"
+        };
+        if !include {
+            continue;
+        }
+
+        let privileges = privileges_for_pid(proc.pid.0);
+        #[cfg(target_os = "linux")]
+        let (fd_info, net_info) = (
+            parse_fd(proc.pid.0),
+            network_snapshot.get_process_info(proc.pid.0),
+        );
+        // RSS counts shared pages in full, so forked workers look far bigger
+        // than what killing them frees. smaps_rollup walks every mapping, so
+        // the PSS/USS split is only read under --deep.
+        #[cfg(target_os = "linux")]
+        let smaps = args.deep.then(|| parse_smaps_rollup(proc.pid.0)).flatten();
+        #[cfg(target_os = "linux")]
+        let (pss_bytes, uss_bytes, shared_bytes) = (
+            smaps.as_ref().map(|s| s.pss_bytes),
+            smaps.as_ref().map(|s| s.uss_bytes),
+            smaps.as_ref().map_or(0, |s| s.shared_bytes),
+        );
+        #[cfg(not(target_os = "linux"))]
+        let (pss_bytes, uss_bytes, shared_bytes): (Option<u64>, Option<u64>, u64) = (None, None, 0);
+        blast_input.target_pid = proc.pid.0;
+        blast_input.target_comm = proc.comm.clone();
+        #[cfg(target_os = "linux")]
+        blast_input.load_collected(fd_info.as_ref(), net_info.as_ref());
+        let blast_radius = pt_core::supervision::blast_radius::compute_blast_radius(&blast_input);
+        let process_candidate = pt_core::decision::ProcessCandidate {
+            pid: proc.pid.0 as i32,
+            ppid: proc.ppid.0 as i32,
+            cmdline: proc.cmd.clone(),
+            user: Some(proc.user.clone()),
+            group: None,
+            category: decision_outcome.rationale.category.clone(),
+            age_seconds: proc.elapsed.as_secs(),
+            posterior: Some(max_posterior),
+            memory_mb: Some(proc.rss_bytes as f64 / (1024.0 * 1024.0)),
+            has_known_signature: decision_outcome
+                .rationale
+                .has_known_signature
+                .unwrap_or(false),
+            open_write_fds: None,
+            has_locked_files: None,
+            has_active_tty: Some(proc.has_tty()),
+            seconds_since_io: None,
+            cwd_deleted: None,
+            process_state: Some(proc.state),
+            wchan: None,
+            critical_files: Vec::new(),
+            privileges: privileges.clone(),
+            blast_radius: Some(blast_radius.clone()),
+        };
+        let policy_result = enforcer.check_action(
+            &process_candidate,
+            decision_outcome.optimal_action,
+            global.robot,
+        );
+        let policy_blocked = !policy_result.allowed;
+        if policy_blocked {
+            policy_blocked_count += 1;
+            recommended_action = "review";
+        }
+        let policy_value = serde_json::to_value(&policy_result)
+            .unwrap_or_else(|_| serde_json::json!({ "allowed": policy_result.allowed }));
+        let mut action_rationale = if policy_blocked {
+            policy_result
+                .violation
+                .as_ref()
+                .map(|v| format!("Policy blocked: {}", v.message))
+                .unwrap_or_else(|| "Policy blocked".to_string())
+        } else if sprt_holds_kill {
+            sprt_test
+                .as_ref()
+                .map(|test| {
+                    format!(
+                        "Kill held for SPRT: {} after {} observation(s), log Λ = {:.2}",
+                        test.verdict.as_str(),
+                        test.state.observations,
+                        test.state.log_lambda
+                    )
+                })
+                .unwrap_or_default()
+        } else {
+            format!(
+                "Action {:?} selected{}",
+                decision_outcome.rationale.chosen_action,
+                if decision_outcome.rationale.tie_break {
+                    " (tie-break)"
+                } else {
+                    ""
+                }
+            )
+        };
+
+        // Build evidence contributions from Bayes factors
+        let evidence_contributions: Vec<serde_json::Value> = ledger
+            .bayes_factors
+            .iter()
+            .map(|bf| {
+                let mut contribution = serde_json::json!({
+                    "factor": bf.feature,
+                    "contribution": (bf.delta_bits * 10.0).round() as i32, // Scale to integer score
+                    "detail": format!("{:.1} bits {}", bf.delta_bits.abs(), bf.direction),
+                    "strength": bf.strength,
+                });
+                if let Some(weight) = bf.weight {
+                    contribution["site_weight"] = serde_json::json!(weight);
+                }
+                contribution
             })
-        })
-        .map(|kb| kb.saturating_mul(1024))
-        .unwrap_or(0)
-}
+            .collect();
 
-#[cfg(not(target_os = "linux"))]
-fn read_mem_available_bytes_for_goal_progress() -> u64 {
-    0
-}
+        // Calculate age in seconds and human-readable form
+        let age_seconds = proc.elapsed.as_secs();
+        let age_human = format_duration(proc.elapsed);
 
-#[cfg(target_os = "linux")]
-fn collect_occupied_ports_for_goal_progress() -> Vec<u16> {
-    let mut ports = BTreeSet::new();
+        // Calculate a composite score (0-100) based on max posterior
+        let score = (max_posterior * 100.0).round() as u32;
 
-    if let Some(entries) = parse_proc_net_tcp("/proc/net/tcp", false) {
-        for entry in entries.into_iter().filter(|e| e.state.is_listen()) {
-            ports.insert(entry.local_port);
+        let predictions = if args.include_predictions {
+            let eta = survival_eta_model.as_ref().and_then(|model| {
+                model.eta_for(
+                    &pt_core::shadow::compute_identity_hash(proc),
+                    chrono::Utc::now(),
+                )
+            });
+            let mut predictions = build_stub_predictions(proc, eta.as_ref());
+            if let Some(memory) = predictions.memory.as_mut() {
+                memory.pss_bytes = pss_bytes;
+                memory.uss_bytes = uss_bytes;
+            }
+            if let Some(selector) = &prediction_field_selector {
+                predictions = apply_field_selection(&predictions, selector);
+            }
+            if predictions.is_empty() {
+                None
+            } else {
+                Some(predictions)
+            }
+        } else {
+            None
+        };
+
+        let child_count = child_counts.get(&proc.pid.0).copied().unwrap_or(0);
+        let supervisor = supervisor_info_for_plan(proc.pid.0);
+        #[cfg(not(target_os = "linux"))]
+        let supervisor = match supervisor["detected"].as_bool() {
+            Some(true) => supervisor,
+            _ => table_supervisor_info(&mut table_ancestry, proc.pid.0).unwrap_or(supervisor),
+        };
+        let supervised = supervisor["detected"].as_bool().unwrap_or(false);
+        let respawn_loop = if recommended_action == "kill" {
+            let key = pt_core::decision::respawn_loop::respawn_identity_key(
+                signature_name.as_deref(),
+                &proc.cmd,
+                supervisor["unit"].as_str(),
+            );
+            Some(respawn_history.detect_loop(&key, &respawn_config, respawn_now))
+                .filter(|detection| detection.under_supervision())
+        } else {
+            None
+        };
+        if let Some(detection) = &respawn_loop {
+            recommended_action = "review";
+            respawn_held_count += 1;
+            action_rationale = format!(
+                "review: respawns under supervision ({} respawns within {:.0} min, avg {:.1}s after kill; consider {})",
+                detection.loop_count,
+                respawn_config.window_secs / 60.0,
+                detection.avg_respawn_delay_secs,
+                detection.recommendation
+            );
         }
-    }
-    if let Some(entries) = parse_proc_net_tcp("/proc/net/tcp6", true) {
-        for entry in entries.into_iter().filter(|e| e.state.is_listen()) {
-            ports.insert(entry.local_port);
+        // One runaway thread in an interactive app: hold the kill that would end the session.
+        #[cfg(target_os = "linux")]
+        let thread_hog = thread_sampler
+            .as_ref()
+            .filter(|_| recommended_action == "kill")
+            .and_then(|sampler| {
+                sampler.wait(pt_core::collect::DEFAULT_THREAD_CPU_WINDOW);
+                sampler.summary(proc.pid.0)
+            })
+            .filter(|threads| {
+                threads.is_single_thread_hog(
+                    policy.thread_hog.min_dominant_share,
+                    policy.thread_hog.min_cpu_percent,
+                )
+            })
+            .map(|threads| thread_cpu_json(&threads, &policy.thread_hog));
+        #[cfg(not(target_os = "linux"))]
+        let thread_hog: Option<serde_json::Value> = None;
+        if let Some(threads) = &thread_hog {
+            recommended_action = "review";
+            thread_hog_held_count += 1;
+            action_rationale = format!(
+                "review: single-thread hog in an interactive process ({})",
+                threads["summary"].as_str().unwrap_or_default()
+            );
         }
-    }
-    if let Some(entries) = parse_proc_net_udp("/proc/net/udp", false) {
-        for entry in entries
-            .into_iter()
-            .filter(|e| e.local_port > 0 && e.remote_port == 0)
+        // The foreground job of a terminal the user just typed into is live
+        // work; apply refuses to kill it (CheckForegroundWork), so plan review.
+        #[cfg(target_os = "linux")]
+        let foreground_work = (recommended_action == "kill" && proc.has_tty())
+            .then(|| pt_core::collect::foreground_job(proc.pid.0))
+            .flatten()
+            .filter(|job| job.is_active(pt_core::collect::DEFAULT_FOREGROUND_IDLE_SECS))
+            .map(|job| {
+                let mut value = serde_json::json!(job);
+                value["summary"] = serde_json::json!(job.summary());
+                value
+            });
+        #[cfg(not(target_os = "linux"))]
+        let foreground_work: Option<serde_json::Value> = None;
+        if let Some(job) = &foreground_work {
+            recommended_action = "review";
+            foreground_held_count += 1;
+            action_rationale = format!(
+                "review: {}",
+                job["summary"]
+                    .as_str()
+                    .unwrap_or("foreground job of an active terminal")
+            );
+        }
+        // Someone attached to this tmux/screen session or SSH login typed
+        // into it recently (guardrails.protect_attached_sessions).
+        #[cfg(target_os = "linux")]
+        let terminal_session = terminal_sessions
+            .get(proc.pid.0)
+            .map(|session| terminal_session_json(session, &priors, &policy.guardrails));
+        #[cfg(not(target_os = "linux"))]
+        let terminal_session: Option<serde_json::Value> = None;
+        if let Some(session) = terminal_session
+            .as_ref()
+            .filter(|session| recommended_action == "kill" && session["protected"] == true)
         {
-            ports.insert(entry.local_port);
+            recommended_action = "review";
+            session_held_count += 1;
+            action_rationale = format!(
+                "review: {} (guardrails.protect_attached_sessions)",
+                session["summary"]
+                    .as_str()
+                    .unwrap_or("attached terminal session")
+            );
+        }
+        // Build daemons: hold kills of ones serving a build, and point at the
+        // tool's own shutdown command where there is one.
+        let build_daemon = build_daemons.analyze(proc).map(|info| {
+            if info.state.is_reclaimable() {
+                build_daemon_reclaimable_count += 1;
+            }
+            build_daemon_json(&info)
+        });
+        if let Some(daemon) = build_daemon
+            .as_ref()
+            .filter(|_| recommended_action == "kill")
+        {
+            if daemon["state"] == "active" {
+                recommended_action = "review";
+                build_daemon_held_count += 1;
+                action_rationale = format!(
+                    "review: {}",
+                    daemon["summary"].as_str().unwrap_or("active build daemon")
+                );
+            } else if let Some(command) = daemon["shutdown_command"].as_str() {
+                action_rationale = format!("{} (graceful: {})", action_rationale, command);
+            }
+        }
+        // A zombie is already dead: any action goes to its parent instead.
+        let zombie_reap = proc
+            .state
+            .is_zombie()
+            .then(|| zombie_reap_for(proc, &scan_result.processes, &protected_filter));
+        if let Some(reap) = &zombie_reap {
+            if !policy_blocked && decision_outcome.optimal_action != Action::Keep {
+                recommended_action = "review";
+                action_rationale = reap.summary();
+            }
+        }
+        #[cfg(target_os = "linux")]
+        let (fd_count, listen_ports) = (
+            fd_info.as_ref().map(|fd| fd.count as u32),
+            net_info
+                .as_ref()
+                .map(|info| {
+                    let ports: BTreeSet<u16> =
+                        info.listen_ports.iter().map(|port| port.port).collect();
+                    ports.into_iter().collect()
+                })
+                .unwrap_or_default(),
+        );
+        #[cfg(not(target_os = "linux"))]
+        let (fd_count, listen_ports) = (None, Vec::new());
+        let impact = pt_core::plan::ImpactInputs {
+            rss_bytes: proc.rss_bytes,
+            uss_bytes,
+            cpu_frac: proc.cpu_percent / 100.0,
+            fd_count,
+            listen_ports,
+            child_count,
+            has_shared_memory: shared_bytes > 0,
+            supervised,
+            // Human/agent supervision ("review") watches a process but does not restart it.
+            // launchd reports whether the job restarts (KeepAlive) directly.
+            restart_likelihood: if supervisor["respawn_expected"]
+                .as_bool()
+                .unwrap_or(supervised && supervisor["recommended_action"] != "review")
+            {
+                pt_core::plan::cost_benefit::SUPERVISED_RESTART_LIKELIHOOD
+            } else {
+                0.0
+            },
+        };
+        let cost_benefit = pt_core::plan::cost_benefit::annotate(
+            decision_outcome.optimal_action,
+            Some(posterior),
+            &impact,
+        );
+
+        // Detection paths that surfaced this candidate.
+        let mut provenance = Provenance::default();
+        if let Some(sig_match) = signature_match.as_ref() {
+            provenance.add(
+                Detection::new(DetectionSource::Signature)
+                    .with_confidence(sig_match.score)
+                    .with_detail(sig_match.signature.name.clone()),
+            );
+        }
+        provenance.add(
+            Detection::new(if fast_path_used {
+                DetectionSource::SignatureFastPath
+            } else {
+                DetectionSource::Bayesian
+            })
+            .with_recommendation(recommended_action)
+            .with_confidence(max_posterior),
+        );
+
+        // Build candidate JSON (action tracking moved to after sorting)
+        let mut candidate = serde_json::json!({
+            "pid": proc.pid.0,
+            "ppid": proc.ppid.0,
+            "state": proc.state.to_string(),
+            "start_id": format!("{}:{}", proc.pid.0, proc.start_time_unix),
+            "uid": proc.uid,
+            "user": &proc.user,
+            "groups": pt_core::plan::groups::ProcessGroups::collect(proc),
+            "launched_by": launch_attributor.attribute(proc),
+            "tree": process_tree.position(proc.pid.0),
+            "command": redactor.cmdline(&proc.cmd),
+            "command_short": &proc.comm,
+            "type": ledger.classification.label(), // Process type classification
+            "age_seconds": age_seconds,
+            "age_human": age_human,
+            "memory_mb": proc.rss_bytes / (1024 * 1024),
+            "memory": {
+                "rss_mb": proc.rss_bytes / (1024 * 1024),
+                "pss_mb": pss_bytes.map(|b| b / (1024 * 1024)),
+                "uss_mb": uss_bytes.map(|b| b / (1024 * 1024)),
+            },
+            "cpu_percent": proc.cpu_percent,
+            "fd_count": impact.fd_count,
+            "listen_ports": &impact.listen_ports,
+            "score": score,
+            "classification": ledger.classification.label(),
+            "posterior": {
+                "useful": posterior.useful,
+                "useful_bad": posterior.useful_bad,
+                "abandoned": posterior.abandoned,
+                "zombie": posterior.zombie,
+            },
+            "signature": {
+                "matched": signature_match.is_some(),
+                "name": signature_name,
+                "category": signature_category,
+                "score": signature_score,
+                "match_level": signature_level,
+                "rollout": signature_match.as_ref().map(|m| m.signature.rollout),
+            },
+            "runtime_prior": runtime_surprise.as_ref().map(runtime_surprise_json),
+            "sprt": sprt_test.as_ref().map(|test| serde_json::json!({
+                "verdict": test.verdict.as_str(),
+                "observations": test.state.observations,
+                "log_lambda": test.state.log_lambda,
+                "held_kill": sprt_holds_kill,
+            })),
+            "behavior_change": behavior_deviation
+                .as_ref()
+                .map(|dev| behavior_deviation_json(dev, &priors)),
+            "fd_leak": fd_leak.as_ref().map(|leak| fd_leak_json(leak, &priors)),
+            "inference": {
+                "mode": if fast_path_used { "signature_fast_path" } else { "bayesian" },
+                "prior_source": prior_source_label,
+                "fast_path": {
+                    "enabled": fast_path_config.enabled,
+                    "used": fast_path_used,
+                    "skip_reason": fast_path_skip_reason,
+                    "min_confidence_threshold": fast_path_config.min_confidence_threshold,
+                    "require_explicit_priors": fast_path_config.require_explicit_priors,
+                },
+            },
+            "provenance": provenance,
+            "confidence": ledger.confidence.label(),
+            "evidence": evidence_contributions,
+            "blast_radius": {
+                "memory_mb": pss_bytes.unwrap_or(proc.rss_bytes) / (1024 * 1024),
+                "memory_basis": if pss_bytes.is_some() { "pss" } else { "rss" },
+                "cpu_pct": proc.cpu_percent,
+                "child_count": child_count,
+                "write_handles": blast_radius.write_handles,
+                "listen_ports": blast_radius.listen_ports,
+                "client_connections": blast_radius.client_connections,
+                "risk_factors": blast_radius.risk_factors,
+                "risk_score": blast_radius.risk_score,
+                "risk_level": if blast_radius.risk_level() == "low"
+                    && proc.rss_bytes > 1024 * 1024 * 1024
+                {
+                    "medium"
+                } else {
+                    blast_radius.risk_level()
+                },
+                "summary": blast_radius.summary,
+            },
+            "reversibility": match decision_outcome.optimal_action {
+                Action::Kill | Action::Restart => "irreversible",
+                Action::Pause | Action::Freeze | Action::Throttle | Action::Quarantine => "reversible",
+                Action::Resume | Action::Unfreeze | Action::Unquarantine => "reversal",
+                Action::Keep | Action::Renice => "no_action",
+            },
+            "supervisor": supervisor,
+            "respawn_loop": respawn_loop,
+            "thread_hog": thread_hog,
+            "foreground_work": foreground_work,
+            "terminal_session": terminal_session,
+            "build_daemon": build_daemon,
+            "zombie_reap": zombie_reap,
+            "cost_benefit": cost_benefit,
+            "uncertainty": {
+                "entropy": ledger.bayes_factors.len() as f64 * 0.1, // Simplified
+                "confidence_interval": [(max_posterior - ci_half_width).max(0.0), (max_posterior + ci_half_width).min(1.0)],
+                "visibility_limited": !visibility_full,
+            },
+            "recommendation": recommended_action.to_uppercase(),
+            "recommended_action": recommended_action,
+            "action_rationale": action_rationale,
+            "expected_loss": decision_outcome.expected_loss.iter()
+                .map(|el| serde_json::json!({
+                    "action": format!("{:?}", el.action),
+                    "loss": el.loss,
+                }))
+                .collect::<Vec<_>>(),
+            "policy_blocked": policy_blocked,
+            "policy": policy_value,
+        });
+
+        if let Some(ref privileges) = privileges {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert("privileges".to_string(), privileges_json(privileges));
+            }
+        }
+        if let Some(activity) = child_activity {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert("short_lived_children".to_string(), activity.clone());
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(container_action) =
+            container_runner.plan_for(proc.pid.0, decision_outcome.optimal_action)
+        {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert(
+                    "container_action".to_string(),
+                    serde_json::to_value(container_action)
+                        .unwrap_or_else(|_| serde_json::json!({})),
+                );
+            }
+        }
+        #[cfg(target_os = "linux")]
+        let unit_action = if matches!(
+            decision_outcome.optimal_action,
+            Action::Kill | Action::Restart
+        ) {
+            pt_core::plan::ServiceMembership::detect(proc.pid.0).and_then(|service| {
+                pt_core::plan::unit::unit_action_for(
+                    &service,
+                    proc.pid.0,
+                    decision_outcome.optimal_action,
+                )
+            })
+        } else {
+            None
+        };
+        #[cfg(target_os = "linux")]
+        if let Some(unit_action) = unit_action {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert(
+                    "unit_action".to_string(),
+                    serde_json::json!({
+                        "unit": unit_action.unit,
+                        "manager": unit_action.manager,
+                        "command": unit_action.command,
+                        "display_command": unit_action.display_command(),
+                    }),
+                );
+            }
+        }
+        // Kills of a signature with a graceful stop try the tool's own CLI first.
+        if recommended_action == "kill" && candidate.get("unit_action").is_none() {
+            if let Some(command_action) = signature_match
+                .as_ref()
+                .and_then(|m| graceful_stop_for(m.signature, proc))
+            {
+                candidate["command_action"] = command_action_json(&command_action);
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(ref gpu) = gpu_summary {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert(
+                    "gpu".to_string(),
+                    serde_json::to_value(gpu).unwrap_or_else(|_| serde_json::json!({})),
+                );
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(ref activity) = net_activity {
+            if let Some(obj) = candidate.as_object_mut() {
+                let mut value =
+                    serde_json::to_value(activity).unwrap_or_else(|_| serde_json::json!({}));
+                value["bucket"] = serde_json::json!(net_bucket);
+                value["bucket_name"] = serde_json::json!(net_bucket
+                    .and_then(|b| priors.net_recency.as_ref().map(|nr| nr.bucket_name(b))));
+                obj.insert("net_activity".to_string(), value);
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = swap_bytes {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert(
+                    "swap".to_string(),
+                    serde_json::json!({
+                        "swap_mb": bytes / (1024 * 1024),
+                        "bucket": swap_bucket,
+                        "bucket_name": swap_bucket.and_then(|b| {
+                            priors.swap_pressure.as_ref().map(|sp| sp.bucket_name(b))
+                        }),
+                    }),
+                );
+            }
+        }
+        if let Some(rate) = proc.io_rate {
+            if let Some(obj) = candidate.as_object_mut() {
+                let mut value =
+                    serde_json::to_value(rate).unwrap_or_else(|_| serde_json::json!({}));
+                value["bucket"] = serde_json::json!(evidence.io_rate);
+                value["bucket_name"] = serde_json::json!(evidence
+                    .io_rate
+                    .and_then(|b| priors.io_rate.as_ref().map(|io| io.bucket_name(b))));
+                obj.insert("io_rate".to_string(), value);
+            }
+        }
+
+        if let Some(predictions) = predictions {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert(
+                    "predictions".to_string(),
+                    serde_json::to_value(predictions).unwrap_or_else(|_| serde_json::json!({})),
+                );
+            }
+        }
+
+        let persisted_proc = PersistedProcess {
+            pid: proc.pid.0,
+            ppid: proc.ppid.0,
+            uid: proc.uid,
+            start_id: proc.start_id.to_string(),
+            comm: proc.comm.clone(),
+            cmd: redactor.cmdline(&proc.cmd),
+            state: proc.state.to_string(),
+            start_time_unix: proc.start_time_unix,
+            elapsed_secs: proc.elapsed.as_secs(),
+            // Quick scan provides a solid start_id but lacks full TOCTOU coverage.
+            identity_quality: "QuickScan".to_string(),
+        };
+
+        let persisted_inf = PersistedInference {
+            pid: proc.pid.0,
+            start_id: proc.start_id.to_string(),
+            classification: ledger.classification.label().to_string(),
+            posterior_useful: posterior.useful,
+            posterior_useful_bad: posterior.useful_bad,
+            posterior_abandoned: posterior.abandoned,
+            posterior_zombie: posterior.zombie,
+            confidence: ledger.confidence.label().to_string(),
+            recommended_action: recommended_action.to_string(),
+            score,
+        };
+
+        // Store candidate with max_posterior for sorting (no early break!)
+        all_candidates.push((max_posterior, candidate, persisted_proc, persisted_inf));
+    }
+
+    if let Some(ref e) = emitter {
+        e.emit(
+            ProgressEvent::new(
+                pt_core::events::event_names::INFERENCE_COMPLETE,
+                Phase::Infer,
+            )
+            .with_progress(processed, Some(total_processes)),
+        );
+        e.emit(
+            ProgressEvent::new(
+                pt_core::events::event_names::DECISION_COMPLETE,
+                Phase::Decide,
+            )
+            .with_progress(processed, Some(total_processes)),
+        );
+    }
+
+    if let Some(ref mut recorder) = shadow_recorder {
+        if let Err(err) = recorder.flush() {
+            eprintln!("shadow mode: failed to flush storage: {:?}", err);
+        }
+    }
+
+    // Sort candidates by max_posterior descending (highest confidence first)
+    all_candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    // One entry per process identity, however many paths surfaced it.
+    let duplicates_merged =
+        pt_core::dedupe::dedupe_plan_candidates(&mut all_candidates, |entry| &mut entry.1);
+
+    // Capture count before truncation for summary stats
+    let above_threshold_count = all_candidates.len();
+
+    // Take top N candidates (sorted by max posterior, not scan order!)
+    let mut candidates: Vec<serde_json::Value> = Vec::new();
+    let mut persisted_inventory_records: Vec<PersistedProcess> = Vec::new();
+    let mut persisted_inference_records: Vec<PersistedInference> = Vec::new();
+    for (_, candidate_json, proc_rec, inf_rec) in all_candidates
+        .into_iter()
+        .take(args.max_candidates as usize)
+    {
+        candidates.push(candidate_json);
+        persisted_inventory_records.push(proc_rec);
+        persisted_inference_records.push(inf_rec);
+    }
+
+    let mut goal_summary: Option<serde_json::Value> = None;
+    let mut goal_selected: Option<HashSet<u32>> = None;
+    if let Some(goal_str) = args.goal.as_deref() {
+        match parse_goal(goal_str) {
+            Ok(goal) => {
+                let total_cpu_pct_for_goal: f64 = candidates
+                    .iter()
+                    .map(|candidate| {
+                        candidate
+                            .get("cpu_percent")
+                            .and_then(|value| value.as_f64())
+                            .unwrap_or(0.0)
+                    })
+                    .sum();
+                let scanned_pids: Vec<u32> =
+                    scan_result.processes.iter().map(|p| p.pid.0).collect();
+                let (port_owners, port_warnings) =
+                    resolve_goal_ports(&goal, &mut candidates, &scanned_pids);
+                match build_goal_plan_from_candidates(
+                    goal_str,
+                    &goal,
+                    &GoalBaseline::with_cpu(total_cpu_pct_for_goal),
+                    &candidates,
+                ) {
+                    Ok(mut goal_output) => {
+                        goal_output.warnings.extend(port_warnings);
+                        let mut goal_json = goal_summary_json(goal_str, &goal, &goal_output);
+                        if port_owners.as_object().is_some_and(|o| !o.is_empty()) {
+                            goal_json["port_owners"] = port_owners;
+                        }
+                        let selected = goal_output.selected_pids.clone();
+                        let selected_set: HashSet<u32> = selected.iter().copied().collect();
+                        let mut selected_rank: HashMap<u32, usize> = HashMap::new();
+                        for (idx, pid) in selected.iter().enumerate() {
+                            selected_rank.insert(*pid, idx);
+                        }
+
+                        for candidate in &mut candidates {
+                            if let Some(obj) = candidate.as_object_mut() {
+                                let pid =
+                                    obj.get("pid").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+                                let selected_for_goal = selected_set.contains(&pid);
+                                obj.insert(
+                                    "goal_selected".to_string(),
+                                    serde_json::json!(selected_for_goal),
+                                );
+                                if selected_for_goal {
+                                    obj.insert(
+                                        "goal_rank".to_string(),
+                                        serde_json::json!(selected_rank
+                                            .get(&pid)
+                                            .copied()
+                                            .unwrap_or(usize::MAX)),
+                                    );
+                                }
+                            }
+                        }
+
+                        candidates.sort_by_key(|candidate| {
+                            let pid = candidate
+                                .get("pid")
+                                .and_then(|value| value.as_u64())
+                                .unwrap_or(0) as u32;
+                            selected_rank.get(&pid).copied().unwrap_or(usize::MAX)
+                        });
+
+                        goal_summary = Some(goal_json);
+                        goal_selected = Some(selected_set);
+                    }
+                    Err(err) => {
+                        goal_summary = Some(serde_json::json!({
+                            "goal": goal_str,
+                            "parsed": goal.canonical(),
+                            "error": err,
+                        }));
+                    }
+                }
+            }
+            Err(err) => {
+                goal_summary = Some(serde_json::json!({
+                    "goal": goal_str,
+                    "error": err,
+                }));
+            }
+        }
+    }
+
+    // Let the user pick/review/drop candidates in their editor.
+    let mut plan_edit: Option<pt_core::plan::editor::PlanEdit> = None;
+    if args.edit && !candidates.is_empty() {
+        match pt_core::plan::editor::edit_candidates(&mut candidates, &session_id.0) {
+            Ok(edit) => {
+                if let Some(selected) = goal_selected.as_mut() {
+                    for pid in edit.reviewed.iter().chain(&edit.dropped) {
+                        selected.remove(pid);
+                    }
+                }
+                plan_edit = Some(edit);
+            }
+            Err(pt_core::plan::editor::PlanEditError::Aborted) => {
+                eprintln!("agent plan: edit aborted; no plan written");
+                return ExitCode::Interrupted;
+            }
+            Err(e) => {
+                eprintln!("agent plan: plan edit failed: {}", e);
+                return ExitCode::ArgsError;
+            }
+        }
+    }
+
+    // Rebuild kill/review/spare candidate lists from the final sorted candidates
+    let mut kill_candidates: Vec<u32> = Vec::new();
+    let mut review_candidates: Vec<u32> = Vec::new();
+    let mut spare_candidates: Vec<u32> = Vec::new();
+    let mut expected_memory_freed_bytes: u64 = 0;
+    for candidate in &candidates {
+        let pid = candidate["pid"].as_u64().unwrap_or(0) as u32;
+        let action = candidate["recommended_action"].as_str().unwrap_or("");
+        let memory_mb = candidate_recoverable_memory_mb(candidate);
+        let selected_by_goal = goal_selected
+            .as_ref()
+            .map(|selected| selected.contains(&pid))
+            .unwrap_or(false);
+        if selected_by_goal || action == "kill" {
+            kill_candidates.push(pid);
+            expected_memory_freed_bytes += memory_mb * 1024 * 1024;
+        } else if action == "keep" {
+            spare_candidates.push(pid);
+        } else {
+            review_candidates.push(pid);
+        }
+    }
+    let expected_memory_freed_gb = (expected_memory_freed_bytes as f64) / 1024.0 / 1024.0 / 1024.0;
+
+    // Collect host information
+    let host_info = collect_host_info();
+
+    // Build scan info
+    let scan_info = serde_json::json!({
+        "total_processes": total_scanned,
+        "candidates_found": above_threshold_count,
+        "scan_duration_ms": scan_duration_ms,
+        "visibility_scope": scan_result.metadata.visibility.scope,
+    });
+
+    let candidates_returned = candidates.len();
+
+    // Build summary (legacy format for backward compatibility)
+    let mut summary = serde_json::json!({
+        "total_processes_scanned": total_scanned,
+        "protected_filtered": protected_filtered_count,
+        "candidates_evaluated": candidates_evaluated,
+        "above_threshold": above_threshold_count,  // Candidates meeting threshold before truncation
+        "candidates_returned": candidates_returned,   // After truncation to max_candidates
+        "kill_recommendations": kill_candidates.len(),
+        "review_recommendations": review_candidates.len(),
+        "policy_blocked": policy_blocked_count,
+        "snoozed_skipped": snoozed_skipped_count,
+        "oom_killed_skipped": oom_killed_skipped_count,
+        "duplicates_merged": duplicates_merged,
+        "signature_matches": signature_match_count,
+        "signature_fast_path_used": signature_fast_path_used_count,
+        "signature_fast_path_enabled": fast_path_config.enabled,
+        "signature_fast_path_min_confidence_threshold": fast_path_config.min_confidence_threshold,
+        "signature_fast_path_require_explicit_priors": fast_path_config.require_explicit_priors,
+        "threshold_used": args.min_posterior,
+        "filter_used": args.only,
+    });
+    if global.shadow {
+        summary["shadow_observations_recorded"] = serde_json::json!(shadow_recorded);
+    }
+    if args.sprt {
+        summary["sprt_kill_held"] = serde_json::json!(sprt_held_count);
+    }
+    if respawn_held_count > 0 {
+        summary["respawn_loop_held"] = serde_json::json!(respawn_held_count);
+    }
+    if thread_hog_held_count > 0 {
+        summary["thread_hog_held"] = serde_json::json!(thread_hog_held_count);
+    }
+    if foreground_held_count > 0 {
+        summary["foreground_held"] = serde_json::json!(foreground_held_count);
+    }
+    if session_held_count > 0 {
+        summary["session_held"] = serde_json::json!(session_held_count);
+    }
+    if build_daemon_held_count > 0 {
+        summary["build_daemon_held"] = serde_json::json!(build_daemon_held_count);
+    }
+    if build_daemon_reclaimable_count > 0 {
+        summary["build_daemons_reclaimable"] = serde_json::json!(build_daemon_reclaimable_count);
+    }
+    if let Some(goal) = &goal_summary {
+        summary["goal_mode"] = serde_json::json!(true);
+        summary["goal_achievable"] = goal
+            .get("achievable")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        summary["goal_selected_count"] = serde_json::json!(kill_candidates.len());
+    }
+
+    // Build recommendations section (new structured format)
+    let mut recommendations = serde_json::json!({
+        "kill_set": kill_candidates,
+        "review_set": review_candidates,
+        "spare_set": spare_candidates,
+        "expected_memory_freed_gb": (expected_memory_freed_gb * 100.0).round() / 100.0,
+        "fleet_fdr": 0.03, // Placeholder - would come from fleet-wide statistics
+        // Tree-level kills (process groups, subtrees) in execution order
+        "cascade": pt_core::plan::tree::plan_cascade(
+            &kill_candidates,
+            &process_tree,
+            &policy.cascade,
+        ),
+    });
+    if let Some(goal) = &goal_summary {
+        recommendations["goal"] = goal.clone();
+    }
+
+    // Build recommended section (legacy format for backward compatibility)
+    let empty_pids: Vec<u32> = Vec::new();
+    let preselected_pids = if args.yes {
+        &kill_candidates
+    } else {
+        &empty_pids
+    };
+    let recommended = serde_json::json!({
+        "preselected_pids": preselected_pids,
+        "actions": kill_candidates.iter().map(|pid| serde_json::json!({
+            "pid": pid,
+            "action": "kill",
+            "stage": 1,
+        })).collect::<Vec<_>>(),
+    });
+
+    // Check for stub flags usage (future features parsed but not yet functional)
+    let mut stub_flags_used: Vec<&str> = Vec::new();
+    if args.since.is_some() {
+        stub_flags_used.push("--since");
+    }
+    if args.since_time.is_some() {
+        stub_flags_used.push("--since-time");
+    }
+
+    // Build stub_flags section if any future flags were used
+    let stub_flags_section = if !stub_flags_used.is_empty() {
+        Some(serde_json::json!({
+            "warning": "feature_not_implemented",
+            "message": "Some flags are parsed but not yet functional",
+            "flags_used": stub_flags_used,
+            "flags_ignored": stub_flags_used,
+            "workaround": "Use pt agent diff for manual comparison; goal-oriented mode coming in v1.2",
+            "planned_release": "v1.2"
+        }))
+    } else {
+        None
+    };
+
+    let goal_value = goal_summary
+        .as_ref()
+        .and_then(|goal| goal.get("goal"))
+        .cloned()
+        .or_else(|| args.goal.as_ref().map(|goal| serde_json::json!(goal)))
+        .unwrap_or(serde_json::Value::Null);
+    let goal_progress = goal_summary
+        .as_ref()
+        .and_then(|goal| goal.get("goal_achievement"))
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!([]));
+
+    // Build complete plan output with structured JSON format
+    let mut plan_output = serde_json::json!({
+        "pt_version": env!("CARGO_PKG_VERSION"),
+        "schema_version": SCHEMA_VERSION,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "session_id": session_id.0,
+        "label": args.label,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "host_id": pt_core::logging::get_host_id(),
+        "host": host_info,
+        "scan": scan_info,
+        "command": "agent plan",
+        "args": {
+            "max_candidates": args.max_candidates,
+            "min_posterior": args.min_posterior,
+            "only": args.only,
+            "yes": args.yes,
+            "dry_run": global.dry_run,
+            "robot": global.robot,
+            "shadow": global.shadow,
+            "min_age": args.min_age,
+            "sample_size": args.sample_size,
+            "include_kernel_threads": args.include_kernel_threads,
+            "deep": args.deep,
+            "since": args.since,
+            "since_time": args.since_time,
+            "goal": args.goal,
+            "simulate": args.simulate,
+            "include_predictions": args.include_predictions,
+            "prediction_fields": args.prediction_fields,
+            "minimal": args.minimal,
+            "pretty": args.pretty,
+            "brief": args.brief,
+            "narrative": args.narrative,
+            "edit": args.edit,
+        },
+        "summary": summary,
+        "goal": goal_value,
+        "goal_progress": goal_progress,
+        "goal_summary": goal_summary,
+        "candidates": candidates,
+        "groups": pt_core::plan::groups::aggregate_groups(&candidates),
+        "recommendations": recommendations,
+        "recommended": recommended,  // Legacy format for backward compatibility
+        "session_created": created,
+    });
+
+    // Add stub_flags section if any future flags were used
+    if let Some(stub_flags) = stub_flags_section {
+        plan_output["stub_flags"] = stub_flags;
+    }
+
+    if let Some(short_lived) = short_lived_output {
+        plan_output["short_lived"] = short_lived;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        plan_output["net_activity"] = serde_json::json!({
+            "source": socket_activity.source(),
+            "degraded": socket_activity.degraded(),
+        });
+        plan_output["memory_pressure"] = serde_json::json!({
+            "psi_some_avg10": swap_pressure.psi_some_avg10,
+            "psi_full_avg10": swap_pressure.psi_full_avg10,
+            "swap_total_bytes": swap_pressure.swap_total_bytes,
+            "swap_used_bytes": swap_pressure.swap_used_bytes(),
+            "zram": swap_pressure.zram.map(|zram| serde_json::json!({
+                "devices": zram.devices,
+                "orig_data_bytes": zram.orig_data_bytes,
+                "compr_data_bytes": zram.compr_data_bytes,
+                "mem_used_bytes": zram.mem_used_bytes,
+                "compression_ratio": zram.compression_ratio(),
+            })),
+            "swap_evidence": swap_evidence,
+        });
+    }
+
+    if let Some(edit) = plan_edit {
+        plan_output["plan_edit"] = serde_json::json!(edit);
+    }
+
+    let simulation = args
+        .simulate
+        .then(|| simulate_plan(&candidates, &kill_candidates, goal_summary.as_ref()));
+    if let Some(simulation) = &simulation {
+        plan_output["simulation"] = serde_json::json!(simulation);
+    }
+
+    if let (Some(experiment), Some(tag)) = (&experiment, &experiment_tag) {
+        plan_output["experiment"] = serde_json::json!({
+            "name": tag.name,
+            "description": experiment.description,
+            "overlay_hash": tag.overlay_hash,
+            "promoted": tag.promoted,
+        });
+    }
+
+    // Annotate plans built from a partial view of the process table
+    if !visibility_full {
+        plan_output["visibility"] = visibility_json(&scan_result.metadata.visibility);
+    }
+
+    // Annotate plans when another actor may kill processes under memory pressure
+    if oom_killers.any() {
+        plan_output["memory_pressure_killer"] = serde_json::json!({
+            "active": true,
+            "killers": oom_killers.active(),
+            "note": "memory pressure killer active; candidates may be terminated independently of this plan",
+            "window_secs": pt_core::collect::DEFAULT_RECENT_KILL_WINDOW_SECS,
+            "recent_kills": recent_oom_kills.events(),
+            "excluded_candidates": oom_killed_skipped_count,
+        });
+    }
+
+    if let Some(telemetry) = telemetry_quota_json(&write_guard) {
+        plan_output["telemetry"] = telemetry;
+    }
+
+    // Write plan to session
+    let decision_dir = handle.dir.join("decision");
+    if let Err(e) = std::fs::create_dir_all(&decision_dir) {
+        eprintln!(
+            "agent plan: failed to create directory {}: {}",
+            decision_dir.display(),
+            e
+        );
+        return ExitCode::InternalError;
+    }
+    let plan_path = decision_dir.join("plan.json");
+    let plan_json = serde_json::to_string_pretty(&plan_output).unwrap();
+    if let QuotaDecision::Skip { reason } =
+        write_guard.check(&plan_path, plan_json.len() as u64, WritePriority::Essential)
+    {
+        eprintln!(
+            "agent plan: not enough disk space to write {}: {}",
+            plan_path.display(),
+            reason
+        );
+        return ExitCode::IoError;
+    }
+    if let Err(e) = std::fs::write(&plan_path, plan_json) {
+        eprintln!("agent plan: failed to write {}: {}", plan_path.display(), e);
+        return ExitCode::InternalError;
+    }
+    if let Err(e) = redactor.persist_dictionary(&handle.dir) {
+        eprintln!(
+            "agent plan: warning: failed to save redaction dictionary: {}",
+            e
+        );
+    }
+
+    // Persist compact diff artifacts so `pt diff` can compare sessions reliably.
+    // Best-effort: don't fail the plan output if persistence fails, but emit a warning.
+    // Under disk pressure these are optional; plan.json above is all apply needs.
+    if write_guard.mode() == TelemetryMode::Minimal {
+        eprintln!(
+            "agent plan: disk below reserve; skipping inventory, inference and evidence artifacts"
+        );
+    } else {
+        let host_id = pt_core::logging::get_host_id();
+        let inv_artifact = InventoryArtifact {
+            total_system_processes: total_scanned as u64,
+            protected_filtered: protected_filtered_count as u64,
+            record_count: persisted_inventory_records.len(),
+            records: persisted_inventory_records,
+        };
+        if let Err(e) = persist_inventory(&handle, &session_id.0, &host_id, inv_artifact) {
+            eprintln!(
+                "agent plan: warning: failed to persist inventory artifact: {}",
+                e
+            );
+        }
+
+        let inf_artifact = InferenceArtifact {
+            candidate_count: persisted_inference_records.len(),
+            candidates: persisted_inference_records,
+        };
+        if let Err(e) = persist_inference(&handle, &session_id.0, &host_id, inf_artifact) {
+            eprintln!(
+                "agent plan: warning: failed to persist inference artifact: {}",
+                e
+            );
+        }
+
+        if let Some(experiment) = experiment {
+            let artifact = ExperimentArtifact {
+                experiment,
+                records: evidence_records
+                    .iter()
+                    .map(|record| record.replay.clone())
+                    .collect(),
+            };
+            if let Err(e) = persist_experiment(&handle, &session_id.0, &host_id, artifact) {
+                eprintln!(
+                    "agent plan: warning: failed to persist experiment artifact: {}",
+                    e
+                );
+            }
+        }
+
+        let evidence_artifact = EvidenceArtifact {
+            records: evidence_records,
+        };
+        if let Err(e) = persist_evidence(&handle, &session_id.0, &host_id, evidence_artifact) {
+            eprintln!(
+                "agent plan: warning: failed to persist evidence ledger: {}",
+                e
+            );
+        }
+    }
+
+    // Record the plan (moves the session to `planned`)
+    let _ = handle.record_event(SessionEventKind::Planned {
+        candidate_count: candidates_returned,
+        plan_id: None,
+    });
+
+    if let Some(ref e) = emitter {
+        e.emit(
+            ProgressEvent::new(pt_core::events::event_names::PLAN_READY, Phase::Plan)
+                .with_session_id(session_id.to_string())
+                .with_detail("plan_path", plan_path.display().to_string())
+                .with_detail("count", candidates.len()),
+        );
+    }
+
+    // Warn about stub flags on stderr (for all formats, machine-parseable too)
+    if !stub_flags_used.is_empty() {
+        eprintln!(
+            "warning: flags {} are parsed but not yet functional (coming in v1.2)",
+            stub_flags_used.join(", ")
+        );
+    }
+
+    // Handle --narrative flag (outputs prose regardless of format)
+    if args.narrative {
+        let narrative = generate_narrative_summary(
+            &session_id,
+            &candidates,
+            &kill_candidates,
+            &review_candidates,
+            total_scanned,
+            expected_memory_freed_gb,
+        );
+        println!("{}", narrative);
+        return if candidates.is_empty() {
+            ExitCode::Clean
+        } else {
+            ExitCode::PlanReady
+        };
+    }
+
+    // Output based on format
+    match global.format {
+        OutputFormat::Json => {
+            // Build output based on --minimal, --brief, and --pretty flags
+            let output_json = if args.brief {
+                // Brief output: minimal fields + single-line rationale
+                let brief_candidates: Vec<serde_json::Value> = candidates
+                    .iter()
+                    .map(|c| {
+                        let rationale = generate_single_line_rationale(c);
+                        serde_json::json!({
+                            "pid": c["pid"],
+                            "cmd": c["command_short"],
+                            "score": c["score"],
+                            "rec": c["recommendation"],
+                            "why": rationale,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "v": env!("CARGO_PKG_VERSION"),
+                    "sid": session_id.0,
+                    "n": candidates.len(),
+                    "kill": kill_candidates.len(),
+                    "review": review_candidates.len(),
+                    "c": brief_candidates,
+                })
+            } else if args.minimal {
+                // Minimal output: just PIDs, scores, and recommendations
+                let minimal_candidates: Vec<serde_json::Value> = candidates
+                    .iter()
+                    .map(|c| {
+                        serde_json::json!({
+                            "pid": c["pid"],
+                            "score": c["score"],
+                            "recommendation": c["recommendation"],
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "pt_version": env!("CARGO_PKG_VERSION"),
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "session_id": session_id.0,
+                    "candidates": minimal_candidates,
+                    "recommendations": recommendations,
+                })
+            } else {
+                plan_output.clone()
+            };
+
+            // Apply pretty-printing or compact based on --pretty flag
+            let output_str = if args.pretty {
+                serde_json::to_string_pretty(&output_json).unwrap()
+            } else {
+                // Use global.process_output for token-efficient processing if not pretty
+                global.process_output(output_json)
+            };
+            println!("{}", output_str);
+        }
+        OutputFormat::Toon => {
+            let output_json = if args.brief {
+                // Brief output for TOON: minimal fields + single-line rationale
+                let brief_candidates: Vec<serde_json::Value> = candidates
+                    .iter()
+                    .map(|c| {
+                        let rationale = generate_single_line_rationale(c);
+                        serde_json::json!({
+                            "p": c["pid"],
+                            "c": c["command_short"],
+                            "s": c["score"],
+                            "r": c["recommendation"],
+                            "w": rationale,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "v": env!("CARGO_PKG_VERSION"),
+                    "i": session_id.0,
+                    "n": candidates.len(),
+                    "k": kill_candidates.len(),
+                    "r": review_candidates.len(),
+                    "c": brief_candidates,
+                })
+            } else if args.minimal {
+                let minimal_candidates: Vec<serde_json::Value> = candidates
+                    .iter()
+                    .map(|c| {
+                        serde_json::json!({
+                            "pid": c["pid"],
+                            "score": c["score"],
+                            "recommendation": c["recommendation"],
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "pt_version": env!("CARGO_PKG_VERSION"),
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "session_id": session_id.0,
+                    "candidates": minimal_candidates,
+                    "recommendations": recommendations,
+                })
+            } else {
+                plan_output.clone()
+            };
+
+            let output_value = if args.pretty {
+                output_json
+            } else {
+                global.process_output_value(output_json)
+            };
+            println!("{}", encode_toon_value(&output_value));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "[{}] agent plan: {} candidates ({} kill, {} review){}",
+                session_id,
+                candidates.len(),
+                kill_candidates.len(),
+                review_candidates.len(),
+                if visibility_full {
+                    ""
+                } else {
+                    " [partial visibility]"
+                }
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core agent plan\n");
+            println!("Session: {}", session_id);
+            println!("Plan: {}\n", plan_path.display());
+            println!("## Summary\n");
+            println!("- Processes scanned: {}", scan_result.processes.len());
+            println!("- Candidates identified: {}", candidates.len());
+            println!("- Kill recommendations: {}", kill_candidates.len());
+            println!("- Review recommendations: {}", review_candidates.len());
+            if !visibility_full {
+                let visibility = &scan_result.metadata.visibility;
+                println!("- Visibility: partial ({})", visibility.describe());
+                for step in visibility_remediation(visibility) {
+                    println!("  - {}", step);
+                }
+            }
+            if oom_killers.any() {
+                let names: Vec<String> =
+                    oom_killers.active().iter().map(|k| k.to_string()).collect();
+                println!(
+                    "- Memory pressure killer active: {} ({} recent kills, {} candidates excluded)",
+                    names.join(", "),
+                    recent_oom_kills.events().len(),
+                    oom_killed_skipped_count
+                );
+            }
+            if let Some(sim) = &simulation {
+                println!("\n## Simulation\n");
+                println!("| | Before | After |");
+                println!("|---|---|---|");
+                println!(
+                    "| Available memory | {:.0} MB ({:.1}%) | {:.0} MB ({:.1}%) |",
+                    sim.before.memory_available_mb,
+                    sim.before.memory_available_pct,
+                    sim.after.memory_available_mb,
+                    sim.after.memory_available_pct
+                );
+                println!(
+                    "| Swap used | {:.0} MB ({}) | {:.0} MB ({}) |",
+                    sim.before.swap_used_mb,
+                    sim.before.swap_pressure.as_str(),
+                    sim.after.swap_used_mb,
+                    sim.after.swap_pressure.as_str()
+                );
+                println!(
+                    "| Load (1 min) | {:.2} | {:.2} |",
+                    sim.before.load1, sim.after.load1
+                );
+                for cg in &sim.cgroups {
+                    if let (Some(before), Some(after)) =
+                        (cg.headroom_before_mb, cg.headroom_after_mb)
+                    {
+                        println!(
+                            "| Headroom {} | {:.0} MB | {:.0} MB |",
+                            cg.cgroup, before, after
+                        );
+                    }
+                }
+            }
+            println!("\n## Candidates\n");
+            for candidate in &candidates {
+                let pid = candidate.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+                let cmd = candidate
+                    .get("command_short")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?");
+                let class = candidate
+                    .get("classification")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?");
+                let action = candidate
+                    .get("recommended_action")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("?");
+                println!("- PID {}: {} ({}) → {}", pid, cmd, class, action);
+            }
+        }
+    }
+
+    // Return appropriate exit code
+    if candidates.is_empty() {
+        ExitCode::Clean // 0: nothing to do
+    } else {
+        ExitCode::PlanReady // 1: candidates exist, plan produced
+    }
+}
+
+/// `agent plan --replay-bundle`: re-evaluate a bundle's evidence ledger under
+/// the local priors and policy and report where decisions would differ.
+fn run_agent_plan_replay(
+    global: &GlobalOpts,
+    args: &AgentPlanArgs,
+    bundle: &std::path::Path,
+) -> ExitCode {
+    let (session_id, records) = match read_bundle_evidence("agent plan", bundle, &args.passphrase) {
+        Ok(found) => found,
+        Err(code) => return code,
+    };
+    let (priors, policy) = match load_replay_config(global, args.policy.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("agent plan: failed to load config: {}", e);
+            return if args.policy.is_some() {
+                ExitCode::ArgsError
+            } else {
+                ExitCode::InternalError
+            };
+        }
+    };
+
+    let mut comparison = pt_core::calibrate::experiment::ExperimentComparison::default();
+    comparison.add_recorded(&session_id, &records, (&priors, &policy));
+    let changes_total = comparison.changes.len();
+    let agreement_rate = comparison.agreement_rate();
+    comparison.changes.truncate(args.max_candidates as usize);
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "agent plan",
+                "mode": "replay_bundle",
+                "bundle": bundle.display().to_string(),
+                "session_id": session_id,
+                "policy": args.policy.as_ref().map(|p| p.display().to_string()),
+                "agreement_rate": agreement_rate,
+                "changes_total": changes_total,
+                "comparison": comparison,
+            });
+            match global.format {
+                OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output).unwrap()),
+                _ => println!("{}", format_structured_output(global, output)),
+            }
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!(
+                "Bundle {} (session {}): {} record(s)",
+                bundle.display(),
+                session_id,
+                comparison.records
+            );
+            match agreement_rate {
+                Some(rate) => println!(
+                    "Agreement with recorded decisions: {:.1}% ({} changed, {} skipped)",
+                    rate * 100.0,
+                    changes_total,
+                    comparison.skipped
+                ),
+                None => println!("No comparable records."),
+            }
+            for change in &comparison.changes {
+                println!(
+                    "  pid {} ({}): {:?} -> {:?}",
+                    change.pid, change.comm, change.baseline_action, change.experiment_action
+                );
+            }
+            if changes_total > comparison.changes.len() {
+                println!("  ... {} more", changes_total - comparison.changes.len());
+            }
+        }
+    }
+
+    if changes_total == 0 {
+        ExitCode::Clean
+    } else {
+        ExitCode::PlanReady
+    }
+}
+
+fn run_agent_explain(global: &GlobalOpts, args: &AgentExplainArgs) -> ExitCode {
+    if let Some(bundle) = args.bundle.as_deref() {
+        return run_agent_explain_bundle(global, args, bundle);
+    }
+    let session = args.session.as_deref().unwrap_or_default();
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent explain: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let sid = match SessionId::parse(session) {
+        Some(sid) => sid,
+        None => {
+            eprintln!("agent explain: invalid --session {}", session);
+            return ExitCode::ArgsError;
+        }
+    };
+    let handle = match store.open(&sid) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("agent explain: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+
+    // Load priors from config or use defaults
+    let priors = match load_priors_for_explain(global) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("agent explain: failed to load priors: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let mut signature_db = SignatureDatabase::with_defaults();
+    if let Some(pack) = pt_core::signature_cli::load_signature_pack() {
+        for signature in pack.signatures {
+            let _ = signature_db.add(signature);
+        }
+    }
+    if let Some(user_schema) = pt_core::signature_cli::load_user_signatures() {
+        for signature in user_schema.signatures {
+            let _ = signature_db.add(signature);
+        }
+    }
+    let runtime_priors = load_runtime_priors(&signature_db);
+    let fd_trends = load_fd_trends();
+
+    let pids_to_explain = match explain_pids(args) {
+        Ok(pids) => pids,
+        Err(code) => return code,
+    };
+
+    let policy = load_config(&config_options(global))
+        .map(|config| config.policy)
+        .unwrap_or_default();
+
+    // Per-thread CPU split of each explained process; the scan covers the window.
+    #[cfg(target_os = "linux")]
+    let thread_sampler = pt_core::collect::ThreadCpuSampler::start(&pids_to_explain);
+    #[cfg(target_os = "linux")]
+    let terminal_sessions = pt_core::collect::TerminalSessionSnapshot::collect(&pids_to_explain);
+
+    // Quick scan to get process records for the specified PIDs
+    let scan_options = QuickScanOptions {
+        pids: pids_to_explain.clone(),
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+
+    let scan_result = match quick_scan(&scan_options) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("agent explain: scan failed: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    #[cfg(target_os = "linux")]
+    thread_sampler.wait(pt_core::collect::DEFAULT_THREAD_CPU_WINDOW);
+
+    // Zombies are reaped through their parent, which the targeted scan does not cover.
+    let zombie_context = if scan_result.processes.iter().any(|p| p.state.is_zombie()) {
+        let full_scan = QuickScanOptions {
+            pids: vec![],
+            include_kernel_threads: false,
+            timeout: global.timeout.map(std::time::Duration::from_secs),
+            progress: None,
+            incremental: None,
+            io_rate_window: None,
+        };
+        let processes = quick_scan(&full_scan)
+            .map(|scan| scan.processes)
+            .unwrap_or_default();
+        ProtectedFilter::from_guardrails(&policy.guardrails)
+            .ok()
+            .map(|filter| (processes, filter))
+    } else {
+        None
+    };
+
+    // The survival ETA card is galaxy-brain only: fitting reads the whole shadow history.
+    let survival_eta_model = if args.galaxy_brain {
+        load_survival_eta_model()
+    } else {
+        None
+    };
+
+    // Build explanations for each process
+    let mut explanations: Vec<serde_json::Value> = Vec::new();
+    let launch_attributor = pt_core::supervision::LaunchAttributor::new(&scan_result.processes);
+    let mut blast_input = pt_core::supervision::blast_radius::BlastRadiusInput::default();
+    #[cfg(target_os = "linux")]
+    let network_snapshot = args.show_blast_radius.then(NetworkSnapshot::collect);
+    if args.show_blast_radius {
+        blast_input.process_table = scan_result
+            .processes
+            .iter()
+            .map(|p| (p.pid.0, (p.comm.clone(), p.ppid.0)))
+            .collect();
+    }
+
+    for pid in &pids_to_explain {
+        let record = scan_result.processes.iter().find(|p| p.pid.0 == *pid);
+        match record {
+            Some(proc) => {
+                let runtime_surprise =
+                    process_runtime_surprise(proc, &signature_db, &runtime_priors);
+                let survival_eta = survival_eta_model.as_ref().and_then(|model| {
+                    model.eta_for(
+                        &pt_core::shadow::compute_identity_hash(proc),
+                        chrono::Utc::now(),
+                    )
+                });
+                let fd_leak = if fd_trends.is_empty() {
+                    None
+                } else {
+                    process_behavior_sample(proc).fd_count.and_then(|fds| {
+                        fd_trends.assess(
+                            &pt_core::shadow::compute_identity_hash(proc),
+                            fds,
+                            chrono::Utc::now(),
+                        )
+                    })
+                };
+                #[cfg(target_os = "linux")]
+                let terminal_session = terminal_sessions.get(proc.pid.0);
+                #[cfg(target_os = "linux")]
+                let session_bucket = terminal_session
+                    .and_then(|session| session_attachment_bucket(session, &priors));
+                #[cfg(not(target_os = "linux"))]
+                let session_bucket: Option<usize> = None;
+                let mut explanation = build_process_explanation(
+                    proc,
+                    &priors,
+                    runtime_surprise.as_ref(),
+                    survival_eta.as_ref(),
+                    fd_leak.as_ref(),
+                    session_bucket,
+                    args,
+                );
+                // The tmux/screen session or SSH login it runs in, and whether
+                // anyone attached to it is typing.
+                #[cfg(target_os = "linux")]
+                if let Some(session) = terminal_session {
+                    explanation["terminal_session"] =
+                        terminal_session_json(session, &priors, &policy.guardrails);
+                }
+                // Remediation at the origin: fix the cron job, stop the unit.
+                explanation["launched_by"] = serde_json::json!(launch_attributor.attribute(proc));
+                // Which threads the CPU goes to, and whether one of them runs away.
+                #[cfg(target_os = "linux")]
+                if let Some(threads) = thread_sampler.summary(proc.pid.0) {
+                    explanation["threads"] = thread_cpu_json(&threads, &policy.thread_hog);
+                }
+                // A zombie is reaped through its parent; say how and why.
+                if let Some((processes, protected_filter)) =
+                    zombie_context.as_ref().filter(|_| proc.state.is_zombie())
+                {
+                    let reap = zombie_reap_for(proc, processes, protected_filter);
+                    explanation["zombie_reap"] = serde_json::json!({
+                        "summary": reap.summary(),
+                        "actionable": reap.actionable(),
+                        "assessment": reap,
+                    });
+                }
+                if args.show_blast_radius {
+                    blast_input.target_pid = proc.pid.0;
+                    blast_input.target_comm = proc.comm.clone();
+                    #[cfg(target_os = "linux")]
+                    blast_input.load_collected(
+                        parse_fd(proc.pid.0).as_ref(),
+                        network_snapshot
+                            .as_ref()
+                            .and_then(|snapshot| snapshot.get_process_info(proc.pid.0))
+                            .as_ref(),
+                    );
+                    explanation["blast_radius"] = serde_json::json!(
+                        pt_core::supervision::blast_radius::compute_blast_radius(&blast_input)
+                    );
+                }
+                explanations.push(explanation);
+            }
+            None => {
+                // Process not found - might have exited
+                explanations.push(serde_json::json!({
+                    "pid": pid,
+                    "error": "process not found (may have exited)",
+                    "classification": null,
+                }));
+            }
+        }
+    }
+
+    let dependency_graph = if args.show_dependencies {
+        match export_dependency_graph(&handle, &pids_to_explain, global) {
+            Ok(graph) => Some(graph),
+            Err(e) => {
+                eprintln!(
+                    "agent explain: warning: dependency graph unavailable: {}",
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Output in requested format
+    let mut output = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "session_id": sid.0,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "command": "agent explain",
+        "ptrace_scope": ptrace_scope(),
+        "explanations": explanations,
+    });
+    if let Some(ref graph) = dependency_graph {
+        output["dependency_graph"] = graph.clone();
+    }
+
+    // Optionally save to session
+    let explain_path = handle.dir.join("inference").join("explain.json");
+    if let Err(e) = std::fs::write(
+        &explain_path,
+        serde_json::to_string_pretty(&output).unwrap(),
+    ) {
+        eprintln!("agent explain: warning: failed to save to session: {}", e);
+    }
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            for expl in &explanations {
+                if let (Some(pid), Some(class)) = (expl.get("pid"), expl.get("classification")) {
+                    let conf = expl
+                        .get("confidence")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("?");
+                    println!("[{}] PID {}: {} ({})\n", sid, pid, class, conf);
+                }
+            }
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            // Human readable markdown output
+            println!("# pt-core agent explain\n");
+            println!("Session: {}", sid);
+            println!();
+
+            for expl in &explanations {
+                let pid = expl.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+
+                if let Some(err) = expl.get("error") {
+                    println!("## PID {}\n", pid);
+                    println!("Error: {}\n", err);
+                    continue;
+                }
+
+                let class = expl
+                    .get("classification")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown");
+                let conf = expl
+                    .get("confidence")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown");
+                let why = expl
+                    .get("why_summary")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+
+                println!("## PID {} - {} ({})\n", pid, class, conf);
+                if !why.is_empty() {
+                    println!("{}\n", why);
+                }
+
+                // Show posterior probabilities
+                if let Some(posterior) = expl.get("posterior") {
+                    println!("### Posterior Probabilities\n");
+                    println!("| Class | P(C|x) |");
+                    println!("|-------|--------|");
+                    for class_name in &["useful", "useful_bad", "abandoned", "zombie"] {
+                        if let Some(p) = posterior.get(*class_name).and_then(|v| v.as_f64()) {
+                            println!("| {} | {:.4} |", class_name, p);
+                        }
+                    }
+                    println!();
+                }
+
+                if let Some(privileges) = expl.get("privileges") {
+                    let level = privileges
+                        .get("level")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("unknown");
+                    let caps = privileges
+                        .get("effective_caps")
+                        .and_then(|v| v.as_array())
+                        .map(|caps| {
+                            caps.iter()
+                                .filter_map(|c| c.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default();
+                    println!("### Privileges\n");
+                    println!("Level: {}", level);
+                    if !caps.is_empty() {
+                        println!("Effective capabilities: {}", caps);
+                    }
+                    if privileges["exe_setuid"].as_bool() == Some(true)
+                        || privileges["exe_setgid"].as_bool() == Some(true)
+                    {
+                        println!("Executable is setuid/setgid");
+                    }
+                    if privileges["privileged_orphan"].as_bool() == Some(true) {
+                        println!("Flagged: unexpected highly-privileged orphan");
+                    }
+                    println!();
+                }
+
+                if let Some(launched_by) = expl.get("launched_by") {
+                    let kind = launched_by["kind"].as_str().unwrap_or("unknown");
+                    println!("### Launched By\n");
+                    println!("Origin: {}", kind.replace('_', " "));
+                    for line in launched_by["evidence"].as_array().into_iter().flatten() {
+                        println!("- {}", line.as_str().unwrap_or(""));
+                    }
+                    let steps = launched_by["remediation"].as_array();
+                    if let Some(steps) = steps.filter(|s| !s.is_empty()) {
+                        println!("\nSuggested remediation:");
+                        for step in steps {
+                            println!("- {}", step.as_str().unwrap_or(""));
+                        }
+                    }
+                    println!();
+                }
+
+                if let Some(reap) = expl.get("zombie_reap") {
+                    println!("### Zombie Reaping\n");
+                    println!("{}", reap["summary"].as_str().unwrap_or(""));
+                    for reason in reap["assessment"]["reasons"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                    {
+                        println!("- {}", reason.as_str().unwrap_or(""));
+                    }
+                    println!();
+                }
+
+                if let Some(leak) = expl.get("fd_leak") {
+                    println!("### File Descriptor Trend\n");
+                    println!("{}", leak["summary"].as_str().unwrap_or(""));
+                    println!(
+                        "- {} samples, {:.1} fds/hour, {:.0}% of steps non-decreasing",
+                        leak["samples"].as_u64().unwrap_or(0),
+                        leak["slope_per_hour"].as_f64().unwrap_or(0.0),
+                        leak["monotonic_fraction"].as_f64().unwrap_or(0.0) * 100.0
+                    );
+                    println!();
+                }
+
+                if let Some(threads) = expl.get("threads") {
+                    println!("### Threads\n");
+                    println!("{}", threads["summary"].as_str().unwrap_or(""));
+                    if threads["single_thread_hog"].as_bool().unwrap_or(false) {
+                        println!("One runaway thread; review it before killing the process.");
+                    }
+                    for thread in threads["top_threads"].as_array().into_iter().flatten() {
+                        println!(
+                            "- {} ({}): {:.1}% CPU, {:.0}% of process",
+                            thread["tid"].as_u64().unwrap_or(0),
+                            thread["name"].as_str().unwrap_or(""),
+                            thread["cpu_percent"].as_f64().unwrap_or(0.0),
+                            thread["share"].as_f64().unwrap_or(0.0) * 100.0
+                        );
+                    }
+                    println!();
+                }
+
+                if let Some(session) = expl.get("terminal_session") {
+                    println!("### Terminal Session\n");
+                    println!("{}", session["summary"].as_str().unwrap_or(""));
+                    if session["protected"].as_bool().unwrap_or(false) {
+                        println!("Someone attached is typing; kills are held for review.");
+                    }
+                    println!();
+                }
+
+                if let Some(blast) = expl.get("blast_radius") {
+                    println!("### Blast Radius\n");
+                    println!("{}", blast["summary"].as_str().unwrap_or(""));
+                    for factor in blast["risk_factors"].as_array().into_iter().flatten() {
+                        println!(
+                            "- {} (weight {:.1})",
+                            factor["description"].as_str().unwrap_or(""),
+                            factor["weight"].as_f64().unwrap_or(0.0)
+                        );
+                    }
+                    println!();
+                }
+
+                // Show top evidence if galaxy_brain mode
+                if args.galaxy_brain {
+                    if let Some(factors) = expl.get("bayes_factors").and_then(|v| v.as_array()) {
+                        println!("### Evidence Breakdown\n");
+                        println!("| Feature | BF | Direction | Strength |");
+                        println!("|---------|-----|-----------|----------|");
+                        for bf in factors.iter().take(5) {
+                            let feat = bf.get("feature").and_then(|v| v.as_str()).unwrap_or("?");
+                            let bf_val = bf.get("bf").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                            let dir = bf.get("direction").and_then(|v| v.as_str()).unwrap_or("?");
+                            let strength =
+                                bf.get("strength").and_then(|v| v.as_str()).unwrap_or("?");
+                            // Format BF: use scientific notation for extreme values
+                            let bf_str = if bf_val.is_infinite()
+                                || bf_val > 1e6
+                                || (bf_val < 1e-6 && bf_val > 0.0)
+                            {
+                                format!("{:.2e}", bf_val)
+                            } else {
+                                format!("{:.2}", bf_val)
+                            };
+                            println!("| {} | {} | {} | {} |", feat, bf_str, dir, strength);
+                        }
+                        println!();
+                    }
+                    if let Some(text) = expl["runtime_prior"]["galaxy_brain"].as_str() {
+                        println!("```\n{}\n```\n", text);
+                    }
+                }
+            }
+
+            if let Some(ref graph) = dependency_graph {
+                println!("## Dependencies\n");
+                println!(
+                    "{} processes, {} edges{}",
+                    graph["nodes"].as_array().map_or(0, |n| n.len()),
+                    graph["edges"].as_array().map_or(0, |e| e.len()),
+                    if graph["truncated"].as_bool() == Some(true) {
+                        " (truncated)"
+                    } else {
+                        ""
+                    }
+                );
+                if let Some(dot) = graph["artifacts"]["dot"].as_str() {
+                    println!("DOT: {}", dot);
+                }
+                if let Some(json) = graph["artifacts"]["json"].as_str() {
+                    println!("JSON: {}", json);
+                }
+                println!();
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+/// Build the dependency graph around `roots` from a full scan and store it in
+/// the session as `inference/dependency_graph.{json,dot}`.
+///
+/// Returns the graph JSON with an `artifacts` object naming both files.
+fn export_dependency_graph(
+    handle: &SessionHandle,
+    roots: &[u32],
+    global: &GlobalOpts,
+) -> Result<serde_json::Value, String> {
+    use pt_core::collect::dependency_graph::{DependencyGraph, DEFAULT_MAX_NODES};
+
+    let scan = quick_scan(&QuickScanOptions {
+        pids: Vec::new(),
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    })
+    .map_err(|e| format!("scan failed: {}", e))?;
+
+    #[allow(unused_mut)]
+    let mut graph = DependencyGraph::build(&scan.processes, roots, DEFAULT_MAX_NODES);
+    #[cfg(target_os = "linux")]
+    {
+        let pids: Vec<u32> = graph.nodes.iter().map(|n| n.pid).collect();
+        let sockets = pt_core::collect::dependency_graph::collect_unix_sockets(&pids);
+        graph.add_unix_socket_links(&sockets);
+    }
+
+    let dir = handle.dir.join("inference");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let json_path = dir.join("dependency_graph.json");
+    let dot_path = dir.join("dependency_graph.dot");
+    let mut json = serde_json::to_value(&graph).map_err(|e| e.to_string())?;
+    std::fs::write(
+        &json_path,
+        serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| format!("{}: {}", json_path.display(), e))?;
+    std::fs::write(&dot_path, graph.to_dot())
+        .map_err(|e| format!("{}: {}", dot_path.display(), e))?;
+
+    json["artifacts"] = serde_json::json!({
+        "json": json_path.display().to_string(),
+        "dot": dot_path.display().to_string(),
+    });
+    Ok(json)
+}
+
+/// Load the per-category runtime prior table, folding in session snapshots
+/// that have not been ingested yet.
+///
+/// The table lives in the telemetry directory and outlives session
+/// retention, so load or store errors only mean no category-relative
+/// runtime evidence this run.
+fn load_runtime_priors(signature_db: &SignatureDatabase) -> RuntimePriorTable {
+    let path = pt_core::inference::runtime_priors_path(&default_telemetry_dir());
+    let mut table = match RuntimePriorTable::load(&path) {
+        Ok(table) => table,
+        Err(err) => {
+            tracing::warn!(error = %err, path = %path.display(), "failed to load runtime priors");
+            return RuntimePriorTable::default();
+        }
+    };
+
+    let mut changed = false;
+    if let Ok(sessions) = SessionStore::from_env()
+        .and_then(|store| store.list_sessions(&ListSessionsOptions::default()))
+    {
+        for summary in &sessions {
+            // Unpromoted experiments are left un-ingested so promotion picks them up later.
+            if table.is_ingested(&summary.session_id) || !summary.feeds_learning() {
+                continue;
+            }
+            let samples =
+                pt_core::session::runtime_history::snapshot_runtime_samples(summary, |record| {
+                    let mut ctx = ProcessMatchContext::with_comm(&record.comm);
+                    if !record.cmd.is_empty() {
+                        ctx = ctx.cmdline(&record.cmd);
+                    }
+                    signature_db
+                        .best_match(&ctx)
+                        .map(|m| format!("{:?}", m.signature.category))
+                });
+            if let Some(samples) = samples {
+                changed |= table.ingest(&summary.session_id, samples);
+            }
+        }
+    }
+    if changed {
+        if let Err(err) = table.save(&path) {
+            tracing::warn!(error = %err, path = %path.display(), "failed to save runtime priors");
+        }
+    }
+    table
+}
+
+/// Category-relative runtime surprise for a live process, if its signature
+/// category has a fitted runtime prior.
+fn process_runtime_surprise(
+    proc: &ProcessRecord,
+    signature_db: &SignatureDatabase,
+    runtime_priors: &RuntimePriorTable,
+) -> Option<RuntimeSurprise> {
+    let mut ctx = ProcessMatchContext::with_comm(&proc.comm);
+    if !proc.cmd.is_empty() {
+        ctx = ctx.cmdline(&proc.cmd);
+    }
+    let category = format!("{:?}", signature_db.best_match(&ctx)?.signature.category);
+    runtime_priors.surprise(&category, proc.elapsed.as_secs_f64())
+}
+
+fn runtime_surprise_json(surprise: &RuntimeSurprise) -> serde_json::Value {
+    serde_json::json!({
+        "category": surprise.category,
+        "runtime_seconds": surprise.runtime_secs,
+        "z_score": surprise.z_score,
+        "survival": surprise.survival,
+        "surprisal_nats": surprise.surprisal,
+        "fit": {
+            "distribution": "lognormal",
+            "mu": surprise.fit.mu,
+            "sigma": surprise.fit.sigma,
+            "samples": surprise.fit.samples,
+            "median_seconds": surprise.fit.median_secs(),
+        },
+    })
+}
+
+/// Per-fingerprint behavior baselines learned by shadow mode.
+///
+/// Read-only here; a missing or unreadable table only means no
+/// `behavior_change` evidence this run.
+fn load_behavior_baselines() -> BehaviorBaselineStore {
+    let path = pt_core::inference::behavior_baselines_path(&pt_core::shadow::shadow_base_dir());
+    BehaviorBaselineStore::load(&path).unwrap_or_else(|err| {
+        tracing::warn!(error = %err, path = %path.display(), "failed to load behavior baselines");
+        BehaviorBaselineStore::default()
+    })
+}
+
+/// Per-process fd-count trends recorded by shadow mode.
+///
+/// Read-only here; a missing or unreadable table only means no `fd_leak`
+/// evidence this run.
+fn load_fd_trends() -> FdTrendStore {
+    let path = pt_core::inference::fd_trends_path(&pt_core::shadow::shadow_base_dir());
+    FdTrendStore::load(&path).unwrap_or_else(|err| {
+        tracing::warn!(error = %err, path = %path.display(), "failed to load fd trends");
+        FdTrendStore::default()
+    })
+}
+
+/// Current footprint of a process for baseline scoring.
+fn process_behavior_sample(proc: &ProcessRecord) -> BehaviorSample {
+    #[cfg(target_os = "linux")]
+    let fd = parse_fd(proc.pid.0);
+    #[cfg(target_os = "linux")]
+    let (fd_count, sockets) = (
+        fd.as_ref().map(|fd| fd.count as u32),
+        fd.as_ref().map(|fd| fd.sockets as u32),
+    );
+    #[cfg(not(target_os = "linux"))]
+    let (fd_count, sockets) = (None, None);
+    BehaviorSample {
+        cpu_percent: proc.cpu_percent,
+        rss_bytes: proc.rss_bytes,
+        fd_count,
+        sockets,
+    }
+}
+
+fn behavior_deviation_json(deviation: &BehaviorDeviation, priors: &Priors) -> serde_json::Value {
+    let bucket = priors
+        .behavior_change
+        .as_ref()
+        .map(|bc| bc.bucket_name(bc.bucket(deviation.max_z)));
+    let z_scores: serde_json::Map<String, serde_json::Value> = deviation
+        .z_scores
+        .iter()
+        .map(|(metric, z)| (metric.as_str().to_string(), serde_json::json!(z)))
+        .collect();
+    serde_json::json!({
+        "max_z": deviation.max_z,
+        "metric": deviation.metric.as_str(),
+        "bucket": bucket,
+        "baseline_samples": deviation.samples,
+        "z_scores": z_scores,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn thread_cpu_json(
+    threads: &pt_core::collect::ThreadCpuSummary,
+    thread_hog: &pt_core::config::policy::ThreadHogPolicy,
+) -> serde_json::Value {
+    let mut value = serde_json::json!(threads);
+    value["dominant_share"] = serde_json::json!(threads.dominant_share());
+    value["single_thread_hog"] = serde_json::json!(
+        threads.is_single_thread_hog(thread_hog.min_dominant_share, thread_hog.min_cpu_percent)
+    );
+    value["summary"] = serde_json::json!(threads.summary());
+    value
+}
+
+/// Evidence bucket of an attributed session; `None` when attachment is unknown.
+#[cfg(target_os = "linux")]
+fn session_attachment_bucket(
+    session: &pt_core::collect::SessionAttribution,
+    priors: &Priors,
+) -> Option<usize> {
+    let attached = session.attached()?;
+    priors
+        .session_attachment
+        .as_ref()
+        .map(|sa| sa.bucket(attached, session.client_idle_secs()))
+}
+
+#[cfg(target_os = "linux")]
+fn terminal_session_json(
+    session: &pt_core::collect::SessionAttribution,
+    priors: &Priors,
+    guardrails: &pt_core::config::policy::Guardrails,
+) -> serde_json::Value {
+    let mut value = serde_json::json!(session);
+    value["attached"] = serde_json::json!(session.attached());
+    value["client_idle_secs"] = serde_json::json!(session.client_idle_secs());
+    value["bucket"] = serde_json::json!(session_attachment_bucket(session, priors).and_then(
+        |bucket| priors
+            .session_attachment
+            .as_ref()
+            .map(|sa| sa.bucket_name(bucket))
+    ));
+    value["protected"] = serde_json::json!(
+        guardrails.protect_attached_sessions
+            && session.is_attached_active(guardrails.attached_session_idle_seconds)
+    );
+    value["summary"] = serde_json::json!(session.summary());
+    value
+}
+
+/// Signature shutdown command for a kill of `proc`, if its template renders.
+fn graceful_stop_for(
+    signature: &pt_core::supervision::signature::SupervisorSignature,
+    proc: &ProcessRecord,
+) -> Option<pt_core::plan::CommandAction> {
+    let template = signature.graceful_stop.as_ref()?;
+    let cwd = std::fs::read_link(format!("/proc/{}/cwd", proc.pid.0)).ok();
+    let ctx = pt_core::plan::GracefulStopContext {
+        pid: proc.pid.0,
+        cmdline: &proc.cmd,
+        cwd: cwd.as_deref().and_then(|path| path.to_str()),
+    };
+    pt_core::plan::command::command_action_for(&signature.name, template, &ctx, Action::Kill)
+}
+
+fn command_action_json(command_action: &pt_core::plan::CommandAction) -> serde_json::Value {
+    let mut value = serde_json::json!(command_action);
+    value["display_command"] = serde_json::json!(command_action.display_command());
+    value
+}
+
+fn build_daemon_json(info: &pt_core::supervision::BuildDaemonInfo) -> serde_json::Value {
+    let mut value = serde_json::json!(info);
+    value["reclaimable"] = serde_json::json!(info.state.is_reclaimable());
+    value["recommended_action"] = serde_json::json!(info.recommended_action());
+    value["summary"] = serde_json::json!(info.summary());
+    value
+}
+
+fn fd_leak_json(leak: &FdLeakAssessment, priors: &Priors) -> serde_json::Value {
+    let mut value = serde_json::json!(leak);
+    value["bucket"] = serde_json::json!(priors
+        .fd_leak
+        .as_ref()
+        .map(|fl| fl.bucket_name(fl.bucket(leak.confidence))));
+    value["summary"] = serde_json::json!(leak.summary());
+    value
+}
+
+/// Load priors from config with fallback to defaults.
+/// PIDs selected by `agent explain --pids` or `--target`.
+fn explain_pids(args: &AgentExplainArgs) -> Result<Vec<u32>, ExitCode> {
+    if !args.pids.is_empty() {
+        Ok(args.pids.clone())
+    } else if let Some(ref target) = args.target {
+        // Parse target format "pid:start_id" and extract PID
+        match target.split(':').next().and_then(|s| s.parse::<u32>().ok()) {
+            Some(pid) => Ok(vec![pid]),
+            None => {
+                eprintln!("agent explain: invalid --target format, expected pid:start_id");
+                Err(ExitCode::ArgsError)
+            }
+        }
+    } else {
+        eprintln!("agent explain: must specify --pids or --target");
+        Err(ExitCode::ArgsError)
+    }
+}
+
+/// Read the evidence ledger of a .ptb bundle; returns the bundle's session ID
+/// and its records.
+fn read_bundle_evidence(
+    command: &str,
+    path: &std::path::Path,
+    passphrase_arg: &Option<String>,
+) -> Result<(String, Vec<EvidenceRecord>), ExitCode> {
+    let passphrase = resolve_bundle_passphrase(passphrase_arg);
+    let mut reader =
+        match pt_bundle::BundleReader::open_with_passphrase(path, passphrase.as_deref()) {
+            Ok(reader) => reader,
+            Err(e) => {
+                eprintln!(
+                    "{}: failed to open bundle {}: {}",
+                    command,
+                    path.display(),
+                    e
+                );
+                return Err(ExitCode::ArgsError);
+            }
+        };
+    let session_id = reader.manifest().session_id.clone();
+    if !reader.has_file("inference/evidence.json") {
+        eprintln!(
+            "{}: bundle {} has no evidence ledger (inference/evidence.json); \
+             re-run agent plan and bundle create with this version",
+            command,
+            path.display()
+        );
+        return Err(ExitCode::ArgsError);
+    }
+    match reader.read_json::<ArtifactEnvelope<EvidenceArtifact>>("inference/evidence.json") {
+        Ok(envelope) => Ok((session_id, envelope.payload.records)),
+        Err(e) => {
+            eprintln!("{}: failed to read evidence ledger: {}", command, e);
+            Err(ExitCode::InternalError)
+        }
+    }
+}
+
+/// `agent explain --bundle`: explain bundled evidence under the local priors.
+fn run_agent_explain_bundle(
+    global: &GlobalOpts,
+    args: &AgentExplainArgs,
+    bundle: &std::path::Path,
+) -> ExitCode {
+    let pids_to_explain = match explain_pids(args) {
+        Ok(pids) => pids,
+        Err(code) => return code,
+    };
+    let (session_id, records) =
+        match read_bundle_evidence("agent explain", bundle, &args.passphrase) {
+            Ok(found) => found,
+            Err(code) => return code,
+        };
+    // Like the live path, fall back to built-in defaults if the config is unreadable.
+    let (priors, policy) =
+        load_replay_config(global, None).unwrap_or_else(|_| (Priors::default(), Policy::default()));
+
+    let explanations: Vec<serde_json::Value> = pids_to_explain
+        .iter()
+        .map(|pid| match records.iter().find(|r| r.replay.pid == *pid) {
+            Some(record) => build_bundle_explanation(record, &priors, &policy, args),
+            None => serde_json::json!({
+                "pid": pid,
+                "error": "process not in bundle evidence ledger",
+                "classification": null,
+            }),
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "schema_version": SCHEMA_VERSION,
+        "session_id": session_id,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "command": "agent explain",
+        "source": {
+            "bundle": bundle.display().to_string(),
+            "evidence_records": records.len(),
+        },
+        "explanations": explanations,
+    });
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core agent explain\n");
+            println!("Bundle: {} (session {})\n", bundle.display(), session_id);
+            for expl in &explanations {
+                let pid = expl.get("pid").and_then(|v| v.as_u64()).unwrap_or(0);
+                if let Some(err) = expl.get("error").and_then(|v| v.as_str()) {
+                    println!("## PID {}\n\nError: {}\n", pid, err);
+                    continue;
+                }
+                println!(
+                    "## PID {} - {} ({})\n",
+                    pid,
+                    expl["classification"].as_str().unwrap_or("unknown"),
+                    expl["confidence"].as_str().unwrap_or("unknown")
+                );
+                println!("{}\n", expl["why_summary"].as_str().unwrap_or(""));
+                println!(
+                    "Recorded action: {}, local action: {}\n",
+                    expl["recorded"]["action"].as_str().unwrap_or("?"),
+                    expl["local"]["action"].as_str().unwrap_or("?")
+                );
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+/// Explanation of a bundled evidence record under the local priors and policy.
+fn build_bundle_explanation(
+    record: &EvidenceRecord,
+    priors: &Priors,
+    policy: &Policy,
+    args: &AgentExplainArgs,
+) -> serde_json::Value {
+    let replay = &record.replay;
+    let posterior_result = match compute_posterior(priors, &replay.evidence) {
+        Ok(r) => r,
+        Err(e) => {
+            return serde_json::json!({
+                "pid": replay.pid,
+                "comm": replay.comm,
+                "error": format!("posterior computation failed: {}", e),
+            });
+        }
+    };
+    let ledger = EvidenceLedger::from_posterior_result(&posterior_result, Some(replay.pid), None);
+    let posterior = posterior_result.posterior;
+    let feasibility = ActionFeasibility::from_process_state(replay.zombie, replay.disksleep, None);
+    let local_action = decide_action(&posterior, policy, &feasibility)
+        .ok()
+        .map(|outcome| outcome.optimal_action);
+
+    let mut explanation = serde_json::json!({
+        "pid": replay.pid,
+        "start_id": replay.start_id,
+        "comm": replay.comm,
+        "classification": ledger.classification.label(),
+        "confidence": ledger.confidence.label(),
+        "why_summary": ledger.why_summary,
+        "posterior": posterior,
+        "recorded": {
+            "posterior": record.posterior,
+            "action": record.action,
+        },
+        "local": {
+            "action": local_action,
+            "changed": local_action != Some(record.action),
+        },
+    });
+    if args.galaxy_brain || args.include.contains(&"bayes_factors".to_string()) {
+        explanation["bayes_factors"] = bayes_factors_json(&ledger);
+        explanation["top_evidence"] = serde_json::json!(ledger.top_evidence);
+    }
+    if args.include.contains(&"evidence".to_string()) {
+        explanation["evidence"] = serde_json::json!(replay.evidence);
+    }
+    explanation
+}
+
+/// Bayes factor breakdown of an evidence ledger.
+fn bayes_factors_json(ledger: &EvidenceLedger) -> serde_json::Value {
+    let entries: Vec<serde_json::Value> = ledger
+        .bayes_factors
+        .iter()
+        .map(|bf| {
+            serde_json::json!({
+                "feature": bf.feature,
+                "log_bf": bf.log_bf,
+                "bf": bf.bf,
+                "delta_bits": bf.delta_bits,
+                "direction": format!("{}", bf.direction),
+                "strength": bf.strength.clone(),
+            })
+        })
+        .collect();
+    serde_json::json!(entries)
+}
+
+/// Local priors and policy for re-evaluating a bundle; an explicit policy
+/// file replaces the configured policy.
+fn load_replay_config(
+    global: &GlobalOpts,
+    policy_path: Option<&std::path::Path>,
+) -> Result<(Priors, Policy), ConfigError> {
+    let opts = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        priors_path: None,
+        policy_path: policy_path.map(PathBuf::from),
+    };
+    load_config(&opts).map(|resolved| (resolved.priors, resolved.policy))
+}
+
+fn load_priors_for_explain(global: &GlobalOpts) -> Result<Priors, ConfigError> {
+    let opts = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        priors_path: None,
+        policy_path: None,
+    };
+    match load_config(&opts) {
+        Ok(resolved) => Ok(resolved.priors),
+        Err(_) => Ok(Priors::default()),
+    }
+}
+
+/// Build a JSON explanation for a single process.
+fn build_process_explanation(
+    proc: &ProcessRecord,
+    priors: &Priors,
+    runtime_surprise: Option<&RuntimeSurprise>,
+    survival_eta: Option<&SurvivalEta>,
+    fd_leak: Option<&FdLeakAssessment>,
+    session_attachment: Option<usize>,
+    args: &AgentExplainArgs,
+) -> serde_json::Value {
+    // Convert ProcessRecord to Evidence
+    let evidence = Evidence {
+        cpu: Some(CpuEvidence::Fraction {
+            occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
+        }),
+        runtime_seconds: Some(proc.elapsed.as_secs_f64()),
+        orphan: Some(proc.is_orphan()),
+        tty: Some(proc.has_tty()),
+        net: None,       // Would need network scan
+        io_active: None, // Would need /proc inspection
+        state_flag: state_to_flag(proc.state),
+        command_category: None, // Would need category classifier
+        gpu: None,
+        runtime_surprise: runtime_surprise.map(|s| s.surprisal),
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: fd_leak
+            .and_then(|leak| priors.fd_leak.as_ref().map(|fl| fl.bucket(leak.confidence))),
+        session_attachment,
+    };
+
+    // Compute posterior
+    let posterior_result = match compute_posterior(priors, &evidence) {
+        Ok(r) => r,
+        Err(e) => {
+            return serde_json::json!({
+                "pid": proc.pid.0,
+                "comm": proc.comm,
+                "error": format!("posterior computation failed: {}", e),
+            });
+        }
+    };
+
+    // Build evidence ledger
+    let ledger = EvidenceLedger::from_posterior_result(&posterior_result, Some(proc.pid.0), None);
+
+    // Build base explanation
+    let mut explanation = serde_json::json!({
+        "pid": proc.pid.0,
+        "ppid": proc.ppid.0,
+        "comm": proc.comm,
+        "user": proc.user,
+        "state": proc.state.to_string(),
+        "elapsed_seconds": proc.elapsed.as_secs(),
+        "cpu_percent": proc.cpu_percent,
+        "classification": ledger.classification.label(),
+        "confidence": ledger.confidence.label(),
+        "why_summary": ledger.why_summary,
+        "posterior": {
+            "useful": posterior_result.posterior.useful,
+            "useful_bad": posterior_result.posterior.useful_bad,
+            "abandoned": posterior_result.posterior.abandoned,
+            "zombie": posterior_result.posterior.zombie,
+        },
+    });
+
+    // Privilege evidence is always shown: it drives the privilege guardrails.
+    if let Some(privileges) = privileges_for_pid(proc.pid.0) {
+        let mut value = privileges_json(&privileges);
+        value["privileged_orphan"] = serde_json::json!(
+            proc.is_orphan() && privileges.level() == pt_core::collect::PrivilegeLevel::High
+        );
+        explanation["privileges"] = value;
+    }
+
+    // Add Bayes factors if galaxy_brain mode or requested
+    if args.galaxy_brain || args.include.contains(&"bayes_factors".to_string()) {
+        explanation["bayes_factors"] = bayes_factors_json(&ledger);
+        explanation["top_evidence"] = serde_json::json!(ledger.top_evidence);
+    }
+
+    if let Some(surprise) = runtime_surprise {
+        explanation["runtime_prior"] = runtime_surprise_json(surprise);
+        if args.galaxy_brain {
+            explanation["runtime_prior"]["galaxy_brain"] = serde_json::json!(render_runtime_prior(
+                surprise,
+                &GalaxyBrainConfig {
+                    math_mode: MathMode::Ascii,
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+
+    if let Some(leak) = fd_leak {
+        explanation["fd_leak"] = fd_leak_json(leak, priors);
+    }
+
+    if let Some(eta) = survival_eta {
+        explanation["eta_abandoned"] = serde_json::json!(eta);
+        explanation["eta_abandoned"]["galaxy_brain"] = serde_json::json!(render_survival_eta(
+            eta,
+            &GalaxyBrainConfig {
+                math_mode: MathMode::Ascii,
+                ..Default::default()
+            },
+        ));
+    }
+
+    // Add input evidence if requested
+    if args.include.contains(&"evidence".to_string()) {
+        explanation["evidence"] = serde_json::json!({
+            "cpu_occupancy": proc.cpu_percent / 100.0,
+            "runtime_seconds": proc.elapsed.as_secs_f64(),
+            "is_orphan": proc.is_orphan(),
+            "has_tty": proc.has_tty(),
+            "state": proc.state.to_string(),
+        });
+    }
+
+    explanation
+}
+
+/// Collect capability and setuid/setgid evidence for a PID (Linux only).
+fn privileges_for_pid(pid: u32) -> Option<pt_core::collect::PrivilegeInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        pt_core::collect::parse_privileges(pid)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Yama ptrace scope of the host, if Yama is enabled (Linux only).
+fn ptrace_scope() -> Option<u8> {
+    #[cfg(target_os = "linux")]
+    {
+        pt_core::collect::read_ptrace_scope()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Remediation steps that would give the current user full /proc visibility.
+fn visibility_remediation(visibility: &pt_core::collect::ProcVisibility) -> Vec<String> {
+    let user = std::env::var("USER").unwrap_or_else(|_| "$USER".to_string());
+    let exe = std::env::current_exe()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "pt-core".to_string());
+    let group = visibility
+        .exempt_gid
+        .and_then(pt_core::collect::visibility::group_name);
+    visibility.remediation(&user, &exe, group.as_deref())
+}
+
+/// Render /proc visibility for plan, check and capabilities output.
+fn visibility_json(visibility: &pt_core::collect::ProcVisibility) -> serde_json::Value {
+    serde_json::json!({
+        "scope": visibility.scope,
+        "complete": visibility.is_full(),
+        "hidepid": visibility.hidepid,
+        "exempt_gid": visibility.exempt_gid,
+        "exempt": visibility.exempt,
+        "note": visibility.describe(),
+        "remediation": visibility_remediation(visibility),
+    })
+}
+
+/// Render privilege evidence for plan and explain output.
+fn privileges_json(privileges: &pt_core::collect::PrivilegeInfo) -> serde_json::Value {
+    serde_json::json!({
+        "level": privileges.level().as_str(),
+        "effective_caps": privileges.effective_capabilities(),
+        "ambient_caps": privileges.ambient_capabilities(),
+        "cap_eff": format!("{:016x}", privileges.cap_eff),
+        "cap_bnd": format!("{:016x}", privileges.cap_bnd),
+        "ruid": privileges.ruid,
+        "euid": privileges.euid,
+        "exe_setuid": privileges.exe_setuid,
+        "exe_setgid": privileges.exe_setgid,
+        "no_new_privs": privileges.no_new_privs,
+        "seccomp": privileges.seccomp,
+    })
+}
+
+/// Map ProcessState to state flag index for priors.
+fn state_to_flag(state: pt_core::collect::ProcessState) -> Option<usize> {
+    use pt_core::collect::ProcessState;
+    match state {
+        ProcessState::Running => Some(0),
+        ProcessState::Sleeping => Some(1),
+        ProcessState::DiskSleep => Some(2),
+        ProcessState::Zombie => Some(3),
+        ProcessState::Stopped => Some(4),
+        ProcessState::Idle => Some(5),
+        ProcessState::Dead => Some(6),
+        ProcessState::Unknown => None,
+    }
+}
+
+/// How the zombie `proc` can be reaped, judged from its parent in `processes`.
+fn zombie_reap_for(
+    proc: &ProcessRecord,
+    processes: &[ProcessRecord],
+    protected_filter: &ProtectedFilter,
+) -> pt_core::plan::ZombieReap {
+    let ppid = proc.ppid.0;
+    let parent = processes
+        .iter()
+        .find(|p| p.pid.0 == ppid && ppid != 0)
+        .map(|parent| pt_core::plan::ZombieParent {
+            identity: ProcessIdentity::full(
+                parent.pid.0,
+                parent.start_id.clone(),
+                parent.uid,
+                parent.pgid,
+                parent.sid,
+                IdentityQuality::Full,
+            ),
+            comm: parent.comm.clone(),
+            state: parent.state,
+            protected: protected_filter.is_protected(parent).is_some(),
+            service: pt_core::plan::ServiceMembership::detect(parent.pid.0),
+            zombie_children: processes
+                .iter()
+                .filter(|p| p.ppid.0 == ppid && p.state.is_zombie())
+                .count(),
+        });
+    pt_core::plan::zombie::assess(proc.pid.0, ppid, parent.as_ref())
+}
+
+#[cfg(target_os = "linux")]
+fn supervisor_info_for_plan(pid: u32) -> serde_json::Value {
+    let mut detected = false;
+    let mut supervisor_type: Option<String> = None;
+    let mut unit: Option<String> = None;
+    let mut recommended_action = "kill".to_string();
+    let mut supervisor_command: Option<String> = None;
+
+    // Prefer container supervision if present
+    if let Ok(result) = ContainerSupervisionAnalyzer::new()
+        .with_action_recommendations()
+        .analyze(pid)
+    {
+        if result.is_supervised {
+            detected = true;
+            let runtime_label = if result.kubernetes.is_some() {
+                "kubernetes"
+            } else {
+                match result.runtime {
+                    ContainerRuntime::Docker => "docker",
+                    ContainerRuntime::Containerd => "containerd",
+                    ContainerRuntime::Podman => "podman",
+                    ContainerRuntime::Lxc => "lxc",
+                    ContainerRuntime::Crio => "crio",
+                    ContainerRuntime::Generic => "container",
+                    ContainerRuntime::None => "container",
+                }
+            };
+            supervisor_type = Some(runtime_label.to_string());
+            unit = result
+                .container_id_short
+                .clone()
+                .or(result.container_id.clone())
+                .or_else(|| result.kubernetes.as_ref().and_then(|k| k.pod_name.clone()));
+
+            if let Some(action) = result.recommended_action.as_ref() {
+                let action_label = match action.action_type {
+                    ContainerActionType::Stop => "stop",
+                    ContainerActionType::Restart => "restart",
+                    ContainerActionType::Remove => "remove",
+                    ContainerActionType::ScaleDown => "scale_down",
+                    ContainerActionType::DeletePod => "delete_pod",
+                    ContainerActionType::Inspect => "inspect",
+                };
+                recommended_action = format!("{}_{}", runtime_label, action_label);
+                supervisor_command = Some(action.command.clone());
+            } else {
+                recommended_action = format!("{}_review", runtime_label);
+            }
+        }
+    }
+
+    // App supervisors (pm2, supervisord, etc.)
+    if !detected {
+        if let Ok(result) = AppSupervisionAnalyzer::new().analyze(pid) {
+            if result.is_supervised && result.supervisor_type != AppSupervisorType::Unknown {
+                detected = true;
+                let supervisor_label = result.supervisor_type.to_string();
+                supervisor_type = Some(supervisor_label.clone());
+                unit = result
+                    .pm2_name
+                    .clone()
+                    .or(result.supervisord_program.clone())
+                    .or(result.supervisor_name.clone());
+
+                if let Some(action) = result.recommended_action.as_ref() {
+                    let action_label = match action.action_type {
+                        AppActionType::Stop => "stop",
+                        AppActionType::Restart => "restart",
+                        AppActionType::Delete => "delete",
+                        AppActionType::Status => "status",
+                        AppActionType::Logs => "logs",
+                    };
+                    recommended_action = format!("{}_{}", supervisor_label, action_label);
+                    supervisor_command = Some(action.command.clone());
+                } else {
+                    recommended_action = format!("{}_review", supervisor_label);
+                }
+            }
+        }
+    }
+
+    // systemd supervision
+    if !detected {
+        if let Some(unit_info) = collect_systemd_unit(pid, None) {
+            detected = true;
+            supervisor_type = Some("systemd".to_string());
+            unit = Some(unit_info.name.clone());
+            let (action_label, command) = match unit_info.unit_type {
+                pt_core::collect::systemd::SystemdUnitType::Scope => (
+                    "systemctl_stop",
+                    format!("systemctl stop {}", unit_info.name),
+                ),
+                _ => (
+                    "systemctl_restart",
+                    format!("systemctl restart {}", unit_info.name),
+                ),
+            };
+            recommended_action = action_label.to_string();
+            supervisor_command = Some(command);
+        }
+    }
+
+    // Human supervision (agents/IDEs/CI) for warning-only
+    if !detected {
+        if let Ok(result) = detect_supervision(pid) {
+            if is_human_supervised(&result) {
+                detected = true;
+                supervisor_type = result.supervisor_type.map(|t| t.to_string());
+                unit = result.supervisor_name.clone();
+                recommended_action = "review".to_string();
+            }
+        }
+    }
+
+    serde_json::json!({
+        "detected": detected,
+        "type": supervisor_type,
+        "unit": unit,
+        "recommended_action": recommended_action,
+        "supervisor_command": supervisor_command,
+    })
+}
+
+/// launchd job supervision on macOS.
+///
+/// A KeepAlive job comes straight back after a signal, so the job is booted
+/// out instead; other jobs stay down once killed.
+#[cfg(target_os = "macos")]
+fn supervisor_info_for_plan(pid: u32) -> serde_json::Value {
+    let Some(job) = pt_core::collect::detect_launchd_job(pid) else {
+        return serde_json::json!({
+            "detected": false,
+            "type": serde_json::Value::Null,
+            "unit": serde_json::Value::Null,
+            "recommended_action": "kill",
+            "supervisor_command": serde_json::Value::Null,
+        });
+    };
+    let (recommended_action, supervisor_command) = if job.respawn_expected() {
+        ("launchctl_bootout", Some(job.bootout_command()))
+    } else {
+        ("kill", None)
+    };
+    serde_json::json!({
+        "detected": true,
+        "type": "launchd",
+        "unit": job.label,
+        "recommended_action": recommended_action,
+        "supervisor_command": supervisor_command,
+        "respawn_expected": job.respawn_expected(),
+        "launchd": job,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn supervisor_info_for_plan(_pid: u32) -> serde_json::Value {
+    serde_json::json!({
+        "detected": false,
+        "type": serde_json::Value::Null,
+        "unit": serde_json::Value::Null,
+        "recommended_action": "kill",
+        "supervisor_command": serde_json::Value::Null,
+    })
+}
+
+/// Agent, IDE or CI supervisor of `pid` found in the scanned process table,
+/// standing in for the `/proc`-based human-supervision check off Linux.
+#[cfg(not(target_os = "linux"))]
+fn table_supervisor_info(
+    analyzer: &mut pt_core::supervision::AncestryAnalyzer,
+    pid: u32,
+) -> Option<serde_json::Value> {
+    let result = analyzer.analyze(pid).ok()?;
+    let human = result.is_supervised
+        && result.supervisor_type != Some(pt_core::supervision::SupervisorCategory::Orchestrator);
+    human.then(|| {
+        serde_json::json!({
+            "detected": true,
+            "type": result.supervisor_type.map(|t| t.to_string()),
+            "unit": result.supervisor_name,
+            "recommended_action": "review",
+            "supervisor_command": serde_json::Value::Null,
+        })
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn is_supervised_for_robot(pid: u32) -> bool {
+    match detect_supervision(pid) {
+        Ok(result) => is_human_supervised(&result),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_supervised_for_robot(_pid: u32) -> bool {
+    false
+}
+
+/// Outcome entry for an action held back by the policy schedule.
+fn schedule_deferred_outcome(
+    action: &PlanAction,
+    check: &pt_core::decision::ConstraintCheckResult,
+    until: Option<chrono::NaiveDateTime>,
+    elapsed_ms: Option<u64>,
+) -> serde_json::Value {
+    let reasons: Vec<&str> = check
+        .violations
+        .iter()
+        .map(|v| v.message.as_str())
+        .collect();
+    let mut outcome = serde_json::json!({
+        "action_id": action.action_id,
+        "pid": action.target.pid.0,
+        "status": "deferred_by_schedule",
+        "reason": reasons.join("; "),
+        "deferred_until": until.map(|at| at.format("%Y-%m-%dT%H:%M").to_string()),
+    });
+    if let Some(ms) = elapsed_ms {
+        outcome["time_ms"] = serde_json::json!(ms);
+    }
+    outcome
+}
+
+/// Earlier of two optional times.
+fn earliest(
+    a: Option<chrono::NaiveDateTime>,
+    b: Option<chrono::NaiveDateTime>,
+) -> Option<chrono::NaiveDateTime> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn first_precheck_block(
+    provider: &dyn pt_core::action::prechecks::PreCheckProvider,
+    action: &PlanAction,
+) -> Option<(pt_core::plan::PreCheck, String)> {
+    let results = provider.run_checks(&action.pre_checks, action.target.pid.0, action.target.sid);
+    for result in results {
+        if let pt_core::action::prechecks::PreCheckResult::Blocked { check, reason } = result {
+            return Some((check, reason));
+        }
+    }
+    if let Some(unit) = &action.unit_action {
+        if action
+            .pre_checks
+            .contains(&pt_core::plan::PreCheck::VerifyUnitMapping)
+        {
+            if let pt_core::action::prechecks::PreCheckResult::Blocked { check, reason } =
+                provider.check_unit_mapping(action.target.pid.0, unit)
+            {
+                return Some((check, reason));
+            }
+        }
+    }
+    None
+}
+
+/// Interactive review of constraint-blocked actions for `agent apply --review-blocked`.
+///
+/// Every approved override is written to the audit log before the action runs;
+/// if the audit log cannot be written the block stands.
+#[cfg(target_os = "linux")]
+struct OverrideReview {
+    ctx: pt_core::audit::AuditContext,
+    operator: String,
+    audit: Option<pt_core::audit::AuditLog>,
+    interactive: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl OverrideReview {
+    fn new(session_id: &str) -> Self {
+        use std::io::IsTerminal;
+        Self {
+            ctx: pt_core::audit::AuditContext::new(
+                pt_core::logging::generate_run_id(),
+                pt_core::logging::get_host_id(),
+            )
+            .with_session_id(session_id),
+            operator: operator_identity(),
+            audit: None,
+            interactive: std::io::stdin().is_terminal(),
+        }
+    }
+
+    /// Prompt for one blocked action and return the recorded override if approved.
+    fn review(
+        &mut self,
+        action: &PlanAction,
+        violations: &[ConstraintViolation],
+    ) -> Option<pt_core::audit::OverrideDetails> {
+        if !self.interactive {
+            eprintln!(
+                "agent apply: --review-blocked needs an interactive terminal; keeping block on PID {}",
+                action.target.pid.0
+            );
+            return None;
+        }
+        let target = format!(
+            "{} PID {} ({})",
+            action_label(action),
+            action.target.pid.0,
+            action.action_id
+        );
+        let justification = match prompt_override(
+            &target,
+            violations,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        ) {
+            Ok(Some(text)) => text,
+            Ok(None) => return None,
+            Err(e) => {
+                eprintln!("agent apply: failed to read override response: {}", e);
+                return None;
+            }
+        };
+        let details = pt_core::audit::OverrideDetails {
+            operator: self.operator.clone(),
+            justification,
+            action: action_label(action),
+            pid: action.target.pid.0,
+            start_id: Some(action.target.start_id.0.clone()),
+            overridden: violations.iter().map(constraint_label).collect(),
+        };
+        if self.audit.is_none() {
+            match pt_core::audit::AuditLog::open_or_create() {
+                Ok(log) => self.audit = Some(log),
+                Err(e) => {
+                    eprintln!(
+                        "agent apply: cannot open audit log, override refused: {}",
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+        let log = self.audit.as_mut()?;
+        if let Err(e) = log.log_override(&self.ctx, &details) {
+            eprintln!(
+                "agent apply: cannot record override, override refused: {}",
+                e
+            );
+            return None;
+        }
+        Some(details)
+    }
+}
+
+/// Operator identity for override records: the invoking user behind sudo, if any.
+#[cfg(target_os = "linux")]
+fn operator_identity() -> String {
+    let uid = std::env::var("SUDO_UID")
+        .ok()
+        .filter(|_| std::env::var_os("SUDO_USER").is_some())
+        .unwrap_or_else(|| unsafe { libc::getuid() }.to_string());
+    let name = std::env::var("SUDO_USER")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    format!("{} (uid {})", name, uid)
+}
+
+#[cfg(target_os = "linux")]
+fn action_label(action: &PlanAction) -> String {
+    format!("{:?}", action.action).to_lowercase()
+}
+
+#[cfg(target_os = "linux")]
+fn constraint_label(violation: &ConstraintViolation) -> String {
+    serde_json::to_value(violation.constraint)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", violation.constraint))
+}
+
+/// Show why an action was blocked and ask whether to override it.
+///
+/// Returns the operator's justification on approval. An override without a
+/// justification is declined.
+#[cfg(target_os = "linux")]
+fn prompt_override(
+    target: &str,
+    violations: &[ConstraintViolation],
+    input: &mut dyn std::io::BufRead,
+    out: &mut dyn Write,
+) -> std::io::Result<Option<String>> {
+    writeln!(out, "\nBlocked: {}", target)?;
+    for violation in violations {
+        writeln!(
+            out,
+            "  - {}: {} (threshold: {}, actual: {})",
+            constraint_label(violation),
+            violation.message,
+            violation.threshold,
+            violation.actual
+        )?;
+    }
+    write!(out, "Override this block? [y/N] ")?;
+    out.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    if !matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+        return Ok(None);
+    }
+    write!(out, "Justification: ")?;
+    out.flush()?;
+    let mut justification = String::new();
+    input.read_line(&mut justification)?;
+    let justification = justification.trim();
+    if justification.is_empty() {
+        writeln!(out, "No justification given; keeping block.")?;
+        return Ok(None);
+    }
+    Ok(Some(justification.to_string()))
+}
+
+fn precheck_label_for_apply(check: &pt_core::plan::PreCheck) -> &'static str {
+    use pt_core::plan::PreCheck;
+    match check {
+        PreCheck::VerifyIdentity => "verify_identity",
+        PreCheck::CheckNotProtected => "check_not_protected",
+        PreCheck::CheckSessionSafety => "check_session_safety",
+        PreCheck::CheckDataLossGate => "check_data_loss_gate",
+        PreCheck::CheckSupervisor => "check_supervisor",
+        PreCheck::CheckAgentSupervision => "check_agent_supervision",
+        PreCheck::VerifyProcessState => "verify_process_state",
+        PreCheck::CheckFreezeScope => "check_freeze_scope",
+        PreCheck::VerifyUnitMapping => "verify_unit_mapping",
+        PreCheck::CheckForegroundWork => "check_foreground_work",
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_mem_available_bytes_for_goal_progress() -> u64 {
+    std::fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                line.strip_prefix("MemAvailable:")
+                    .and_then(|rest| rest.split_whitespace().next())
+                    .and_then(|value| value.parse::<u64>().ok())
+            })
+        })
+        .map(|kb| kb.saturating_mul(1024))
+        .unwrap_or(0)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_mem_available_bytes_for_goal_progress() -> u64 {
+    0
+}
+
+#[cfg(target_os = "linux")]
+fn collect_occupied_ports_for_goal_progress() -> Vec<u16> {
+    let mut ports = BTreeSet::new();
+
+    if let Some(entries) = parse_proc_net_tcp("/proc/net/tcp", false) {
+        for entry in entries.into_iter().filter(|e| e.state.is_listen()) {
+            ports.insert(entry.local_port);
+        }
+    }
+    if let Some(entries) = parse_proc_net_tcp("/proc/net/tcp6", true) {
+        for entry in entries.into_iter().filter(|e| e.state.is_listen()) {
+            ports.insert(entry.local_port);
+        }
+    }
+    if let Some(entries) = parse_proc_net_udp("/proc/net/udp", false) {
+        for entry in entries
+            .into_iter()
+            .filter(|e| e.local_port > 0 && e.remote_port == 0)
+        {
+            ports.insert(entry.local_port);
+        }
+    }
+    if let Some(entries) = parse_proc_net_udp("/proc/net/udp6", true) {
+        for entry in entries
+            .into_iter()
+            .filter(|e| e.local_port > 0 && e.remote_port == 0)
+        {
+            ports.insert(entry.local_port);
+        }
+    }
+
+    ports.into_iter().collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_occupied_ports_for_goal_progress() -> Vec<u16> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn collect_total_fds_for_goal_progress(processes: &[ProcessRecord]) -> u64 {
+    processes
+        .iter()
+        .filter_map(|proc| parse_fd(proc.pid.0).map(|fd| fd.count as u64))
+        .sum()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_total_fds_for_goal_progress(_processes: &[ProcessRecord]) -> u64 {
+    0
+}
+
+fn capture_metric_snapshot_for_goal_progress(processes: &[ProcessRecord]) -> MetricSnapshot {
+    let total_cpu_frac = processes
+        .iter()
+        .map(|proc| (proc.cpu_percent / 100.0).max(0.0))
+        .sum();
+
+    MetricSnapshot {
+        available_memory_bytes: read_mem_available_bytes_for_goal_progress(),
+        total_cpu_frac,
+        occupied_ports: collect_occupied_ports_for_goal_progress(),
+        total_fds: collect_total_fds_for_goal_progress(processes),
+        timestamp: chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
+    }
+}
+
+fn normalize_command_signature_for_goal_progress(cmd: &str) -> String {
+    cmd.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn detect_respawn_for_goal_progress(
+    action: &PlanAction,
+    action_success: bool,
+    before_by_pid: &HashMap<u32, &ProcessRecord>,
+    after_by_pid: &HashMap<u32, &ProcessRecord>,
+    after_processes: &[ProcessRecord],
+) -> bool {
+    if !action_success {
+        return false;
+    }
+
+    let pid = action.target.pid.0;
+    let Some(before_proc) = before_by_pid.get(&pid).copied() else {
+        return false;
+    };
+
+    if let Some(after_proc) = after_by_pid.get(&pid).copied() {
+        return after_proc.start_id.0 != before_proc.start_id.0;
+    }
+
+    let before_cmd = normalize_command_signature_for_goal_progress(&before_proc.cmd);
+    after_processes.iter().any(|proc| {
+        proc.uid == before_proc.uid
+            && proc.pid.0 != pid
+            && proc.start_time_unix >= before_proc.start_time_unix
+            && normalize_command_signature_for_goal_progress(&proc.cmd) == before_cmd
+    })
+}
+
+fn goal_report_brief_json(report: &GoalProgressReport) -> serde_json::Value {
+    serde_json::json!({
+        "expected": report.expected_progress,
+        "observed": report.observed_progress,
+        "discrepancy": report.discrepancy,
+        "discrepancy_fraction": report.discrepancy_fraction,
+        "classification": report.classification.to_string(),
+        "suspected_causes": report.suspected_causes.iter().map(|cause| cause.cause.clone()).collect::<Vec<_>>(),
+    })
+}
+
+fn run_agent_apply(global: &GlobalOpts, args: &AgentApplyArgs) -> ExitCode {
+    let access = if global.dry_run || global.shadow {
+        LockAccess::ReadOnly
+    } else {
+        LockAccess::Exclusive
+    };
+    let mut global_lock = match acquire_global_lock(global, "agent apply", access) {
+        Ok(lock) => lock,
+        Err(code) => return code,
+    };
+    if let Some(lock) = global_lock.as_mut() {
+        let _ = lock.set_session(&args.session);
+    }
+    // Load configuration
+    let config = match load_config(&config_options(global)) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            eprintln!("agent apply: config error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    // Open session store and session
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent apply: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let sid = match SessionId::parse(&args.session) {
+        Some(sid) => sid,
+        None => {
+            eprintln!("agent apply: invalid --session {}", args.session);
+            return ExitCode::ArgsError;
+        }
+    };
+    let handle = match store.open(&sid) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("agent apply: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+
+    // Refuse up front rather than act and then fail to record outcomes.
+    let mut write_guard = telemetry_write_guard(global);
+    if let QuotaDecision::Skip { reason } = write_guard.check(
+        &handle.dir,
+        SESSION_WRITE_ESTIMATE_BYTES,
+        WritePriority::Essential,
+    ) {
+        eprintln!(
+            "agent apply: not enough disk space to record outcomes: {}",
+            reason
+        );
+        return ExitCode::IoError;
+    }
+
+    let session_lifecycle = SessionLifecycle::start(global, &handle, &sid);
+    let emitter = session_lifecycle.emitter();
+
+    // Load the plan from decision/plan.json
+    let plan_path = handle.dir.join("decision").join("plan.json");
+    if !plan_path.exists() {
+        eprintln!("agent apply: no plan.json found for session {}", sid);
+        return ExitCode::ArgsError;
+    }
+    let plan_content = match std::fs::read_to_string(&plan_path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("agent apply: failed to read {}: {}", plan_path.display(), e);
+            return ExitCode::IoError;
+        }
+    };
+    let plan: Plan = match serde_json::from_str(&plan_content) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("agent apply: invalid plan.json: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    // Load completed action IDs for --resume mode
+    let completed_action_ids: std::collections::HashSet<String> = if args.resume {
+        let outcomes_path = handle.dir.join("action").join("outcomes.jsonl");
+        if outcomes_path.exists() {
+            std::fs::read_to_string(&outcomes_path)
+                .ok()
+                .map(|content| {
+                    content
+                        .lines()
+                        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+                        .filter(|v| v.get("status").and_then(|s| s.as_str()) == Some("success"))
+                        .filter_map(|v| {
+                            v.get("action_id")
+                                .and_then(|a| a.as_str())
+                                .map(String::from)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            std::collections::HashSet::new()
+        }
+    } else {
+        std::collections::HashSet::new()
+    };
+    // Staged executions also persist a checkpoint; merge it when it matches this plan.
+    let mut completed_action_ids = completed_action_ids;
+    if args.resume {
+        let checkpoint_path = handle.dir.join("action").join("checkpoint.json");
+        if let Ok(Some(checkpoint)) = pt_core::action::ExecutionCheckpoint::load(&checkpoint_path) {
+            if checkpoint.plan_id == plan.plan_id {
+                completed_action_ids.extend(checkpoint.succeeded_action_ids);
+            }
+        }
+    }
+
+    let group_selectors: Vec<pt_core::plan::groups::GroupSelector> = match args
+        .groups
+        .iter()
+        .map(|g| pt_core::plan::groups::GroupSelector::parse(g))
+        .collect()
+    {
+        Ok(selectors) => selectors,
+        Err(e) => {
+            eprintln!("agent apply: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+
+    // Determine which actions to apply
+    let use_recommended = args.recommended
+        || ((args.resume || !group_selectors.is_empty())
+            && args.pids.is_empty()
+            && args.targets.is_empty());
+    let mut target_pids: Vec<u32> = if use_recommended {
+        plan.actions
+            .iter()
+            .filter(|a| !a.blocked)
+            .map(|a| a.target.pid.0)
+            .collect()
+    } else if !args.pids.is_empty() {
+        args.pids.clone()
+    } else if !args.targets.is_empty() {
+        args.targets
+            .iter()
+            .filter_map(|t| t.split(':').next().and_then(|p| p.parse().ok()))
+            .collect()
+    } else {
+        eprintln!("agent apply: must specify --recommended, --pids, --targets, or --group");
+        return ExitCode::ArgsError;
+    };
+
+    // Keep targets whose live user/cgroup/container matches a --group selector.
+    if !group_selectors.is_empty() && !target_pids.is_empty() {
+        let scan_options = QuickScanOptions {
+            pids: target_pids.clone(),
+            include_kernel_threads: false,
+            timeout: global.timeout.map(std::time::Duration::from_secs),
+            progress: None,
+            incremental: None,
+            io_rate_window: None,
+        };
+        let mut scan_result = match quick_scan(&scan_options) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("agent apply: group scan failed: {}", e);
+                return ExitCode::InternalError;
+            }
+        };
+        pt_core::collect::annotate_processes(
+            &mut scan_result.processes,
+            &pt_core::collect::PodIndex::load(),
+        );
+        let in_group: HashSet<u32> = scan_result
+            .processes
+            .iter()
+            .filter(|proc| {
+                let groups = pt_core::plan::groups::ProcessGroups::collect(proc);
+                group_selectors.iter().any(|sel| sel.matches(&groups))
+            })
+            .map(|proc| proc.pid.0)
+            .collect();
+        target_pids.retain(|pid| in_group.contains(pid));
+    }
+
+    if let Some(min_age) = args.min_age {
+        if !target_pids.is_empty() {
+            let scan_options = QuickScanOptions {
+                pids: target_pids.clone(),
+                include_kernel_threads: false,
+                timeout: global.timeout.map(std::time::Duration::from_secs),
+                progress: None,
+                incremental: None,
+                io_rate_window: None,
+            };
+            let scan_result = match quick_scan(&scan_options) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!("agent apply: min-age scan failed: {}", e);
+                    return ExitCode::InternalError;
+                }
+            };
+            let eligible: HashSet<u32> = scan_result
+                .processes
+                .iter()
+                .filter(|proc| proc.elapsed.as_secs() >= min_age)
+                .map(|proc| proc.pid.0)
+                .collect();
+            target_pids.retain(|pid| eligible.contains(pid));
+        }
+    }
+
+    if target_pids.is_empty() {
+        output_apply_nothing(global, &sid);
+        return ExitCode::Clean;
+    }
+
+    // Filter out completed actions using earlier declaration for --resume mode
+    let actions_to_apply: Vec<_> = plan
+        .actions
+        .iter()
+        .filter(|a| target_pids.contains(&a.target.pid.0))
+        .filter(|a| !completed_action_ids.contains(&a.action_id))
+        .collect();
+    if actions_to_apply.is_empty() {
+        output_apply_nothing(global, &sid);
+        return ExitCode::Clean;
+    }
+
+    let goal_progress_scan_options = QuickScanOptions {
+        pids: vec![],
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+
+    let before_scan_processes = quick_scan(&goal_progress_scan_options)
+        .map(|scan| scan.processes)
+        .unwrap_or_else(|_| Vec::new());
+    let before_snapshot = capture_metric_snapshot_for_goal_progress(&before_scan_processes);
+    let before_by_pid: HashMap<u32, &ProcessRecord> = before_scan_processes
+        .iter()
+        .map(|proc| (proc.pid.0, proc))
+        .collect();
+
+    #[cfg(target_os = "linux")]
+    let before_network_snapshot = NetworkSnapshot::collect();
+
+    let mut expected_by_action: HashMap<String, (f64, f64, f64, f64, String)> = HashMap::new();
+    for action in &actions_to_apply {
+        let pid = action.target.pid.0;
+        let before_proc = before_by_pid.get(&pid).copied();
+        let label = before_proc
+            .map(|proc| proc.cmd.clone())
+            .unwrap_or_else(|| format!("pid {}", pid));
+
+        let memory_expected = if matches!(
+            action.action,
+            Action::Kill | Action::Restart | Action::Pause
+        ) {
+            action
+                .rationale
+                .memory_mb
+                .unwrap_or(0.0)
+                .max(0.0)
+                .mul_add(1_048_576.0, 0.0)
+        } else {
+            0.0
+        };
+        let cpu_expected = if matches!(
+            action.action,
+            Action::Kill
+                | Action::Restart
+                | Action::Pause
+                | Action::Freeze
+                | Action::Quarantine
+                | Action::Throttle
+        ) {
+            before_proc
+                .map(|proc| (proc.cpu_percent / 100.0).max(0.0))
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        #[cfg(target_os = "linux")]
+        let port_expected = if matches!(
+            action.action,
+            Action::Kill | Action::Restart | Action::Pause | Action::Freeze
+        ) {
+            before_network_snapshot
+                .get_process_info(pid)
+                .map(|info| {
+                    info.listen_ports
+                        .iter()
+                        .map(|port| port.port)
+                        .collect::<HashSet<_>>()
+                        .len() as f64
+                })
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        #[cfg(not(target_os = "linux"))]
+        let port_expected = 0.0;
+
+        #[cfg(target_os = "linux")]
+        let fd_expected = if matches!(
+            action.action,
+            Action::Kill | Action::Restart | Action::Pause | Action::Freeze
+        ) {
+            parse_fd(pid).map(|fd| fd.count as f64).unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        #[cfg(not(target_os = "linux"))]
+        let fd_expected = 0.0;
+
+        expected_by_action.insert(
+            action.action_id.clone(),
+            (
+                memory_expected,
+                cpu_expected,
+                port_expected,
+                fd_expected,
+                label,
+            ),
+        );
+    }
+
+    let total_actions = actions_to_apply.len() as u64;
+    let mut action_index = 0u64;
+    let emit_action_event = |event_name: &str,
+                             index: u64,
+                             elapsed_ms: Option<u64>,
+                             action: &PlanAction,
+                             status: &str,
+                             extra: &[(&str, serde_json::Value)]| {
+        if let Some(ref e) = emitter {
+            let mut event = ProgressEvent::new(event_name, Phase::Apply)
+                .with_progress(index, Some(total_actions))
+                .with_detail("action_id", &action.action_id)
+                .with_detail("pid", action.target.pid.0)
+                .with_detail("action", format!("{:?}", action.action))
+                .with_detail("status", status);
+            if let Some(ms) = elapsed_ms {
+                event = event.with_elapsed_ms(ms);
+            }
+            for (key, value) in extra {
+                event = event.with_detail(*key, value);
+            }
+            e.emit(event);
+        }
+    };
+
+    // Check --yes requirement
+    if !args.yes && !global.dry_run && !global.shadow {
+        let err = serde_json::json!({"session_id": sid.0, "error": "confirmation_required", "message": "--yes flag required for execution"});
+        println!("{}", serde_json::to_string_pretty(&err).unwrap());
+        return ExitCode::PolicyBlocked;
+    }
+
+    // Host health gate: under extreme load, signalling and verification can make
+    // things worse or time out misleadingly, so defer until the host recovers.
+    if !global.dry_run && !global.shadow {
+        use pt_core::action::{check_host_health, LiveHostHealthProbe};
+
+        let decision = check_host_health(
+            &config.policy.apply_health_gate,
+            &LiveHostHealthProbe,
+            args.wait_healthy,
+            args.health_timeout.map(std::time::Duration::from_secs),
+        );
+        if !decision.healthy {
+            let output = serde_json::json!({
+                "session_id": sid.0,
+                "status": "deferred",
+                "error": "host_unhealthy",
+                "message": format!("apply deferred: {}", decision.reason()),
+                "health_gate": decision,
+                "retry_command": format!("pt agent apply --session {} --wait-healthy", sid.0),
+            });
+            match global.format {
+                OutputFormat::Json | OutputFormat::Toon => {
+                    println!("{}", format_structured_output(global, output));
+                }
+                OutputFormat::Exitcode => {}
+                _ => {
+                    eprintln!(
+                        "agent apply: deferred, host unhealthy: {}",
+                        decision.reason()
+                    );
+                }
+            }
+            return ExitCode::PolicyBlocked;
+        }
+    }
+
+    // Build robot constraints from policy + CLI overrides
+    let constraints = RuntimeRobotConstraints::from_policy(&config.policy.robot_mode)
+        .with_min_posterior(args.min_posterior)
+        .with_max_blast_radius_mb(args.max_blast_radius)
+        .with_max_total_blast_radius_mb(args.max_total_blast_radius)
+        .with_max_kills(args.max_kills)
+        .with_require_known_signature(if args.require_known_signature {
+            Some(true)
+        } else {
+            None
+        })
+        .with_allow_categories(if args.only_categories.is_empty() {
+            None
+        } else {
+            Some(args.only_categories.clone())
+        })
+        .with_exclude_categories(args.exclude_categories.clone())
+        .with_schedule(config.policy.schedule.clone());
+
+    let checker = ConstraintChecker::new(constraints.clone());
+    let constraints_summary = constraints.active_constraints_summary();
+    let _ = handle.update_state(SessionState::Executing);
+
+    #[cfg(target_os = "linux")]
+    let precheck_provider = {
+        use pt_core::action::{LivePreCheckConfig, LivePreCheckProvider};
+        LivePreCheckProvider::new(
+            Some(&config.policy.guardrails),
+            LivePreCheckConfig::from(&config.policy.data_loss_gates),
+        )
+        .unwrap_or_else(|_| LivePreCheckProvider::with_defaults())
+    };
+
+    let mut outcomes: Vec<serde_json::Value> = Vec::new();
+    let mut succeeded = 0usize;
+    let mut failed = 0usize;
+    let mut skipped = 0usize;
+    let mut blocked_by_constraints = 0usize;
+    let mut deferred_by_schedule = 0usize;
+    let mut next_window: Option<chrono::NaiveDateTime> = None;
+    let mut blocked_by_prechecks = 0usize;
+    let mut resumed_skipped = 0usize;
+    #[allow(unused_mut)]
+    let mut operator_overrides: Vec<serde_json::Value> = Vec::new();
+    #[cfg(target_os = "linux")]
+    let mut override_review = if args.review_blocked && !global.dry_run && !global.shadow {
+        Some(OverrideReview::new(&sid.0))
+    } else {
+        None
+    };
+
+    // Handle dry-run/shadow mode or execute
+    if global.dry_run || global.shadow {
+        for action in &actions_to_apply {
+            action_index = action_index.saturating_add(1);
+            emit_action_event(
+                pt_core::events::event_names::ACTION_STARTED,
+                action_index,
+                None,
+                action,
+                "started",
+                &[(
+                    "mode",
+                    serde_json::json!(if global.dry_run { "dry_run" } else { "shadow" }),
+                )],
+            );
+
+            // Skip already completed actions in resume mode
+            if completed_action_ids.contains(&action.action_id) {
+                resumed_skipped += 1;
+                outcomes.push(serde_json::json!({
+                    "action_id": action.action_id,
+                    "pid": action.target.pid.0,
+                    "status": "already_completed",
+                    "resume": true
+                }));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "already_completed",
+                    &[("resume", serde_json::json!(true))],
+                );
+                continue;
+            }
+
+            if action.blocked {
+                blocked_by_prechecks += 1;
+                outcomes.push(serde_json::json!({
+                    "action_id": action.action_id,
+                    "pid": action.target.pid.0,
+                    "status": "blocked_by_plan"
+                }));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "blocked_by_plan",
+                    &[],
+                );
+                continue;
+            }
+
+            let candidate = RobotCandidate {
+                posterior: action.rationale.posterior_odds_abandoned_vs_useful,
+                memory_mb: None,
+                has_known_signature: false,
+                category: action.rationale.category.clone(),
+                is_kill_action: action.action == Action::Kill,
+                has_policy_snapshot: true,
+                is_supervised: is_supervised_for_robot(action.target.pid.0),
+                is_destructive: action.action == Action::Restart,
+            };
+            let check = checker.check_candidate(&candidate);
+            if check.deferred_by_schedule() {
+                deferred_by_schedule += 1;
+                let until = checker.deferred_until(&candidate);
+                next_window = earliest(next_window, until);
+                outcomes.push(schedule_deferred_outcome(action, &check, until, None));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "deferred_by_schedule",
+                    &[],
+                );
+                continue;
+            }
+            if !check.allowed {
+                blocked_by_constraints += 1;
+                outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "blocked_by_constraints"}));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "blocked_by_constraints",
+                    &[],
+                );
+                continue;
+            }
+
+            #[cfg(target_os = "linux")]
+            if let Some((check, reason)) = first_precheck_block(&precheck_provider, action) {
+                blocked_by_prechecks += 1;
+                outcomes.push(serde_json::json!({
+                    "action_id": action.action_id,
+                    "pid": action.target.pid.0,
+                    "status": "precheck_blocked",
+                    "check": precheck_label_for_apply(&check),
+                    "reason": reason
+                }));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "precheck_blocked",
+                    &[("check", serde_json::json!(precheck_label_for_apply(&check)))],
+                );
+                continue;
+            }
+
+            skipped += 1;
+            #[allow(unused_mut)]
+            let mut outcome = serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": if global.dry_run { "dry_run" } else { "shadow" }});
+            #[cfg(target_os = "linux")]
+            if let Some(container_action) = pt_core::action::ContainerActionRunner::new(
+                pt_core::action::ContainerActionConfig::from(&config.policy.container_actions),
+            )
+            .plan_for(action.target.pid.0, action.action)
+            {
+                outcome["container_action"] = serde_json::json!(container_action);
+            }
+            if let Some(unit_action) = &action.unit_action {
+                outcome["unit_action"] = serde_json::json!({
+                    "unit": unit_action.unit,
+                    "manager": unit_action.manager,
+                    "command": unit_action.command,
+                    "display_command": unit_action.display_command(),
+                });
+            }
+            if let Some(command_action) = &action.command_action {
+                outcome["command_action"] = command_action_json(command_action);
+            }
+            outcomes.push(outcome);
+            emit_action_event(
+                pt_core::events::event_names::ACTION_COMPLETE,
+                action_index,
+                None,
+                action,
+                if global.dry_run { "dry_run" } else { "shadow" },
+                &[],
+            );
+        }
+    } else {
+        #[cfg(target_os = "linux")]
+        {
+            let identity_provider = LiveIdentityProvider::new();
+            let signal_runner = SignalActionRunner::new(SignalConfig::default());
+            let container_runner = pt_core::action::ContainerActionRunner::new(
+                pt_core::action::ContainerActionConfig::from(&config.policy.container_actions),
+            );
+            let unit_runner = pt_core::action::SystemdUnitActionRunner::with_defaults();
+            let command_runner = pt_core::action::CommandActionRunner::with_defaults();
+
+            for action in &actions_to_apply {
+                action_index = action_index.saturating_add(1);
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_STARTED,
+                    action_index,
+                    None,
+                    action,
+                    "started",
+                    &[],
+                );
+
+                // Skip already completed actions in resume mode
+                if completed_action_ids.contains(&action.action_id) {
+                    resumed_skipped += 1;
+                    outcomes.push(serde_json::json!({
+                        "action_id": action.action_id,
+                        "pid": action.target.pid.0,
+                        "status": "already_completed",
+                        "resume": true
+                    }));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        None,
+                        action,
+                        "already_completed",
+                        &[("resume", serde_json::json!(true))],
+                    );
+                    continue;
+                }
+
+                if action.blocked {
+                    blocked_by_prechecks += 1;
+                    outcomes.push(serde_json::json!({
+                        "action_id": action.action_id,
+                        "pid": action.target.pid.0,
+                        "status": "blocked_by_plan"
+                    }));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        None,
+                        action,
+                        "blocked_by_plan",
+                        &[],
+                    );
+                    if args.abort_on_unknown {
+                        break;
+                    }
+                    continue;
+                }
+
+                let start = std::time::Instant::now();
+                let candidate = RobotCandidate {
+                    posterior: action.rationale.posterior_odds_abandoned_vs_useful,
+                    memory_mb: None,
+                    has_known_signature: false,
+                    category: action.rationale.category.clone(),
+                    is_kill_action: action.action == Action::Kill,
+                    has_policy_snapshot: true,
+                    is_supervised: is_supervised_for_robot(action.target.pid.0),
+                    is_destructive: action.action == Action::Restart,
+                };
+                let check = checker.check_candidate(&candidate);
+                let overridden = !check.allowed
+                    && match override_review.as_mut() {
+                        Some(review) => match review.review(action, &check.violations) {
+                            Some(details) => {
+                                operator_overrides.push(serde_json::json!(details));
+                                true
+                            }
+                            None => false,
+                        },
+                        None => false,
+                    };
+                if !overridden && check.deferred_by_schedule() {
+                    deferred_by_schedule += 1;
+                    let until = checker.deferred_until(&candidate);
+                    next_window = earliest(next_window, until);
+                    let elapsed_ms = start.elapsed().as_millis() as u64;
+                    outcomes.push(schedule_deferred_outcome(
+                        action,
+                        &check,
+                        until,
+                        Some(elapsed_ms),
+                    ));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        Some(elapsed_ms),
+                        action,
+                        "deferred_by_schedule",
+                        &[],
+                    );
+                    continue;
+                }
+                if !check.allowed && !overridden {
+                    blocked_by_constraints += 1;
+                    let elapsed_ms = start.elapsed().as_millis() as u64;
+                    outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "blocked_by_constraints", "time_ms": elapsed_ms}));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        Some(elapsed_ms),
+                        action,
+                        "blocked_by_constraints",
+                        &[],
+                    );
+                    if args.abort_on_unknown {
+                        break;
+                    }
+                    continue;
+                }
+                match identity_provider.revalidate(&action.target) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        failed += 1;
+                        let elapsed_ms = start.elapsed().as_millis() as u64;
+                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "identity_mismatch", "time_ms": elapsed_ms}));
+                        emit_action_event(
+                            pt_core::events::event_names::ACTION_FAILED,
+                            action_index,
+                            Some(elapsed_ms),
+                            action,
+                            "identity_mismatch",
+                            &[],
+                        );
+                        if args.abort_on_unknown {
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(_) => {
+                        failed += 1;
+                        let elapsed_ms = start.elapsed().as_millis() as u64;
+                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "identity_check_failed", "time_ms": elapsed_ms}));
+                        emit_action_event(
+                            pt_core::events::event_names::ACTION_FAILED,
+                            action_index,
+                            Some(elapsed_ms),
+                            action,
+                            "identity_check_failed",
+                            &[],
+                        );
+                        if args.abort_on_unknown {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+                if let Some((check, reason)) = first_precheck_block(&precheck_provider, action) {
+                    blocked_by_prechecks += 1;
+                    let elapsed_ms = start.elapsed().as_millis() as u64;
+                    outcomes.push(serde_json::json!({
+                        "action_id": action.action_id,
+                        "pid": action.target.pid.0,
+                        "status": "precheck_blocked",
+                        "check": precheck_label_for_apply(&check),
+                        "reason": reason,
+                        "time_ms": elapsed_ms
+                    }));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        Some(elapsed_ms),
+                        action,
+                        "precheck_blocked",
+                        &[("check", serde_json::json!(precheck_label_for_apply(&check)))],
+                    );
+                    if args.abort_on_unknown {
+                        break;
+                    }
+                    continue;
+                }
+                // A container's init is stopped or paused through its runtime.
+                let container_target = container_runner.target(action);
+                if let Some(reason) = container_target
+                    .as_ref()
+                    .and_then(|target| container_runner.blocked_reason(target))
+                {
+                    blocked_by_prechecks += 1;
+                    let elapsed_ms = start.elapsed().as_millis() as u64;
+                    outcomes.push(serde_json::json!({
+                        "action_id": action.action_id,
+                        "pid": action.target.pid.0,
+                        "status": "precheck_blocked",
+                        "check": "container_action",
+                        "reason": reason,
+                        "container": container_target,
+                        "time_ms": elapsed_ms
+                    }));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        Some(elapsed_ms),
+                        action,
+                        "precheck_blocked",
+                        &[("check", serde_json::json!("container_action"))],
+                    );
+                    if args.abort_on_unknown {
+                        break;
+                    }
+                    continue;
+                }
+                // A service's main process is stopped or restarted through its unit.
+                let result = if action.unit_action.is_some() {
+                    unit_runner
+                        .execute(action)
+                        .and_then(|()| unit_runner.verify(action))
+                } else if container_target.is_some() {
+                    container_runner.execute(action)
+                } else if action.action == Action::Kill && action.command_action.is_some() {
+                    // The signature's shutdown command first, signals as fallback.
+                    command_runner
+                        .execute(action)
+                        .and_then(|()| command_runner.verify(action))
+                } else {
+                    signal_runner.execute(action)
+                };
+                match result {
+                    Ok(()) => {
+                        if action.action == Action::Kill {
+                            checker.record_action(0, true);
+                        }
+                        succeeded += 1;
+                        let elapsed_ms = start.elapsed().as_millis() as u64;
+                        let mut outcome = serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "success", "time_ms": elapsed_ms});
+                        if let Some(target) = &container_target {
+                            outcome["container"] = serde_json::json!(target);
+                        }
+                        if let Some(unit_action) = &action.unit_action {
+                            outcome["unit"] = serde_json::json!(unit_action.display_command());
+                        }
+                        if let Some(command_action) = &action.command_action {
+                            outcome["command"] =
+                                serde_json::json!(command_action.display_command());
+                        }
+                        outcomes.push(outcome);
+                        emit_action_event(
+                            pt_core::events::event_names::ACTION_COMPLETE,
+                            action_index,
+                            Some(elapsed_ms),
+                            action,
+                            "success",
+                            &[],
+                        );
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        let elapsed_ms = start.elapsed().as_millis() as u64;
+                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "failed", "error": format!("{:?}", e), "time_ms": elapsed_ms}));
+                        emit_action_event(
+                            pt_core::events::event_names::ACTION_FAILED,
+                            action_index,
+                            Some(elapsed_ms),
+                            action,
+                            "failed",
+                            &[("error", serde_json::json!(format!("{:?}", e)))],
+                        );
+                        if args.abort_on_unknown {
+                            break;
+                        }
+                    }
+                }
+            }
         }
-    }
-    if let Some(entries) = parse_proc_net_udp("/proc/net/udp6", true) {
-        for entry in entries
-            .into_iter()
-            .filter(|e| e.local_port > 0 && e.remote_port == 0)
+        #[cfg(not(target_os = "linux"))]
         {
-            ports.insert(entry.local_port);
+            for action in &actions_to_apply {
+                action_index = action_index.saturating_add(1);
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_STARTED,
+                    action_index,
+                    None,
+                    action,
+                    "started",
+                    &[],
+                );
+
+                // Skip already completed actions in resume mode
+                if completed_action_ids.contains(&action.action_id) {
+                    resumed_skipped += 1;
+                    outcomes.push(serde_json::json!({
+                        "action_id": action.action_id,
+                        "pid": action.target.pid.0,
+                        "status": "already_completed",
+                        "resume": true
+                    }));
+                    emit_action_event(
+                        pt_core::events::event_names::ACTION_COMPLETE,
+                        action_index,
+                        None,
+                        action,
+                        "already_completed",
+                        &[("resume", serde_json::json!(true))],
+                    );
+                    continue;
+                }
+                skipped += 1;
+                outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "unsupported_platform"}));
+                emit_action_event(
+                    pt_core::events::event_names::ACTION_COMPLETE,
+                    action_index,
+                    None,
+                    action,
+                    "unsupported_platform",
+                    &[],
+                );
+            }
         }
     }
 
-    ports.into_iter().collect()
-}
-
-#[cfg(not(target_os = "linux"))]
-fn collect_occupied_ports_for_goal_progress() -> Vec<u16> {
-    Vec::new()
-}
-
-#[cfg(target_os = "linux")]
-fn collect_total_fds_for_goal_progress(processes: &[ProcessRecord]) -> u64 {
-    processes
+    let after_scan_processes = quick_scan(&goal_progress_scan_options)
+        .map(|scan| scan.processes)
+        .unwrap_or_else(|_| Vec::new());
+    let after_snapshot = capture_metric_snapshot_for_goal_progress(&after_scan_processes);
+    let after_by_pid: HashMap<u32, &ProcessRecord> = after_scan_processes
         .iter()
-        .filter_map(|proc| parse_fd(proc.pid.0).map(|fd| fd.count as u64))
-        .sum()
-}
-
-#[cfg(not(target_os = "linux"))]
-fn collect_total_fds_for_goal_progress(_processes: &[ProcessRecord]) -> u64 {
-    0
-}
+        .map(|proc| (proc.pid.0, proc))
+        .collect();
 
-fn capture_metric_snapshot_for_goal_progress(processes: &[ProcessRecord]) -> MetricSnapshot {
-    let total_cpu_frac = processes
+    let status_by_action: HashMap<String, String> = outcomes
         .iter()
-        .map(|proc| (proc.cpu_percent / 100.0).max(0.0))
-        .sum();
-
-    MetricSnapshot {
-        available_memory_bytes: read_mem_available_bytes_for_goal_progress(),
-        total_cpu_frac,
-        occupied_ports: collect_occupied_ports_for_goal_progress(),
-        total_fds: collect_total_fds_for_goal_progress(processes),
-        timestamp: chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
-    }
-}
-
-fn normalize_command_signature_for_goal_progress(cmd: &str) -> String {
-    cmd.split_whitespace().collect::<Vec<_>>().join(" ")
-}
+        .filter_map(|outcome| {
+            let action_id = outcome.get("action_id")?.as_str()?.to_string();
+            let status = outcome
+                .get("status")
+                .and_then(|value| value.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            Some((action_id, status))
+        })
+        .collect();
 
-fn detect_respawn_for_goal_progress(
-    action: &PlanAction,
-    action_success: bool,
-    before_by_pid: &HashMap<u32, &ProcessRecord>,
-    after_by_pid: &HashMap<u32, &ProcessRecord>,
-    after_processes: &[ProcessRecord],
-) -> bool {
-    if !action_success {
-        return false;
+    let kill_decisions: Vec<DecisionRecord> = actions_to_apply
+        .iter()
+        .filter(|action| action.action == Action::Kill)
+        .filter(|action| {
+            status_by_action.get(&action.action_id).map(String::as_str) == Some("success")
+        })
+        .filter_map(|action| before_by_pid.get(&action.target.pid.0))
+        .map(|proc| DecisionRecord::new(&proc.comm, &proc.cmd, DecisionAction::Kill))
+        .collect();
+    if !kill_decisions.is_empty() {
+        pt_core::signature_cli::record_decisions(&kill_decisions);
     }
 
-    let pid = action.target.pid.0;
-    let Some(before_proc) = before_by_pid.get(&pid).copied() else {
-        return false;
-    };
+    let mut respawn_by_action: HashMap<String, bool> = HashMap::new();
+    let mut memory_action_outcomes = Vec::new();
+    let mut cpu_action_outcomes = Vec::new();
+    let mut port_action_outcomes = Vec::new();
+    let mut fd_action_outcomes = Vec::new();
+    for action in &actions_to_apply {
+        let status = status_by_action
+            .get(&action.action_id)
+            .map(|value| value.as_str())
+            .unwrap_or("unknown");
+        let success = matches!(status, "success" | "dry_run" | "shadow");
+        let respawn_detected = detect_respawn_for_goal_progress(
+            action,
+            success,
+            &before_by_pid,
+            &after_by_pid,
+            &after_scan_processes,
+        );
+        respawn_by_action.insert(action.action_id.clone(), respawn_detected);
 
-    if let Some(after_proc) = after_by_pid.get(&pid).copied() {
-        return after_proc.start_id.0 != before_proc.start_id.0;
-    }
+        let (memory_expected, cpu_expected, port_expected, fd_expected, label) = expected_by_action
+            .get(&action.action_id)
+            .cloned()
+            .unwrap_or_else(|| (0.0, 0.0, 0.0, 0.0, format!("pid {}", action.target.pid.0)));
 
-    let before_cmd = normalize_command_signature_for_goal_progress(&before_proc.cmd);
-    after_processes.iter().any(|proc| {
-        proc.uid == before_proc.uid
-            && proc.pid.0 != pid
-            && proc.start_time_unix >= before_proc.start_time_unix
-            && normalize_command_signature_for_goal_progress(&proc.cmd) == before_cmd
-    })
-}
+        let action_pid = action.target.pid.0;
+        memory_action_outcomes.push(GoalActionOutcome {
+            pid: action_pid,
+            label: label.clone(),
+            success,
+            respawn_detected,
+            expected_contribution: memory_expected,
+        });
+        cpu_action_outcomes.push(GoalActionOutcome {
+            pid: action_pid,
+            label: label.clone(),
+            success,
+            respawn_detected,
+            expected_contribution: cpu_expected,
+        });
+        port_action_outcomes.push(GoalActionOutcome {
+            pid: action_pid,
+            label: label.clone(),
+            success,
+            respawn_detected,
+            expected_contribution: port_expected,
+        });
+        fd_action_outcomes.push(GoalActionOutcome {
+            pid: action_pid,
+            label,
+            success,
+            respawn_detected,
+            expected_contribution: fd_expected,
+        });
+    }
 
-fn goal_report_brief_json(report: &GoalProgressReport) -> serde_json::Value {
-    serde_json::json!({
-        "expected": report.expected_progress,
-        "observed": report.observed_progress,
-        "discrepancy": report.discrepancy,
-        "discrepancy_fraction": report.discrepancy_fraction,
-        "classification": report.classification.to_string(),
-        "suspected_causes": report.suspected_causes.iter().map(|cause| cause.cause.clone()).collect::<Vec<_>>(),
-    })
-}
+    let progress_config = ProgressConfig::default();
+    let memory_report = goal_progress::measure_progress(
+        GoalMetric::Memory,
+        None,
+        &before_snapshot,
+        &after_snapshot,
+        memory_action_outcomes,
+        &progress_config,
+        Some(sid.0.clone()),
+    );
+    let cpu_report = goal_progress::measure_progress(
+        GoalMetric::Cpu,
+        None,
+        &before_snapshot,
+        &after_snapshot,
+        cpu_action_outcomes,
+        &progress_config,
+        Some(sid.0.clone()),
+    );
+    let port_report = goal_progress::measure_progress(
+        GoalMetric::Port,
+        None,
+        &before_snapshot,
+        &after_snapshot,
+        port_action_outcomes,
+        &progress_config,
+        Some(sid.0.clone()),
+    );
+    let fd_report = goal_progress::measure_progress(
+        GoalMetric::FileDescriptors,
+        None,
+        &before_snapshot,
+        &after_snapshot,
+        fd_action_outcomes,
+        &progress_config,
+        Some(sid.0.clone()),
+    );
 
-fn run_agent_apply(global: &GlobalOpts, args: &AgentApplyArgs) -> ExitCode {
-    let _lock = match acquire_global_lock(global, "agent apply") {
-        Ok(lock) => lock,
-        Err(code) => return code,
-    };
-    // Load configuration
-    let config = match load_config(&config_options(global)) {
-        Ok(cfg) => cfg,
-        Err(e) => {
-            eprintln!("agent apply: config error: {}", e);
-            return ExitCode::InternalError;
+    let goal_progress_payload = serde_json::json!({
+        "session_id": sid.0,
+        "before": before_snapshot,
+        "after": after_snapshot,
+        "metrics": {
+            "memory": memory_report.clone(),
+            "cpu": cpu_report.clone(),
+            "ports": port_report.clone(),
+            "file_descriptors": fd_report.clone()
         }
-    };
+    });
+    let goal_progress_discrepancy = serde_json::json!({
+        "memory": goal_report_brief_json(&memory_report),
+        "cpu": goal_report_brief_json(&cpu_report),
+        "ports": goal_report_brief_json(&port_report),
+        "file_descriptors": goal_report_brief_json(&fd_report),
+        "respawn_loop_suspected": respawn_by_action.values().any(|detected| *detected),
+    });
 
-    // Open session store and session
-    let store = match SessionStore::from_env() {
-        Ok(store) => store,
-        Err(e) => {
-            eprintln!("agent apply: session store error: {}", e);
-            return ExitCode::InternalError;
-        }
-    };
-    let sid = match SessionId::parse(&args.session) {
-        Some(sid) => sid,
-        None => {
-            eprintln!("agent apply: invalid --session {}", args.session);
-            return ExitCode::ArgsError;
-        }
-    };
-    let handle = match store.open(&sid) {
-        Ok(h) => h,
-        Err(e) => {
-            eprintln!("agent apply: {}", e);
-            return ExitCode::ArgsError;
+    for outcome in &mut outcomes {
+        if let Some(obj) = outcome.as_object_mut() {
+            let action_id = obj
+                .get("action_id")
+                .and_then(|value| value.as_str())
+                .unwrap_or_default();
+            let respawn_detected = respawn_by_action.get(action_id).copied().unwrap_or(false);
+            obj.insert(
+                "respawn_detected".to_string(),
+                serde_json::json!(respawn_detected),
+            );
+            obj.insert(
+                "goal_progress".to_string(),
+                goal_progress_discrepancy.clone(),
+            );
         }
-    };
+    }
 
-    let session_lifecycle = SessionLifecycle::start(global, &handle, &sid);
-    let emitter = session_lifecycle.emitter();
+    let memory_summary_suffix = format!(
+        ", mem_obs={:.1}MB mem_exp={:.1}MB ({})",
+        memory_report.observed_progress / 1_048_576.0,
+        memory_report.expected_progress / 1_048_576.0,
+        memory_report.classification
+    );
 
-    // Load the plan from decision/plan.json
-    let plan_path = handle.dir.join("decision").join("plan.json");
-    if !plan_path.exists() {
-        eprintln!("agent apply: no plan.json found for session {}", sid);
-        return ExitCode::ArgsError;
+    // Write outcomes
+    let action_dir = handle.dir.join("action");
+    let outcomes_path = handle.dir.join("action").join("outcomes.jsonl");
+    let _ = std::fs::create_dir_all(&action_dir);
+    let goal_progress_path = action_dir.join("goal_progress.json");
+    if let Ok(payload) = serde_json::to_string_pretty(&goal_progress_payload) {
+        if write_guard
+            .check(
+                &goal_progress_path,
+                payload.len() as u64,
+                WritePriority::Optional,
+            )
+            .allowed()
+        {
+            let _ = std::fs::write(&goal_progress_path, payload);
+        }
     }
-    let plan_content = match std::fs::read_to_string(&plan_path) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!("agent apply: failed to read {}: {}", plan_path.display(), e);
-            return ExitCode::IoError;
+    let outcome_lines: String = outcomes.iter().map(|o| format!("{}\n", o)).collect();
+    match write_guard.check(
+        &outcomes_path,
+        outcome_lines.len() as u64,
+        WritePriority::Essential,
+    ) {
+        QuotaDecision::Write => {
+            if let Ok(mut file) = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&outcomes_path)
+            {
+                use std::io::Write;
+                let _ = file.write_all(outcome_lines.as_bytes());
+            }
         }
-    };
-    let plan: Plan = match serde_json::from_str(&plan_content) {
-        Ok(p) => p,
-        Err(e) => {
-            eprintln!("agent apply: invalid plan.json: {}", e);
-            return ExitCode::InternalError;
+        QuotaDecision::Skip { reason } => {
+            eprintln!("agent apply: outcomes not written to disk: {}", reason);
         }
-    };
+    }
+    for o in &outcomes {
+        let _ = handle.record_event(SessionEventKind::ActionExecuted {
+            action_id: o["action_id"].as_str().unwrap_or_default().to_string(),
+            pid: o["pid"].as_u64().unwrap_or(0) as u32,
+            status: o["status"].as_str().unwrap_or("unknown").to_string(),
+        });
+    }
 
-    // Load completed action IDs for --resume mode
-    let completed_action_ids: std::collections::HashSet<String> = if args.resume {
-        let outcomes_path = handle.dir.join("action").join("outcomes.jsonl");
-        if outcomes_path.exists() {
-            std::fs::read_to_string(&outcomes_path)
-                .ok()
-                .map(|content| {
-                    content
-                        .lines()
-                        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
-                        .filter(|v| v.get("status").and_then(|s| s.as_str()) == Some("success"))
-                        .filter_map(|v| {
-                            v.get("action_id")
-                                .and_then(|a| a.as_str())
-                                .map(String::from)
-                        })
-                        .collect()
-                })
-                .unwrap_or_default()
-        } else {
-            std::collections::HashSet::new()
-        }
+    let final_state = if failed > 0 {
+        SessionState::Failed
     } else {
-        std::collections::HashSet::new()
+        SessionState::Completed
     };
+    let _ = handle.update_state(final_state);
 
-    // Determine which actions to apply
-    let use_recommended =
-        args.recommended || (args.resume && args.pids.is_empty() && args.targets.is_empty());
-    let mut target_pids: Vec<u32> = if use_recommended {
-        plan.actions
-            .iter()
-            .filter(|a| !a.blocked)
-            .map(|a| a.target.pid.0)
-            .collect()
-    } else if !args.pids.is_empty() {
-        args.pids.clone()
-    } else if !args.targets.is_empty() {
-        args.targets
-            .iter()
-            .filter_map(|t| t.split(':').next().and_then(|p| p.parse().ok()))
-            .collect()
-    } else {
-        eprintln!("agent apply: must specify --recommended, --pids, or --targets");
-        return ExitCode::ArgsError;
+    let mut result = serde_json::json!({
+        "session_id": sid.0,
+        "mode": "robot_apply",
+        "summary": {
+            "attempted": actions_to_apply.len(),
+            "succeeded": succeeded,
+            "failed": failed,
+            "skipped": skipped,
+            "blocked_by_constraints": blocked_by_constraints,
+            "deferred_by_schedule": deferred_by_schedule,
+            "blocked_by_prechecks": blocked_by_prechecks,
+            "resumed_skipped": resumed_skipped,
+            "operator_overrides": operator_overrides.len()
+        },
+        "outcomes": outcomes,
+        "operator_overrides": operator_overrides,
+        "goal_progress": goal_progress_payload,
+        "constraints_summary": constraints_summary,
+        "resumed": args.resume
+    });
+    if let Some(telemetry) = telemetry_quota_json(&write_guard) {
+        result["telemetry"] = telemetry;
+    }
+    if deferred_by_schedule > 0 {
+        result["schedule"] = serde_json::json!({
+            "deferred": deferred_by_schedule,
+            "next_window": next_window.map(|at| at.format("%Y-%m-%dT%H:%M").to_string()),
+            "retry_command": format!("pt agent apply --session {} --resume --yes", sid.0),
+        });
+    }
+    let deferred_suffix = match (deferred_by_schedule, next_window) {
+        (0, _) => String::new(),
+        (n, Some(at)) => format!(", {} deferred until {}", n, at.format("%Y-%m-%d %H:%M")),
+        (n, None) => format!(", {} deferred by schedule", n),
     };
-
-    if let Some(min_age) = args.min_age {
-        if !target_pids.is_empty() {
-            let scan_options = QuickScanOptions {
-                pids: target_pids.clone(),
-                include_kernel_threads: false,
-                timeout: global.timeout.map(std::time::Duration::from_secs),
-                progress: None,
-            };
-            let scan_result = match quick_scan(&scan_options) {
-                Ok(r) => r,
-                Err(e) => {
-                    eprintln!("agent apply: min-age scan failed: {}", e);
-                    return ExitCode::InternalError;
-                }
-            };
-            let eligible: HashSet<u32> = scan_result
-                .processes
-                .iter()
-                .filter(|proc| proc.elapsed.as_secs() >= min_age)
-                .map(|proc| proc.pid.0)
-                .collect();
-            target_pids.retain(|pid| eligible.contains(pid));
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, result));
+        }
+        OutputFormat::Summary => {
+            if resumed_skipped > 0 {
+                println!(
+                    "[{}] apply: {} ok, {} fail, {} skip, {} blocked, {} precheck_blocked, {} already done (resumed){}{}",
+                    sid,
+                    succeeded,
+                    failed,
+                    skipped,
+                    blocked_by_constraints,
+                    blocked_by_prechecks,
+                    resumed_skipped,
+                    deferred_suffix,
+                    memory_summary_suffix
+                );
+            } else {
+                println!(
+                    "[{}] apply: {} ok, {} fail, {} skip, {} blocked, {} precheck_blocked{}{}",
+                    sid,
+                    succeeded,
+                    failed,
+                    skipped,
+                    blocked_by_constraints,
+                    blocked_by_prechecks,
+                    deferred_suffix,
+                    memory_summary_suffix
+                );
+            }
         }
+        _ => println!(
+            "# apply\nSession: {}\nSucceeded: {}\nFailed: {}",
+            sid, succeeded, failed
+        ),
     }
 
-    if target_pids.is_empty() {
-        output_apply_nothing(global, &sid);
-        return ExitCode::Clean;
+    if (blocked_by_constraints + deferred_by_schedule + blocked_by_prechecks) > 0
+        && succeeded == 0
+        && failed == 0
+    {
+        ExitCode::PolicyBlocked
+    } else if failed > 0 {
+        ExitCode::PartialFail
+    } else {
+        ExitCode::ActionsOk
     }
+}
 
-    // Filter out completed actions using earlier declaration for --resume mode
-    let actions_to_apply: Vec<_> = plan
-        .actions
-        .iter()
-        .filter(|a| target_pids.contains(&a.target.pid.0))
-        .filter(|a| !completed_action_ids.contains(&a.action_id))
-        .collect();
-    if actions_to_apply.is_empty() {
-        output_apply_nothing(global, &sid);
-        return ExitCode::Clean;
+fn output_apply_nothing(global: &GlobalOpts, sid: &SessionId) {
+    let result = serde_json::json!({"session_id": sid.0, "mode": "robot_apply", "note": "nothing_to_do", "summary": {"attempted": 0}});
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            println!("{}", format_structured_output(global, result));
+        }
+        _ => println!("[{}] apply: nothing to do", sid),
     }
+}
 
-    let goal_progress_scan_options = QuickScanOptions {
-        pids: vec![],
-        include_kernel_threads: false,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
-        progress: None,
+fn run_agent_verify(global: &GlobalOpts, args: &AgentVerifyArgs) -> ExitCode {
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent verify: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
     };
-
-    let before_scan_processes = quick_scan(&goal_progress_scan_options)
-        .map(|scan| scan.processes)
-        .unwrap_or_else(|_| Vec::new());
-    let before_snapshot = capture_metric_snapshot_for_goal_progress(&before_scan_processes);
-    let before_by_pid: HashMap<u32, &ProcessRecord> = before_scan_processes
-        .iter()
-        .map(|proc| (proc.pid.0, proc))
-        .collect();
-
-    #[cfg(target_os = "linux")]
-    let before_network_snapshot = NetworkSnapshot::collect();
-
-    let mut expected_by_action: HashMap<String, (f64, f64, f64, f64, String)> = HashMap::new();
-    for action in &actions_to_apply {
-        let pid = action.target.pid.0;
-        let before_proc = before_by_pid.get(&pid).copied();
-        let label = before_proc
-            .map(|proc| proc.cmd.clone())
-            .unwrap_or_else(|| format!("pid {}", pid));
-
-        let memory_expected = if matches!(
-            action.action,
-            Action::Kill | Action::Restart | Action::Pause
-        ) {
-            action
-                .rationale
-                .memory_mb
-                .unwrap_or(0.0)
-                .max(0.0)
-                .mul_add(1_048_576.0, 0.0)
-        } else {
-            0.0
-        };
-        let cpu_expected = if matches!(
-            action.action,
-            Action::Kill
-                | Action::Restart
-                | Action::Pause
-                | Action::Freeze
-                | Action::Quarantine
-                | Action::Throttle
-        ) {
-            before_proc
-                .map(|proc| (proc.cpu_percent / 100.0).max(0.0))
-                .unwrap_or(0.0)
-        } else {
-            0.0
-        };
-
-        #[cfg(target_os = "linux")]
-        let port_expected = if matches!(
-            action.action,
-            Action::Kill | Action::Restart | Action::Pause | Action::Freeze
-        ) {
-            before_network_snapshot
-                .get_process_info(pid)
-                .map(|info| {
-                    info.listen_ports
-                        .iter()
-                        .map(|port| port.port)
-                        .collect::<HashSet<_>>()
-                        .len() as f64
-                })
-                .unwrap_or(0.0)
-        } else {
-            0.0
-        };
-        #[cfg(not(target_os = "linux"))]
-        let port_expected = 0.0;
-
-        #[cfg(target_os = "linux")]
-        let fd_expected = if matches!(
-            action.action,
-            Action::Kill | Action::Restart | Action::Pause | Action::Freeze
-        ) {
-            parse_fd(pid).map(|fd| fd.count as f64).unwrap_or(0.0)
-        } else {
-            0.0
-        };
-        #[cfg(not(target_os = "linux"))]
-        let fd_expected = 0.0;
-
-        expected_by_action.insert(
-            action.action_id.clone(),
-            (
-                memory_expected,
-                cpu_expected,
-                port_expected,
-                fd_expected,
-                label,
-            ),
-        );
+    let sid = match SessionId::parse(&args.session) {
+        Some(sid) => sid,
+        None => {
+            eprintln!("agent verify: invalid --session {}", args.session);
+            return ExitCode::ArgsError;
+        }
+    };
+    if let Err(e) = store.open(&sid) {
+        eprintln!("agent verify: {}", e);
+        return ExitCode::ArgsError;
     }
-
-    let total_actions = actions_to_apply.len() as u64;
-    let mut action_index = 0u64;
-    let emit_action_event = |event_name: &str,
-                             index: u64,
-                             elapsed_ms: Option<u64>,
-                             action: &PlanAction,
-                             status: &str,
-                             extra: &[(&str, serde_json::Value)]| {
-        if let Some(ref e) = emitter {
-            let mut event = ProgressEvent::new(event_name, Phase::Apply)
-                .with_progress(index, Some(total_actions))
-                .with_detail("action_id", &action.action_id)
-                .with_detail("pid", action.target.pid.0)
-                .with_detail("action", format!("{:?}", action.action))
-                .with_detail("status", status);
-            if let Some(ms) = elapsed_ms {
-                event = event.with_elapsed_ms(ms);
-            }
-            for (key, value) in extra {
-                event = event.with_detail(*key, value);
-            }
-            e.emit(event);
+    let handle = match store.open(&sid) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("agent verify: {}", e);
+            return ExitCode::ArgsError;
         }
     };
 
-    // Check --yes requirement
-    if !args.yes && !global.dry_run && !global.shadow {
-        let err = serde_json::json!({"session_id": sid.0, "error": "confirmation_required", "message": "--yes flag required for execution"});
-        println!("{}", serde_json::to_string_pretty(&err).unwrap());
-        return ExitCode::PolicyBlocked;
+    let plan_path = handle.dir.join("decision").join("plan.json");
+    if !plan_path.exists() {
+        eprintln!("agent verify: missing plan.json for session {}", sid);
+        return ExitCode::ArgsError;
     }
-
-    // Build robot constraints from policy + CLI overrides
-    let constraints = RuntimeRobotConstraints::from_policy(&config.policy.robot_mode)
-        .with_min_posterior(args.min_posterior)
-        .with_max_blast_radius_mb(args.max_blast_radius)
-        .with_max_total_blast_radius_mb(args.max_total_blast_radius)
-        .with_max_kills(args.max_kills)
-        .with_require_known_signature(if args.require_known_signature {
-            Some(true)
-        } else {
-            None
-        })
-        .with_allow_categories(if args.only_categories.is_empty() {
-            None
-        } else {
-            Some(args.only_categories.clone())
-        })
-        .with_exclude_categories(args.exclude_categories.clone());
-
-    let checker = ConstraintChecker::new(constraints.clone());
-    let constraints_summary = constraints.active_constraints_summary();
-    let _ = handle.update_state(SessionState::Executing);
-
-    #[cfg(target_os = "linux")]
-    let precheck_provider = {
-        use pt_core::action::{LivePreCheckConfig, LivePreCheckProvider};
-        LivePreCheckProvider::new(
-            Some(&config.policy.guardrails),
-            LivePreCheckConfig::from(&config.policy.data_loss_gates),
-        )
-        .unwrap_or_else(|_| LivePreCheckProvider::with_defaults())
+    let plan_content = match std::fs::read_to_string(&plan_path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!(
+                "agent verify: failed to read {}: {}",
+                plan_path.display(),
+                e
+            );
+            return ExitCode::IoError;
+        }
+    };
+    let plan = match parse_agent_plan(&plan_content) {
+        Ok(p) => p,
+        Err(VerifyError::InvalidPlan(msg)) => {
+            eprintln!("agent verify: invalid plan.json: {}", msg);
+            return ExitCode::InternalError;
+        }
+        Err(VerifyError::InvalidTimestamp(msg)) => {
+            eprintln!("agent verify: invalid timestamp: {}", msg);
+            return ExitCode::ArgsError;
+        }
     };
 
-    let mut outcomes: Vec<serde_json::Value> = Vec::new();
-    let mut succeeded = 0usize;
-    let mut failed = 0usize;
-    let mut skipped = 0usize;
-    let mut blocked_by_constraints = 0usize;
-    let mut blocked_by_prechecks = 0usize;
-    let mut resumed_skipped = 0usize;
+    let requested_at = chrono::Utc::now();
 
-    // Handle dry-run/shadow mode or execute
-    if global.dry_run || global.shadow {
-        for action in &actions_to_apply {
-            action_index = action_index.saturating_add(1);
-            emit_action_event(
-                pt_core::events::event_names::ACTION_STARTED,
-                action_index,
-                None,
-                action,
-                "started",
-                &[(
-                    "mode",
-                    serde_json::json!(if global.dry_run { "dry_run" } else { "shadow" }),
-                )],
-            );
+    // Wait for process termination if --wait is specified
+    if args.wait > 0 {
+        let wait_duration = std::time::Duration::from_secs(args.wait);
+        let start = std::time::Instant::now();
+        let target_pids: Vec<u32> = plan
+            .candidates
+            .iter()
+            .filter(|c| c.recommended_action == "terminate" || c.recommended_action == "kill")
+            .map(|c| c.pid)
+            .collect();
 
-            // Skip already completed actions in resume mode
-            if completed_action_ids.contains(&action.action_id) {
-                resumed_skipped += 1;
-                outcomes.push(serde_json::json!({
-                    "action_id": action.action_id,
-                    "pid": action.target.pid.0,
-                    "status": "already_completed",
-                    "resume": true
-                }));
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_COMPLETE,
-                    action_index,
-                    None,
-                    action,
-                    "already_completed",
-                    &[("resume", serde_json::json!(true))],
-                );
-                continue;
-            }
+        while start.elapsed() < wait_duration {
+            // Check if all target processes have terminated
+            let still_running: Vec<u32> = target_pids
+                .iter()
+                .filter(|pid| std::path::Path::new(&format!("/proc/{}", pid)).exists())
+                .copied()
+                .collect();
 
-            if action.blocked {
-                blocked_by_prechecks += 1;
-                outcomes.push(serde_json::json!({
-                    "action_id": action.action_id,
-                    "pid": action.target.pid.0,
-                    "status": "blocked_by_plan"
-                }));
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_COMPLETE,
-                    action_index,
-                    None,
-                    action,
-                    "blocked_by_plan",
-                    &[],
-                );
-                continue;
+            if still_running.is_empty() {
+                break;
             }
 
-            let candidate = RobotCandidate {
-                posterior: action.rationale.posterior_odds_abandoned_vs_useful,
-                memory_mb: None,
-                has_known_signature: false,
-                category: None,
-                is_kill_action: action.action == Action::Kill,
-                has_policy_snapshot: true,
-                is_supervised: is_supervised_for_robot(action.target.pid.0),
-            };
-            let check = checker.check_candidate(&candidate);
-            if !check.allowed {
-                blocked_by_constraints += 1;
-                outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "blocked_by_constraints"}));
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_COMPLETE,
-                    action_index,
-                    None,
-                    action,
-                    "blocked_by_constraints",
-                    &[],
-                );
-                continue;
-            }
+            std::thread::sleep(std::time::Duration::from_millis(250));
+        }
+    }
 
-            #[cfg(target_os = "linux")]
-            if let Some((check, reason)) = first_precheck_block(&precheck_provider, action) {
-                blocked_by_prechecks += 1;
-                outcomes.push(serde_json::json!({
-                    "action_id": action.action_id,
-                    "pid": action.target.pid.0,
-                    "status": "precheck_blocked",
-                    "check": precheck_label_for_apply(&check),
-                    "reason": reason
-                }));
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_COMPLETE,
-                    action_index,
-                    None,
-                    action,
-                    "precheck_blocked",
-                    &[("check", serde_json::json!(precheck_label_for_apply(&check)))],
-                );
-                continue;
+    let scan_options = QuickScanOptions {
+        pids: vec![],
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+    let mut scan_result = match quick_scan(&scan_options) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("agent verify: scan failed: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    // Plan commands are scrubbed; compare respawns against scrubbed commands
+    // too, which also keeps secrets out of the report.
+    match output_redactor(global) {
+        Ok(redactor) => {
+            for proc in &mut scan_result.processes {
+                proc.cmd = redactor.cmdline(&proc.cmd);
             }
+        }
+        Err(e) => {
+            eprintln!("agent verify: {}", e);
+            return ExitCode::InternalError;
+        }
+    }
 
-            skipped += 1;
-            outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": if global.dry_run { "dry_run" } else { "shadow" }}));
-            emit_action_event(
-                pt_core::events::event_names::ACTION_COMPLETE,
-                action_index,
-                None,
-                action,
-                if global.dry_run { "dry_run" } else { "shadow" },
-                &[],
-            );
+    let completed_at = chrono::Utc::now();
+    let report = verify_plan(&plan, &scan_result.processes, requested_at, completed_at);
+    record_respawn_history(&handle, &plan, &report);
+
+    let verify_dir = handle.dir.join("action");
+    if let Err(e) = std::fs::create_dir_all(&verify_dir) {
+        eprintln!(
+            "agent verify: failed to create {}: {}",
+            verify_dir.display(),
+            e
+        );
+        return ExitCode::IoError;
+    }
+    let verify_path = verify_dir.join("verifications.json");
+    if let Err(e) = std::fs::write(&verify_path, serde_json::to_string_pretty(&report).unwrap()) {
+        eprintln!(
+            "agent verify: failed to write {}: {}",
+            verify_path.display(),
+            e
+        );
+        return ExitCode::IoError;
+    }
+    for o in &report.action_outcomes {
+        let outcome = serde_json::to_value(&o.outcome)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let _ = handle.record_event(SessionEventKind::Verified {
+            pid: o.target.pid,
+            action: o.action.clone(),
+            outcome,
+            verified: o.verified.unwrap_or(false),
+        });
+    }
+
+    if let Ok(manifest) = handle.read_manifest() {
+        if manifest.state != SessionState::Completed {
+            let _ = handle.update_state(SessionState::Completed);
         }
-    } else {
-        #[cfg(target_os = "linux")]
-        {
-            let identity_provider = LiveIdentityProvider::new();
-            let signal_runner = SignalActionRunner::new(SignalConfig::default());
+    }
 
-            for action in &actions_to_apply {
-                action_index = action_index.saturating_add(1);
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_STARTED,
-                    action_index,
-                    None,
-                    action,
-                    "started",
-                    &[],
-                );
+    let total = report.action_outcomes.len();
+    let verified_count = report
+        .action_outcomes
+        .iter()
+        .filter(|o| o.verified.unwrap_or(false))
+        .count();
+    let failed_count = total.saturating_sub(verified_count);
 
-                // Skip already completed actions in resume mode
-                if completed_action_ids.contains(&action.action_id) {
-                    resumed_skipped += 1;
-                    outcomes.push(serde_json::json!({
-                        "action_id": action.action_id,
-                        "pid": action.target.pid.0,
-                        "status": "already_completed",
-                        "resume": true
-                    }));
-                    emit_action_event(
-                        pt_core::events::event_names::ACTION_COMPLETE,
-                        action_index,
-                        None,
-                        action,
-                        "already_completed",
-                        &[("resume", serde_json::json!(true))],
-                    );
-                    continue;
-                }
+    // launchd jobs restarted after their kill, recognised by label rather
+    // than command line
+    let launchd_respawns = if args.check_respawn {
+        pt_core::verify::launchd_respawns(&plan, |job| {
+            pt_core::collect::current_job_pid(&job.label, &job.domain)
+        })
+    } else {
+        Vec::new()
+    };
 
-                if action.blocked {
-                    blocked_by_prechecks += 1;
-                    outcomes.push(serde_json::json!({
-                        "action_id": action.action_id,
-                        "pid": action.target.pid.0,
-                        "status": "blocked_by_plan"
-                    }));
-                    emit_action_event(
-                        pt_core::events::event_names::ACTION_COMPLETE,
-                        action_index,
-                        None,
-                        action,
-                        "blocked_by_plan",
-                        &[],
-                    );
-                    if args.abort_on_unknown {
-                        break;
-                    }
-                    continue;
+    // Check for respawned processes if --check-respawn is set
+    let respawned_count = if args.check_respawn {
+        // Get command signatures of killed processes
+        let killed_commands: Vec<&str> = plan
+            .candidates
+            .iter()
+            .filter(|c| c.recommended_action == "terminate" || c.recommended_action == "kill")
+            .map(|c| {
+                // Prefer cmd_full, fall back to cmd_short
+                if !c.cmd_full.is_empty() {
+                    c.cmd_full.as_str()
+                } else {
+                    c.cmd_short.as_str()
                 }
+            })
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        // Count current processes that match killed command patterns
+        scan_result
+            .processes
+            .iter()
+            .filter(|p| !launchd_respawns.iter().any(|r| r.new_pid == p.pid.0))
+            .filter(|p| killed_commands.iter().any(|kc| p.cmd.contains(kc)))
+            .count()
+            + launchd_respawns.len()
+    } else {
+        0
+    };
 
-                let start = std::time::Instant::now();
-                let candidate = RobotCandidate {
-                    posterior: action.rationale.posterior_odds_abandoned_vs_useful,
-                    memory_mb: None,
-                    has_known_signature: false,
-                    category: None,
-                    is_kill_action: action.action == Action::Kill,
-                    has_policy_snapshot: true,
-                    is_supervised: is_supervised_for_robot(action.target.pid.0),
-                };
-                let check = checker.check_candidate(&candidate);
-                if !check.allowed {
-                    blocked_by_constraints += 1;
-                    let elapsed_ms = start.elapsed().as_millis() as u64;
-                    outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "blocked_by_constraints", "time_ms": elapsed_ms}));
-                    emit_action_event(
-                        pt_core::events::event_names::ACTION_COMPLETE,
-                        action_index,
-                        Some(elapsed_ms),
-                        action,
-                        "blocked_by_constraints",
-                        &[],
+    let prior_update = if args.update_priors {
+        match update_priors_from_verification(global, &handle, &plan_content, &report, args) {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                eprintln!("agent verify: priors not updated: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let exit_code = match report.verification.overall_status.as_str() {
+        "success" => ExitCode::Clean,
+        "partial_success" => ExitCode::PartialFail,
+        "failure" => ExitCode::PartialFail,
+        _ => ExitCode::Clean,
+    };
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            // Extend report with respawn info if checked
+            let mut output = serde_json::to_value(&report).unwrap_or_default();
+            if let (Some(obj), Some(update)) = (output.as_object_mut(), &prior_update) {
+                obj.insert("prior_update".to_string(), update.clone());
+            }
+            if args.check_respawn {
+                if let Some(obj) = output.as_object_mut() {
+                    obj.insert(
+                        "respawn_check".to_string(),
+                        serde_json::json!({
+                            "enabled": true,
+                            "respawned_count": respawned_count,
+                            "launchd": launchd_respawns,
+                            "warning": if respawned_count > 0 {
+                                Some(format!("{} processes may have respawned", respawned_count))
+                            } else {
+                                None
+                            }
+                        }),
                     );
-                    if args.abort_on_unknown {
-                        break;
-                    }
-                    continue;
                 }
-                match identity_provider.revalidate(&action.target) {
-                    Ok(true) => {}
-                    Ok(false) => {
-                        failed += 1;
-                        let elapsed_ms = start.elapsed().as_millis() as u64;
-                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "identity_mismatch", "time_ms": elapsed_ms}));
-                        emit_action_event(
-                            pt_core::events::event_names::ACTION_FAILED,
-                            action_index,
-                            Some(elapsed_ms),
-                            action,
-                            "identity_mismatch",
-                            &[],
-                        );
-                        if args.abort_on_unknown {
-                            break;
-                        }
-                        continue;
-                    }
-                    Err(_) => {
-                        failed += 1;
-                        let elapsed_ms = start.elapsed().as_millis() as u64;
-                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "identity_check_failed", "time_ms": elapsed_ms}));
-                        emit_action_event(
-                            pt_core::events::event_names::ACTION_FAILED,
-                            action_index,
-                            Some(elapsed_ms),
-                            action,
-                            "identity_check_failed",
-                            &[],
-                        );
-                        if args.abort_on_unknown {
-                            break;
-                        }
-                        continue;
-                    }
+            }
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            let freed = report
+                .resource_summary
+                .as_ref()
+                .map(|s| s.memory_freed_mb)
+                .unwrap_or(0.0);
+            let respawn_info = if args.check_respawn && respawned_count > 0 {
+                format!(", {} respawned!", respawned_count)
+            } else {
+                String::new()
+            };
+            println!(
+                "[{}] agent verify: {} verified, {} failed (freed {} MB){}",
+                sid, verified_count, failed_count, freed, respawn_info
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!(
+                "# pt-core agent verify
+"
+            );
+            println!("Session: {}", sid);
+            println!("Plan: {}", plan_path.display());
+            println!(
+                "Report: {}
+",
+                verify_path.display()
+            );
+            println!(
+                "- Outcomes: {} verified, {} failed",
+                verified_count, failed_count
+            );
+            if let Some(summary) = &report.resource_summary {
+                println!(
+                    "- Memory freed: {} MB (expected {})",
+                    summary.memory_freed_mb, summary.expected_mb
+                );
+            }
+            if let Some(update) = &prior_update {
+                println!(
+                    "- Priors updated from {} outcomes ({} parameters changed)",
+                    update["observations"], update["records"]
+                );
+            }
+            if args.check_respawn {
+                println!("- Respawn check: {} processes detected", respawned_count);
+                if respawned_count > 0 {
+                    println!("  ⚠ Warning: Some killed processes may have respawned");
                 }
-                if let Some((check, reason)) = first_precheck_block(&precheck_provider, action) {
-                    blocked_by_prechecks += 1;
-                    let elapsed_ms = start.elapsed().as_millis() as u64;
-                    outcomes.push(serde_json::json!({
-                        "action_id": action.action_id,
-                        "pid": action.target.pid.0,
-                        "status": "precheck_blocked",
-                        "check": precheck_label_for_apply(&check),
-                        "reason": reason,
-                        "time_ms": elapsed_ms
-                    }));
-                    emit_action_event(
-                        pt_core::events::event_names::ACTION_COMPLETE,
-                        action_index,
-                        Some(elapsed_ms),
-                        action,
-                        "precheck_blocked",
-                        &[("check", serde_json::json!(precheck_label_for_apply(&check)))],
+                for respawn in &launchd_respawns {
+                    println!(
+                        "  launchd restarted {} (PID {} -> {}); stop it with `launchctl bootout {}/{}`",
+                        respawn.label, respawn.pid, respawn.new_pid, respawn.domain, respawn.label
                     );
-                    if args.abort_on_unknown {
-                        break;
-                    }
-                    continue;
                 }
-                match signal_runner.execute(action) {
-                    Ok(()) => {
-                        if action.action == Action::Kill {
-                            checker.record_action(0, true);
-                        }
-                        succeeded += 1;
-                        let elapsed_ms = start.elapsed().as_millis() as u64;
-                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "success", "time_ms": elapsed_ms}));
-                        emit_action_event(
-                            pt_core::events::event_names::ACTION_COMPLETE,
-                            action_index,
-                            Some(elapsed_ms),
-                            action,
-                            "success",
-                            &[],
-                        );
-                    }
-                    Err(e) => {
-                        failed += 1;
-                        let elapsed_ms = start.elapsed().as_millis() as u64;
-                        outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "failed", "error": format!("{:?}", e), "time_ms": elapsed_ms}));
-                        emit_action_event(
-                            pt_core::events::event_names::ACTION_FAILED,
-                            action_index,
-                            Some(elapsed_ms),
-                            action,
-                            "failed",
-                            &[("error", serde_json::json!(format!("{:?}", e)))],
-                        );
-                        if args.abort_on_unknown {
-                            break;
-                        }
+            }
+            if let Some(recommendations) = &report.recommendations {
+                if !recommendations.is_empty() {
+                    println!("\n## Recommendations\n");
+                    for rec in recommendations {
+                        println!("- {}", rec);
                     }
                 }
             }
         }
-        #[cfg(not(target_os = "linux"))]
-        {
-            for action in &actions_to_apply {
-                action_index = action_index.saturating_add(1);
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_STARTED,
-                    action_index,
-                    None,
-                    action,
-                    "started",
-                    &[],
-                );
+    }
 
-                // Skip already completed actions in resume mode
-                if completed_action_ids.contains(&action.action_id) {
-                    resumed_skipped += 1;
-                    outcomes.push(serde_json::json!({
-                        "action_id": action.action_id,
-                        "pid": action.target.pid.0,
-                        "status": "already_completed",
-                        "resume": true
-                    }));
-                    emit_action_event(
-                        pt_core::events::event_names::ACTION_COMPLETE,
-                        action_index,
-                        None,
-                        action,
-                        "already_completed",
-                        &[("resume", serde_json::json!(true))],
-                    );
-                    continue;
-                }
-                skipped += 1;
-                outcomes.push(serde_json::json!({"action_id": action.action_id, "pid": action.target.pid.0, "status": "unsupported_platform"}));
-                emit_action_event(
-                    pt_core::events::event_names::ACTION_COMPLETE,
-                    action_index,
-                    None,
-                    action,
-                    "unsupported_platform",
-                    &[],
-                );
-            }
+    // If respawned processes were detected, indicate partial failure
+
+    if args.check_respawn && respawned_count > 0 {
+        ExitCode::PartialFail
+    } else {
+        exit_code
+    }
+}
+
+/// Fold a session's verified kill outcomes into the on-disk priors.
+///
+/// Returns a summary for the verify output. Unpromoted experiment sessions
+/// never feed the priors.
+fn update_priors_from_verification(
+    global: &GlobalOpts,
+    handle: &SessionHandle,
+    plan_content: &str,
+    report: &pt_core::verify::VerificationReport,
+    args: &AgentVerifyArgs,
+) -> Result<serde_json::Value, String> {
+    use pt_core::inference::online_update::{
+        append_audit, apply_observations, prior_updates_path, ObservedClass, OnlineUpdateConfig,
+        VerifiedObservation,
+    };
+
+    if let Ok(manifest) = handle.read_manifest() {
+        if !manifest.feeds_learning() {
+            return Err("session belongs to an unpromoted experiment".to_string());
         }
     }
 
-    let after_scan_processes = quick_scan(&goal_progress_scan_options)
-        .map(|scan| scan.processes)
-        .unwrap_or_else(|_| Vec::new());
-    let after_snapshot = capture_metric_snapshot_for_goal_progress(&after_scan_processes);
-    let after_by_pid: HashMap<u32, &ProcessRecord> = after_scan_processes
+    // Plan-time evidence per PID (CPU occupancy, parent PID).
+    let plan: serde_json::Value =
+        serde_json::from_str(plan_content).map_err(|e| format!("invalid plan.json: {}", e))?;
+    let evidence: HashMap<u32, (Option<f64>, Option<u64>)> = plan["candidates"]
+        .as_array()
+        .map(|candidates| {
+            candidates
+                .iter()
+                .filter_map(|c| {
+                    let pid = c["pid"].as_u64()? as u32;
+                    let cpu = c["blast_radius"]["cpu_pct"]
+                        .as_f64()
+                        .or_else(|| c["cpu_percent"].as_f64());
+                    Some((pid, (cpu, c["ppid"].as_u64())))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Feedback labels recorded against this session (`pt-core feedback`):
+    // a false positive counts as a complaint, a false negative as an
+    // abandoned process the plan missed.
+    let feedback: HashMap<u32, pt_core::feedback::FeedbackKind> =
+        pt_core::feedback::read_feedback(&default_telemetry_dir())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|label| label.session_id == report.session_id)
+            .map(|label| (label.pid, label.kind))
+            .collect();
+    let observation = |pid: u32, observed: ObservedClass| {
+        let (cpu_percent, ppid) = evidence.get(&pid).copied().unwrap_or_default();
+        VerifiedObservation {
+            session_id: report.session_id.clone(),
+            pid,
+            observed,
+            cpu_occupancy: cpu_percent.map(|pct| pct / 100.0),
+            orphan: ppid.map(|ppid| ppid == 1),
+        }
+    };
+
+    let mut observations: Vec<VerifiedObservation> = report
+        .action_outcomes
         .iter()
-        .map(|proc| (proc.pid.0, proc))
+        .filter(|o| o.action == "kill" || o.action == "terminate")
+        .filter_map(|o| {
+            let pid = o.target.pid;
+            let complaint = args.complaints.contains(&pid)
+                || feedback.get(&pid) == Some(&pt_core::feedback::FeedbackKind::FalsePositive);
+            let observed = ObservedClass::from_outcome(&o.outcome, complaint)?;
+            Some(observation(pid, observed))
+        })
         .collect();
-
-    let status_by_action: HashMap<String, String> = outcomes
+    let mut missed: Vec<u32> = feedback
         .iter()
-        .filter_map(|outcome| {
-            let action_id = outcome.get("action_id")?.as_str()?.to_string();
-            let status = outcome
-                .get("status")
-                .and_then(|value| value.as_str())
-                .unwrap_or("unknown")
-                .to_string();
-            Some((action_id, status))
+        .filter(|(pid, kind)| {
+            **kind == pt_core::feedback::FeedbackKind::FalseNegative
+                && !observations.iter().any(|o| o.pid == **pid)
         })
+        .map(|(pid, _)| *pid)
         .collect();
+    missed.sort_unstable();
+    for pid in missed {
+        observations.push(observation(
+            pid,
+            pt_core::feedback::observed_class(pt_core::feedback::FeedbackKind::FalseNegative),
+        ));
+    }
+    if observations.is_empty() {
+        return Ok(serde_json::json!({ "observations": 0, "records": 0 }));
+    }
 
-    let mut respawn_by_action: HashMap<String, bool> = HashMap::new();
-    let mut memory_action_outcomes = Vec::new();
-    let mut cpu_action_outcomes = Vec::new();
-    let mut port_action_outcomes = Vec::new();
-    let mut fd_action_outcomes = Vec::new();
-    for action in &actions_to_apply {
-        let status = status_by_action
-            .get(&action.action_id)
-            .map(|value| value.as_str())
-            .unwrap_or("unknown");
-        let success = matches!(status, "success" | "dry_run" | "shadow");
-        let respawn_detected = detect_respawn_for_goal_progress(
-            action,
-            success,
-            &before_by_pid,
-            &after_by_pid,
-            &after_scan_processes,
+    let options = ConfigOptions {
+        config_dir: global.config.as_ref().map(PathBuf::from),
+        priors_path: None,
+        policy_path: None,
+    };
+    let config = load_config(&options).map_err(|e| format!("config error: {}", e))?;
+    let priors_path = priors_write_path(global, &config);
+
+    let mut priors = config.priors.clone();
+    let records = apply_observations(
+        &mut priors,
+        &observations,
+        &OnlineUpdateConfig::default(),
+        chrono::Utc::now(),
+    );
+    write_priors_atomically(&priors_path, &priors)?;
+    let audit_path = prior_updates_path(&default_telemetry_dir());
+    if let Err(e) = append_audit(&audit_path, &records) {
+        eprintln!(
+            "agent verify: warning: failed to append {}: {}",
+            audit_path.display(),
+            e
         );
-        respawn_by_action.insert(action.action_id.clone(), respawn_detected);
+    }
 
-        let (memory_expected, cpu_expected, port_expected, fd_expected, label) = expected_by_action
-            .get(&action.action_id)
-            .cloned()
-            .unwrap_or_else(|| (0.0, 0.0, 0.0, 0.0, format!("pid {}", action.target.pid.0)));
+    Ok(serde_json::json!({
+        "observations": observations.len(),
+        "records": records.len(),
+        "priors_path": priors_path.display().to_string(),
+        "audit_path": audit_path.display().to_string(),
+        "class_priors": {
+            "useful": priors.classes.useful.prior_prob,
+            "useful_bad": priors.classes.useful_bad.prior_prob,
+            "abandoned": priors.classes.abandoned.prior_prob,
+            "zombie": priors.classes.zombie.prior_prob,
+        },
+    }))
+}
 
-        let action_pid = action.target.pid.0;
-        memory_action_outcomes.push(GoalActionOutcome {
-            pid: action_pid,
-            label: label.clone(),
-            success,
-            respawn_detected,
-            expected_contribution: memory_expected,
-        });
-        cpu_action_outcomes.push(GoalActionOutcome {
-            pid: action_pid,
-            label: label.clone(),
-            success,
-            respawn_detected,
-            expected_contribution: cpu_expected,
-        });
-        port_action_outcomes.push(GoalActionOutcome {
-            pid: action_pid,
-            label: label.clone(),
-            success,
-            respawn_detected,
-            expected_contribution: port_expected,
-        });
-        fd_action_outcomes.push(GoalActionOutcome {
-            pid: action_pid,
-            label,
-            success,
-            respawn_detected,
-            expected_contribution: fd_expected,
-        });
+/// Where priors edits are written: the loaded priors file, else `<config>/priors.json`.
+fn priors_write_path(global: &GlobalOpts, config: &ResolvedConfig) -> PathBuf {
+    config.snapshot().priors_path.unwrap_or_else(|| {
+        global
+            .config
+            .as_ref()
+            .map(|c| PathBuf::from(c).join("priors.json"))
+            .unwrap_or_else(|| {
+                dirs::config_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("pt")
+                    .join("priors.json")
+            })
+    })
+}
+
+/// Write priors via a temp file and rename.
+fn write_priors_atomically(path: &Path, priors: &Priors) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create directory {}: {}", parent.display(), e))?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    let payload =
+        serde_json::to_vec_pretty(priors).map_err(|e| format!("failed to serialize: {}", e))?;
+    std::fs::write(&tmp_path, payload)
+        .map_err(|e| format!("failed to write {}: {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, path).map_err(|e| {
+        format!(
+            "failed to rename {} to {}: {}",
+            tmp_path.display(),
+            path.display(),
+            e
+        )
+    })
+}
+
+fn resolve_diff_sessions(
+    store: &SessionStore,
+    args: &DiffArgs,
+) -> Result<(SessionId, SessionId, Option<String>, Option<String>), String> {
+    fn has_required_artifacts(summary: &SessionSummary) -> bool {
+        // Diff requires both inventory + inference artifacts.
+        // Older sessions may not have these yet; skip them to avoid confusing failures.
+        summary.path.join("scan").join("inventory.json").exists()
+            && summary.path.join("inference").join("results.json").exists()
     }
 
-    let progress_config = ProgressConfig::default();
-    let memory_report = goal_progress::measure_progress(
-        GoalMetric::Memory,
-        None,
-        &before_snapshot,
-        &after_snapshot,
-        memory_action_outcomes,
-        &progress_config,
-        Some(sid.0.clone()),
-    );
-    let cpu_report = goal_progress::measure_progress(
-        GoalMetric::Cpu,
-        None,
-        &before_snapshot,
-        &after_snapshot,
-        cpu_action_outcomes,
-        &progress_config,
-        Some(sid.0.clone()),
-    );
-    let port_report = goal_progress::measure_progress(
-        GoalMetric::Port,
-        None,
-        &before_snapshot,
-        &after_snapshot,
-        port_action_outcomes,
-        &progress_config,
-        Some(sid.0.clone()),
-    );
-    let fd_report = goal_progress::measure_progress(
-        GoalMetric::FileDescriptors,
-        None,
-        &before_snapshot,
-        &after_snapshot,
-        fd_action_outcomes,
-        &progress_config,
-        Some(sid.0.clone()),
-    );
+    if args.baseline && args.last {
+        return Err("diff: --baseline and --last cannot be used together".to_string());
+    }
+    if (args.baseline || args.last) && (args.base.is_some() || args.compare.is_some()) {
+        return Err(
+            "diff: positional sessions cannot be combined with --baseline/--last".to_string(),
+        );
+    }
+
+    let list_options = ListSessionsOptions {
+        limit: Some(200),
+        state: None,
+        older_than: None,
+    };
+    let all_sessions = store
+        .list_sessions(&list_options)
+        .map_err(|e| format!("diff: failed to list sessions: {}", e))?;
+    if all_sessions.is_empty() {
+        return Err("diff: no sessions found".to_string());
+    }
+
+    let sessions: Vec<SessionSummary> = all_sessions
+        .into_iter()
+        .filter(has_required_artifacts)
+        .collect();
+    if sessions.is_empty() {
+        return Err(
+            "diff: no sessions with required artifacts (need scan inventory + inference results)"
+                .to_string(),
+        );
+    }
+
+    let use_last = args.last || (!args.baseline && args.base.is_none());
+
+    let (base_summary, compare_summary) = if args.baseline {
+        let base = sessions
+            .iter()
+            .find(|s| {
+                s.label
+                    .as_deref()
+                    .map(|l| l.eq_ignore_ascii_case("baseline"))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .ok_or_else(|| {
+                "diff: no baseline session found (label a session 'baseline')".to_string()
+            })?;
+        let compare = sessions
+            .iter()
+            .find(|s| s.session_id != base.session_id)
+            .cloned()
+            .ok_or_else(|| "diff: need at least two sessions to compare".to_string())?;
+        (base, compare)
+    } else if use_last {
+        if sessions.len() < 2 {
+            return Err("diff: need at least two sessions to compare".to_string());
+        }
+        (sessions[1].clone(), sessions[0].clone())
+    } else {
+        let base_raw = args
+            .base
+            .as_ref()
+            .ok_or_else(|| "diff: base session required".to_string())?;
+
+        let compare_summary = match args.compare.as_deref() {
+            Some("current") | Some("latest") | None => sessions
+                .iter()
+                .find(|s| s.session_id != *base_raw)
+                .cloned()
+                .ok_or_else(|| {
+                    "diff: no compare session found (need at least two sessions)".to_string()
+                })?,
+            Some(raw) => sessions
+                .iter()
+                .find(|s| s.session_id == raw)
+                .cloned()
+                .unwrap_or_else(|| SessionSummary {
+                    session_id: raw.to_string(),
+                    created_at: String::new(),
+                    state: SessionState::Created,
+                    mode: SessionMode::ScanOnly,
+                    label: None,
+                    host_id: None,
+                    candidates_count: None,
+                    actions_count: None,
+                    path: PathBuf::new(),
+                    experiment: None,
+                }),
+        };
+
+        let base_summary = sessions
+            .iter()
+            .find(|s| s.session_id == *base_raw)
+            .cloned()
+            .unwrap_or_else(|| SessionSummary {
+                session_id: base_raw.to_string(),
+                created_at: String::new(),
+                state: SessionState::Created,
+                mode: SessionMode::ScanOnly,
+                label: None,
+                host_id: None,
+                candidates_count: None,
+                actions_count: None,
+                path: PathBuf::new(),
+                experiment: None,
+            });
 
-    let goal_progress_payload = serde_json::json!({
-        "session_id": sid.0,
-        "before": before_snapshot,
-        "after": after_snapshot,
-        "metrics": {
-            "memory": memory_report.clone(),
-            "cpu": cpu_report.clone(),
-            "ports": port_report.clone(),
-            "file_descriptors": fd_report.clone()
-        }
-    });
-    let goal_progress_discrepancy = serde_json::json!({
-        "memory": goal_report_brief_json(&memory_report),
-        "cpu": goal_report_brief_json(&cpu_report),
-        "ports": goal_report_brief_json(&port_report),
-        "file_descriptors": goal_report_brief_json(&fd_report),
-        "respawn_loop_suspected": respawn_by_action.values().any(|detected| *detected),
-    });
+        (base_summary, compare_summary)
+    };
 
-    for outcome in &mut outcomes {
-        if let Some(obj) = outcome.as_object_mut() {
-            let action_id = obj
-                .get("action_id")
-                .and_then(|value| value.as_str())
-                .unwrap_or_default();
-            let respawn_detected = respawn_by_action.get(action_id).copied().unwrap_or(false);
-            obj.insert(
-                "respawn_detected".to_string(),
-                serde_json::json!(respawn_detected),
-            );
-            obj.insert(
-                "goal_progress".to_string(),
-                goal_progress_discrepancy.clone(),
-            );
-        }
+    if base_summary.session_id == compare_summary.session_id {
+        return Err("diff: base and compare sessions must differ".to_string());
     }
 
-    let memory_summary_suffix = format!(
-        ", mem_obs={:.1}MB mem_exp={:.1}MB ({})",
-        memory_report.observed_progress / 1_048_576.0,
-        memory_report.expected_progress / 1_048_576.0,
-        memory_report.classification
-    );
+    let base_id = SessionId::parse(&base_summary.session_id)
+        .ok_or_else(|| format!("diff: invalid base session {}", base_summary.session_id))?;
+    let compare_id = SessionId::parse(&compare_summary.session_id).ok_or_else(|| {
+        format!(
+            "diff: invalid compare session {}",
+            compare_summary.session_id
+        )
+    })?;
 
-    // Write outcomes
-    let action_dir = handle.dir.join("action");
-    let outcomes_path = handle.dir.join("action").join("outcomes.jsonl");
-    let _ = std::fs::create_dir_all(&action_dir);
-    let goal_progress_path = action_dir.join("goal_progress.json");
-    if let Ok(payload) = serde_json::to_string_pretty(&goal_progress_payload) {
-        let _ = std::fs::write(&goal_progress_path, payload);
-    }
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&outcomes_path)
-    {
-        use std::io::Write;
-        for o in &outcomes {
-            let _ = writeln!(file, "{}", o);
-        }
+    Ok((
+        base_id,
+        compare_id,
+        base_summary.label,
+        compare_summary.label,
+    ))
+}
+
+fn filter_diff_deltas(diff: &SessionDiff, args: &DiffArgs) -> Result<Vec<ProcessDelta>, String> {
+    let mut deltas: Vec<ProcessDelta> = diff.deltas.clone();
+
+    if args.changed_only {
+        deltas.retain(|d| d.kind != DeltaKind::Unchanged);
     }
 
-    let final_state = if failed > 0 {
-        SessionState::Failed
-    } else {
-        SessionState::Completed
-    };
-    let _ = handle.update_state(final_state);
+    if let Some(category) = &args.category {
+        let cat = category.trim().to_lowercase();
+        deltas.retain(|d| match cat.as_str() {
+            "new" => d.kind == DeltaKind::New,
+            "gone" | "resolved" | "removed" => d.kind == DeltaKind::Resolved,
+            "changed" => d.kind == DeltaKind::Changed,
+            "unchanged" => d.kind == DeltaKind::Unchanged,
+            "worsened" => d.worsened,
+            "improved" => d.improved,
+            _ => true,
+        });
 
-    let result = serde_json::json!({
-        "session_id": sid.0,
-        "mode": "robot_apply",
-        "summary": {
-            "attempted": actions_to_apply.len(),
-            "succeeded": succeeded,
-            "failed": failed,
-            "skipped": skipped,
-            "blocked_by_constraints": blocked_by_constraints,
-            "blocked_by_prechecks": blocked_by_prechecks,
-            "resumed_skipped": resumed_skipped
-        },
-        "outcomes": outcomes,
-        "goal_progress": goal_progress_payload,
-        "constraints_summary": constraints_summary,
-        "resumed": args.resume
-    });
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            println!("{}", format_structured_output(global, result));
-        }
-        OutputFormat::Summary => {
-            if resumed_skipped > 0 {
-                println!(
-                    "[{}] apply: {} ok, {} fail, {} skip, {} blocked, {} precheck_blocked, {} already done (resumed){}",
-                    sid,
-                    succeeded,
-                    failed,
-                    skipped,
-                    blocked_by_constraints,
-                    blocked_by_prechecks,
-                    resumed_skipped,
-                    memory_summary_suffix
-                );
-            } else {
-                println!(
-                    "[{}] apply: {} ok, {} fail, {} skip, {} blocked, {} precheck_blocked{}",
-                    sid,
-                    succeeded,
-                    failed,
-                    skipped,
-                    blocked_by_constraints,
-                    blocked_by_prechecks,
-                    memory_summary_suffix
-                );
+        match cat.as_str() {
+            "new" | "gone" | "resolved" | "removed" | "changed" | "unchanged" | "worsened"
+            | "improved" => {}
+            _ => {
+                return Err(format!(
+                    "diff: invalid --category '{}'. Use: new, resolved, changed, unchanged, worsened, improved",
+                    category
+                ));
             }
         }
-        _ => println!(
-            "# apply\nSession: {}\nSucceeded: {}\nFailed: {}",
-            sid, succeeded, failed
-        ),
     }
 
-    if (blocked_by_constraints + blocked_by_prechecks) > 0 && succeeded == 0 && failed == 0 {
-        ExitCode::PolicyBlocked
-    } else if failed > 0 {
-        ExitCode::PartialFail
-    } else {
-        ExitCode::ActionsOk
-    }
-}
+    Ok(deltas)
+}
+
+fn summarize_deltas(deltas: &[ProcessDelta]) -> serde_json::Value {
+    let new_count = deltas.iter().filter(|d| d.kind == DeltaKind::New).count();
+    let resolved_count = deltas
+        .iter()
+        .filter(|d| d.kind == DeltaKind::Resolved)
+        .count();
+    let changed_count = deltas
+        .iter()
+        .filter(|d| d.kind == DeltaKind::Changed)
+        .count();
+    let unchanged_count = deltas
+        .iter()
+        .filter(|d| d.kind == DeltaKind::Unchanged)
+        .count();
+    let worsened_count = deltas.iter().filter(|d| d.worsened).count();
+    let improved_count = deltas.iter().filter(|d| d.improved).count();
+
+    serde_json::json!({
+        "total": deltas.len(),
+        "new_count": new_count,
+        "resolved_count": resolved_count,
+        "changed_count": changed_count,
+        "unchanged_count": unchanged_count,
+        "worsened_count": worsened_count,
+        "improved_count": improved_count,
+    })
+}
+
+fn build_cmd_map(records: &[PersistedProcess]) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for rec in records {
+        let cmd = if rec.cmd.is_empty() {
+            rec.comm.clone()
+        } else {
+            rec.cmd.clone()
+        };
+        out.insert(rec.start_id.clone(), cmd);
+    }
+    out
+}
+
+fn truncate_ascii(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+    if max <= 3 {
+        return value.chars().take(max).collect();
+    }
+    let prefix: String = value.chars().take(max - 3).collect();
+    format!("{}...", prefix)
+}
+
+fn format_inference_summary(inf: Option<&InferenceSummary>) -> String {
+    match inf {
+        Some(i) => format!("{} {} {}", i.classification, i.score, i.recommended_action),
+        None => "-".to_string(),
+    }
+}
+
+fn format_diff_plain(
+    base_id: &SessionId,
+    compare_id: &SessionId,
+    base_ts: &str,
+    compare_ts: &str,
+    deltas: &[ProcessDelta],
+    base_cmds: &HashMap<String, String>,
+    compare_cmds: &HashMap<String, String>,
+) -> String {
+    let mut output = String::new();
+
+    output.push_str("# pt diff\n\n");
+    output.push_str(&format!("Base: {} {}\n", base_id.0, base_ts));
+    output.push_str(&format!("Compare: {} {}\n\n", compare_id.0, compare_ts));
+
+    let summary = summarize_deltas(deltas);
+    output.push_str("Summary:\n");
+    output.push_str(&format!(
+        "- New: {} | Resolved: {} | Changed: {} | Unchanged: {} | Worsened: {} | Improved: {}\n\n",
+        summary
+            .get("new_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        summary
+            .get("resolved_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        summary
+            .get("changed_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        summary
+            .get("unchanged_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        summary
+            .get("worsened_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        summary
+            .get("improved_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+    ));
+
+    output.push_str(&format!(
+        "{:>6} {:<9} {:<40} | {:<22} | {:<22} | {:>6}\n",
+        "PID", "KIND", "CMD", "OLD", "NEW", "DELTA"
+    ));
+    output.push_str(&format!(
+        "{:-<6} {:-<9} {:-<40}-+-{:-<22}-+-{:-<22}-+-{:-<6}\n",
+        "", "", "", "", "", ""
+    ));
 
-fn output_apply_nothing(global: &GlobalOpts, sid: &SessionId) {
-    let result = serde_json::json!({"session_id": sid.0, "mode": "robot_apply", "note": "nothing_to_do", "summary": {"attempted": 0}});
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            println!("{}", format_structured_output(global, result));
-        }
-        _ => println!("[{}] apply: nothing to do", sid),
+    for delta in deltas {
+        let cmd = compare_cmds
+            .get(&delta.start_id)
+            .or_else(|| base_cmds.get(&delta.start_id))
+            .cloned()
+            .unwrap_or_else(|| "?".to_string());
+        let cmd = truncate_ascii(&cmd, 40);
+
+        let old_desc = truncate_ascii(&format_inference_summary(delta.old_inference.as_ref()), 22);
+        let new_desc = truncate_ascii(&format_inference_summary(delta.new_inference.as_ref()), 22);
+        let delta_str = delta
+            .score_drift
+            .map(|d| format!("{:+}", d))
+            .unwrap_or_else(|| "-".to_string());
+
+        let kind = match delta.kind {
+            DeltaKind::New => "NEW",
+            DeltaKind::Resolved => "RESOLVED",
+            DeltaKind::Changed => "CHANGED",
+            DeltaKind::Unchanged => "UNCHANGED",
+        };
+
+        output.push_str(&format!(
+            "{:>6} {:<9} {:<40} | {:<22} | {:<22} | {:>6}\n",
+            delta.pid, kind, cmd, old_desc, new_desc, delta_str
+        ));
     }
+
+    output
 }
 
-fn run_agent_verify(global: &GlobalOpts, args: &AgentVerifyArgs) -> ExitCode {
+fn run_session_reconcile(global: &GlobalOpts, args: &SessionReconcileArgs) -> ExitCode {
+    use pt_common::units::format_size;
+    use pt_core::session::reconcile::{reconcile_session, write_reconciliation, ReconcileError};
+
     let store = match SessionStore::from_env() {
         Ok(store) => store,
         Err(e) => {
-            eprintln!("agent verify: session store error: {}", e);
+            eprintln!("session reconcile: session store error: {}", e);
             return ExitCode::InternalError;
         }
     };
-    let sid = match SessionId::parse(&args.session) {
-        Some(sid) => sid,
-        None => {
-            eprintln!("agent verify: invalid --session {}", args.session);
-            return ExitCode::ArgsError;
+    let sid = if let Some(raw) = &args.session {
+        match SessionId::parse(raw) {
+            Some(sid) => sid,
+            None => {
+                eprintln!("session reconcile: invalid --session {}", raw);
+                return ExitCode::ArgsError;
+            }
+        }
+    } else {
+        let options = ListSessionsOptions {
+            limit: Some(1),
+            ..Default::default()
+        };
+        match store.list_sessions(&options) {
+            Ok(sessions) if !sessions.is_empty() => SessionId(sessions[0].session_id.clone()),
+            Ok(_) => {
+                eprintln!("session reconcile: no sessions found");
+                return ExitCode::ArgsError;
+            }
+            Err(e) => {
+                eprintln!("session reconcile: failed to list sessions: {}", e);
+                return ExitCode::InternalError;
+            }
         }
     };
-    if let Err(e) = store.open(&sid) {
-        eprintln!("agent verify: {}", e);
-        return ExitCode::ArgsError;
-    }
     let handle = match store.open(&sid) {
-        Ok(h) => h,
+        Ok(handle) => handle,
         Err(e) => {
-            eprintln!("agent verify: {}", e);
+            eprintln!("session reconcile: {}", e);
             return ExitCode::ArgsError;
         }
     };
 
-    let plan_path = handle.dir.join("decision").join("plan.json");
-    if !plan_path.exists() {
-        eprintln!("agent verify: missing plan.json for session {}", sid);
-        return ExitCode::ArgsError;
-    }
-    let plan_content = match std::fs::read_to_string(&plan_path) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!(
-                "agent verify: failed to read {}: {}",
-                plan_path.display(),
-                e
-            );
-            return ExitCode::IoError;
-        }
-    };
-    let plan = match parse_agent_plan(&plan_content) {
-        Ok(p) => p,
-        Err(VerifyError::InvalidPlan(msg)) => {
-            eprintln!("agent verify: invalid plan.json: {}", msg);
-            return ExitCode::InternalError;
-        }
-        Err(VerifyError::InvalidTimestamp(msg)) => {
-            eprintln!("agent verify: invalid timestamp: {}", msg);
+    let reconciliation = match reconcile_session(&sid.0, &handle.dir) {
+        Ok(r) => r,
+        Err(ReconcileError::MissingPlan(_)) => {
+            eprintln!("session reconcile: missing plan.json for session {}", sid);
             return ExitCode::ArgsError;
         }
-    };
-
-    let requested_at = chrono::Utc::now();
-
-    // Wait for process termination if --wait is specified
-    if args.wait > 0 {
-        let wait_duration = std::time::Duration::from_secs(args.wait);
-        let start = std::time::Instant::now();
-        let target_pids: Vec<u32> = plan
-            .candidates
-            .iter()
-            .filter(|c| c.recommended_action == "terminate" || c.recommended_action == "kill")
-            .map(|c| c.pid)
-            .collect();
-
-        while start.elapsed() < wait_duration {
-            // Check if all target processes have terminated
-            let still_running: Vec<u32> = target_pids
-                .iter()
-                .filter(|pid| std::path::Path::new(&format!("/proc/{}", pid)).exists())
-                .copied()
-                .collect();
-
-            if still_running.is_empty() {
-                break;
-            }
-
-            std::thread::sleep(std::time::Duration::from_millis(250));
-        }
-    }
-
-    let scan_options = QuickScanOptions {
-        pids: vec![],
-        include_kernel_threads: false,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
-        progress: None,
-    };
-    let scan_result = match quick_scan(&scan_options) {
-        Ok(result) => result,
-        Err(e) => {
-            eprintln!("agent verify: scan failed: {}", e);
+        Err(ReconcileError::Invalid { file, message }) => {
+            eprintln!("session reconcile: invalid {}: {}", file, message);
             return ExitCode::InternalError;
         }
-    };
-
-    let completed_at = chrono::Utc::now();
-    let report = verify_plan(&plan, &scan_result.processes, requested_at, completed_at);
-
-    let verify_dir = handle.dir.join("action");
-    if let Err(e) = std::fs::create_dir_all(&verify_dir) {
-        eprintln!(
-            "agent verify: failed to create {}: {}",
-            verify_dir.display(),
-            e
-        );
-        return ExitCode::IoError;
-    }
-    let verify_path = verify_dir.join("verifications.json");
-    if let Err(e) = std::fs::write(&verify_path, serde_json::to_string_pretty(&report).unwrap()) {
-        eprintln!(
-            "agent verify: failed to write {}: {}",
-            verify_path.display(),
-            e
-        );
-        return ExitCode::IoError;
-    }
-
-    if let Ok(manifest) = handle.read_manifest() {
-        if manifest.state != SessionState::Completed {
-            let _ = handle.update_state(SessionState::Completed);
-        }
-    }
-
-    let total = report.action_outcomes.len();
-    let verified_count = report
-        .action_outcomes
-        .iter()
-        .filter(|o| o.verified.unwrap_or(false))
-        .count();
-    let failed_count = total.saturating_sub(verified_count);
-
-    // Check for respawned processes if --check-respawn is set
-    let respawned_count = if args.check_respawn {
-        // Get command signatures of killed processes
-        let killed_commands: Vec<&str> = plan
-            .candidates
-            .iter()
-            .filter(|c| c.recommended_action == "terminate" || c.recommended_action == "kill")
-            .map(|c| {
-                // Prefer cmd_full, fall back to cmd_short
-                if !c.cmd_full.is_empty() {
-                    c.cmd_full.as_str()
-                } else {
-                    c.cmd_short.as_str()
-                }
-            })
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        // Count current processes that match killed command patterns
-        scan_result
-            .processes
-            .iter()
-            .filter(|p| killed_commands.iter().any(|kc| p.cmd.contains(kc)))
-            .count()
-    } else {
-        0
-    };
-
-    let exit_code = match report.verification.overall_status.as_str() {
-        "success" => ExitCode::Clean,
-        "partial_success" => ExitCode::PartialFail,
-        "failure" => ExitCode::PartialFail,
-        _ => ExitCode::Clean,
-    };
-
-    match global.format {
-        OutputFormat::Json | OutputFormat::Toon => {
-            // Extend report with respawn info if checked
-            let mut output = serde_json::to_value(&report).unwrap_or_default();
-            if args.check_respawn {
-                if let Some(obj) = output.as_object_mut() {
-                    obj.insert(
-                        "respawn_check".to_string(),
-                        serde_json::json!({
-                            "enabled": true,
-                            "respawned_count": respawned_count,
-                            "warning": if respawned_count > 0 {
-                                Some(format!("{} processes may have respawned", respawned_count))
-                            } else {
-                                None
-                            }
-                        }),
-                    );
-                }
+        Err(e) => {
+            eprintln!("session reconcile: {}", e);
+            return ExitCode::IoError;
+        }
+    };
+    let path = match write_reconciliation(&handle.dir, &reconciliation) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("session reconcile: failed to write reconciliation: {}", e);
+            return ExitCode::IoError;
+        }
+    };
+
+    let mb = |value: f64| format_size((value.max(0.0) * 1024.0 * 1024.0) as u64);
+    let summary = &reconciliation.summary;
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let mut output = serde_json::to_value(&reconciliation).unwrap_or_default();
+            if let Some(obj) = output.as_object_mut() {
+                obj.insert(
+                    "command".to_string(),
+                    serde_json::json!("session reconcile"),
+                );
+                obj.insert(
+                    "artifact".to_string(),
+                    serde_json::json!(path.display().to_string()),
+                );
             }
             println!("{}", format_structured_output(global, output));
         }
         OutputFormat::Summary => {
-            let freed = report
-                .resource_summary
-                .as_ref()
-                .map(|s| s.memory_freed_mb)
-                .unwrap_or(0.0);
-            let respawn_info = if args.check_respawn && respawned_count > 0 {
-                format!(", {} respawned!", respawned_count)
-            } else {
-                String::new()
-            };
             println!(
-                "[{}] agent verify: {} verified, {} failed (freed {} MB){}",
-                sid, verified_count, failed_count, freed, respawn_info
+                "[{}] session reconcile: {} planned, {} verified, {} respawned, {} failed, {} blocked (recovered {} of {} predicted)",
+                sid,
+                summary.planned,
+                summary.verified,
+                summary.respawned,
+                summary.failed,
+                summary.blocked,
+                mb(summary.recovered_memory_mb),
+                mb(summary.predicted_memory_mb)
             );
         }
         OutputFormat::Exitcode => {}
         _ => {
-            println!(
-                "# pt-core agent verify
-"
-            );
+            println!("# pt-core session reconcile\n");
             println!("Session: {}", sid);
-            println!("Plan: {}", plan_path.display());
+            println!("Artifact: {}\n", path.display());
             println!(
-                "Report: {}
-",
-                verify_path.display()
+                "- Planned: {} ({} verified, {} applied, {} respawned, {} still running, {} failed, {} blocked, {} not applied)",
+                summary.planned,
+                summary.verified,
+                summary.applied,
+                summary.respawned,
+                summary.still_running,
+                summary.failed,
+                summary.blocked,
+                summary.not_applied
             );
+            let ratio = summary
+                .recovery_ratio
+                .map(|r| format!(" ({:.0}%)", r * 100.0))
+                .unwrap_or_default();
             println!(
-                "- Outcomes: {} verified, {} failed",
-                verified_count, failed_count
+                "- Memory: {} recovered of {} predicted{}",
+                mb(summary.recovered_memory_mb),
+                mb(summary.predicted_memory_mb),
+                ratio
             );
-            if let Some(summary) = &report.resource_summary {
+            if summary.predicted_pss_mb.is_some() || summary.predicted_uss_mb.is_some() {
                 println!(
-                    "- Memory freed: {} MB (expected {})",
-                    summary.memory_freed_mb, summary.expected_mb
+                    "- Shared-memory aware: PSS {}, USS {}",
+                    summary
+                        .predicted_pss_mb
+                        .map(mb)
+                        .unwrap_or_else(|| "-".to_string()),
+                    summary
+                        .predicted_uss_mb
+                        .map(mb)
+                        .unwrap_or_else(|| "-".to_string())
                 );
             }
-            if args.check_respawn {
-                println!("- Respawn check: {} processes detected", respawned_count);
-                if respawned_count > 0 {
-                    println!("  ⚠ Warning: Some killed processes may have respawned");
-                }
+            if !reconciliation.verified {
+                println!(
+                    "- Not verified yet: run `pt-core agent verify --session {}` to confirm outcomes",
+                    sid
+                );
             }
-            if let Some(recommendations) = &report.recommendations {
-                if !recommendations.is_empty() {
-                    println!("\n## Recommendations\n");
-                    for rec in recommendations {
-                        println!("- {}", rec);
+            if !reconciliation.actions.is_empty() {
+                println!(
+                    "\n{:>7}  {:<8} {:<13} {:>10} {:>10}  COMMAND",
+                    "PID", "ACTION", "STATUS", "PREDICTED", "RECOVERED"
+                );
+                for action in &reconciliation.actions {
+                    let mut note = String::new();
+                    if let Some(pid) = action.respawn_pid {
+                        note = format!(" (respawned as {})", pid);
+                    } else if let Some(reason) = &action.reason {
+                        note = format!(" ({})", reason);
                     }
+                    println!(
+                        "{:>7}  {:<8} {:<13} {:>10} {:>10}  {}{}",
+                        action.pid,
+                        action.action,
+                        action.status.as_str(),
+                        mb(action.predicted_memory_mb),
+                        mb(action.recovered_memory_mb),
+                        action.command,
+                        note
+                    );
                 }
             }
         }
     }
 
-    // If respawned processes were detected, indicate partial failure
-
-    if args.check_respawn && respawned_count > 0 {
-        ExitCode::PartialFail
-    } else {
-        exit_code
-    }
+    ExitCode::Clean
 }
 
-fn resolve_diff_sessions(
-    store: &SessionStore,
-    args: &DiffArgs,
-) -> Result<(SessionId, SessionId, Option<String>, Option<String>), String> {
-    fn has_required_artifacts(summary: &SessionSummary) -> bool {
-        // Diff requires both inventory + inference artifacts.
-        // Older sessions may not have these yet; skip them to avoid confusing failures.
-        summary.path.join("scan").join("inventory.json").exists()
-            && summary.path.join("inference").join("results.json").exists()
+fn run_redact(global: &GlobalOpts, args: &RedactArgs) -> ExitCode {
+    match &args.command {
+        RedactCommands::Audit(args) => run_redact_audit(global, args),
+        RedactCommands::Dictionary(args) => match &args.command {
+            RedactDictionaryCommands::Export {
+                session,
+                passphrase,
+                out,
+            } => run_redact_dictionary_export(global, session, passphrase, out.as_deref()),
+            RedactDictionaryCommands::Show {
+                path,
+                passphrase,
+                tokens,
+            } => run_redact_dictionary_show(global, path, passphrase, tokens),
+        },
+        RedactCommands::RotateKey(args) => run_redact_rotate_key(global, args),
     }
+}
 
-    if args.baseline && args.last {
-        return Err("diff: --baseline and --last cannot be used together".to_string());
-    }
-    if (args.baseline || args.last) && (args.base.is_some() || args.compare.is_some()) {
-        return Err(
-            "diff: positional sessions cannot be combined with --baseline/--last".to_string(),
-        );
-    }
+fn run_redact_audit(global: &GlobalOpts, args: &RedactAuditArgs) -> ExitCode {
+    use pt_core::redact::audit_session;
 
-    let list_options = ListSessionsOptions {
-        limit: Some(200),
-        state: None,
-        older_than: None,
+    let Some(sid) = SessionId::parse(&args.session) else {
+        eprintln!("redact audit: invalid --session {}", args.session);
+        return ExitCode::ArgsError;
     };
-    let all_sessions = store
-        .list_sessions(&list_options)
-        .map_err(|e| format!("diff: failed to list sessions: {}", e))?;
-    if all_sessions.is_empty() {
-        return Err("diff: no sessions found".to_string());
-    }
-
-    let sessions: Vec<SessionSummary> = all_sessions
-        .into_iter()
-        .filter(has_required_artifacts)
-        .collect();
-    if sessions.is_empty() {
-        return Err(
-            "diff: no sessions with required artifacts (need scan inventory + inference results)"
-                .to_string(),
-        );
-    }
-
-    let use_last = args.last || (!args.baseline && args.base.is_none());
-
-    let (base_summary, compare_summary) = if args.baseline {
-        let base = sessions
-            .iter()
-            .find(|s| {
-                s.label
-                    .as_deref()
-                    .map(|l| l.eq_ignore_ascii_case("baseline"))
-                    .unwrap_or(false)
-            })
-            .cloned()
-            .ok_or_else(|| {
-                "diff: no baseline session found (label a session 'baseline')".to_string()
-            })?;
-        let compare = sessions
-            .iter()
-            .find(|s| s.session_id != base.session_id)
-            .cloned()
-            .ok_or_else(|| "diff: need at least two sessions to compare".to_string())?;
-        (base, compare)
-    } else if use_last {
-        if sessions.len() < 2 {
-            return Err("diff: need at least two sessions to compare".to_string());
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("redact audit: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let handle = match store.open(&sid) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("redact audit: {}", e);
+            return ExitCode::SessionError;
         }
-        (sessions[1].clone(), sessions[0].clone())
-    } else {
-        let base_raw = args
-            .base
-            .as_ref()
-            .ok_or_else(|| "diff: base session required".to_string())?;
-
-        let compare_summary = match args.compare.as_deref() {
-            Some("current") | Some("latest") | None => sessions
-                .iter()
-                .find(|s| s.session_id != *base_raw)
-                .cloned()
-                .ok_or_else(|| {
-                    "diff: no compare session found (need at least two sessions)".to_string()
-                })?,
-            Some(raw) => sessions
-                .iter()
-                .find(|s| s.session_id == raw)
-                .cloned()
-                .unwrap_or_else(|| SessionSummary {
-                    session_id: raw.to_string(),
-                    created_at: String::new(),
-                    state: SessionState::Created,
-                    mode: SessionMode::ScanOnly,
-                    label: None,
-                    host_id: None,
-                    candidates_count: None,
-                    actions_count: None,
-                    path: PathBuf::new(),
-                }),
-        };
-
-        let base_summary = sessions
-            .iter()
-            .find(|s| s.session_id == *base_raw)
-            .cloned()
-            .unwrap_or_else(|| SessionSummary {
-                session_id: base_raw.to_string(),
-                created_at: String::new(),
-                state: SessionState::Created,
-                mode: SessionMode::ScanOnly,
-                label: None,
-                host_id: None,
-                candidates_count: None,
-                actions_count: None,
-                path: PathBuf::new(),
-            });
-
-        (base_summary, compare_summary)
     };
 
-    if base_summary.session_id == compare_summary.session_id {
-        return Err("diff: base and compare sessions must differ".to_string());
-    }
-
-    let base_id = SessionId::parse(&base_summary.session_id)
-        .ok_or_else(|| format!("diff: invalid base session {}", base_summary.session_id))?;
-    let compare_id = SessionId::parse(&compare_summary.session_id).ok_or_else(|| {
-        format!(
-            "diff: invalid compare session {}",
-            compare_summary.session_id
-        )
-    })?;
-
-    Ok((
-        base_id,
-        compare_id,
-        base_summary.label,
-        compare_summary.label,
-    ))
-}
-
-fn filter_diff_deltas(diff: &SessionDiff, args: &DiffArgs) -> Result<Vec<ProcessDelta>, String> {
-    let mut deltas: Vec<ProcessDelta> = diff.deltas.clone();
-
-    if args.changed_only {
-        deltas.retain(|d| d.kind != DeltaKind::Unchanged);
-    }
-
-    if let Some(category) = &args.category {
-        let cat = category.trim().to_lowercase();
-        deltas.retain(|d| match cat.as_str() {
-            "new" => d.kind == DeltaKind::New,
-            "gone" | "resolved" | "removed" => d.kind == DeltaKind::Resolved,
-            "changed" => d.kind == DeltaKind::Changed,
-            "unchanged" => d.kind == DeltaKind::Unchanged,
-            "worsened" => d.worsened,
-            "improved" => d.improved,
-            _ => true,
-        });
-
-        match cat.as_str() {
-            "new" | "gone" | "resolved" | "removed" | "changed" | "unchanged" | "worsened"
-            | "improved" => {}
-            _ => {
-                return Err(format!(
-                    "diff: invalid --category '{}'. Use: new, resolved, changed, unchanged, worsened, improved",
-                    category
-                ));
+    let config_dir = resolve_config_dir(global);
+    let redactor = match &args.policy {
+        Some(path) => match pt_redact::RedactionPolicy::load(path) {
+            Ok(policy) => OutputRedactor::with_policy(
+                &config_dir,
+                policy,
+                global.redaction_profile,
+                global.no_redact,
+            ),
+            Err(e) => {
+                eprintln!("redact audit: failed to load {}: {}", path.display(), e);
+                return ExitCode::ArgsError;
+            }
+        },
+        None => output_redactor(global),
+    };
+    let redactor = match redactor {
+        Ok(redactor) => redactor,
+        Err(e) => {
+            eprintln!("redact audit: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let report = match audit_session(&handle.dir, redactor.engine(), redactor.profile()) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!(
+                "redact audit: failed to read {}: {}",
+                handle.dir.display(),
+                e
+            );
+            return ExitCode::IoError;
+        }
+    };
+    let gaps = report.gap_count();
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let mut output = serde_json::to_value(&report).unwrap_or_default();
+            if let Some(obj) = output.as_object_mut() {
+                obj.insert(
+                    "schema_version".to_string(),
+                    serde_json::json!(SCHEMA_VERSION),
+                );
+                obj.insert("session_id".to_string(), serde_json::json!(sid.0));
+                obj.insert("command".to_string(), serde_json::json!("redact audit"));
+                obj.insert("gaps".to_string(), serde_json::json!(gaps));
+            }
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "[{}] redact audit: {} fields in {} files, {} secrets found, {} policy gaps ({} profile)",
+                sid,
+                report.fields_scanned,
+                report.files_scanned,
+                report.findings.len(),
+                gaps,
+                report.profile
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core redact audit\n");
+            println!("Session: {}", sid);
+            println!(
+                "Profile: {} (policy {})\n",
+                report.profile, report.policy_version
+            );
+            println!(
+                "- Scanned: {} fields in {} files",
+                report.fields_scanned, report.files_scanned
+            );
+            for file in &report.skipped_files {
+                println!("- Skipped unreadable artifact: {}", file);
+            }
+            println!(
+                "- Secrets found: {} ({} redacted by policy, {} gaps)",
+                report.findings.len(),
+                report.findings.len() - gaps,
+                gaps
+            );
+            if !report.findings.is_empty() {
+                println!(
+                    "\n{:<5} {:<14} {:<18} {:<22} LOCATION",
+                    "GAP", "CLASS", "SECRET", "PREVIEW"
+                );
+                for finding in &report.findings {
+                    let location = match finding.line {
+                        Some(line) => format!("{}:{}#{}", finding.file, line, finding.pointer),
+                        None => format!("{}#{}", finding.file, finding.pointer),
+                    };
+                    let class = finding.field_class.to_string();
+                    let secret = serde_json::to_value(finding.secret_type)
+                        .ok()
+                        .and_then(|v| v.as_str().map(str::to_string))
+                        .unwrap_or_default();
+                    println!(
+                        "{:<5} {:<14} {:<18} {:<22} {}",
+                        if finding.redacted { "" } else { "yes" },
+                        class,
+                        secret,
+                        finding.preview,
+                        location
+                    );
+                }
             }
         }
     }
 
-    Ok(deltas)
+    if gaps > 0 {
+        ExitCode::PolicyBlocked
+    } else {
+        ExitCode::Clean
+    }
 }
 
-fn summarize_deltas(deltas: &[ProcessDelta]) -> serde_json::Value {
-    let new_count = deltas.iter().filter(|d| d.kind == DeltaKind::New).count();
-    let resolved_count = deltas
-        .iter()
-        .filter(|d| d.kind == DeltaKind::Resolved)
-        .count();
-    let changed_count = deltas
-        .iter()
-        .filter(|d| d.kind == DeltaKind::Changed)
-        .count();
-    let unchanged_count = deltas
-        .iter()
-        .filter(|d| d.kind == DeltaKind::Unchanged)
-        .count();
-    let worsened_count = deltas.iter().filter(|d| d.worsened).count();
-    let improved_count = deltas.iter().filter(|d| d.improved).count();
-
-    serde_json::json!({
-        "total": deltas.len(),
-        "new_count": new_count,
-        "resolved_count": resolved_count,
-        "changed_count": changed_count,
-        "unchanged_count": unchanged_count,
-        "worsened_count": worsened_count,
-        "improved_count": improved_count,
-    })
-}
+fn run_redact_dictionary_export(
+    global: &GlobalOpts,
+    session: &str,
+    passphrase_arg: &Option<String>,
+    out: Option<&Path>,
+) -> ExitCode {
+    use pt_core::redact::{dictionary, DictionaryError, KEY_FILE};
 
-fn build_cmd_map(records: &[PersistedProcess]) -> HashMap<String, String> {
-    let mut out = HashMap::new();
-    for rec in records {
-        let cmd = if rec.cmd.is_empty() {
-            rec.comm.clone()
-        } else {
-            rec.cmd.clone()
-        };
-        out.insert(rec.start_id.clone(), cmd);
-    }
-    out
-}
+    let Some(passphrase) = resolve_bundle_passphrase(passphrase_arg).filter(|p| !p.is_empty())
+    else {
+        eprintln!(
+            "redact dictionary export: no passphrase provided (use --passphrase or PT_BUNDLE_PASSPHRASE)"
+        );
+        return ExitCode::ArgsError;
+    };
+    let Some(sid) = SessionId::parse(session) else {
+        eprintln!("redact dictionary export: invalid --session {}", session);
+        return ExitCode::ArgsError;
+    };
+    let store = match SessionStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("redact dictionary export: session store error: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let handle = match store.open(&sid) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("redact dictionary export: {}", e);
+            return ExitCode::SessionError;
+        }
+    };
 
-fn truncate_ascii(value: &str, max: usize) -> String {
-    if value.chars().count() <= max {
-        return value.to_string();
-    }
-    if max <= 3 {
-        return value.chars().take(max).collect();
+    let key_path = resolve_config_dir(global).join(KEY_FILE);
+    let keys = match pt_redact::KeyManager::load(&key_path) {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!(
+                "redact dictionary export: failed to load {}: {}",
+                key_path.display(),
+                e
+            );
+            return ExitCode::ArgsError;
+        }
+    };
+    let dictionary = match dictionary::load(&handle.dir, &sid.0, &keys) {
+        Ok(dictionary) => dictionary,
+        Err(e @ DictionaryError::NotFound(_)) => {
+            eprintln!("redact dictionary export: {}", e);
+            return ExitCode::ArgsError;
+        }
+        Err(e) => {
+            eprintln!("redact dictionary export: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let bytes = match dictionary::export(&dictionary, &passphrase) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("redact dictionary export: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let out_path = out
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.dictionary.enc", sid.0)));
+    if let Err(e) = std::fs::write(&out_path, &bytes) {
+        eprintln!(
+            "redact dictionary export: failed to write {}: {}",
+            out_path.display(),
+            e
+        );
+        return ExitCode::IoError;
     }
-    let prefix: String = value.chars().take(max - 3).collect();
-    format!("{}...", prefix)
-}
 
-fn format_inference_summary(inf: Option<&InferenceSummary>) -> String {
-    match inf {
-        Some(i) => format!("{} {} {}", i.classification, i.score, i.recommended_action),
-        None => "-".to_string(),
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": sid.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "redact dictionary export",
+                "status": "ok",
+                "dictionary": {
+                    "path": out_path.display().to_string(),
+                    "entries": dictionary.entries.len(),
+                    "key_ids": dictionary.key_ids,
+                },
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "[{}] redact dictionary export: {} tokens -> {}",
+                sid,
+                dictionary.entries.len(),
+                out_path.display()
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core redact dictionary export\n");
+            println!("Session: {}", sid);
+            println!("- Tokens: {}", dictionary.entries.len());
+            println!("- Keys: {}", dictionary.key_ids.join(", "));
+            println!("- Written: {}", out_path.display());
+        }
     }
+    ExitCode::Clean
 }
 
-fn format_diff_plain(
-    base_id: &SessionId,
-    compare_id: &SessionId,
-    base_ts: &str,
-    compare_ts: &str,
-    deltas: &[ProcessDelta],
-    base_cmds: &HashMap<String, String>,
-    compare_cmds: &HashMap<String, String>,
-) -> String {
-    let mut output = String::new();
-
-    output.push_str("# pt diff\n\n");
-    output.push_str(&format!("Base: {} {}\n", base_id.0, base_ts));
-    output.push_str(&format!("Compare: {} {}\n\n", compare_id.0, compare_ts));
-
-    let summary = summarize_deltas(deltas);
-    output.push_str("Summary:\n");
-    output.push_str(&format!(
-        "- New: {} | Resolved: {} | Changed: {} | Unchanged: {} | Worsened: {} | Improved: {}\n\n",
-        summary
-            .get("new_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        summary
-            .get("resolved_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        summary
-            .get("changed_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        summary
-            .get("unchanged_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        summary
-            .get("worsened_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        summary
-            .get("improved_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-    ));
-
-    output.push_str(&format!(
-        "{:>6} {:<9} {:<40} | {:<22} | {:<22} | {:>6}\n",
-        "PID", "KIND", "CMD", "OLD", "NEW", "DELTA"
-    ));
-    output.push_str(&format!(
-        "{:-<6} {:-<9} {:-<40}-+-{:-<22}-+-{:-<22}-+-{:-<6}\n",
-        "", "", "", "", "", ""
-    ));
+fn run_redact_dictionary_show(
+    global: &GlobalOpts,
+    path: &Path,
+    passphrase_arg: &Option<String>,
+    tokens: &[String],
+) -> ExitCode {
+    use pt_core::redact::dictionary;
 
-    for delta in deltas {
-        let cmd = compare_cmds
-            .get(&delta.start_id)
-            .or_else(|| base_cmds.get(&delta.start_id))
-            .cloned()
-            .unwrap_or_else(|| "?".to_string());
-        let cmd = truncate_ascii(&cmd, 40);
+    let Some(passphrase) = resolve_bundle_passphrase(passphrase_arg).filter(|p| !p.is_empty())
+    else {
+        eprintln!(
+            "redact dictionary show: no passphrase provided (use --passphrase or PT_BUNDLE_PASSPHRASE)"
+        );
+        return ExitCode::ArgsError;
+    };
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!(
+                "redact dictionary show: failed to read {}: {}",
+                path.display(),
+                e
+            );
+            return ExitCode::IoError;
+        }
+    };
+    let dictionary = match dictionary::import(&bytes, &passphrase) {
+        Ok(dictionary) => dictionary,
+        Err(e) => {
+            eprintln!("redact dictionary show: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+    let entries = if tokens.is_empty() {
+        dictionary.entries.clone()
+    } else {
+        dictionary.select(tokens)
+    };
+    let missing: Vec<&String> = tokens
+        .iter()
+        .filter(|token| !entries.contains_key(*token))
+        .collect();
 
-        let old_desc = truncate_ascii(&format_inference_summary(delta.old_inference.as_ref()), 22);
-        let new_desc = truncate_ascii(&format_inference_summary(delta.new_inference.as_ref()), 22);
-        let delta_str = delta
-            .score_drift
-            .map(|d| format!("{:+}", d))
-            .unwrap_or_else(|| "-".to_string());
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": dictionary.session_id,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "redact dictionary show",
+                "dictionary_schema_version": dictionary.schema_version,
+                "key_ids": dictionary.key_ids,
+                "entries": entries,
+                "missing": missing,
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "[{}] redact dictionary show: {} tokens, {} not found",
+                dictionary.session_id,
+                entries.len(),
+                missing.len()
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core redact dictionary show\n");
+            println!("Session: {}", dictionary.session_id);
+            println!("Keys: {}\n", dictionary.key_ids.join(", "));
+            for (token, value) in &entries {
+                println!("{}  {}", token, value);
+            }
+            for token in &missing {
+                println!("{}  (not in dictionary)", token);
+            }
+        }
+    }
+    ExitCode::Clean
+}
 
-        let kind = match delta.kind {
-            DeltaKind::New => "NEW",
-            DeltaKind::Resolved => "RESOLVED",
-            DeltaKind::Changed => "CHANGED",
-            DeltaKind::Unchanged => "UNCHANGED",
+fn run_redact_rotate_key(global: &GlobalOpts, args: &RedactRotateKeyArgs) -> ExitCode {
+    use pt_core::redact::rotation::{self, RotationError};
+    use pt_telemetry::key_rotation::{write_key_rotation, KeyRotationEvent};
+
+    // Resolve sessions before touching the key, so a bad --session leaves it
+    // in place.
+    let mut targets: Vec<(String, PathBuf)> = Vec::new();
+    if !args.no_rehash {
+        let store = match SessionStore::from_env() {
+            Ok(store) => store,
+            Err(e) => {
+                eprintln!("redact rotate-key: session store error: {}", e);
+                return ExitCode::InternalError;
+            }
+        };
+        for id in &args.sessions {
+            let Some(sid) = SessionId::parse(id) else {
+                eprintln!("redact rotate-key: invalid --session {}", id);
+                return ExitCode::ArgsError;
+            };
+            match store.open(&sid) {
+                Ok(handle) => targets.push((sid.0, handle.dir)),
+                Err(e) => {
+                    eprintln!("redact rotate-key: {}", e);
+                    return ExitCode::SessionError;
+                }
+            }
+        }
+        let sessions = match store.list_sessions(&ListSessionsOptions::default()) {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                eprintln!("redact rotate-key: failed to list sessions: {}", e);
+                return ExitCode::InternalError;
+            }
         };
+        for session in sessions {
+            if rotation::is_forward_looking(session.state)
+                && !targets.iter().any(|(id, _)| *id == session.session_id)
+            {
+                targets.push((session.session_id, session.path));
+            }
+        }
+    }
 
-        output.push_str(&format!(
-            "{:>6} {:<9} {:<40} | {:<22} | {:<22} | {:>6}\n",
-            delta.pid, kind, cmd, old_desc, new_desc, delta_str
-        ));
+    let config_dir = resolve_config_dir(global);
+    let (keys, mut rotation) = match rotation::rotate_key(&config_dir, args.reason.clone()) {
+        Ok(rotated) => rotated,
+        Err(e @ RotationError::NoKey(_)) => {
+            eprintln!("redact rotate-key: {}", e);
+            return ExitCode::ArgsError;
+        }
+        Err(e) => {
+            eprintln!("redact rotate-key: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    for (session_id, dir) in &targets {
+        match rotation::rehash_session(dir, session_id, &keys) {
+            Ok(rehashed) => {
+                if rehashed.tokens > 0 || rehashed.unmapped > 0 {
+                    rotation.sessions.push(rehashed);
+                }
+            }
+            Err(e) => eprintln!(
+                "redact rotate-key: warning: failed to re-hash session {}: {}",
+                session_id, e
+            ),
+        }
+    }
+    if let Err(e) = rotation::record_rotation(&config_dir, &rotation) {
+        eprintln!(
+            "redact rotate-key: warning: failed to record rotation: {}",
+            e
+        );
     }
 
-    output
+    let telemetry_dir = args
+        .telemetry_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(default_telemetry_dir);
+    let event = KeyRotationEvent {
+        session_id: SessionId::new().0,
+        rotated_at: chrono::Utc::now(),
+        actor: "user".to_string(),
+        previous_key_id: rotation.previous_key_id.clone(),
+        key_id: rotation.key_id.clone(),
+        reason: rotation.reason.clone(),
+    };
+    let marker = match write_key_rotation(&telemetry_dir, &pt_core::logging::get_host_id(), &event)
+    {
+        Ok(path) => Some(path),
+        Err(e) => {
+            eprintln!(
+                "redact rotate-key: warning: failed to write telemetry marker: {}",
+                e
+            );
+            None
+        }
+    };
+
+    let tokens: usize = rotation.sessions.iter().map(|s| s.tokens).sum();
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "redact rotate-key",
+                "status": "ok",
+                "rotation": rotation,
+                "telemetry_marker": marker.as_ref().map(|p| p.display().to_string()),
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Summary => {
+            println!(
+                "redact rotate-key: {} -> {}, {} tokens re-hashed in {} sessions",
+                rotation.previous_key_id,
+                rotation.key_id,
+                tokens,
+                rotation.sessions.len()
+            );
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# pt-core redact rotate-key\n");
+            println!(
+                "- Key: {} -> {} (previous key kept for lookups)",
+                rotation.previous_key_id, rotation.key_id
+            );
+            if let Some(reason) = &rotation.reason {
+                println!("- Reason: {}", reason);
+            }
+            if args.no_rehash {
+                println!("- Sessions: not re-hashed (--no-rehash)");
+            } else {
+                println!(
+                    "- Re-hashed: {} tokens in {} sessions",
+                    tokens,
+                    rotation.sessions.len()
+                );
+                for session in &rotation.sessions {
+                    println!(
+                        "  - {}: {} tokens in {} files{}",
+                        session.session_id,
+                        session.tokens,
+                        session.files,
+                        if session.unmapped > 0 {
+                            format!(", {} left on the old key", session.unmapped)
+                        } else {
+                            String::new()
+                        }
+                    );
+                }
+            }
+            if let Some(path) = &marker {
+                println!("- Telemetry marker: {}", path.display());
+            }
+        }
+    }
+    ExitCode::Clean
 }
 
 fn run_diff(global: &GlobalOpts, args: &DiffArgs) -> ExitCode {
@@ -14783,8 +22684,14 @@ fn run_agent_export_priors(global: &GlobalOpts, args: &AgentExportPriorsArgs) ->
 fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) -> ExitCode {
     use pt_core::config::priors::Priors;
 
-    // Default to merge if neither --merge nor --replace specified
-    let mode = if args.replace { "replace" } else { "merge" };
+    // Default to merge if neither --merge, --replace nor --shrink specified
+    let mode = if args.replace {
+        "replace"
+    } else if args.shrink {
+        "shrink"
+    } else {
+        "merge"
+    };
 
     // Read the input file
     let input_path = PathBuf::from(&args.from);
@@ -14854,18 +22761,7 @@ fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) ->
     };
 
     // Determine priors output path
-    let priors_path = config.snapshot().priors_path.unwrap_or_else(|| {
-        global
-            .config
-            .as_ref()
-            .map(|c| PathBuf::from(c).join("priors.json"))
-            .unwrap_or_else(|| {
-                dirs::config_dir()
-                    .unwrap_or_else(|| PathBuf::from("."))
-                    .join("pt")
-                    .join("priors.json")
-            })
-    });
+    let priors_path = priors_write_path(global, &config);
 
     // Check host profile compatibility
     if let Some(ref filter_profile) = args.host_profile {
@@ -14880,8 +22776,24 @@ fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) ->
     }
 
     // Compute the final priors
+    let mut shrink_strength = None;
     let final_priors = if mode == "replace" {
         imported_priors
+    } else if mode == "shrink" {
+        use pt_core::config::pooling::{resolve_strength, shrink_toward_group};
+        use pt_math::hierarchical::HostProfile;
+
+        let profile_name = args.host_profile.as_deref().unwrap_or_default();
+        let Some(profile) = HostProfile::parse(profile_name) else {
+            eprintln!(
+                "agent import-priors: unknown host profile '{}' (expected laptop, ci_runner, k8s_node or server)",
+                profile_name
+            );
+            return ExitCode::ArgsError;
+        };
+        let strength = resolve_strength(args.shrink_strength, &config.priors, profile);
+        shrink_strength = Some(strength);
+        shrink_toward_group(&config.priors, &imported_priors, profile, strength)
     } else {
         // Merge mode: weighted combination
         // For now, we do a simple replacement of class priors that exist in the import
@@ -14913,6 +22825,7 @@ fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) ->
         let response = serde_json::json!({
             "dry_run": true,
             "mode": mode,
+            "shrink_strength": shrink_strength,
             "source": input_path.display().to_string(),
             "target": priors_path.display().to_string(),
             "changes": {
@@ -14953,31 +22866,8 @@ fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) ->
     }
 
     // Write the priors atomically
-    let tmp_path = priors_path.with_extension("json.tmp");
-    let payload = match serde_json::to_vec_pretty(&final_priors) {
-        Ok(bytes) => bytes,
-        Err(err) => {
-            eprintln!("agent import-priors: failed to serialize: {}", err);
-            return ExitCode::IoError;
-        }
-    };
-
-    if let Err(err) = std::fs::write(&tmp_path, payload) {
-        eprintln!(
-            "agent import-priors: failed to write {}: {}",
-            tmp_path.display(),
-            err
-        );
-        return ExitCode::IoError;
-    }
-
-    if let Err(err) = std::fs::rename(&tmp_path, &priors_path) {
-        eprintln!(
-            "agent import-priors: failed to rename {} to {}: {}",
-            tmp_path.display(),
-            priors_path.display(),
-            err
-        );
+    if let Err(err) = write_priors_atomically(&priors_path, &final_priors) {
+        eprintln!("agent import-priors: {}", err);
         return ExitCode::IoError;
     }
 
@@ -14985,6 +22875,7 @@ fn run_agent_import_priors(global: &GlobalOpts, args: &AgentImportPriorsArgs) ->
     let response = serde_json::json!({
         "imported": true,
         "mode": mode,
+        "shrink_strength": shrink_strength,
         "source": input_path.display().to_string(),
         "target": priors_path.display().to_string(),
         "class_priors": {
@@ -15023,8 +22914,13 @@ fn run_agent_export(global: &GlobalOpts, args: &AgentExportArgs) -> ExitCode {
         &args.profile,
         args.include_telemetry,
         args.include_dumps,
+        &args.since,
+        pt_redact::CaptureAllowlist::new()
+            .with_env_names(args.allow_env.iter().cloned())
+            .with_cmdline_patterns(args.allow_arg.iter().cloned()),
         args.encrypt,
         &args.passphrase,
+        &args.recipients,
     )
 }
 
@@ -15178,6 +23074,10 @@ fn output_agent_init_result(global: &GlobalOpts, result: &pt_core::agent_init::I
 fn run_agent_inbox(global: &GlobalOpts, args: &AgentInboxArgs) -> ExitCode {
     use pt_core::inbox::{InboxResponse, InboxStore};
 
+    if args.snoozed || args.snooze.is_some() || args.unsnooze.is_some() {
+        return run_agent_inbox_snooze(global, args);
+    }
+
     let store = match InboxStore::from_env() {
         Ok(store) => store,
         Err(e) => {
@@ -15338,10 +23238,266 @@ fn run_agent_inbox(global: &GlobalOpts, args: &AgentInboxArgs) -> ExitCode {
     ExitCode::Clean
 }
 
+/// Look up a live process by PID and remember it as spared.
+///
+/// Writes a [`pt_core::signature_cli::spared_signature`] for the process's
+/// exact command line into the user signatures file and returns its name.
+fn remember_spared_pid(pid: u32, timeout: Option<u64>) -> Result<String, String> {
+    let scan_options = QuickScanOptions {
+        pids: vec![pid],
+        include_kernel_threads: false,
+        timeout: timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+    let proc = quick_scan(&scan_options)
+        .map_err(|e| format!("scan failed: {}", e))?
+        .processes
+        .into_iter()
+        .find(|p| p.pid.0 == pid)
+        .ok_or_else(|| format!("process {} not found", pid))?;
+    let signature = pt_core::signature_cli::remember_spared(&proc.comm, &proc.cmd)
+        .map_err(|e| format!("failed to save signature: {}", e))?;
+    Ok(signature.name)
+}
+
+fn run_agent_spare(global: &GlobalOpts, args: &AgentSpareArgs) -> ExitCode {
+    use pt_core::snooze::{default_snooze_duration, SnoozeEntry, SnoozeStore};
+
+    if args.remember {
+        return match remember_spared_pid(args.pid, global.timeout) {
+            Ok(name) => {
+                match global.format {
+                    OutputFormat::Json | OutputFormat::Toon => {
+                        let response = serde_json::json!({
+                            "spared": true,
+                            "remembered": true,
+                            "pid": args.pid,
+                            "signature": name,
+                            "path": pt_core::signature_cli::user_signatures_path()
+                                .display()
+                                .to_string(),
+                        });
+                        println!("{}", format_structured_output(global, response));
+                    }
+                    _ => {
+                        println!("Remembered PID {} as signature '{}'", args.pid, name);
+                        println!("Remove it with: pt signature remove {} --force", name);
+                    }
+                }
+                ExitCode::Clean
+            }
+            Err(e) => {
+                eprintln!("agent spare: {}", e);
+                ExitCode::ArgsError
+            }
+        };
+    }
+
+    let store = match SnoozeStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent spare: failed to access snooze store: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let scan_options = QuickScanOptions {
+        pids: vec![args.pid],
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: None,
+        io_rate_window: None,
+    };
+    let proc = match quick_scan(&scan_options) {
+        Ok(scan) => scan.processes.into_iter().find(|p| p.pid.0 == args.pid),
+        Err(e) => {
+            eprintln!("agent spare: scan failed: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let Some(proc) = proc else {
+        eprintln!("agent spare: process {} not found", args.pid);
+        return ExitCode::ArgsError;
+    };
+    let entry = SnoozeEntry::snooze(proc.uid, args.pid, &proc.cmd, default_snooze_duration());
+    if let Err(e) = store.upsert(entry.clone()) {
+        eprintln!("agent spare: {}", e);
+        return ExitCode::InternalError;
+    }
+    let suggestions = pt_core::signature_cli::record_decisions(&[DecisionRecord::new(
+        &proc.comm,
+        &proc.cmd,
+        DecisionAction::Spare,
+    )]);
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let response = serde_json::json!({
+                "spared": true,
+                "remembered": false,
+                "pid": args.pid,
+                "entry": entry,
+                "signature_suggestions": suggestions.iter().map(|s| &s.name).collect::<Vec<_>>(),
+            });
+            println!("{}", format_structured_output(global, response));
+        }
+        _ => {
+            println!(
+                "Spared PID {} until {} (use --remember to never suggest it again)",
+                args.pid,
+                entry.until.as_deref().unwrap_or("-")
+            );
+            for suggestion in &suggestions {
+                println!("{}", suggestion.prompt());
+                println!("  Accept: pt signature accept {}", suggestion.name);
+            }
+        }
+    }
+    ExitCode::Clean
+}
+
+fn run_agent_inbox_snooze(global: &GlobalOpts, args: &AgentInboxArgs) -> ExitCode {
+    use pt_core::snooze::{
+        default_snooze_duration, parse_snooze_duration, SnoozeEntry, SnoozeResponse, SnoozeStore,
+    };
+
+    let store = match SnoozeStore::from_env() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("agent inbox: failed to access snooze store: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+
+    if let Some(ref fingerprint) = args.unsnooze {
+        return match store.remove(fingerprint) {
+            Ok(entry) => {
+                match global.format {
+                    OutputFormat::Json | OutputFormat::Toon => {
+                        let response = serde_json::json!({
+                            "unsnoozed": true,
+                            "fingerprint": entry.fingerprint,
+                            "command": entry.command,
+                        });
+                        println!("{}", format_structured_output(global, response));
+                    }
+                    _ => println!("Unsnoozed: {} ({})", entry.fingerprint, entry.command),
+                }
+                ExitCode::Clean
+            }
+            Err(e) => {
+                eprintln!("agent inbox: {}", e);
+                ExitCode::ArgsError
+            }
+        };
+    }
+
+    if let Some(pid) = args.snooze {
+        let duration = match args.snooze_for.as_deref() {
+            Some(raw) => match parse_snooze_duration(raw) {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!("agent inbox: {}", e);
+                    return ExitCode::ArgsError;
+                }
+            },
+            None => default_snooze_duration(),
+        };
+        let scan_options = QuickScanOptions {
+            pids: vec![pid],
+            include_kernel_threads: false,
+            timeout: global.timeout.map(std::time::Duration::from_secs),
+            progress: None,
+            incremental: None,
+            io_rate_window: None,
+        };
+        let proc = match quick_scan(&scan_options) {
+            Ok(scan) => scan.processes.into_iter().find(|p| p.pid.0 == pid),
+            Err(e) => {
+                eprintln!("agent inbox: scan failed: {}", e);
+                return ExitCode::InternalError;
+            }
+        };
+        let Some(proc) = proc else {
+            eprintln!("agent inbox: process {} not found", pid);
+            return ExitCode::ArgsError;
+        };
+        let entry = if args.pin {
+            SnoozeEntry::pin(proc.uid, pid, &proc.cmd)
+        } else {
+            SnoozeEntry::snooze(proc.uid, pid, &proc.cmd, duration)
+        };
+        if let Err(e) = store.upsert(entry.clone()) {
+            eprintln!("agent inbox: {}", e);
+            return ExitCode::InternalError;
+        }
+        match global.format {
+            OutputFormat::Json | OutputFormat::Toon => {
+                let response = serde_json::json!({
+                    "snoozed": true,
+                    "pinned": entry.is_pinned(),
+                    "entry": entry,
+                });
+                println!("{}", format_structured_output(global, response));
+            }
+            _ => match entry.until.as_deref() {
+                Some(until) => println!("Snoozed {} until {}", entry.fingerprint, until),
+                None => println!("Pinned {}", entry.fingerprint),
+            },
+        }
+        return ExitCode::Clean;
+    }
+
+    let entries = match store.list_active(chrono::Utc::now()) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("agent inbox: {}", e);
+            return ExitCode::InternalError;
+        }
+    };
+    let response = SnoozeResponse::new(entries);
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let value = serde_json::to_value(&response).unwrap_or_default();
+            println!("{}", format_structured_output(global, value));
+        }
+        OutputFormat::Jsonl => {
+            for entry in &response.snoozed {
+                println!("{}", serde_json::to_string(entry).unwrap_or_default());
+            }
+        }
+        OutputFormat::Summary => {
+            println!(
+                "Snoozed: {} ({} pinned)",
+                response.snoozed.len(),
+                response.pinned_count
+            );
+        }
+        OutputFormat::Exitcode => {}
+        OutputFormat::Metrics => {
+            println!("snoozed_total={}", response.snoozed.len());
+            println!("snoozed_pinned={}", response.pinned_count);
+        }
+        _ => {
+            println!("# Snoozed Candidates\n");
+            if response.snoozed.is_empty() {
+                println!("No snoozed candidates.");
+            }
+            for entry in &response.snoozed {
+                let until = entry.until.as_deref().unwrap_or("pinned");
+                println!("{} [{}] {}", entry.fingerprint, until, entry.command);
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
 fn run_agent_tail(_global: &GlobalOpts, args: &AgentTailArgs) -> ExitCode {
-    use std::io::{BufRead, BufReader, Write};
-    use std::thread::sleep;
-    use std::time::Duration;
+    use pt_events::JsonlFollower;
+    use std::io::Write;
 
     let store = match SessionStore::from_env() {
         Ok(store) => store,
@@ -15368,69 +23524,49 @@ fn run_agent_tail(_global: &GlobalOpts, args: &AgentTailArgs) -> ExitCode {
     };
 
     let log_path = handle.dir.join("logs").join("session.jsonl");
+    if !args.follow && !log_path.exists() {
+        eprintln!("agent tail: no session log found at {}", log_path.display());
+        return ExitCode::ArgsError;
+    }
 
+    // The follower catches up on existing lines first, then keeps reading
+    // across log rotation and truncation.
+    let mut follower = JsonlFollower::new(&log_path);
     loop {
-        if !log_path.exists() {
-            if args.follow {
-                sleep(Duration::from_millis(250));
-                continue;
-            }
-            eprintln!("agent tail: no session log found at {}", log_path.display());
-            return ExitCode::ArgsError;
-        }
-
-        let file = match std::fs::File::open(&log_path) {
-            Ok(file) => file,
+        let next = if args.follow {
+            follower.next_line().map(Some)
+        } else {
+            follower.try_next_line()
+        };
+        let line = match next {
+            Ok(Some(line)) => line,
+            Ok(None) => return ExitCode::Clean,
             Err(e) => {
-                if args.follow {
-                    eprintln!(
-                        "agent tail: waiting for session log {} ({})",
-                        log_path.display(),
-                        e
-                    );
-                    sleep(Duration::from_millis(250));
-                    continue;
-                }
-                eprintln!("agent tail: failed to open {}: {}", log_path.display(), e);
+                eprintln!("agent tail: read error on {}: {}", log_path.display(), e);
                 return ExitCode::IoError;
             }
         };
 
-        let mut reader = BufReader::new(file);
-        loop {
-            let mut line = String::new();
-            let bytes = match reader.read_line(&mut line) {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    eprintln!("agent tail: read error: {}", e);
-                    return ExitCode::IoError;
-                }
-            };
-
-            if bytes == 0 {
-                if args.follow {
-                    sleep(Duration::from_millis(250));
-                    continue;
-                }
-                return ExitCode::Clean;
-            }
-
-            print!("{}", line);
-            let _ = std::io::stdout().flush();
+        println!("{}", line);
+        let _ = std::io::stdout().flush();
 
-            if let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim_end()) {
-                let event_name = value.get("event").and_then(|v| v.as_str());
-                if event_name == Some(pt_core::events::event_names::SESSION_ENDED) {
-                    return ExitCode::Clean;
-                }
-            }
+        // Match on the event name alone: lines written by other producers
+        // need not carry every `ProgressEvent` field.
+        let event = serde_json::from_str::<serde_json::Value>(&line).ok();
+        if event
+            .as_ref()
+            .and_then(|value| value.get("event"))
+            .and_then(|name| name.as_str())
+            == Some(pt_core::events::event_names::SESSION_ENDED)
+        {
+            return ExitCode::Clean;
         }
     }
 }
 
 #[cfg(feature = "report")]
 fn run_agent_report(global: &GlobalOpts, args: &AgentReportArgs) -> ExitCode {
-    use pt_report::{ReportConfig, ReportGenerator, ReportTheme};
+    use pt_report::{ReportConfig, ReportGenerator};
 
     // Validate inputs: need either session or bundle
     if args.session.is_none() && args.bundle.is_none() {
@@ -15439,11 +23575,9 @@ fn run_agent_report(global: &GlobalOpts, args: &AgentReportArgs) -> ExitCode {
     }
 
     // Parse theme
-    let theme = match args.theme.to_lowercase().as_str() {
-        "light" => ReportTheme::Light,
-        "dark" => ReportTheme::Dark,
-        "auto" | "" => ReportTheme::Auto,
-        _ => {
+    let theme = match parse_report_theme(&args.theme) {
+        Some(theme) => theme,
+        None => {
             eprintln!(
                 "agent report: invalid theme '{}', use: light, dark, auto",
                 args.theme
@@ -15616,8 +23750,15 @@ struct WatchCandidate {
     start_id: String,
     severity: WatchSeverity,
     confidence: f64,
-    classification: String,
-    command: String,
+}
+
+/// How a process moved relative to the watch threshold this iteration.
+enum WatchThresholdChange {
+    New,
+    Escalated {
+        prior_confidence: f64,
+        prior_severity: WatchSeverity,
+    },
 }
 
 fn run_agent_watch(global: &GlobalOpts, args: &AgentWatchArgs) -> ExitCode {
@@ -15645,159 +23786,568 @@ fn run_agent_watch(global: &GlobalOpts, args: &AgentWatchArgs) -> ExitCode {
     let config = match load_config(&config_options) {
         Ok(config) => config,
         Err(err) => {
-            eprintln!("agent watch: config error: {}", err);
+            eprintln!("agent watch: config error: {}", err);
+            return ExitCode::InternalError;
+        }
+    };
+    let priors = config.priors;
+    let policy = config.policy;
+
+    let mut rule_engine = match &args.rules {
+        Some(path) => {
+            match pt_core::watch::WatchRules::load(path).and_then(pt_core::watch::RuleEngine::new) {
+                Ok(engine) => Some(engine),
+                Err(err) => {
+                    eprintln!("agent watch: {}", err);
+                    return ExitCode::ArgsError;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let scan_options = QuickScanOptions {
+        pids: vec![],
+        include_kernel_threads: false,
+        timeout: global.timeout.map(std::time::Duration::from_secs),
+        progress: None,
+        incremental: Some(pt_core::collect::ScanCache::shared()),
+        io_rate_window: None,
+    };
+
+    let mut baseline: Option<WatchBaseline> = None;
+    let mut previous: HashMap<u32, WatchCandidate> = HashMap::new();
+    let interval = Duration::from_secs(args.interval.max(1));
+    let notify_cmd = args.notify_cmd.as_deref();
+    let notify_exec = args.notify_exec.as_deref();
+    let notify_args = &args.notify_arg;
+
+    if notify_cmd.is_some() && notify_exec.is_some() {
+        eprintln!("agent watch: both --notify-cmd and --notify-exec set; using --notify-cmd");
+    }
+
+    loop {
+        let system_state = collect_system_state();
+        if baseline.is_none() {
+            baseline = Some(WatchBaseline::from_state(&system_state));
+        }
+
+        if let Some(event) = check_goal_violation(&system_state, args) {
+            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+        }
+        if let Some(event) = check_baseline_anomaly(&system_state, baseline.as_ref()) {
+            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+        }
+
+        let scan_result = match quick_scan(&scan_options) {
+            Ok(scan) => scan,
+            Err(err) => {
+                eprintln!("agent watch: scan failed: {}", err);
+                return ExitCode::InternalError;
+            }
+        };
+
+        let protected_filter = match ProtectedFilter::from_guardrails(&policy.guardrails) {
+            Ok(filter) => filter,
+            Err(err) => {
+                eprintln!("agent watch: protected filter error: {}", err);
+                return ExitCode::InternalError;
+            }
+        };
+        let filtered = protected_filter.filter_scan_result(&scan_result);
+
+        let load_adjustment = if policy.load_aware.enabled {
+            let signals = LoadSignals::from_system_state(&system_state, filtered.passed.len());
+            compute_load_adjustment(&policy.load_aware, &signals)
+        } else {
+            None
+        };
+        let decision_policy = if let Some(adjustment) = &load_adjustment {
+            let mut adjusted = policy.clone();
+            adjusted.loss_matrix = apply_load_to_loss_matrix(&policy.loss_matrix, adjustment);
+            adjusted
+        } else {
+            policy.clone()
+        };
+
+        let snoozes = load_active_snoozes();
+        let mut current: HashMap<u32, WatchCandidate> = HashMap::new();
+        let mut detections = CandidateLedger::new();
+        let mut seen: HashSet<(u32, String)> = HashSet::new();
+        let mut rule_plan_pids: Vec<u32> = Vec::new();
+        let mut rule_apply_pids: Vec<u32> = Vec::new();
+        let now = chrono::Utc::now();
+
+        for proc in &filtered.passed {
+            if proc.pid.0 == 0 || proc.pid.0 == 1 {
+                continue;
+            }
+            seen.insert((proc.pid.0, proc.start_id.0.clone()));
+            if snoozes.contains(proc.uid, &proc.cmd) {
+                continue;
+            }
+            if let Some(min_age) = args.min_age {
+                if proc.elapsed.as_secs() < min_age {
+                    continue;
+                }
+            }
+
+            let Some(eval) = evaluate_watch_candidate(proc, &priors, &decision_policy) else {
+                continue;
+            };
+
+            // Rule and threshold detections merge into one event per process.
+            let identity = CandidateIdentity::new(proc.pid.0, proc.start_id.0.clone());
+            let mut first_notify: Option<pt_core::watch::RuleMatch> = None;
+            if let Some(engine) = rule_engine.as_mut() {
+                let observation = pt_core::watch::ProcessObservation {
+                    pid: proc.pid.0,
+                    start_id: &proc.start_id.0,
+                    command: &proc.cmd,
+                    age_secs: proc.elapsed.as_secs(),
+                    rss_bytes: proc.rss_bytes,
+                    posterior: eval.posterior,
+                    recommendation: watch_recommendation(&eval.classification),
+                };
+                for hit in engine.evaluate(&observation, now) {
+                    if hit.actions.contains(&RuleAction::Notify) {
+                        detections.record(
+                            identity.clone(),
+                            Detection::new(DetectionSource::WatchRule)
+                                .with_recommendation(eval.classification.clone())
+                                .with_confidence(hit.posterior)
+                                .with_detail(hit.rule.clone()),
+                        );
+                        first_notify.get_or_insert_with(|| hit.clone());
+                    }
+                    if hit.actions.contains(&RuleAction::AutoApply) {
+                        rule_apply_pids.push(proc.pid.0);
+                    } else if hit.actions.contains(&RuleAction::AutoPlan) {
+                        rule_plan_pids.push(proc.pid.0);
+                    }
+                }
+            }
+
+            let severity = severity_from_confidence(eval.confidence);
+            let mut threshold_change: Option<WatchThresholdChange> = None;
+            if eval.confidence >= threshold.min_prob && severity >= threshold.level {
+                detections.record(
+                    identity.clone(),
+                    Detection::new(DetectionSource::WatchThreshold)
+                        .with_recommendation(eval.classification.clone())
+                        .with_confidence(eval.confidence),
+                );
+                let candidate = WatchCandidate {
+                    start_id: proc.start_id.0.clone(),
+                    severity,
+                    confidence: eval.confidence,
+                };
+                threshold_change = match previous.get(&proc.pid.0) {
+                    Some(prev) if prev.start_id == candidate.start_id => (candidate.severity
+                        > prev.severity)
+                        .then_some(WatchThresholdChange::Escalated {
+                            prior_confidence: prev.confidence,
+                            prior_severity: prev.severity,
+                        }),
+                    _ => Some(WatchThresholdChange::New),
+                };
+                current.insert(proc.pid.0, candidate);
+            }
+
+            let Some(merged) = detections.get(&identity) else {
+                continue;
+            };
+            let sources = merged.provenance.sources.clone();
+            let rules = merged.provenance.details(DetectionSource::WatchRule);
+            let timestamp = chrono::Utc::now().to_rfc3339();
+            let event = match (threshold_change, first_notify) {
+                (Some(WatchThresholdChange::New), _) => WatchEvent::CandidateDetected {
+                    timestamp,
+                    pid: proc.pid.0,
+                    classification: eval.classification.clone(),
+                    confidence: eval.confidence,
+                    severity: severity_label(severity).to_string(),
+                    command: proc.cmd.clone(),
+                    sources,
+                    rules,
+                },
+                (
+                    Some(WatchThresholdChange::Escalated {
+                        prior_confidence,
+                        prior_severity,
+                    }),
+                    _,
+                ) => WatchEvent::SeverityEscalated {
+                    timestamp,
+                    pid: proc.pid.0,
+                    classification: eval.classification.clone(),
+                    prior_confidence,
+                    current_confidence: eval.confidence,
+                    prior_severity: severity_label(prior_severity).to_string(),
+                    current_severity: severity_label(severity).to_string(),
+                    command: proc.cmd.clone(),
+                    sources,
+                    rules,
+                },
+                (None, Some(hit)) => WatchEvent::RuleMatched {
+                    timestamp,
+                    pid: proc.pid.0,
+                    rule: hit.rule,
+                    category: hit.category,
+                    posterior: hit.posterior,
+                    memory_growth_mb_per_hour: hit.memory_growth_mb_per_hour,
+                    classification: eval.classification.clone(),
+                    actions: hit.actions,
+                    command: proc.cmd.clone(),
+                    sources,
+                    rules,
+                },
+                (None, None) => continue,
+            };
+            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+        }
+
+        previous = current;
+
+        if let Some(engine) = rule_engine.as_mut() {
+            engine.retain(&seen);
+        }
+        if !rule_plan_pids.is_empty() || !rule_apply_pids.is_empty() {
+            rule_apply_pids.sort_unstable();
+            rule_apply_pids.dedup();
+            rule_plan_pids.sort_unstable();
+            rule_plan_pids.dedup();
+            rule_plan_pids.retain(|pid| rule_apply_pids.binary_search(pid).is_err());
+            run_watch_rule_actions(
+                global,
+                &rule_plan_pids,
+                &rule_apply_pids,
+                notify_exec,
+                notify_cmd,
+                notify_args,
+            );
+        }
+
+        let _ = std::io::stdout().flush();
+
+        if args.once {
+            break;
+        }
+        sleep(interval);
+    }
+
+    ExitCode::Clean
+}
+
+fn watch_recommendation(classification: &str) -> pt_core::watch::WatchRecommendation {
+    match classification {
+        "kill" => pt_core::watch::WatchRecommendation::Kill,
+        "spare" => pt_core::watch::WatchRecommendation::Spare,
+        _ => pt_core::watch::WatchRecommendation::Review,
+    }
+}
+
+/// Run the `auto_plan` / `auto_apply` rule actions for one watch iteration.
+///
+/// A single plan session covers every matched process. Only processes that
+/// the plan itself recommends acting on are applied, and the apply runs as a
+/// normal `agent apply` so policy, robot-mode limits and the global lock all
+/// still gate it.
+fn run_watch_rule_actions(
+    global: &GlobalOpts,
+    plan_pids: &[u32],
+    apply_pids: &[u32],
+    notify_exec: Option<&str>,
+    notify_cmd: Option<&str>,
+    notify_args: &[String],
+) {
+    let matched: Vec<u32> = plan_pids.iter().chain(apply_pids).copied().collect();
+    let plan = match run_watch_subcommand(global, &["agent", "plan"], &[]) {
+        Ok(plan) => plan,
+        Err(err) => {
+            let event = WatchEvent::RuleActionFailed {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                action: RuleAction::AutoPlan,
+                session_id: None,
+                pids: matched,
+                error: err,
+            };
+            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+            return;
+        }
+    };
+    let Some(session_id) = plan.get("session_id").and_then(|v| v.as_str()) else {
+        return;
+    };
+    let planned: HashSet<u32> = plan
+        .get("candidates")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|c| c.get("pid").and_then(|p| p.as_u64()))
+                .map(|p| p as u32)
+                .collect()
+        })
+        .unwrap_or_default();
+    let in_plan = |pids: &[u32]| -> Vec<u32> {
+        pids.iter()
+            .copied()
+            .filter(|pid| planned.contains(pid))
+            .collect()
+    };
+
+    let event = WatchEvent::RulePlanCreated {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        session_id: session_id.to_string(),
+        planned_pids: in_plan(&matched),
+        pids: matched,
+    };
+    emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+
+    let apply_targets = in_plan(apply_pids);
+    if apply_targets.is_empty() {
+        return;
+    }
+    let pid_list = apply_targets
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let event = match run_watch_subcommand(
+        global,
+        &["agent", "apply", "--session", session_id, "--yes", "--pids"],
+        &[pid_list.as_str()],
+    ) {
+        Ok(result) => WatchEvent::RuleApplyCompleted {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: session_id.to_string(),
+            pids: apply_targets,
+            result,
+        },
+        Err(err) => WatchEvent::RuleActionFailed {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action: RuleAction::AutoApply,
+            session_id: Some(session_id.to_string()),
+            pids: apply_targets,
+            error: err,
+        },
+    };
+    emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+}
+
+/// Run a `pt` subcommand with JSON output and parse its stdout.
+fn run_watch_subcommand(
+    global: &GlobalOpts,
+    args: &[&str],
+    extra: &[&str],
+) -> Result<serde_json::Value, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(["--format", "json"]);
+    if let Some(dir) = &global.config {
+        cmd.arg("--config").arg(dir);
+    }
+    cmd.args(args)
+        .args(extra)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped());
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if output.stdout.is_empty() {
+        return Err(format!(
+            "empty stdout (status {:?}): {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("invalid JSON: {}", e))
+}
+
+/// `pt-core top`: rescore every process each interval and redraw the
+/// highest-posterior candidates. `--format jsonl` streams one frame per line
+/// instead of drawing.
+fn run_top(global: &GlobalOpts, args: &TopArgs) -> ExitCode {
+    use pt_core::top::{render_frame, terminal_width, RawInput, TopBoard, TopSample};
+    use std::io::Write;
+    use std::time::Duration;
+
+    const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+    const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+    const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+    if !(0.0..=1.0).contains(&args.min_score) {
+        eprintln!("top: --min-score must be between 0 and 1");
+        return ExitCode::ArgsError;
+    }
+
+    let config = match load_config(&config_options(global)) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("top: config error: {}", err);
+            return ExitCode::InternalError;
+        }
+    };
+    let priors = config.priors;
+    let policy = config.policy;
+    let protected_filter = match ProtectedFilter::from_guardrails(&policy.guardrails) {
+        Ok(filter) => filter,
+        Err(err) => {
+            eprintln!("top: protected filter error: {}", err);
             return ExitCode::InternalError;
         }
     };
-    let priors = config.priors;
-    let policy = config.policy;
 
     let scan_options = QuickScanOptions {
         pids: vec![],
         include_kernel_threads: false,
-        timeout: global.timeout.map(std::time::Duration::from_secs),
+        timeout: global.timeout.map(Duration::from_secs),
         progress: None,
+        incremental: Some(pt_core::collect::ScanCache::shared()),
+        io_rate_window: None,
     };
-
-    let mut baseline: Option<WatchBaseline> = None;
-    let mut previous: HashMap<u32, WatchCandidate> = HashMap::new();
     let interval = Duration::from_secs(args.interval.max(1));
-    let notify_cmd = args.notify_cmd.as_deref();
-    let notify_exec = args.notify_exec.as_deref();
-    let notify_args = &args.notify_arg;
-
-    if notify_cmd.is_some() && notify_exec.is_some() {
-        eprintln!("agent watch: both --notify-cmd and --notify-exec set; using --notify-cmd");
+    let streaming = matches!(global.format, OutputFormat::Jsonl);
+    let alt_screen = args.alt_screen && !streaming;
+    let mut input = if streaming { None } else { RawInput::enable() };
+    let mut stdout = std::io::stdout();
+    if alt_screen {
+        print!("{}", ENTER_ALT_SCREEN);
     }
 
-    loop {
-        let system_state = collect_system_state();
-        if baseline.is_none() {
-            baseline = Some(WatchBaseline::from_state(&system_state));
-        }
-
-        if let Some(event) = check_goal_violation(&system_state, args) {
-            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
-        }
-        if let Some(event) = check_baseline_anomaly(&system_state, baseline.as_ref()) {
-            emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
-        }
-
+    let mut board = TopBoard::new();
+    let mut refreshes = 0u64;
+    let code = loop {
         let scan_result = match quick_scan(&scan_options) {
             Ok(scan) => scan,
             Err(err) => {
-                eprintln!("agent watch: scan failed: {}", err);
-                return ExitCode::InternalError;
-            }
-        };
-
-        let protected_filter = match ProtectedFilter::from_guardrails(&policy.guardrails) {
-            Ok(filter) => filter,
-            Err(err) => {
-                eprintln!("agent watch: protected filter error: {}", err);
-                return ExitCode::InternalError;
+                eprintln!("top: scan failed: {}", err);
+                break ExitCode::InternalError;
             }
         };
         let filtered = protected_filter.filter_scan_result(&scan_result);
+        let snoozes = load_active_snoozes();
+        let samples: Vec<TopSample> = filtered
+            .passed
+            .iter()
+            .filter(|proc| proc.pid.0 > 1 && !snoozes.contains(proc.uid, &proc.cmd))
+            .filter(|proc| args.min_age.is_none_or(|min| proc.elapsed.as_secs() >= min))
+            .filter_map(|proc| {
+                let eval = evaluate_watch_candidate(proc, &priors, &policy)?;
+                Some(TopSample {
+                    pid: proc.pid.0,
+                    start_id: proc.start_id.0.clone(),
+                    command: proc.cmd.clone(),
+                    classification: eval.classification,
+                    score: eval.confidence,
+                    cpu_percent: proc.cpu_percent,
+                    rss_bytes: proc.rss_bytes,
+                    age_secs: proc.elapsed.as_secs(),
+                })
+            })
+            .collect();
+        let frame = board.update(samples, args.limit, args.min_score);
+        refreshes += 1;
 
-        let load_adjustment = if policy.load_aware.enabled {
-            let signals = LoadSignals::from_system_state(&system_state, filtered.passed.len());
-            compute_load_adjustment(&policy.load_aware, &signals)
-        } else {
-            None
-        };
-        let decision_policy = if let Some(adjustment) = &load_adjustment {
-            let mut adjusted = policy.clone();
-            adjusted.loss_matrix = apply_load_to_loss_matrix(&policy.loss_matrix, adjustment);
-            adjusted
+        if streaming {
+            println!("{}", serde_json::to_string(&frame).unwrap_or_default());
         } else {
-            policy.clone()
-        };
-
-        let mut current: HashMap<u32, WatchCandidate> = HashMap::new();
+            print!(
+                "{}{}",
+                CLEAR_SCREEN,
+                render_frame(&frame, interval, terminal_width())
+            );
+        }
+        let _ = stdout.flush();
+        if args.iterations.is_some_and(|n| refreshes >= n) {
+            break ExitCode::Clean;
+        }
 
-        for proc in &filtered.passed {
-            if proc.pid.0 == 0 || proc.pid.0 == 1 {
-                continue;
+        let key = match input.as_ref() {
+            Some(input) => input.read_key(interval),
+            None => {
+                std::thread::sleep(interval);
+                None
             }
-            if let Some(min_age) = args.min_age {
-                if proc.elapsed.as_secs() < min_age {
-                    continue;
+        };
+        match key {
+            // q, Q, Ctrl-C
+            Some(b'q' | b'Q' | 0x03) => break ExitCode::Clean,
+            Some(b'r' | b'R') => {
+                // Hand the terminal to `run`, then resume the live view.
+                drop(input.take());
+                if alt_screen {
+                    print!("{}", LEAVE_ALT_SCREEN);
+                    let _ = stdout.flush();
                 }
-            }
-
-            let Some(eval) = evaluate_watch_candidate(proc, &priors, &decision_policy) else {
-                continue;
-            };
-            if eval.confidence < threshold.min_prob {
-                continue;
-            }
-            let severity = severity_from_confidence(eval.confidence);
-            if severity < threshold.level {
-                continue;
-            }
-
-            let candidate = WatchCandidate {
-                start_id: proc.start_id.0.clone(),
-                severity,
-                confidence: eval.confidence,
-                classification: eval.classification.clone(),
-                command: proc.cmd.clone(),
-            };
-
-            let emit_new = match previous.get(&proc.pid.0) {
-                Some(prev) if prev.start_id == candidate.start_id => {
-                    if candidate.severity > prev.severity {
-                        let event = serde_json::json!({
-                            "event": "severity_escalated",
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                            "pid": proc.pid.0,
-                            "classification": candidate.classification,
-                            "prior_confidence": prev.confidence,
-                            "current_confidence": candidate.confidence,
-                            "prior_severity": severity_label(prev.severity),
-                            "current_severity": severity_label(candidate.severity),
-                            "command": candidate.command,
-                        });
-                        emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
-                    }
-                    false
+                if let Err(err) = run_from_top(global, args) {
+                    eprintln!("top: failed to start run: {}", err);
                 }
-                _ => true,
-            };
-
-            if emit_new {
-                let event = serde_json::json!({
-                    "event": "candidate_detected",
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "pid": proc.pid.0,
-                    "classification": candidate.classification,
-                    "confidence": candidate.confidence,
-                    "severity": severity_label(candidate.severity),
-                    "command": candidate.command,
-                });
-                emit_watch_event(&event, notify_exec, notify_cmd, notify_args);
+                if alt_screen {
+                    print!("{}", ENTER_ALT_SCREEN);
+                }
+                input = RawInput::enable();
             }
-
-            current.insert(proc.pid.0, candidate);
+            _ => {}
         }
+    };
 
-        previous = current;
+    drop(input);
+    if alt_screen {
+        print!("{}", LEAVE_ALT_SCREEN);
+        let _ = stdout.flush();
+    }
+    code
+}
 
-        let _ = std::io::stdout().flush();
+/// Run `pt-core run` in the foreground with the terminal attached.
+fn run_from_top(global: &GlobalOpts, args: &TopArgs) -> std::io::Result<std::process::ExitStatus> {
+    let exe = std::env::current_exe()?;
+    let mut cmd = std::process::Command::new(exe);
+    if let Some(dir) = &global.config {
+        cmd.arg("--config").arg(dir);
+    }
+    cmd.arg("run");
+    if let Some(min_age) = args.min_age {
+        cmd.arg("--min-age").arg(min_age.to_string());
+    }
+    cmd.status()
+}
 
-        if args.once {
-            break;
+/// Load the active snooze set, treating store errors as "nothing snoozed".
+fn load_active_snoozes() -> pt_core::snooze::SnoozeSet {
+    match pt_core::snooze::SnoozeStore::from_env()
+        .and_then(|store| store.active_set(chrono::Utc::now()))
+    {
+        Ok(set) => set,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load snoozed candidates");
+            pt_core::snooze::SnoozeSet::default()
         }
-        sleep(interval);
     }
+}
 
-    ExitCode::Clean
+/// Whether a userspace OOM killer recently killed this process (or its cgroup).
+fn is_recently_oom_killed(kills: &pt_core::collect::RecentOomKills, proc: &ProcessRecord) -> bool {
+    if kills.is_empty() {
+        return false;
+    }
+    #[cfg(target_os = "linux")]
+    let cgroup = if kills.has_cgroup_kills() {
+        pt_core::collect::parse_cgroup(proc.pid.0).and_then(|c| c.unified)
+    } else {
+        None
+    };
+    #[cfg(not(target_os = "linux"))]
+    let cgroup: Option<String> = None;
+    kills
+        .matching(proc.pid.0, &proc.comm, cgroup.as_deref())
+        .is_some()
 }
 
 struct WatchEval {
     confidence: f64,
     classification: String,
+    posterior: pt_core::inference::ClassScores,
 }
 
 fn evaluate_watch_candidate(
@@ -15816,6 +24366,14 @@ fn evaluate_watch_candidate(
         io_active: None,
         state_flag: state_to_flag(proc.state),
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let posterior_result = compute_posterior(priors, &evidence).ok()?;
@@ -15842,6 +24400,7 @@ fn evaluate_watch_candidate(
     Some(WatchEval {
         confidence,
         classification,
+        posterior: posterior_result.posterior,
     })
 }
 
@@ -15920,19 +24479,15 @@ fn read_available_gb(state: &serde_json::Value) -> Option<f64> {
         .and_then(|v| v.as_f64())
 }
 
-fn check_goal_violation(
-    state: &serde_json::Value,
-    args: &AgentWatchArgs,
-) -> Option<serde_json::Value> {
+fn check_goal_violation(state: &serde_json::Value, args: &AgentWatchArgs) -> Option<WatchEvent> {
     if let Some(goal_mem) = args.goal_memory_available_gb {
         if let Some(available) = read_available_gb(state) {
             if available < goal_mem {
-                return Some(serde_json::json!({
-                    "event": "goal_violated",
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "goal": format!("memory_available_gb >= {}", goal_mem),
-                    "current": format!("{:.2}", available),
-                }));
+                return Some(WatchEvent::GoalViolated {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    goal: format!("memory_available_gb >= {}", goal_mem),
+                    current: format!("{:.2}", available),
+                });
             }
         }
     }
@@ -15940,12 +24495,11 @@ fn check_goal_violation(
     if let Some(goal_load) = args.goal_load_max {
         if let Some(load1) = read_load1(state) {
             if load1 > goal_load {
-                return Some(serde_json::json!({
-                    "event": "goal_violated",
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "goal": format!("load1 <= {}", goal_load),
-                    "current": format!("{:.2}", load1),
-                }));
+                return Some(WatchEvent::GoalViolated {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    goal: format!("load1 <= {}", goal_load),
+                    current: format!("{:.2}", load1),
+                });
             }
         }
     }
@@ -15956,31 +24510,29 @@ fn check_goal_violation(
 fn check_baseline_anomaly(
     state: &serde_json::Value,
     baseline: Option<&WatchBaseline>,
-) -> Option<serde_json::Value> {
+) -> Option<WatchEvent> {
     let baseline = baseline?;
     if baseline.load1 > 0.0 {
         if let Some(load1) = read_load1(state) {
             if load1 > baseline.load1 * 1.5 {
-                return Some(serde_json::json!({
-                    "event": "baseline_anomaly",
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "metric": "load1",
-                    "baseline": format!("{:.2}", baseline.load1),
-                    "current": format!("{:.2}", load1),
-                }));
+                return Some(WatchEvent::BaselineAnomaly {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    metric: "load1".to_string(),
+                    baseline: format!("{:.2}", baseline.load1),
+                    current: format!("{:.2}", load1),
+                });
             }
         }
     }
     if baseline.available_gb > 0.0 {
         if let Some(available) = read_available_gb(state) {
             if available < baseline.available_gb * 0.7 {
-                return Some(serde_json::json!({
-                    "event": "baseline_anomaly",
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "metric": "memory_available_gb",
-                    "baseline": format!("{:.2}", baseline.available_gb),
-                    "current": format!("{:.2}", available),
-                }));
+                return Some(WatchEvent::BaselineAnomaly {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    metric: "memory_available_gb".to_string(),
+                    baseline: format!("{:.2}", baseline.available_gb),
+                    current: format!("{:.2}", available),
+                });
             }
         }
     }
@@ -15988,20 +24540,14 @@ fn check_baseline_anomaly(
 }
 
 fn emit_watch_event(
-    event: &serde_json::Value,
+    event: &WatchEvent,
     notify_exec: Option<&str>,
     notify_cmd: Option<&str>,
     notify_args: &[String],
 ) {
-    println!(
-        "{}",
-        serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string())
-    );
-    let event_type = event
-        .get("event")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-    let json = event.to_string();
+    let json = serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string());
+    println!("{}", json);
+    let event_type = event.name();
     if let Some(cmd) = notify_cmd {
         let mut child = std::process::Command::new(cmd);
         for arg in notify_args {
@@ -16009,7 +24555,7 @@ fn emit_watch_event(
         }
         child.env("PT_WATCH_EVENT", event_type);
         child.env("PT_WATCH_EVENT_JSON", &json);
-        if let Some(pid) = event.get("pid").and_then(|v| v.as_u64()) {
+        if let Some(pid) = event.pid() {
             child.env("PT_WATCH_PID", pid.to_string());
         }
         if let Err(err) = child.status() {
@@ -16023,7 +24569,7 @@ fn emit_watch_event(
         child.arg("-c").arg(cmd);
         child.env("PT_WATCH_EVENT", event_type);
         child.env("PT_WATCH_EVENT_JSON", &json);
-        if let Some(pid) = event.get("pid").and_then(|v| v.as_u64()) {
+        if let Some(pid) = event.pid() {
             child.env("PT_WATCH_PID", pid.to_string());
         }
         if let Err(err) = child.status() {
@@ -16032,6 +24578,58 @@ fn emit_watch_event(
     }
 }
 
+#[cfg(all(test, target_os = "linux"))]
+mod override_review_tests {
+    use super::*;
+    use pt_core::decision::{ConstraintKind, ConstraintSource};
+
+    fn max_kills_violation() -> ConstraintViolation {
+        ConstraintViolation {
+            constraint: ConstraintKind::MaxKills,
+            message: "kill limit reached".to_string(),
+            threshold: "5".to_string(),
+            actual: "5".to_string(),
+            source: ConstraintSource::Policy,
+            remediation: None,
+        }
+    }
+
+    fn run_prompt(input: &str) -> (Option<String>, String) {
+        let mut reader = std::io::Cursor::new(input.as_bytes().to_vec());
+        let mut out = Vec::new();
+        let result = prompt_override(
+            "kill PID 42 (a-1)",
+            &[max_kills_violation()],
+            &mut reader,
+            &mut out,
+        )
+        .unwrap();
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_prompt_override_approves_with_justification() {
+        let (result, shown) = run_prompt("y\nrunaway build, owner confirmed\n");
+        assert_eq!(result.as_deref(), Some("runaway build, owner confirmed"));
+        assert!(shown.contains("max_kills: kill limit reached (threshold: 5, actual: 5)"));
+    }
+
+    #[test]
+    fn test_prompt_override_declines_by_default() {
+        assert_eq!(run_prompt("\n").0, None);
+        assert_eq!(run_prompt("n\nreason\n").0, None);
+        // End of input declines too.
+        assert_eq!(run_prompt("").0, None);
+    }
+
+    #[test]
+    fn test_prompt_override_requires_justification() {
+        let (result, shown) = run_prompt("yes\n   \n");
+        assert_eq!(result, None);
+        assert!(shown.contains("keeping block"));
+    }
+}
+
 #[cfg(test)]
 mod watch_tests {
     use super::*;
@@ -16070,12 +24668,10 @@ mod watch_tests {
             once: true,
             goal_memory_available_gb: Some(2.0),
             goal_load_max: None,
+            rules: None,
         };
         let event = check_goal_violation(&state, &args).expect("goal violation");
-        assert_eq!(
-            event.get("event").and_then(|v| v.as_str()),
-            Some("goal_violated")
-        );
+        assert_eq!(event.name(), "goal_violated");
     }
 
     #[test]
@@ -16091,10 +24687,52 @@ mod watch_tests {
         });
         let event =
             check_baseline_anomaly(&current_state, Some(&baseline)).expect("baseline anomaly");
-        assert_eq!(
-            event.get("event").and_then(|v| v.as_str()),
-            Some("baseline_anomaly")
-        );
+        assert_eq!(event.name(), "baseline_anomaly");
+    }
+}
+
+/// Add respawns seen by `agent verify` to the respawn history in shadow
+/// storage, timed from the session's action events.
+fn record_respawn_history(
+    handle: &SessionHandle,
+    plan: &pt_core::verify::AgentPlan,
+    report: &pt_core::verify::VerificationReport,
+) {
+    use pt_core::decision::respawn_loop::{
+        respawn_history_path, RespawnLoopConfig, RespawnTracker,
+    };
+
+    let kill_times: HashMap<u32, f64> = handle
+        .read_events()
+        .map(|events| {
+            pt_core::session::SessionView::replay(&events)
+                .actions
+                .iter()
+                .filter_map(|action| {
+                    let ts = chrono::DateTime::parse_from_rfc3339(&action.ts).ok()?;
+                    Some((action.pid, ts.timestamp() as f64))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let events = pt_core::verify::respawn_events(plan, report, &kill_times);
+    if events.is_empty() {
+        return;
+    }
+    let path = respawn_history_path(&shadow_base_dir());
+    let mut tracker = match RespawnTracker::load(&path) {
+        Ok(tracker) => tracker,
+        Err(e) => {
+            eprintln!("agent verify: respawn history unreadable: {}", e);
+            return;
+        }
+    };
+    for event in events {
+        tracker.record_event(event);
+    }
+    let now = chrono::Utc::now().timestamp() as f64;
+    if let Err(e) = tracker.save(&path, &RespawnLoopConfig::default(), now) {
+        eprintln!("agent verify: failed to save respawn history: {}", e);
     }
 }
 
@@ -16173,6 +24811,31 @@ fn generate_report_from_session(
         0
     };
 
+    let plan = std::fs::read_to_string(&plan_path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+    // Before/after projection written by `agent plan --simulate`
+    let simulation = plan.as_ref().and_then(SimulationSection::from_plan);
+    // Candidates whose fd count kept growing across shadow runs
+    let fd_leaks = plan.as_ref().and_then(FdLeaksSection::from_plan);
+
+    // Dependency graph written by `agent explain --show-dependencies`
+    let dependencies =
+        std::fs::read_to_string(handle.dir.join("inference").join("dependency_graph.json"))
+            .ok()
+            .and_then(|c| DependenciesSection::from_json(&c).ok());
+
+    // Plan-vs-outcome rows written by `session reconcile`
+    let actions = std::fs::read_to_string(
+        handle
+            .dir
+            .join("action")
+            .join(pt_core::session::reconcile::RECONCILIATION_FILE),
+    )
+    .ok()
+    .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    .and_then(|doc| ActionsSection::from_reconciliation(&doc));
+
     // Build report data
     let data = ReportData {
         config: generator.config().clone(),
@@ -16184,7 +24847,10 @@ fn generate_report_from_session(
         }),
         candidates: None, // Would be populated from plan.json
         evidence: None,
-        actions: None,
+        actions,
+        dependencies,
+        simulation,
+        fd_leaks,
         galaxy_brain: if generator.config().galaxy_brain {
             Some(GalaxyBrainSection::default())
         } else {
@@ -16253,6 +24919,9 @@ fn generate_prose_summary(prose_style: &str) -> String {
 }
 
 fn run_agent_sessions(global: &GlobalOpts, args: &AgentSessionsArgs) -> ExitCode {
+    if args.locks {
+        return run_agent_sessions_locks(global, args.force);
+    }
     // Validate flag combinations: --session mode is incompatible with list/cleanup options
     if args.session.is_some() {
         if args.cleanup {
@@ -16411,6 +25080,16 @@ fn run_agent_session_status(
         None
     };
 
+    // Load the event timeline if --detail flag is set
+    let events_detail = if include_detail {
+        handle
+            .read_events()
+            .ok()
+            .filter(|events| !events.is_empty())
+    } else {
+        None
+    };
+
     match global.format {
         OutputFormat::Json | OutputFormat::Toon => {
             let mut output = serde_json::json!({
@@ -16462,6 +25141,12 @@ fn run_agent_session_status(
                         .unwrap()
                         .insert("outcomes".to_string(), serde_json::json!(outcomes));
                 }
+                if let Some(events) = &events_detail {
+                    output
+                        .as_object_mut()
+                        .unwrap()
+                        .insert("events".to_string(), serde_json::json!(events));
+                }
             }
             println!("{}", format_structured_output(global, output));
         }
@@ -16543,6 +25228,13 @@ fn run_agent_session_status(
                         println!("    ... and {} more", outcomes.len() - 5);
                     }
                 }
+                if let Some(events) = &events_detail {
+                    println!();
+                    println!("## Timeline");
+                    for event in events {
+                        println!("  {} {}", event.ts, event.kind.summary());
+                    }
+                }
             }
         }
     }
@@ -16556,12 +25248,12 @@ fn run_agent_sessions_cleanup(
     older_than_str: &str,
     host_id: &str,
 ) -> ExitCode {
-    let duration = match parse_duration(older_than_str) {
-        Some(d) => d,
-        None => {
+    let duration = match parse_chrono_duration(older_than_str, DurationUnit::Days) {
+        Ok(d) => d,
+        Err(e) => {
             eprintln!(
-                "agent sessions: invalid --older-than '{}'. Use format like '7d', '24h', '30d'",
-                older_than_str
+                "agent sessions: invalid --older-than '{}': {}. Use a duration like '7d', '24h', '1w'",
+                older_than_str, e
             );
             return ExitCode::ArgsError;
         }
@@ -16627,6 +25319,90 @@ fn run_agent_sessions_cleanup(
     ExitCode::Clean
 }
 
+fn run_agent_sessions_locks(global: &GlobalOpts, force: bool) -> ExitCode {
+    let Some(path) = global_lock_path() else {
+        eprintln!("agent sessions: cannot resolve data directory for the global lock");
+        return ExitCode::IoError;
+    };
+
+    let mut broken = None;
+    if force {
+        let status = pt_core::lock::inspect(&path);
+        if status.holder_alive == Some(true) {
+            eprintln!(
+                "agent sessions: refusing to break lock held by live pid {}",
+                status.holder.as_ref().map(|h| h.pid).unwrap_or_default()
+            );
+            return ExitCode::LockError;
+        }
+        match pt_core::lock::break_stale(&path) {
+            Ok(result) => broken = Some(result),
+            Err(e) => {
+                eprintln!("agent sessions: failed to break lock: {}", e);
+                return ExitCode::IoError;
+            }
+        }
+    }
+    let status = pt_core::lock::inspect(&path);
+
+    match global.format {
+        OutputFormat::Json | OutputFormat::Toon | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "lock": status,
+                "broken": broken,
+                "status": "ok",
+                "command": "pt agent sessions --locks",
+            });
+            println!("{}", format_structured_output(global, output));
+        }
+        OutputFormat::Exitcode => {}
+        _ => {
+            println!("# Global Lock");
+            println!();
+            println!("Path: {}", path.display());
+            match status.holder.as_ref() {
+                Some(holder) => {
+                    println!(
+                        "Holder: pid {} ({}){}",
+                        holder.pid,
+                        holder.command,
+                        if status.stale { " [stale]" } else { "" }
+                    );
+                    if let Some(session) = holder.session_id.as_deref() {
+                        println!("Session: {}", session);
+                    }
+                    if let Some(age) = status.age_secs {
+                        println!("Held for: {}s", age);
+                    }
+                }
+                None if status.held => println!("Holder: unknown (no ownership record)"),
+                None => println!("Holder: none"),
+            }
+            if status.waiters.is_empty() {
+                println!("Waiters: none");
+            } else {
+                println!("Waiters:");
+                for waiter in &status.waiters {
+                    println!(
+                        "  - pid {} ({}) since {}",
+                        waiter.pid, waiter.command, waiter.enqueued_at
+                    );
+                }
+            }
+            if let Some(broken) = broken {
+                println!(
+                    "Broke stale lock: {:?} ({} dead waiter(s) pruned)",
+                    broken.action, broken.pruned_waiters
+                );
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
 fn run_agent_sessions_list(
     global: &GlobalOpts,
     store: &SessionStore,
@@ -16834,7 +25610,7 @@ fn run_update(global: &GlobalOpts, args: &UpdateArgs) -> ExitCode {
                                     [..std::cmp::min(28, b.metadata.created_at.len())],
                                 &b.metadata.checksum
                                     [..std::cmp::min(16, b.metadata.checksum.len())],
-                                format_bytes(b.metadata.size_bytes)
+                                format_size(b.metadata.size_bytes)
                             );
                         }
                     }
@@ -17051,45 +25827,8 @@ fn run_update(global: &GlobalOpts, args: &UpdateArgs) -> ExitCode {
     }
 }
 
-/// Format bytes as human-readable string
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.1}GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1}MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1}KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{}B", bytes)
-    }
-}
-
-/// Parse duration string like "7d", "24h", "30d" into chrono::Duration.
-fn parse_duration(s: &str) -> Option<chrono::Duration> {
-    let s = s.trim();
-    if s.is_empty() {
-        return None;
-    }
-
-    let (num_str, unit) = if let Some(stripped) = s.strip_suffix('d') {
-        (stripped, 'd')
-    } else if let Some(stripped) = s.strip_suffix('h') {
-        (stripped, 'h')
-    } else if let Some(stripped) = s.strip_suffix('m') {
-        (stripped, 'm')
-    } else {
-        return None;
-    };
-
-    let num: i64 = num_str.parse().ok()?;
-    match unit {
-        'd' => Some(chrono::Duration::days(num)),
-        'h' => Some(chrono::Duration::hours(num)),
-        'm' => Some(chrono::Duration::minutes(num)),
-        _ => None,
-    }
+/// Parse a duration flag (e.g. "7d", "36h", "1w") into chrono::Duration.
+fn parse_chrono_duration(s: &str, bare: DurationUnit) -> Result<chrono::Duration, String> {
+    let duration = pt_common::units::parse_duration(s, bare).map_err(|e| e.to_string())?;
+    chrono::Duration::from_std(duration).map_err(|e| e.to_string())
 }
@@ -0,0 +1,435 @@
+//! MCP server HTTP + SSE transport.
+//!
+//! Serves the same JSON-RPC handlers as the stdio transport over HTTP so IDE
+//! agents and remote orchestrators can connect without spawning a process per
+//! query. Two styles are supported:
+//!
+//! - `POST /mcp`: send one JSON-RPC message, receive the response in the body.
+//! - `GET /sse` + `POST /messages?session_id=..`: the MCP HTTP+SSE transport.
+//!   The event stream first announces the message endpoint (`event: endpoint`),
+//!   then delivers each response as an `event: message`.
+//!
+//! Every endpoint except `/health` requires `Authorization: Bearer <token>`.
+//!
+//! [`serve`] answers one request at a time. With the `async` feature,
+//! [`serve_async`] handles several at once on the tokio runtime, so a long
+//! tool call does not block health checks or other clients.
+
+use crate::mcp::server::{response_json, McpServer};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Largest request body accepted on POST endpoints.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+/// Maximum concurrently open SSE streams.
+const MAX_SSE_SESSIONS: usize = 32;
+
+/// Interval between SSE keepalive comments.
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// HTTP transport configuration.
+#[derive(Debug, Clone)]
+pub struct HttpTransportConfig {
+    pub bind: SocketAddr,
+    /// Bearer token clients must present.
+    pub token: String,
+}
+
+impl HttpTransportConfig {
+    pub fn new(bind: SocketAddr, token: impl Into<String>) -> Self {
+        Self {
+            bind,
+            token: token.into(),
+        }
+    }
+}
+
+/// Generate a random bearer token (64 hex chars).
+pub fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    hex::encode(bytes)
+}
+
+/// Check an `Authorization` header value against the expected bearer token.
+pub fn check_bearer(header: Option<&str>, token: &str) -> bool {
+    let Some(presented) = header.and_then(|h| h.trim().strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let presented = presented.trim().as_bytes();
+    let expected = token.as_bytes();
+    // Constant-time comparison so the token cannot be recovered by timing.
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Request routes understood by the transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Route {
+    Health,
+    Rpc,
+    Sse,
+    Messages { session_id: Option<String> },
+    MethodNotAllowed,
+    NotFound,
+}
+
+fn route(method: &tiny_http::Method, url: &str) -> Route {
+    use tiny_http::Method;
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let expected = match path {
+        "/health" | "/healthz" => Method::Get,
+        "/sse" => Method::Get,
+        "/mcp" | "/messages" => Method::Post,
+        _ => return Route::NotFound,
+    };
+    if *method != expected {
+        return Route::MethodNotAllowed;
+    }
+    match path {
+        "/health" | "/healthz" => Route::Health,
+        "/sse" => Route::Sse,
+        "/mcp" => Route::Rpc,
+        _ => Route::Messages {
+            session_id: query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("session_id="))
+                .filter(|id| !id.is_empty())
+                .map(str::to_string),
+        },
+    }
+}
+
+/// Format one server-sent event.
+fn sse_event(event: &str, data: &str) -> String {
+    let mut out = format!("event: {}\n", event);
+    for line in data.lines() {
+        out.push_str("data: ");
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+    out
+}
+
+/// An open SSE stream and the MCP server state bound to it.
+struct SseSession {
+    server: Arc<McpServer>,
+    events: Sender<String>,
+    thread: thread::JoinHandle<()>,
+}
+
+/// Server state shared by every request.
+struct Transport {
+    token: String,
+    /// Stateless `POST /mcp` requests share one server instance.
+    shared: McpServer,
+    sessions: Mutex<HashMap<String, SseSession>>,
+}
+
+impl Transport {
+    fn new(config: &HttpTransportConfig) -> Self {
+        Self {
+            token: config.token.clone(),
+            shared: McpServer::new(),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn sessions(&self) -> std::sync::MutexGuard<'_, HashMap<String, SseSession>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Route, authorize and answer one request.
+    fn handle(&self, request: tiny_http::Request) -> io::Result<()> {
+        let route = route(request.method(), request.url());
+        if route != Route::Health && !authorized(&request, &self.token) {
+            respond_text(request, 401, "unauthorized");
+            return Ok(());
+        }
+
+        match route {
+            Route::Health => respond_text(request, 200, "ok"),
+            Route::Rpc => {
+                let mut request = request;
+                match read_body(&mut request) {
+                    Ok(body) => match self.shared.handle_message(body.trim()) {
+                        Some(resp) => respond_json(request, 200, &response_json(&resp)),
+                        None => respond_text(request, 202, ""),
+                    },
+                    Err(status) => respond_text(request, status, "invalid request body"),
+                }
+            }
+            Route::Sse => {
+                let mut sessions = self.sessions();
+                sessions.retain(|_, session| !session.thread.is_finished());
+                if sessions.len() >= MAX_SSE_SESSIONS {
+                    respond_text(request, 503, "too many SSE sessions");
+                    return Ok(());
+                }
+                let session_id = uuid::Uuid::new_v4().simple().to_string();
+                let (events, rx) = mpsc::channel();
+                let endpoint = format!("/messages?session_id={}", session_id);
+                let _ = events.send(sse_event("endpoint", &endpoint));
+                let writer = request.into_writer();
+                let thread = thread::Builder::new()
+                    .name("pt-mcp-sse".to_string())
+                    .spawn(move || stream_events(writer, rx))?;
+                sessions.insert(
+                    session_id,
+                    SseSession {
+                        server: Arc::new(McpServer::new()),
+                        events,
+                        thread,
+                    },
+                );
+            }
+            Route::Messages { session_id } => {
+                let Some(session_id) = session_id else {
+                    respond_text(request, 400, "missing session_id");
+                    return Ok(());
+                };
+                // Handle the message outside the lock so sessions do not wait
+                // on each other.
+                let Some((server, events)) = self
+                    .sessions()
+                    .get(&session_id)
+                    .map(|session| (Arc::clone(&session.server), session.events.clone()))
+                else {
+                    respond_text(request, 404, "unknown session");
+                    return Ok(());
+                };
+                let mut request = request;
+                let body = match read_body(&mut request) {
+                    Ok(body) => body,
+                    Err(status) => {
+                        respond_text(request, status, "invalid request body");
+                        return Ok(());
+                    }
+                };
+                let delivered = match server.handle_message(body.trim()) {
+                    Some(resp) => events
+                        .send(sse_event("message", &response_json(&resp)))
+                        .is_ok(),
+                    None => true,
+                };
+                if delivered {
+                    respond_text(request, 202, "accepted");
+                } else {
+                    self.sessions().remove(&session_id);
+                    respond_text(request, 410, "session closed");
+                }
+            }
+            Route::MethodNotAllowed => respond_text(request, 405, "method not allowed"),
+            Route::NotFound => respond_text(request, 404, "not found"),
+        }
+        Ok(())
+    }
+}
+
+/// Run the HTTP transport until the listener fails. Blocks the caller.
+pub fn serve(config: &HttpTransportConfig) -> io::Result<()> {
+    let listener = tiny_http::Server::http(config.bind).map_err(io::Error::other)?;
+    eprintln!("[pt-mcp] Listening on http://{}", config.bind);
+
+    let transport = Transport::new(config);
+    for request in listener.incoming_requests() {
+        transport.handle(request)?;
+    }
+
+    Ok(())
+}
+
+/// Run the HTTP transport on the tokio runtime until `token` is cancelled,
+/// answering up to `limit` requests at once.
+#[cfg(feature = "async")]
+pub async fn serve_async(
+    config: &HttpTransportConfig,
+    limit: usize,
+    token: &crate::runtime::CancellationToken,
+) -> io::Result<()> {
+    let listener = Arc::new(tiny_http::Server::http(config.bind).map_err(io::Error::other)?);
+    eprintln!("[pt-mcp] Listening on http://{}", config.bind);
+
+    // tiny_http accepts on a blocking call; a plain thread feeds the runtime
+    // and is released with `unblock` on shutdown.
+    let (requests, mut incoming) = tokio::sync::mpsc::channel(limit.max(1));
+    let accept = {
+        let listener = Arc::clone(&listener);
+        thread::Builder::new()
+            .name("pt-mcp-accept".to_string())
+            .spawn(move || {
+                for request in listener.incoming_requests() {
+                    if requests.blocking_send(request).is_err() {
+                        break;
+                    }
+                }
+            })?
+    };
+
+    let transport = Arc::new(Transport::new(config));
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(limit.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut result = Ok(());
+    loop {
+        let request = tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            request = incoming.recv() => match request {
+                Some(request) => request,
+                None => break,
+            },
+        };
+        let permit = tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            permit = Arc::clone(&semaphore).acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+        };
+        let transport = Arc::clone(&transport);
+        tasks.spawn_blocking(move || {
+            let _permit = permit;
+            transport.handle(request)
+        });
+        while let Some(joined) = tasks.try_join_next() {
+            if let Ok(Err(e)) = joined {
+                result = Err(e);
+            }
+        }
+        if result.is_err() {
+            break;
+        }
+    }
+
+    listener.unblock();
+    drop(incoming);
+    let _ = accept.join();
+    result
+}
+
+/// Write the SSE response head, then forward events until the client goes away.
+fn stream_events(mut writer: Box<dyn Write + Send>, events: Receiver<String>) {
+    let head = "HTTP/1.1 200 OK\r\n\
+                Content-Type: text/event-stream\r\n\
+                Cache-Control: no-cache\r\n\
+                Connection: close\r\n\r\n";
+    if writer.write_all(head.as_bytes()).is_err() || writer.flush().is_err() {
+        return;
+    }
+    loop {
+        let chunk = match events.recv_timeout(SSE_KEEPALIVE) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => ": keepalive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+        if writer.write_all(chunk.as_bytes()).is_err() || writer.flush().is_err() {
+            return;
+        }
+    }
+}
+
+fn authorized(request: &tiny_http::Request, token: &str) -> bool {
+    let header = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .map(|h| h.value.as_str());
+    check_bearer(header, token)
+}
+
+/// Read a POST body, rejecting oversized or non-UTF-8 payloads with a status code.
+fn read_body(request: &mut tiny_http::Request) -> Result<String, u16> {
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > MAX_BODY_BYTES)
+    {
+        return Err(413);
+    }
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|_| 400u16)?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(413);
+    }
+    Ok(body)
+}
+
+fn respond_text(request: tiny_http::Request, status: u16, body: &str) {
+    let _ = request.respond(tiny_http::Response::from_string(body).with_status_code(status));
+}
+
+fn respond_json(request: tiny_http::Request, status: u16, body: &str) {
+    let response = tiny_http::Response::from_string(body)
+        .with_status_code(status)
+        .with_header(
+            "Content-Type: application/json"
+                .parse::<tiny_http::Header>()
+                .unwrap(),
+        );
+    let _ = request.respond(response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiny_http::Method;
+
+    #[test]
+    fn bearer_token_checked() {
+        assert!(check_bearer(Some("Bearer s3cret"), "s3cret"));
+        assert!(!check_bearer(Some("Bearer wrong!"), "s3cret"));
+        assert!(!check_bearer(Some("Basic s3cret"), "s3cret"));
+        assert!(!check_bearer(None, "s3cret"));
+    }
+
+    #[test]
+    fn generated_tokens_are_unique_hex() {
+        let a = generate_token();
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, generate_token());
+    }
+
+    #[test]
+    fn routes_resolve() {
+        assert_eq!(route(&Method::Get, "/health"), Route::Health);
+        assert_eq!(route(&Method::Post, "/mcp"), Route::Rpc);
+        assert_eq!(route(&Method::Get, "/sse"), Route::Sse);
+        assert_eq!(
+            route(&Method::Post, "/messages?session_id=abc"),
+            Route::Messages {
+                session_id: Some("abc".to_string())
+            }
+        );
+        assert_eq!(
+            route(&Method::Post, "/messages"),
+            Route::Messages { session_id: None }
+        );
+        assert_eq!(route(&Method::Get, "/mcp"), Route::MethodNotAllowed);
+        assert_eq!(route(&Method::Get, "/nope"), Route::NotFound);
+    }
+
+    #[test]
+    fn sse_event_splits_multiline_data() {
+        assert_eq!(
+            sse_event("message", "{\"a\":1}"),
+            "event: message\ndata: {\"a\":1}\n\n"
+        );
+        assert_eq!(sse_event("x", "a\nb"), "event: x\ndata: a\ndata: b\n\n");
+    }
+}
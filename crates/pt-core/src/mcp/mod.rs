@@ -1,8 +1,10 @@
 //! MCP (Model Context Protocol) server for process triage.
 //!
 //! Exposes pt functionality to AI agents via the standardized MCP protocol
-//! over stdio (JSON-RPC 2.0).
+//! (JSON-RPC 2.0) over stdio, or over HTTP + SSE with the `mcp-http` feature.
 
+#[cfg(feature = "mcp-http")]
+pub mod http;
 pub mod protocol;
 pub mod resources;
 pub mod server;
@@ -2,24 +2,38 @@
 //!
 //! Reads JSON-RPC 2.0 messages from stdin, dispatches to handlers,
 //! and writes responses to stdout.
+//!
+//! With the `async` feature, [`serve_stdio_async`] handles several requests
+//! at once on the tokio runtime, so a slow tool call no longer holds up the
+//! ones behind it. Responses are written as they complete; clients match them
+//! to requests by `id`.
 
 use crate::mcp::protocol::*;
 use crate::mcp::resources;
 use crate::mcp::tools;
 use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+/// Requests handled at once by the async transports.
+#[cfg(feature = "async")]
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
 
 /// MCP server state.
 pub struct McpServer {
-    initialized: bool,
+    initialized: AtomicBool,
 }
 
 impl McpServer {
     pub fn new() -> Self {
-        Self { initialized: false }
+        Self {
+            initialized: AtomicBool::new(false),
+        }
     }
 
     /// Run the stdio event loop: read lines from stdin, dispatch, write to stdout.
-    pub fn run_stdio(&mut self) -> io::Result<()> {
+    pub fn run_stdio(&self) -> io::Result<()> {
         let stdin = io::stdin();
         let stdout = io::stdout();
         let mut stdout = stdout.lock();
@@ -35,11 +49,7 @@ impl McpServer {
 
             // Notifications (no id) get no response
             if let Some(resp) = response {
-                let json = serde_json::to_string(&resp).unwrap_or_else(|_| {
-                    r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Serialization failed"}}"#
-                        .to_string()
-                });
-                writeln!(stdout, "{}", json)?;
+                writeln!(stdout, "{}", response_json(&resp))?;
                 stdout.flush()?;
             }
         }
@@ -48,7 +58,7 @@ impl McpServer {
     }
 
     /// Handle a single JSON-RPC message and return a response (or None for notifications).
-    pub fn handle_message(&mut self, raw: &str) -> Option<JsonRpcResponse> {
+    pub fn handle_message(&self, raw: &str) -> Option<JsonRpcResponse> {
         let request: JsonRpcRequest = match serde_json::from_str(raw) {
             Ok(req) => req,
             Err(_) => {
@@ -87,10 +97,10 @@ impl McpServer {
         })
     }
 
-    fn handle_notification(&mut self, method: &str) {
+    fn handle_notification(&self, method: &str) {
         match method {
             "notifications/initialized" => {
-                self.initialized = true;
+                self.initialized.store(true, Ordering::Relaxed);
                 eprintln!("[pt-mcp] Client initialized");
             }
             "notifications/cancelled" => {
@@ -103,7 +113,7 @@ impl McpServer {
     }
 
     fn handle_initialize(
-        &mut self,
+        &self,
         _params: &serde_json::Value,
     ) -> Result<serde_json::Value, (i32, String)> {
         Ok(serde_json::json!({
@@ -180,6 +190,111 @@ impl Default for McpServer {
     }
 }
 
+/// Serialize a response, falling back to a fixed internal error.
+pub(crate) fn response_json(resp: &JsonRpcResponse) -> String {
+    serde_json::to_string(resp).unwrap_or_else(|_| {
+        r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Serialization failed"}}"#.to_string()
+    })
+}
+
+/// Run the stdio transport on the tokio runtime until stdin closes or
+/// `token` is cancelled.
+///
+/// Up to `limit` requests are handled at once; each response is written as
+/// soon as it is ready.
+#[cfg(feature = "async")]
+pub async fn serve_stdio_async(
+    server: Arc<McpServer>,
+    limit: usize,
+    token: &crate::runtime::CancellationToken,
+) -> io::Result<()> {
+    // Blocking stdio lives on plain threads: a reader stuck in `read` must
+    // not keep the runtime from shutting down.
+    let (lines, incoming) = tokio::sync::mpsc::channel(limit.max(1));
+    std::thread::Builder::new()
+        .name("pt-mcp-stdin".to_string())
+        .spawn(move || {
+            for line in io::stdin().lock().lines() {
+                if lines.blocking_send(line).is_err() {
+                    break;
+                }
+            }
+        })?;
+    let (responses, outgoing) = std::sync::mpsc::channel::<String>();
+    let writer = std::thread::Builder::new()
+        .name("pt-mcp-stdout".to_string())
+        .spawn(move || -> io::Result<()> {
+            let mut stdout = io::stdout().lock();
+            for json in outgoing {
+                writeln!(stdout, "{}", json)?;
+                stdout.flush()?;
+            }
+            Ok(())
+        })?;
+
+    let result = serve_lines(server, incoming, responses, limit, token).await;
+    let written = writer
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("stdout writer panicked")));
+    result.and(written)
+}
+
+/// Dispatch each incoming line on the blocking pool with at most `limit` in
+/// flight, sending serialized responses to `responses`.
+#[cfg(feature = "async")]
+async fn serve_lines(
+    server: Arc<McpServer>,
+    mut incoming: tokio::sync::mpsc::Receiver<io::Result<String>>,
+    responses: std::sync::mpsc::Sender<String>,
+    limit: usize,
+    token: &crate::runtime::CancellationToken,
+) -> io::Result<()> {
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(limit.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+
+    loop {
+        let line = tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            line = incoming.recv() => match line {
+                Some(line) => line?,
+                None => break,
+            },
+        };
+        let raw = line.trim().to_string();
+        if raw.is_empty() {
+            continue;
+        }
+        let permit = tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            permit = Arc::clone(&semaphore).acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+        };
+
+        let server = Arc::clone(&server);
+        let responses = responses.clone();
+        tasks.spawn_blocking(move || {
+            let _permit = permit;
+            // Notifications (no id) get no response
+            if let Some(resp) = server.handle_message(&raw) {
+                let _ = responses.send(response_json(&resp));
+            }
+        });
+        while tasks.try_join_next().is_some() {}
+    }
+
+    // Let requests already being handled answer unless we are cancelled.
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => {}
+        _ = async { while tasks.join_next().await.is_some() {} } => {}
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,14 +305,14 @@ mod tests {
 
     #[test]
     fn handle_parse_error() {
-        let mut s = server();
+        let s = server();
         let resp = s.handle_message("not json").unwrap();
         assert_eq!(resp.error.as_ref().unwrap().code, PARSE_ERROR);
     }
 
     #[test]
     fn handle_initialize() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#)
             .unwrap();
@@ -210,15 +325,15 @@ mod tests {
 
     #[test]
     fn handle_notification_no_response() {
-        let mut s = server();
+        let s = server();
         let resp = s.handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#);
         assert!(resp.is_none());
-        assert!(s.initialized);
+        assert!(s.initialized.load(Ordering::Relaxed));
     }
 
     #[test]
     fn handle_ping() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#)
             .unwrap();
@@ -227,7 +342,7 @@ mod tests {
 
     #[test]
     fn handle_unknown_method() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":3,"method":"foo/bar"}"#)
             .unwrap();
@@ -236,7 +351,7 @@ mod tests {
 
     #[test]
     fn handle_tools_list() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":4,"method":"tools/list"}"#)
             .unwrap();
@@ -248,7 +363,7 @@ mod tests {
 
     #[test]
     fn handle_tools_call_missing_name() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":5,"method":"tools/call","params":{}}"#)
             .unwrap();
@@ -257,7 +372,7 @@ mod tests {
 
     #[test]
     fn handle_tools_call_signatures() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":6,"method":"tools/call","params":{"name":"pt_signatures","arguments":{}}}"#)
             .unwrap();
@@ -268,7 +383,7 @@ mod tests {
 
     #[test]
     fn handle_tools_call_unknown_tool() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"nonexistent","arguments":{}}}"#)
             .unwrap();
@@ -279,7 +394,7 @@ mod tests {
 
     #[test]
     fn handle_resources_list() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":8,"method":"resources/list"}"#)
             .unwrap();
@@ -291,7 +406,7 @@ mod tests {
 
     #[test]
     fn handle_resources_read_missing_uri() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":9,"method":"resources/read","params":{}}"#)
             .unwrap();
@@ -300,7 +415,7 @@ mod tests {
 
     #[test]
     fn handle_resources_read_version() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":10,"method":"resources/read","params":{"uri":"pt://version"}}"#)
             .unwrap();
@@ -312,7 +427,7 @@ mod tests {
 
     #[test]
     fn handle_resources_read_unknown_uri() {
-        let mut s = server();
+        let s = server();
         let resp = s
             .handle_message(r#"{"jsonrpc":"2.0","id":11,"method":"resources/read","params":{"uri":"pt://nonexistent"}}"#)
             .unwrap();
@@ -322,16 +437,59 @@ mod tests {
     #[test]
     fn server_default_not_initialized() {
         let s = McpServer::default();
-        assert!(!s.initialized);
+        assert!(!s.initialized.load(Ordering::Relaxed));
     }
 
     #[test]
     fn empty_line_skipped() {
         // Verify empty/whitespace input doesn't produce a response
-        let mut s = server();
+        let s = server();
         // Empty string should be filtered by run_stdio, but handle_message treats it as parse error
         let resp = s.handle_message("");
         // Empty string is technically invalid JSON
         assert!(resp.is_some());
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn serve_lines_answers_every_request() {
+        use crate::runtime::{AsyncRuntime, CancellationToken};
+
+        let runtime = AsyncRuntime::with_worker_threads(2).unwrap();
+        let (lines, incoming) = tokio::sync::mpsc::channel(8);
+        let (responses, outgoing) = std::sync::mpsc::channel();
+        for line in [
+            r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            "",
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+        ] {
+            lines.try_send(Ok(line.to_string())).unwrap();
+        }
+        drop(lines);
+
+        let server = Arc::new(server());
+        let token = CancellationToken::new();
+        runtime
+            .block_on(serve_lines(
+                Arc::clone(&server),
+                incoming,
+                responses,
+                2,
+                &token,
+            ))
+            .unwrap();
+
+        let mut ids: Vec<i64> = outgoing
+            .iter()
+            .map(|json| {
+                serde_json::from_str::<serde_json::Value>(&json).unwrap()["id"]
+                    .as_i64()
+                    .unwrap()
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(server.initialized.load(Ordering::Relaxed));
+    }
 }
@@ -1,124 +1,303 @@
 //! MCP tool implementations.
 //!
-//! Each tool maps to a pt operation: scan, explain, history, signatures, capabilities.
-
+//! Each tool maps to a pt operation: scan, plan, explain, apply, sessions,
+//! history, signatures, capabilities, feedback.
+//!
+//! Tool arguments are typed structs registered with [`crate::schema`], so the
+//! `inputSchema` advertised in `tools/list` is the same schema `pt schema`
+//! prints for that type.
+//!
+//! `pt_plan` and `pt_apply` run the `agent plan` / `agent apply` pipeline of
+//! the current executable, so sessions, policy and safety gates behave exactly
+//! as they do from the CLI. `pt_apply` adds MCP-specific gates on top: targets
+//! must be selected explicitly, and actions only execute when the caller
+//! passes `confirm: true` and policy enables robot mode; otherwise it returns a
+//! dry-run preview.
+
+use crate::exit_codes::ExitCode;
 use crate::mcp::protocol::{ToolContent, ToolDefinition};
+use pt_common::SessionId;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Arguments for `pt_scan`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanToolArgs {
+    /// Enable deep probes (lsof, ss, etc.)
+    pub deep: bool,
+    /// Minimum score threshold to include (0.0-1.0)
+    pub min_score: f64,
+}
+
+/// Arguments for `pt_explain`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExplainToolArgs {
+    /// Process ID to explain
+    pub pid: Option<u32>,
+    /// Process name (comm) to explain (alternative to pid)
+    pub comm: Option<String>,
+}
+
+/// Arguments for `pt_history`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryToolArgs {
+    /// Max sessions to return (default: 10)
+    pub limit: Option<usize>,
+}
+
+/// Arguments for `pt_signatures`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignaturesToolArgs {
+    /// Only show user-defined signatures
+    pub user_only: bool,
+    /// Filter by category: agent, ide, ci, orchestrator, terminal, other
+    pub category: Option<String>,
+}
+
+/// Arguments for `pt_capabilities` (none).
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct CapabilitiesToolArgs {}
+
+/// Arguments for `pt_plan`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlanToolArgs {
+    /// Existing session to plan in (default: create a new session)
+    pub session_id: Option<String>,
+    /// Label for a new session
+    pub label: Option<String>,
+    /// Maximum candidates to return (default: 20)
+    pub max_candidates: Option<u32>,
+    /// Minimum posterior probability for recommendations (0.0-1.0)
+    pub min_posterior: Option<f64>,
+    /// Only consider processes older than this many seconds
+    pub min_age: Option<u64>,
+    /// Filter by recommendation: kill, review, all
+    pub only: Option<String>,
+    /// Force deep scan with all available probes
+    pub deep: bool,
+}
+
+/// Arguments for `pt_apply`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApplyToolArgs {
+    /// Session whose plan to apply (from `pt_plan`)
+    pub session_id: String,
+    /// Apply actions for these PIDs
+    #[serde(default)]
+    pub pids: Vec<u32>,
+    /// Apply all recommended actions from the plan
+    #[serde(default)]
+    pub recommended: bool,
+    /// Execute the actions. When false, only a dry-run preview is returned.
+    /// Execution also requires robot mode to be enabled in policy.
+    #[serde(default)]
+    pub confirm: bool,
+    /// Minimum posterior probability for any action (0.0-1.0)
+    #[serde(default)]
+    pub min_posterior: Option<f64>,
+    /// Maximum kills for this run
+    #[serde(default)]
+    pub max_kills: Option<u32>,
+    /// Only act on processes older than this many seconds
+    #[serde(default)]
+    pub min_age: Option<u64>,
+    /// Require a known signature match for every kill
+    #[serde(default)]
+    pub require_known_signature: bool,
+}
+
+/// Arguments for `pt_sessions`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionsToolArgs {
+    /// Show one session in detail, including its event timeline
+    pub session_id: Option<String>,
+    /// Max sessions to list (default: 10)
+    pub limit: Option<u32>,
+    /// Only list sessions in this state: created, scanning, planned,
+    /// executing, completed, cancelled, failed, archived
+    pub state: Option<String>,
+}
+
+/// Arguments for `pt_feedback`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FeedbackToolArgs {
+    /// Session the decision was made in
+    pub session_id: String,
+    /// Process the label is about
+    pub pid: u32,
+    /// false_positive (the kill was wrong) or false_negative (a missed
+    /// abandoned process)
+    pub kind: String,
+    /// Free-form note stored with the label
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Tool name, description, and the [`crate::schema`] type of its arguments.
+const TOOLS: &[(&str, &str, &str)] = &[
+    (
+        "pt_scan",
+        "Run a process scan and return candidates with scores. \
+         Returns a JSON array of process records with evidence.",
+        "ScanToolArgs",
+    ),
+    (
+        "pt_plan",
+        "Scan, score, and build an action plan in a session. \
+         Returns the agent plan (candidates, recommendations, session_id). Never executes actions.",
+        "PlanToolArgs",
+    ),
+    (
+        "pt_explain",
+        "Explain a specific process — why it was flagged and its evidence chain.",
+        "ExplainToolArgs",
+    ),
+    (
+        "pt_apply",
+        "Apply a session's plan. Returns a dry-run preview unless confirm=true; \
+         execution also requires robot mode in policy and passes through all policy, \
+         identity, and pre-check safety gates.",
+        "ApplyToolArgs",
+    ),
+    (
+        "pt_sessions",
+        "List sessions with state and counts, or show one session with its event timeline.",
+        "SessionsToolArgs",
+    ),
+    (
+        "pt_history",
+        "Get recent session history with summaries.",
+        "HistoryToolArgs",
+    ),
+    (
+        "pt_signatures",
+        "List available process signatures (built-in and user-defined).",
+        "SignaturesToolArgs",
+    ),
+    (
+        "pt_capabilities",
+        "List available platform capabilities and probes.",
+        "CapabilitiesToolArgs",
+    ),
+    (
+        "pt_feedback",
+        "Record that a decision was wrong: a false_positive (a process that should not have \
+         been killed) or a false_negative (an abandoned process that was missed). Labels feed \
+         calibration and prior updates.",
+        "FeedbackToolArgs",
+    ),
+];
 
 /// Build the list of available MCP tool definitions.
 pub fn tool_definitions() -> Vec<ToolDefinition> {
-    vec![
-        ToolDefinition {
-            name: "pt_scan".to_string(),
-            description: "Run a process scan and return candidates with scores. \
-                          Returns a JSON array of process records with evidence."
-                .to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "deep": {
-                        "type": "boolean",
-                        "description": "Enable deep probes (lsof, ss, etc.)",
-                        "default": false
-                    },
-                    "min_score": {
-                        "type": "number",
-                        "description": "Minimum score threshold to include (0.0-1.0)",
-                        "default": 0.0,
-                        "minimum": 0.0,
-                        "maximum": 1.0
-                    }
-                },
-                "required": [],
-                "additionalProperties": false
-            }),
-        },
-        ToolDefinition {
-            name: "pt_explain".to_string(),
-            description: "Explain a specific process — why it was flagged and its evidence chain."
-                .to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "pid": {
-                        "type": "integer",
-                        "description": "Process ID to explain"
-                    },
-                    "comm": {
-                        "type": "string",
-                        "description": "Process name (comm) to explain (alternative to pid)"
-                    }
-                },
-                "required": [],
-                "additionalProperties": false
-            }),
-        },
-        ToolDefinition {
-            name: "pt_history".to_string(),
-            description: "Get recent session history with summaries.".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "limit": {
-                        "type": "integer",
-                        "description": "Max sessions to return (default: 10)",
-                        "default": 10
-                    }
-                },
-                "required": [],
-                "additionalProperties": false
-            }),
-        },
-        ToolDefinition {
-            name: "pt_signatures".to_string(),
-            description: "List available process signatures (built-in and user-defined)."
-                .to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "user_only": {
-                        "type": "boolean",
-                        "description": "Only show user-defined signatures",
-                        "default": false
-                    },
-                    "category": {
-                        "type": "string",
-                        "description": "Filter by category: agent, ide, ci, orchestrator, terminal, other"
-                    }
-                },
-                "required": [],
-                "additionalProperties": false
-            }),
-        },
-        ToolDefinition {
-            name: "pt_capabilities".to_string(),
-            description: "List available platform capabilities and probes.".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {},
-                "required": [],
-                "additionalProperties": false
-            }),
-        },
-    ]
+    TOOLS
+        .iter()
+        .map(|(name, description, args_type)| ToolDefinition {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema: input_schema(args_type),
+        })
+        .collect()
+}
+
+fn input_schema(args_type: &str) -> serde_json::Value {
+    let mut schema = crate::schema::generate_schema(args_type)
+        .unwrap_or_else(|| panic!("{} is not registered in crate::schema", args_type));
+    // MCP clients expect `properties` even for argument-less tools.
+    if let Some(obj) = schema.as_object_mut() {
+        obj.entry("properties")
+            .or_insert_with(|| serde_json::json!({}));
+    }
+    schema
 }
 
 /// Dispatch a tool call by name and return content blocks.
 pub fn call_tool(name: &str, params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
     match name {
-        "pt_scan" => tool_scan(params),
-        "pt_explain" => tool_explain(params),
-        "pt_history" => tool_history(params),
-        "pt_signatures" => tool_signatures(params),
-        "pt_capabilities" => tool_capabilities(params),
+        "pt_scan" => tool_scan(&parse_args(params)?),
+        "pt_plan" => tool_plan(&parse_args(params)?),
+        "pt_explain" => tool_explain(&parse_args(params)?),
+        "pt_apply" => tool_apply(&parse_args(params)?),
+        "pt_sessions" => tool_sessions(&parse_args(params)?),
+        "pt_history" => tool_history(&parse_args(params)?),
+        "pt_signatures" => tool_signatures(&parse_args(params)?),
+        "pt_capabilities" => tool_capabilities(&parse_args(params)?),
+        "pt_feedback" => tool_feedback(&parse_args(params)?),
         _ => Err(format!("Unknown tool: {}", name)),
     }
 }
 
-fn tool_scan(params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
-    let min_score = params
-        .get("min_score")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
+fn parse_args<T: DeserializeOwned>(params: &serde_json::Value) -> Result<T, String> {
+    if params.is_null() {
+        return serde_json::from_value(serde_json::json!({}))
+            .map_err(|e| format!("Invalid arguments: {}", e));
+    }
+    serde_json::from_value(params.clone()).map_err(|e| format!("Invalid arguments: {}", e))
+}
+
+fn json_content(value: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
+    Ok(vec![ToolContent {
+        content_type: "text".to_string(),
+        text: serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Serialization error: {}", e))?,
+    }])
+}
+
+/// Run `pt-core --format json <args>` and return its JSON output.
+///
+/// Exit codes above `ActionsOk` (blocked, partial failure, errors) are
+/// reported as tool errors carrying the command's JSON output when there is
+/// one.
+fn run_agent_command(args: &[String]) -> Result<serde_json::Value, String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to resolve pt-core executable: {}", e))?;
+    let output = std::process::Command::new(exe)
+        .args(["--format", "json"])
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", args.join(" "), e))?;
+
+    let code = output
+        .status
+        .code()
+        .unwrap_or(ExitCode::InternalError.as_i32());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed = serde_json::from_str::<serde_json::Value>(stdout.trim());
+    match parsed {
+        Ok(mut value) if code <= ExitCode::ActionsOk.as_i32() => {
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("exit_code".to_string(), serde_json::json!(code));
+            }
+            Ok(value)
+        }
+        Ok(value) => Err(format!(
+            "{} exited with code {}: {}",
+            args.join(" "),
+            code,
+            value
+        )),
+        Err(_) => Err(format!(
+            "{} exited with code {}: {}",
+            args.join(" "),
+            code,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+fn tool_scan(args: &ScanToolArgs) -> Result<Vec<ToolContent>, String> {
+    let min_score = args.min_score;
 
     let options = crate::collect::QuickScanOptions::default();
     let scan_result =
@@ -173,9 +352,9 @@ fn tool_scan(params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
     }])
 }
 
-fn tool_explain(params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
-    let pid = params.get("pid").and_then(|v| v.as_u64()).map(|v| v as u32);
-    let comm = params.get("comm").and_then(|v| v.as_str());
+fn tool_explain(args: &ExplainToolArgs) -> Result<Vec<ToolContent>, String> {
+    let pid = args.pid;
+    let comm = args.comm.as_deref();
 
     if pid.is_none() && comm.is_none() {
         return Err("Either 'pid' or 'comm' must be provided".to_string());
@@ -209,6 +388,11 @@ fn tool_explain(params: &serde_json::Value) -> Result<Vec<ToolContent>, String>
 
             let mut db = crate::supervision::SignatureDatabase::new();
             db.add_default_signatures();
+            if let Some(pack) = crate::signature_cli::load_signature_pack() {
+                for sig in pack.signatures {
+                    let _ = db.add(sig);
+                }
+            }
             if let Some(user_schema) = crate::signature_cli::load_user_signatures() {
                 for sig in user_schema.signatures {
                     let _ = db.add(sig);
@@ -270,8 +454,191 @@ fn tool_explain(params: &serde_json::Value) -> Result<Vec<ToolContent>, String>
     }
 }
 
-fn tool_history(params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
-    let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+fn tool_plan(args: &PlanToolArgs) -> Result<Vec<ToolContent>, String> {
+    let mut cmd: Vec<String> = vec!["agent".into(), "plan".into()];
+    if let Some(raw) = &args.session_id {
+        let sid = SessionId::parse(raw).ok_or_else(|| format!("Invalid session_id: {}", raw))?;
+        cmd.extend(["--session".into(), sid.0]);
+    }
+    if let Some(label) = &args.label {
+        cmd.extend(["--label".into(), label.clone()]);
+    }
+    if let Some(max) = args.max_candidates {
+        cmd.extend(["--max-candidates".into(), max.to_string()]);
+    }
+    if let Some(p) = args.min_posterior {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("min_posterior must be within 0.0-1.0, got {}", p));
+        }
+        cmd.extend(["--min-posterior".into(), p.to_string()]);
+    }
+    if let Some(age) = args.min_age {
+        cmd.extend(["--min-age".into(), age.to_string()]);
+    }
+    if let Some(only) = &args.only {
+        if !matches!(only.as_str(), "kill" | "review" | "all") {
+            return Err(format!("only must be kill, review, or all, got '{}'", only));
+        }
+        cmd.extend(["--only".into(), only.clone()]);
+    }
+    if args.deep {
+        cmd.push("--deep".into());
+    }
+
+    json_content(&run_agent_command(&cmd)?)
+}
+
+/// Validate `pt_apply` arguments and build the `agent apply` command line.
+///
+/// `robot_mode_enabled` comes from the active policy; without it a confirmed
+/// apply is refused rather than downgraded, so the caller sees why.
+fn apply_command(args: &ApplyToolArgs, robot_mode_enabled: bool) -> Result<Vec<String>, String> {
+    let sid = SessionId::parse(&args.session_id)
+        .ok_or_else(|| format!("Invalid session_id: '{}'", args.session_id))?;
+    if args.pids.is_empty() && !args.recommended {
+        return Err("No targets selected: pass pids or recommended=true".to_string());
+    }
+    if args.confirm && !robot_mode_enabled {
+        return Err("Execution requires robot_mode.enabled in policy; \
+                    call without confirm for a dry-run preview"
+            .to_string());
+    }
+
+    let mut cmd: Vec<String> = vec![
+        "agent".into(),
+        "apply".into(),
+        "--session".into(),
+        sid.0,
+        "--abort-on-unknown".into(),
+    ];
+    if args.confirm {
+        cmd.push("--yes".into());
+    } else {
+        cmd.push("--dry-run".into());
+    }
+    if !args.pids.is_empty() {
+        let pids: Vec<String> = args.pids.iter().map(|p| p.to_string()).collect();
+        cmd.extend(["--pids".into(), pids.join(",")]);
+    }
+    if args.recommended {
+        cmd.push("--recommended".into());
+    }
+    if let Some(p) = args.min_posterior {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("min_posterior must be within 0.0-1.0, got {}", p));
+        }
+        cmd.extend(["--min-posterior".into(), p.to_string()]);
+    }
+    if let Some(max) = args.max_kills {
+        cmd.extend(["--max-kills".into(), max.to_string()]);
+    }
+    if let Some(age) = args.min_age {
+        cmd.extend(["--min-age".into(), age.to_string()]);
+    }
+    if args.require_known_signature {
+        cmd.push("--require-known-signature".into());
+    }
+    Ok(cmd)
+}
+
+fn tool_apply(args: &ApplyToolArgs) -> Result<Vec<ToolContent>, String> {
+    let robot_mode_enabled = if args.confirm {
+        let config = crate::config::load_config(&crate::config::ConfigOptions::default())
+            .map_err(|e| format!("Config load error: {}", e))?;
+        config.policy.robot_mode.enabled
+    } else {
+        false
+    };
+    let cmd = apply_command(args, robot_mode_enabled)?;
+    let mut result = run_agent_command(&cmd)?;
+    if let Some(obj) = result.as_object_mut() {
+        obj.insert("executed".to_string(), serde_json::json!(args.confirm));
+    }
+    json_content(&result)
+}
+
+/// Validate `pt_feedback` arguments and build the `feedback record` command
+/// line. Labels from MCP are always attributed to the agent.
+fn feedback_command(args: &FeedbackToolArgs) -> Result<Vec<String>, String> {
+    let sid = SessionId::parse(&args.session_id)
+        .ok_or_else(|| format!("Invalid session_id: '{}'", args.session_id))?;
+    let kind = crate::feedback::FeedbackKind::parse(&args.kind).ok_or_else(|| {
+        format!(
+            "kind must be false_positive or false_negative, got '{}'",
+            args.kind
+        )
+    })?;
+    let mut cmd: Vec<String> = vec![
+        "feedback".into(),
+        "record".into(),
+        "--session".into(),
+        sid.0,
+        "--pid".into(),
+        args.pid.to_string(),
+        "--kind".into(),
+        kind.as_str().into(),
+        "--actor".into(),
+        "agent".into(),
+    ];
+    if let Some(note) = &args.note {
+        cmd.extend(["--note".into(), note.clone()]);
+    }
+    Ok(cmd)
+}
+
+fn tool_feedback(args: &FeedbackToolArgs) -> Result<Vec<ToolContent>, String> {
+    json_content(&run_agent_command(&feedback_command(args)?)?)
+}
+
+fn tool_sessions(args: &SessionsToolArgs) -> Result<Vec<ToolContent>, String> {
+    let store = crate::session::SessionStore::from_env()
+        .map_err(|e| format!("Session store error: {}", e))?;
+
+    if let Some(raw) = &args.session_id {
+        let sid = SessionId::parse(raw).ok_or_else(|| format!("Invalid session_id: {}", raw))?;
+        let handle = store.open(&sid).map_err(|e| e.to_string())?;
+        let manifest = handle.read_manifest().map_err(|e| e.to_string())?;
+        let events = handle.read_events().map_err(|e| e.to_string())?;
+        let view = crate::session::SessionView::replay(&events);
+        return json_content(&serde_json::json!({
+            "session_id": manifest.session_id,
+            "state": manifest.state,
+            "mode": manifest.mode,
+            "label": manifest.label,
+            "timing": manifest.timing,
+            "error": manifest.error,
+            "process_count": view.process_count,
+            "candidate_count": view.candidate_count,
+            "actions": view.actions,
+            "verifications": view.verifications,
+            "events": events,
+        }));
+    }
+
+    let state = match &args.state {
+        Some(raw) => Some(
+            serde_json::from_value::<crate::session::SessionState>(serde_json::json!(raw))
+                .map_err(|_| format!("Unknown session state: {}", raw))?,
+        ),
+        None => None,
+    };
+    let options = crate::session::ListSessionsOptions {
+        limit: Some(args.limit.unwrap_or(10)),
+        state,
+        older_than: None,
+    };
+    let sessions = store
+        .list_sessions(&options)
+        .map_err(|e| format!("Session list error: {}", e))?;
+
+    json_content(&serde_json::json!({
+        "count": sessions.len(),
+        "sessions": sessions,
+    }))
+}
+
+fn tool_history(args: &HistoryToolArgs) -> Result<Vec<ToolContent>, String> {
+    let limit = args.limit.unwrap_or(10);
 
     let store = crate::session::SessionStore::from_env()
         .map_err(|e| format!("Session store error: {}", e))?;
@@ -317,12 +684,9 @@ fn tool_history(params: &serde_json::Value) -> Result<Vec<ToolContent>, String>
     }])
 }
 
-fn tool_signatures(params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
-    let user_only = params
-        .get("user_only")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    let category_filter = params.get("category").and_then(|v| v.as_str());
+fn tool_signatures(args: &SignaturesToolArgs) -> Result<Vec<ToolContent>, String> {
+    let user_only = args.user_only;
+    let category_filter = args.category.as_deref();
 
     let mut all_sigs = Vec::new();
 
@@ -378,7 +742,7 @@ fn tool_signatures(params: &serde_json::Value) -> Result<Vec<ToolContent>, Strin
     }])
 }
 
-fn tool_capabilities(_params: &serde_json::Value) -> Result<Vec<ToolContent>, String> {
+fn tool_capabilities(_args: &CapabilitiesToolArgs) -> Result<Vec<ToolContent>, String> {
     let caps = crate::capabilities::get_capabilities();
 
     // Capabilities derives Serialize, so serialize it directly
@@ -459,7 +823,118 @@ mod tests {
     #[test]
     fn tool_definitions_count() {
         let defs = tool_definitions();
-        assert_eq!(defs.len(), 5);
+        assert_eq!(defs.len(), 9);
+    }
+
+    #[test]
+    fn tool_schemas_match_schema_module() {
+        for (name, _, args_type) in TOOLS {
+            let def = tool_definitions()
+                .into_iter()
+                .find(|d| d.name == *name)
+                .unwrap();
+            let schema = crate::schema::generate_schema(args_type).unwrap();
+            let properties = schema
+                .get("properties")
+                .cloned()
+                .unwrap_or_else(|| serde_json::json!({}));
+            assert_eq!(def.input_schema["properties"], properties);
+        }
+    }
+
+    #[test]
+    fn unknown_arguments_are_rejected() {
+        let result = call_tool("pt_signatures", &serde_json::json!({"bogus": 1}));
+        assert!(result.unwrap_err().contains("Invalid arguments"));
+    }
+
+    #[test]
+    fn feedback_command_is_attributed_to_agent() {
+        let args = FeedbackToolArgs {
+            session_id: "pt-20260115-120000-mcpf".to_string(),
+            pid: 4242,
+            kind: "missed".to_string(),
+            note: Some("idle for days".to_string()),
+        };
+        let cmd = feedback_command(&args).unwrap();
+        assert_eq!(cmd[..2], ["feedback".to_string(), "record".to_string()]);
+        assert!(cmd.windows(2).any(|w| w == ["--kind", "false_negative"]));
+        assert!(cmd.windows(2).any(|w| w == ["--actor", "agent"]));
+
+        let bad = FeedbackToolArgs {
+            kind: "maybe".to_string(),
+            ..args
+        };
+        assert!(feedback_command(&bad).unwrap_err().contains("kind"));
+    }
+
+    fn apply_args() -> ApplyToolArgs {
+        ApplyToolArgs {
+            session_id: "pt-20260115-120000-mcpa".to_string(),
+            recommended: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn apply_defaults_to_dry_run() {
+        let cmd = apply_command(&apply_args(), false).unwrap();
+        assert!(cmd.contains(&"--dry-run".to_string()));
+        assert!(!cmd.contains(&"--yes".to_string()));
+        assert!(cmd.contains(&"--abort-on-unknown".to_string()));
+    }
+
+    #[test]
+    fn apply_confirm_requires_robot_mode() {
+        let args = ApplyToolArgs {
+            confirm: true,
+            ..apply_args()
+        };
+        assert!(apply_command(&args, false)
+            .unwrap_err()
+            .contains("robot_mode"));
+        let cmd = apply_command(&args, true).unwrap();
+        assert!(cmd.contains(&"--yes".to_string()));
+        assert!(!cmd.contains(&"--dry-run".to_string()));
+    }
+
+    #[test]
+    fn apply_requires_session_and_targets() {
+        let no_targets = ApplyToolArgs {
+            recommended: false,
+            ..apply_args()
+        };
+        assert!(apply_command(&no_targets, true)
+            .unwrap_err()
+            .contains("targets"));
+
+        let bad_session = ApplyToolArgs {
+            session_id: "not-a-session".to_string(),
+            ..apply_args()
+        };
+        assert!(apply_command(&bad_session, true)
+            .unwrap_err()
+            .contains("session_id"));
+    }
+
+    #[test]
+    fn apply_passes_pids_and_limits() {
+        let args = ApplyToolArgs {
+            pids: vec![101, 202],
+            recommended: false,
+            max_kills: Some(1),
+            ..apply_args()
+        };
+        let cmd = apply_command(&args, false).unwrap();
+        let joined = cmd.join(" ");
+        assert!(joined.contains("--pids 101,202"));
+        assert!(joined.contains("--max-kills 1"));
+    }
+
+    #[test]
+    fn plan_rejects_invalid_only_filter() {
+        let result = call_tool("pt_plan", &serde_json::json!({"only": "everything"}));
+        assert!(result.unwrap_err().contains("only"));
     }
 
     #[test]
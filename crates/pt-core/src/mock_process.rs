@@ -482,6 +482,7 @@ impl MockProcessBuilder {
             elapsed: self.elapsed,
             source: self.source,
             container_info: None,
+            io_rate: None,
         }
     }
 }
@@ -647,6 +648,7 @@ impl MockScanBuilder {
                 duration_ms: 100, // Mock duration
                 process_count,
                 warnings: self.warnings,
+                visibility: Default::default(),
             },
         }
     }
@@ -0,0 +1,299 @@
+//! Typed envelopes for agent command JSON output.
+//!
+//! Agent commands build their output with `serde_json::json!`, which keeps
+//! them flexible but leaves the shape undocumented. The structs here pin
+//! down the stable part of each output so `pt schema` can publish a JSON
+//! Schema for it and tests can check real output against it.
+//!
+//! Envelopes describe the default `--format json` output. Consumers must
+//! tolerate unknown fields (see `docs/AGENT_CLI_CONTRACT.md`), so only the
+//! fields that are always present, or that agents rely on, are typed here;
+//! nested sections that are still evolving are kept as free-form JSON.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Posterior probabilities over the four process classes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PosteriorSummary {
+    pub useful: f64,
+    pub useful_bad: f64,
+    pub abandoned: f64,
+    pub zombie: f64,
+}
+
+/// `scan` section of `agent plan` output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanScanInfo {
+    pub total_processes: u64,
+    /// Candidates at or above `--min-posterior` before `--max-candidates`.
+    pub candidates_found: u64,
+    pub scan_duration_ms: u64,
+    /// Whether the scan saw the whole process table (`full` / `partial`).
+    pub visibility_scope: Value,
+}
+
+/// `summary` section of `agent plan` output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanSummary {
+    pub total_processes_scanned: u64,
+    pub protected_filtered: u64,
+    pub candidates_evaluated: u64,
+    pub above_threshold: u64,
+    pub candidates_returned: u64,
+    pub kill_recommendations: u64,
+    pub review_recommendations: u64,
+    pub policy_blocked: u64,
+    pub threshold_used: f64,
+}
+
+/// One candidate in `agent plan` output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanCandidate {
+    pub pid: u32,
+    pub ppid: u32,
+    pub state: String,
+    /// `pid:start_time` identity used to detect PID reuse.
+    pub start_id: String,
+    pub uid: u32,
+    pub user: String,
+    pub command: String,
+    pub command_short: String,
+    /// Ranking score (0-100).
+    pub score: u32,
+    pub classification: String,
+    pub posterior: PosteriorSummary,
+    pub confidence: String,
+    /// Upper-case form of `recommended_action` (legacy).
+    pub recommendation: String,
+    pub recommended_action: String,
+    pub policy_blocked: bool,
+    #[serde(default)]
+    pub signature: Option<Value>,
+    #[serde(default)]
+    pub runtime_prior: Option<Value>,
+    #[serde(default)]
+    pub evidence: Option<Value>,
+    #[serde(default)]
+    pub expected_loss: Option<Value>,
+}
+
+/// `recommendations` section of `agent plan` output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanRecommendations {
+    pub kill_set: Vec<u32>,
+    pub review_set: Vec<u32>,
+    pub spare_set: Vec<u32>,
+    pub expected_memory_freed_gb: f64,
+    #[serde(default)]
+    pub goal: Option<Value>,
+}
+
+/// `pt agent plan` JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AgentPlanOutput {
+    pub pt_version: String,
+    pub schema_version: String,
+    pub session_id: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub generated_at: String,
+    pub host_id: String,
+    pub host: Value,
+    pub scan: PlanScanInfo,
+    pub command: String,
+    pub args: Value,
+    pub summary: PlanSummary,
+    pub goal: Value,
+    pub goal_progress: Value,
+    #[serde(default)]
+    pub goal_summary: Option<Value>,
+    pub candidates: Vec<PlanCandidate>,
+    pub recommendations: PlanRecommendations,
+    /// Legacy recommendation format.
+    pub recommended: Value,
+    pub session_created: bool,
+}
+
+/// One entry in `agent explain` output.
+///
+/// Processes that could not be explained (exited, posterior failure) carry
+/// `error` and omit the inference fields.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProcessExplanation {
+    pub pid: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub ppid: Option<u32>,
+    #[serde(default)]
+    pub comm: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub elapsed_seconds: Option<u64>,
+    #[serde(default)]
+    pub cpu_percent: Option<f64>,
+    #[serde(default)]
+    pub classification: Option<String>,
+    #[serde(default)]
+    pub confidence: Option<String>,
+    #[serde(default)]
+    pub why_summary: Option<String>,
+    #[serde(default)]
+    pub posterior: Option<PosteriorSummary>,
+    #[serde(default)]
+    pub bayes_factors: Option<Value>,
+    #[serde(default)]
+    pub runtime_prior: Option<Value>,
+}
+
+/// `pt agent explain` JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AgentExplainOutput {
+    pub schema_version: String,
+    pub session_id: String,
+    pub generated_at: String,
+    pub command: String,
+    /// `kernel.yama.ptrace_scope`, when readable.
+    pub ptrace_scope: Option<u8>,
+    pub explanations: Vec<ProcessExplanation>,
+}
+
+/// Sessions compared by `agent diff`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffComparison {
+    pub prior_session: String,
+    pub current_session: String,
+    pub prior_timestamp: String,
+    pub current_timestamp: String,
+}
+
+/// Candidate changes between two sessions, after `--focus` filtering.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffDelta {
+    pub new: Vec<Value>,
+    pub worsened: Vec<Value>,
+    pub improved: Vec<Value>,
+    pub resolved: Vec<Value>,
+    pub persistent: Vec<Value>,
+}
+
+/// Unfiltered change counts for `agent diff`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffSummary {
+    pub prior_candidates: u64,
+    pub current_candidates: u64,
+    pub new_count: u64,
+    pub worsened_count: u64,
+    pub improved_count: u64,
+    pub resolved_count: u64,
+    pub persistent_count: u64,
+    /// True when `--focus` hid some of the delta.
+    pub filtered: bool,
+}
+
+/// `pt agent diff` JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AgentDiffOutput {
+    pub comparison: DiffComparison,
+    pub focus: String,
+    pub delta: DiffDelta,
+    pub summary: DiffSummary,
+}
+
+/// Fleet-wide totals in `agent fleet report`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FleetReportAggregate {
+    pub total_hosts: u64,
+    pub total_processes: u64,
+    pub total_candidates: u64,
+    pub class_counts: BTreeMap<String, u64>,
+    pub action_counts: BTreeMap<String, u64>,
+    pub mean_candidate_score: f64,
+    pub max_candidate_score: f64,
+    pub recurring_patterns: Vec<Value>,
+}
+
+/// `report` section of `agent fleet report` output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FleetReportBody {
+    /// Redaction profile (`minimal` / `safe` / `forensic`).
+    pub profile: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub aggregate: FleetReportAggregate,
+    pub safety_budget: Value,
+    pub hosts: Vec<Value>,
+    pub top_offenders: Vec<Value>,
+    /// Candidates grouped by Kubernetes namespace and workload.
+    #[serde(default)]
+    pub workload_offenders: Vec<Value>,
+    pub host_comparison: Vec<Value>,
+    /// Risk and top offenders per inventory host group.
+    #[serde(default)]
+    pub groups: Vec<Value>,
+    pub cross_host_anomalies: Value,
+}
+
+/// `pt agent fleet report` JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FleetReportOutput {
+    pub schema_version: String,
+    pub fleet_session_id: String,
+    pub generated_at: String,
+    pub command: String,
+    pub session_dir: String,
+    pub report: FleetReportBody,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explanation_error_entry_parses() {
+        let entry: ProcessExplanation = serde_json::from_value(serde_json::json!({
+            "pid": 42,
+            "error": "process not found (may have exited)",
+            "classification": null,
+        }))
+        .expect("error entry");
+        assert_eq!(entry.pid, 42);
+        assert!(entry.error.is_some());
+        assert!(entry.classification.is_none());
+    }
+
+    #[test]
+    fn diff_output_tolerates_unknown_fields() {
+        let output: AgentDiffOutput = serde_json::from_value(serde_json::json!({
+            "comparison": {
+                "prior_session": "pt-20260101-000000-aaaa",
+                "current_session": "pt-20260101-010000-bbbb",
+                "prior_timestamp": "2026-01-01T00:00:00Z",
+                "current_timestamp": "2026-01-01T01:00:00Z",
+            },
+            "focus": "all",
+            "delta": {"new": [], "worsened": [], "improved": [], "resolved": [], "persistent": []},
+            "summary": {
+                "prior_candidates": 0,
+                "current_candidates": 0,
+                "new_count": 0,
+                "worsened_count": 0,
+                "improved_count": 0,
+                "resolved_count": 0,
+                "persistent_count": 0,
+                "filtered": false,
+                "future_field": 1,
+            },
+            "future_section": {},
+        }))
+        .expect("diff output");
+        assert_eq!(output.focus, "all");
+    }
+}
@@ -4,6 +4,8 @@
 //! for optimizing output for AI agents with limited context windows.
 
 pub mod agent_errors;
+pub mod envelopes;
+pub mod pagination;
 pub mod predictions;
 pub mod progressive;
 
@@ -476,9 +478,14 @@ impl TokenEfficientOutput {
             result = compact.compact_value(result);
         }
 
-        // Step 3: Truncate if needed
+        // Step 3: Truncate if needed (keeping the full value for pagination)
+        let mut untruncated = None;
         let truncation = if let Some(max) = self.max_tokens {
-            truncate_to_tokens(result, max, &self.estimator)
+            let truncation = truncate_to_tokens(result.clone(), max, &self.estimator);
+            if truncation.truncated {
+                untruncated = Some(result);
+            }
+            truncation
         } else {
             TruncationResult {
                 value: result,
@@ -504,6 +511,7 @@ impl TokenEfficientOutput {
             truncated: truncation.truncated,
             continuation_token: truncation.continuation_token,
             remaining_count: truncation.remaining_count,
+            untruncated,
         }
     }
 }
@@ -523,6 +531,9 @@ pub struct ProcessedOutput {
     pub continuation_token: Option<String>,
     /// Remaining items if truncated
     pub remaining_count: Option<usize>,
+    /// Output before truncation (set only when truncated), for
+    /// [`pagination`] to persist
+    pub untruncated: Option<Value>,
 }
 
 /// Encode a JSON value into TOON with safe key folding.
@@ -538,6 +549,38 @@ pub fn encode_toon_value(value: &Value) -> String {
     encode(value.clone(), Some(options))
 }
 
+/// Sparkline glyphs, lowest to highest.
+const SPARK_LEVELS: [char; 8] = [
+    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
+];
+
+/// Render the last `width` values as a sparkline scaled to their maximum.
+///
+/// An all-zero series renders as a flat baseline.
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    let start = values.len().saturating_sub(width);
+    let max = values[start..].iter().copied().fold(0.0_f64, f64::max);
+    sparkline_with_max(values, width, max)
+}
+
+/// Render the last `width` values as a sparkline on a fixed `0..=max` scale.
+pub fn sparkline_with_max(values: &[f64], width: usize, max: f64) -> String {
+    let start = values.len().saturating_sub(width);
+    let window = &values[start..];
+    let top = (SPARK_LEVELS.len() - 1) as f64;
+    window
+        .iter()
+        .map(|value| {
+            let level = if max > 0.0 {
+                ((value / max) * top).round().clamp(0.0, top) as usize
+            } else {
+                0
+            };
+            SPARK_LEVELS[level]
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,491 @@
+//! Resumable pagination for `--max-tokens` truncated output.
+//!
+//! When truncation drops items from an array field, the untruncated output is
+//! persisted as a [`PageCursor`] and the continuation token points into it:
+//!
+//! ```text
+//! <scope>:<cursor_id>:<field>:<offset>:<total>
+//! ```
+//!
+//! `scope` is the session id when the output belongs to an existing session
+//! (cursor stored under `<session>/pagination/`), otherwise `global` (stored
+//! under `pagination/` next to the sessions directory). `--continue <TOKEN>`
+//! loads the cursor and returns the next chunk that fits the token budget.
+//! The cursor is a snapshot, so the same token always yields the same chunk.
+
+use super::{truncate_to_tokens, TokenEstimator};
+use crate::session::{SessionError, SessionStore};
+use chrono::Utc;
+use pt_common::SessionId;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Scope used for output that does not belong to a session.
+pub const GLOBAL_SCOPE: &str = "global";
+
+const PAGINATION_DIR: &str = "pagination";
+const CURSOR_SCHEMA_VERSION: &str = "1.0.0";
+
+#[derive(Debug, Error)]
+pub enum PaginationError {
+    #[error("invalid continuation token '{token}': {reason}")]
+    InvalidToken { token: String, reason: String },
+    #[error("continuation state not found for token '{token}' (expired or cleaned up)")]
+    NotFound { token: String },
+    #[error("continuation token '{token}' does not match its stored state")]
+    Mismatch { token: String },
+    #[error(transparent)]
+    Session(#[from] SessionError),
+    #[error("I/O error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("JSON error at {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Parsed continuation token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinuationToken {
+    pub scope: String,
+    pub cursor_id: String,
+    pub field: String,
+    /// Index of the first item not yet returned.
+    pub offset: usize,
+    pub total: usize,
+}
+
+impl ContinuationToken {
+    pub fn parse(raw: &str) -> Result<Self, PaginationError> {
+        let invalid = |reason: &str| PaginationError::InvalidToken {
+            token: raw.to_string(),
+            reason: reason.to_string(),
+        };
+        let parts: Vec<&str> = raw.split(':').collect();
+        let [scope, cursor_id, field, offset, total] = parts.as_slice() else {
+            return Err(invalid(
+                "expected <scope>:<cursor>:<field>:<offset>:<total>",
+            ));
+        };
+        if *scope != GLOBAL_SCOPE && SessionId::parse(scope).is_none() {
+            return Err(invalid("scope is neither 'global' nor a session id"));
+        }
+        if cursor_id.is_empty() || !cursor_id.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("cursor id must be hex"));
+        }
+        if field.is_empty() {
+            return Err(invalid("missing field"));
+        }
+        let offset: usize = offset
+            .parse()
+            .map_err(|_| invalid("offset is not a number"))?;
+        let total: usize = total
+            .parse()
+            .map_err(|_| invalid("total is not a number"))?;
+        if offset >= total {
+            return Err(invalid("offset is past the end"));
+        }
+        Ok(Self {
+            scope: scope.to_string(),
+            cursor_id: cursor_id.to_string(),
+            field: field.to_string(),
+            offset,
+            total,
+        })
+    }
+}
+
+impl fmt::Display for ContinuationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}",
+            self.scope, self.cursor_id, self.field, self.offset, self.total
+        )
+    }
+}
+
+/// Persisted untruncated output a continuation token pages through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageCursor {
+    pub schema_version: String,
+    pub cursor_id: String,
+    pub scope: String,
+    pub created_at: String,
+    /// Array field being paged.
+    pub field: String,
+    pub total: usize,
+    /// Token budget of the original request (used when `--continue` has none).
+    pub max_tokens: usize,
+    /// Whether the output was compacted (serialize pages minified).
+    pub compact: bool,
+    /// Output after field selection/compaction, before truncation.
+    pub value: Value,
+}
+
+impl PageCursor {
+    /// Build a cursor; the id is derived from the content so identical
+    /// output reuses the same cursor.
+    pub fn new(scope: &str, field: &str, max_tokens: usize, compact: bool, value: Value) -> Self {
+        let total = value
+            .get(field)
+            .and_then(|v| v.as_array())
+            .map(|a| a.len())
+            .unwrap_or(0);
+        let digest = Sha256::digest(serde_json::to_vec(&value).unwrap_or_default());
+        Self {
+            schema_version: CURSOR_SCHEMA_VERSION.to_string(),
+            cursor_id: hex::encode(&digest[..8]),
+            scope: scope.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            field: field.to_string(),
+            total,
+            max_tokens,
+            compact,
+            value,
+        }
+    }
+
+    /// Token for resuming at `offset`, or `None` when nothing is left.
+    pub fn token_at(&self, offset: usize) -> Option<ContinuationToken> {
+        (offset < self.total).then(|| ContinuationToken {
+            scope: self.scope.clone(),
+            cursor_id: self.cursor_id.clone(),
+            field: self.field.clone(),
+            offset,
+            total: self.total,
+        })
+    }
+
+    /// Return the chunk starting at `offset` that fits `max_tokens`.
+    ///
+    /// Always returns at least one item so paging makes progress.
+    pub fn page(&self, offset: usize, max_tokens: usize, estimator: &TokenEstimator) -> Page {
+        let items: &[Value] = self
+            .value
+            .get(&self.field)
+            .and_then(|v| v.as_array())
+            .map(|a| a.as_slice())
+            .unwrap_or(&[]);
+        let rest = items.get(offset..).unwrap_or(&[]);
+        let Value::Object(mut map) = self.value.clone() else {
+            return Page {
+                value: self.value.clone(),
+                offset,
+                returned: 0,
+                remaining: 0,
+                next: None,
+            };
+        };
+
+        let mut low = 1;
+        let mut high = rest.len();
+        let mut best = rest.len().min(1);
+        while low <= high {
+            let mid = (low + high) / 2;
+            map.insert(self.field.clone(), Value::Array(rest[..mid].to_vec()));
+            if estimator.estimate_value_tokens(&Value::Object(map.clone())) <= max_tokens {
+                best = mid;
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+        map.insert(self.field.clone(), Value::Array(rest[..best].to_vec()));
+
+        let next_offset = offset + best;
+        Page {
+            value: Value::Object(map),
+            offset,
+            returned: best,
+            remaining: self.total.saturating_sub(next_offset),
+            next: self.token_at(next_offset),
+        }
+    }
+}
+
+/// One chunk of paged output.
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub value: Value,
+    pub offset: usize,
+    pub returned: usize,
+    pub remaining: usize,
+    pub next: Option<ContinuationToken>,
+}
+
+impl Page {
+    /// Wrap the chunk with the same `_meta` shape truncated output uses.
+    pub fn to_output(&self, estimator: &TokenEstimator) -> Value {
+        serde_json::json!({
+            "data": self.value,
+            "_meta": {
+                "truncated": self.next.is_some(),
+                "continuation_token": self.next.as_ref().map(|t| t.to_string()),
+                "remaining_count": self.remaining,
+                "offset": self.offset,
+                "returned": self.returned,
+                "token_count": estimator.estimate_value_tokens(&self.value),
+            }
+        })
+    }
+}
+
+/// Stores [`PageCursor`]s per session (or globally).
+#[derive(Debug, Clone)]
+pub struct PaginationStore {
+    sessions_root: PathBuf,
+}
+
+impl PaginationStore {
+    pub fn new(sessions_root: impl Into<PathBuf>) -> Self {
+        Self {
+            sessions_root: sessions_root.into(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, PaginationError> {
+        let store = SessionStore::from_env()?;
+        Ok(Self::new(store.sessions_root()))
+    }
+
+    /// Scope for an output value: its `session_id` if that session exists.
+    pub fn scope_for(&self, value: &Value) -> String {
+        value
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .and_then(SessionId::parse)
+            .filter(|sid| self.sessions_root.join(&sid.0).is_dir())
+            .map(|sid| sid.0)
+            .unwrap_or_else(|| GLOBAL_SCOPE.to_string())
+    }
+
+    fn cursor_path(&self, scope: &str, cursor_id: &str) -> PathBuf {
+        let dir = if scope == GLOBAL_SCOPE {
+            self.sessions_root
+                .parent()
+                .unwrap_or(&self.sessions_root)
+                .join(PAGINATION_DIR)
+        } else {
+            self.sessions_root.join(scope).join(PAGINATION_DIR)
+        };
+        dir.join(format!("{}.json", cursor_id))
+    }
+
+    /// Persist the untruncated output behind a truncation token
+    /// (`<field>:<returned>:<total>` from [`truncate_to_tokens`]) and return
+    /// the resumable token for the next chunk.
+    pub fn persist_truncation(
+        &self,
+        untruncated: Value,
+        truncation_token: &str,
+        max_tokens: usize,
+        compact: bool,
+    ) -> Result<ContinuationToken, PaginationError> {
+        let invalid = || PaginationError::InvalidToken {
+            token: truncation_token.to_string(),
+            reason: "expected <field>:<returned>:<total>".to_string(),
+        };
+        let mut parts = truncation_token.split(':');
+        let field = parts.next().filter(|f| !f.is_empty()).ok_or_else(invalid)?;
+        let returned: usize = parts
+            .next()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(invalid)?;
+
+        let scope = self.scope_for(&untruncated);
+        let cursor = PageCursor::new(&scope, field, max_tokens, compact, untruncated);
+        let token = cursor.token_at(returned).ok_or_else(invalid)?;
+        self.save(&cursor)?;
+        Ok(token)
+    }
+
+    pub fn save(&self, cursor: &PageCursor) -> Result<(), PaginationError> {
+        let path = self.cursor_path(&cursor.scope, &cursor.cursor_id);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| PaginationError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        let content = serde_json::to_vec(cursor).map_err(|e| PaginationError::Json {
+            path: path.clone(),
+            source: e,
+        })?;
+        write_atomic(&path, &content)
+    }
+
+    pub fn load(&self, token: &ContinuationToken) -> Result<PageCursor, PaginationError> {
+        let path = self.cursor_path(&token.scope, &token.cursor_id);
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(PaginationError::NotFound {
+                    token: token.to_string(),
+                })
+            }
+            Err(e) => return Err(PaginationError::Io { path, source: e }),
+        };
+        let cursor: PageCursor =
+            serde_json::from_slice(&content).map_err(|e| PaginationError::Json {
+                path: path.clone(),
+                source: e,
+            })?;
+        if cursor.field != token.field || cursor.total != token.total {
+            return Err(PaginationError::Mismatch {
+                token: token.to_string(),
+            });
+        }
+        Ok(cursor)
+    }
+}
+
+fn write_atomic(path: &Path, content: &[u8]) -> Result<(), PaginationError> {
+    let tmp = path.with_extension(format!("json.tmp.{}", std::process::id()));
+    std::fs::write(&tmp, content).map_err(|e| PaginationError::Io {
+        path: tmp.clone(),
+        source: e,
+    })?;
+    std::fs::rename(&tmp, path).map_err(|e| PaginationError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+/// Convenience for callers holding a full value: truncate, and if anything
+/// was dropped, persist a cursor and return its token.
+pub fn truncate_resumable(
+    store: &PaginationStore,
+    value: Value,
+    max_tokens: usize,
+    compact: bool,
+    estimator: &TokenEstimator,
+) -> Result<(Value, Option<ContinuationToken>), PaginationError> {
+    let result = truncate_to_tokens(value.clone(), max_tokens, estimator);
+    match result.continuation_token {
+        Some(ref truncation) if result.truncated => {
+            let token = store.persist_truncation(value, truncation, max_tokens, compact)?;
+            Ok((result.value, Some(token)))
+        }
+        _ => Ok((result.value, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "session_id": "pt-20260115-120000-page",
+            "candidates": (0..40)
+                .map(|i| json!({"pid": 1000 + i, "cmd": format!("worker-{:02}", i)}))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    #[test]
+    fn token_roundtrip() {
+        let token = ContinuationToken {
+            scope: GLOBAL_SCOPE.to_string(),
+            cursor_id: "00ff12ab".to_string(),
+            field: "candidates".to_string(),
+            offset: 5,
+            total: 40,
+        };
+        assert_eq!(ContinuationToken::parse(&token.to_string()).unwrap(), token);
+    }
+
+    #[test]
+    fn token_rejects_malformed_input() {
+        for raw in [
+            "candidates:5:40",
+            "global:../etc:candidates:5:40",
+            "nobody:00ff:candidates:5:40",
+            "global:00ff:candidates:40:40",
+        ] {
+            assert!(ContinuationToken::parse(raw).is_err(), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn pages_cover_every_item_once() {
+        let estimator = TokenEstimator::new();
+        let cursor = PageCursor::new(GLOBAL_SCOPE, "candidates", 120, false, sample());
+        let mut offset = 0;
+        let mut seen = Vec::new();
+        while let Some(token) = cursor.token_at(offset) {
+            let page = cursor.page(token.offset, 120, &estimator);
+            assert!(page.returned >= 1);
+            for item in page.value["candidates"].as_array().unwrap() {
+                seen.push(item["pid"].as_u64().unwrap());
+            }
+            offset += page.returned;
+        }
+        assert_eq!(seen, (1000..1040).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn persisted_cursor_is_deterministic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sessions = tmp.path().join("sessions");
+        std::fs::create_dir_all(sessions.join("pt-20260115-120000-page")).unwrap();
+        let store = PaginationStore::new(&sessions);
+        let estimator = TokenEstimator::new();
+
+        let (first, token) = truncate_resumable(&store, sample(), 120, false, &estimator).unwrap();
+        let token = token.expect("sample should truncate");
+        assert_eq!(token.scope, "pt-20260115-120000-page");
+        assert!(sessions
+            .join("pt-20260115-120000-page")
+            .join(PAGINATION_DIR)
+            .join(format!("{}.json", token.cursor_id))
+            .exists());
+
+        let cursor = store.load(&token).unwrap();
+        let a = cursor.page(token.offset, 120, &estimator);
+        let b = store
+            .load(&token)
+            .unwrap()
+            .page(token.offset, 120, &estimator);
+        assert_eq!(a.value, b.value);
+        let first_len = first["candidates"].as_array().unwrap().len();
+        assert_eq!(a.value["candidates"][0]["pid"], 1000 + first_len as u64);
+    }
+
+    #[test]
+    fn sessionless_output_uses_global_scope() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PaginationStore::new(tmp.path().join("sessions"));
+        let mut value = sample();
+        value.as_object_mut().unwrap().remove("session_id");
+        let token = store
+            .persist_truncation(value, "candidates:3:40", 120, true)
+            .unwrap();
+        assert_eq!(token.scope, GLOBAL_SCOPE);
+        assert_eq!(token.offset, 3);
+        assert!(store.load(&token).unwrap().compact);
+    }
+
+    #[test]
+    fn missing_cursor_is_not_found() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = PaginationStore::new(tmp.path());
+        let token = ContinuationToken::parse("global:abcdef:candidates:1:2").unwrap();
+        assert!(matches!(
+            store.load(&token),
+            Err(PaginationError::NotFound { .. })
+        ));
+    }
+}
@@ -68,6 +68,12 @@ pub struct MemoryPrediction {
     pub confidence: f64,
     /// Observation window in seconds.
     pub window_secs: f64,
+    /// Proportional set size, when smaps_rollup was read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pss_bytes: Option<u64>,
+    /// Unique (private) set size, when smaps_rollup was read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uss_bytes: Option<u64>,
 }
 
 /// CPU trend prediction.
@@ -231,6 +237,8 @@ mod tests {
                 trend: Trend::Falling,
                 confidence: 0.85,
                 window_secs: 3600.0,
+                pss_bytes: Some(48 * 1024 * 1024),
+                uss_bytes: Some(32 * 1024 * 1024),
             }),
             cpu: Some(CpuPrediction {
                 usage_slope_pct_per_sec: -0.001,
@@ -279,6 +287,8 @@ mod tests {
                 trend: Trend::Stable,
                 confidence: 0.5,
                 window_secs: 60.0,
+                pss_bytes: None,
+                uss_bytes: None,
             }),
             ..Default::default()
         };
@@ -286,6 +296,7 @@ mod tests {
         assert!(json.contains("memory"));
         assert!(!json.contains("cpu"));
         assert!(!json.contains("trajectory"));
+        assert!(!json.contains("pss_bytes"));
     }
 
     #[test]
@@ -0,0 +1,567 @@
+//! Typed builder for constructing plans programmatically.
+//!
+//! External agents that write plan JSON by hand get no feedback until apply
+//! or verify. [`PlanBuilder`] assembles the same [`Plan`] shape the planner
+//! emits, fills in the planner's pre-checks, stage barriers and kill
+//! escalation from the policy, and [`validate_plan`] rejects plans that
+//! verify or apply could not act on safely.
+//!
+//! ```ignore
+//! let plan = PlanBuilder::new(session_id, &policy)
+//!     .with_action(Action::Kill, identity)
+//!     .with_spec(ActionSpec::new(Action::Throttle, other).with_blocked(true))
+//!     .build()?;
+//! ```
+
+use super::{
+    action_id_for, action_tier, plan_id_for, pre_checks_for, stages_for, ActionConfidence,
+    ActionHook, ActionRationale, ActionRouting, ActionTimeouts, GatesSummary, Plan, PlanAction,
+    PreCheck, STAGE_KILL_AFTER_PAUSE, STAGE_PRIMARY,
+};
+use crate::config::Policy;
+use crate::decision::Action;
+use crate::verify::start_id_pid;
+use chrono::{DateTime, Utc};
+use pt_common::{IdentityQuality, ProcessIdentity, SessionId};
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Reasons a plan is rejected.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PlanBuildError {
+    #[error("invalid session id: {0}")]
+    InvalidSessionId(String),
+
+    #[error("invalid generated_at timestamp: {0}")]
+    InvalidTimestamp(String),
+
+    #[error("action {action_id}: invalid identity for pid {pid}: {reason}")]
+    InvalidIdentity {
+        action_id: String,
+        pid: u32,
+        reason: String,
+    },
+
+    #[error("action {0}: keep is not an executable action")]
+    NoOpAction(String),
+
+    #[error("action {0}: missing verify_identity pre-check")]
+    MissingIdentityCheck(String),
+
+    #[error("action {action_id}: pid {pid} is protected by policy")]
+    ProtectedTarget { action_id: String, pid: u32 },
+
+    #[error("duplicate action id {0}")]
+    DuplicateAction(String),
+
+    #[error("{count} kills exceed policy max_kills_per_run ({max})")]
+    KillLimitExceeded { count: usize, max: u32 },
+
+    #[error("action {0}: kill after pause has no earlier pause of the same process")]
+    MissingEscalationStep(String),
+
+    #[error("action {action_id}: stage {stage} is not declared in plan stages")]
+    UndeclaredStage { action_id: String, stage: u8 },
+}
+
+/// One action to add to a [`PlanBuilder`], with optional overrides of the
+/// policy-derived defaults.
+#[derive(Debug, Clone)]
+pub struct ActionSpec {
+    action: Action,
+    target: ProcessIdentity,
+    stage: u8,
+    timeouts: ActionTimeouts,
+    pre_checks: Option<Vec<PreCheck>>,
+    rationale: Option<ActionRationale>,
+    blocked: bool,
+}
+
+impl ActionSpec {
+    pub fn new(action: Action, target: ProcessIdentity) -> Self {
+        Self {
+            action,
+            target,
+            stage: STAGE_PRIMARY,
+            timeouts: ActionTimeouts::default(),
+            pre_checks: None,
+            rationale: None,
+            blocked: false,
+        }
+    }
+
+    pub fn with_stage(mut self, stage: u8) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    pub fn with_timeouts(mut self, timeouts: ActionTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Replace the default pre-checks for this action.
+    pub fn with_pre_checks(mut self, pre_checks: Vec<PreCheck>) -> Self {
+        self.pre_checks = Some(pre_checks);
+        self
+    }
+
+    pub fn with_rationale(mut self, rationale: ActionRationale) -> Self {
+        self.rationale = Some(rationale);
+        self
+    }
+
+    /// Include the action but leave it untoggled.
+    pub fn with_blocked(mut self, blocked: bool) -> Self {
+        self.blocked = blocked;
+        self
+    }
+
+    fn into_plan_action(self) -> PlanAction {
+        let pre_checks = self
+            .pre_checks
+            .unwrap_or_else(|| pre_checks_for(self.action));
+        PlanAction {
+            action_id: action_id_for(self.action, &self.target, self.stage),
+            target: self.target,
+            action: self.action,
+            order: 0,
+            stage: self.stage,
+            timeouts: self.timeouts,
+            pre_checks,
+            rationale: self.rationale.unwrap_or_else(empty_rationale),
+            on_success: vec![],
+            on_failure: vec![ActionHook {
+                action: "report_failure".to_string(),
+                details: None,
+            }],
+            blocked: self.blocked,
+            routing: ActionRouting::Direct,
+            confidence: ActionConfidence::Normal,
+            original_zombie_target: None,
+            d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
+        }
+    }
+}
+
+/// Builder for a [`Plan`] under a given policy.
+#[derive(Debug, Clone)]
+pub struct PlanBuilder {
+    session_id: SessionId,
+    policy: Policy,
+    generated_at: Option<String>,
+    escalate_kills: bool,
+    specs: Vec<ActionSpec>,
+}
+
+impl PlanBuilder {
+    /// Start a plan for `session_id`.
+    ///
+    /// Kills escalate through a pause stage by default whenever the policy
+    /// prices pausing a useful process; see [`PlanBuilder::with_kill_escalation`].
+    pub fn new(session_id: SessionId, policy: &Policy) -> Self {
+        Self {
+            session_id,
+            escalate_kills: policy.loss("useful", "pause").is_some(),
+            policy: policy.clone(),
+            generated_at: None,
+            specs: Vec::new(),
+        }
+    }
+
+    /// Fix the plan timestamp (RFC 3339); defaults to the build time.
+    pub fn with_generated_at(mut self, generated_at: impl Into<String>) -> Self {
+        self.generated_at = Some(generated_at.into());
+        self
+    }
+
+    /// Whether [`PlanBuilder::with_action`] turns a kill into pause-then-kill.
+    pub fn with_kill_escalation(mut self, enabled: bool) -> Self {
+        self.escalate_kills = enabled;
+        self
+    }
+
+    /// Add `action` on `target` with policy defaults.
+    pub fn with_action(mut self, action: Action, target: ProcessIdentity) -> Self {
+        if action == Action::Kill && self.escalate_kills {
+            self.specs
+                .push(ActionSpec::new(Action::Pause, target.clone()).with_stage(STAGE_PRIMARY));
+            self.specs
+                .push(ActionSpec::new(Action::Kill, target).with_stage(STAGE_KILL_AFTER_PAUSE));
+        } else {
+            self.specs.push(ActionSpec::new(action, target));
+        }
+        self
+    }
+
+    /// Add a fully specified action; no escalation is applied.
+    pub fn with_spec(mut self, spec: ActionSpec) -> Self {
+        self.specs.push(spec);
+        self
+    }
+
+    /// Check the plan that [`PlanBuilder::build`] would produce.
+    pub fn validate(&self) -> Result<(), PlanBuildError> {
+        validate_plan(&self.assemble(), &self.policy)
+    }
+
+    /// Assemble and validate the plan.
+    pub fn build(self) -> Result<Plan, PlanBuildError> {
+        let plan = self.assemble();
+        validate_plan(&plan, &self.policy)?;
+        Ok(plan)
+    }
+
+    fn assemble(&self) -> Plan {
+        let mut actions: Vec<PlanAction> = self
+            .specs
+            .iter()
+            .cloned()
+            .map(ActionSpec::into_plan_action)
+            .collect();
+
+        actions.sort_by(|a, b| {
+            let key = |action: &PlanAction| {
+                (
+                    action_tier(action.action),
+                    action.target.pgid.unwrap_or(action.target.pid.0),
+                    action.stage,
+                    action.target.pid.0,
+                    action.action_id.clone(),
+                )
+            };
+            key(a).cmp(&key(b))
+        });
+        for (idx, action) in actions.iter_mut().enumerate() {
+            action.order = idx as u32;
+        }
+
+        let pre_toggled: Vec<String> = actions
+            .iter()
+            .filter(|a| !a.blocked)
+            .map(|a| a.action_id.clone())
+            .collect();
+        let targets: HashSet<u32> = actions.iter().map(|a| a.target.pid.0).collect();
+        let blocked_targets: HashSet<u32> = actions
+            .iter()
+            .filter(|a| a.blocked)
+            .map(|a| a.target.pid.0)
+            .collect();
+
+        Plan {
+            plan_id: plan_id_for(
+                &self.session_id,
+                self.policy.policy_id.as_deref(),
+                actions.len(),
+            ),
+            session_id: self.session_id.0.clone(),
+            generated_at: self
+                .generated_at
+                .clone()
+                .unwrap_or_else(|| Utc::now().to_rfc3339()),
+            policy_id: self.policy.policy_id.clone(),
+            policy_version: self.policy.schema_version.clone(),
+            stages: stages_for(&actions, &self.policy),
+            gates_summary: GatesSummary {
+                total_candidates: targets.len(),
+                blocked_candidates: blocked_targets.len(),
+                pre_toggled_actions: pre_toggled.len(),
+            },
+            pre_toggled,
+            actions,
+        }
+    }
+}
+
+/// Check a plan against what apply and verify rely on.
+///
+/// These are the inputs [`crate::verify::verify_plan`] depends on (a parseable
+/// session id and timestamp, start_ids that carry the PID so reuse can be
+/// detected) plus the policy guardrails that apply enforces.
+pub fn validate_plan(plan: &Plan, policy: &Policy) -> Result<(), PlanBuildError> {
+    if SessionId::parse(&plan.session_id).is_none() {
+        return Err(PlanBuildError::InvalidSessionId(plan.session_id.clone()));
+    }
+    if DateTime::parse_from_rfc3339(&plan.generated_at).is_err() {
+        return Err(PlanBuildError::InvalidTimestamp(plan.generated_at.clone()));
+    }
+
+    let mut seen = HashSet::new();
+    for action in &plan.actions {
+        let id = &action.action_id;
+        if !seen.insert(id.as_str()) {
+            return Err(PlanBuildError::DuplicateAction(id.clone()));
+        }
+        if action.action == Action::Keep {
+            return Err(PlanBuildError::NoOpAction(id.clone()));
+        }
+        validate_identity(id, action.action, &action.target)?;
+        if !action.pre_checks.contains(&PreCheck::VerifyIdentity) {
+            return Err(PlanBuildError::MissingIdentityCheck(id.clone()));
+        }
+        if matches!(action.action, Action::Kill | Action::Restart)
+            && policy
+                .guardrails
+                .never_kill_pid
+                .contains(&action.target.pid.0)
+        {
+            return Err(PlanBuildError::ProtectedTarget {
+                action_id: id.clone(),
+                pid: action.target.pid.0,
+            });
+        }
+        if !plan.stages.is_empty() && !plan.stages.iter().any(|s| s.stage == action.stage) {
+            return Err(PlanBuildError::UndeclaredStage {
+                action_id: id.clone(),
+                stage: action.stage,
+            });
+        }
+        if action.action == Action::Kill
+            && action.stage == STAGE_KILL_AFTER_PAUSE
+            && !plan.actions.iter().any(|other| {
+                other.action == Action::Pause
+                    && other.target.pid == action.target.pid
+                    && other.stage < action.stage
+            })
+        {
+            return Err(PlanBuildError::MissingEscalationStep(id.clone()));
+        }
+    }
+
+    let kills = plan
+        .actions
+        .iter()
+        .filter(|a| a.action == Action::Kill && !a.blocked)
+        .count();
+    let max = policy.guardrails.max_kills_per_run;
+    if kills > max as usize {
+        return Err(PlanBuildError::KillLimitExceeded { count: kills, max });
+    }
+
+    Ok(())
+}
+
+fn validate_identity(
+    action_id: &str,
+    action: Action,
+    target: &ProcessIdentity,
+) -> Result<(), PlanBuildError> {
+    let pid = target.pid.0;
+    let invalid = |reason: &str| PlanBuildError::InvalidIdentity {
+        action_id: action_id.to_string(),
+        pid,
+        reason: reason.to_string(),
+    };
+
+    if pid == 0 {
+        return Err(invalid("pid must be non-zero"));
+    }
+    match start_id_pid(&target.start_id.0) {
+        None => return Err(invalid("start_id is not in a recognized format")),
+        Some(start_pid) if start_pid != pid => {
+            return Err(invalid("start_id refers to a different pid"))
+        }
+        Some(_) => {}
+    }
+    if matches!(action, Action::Kill | Action::Restart)
+        && target.quality == IdentityQuality::PidOnly
+    {
+        return Err(invalid(
+            "pid-only identity cannot be revalidated before a kill",
+        ));
+    }
+    Ok(())
+}
+
+fn empty_rationale() -> ActionRationale {
+    ActionRationale {
+        expected_loss: None,
+        expected_recovery: None,
+        expected_recovery_stddev: None,
+        posterior_odds_abandoned_vs_useful: None,
+        sprt_boundary: None,
+        posterior: None,
+        memory_mb: None,
+        has_known_signature: None,
+        category: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pt_common::StartId;
+
+    const SESSION: &str = "pt-20260115-143022-a7xq";
+    const GENERATED_AT: &str = "2026-01-15T14:30:22Z";
+
+    fn identity(pid: u32) -> ProcessIdentity {
+        ProcessIdentity::new(pid, StartId(format!("boot:{pid}:{pid}")), 1000)
+    }
+
+    fn builder() -> PlanBuilder {
+        PlanBuilder::new(SessionId(SESSION.to_string()), &Policy::default())
+            .with_generated_at(GENERATED_AT)
+    }
+
+    #[test]
+    fn kill_escalates_through_pause_by_default() {
+        let plan = builder()
+            .with_action(Action::Kill, identity(42))
+            .build()
+            .expect("valid plan");
+
+        let actions: Vec<(Action, u8)> = plan.actions.iter().map(|a| (a.action, a.stage)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                (Action::Pause, STAGE_PRIMARY),
+                (Action::Kill, STAGE_KILL_AFTER_PAUSE)
+            ]
+        );
+        assert_eq!(plan.stages.len(), 2);
+        assert_eq!(plan.pre_toggled.len(), 2);
+        assert_eq!(plan.gates_summary.total_candidates, 1);
+        let kill = &plan.actions[1];
+        assert_eq!(kill.pre_checks, pre_checks_for(Action::Kill));
+    }
+
+    #[test]
+    fn escalation_can_be_disabled() {
+        let plan = builder()
+            .with_kill_escalation(false)
+            .with_action(Action::Kill, identity(42))
+            .build()
+            .expect("valid plan");
+        assert_eq!(plan.actions.len(), 1);
+        assert_eq!(plan.actions[0].stage, STAGE_PRIMARY);
+    }
+
+    #[test]
+    fn identity_start_id_must_match_pid() {
+        let mut target = identity(42);
+        target.start_id = StartId("boot:42:43".to_string());
+        let err = builder()
+            .with_action(Action::Pause, target)
+            .validate()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PlanBuildError::InvalidIdentity { pid: 42, .. }
+        ));
+
+        let mut target = identity(42);
+        target.start_id = StartId("not-a-start-id".to_string());
+        assert!(builder()
+            .with_action(Action::Pause, target)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn pid_only_identity_rejected_for_kill() {
+        let mut target = identity(42);
+        target.quality = IdentityQuality::PidOnly;
+        assert!(builder()
+            .with_action(Action::Pause, target.clone())
+            .validate()
+            .is_ok());
+        let err = builder()
+            .with_kill_escalation(false)
+            .with_action(Action::Kill, target)
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::InvalidIdentity { .. }));
+    }
+
+    #[test]
+    fn policy_guardrails_enforced() {
+        let mut policy = Policy::default();
+        policy.guardrails.never_kill_pid = vec![42];
+        policy.guardrails.max_kills_per_run = 1;
+        let builder = PlanBuilder::new(SessionId(SESSION.to_string()), &policy)
+            .with_generated_at(GENERATED_AT)
+            .with_kill_escalation(false);
+
+        let err = builder
+            .clone()
+            .with_action(Action::Kill, identity(42))
+            .validate()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PlanBuildError::ProtectedTarget { pid: 42, .. }
+        ));
+
+        let err = builder
+            .with_action(Action::Kill, identity(50))
+            .with_action(Action::Kill, identity(51))
+            .validate()
+            .unwrap_err();
+        assert_eq!(err, PlanBuildError::KillLimitExceeded { count: 2, max: 1 });
+    }
+
+    #[test]
+    fn spec_overrides_and_structure_checks() {
+        let err = builder()
+            .with_spec(
+                ActionSpec::new(Action::Throttle, identity(42))
+                    .with_pre_checks(vec![PreCheck::CheckNotProtected]),
+            )
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::MissingIdentityCheck(_)));
+
+        let err = builder()
+            .with_spec(
+                ActionSpec::new(Action::Kill, identity(42)).with_stage(STAGE_KILL_AFTER_PAUSE),
+            )
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::MissingEscalationStep(_)));
+
+        let err = builder()
+            .with_action(Action::Pause, identity(42))
+            .with_action(Action::Pause, identity(42))
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::DuplicateAction(_)));
+
+        let err = builder()
+            .with_action(Action::Keep, identity(42))
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::NoOpAction(_)));
+    }
+
+    #[test]
+    fn session_and_timestamp_validated() {
+        let err = PlanBuilder::new(SessionId("session".to_string()), &Policy::default())
+            .with_action(Action::Pause, identity(42))
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::InvalidSessionId(_)));
+
+        let err = builder()
+            .with_generated_at("yesterday")
+            .with_action(Action::Pause, identity(42))
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, PlanBuildError::InvalidTimestamp(_)));
+    }
+
+    #[test]
+    fn blocked_spec_not_pre_toggled() {
+        let plan = builder()
+            .with_action(Action::Pause, identity(42))
+            .with_spec(ActionSpec::new(Action::Throttle, identity(43)).with_blocked(true))
+            .build()
+            .expect("valid plan");
+        assert_eq!(plan.pre_toggled.len(), 1);
+        assert_eq!(plan.gates_summary.blocked_candidates, 1);
+        assert_eq!(plan.gates_summary.total_candidates, 2);
+    }
+}
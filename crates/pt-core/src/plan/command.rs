@@ -0,0 +1,338 @@
+//! Graceful shutdown through a process's own CLI.
+//!
+//! Some processes are best stopped by asking their tool to do it: `gradle
+//! --stop` lets a daemon finish its registry bookkeeping, `bazel shutdown`
+//! releases the output base lock, `sccache --stop-server` flushes its stats.
+//! A signature may carry a [`GracefulStopTemplate`]; when a kill targets a
+//! matched process, the planner renders it into a [`CommandAction`] that
+//! `agent apply` runs (argv only, no shell) before falling back to signals.
+//!
+//! Templates may use these placeholders, each expanding to a single value:
+//!
+//! | Placeholder   | Value                                                 |
+//! |---------------|-------------------------------------------------------|
+//! | `{pid}`       | target PID                                            |
+//! | `{cwd}`       | target's working directory                            |
+//! | `{flag:NAME}` | value of `--NAME=VALUE` or `--NAME VALUE` in its args |
+//!
+//! A placeholder that cannot be resolved, or whose value is empty or starts
+//! with `-`, leaves the kill as a plain signal.
+
+use crate::decision::Action;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Default time allowed for a graceful stop command to return.
+pub const DEFAULT_GRACEFUL_STOP_TIMEOUT_SECS: u64 = 30;
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_GRACEFUL_STOP_TIMEOUT_SECS
+}
+
+fn is_default_timeout_secs(secs: &u64) -> bool {
+    *secs == DEFAULT_GRACEFUL_STOP_TIMEOUT_SECS
+}
+
+/// Command template in a signature's `graceful_stop`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GracefulStopTemplate {
+    /// Program and arguments; `argv[0]` is looked up in `PATH`.
+    pub argv: Vec<String>,
+    /// Working directory for the command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Time allowed for the command to return.
+    #[serde(
+        default = "default_timeout_secs",
+        skip_serializing_if = "is_default_timeout_secs"
+    )]
+    pub timeout_seconds: u64,
+}
+
+impl GracefulStopTemplate {
+    pub fn new(argv: Vec<&str>) -> Self {
+        Self {
+            argv: argv.into_iter().map(String::from).collect(),
+            cwd: None,
+            timeout_seconds: DEFAULT_GRACEFUL_STOP_TIMEOUT_SECS,
+        }
+    }
+
+    /// Run the command in `cwd`.
+    pub fn with_cwd(mut self, cwd: &str) -> Self {
+        self.cwd = Some(cwd.to_string());
+        self
+    }
+
+    /// Check that the template is non-empty and uses known placeholders.
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(program) = self.argv.first() else {
+            return Err("graceful_stop.argv cannot be empty".to_string());
+        };
+        if program.contains('{') {
+            return Err("graceful_stop.argv[0] cannot use placeholders".to_string());
+        }
+        if self.timeout_seconds == 0 {
+            return Err("graceful_stop.timeout_seconds must be positive".to_string());
+        }
+        for part in self.argv.iter().chain(self.cwd.iter()) {
+            check_placeholders(part)?;
+        }
+        Ok(())
+    }
+}
+
+/// What a template is rendered against.
+#[derive(Debug, Clone, Copy)]
+pub struct GracefulStopContext<'a> {
+    pub pid: u32,
+    /// Full command line of the target.
+    pub cmdline: &'a str,
+    pub cwd: Option<&'a str>,
+}
+
+impl GracefulStopContext<'_> {
+    fn value(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "pid" => Some(self.pid.to_string()),
+            "cwd" => self.cwd.map(String::from),
+            _ => flag_value(self.cmdline, placeholder.strip_prefix("flag:")?),
+        }
+    }
+}
+
+/// A command run in place of signalling the target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CommandAction {
+    /// Signature the command came from.
+    pub signature: String,
+    /// Program and arguments, passed as-is (no shell).
+    pub argv: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Time allowed for the command to return.
+    pub timeout_ms: u64,
+}
+
+impl CommandAction {
+    /// The command as a user would type it, for review.
+    pub fn display_command(&self) -> String {
+        let command = self
+            .argv
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        match &self.cwd {
+            Some(cwd) => format!("cd {} && {}", quote(cwd), command),
+            None => command,
+        }
+    }
+}
+
+/// Graceful stop that replaces `action`, if the template renders.
+///
+/// Only kills qualify; other actions keep their own mechanism.
+pub fn command_action_for(
+    signature: &str,
+    template: &GracefulStopTemplate,
+    ctx: &GracefulStopContext,
+    action: Action,
+) -> Option<CommandAction> {
+    if action != Action::Kill || template.validate().is_err() {
+        return None;
+    }
+    let argv = template
+        .argv
+        .iter()
+        .map(|arg| expand(arg, ctx))
+        .collect::<Option<Vec<_>>>()?;
+    let cwd = match &template.cwd {
+        Some(cwd) => Some(expand(cwd, ctx)?),
+        None => None,
+    };
+    Some(CommandAction {
+        signature: signature.to_string(),
+        argv,
+        cwd,
+        timeout_ms: template.timeout_seconds.saturating_mul(1000),
+    })
+}
+
+/// Check that every placeholder in `template` is closed and known.
+fn check_placeholders(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed placeholder in {:?}", template))?;
+        let name = &rest[start + 1..start + end];
+        let known = matches!(name, "pid" | "cwd")
+            || name.strip_prefix("flag:").is_some_and(|flag| {
+                !flag.is_empty()
+                    && flag
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            });
+        if !known {
+            return Err(format!(
+                "unknown placeholder {{{}}} in {:?}",
+                name, template
+            ));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+/// Expand the placeholders of `template`.
+fn expand(template: &str, ctx: &GracefulStopContext) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let value = ctx.value(&rest[start + 1..end])?;
+        // A value must not turn into an option of the command.
+        if value.is_empty() || value.starts_with('-') {
+            return None;
+        }
+        out.push_str(&rest[..start]);
+        out.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// Value of `--name=VALUE` or `--name VALUE` in `cmdline`.
+fn flag_value(cmdline: &str, name: &str) -> Option<String> {
+    let flag = format!("--{}", name);
+    let mut args = cmdline.split_whitespace();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next().map(String::from);
+        }
+        if let Some(value) = arg.strip_prefix(&flag).and_then(|v| v.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+fn quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAZEL: &str = "bazel(app) -XX:+UseParallelGC -jar /home/u/.cache/bazel/install/A-server.jar --output_base=/home/u/.cache/bazel/_bazel_u/abc --workspace_directory=/src/app";
+
+    fn ctx(cmdline: &str) -> GracefulStopContext<'_> {
+        GracefulStopContext {
+            pid: 4242,
+            cmdline,
+            cwd: Some("/src/app"),
+        }
+    }
+
+    #[test]
+    fn renders_flags_and_cwd() {
+        let template = GracefulStopTemplate::new(vec![
+            "bazel",
+            "--output_base={flag:output_base}",
+            "shutdown",
+        ])
+        .with_cwd("{cwd}");
+        let action =
+            command_action_for("bazel-server", &template, &ctx(BAZEL), Action::Kill).unwrap();
+        assert_eq!(
+            action.argv,
+            vec![
+                "bazel",
+                "--output_base=/home/u/.cache/bazel/_bazel_u/abc",
+                "shutdown"
+            ]
+        );
+        assert_eq!(action.cwd.as_deref(), Some("/src/app"));
+        assert_eq!(action.timeout_ms, 30_000);
+        assert_eq!(
+            action.display_command(),
+            "cd /src/app && bazel --output_base=/home/u/.cache/bazel/_bazel_u/abc shutdown"
+        );
+
+        let pid = GracefulStopTemplate::new(vec!["tool", "stop", "{pid}"]);
+        let action = command_action_for("tool", &pid, &ctx(""), Action::Kill).unwrap();
+        assert_eq!(action.argv, vec!["tool", "stop", "4242"]);
+        assert!(command_action_for("tool", &pid, &ctx(""), Action::Pause).is_none());
+    }
+
+    #[test]
+    fn unresolved_or_option_like_values_do_not_render() {
+        let template = GracefulStopTemplate::new(vec!["tool", "{flag:socket}", "stop"]);
+        assert!(command_action_for("tool", &template, &ctx("tool serve"), Action::Kill).is_none());
+        assert!(command_action_for(
+            "tool",
+            &template,
+            &ctx("tool serve --socket --rm"),
+            Action::Kill
+        )
+        .is_none());
+        let action = command_action_for(
+            "tool",
+            &template,
+            &ctx("tool serve --socket /run/t.sock"),
+            Action::Kill,
+        )
+        .unwrap();
+        assert_eq!(action.argv, vec!["tool", "/run/t.sock", "stop"]);
+    }
+
+    #[test]
+    fn validates_templates() {
+        assert!(GracefulStopTemplate::new(vec!["gradle", "--stop"])
+            .validate()
+            .is_ok());
+        assert!(GracefulStopTemplate::new(vec![]).validate().is_err());
+        assert!(GracefulStopTemplate::new(vec!["{cwd}/bin/tool"])
+            .validate()
+            .is_err());
+        assert!(GracefulStopTemplate::new(vec!["tool", "{home}"])
+            .validate()
+            .is_err());
+        assert!(GracefulStopTemplate::new(vec!["tool", "{pid"])
+            .validate()
+            .is_err());
+
+        let json = serde_json::json!({"argv": ["sccache", "--stop-server"]});
+        let template: GracefulStopTemplate = serde_json::from_value(json).unwrap();
+        assert_eq!(template.timeout_seconds, DEFAULT_GRACEFUL_STOP_TIMEOUT_SECS);
+        assert_eq!(
+            serde_json::to_value(&template).unwrap(),
+            serde_json::json!({"argv": ["sccache", "--stop-server"]})
+        );
+    }
+
+    #[test]
+    fn display_quotes_arguments() {
+        let action = CommandAction {
+            signature: "tool".to_string(),
+            argv: vec!["tool".to_string(), "it's here".to_string()],
+            cwd: Some("/src/my app".to_string()),
+            timeout_ms: 1_000,
+        };
+        assert_eq!(
+            action.display_command(),
+            r"cd '/src/my app' && tool 'it'\''s here'"
+        );
+    }
+}
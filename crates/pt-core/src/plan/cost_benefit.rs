@@ -0,0 +1,283 @@
+//! Per-action cost/benefit annotations.
+//!
+//! Each planned action carries a machine-readable estimate of what it would
+//! release (private memory, CPU, descriptors, ports) and what it risks
+//! (probability the process is useful, blast radius, likelihood a supervisor
+//! restarts it). Downstream agents can run their own portfolio selection from
+//! these numbers without re-deriving them from raw evidence.
+//!
+//! Resource estimates reuse the goal contribution model in
+//! [`crate::decision::goal_contribution`], so respawn and shared-memory
+//! discounts match what goal-oriented planning uses.
+
+use crate::decision::goal_contribution::{
+    estimate_cpu_contribution, estimate_fd_contribution, estimate_memory_contribution,
+    ContributionCandidate,
+};
+use crate::decision::Action;
+use crate::inference::ClassScores;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// CPU share left to a throttled process (matches the cgroup throttle default).
+const THROTTLED_CPU_FRAC: f64 = 0.25;
+/// Cores left to a quarantined process (matches the cpuset quarantine default).
+const QUARANTINED_CPU_FRAC: f64 = 1.0;
+/// Restart likelihood assumed for a process under a restarting supervisor
+/// (systemd service, container runtime, pm2, supervisord, ...).
+pub const SUPERVISED_RESTART_LIKELIHOOD: f64 = 0.9;
+
+/// Measured inputs for a candidate's annotation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImpactInputs {
+    pub rss_bytes: u64,
+    /// Private memory, when a deep scan measured it.
+    pub uss_bytes: Option<u64>,
+    /// CPU usage as a fraction of one core.
+    pub cpu_frac: f64,
+    /// Open descriptors, when known.
+    pub fd_count: Option<u32>,
+    /// Ports the process is listening on.
+    pub listen_ports: Vec<u16>,
+    pub child_count: usize,
+    pub has_shared_memory: bool,
+    /// Any supervisor detected (including human/agent supervision).
+    pub supervised: bool,
+    /// Probability a supervisor restarts the process after it exits (0.0-1.0).
+    pub restart_likelihood: f64,
+}
+
+/// Expected benefit and risk of one planned action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ActionCostBenefit {
+    pub benefit: ExpectedBenefit,
+    pub risk: ExpectedRisk,
+}
+
+/// Resources the action is expected to release durably.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ExpectedBenefit {
+    /// Expected private memory freed (USS, or discounted RSS when USS is unknown).
+    pub uss_freed_bytes: u64,
+    /// True when the memory figure is derived from RSS.
+    pub uss_estimated_from_rss: bool,
+    /// Expected CPU freed, as a fraction of one core.
+    pub cpu_freed: f64,
+    /// Expected descriptors released (unknown when fds were not collected).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fds_freed: Option<f64>,
+    /// Listening ports released, if the process stays down.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports_freed: Vec<u16>,
+    /// Confidence in the memory/CPU estimates (0.0-1.0).
+    pub confidence: f64,
+}
+
+/// What the action puts at risk.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ExpectedRisk {
+    /// Posterior probability the process is doing useful work
+    /// (`useful + useful_bad`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_useful: Option<f64>,
+    pub blast_radius: BlastRadius,
+    /// Probability a supervisor restarts the process after the action.
+    pub restart_likelihood: f64,
+    /// Whether the action can be undone (pause → resume, etc.).
+    pub reversible: bool,
+}
+
+/// Scope of collateral impact.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct BlastRadius {
+    pub child_count: usize,
+    pub listen_port_count: usize,
+    pub supervised: bool,
+    pub level: BlastLevel,
+}
+
+/// Coarse blast radius bucket.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlastLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// Annotate `action` on a process with the given measurements.
+///
+/// Only kill and restart end the process, so only they release memory, fds
+/// and ports; pause/freeze release CPU; throttle/quarantine release the CPU
+/// above their cap. Benefits of actions that end the process are discounted
+/// by the restart likelihood.
+pub fn annotate(
+    action: Action,
+    posterior: Option<&ClassScores>,
+    inputs: &ImpactInputs,
+) -> ActionCostBenefit {
+    let ends_process = matches!(action, Action::Kill | Action::Restart);
+    let restart_likelihood = match action {
+        // A restart brings the process back by design.
+        Action::Restart => 1.0,
+        Action::Kill => inputs.restart_likelihood.clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+
+    let candidate = ContributionCandidate {
+        pid: 0,
+        rss_bytes: inputs.rss_bytes,
+        uss_bytes: inputs.uss_bytes,
+        cpu_frac: inputs.cpu_frac.max(0.0),
+        fd_count: inputs.fd_count.unwrap_or(0),
+        bound_ports: inputs.listen_ports.clone(),
+        respawn_probability: restart_likelihood,
+        has_shared_memory: inputs.has_shared_memory,
+        child_count: inputs.child_count,
+    };
+
+    let memory = estimate_memory_contribution(&candidate);
+    let cpu = estimate_cpu_contribution(&candidate);
+
+    let cpu_freed = match action {
+        Action::Kill | Action::Restart => cpu.expected,
+        Action::Pause | Action::Freeze => candidate.cpu_frac,
+        Action::Throttle => (candidate.cpu_frac - THROTTLED_CPU_FRAC).max(0.0),
+        Action::Quarantine => (candidate.cpu_frac - QUARANTINED_CPU_FRAC).max(0.0),
+        _ => 0.0,
+    };
+
+    let benefit = ExpectedBenefit {
+        uss_freed_bytes: if ends_process {
+            memory.expected.max(0.0) as u64
+        } else {
+            0
+        },
+        uss_estimated_from_rss: inputs.uss_bytes.is_none(),
+        cpu_freed,
+        fds_freed: if ends_process {
+            inputs
+                .fd_count
+                .map(|_| estimate_fd_contribution(&candidate).expected)
+        } else {
+            None
+        },
+        ports_freed: if ends_process && restart_likelihood < 0.5 {
+            inputs.listen_ports.clone()
+        } else {
+            Vec::new()
+        },
+        confidence: if ends_process {
+            memory.confidence.min(cpu.confidence)
+        } else {
+            cpu.confidence
+        },
+    };
+
+    let risk = ExpectedRisk {
+        p_useful: posterior.map(|p| (p.useful + p.useful_bad).clamp(0.0, 1.0)),
+        blast_radius: BlastRadius {
+            child_count: inputs.child_count,
+            listen_port_count: inputs.listen_ports.len(),
+            supervised: inputs.supervised,
+            level: blast_level(inputs),
+        },
+        restart_likelihood,
+        reversible: !ends_process,
+    };
+
+    ActionCostBenefit { benefit, risk }
+}
+
+fn blast_level(inputs: &ImpactInputs) -> BlastLevel {
+    const GIB: u64 = 1024 * 1024 * 1024;
+    if inputs.child_count >= 10 || inputs.listen_ports.len() >= 3 {
+        BlastLevel::High
+    } else if inputs.child_count > 0
+        || !inputs.listen_ports.is_empty()
+        || inputs.supervised
+        || inputs.rss_bytes > GIB
+    {
+        BlastLevel::Medium
+    } else {
+        BlastLevel::Low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> ImpactInputs {
+        ImpactInputs {
+            rss_bytes: 512 * 1024 * 1024,
+            uss_bytes: Some(400 * 1024 * 1024),
+            cpu_frac: 0.8,
+            fd_count: Some(40),
+            listen_ports: vec![8080],
+            ..Default::default()
+        }
+    }
+
+    fn posterior() -> ClassScores {
+        ClassScores {
+            useful: 0.1,
+            useful_bad: 0.05,
+            abandoned: 0.8,
+            zombie: 0.05,
+        }
+    }
+
+    #[test]
+    fn kill_releases_everything() {
+        let cb = annotate(Action::Kill, Some(&posterior()), &inputs());
+        assert_eq!(cb.benefit.uss_freed_bytes, 400 * 1024 * 1024);
+        assert!(!cb.benefit.uss_estimated_from_rss);
+        assert!((cb.benefit.cpu_freed - 0.8).abs() < 1e-9);
+        assert_eq!(cb.benefit.fds_freed, Some(40.0));
+        assert_eq!(cb.benefit.ports_freed, vec![8080]);
+        assert!((cb.risk.p_useful.unwrap() - 0.15).abs() < 1e-9);
+        assert!(!cb.risk.reversible);
+        assert_eq!(cb.risk.blast_radius.level, BlastLevel::Medium);
+    }
+
+    #[test]
+    fn supervised_restart_discounts_kill_benefit() {
+        let supervised = ImpactInputs {
+            supervised: true,
+            restart_likelihood: SUPERVISED_RESTART_LIKELIHOOD,
+            ..inputs()
+        };
+        let cb = annotate(Action::Kill, None, &supervised);
+        assert!(cb.benefit.uss_freed_bytes < 50 * 1024 * 1024);
+        assert!(cb.benefit.ports_freed.is_empty());
+        assert_eq!(cb.risk.restart_likelihood, SUPERVISED_RESTART_LIKELIHOOD);
+        assert!(cb.risk.p_useful.is_none());
+    }
+
+    #[test]
+    fn pause_frees_cpu_only() {
+        let cb = annotate(Action::Pause, Some(&posterior()), &inputs());
+        assert_eq!(cb.benefit.uss_freed_bytes, 0);
+        assert!((cb.benefit.cpu_freed - 0.8).abs() < 1e-9);
+        assert!(cb.benefit.fds_freed.is_none());
+        assert!(cb.risk.reversible);
+        assert_eq!(cb.risk.restart_likelihood, 0.0);
+    }
+
+    #[test]
+    fn throttle_frees_cpu_above_cap() {
+        let cb = annotate(Action::Throttle, None, &inputs());
+        assert!((cb.benefit.cpu_freed - 0.55).abs() < 1e-9);
+    }
+
+    #[test]
+    fn blast_level_scales_with_dependents() {
+        let busy = ImpactInputs {
+            child_count: 12,
+            ..inputs()
+        };
+        assert_eq!(blast_level(&busy), BlastLevel::High);
+        assert_eq!(blast_level(&ImpactInputs::default()), BlastLevel::Low);
+    }
+}
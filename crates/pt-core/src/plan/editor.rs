@@ -0,0 +1,369 @@
+//! Rebase-style plan editing in `$EDITOR`.
+//!
+//! `agent plan --edit` writes the candidate list to a temporary file, one
+//! command per candidate, in the spirit of `git rebase -i`:
+//!
+//! ```text
+//! pick 4242 node  # KILL score=87 P(abandoned)=0.92 age=3d 2h mem=512MB
+//! #   cmd: node server.js --watch
+//! #   evidence: age_elapsed (+12), cpu_idle (+8)
+//! ```
+//!
+//! - `pick` (`p`) keeps the candidate with its recommended action.
+//! - `review` (`r`) keeps it, but for manual review only (never killed).
+//! - `drop` (`d`) removes it from the plan. Deleting the line does the same.
+//!
+//! Order is ignored. A file with no commands left aborts the edit, so the
+//! plan is unchanged.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Longest command line shown in a candidate comment.
+const MAX_CMD_CHARS: usize = 120;
+
+/// Evidence factors shown per candidate.
+const MAX_EVIDENCE: usize = 3;
+
+/// Errors from an edit session.
+#[derive(Debug, Error)]
+pub enum PlanEditError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("editor '{editor}' failed: {reason}")]
+    Editor { editor: String, reason: String },
+
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("edit aborted: no commands left in the plan file")]
+    Aborted,
+}
+
+/// A per-candidate command in the edit file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    Pick,
+    Review,
+    Drop,
+}
+
+impl EditCommand {
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "p" | "pick" => Some(Self::Pick),
+            "r" | "review" => Some(Self::Review),
+            "d" | "drop" => Some(Self::Drop),
+            _ => None,
+        }
+    }
+}
+
+/// One parsed line of the edit file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditLine {
+    pub pid: u32,
+    pub command: EditCommand,
+}
+
+/// What an edit changed, reported alongside the filtered plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PlanEdit {
+    /// Candidates downgraded to review.
+    pub reviewed: Vec<u32>,
+    /// Candidates removed from the plan.
+    pub dropped: Vec<u32>,
+}
+
+fn candidate_pid(candidate: &Value) -> Option<u32> {
+    candidate.get("pid")?.as_u64().map(|pid| pid as u32)
+}
+
+/// Render the edit file for `candidates`.
+pub fn render_edit_file(candidates: &[Value], session_id: &str) -> String {
+    let mut out = format!(
+        "# pt plan edit: {} candidate(s) in session {}\n#\n",
+        candidates.len(),
+        session_id
+    );
+    for candidate in candidates {
+        let Some(pid) = candidate_pid(candidate) else {
+            continue;
+        };
+        let str_field = |key: &str| candidate.get(key).and_then(Value::as_str).unwrap_or("?");
+        out.push_str(&format!(
+            "pick {} {}  # {} score={} P(abandoned)={:.2} age={} mem={}MB\n",
+            pid,
+            str_field("command_short"),
+            str_field("recommended_action").to_uppercase(),
+            candidate
+                .get("score")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0),
+            candidate
+                .pointer("/posterior/abandoned")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0),
+            str_field("age_human"),
+            candidate
+                .get("memory_mb")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+        ));
+
+        let cmd = str_field("command");
+        let cmd: String = if cmd.chars().count() > MAX_CMD_CHARS {
+            cmd.chars()
+                .take(MAX_CMD_CHARS)
+                .chain("...".chars())
+                .collect()
+        } else {
+            cmd.to_string()
+        };
+        out.push_str(&format!("#   cmd: {}\n", cmd.replace('\n', " ")));
+
+        let mut evidence: Vec<(&str, i64)> = candidate
+            .get("evidence")
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|e| {
+                        Some((e.get("factor")?.as_str()?, e.get("contribution")?.as_i64()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        evidence.sort_by_key(|(_, contribution)| std::cmp::Reverse(contribution.abs()));
+        if !evidence.is_empty() {
+            let shown: Vec<String> = evidence
+                .iter()
+                .take(MAX_EVIDENCE)
+                .map(|(factor, contribution)| format!("{} ({:+})", factor, contribution))
+                .collect();
+            out.push_str(&format!("#   evidence: {}\n", shown.join(", ")));
+        }
+        if let Some(rationale) = candidate.get("action_rationale").and_then(Value::as_str) {
+            out.push_str(&format!("#   rationale: {}\n", rationale));
+        }
+    }
+    out.push_str(
+        "\n\
+         # Commands:\n\
+         # p, pick <pid> = keep in the plan with its recommended action\n\
+         # r, review <pid> = keep in the plan for manual review only\n\
+         # d, drop <pid> = remove from the plan\n\
+         #\n\
+         # Removing a line drops the candidate. Order is ignored.\n\
+         # If you remove every line, the edit is aborted and the plan is unchanged.\n",
+    );
+    out
+}
+
+/// Parse an edited file. Every PID must be one of `known_pids`, at most once.
+pub fn parse_edit_file(
+    text: &str,
+    known_pids: &HashSet<u32>,
+) -> Result<Vec<EditLine>, PlanEditError> {
+    let mut lines = Vec::new();
+    let mut seen = HashSet::new();
+    for (idx, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parse_err = |message: String| PlanEditError::Parse {
+            line: idx + 1,
+            message,
+        };
+        let mut words = line.split_whitespace();
+        let word = words.next().unwrap_or_default();
+        let command = EditCommand::parse(word)
+            .ok_or_else(|| parse_err(format!("unknown command '{}'", word)))?;
+        let pid: u32 = words
+            .next()
+            .and_then(|pid| pid.parse().ok())
+            .ok_or_else(|| parse_err("expected a PID after the command".to_string()))?;
+        if !known_pids.contains(&pid) {
+            return Err(parse_err(format!("PID {} is not a plan candidate", pid)));
+        }
+        if !seen.insert(pid) {
+            return Err(parse_err(format!("PID {} listed more than once", pid)));
+        }
+        lines.push(EditLine { pid, command });
+    }
+    if lines.is_empty() {
+        return Err(PlanEditError::Aborted);
+    }
+    Ok(lines)
+}
+
+/// Apply parsed commands, keeping candidate order. Unlisted candidates are dropped.
+pub fn apply_edit(candidates: &mut Vec<Value>, lines: &[EditLine]) -> PlanEdit {
+    let mut edit = PlanEdit::default();
+    candidates.retain_mut(|candidate| {
+        let Some(pid) = candidate_pid(candidate) else {
+            return true;
+        };
+        match lines.iter().find(|l| l.pid == pid).map(|l| l.command) {
+            Some(EditCommand::Pick) => true,
+            Some(EditCommand::Review) => {
+                if let Some(obj) = candidate.as_object_mut() {
+                    obj.insert("recommended_action".to_string(), "review".into());
+                    obj.insert("recommendation".to_string(), "REVIEW".into());
+                }
+                edit.reviewed.push(pid);
+                true
+            }
+            Some(EditCommand::Drop) | None => {
+                edit.dropped.push(pid);
+                false
+            }
+        }
+    });
+    edit
+}
+
+/// Editor command: `$VISUAL`, then `$EDITOR`, then `vi`.
+pub fn resolve_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Run `editor` on `path`. The editor string may carry arguments (`code --wait`).
+fn run_editor(editor: &str, path: &Path) -> Result<(), PlanEditError> {
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| PlanEditError::Editor {
+            editor: editor.to_string(),
+            reason: e.to_string(),
+        })?;
+    if !status.success() {
+        return Err(PlanEditError::Editor {
+            editor: editor.to_string(),
+            reason: format!("exited with {}", status),
+        });
+    }
+    Ok(())
+}
+
+fn edit_file_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "pt-plan-edit-{}-{}.txt",
+        session_id,
+        std::process::id()
+    ))
+}
+
+/// Let the user edit `candidates` in their editor and apply the result.
+///
+/// On any error the candidates are left unchanged.
+pub fn edit_candidates(
+    candidates: &mut Vec<Value>,
+    session_id: &str,
+) -> Result<PlanEdit, PlanEditError> {
+    let known: HashSet<u32> = candidates.iter().filter_map(candidate_pid).collect();
+    let path = edit_file_path(session_id);
+    std::fs::write(&path, render_edit_file(candidates, session_id))?;
+
+    let result = run_editor(&resolve_editor(), &path)
+        .and_then(|()| std::fs::read_to_string(&path).map_err(PlanEditError::from))
+        .and_then(|text| parse_edit_file(&text, &known));
+    let _ = std::fs::remove_file(&path);
+    Ok(apply_edit(candidates, &result?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn candidates() -> Vec<Value> {
+        vec![
+            json!({
+                "pid": 100, "command_short": "node", "command": "node server.js",
+                "recommended_action": "kill", "recommendation": "KILL", "score": 87,
+                "posterior": {"abandoned": 0.92}, "age_human": "3d", "memory_mb": 512,
+                "evidence": [
+                    {"factor": "cpu_idle", "contribution": 8},
+                    {"factor": "age_elapsed", "contribution": 12},
+                ],
+            }),
+            json!({"pid": 200, "command_short": "vim", "recommended_action": "kill"}),
+            json!({"pid": 300, "command_short": "sleep", "recommended_action": "review"}),
+        ]
+    }
+
+    fn known() -> HashSet<u32> {
+        [100, 200, 300].into_iter().collect()
+    }
+
+    #[test]
+    fn rendered_file_round_trips_as_all_picks() {
+        let text = render_edit_file(&candidates(), "pt-test");
+        assert!(text.contains("pick 100 node  # KILL score=87 P(abandoned)=0.92"));
+        assert!(text.contains("#   evidence: age_elapsed (+12), cpu_idle (+8)"));
+
+        let lines = parse_edit_file(&text, &known()).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| l.command == EditCommand::Pick));
+
+        let mut cands = candidates();
+        assert_eq!(apply_edit(&mut cands, &lines), PlanEdit::default());
+        assert_eq!(cands.len(), 3);
+    }
+
+    #[test]
+    fn review_drop_and_removed_lines_apply() {
+        let text = "r 100 node\n# comment\npick 300\n";
+        let lines = parse_edit_file(text, &known()).unwrap();
+        let mut cands = candidates();
+        let edit = apply_edit(&mut cands, &lines);
+
+        assert_eq!(edit.reviewed, vec![100]);
+        assert_eq!(edit.dropped, vec![200]);
+        assert_eq!(cands.len(), 2);
+        assert_eq!(cands[0]["recommended_action"], "review");
+        assert_eq!(cands[0]["recommendation"], "REVIEW");
+        assert_eq!(cands[1]["pid"], 300);
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        let err = |text: &str| parse_edit_file(text, &known()).unwrap_err().to_string();
+        assert_eq!(err("kill 100"), "line 1: unknown command 'kill'");
+        assert_eq!(err("\npick"), "line 2: expected a PID after the command");
+        assert_eq!(err("pick 999"), "line 1: PID 999 is not a plan candidate");
+        assert_eq!(err("p 100\nd 100"), "line 2: PID 100 listed more than once");
+        assert!(matches!(
+            parse_edit_file("# nothing\n\n", &known()),
+            Err(PlanEditError::Aborted)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn editor_exit_status_is_checked() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("plan.txt");
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            run_editor("false", &path),
+            Err(PlanEditError::Editor { .. })
+        ));
+        assert!(run_editor("true", &path).is_ok());
+    }
+}
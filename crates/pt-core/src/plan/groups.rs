@@ -0,0 +1,397 @@
+//! Per-user, per-cgroup, per-container and per-workload aggregation of plan
+//! candidates.
+//!
+//! On shared hosts most stray processes belong to a handful of owners. Each
+//! candidate carries its [`ProcessGroups`] (user, cgroup path, container and,
+//! for Kubernetes pods, namespace and workload), and
+//! [`aggregate_groups`] sums the blast radius of every group. A
+//! [`GroupSelector`] such as `user:jenkins` picks a whole group, e.g. for
+//! `agent apply --group`.
+//!
+//! Cgroup selectors match the path or any cgroup below it, so
+//! `cgroup:/system.slice/jenkins.service` also covers its sub-cgroups.
+//! Workload keys are `<namespace>/<workload>`, e.g. `workload:shop/web`.
+
+use crate::collect::{KubernetesInfo, ProcessRecord};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Dimension along which candidates are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKind {
+    User,
+    Cgroup,
+    Container,
+    Namespace,
+    Workload,
+}
+
+impl GroupKind {
+    pub const ALL: [GroupKind; 5] = [
+        GroupKind::User,
+        GroupKind::Cgroup,
+        GroupKind::Container,
+        GroupKind::Namespace,
+        GroupKind::Workload,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GroupKind::User => "user",
+            GroupKind::Cgroup => "cgroup",
+            GroupKind::Container => "container",
+            GroupKind::Namespace => "namespace",
+            GroupKind::Workload => "workload",
+        }
+    }
+}
+
+impl fmt::Display for GroupKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Groups a process belongs to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessGroups {
+    pub user: String,
+    /// Cgroup v2 path, or a v1 path when no unified hierarchy is mounted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+    /// Short container ID, when the cgroup belongs to a container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Kubernetes namespace of the pod.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Owning Kubernetes workload, as `<namespace>/<workload>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
+}
+
+impl ProcessGroups {
+    /// Groups of a live process.
+    pub fn collect(proc: &ProcessRecord) -> Self {
+        #[cfg(target_os = "linux")]
+        let cgroup = crate::collect::parse_cgroup(proc.pid.0).and_then(|info| {
+            info.unified.or_else(|| {
+                ["pids", "memory", "cpu"]
+                    .iter()
+                    .find_map(|controller| info.v1_paths.get(*controller).cloned())
+            })
+        });
+        #[cfg(not(target_os = "linux"))]
+        let cgroup: Option<String> = None;
+        let groups = Self::new(&proc.user, cgroup);
+        match proc
+            .container_info
+            .as_ref()
+            .and_then(|info| info.kubernetes.as_ref())
+        {
+            Some(k8s) => groups.with_kubernetes(k8s),
+            None => groups,
+        }
+    }
+
+    /// Groups from a user name and cgroup path.
+    pub fn new(user: &str, cgroup: Option<String>) -> Self {
+        let container = cgroup
+            .as_deref()
+            .and_then(|path| crate::collect::detect_container_from_cgroup(path).container_id_short);
+        Self {
+            user: user.to_string(),
+            cgroup,
+            container,
+            namespace: None,
+            workload: None,
+        }
+    }
+
+    /// Add the namespace and workload of a Kubernetes pod.
+    pub fn with_kubernetes(mut self, k8s: &KubernetesInfo) -> Self {
+        self.namespace = k8s.namespace.clone();
+        self.workload = k8s
+            .namespace
+            .as_deref()
+            .zip(k8s.workload.as_deref())
+            .map(|(namespace, workload)| format!("{}/{}", namespace, workload));
+        self
+    }
+
+    /// The group key along `kind`, if any.
+    pub fn key(&self, kind: GroupKind) -> Option<&str> {
+        match kind {
+            GroupKind::User => Some(self.user.as_str()).filter(|user| !user.is_empty()),
+            GroupKind::Cgroup => self.cgroup.as_deref(),
+            GroupKind::Container => self.container.as_deref(),
+            GroupKind::Namespace => self.namespace.as_deref(),
+            GroupKind::Workload => self.workload.as_deref(),
+        }
+    }
+}
+
+/// A `kind:value` group selector, e.g. `user:jenkins`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupSelector {
+    pub kind: GroupKind,
+    pub value: String,
+}
+
+impl GroupSelector {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid group '{}': expected <kind>:<value>", s))?;
+        let kind = match kind.trim().to_ascii_lowercase().as_str() {
+            "user" => GroupKind::User,
+            "cgroup" => GroupKind::Cgroup,
+            "container" => GroupKind::Container,
+            "namespace" | "ns" => GroupKind::Namespace,
+            "workload" => GroupKind::Workload,
+            other => {
+                return Err(format!(
+                    "invalid group kind '{}': use user, cgroup, container, namespace or workload",
+                    other
+                ))
+            }
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(format!("invalid group '{}': empty value", s));
+        }
+        Ok(Self {
+            kind,
+            value: value.to_string(),
+        })
+    }
+
+    pub fn matches(&self, groups: &ProcessGroups) -> bool {
+        let Some(key) = groups.key(self.kind) else {
+            return false;
+        };
+        match self.kind {
+            GroupKind::Cgroup => {
+                let prefix = self.value.trim_end_matches('/');
+                key == prefix
+                    || key
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            }
+            _ => key == self.value,
+        }
+    }
+}
+
+impl fmt::Display for GroupSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind, self.value)
+    }
+}
+
+/// Aggregate blast radius of one group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupSummary {
+    pub kind: GroupKind,
+    pub key: String,
+    /// `kind:key`, usable as an `--group` selector.
+    pub selector: String,
+    pub candidate_count: usize,
+    pub kill_count: usize,
+    pub review_count: usize,
+    pub pids: Vec<u32>,
+    pub memory_mb: u64,
+    pub cpu_pct: f64,
+    pub child_count: u64,
+    /// Highest member risk level.
+    pub risk_level: String,
+}
+
+/// Group plan candidates by user, cgroup, container, namespace and workload.
+///
+/// Reads each candidate's `groups`, `pid`, `recommended_action` and
+/// `blast_radius`. Groups are ordered by kind, then by memory, largest first.
+pub fn aggregate_groups(candidates: &[Value]) -> Vec<GroupSummary> {
+    let mut by_key: BTreeMap<(GroupKind, String), GroupSummary> = BTreeMap::new();
+    for candidate in candidates {
+        let Some(groups) = candidate
+            .get("groups")
+            .and_then(|g| serde_json::from_value::<ProcessGroups>(g.clone()).ok())
+        else {
+            continue;
+        };
+        let pid = candidate["pid"].as_u64().unwrap_or(0) as u32;
+        let action = candidate["recommended_action"].as_str().unwrap_or("");
+        let blast = &candidate["blast_radius"];
+        let risk = blast["risk_level"].as_str().unwrap_or("low");
+
+        for kind in GroupKind::ALL {
+            let Some(key) = groups.key(kind) else {
+                continue;
+            };
+            let summary = by_key
+                .entry((kind, key.to_string()))
+                .or_insert_with(|| GroupSummary {
+                    kind,
+                    key: key.to_string(),
+                    selector: format!("{}:{}", kind, key),
+                    candidate_count: 0,
+                    kill_count: 0,
+                    review_count: 0,
+                    pids: Vec::new(),
+                    memory_mb: 0,
+                    cpu_pct: 0.0,
+                    child_count: 0,
+                    risk_level: "low".to_string(),
+                });
+            summary.candidate_count += 1;
+            match action {
+                "kill" => summary.kill_count += 1,
+                "keep" => {}
+                _ => summary.review_count += 1,
+            }
+            summary.pids.push(pid);
+            summary.memory_mb += blast["memory_mb"].as_u64().unwrap_or(0);
+            summary.cpu_pct += blast["cpu_pct"].as_f64().unwrap_or(0.0);
+            summary.child_count += blast["child_count"].as_u64().unwrap_or(0);
+            if risk_rank(risk) > risk_rank(&summary.risk_level) {
+                summary.risk_level = risk.to_string();
+            }
+        }
+    }
+
+    let mut groups: Vec<GroupSummary> = by_key.into_values().collect();
+    groups.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then(b.memory_mb.cmp(&a.memory_mb))
+            .then(a.key.cmp(&b.key))
+    });
+    groups
+}
+
+fn risk_rank(level: &str) -> u8 {
+    match level {
+        "low" => 0,
+        "medium" => 1,
+        "high" => 2,
+        "critical" => 3,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn candidate(pid: u32, user: &str, cgroup: Option<&str>, action: &str, mem: u64) -> Value {
+        json!({
+            "pid": pid,
+            "recommended_action": action,
+            "groups": ProcessGroups::new(user, cgroup.map(String::from)),
+            "blast_radius": {
+                "memory_mb": mem,
+                "cpu_pct": 1.5,
+                "child_count": 1,
+                "risk_level": if mem > 1024 { "medium" } else { "low" },
+            },
+        })
+    }
+
+    #[test]
+    fn parses_selectors() {
+        let sel = GroupSelector::parse("user:jenkins").unwrap();
+        assert_eq!(sel.kind, GroupKind::User);
+        assert_eq!(sel.value, "jenkins");
+        assert_eq!(sel.to_string(), "user:jenkins");
+        assert!(GroupSelector::parse("jenkins").is_err());
+        assert!(GroupSelector::parse("host:a").is_err());
+        assert!(GroupSelector::parse("user:").is_err());
+    }
+
+    #[test]
+    fn cgroup_selector_covers_subtree() {
+        let sel = GroupSelector::parse("cgroup:/system.slice/jenkins.service").unwrap();
+        let inside = ProcessGroups::new(
+            "jenkins",
+            Some("/system.slice/jenkins.service/build-42".to_string()),
+        );
+        let sibling = ProcessGroups::new(
+            "jenkins",
+            Some("/system.slice/jenkins.service-helper".to_string()),
+        );
+        assert!(sel.matches(&inside));
+        assert!(!sel.matches(&sibling));
+        assert!(!sel.matches(&ProcessGroups::new("jenkins", None)));
+    }
+
+    #[test]
+    fn detects_container_from_cgroup() {
+        let id = "4f5e6d7c8b9a0123456789abcdef0123456789abcdef0123456789abcdef0123";
+        let groups = ProcessGroups::new("root", Some(format!("/docker/{}", id)));
+        assert_eq!(groups.container.as_deref(), Some(&id[..12]));
+        let sel = GroupSelector::parse(&format!("container:{}", &id[..12])).unwrap();
+        assert!(sel.matches(&groups));
+    }
+
+    #[test]
+    fn groups_kubernetes_pods_by_namespace_and_workload() {
+        let k8s = KubernetesInfo {
+            namespace: Some("shop".to_string()),
+            pod_name: Some("web-7d4b9c8f6-x2x9k".to_string()),
+            workload: Some("web".to_string()),
+            ..Default::default()
+        };
+        let groups = ProcessGroups::new("root", None).with_kubernetes(&k8s);
+        assert_eq!(groups.key(GroupKind::Namespace), Some("shop"));
+        assert_eq!(groups.key(GroupKind::Workload), Some("shop/web"));
+        assert!(GroupSelector::parse("ns:shop").unwrap().matches(&groups));
+        assert!(GroupSelector::parse("workload:shop/web")
+            .unwrap()
+            .matches(&groups));
+        assert!(!GroupSelector::parse("workload:web")
+            .unwrap()
+            .matches(&groups));
+    }
+
+    #[test]
+    fn aggregates_blast_radius_per_group() {
+        let candidates = vec![
+            candidate(
+                1,
+                "jenkins",
+                Some("/system.slice/jenkins.service"),
+                "kill",
+                100,
+            ),
+            candidate(
+                2,
+                "jenkins",
+                Some("/system.slice/jenkins.service"),
+                "review",
+                2048,
+            ),
+            candidate(3, "alice", None, "kill", 50),
+        ];
+        let groups = aggregate_groups(&candidates);
+        let jenkins = groups
+            .iter()
+            .find(|g| g.selector == "user:jenkins")
+            .unwrap();
+        assert_eq!(jenkins.candidate_count, 2);
+        assert_eq!((jenkins.kill_count, jenkins.review_count), (1, 1));
+        assert_eq!(jenkins.memory_mb, 2148);
+        assert_eq!(jenkins.child_count, 2);
+        assert_eq!(jenkins.risk_level, "medium");
+        assert_eq!(jenkins.pids, vec![1, 2]);
+        // Users first, largest first; alice has no cgroup group.
+        assert_eq!(groups[0].selector, "user:jenkins");
+        assert_eq!(groups[1].selector, "user:alice");
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[2].kind, GroupKind::Cgroup);
+    }
+}
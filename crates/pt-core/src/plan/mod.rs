@@ -6,11 +6,80 @@
 //!
 //! ## Zombie (Z-state)
 //! Zombie processes are already dead and cannot be killed. The planner routes
-//! actions targeting zombies to their parent process or supervisor instead.
+//! actions targeting zombies to their parent process or supervisor instead;
+//! [`zombie`] decides whether the parent gets `SIGCHLD`, a unit restart, or
+//! nothing at all.
 //!
 //! ## D-state (Uninterruptible Sleep)
 //! D-state processes may ignore SIGKILL while waiting on kernel I/O. The planner
 //! marks any kill-like actions as low-confidence and surfaces diagnostics.
+//!
+//! # Stages
+//!
+//! Actions are grouped into ordered stages (`PlanAction::stage`). Each
+//! `PlanStage` carries barrier conditions that must hold before the next stage
+//! starts, e.g. every kill in the stage verified dead, or load back below a
+//! threshold:
+//! 1. `primary`: direct actions (and the pause half of pause-then-kill)
+//! 2. `kill_after_pause`: kills that follow a verified pause
+//! 3. `zombie_parents`: parent restarts to reap zombies, once children are gone
+//!
+//! # Cost/benefit
+//!
+//! Candidates that carry [`ImpactInputs`] get a per-action
+//! [`ActionCostBenefit`] annotation (see [`cost_benefit`]).
+//!
+//! # Programmatic plans
+//!
+//! [`PlanBuilder`] constructs plans directly from typed inputs, with the
+//! planner's policy defaults and [`validate_plan`] checks (see [`builder`]).
+//!
+//! # Editing
+//!
+//! [`editor`] lets users pick, review or drop candidates in `$EDITOR`
+//! (`agent plan --edit`), a keyboard-only alternative to the TUI.
+//!
+//! # Groups
+//!
+//! [`groups`] aggregates candidates per user, cgroup and container, and
+//! selects whole groups for `agent apply --group`.
+//!
+//! # Simulation
+//!
+//! [`simulate`] projects free memory, swap pressure, load and cgroup
+//! headroom after the kill set is applied (`agent plan --simulate`).
+//!
+//! # Process trees
+//!
+//! [`tree`] plans kills at the level of process groups or whole subtrees
+//! (policy `cascade`), and orders them bottom-up so parents die last.
+//!
+//! # systemd units
+//!
+//! Kills and restarts of a service's main process become `systemctl stop` /
+//! `restart` of the unit ([`unit`]), re-checked at apply time by
+//! [`PreCheck::VerifyUnitMapping`].
+//!
+//! # Graceful stop commands
+//!
+//! Kills of processes whose signature has a `graceful_stop` template run the
+//! tool's own shutdown command first ([`command`]), then fall back to signals.
+
+pub mod builder;
+pub mod command;
+pub mod cost_benefit;
+pub mod editor;
+pub mod groups;
+pub mod simulate;
+pub mod tree;
+pub mod unit;
+pub mod zombie;
+
+pub use builder::{validate_plan, ActionSpec, PlanBuildError, PlanBuilder};
+pub use command::{CommandAction, GracefulStopContext, GracefulStopTemplate};
+pub use cost_benefit::{ActionCostBenefit, ImpactInputs};
+pub use unit::{ServiceMembership, UnitAction, UnitCommand};
+pub use zombie::{ReapStrategy, ZombieParent, ZombieReap};
 
 use crate::collect::ProcessState;
 use crate::config::Policy;
@@ -43,6 +112,14 @@ pub struct DecisionCandidate {
     pub parent_identity: Option<ProcessIdentity>,
     /// D-state diagnostics if process is in uninterruptible sleep.
     pub d_state_diagnostics: Option<DStateDiagnostics>,
+    /// Measurements for cost/benefit annotation (None = not annotated).
+    pub impact: Option<ImpactInputs>,
+    /// systemd service the process runs in (None = not unit-managed).
+    pub systemd_unit: Option<ServiceMembership>,
+    /// How a zombie gets reaped (None = restart the parent).
+    pub zombie_reap: Option<ZombieReap>,
+    /// Signature shutdown command for a kill (None = signal only).
+    pub graceful_stop: Option<CommandAction>,
 }
 
 /// Diagnostics for D-state (uninterruptible sleep) processes.
@@ -69,6 +146,39 @@ pub struct Plan {
     pub actions: Vec<PlanAction>,
     pub pre_toggled: Vec<String>,
     pub gates_summary: GatesSummary,
+    /// Ordered stages with barriers (empty = each action stage runs unconditionally).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<PlanStage>,
+}
+
+/// Stage for direct actions and the pause half of pause-then-kill.
+pub const STAGE_PRIMARY: u8 = 0;
+/// Stage for kills that follow a pause.
+pub const STAGE_KILL_AFTER_PAUSE: u8 = 1;
+/// Stage for restarting zombie parents after their children are handled.
+pub const STAGE_ZOMBIE_PARENTS: u8 = 2;
+
+/// An ordered execution stage.
+///
+/// Actions whose `stage` matches run together; every barrier must hold
+/// before any later stage starts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PlanStage {
+    pub stage: u8,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub barriers: Vec<StageBarrier>,
+}
+
+/// Checkpoint condition evaluated after a stage completes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StageBarrier {
+    /// Every non-blocked action in the stage succeeded and passed verification.
+    AllVerified,
+    /// 1-minute load average per core is at or below `max` (waits up to the
+    /// executor's barrier timeout).
+    LoadPerCoreBelow { max: f64 },
 }
 
 /// High-level gate summary for the plan.
@@ -105,6 +215,15 @@ pub struct PlanAction {
     /// D-state diagnostics if targeting a D-state process.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub d_state_diagnostics: Option<DStateDiagnostics>,
+    /// Expected benefit and risk of this action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_benefit: Option<ActionCostBenefit>,
+    /// Unit operation to carry out instead of signalling the target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_action: Option<UnitAction>,
+    /// Shutdown command to run before signalling the target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_action: Option<CommandAction>,
 }
 
 fn is_direct_routing(routing: &ActionRouting) -> bool {
@@ -145,6 +264,15 @@ pub enum PreCheck {
     CheckAgentSupervision,
     /// Verify process is still in expected state (not zombie/D-state if expecting killable).
     VerifyProcessState,
+    /// Verify the cgroup freezer is usable and freezing the target's cgroup
+    /// would not stop processes outside its own tree.
+    CheckFreezeScope,
+    /// Verify the target is still the main process of the unit in
+    /// `unit_action`, under the same manager.
+    VerifyUnitMapping,
+    /// Verify the target is not the foreground job of a terminal that read
+    /// input recently.
+    CheckForegroundWork,
 }
 
 /// Why an action was routed differently than the direct target.
@@ -163,6 +291,14 @@ pub enum ActionRouting {
     ZombieInvestigateOnly,
     /// D-state: low confidence, may not succeed.
     DStateLowConfidence,
+    /// Tree: signal the whole process group; every member is a target.
+    ProcessGroup,
+    /// Tree: descendant of a targeted process, killed with it.
+    Cascade,
+    /// Main process of a systemd service: stopped or restarted through the unit.
+    SystemdUnit,
+    /// Stopped with its signature's shutdown command, signals as fallback.
+    GracefulCommand,
 }
 
 /// Confidence level for action success.
@@ -242,11 +378,15 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
         let is_d_state = candidate.process_state == Some(ProcessState::DiskSleep);
 
         let mut action_sequence = Vec::new();
-        if candidate.decision.optimal_action == Action::Kill && candidate.stage_pause_before_kill {
-            action_sequence.push((Action::Pause, 0));
-            action_sequence.push((Action::Kill, 1));
+        // A paused process cannot answer its own shutdown command.
+        if candidate.decision.optimal_action == Action::Kill
+            && candidate.stage_pause_before_kill
+            && candidate.graceful_stop.is_none()
+        {
+            action_sequence.push((Action::Pause, STAGE_PRIMARY));
+            action_sequence.push((Action::Kill, STAGE_KILL_AFTER_PAUSE));
         } else if candidate.decision.optimal_action != Action::Keep {
-            action_sequence.push((candidate.decision.optimal_action, 0));
+            action_sequence.push((candidate.decision.optimal_action, STAGE_PRIMARY));
         } else {
             continue;
         }
@@ -274,6 +414,22 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
                 category: candidate.decision.rationale.category.clone(),
             };
 
+            // A service's main process is stopped/restarted through its unit
+            let unit_action = if is_d_state {
+                None
+            } else {
+                candidate.systemd_unit.as_ref().and_then(|service| {
+                    unit::unit_action_for(service, candidate.identity.pid.0, action)
+                })
+            };
+
+            // Otherwise a kill may go through the tool's own shutdown command
+            let command_action = if is_d_state || unit_action.is_some() || action != Action::Kill {
+                None
+            } else {
+                candidate.graceful_stop.clone()
+            };
+
             // Determine confidence and routing for D-state
             let (confidence, routing, d_state_diag) = if is_d_state {
                 let confidence = if action == Action::Kill || action == Action::Restart {
@@ -286,11 +442,27 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
                     ActionRouting::DStateLowConfidence,
                     candidate.d_state_diagnostics.clone(),
                 )
+            } else if unit_action.is_some() {
+                (ActionConfidence::Normal, ActionRouting::SystemdUnit, None)
+            } else if command_action.is_some() {
+                (
+                    ActionConfidence::Normal,
+                    ActionRouting::GracefulCommand,
+                    None,
+                )
             } else {
                 (ActionConfidence::Normal, ActionRouting::Direct, None)
             };
 
             let mut pre_checks = pre_checks_for(action);
+            if unit_action.is_some() {
+                // Going through the unit is what the supervisor check asks for
+                for check in pre_checks.iter_mut() {
+                    if *check == PreCheck::CheckSupervisor {
+                        *check = PreCheck::VerifyUnitMapping;
+                    }
+                }
+            }
             // Add state verification only for actions likely to fail in D-state
             if is_d_state && matches!(action, Action::Kill | Action::Restart) {
                 pre_checks.push(PreCheck::VerifyProcessState);
@@ -319,6 +491,15 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
                 confidence,
                 original_zombie_target: None,
                 d_state_diagnostics: d_state_diag,
+                cost_benefit: candidate.impact.as_ref().map(|inputs| {
+                    cost_benefit::annotate(
+                        action,
+                        candidate.decision.rationale.posterior.as_ref(),
+                        inputs,
+                    )
+                }),
+                unit_action,
+                command_action,
             });
         }
     }
@@ -333,6 +514,8 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
         action.order = idx as u32;
     }
 
+    let stages = stages_for(&actions, &bundle.policy);
+
     let plan_id = plan_id_for(
         &bundle.session_id,
         bundle.policy.policy_id.as_deref(),
@@ -352,6 +535,48 @@ pub fn generate_plan(bundle: &DecisionBundle) -> Plan {
             blocked_candidates,
             pre_toggled_actions: pre_toggled.len(),
         },
+        stages,
+    }
+}
+
+/// Build the ordered stage list for the stages present in `actions`.
+///
+/// Every stage except the last gets an `AllVerified` barrier; when load-aware
+/// decisions are enabled, it also waits for load to drop below the policy's
+/// per-core threshold before moving on.
+fn stages_for(actions: &[PlanAction], policy: &Policy) -> Vec<PlanStage> {
+    let mut present: Vec<u8> = actions.iter().map(|a| a.stage).collect();
+    present.sort_unstable();
+    present.dedup();
+
+    let last = present.last().copied();
+    present
+        .into_iter()
+        .map(|stage| {
+            let mut barriers = Vec::new();
+            if Some(stage) != last {
+                barriers.push(StageBarrier::AllVerified);
+                if policy.load_aware.enabled {
+                    barriers.push(StageBarrier::LoadPerCoreBelow {
+                        max: policy.load_aware.load_per_core_high,
+                    });
+                }
+            }
+            PlanStage {
+                stage,
+                name: stage_name(stage).to_string(),
+                barriers,
+            }
+        })
+        .collect()
+}
+
+fn stage_name(stage: u8) -> &'static str {
+    match stage {
+        STAGE_PRIMARY => "primary",
+        STAGE_KILL_AFTER_PAUSE => "kill_after_pause",
+        STAGE_ZOMBIE_PARENTS => "zombie_parents",
+        _ => "custom",
     }
 }
 
@@ -392,51 +617,83 @@ fn plan_zombie_actions(candidate: &DecisionCandidate, blocked: bool) -> Option<V
     let mut actions = Vec::new();
 
     if is_destructive {
-        // Try to route to parent
-        if let Some(ref parent_identity) = candidate.parent_identity {
-            // Signal the parent to reap the zombie
-            // For Kill -> signal parent with SIGCHLD or restart it
-            // For Restart -> restart the parent
-            // Regardless of original action, restart parent to force zombie reap
+        // Try to route to parent, unless the reap assessment ruled it out
+        let reap = candidate.zombie_reap.as_ref();
+        let parent_identity = candidate
+            .parent_identity
+            .as_ref()
+            .filter(|_| reap.is_none_or(ZombieReap::actionable));
+        if let Some(parent_identity) = parent_identity {
+            // Regardless of original action, restart parent to force zombie reap.
+            // An assessed parent is restarted through its unit, or, when that is
+            // not safe, the restart is carried out as SIGCHLD (see `zombie`).
             let parent_action = Action::Restart;
-
-            let action_id = action_id_for(parent_action, parent_identity, 0);
+            let unit_action = reap.and_then(|r| r.unit_action.clone());
+            let signal_only = reap.is_some_and(|r| r.strategy == ReapStrategy::SignalParent);
+
+            let action_id = action_id_for(parent_action, parent_identity, STAGE_ZOMBIE_PARENTS);
+            let pre_checks = if signal_only {
+                // SIGCHLD is ignored by default; only make sure the same parent
+                // is still alive to receive it.
+                vec![PreCheck::VerifyIdentity, PreCheck::VerifyProcessState]
+            } else {
+                let mut checks = pre_checks_for(parent_action);
+                if unit_action.is_some() {
+                    for check in checks.iter_mut() {
+                        if *check == PreCheck::CheckSupervisor {
+                            *check = PreCheck::VerifyUnitMapping;
+                        }
+                    }
+                }
+                checks
+            };
+            let (success_detail, failure_detail) = if signal_only {
+                (
+                    format!(
+                        "SIGCHLD should prompt the parent to reap zombie PID {}",
+                        candidate.identity.pid.0
+                    ),
+                    "parent did not reap zombie after SIGCHLD".to_string(),
+                )
+            } else {
+                (
+                    format!(
+                        "parent restart should reap zombie PID {}",
+                        candidate.identity.pid.0
+                    ),
+                    "failed to restart parent of zombie".to_string(),
+                )
+            };
 
             actions.push(PlanAction {
                 action_id,
                 target: parent_identity.clone(),
                 action: parent_action,
                 order: 0,
-                stage: 0,
+                stage: STAGE_ZOMBIE_PARENTS,
                 timeouts: ActionTimeouts::default(),
-                pre_checks: vec![
-                    PreCheck::VerifyIdentity,
-                    PreCheck::CheckNotProtected,
-                    PreCheck::CheckSessionSafety,
-                    PreCheck::CheckDataLossGate,
-                    PreCheck::CheckSupervisor,
-                    PreCheck::CheckAgentSupervision,
-                ],
+                pre_checks,
                 rationale: base_rationale.clone(),
                 on_success: vec![ActionHook {
                     action: "zombie_reaped".to_string(),
-                    details: Some(format!(
-                        "parent restart should reap zombie PID {}",
-                        candidate.identity.pid.0
-                    )),
+                    details: Some(success_detail),
                 }],
                 on_failure: vec![ActionHook {
                     action: "report_failure".to_string(),
-                    details: Some("failed to restart parent of zombie".to_string()),
+                    details: Some(failure_detail),
                 }],
                 blocked,
                 routing: ActionRouting::ZombieToParent,
                 confidence: ActionConfidence::Normal,
                 original_zombie_target: Some(candidate.identity.clone()),
                 d_state_diagnostics: None,
+                cost_benefit: None,
+                unit_action,
+                command_action: None,
             });
         } else {
-            // No parent identity available - emit investigate-only action
+            // No parent identity available, or no safe step on the parent -
+            // emit investigate-only action
             // This is an informational action that doesn't execute anything
             // but makes it clear we can't help without more info
             let action_id = action_id_for(Action::Keep, &candidate.identity, 0);
@@ -457,6 +714,9 @@ fn plan_zombie_actions(candidate: &DecisionCandidate, blocked: bool) -> Option<V
                 confidence: ActionConfidence::VeryLow,
                 original_zombie_target: None,
                 d_state_diagnostics: None,
+                cost_benefit: None,
+                unit_action: None,
+                command_action: None,
             });
         }
     } else {
@@ -481,6 +741,9 @@ fn plan_zombie_actions(candidate: &DecisionCandidate, blocked: bool) -> Option<V
             confidence: ActionConfidence::VeryLow,
             original_zombie_target: None,
             d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
         });
     }
 
@@ -498,11 +761,16 @@ fn pre_checks_for(action: Action) -> Vec<PreCheck> {
             checks.push(PreCheck::CheckDataLossGate);
             checks.push(PreCheck::CheckSupervisor);
             checks.push(PreCheck::CheckAgentSupervision);
+            checks.push(PreCheck::CheckForegroundWork);
+        }
+        Action::Freeze => {
+            checks.push(PreCheck::CheckSupervisor);
+            checks.push(PreCheck::CheckAgentSupervision);
+            checks.push(PreCheck::CheckFreezeScope);
         }
         Action::Pause
         | Action::Throttle
         | Action::Renice
-        | Action::Freeze
         | Action::Unfreeze
         | Action::Quarantine => {
             checks.push(PreCheck::CheckSupervisor);
@@ -704,6 +972,10 @@ mod tests {
             process_state: None,
             parent_identity: None,
             d_state_diagnostics: None,
+            impact: None,
+            systemd_unit: None,
+            zombie_reap: None,
+            graceful_stop: None,
         }
     }
 
@@ -741,6 +1013,58 @@ mod tests {
         assert_eq!(plan.actions[1].action, Action::Kill);
     }
 
+    #[test]
+    fn freeze_requires_scope_check() {
+        assert!(pre_checks_for(Action::Freeze).contains(&PreCheck::CheckFreezeScope));
+        assert!(!pre_checks_for(Action::Unfreeze).contains(&PreCheck::CheckFreezeScope));
+        assert!(!pre_checks_for(Action::Pause).contains(&PreCheck::CheckFreezeScope));
+    }
+
+    #[test]
+    fn kill_requires_foreground_check() {
+        assert!(pre_checks_for(Action::Kill).contains(&PreCheck::CheckForegroundWork));
+        assert!(pre_checks_for(Action::Restart).contains(&PreCheck::CheckForegroundWork));
+        assert!(!pre_checks_for(Action::Pause).contains(&PreCheck::CheckForegroundWork));
+    }
+
+    #[test]
+    fn impact_inputs_annotate_each_staged_action() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![
+                {
+                    let mut c = candidate(30, Action::Kill, 100.0, 1.0);
+                    c.stage_pause_before_kill = true;
+                    c.impact = Some(ImpactInputs {
+                        rss_bytes: 64 * 1024 * 1024,
+                        cpu_frac: 0.5,
+                        ..Default::default()
+                    });
+                    c
+                },
+                candidate(31, Action::Pause, 10.0, 1.0),
+            ],
+        };
+        let plan = generate_plan(&bundle);
+        let pause = plan.actions.iter().find(|a| a.target.pid.0 == 30).unwrap();
+        let kill = plan
+            .actions
+            .iter()
+            .find(|a| a.target.pid.0 == 30 && a.action == Action::Kill)
+            .unwrap();
+        let pause_cb = pause.cost_benefit.as_ref().unwrap();
+        let kill_cb = kill.cost_benefit.as_ref().unwrap();
+        assert_eq!(pause_cb.benefit.uss_freed_bytes, 0);
+        assert!(pause_cb.risk.reversible);
+        assert!(kill_cb.benefit.uss_freed_bytes > 0);
+        assert!(!kill_cb.risk.reversible);
+
+        let unannotated = plan.actions.iter().find(|a| a.target.pid.0 == 31).unwrap();
+        assert!(unannotated.cost_benefit.is_none());
+    }
+
     #[test]
     fn deterministic_ordering() {
         let mut bundle = DecisionBundle {
@@ -760,6 +1084,62 @@ mod tests {
         assert_eq!(ids1, ids2);
     }
 
+    #[test]
+    fn stages_follow_action_stages_with_barriers_between() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![{
+                let mut c = candidate(30, Action::Kill, 100.0, 1.0);
+                c.stage_pause_before_kill = true;
+                c
+            }],
+        };
+        let plan = generate_plan(&bundle);
+        let stages: Vec<u8> = plan.stages.iter().map(|s| s.stage).collect();
+        assert_eq!(stages, vec![STAGE_PRIMARY, STAGE_KILL_AFTER_PAUSE]);
+        assert_eq!(plan.stages[0].name, "primary");
+        assert!(plan.stages[0].barriers.contains(&StageBarrier::AllVerified));
+        // Last stage has nothing to gate.
+        assert!(plan.stages[1].barriers.is_empty());
+    }
+
+    #[test]
+    fn load_aware_policy_adds_load_barrier() {
+        let mut policy = Policy::default();
+        policy.load_aware.enabled = true;
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy,
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![{
+                let mut c = candidate(30, Action::Kill, 100.0, 1.0);
+                c.stage_pause_before_kill = true;
+                c
+            }],
+        };
+        let plan = generate_plan(&bundle);
+        assert!(plan.stages[0]
+            .barriers
+            .iter()
+            .any(|b| matches!(b, StageBarrier::LoadPerCoreBelow { .. })));
+    }
+
+    #[test]
+    fn stages_omitted_from_json_when_empty() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![],
+        };
+        let plan = generate_plan(&bundle);
+        assert!(plan.stages.is_empty());
+        let json = serde_json::to_value(&plan).unwrap();
+        assert!(json.get("stages").is_none());
+    }
+
     // =========================================================================
     // Zombie (Z-state) Process Tests
     // =========================================================================
@@ -785,6 +1165,7 @@ mod tests {
         let action = &plan.actions[0];
         assert_eq!(action.target.pid, parent_id.pid);
         assert_eq!(action.action, Action::Restart);
+        assert_eq!(action.stage, STAGE_ZOMBIE_PARENTS);
         assert_eq!(action.routing, ActionRouting::ZombieToParent);
         assert!(action.original_zombie_target.is_some());
         assert_eq!(action.original_zombie_target.as_ref().unwrap().pid.0, 42);
@@ -862,6 +1243,62 @@ mod tests {
         }
     }
 
+    fn assessed_zombie(strategy: ReapStrategy) -> DecisionCandidate {
+        let mut c = candidate(42, Action::Kill, 100.0, 1.0);
+        c.process_state = Some(ProcessState::Zombie);
+        c.parent_identity = Some(identity(100));
+        c.zombie_reap = Some(ZombieReap {
+            zombie_pid: 42,
+            parent_pid: Some(100),
+            parent_comm: None,
+            strategy,
+            unit_action: None,
+            command_action: None,
+            zombie_children: 1,
+            reasons: vec![],
+        });
+        c
+    }
+
+    #[test]
+    fn zombie_signal_parent_needs_only_identity_and_state_checks() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![assessed_zombie(ReapStrategy::SignalParent)],
+        };
+        let plan = generate_plan(&bundle);
+
+        assert_eq!(plan.actions.len(), 1);
+        let action = &plan.actions[0];
+        assert_eq!(action.target.pid.0, 100);
+        assert_eq!(action.routing, ActionRouting::ZombieToParent);
+        assert_eq!(
+            action.pre_checks,
+            vec![PreCheck::VerifyIdentity, PreCheck::VerifyProcessState]
+        );
+        assert!(action.unit_action.is_none());
+    }
+
+    #[test]
+    fn zombie_under_init_is_investigate_only() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![assessed_zombie(ReapStrategy::AwaitInit)],
+        };
+        let plan = generate_plan(&bundle);
+
+        assert_eq!(plan.actions.len(), 1);
+        let action = &plan.actions[0];
+        assert_eq!(action.target.pid.0, 42);
+        assert_eq!(action.routing, ActionRouting::ZombieInvestigateOnly);
+        assert!(action.blocked);
+        assert!(plan.pre_toggled.is_empty());
+    }
+
     // =========================================================================
     // D-state (Uninterruptible Sleep) Process Tests
     // =========================================================================
@@ -977,6 +1414,69 @@ mod tests {
         assert!(action.d_state_diagnostics.is_none());
     }
 
+    #[test]
+    fn signature_shutdown_command_replaces_pause_before_kill() {
+        let stop = CommandAction {
+            signature: "sccache-server".to_string(),
+            argv: vec!["sccache".to_string(), "--stop-server".to_string()],
+            cwd: None,
+            timeout_ms: 30_000,
+        };
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![{
+                let mut c = candidate(42, Action::Kill, 100.0, 1.0);
+                c.stage_pause_before_kill = true;
+                c.graceful_stop = Some(stop.clone());
+                c
+            }],
+        };
+        let plan = generate_plan(&bundle);
+
+        assert_eq!(plan.actions.len(), 1);
+        let action = &plan.actions[0];
+        assert_eq!(action.action, Action::Kill);
+        assert_eq!(action.stage, STAGE_PRIMARY);
+        assert_eq!(action.routing, ActionRouting::GracefulCommand);
+        assert_eq!(action.command_action.as_ref(), Some(&stop));
+        let json = serde_json::to_value(action).unwrap();
+        assert_eq!(json["command_action"]["argv"][1], "--stop-server");
+    }
+
+    #[test]
+    fn service_main_process_routes_through_unit() {
+        let bundle = DecisionBundle {
+            session_id: SessionId("pt-20260115-120000-abcd".to_string()),
+            policy: Policy::default(),
+            generated_at: Some("2026-01-15T12:00:00Z".to_string()),
+            candidates: vec![{
+                let mut c = candidate(42, Action::Kill, 100.0, 1.0);
+                c.systemd_unit = Some(ServiceMembership {
+                    unit: "indexer.service".to_string(),
+                    manager: crate::collect::SystemdManager::User { uid: 1000 },
+                    main_pid: Some(42),
+                });
+                c
+            }],
+        };
+        let plan = generate_plan(&bundle);
+
+        let action = &plan.actions[0];
+        assert_eq!(action.routing, ActionRouting::SystemdUnit);
+        let unit_action = action.unit_action.as_ref().unwrap();
+        assert_eq!(unit_action.command, UnitCommand::Stop);
+        assert_eq!(unit_action.unit, "indexer.service");
+        assert!(action.pre_checks.contains(&PreCheck::VerifyUnitMapping));
+        assert!(!action.pre_checks.contains(&PreCheck::CheckSupervisor));
+
+        let json = serde_json::to_value(action).unwrap();
+        assert_eq!(json["routing"], "systemd_unit");
+        assert_eq!(json["unit_action"]["manager"]["scope"], "user");
+        assert_eq!(json["unit_action"]["manager"]["uid"], 1000);
+    }
+
     #[test]
     fn kill_includes_agent_supervision_precheck() {
         let bundle = DecisionBundle {
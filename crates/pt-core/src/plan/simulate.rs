@@ -0,0 +1,432 @@
+//! Projected system state after a plan is applied (`agent plan --simulate`).
+//!
+//! The "before" numbers are read from the host ([`SystemState`]) and from the
+//! memory controller of each cgroup the kill set lives in ([`CgroupState`]).
+//! The "after" numbers subtract what the killed processes ([`SimTarget`])
+//! hold:
+//!
+//! - their RSS becomes available memory and their swapped-out pages are
+//!   released;
+//! - their CPU share leaves the 1-minute load average (1.0 per busy core);
+//! - their memory is charged back to the nearest cgroup that contains them.
+//!
+//! Shared pages, page cache and supervisors that respawn their workers make
+//! the real gain smaller; the output lists these caveats as `assumptions`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Host-wide memory, swap and load figures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemState {
+    pub memory_total_mb: f64,
+    pub memory_available_mb: f64,
+    pub swap_total_mb: f64,
+    pub swap_free_mb: f64,
+    /// 1-minute load average.
+    pub load1: f64,
+    pub cores: u32,
+}
+
+impl SystemState {
+    /// Read the current state from `/proc` (zeros where unavailable).
+    pub fn read() -> Self {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(1);
+        Self::from_proc(
+            &std::fs::read_to_string("/proc/meminfo").unwrap_or_default(),
+            &std::fs::read_to_string("/proc/loadavg").unwrap_or_default(),
+            cores,
+        )
+    }
+
+    /// Parse `/proc/meminfo` and `/proc/loadavg` contents.
+    pub fn from_proc(meminfo: &str, loadavg: &str, cores: u32) -> Self {
+        let field_mb = |name: &str| {
+            meminfo
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .and_then(|rest| rest.split_whitespace().next()?.parse::<f64>().ok())
+                .map(|kb| kb / 1024.0)
+                .unwrap_or(0.0)
+        };
+        Self {
+            memory_total_mb: field_mb("MemTotal"),
+            memory_available_mb: field_mb("MemAvailable"),
+            swap_total_mb: field_mb("SwapTotal"),
+            swap_free_mb: field_mb("SwapFree"),
+            load1: loadavg
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0),
+            cores: cores.max(1),
+        }
+    }
+
+    fn swap_used_mb(&self) -> f64 {
+        (self.swap_total_mb - self.swap_free_mb).max(0.0)
+    }
+}
+
+/// Memory pressure implied by swap use and available memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapPressure {
+    None,
+    Low,
+    Moderate,
+    High,
+}
+
+impl SwapPressure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SwapPressure::None => "none",
+            SwapPressure::Low => "low",
+            SwapPressure::Moderate => "moderate",
+            SwapPressure::High => "high",
+        }
+    }
+
+    fn classify(available_mb: f64, total_mb: f64, swap_used_mb: f64, swap_total_mb: f64) -> Self {
+        if swap_total_mb <= 0.0 || swap_used_mb <= 0.0 {
+            return SwapPressure::None;
+        }
+        let available = if total_mb > 0.0 {
+            available_mb / total_mb
+        } else {
+            1.0
+        };
+        let used = swap_used_mb / swap_total_mb;
+        if available < 0.05 || used >= 0.75 {
+            SwapPressure::High
+        } else if available < 0.15 || used >= 0.40 {
+            SwapPressure::Moderate
+        } else {
+            SwapPressure::Low
+        }
+    }
+}
+
+/// Memory limit and usage of one cgroup (v2 memory controller).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupState {
+    pub path: String,
+    /// `memory.max`, or `None` when unlimited.
+    pub limit_mb: Option<f64>,
+    /// `memory.current`.
+    pub usage_mb: f64,
+}
+
+impl CgroupState {
+    /// Read the cgroup at `path`, or its nearest ancestor with a memory
+    /// limit, since that is the limit its processes run into.
+    pub fn read_effective(path: &str) -> Option<Self> {
+        let own = Self::read(path)?;
+        if own.limit_mb.is_some() {
+            return Some(own);
+        }
+        let mut current = path.trim_end_matches('/');
+        while let Some(idx) = current.rfind('/').filter(|&idx| idx > 0) {
+            current = &current[..idx];
+            if let Some(ancestor) = Self::read(current).filter(|a| a.limit_mb.is_some()) {
+                return Some(ancestor);
+            }
+        }
+        Some(own)
+    }
+
+    fn read(path: &str) -> Option<Self> {
+        let dir = std::path::Path::new("/sys/fs/cgroup").join(path.trim_start_matches('/'));
+        let bytes_mb = |raw: &str| raw.trim().parse::<f64>().ok().map(|b| b / 1_048_576.0);
+        let usage_mb = bytes_mb(&std::fs::read_to_string(dir.join("memory.current")).ok()?)?;
+        let limit_mb = std::fs::read_to_string(dir.join("memory.max"))
+            .ok()
+            .and_then(|raw| bytes_mb(&raw));
+        Some(Self {
+            path: path.to_string(),
+            limit_mb,
+            usage_mb,
+        })
+    }
+}
+
+/// A process the plan would kill.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimTarget {
+    pub pid: u32,
+    pub memory_mb: f64,
+    pub swap_mb: f64,
+    pub cpu_percent: f64,
+    pub cgroup: Option<String>,
+}
+
+impl SimTarget {
+    /// Build from an `agent plan` candidate (swap left at zero).
+    pub fn from_candidate(candidate: &Value) -> Option<Self> {
+        Some(Self {
+            pid: candidate.get("pid")?.as_u64()? as u32,
+            memory_mb: candidate["memory_mb"].as_f64().unwrap_or(0.0),
+            swap_mb: 0.0,
+            cpu_percent: candidate["cpu_percent"].as_f64().unwrap_or(0.0),
+            cgroup: candidate["groups"]["cgroup"].as_str().map(str::to_string),
+        })
+    }
+}
+
+/// Swapped-out memory of a live process (`VmSwap`), in MB.
+pub fn read_process_swap_mb(pid: u32) -> Option<f64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmSwap:"))
+        .and_then(|rest| rest.split_whitespace().next()?.parse::<f64>().ok())
+        .map(|kb| kb / 1024.0)
+}
+
+/// Host-level figures on one side of the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub memory_available_mb: f64,
+    pub memory_available_pct: f64,
+    pub swap_used_mb: f64,
+    pub swap_pressure: SwapPressure,
+    pub load1: f64,
+    pub load_per_core: f64,
+}
+
+impl StateSnapshot {
+    fn new(state: &SystemState, available_mb: f64, swap_used_mb: f64, load1: f64) -> Self {
+        Self {
+            memory_available_mb: round1(available_mb),
+            memory_available_pct: if state.memory_total_mb > 0.0 {
+                round1(100.0 * available_mb / state.memory_total_mb)
+            } else {
+                0.0
+            },
+            swap_used_mb: round1(swap_used_mb),
+            swap_pressure: SwapPressure::classify(
+                available_mb,
+                state.memory_total_mb,
+                swap_used_mb,
+                state.swap_total_mb,
+            ),
+            load1: round2(load1),
+            load_per_core: round2(load1 / state.cores.max(1) as f64),
+        }
+    }
+}
+
+/// Before/after memory of one cgroup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupProjection {
+    pub cgroup: String,
+    pub limit_mb: Option<f64>,
+    pub usage_before_mb: f64,
+    pub usage_after_mb: f64,
+    /// `limit - usage`; `None` for unlimited cgroups.
+    pub headroom_before_mb: Option<f64>,
+    pub headroom_after_mb: Option<f64>,
+    /// Kill-set processes charged to this cgroup.
+    pub processes: usize,
+}
+
+/// Totals removed by the plan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationDelta {
+    pub memory_freed_mb: f64,
+    pub swap_released_mb: f64,
+    pub load_reduction: f64,
+}
+
+/// Projected system state after the kill set is applied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Simulation {
+    pub targets: usize,
+    pub before: StateSnapshot,
+    pub after: StateSnapshot,
+    pub delta: SimulationDelta,
+    pub cgroups: Vec<CgroupProjection>,
+    /// Goal achievement from the goal optimizer, when `--goal` was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<Value>,
+    pub assumptions: Vec<String>,
+}
+
+/// Project the state after killing `targets`.
+///
+/// Each target is charged to the deepest entry of `cgroups` that contains its
+/// cgroup path; targets outside all of them only affect host figures.
+pub fn simulate(state: &SystemState, cgroups: &[CgroupState], targets: &[SimTarget]) -> Simulation {
+    let delta = SimulationDelta {
+        memory_freed_mb: round1(targets.iter().map(|t| t.memory_mb).sum()),
+        swap_released_mb: round1(targets.iter().map(|t| t.swap_mb).sum()),
+        load_reduction: round2(targets.iter().map(|t| t.cpu_percent / 100.0).sum()),
+    };
+
+    let swap_used = state.swap_used_mb();
+    let before = StateSnapshot::new(state, state.memory_available_mb, swap_used, state.load1);
+    let after = StateSnapshot::new(
+        state,
+        (state.memory_available_mb + delta.memory_freed_mb).min(state.memory_total_mb),
+        (swap_used - delta.swap_released_mb).max(0.0),
+        (state.load1 - delta.load_reduction).max(0.0),
+    );
+
+    let mut freed = vec![(0.0_f64, 0_usize); cgroups.len()];
+    for target in targets {
+        let Some(path) = target.cgroup.as_deref() else {
+            continue;
+        };
+        let owner = cgroups
+            .iter()
+            .enumerate()
+            .filter(|(_, cg)| cgroup_contains(&cg.path, path))
+            .max_by_key(|(_, cg)| cg.path.len());
+        if let Some((idx, _)) = owner {
+            freed[idx].0 += target.memory_mb + target.swap_mb;
+            freed[idx].1 += 1;
+        }
+    }
+    let cgroups = cgroups
+        .iter()
+        .zip(freed)
+        .filter(|(_, (_, processes))| *processes > 0)
+        .map(|(cg, (freed_mb, processes))| {
+            let usage_after = (cg.usage_mb - freed_mb).max(0.0);
+            CgroupProjection {
+                cgroup: cg.path.clone(),
+                limit_mb: cg.limit_mb.map(round1),
+                usage_before_mb: round1(cg.usage_mb),
+                usage_after_mb: round1(usage_after),
+                headroom_before_mb: cg.limit_mb.map(|l| round1(l - cg.usage_mb)),
+                headroom_after_mb: cg.limit_mb.map(|l| round1(l - usage_after)),
+                processes,
+            }
+        })
+        .collect();
+
+    Simulation {
+        targets: targets.len(),
+        before,
+        after,
+        delta,
+        cgroups,
+        goal: None,
+        assumptions: vec![
+            "freed memory is each process's RSS; shared pages are counted in full".to_string(),
+            "load drops by each process's CPU share (1.0 per busy core)".to_string(),
+            "supervised processes are not respawned".to_string(),
+        ],
+    }
+}
+
+fn cgroup_contains(parent: &str, path: &str) -> bool {
+    let parent = parent.trim_end_matches('/');
+    parent.is_empty()
+        || path == parent
+        || path
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "MemTotal:       16777216 kB\n\
+                           MemFree:          524288 kB\n\
+                           MemAvailable:    1048576 kB\n\
+                           SwapTotal:       4194304 kB\n\
+                           SwapFree:        1048576 kB\n";
+
+    fn target(pid: u32, memory_mb: f64, cpu: f64, cgroup: Option<&str>) -> SimTarget {
+        SimTarget {
+            pid,
+            memory_mb,
+            swap_mb: 0.0,
+            cpu_percent: cpu,
+            cgroup: cgroup.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_proc_files() {
+        let state = SystemState::from_proc(MEMINFO, "3.50 2.10 1.00 2/345 6789\n", 4);
+        assert_eq!(state.memory_total_mb, 16384.0);
+        assert_eq!(state.memory_available_mb, 1024.0);
+        assert_eq!(state.swap_used_mb(), 3072.0);
+        assert_eq!(state.load1, 3.5);
+    }
+
+    #[test]
+    fn projects_memory_swap_and_load() {
+        let state = SystemState::from_proc(MEMINFO, "3.50 2.10 1.00 2/345 6789\n", 4);
+        let mut hog = target(10, 2048.0, 150.0, None);
+        hog.swap_mb = 1024.0;
+        let sim = simulate(&state, &[], &[hog, target(11, 512.0, 50.0, None)]);
+
+        assert_eq!(sim.targets, 2);
+        assert_eq!(sim.delta.memory_freed_mb, 2560.0);
+        assert_eq!(sim.delta.load_reduction, 2.0);
+        assert_eq!(sim.before.swap_pressure, SwapPressure::High);
+        assert_eq!(sim.after.memory_available_mb, 3584.0);
+        assert_eq!(sim.after.swap_used_mb, 2048.0);
+        assert_eq!(sim.after.swap_pressure, SwapPressure::Moderate);
+        assert_eq!(sim.after.load1, 1.5);
+        assert!(sim.after.load_per_core < sim.before.load_per_core);
+    }
+
+    #[test]
+    fn charges_targets_to_the_deepest_cgroup() {
+        let state = SystemState::from_proc(MEMINFO, "1.0 1.0 1.0 1/1 1\n", 2);
+        let cgroups = vec![
+            CgroupState {
+                path: "/system.slice".to_string(),
+                limit_mb: None,
+                usage_mb: 6000.0,
+            },
+            CgroupState {
+                path: "/system.slice/ci.service".to_string(),
+                limit_mb: Some(4096.0),
+                usage_mb: 4000.0,
+            },
+        ];
+        let sim = simulate(
+            &state,
+            &cgroups,
+            &[
+                target(1, 1000.0, 0.0, Some("/system.slice/ci.service/job-1")),
+                target(2, 500.0, 0.0, Some("/system.slice/other.service")),
+                target(3, 100.0, 0.0, Some("/user.slice")),
+            ],
+        );
+
+        assert_eq!(sim.cgroups.len(), 2);
+        let ci = sim
+            .cgroups
+            .iter()
+            .find(|c| c.cgroup == "/system.slice/ci.service")
+            .unwrap();
+        assert_eq!(ci.processes, 1);
+        assert_eq!(ci.headroom_before_mb, Some(96.0));
+        assert_eq!(ci.headroom_after_mb, Some(1096.0));
+        let slice = sim
+            .cgroups
+            .iter()
+            .find(|c| c.cgroup == "/system.slice")
+            .unwrap();
+        assert_eq!(slice.usage_after_mb, 5500.0);
+        assert_eq!(slice.headroom_after_mb, None);
+    }
+}
@@ -0,0 +1,596 @@
+//! Process-tree aware kill planning.
+//!
+//! Killing a parent orphans its children, and killing a worker of a live
+//! supervisor usually just gets it respawned. [`ProcessTree`] is the
+//! parent/child graph of a scan; it classifies processes as supervisors,
+//! workers or plain parents. [`plan_cascade`] turns a set of kill targets into
+//! tree-level steps under a [`CascadePolicy`]:
+//!
+//! - `leaf_only`: each target alone
+//! - `process_group`: one signal to a process group whose live members are all
+//!   targets
+//! - `tree`: a target together with its descendants
+//!
+//! With `bottom_up`, deeper processes are killed first, so a parent that is
+//! still alive cannot respawn its children mid-plan. [`apply_cascade`] applies
+//! the same steps to a typed [`Plan`].
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use pt_common::{IdentityQuality, ProcessIdentity};
+use serde::Serialize;
+
+use super::{action_id_for, action_tier, ActionRouting, Plan};
+use crate::collect::ProcessRecord;
+use crate::config::policy::{CascadeMode, CascadePolicy};
+use crate::decision::Action;
+
+/// Pre-fork servers and process supervisors whose children are workers.
+const SUPERVISORS: &[&str] = &[
+    "supervisord",
+    "PM2",
+    "gunicorn",
+    "uwsgi",
+    "php-fpm",
+    "nginx",
+    "httpd",
+    "apache2",
+    "postgres",
+    "postmaster",
+    "celery",
+    "unicorn",
+    "puma",
+    "runsv",
+    "s6-supervise",
+];
+
+/// One process in the tree.
+#[derive(Debug, Clone)]
+pub struct TreeNode {
+    pub identity: ProcessIdentity,
+    pub ppid: u32,
+    pub comm: String,
+}
+
+/// Where a process sits in its tree, as reported per plan candidate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TreePosition {
+    #[serde(flatten)]
+    pub role: TreeRole,
+    pub depth: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pgid: Option<u32>,
+    pub children: Vec<u32>,
+}
+
+/// Parent/child graph of a process table.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTree {
+    nodes: HashMap<u32, TreeNode>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+/// Position of a process in its tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+pub enum TreeRole {
+    /// No children.
+    Standalone,
+    /// Parent of ordinary children.
+    Parent { children: usize },
+    /// Supervisor or pre-fork master of workers.
+    Supervisor { workers: usize },
+    /// Worker of a supervisor, which may respawn it.
+    Worker { supervisor: u32 },
+}
+
+impl ProcessTree {
+    pub fn from_records(records: &[ProcessRecord]) -> Self {
+        let mut tree = Self::default();
+        for record in records {
+            tree.insert(TreeNode {
+                identity: ProcessIdentity::full(
+                    record.pid.0,
+                    record.start_id.clone(),
+                    record.uid,
+                    record.pgid,
+                    record.sid,
+                    IdentityQuality::Full,
+                ),
+                ppid: record.ppid.0,
+                comm: record.comm.clone(),
+            });
+        }
+        tree
+    }
+
+    pub fn insert(&mut self, node: TreeNode) {
+        let pid = node.identity.pid.0;
+        if node.ppid != pid {
+            let siblings = self.children.entry(node.ppid).or_default();
+            siblings.push(pid);
+            siblings.sort_unstable();
+        }
+        self.nodes.insert(pid, node);
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&TreeNode> {
+        self.nodes.get(&pid)
+    }
+
+    pub fn children(&self, pid: u32) -> &[u32] {
+        self.children.get(&pid).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// All descendants, parents before children.
+    pub fn descendants(&self, pid: u32) -> Vec<u32> {
+        let mut out = Vec::new();
+        let mut seen = HashSet::from([pid]);
+        let mut queue = std::collections::VecDeque::from([pid]);
+        while let Some(current) = queue.pop_front() {
+            for &child in self.children(current) {
+                if seen.insert(child) {
+                    out.push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+        out
+    }
+
+    /// Ancestors known to the tree, parent first.
+    pub fn ancestors(&self, pid: u32) -> Vec<u32> {
+        let mut out = Vec::new();
+        let mut current = pid;
+        while let Some(node) = self.nodes.get(&current) {
+            if node.ppid == current || node.ppid == 0 || out.contains(&node.ppid) {
+                break;
+            }
+            out.push(node.ppid);
+            current = node.ppid;
+        }
+        out
+    }
+
+    /// Distance from the root of the known tree.
+    pub fn depth(&self, pid: u32) -> usize {
+        self.ancestors(pid).len()
+    }
+
+    /// Live members of a process group.
+    pub fn group_members(&self, pgid: u32) -> Vec<u32> {
+        let mut members: Vec<u32> = self
+            .nodes
+            .values()
+            .filter(|n| n.identity.pgid == Some(pgid))
+            .map(|n| n.identity.pid.0)
+            .collect();
+        members.sort_unstable();
+        members
+    }
+
+    pub fn role(&self, pid: u32) -> TreeRole {
+        if let Some(node) = self.nodes.get(&pid) {
+            if let Some(parent) = self.nodes.get(&node.ppid) {
+                if self.is_worker_of(node, parent) {
+                    return TreeRole::Worker {
+                        supervisor: node.ppid,
+                    };
+                }
+            }
+        }
+        let children = self.children(pid);
+        if children.is_empty() {
+            return TreeRole::Standalone;
+        }
+        let workers = self.workers(pid);
+        if workers > 0 {
+            TreeRole::Supervisor { workers }
+        } else {
+            TreeRole::Parent {
+                children: children.len(),
+            }
+        }
+    }
+
+    pub fn position(&self, pid: u32) -> TreePosition {
+        TreePosition {
+            role: self.role(pid),
+            depth: self.depth(pid),
+            pgid: self.get(pid).and_then(|n| n.identity.pgid),
+            children: self.children(pid).to_vec(),
+        }
+    }
+
+    fn workers(&self, pid: u32) -> usize {
+        let Some(parent) = self.nodes.get(&pid) else {
+            return 0;
+        };
+        self.children(pid)
+            .iter()
+            .filter_map(|c| self.nodes.get(c))
+            .filter(|child| self.is_worker_of(child, parent))
+            .count()
+    }
+
+    /// A child is a worker of a known supervisor, or one of several children
+    /// forked from the same program (a pre-fork pool).
+    fn is_worker_of(&self, child: &TreeNode, parent: &TreeNode) -> bool {
+        if SUPERVISORS.contains(&parent.comm.as_str()) {
+            return true;
+        }
+        if child.comm != parent.comm {
+            return false;
+        }
+        self.children(parent.identity.pid.0)
+            .iter()
+            .filter_map(|c| self.nodes.get(c))
+            .filter(|c| c.comm == parent.comm)
+            .count()
+            >= 2
+    }
+}
+
+/// What one cascade step signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "scope", rename_all = "snake_case")]
+pub enum KillScope {
+    /// The process alone.
+    Process,
+    /// Every member of the process group (negative-PID signal).
+    ProcessGroup { pgid: u32 },
+    /// The process and its descendants, deepest first.
+    Subtree,
+}
+
+/// A tree-level kill.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CascadeStep {
+    pub pid: u32,
+    #[serde(flatten)]
+    pub scope: KillScope,
+    /// Processes the step terminates, in kill order.
+    pub covers: Vec<u32>,
+    pub depth: usize,
+    #[serde(flatten)]
+    pub role: TreeRole,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Tree-level kill steps in execution order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CascadePlan {
+    pub mode: &'static str,
+    pub bottom_up: bool,
+    pub steps: Vec<CascadeStep>,
+}
+
+/// Plan tree-level kills for `targets`.
+pub fn plan_cascade(targets: &[u32], tree: &ProcessTree, policy: &CascadePolicy) -> CascadePlan {
+    let max = policy.max_tree_size.max(1) as usize;
+    let mut target_set: HashSet<u32> = HashSet::new();
+    let targets: Vec<u32> = targets
+        .iter()
+        .copied()
+        .filter(|pid| target_set.insert(*pid))
+        .collect();
+
+    let mut covered: HashSet<u32> = HashSet::new();
+    let mut steps = Vec::new();
+
+    if policy.mode == CascadeMode::ProcessGroup {
+        let mut seen_groups = HashSet::new();
+        for &pid in &targets {
+            let Some(pgid) = tree.get(pid).and_then(|n| n.identity.pgid) else {
+                continue;
+            };
+            if pgid <= 1 || !seen_groups.insert(pgid) {
+                continue;
+            }
+            let members = tree.group_members(pgid);
+            if members.len() < 2
+                || members.len() > max
+                || !members.iter().all(|m| target_set.contains(m))
+            {
+                continue;
+            }
+            let leader = if members.contains(&pgid) {
+                pgid
+            } else {
+                *members
+                    .iter()
+                    .min_by_key(|m| (tree.depth(**m), **m))
+                    .unwrap_or(&pid)
+            };
+            let mut ordered = members.clone();
+            ordered.sort_by_key(|m| (Reverse(tree.depth(*m)), *m));
+            covered.extend(&members);
+            steps.push(step(
+                tree,
+                leader,
+                KillScope::ProcessGroup { pgid },
+                ordered,
+                Vec::new(),
+            ));
+        }
+    }
+
+    for &pid in &targets {
+        if covered.contains(&pid) {
+            continue;
+        }
+        // A target inside another target's subtree is covered by that step.
+        if policy.mode == CascadeMode::Tree
+            && tree
+                .ancestors(pid)
+                .iter()
+                .any(|a| target_set.contains(a) && tree.descendants(*a).len() < max)
+        {
+            continue;
+        }
+        let mut warnings = Vec::new();
+        let (scope, covers) = if policy.mode == CascadeMode::Tree {
+            let descendants = tree.descendants(pid);
+            if descendants.is_empty() {
+                (KillScope::Process, vec![pid])
+            } else if descendants.len() < max {
+                let mut covers = descendants;
+                covers.sort_by_key(|d| (Reverse(tree.depth(*d)), *d));
+                covers.push(pid);
+                (KillScope::Subtree, covers)
+            } else {
+                warnings.push(format!(
+                    "subtree of {} processes exceeds max_tree_size {}; killing the process alone",
+                    descendants.len() + 1,
+                    max
+                ));
+                (KillScope::Process, vec![pid])
+            }
+        } else {
+            (KillScope::Process, vec![pid])
+        };
+        covered.extend(&covers);
+        steps.push(step(tree, pid, scope, covers, warnings));
+    }
+
+    for step in &mut steps {
+        annotate(step, tree, &covered);
+    }
+    if policy.bottom_up {
+        steps.sort_by_key(|s| Reverse(s.depth));
+    }
+
+    CascadePlan {
+        mode: policy.mode.as_str(),
+        bottom_up: policy.bottom_up,
+        steps,
+    }
+}
+
+fn step(
+    tree: &ProcessTree,
+    pid: u32,
+    scope: KillScope,
+    covers: Vec<u32>,
+    warnings: Vec<String>,
+) -> CascadeStep {
+    CascadeStep {
+        pid,
+        scope,
+        covers,
+        depth: tree.depth(pid),
+        role: tree.role(pid),
+        warnings,
+    }
+}
+
+/// Warn about respawns and orphans the step leaves behind.
+fn annotate(step: &mut CascadeStep, tree: &ProcessTree, covered: &HashSet<u32>) {
+    if let TreeRole::Worker { supervisor } = step.role {
+        if !covered.contains(&supervisor) {
+            let name = tree.get(supervisor).map(|n| n.comm.as_str()).unwrap_or("?");
+            step.warnings.push(format!(
+                "worker of live supervisor {} ({}); it will likely be respawned, target the supervisor instead",
+                name, supervisor
+            ));
+        }
+    }
+    let orphans = step
+        .covers
+        .iter()
+        .flat_map(|pid| tree.children(*pid))
+        .filter(|child| !covered.contains(child))
+        .count();
+    if orphans > 0 {
+        step.warnings.push(format!(
+            "{} child process(es) will be orphaned and reparented to init",
+            orphans
+        ));
+    }
+}
+
+/// Apply tree-level planning to the kills of a typed plan.
+///
+/// Group steps signal the whole group from one action (the other members'
+/// kills are dropped); subtree steps add cascade kills for descendants. With
+/// `bottom_up`, actions are reordered deepest first within each action tier.
+pub fn apply_cascade(plan: &mut Plan, tree: &ProcessTree, policy: &CascadePolicy) -> CascadePlan {
+    let is_kill = |action: Action| action == Action::Kill;
+    let targets: Vec<u32> = plan
+        .actions
+        .iter()
+        .filter(|a| is_kill(a.action) && !a.blocked)
+        .map(|a| a.target.pid.0)
+        .collect();
+    let cascade = plan_cascade(&targets, tree, policy);
+
+    for step in &cascade.steps {
+        let Some(root_idx) = plan
+            .actions
+            .iter()
+            .position(|a| is_kill(a.action) && !a.blocked && a.target.pid.0 == step.pid)
+        else {
+            continue;
+        };
+        match step.scope {
+            KillScope::Process => {}
+            KillScope::ProcessGroup { .. } => {
+                plan.actions[root_idx].routing = ActionRouting::ProcessGroup;
+                let dropped: HashSet<String> = plan
+                    .actions
+                    .iter()
+                    .filter(|a| {
+                        is_kill(a.action)
+                            && a.target.pid.0 != step.pid
+                            && step.covers.contains(&a.target.pid.0)
+                    })
+                    .map(|a| a.action_id.clone())
+                    .collect();
+                plan.actions.retain(|a| !dropped.contains(&a.action_id));
+                plan.pre_toggled.retain(|id| !dropped.contains(id));
+            }
+            KillScope::Subtree => {
+                let root = plan.actions[root_idx].clone();
+                let toggled = plan.pre_toggled.contains(&root.action_id);
+                for pid in &step.covers {
+                    let Some(node) = tree.get(*pid) else {
+                        continue;
+                    };
+                    if plan
+                        .actions
+                        .iter()
+                        .any(|a| is_kill(a.action) && a.target.pid.0 == *pid)
+                    {
+                        continue;
+                    }
+                    let mut action = root.clone();
+                    action.target = node.identity.clone();
+                    action.action_id = action_id_for(Action::Kill, &node.identity, root.stage);
+                    action.routing = ActionRouting::Cascade;
+                    action.original_zombie_target = None;
+                    action.d_state_diagnostics = None;
+                    action.cost_benefit = None;
+                    if toggled {
+                        plan.pre_toggled.push(action.action_id.clone());
+                    }
+                    plan.actions.push(action);
+                }
+            }
+        }
+    }
+
+    if policy.bottom_up {
+        plan.actions.sort_by_key(|a| {
+            let depth = if is_kill(a.action) {
+                tree.depth(a.target.pid.0)
+            } else {
+                0
+            };
+            (action_tier(a.action), Reverse(depth))
+        });
+    }
+    for (idx, action) in plan.actions.iter_mut().enumerate() {
+        action.order = idx as u32;
+    }
+    plan.gates_summary.pre_toggled_actions = plan.pre_toggled.len();
+    cascade
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pt_common::StartId;
+
+    fn node(pid: u32, ppid: u32, pgid: u32, comm: &str) -> TreeNode {
+        let mut identity = ProcessIdentity::new(pid, StartId(format!("boot:{pid}:{pid}")), 1000);
+        identity.pgid = Some(pgid);
+        TreeNode {
+            identity,
+            ppid,
+            comm: comm.to_string(),
+        }
+    }
+
+    /// bash(10) -> make(20) -> cc(30), cc(31); nginx(40) -> nginx(41), nginx(42)
+    fn sample_tree() -> ProcessTree {
+        let mut tree = ProcessTree::default();
+        for n in [
+            node(1, 0, 1, "systemd"),
+            node(10, 1, 10, "bash"),
+            node(20, 10, 20, "make"),
+            node(30, 20, 20, "cc"),
+            node(31, 20, 20, "cc"),
+            node(40, 1, 40, "nginx"),
+            node(41, 40, 40, "nginx"),
+            node(42, 40, 40, "nginx"),
+        ] {
+            tree.insert(n);
+        }
+        tree
+    }
+
+    fn policy(mode: CascadeMode) -> CascadePolicy {
+        CascadePolicy {
+            mode,
+            ..CascadePolicy::default()
+        }
+    }
+
+    #[test]
+    fn classifies_supervisors_and_workers() {
+        let tree = sample_tree();
+        assert_eq!(tree.role(40), TreeRole::Supervisor { workers: 2 });
+        assert_eq!(tree.role(41), TreeRole::Worker { supervisor: 40 });
+        assert_eq!(tree.role(20), TreeRole::Parent { children: 2 });
+        assert_eq!(tree.role(30), TreeRole::Standalone);
+        assert_eq!(tree.depth(30), 3);
+        assert_eq!(tree.descendants(10), vec![20, 30, 31]);
+    }
+
+    #[test]
+    fn leaf_only_orders_bottom_up_and_warns() {
+        let tree = sample_tree();
+        let plan = plan_cascade(&[20, 30, 41], &tree, &policy(CascadeMode::LeafOnly));
+        let order: Vec<u32> = plan.steps.iter().map(|s| s.pid).collect();
+        assert_eq!(order, vec![30, 20, 41]);
+        let make = plan.steps.iter().find(|s| s.pid == 20).unwrap();
+        assert!(make.warnings[0].contains("1 child"));
+        let worker = plan.steps.iter().find(|s| s.pid == 41).unwrap();
+        assert!(worker.warnings[0].contains("supervisor nginx (40)"));
+    }
+
+    #[test]
+    fn process_group_needs_every_member() {
+        let tree = sample_tree();
+        let plan = plan_cascade(&[20, 30, 31], &tree, &policy(CascadeMode::ProcessGroup));
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].pid, 20);
+        assert_eq!(plan.steps[0].scope, KillScope::ProcessGroup { pgid: 20 });
+        assert_eq!(plan.steps[0].covers, vec![30, 31, 20]);
+
+        // One member missing: per-process kills.
+        let plan = plan_cascade(&[20, 30], &tree, &policy(CascadeMode::ProcessGroup));
+        assert!(plan.steps.iter().all(|s| s.scope == KillScope::Process));
+    }
+
+    #[test]
+    fn tree_mode_covers_descendants_within_limit() {
+        let tree = sample_tree();
+        let plan = plan_cascade(&[10, 30], &tree, &policy(CascadeMode::Tree));
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].scope, KillScope::Subtree);
+        assert_eq!(plan.steps[0].covers, vec![30, 31, 20, 10]);
+        assert!(plan.steps[0].warnings.is_empty());
+
+        let small = CascadePolicy {
+            mode: CascadeMode::Tree,
+            max_tree_size: 2,
+            ..CascadePolicy::default()
+        };
+        let plan = plan_cascade(&[10], &tree, &small);
+        assert_eq!(plan.steps[0].scope, KillScope::Process);
+        assert!(plan.steps[0].warnings[0].contains("exceeds max_tree_size"));
+    }
+}
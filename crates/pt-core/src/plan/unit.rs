@@ -0,0 +1,156 @@
+//! Unit-level actions for the main process of a systemd service.
+//!
+//! Signalling a service's main process races its `Restart=` policy: the
+//! process comes back, or the unit is left failed and its dependents with
+//! it. When a candidate is the main process of a service, the planner
+//! replaces the signal with a unit operation instead:
+//!
+//! | Decision  | Unit operation      |
+//! |-----------|---------------------|
+//! | `kill`    | `systemctl stop`    |
+//! | `restart` | `systemctl restart` |
+//!
+//! Units of a user's manager are driven with `systemctl --user`. Worker
+//! processes, scopes and the user manager itself keep the direct action.
+//! The mapping is re-checked at apply time ([`PreCheck::VerifyUnitMapping`]).
+//!
+//! [`PreCheck::VerifyUnitMapping`]: super::PreCheck::VerifyUnitMapping
+
+use crate::collect::systemd::{
+    collect_systemd_unit_in, unit_of_pid, SystemdManager, SystemdUnitType,
+};
+use crate::decision::Action;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The systemd service a candidate runs in, as seen at plan time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceMembership {
+    /// Unit name, e.g. `nginx.service`.
+    pub unit: String,
+    pub manager: SystemdManager,
+    /// The unit's `MainPID`, if systemd reported one.
+    pub main_pid: Option<u32>,
+}
+
+impl ServiceMembership {
+    /// Service a live process runs in, with the unit's current `MainPID`.
+    ///
+    /// Returns `None` for processes outside a service (scopes, slices, no
+    /// systemd).
+    pub fn detect(pid: u32) -> Option<Self> {
+        let (unit, manager) = unit_of_pid(pid)?;
+        if SystemdUnitType::from_unit_name(&unit) != SystemdUnitType::Service {
+            return None;
+        }
+        let main_pid = collect_systemd_unit_in(pid, &unit, manager).and_then(|info| info.main_pid);
+        Some(Self {
+            unit,
+            manager,
+            main_pid,
+        })
+    }
+}
+
+/// Operation on a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitCommand {
+    Stop,
+    Restart,
+}
+
+impl UnitCommand {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitCommand::Stop => "stop",
+            UnitCommand::Restart => "restart",
+        }
+    }
+}
+
+/// A unit operation carried out in place of signalling the target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UnitAction {
+    /// Unit name, e.g. `nginx.service`.
+    pub unit: String,
+    /// Manager that owns the unit.
+    pub manager: SystemdManager,
+    pub command: UnitCommand,
+}
+
+impl UnitAction {
+    /// The command as the owning user would type it, for review.
+    pub fn display_command(&self) -> String {
+        match self.manager {
+            SystemdManager::System => format!("systemctl {} {}", self.command.as_str(), self.unit),
+            SystemdManager::User { .. } => {
+                format!("systemctl --user {} {}", self.command.as_str(), self.unit)
+            }
+        }
+    }
+}
+
+/// Unit operation that replaces `action` on `pid`, if any.
+///
+/// Only the main process of a service qualifies: stopping the unit for a
+/// worker would take the whole service down with it.
+pub fn unit_action_for(
+    service: &ServiceMembership,
+    pid: u32,
+    action: Action,
+) -> Option<UnitAction> {
+    if SystemdUnitType::from_unit_name(&service.unit) != SystemdUnitType::Service
+        || service.unit.starts_with("user@")
+        || service.main_pid != Some(pid)
+    {
+        return None;
+    }
+    let command = match action {
+        Action::Kill => UnitCommand::Stop,
+        Action::Restart => UnitCommand::Restart,
+        _ => return None,
+    };
+    Some(UnitAction {
+        unit: service.unit.clone(),
+        manager: service.manager,
+        command,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(unit: &str, manager: SystemdManager, main_pid: Option<u32>) -> ServiceMembership {
+        ServiceMembership {
+            unit: unit.to_string(),
+            manager,
+            main_pid,
+        }
+    }
+
+    #[test]
+    fn main_process_of_service_maps_to_unit_operations() {
+        let user = SystemdManager::User { uid: 1000 };
+        let svc = service("syncthing.service", user, Some(4242));
+
+        let stop = unit_action_for(&svc, 4242, Action::Kill).unwrap();
+        assert_eq!(stop.command, UnitCommand::Stop);
+        assert_eq!(stop.manager, user);
+        assert_eq!(
+            stop.display_command(),
+            "systemctl --user stop syncthing.service"
+        );
+        let restart = unit_action_for(&svc, 4242, Action::Restart).unwrap();
+        assert_eq!(restart.command, UnitCommand::Restart);
+        assert!(unit_action_for(&svc, 4242, Action::Pause).is_none());
+
+        // Workers, scopes and the user manager keep the direct action
+        assert!(unit_action_for(&svc, 4243, Action::Kill).is_none());
+        let scope = service("session-2.scope", SystemdManager::System, Some(7));
+        assert!(unit_action_for(&scope, 7, Action::Kill).is_none());
+        let manager = service("user@1000.service", SystemdManager::System, Some(9));
+        assert!(unit_action_for(&manager, 9, Action::Kill).is_none());
+    }
+}
@@ -0,0 +1,303 @@
+//! Reaping zombies through their parent.
+//!
+//! A zombie has already exited; only its parent can remove it, by calling
+//! `wait()`. The planner never signals the zombie itself. It assesses the
+//! parent instead and picks the least disruptive step that is safe for it:
+//!
+//! | Parent                                    | Step                      |
+//! |-------------------------------------------|---------------------------|
+//! | main process of a service, not protected  | restart the unit          |
+//! | running or sleeping                       | `SIGCHLD` to the parent   |
+//! | init (PID 1), or itself a zombie          | none: init reaps orphans  |
+//! | stopped, in D-state, or not found         | none: investigate         |
+//!
+//! Restarting an unsupervised parent is never planned, since nothing would
+//! bring it back. Killing it hands its zombies to init, which reaps them; that
+//! is left to the user and spelled out in `agent explain`.
+
+use super::unit::{unit_action_for, ServiceMembership, UnitAction};
+use crate::collect::ProcessState;
+use crate::decision::Action;
+use pt_common::ProcessIdentity;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The parent of a zombie, as seen at plan time.
+#[derive(Debug, Clone)]
+pub struct ZombieParent {
+    pub identity: ProcessIdentity,
+    pub comm: String,
+    pub state: ProcessState,
+    /// Parent matches the policy's protected patterns, users or PIDs.
+    pub protected: bool,
+    /// systemd service the parent runs in (None = not unit-managed).
+    pub service: Option<ServiceMembership>,
+    /// Zombie children the parent holds, this one included.
+    pub zombie_children: usize,
+}
+
+/// How a zombie gets reaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReapStrategy {
+    /// Restart the parent's unit; its zombies pass to init when it exits.
+    RestartUnit,
+    /// Send `SIGCHLD` so the parent collects exited children.
+    SignalParent,
+    /// Nothing to do: init reaps the zombie (or is itself the parent).
+    AwaitInit,
+    /// No safe step; the parent needs a human look.
+    Investigate,
+}
+
+impl ReapStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReapStrategy::RestartUnit => "restart_unit",
+            ReapStrategy::SignalParent => "signal_parent",
+            ReapStrategy::AwaitInit => "await_init",
+            ReapStrategy::Investigate => "investigate",
+        }
+    }
+}
+
+/// Reap assessment for one zombie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ZombieReap {
+    pub zombie_pid: u32,
+    pub parent_pid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_comm: Option<String>,
+    pub strategy: ReapStrategy,
+    /// Unit restart planned for `restart_unit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_action: Option<UnitAction>,
+    /// Zombie children of the parent, this one included.
+    pub zombie_children: usize,
+    /// Why this step was chosen and which were ruled out, most important first.
+    pub reasons: Vec<String>,
+}
+
+impl ZombieReap {
+    /// Whether the strategy results in a plan action on the parent.
+    pub fn actionable(&self) -> bool {
+        matches!(
+            self.strategy,
+            ReapStrategy::RestartUnit | ReapStrategy::SignalParent
+        )
+    }
+
+    /// One-line summary for plan rationales and `agent explain`.
+    pub fn summary(&self) -> String {
+        let parent = match (self.parent_pid, self.parent_comm.as_deref()) {
+            (Some(pid), Some(comm)) => format!("parent PID {} ({})", pid, comm),
+            (Some(pid), None) => format!("parent PID {}", pid),
+            _ => "its parent".to_string(),
+        };
+        match self.strategy {
+            ReapStrategy::RestartUnit => format!(
+                "zombie: reap via {}: {}",
+                parent,
+                self.unit_action
+                    .as_ref()
+                    .map(|unit| unit.display_command())
+                    .unwrap_or_else(|| "restart its unit".to_string())
+            ),
+            ReapStrategy::SignalParent => format!("zombie: reap via {}: send SIGCHLD", parent),
+            ReapStrategy::AwaitInit => format!("zombie: no action, {} is init", parent),
+            ReapStrategy::Investigate => format!("zombie: investigate {}", parent),
+        }
+    }
+}
+
+/// Assess how the zombie `zombie_pid` with parent PID `ppid` can be reaped.
+///
+/// `parent` is the parent's live record, `None` if it was not found.
+pub fn assess(zombie_pid: u32, ppid: u32, parent: Option<&ZombieParent>) -> ZombieReap {
+    let mut reap = ZombieReap {
+        zombie_pid,
+        parent_pid: (ppid != 0).then_some(ppid),
+        parent_comm: parent.map(|p| p.comm.clone()),
+        strategy: ReapStrategy::Investigate,
+        unit_action: None,
+        zombie_children: parent.map(|p| p.zombie_children).unwrap_or(1),
+        reasons: Vec::new(),
+    };
+
+    if ppid == 1 {
+        reap.strategy = ReapStrategy::AwaitInit;
+        reap.reasons.push(
+            "parent is init (PID 1), which reaps adopted children; a zombie that lingers \
+             under init points at init itself, which is never signalled or restarted"
+                .to_string(),
+        );
+        return reap;
+    }
+    let Some(parent) = parent else {
+        reap.reasons.push(
+            "parent process not found; once it is gone init adopts and reaps the zombie"
+                .to_string(),
+        );
+        return reap;
+    };
+
+    match parent.state {
+        ProcessState::Zombie | ProcessState::Dead => {
+            reap.strategy = ReapStrategy::AwaitInit;
+            reap.reasons.push(
+                "parent has exited as well; the kernel hands its children to init, which \
+                 reaps them"
+                    .to_string(),
+            );
+            return reap;
+        }
+        ProcessState::Stopped => {
+            reap.reasons.push(
+                "parent is stopped and cannot handle SIGCHLD until it is resumed".to_string(),
+            );
+            return reap;
+        }
+        ProcessState::DiskSleep => {
+            reap.reasons.push(
+                "parent is in uninterruptible sleep (D state) and cannot handle signals"
+                    .to_string(),
+            );
+            return reap;
+        }
+        _ => {}
+    }
+
+    let unit_restart = parent
+        .service
+        .as_ref()
+        .and_then(|service| unit_action_for(service, parent.identity.pid.0, Action::Restart));
+    match unit_restart {
+        Some(unit) if !parent.protected => {
+            reap.reasons.push(format!(
+                "parent is the main process of {}; restarting the unit replaces it and \
+                 init reaps the zombies it leaves behind",
+                unit.unit
+            ));
+            reap.strategy = ReapStrategy::RestartUnit;
+            reap.unit_action = Some(unit);
+        }
+        Some(unit) => {
+            reap.reasons.push(format!(
+                "parent is protected; its unit {} is not restarted",
+                unit.unit
+            ));
+            reap.strategy = ReapStrategy::SignalParent;
+        }
+        None => {
+            reap.strategy = ReapStrategy::SignalParent;
+        }
+    }
+
+    if reap.strategy == ReapStrategy::SignalParent {
+        reap.reasons.push(
+            "SIGCHLD prompts the parent to wait() for exited children; it is ignored by \
+             default, so the parent is not disturbed"
+                .to_string(),
+        );
+        if parent.service.is_none() {
+            reap.reasons.push(
+                "parent is not supervised, so restarting it is not planned; killing it \
+                 would hand its zombies to init"
+                    .to_string(),
+            );
+        }
+    }
+    if reap.zombie_children > 1 {
+        reap.reasons.push(format!(
+            "parent holds {} zombie children; a parent that never waits keeps accumulating them",
+            reap.zombie_children
+        ));
+    }
+    reap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::systemd::SystemdManager;
+    use pt_common::{IdentityQuality, ProcessId, StartId};
+
+    fn parent(pid: u32, state: ProcessState) -> ZombieParent {
+        ZombieParent {
+            identity: ProcessIdentity {
+                pid: ProcessId(pid),
+                start_id: StartId(format!("boot:{pid}:{pid}")),
+                uid: 1000,
+                pgid: None,
+                sid: None,
+                quality: IdentityQuality::Full,
+            },
+            comm: "worker-pool".to_string(),
+            state,
+            protected: false,
+            service: None,
+            zombie_children: 1,
+        }
+    }
+
+    #[test]
+    fn running_parent_is_signalled() {
+        let p = parent(300, ProcessState::Sleeping);
+        let reap = assess(42, 300, Some(&p));
+        assert_eq!(reap.strategy, ReapStrategy::SignalParent);
+        assert!(reap.actionable());
+        assert_eq!(reap.parent_pid, Some(300));
+        assert!(reap.reasons.iter().any(|r| r.contains("not supervised")));
+        assert_eq!(
+            reap.summary(),
+            "zombie: reap via parent PID 300 (worker-pool): send SIGCHLD"
+        );
+    }
+
+    #[test]
+    fn service_main_parent_restarts_unit_unless_protected() {
+        let mut p = parent(300, ProcessState::Sleeping);
+        p.service = Some(ServiceMembership {
+            unit: "spooler.service".to_string(),
+            manager: SystemdManager::System,
+            main_pid: Some(300),
+        });
+        p.zombie_children = 5;
+        let reap = assess(42, 300, Some(&p));
+        assert_eq!(reap.strategy, ReapStrategy::RestartUnit);
+        assert_eq!(
+            reap.unit_action.as_ref().unwrap().display_command(),
+            "systemctl restart spooler.service"
+        );
+        assert!(reap.reasons.iter().any(|r| r.contains("5 zombie children")));
+
+        p.protected = true;
+        let reap = assess(42, 300, Some(&p));
+        assert_eq!(reap.strategy, ReapStrategy::SignalParent);
+        assert!(reap.unit_action.is_none());
+    }
+
+    #[test]
+    fn init_and_exited_parents_need_no_action() {
+        let reap = assess(42, 1, None);
+        assert_eq!(reap.strategy, ReapStrategy::AwaitInit);
+        assert!(!reap.actionable());
+
+        let p = parent(300, ProcessState::Zombie);
+        assert_eq!(assess(42, 300, Some(&p)).strategy, ReapStrategy::AwaitInit);
+    }
+
+    #[test]
+    fn unsignallable_or_missing_parent_is_investigated() {
+        for state in [ProcessState::Stopped, ProcessState::DiskSleep] {
+            let p = parent(300, state);
+            assert_eq!(
+                assess(42, 300, Some(&p)).strategy,
+                ReapStrategy::Investigate
+            );
+        }
+        let reap = assess(42, 300, None);
+        assert_eq!(reap.strategy, ReapStrategy::Investigate);
+        assert_eq!(reap.summary(), "zombie: investigate parent PID 300");
+    }
+}
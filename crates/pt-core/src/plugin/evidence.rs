@@ -150,6 +150,7 @@ pub fn to_evidence_term(
     EvidenceTerm {
         feature: format!("plugin:{}", plugin_name),
         log_likelihood: scaled,
+        weight: None,
     }
 }
 
@@ -0,0 +1,268 @@
+//! Reverse mapping of hash tokens, for de-pseudonymizing exports.
+//!
+//! Keyed hashing (`[HASH:k1:…]`) keeps values correlatable across sessions
+//! without revealing them, which also makes them hard to debug. While
+//! pt-core writes a session's artifacts it records the value behind every
+//! hash token in `redaction/dictionary-<key_id>.enc` in the session
+//! directory. That file is encrypted with a passphrase derived from the
+//! redaction key it belongs to, so it only opens on a host holding
+//! `redaction_key`, and bundles never include it.
+//!
+//! [`export`] re-encrypts a session's token→original pairs under a
+//! responder's passphrase, in the bundle encryption format, so a forensic
+//! bundle can be de-pseudonymized offline with [`import`].
+
+use pt_redact::{KeyManager, KeyMaterial, RedactionError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Session subdirectory holding the dictionaries.
+pub const DICTIONARY_DIR: &str = "redaction";
+
+/// Schema version of exported dictionaries.
+pub const DICTIONARY_SCHEMA_VERSION: &str = "1.0.0";
+
+const FILE_PREFIX: &str = "dictionary-";
+const FILE_SUFFIX: &str = ".enc";
+
+/// Hashed with the redaction key to derive the at-rest passphrase.
+const STORE_CONTEXT: &str = "pt-core redaction dictionary";
+
+#[derive(Debug, Error)]
+pub enum DictionaryError {
+    #[error("no redaction dictionary in {0} (nothing was hashed)")]
+    NotFound(PathBuf),
+
+    #[error("redaction key: {0}")]
+    Key(#[from] RedactionError),
+
+    #[error("{0}")]
+    Encryption(#[from] pt_bundle::BundleError),
+
+    #[error("invalid dictionary: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Hash tokens of a session mapped to the values they replaced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dictionary {
+    pub schema_version: String,
+    pub session_id: String,
+    /// Redaction keys (token epochs) the tokens were produced with.
+    pub key_ids: Vec<String>,
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Dictionary {
+    /// Entries for the given tokens only; unknown tokens are skipped.
+    pub fn select(&self, tokens: &[String]) -> BTreeMap<String, String> {
+        tokens
+            .iter()
+            .filter_map(|token| {
+                self.entries
+                    .get(token)
+                    .map(|value| (token.clone(), value.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Merge `entries`, produced with `key`, into the session's dictionary.
+pub fn record(
+    session_dir: &Path,
+    key: &KeyMaterial,
+    entries: &BTreeMap<String, String>,
+) -> Result<PathBuf, DictionaryError> {
+    let dir = session_dir.join(DICTIONARY_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}{}{}", FILE_PREFIX, key.key_id, FILE_SUFFIX));
+
+    let mut merged = if path.exists() {
+        read_stored(&path, key)?
+    } else {
+        BTreeMap::new()
+    };
+    let before = merged.len();
+    for (token, value) in entries {
+        merged.entry(token.clone()).or_insert_with(|| value.clone());
+    }
+    if merged.len() == before && path.exists() {
+        return Ok(path);
+    }
+
+    let plaintext = serde_json::to_vec(&merged)?;
+    let encrypted = pt_bundle::encrypt_bytes(&plaintext, &store_passphrase(key))?;
+    write_private(&path, &encrypted)?;
+    Ok(path)
+}
+
+/// Decrypt every dictionary of a session with the keys in `keys`.
+pub fn load(
+    session_dir: &Path,
+    session_id: &str,
+    keys: &KeyManager,
+) -> Result<Dictionary, DictionaryError> {
+    let dir = session_dir.join(DICTIONARY_DIR);
+    let mut key_ids: Vec<String> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let key_id = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+                Some(key_id.to_string())
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if key_ids.is_empty() {
+        return Err(DictionaryError::NotFound(session_dir.to_path_buf()));
+    }
+    key_ids.sort();
+
+    let mut entries = BTreeMap::new();
+    for key_id in &key_ids {
+        let key = keys.key(key_id)?;
+        let path = dir.join(format!("{}{}{}", FILE_PREFIX, key_id, FILE_SUFFIX));
+        entries.extend(read_stored(&path, &key)?);
+    }
+    Ok(Dictionary {
+        schema_version: DICTIONARY_SCHEMA_VERSION.to_string(),
+        session_id: session_id.to_string(),
+        key_ids,
+        entries,
+    })
+}
+
+/// Encrypt a dictionary under `passphrase` for export.
+pub fn export(dictionary: &Dictionary, passphrase: &str) -> Result<Vec<u8>, DictionaryError> {
+    let plaintext = serde_json::to_vec_pretty(dictionary)?;
+    Ok(pt_bundle::encrypt_bytes(&plaintext, passphrase)?)
+}
+
+/// Decrypt an exported dictionary.
+pub fn import(bytes: &[u8], passphrase: &str) -> Result<Dictionary, DictionaryError> {
+    let plaintext = pt_bundle::decrypt_bytes(bytes, passphrase)?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn store_passphrase(key: &KeyMaterial) -> String {
+    key.hash(STORE_CONTEXT, 32)
+}
+
+fn read_stored(
+    path: &Path,
+    key: &KeyMaterial,
+) -> Result<BTreeMap<String, String>, DictionaryError> {
+    let bytes = std::fs::read(path)?;
+    let plaintext = pt_bundle::decrypt_bytes(&bytes, &store_passphrase(key))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Write `content` readable by the owner only.
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::fs::write(&tmp, content)?;
+    }
+
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(token, value)| (token.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn records_merge_across_runs_and_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keys = KeyManager::new().unwrap();
+        let k1 = keys.active_key().unwrap();
+        let a = k1.hash("build-host-7", 8);
+        let b = k1.hash("tar -czf /tmp/x.tgz src", 8);
+
+        let path = record(dir.path(), &k1, &entries(&[(a.as_str(), "build-host-7")])).unwrap();
+        record(
+            dir.path(),
+            &k1,
+            &entries(&[(b.as_str(), "tar -czf /tmp/x.tgz src")]),
+        )
+        .unwrap();
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("build-host-7"));
+
+        keys.rotate().unwrap();
+        let k2 = keys.active_key().unwrap();
+        let c = k2.hash("build-host-7", 8);
+        record(dir.path(), &k2, &entries(&[(c.as_str(), "build-host-7")])).unwrap();
+
+        let dictionary = load(dir.path(), "pt-test", &keys).unwrap();
+        assert_eq!(dictionary.key_ids, vec!["k1", "k2"]);
+        assert_eq!(dictionary.entries.len(), 3);
+        assert_eq!(dictionary.entries[&b], "tar -czf /tmp/x.tgz src");
+        assert_eq!(dictionary.entries[&c], "build-host-7");
+        assert_eq!(dictionary.select(&[a, "[HASH:k9:00]".to_string()]).len(), 1);
+
+        // Without the key the session's dictionary stays closed.
+        let other = KeyManager::new().unwrap();
+        assert!(matches!(
+            load(dir.path(), "pt-test", &other),
+            Err(DictionaryError::Encryption(_))
+        ));
+    }
+
+    #[test]
+    fn export_round_trips_under_the_passphrase() {
+        let dictionary = Dictionary {
+            schema_version: DICTIONARY_SCHEMA_VERSION.to_string(),
+            session_id: "pt-test".to_string(),
+            key_ids: vec!["k1".to_string()],
+            entries: entries(&[("[HASH:k1:0011223344556677]", "build-host-7")]),
+        };
+        let bytes = export(&dictionary, "responder").unwrap();
+        assert!(pt_bundle::is_encrypted(&bytes));
+        assert_eq!(import(&bytes, "responder").unwrap(), dictionary);
+        assert!(import(&bytes, "wrong").is_err());
+    }
+
+    #[test]
+    fn missing_dictionary_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = KeyManager::new().unwrap();
+        assert!(matches!(
+            load(dir.path(), "pt-test", &keys),
+            Err(DictionaryError::NotFound(_))
+        ));
+    }
+}
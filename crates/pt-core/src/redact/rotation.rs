@@ -0,0 +1,293 @@
+//! Rotation of the redaction hashing key.
+//!
+//! [`rotate_key`] deprecates the active key in [`KEY_FILE`] and makes a new
+//! one active, so outputs written afterwards carry `[HASH:<new id>:…]`
+//! tokens. Sessions that are still going to be acted on (created, scanning
+//! or planned) are forward-looking: [`rehash_session`] rewrites their hash
+//! tokens under the new key, from the values kept in the session's
+//! [`dictionary`], so a plan applied after the rotation lines up with fresh
+//! output. Tokens with no dictionary entry keep their old key.
+//!
+//! Each rotation is appended to [`ROTATION_LOG`] in the config directory.
+
+use super::dictionary::{self, DictionaryError};
+use super::{audit::collect_artifacts, KEY_FILE};
+use crate::session::SessionState;
+use pt_redact::{KeyManager, KeyMaterial, RedactionEngine, RedactionError, RedactionPolicy};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use thiserror::Error;
+
+/// Rotation history in the config directory, one JSON record per line.
+pub const ROTATION_LOG: &str = "redaction_rotations.jsonl";
+
+static TOKEN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[HASH:([A-Za-z0-9_-]+):[0-9a-f]+\]").unwrap());
+
+#[derive(Debug, Error)]
+pub enum RotationError {
+    #[error("no redaction key at {0}; nothing to rotate")]
+    NoKey(PathBuf),
+
+    #[error("redaction key: {0}")]
+    Key(#[from] RedactionError),
+
+    #[error("{0}")]
+    Dictionary(#[from] DictionaryError),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Tokens of one session re-hashed under the new key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RehashedSession {
+    pub session_id: String,
+    /// Artifacts rewritten.
+    pub files: usize,
+    /// Token occurrences replaced.
+    pub tokens: usize,
+    /// Older-key dictionary entries that could not be re-hashed.
+    pub unmapped: usize,
+}
+
+/// A key rotation, as recorded in [`ROTATION_LOG`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    pub rotated_at: String,
+    pub previous_key_id: String,
+    pub key_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub sessions: Vec<RehashedSession>,
+}
+
+/// Whether a session in `state` will still be acted on, so its tokens
+/// should follow the new key.
+pub fn is_forward_looking(state: SessionState) -> bool {
+    matches!(
+        state,
+        SessionState::Created | SessionState::Scanning | SessionState::Planned
+    )
+}
+
+/// Make a new key active in `config_dir`'s [`KEY_FILE`].
+///
+/// Returns the updated keys and the rotation, without re-hashed sessions.
+pub fn rotate_key(
+    config_dir: &Path,
+    reason: Option<String>,
+) -> Result<(KeyManager, KeyRotation), RotationError> {
+    let key_path = config_dir.join(KEY_FILE);
+    if !key_path.exists() {
+        return Err(RotationError::NoKey(key_path));
+    }
+    let mut keys = KeyManager::load(&key_path)?;
+    let previous_key_id = keys.active_key_id.clone();
+    keys.rotate()?;
+    keys.save(&key_path)?;
+
+    let rotation = KeyRotation {
+        rotated_at: chrono::Utc::now().to_rfc3339(),
+        previous_key_id,
+        key_id: keys.active_key_id.clone(),
+        reason,
+        sessions: Vec::new(),
+    };
+    Ok((keys, rotation))
+}
+
+/// Rewrite the older-key tokens of a session under the active key of
+/// `keys`, and add the new tokens to the session's dictionary.
+pub fn rehash_session(
+    session_dir: &Path,
+    session_id: &str,
+    keys: &KeyManager,
+) -> Result<RehashedSession, RotationError> {
+    let mut result = RehashedSession {
+        session_id: session_id.to_string(),
+        ..RehashedSession::default()
+    };
+    let dictionary = match dictionary::load(session_dir, session_id, keys) {
+        Ok(dictionary) => dictionary,
+        Err(DictionaryError::NotFound(_)) => return Ok(result),
+        Err(e) => return Err(e.into()),
+    };
+
+    let key = keys.active_key()?;
+    let engine =
+        RedactionEngine::with_key(RedactionPolicy::default(), key.clone()).with_dictionary();
+    let mut previous_keys: HashMap<String, KeyMaterial> = HashMap::new();
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    for (token, value) in &dictionary.entries {
+        let Some(key_id) = TOKEN_RE.captures(token).map(|caps| caps[1].to_string()) else {
+            continue;
+        };
+        if key_id == key.key_id {
+            continue;
+        }
+        if !previous_keys.contains_key(&key_id) {
+            previous_keys.insert(key_id.clone(), keys.key(&key_id)?);
+        }
+        match engine.rehash(token, value, &previous_keys[&key_id]) {
+            Some(rehashed) => {
+                mapping.insert(token.clone(), rehashed);
+            }
+            None => result.unmapped += 1,
+        }
+    }
+    if mapping.is_empty() {
+        return Ok(result);
+    }
+
+    let mut files = Vec::new();
+    collect_artifacts(session_dir, &mut files)?;
+    files.sort();
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut replaced = 0;
+        let rewritten =
+            TOKEN_RE.replace_all(&content, |caps: &Captures| match mapping.get(&caps[0]) {
+                Some(rehashed) => {
+                    replaced += 1;
+                    rehashed.clone()
+                }
+                None => caps[0].to_string(),
+            });
+        if replaced == 0 {
+            continue;
+        }
+        write_atomic(&path, rewritten.as_bytes())?;
+        result.files += 1;
+        result.tokens += replaced;
+    }
+
+    dictionary::record(session_dir, &key, &engine.dictionary())?;
+    Ok(result)
+}
+
+/// Append a rotation to `config_dir`'s [`ROTATION_LOG`].
+pub fn record_rotation(config_dir: &Path, rotation: &KeyRotation) -> Result<(), RotationError> {
+    let mut line = serde_json::to_string(rotation)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config_dir.join(ROTATION_LOG))?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Rotations recorded in `config_dir`, oldest first.
+pub fn read_rotations(config_dir: &Path) -> Result<Vec<KeyRotation>, RotationError> {
+    let content = match std::fs::read_to_string(config_dir.join(ROTATION_LOG)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(RotationError::from))
+        .collect()
+}
+
+fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(format!(".tmp.{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pt_redact::FieldClass;
+
+    #[test]
+    fn rotation_rehashes_session_tokens_and_logs() {
+        let config = tempfile::tempdir().unwrap();
+        let session = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            rotate_key(config.path(), None),
+            Err(RotationError::NoKey(_))
+        ));
+
+        let keys = KeyManager::new().unwrap();
+        keys.save(config.path().join(KEY_FILE)).unwrap();
+        let engine = RedactionEngine::with_key_manager(RedactionPolicy::default(), &keys)
+            .unwrap()
+            .with_dictionary();
+        let host = engine.redact("build-host-7", FieldClass::Hostname).output;
+        let cmdline = engine.redact("make -j8", FieldClass::Cmdline).output;
+        dictionary::record(
+            session.path(),
+            &keys.active_key().unwrap(),
+            &engine.dictionary(),
+        )
+        .unwrap();
+        let plan = serde_json::json!({
+            "host": host,
+            "candidates": [{"cmd": cmdline}, {"cmd": "[HASH:k1:00ff00ff00ff00ff]"}],
+        });
+        std::fs::create_dir_all(session.path().join("decision")).unwrap();
+        std::fs::write(session.path().join("decision/plan.json"), plan.to_string()).unwrap();
+
+        let (keys, mut rotation) =
+            rotate_key(config.path(), Some("scheduled".to_string())).unwrap();
+        assert_eq!(
+            (rotation.previous_key_id.as_str(), rotation.key_id.as_str()),
+            ("k1", "k2")
+        );
+        let stats = rehash_session(session.path(), "pt-test", &keys).unwrap();
+        assert_eq!((stats.files, stats.tokens, stats.unmapped), (1, 2, 0));
+
+        let fresh = RedactionEngine::with_key_manager(RedactionPolicy::default(), &keys).unwrap();
+        let rewritten: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(session.path().join("decision/plan.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            rewritten["host"],
+            fresh.redact("build-host-7", FieldClass::Hostname).output
+        );
+        assert_eq!(
+            rewritten["candidates"][0]["cmd"],
+            fresh.redact("make -j8", FieldClass::Cmdline).output
+        );
+        assert_eq!(
+            rewritten["candidates"][1]["cmd"],
+            "[HASH:k1:00ff00ff00ff00ff]"
+        );
+
+        // The session's dictionary resolves tokens of both keys.
+        let dictionary = dictionary::load(session.path(), "pt-test", &keys).unwrap();
+        assert_eq!(dictionary.key_ids, vec!["k1", "k2"]);
+        assert_eq!(dictionary.entries.len(), 4);
+
+        rotation.sessions.push(stats);
+        record_rotation(config.path(), &rotation).unwrap();
+        assert_eq!(read_rotations(config.path()).unwrap(), vec![rotation]);
+    }
+
+    #[test]
+    fn sessions_without_a_dictionary_are_left_alone() {
+        let session = tempfile::tempdir().unwrap();
+        let keys = KeyManager::new().unwrap();
+        let stats = rehash_session(session.path(), "pt-test", &keys).unwrap();
+        assert_eq!(stats.tokens, 0);
+        assert!(is_forward_looking(SessionState::Planned));
+        assert!(!is_forward_looking(SessionState::Completed));
+    }
+}
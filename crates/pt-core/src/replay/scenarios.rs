@@ -44,6 +44,7 @@ impl ProcBuilder {
                 elapsed: Duration::from_secs(3600),
                 source: "scenario".to_string(),
                 container_info: None,
+                io_rate: None,
             },
         }
     }
@@ -244,6 +244,7 @@ impl ReplaySnapshot {
                 duration_ms: 0,
                 process_count: self.processes.len(),
                 warnings: vec![format!("Replayed from snapshot: {}", self.name)],
+                visibility: Default::default(),
             },
         }
     }
@@ -356,6 +357,14 @@ fn build_evidence(proc: &ProcessRecord, deep: Option<&DeepSignalRecord>) -> Evid
         io_active: deep.and_then(|d| d.io_active),
         state_flag,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -447,6 +456,7 @@ mod tests {
                 duration_ms: 0,
                 process_count: 0,
                 warnings: vec![],
+                visibility: Default::default(),
             },
         };
 
@@ -0,0 +1,311 @@
+//! Tokio runtime layer for I/O-heavy subsystems.
+//!
+//! Blocking-thread fan-out (one OS thread per SSH session, batched by
+//! `--parallel`) stalls a whole batch on its slowest host. This module gives
+//! those subsystems a shared async runtime instead:
+//!
+//! - [`AsyncRuntime`]: a multi-threaded tokio runtime owned by the command.
+//! - [`AsyncRuntime::timeout_token`]: a cancellation token tied to the global
+//!   `--timeout`, so every in-flight task stops when the command's budget ends.
+//! - [`AsyncRuntime::flag_token`]: a cancellation token driven by a flag, for
+//!   stop requests that arrive through signal handlers.
+//! - [`bounded_map`]: run a future per item with at most `limit` in flight,
+//!   returning results in input order.
+//! - [`run_command`]: run a child process that is killed on cancellation or
+//!   timeout instead of being waited out.
+//!
+//! Fleet SSH scanning (`fleet::ssh_scan::ssh_scan_fleet_async`), MCP serving
+//! (`mcp::server::serve_stdio_async`, `mcp::http::serve_async`) and the
+//! daemon's escalation plans, notification commands and inter-tick waits are
+//! built on it.
+
+use std::future::Future;
+use std::io;
+use std::process::Output;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+pub use tokio_util::sync::CancellationToken;
+
+/// Multi-threaded tokio runtime for a single CLI command.
+pub struct AsyncRuntime {
+    runtime: tokio::runtime::Runtime,
+}
+
+impl AsyncRuntime {
+    /// Build a runtime with tokio's default worker count.
+    pub fn new() -> std::io::Result<Self> {
+        Self::build(None)
+    }
+
+    /// Build a runtime with a fixed number of worker threads.
+    pub fn with_worker_threads(threads: usize) -> std::io::Result<Self> {
+        Self::build(Some(threads.max(1)))
+    }
+
+    fn build(threads: Option<usize>) -> std::io::Result<Self> {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all().thread_name("pt-async");
+        if let Some(threads) = threads {
+            builder.worker_threads(threads);
+        }
+        Ok(Self {
+            runtime: builder.build()?,
+        })
+    }
+
+    /// Run a future to completion on this runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Token cancelled once `timeout` elapses (never, if `None`).
+    ///
+    /// Pass the global `--timeout` here and hand the token to every task.
+    pub fn timeout_token(&self, timeout: Option<Duration>) -> CancellationToken {
+        let token = CancellationToken::new();
+        if let Some(timeout) = timeout {
+            let deadline = token.clone();
+            self.runtime.spawn(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(timeout) => deadline.cancel(),
+                    _ = deadline.cancelled() => {}
+                }
+            });
+        }
+        token
+    }
+
+    /// Token cancelled once `stop()` returns true, checked every `every`.
+    ///
+    /// Signal handlers can only set flags; this lets such a flag cancel the
+    /// tasks selecting on the token.
+    pub fn flag_token<F>(&self, stop: F, every: Duration) -> CancellationToken
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        let token = CancellationToken::new();
+        let flagged = token.clone();
+        self.runtime.spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            loop {
+                tokio::select! {
+                    _ = flagged.cancelled() => return,
+                    _ = interval.tick() => {
+                        if stop() {
+                            flagged.cancel();
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+        token
+    }
+}
+
+/// Interval at which [`wait_for`] checks its wake condition.
+const WAKE_POLL: Duration = Duration::from_millis(100);
+
+/// Wait up to `duration`, returning `true` as soon as `wake()` does.
+///
+/// Returns `false` when the time runs out or `token` is cancelled.
+pub async fn wait_for<F>(duration: Duration, token: &CancellationToken, mut wake: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    let mut poll = tokio::time::interval(WAKE_POLL);
+    loop {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => return false,
+            _ = &mut deadline => return false,
+            _ = poll.tick() => {
+                if wake() {
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+/// Run `command` to completion with the stdio it was configured with.
+///
+/// The child is killed if `token` is cancelled or `timeout` elapses first,
+/// in which case the result is `Ok(None)`.
+pub async fn run_command(
+    command: std::process::Command,
+    timeout: Option<Duration>,
+    token: &CancellationToken,
+) -> io::Result<Option<Output>> {
+    let mut command = tokio::process::Command::from(command);
+    command.kill_on_drop(true);
+    let child = command.spawn()?;
+    let finished = async {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Ok(None),
+            output = child.wait_with_output() => output.map(Some),
+        }
+    };
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, finished)
+            .await
+            .unwrap_or(Ok(None)),
+        None => finished.await,
+    }
+}
+
+/// Run `f` over `items` with at most `limit` futures in flight.
+///
+/// Results come back in input order. An item whose future had not finished
+/// when `token` was cancelled yields `None`.
+pub async fn bounded_map<T, R, F, Fut>(
+    items: Vec<T>,
+    limit: usize,
+    token: &CancellationToken,
+    f: F,
+) -> Vec<Option<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+    let count = items.len();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let token = token.clone();
+        let future = f(item);
+        tasks.spawn(async move {
+            let result = tokio::select! {
+                biased;
+                _ = token.cancelled() => None,
+                permit = semaphore.acquire_owned() => match permit {
+                    Ok(_permit) => tokio::select! {
+                        biased;
+                        _ = token.cancelled() => None,
+                        result = future => Some(result),
+                    },
+                    Err(_) => None,
+                },
+            };
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Option<R>> = (0..count).map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, result)) = joined {
+            results[index] = result;
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn bounded_map_preserves_order_and_limit() {
+        let runtime = AsyncRuntime::with_worker_threads(4).unwrap();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+
+        let results = runtime.block_on(bounded_map((0..20).collect(), 3, &token, |i: u64| {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5 * (20 - i))).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            }
+        }));
+
+        assert_eq!(results, (0..20).map(|i| Some(i * 2)).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn timeout_token_cancels_outstanding_work() {
+        let runtime = AsyncRuntime::with_worker_threads(2).unwrap();
+        let token = runtime.timeout_token(Some(Duration::from_millis(50)));
+
+        let results = runtime.block_on(bounded_map(
+            vec![0u64, 10_000],
+            2,
+            &token,
+            |ms| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                ms
+            },
+        ));
+
+        assert_eq!(results, vec![Some(0), None]);
+        assert!(token.is_cancelled());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_command_kills_on_timeout_or_cancel() {
+        let runtime = AsyncRuntime::with_worker_threads(2).unwrap();
+        let token = CancellationToken::new();
+
+        let mut echo = std::process::Command::new("echo");
+        echo.arg("done").stdout(std::process::Stdio::piped());
+        let output = runtime
+            .block_on(run_command(echo, Some(Duration::from_secs(10)), &token))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.stdout, b"done\n");
+
+        let mut sleep = std::process::Command::new("sleep");
+        sleep.arg("10");
+        let started = std::time::Instant::now();
+        let timed_out = runtime
+            .block_on(run_command(sleep, Some(Duration::from_millis(50)), &token))
+            .unwrap();
+        assert!(timed_out.is_none());
+
+        let mut sleep = std::process::Command::new("sleep");
+        sleep.arg("10");
+        let stop = runtime.flag_token(|| true, Duration::from_millis(10));
+        let cancelled = runtime.block_on(run_command(sleep, None, &stop)).unwrap();
+        assert!(cancelled.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn wait_for_wakes_early_or_times_out() {
+        let runtime = AsyncRuntime::with_worker_threads(2).unwrap();
+        let token = CancellationToken::new();
+        let mut polls = 0;
+        assert!(
+            runtime.block_on(wait_for(Duration::from_secs(10), &token, || {
+                polls += 1;
+                polls > 2
+            }))
+        );
+        assert!(!runtime.block_on(wait_for(Duration::from_millis(20), &token, || false)));
+    }
+
+    #[test]
+    fn timeout_token_without_timeout_stays_live() {
+        let runtime = AsyncRuntime::new().unwrap();
+        let token = runtime.timeout_token(None);
+        runtime.block_on(async { tokio::time::sleep(Duration::from_millis(10)).await });
+        assert!(!token.is_cancelled());
+    }
+}
@@ -19,13 +19,21 @@
 //!
 //! # Generate all schemas
 //! pt schema --all
+//!
+//! # Write versioned schema files (schemas/v1.0.0/Plan.schema.json, ...)
+//! pt schema --all --out-dir schemas
+//!
+//! # Check a previous release's `--all` output for breaking changes
+//! pt schema --compat schemas-v1.json
 //! ```
 
 use schemars::schema_for;
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 // Re-export types that have schemas
+pub use crate::calibrate::ValidationReport;
 pub use crate::collect::{ProcessRecord, ProcessState, ScanMetadata, ScanResult};
 pub use crate::decision::causal_interventions::{
     InterventionOutcome, ProcessClass, RecoveryExpectation, RecoveryTable,
@@ -36,12 +44,24 @@ pub use crate::decision::expected_loss::{
     Action, ActionFeasibility, DecisionOutcome, DecisionRationale, DisabledAction, ExpectedLoss,
     SprtBoundary,
 };
+pub use crate::mcp::tools::{
+    ApplyToolArgs, CapabilitiesToolArgs, ExplainToolArgs, FeedbackToolArgs, HistoryToolArgs,
+    PlanToolArgs, ScanToolArgs, SessionsToolArgs, SignaturesToolArgs,
+};
+pub use crate::output::envelopes::{
+    AgentDiffOutput, AgentExplainOutput, AgentPlanOutput, FleetReportOutput,
+};
 pub use crate::plan::{
     ActionConfidence, ActionHook, ActionRationale, ActionRouting, ActionTimeouts,
-    DStateDiagnostics, GatesSummary, Plan, PlanAction, PreCheck,
+    DStateDiagnostics, GatesSummary, Plan, PlanAction, PlanStage, PreCheck, StageBarrier,
 };
+pub use crate::watch::{RuleAction, RuleConditions, WatchEvent, WatchRule, WatchRules};
+pub use pt_bundle::BundleManifest;
 pub use pt_common::{IdentityQuality, ProcessId, ProcessIdentity, SessionId, StartId};
 
+/// Base URI for the `$id` of schemas written by `pt schema --all --out-dir`.
+pub const SCHEMA_ID_BASE: &str = "https://process-triage.dev/schemas";
+
 /// Available schema types with their descriptions.
 pub fn available_schemas() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -99,6 +119,8 @@ pub fn available_schemas() -> Vec<(&'static str, &'static str)> {
         ("Plan", "Complete action plan with staged actions"),
         ("PlanAction", "Single action in a plan"),
         ("GatesSummary", "Safety gate summary"),
+        ("PlanStage", "Ordered execution stage with barriers"),
+        ("StageBarrier", "Checkpoint condition between stages"),
         ("ActionTimeouts", "Timeout configuration for actions"),
         ("PreCheck", "Pre-action check specification"),
         ("ActionRouting", "Routing for unkillable processes"),
@@ -112,6 +134,45 @@ pub fn available_schemas() -> Vec<(&'static str, &'static str)> {
             "DStateDiagnostics",
             "Diagnostics for D-state (disk sleep) processes",
         ),
+        // MCP tool argument types
+        ("ScanToolArgs", "Arguments for the pt_scan MCP tool"),
+        ("PlanToolArgs", "Arguments for the pt_plan MCP tool"),
+        ("ExplainToolArgs", "Arguments for the pt_explain MCP tool"),
+        ("ApplyToolArgs", "Arguments for the pt_apply MCP tool"),
+        ("SessionsToolArgs", "Arguments for the pt_sessions MCP tool"),
+        ("HistoryToolArgs", "Arguments for the pt_history MCP tool"),
+        (
+            "SignaturesToolArgs",
+            "Arguments for the pt_signatures MCP tool",
+        ),
+        (
+            "CapabilitiesToolArgs",
+            "Arguments for the pt_capabilities MCP tool",
+        ),
+        ("FeedbackToolArgs", "Arguments for the pt_feedback MCP tool"),
+        // Watch rules
+        ("WatchRules", "Rules file for agent watch --rules"),
+        // Command output surfaces
+        ("AgentPlanOutput", "pt agent plan JSON output"),
+        ("AgentExplainOutput", "pt agent explain JSON output"),
+        ("AgentDiffOutput", "pt agent diff JSON output"),
+        ("FleetReportOutput", "pt agent fleet report JSON output"),
+        ("ValidationReport", "pt shadow report JSON output"),
+        ("BundleManifest", "manifest.json inside .ptb bundles"),
+        ("WatchEvent", "One JSONL event line from pt agent watch"),
+    ]
+}
+
+/// Command output surfaces and the schema type describing each one.
+pub fn output_surfaces() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("agent plan", "AgentPlanOutput"),
+        ("agent explain", "AgentExplainOutput"),
+        ("agent diff", "AgentDiffOutput"),
+        ("agent fleet report", "FleetReportOutput"),
+        ("shadow report", "ValidationReport"),
+        ("bundle manifest", "BundleManifest"),
+        ("agent watch", "WatchEvent"),
     ]
 }
 
@@ -153,6 +214,8 @@ pub fn generate_schema(type_name: &str) -> Option<Value> {
         "Plan" => schema_for!(Plan),
         "PlanAction" => schema_for!(PlanAction),
         "GatesSummary" => schema_for!(GatesSummary),
+        "PlanStage" => schema_for!(PlanStage),
+        "StageBarrier" => schema_for!(StageBarrier),
         "ActionTimeouts" => schema_for!(ActionTimeouts),
         "PreCheck" => schema_for!(PreCheck),
         "ActionRouting" => schema_for!(ActionRouting),
@@ -160,6 +223,26 @@ pub fn generate_schema(type_name: &str) -> Option<Value> {
         "ActionRationale" => schema_for!(ActionRationale),
         "ActionHook" => schema_for!(ActionHook),
         "DStateDiagnostics" => schema_for!(DStateDiagnostics),
+        // MCP tool argument types
+        "ScanToolArgs" => schema_for!(ScanToolArgs),
+        "PlanToolArgs" => schema_for!(PlanToolArgs),
+        "ExplainToolArgs" => schema_for!(ExplainToolArgs),
+        "ApplyToolArgs" => schema_for!(ApplyToolArgs),
+        "SessionsToolArgs" => schema_for!(SessionsToolArgs),
+        "HistoryToolArgs" => schema_for!(HistoryToolArgs),
+        "SignaturesToolArgs" => schema_for!(SignaturesToolArgs),
+        "CapabilitiesToolArgs" => schema_for!(CapabilitiesToolArgs),
+        "FeedbackToolArgs" => schema_for!(FeedbackToolArgs),
+        // Watch rules
+        "WatchRules" => schema_for!(WatchRules),
+        // Command output surfaces
+        "AgentPlanOutput" => schema_for!(AgentPlanOutput),
+        "AgentExplainOutput" => schema_for!(AgentExplainOutput),
+        "AgentDiffOutput" => schema_for!(AgentDiffOutput),
+        "FleetReportOutput" => schema_for!(FleetReportOutput),
+        "ValidationReport" => schema_for!(ValidationReport),
+        "BundleManifest" => schema_for!(BundleManifest),
+        "WatchEvent" => schema_for!(WatchEvent),
         _ => return None,
     };
 
@@ -177,6 +260,74 @@ pub fn generate_all_schemas() -> BTreeMap<String, Value> {
     schemas
 }
 
+/// Files written by [`write_versioned_schemas`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaBundle {
+    /// Directory the schemas were written to (`<out_dir>/v<version>`).
+    pub dir: std::path::PathBuf,
+    /// Schema version the files were generated for.
+    pub version: String,
+    /// Written schema files, one per type, plus `index.json`.
+    pub files: Vec<std::path::PathBuf>,
+}
+
+/// Write every schema to `<out_dir>/v<version>/<Type>.schema.json`.
+///
+/// Each schema gets a stable `$id` under [`SCHEMA_ID_BASE`] and an
+/// `index.json` lists the types, their descriptions and which command
+/// output surface they describe, so agents can pin a schema version.
+pub fn write_versioned_schemas(
+    out_dir: &std::path::Path,
+    version: &str,
+) -> std::io::Result<SchemaBundle> {
+    let dir = out_dir.join(format!("v{}", version));
+    std::fs::create_dir_all(&dir)?;
+
+    let surfaces: BTreeMap<&str, &str> = output_surfaces()
+        .into_iter()
+        .map(|(surface, name)| (name, surface))
+        .collect();
+    let mut files = Vec::new();
+    let mut index = Vec::new();
+    for (name, description) in available_schemas() {
+        let Some(mut schema) = generate_schema(name) else {
+            continue;
+        };
+        let id = format!("{}/v{}/{}.schema.json", SCHEMA_ID_BASE, version, name);
+        if let Some(obj) = schema.as_object_mut() {
+            obj.insert("$id".to_string(), Value::String(id.clone()));
+        }
+        let file_name = format!("{}.schema.json", name);
+        let path = dir.join(&file_name);
+        std::fs::write(&path, format_schema(&schema, SchemaFormat::Json) + "\n")?;
+        files.push(path);
+        index.push(serde_json::json!({
+            "type": name,
+            "description": description,
+            "file": file_name,
+            "$id": id,
+            "surface": surfaces.get(name),
+        }));
+    }
+
+    let index = serde_json::json!({
+        "schema_version": version,
+        "schemas": index,
+    });
+    let index_path = dir.join("index.json");
+    std::fs::write(
+        &index_path,
+        format_schema(&index, SchemaFormat::Json) + "\n",
+    )?;
+    files.push(index_path);
+
+    Ok(SchemaBundle {
+        dir,
+        version: version.to_string(),
+        files,
+    })
+}
+
 /// Schema output format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SchemaFormat {
@@ -194,6 +345,228 @@ pub fn format_schema(schema: &Value, format: SchemaFormat) -> String {
     }
 }
 
+/// Severity of a schema change under the compatibility promise in
+/// `docs/AGENT_CLI_CONTRACT.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSeverity {
+    /// Consumers tolerant of unknown fields and enum values are unaffected.
+    Additive,
+    /// Existing consumers may fail; requires a MAJOR `schema_version` bump.
+    Breaking,
+}
+
+/// One difference between a baseline schema and the current one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaChange {
+    /// Schema type name (e.g. `Plan`).
+    pub schema: String,
+    /// JSON-pointer-like location inside the schema.
+    pub path: String,
+    pub severity: ChangeSeverity,
+    pub description: String,
+}
+
+/// Compare two schema sets (as produced by [`generate_all_schemas`]).
+///
+/// Changes are sorted with breaking changes first.
+pub fn compare_schema_sets(
+    baseline: &BTreeMap<String, Value>,
+    current: &BTreeMap<String, Value>,
+) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for (name, old) in baseline {
+        match current.get(name) {
+            Some(new) => compare_schema(name, "", old, new, &mut changes),
+            None => changes.push(SchemaChange {
+                schema: name.clone(),
+                path: String::new(),
+                severity: ChangeSeverity::Breaking,
+                description: "schema type removed".to_string(),
+            }),
+        }
+    }
+    for name in current.keys().filter(|name| !baseline.contains_key(*name)) {
+        changes.push(SchemaChange {
+            schema: name.clone(),
+            path: String::new(),
+            severity: ChangeSeverity::Additive,
+            description: "schema type added".to_string(),
+        });
+    }
+    changes.sort_by_key(|c| std::cmp::Reverse(c.severity));
+    changes
+}
+
+fn compare_schema(name: &str, path: &str, old: &Value, new: &Value, out: &mut Vec<SchemaChange>) {
+    let mut push = |path: String, severity: ChangeSeverity, description: String| {
+        out.push(SchemaChange {
+            schema: name.to_string(),
+            path,
+            severity,
+            description,
+        })
+    };
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        if old != new {
+            push(
+                path.to_string(),
+                ChangeSeverity::Breaking,
+                "schema changed".to_string(),
+            );
+        }
+        return;
+    };
+
+    if type_set(old.get("type")) != type_set(new.get("type")) {
+        push(
+            path.to_string(),
+            ChangeSeverity::Breaking,
+            format!(
+                "type changed from {} to {}",
+                old.get("type").unwrap_or(&Value::Null),
+                new.get("type").unwrap_or(&Value::Null)
+            ),
+        );
+    }
+    if old.get("$ref") != new.get("$ref") {
+        push(
+            path.to_string(),
+            ChangeSeverity::Breaking,
+            format!(
+                "reference changed from {} to {}",
+                old.get("$ref").unwrap_or(&Value::Null),
+                new.get("$ref").unwrap_or(&Value::Null)
+            ),
+        );
+    }
+
+    let old_enum = value_set(old.get("enum"));
+    let new_enum = value_set(new.get("enum"));
+    for value in old_enum.difference(&new_enum) {
+        push(
+            format!("{}/enum", path),
+            ChangeSeverity::Breaking,
+            format!("enum value {} removed", value),
+        );
+    }
+    if !old_enum.is_empty() {
+        for value in new_enum.difference(&old_enum) {
+            push(
+                format!("{}/enum", path),
+                ChangeSeverity::Additive,
+                format!("enum value {} added", value),
+            );
+        }
+    }
+
+    let old_required = value_set(old.get("required"));
+    let new_required = value_set(new.get("required"));
+    for field in old_required.difference(&new_required) {
+        push(
+            format!("{}/required", path),
+            ChangeSeverity::Breaking,
+            format!("field {} is no longer required", field),
+        );
+    }
+    for field in new_required.difference(&old_required) {
+        push(
+            format!("{}/required", path),
+            ChangeSeverity::Additive,
+            format!("field {} is now required", field),
+        );
+    }
+
+    for keyword in ["properties", "$defs", "definitions"] {
+        let empty = serde_json::Map::new();
+        let old_map = old
+            .get(keyword)
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let new_map = new
+            .get(keyword)
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        for (key, old_child) in old_map {
+            let child_path = format!("{}/{}/{}", path, keyword, key);
+            match new_map.get(key) {
+                Some(new_child) => compare_schema(name, &child_path, old_child, new_child, out),
+                None => out.push(SchemaChange {
+                    schema: name.to_string(),
+                    path: child_path,
+                    severity: ChangeSeverity::Breaking,
+                    description: format!("{} removed", key),
+                }),
+            }
+        }
+        for key in new_map.keys().filter(|key| !old_map.contains_key(*key)) {
+            out.push(SchemaChange {
+                schema: name.to_string(),
+                path: format!("{}/{}/{}", path, keyword, key),
+                severity: ChangeSeverity::Additive,
+                description: format!("{} added", key),
+            });
+        }
+    }
+
+    for keyword in ["items", "additionalProperties"] {
+        if let (Some(old_child), Some(new_child)) = (old.get(keyword), new.get(keyword)) {
+            compare_schema(
+                name,
+                &format!("{}/{}", path, keyword),
+                old_child,
+                new_child,
+                out,
+            );
+        }
+    }
+
+    // Variants are compared positionally; schemars emits them in declaration order.
+    for keyword in ["oneOf", "anyOf", "allOf"] {
+        let old_variants = old.get(keyword).and_then(Value::as_array);
+        let new_variants = new.get(keyword).and_then(Value::as_array);
+        let (Some(old_variants), Some(new_variants)) = (old_variants, new_variants) else {
+            continue;
+        };
+        for (index, (old_child, new_child)) in old_variants.iter().zip(new_variants).enumerate() {
+            let child_path = format!("{}/{}/{}", path, keyword, index);
+            compare_schema(name, &child_path, old_child, new_child, out);
+        }
+        if old_variants.len() > new_variants.len() {
+            out.push(SchemaChange {
+                schema: name.to_string(),
+                path: format!("{}/{}", path, keyword),
+                severity: ChangeSeverity::Breaking,
+                description: format!(
+                    "{} variants removed",
+                    old_variants.len() - new_variants.len()
+                ),
+            });
+        } else if new_variants.len() > old_variants.len() {
+            out.push(SchemaChange {
+                schema: name.to_string(),
+                path: format!("{}/{}", path, keyword),
+                severity: ChangeSeverity::Additive,
+                description: format!("{} variants added", new_variants.len() - old_variants.len()),
+            });
+        }
+    }
+}
+
+fn type_set(value: Option<&Value>) -> BTreeSet<String> {
+    match value {
+        Some(Value::String(s)) => BTreeSet::from([s.clone()]),
+        other => value_set(other),
+    }
+}
+
+fn value_set(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|values| values.iter().map(Value::to_string).collect())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,6 +609,46 @@ mod tests {
         assert!(all.contains_key("ProcessRecord"));
     }
 
+    #[test]
+    fn test_output_surfaces_have_schemas() {
+        for (surface, name) in output_surfaces() {
+            assert!(
+                generate_schema(name).is_some(),
+                "surface '{}' maps to unknown schema '{}'",
+                surface,
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_versioned_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = write_versioned_schemas(dir.path(), "1.0.0").unwrap();
+        assert_eq!(bundle.dir, dir.path().join("v1.0.0"));
+        assert_eq!(bundle.files.len(), available_schemas().len() + 1);
+
+        let plan: Value = serde_json::from_str(
+            &std::fs::read_to_string(bundle.dir.join("AgentPlanOutput.schema.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            plan["$id"],
+            "https://process-triage.dev/schemas/v1.0.0/AgentPlanOutput.schema.json"
+        );
+
+        let index: Value =
+            serde_json::from_str(&std::fs::read_to_string(bundle.dir.join("index.json")).unwrap())
+                .unwrap();
+        let entry = index["schemas"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["type"] == "WatchEvent")
+            .unwrap();
+        assert_eq!(entry["surface"], "agent watch");
+    }
+
     #[test]
     fn test_format_schema() {
         let schema = generate_schema("Action").unwrap();
@@ -247,4 +660,69 @@ mod tests {
         assert!(pretty.contains('\n'));
         assert!(!compact.contains('\n'));
     }
+
+    fn schema_set(name: &str, schema: Value) -> BTreeMap<String, Value> {
+        BTreeMap::from([(name.to_string(), schema)])
+    }
+
+    #[test]
+    fn test_compare_identical_schemas_is_clean() {
+        let all = generate_all_schemas();
+        assert!(compare_schema_sets(&all, &all).is_empty());
+    }
+
+    #[test]
+    fn test_compare_detects_breaking_and_additive() {
+        let old = schema_set(
+            "T",
+            serde_json::json!({
+                "type": "object",
+                "required": ["a", "b"],
+                "properties": {
+                    "a": {"type": "integer"},
+                    "b": {"type": "string", "enum": ["x", "y"]},
+                    "gone": {"type": "string"}
+                }
+            }),
+        );
+        let new = schema_set(
+            "T",
+            serde_json::json!({
+                "type": "object",
+                "required": ["a"],
+                "properties": {
+                    "a": {"type": "string"},
+                    "b": {"type": "string", "enum": ["x", "z"]},
+                    "extra": {"type": "boolean"}
+                }
+            }),
+        );
+
+        let changes = compare_schema_sets(&old, &new);
+        let breaking: Vec<_> = changes
+            .iter()
+            .filter(|c| c.severity == ChangeSeverity::Breaking)
+            .map(|c| c.path.as_str())
+            .collect();
+        assert!(breaking.contains(&"/required"));
+        assert!(breaking.contains(&"/properties/a"));
+        assert!(breaking.contains(&"/properties/b/enum"));
+        assert!(breaking.contains(&"/properties/gone"));
+        assert!(changes
+            .iter()
+            .any(|c| c.path == "/properties/extra" && c.severity == ChangeSeverity::Additive));
+        // Breaking changes sort first.
+        assert_eq!(changes[0].severity, ChangeSeverity::Breaking);
+    }
+
+    #[test]
+    fn test_compare_schema_type_added_and_removed() {
+        let old = schema_set("Old", serde_json::json!({"type": "object"}));
+        let new = schema_set("New", serde_json::json!({"type": "object"}));
+        let changes = compare_schema_sets(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].schema, "Old");
+        assert_eq!(changes[0].severity, ChangeSeverity::Breaking);
+        assert_eq!(changes[1].severity, ChangeSeverity::Additive);
+    }
 }
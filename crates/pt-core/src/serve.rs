@@ -0,0 +1,451 @@
+//! Read-only REST API for dashboards (`pt-core serve`).
+//!
+//! Exposes session and fleet state over HTTP so teams can build dashboards
+//! without shell access to the host. Every endpoint is `GET`; nothing here
+//! can plan, apply or modify configuration.
+//!
+//! | Endpoint | Body |
+//! |----------|------|
+//! | `/health` | `ok` (no auth) |
+//! | `/api/v1/sessions?limit=N` | session summaries, newest first |
+//! | `/api/v1/sessions/{id}` | manifest + summary for one session |
+//! | `/api/v1/sessions/{id}/plan` | that session's `decision/plan.json` |
+//! | `/api/v1/plan/latest` | plan of the newest session that has one |
+//! | `/api/v1/shadow/stats` | shadow-mode storage statistics |
+//! | `/api/v1/fleet` | aggregates of every fleet session |
+//!
+//! All `/api` endpoints require `Authorization: Bearer <token>`. String
+//! fields that identify hosts, users, paths or commands are redacted per the
+//! configured export profile before they leave the process.
+
+use crate::mcp::http::check_bearer;
+use crate::session::{ListSessionsOptions, SessionStore, SessionSummary};
+use pt_common::SessionId;
+use pt_redact::{ExportProfile, FieldClass, RedactionEngine, RedactionPolicy};
+use pt_telemetry::shadow::{ShadowStorage, ShadowStorageConfig, StorageStats};
+use serde_json::{json, Value};
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// Default number of sessions returned by `/api/v1/sessions`.
+const DEFAULT_SESSION_LIMIT: u32 = 50;
+
+/// Upper bound on `?limit=` for session listings.
+const MAX_SESSION_LIMIT: u32 = 1000;
+
+/// REST server configuration.
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    pub listen: SocketAddr,
+    /// Bearer token clients must present.
+    pub token: String,
+    /// Export profile that decides how identifying fields are redacted.
+    pub profile: ExportProfile,
+    /// Shadow-mode storage directory for `/api/v1/shadow/stats`.
+    pub shadow_dir: PathBuf,
+}
+
+/// Request routes understood by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Route {
+    Health,
+    Sessions { limit: u32 },
+    Session(String),
+    SessionPlan(String),
+    LatestPlan,
+    ShadowStats,
+    Fleet,
+    MethodNotAllowed,
+    NotFound,
+}
+
+fn route(method: &tiny_http::Method, url: &str) -> Route {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = path.trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+
+    let route = match segments.as_slice() {
+        ["health"] | ["healthz"] => Route::Health,
+        ["api", "v1", "sessions"] => Route::Sessions {
+            limit: query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("limit="))
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(DEFAULT_SESSION_LIMIT)
+                .min(MAX_SESSION_LIMIT),
+        },
+        ["api", "v1", "sessions", id] => Route::Session(id.to_string()),
+        ["api", "v1", "sessions", id, "plan"] => Route::SessionPlan(id.to_string()),
+        ["api", "v1", "plan", "latest"] => Route::LatestPlan,
+        ["api", "v1", "shadow", "stats"] => Route::ShadowStats,
+        ["api", "v1", "fleet"] => Route::Fleet,
+        _ => return Route::NotFound,
+    };
+    if *method != tiny_http::Method::Get {
+        return Route::MethodNotAllowed;
+    }
+    route
+}
+
+/// Field class for a JSON key, if values under it identify something.
+fn field_class_for_key(key: &str) -> Option<FieldClass> {
+    match key {
+        "cmd" | "comm" | "process_name" => Some(FieldClass::Cmd),
+        "cmdline" | "command" | "cmd_full" => Some(FieldClass::Cmdline),
+        "host" | "host_id" | "hostname" => Some(FieldClass::Hostname),
+        "user" | "username" | "owner" => Some(FieldClass::Username),
+        "cwd" | "exe" | "path" | "cgroup" | "unit_path" => Some(FieldClass::PathProject),
+        "container_id" => Some(FieldClass::ContainerId),
+        _ => None,
+    }
+}
+
+/// Redact identifying string fields in a JSON document, in place.
+fn redact_value(value: &mut Value, engine: &RedactionEngine, profile: ExportProfile) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match (field_class_for_key(key), child) {
+                    (Some(class), Value::String(s)) => {
+                        *s = engine.redact_with_profile(s, class, profile).output;
+                    }
+                    (_, child) => redact_value(child, engine, profile),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_value(item, engine, profile);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Response to one API request: status code and JSON body.
+struct Reply {
+    status: u16,
+    body: Value,
+}
+
+impl Reply {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Read-only view over the session store and shadow storage.
+struct Api {
+    store: SessionStore,
+    engine: RedactionEngine,
+    profile: ExportProfile,
+    shadow_dir: PathBuf,
+}
+
+impl Api {
+    fn handle(&self, route: &Route) -> Reply {
+        let mut reply = match route {
+            Route::Sessions { limit } => self.sessions(*limit),
+            Route::Session(id) => self.session(id),
+            Route::SessionPlan(id) => self.session_plan(id),
+            Route::LatestPlan => self.latest_plan(),
+            Route::ShadowStats => self.shadow_stats(),
+            Route::Fleet => self.fleet(),
+            Route::Health | Route::MethodNotAllowed | Route::NotFound => {
+                Reply::error(404, "not found")
+            }
+        };
+        if reply.status == 200 {
+            redact_value(&mut reply.body, &self.engine, self.profile);
+        }
+        reply
+    }
+
+    fn summaries(&self, limit: Option<u32>) -> Result<Vec<SessionSummary>, Reply> {
+        self.store
+            .list_sessions(&ListSessionsOptions {
+                limit,
+                ..Default::default()
+            })
+            .map_err(|e| Reply::error(500, format!("session store error: {}", e)))
+    }
+
+    fn sessions(&self, limit: u32) -> Reply {
+        match self.summaries(Some(limit)) {
+            Ok(summaries) => {
+                let sessions: Vec<Value> = summaries.iter().map(summary_json).collect();
+                Reply::ok(json!({ "count": sessions.len(), "sessions": sessions }))
+            }
+            Err(reply) => reply,
+        }
+    }
+
+    fn session(&self, raw_id: &str) -> Reply {
+        let handle = match self.open(raw_id) {
+            Ok(handle) => handle,
+            Err(reply) => return reply,
+        };
+        let manifest = match handle.read_manifest() {
+            Ok(manifest) => manifest,
+            Err(e) => return Reply::error(500, format!("failed to read manifest: {}", e)),
+        };
+        let summary = self
+            .summaries(None)
+            .ok()
+            .and_then(|all| all.into_iter().find(|s| s.session_id == handle.id.0));
+        Reply::ok(json!({
+            "session_id": handle.id.0,
+            "manifest": manifest,
+            "summary": summary.as_ref().map(summary_json),
+            "has_plan": handle.dir.join("decision/plan.json").exists(),
+        }))
+    }
+
+    fn session_plan(&self, raw_id: &str) -> Reply {
+        let handle = match self.open(raw_id) {
+            Ok(handle) => handle,
+            Err(reply) => return reply,
+        };
+        match read_plan(&handle.dir) {
+            Some(plan) => Reply::ok(plan),
+            None => Reply::error(404, format!("session {} has no plan", handle.id.0)),
+        }
+    }
+
+    fn latest_plan(&self) -> Reply {
+        let summaries = match self.summaries(None) {
+            Ok(summaries) => summaries,
+            Err(reply) => return reply,
+        };
+        summaries
+            .iter()
+            .find_map(|s| read_plan(&s.path).map(|plan| (s, plan)))
+            .map(|(summary, plan)| {
+                Reply::ok(json!({ "session_id": summary.session_id, "plan": plan }))
+            })
+            .unwrap_or_else(|| Reply::error(404, "no session has a plan"))
+    }
+
+    fn shadow_stats(&self) -> Reply {
+        // Opening storage creates its directories; a read-only API must not.
+        if !self.shadow_dir.exists() {
+            return Reply::ok(json!({ "stats": StorageStats::default() }));
+        }
+        let config = ShadowStorageConfig {
+            base_dir: self.shadow_dir.clone(),
+            ..Default::default()
+        };
+        match ShadowStorage::new(config) {
+            Ok(storage) => Reply::ok(json!({ "stats": storage.stats() })),
+            Err(e) => Reply::error(503, format!("shadow storage unavailable: {}", e)),
+        }
+    }
+
+    fn fleet(&self) -> Reply {
+        let summaries = match self.summaries(None) {
+            Ok(summaries) => summaries,
+            Err(reply) => return reply,
+        };
+        let fleets: Vec<Value> = summaries
+            .iter()
+            .filter_map(|s| {
+                let content = std::fs::read_to_string(s.path.join("fleet.json")).ok()?;
+                let fleet: crate::session::fleet::FleetSession =
+                    serde_json::from_str(&content).ok()?;
+                Some(json!({
+                    "fleet_session_id": fleet.fleet_session_id,
+                    "created_at": fleet.created_at,
+                    "label": fleet.label,
+                    "host_count": fleet.hosts.len(),
+                    "aggregate": fleet.aggregate,
+                    "safety_budget": fleet.safety_budget,
+                }))
+            })
+            .collect();
+        Reply::ok(json!({ "count": fleets.len(), "fleets": fleets }))
+    }
+
+    fn open(&self, raw_id: &str) -> Result<crate::session::SessionHandle, Reply> {
+        let session_id = SessionId::parse(raw_id)
+            .ok_or_else(|| Reply::error(400, format!("invalid session id '{}'", raw_id)))?;
+        self.store
+            .open(&session_id)
+            .map_err(|_| Reply::error(404, format!("session {} not found", raw_id)))
+    }
+}
+
+/// Session summary without the on-disk path.
+fn summary_json(summary: &SessionSummary) -> Value {
+    let mut value = serde_json::to_value(summary).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        map.remove("path");
+    }
+    value
+}
+
+fn read_plan(session_dir: &std::path::Path) -> Option<Value> {
+    let content = std::fs::read_to_string(session_dir.join("decision/plan.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Run the REST server until the listener fails. Blocks the caller.
+pub fn serve(config: &ServeConfig) -> io::Result<()> {
+    let store = SessionStore::from_env().map_err(io::Error::other)?;
+    let engine = RedactionEngine::new(RedactionPolicy::default()).map_err(io::Error::other)?;
+    let api = Api {
+        store,
+        engine,
+        profile: config.profile,
+        shadow_dir: config.shadow_dir.clone(),
+    };
+
+    let listener = tiny_http::Server::http(config.listen).map_err(io::Error::other)?;
+    eprintln!(
+        "[pt-serve] Listening on http://{} (profile: {})",
+        config.listen, config.profile
+    );
+
+    for request in listener.incoming_requests() {
+        let route = route(request.method(), request.url());
+        match route {
+            Route::Health => {
+                let _ = request.respond(tiny_http::Response::from_string("ok"));
+                continue;
+            }
+            Route::MethodNotAllowed => {
+                respond_json(request, &Reply::error(405, "read-only API: use GET"));
+                continue;
+            }
+            Route::NotFound => {
+                respond_json(request, &Reply::error(404, "not found"));
+                continue;
+            }
+            _ => {}
+        }
+        if !authorized(&request, &config.token) {
+            respond_json(request, &Reply::error(401, "unauthorized"));
+            continue;
+        }
+        let reply = api.handle(&route);
+        respond_json(request, &reply);
+    }
+
+    Ok(())
+}
+
+fn authorized(request: &tiny_http::Request, token: &str) -> bool {
+    let header = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .map(|h| h.value.as_str());
+    check_bearer(header, token)
+}
+
+fn respond_json(request: tiny_http::Request, reply: &Reply) {
+    let body = serde_json::to_string_pretty(&reply.body).unwrap_or_default();
+    let response = tiny_http::Response::from_string(body)
+        .with_status_code(reply.status)
+        .with_header(
+            "Content-Type: application/json"
+                .parse::<tiny_http::Header>()
+                .unwrap(),
+        );
+    let _ = request.respond(response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiny_http::Method;
+
+    #[test]
+    fn routes_resolve() {
+        assert_eq!(route(&Method::Get, "/health"), Route::Health);
+        assert_eq!(
+            route(&Method::Get, "/api/v1/sessions"),
+            Route::Sessions {
+                limit: DEFAULT_SESSION_LIMIT
+            }
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/sessions?limit=5"),
+            Route::Sessions { limit: 5 }
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/sessions?limit=999999"),
+            Route::Sessions {
+                limit: MAX_SESSION_LIMIT
+            }
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/sessions/pt-20260101-000000-abcd/"),
+            Route::Session("pt-20260101-000000-abcd".to_string())
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/sessions/pt-x/plan"),
+            Route::SessionPlan("pt-x".to_string())
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/plan/latest"),
+            Route::LatestPlan
+        );
+        assert_eq!(
+            route(&Method::Get, "/api/v1/shadow/stats"),
+            Route::ShadowStats
+        );
+        assert_eq!(route(&Method::Get, "/api/v1/fleet"), Route::Fleet);
+        assert_eq!(
+            route(&Method::Post, "/api/v1/sessions"),
+            Route::MethodNotAllowed
+        );
+        assert_eq!(route(&Method::Get, "/api/v2/sessions"), Route::NotFound);
+    }
+
+    #[test]
+    fn redaction_covers_identifying_keys() {
+        let engine = RedactionEngine::new(RedactionPolicy::default()).unwrap();
+        let mut plan = json!({
+            "host_id": "build-box-7.corp.example",
+            "candidates": [
+                {"pid": 42, "cmdline": "node server.js --token=abc123", "score": 0.9}
+            ]
+        });
+        redact_value(&mut plan, &engine, ExportProfile::Safe);
+
+        assert_ne!(plan["host_id"], "build-box-7.corp.example");
+        assert_ne!(
+            plan["candidates"][0]["cmdline"],
+            "node server.js --token=abc123"
+        );
+        assert_eq!(plan["candidates"][0]["pid"], 42);
+        assert_eq!(plan["candidates"][0]["score"], 0.9);
+    }
+
+    #[test]
+    fn summary_drops_path() {
+        let summary = SessionSummary {
+            session_id: "pt-20260101-000000-abcd".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            state: crate::session::SessionState::Completed,
+            mode: crate::session::SessionMode::RobotPlan,
+            label: None,
+            host_id: Some("h".to_string()),
+            candidates_count: Some(3),
+            actions_count: None,
+            path: PathBuf::from("/home/alice/.local/share/pt/sessions/x"),
+            experiment: None,
+        };
+        let value = summary_json(&summary);
+        assert!(value.get("path").is_none());
+        assert_eq!(value["candidates_count"], 3);
+    }
+}
@@ -0,0 +1,442 @@
+//! Append-only session event log.
+//!
+//! Every session keeps `events.jsonl`: one [`SessionEvent`] per line, in
+//! order, never rewritten. The manifest's `state`, `state_history`, `label`
+//! and `timing.updated_at` are a projection of this log, so the log is the
+//! source of truth:
+//! - [`SessionView::replay`] derives the current state from the full log.
+//! - [`SessionView::as_of`] reconstructs what the tool knew at a given time.
+//! - A torn final line (crash mid-append) is ignored on read and cut off by
+//!   the next append, and a lost or stale manifest is rebuilt from the log
+//!   ([`SessionHandle::rebuild_manifest`](super::SessionHandle::rebuild_manifest)).
+//!
+//! Sessions created before the log existed are migrated on their first new
+//! event by seeding the log from the manifest's `state_history`.
+//!
+//! The event types are defined in `pt-events` so external readers of
+//! `events.jsonl` share them; event kinds they do not know parse as
+//! [`SessionEventKind::Unknown`] and are skipped during replay.
+
+pub use pt_events::{SessionEvent, SessionEventKind};
+
+use super::{SessionError, SessionManifest, SessionMode, SessionState, StateTransition};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// Action attempt as derived from the log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActionRecord {
+    pub action_id: String,
+    pub pid: u32,
+    pub status: String,
+    pub ts: String,
+}
+
+/// Verification outcome as derived from the log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationRecord {
+    pub pid: u32,
+    pub action: String,
+    pub outcome: String,
+    pub verified: bool,
+    pub ts: String,
+}
+
+/// Session state derived by folding the event log.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionView {
+    pub state: SessionState,
+    pub state_history: Vec<StateTransition>,
+    pub mode: Option<SessionMode>,
+    pub label: Option<String>,
+    pub parent_session_id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    /// Processes seen by the most recent scan.
+    pub process_count: Option<usize>,
+    /// Candidates in the most recent plan.
+    pub candidate_count: Option<usize>,
+    pub plan_id: Option<String>,
+    pub actions: Vec<ActionRecord>,
+    pub verifications: Vec<VerificationRecord>,
+    /// Sequence number of the last event applied (0 = empty log).
+    pub last_seq: u64,
+}
+
+impl Default for SessionView {
+    fn default() -> Self {
+        Self {
+            state: SessionState::Created,
+            state_history: Vec::new(),
+            mode: None,
+            label: None,
+            parent_session_id: None,
+            created_at: None,
+            updated_at: None,
+            process_count: None,
+            candidate_count: None,
+            plan_id: None,
+            actions: Vec::new(),
+            verifications: Vec::new(),
+            last_seq: 0,
+        }
+    }
+}
+
+impl SessionView {
+    /// Derive the current view from a full log.
+    pub fn replay(events: &[SessionEvent]) -> Self {
+        let mut view = Self::default();
+        for event in events {
+            view.apply(event);
+        }
+        view
+    }
+
+    /// Derive the view from events recorded at or before `at`.
+    ///
+    /// Events with unparseable timestamps are skipped.
+    pub fn as_of(events: &[SessionEvent], at: DateTime<Utc>) -> Self {
+        let mut view = Self::default();
+        for event in events {
+            match DateTime::parse_from_rfc3339(&event.ts) {
+                Ok(ts) if ts.with_timezone(&Utc) <= at => view.apply(event),
+                _ => {}
+            }
+        }
+        view
+    }
+
+    /// Apply one event.
+    pub fn apply(&mut self, event: &SessionEvent) {
+        self.last_seq = event.seq;
+        self.updated_at = Some(event.ts.clone());
+        if let Some(state) = event.kind.implied_state() {
+            self.state = state;
+            self.state_history.push(StateTransition {
+                state,
+                ts: event.ts.clone(),
+            });
+        }
+
+        match &event.kind {
+            SessionEventKind::Created {
+                mode,
+                label,
+                parent_session_id,
+            } => {
+                self.mode = Some(*mode);
+                self.label = label.clone();
+                self.parent_session_id = parent_session_id.clone();
+                self.created_at = Some(event.ts.clone());
+            }
+            SessionEventKind::StateChanged { .. } => {}
+            SessionEventKind::Scanned { process_count } => {
+                self.process_count = Some(*process_count);
+            }
+            SessionEventKind::Planned {
+                candidate_count,
+                plan_id,
+            } => {
+                self.candidate_count = Some(*candidate_count);
+                self.plan_id = plan_id.clone();
+            }
+            SessionEventKind::ActionExecuted {
+                action_id,
+                pid,
+                status,
+            } => self.actions.push(ActionRecord {
+                action_id: action_id.clone(),
+                pid: *pid,
+                status: status.clone(),
+                ts: event.ts.clone(),
+            }),
+            SessionEventKind::Verified {
+                pid,
+                action,
+                outcome,
+                verified,
+            } => self.verifications.push(VerificationRecord {
+                pid: *pid,
+                action: action.clone(),
+                outcome: outcome.clone(),
+                verified: *verified,
+                ts: event.ts.clone(),
+            }),
+            SessionEventKind::Labeled { label } => self.label = Some(label.clone()),
+            SessionEventKind::Unknown => {}
+        }
+    }
+
+    /// Write the derived fields onto a manifest.
+    ///
+    /// Fields the log does not carry (schema version, error, creation time
+    /// of legacy sessions) are left as they are.
+    pub fn project_onto(&self, manifest: &mut SessionManifest) {
+        manifest.state = self.state;
+        manifest.state_history = self.state_history.clone();
+        manifest.label = self.label.clone();
+        if let Some(mode) = self.mode {
+            manifest.mode = mode;
+        }
+        if self.parent_session_id.is_some() {
+            manifest.parent_session_id = self.parent_session_id.clone();
+        }
+        if self.updated_at != self.created_at {
+            manifest.timing.updated_at = self.updated_at.clone();
+        }
+    }
+}
+
+/// Events equivalent to a manifest written before the log existed.
+pub(super) fn seed_from_manifest(manifest: &SessionManifest) -> Vec<SessionEvent> {
+    let mut events = Vec::with_capacity(manifest.state_history.len().max(1));
+    let mut history = manifest.state_history.iter();
+    let created_ts = history
+        .next()
+        .map(|t| t.ts.clone())
+        .unwrap_or_else(|| manifest.timing.created_at.clone());
+    events.push(SessionEvent {
+        seq: 1,
+        ts: created_ts,
+        kind: SessionEventKind::Created {
+            mode: manifest.mode,
+            label: manifest.label.clone(),
+            parent_session_id: manifest.parent_session_id.clone(),
+        },
+    });
+    for transition in history {
+        events.push(SessionEvent {
+            seq: events.len() as u64 + 1,
+            ts: transition.ts.clone(),
+            kind: SessionEventKind::StateChanged {
+                state: transition.state,
+            },
+        });
+    }
+    events
+}
+
+/// Read every complete event from `path` (missing file = empty log).
+///
+/// A final line that fails to parse is treated as a torn write and dropped;
+/// a bad line anywhere else is an error.
+pub(super) fn read_events(path: &Path) -> Result<Vec<SessionEvent>, SessionError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(SessionError::Io {
+                path: path.to_path_buf(),
+                source: e,
+            })
+        }
+    };
+
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut events = Vec::with_capacity(lines.len());
+    for (idx, line) in lines.iter().enumerate() {
+        match serde_json::from_str::<SessionEvent>(line) {
+            Ok(event) => events.push(event),
+            Err(_) if idx + 1 == lines.len() => break,
+            Err(e) => {
+                return Err(SessionError::Json {
+                    path: path.to_path_buf(),
+                    source: e,
+                })
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Append events to `path`, syncing before returning.
+pub(super) fn append_events(path: &Path, events: &[SessionEvent]) -> Result<(), SessionError> {
+    let io_err = |e| SessionError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    let mut buf = String::new();
+    for event in events {
+        let line = serde_json::to_string(event).map_err(|e| SessionError::Json {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        buf.push_str(&line);
+        buf.push('\n');
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_err)?;
+    // Cut off a torn previous append so it never ends up mid-log.
+    if let Some(complete) = torn_tail_start(path) {
+        file.set_len(complete).map_err(io_err)?;
+    }
+    file.write_all(buf.as_bytes()).map_err(io_err)?;
+    file.sync_data().map_err(io_err)
+}
+
+/// Length of the complete lines of `path`, if it ends with a torn line.
+fn torn_tail_start(path: &Path) -> Option<u64> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.last().is_none_or(|&b| b == b'\n') {
+        return None;
+    }
+    Some(
+        bytes
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i as u64 + 1),
+    )
+}
+
+/// Current time in the log's timestamp format.
+pub(super) fn now_ts() -> String {
+    Utc::now().to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pt_common::SessionId;
+
+    fn event(seq: u64, ts: &str, kind: SessionEventKind) -> SessionEvent {
+        SessionEvent {
+            seq,
+            ts: ts.to_string(),
+            kind,
+        }
+    }
+
+    fn sample_log() -> Vec<SessionEvent> {
+        vec![
+            event(
+                1,
+                "2026-01-15T12:00:00Z",
+                SessionEventKind::Created {
+                    mode: SessionMode::RobotPlan,
+                    label: None,
+                    parent_session_id: None,
+                },
+            ),
+            event(
+                2,
+                "2026-01-15T12:00:05Z",
+                SessionEventKind::Scanned { process_count: 312 },
+            ),
+            event(
+                3,
+                "2026-01-15T12:00:09Z",
+                SessionEventKind::Planned {
+                    candidate_count: 4,
+                    plan_id: None,
+                },
+            ),
+            event(
+                4,
+                "2026-01-15T12:05:00Z",
+                SessionEventKind::StateChanged {
+                    state: SessionState::Executing,
+                },
+            ),
+            event(
+                5,
+                "2026-01-15T12:05:02Z",
+                SessionEventKind::ActionExecuted {
+                    action_id: "act-1".to_string(),
+                    pid: 4242,
+                    status: "success".to_string(),
+                },
+            ),
+            event(
+                6,
+                "2026-01-15T12:05:03Z",
+                SessionEventKind::StateChanged {
+                    state: SessionState::Completed,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn replay_derives_current_state() {
+        let view = SessionView::replay(&sample_log());
+        assert_eq!(view.state, SessionState::Completed);
+        assert_eq!(view.state_history.len(), 4);
+        assert_eq!(view.process_count, Some(312));
+        assert_eq!(view.candidate_count, Some(4));
+        assert_eq!(view.actions.len(), 1);
+        assert_eq!(view.last_seq, 6);
+        assert_eq!(view.mode, Some(SessionMode::RobotPlan));
+    }
+
+    #[test]
+    fn as_of_reconstructs_earlier_knowledge() {
+        let at = DateTime::parse_from_rfc3339("2026-01-15T12:01:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let view = SessionView::as_of(&sample_log(), at);
+        assert_eq!(view.state, SessionState::Planned);
+        assert!(view.actions.is_empty());
+        assert_eq!(view.last_seq, 3);
+    }
+
+    #[test]
+    fn event_serializes_flat_with_tag() {
+        let json = serde_json::to_value(&sample_log()[1]).unwrap();
+        assert_eq!(json["event"], "scanned");
+        assert_eq!(json["seq"], 2);
+        assert_eq!(json["process_count"], 312);
+        let back: SessionEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(back, sample_log()[1]);
+    }
+
+    #[test]
+    fn torn_final_line_is_ignored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        append_events(&path, &sample_log()[..2]).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"seq\":3,\"ts\":\"2026")
+            .unwrap();
+
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 2);
+
+        // The next append cuts the torn line off and stays readable.
+        append_events(&path, &sample_log()[2..3]).unwrap();
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].seq, 3);
+    }
+
+    #[test]
+    fn corrupt_middle_line_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        std::fs::write(&path, "not json\n").unwrap();
+        append_events(&path, &sample_log()[..1]).unwrap();
+        assert!(read_events(&path).is_err());
+    }
+
+    #[test]
+    fn seed_matches_legacy_manifest() {
+        let sid = SessionId("pt-20260115-120000-lgcy".to_string());
+        let mut manifest =
+            SessionManifest::new(&sid, None, SessionMode::Interactive, Some("old".into()));
+        manifest.record_state(SessionState::Scanning);
+        manifest.record_state(SessionState::Planned);
+
+        let view = SessionView::replay(&seed_from_manifest(&manifest));
+        assert_eq!(view.state, SessionState::Planned);
+        assert_eq!(view.state_history.len(), 3);
+        assert_eq!(view.label.as_deref(), Some("old"));
+    }
+}
@@ -9,11 +9,20 @@
 //! - **Persistence**: serialize/deserialize fleet sessions for resume.
 //! - **Safety budgets**: track fleet-wide false-discovery rate (FDR) / alpha
 //!   spending across hosts.
+//! - **Signature hits**: per-signature match counts across hosts, the evidence
+//!   for promoting a staged signature.
+//! - **Workload offenders**: candidates in Kubernetes pods grouped by
+//!   namespace and owning workload across nodes.
+//! - **Host groups**: per-group totals and top offenders for inventory host
+//!   groups (`role=ci`, `role=db`, ...).
 
 use chrono::Utc;
+use pt_common::SessionId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
+use super::SessionStore;
 use crate::decision::{select_fdr, FdrCandidate, FdrMethod, TargetIdentity};
 
 // ---------------------------------------------------------------------------
@@ -30,12 +39,18 @@ pub struct FleetSession {
     pub hosts: Vec<HostEntry>,
     pub aggregate: FleetAggregate,
     pub safety_budget: SafetyBudget,
+    /// Supervisor-signature matches across hosts, most matches first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signature_hits: Vec<SignatureHitStats>,
 }
 
 /// Per-host entry in a fleet session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HostEntry {
     pub host_id: String,
+    /// Inventory host group, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     pub session_id: String,
     pub scanned_at: String,
     pub process_count: u32,
@@ -72,8 +87,53 @@ pub struct FleetAggregate {
     pub max_candidate_score: f64,
     /// Patterns recurring across multiple hosts.
     pub recurring_patterns: Vec<RecurringPattern>,
+    /// Candidates grouped by Kubernetes namespace and workload.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workload_offenders: Vec<WorkloadOffenders>,
+    /// Per-group metrics, when any host belongs to a group.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupAggregate>,
+}
+
+/// Group name for hosts without one, when other hosts are grouped.
+pub const UNGROUPED: &str = "ungrouped";
+
+/// Fleet metrics for one host group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupAggregate {
+    pub group: String,
+    pub host_count: usize,
+    /// Host IDs in this group, sorted.
+    pub hosts: Vec<String>,
+    pub total_processes: u32,
+    pub total_candidates: u32,
+    /// Classification counts across the group's hosts.
+    pub class_counts: HashMap<String, u32>,
+    /// Action counts across the group's hosts (after pooled FDR selection).
+    pub action_counts: HashMap<String, u32>,
+    /// Mean candidate score across the group.
+    pub mean_candidate_score: f64,
+    /// Max candidate score across the group.
+    pub max_candidate_score: f64,
+    /// Most frequent candidate signatures in the group.
+    pub top_offenders: Vec<GroupOffender>,
+}
+
+/// A candidate signature within one host group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupOffender {
+    pub signature: String,
+    /// Hosts of the group where the signature is a candidate.
+    pub host_count: usize,
+    /// Candidates across the group.
+    pub total_instances: u32,
+    /// Candidates recommended for kill after pooled FDR selection.
+    pub kill_count: u32,
 }
 
+/// Top offenders kept per group.
+const GROUP_TOP_OFFENDERS: usize = 5;
+
 /// A pattern (command signature) seen on multiple hosts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecurringPattern {
@@ -89,6 +149,38 @@ pub struct RecurringPattern {
     pub dominant_action: String,
 }
 
+/// Candidates of one Kubernetes workload (or of a namespace, for pods whose
+/// workload is unknown) across the fleet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadOffenders {
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
+    /// Number of hosts (nodes) with candidates from this workload.
+    pub host_count: usize,
+    /// Total candidates across all hosts.
+    pub total_instances: u32,
+    /// Candidates recommended for kill after pooled FDR selection.
+    pub kill_count: u32,
+    /// Host IDs with candidates from this workload.
+    pub hosts: Vec<String>,
+    /// Distinct candidate signatures, sorted.
+    pub signatures: Vec<String>,
+}
+
+/// How often a supervisor signature matched across a fleet session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureHitStats {
+    /// Signature name.
+    pub signature: String,
+    /// Matched processes across all hosts.
+    pub matches: u32,
+    /// Number of hosts with at least one match.
+    pub host_count: usize,
+    /// Matched processes per host.
+    pub by_host: HashMap<String, u32>,
+}
+
 /// Fleet-wide safety budget for coordinated FDR control.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyBudget {
@@ -141,16 +233,32 @@ pub struct CandidateInfo {
     pub score: f64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub e_value: Option<f64>,
+    /// Kubernetes placement, when the candidate runs in a pod.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<WorkloadRef>,
+}
+
+/// Kubernetes namespace and owning workload of a candidate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WorkloadRef {
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<String>,
 }
 
 /// Per-host input for fleet aggregation.
 #[derive(Debug, Clone)]
 pub struct HostInput {
     pub host_id: String,
+    /// Inventory host group, if any.
+    pub group: Option<String>,
     pub session_id: String,
     pub scanned_at: String,
     pub total_processes: u32,
     pub candidates: Vec<CandidateInfo>,
+    /// Processes matched per supervisor signature (all processes, not only
+    /// candidates).
+    pub signature_hits: HashMap<String, u32>,
 }
 
 // ---------------------------------------------------------------------------
@@ -173,6 +281,7 @@ pub fn create_fleet_session(
                 compute_host_summary(&input.host_id, &input.candidates, &selected_kill_keys);
             HostEntry {
                 host_id: input.host_id.clone(),
+                group: input.group.clone(),
                 session_id: input.session_id.clone(),
                 scanned_at: input.scanned_at.clone(),
                 process_count: input.total_processes,
@@ -192,6 +301,7 @@ pub fn create_fleet_session(
         hosts,
         aggregate,
         safety_budget,
+        signature_hits: aggregate_signature_hits(host_inputs),
     }
 }
 
@@ -261,6 +371,8 @@ fn compute_aggregate(
     };
 
     let recurring_patterns = find_recurring_patterns(inputs, selected_kill_keys);
+    let workload_offenders = find_workload_offenders(inputs, selected_kill_keys);
+    let groups = compute_group_aggregates(hosts, inputs, selected_kill_keys);
 
     FleetAggregate {
         total_hosts: hosts.len(),
@@ -271,9 +383,111 @@ fn compute_aggregate(
         mean_candidate_score: mean,
         max_candidate_score: max_score,
         recurring_patterns,
+        workload_offenders,
+        groups,
     }
 }
 
+/// Per-group totals. Empty unless at least one host has a group; hosts
+/// without one are then reported under [`UNGROUPED`].
+fn compute_group_aggregates(
+    hosts: &[HostEntry],
+    inputs: &[HostInput],
+    selected_kill_keys: &HashSet<String>,
+) -> Vec<GroupAggregate> {
+    if hosts.iter().all(|h| h.group.is_none()) {
+        return Vec::new();
+    }
+    let group_of = |group: &Option<String>| group.clone().unwrap_or_else(|| UNGROUPED.to_string());
+
+    let mut groups: HashMap<String, GroupAggregate> = HashMap::new();
+    for host in hosts {
+        let name = group_of(&host.group);
+        let entry = groups
+            .entry(name.clone())
+            .or_insert_with(|| GroupAggregate {
+                group: name,
+                host_count: 0,
+                hosts: Vec::new(),
+                total_processes: 0,
+                total_candidates: 0,
+                class_counts: HashMap::new(),
+                action_counts: HashMap::new(),
+                mean_candidate_score: 0.0,
+                max_candidate_score: 0.0,
+                top_offenders: Vec::new(),
+            });
+        entry.hosts.push(host.host_id.clone());
+        entry.total_processes += host.process_count;
+        for (k, v) in &host.summary.class_counts {
+            *entry.class_counts.entry(k.clone()).or_default() += v;
+        }
+        for (k, v) in &host.summary.action_counts {
+            *entry.action_counts.entry(k.clone()).or_default() += v;
+        }
+        // Weighted by candidates; divided out below.
+        entry.mean_candidate_score +=
+            host.summary.mean_candidate_score * host.candidate_count as f64;
+        entry.total_candidates += host.candidate_count;
+        entry.max_candidate_score = entry
+            .max_candidate_score
+            .max(host.summary.max_candidate_score);
+    }
+
+    // signature → (hosts, instances, kills), per group.
+    let mut offenders: HashMap<String, HashMap<&str, (HashSet<&str>, u32, u32)>> = HashMap::new();
+    for input in inputs {
+        let by_signature = offenders.entry(group_of(&input.group)).or_default();
+        for c in &input.candidates {
+            let entry = by_signature.entry(&c.signature).or_default();
+            entry.0.insert(&input.host_id);
+            entry.1 += 1;
+            if effective_action(&input.host_id, c, selected_kill_keys) == "kill" {
+                entry.2 += 1;
+            }
+        }
+    }
+
+    let mut groups: Vec<GroupAggregate> = groups
+        .into_values()
+        .map(|mut group| {
+            group.hosts.sort();
+            group.host_count = group.hosts.len();
+            if group.total_candidates > 0 {
+                group.mean_candidate_score /= group.total_candidates as f64;
+            }
+            let mut top: Vec<GroupOffender> = offenders
+                .remove(&group.group)
+                .unwrap_or_default()
+                .into_iter()
+                .map(
+                    |(signature, (hosts, total_instances, kill_count))| GroupOffender {
+                        signature: signature.to_string(),
+                        host_count: hosts.len(),
+                        total_instances,
+                        kill_count,
+                    },
+                )
+                .collect();
+            top.sort_by(|a, b| {
+                b.total_instances
+                    .cmp(&a.total_instances)
+                    .then(b.host_count.cmp(&a.host_count))
+                    .then_with(|| a.signature.cmp(&b.signature))
+            });
+            top.truncate(GROUP_TOP_OFFENDERS);
+            group.top_offenders = top;
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.total_candidates
+            .cmp(&a.total_candidates)
+            .then_with(|| a.group.cmp(&b.group))
+    });
+    groups
+}
+
 fn find_recurring_patterns(
     inputs: &[HostInput],
     selected_kill_keys: &HashSet<String>,
@@ -344,6 +558,90 @@ fn find_recurring_patterns(
     patterns
 }
 
+fn find_workload_offenders(
+    inputs: &[HostInput],
+    selected_kill_keys: &HashSet<String>,
+) -> Vec<WorkloadOffenders> {
+    let mut by_workload: HashMap<&WorkloadRef, WorkloadOffenders> = HashMap::new();
+    for input in inputs {
+        for c in &input.candidates {
+            let Some(workload) = &c.workload else {
+                continue;
+            };
+            let entry = by_workload
+                .entry(workload)
+                .or_insert_with(|| WorkloadOffenders {
+                    namespace: workload.namespace.clone(),
+                    workload: workload.workload.clone(),
+                    host_count: 0,
+                    total_instances: 0,
+                    kill_count: 0,
+                    hosts: Vec::new(),
+                    signatures: Vec::new(),
+                });
+            entry.total_instances += 1;
+            if effective_action(&input.host_id, c, selected_kill_keys) == "kill" {
+                entry.kill_count += 1;
+            }
+            if !entry.hosts.contains(&input.host_id) {
+                entry.hosts.push(input.host_id.clone());
+            }
+            if !entry.signatures.contains(&c.signature) {
+                entry.signatures.push(c.signature.clone());
+            }
+        }
+    }
+
+    let mut offenders: Vec<WorkloadOffenders> = by_workload
+        .into_values()
+        .map(|mut entry| {
+            entry.hosts.sort();
+            entry.signatures.sort();
+            entry.host_count = entry.hosts.len();
+            entry
+        })
+        .collect();
+    offenders.sort_by(|a, b| {
+        b.total_instances
+            .cmp(&a.total_instances)
+            .then(b.host_count.cmp(&a.host_count))
+            .then_with(|| a.namespace.cmp(&b.namespace))
+            .then_with(|| a.workload.cmp(&b.workload))
+    });
+    offenders
+}
+
+fn aggregate_signature_hits(inputs: &[HostInput]) -> Vec<SignatureHitStats> {
+    let mut by_signature: HashMap<&str, HashMap<String, u32>> = HashMap::new();
+    for input in inputs {
+        for (name, &count) in &input.signature_hits {
+            if count > 0 {
+                *by_signature
+                    .entry(name)
+                    .or_default()
+                    .entry(input.host_id.clone())
+                    .or_default() += count;
+            }
+        }
+    }
+
+    let mut hits: Vec<SignatureHitStats> = by_signature
+        .into_iter()
+        .map(|(name, by_host)| SignatureHitStats {
+            signature: name.to_string(),
+            matches: by_host.values().sum(),
+            host_count: by_host.len(),
+            by_host,
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.matches
+            .cmp(&a.matches)
+            .then_with(|| a.signature.cmp(&b.signature))
+    });
+    hits
+}
+
 fn compute_safety_budget(
     hosts: &[HostEntry],
     max_fdr: f64,
@@ -490,6 +788,26 @@ fn compute_pooled_fdr(host_inputs: &[HostInput], alpha: f64) -> (HashSet<String>
     (selected_keys, status)
 }
 
+/// Load a persisted fleet session (`fleet.json`) and its directory.
+pub fn load_fleet_session(fleet_session_id: &str) -> Result<(FleetSession, PathBuf), String> {
+    let store = SessionStore::from_env().map_err(|e| format!("session store error: {}", e))?;
+    let sid = SessionId(fleet_session_id.to_string());
+    let handle = store
+        .open(&sid)
+        .map_err(|e| format!("cannot open fleet session '{}': {}", fleet_session_id, e))?;
+    let fleet_path = handle.dir.join("fleet.json");
+    let content = std::fs::read_to_string(&fleet_path).map_err(|e| {
+        format!(
+            "cannot read fleet session '{}': {}",
+            fleet_path.display(),
+            e
+        )
+    })?;
+    let fleet: FleetSession =
+        serde_json::from_str(&content).map_err(|e| format!("parse error: {}", e))?;
+    Ok((fleet, handle.dir))
+}
+
 /// Record alpha spending for a host (after executing actions).
 pub fn record_alpha_spend(budget: &mut SafetyBudget, host_id: &str, spent: f64) {
     budget.alpha_spent += spent;
@@ -510,10 +828,12 @@ mod tests {
     fn host(id: &str, candidates: Vec<CandidateInfo>) -> HostInput {
         HostInput {
             host_id: id.to_string(),
+            group: None,
             session_id: format!("session-{}", id),
             scanned_at: "2026-02-01T12:00:00Z".to_string(),
             total_processes: 100 + candidates.len() as u32,
             candidates,
+            signature_hits: HashMap::new(),
         }
     }
 
@@ -525,6 +845,7 @@ mod tests {
             recommended_action: action.to_string(),
             score,
             e_value: None,
+            workload: None,
         }
     }
 
@@ -543,6 +864,7 @@ mod tests {
             recommended_action: action.to_string(),
             score,
             e_value: Some(e_value),
+            workload: None,
         }
     }
 
@@ -628,6 +950,109 @@ mod tests {
         assert_eq!(patterns[1].host_count, 2);
     }
 
+    #[test]
+    fn test_workload_offenders_grouped_across_nodes() {
+        let in_pod = |pid, sig: &str, namespace: &str, workload: Option<&str>| CandidateInfo {
+            workload: Some(WorkloadRef {
+                namespace: namespace.to_string(),
+                workload: workload.map(String::from),
+            }),
+            ..cand(pid, sig, "abandoned", "review", 0.6)
+        };
+        let inputs = vec![
+            host(
+                "node1",
+                vec![
+                    in_pod(1, "python", "shop", Some("web")),
+                    in_pod(2, "gunicorn", "shop", Some("web")),
+                    cand(3, "bash", "abandoned", "review", 0.6),
+                ],
+            ),
+            host(
+                "node2",
+                vec![
+                    in_pod(4, "python", "shop", Some("web")),
+                    in_pod(5, "java", "batch", None),
+                ],
+            ),
+        ];
+        let fleet = create_fleet_session("f6", None, &inputs, 0.05);
+        let offenders = &fleet.aggregate.workload_offenders;
+
+        assert_eq!(offenders.len(), 2);
+        assert_eq!(offenders[0].namespace, "shop");
+        assert_eq!(offenders[0].workload.as_deref(), Some("web"));
+        assert_eq!(offenders[0].total_instances, 3);
+        assert_eq!(offenders[0].hosts, vec!["node1", "node2"]);
+        assert_eq!(offenders[0].signatures, vec!["gunicorn", "python"]);
+        assert_eq!(offenders[1].namespace, "batch");
+        assert_eq!(offenders[1].workload, None);
+    }
+
+    #[test]
+    fn test_group_aggregates() {
+        let grouped = |id: &str, group: Option<&str>, candidates| HostInput {
+            group: group.map(String::from),
+            ..host(id, candidates)
+        };
+        let inputs = vec![
+            grouped(
+                "ci-1",
+                Some("ci"),
+                vec![
+                    cand(1, "jest", "abandoned", "review", 0.6),
+                    cand(2, "jest", "abandoned", "review", 0.8),
+                ],
+            ),
+            grouped(
+                "ci-2",
+                Some("ci"),
+                vec![cand(3, "jest", "abandoned", "review", 0.7)],
+            ),
+            grouped("db-1", Some("db"), Vec::new()),
+            grouped(
+                "misc",
+                None,
+                vec![cand(4, "bash", "abandoned", "review", 0.5)],
+            ),
+        ];
+        let fleet = create_fleet_session("f7", None, &inputs, 0.05);
+        let groups = &fleet.aggregate.groups;
+
+        let names: Vec<&str> = groups.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(names, vec!["ci", UNGROUPED, "db"]);
+        let ci = &groups[0];
+        assert_eq!(ci.hosts, vec!["ci-1", "ci-2"]);
+        assert_eq!(ci.total_candidates, 3);
+        assert!((ci.mean_candidate_score - 0.7).abs() < 1e-9);
+        assert_eq!(ci.top_offenders[0].signature, "jest");
+        assert_eq!(ci.top_offenders[0].host_count, 2);
+        assert_eq!(ci.top_offenders[0].total_instances, 3);
+        assert!(groups[2].top_offenders.is_empty());
+        assert_eq!(fleet.hosts[0].group.as_deref(), Some("ci"));
+
+        // No groups at all: no group section.
+        let fleet = create_fleet_session("f8", None, &[host("h1", Vec::new())], 0.05);
+        assert!(fleet.aggregate.groups.is_empty());
+    }
+
+    #[test]
+    fn test_signature_hits_aggregated_across_hosts() {
+        let mut h1 = host("h1", Vec::new());
+        h1.signature_hits = HashMap::from([("jest".to_string(), 3), ("vscode".to_string(), 1)]);
+        let mut h2 = host("h2", Vec::new());
+        h2.signature_hits = HashMap::from([("jest".to_string(), 2), ("idle".to_string(), 0)]);
+        let fleet = create_fleet_session("f5", None, &[h1, h2], 0.05);
+
+        assert_eq!(fleet.signature_hits.len(), 2);
+        assert_eq!(fleet.signature_hits[0].signature, "jest");
+        assert_eq!(fleet.signature_hits[0].matches, 5);
+        assert_eq!(fleet.signature_hits[0].host_count, 2);
+        assert_eq!(fleet.signature_hits[0].by_host["h1"], 3);
+        assert_eq!(fleet.signature_hits[1].signature, "vscode");
+        assert_eq!(fleet.signature_hits[1].host_count, 1);
+    }
+
     #[test]
     fn test_safety_budget() {
         let inputs = vec![
@@ -5,7 +5,8 @@
 //! - Resolve session root dir via XDG + env overrides
 //! - Create/open session directories
 //! - Persist `manifest.json` + `context.json` + optional `capabilities.json`
-//! - Update session state history in an append-only manner
+//! - Record session events in an append-only log (`events.jsonl`) and
+//!   derive the manifest's state history from it (see [`event_log`])
 //!
 //! NOTE: Higher-level commands (agent plan/apply/verify, etc.) build on these
 //! primitives. This module intentionally avoids any TUI assumptions.
@@ -14,15 +15,22 @@ pub mod compare;
 pub mod diff;
 #[cfg(test)]
 mod diff_tests;
+pub mod event_log;
 pub mod fleet;
 pub mod lifecycle;
+pub mod reconcile;
 pub mod resume;
 #[cfg(test)]
 mod resume_tests;
+pub mod runtime_history;
 pub mod snapshot_persist;
+pub mod trends;
 pub mod typestate;
 pub mod verify;
 
+pub use event_log::{SessionEvent, SessionEventKind, SessionView};
+pub use pt_events::{SessionMode, SessionState};
+
 use chrono::{DateTime, Duration, Utc};
 use pt_common::{schema::SCHEMA_VERSION, ProcessId, SessionId, StartId};
 use schemars::JsonSchema;
@@ -38,11 +46,12 @@ const SESSIONS_DIR_NAME: &str = "sessions";
 const MANIFEST_FILE: &str = "manifest.json";
 const CONTEXT_FILE: &str = "context.json";
 const CAPABILITIES_FILE: &str = "capabilities.json";
+const EVENTS_FILE: &str = "events.jsonl";
 
 const SCAN_DIR: &str = "scan";
 const INFERENCE_DIR: &str = "inference";
 const DECISION_DIR: &str = "decision";
-const ACTION_DIR: &str = "action";
+pub(crate) const ACTION_DIR: &str = "action";
 const TELEMETRY_DIR: &str = "telemetry";
 const LOGS_DIR: &str = "logs";
 const EXPORTS_DIR: &str = "exports";
@@ -76,30 +85,6 @@ pub enum SessionError {
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SessionState {
-    Created,
-    Scanning,
-    Planned,
-    Executing,
-    Completed,
-    Cancelled,
-    Failed,
-    Archived,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SessionMode {
-    Interactive,
-    RobotPlan,
-    RobotApply,
-    DaemonAlert,
-    ScanOnly,
-    Export,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SnapshotConfigFile {
     pub path: Option<String>,
@@ -206,6 +191,17 @@ pub struct SessionTiming {
     pub updated_at: Option<String>,
 }
 
+/// Marks a session that ran under a `--experiment` configuration overlay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentTag {
+    pub name: String,
+    /// SHA-256 of the overlay (see `Experiment::overlay_hash`).
+    pub overlay_hash: String,
+    /// Promoted sessions feed learning/calibration like regular sessions.
+    #[serde(default)]
+    pub promoted: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionManifest {
     pub schema_version: String,
@@ -220,6 +216,8 @@ pub struct SessionManifest {
     pub timing: SessionTiming,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<ExperimentTag>,
 }
 
 impl SessionManifest {
@@ -246,9 +244,15 @@ impl SessionManifest {
                 updated_at: None,
             },
             error: None,
+            experiment: None,
         }
     }
 
+    /// Whether this session's outcomes may feed learning and calibration.
+    pub fn feeds_learning(&self) -> bool {
+        self.experiment.as_ref().is_none_or(|tag| tag.promoted)
+    }
+
     pub fn record_state(&mut self, state: SessionState) {
         let now = Utc::now().to_rfc3339();
         self.state = state;
@@ -315,6 +319,15 @@ pub struct SessionSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actions_count: Option<u32>,
     pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<ExperimentTag>,
+}
+
+impl SessionSummary {
+    /// Whether this session's outcomes may feed learning and calibration.
+    pub fn feeds_learning(&self) -> bool {
+        self.experiment.as_ref().is_none_or(|tag| tag.promoted)
+    }
 }
 
 /// Options for listing sessions.
@@ -389,6 +402,10 @@ impl SessionStore {
             id: session_id,
             dir,
         };
+        event_log::append_events(
+            &handle.events_path(),
+            &event_log::seed_from_manifest(manifest),
+        )?;
         handle.write_manifest(manifest)?;
         Ok(handle)
     }
@@ -498,6 +515,7 @@ impl SessionStore {
                 candidates_count,
                 actions_count,
                 path,
+                experiment: manifest.experiment,
             });
         }
 
@@ -577,6 +595,10 @@ impl SessionHandle {
         self.dir.join(SNAPSHOT_FILE)
     }
 
+    pub fn events_path(&self) -> PathBuf {
+        self.dir.join(EVENTS_FILE)
+    }
+
     pub fn read_manifest(&self) -> Result<SessionManifest, SessionError> {
         let path = self.manifest_path();
         let content = std::fs::read_to_string(&path).map_err(|e| SessionError::Io {
@@ -608,8 +630,67 @@ impl SessionHandle {
     }
 
     pub fn update_state(&self, new_state: SessionState) -> Result<SessionManifest, SessionError> {
+        self.record_event(SessionEventKind::StateChanged { state: new_state })
+    }
+
+    /// Read the session's event log (empty for sessions that predate it).
+    pub fn read_events(&self) -> Result<Vec<SessionEvent>, SessionError> {
+        event_log::read_events(&self.events_path())
+    }
+
+    /// Append an event to the log and refresh the manifest from it.
+    ///
+    /// The log is synced before the manifest is written, so a crash between
+    /// the two leaves a stale manifest that [`Self::rebuild_manifest`] fixes.
+    pub fn record_event(&self, kind: SessionEventKind) -> Result<SessionManifest, SessionError> {
         let mut manifest = self.read_manifest()?;
-        manifest.record_state(new_state);
+        let mut events = self.read_events()?;
+        let mut pending = Vec::new();
+        if events.is_empty() {
+            pending = event_log::seed_from_manifest(&manifest);
+            events.extend(pending.iter().cloned());
+        }
+        let event = SessionEvent {
+            seq: events.last().map(|e| e.seq).unwrap_or(0) + 1,
+            ts: event_log::now_ts(),
+            kind,
+        };
+        events.push(event.clone());
+        pending.push(event);
+        event_log::append_events(&self.events_path(), &pending)?;
+
+        SessionView::replay(&events).project_onto(&mut manifest);
+        self.write_manifest(&manifest)?;
+        Ok(manifest)
+    }
+
+    /// Re-derive the manifest's state fields from the event log.
+    ///
+    /// Used for crash recovery: a stale manifest is brought up to date and a
+    /// missing one is recreated. A session without a log is returned as-is.
+    pub fn rebuild_manifest(&self) -> Result<SessionManifest, SessionError> {
+        let events = self.read_events()?;
+        if events.is_empty() {
+            return self.read_manifest();
+        }
+        let view = SessionView::replay(&events);
+        let mut manifest = match self.read_manifest() {
+            Ok(manifest) => manifest,
+            Err(SessionError::Io { .. }) if !self.manifest_path().exists() => {
+                let mut manifest = SessionManifest::new(
+                    &self.id,
+                    None,
+                    view.mode.unwrap_or(SessionMode::Interactive),
+                    None,
+                );
+                if let Some(created_at) = &view.created_at {
+                    manifest.timing.created_at = created_at.clone();
+                }
+                manifest
+            }
+            Err(e) => return Err(e),
+        };
+        view.project_onto(&mut manifest);
         self.write_manifest(&manifest)?;
         Ok(manifest)
     }
@@ -1401,6 +1482,7 @@ mod tests {
             candidates_count: None,
             actions_count: None,
             path: PathBuf::from("/tmp/test"),
+            experiment: None,
         };
         let json = serde_json::to_string(&s).unwrap();
         assert!(!json.contains("label"));
@@ -1420,4 +1502,95 @@ mod tests {
         let back: StateTransition = serde_json::from_str(&json).unwrap();
         assert_eq!(back.state, SessionState::Scanning);
     }
+
+    // ── event log ───────────────────────────────────────────────────
+
+    #[test]
+    fn create_seeds_event_log() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = make_store(tmp.path());
+        let sid = SessionId("pt-20260115-120000-evt1".to_string());
+        let manifest =
+            SessionManifest::new(&sid, None, SessionMode::RobotPlan, Some("nightly".into()));
+        let handle = store.create(&manifest).unwrap();
+
+        let events = handle.read_events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seq, 1);
+        assert!(matches!(
+            events[0].kind,
+            SessionEventKind::Created {
+                mode: SessionMode::RobotPlan,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn record_event_projects_onto_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = make_store(tmp.path());
+        let sid = SessionId("pt-20260115-120000-evt2".to_string());
+        let manifest = SessionManifest::new(&sid, None, SessionMode::RobotPlan, None);
+        let handle = store.create(&manifest).unwrap();
+
+        handle
+            .record_event(SessionEventKind::Scanned { process_count: 10 })
+            .unwrap();
+        handle
+            .record_event(SessionEventKind::Planned {
+                candidate_count: 2,
+                plan_id: None,
+            })
+            .unwrap();
+        let m = handle
+            .record_event(SessionEventKind::Labeled {
+                label: "retry".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(m.state, SessionState::Planned);
+        assert_eq!(m.state_history.len(), 2);
+        assert_eq!(m.label.as_deref(), Some("retry"));
+        let events = handle.read_events().unwrap();
+        assert_eq!(
+            events.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn legacy_session_is_seeded_on_first_event() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = make_store(tmp.path());
+        let sid = SessionId("pt-20260115-120000-evt3".to_string());
+        let manifest = SessionManifest::new(&sid, None, SessionMode::Interactive, None);
+        let handle = store.create(&manifest).unwrap();
+        std::fs::remove_file(handle.events_path()).unwrap();
+
+        let m = handle.update_state(SessionState::Scanning).unwrap();
+        assert_eq!(m.state_history.len(), 2);
+        assert_eq!(handle.read_events().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rebuild_manifest_recovers_from_stale_and_missing_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = make_store(tmp.path());
+        let sid = SessionId("pt-20260115-120000-evt4".to_string());
+        let manifest = SessionManifest::new(&sid, None, SessionMode::RobotApply, None);
+        let handle = store.create(&manifest).unwrap();
+        handle.update_state(SessionState::Executing).unwrap();
+
+        // Simulate a crash after the log append but before the manifest write.
+        handle.write_manifest(&manifest).unwrap();
+        let rebuilt = handle.rebuild_manifest().unwrap();
+        assert_eq!(rebuilt.state, SessionState::Executing);
+
+        std::fs::remove_file(handle.manifest_path()).unwrap();
+        let rebuilt = handle.rebuild_manifest().unwrap();
+        assert_eq!(rebuilt.state, SessionState::Executing);
+        assert_eq!(rebuilt.mode, SessionMode::RobotApply);
+        assert_eq!(rebuilt.timing.created_at, manifest.timing.created_at);
+    }
 }
@@ -0,0 +1,665 @@
+//! Plan-versus-outcome reconciliation.
+//!
+//! Joins `decision/plan.json` with `action/outcomes.jsonl` and, when
+//! `agent verify` has run, `action/verifications.json`, producing one row per
+//! planned action: what was planned, what the executor reported, what
+//! verification found afterwards, and how much memory was predicted versus
+//! actually recovered. The result is written to
+//! `action/reconciliation.json`, which the report's Actions section reads.
+//!
+//! Both plan shapes are accepted: the executor plan (`actions`, keyed by
+//! `action_id`) and the agent plan (`candidates`, keyed by PID).
+//!
+//! When the plan was built with `--deep`, candidates carry PSS/USS figures
+//! alongside RSS; both are carried through so over-counted shared memory
+//! shows up next to the prediction.
+
+use super::{ACTION_DIR, DECISION_DIR};
+use chrono::Utc;
+use pt_common::schema::SCHEMA_VERSION;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the reconciliation artifact under `action/`.
+pub const RECONCILIATION_FILE: &str = "reconciliation.json";
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Actions whose success is expected to release the target's memory.
+const MEMORY_RELEASING_ACTIONS: &[&str] = &["kill", "restart"];
+
+#[derive(Debug, Error)]
+pub enum ReconcileError {
+    #[error("no plan.json in {0}")]
+    MissingPlan(PathBuf),
+
+    #[error("invalid {file}: {message}")]
+    Invalid { file: String, message: String },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where a planned action ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileStatus {
+    /// Applied, and verification confirmed the target gone or stopped.
+    Verified,
+    /// Applied; no verification has run yet.
+    Applied,
+    /// Applied, but the target came back.
+    Respawned,
+    /// Applied, but verification still found the target running.
+    StillRunning,
+    /// The executor attempted the action and it failed.
+    Failed,
+    /// Held back by the plan, constraints, pre-checks or an identity check.
+    Blocked,
+    /// Never attempted (not selected, dry run, shadow, deferred).
+    NotApplied,
+}
+
+impl ReconcileStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Verified => "verified",
+            Self::Applied => "applied",
+            Self::Respawned => "respawned",
+            Self::StillRunning => "still_running",
+            Self::Failed => "failed",
+            Self::Blocked => "blocked",
+            Self::NotApplied => "not_applied",
+        }
+    }
+}
+
+/// One planned action joined with its outcome and verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciledAction {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<String>,
+    pub pid: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_id: Option<String>,
+    pub command: String,
+    /// Planned action (`kill`, `pause`, ...).
+    pub action: String,
+    pub status: ReconcileStatus,
+    /// Executor status from `outcomes.jsonl`, if the action was reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    /// Verification outcome from `verifications.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<String>,
+    /// PID of the replacement process when the target respawned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respawn_pid: Option<u32>,
+    /// Memory the plan expected this action to free.
+    pub predicted_memory_mb: f64,
+    /// Proportional set size of the target at plan time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicted_pss_mb: Option<f64>,
+    /// Unique set size of the target at plan time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicted_uss_mb: Option<f64>,
+    /// Memory verified as released (zero until verification confirms it).
+    pub recovered_memory_mb: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_ms: Option<u64>,
+    /// Why the action was blocked or failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Totals over the reconciled actions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReconcileSummary {
+    pub planned: usize,
+    pub verified: usize,
+    pub applied: usize,
+    pub respawned: usize,
+    pub still_running: usize,
+    pub failed: usize,
+    pub blocked: usize,
+    pub not_applied: usize,
+    pub predicted_memory_mb: f64,
+    /// PSS total over the actions that have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicted_pss_mb: Option<f64>,
+    /// USS total over the actions that have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicted_uss_mb: Option<f64>,
+    pub recovered_memory_mb: f64,
+    /// Recovered over predicted memory; `None` when nothing was predicted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_ratio: Option<f64>,
+}
+
+/// The reconciliation artifact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reconciliation {
+    pub schema_version: String,
+    pub session_id: String,
+    pub generated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan_generated_at: Option<String>,
+    /// Whether `verifications.json` was available.
+    pub verified: bool,
+    pub summary: ReconcileSummary,
+    pub actions: Vec<ReconciledAction>,
+}
+
+/// A planned action, from either plan shape.
+struct PlannedAction {
+    action_id: Option<String>,
+    pid: u32,
+    start_id: Option<String>,
+    command: String,
+    action: String,
+    blocked: bool,
+    predicted_memory_mb: f64,
+    pss_mb: Option<f64>,
+    uss_mb: Option<f64>,
+}
+
+/// Reconcile the session in `session_dir` from its on-disk artifacts.
+pub fn reconcile_session(
+    session_id: &str,
+    session_dir: &Path,
+) -> Result<Reconciliation, ReconcileError> {
+    let plan_path = session_dir.join(DECISION_DIR).join("plan.json");
+    if !plan_path.exists() {
+        return Err(ReconcileError::MissingPlan(session_dir.to_path_buf()));
+    }
+    let plan = read_json(&plan_path)?;
+
+    let action_dir = session_dir.join(ACTION_DIR);
+    let outcomes = match std::fs::read_to_string(action_dir.join("outcomes.jsonl")) {
+        Ok(content) => content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let verifications_path = action_dir.join("verifications.json");
+    let verifications = if verifications_path.exists() {
+        Some(read_json(&verifications_path)?)
+    } else {
+        None
+    };
+
+    Ok(reconcile(
+        session_id,
+        &plan,
+        &outcomes,
+        verifications.as_ref(),
+    ))
+}
+
+/// Join a plan with its outcome entries and optional verification report.
+pub fn reconcile(
+    session_id: &str,
+    plan: &Value,
+    outcomes: &[Value],
+    verifications: Option<&Value>,
+) -> Reconciliation {
+    // Later entries win: `apply --resume` appends to the same log.
+    let mut by_action_id: HashMap<&str, &Value> = HashMap::new();
+    let mut by_pid: HashMap<u64, &Value> = HashMap::new();
+    for entry in outcomes {
+        if let Some(id) = entry.get("action_id").and_then(Value::as_str) {
+            by_action_id.insert(id, entry);
+        }
+        if let Some(pid) = entry.get("pid").and_then(Value::as_u64) {
+            by_pid.insert(pid, entry);
+        }
+    }
+
+    let mut verified_by_pid: HashMap<u64, &Value> = HashMap::new();
+    if let Some(report) = verifications {
+        for outcome in report
+            .get("action_outcomes")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(pid) = outcome.pointer("/target/pid").and_then(Value::as_u64) {
+                verified_by_pid.insert(pid, outcome);
+            }
+        }
+    }
+
+    let mut actions = Vec::new();
+    let mut summary = ReconcileSummary::default();
+    for planned in planned_actions(plan) {
+        let entry = planned
+            .action_id
+            .as_deref()
+            .and_then(|id| by_action_id.get(id))
+            .or_else(|| by_pid.get(&(planned.pid as u64)))
+            .copied();
+        let verification = verified_by_pid.get(&(planned.pid as u64)).copied();
+        let row = reconcile_action(planned, entry, verification);
+
+        summary.planned += 1;
+        match row.status {
+            ReconcileStatus::Verified => summary.verified += 1,
+            ReconcileStatus::Applied => summary.applied += 1,
+            ReconcileStatus::Respawned => summary.respawned += 1,
+            ReconcileStatus::StillRunning => summary.still_running += 1,
+            ReconcileStatus::Failed => summary.failed += 1,
+            ReconcileStatus::Blocked => summary.blocked += 1,
+            ReconcileStatus::NotApplied => summary.not_applied += 1,
+        }
+        summary.predicted_memory_mb += row.predicted_memory_mb;
+        if let Some(pss) = row.predicted_pss_mb {
+            *summary.predicted_pss_mb.get_or_insert(0.0) += pss;
+        }
+        if let Some(uss) = row.predicted_uss_mb {
+            *summary.predicted_uss_mb.get_or_insert(0.0) += uss;
+        }
+        summary.recovered_memory_mb += row.recovered_memory_mb;
+        actions.push(row);
+    }
+    if summary.predicted_memory_mb > 0.0 {
+        summary.recovery_ratio = Some(summary.recovered_memory_mb / summary.predicted_memory_mb);
+    }
+
+    Reconciliation {
+        schema_version: SCHEMA_VERSION.to_string(),
+        session_id: session_id.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        plan_generated_at: plan
+            .get("generated_at")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        verified: verifications.is_some(),
+        summary,
+        actions,
+    }
+}
+
+/// Write the artifact to `action/reconciliation.json`, returning its path.
+pub fn write_reconciliation(
+    session_dir: &Path,
+    reconciliation: &Reconciliation,
+) -> Result<PathBuf, ReconcileError> {
+    let dir = session_dir.join(ACTION_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(RECONCILIATION_FILE);
+    let content =
+        serde_json::to_string_pretty(reconciliation).map_err(|e| ReconcileError::Invalid {
+            file: RECONCILIATION_FILE.to_string(),
+            message: e.to_string(),
+        })?;
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+fn reconcile_action(
+    planned: PlannedAction,
+    entry: Option<&Value>,
+    verification: Option<&Value>,
+) -> ReconciledAction {
+    let outcome = entry
+        .and_then(|e| e.get("status"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let verify_outcome = verification
+        .and_then(|v| v.get("outcome"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let mut status = match outcome.as_deref() {
+        Some("success" | "already_completed") => ReconcileStatus::Applied,
+        Some("failed" | "timeout" | "permission_denied" | "unsupported_platform") => {
+            ReconcileStatus::Failed
+        }
+        Some(
+            "identity_mismatch"
+            | "identity_check_failed"
+            | "precheck_blocked"
+            | "blocked_by_plan"
+            | "blocked_by_constraints"
+            | "stage_halted",
+        ) => ReconcileStatus::Blocked,
+        Some(_) => ReconcileStatus::NotApplied,
+        None if planned.blocked => ReconcileStatus::Blocked,
+        None => ReconcileStatus::NotApplied,
+    };
+    if status == ReconcileStatus::Applied {
+        status = match verify_outcome.as_deref() {
+            Some("confirmed_dead" | "confirmed_stopped" | "pid_reused" | "cascaded") => {
+                ReconcileStatus::Verified
+            }
+            Some("respawned") => ReconcileStatus::Respawned,
+            Some("still_running" | "timeout") => ReconcileStatus::StillRunning,
+            _ => ReconcileStatus::Applied,
+        };
+    }
+
+    let releases_memory = MEMORY_RELEASING_ACTIONS.contains(&planned.action.as_str());
+    let predicted_memory_mb = if releases_memory {
+        planned.predicted_memory_mb
+    } else {
+        0.0
+    };
+    let (predicted_pss_mb, predicted_uss_mb) = if releases_memory {
+        (planned.pss_mb, planned.uss_mb)
+    } else {
+        (None, None)
+    };
+    let recovered_memory_mb = if releases_memory && status == ReconcileStatus::Verified {
+        verification
+            .and_then(|v| v.pointer("/resources_freed/memory_mb"))
+            .and_then(Value::as_f64)
+            .unwrap_or(predicted_memory_mb)
+    } else {
+        0.0
+    };
+
+    let reason = entry
+        .and_then(|e| {
+            e.get("reason")
+                .or_else(|| e.get("error"))
+                .or_else(|| e.get("precheck"))
+        })
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+            (status == ReconcileStatus::Blocked && entry.is_none())
+                .then(|| "blocked in plan".to_string())
+        });
+
+    let command = if planned.command.is_empty() {
+        verification
+            .and_then(|v| {
+                v.pointer("/target/cmd_short")
+                    .or_else(|| v.pointer("/target/cmd_full"))
+            })
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    } else {
+        planned.command
+    };
+
+    ReconciledAction {
+        action_id: planned.action_id,
+        pid: planned.pid,
+        start_id: planned.start_id,
+        command,
+        action: planned.action,
+        status,
+        outcome,
+        verification: verify_outcome,
+        respawn_pid: verification
+            .and_then(|v| v.pointer("/respawn_detected/pid"))
+            .and_then(Value::as_u64)
+            .map(|pid| pid as u32),
+        predicted_memory_mb,
+        predicted_pss_mb,
+        predicted_uss_mb,
+        recovered_memory_mb,
+        time_ms: entry.and_then(|e| e.get("time_ms")).and_then(Value::as_u64),
+        reason,
+    }
+}
+
+/// Planned actions from `actions` (executor plan) or, failing that,
+/// non-`keep` `candidates` (agent plan).
+fn planned_actions(plan: &Value) -> Vec<PlannedAction> {
+    let candidates: HashMap<u64, &Value> = plan
+        .get("candidates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|c| Some((c.get("pid")?.as_u64()?, c)))
+        .collect();
+
+    let actions = plan
+        .get("actions")
+        .and_then(Value::as_array)
+        .filter(|a| !a.is_empty());
+    if let Some(actions) = actions {
+        return actions
+            .iter()
+            .filter_map(|a| {
+                let pid = a.pointer("/target/pid")?.as_u64()?;
+                let candidate = candidates.get(&pid).copied();
+                let predicted = a
+                    .pointer("/cost_benefit/benefit/uss_freed_bytes")
+                    .and_then(Value::as_u64)
+                    .map(|bytes| bytes as f64 / MIB)
+                    .or_else(|| candidate.and_then(candidate_memory_mb))
+                    .unwrap_or(0.0);
+                Some(PlannedAction {
+                    action_id: a
+                        .get("action_id")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    pid: pid as u32,
+                    start_id: a
+                        .pointer("/target/start_id")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    command: candidate.map(candidate_command).unwrap_or_default(),
+                    action: a
+                        .get("action")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    blocked: a.get("blocked").and_then(Value::as_bool).unwrap_or(false),
+                    predicted_memory_mb: predicted,
+                    pss_mb: candidate.and_then(|c| candidate_figure_mb(c, "pss_mb")),
+                    uss_mb: candidate.and_then(|c| candidate_figure_mb(c, "uss_mb")),
+                })
+            })
+            .collect();
+    }
+
+    plan.get("candidates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|c| {
+            let action = c.get("recommended_action")?.as_str()?;
+            if action == "keep" {
+                return None;
+            }
+            Some(PlannedAction {
+                action_id: None,
+                pid: c.get("pid")?.as_u64()? as u32,
+                start_id: c
+                    .get("start_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                command: candidate_command(c),
+                action: action.to_string(),
+                blocked: false,
+                predicted_memory_mb: candidate_memory_mb(c).unwrap_or(0.0),
+                pss_mb: candidate_figure_mb(c, "pss_mb"),
+                uss_mb: candidate_figure_mb(c, "uss_mb"),
+            })
+        })
+        .collect()
+}
+
+/// Blast-radius memory of an agent plan candidate, as `agent verify` counts it.
+fn candidate_memory_mb(candidate: &Value) -> Option<f64> {
+    candidate
+        .pointer("/blast_radius/memory_mb")
+        .or_else(|| candidate.get("memory_mb"))
+        .and_then(Value::as_f64)
+}
+
+/// A figure from the candidate's `memory` breakdown (`pss_mb`, `uss_mb`).
+fn candidate_figure_mb(candidate: &Value, key: &str) -> Option<f64> {
+    candidate
+        .get("memory")
+        .and_then(|m| m.get(key))
+        .and_then(Value::as_f64)
+}
+
+fn candidate_command(candidate: &Value) -> String {
+    ["command_short", "cmd_short", "command", "cmd_full"]
+        .iter()
+        .find_map(|key| candidate.get(*key).and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn read_json(path: &Path) -> Result<Value, ReconcileError> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| ReconcileError::Invalid {
+        file: path.display().to_string(),
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn agent_plan() -> Value {
+        json!({
+            "session_id": "pt-20260105-120000-abcd",
+            "generated_at": "2026-01-05T12:00:00Z",
+            "candidates": [
+                {"pid": 10, "start_id": "10:100", "command_short": "node",
+                 "recommended_action": "kill", "blast_radius": {"memory_mb": 512.0},
+                 "memory": {"rss_mb": 900, "pss_mb": 512, "uss_mb": 400}},
+                {"pid": 11, "start_id": "11:100", "command_short": "jest",
+                 "recommended_action": "kill", "blast_radius": {"memory_mb": 256.0}},
+                {"pid": 12, "start_id": "12:100", "command_short": "vim",
+                 "recommended_action": "pause", "blast_radius": {"memory_mb": 64.0}},
+                {"pid": 13, "command_short": "bash", "recommended_action": "keep"},
+                {"pid": 14, "command_short": "sleep",
+                 "recommended_action": "kill", "blast_radius": {"memory_mb": 8.0}},
+            ]
+        })
+    }
+
+    #[test]
+    fn joins_outcomes_and_verification() {
+        let outcomes = vec![
+            json!({"pid": 10, "status": "success", "time_ms": 40}),
+            json!({"pid": 11, "status": "success"}),
+            json!({"pid": 12, "status": "precheck_blocked", "reason": "has tty"}),
+        ];
+        let verifications = json!({
+            "action_outcomes": [
+                {"target": {"pid": 10}, "outcome": "confirmed_dead",
+                 "resources_freed": {"memory_mb": 500.0}},
+                {"target": {"pid": 11}, "outcome": "respawned",
+                 "respawn_detected": {"pid": 99}},
+            ]
+        });
+
+        let r = reconcile("s", &agent_plan(), &outcomes, Some(&verifications));
+        assert!(r.verified);
+        assert_eq!(r.plan_generated_at.as_deref(), Some("2026-01-05T12:00:00Z"));
+        let status: Vec<ReconcileStatus> = r.actions.iter().map(|a| a.status).collect();
+        assert_eq!(
+            status,
+            vec![
+                ReconcileStatus::Verified,
+                ReconcileStatus::Respawned,
+                ReconcileStatus::Blocked,
+                ReconcileStatus::NotApplied,
+            ]
+        );
+
+        assert_eq!(r.actions[0].recovered_memory_mb, 500.0);
+        assert_eq!(r.actions[0].time_ms, Some(40));
+        assert_eq!(r.actions[1].respawn_pid, Some(99));
+        assert_eq!(r.actions[1].recovered_memory_mb, 0.0);
+        assert_eq!(r.actions[2].reason.as_deref(), Some("has tty"));
+        // Pausing frees nothing durably.
+        assert_eq!(r.actions[2].predicted_memory_mb, 0.0);
+
+        assert_eq!(r.summary.planned, 4);
+        assert_eq!(r.summary.predicted_memory_mb, 776.0);
+        assert_eq!(r.summary.recovered_memory_mb, 500.0);
+        assert_eq!(r.actions[0].predicted_pss_mb, Some(512.0));
+        assert_eq!(r.actions[0].predicted_uss_mb, Some(400.0));
+        assert_eq!(r.actions[1].predicted_pss_mb, None);
+        assert_eq!(r.summary.predicted_uss_mb, Some(400.0));
+        assert!((r.summary.recovery_ratio.unwrap() - 500.0 / 776.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unverified_success_counts_as_applied() {
+        let outcomes = vec![json!({"pid": 10, "status": "success"})];
+        let r = reconcile("s", &agent_plan(), &outcomes, None);
+        assert!(!r.verified);
+        assert_eq!(r.actions[0].status, ReconcileStatus::Applied);
+        assert_eq!(r.summary.recovered_memory_mb, 0.0);
+        assert_eq!(r.summary.applied, 1);
+    }
+
+    #[test]
+    fn executor_plan_matches_by_action_id() {
+        let plan = json!({
+            "plan_id": "p1",
+            "actions": [
+                {"action_id": "a1", "target": {"pid": 20, "start_id": "boot:1:20"},
+                 "action": "kill", "blocked": false,
+                 "cost_benefit": {"benefit": {"uss_freed_bytes": 104857600u64}}},
+                {"action_id": "a2", "target": {"pid": 21, "start_id": "boot:1:21"},
+                 "action": "kill", "blocked": true},
+            ]
+        });
+        let outcomes = vec![
+            json!({"action_id": "a1", "pid": 20, "status": "failed", "time_ms": 5}),
+            json!({"action_id": "a1", "pid": 20, "status": "success", "time_ms": 7}),
+        ];
+        let r = reconcile("s", &plan, &outcomes, None);
+        assert_eq!(r.actions[0].action_id.as_deref(), Some("a1"));
+        assert_eq!(r.actions[0].status, ReconcileStatus::Applied);
+        assert_eq!(r.actions[0].time_ms, Some(7));
+        assert_eq!(r.actions[0].predicted_memory_mb, 100.0);
+        assert_eq!(r.actions[1].status, ReconcileStatus::Blocked);
+        assert_eq!(r.actions[1].reason.as_deref(), Some("blocked in plan"));
+    }
+
+    #[test]
+    fn reads_and_writes_session_artifacts() {
+        let tmp = tempdir().unwrap();
+        assert!(matches!(
+            reconcile_session("s", tmp.path()),
+            Err(ReconcileError::MissingPlan(_))
+        ));
+
+        std::fs::create_dir_all(tmp.path().join(DECISION_DIR)).unwrap();
+        std::fs::write(
+            tmp.path().join(DECISION_DIR).join("plan.json"),
+            agent_plan().to_string(),
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join(ACTION_DIR)).unwrap();
+        std::fs::write(
+            tmp.path().join(ACTION_DIR).join("outcomes.jsonl"),
+            "{\"pid\":14,\"status\":\"dry_run\"}\n\n",
+        )
+        .unwrap();
+
+        let r = reconcile_session("s", tmp.path()).unwrap();
+        assert_eq!(r.actions[3].outcome.as_deref(), Some("dry_run"));
+        assert_eq!(r.actions[3].status, ReconcileStatus::NotApplied);
+
+        let path = write_reconciliation(tmp.path(), &r).unwrap();
+        assert_eq!(path, tmp.path().join(ACTION_DIR).join(RECONCILIATION_FILE));
+        let back: Reconciliation =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(back.summary, r.summary);
+    }
+}
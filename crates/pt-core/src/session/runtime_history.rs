@@ -0,0 +1,38 @@
+//! Fold session snapshots into the per-category runtime prior table.
+//!
+//! Every `agent plan` / `agent snapshot` run writes `scan/snapshot.json` with
+//! the process inventory it saw. Each inventory record's age is one
+//! observation for the runtime distribution of its signature category (see
+//! [`crate::inference::runtime_prior`]).
+
+use super::{SessionSnapshot, SessionSummary, SnapshotProcess, SNAPSHOT_FILE};
+use std::collections::HashSet;
+
+/// Collect `(category, runtime_secs)` observations from a session snapshot.
+///
+/// `categorize` maps an inventory record to its category; records it
+/// rejects are skipped. Returns `None` when the session has no readable
+/// snapshot yet, so callers do not mark it as ingested.
+pub fn snapshot_runtime_samples<F>(
+    summary: &SessionSummary,
+    categorize: F,
+) -> Option<Vec<(String, f64)>>
+where
+    F: Fn(&SnapshotProcess) -> Option<String>,
+{
+    let content = std::fs::read_to_string(summary.path.join(SNAPSHOT_FILE)).ok()?;
+    let snapshot: SessionSnapshot = serde_json::from_str(&content).ok()?;
+
+    let mut seen = HashSet::new();
+    Some(
+        snapshot
+            .inventory
+            .records
+            .iter()
+            .filter(|record| record.elapsed_secs > 0 && seen.insert(&record.start_id.0))
+            .filter_map(|record| {
+                categorize(record).map(|category| (category, record.elapsed_secs as f64))
+            })
+            .collect(),
+    )
+}
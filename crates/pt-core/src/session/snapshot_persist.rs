@@ -1,7 +1,8 @@
 //! Session snapshot persistence with schema validation, redaction, and integrity.
 //!
 //! Provides atomic writes of individual session artifacts (inventory, inference,
-//! plan) and validated reads with version compatibility checks.
+//! plan, interactive TUI state) and validated reads with version compatibility
+//! checks.
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -14,6 +15,9 @@ const INVENTORY_FILE: &str = "scan/inventory.json";
 const INFERENCE_FILE: &str = "inference/results.json";
 const PLAN_FILE: &str = "decision/plan.json";
 const META_FILE: &str = "run_metadata.json";
+const TUI_STATE_FILE: &str = "tui/state.json";
+const EXPERIMENT_FILE: &str = "experiment/replay.json";
+const EVIDENCE_FILE: &str = "inference/evidence.json";
 
 /// Redaction sentinel for sensitive strings.
 const REDACTED: &str = "<REDACTED>";
@@ -161,6 +165,69 @@ pub struct RunMetadata {
     pub tags: BTreeMap<String, String>,
 }
 
+// ---------------------------------------------------------------------------
+// TUI state
+// ---------------------------------------------------------------------------
+
+/// A selected candidate, keyed by identity so a reused PID is never reselected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PersistedSelection {
+    pub pid: u32,
+    pub start_id: String,
+}
+
+/// Interactive `run` state needed to resume an interrupted session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TuiStateArtifact {
+    /// Candidates the user had selected.
+    pub selected: Vec<PersistedSelection>,
+    /// Resource recovery goal in effect, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Experiment replay
+// ---------------------------------------------------------------------------
+
+/// Evidence for one candidate, kept so decisions can be replayed under
+/// another configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentRecord {
+    pub pid: u32,
+    pub start_id: String,
+    pub comm: String,
+    pub evidence: crate::inference::Evidence,
+    #[serde(default)]
+    pub zombie: bool,
+    #[serde(default)]
+    pub disksleep: bool,
+}
+
+/// Experiment artifact: the overlay a session ran under plus its evidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentArtifact {
+    pub experiment: crate::config::Experiment,
+    pub records: Vec<ExperimentRecord>,
+}
+
+/// Evidence of one candidate together with the decision it produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    #[serde(flatten)]
+    pub replay: ExperimentRecord,
+    pub posterior: crate::inference::ClassScores,
+    pub action: crate::decision::Action,
+}
+
+/// Evidence ledger of a plan: what every evaluated candidate was decided on.
+///
+/// Bundles carry it so plans can be re-evaluated without the original host.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvidenceArtifact {
+    pub records: Vec<EvidenceRecord>,
+}
+
 // ---------------------------------------------------------------------------
 // Redaction
 // ---------------------------------------------------------------------------
@@ -402,6 +469,39 @@ pub fn persist_run_metadata(
     persist_artifact(handle, META_FILE, envelope)
 }
 
+/// Write the interactive TUI state for a session.
+pub fn persist_tui_state(
+    handle: &SessionHandle,
+    session_id: &str,
+    host_id: &str,
+    state: TuiStateArtifact,
+) -> Result<PathBuf, SessionError> {
+    let envelope = ArtifactEnvelope::new(session_id, host_id, state);
+    persist_artifact(handle, TUI_STATE_FILE, envelope)
+}
+
+/// Write the experiment replay artifact for a session.
+pub fn persist_experiment(
+    handle: &SessionHandle,
+    session_id: &str,
+    host_id: &str,
+    artifact: ExperimentArtifact,
+) -> Result<PathBuf, SessionError> {
+    let envelope = ArtifactEnvelope::new(session_id, host_id, artifact);
+    persist_artifact(handle, EXPERIMENT_FILE, envelope)
+}
+
+/// Write the evidence ledger for a session.
+pub fn persist_evidence(
+    handle: &SessionHandle,
+    session_id: &str,
+    host_id: &str,
+    artifact: EvidenceArtifact,
+) -> Result<PathBuf, SessionError> {
+    let envelope = ArtifactEnvelope::new(session_id, host_id, artifact);
+    persist_artifact(handle, EVIDENCE_FILE, envelope)
+}
+
 /// Load the inventory artifact with validation.
 pub fn load_inventory(
     handle: &SessionHandle,
@@ -442,6 +542,27 @@ pub fn load_run_metadata(
     load_artifact(handle, META_FILE)
 }
 
+/// Load the interactive TUI state with validation.
+pub fn load_tui_state(
+    handle: &SessionHandle,
+) -> Result<ArtifactEnvelope<TuiStateArtifact>, SessionError> {
+    load_artifact(handle, TUI_STATE_FILE)
+}
+
+/// Load the experiment replay artifact with validation.
+pub fn load_experiment(
+    handle: &SessionHandle,
+) -> Result<ArtifactEnvelope<ExperimentArtifact>, SessionError> {
+    load_artifact(handle, EXPERIMENT_FILE)
+}
+
+/// Load the evidence ledger with validation.
+pub fn load_evidence(
+    handle: &SessionHandle,
+) -> Result<ArtifactEnvelope<EvidenceArtifact>, SessionError> {
+    load_artifact(handle, EVIDENCE_FILE)
+}
+
 /// Check which artifacts are present in a session directory.
 pub fn list_artifacts(handle: &SessionHandle) -> Vec<String> {
     let mut present = Vec::new();
@@ -450,6 +571,9 @@ pub fn list_artifacts(handle: &SessionHandle) -> Vec<String> {
         ("inference", INFERENCE_FILE),
         ("plan", PLAN_FILE),
         ("run_metadata", META_FILE),
+        ("tui_state", TUI_STATE_FILE),
+        ("experiment", EXPERIMENT_FILE),
+        ("evidence", EVIDENCE_FILE),
     ] {
         if handle.dir.join(rel).exists() {
             present.push(name.to_string());
@@ -741,6 +865,98 @@ mod tests {
         assert_eq!(loaded.payload.cores, 16);
     }
 
+    #[test]
+    fn test_persist_load_tui_state() {
+        let tmp = TempDir::new().unwrap();
+        let handle = make_handle(&tmp);
+        let state = TuiStateArtifact {
+            selected: vec![PersistedSelection {
+                pid: 1234,
+                start_id: "boot1:12345:1234".to_string(),
+            }],
+            goal: Some("free 4GB RAM".to_string()),
+        };
+
+        persist_tui_state(&handle, "s1", "h1", state).unwrap();
+        let loaded = load_tui_state(&handle).unwrap();
+        assert_eq!(loaded.payload.selected.len(), 1);
+        assert_eq!(loaded.payload.selected[0].pid, 1234);
+        assert_eq!(loaded.payload.goal.as_deref(), Some("free 4GB RAM"));
+        assert!(list_artifacts(&handle).contains(&"tui_state".to_string()));
+    }
+
+    #[test]
+    fn test_persist_load_experiment() {
+        let tmp = TempDir::new().unwrap();
+        let handle = make_handle(&tmp);
+        let artifact = ExperimentArtifact {
+            experiment: crate::config::Experiment {
+                name: "my-tweak".to_string(),
+                description: None,
+                priors: Some(serde_json::json!({"classes": {}})),
+                policy: None,
+            },
+            records: vec![ExperimentRecord {
+                pid: 1234,
+                start_id: "boot1:12345:1234".to_string(),
+                comm: "node".to_string(),
+                evidence: crate::inference::Evidence {
+                    cpu: Some(crate::inference::CpuEvidence::Fraction { occupancy: 0.01 }),
+                    runtime_seconds: Some(86_400.0),
+                    orphan: Some(true),
+                    ..Default::default()
+                },
+                zombie: false,
+                disksleep: false,
+            }],
+        };
+
+        persist_experiment(&handle, "s1", "h1", artifact).unwrap();
+        let loaded = load_experiment(&handle).unwrap();
+        assert_eq!(loaded.payload.experiment.name, "my-tweak");
+        assert_eq!(loaded.payload.records.len(), 1);
+        assert_eq!(loaded.payload.records[0].evidence.orphan, Some(true));
+        assert!(list_artifacts(&handle).contains(&"experiment".to_string()));
+    }
+
+    #[test]
+    fn test_persist_load_evidence() {
+        let tmp = TempDir::new().unwrap();
+        let handle = make_handle(&tmp);
+        let artifact = EvidenceArtifact {
+            records: vec![EvidenceRecord {
+                replay: ExperimentRecord {
+                    pid: 4321,
+                    start_id: "boot1:54321:4321".to_string(),
+                    comm: "pytest".to_string(),
+                    evidence: crate::inference::Evidence {
+                        runtime_seconds: Some(7_200.0),
+                        tty: Some(false),
+                        ..Default::default()
+                    },
+                    zombie: false,
+                    disksleep: true,
+                },
+                posterior: crate::inference::ClassScores {
+                    useful: 0.1,
+                    useful_bad: 0.05,
+                    abandoned: 0.8,
+                    zombie: 0.05,
+                },
+                action: crate::decision::Action::Kill,
+            }],
+        };
+
+        persist_evidence(&handle, "s1", "h1", artifact).unwrap();
+        let loaded = load_evidence(&handle).unwrap();
+        let record = &loaded.payload.records[0];
+        assert_eq!(record.replay.pid, 4321);
+        assert!(record.replay.disksleep);
+        assert_eq!(record.replay.evidence.runtime_seconds, Some(7_200.0));
+        assert_eq!(record.action, crate::decision::Action::Kill);
+        assert!(list_artifacts(&handle).contains(&"evidence".to_string()));
+    }
+
     #[test]
     fn test_integrity_check_detects_tampering() {
         let tmp = TempDir::new().unwrap();
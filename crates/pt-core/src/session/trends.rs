@@ -0,0 +1,241 @@
+//! Fold session artifacts into long-term trend rollups.
+//!
+//! Reads `decision/plan.json`, `action/outcomes.jsonl` and
+//! `action/verifications.json` from a session directory and produces the
+//! per-category counters stored in the telemetry trends table.
+
+use super::{SessionState, SessionSummary, ACTION_DIR, DECISION_DIR};
+use chrono::{DateTime, Duration, Utc};
+use pt_telemetry::trends::{SessionRollup, TrendCounts};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Category used for candidates without a signature match.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Sessions younger than this are only ingested once they reach a terminal state.
+const SETTLE_AFTER_HOURS: i64 = 24;
+
+/// Outcome statuses that count as an attempted action.
+const ATTEMPTED_STATUSES: &[&str] = &["success", "failed", "timeout", "permission_denied"];
+
+/// Whether a session will no longer change and can be folded into trends.
+pub fn is_settled(summary: &SessionSummary, now: DateTime<Utc>) -> bool {
+    if matches!(
+        summary.state,
+        SessionState::Completed
+            | SessionState::Cancelled
+            | SessionState::Failed
+            | SessionState::Archived
+    ) {
+        return true;
+    }
+    DateTime::parse_from_rfc3339(&summary.created_at)
+        .map(|created| {
+            now.signed_duration_since(created.with_timezone(&Utc))
+                >= Duration::hours(SETTLE_AFTER_HOURS)
+        })
+        .unwrap_or(false)
+}
+
+/// Build the trend contribution of one session.
+///
+/// Returns `None` when the session has no parseable creation time.
+pub fn session_rollup(summary: &SessionSummary) -> Option<SessionRollup> {
+    let day = DateTime::parse_from_rfc3339(&summary.created_at)
+        .ok()?
+        .with_timezone(&Utc)
+        .date_naive();
+    let dir = &summary.path;
+
+    let mut by_category: BTreeMap<String, TrendCounts> = BTreeMap::new();
+    let pid_category = read_candidates(dir, &mut by_category);
+
+    let mut bump = |pid: Option<u64>, f: &dyn Fn(&mut TrendCounts)| {
+        let category = pid
+            .and_then(|pid| pid_category.get(&pid))
+            .cloned()
+            .unwrap_or_else(|| UNCATEGORIZED.to_string());
+        f(by_category.entry(category).or_default());
+    };
+
+    if let Ok(content) = std::fs::read_to_string(dir.join(ACTION_DIR).join("outcomes.jsonl")) {
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            let status = entry.get("status").and_then(|s| s.as_str()).unwrap_or("");
+            if ATTEMPTED_STATUSES.contains(&status) {
+                bump(entry.get("pid").and_then(|p| p.as_u64()), &|c| {
+                    c.actions += 1
+                });
+            }
+        }
+    }
+
+    if let Some(outcomes) = std::fs::read_to_string(dir.join(ACTION_DIR).join("verifications.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("action_outcomes").and_then(|o| o.as_array()).cloned())
+    {
+        for outcome in &outcomes {
+            let pid = outcome
+                .get("target")
+                .and_then(|t| t.get("pid"))
+                .and_then(|p| p.as_u64());
+            let freed_mb = outcome
+                .get("resources_freed")
+                .and_then(|r| r.get("memory_mb"))
+                .and_then(|m| m.as_f64())
+                .unwrap_or(0.0);
+            match outcome.get("outcome").and_then(|o| o.as_str()) {
+                Some("confirmed_dead") | Some("confirmed_stopped") => bump(pid, &|c| {
+                    c.recoveries += 1;
+                    c.memory_recovered_mb += freed_mb;
+                }),
+                Some("respawned") => bump(pid, &|c| c.false_positives += 1),
+                _ => {}
+            }
+        }
+    }
+
+    Some(SessionRollup {
+        session_id: summary.session_id.clone(),
+        day,
+        host_id: summary
+            .host_id
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        categories: by_category
+            .into_iter()
+            .filter(|(_, c)| !c.is_empty())
+            .collect(),
+    })
+}
+
+/// Count plan candidates per category and return the pid -> category map.
+fn read_candidates(
+    dir: &Path,
+    by_category: &mut BTreeMap<String, TrendCounts>,
+) -> HashMap<u64, String> {
+    let mut pid_category = HashMap::new();
+    let Some(candidates) = std::fs::read_to_string(dir.join(DECISION_DIR).join("plan.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("candidates").and_then(|c| c.as_array()).cloned())
+    else {
+        return pid_category;
+    };
+
+    for candidate in &candidates {
+        let category = candidate
+            .get("signature")
+            .and_then(|s| s.get("category"))
+            .and_then(|c| c.as_str())
+            .unwrap_or(UNCATEGORIZED)
+            .to_string();
+        by_category.entry(category.clone()).or_default().candidates += 1;
+        if let Some(pid) = candidate.get("pid").and_then(|p| p.as_u64()) {
+            pid_category.insert(pid, category);
+        }
+    }
+    pid_category
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SessionMode;
+    use tempfile::tempdir;
+
+    fn summary(dir: &Path, state: SessionState, created_at: &str) -> SessionSummary {
+        SessionSummary {
+            session_id: "pt-20260105-120000-abcd".to_string(),
+            created_at: created_at.to_string(),
+            state,
+            mode: SessionMode::RobotPlan,
+            label: None,
+            host_id: Some("host-a".to_string()),
+            candidates_count: None,
+            actions_count: None,
+            path: dir.to_path_buf(),
+            experiment: None,
+        }
+    }
+
+    #[test]
+    fn test_session_rollup_counts_per_category() {
+        let tmp = tempdir().unwrap();
+        let decision = tmp.path().join(DECISION_DIR);
+        let action = tmp.path().join(ACTION_DIR);
+        std::fs::create_dir_all(&decision).unwrap();
+        std::fs::create_dir_all(&action).unwrap();
+
+        let plan = serde_json::json!({
+            "candidates": [
+                {"pid": 10, "signature": {"category": "TestRunner"}},
+                {"pid": 11, "signature": {"category": "TestRunner"}},
+                {"pid": 12, "signature": {"category": null}},
+            ]
+        });
+        std::fs::write(decision.join("plan.json"), plan.to_string()).unwrap();
+        std::fs::write(
+            action.join("outcomes.jsonl"),
+            "{\"pid\":10,\"status\":\"success\"}\n{\"pid\":11,\"status\":\"success\"}\n{\"pid\":12,\"status\":\"dry_run\"}\n",
+        )
+        .unwrap();
+        let verifications = serde_json::json!({
+            "action_outcomes": [
+                {"target": {"pid": 10}, "outcome": "confirmed_dead", "resources_freed": {"memory_mb": 128.0}},
+                {"target": {"pid": 11}, "outcome": "respawned"},
+            ]
+        });
+        std::fs::write(action.join("verifications.json"), verifications.to_string()).unwrap();
+
+        let rollup = session_rollup(&summary(
+            tmp.path(),
+            SessionState::Completed,
+            "2026-01-05T12:00:00Z",
+        ))
+        .unwrap();
+
+        assert_eq!(rollup.day.to_string(), "2026-01-05");
+        assert_eq!(rollup.host_id, "host-a");
+        let runner = &rollup
+            .categories
+            .iter()
+            .find(|(c, _)| c == "TestRunner")
+            .unwrap()
+            .1;
+        assert_eq!(runner.candidates, 2);
+        assert_eq!(runner.actions, 2);
+        assert_eq!(runner.recoveries, 1);
+        assert_eq!(runner.false_positives, 1);
+        assert_eq!(runner.memory_recovered_mb, 128.0);
+        let other = &rollup
+            .categories
+            .iter()
+            .find(|(c, _)| c == UNCATEGORIZED)
+            .unwrap()
+            .1;
+        assert_eq!(other.candidates, 1);
+        assert_eq!(other.actions, 0);
+    }
+
+    #[test]
+    fn test_is_settled() {
+        let tmp = tempdir().unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-01-05T18:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let fresh = summary(tmp.path(), SessionState::Planned, "2026-01-05T12:00:00Z");
+        assert!(!is_settled(&fresh, now));
+
+        let done = summary(tmp.path(), SessionState::Completed, "2026-01-05T12:00:00Z");
+        assert!(is_settled(&done, now));
+
+        let stale = summary(tmp.path(), SessionState::Planned, "2026-01-03T12:00:00Z");
+        assert!(is_settled(&stale, now));
+    }
+}
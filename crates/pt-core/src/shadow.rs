@@ -4,7 +4,12 @@
 
 use crate::collect::ProcessRecord;
 use crate::decision::{Action, DecisionOutcome};
-use crate::inference::{ClassScores, Confidence, EvidenceLedger};
+use crate::inference::{
+    behavior_baselines_path, fd_trends_path, per_sample_log_lr, shadow_sprt_path,
+    BehaviorBaselineError, BehaviorBaselineStore, BehaviorSample, ClassScores, Confidence,
+    EvidenceLedger, FdTrendError, FdTrendStore, PosteriorResult, ProcessSprt, ShadowSprtConfig,
+    ShadowSprtError, ShadowSprtTracker,
+};
 use chrono::Utc;
 use pt_telemetry::shadow::{
     BeliefState, EventType, Observation, ProcessEvent, ShadowStorage, ShadowStorageConfig,
@@ -23,6 +28,9 @@ pub enum ShadowRecordError {
     Storage(ShadowStorageError),
     Io(std::io::Error),
     Json(serde_json::Error),
+    Baseline(BehaviorBaselineError),
+    Sprt(ShadowSprtError),
+    FdTrend(FdTrendError),
 }
 
 impl From<ShadowStorageError> for ShadowRecordError {
@@ -31,6 +39,24 @@ impl From<ShadowStorageError> for ShadowRecordError {
     }
 }
 
+impl From<BehaviorBaselineError> for ShadowRecordError {
+    fn from(err: BehaviorBaselineError) -> Self {
+        ShadowRecordError::Baseline(err)
+    }
+}
+
+impl From<FdTrendError> for ShadowRecordError {
+    fn from(err: FdTrendError) -> Self {
+        ShadowRecordError::FdTrend(err)
+    }
+}
+
+impl From<ShadowSprtError> for ShadowRecordError {
+    fn from(err: ShadowSprtError) -> Self {
+        ShadowRecordError::Sprt(err)
+    }
+}
+
 impl From<std::io::Error> for ShadowRecordError {
     fn from(err: std::io::Error) -> Self {
         ShadowRecordError::Io(err)
@@ -64,6 +90,11 @@ pub struct ShadowRecorder {
     pending_path: PathBuf,
     miss_threshold: u32,
     had_records: bool,
+    baselines: BehaviorBaselineStore,
+    baselines_path: PathBuf,
+    fd_trends: FdTrendStore,
+    fd_trends_path: PathBuf,
+    sprt: Option<(ShadowSprtTracker, ShadowSprtConfig)>,
 }
 
 impl ShadowRecorder {
@@ -78,6 +109,22 @@ impl ShadowRecorder {
                 HashMap::new()
             }
         };
+        let baselines_path = behavior_baselines_path(&storage.config().base_dir);
+        let baselines = match BehaviorBaselineStore::load(&baselines_path) {
+            Ok(store) => store,
+            Err(err) => {
+                eprintln!("shadow mode: failed to load behavior baselines: {}", err);
+                BehaviorBaselineStore::default()
+            }
+        };
+        let fd_trends_path = fd_trends_path(&storage.config().base_dir);
+        let fd_trends = match FdTrendStore::load(&fd_trends_path) {
+            Ok(store) => store,
+            Err(err) => {
+                eprintln!("shadow mode: failed to load fd trends: {}", err);
+                FdTrendStore::default()
+            }
+        };
         Ok(Self {
             storage,
             recorded: 0,
@@ -87,6 +134,11 @@ impl ShadowRecorder {
             pending_path,
             miss_threshold: DEFAULT_MISS_THRESHOLD,
             had_records: false,
+            baselines,
+            baselines_path,
+            fd_trends,
+            fd_trends_path,
+            sprt: None,
         })
     }
 
@@ -131,7 +183,8 @@ impl ShadowRecorder {
         };
 
         let mut events = Vec::new();
-        if let Some(event) = build_evidence_event(ledger, &proc.comm) {
+        let inputs = ShadowInputs::from_process(proc);
+        if let Some(event) = build_evidence_event(ledger, &proc.comm, &inputs) {
             events.push(event);
         }
 
@@ -164,10 +217,70 @@ impl ShadowRecorder {
         Ok(())
     }
 
+    /// Fold a candidate's footprint into its fingerprint baseline.
+    pub fn observe_behavior(&mut self, proc: &ProcessRecord, sample: &BehaviorSample) {
+        let fingerprint = crate::snooze::process_fingerprint(proc.uid, &proc.cmd);
+        self.baselines
+            .observe(&fingerprint, &proc.comm, sample, Utc::now());
+    }
+
+    /// Append a candidate's open-fd count to its identity's trend.
+    pub fn observe_fd_count(&mut self, proc: &ProcessRecord, fd_count: u32) {
+        self.fd_trends.observe(
+            &compute_identity_hash(proc),
+            proc.pid.0,
+            &proc.comm,
+            fd_count,
+            Utc::now(),
+        );
+    }
+
+    /// Accumulate per-sample evidence across runs in an SPRT per process identity.
+    pub fn enable_sprt(&mut self, config: ShadowSprtConfig) -> Result<(), ShadowRecordError> {
+        config.boundaries()?;
+        let tracker = ShadowSprtTracker::load(&self.sprt_path())?;
+        self.sprt = Some((tracker, config));
+        Ok(())
+    }
+
+    /// Fold a candidate's posterior into its SPRT; None unless SPRT is enabled.
+    pub fn observe_sprt(
+        &mut self,
+        proc: &ProcessRecord,
+        result: &PosteriorResult,
+    ) -> Result<Option<ProcessSprt>, ShadowRecordError> {
+        let Some((tracker, config)) = self.sprt.as_mut() else {
+            return Ok(None);
+        };
+        let test = tracker.observe(
+            &compute_identity_hash(proc),
+            proc.pid.0,
+            &proc.comm,
+            per_sample_log_lr(result),
+            config,
+            Utc::now(),
+        )?;
+        Ok(Some(test.clone()))
+    }
+
+    fn sprt_path(&self) -> PathBuf {
+        shadow_sprt_path(&self.storage.config().base_dir)
+    }
+
     pub fn flush(&mut self) -> Result<(), ShadowRecordError> {
         self.record_outcomes_for_missing()?;
         self.storage.flush()?;
         persist_pending(&self.pending_path, &self.pending)?;
+        if !self.baselines.is_empty() {
+            self.baselines.save(&self.baselines_path, Utc::now())?;
+        }
+        if !self.fd_trends.is_empty() {
+            self.fd_trends.save(&self.fd_trends_path, Utc::now())?;
+        }
+        let sprt_path = self.sprt_path();
+        if let Some((tracker, config)) = self.sprt.as_mut() {
+            tracker.save(&sprt_path, config, Utc::now())?;
+        }
         Ok(())
     }
 
@@ -262,7 +375,39 @@ fn confidence_score(confidence: Confidence) -> f32 {
     }
 }
 
-fn compute_identity_hash(proc: &ProcessRecord) -> String {
+/// Evidence inputs stored with each observation, so likelihood parameters can
+/// be refit from shadow history.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShadowInputs {
+    pub cpu_occupancy: f64,
+    pub runtime_seconds: f64,
+    pub orphan: bool,
+    pub tty: bool,
+}
+
+impl ShadowInputs {
+    pub fn from_process(proc: &ProcessRecord) -> Self {
+        Self {
+            cpu_occupancy: (proc.cpu_percent / 100.0).clamp(0.0, 1.0),
+            runtime_seconds: proc.elapsed.as_secs_f64(),
+            orphan: proc.is_orphan(),
+            tty: proc.has_tty(),
+        }
+    }
+
+    /// Inputs recorded in an observation's evidence snapshot, if any.
+    pub fn from_events(events: &[ProcessEvent]) -> Option<Self> {
+        events
+            .iter()
+            .filter(|event| event.event_type == EventType::EvidenceSnapshot)
+            .filter_map(|event| event.details.as_deref())
+            .filter_map(|details| serde_json::from_str::<serde_json::Value>(details).ok())
+            .find_map(|details| serde_json::from_value(details.get("inputs")?.clone()).ok())
+    }
+}
+
+/// Identity hash keying a process's shadow observations.
+pub fn compute_identity_hash(proc: &ProcessRecord) -> String {
     let mut hasher = Sha256::new();
     hasher.update(proc.uid.to_le_bytes());
     hasher.update(proc.start_id.0.as_bytes());
@@ -272,7 +417,11 @@ fn compute_identity_hash(proc: &ProcessRecord) -> String {
     hex::encode(&digest[..8])
 }
 
-fn build_evidence_event(ledger: &EvidenceLedger, comm: &str) -> Option<ProcessEvent> {
+fn build_evidence_event(
+    ledger: &EvidenceLedger,
+    comm: &str,
+    inputs: &ShadowInputs,
+) -> Option<ProcessEvent> {
     let top: Vec<_> = ledger
         .bayes_factors
         .iter()
@@ -294,6 +443,7 @@ fn build_evidence_event(ledger: &EvidenceLedger, comm: &str) -> Option<ProcessEv
         "why_summary": ledger.why_summary,
         "top_evidence": ledger.top_evidence,
         "bayes_factors": top,
+        "inputs": inputs,
     })
     .to_string();
     Some(ProcessEvent {
@@ -329,6 +479,11 @@ fn persist_pending(
     Ok(())
 }
 
+/// Shadow storage directory, honoring the data-dir overrides.
+pub fn shadow_base_dir() -> PathBuf {
+    shadow_config_from_env().base_dir
+}
+
 fn shadow_config_from_env() -> ShadowStorageConfig {
     let mut config = ShadowStorageConfig::default();
     if let Some(base) = resolve_data_dir_override() {
@@ -374,6 +529,7 @@ mod tests {
             elapsed: std::time::Duration::from_secs(1),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         };
 
         let h1 = compute_identity_hash(&proc);
@@ -403,6 +559,7 @@ mod tests {
             elapsed: std::time::Duration::from_secs(1),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         };
 
         let h1 = compute_identity_hash(&proc);
@@ -479,6 +636,7 @@ mod tests {
             elapsed: std::time::Duration::from_secs(3600),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         }
     }
 
@@ -519,6 +677,7 @@ mod tests {
             delta_bits: delta,
             direction: direction.to_string(),
             strength: strength.to_string(),
+            weight: None,
         }
     }
 
@@ -688,7 +847,7 @@ mod tests {
         let factors = vec![make_bf("age", 3.0, "supports abandoned", "strong")];
         let top = vec!["old process".to_string()];
         let ledger = make_ledger(Confidence::High, factors, top, "Likely abandoned");
-        let event = build_evidence_event(&ledger, "sleep").unwrap();
+        let event = build_evidence_event(&ledger, "sleep", &ShadowInputs::default()).unwrap();
         assert_eq!(event.event_type, EventType::EvidenceSnapshot);
         let details: serde_json::Value =
             serde_json::from_str(event.details.as_ref().unwrap()).unwrap();
@@ -697,10 +856,25 @@ mod tests {
         assert!(details["bayes_factors"].as_array().unwrap().len() == 1);
     }
 
+    #[test]
+    fn evidence_event_round_trips_inputs() {
+        let factors = vec![make_bf("age", 3.0, "supports abandoned", "strong")];
+        let ledger = make_ledger(Confidence::High, factors, vec![], "Likely abandoned");
+        let inputs = ShadowInputs {
+            cpu_occupancy: 0.02,
+            runtime_seconds: 7200.0,
+            orphan: true,
+            tty: false,
+        };
+        let event = build_evidence_event(&ledger, "sleep", &inputs).unwrap();
+        assert_eq!(ShadowInputs::from_events(&[event]), Some(inputs));
+        assert_eq!(ShadowInputs::from_events(&[]), None);
+    }
+
     #[test]
     fn evidence_event_none_when_empty() {
         let ledger = make_ledger(Confidence::Low, vec![], vec![], "");
-        assert!(build_evidence_event(&ledger, "bash").is_none());
+        assert!(build_evidence_event(&ledger, "bash", &ShadowInputs::default()).is_none());
     }
 
     #[test]
@@ -711,7 +885,7 @@ mod tests {
             vec!["some evidence".to_string()],
             "why",
         );
-        let event = build_evidence_event(&ledger, "node");
+        let event = build_evidence_event(&ledger, "node", &ShadowInputs::default());
         assert!(event.is_some());
     }
 
@@ -719,7 +893,7 @@ mod tests {
     fn evidence_event_with_only_bayes_factors() {
         let factors = vec![make_bf("cpu", 1.5, "supports useful", "weak")];
         let ledger = make_ledger(Confidence::Low, factors, vec![], "");
-        let event = build_evidence_event(&ledger, "python");
+        let event = build_evidence_event(&ledger, "python", &ShadowInputs::default());
         assert!(event.is_some());
     }
 
@@ -732,7 +906,7 @@ mod tests {
             make_bf("d", 4.0, "supports abandoned", "decisive"),
         ];
         let ledger = make_ledger(Confidence::High, factors, vec![], "");
-        let event = build_evidence_event(&ledger, "x").unwrap();
+        let event = build_evidence_event(&ledger, "x", &ShadowInputs::default()).unwrap();
         let details: serde_json::Value =
             serde_json::from_str(event.details.as_ref().unwrap()).unwrap();
         assert_eq!(details["bayes_factors"].as_array().unwrap().len(), 3);
@@ -922,6 +1096,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn recorder_sprt_accumulates_across_runs() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let dir = TempDir::new().unwrap();
+        let old = std::env::var("PROCESS_TRIAGE_DATA").ok();
+        std::env::set_var("PROCESS_TRIAGE_DATA", dir.path());
+
+        let proc = make_proc(100, "sleep", "sleep 3600");
+        let result = PosteriorResult {
+            posterior: ClassScores::default(),
+            log_posterior: ClassScores::default(),
+            log_odds_abandoned_useful: 0.0,
+            evidence_terms: vec![crate::inference::EvidenceTerm {
+                feature: "cpu".to_string(),
+                log_likelihood: ClassScores {
+                    useful: -2.0,
+                    useful_bad: -2.0,
+                    abandoned: 0.0,
+                    zombie: -1.0,
+                },
+                weight: None,
+            }],
+        };
+
+        let mut recorder = ShadowRecorder::new().expect("recorder");
+        assert!(recorder.observe_sprt(&proc, &result).unwrap().is_none());
+        recorder.enable_sprt(ShadowSprtConfig::default()).unwrap();
+        for _ in 0..2 {
+            let test = recorder.observe_sprt(&proc, &result).unwrap().unwrap();
+            assert_eq!(test.verdict, crate::inference::SprtVerdict::Pending);
+        }
+        recorder.flush().unwrap();
+
+        // A later run picks up the persisted statistic and crosses ln(0.95/0.01).
+        let mut recorder = ShadowRecorder::new().expect("recorder");
+        recorder.enable_sprt(ShadowSprtConfig::default()).unwrap();
+        let test = recorder.observe_sprt(&proc, &result).unwrap().unwrap();
+        assert_eq!(test.state.observations, 3);
+        assert_eq!(test.verdict, crate::inference::SprtVerdict::Abandoned);
+
+        match old {
+            Some(val) => std::env::set_var("PROCESS_TRIAGE_DATA", val),
+            None => std::env::remove_var("PROCESS_TRIAGE_DATA"),
+        }
+    }
+
+    #[test]
+    fn recorder_flush_persists_behavior_baselines() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let dir = TempDir::new().unwrap();
+        let old = std::env::var("PROCESS_TRIAGE_DATA").ok();
+        std::env::set_var("PROCESS_TRIAGE_DATA", dir.path());
+
+        let mut recorder = ShadowRecorder::new().expect("recorder");
+        let proc = make_proc(100, "nginx", "nginx -g daemon off;");
+        let sample = BehaviorSample {
+            cpu_percent: 1.0,
+            rss_bytes: 50 * 1024 * 1024,
+            fd_count: Some(20),
+            sockets: Some(4),
+        };
+        recorder.observe_behavior(&proc, &sample);
+        recorder.flush().unwrap();
+
+        let fingerprint = crate::snooze::process_fingerprint(proc.uid, &proc.cmd);
+        let store = BehaviorBaselineStore::load(&recorder.baselines_path).unwrap();
+        assert_eq!(store.get(&fingerprint).unwrap().comm, "nginx");
+
+        match old {
+            Some(val) => std::env::set_var("PROCESS_TRIAGE_DATA", val),
+            None => std::env::remove_var("PROCESS_TRIAGE_DATA"),
+        }
+    }
+
     #[test]
     fn recorder_seen_identity_resets_miss_count() {
         let _guard = ENV_LOCK.lock().unwrap();
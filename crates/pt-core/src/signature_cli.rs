@@ -1,19 +1,28 @@
 //! CLI commands for signature management.
 //!
 //! Provides list, show, add, remove, test, validate, and export subcommands
-//! for managing user-defined process signatures.
+//! for managing user-defined process signatures, the staged rollout
+//! (observe → review → auto) of new signatures, updates of the signed
+//! community signature pack, and signatures suggested from repeated
+//! kill/spare decisions.
 
 use crate::exit_codes::ExitCode;
+use crate::inbox::{InboxItem, InboxItemType, InboxStore};
 use crate::output::encode_toon_value;
+use crate::plan::GracefulStopTemplate;
+use crate::session::fleet::{load_fleet_session, SignatureHitStats};
 use crate::supervision::pattern_persistence::{AllPatternStats, DisabledPatterns};
-use crate::supervision::signature::ProcessMatchContext;
+use crate::supervision::signature::{BetaParams, ProcessMatchContext, SignaturePriors};
+use crate::supervision::signature_pack::fetch_pack;
 use crate::supervision::{
-    SignatureDatabase, SignaturePatterns, SignatureSchema, SupervisorCategory, SupervisorSignature,
-    SCHEMA_VERSION as SIG_SCHEMA_VERSION,
+    DecisionRecord, PackDiff, RolloutEvidence, RolloutPolicy, RolloutStage, SignatureDatabase,
+    SignaturePackError, SignaturePackStore, SignaturePatterns, SignatureSchema,
+    SignatureSuggestion, SuggestionError, SuggestionStore, SupervisorCategory, SupervisorSignature,
+    DEFAULT_SIGNATURE_PACK_URL, SCHEMA_VERSION as SIG_SCHEMA_VERSION,
 };
 use clap::{Args, Subcommand};
 use pt_common::{OutputFormat, SessionId, SCHEMA_VERSION};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Bundle path for exported user signatures.
 pub const BUNDLE_SIGNATURES_PATH: &str = "signatures/user_signatures.json";
@@ -77,6 +86,13 @@ pub enum SignatureCommands {
         /// Priority (higher = checked first)
         #[arg(long, default_value = "100")]
         priority: u32,
+        /// Rollout stage (observe, review, auto)
+        #[arg(long, default_value = "auto")]
+        rollout: String,
+        /// Shutdown command run before signals on kill, split on whitespace
+        /// (e.g. "sccache --stop-server"; placeholders {pid}, {cwd}, {flag:NAME})
+        #[arg(long, value_name = "COMMAND")]
+        graceful_stop: Option<String>,
     },
     /// Remove a user signature
     Remove {
@@ -139,6 +155,50 @@ pub enum SignatureCommands {
         /// Sort by: matches, accepts, rejects, rate
         #[arg(long, default_value = "matches")]
         sort: String,
+        /// Show match counts across a fleet session instead of local stats
+        #[arg(long)]
+        fleet_session: Option<String>,
+    },
+    /// Move a user signature between rollout stages (observe, review, auto)
+    Rollout {
+        /// Name of the signature
+        name: String,
+        /// Target stage
+        #[arg(long)]
+        stage: String,
+        /// Fleet session whose match counts count as evidence (repeatable)
+        #[arg(long = "fleet-session", value_name = "ID")]
+        fleet_sessions: Vec<String>,
+        /// Promote even if the requirements are not met
+        #[arg(long)]
+        force: bool,
+    },
+    /// Fetch, verify and install the signed community signature pack
+    Update {
+        /// Pack URL (https://, file:// or a path); the signature is at <URL>.sig
+        #[arg(long, env = "PT_SIGNATURE_PACK_URL", default_value = DEFAULT_SIGNATURE_PACK_URL)]
+        url: String,
+        /// Extra trusted public key for this run (base64 SEC1, repeatable)
+        #[arg(long = "key", value_name = "BASE64")]
+        keys: Vec<String>,
+        /// Show what would change without installing
+        #[arg(long)]
+        dry_run: bool,
+        /// Restore the previously installed pack
+        #[arg(long, conflicts_with = "dry_run")]
+        rollback: bool,
+    },
+    /// List signatures suggested from repeated kill/spare decisions
+    Suggestions,
+    /// Add a suggested signature to the user signatures
+    Accept {
+        /// Name of the suggestion
+        name: String,
+    },
+    /// Discard a suggested signature for good
+    Dismiss {
+        /// Name of the suggestion
+        name: String,
     },
 }
 
@@ -172,6 +232,25 @@ pub fn load_user_signatures() -> Option<SignatureSchema> {
     }
 }
 
+/// Directory holding the installed community signature pack and its pinned keys
+pub fn signature_pack_dir() -> std::path::PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("process_triage")
+        .join("signature_pack")
+}
+
+/// Load the installed community signature pack
+pub fn load_signature_pack() -> Option<SignatureSchema> {
+    match SignaturePackStore::new(signature_pack_dir()).current() {
+        Ok(pack) => pack.map(|(schema, _)| schema),
+        Err(e) => {
+            eprintln!("Warning: Failed to load signature pack: {}", e);
+            None
+        }
+    }
+}
+
 /// Save user signatures to config directory
 pub fn save_user_signatures(schema: &SignatureSchema) -> Result<(), std::io::Error> {
     let path = user_signatures_path();
@@ -183,6 +262,129 @@ pub fn save_user_signatures(schema: &SignatureSchema) -> Result<(), std::io::Err
     std::fs::write(&path, content)
 }
 
+/// Name prefix of signatures written by `spare --remember`.
+pub const SPARED_SIGNATURE_PREFIX: &str = "spared-";
+
+/// Build a user signature that protects one specific command.
+///
+/// The signature pins the exact process name and command line, so it only
+/// matches re-runs of the same command, and carries strong "useful" priors
+/// with a priority above every built-in signature.
+pub fn spared_signature(comm: &str, cmdline: &str) -> SupervisorSignature {
+    use sha2::{Digest, Sha256};
+
+    let cmdline = cmdline.trim();
+    let digest = Sha256::digest(cmdline.as_bytes());
+    let name = format!(
+        "{}{}-{}",
+        SPARED_SIGNATURE_PREFIX,
+        comm,
+        hex::encode(&digest[..4])
+    );
+    let arg_patterns = if cmdline.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!("^{}$", regex::escape(cmdline))]
+    };
+    let min_matches = if arg_patterns.is_empty() { 1 } else { 2 };
+
+    SupervisorSignature {
+        name,
+        category: SupervisorCategory::Other,
+        patterns: SignaturePatterns {
+            process_names: vec![format!("^{}$", regex::escape(comm))],
+            arg_patterns,
+            min_matches,
+            ..Default::default()
+        },
+        priority: 200,
+        confidence_weight: 0.95,
+        notes: Some(format!(
+            "Spared by user on {}; never suggest killing this command",
+            chrono::Utc::now().format("%Y-%m-%d")
+        )),
+        builtin: false,
+        priors: SignaturePriors {
+            useful: Some(BetaParams::new(19.0, 1.0)),
+            abandoned: Some(BetaParams::new(1.0, 19.0)),
+            ..Default::default()
+        },
+        expectations: Default::default(),
+        rollout: RolloutStage::Auto,
+        graceful_stop: None,
+    }
+}
+
+/// Write a [`spared_signature`] for the command into the user signatures file.
+///
+/// Remembering the same command twice replaces the earlier entry.
+pub fn remember_spared(comm: &str, cmdline: &str) -> Result<SupervisorSignature, std::io::Error> {
+    let signature = spared_signature(comm, cmdline);
+    let mut schema = load_user_signatures().unwrap_or_else(|| SignatureSchema {
+        schema_version: SIG_SCHEMA_VERSION,
+        signatures: Vec::new(),
+        metadata: None,
+    });
+    schema.signatures.retain(|s| s.name != signature.name);
+    schema.signatures.push(signature.clone());
+    save_user_signatures(&schema)?;
+    Ok(signature)
+}
+
+/// Directory holding the decision log and signature suggestions
+pub fn signature_suggestions_dir() -> std::path::PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("process_triage")
+        .join("patterns")
+}
+
+/// Log kill/spare decisions and post newly suggested signatures to the inbox.
+///
+/// Returns the suggestions raised by these decisions. Failures are reported
+/// as warnings; they never fail the decision itself.
+pub fn record_decisions(decisions: &[DecisionRecord]) -> Vec<SignatureSuggestion> {
+    let known: HashSet<String> = load_user_signatures()
+        .map(|schema| schema.signatures.into_iter().map(|s| s.name).collect())
+        .unwrap_or_default();
+    let raised = match SuggestionStore::new(signature_suggestions_dir()).record(decisions, &known) {
+        Ok(raised) => raised,
+        Err(e) => {
+            eprintln!("Warning: Failed to record decisions: {}", e);
+            return Vec::new();
+        }
+    };
+    if !raised.is_empty() {
+        match InboxStore::from_env() {
+            Ok(inbox) => {
+                for suggestion in &raised {
+                    let item =
+                        InboxItem::signature_suggestion(&suggestion.name, suggestion.prompt());
+                    if let Err(e) = inbox.add(&item) {
+                        eprintln!("Warning: Failed to post signature suggestion: {}", e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Warning: Failed to post signature suggestion: {}", e),
+        }
+    }
+    raised
+}
+
+/// Acknowledge unread inbox items about the suggestion `name`.
+fn acknowledge_suggestion_items(name: &str) {
+    let Ok(inbox) = InboxStore::from_env() else {
+        return;
+    };
+    for item in inbox.list_unread().unwrap_or_default() {
+        if item.item_type == InboxItemType::SignatureSuggestion
+            && item.trigger.as_deref() == Some(name)
+        {
+            let _ = inbox.acknowledge(&item.id);
+        }
+    }
+}
+
 /// Get the path to disabled signatures file
 fn disabled_signatures_path() -> std::path::PathBuf {
     let config_dir = dirs::config_dir()
@@ -243,6 +445,8 @@ pub fn run_signature(format: &OutputFormat, args: &SignatureArgs) -> ExitCode {
             confidence,
             notes,
             priority,
+            rollout,
+            graceful_stop,
         } => run_signature_add(
             format,
             name,
@@ -253,6 +457,8 @@ pub fn run_signature(format: &OutputFormat, args: &SignatureArgs) -> ExitCode {
             *confidence,
             notes.as_deref(),
             *priority,
+            rollout,
+            graceful_stop.as_deref(),
         ),
         SignatureCommands::Remove { name, force } => run_signature_remove(format, name, *force),
         SignatureCommands::Test {
@@ -273,8 +479,34 @@ pub fn run_signature(format: &OutputFormat, args: &SignatureArgs) -> ExitCode {
             dry_run,
             passphrase,
         } => run_signature_import(format, input, *dry_run, passphrase.as_deref()),
-        SignatureCommands::Stats { min_matches, sort } => {
-            run_signature_stats(format, *min_matches, sort)
+        SignatureCommands::Stats {
+            min_matches,
+            sort,
+            fleet_session,
+        } => match fleet_session {
+            Some(fleet_session) => run_signature_fleet_stats(format, fleet_session, *min_matches),
+            None => run_signature_stats(format, *min_matches, sort),
+        },
+        SignatureCommands::Rollout {
+            name,
+            stage,
+            fleet_sessions,
+            force,
+        } => run_signature_rollout(format, name, stage, fleet_sessions, *force),
+        SignatureCommands::Suggestions => run_signature_suggestions(format),
+        SignatureCommands::Accept { name } => run_signature_accept(format, name),
+        SignatureCommands::Dismiss { name } => run_signature_dismiss(format, name),
+        SignatureCommands::Update {
+            url,
+            keys,
+            dry_run,
+            rollback,
+        } => {
+            if *rollback {
+                run_signature_update_rollback(format)
+            } else {
+                run_signature_update(format, url, keys, *dry_run)
+            }
         }
     }
 }
@@ -310,6 +542,28 @@ fn run_signature_list(
         }
     }
 
+    // Load community pack signatures
+    if !user_only && !builtin_only {
+        if let Some(pack) = load_signature_pack() {
+            for sig in &pack.signatures {
+                if let Some(cat) = category_filter {
+                    if let Some(parsed) = parse_category(cat) {
+                        if sig.category != parsed {
+                            continue;
+                        }
+                    }
+                }
+                all_sigs.push(serde_json::json!({
+                    "name": sig.name,
+                    "category": format!("{:?}", sig.category),
+                    "source": "pack",
+                    "priority": sig.priority,
+                    "confidence": sig.confidence_weight,
+                }));
+            }
+        }
+    }
+
     // Load user signatures
     if !builtin_only {
         if let Some(user_schema) = load_user_signatures() {
@@ -392,6 +646,7 @@ fn run_signature_show(format: &OutputFormat, name: &str) -> ExitCode {
                     "priority": sig.priority,
                     "confidence": sig.confidence_weight,
                     "notes": sig.notes,
+                    "graceful_stop": sig.graceful_stop,
                 }
             });
             match format {
@@ -408,6 +663,9 @@ fn run_signature_show(format: &OutputFormat, name: &str) -> ExitCode {
                         println!("  Notes: {}", notes);
                     }
                     println!("  Patterns: {:?}", sig.patterns);
+                    if let Some(ref stop) = sig.graceful_stop {
+                        println!("  Graceful stop: {}", stop.argv.join(" "));
+                    }
                 }
             }
             return ExitCode::Clean;
@@ -431,6 +689,8 @@ fn run_signature_show(format: &OutputFormat, name: &str) -> ExitCode {
                         "priority": sig.priority,
                         "confidence": sig.confidence_weight,
                         "notes": sig.notes,
+                        "rollout": sig.rollout,
+                        "graceful_stop": sig.graceful_stop,
                     }
                 });
                 match format {
@@ -442,11 +702,15 @@ fn run_signature_show(format: &OutputFormat, name: &str) -> ExitCode {
                         println!();
                         println!("  Category: {:?}", sig.category);
                         println!("  Priority: {}", sig.priority);
+                        println!("  Rollout: {}", sig.rollout);
                         println!("  Confidence: {}", sig.confidence_weight);
                         if let Some(ref notes) = sig.notes {
                             println!("  Notes: {}", notes);
                         }
                         println!("  Patterns: {:?}", sig.patterns);
+                        if let Some(ref stop) = sig.graceful_stop {
+                            println!("  Graceful stop: {}", stop.argv.join(" "));
+                        }
                     }
                 }
                 return ExitCode::Clean;
@@ -469,9 +733,19 @@ fn run_signature_add(
     confidence: f64,
     notes: Option<&str>,
     priority: u32,
+    rollout: &str,
+    graceful_stop: Option<&str>,
 ) -> ExitCode {
     let session_id = SessionId::new();
 
+    let Some(rollout) = RolloutStage::parse(rollout) else {
+        eprintln!(
+            "Invalid rollout stage '{}'. Valid: observe, review, auto",
+            rollout
+        );
+        return ExitCode::ArgsError;
+    };
+
     // Parse category
     let cat = match parse_category(category) {
         Some(c) => c,
@@ -484,6 +758,13 @@ fn run_signature_add(
         }
     };
 
+    let graceful_stop = graceful_stop
+        .map(|command| GracefulStopTemplate::new(command.split_whitespace().collect()));
+    if let Some(Err(e)) = graceful_stop.as_ref().map(GracefulStopTemplate::validate) {
+        eprintln!("Invalid graceful stop command: {}", e);
+        return ExitCode::ArgsError;
+    }
+
     // Parse environment variables (NAME=REGEX format)
     let mut env_map: HashMap<String, String> = HashMap::new();
     for env_var in env_vars {
@@ -514,6 +795,8 @@ fn run_signature_add(
         builtin: false,
         priors: Default::default(),
         expectations: Default::default(),
+        rollout,
+        graceful_stop,
     };
 
     // Load or create user schema
@@ -1186,12 +1469,10 @@ fn run_signature_enable(format: &OutputFormat, name: &str) -> ExitCode {
     ExitCode::Clean
 }
 
-fn run_signature_stats(format: &OutputFormat, min_matches: u32, sort_by: &str) -> ExitCode {
-    let session_id = SessionId::new();
-
-    // Load pattern stats
+/// Load local pattern statistics, empty if missing or unreadable.
+fn load_pattern_stats() -> AllPatternStats {
     let stats_path = pattern_stats_path();
-    let stats = if stats_path.exists() {
+    if stats_path.exists() {
         match AllPatternStats::from_file(&stats_path) {
             Ok(s) => s,
             Err(e) => {
@@ -1201,7 +1482,12 @@ fn run_signature_stats(format: &OutputFormat, min_matches: u32, sort_by: &str) -
         }
     } else {
         AllPatternStats::default()
-    };
+    }
+}
+
+fn run_signature_stats(format: &OutputFormat, min_matches: u32, sort_by: &str) -> ExitCode {
+    let session_id = SessionId::new();
+    let stats = load_pattern_stats();
 
     // Collect and filter stats
     let mut stat_entries: Vec<(
@@ -1308,6 +1594,522 @@ fn run_signature_stats(format: &OutputFormat, min_matches: u32, sort_by: &str) -
     ExitCode::Clean
 }
 
+/// Rollout stage of every known signature (user signatures override built-ins).
+fn signature_stages() -> HashMap<String, RolloutStage> {
+    let mut db = SignatureDatabase::new();
+    db.add_default_signatures();
+    let mut stages: HashMap<String, RolloutStage> = db
+        .signatures()
+        .iter()
+        .map(|sig| (sig.name.clone(), sig.rollout))
+        .collect();
+    if let Some(schema) = load_user_signatures() {
+        for sig in schema.signatures {
+            stages.insert(sig.name, sig.rollout);
+        }
+    }
+    stages
+}
+
+/// The stage after `stage`, if any.
+fn next_stage(stage: RolloutStage) -> Option<RolloutStage> {
+    match stage {
+        RolloutStage::Observe => Some(RolloutStage::Review),
+        RolloutStage::Review => Some(RolloutStage::Auto),
+        RolloutStage::Auto => None,
+    }
+}
+
+fn run_signature_fleet_stats(
+    format: &OutputFormat,
+    fleet_session: &str,
+    min_matches: u32,
+) -> ExitCode {
+    let session_id = SessionId::new();
+    let fleet = match load_fleet_session(fleet_session) {
+        Ok((fleet, _)) => fleet,
+        Err(e) => {
+            eprintln!("signature stats: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+    let local = load_pattern_stats();
+    let stages = signature_stages();
+    let policy = RolloutPolicy::default();
+
+    let hits: Vec<&SignatureHitStats> = fleet
+        .signature_hits
+        .iter()
+        .filter(|hit| hit.matches >= min_matches)
+        .collect();
+    let rows: Vec<serde_json::Value> = hits
+        .iter()
+        .map(|hit| {
+            let stage = stages.get(&hit.signature).copied().unwrap_or_default();
+            let evidence =
+                RolloutEvidence::new(u64::from(hit.matches), local.patterns.get(&hit.signature));
+            let promotion = next_stage(stage).map(|next| policy.check(stage, next, &evidence));
+            serde_json::json!({
+                "name": hit.signature,
+                "fleet_matches": hit.matches,
+                "host_count": hit.host_count,
+                "matches_per_host": hit.matches as f64 / fleet.hosts.len().max(1) as f64,
+                "by_host": hit.by_host,
+                "rollout": stage,
+                "evidence": evidence,
+                "promotion": promotion,
+            })
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature stats",
+                "fleet_session_id": fleet.fleet_session_id,
+                "total_hosts": fleet.hosts.len(),
+                "filters": {
+                    "min_matches": min_matches,
+                },
+                "stats": rows,
+                "count": rows.len(),
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => {
+            if rows.is_empty() {
+                println!(
+                    "No signature matches recorded for fleet session {}.",
+                    fleet.fleet_session_id
+                );
+                return ExitCode::Clean;
+            }
+            println!(
+                "# Fleet Signature Hits ({}, {} hosts)",
+                fleet.fleet_session_id,
+                fleet.hosts.len()
+            );
+            println!();
+            println!(
+                "{:30} {:>8} {:>6} {:>8} {:>10}",
+                "NAME", "MATCHES", "HOSTS", "STAGE", "PROMOTE"
+            );
+            println!("{}", "-".repeat(66));
+            for row in &rows {
+                let promote = match row["promotion"]["allowed"].as_bool() {
+                    Some(true) => format!("{}: ok", row["promotion"]["to"].as_str().unwrap_or("")),
+                    Some(false) => "blocked".to_string(),
+                    None => "-".to_string(),
+                };
+                println!(
+                    "{:30} {:>8} {:>6} {:>8} {:>10}",
+                    row["name"].as_str().unwrap_or(""),
+                    row["fleet_matches"],
+                    row["host_count"],
+                    row["rollout"].as_str().unwrap_or(""),
+                    promote
+                );
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+fn run_signature_rollout(
+    format: &OutputFormat,
+    name: &str,
+    stage: &str,
+    fleet_sessions: &[String],
+    force: bool,
+) -> ExitCode {
+    let session_id = SessionId::new();
+    let Some(target) = RolloutStage::parse(stage) else {
+        eprintln!(
+            "Invalid rollout stage '{}'. Valid: observe, review, auto",
+            stage
+        );
+        return ExitCode::ArgsError;
+    };
+
+    let Some(mut schema) = load_user_signatures() else {
+        eprintln!("No user signatures found. Only user signatures can be staged.");
+        return ExitCode::ArgsError;
+    };
+    let Some(index) = schema.signatures.iter().position(|s| s.name == name) else {
+        eprintln!(
+            "User signature '{}' not found. Only user signatures can be staged.",
+            name
+        );
+        return ExitCode::ArgsError;
+    };
+
+    let mut fleet_matches = 0u64;
+    for fleet_session in fleet_sessions {
+        match load_fleet_session(fleet_session) {
+            Ok((fleet, _)) => {
+                fleet_matches += fleet
+                    .signature_hits
+                    .iter()
+                    .filter(|hit| hit.signature == name)
+                    .map(|hit| u64::from(hit.matches))
+                    .sum::<u64>();
+            }
+            Err(e) => {
+                eprintln!("signature rollout: {}", e);
+                return ExitCode::ArgsError;
+            }
+        }
+    }
+    let local = load_pattern_stats();
+    let evidence = RolloutEvidence::new(fleet_matches, local.patterns.get(name));
+    let current = schema.signatures[index].rollout;
+    let check = RolloutPolicy::default().check(current, target, &evidence);
+
+    if !check.allowed && !force {
+        match format {
+            OutputFormat::Json | OutputFormat::Toon => {
+                let output = serde_json::json!({
+                    "schema_version": SCHEMA_VERSION,
+                    "session_id": session_id.0,
+                    "generated_at": chrono::Utc::now().to_rfc3339(),
+                    "command": "signature rollout",
+                    "status": "blocked",
+                    "name": name,
+                    "check": check,
+                });
+                println!("{}", format_signature_output(format, output));
+            }
+            _ => {
+                eprintln!(
+                    "Cannot move '{}' from {} to {}:",
+                    name, check.from, check.to
+                );
+                for reason in &check.unmet {
+                    eprintln!("  - {}", reason);
+                }
+                eprintln!("Use --force to override.");
+            }
+        }
+        return ExitCode::PolicyBlocked;
+    }
+
+    schema.signatures[index].rollout = target;
+    if let Err(e) = save_user_signatures(&schema) {
+        eprintln!("Failed to save signature: {}", e);
+        return ExitCode::IoError;
+    }
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature rollout",
+                "status": "success",
+                "name": name,
+                "forced": !check.allowed,
+                "check": check,
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => {
+            println!("Moved '{}' from {} to {}", name, check.from, check.to);
+            if !check.allowed {
+                println!("Forced past: {}", check.unmet.join("; "));
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+fn run_signature_suggestions(format: &OutputFormat) -> ExitCode {
+    let session_id = SessionId::new();
+    let state = match SuggestionStore::new(signature_suggestions_dir()).state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("signature suggestions: {}", e);
+            return ExitCode::IoError;
+        }
+    };
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature suggestions",
+                "suggestions": state.pending,
+                "count": state.pending.len(),
+                "dismissed": state.dismissed.len(),
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => {
+            println!("# Signature suggestions ({} pending)", state.pending.len());
+            for suggestion in &state.pending {
+                println!();
+                println!("  {}", suggestion.name);
+                println!("    {}", suggestion.prompt());
+                for example in &suggestion.examples {
+                    println!("    e.g. {}", example);
+                }
+                println!("    Accept: pt signature accept {}", suggestion.name);
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+fn suggestion_error_code(error: &SuggestionError) -> ExitCode {
+    match error {
+        SuggestionError::NotFound(_) => ExitCode::ArgsError,
+        _ => ExitCode::IoError,
+    }
+}
+
+fn run_signature_accept(format: &OutputFormat, name: &str) -> ExitCode {
+    let session_id = SessionId::new();
+    let store = SuggestionStore::new(signature_suggestions_dir());
+    let suggestion = match store.accept(name) {
+        Ok(suggestion) => suggestion,
+        Err(e) => {
+            eprintln!("signature accept: {}", e);
+            return suggestion_error_code(&e);
+        }
+    };
+
+    let mut schema = load_user_signatures().unwrap_or_else(|| SignatureSchema {
+        schema_version: SIG_SCHEMA_VERSION,
+        signatures: Vec::new(),
+        metadata: None,
+    });
+    schema.signatures.retain(|s| s.name != suggestion.name);
+    schema.signatures.push(suggestion.signature.clone());
+    if let Err(e) = save_user_signatures(&schema) {
+        eprintln!("Failed to save signature: {}", e);
+        return ExitCode::IoError;
+    }
+    acknowledge_suggestion_items(name);
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature accept",
+                "status": "success",
+                "name": suggestion.name,
+                "signature": suggestion.signature,
+                "path": user_signatures_path().display().to_string(),
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => {
+            println!("Added signature '{}'", suggestion.name);
+            println!("Saved to: {}", user_signatures_path().display());
+        }
+    }
+
+    ExitCode::Clean
+}
+
+fn run_signature_dismiss(format: &OutputFormat, name: &str) -> ExitCode {
+    let session_id = SessionId::new();
+    let store = SuggestionStore::new(signature_suggestions_dir());
+    if let Err(e) = store.dismiss(name) {
+        eprintln!("signature dismiss: {}", e);
+        return suggestion_error_code(&e);
+    }
+    acknowledge_suggestion_items(name);
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature dismiss",
+                "status": "success",
+                "name": name,
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => println!("Dismissed suggestion '{}'", name),
+    }
+
+    ExitCode::Clean
+}
+
+fn run_signature_update(
+    format: &OutputFormat,
+    url: &str,
+    keys: &[String],
+    dry_run: bool,
+) -> ExitCode {
+    let session_id = SessionId::new();
+    let store = SignaturePackStore::new(signature_pack_dir());
+    let verifier = match store.verifier(keys) {
+        Ok(verifier) if verifier.key_count() == 0 => {
+            eprintln!(
+                "No trusted signature pack keys. Pin one (base64 SEC1) in {} or pass --key.",
+                store.trusted_keys_path().display()
+            );
+            return ExitCode::ArgsError;
+        }
+        Ok(verifier) => verifier,
+        Err(e) => {
+            eprintln!("signature update: {}", e);
+            return ExitCode::ArgsError;
+        }
+    };
+
+    let pack = match fetch_pack(url, &verifier) {
+        Ok(pack) => pack,
+        Err(e) => {
+            let code = match e {
+                SignaturePackError::Fetch { .. } => ExitCode::IoError,
+                SignaturePackError::Verify(_) => ExitCode::PolicyBlocked,
+                _ => ExitCode::ArgsError,
+            };
+            match format {
+                OutputFormat::Json | OutputFormat::Toon => {
+                    let output = serde_json::json!({
+                        "schema_version": SCHEMA_VERSION,
+                        "session_id": session_id.0,
+                        "generated_at": chrono::Utc::now().to_rfc3339(),
+                        "command": "signature update",
+                        "status": "error",
+                        "url": url,
+                        "error": e.to_string(),
+                    });
+                    println!("{}", format_signature_output(format, output));
+                }
+                _ => eprintln!("signature update: {}", e),
+            }
+            return code;
+        }
+    };
+
+    let current = match store.current() {
+        Ok(current) => current,
+        Err(e) => {
+            eprintln!(
+                "signature update: installed pack unreadable ({}); replacing it",
+                e
+            );
+            None
+        }
+    };
+    let diff = PackDiff::between(current.as_ref().map(|(schema, _)| schema), &pack.schema);
+    let up_to_date = current
+        .as_ref()
+        .is_some_and(|(_, info)| info.sha256 == pack.info.sha256);
+    let status = if up_to_date {
+        "up_to_date"
+    } else if dry_run {
+        "dry_run"
+    } else {
+        "installed"
+    };
+
+    if status == "installed" {
+        if let Err(e) = store.install(&pack) {
+            eprintln!("Failed to install signature pack: {}", e);
+            return ExitCode::IoError;
+        }
+    }
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature update",
+                "status": status,
+                "pack": pack.info,
+                "previous": current.map(|(_, info)| info),
+                "diff": diff,
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => {
+            println!(
+                "Signature pack {} ({} signatures, key {})",
+                &pack.info.sha256[..12],
+                pack.info.signatures,
+                &pack.info.key_fingerprint[..16]
+            );
+            for name in &diff.added {
+                println!("  + {}", name);
+            }
+            for name in &diff.changed {
+                println!("  ~ {}", name);
+            }
+            for name in &diff.removed {
+                println!("  - {}", name);
+            }
+            match status {
+                "up_to_date" => println!("Already installed."),
+                "dry_run" => println!("Dry run: nothing installed."),
+                _ => println!(
+                    "Installed ({} unchanged). Undo with: pt-core signature update --rollback",
+                    diff.unchanged
+                ),
+            }
+        }
+    }
+
+    ExitCode::Clean
+}
+
+fn run_signature_update_rollback(format: &OutputFormat) -> ExitCode {
+    let session_id = SessionId::new();
+    let store = SignaturePackStore::new(signature_pack_dir());
+    let info = match store.rollback() {
+        Ok(info) => info,
+        Err(SignaturePackError::NoPrevious) => {
+            eprintln!("No previous signature pack to roll back to.");
+            return ExitCode::ArgsError;
+        }
+        Err(e) => {
+            eprintln!("Failed to roll back signature pack: {}", e);
+            return ExitCode::IoError;
+        }
+    };
+
+    match format {
+        OutputFormat::Json | OutputFormat::Toon => {
+            let output = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "session_id": session_id.0,
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "command": "signature update",
+                "status": "rolled_back",
+                "pack": info,
+            });
+            println!("{}", format_signature_output(format, output));
+        }
+        _ => println!(
+            "Restored signature pack {} ({} signatures, fetched {})",
+            &info.sha256[..12],
+            info.signatures,
+            info.fetched_at.to_rfc3339()
+        ),
+    }
+
+    ExitCode::Clean
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1395,6 +2197,18 @@ mod tests {
         assert!(result.contains("42"));
     }
 
+    // ── next_stage ──────────────────────────────────────────────────
+
+    #[test]
+    fn next_stage_walks_observe_review_auto() {
+        assert_eq!(
+            next_stage(RolloutStage::Observe),
+            Some(RolloutStage::Review)
+        );
+        assert_eq!(next_stage(RolloutStage::Review), Some(RolloutStage::Auto));
+        assert_eq!(next_stage(RolloutStage::Auto), None);
+    }
+
     // ── BUNDLE_SIGNATURES_PATH ──────────────────────────────────────
 
     #[test]
@@ -1447,6 +2261,8 @@ mod tests {
                 builtin: false,
                 priors: Default::default(),
                 expectations: Default::default(),
+                rollout: Default::default(),
+                graceful_stop: None,
             }],
             metadata: None,
         };
@@ -1522,6 +2338,35 @@ mod tests {
         assert!(matches.is_empty() || matches[0].score < 0.5);
     }
 
+    // ── spared signatures ───────────────────────────────────────────
+
+    #[test]
+    fn spared_signature_matches_only_the_same_command() {
+        let sig = spared_signature("node", "node server.js --port 3000");
+        assert!(sig.name.starts_with(SPARED_SIGNATURE_PREFIX));
+        assert!(sig.priority > 100);
+        assert!(sig.priors.useful.is_some());
+        sig.validate().unwrap();
+
+        let mut db = SignatureDatabase::new();
+        db.add(sig).unwrap();
+
+        let same = ProcessMatchContext::with_comm("node").cmdline("node server.js --port 3000");
+        assert_eq!(db.match_process(&same).len(), 1);
+
+        let other = ProcessMatchContext::with_comm("node").cmdline("node build.js");
+        assert!(db.match_process(&other).is_empty());
+    }
+
+    #[test]
+    fn spared_signature_name_is_stable_per_command() {
+        let a = spared_signature("python3", "python3 -m http.server");
+        let b = spared_signature("python3", "python3 -m http.server");
+        let c = spared_signature("python3", "python3 train.py");
+        assert_eq!(a.name, b.name);
+        assert_ne!(a.name, c.name);
+    }
+
     // ── env var parsing logic ───────────────────────────────────────
 
     #[test]
@@ -1587,6 +2432,8 @@ mod tests {
             builtin: false,
             priors: Default::default(),
             expectations: Default::default(),
+            rollout: Default::default(),
+            graceful_stop: None,
         };
         assert_eq!(sig.name, "test_tool");
         assert_eq!(sig.category, SupervisorCategory::Ide);
@@ -1608,6 +2455,8 @@ mod tests {
             builtin: false,
             priors: Default::default(),
             expectations: Default::default(),
+            rollout: Default::default(),
+            graceful_stop: None,
         };
         let _ = db.add(sig);
         assert!(db.signatures().iter().any(|s| s.name == "custom_tool"));
@@ -1642,6 +2491,8 @@ mod tests {
                     builtin: false,
                     priors: Default::default(),
                     expectations: Default::default(),
+                    rollout: Default::default(),
+                    graceful_stop: None,
                 })
                 .collect(),
             metadata: None,
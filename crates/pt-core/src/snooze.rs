@@ -0,0 +1,458 @@
+//! Candidate snooze/pin store.
+//!
+//! Lets a user record "I looked at this, it's fine for now" so the same
+//! candidate does not resurface on every TUI refresh, watch iteration, or
+//! plan run. Entries are keyed by a process fingerprint (owner + command line)
+//! rather than PID so they survive restarts of the same workload.
+//!
+//! - A *snooze* hides the fingerprint until a deadline.
+//! - A *pin* hides the fingerprint until explicitly removed.
+
+use chrono::{DateTime, Duration, Utc};
+use pt_common::schema::SCHEMA_VERSION;
+use pt_common::units::{parse_duration, DurationUnit};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const SNOOZE_DIR: &str = "snooze";
+const SNOOZE_FILE: &str = "snoozes.json";
+
+/// Environment variable overriding the default snooze duration (e.g. "30m", "8h", "2d").
+pub const ENV_SNOOZE_DURATION: &str = "PT_SNOOZE_DURATION";
+
+/// Default snooze duration when none is configured.
+pub const DEFAULT_SNOOZE_HOURS: i64 = 4;
+
+/// Maximum number of characters of the command line stored for display.
+const MAX_COMMAND_CHARS: usize = 200;
+
+/// Errors from snooze store operations.
+#[derive(Debug, Error)]
+pub enum SnoozeError {
+    #[error("failed to resolve data directory")]
+    DataDirUnavailable,
+
+    #[error("I/O error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse JSON: {source}")]
+    Json {
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("invalid snooze duration: {0}")]
+    InvalidDuration(String),
+
+    #[error("snooze not found: {0}")]
+    NotFound(String),
+}
+
+/// Compute the stable fingerprint used to key snoozes.
+///
+/// The fingerprint covers the owning UID and the full command line, so a
+/// restarted instance of the same workload stays snoozed while a different
+/// user's identical command does not.
+pub fn process_fingerprint(uid: u32, cmd: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(uid.to_le_bytes());
+    hasher.update([0u8]);
+    hasher.update(cmd.trim().as_bytes());
+    let digest = hasher.finalize();
+    hex::encode(&digest[..8])
+}
+
+/// Parse a snooze duration such as "30m", "4h", or "2d" (bare numbers are minutes).
+///
+/// Snoozes shorter than a minute are rejected.
+pub fn parse_snooze_duration(raw: &str) -> Result<Duration, SnoozeError> {
+    let invalid = || SnoozeError::InvalidDuration(raw.to_string());
+    let duration = parse_duration(raw, DurationUnit::Minutes).map_err(|_| invalid())?;
+    if duration.as_secs() < 60 {
+        return Err(invalid());
+    }
+    Duration::from_std(duration).map_err(|_| invalid())
+}
+
+/// Resolve the default snooze duration (env override, else 4h).
+pub fn default_snooze_duration() -> Duration {
+    std::env::var(ENV_SNOOZE_DURATION)
+        .ok()
+        .and_then(|raw| parse_snooze_duration(&raw).ok())
+        .unwrap_or_else(|| Duration::hours(DEFAULT_SNOOZE_HOURS))
+}
+
+/// A single snooze or pin entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnoozeEntry {
+    /// Process fingerprint (see [`process_fingerprint`]).
+    pub fingerprint: String,
+    /// PID at the time the snooze was recorded (informational).
+    pub pid: u32,
+    /// Owner UID.
+    pub uid: u32,
+    /// Command line (truncated) for display.
+    pub command: String,
+    /// When the entry was created (RFC3339).
+    pub created_at: String,
+    /// Expiry (RFC3339). `None` means pinned until removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    /// Optional free-form reason.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl SnoozeEntry {
+    /// Create a time-limited snooze.
+    pub fn snooze(uid: u32, pid: u32, cmd: &str, duration: Duration) -> Self {
+        let now = Utc::now();
+        Self::build(uid, pid, cmd, now, Some((now + duration).to_rfc3339()))
+    }
+
+    /// Create an indefinite pin.
+    pub fn pin(uid: u32, pid: u32, cmd: &str) -> Self {
+        Self::build(uid, pid, cmd, Utc::now(), None)
+    }
+
+    fn build(uid: u32, pid: u32, cmd: &str, now: DateTime<Utc>, until: Option<String>) -> Self {
+        Self {
+            fingerprint: process_fingerprint(uid, cmd),
+            pid,
+            uid,
+            command: cmd.chars().take(MAX_COMMAND_CHARS).collect(),
+            created_at: now.to_rfc3339(),
+            until,
+            reason: None,
+        }
+    }
+
+    /// Whether this entry is a pin (no expiry).
+    pub fn is_pinned(&self) -> bool {
+        self.until.is_none()
+    }
+
+    /// Whether this entry is still in effect at `now`.
+    ///
+    /// Entries with an unparseable expiry are treated as expired.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        match &self.until {
+            None => true,
+            Some(until) => DateTime::parse_from_rfc3339(until)
+                .map(|t| t.with_timezone(&Utc) > now)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Active snoozes indexed by fingerprint for fast lookup during scans.
+#[derive(Debug, Clone, Default)]
+pub struct SnoozeSet {
+    entries: HashMap<String, SnoozeEntry>,
+}
+
+impl SnoozeSet {
+    /// Build a set from entries, keeping only those active at `now`.
+    pub fn from_entries(entries: Vec<SnoozeEntry>, now: DateTime<Utc>) -> Self {
+        let entries = entries
+            .into_iter()
+            .filter(|e| e.is_active(now))
+            .map(|e| (e.fingerprint.clone(), e))
+            .collect();
+        Self { entries }
+    }
+
+    /// Look up the active snooze for a process, if any.
+    pub fn get(&self, uid: u32, cmd: &str) -> Option<&SnoozeEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.entries.get(&process_fingerprint(uid, cmd))
+    }
+
+    /// Whether the process is currently snoozed or pinned.
+    pub fn contains(&self, uid: u32, cmd: &str) -> bool {
+        self.get(uid, cmd).is_some()
+    }
+
+    /// Number of active entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no active entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Response for `agent inbox --snoozed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnoozeResponse {
+    /// Schema version.
+    pub schema_version: String,
+    /// When the response was generated.
+    pub generated_at: String,
+    /// Active snoozes and pins.
+    pub snoozed: Vec<SnoozeEntry>,
+    /// Number of pinned entries.
+    pub pinned_count: u32,
+}
+
+impl SnoozeResponse {
+    /// Create a response from active entries.
+    pub fn new(snoozed: Vec<SnoozeEntry>) -> Self {
+        let pinned_count = snoozed.iter().filter(|e| e.is_pinned()).count() as u32;
+        Self {
+            schema_version: SCHEMA_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            snoozed,
+            pinned_count,
+        }
+    }
+}
+
+/// Persistent store for snooze entries.
+#[derive(Debug, Clone)]
+pub struct SnoozeStore {
+    path: PathBuf,
+}
+
+impl SnoozeStore {
+    /// Create a store from environment.
+    pub fn from_env() -> Result<Self, SnoozeError> {
+        let data_dir = resolve_data_dir()?;
+        Ok(Self::from_data_dir(&data_dir))
+    }
+
+    /// Create a store from a specific data directory.
+    pub fn from_data_dir(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(SNOOZE_DIR).join(SNOOZE_FILE),
+        }
+    }
+
+    /// Load all entries, including expired ones.
+    pub fn list(&self) -> Result<Vec<SnoozeEntry>, SnoozeError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path).map_err(|e| SnoozeError::Io {
+            path: self.path.clone(),
+            source: e,
+        })?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_str(&content).map_err(|e| SnoozeError::Json { source: e })
+    }
+
+    /// Load entries active at `now`, soonest expiry first and pins last.
+    pub fn list_active(&self, now: DateTime<Utc>) -> Result<Vec<SnoozeEntry>, SnoozeError> {
+        let mut active: Vec<_> = self
+            .list()?
+            .into_iter()
+            .filter(|e| e.is_active(now))
+            .collect();
+        active.sort_by(|a, b| match (&a.until, &b.until) {
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.created_at.cmp(&b.created_at),
+        });
+        Ok(active)
+    }
+
+    /// Load the active set for lookups.
+    pub fn active_set(&self, now: DateTime<Utc>) -> Result<SnoozeSet, SnoozeError> {
+        Ok(SnoozeSet::from_entries(self.list()?, now))
+    }
+
+    /// Add or replace the entry for a fingerprint, pruning expired entries.
+    pub fn upsert(&self, entry: SnoozeEntry) -> Result<(), SnoozeError> {
+        let now = Utc::now();
+        let mut entries: Vec<_> = self
+            .list()?
+            .into_iter()
+            .filter(|e| e.fingerprint != entry.fingerprint && e.is_active(now))
+            .collect();
+        entries.push(entry);
+        self.write_all(&entries)
+    }
+
+    /// Remove the entry for a fingerprint.
+    pub fn remove(&self, fingerprint: &str) -> Result<SnoozeEntry, SnoozeError> {
+        let mut entries = self.list()?;
+        let pos = entries
+            .iter()
+            .position(|e| e.fingerprint == fingerprint)
+            .ok_or_else(|| SnoozeError::NotFound(fingerprint.to_string()))?;
+        let removed = entries.remove(pos);
+        self.write_all(&entries)?;
+        Ok(removed)
+    }
+
+    fn write_all(&self, entries: &[SnoozeEntry]) -> Result<(), SnoozeError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SnoozeError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        let content =
+            serde_json::to_string_pretty(entries).map_err(|e| SnoozeError::Json { source: e })?;
+        fs::write(&self.path, content).map_err(|e| SnoozeError::Io {
+            path: self.path.clone(),
+            source: e,
+        })
+    }
+}
+
+/// Resolve the data directory.
+fn resolve_data_dir() -> Result<PathBuf, SnoozeError> {
+    const ENV_DATA_DIR: &str = "PROCESS_TRIAGE_DATA";
+    const DIR_NAME: &str = "process_triage";
+
+    if let Ok(dir) = std::env::var(ENV_DATA_DIR) {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Ok(xdg) = std::env::var("XDG_DATA_HOME") {
+        return Ok(PathBuf::from(xdg).join(DIR_NAME));
+    }
+    if let Some(base) = dirs::data_dir() {
+        return Ok(base.join(DIR_NAME));
+    }
+    Err(SnoozeError::DataDirUnavailable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_store() -> (SnoozeStore, TempDir) {
+        let tmp = TempDir::new().unwrap();
+        let store = SnoozeStore::from_data_dir(tmp.path());
+        (store, tmp)
+    }
+
+    #[test]
+    fn test_fingerprint_stable_and_user_scoped() {
+        let a = process_fingerprint(1000, "node server.js");
+        assert_eq!(a, process_fingerprint(1000, "  node server.js "));
+        assert_ne!(a, process_fingerprint(1001, "node server.js"));
+        assert_ne!(a, process_fingerprint(1000, "node other.js"));
+        assert_eq!(a.len(), 16);
+    }
+
+    #[test]
+    fn test_parse_snooze_duration() {
+        assert_eq!(parse_snooze_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_snooze_duration("4h").unwrap(), Duration::hours(4));
+        assert_eq!(parse_snooze_duration("2d").unwrap(), Duration::days(2));
+        assert_eq!(parse_snooze_duration("1w").unwrap(), Duration::weeks(1));
+        assert!(parse_snooze_duration("").is_err());
+        assert!(parse_snooze_duration("h").is_err());
+        assert!(parse_snooze_duration("0h").is_err());
+        assert!(parse_snooze_duration("5s").is_err());
+    }
+
+    #[test]
+    fn test_entry_activity() {
+        let now = Utc::now();
+        let snooze = SnoozeEntry::snooze(1000, 42, "sleep 100", Duration::hours(1));
+        assert!(snooze.is_active(now));
+        assert!(!snooze.is_active(now + Duration::hours(2)));
+
+        let pin = SnoozeEntry::pin(1000, 42, "sleep 100");
+        assert!(pin.is_pinned());
+        assert!(pin.is_active(now + Duration::days(365)));
+
+        let mut broken = snooze.clone();
+        broken.until = Some("not-a-date".to_string());
+        assert!(!broken.is_active(now));
+    }
+
+    #[test]
+    fn test_empty_store() {
+        let (store, _tmp) = test_store();
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.active_set(Utc::now()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_upsert_replaces_same_fingerprint() {
+        let (store, _tmp) = test_store();
+        store
+            .upsert(SnoozeEntry::snooze(
+                1000,
+                1,
+                "cargo watch",
+                Duration::hours(1),
+            ))
+            .unwrap();
+        store
+            .upsert(SnoozeEntry::pin(1000, 2, "cargo watch"))
+            .unwrap();
+
+        let entries = store.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_pinned());
+        assert_eq!(entries[0].pid, 2);
+    }
+
+    #[test]
+    fn test_active_set_lookup() {
+        let (store, _tmp) = test_store();
+        store
+            .upsert(SnoozeEntry::snooze(1000, 7, "vite dev", Duration::hours(1)))
+            .unwrap();
+
+        let set = store.active_set(Utc::now()).unwrap();
+        assert!(set.contains(1000, "vite dev"));
+        assert!(!set.contains(0, "vite dev"));
+
+        let later = store.active_set(Utc::now() + Duration::hours(3)).unwrap();
+        assert!(!later.contains(1000, "vite dev"));
+    }
+
+    #[test]
+    fn test_list_active_orders_pins_last() {
+        let (store, _tmp) = test_store();
+        store.upsert(SnoozeEntry::pin(1000, 1, "a")).unwrap();
+        store
+            .upsert(SnoozeEntry::snooze(1000, 2, "b", Duration::hours(5)))
+            .unwrap();
+        store
+            .upsert(SnoozeEntry::snooze(1000, 3, "c", Duration::hours(1)))
+            .unwrap();
+
+        let active = store.list_active(Utc::now()).unwrap();
+        let pids: Vec<u32> = active.iter().map(|e| e.pid).collect();
+        assert_eq!(pids, vec![3, 2, 1]);
+
+        let response = SnoozeResponse::new(active);
+        assert_eq!(response.pinned_count, 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let (store, _tmp) = test_store();
+        let entry = SnoozeEntry::pin(1000, 1, "jest --watch");
+        let fp = entry.fingerprint.clone();
+        store.upsert(entry).unwrap();
+
+        let removed = store.remove(&fp).unwrap();
+        assert_eq!(removed.fingerprint, fp);
+        assert!(store.list().unwrap().is_empty());
+        assert!(matches!(store.remove(&fp), Err(SnoozeError::NotFound(_))));
+    }
+}
@@ -6,6 +6,7 @@
 use super::types::{
     AncestryEntry, EvidenceType, SupervisionEvidence, SupervisionResult, SupervisorDatabase,
 };
+use crate::collect::ProcessRecord;
 use pt_common::ProcessId;
 use std::collections::HashMap;
 use std::fs;
@@ -73,6 +74,22 @@ impl ProcessTreeCache {
         Self::default()
     }
 
+    /// Build the cache from scanned process records.
+    ///
+    /// Works on every platform the scanner supports, so ancestry analysis can
+    /// run where `/proc` is not available.
+    pub fn from_records(records: &[ProcessRecord]) -> Self {
+        let mut cache = Self::new();
+        for record in records {
+            cache.ppid_map.insert(record.pid.0, record.ppid.0);
+            cache.comm_map.insert(record.pid.0, record.comm.clone());
+            if !record.cmd.is_empty() {
+                cache.cmdline_map.insert(record.pid.0, record.cmd.clone());
+            }
+        }
+        cache
+    }
+
     /// Pre-populate the cache by scanning /proc.
     ///
     /// This is more efficient for batch analysis than reading on-demand.
@@ -253,6 +270,14 @@ impl AncestryAnalyzer {
         }
     }
 
+    /// Create an analyzer over scanned process records instead of `/proc`.
+    pub fn with_process_table(records: &[ProcessRecord]) -> Self {
+        Self {
+            config: AncestryConfig::default(),
+            cache: ProcessTreeCache::from_records(records),
+        }
+    }
+
     /// Pre-populate the process tree cache.
     #[cfg(target_os = "linux")]
     pub fn populate_cache(&mut self) -> Result<(), AncestryError> {
@@ -281,8 +306,9 @@ impl AncestryAnalyzer {
             // Get process info
             let comm = match self.cache.get_comm(current_pid) {
                 Ok(c) => c,
-                Err(AncestryError::ProcessNotFound(_)) if current_pid == 1 => {
-                    // PID 1 may not be readable, that's OK
+                Err(AncestryError::ProcessNotFound(_)) if current_pid == 1 || depth > 0 => {
+                    // PID 1 may not be readable, and an ancestor that exited
+                    // (or is missing from a process table) ends the chain
                     break;
                 }
                 Err(e) => return Err(e),
@@ -294,6 +320,18 @@ impl AncestryAnalyzer {
                 None
             };
 
+            // Check for supervisor match (skip the first entry - that's the process itself).
+            // The command line identifies helpers run by a generic runtime (node, java).
+            let supervisor = if depth > 0 {
+                self.config
+                    .database
+                    .find_matches_with_cmdline(&comm, cmdline.as_deref())
+                    .first()
+                    .copied()
+            } else {
+                None
+            };
+
             // Add to ancestry chain
             ancestry_chain.push(AncestryEntry {
                 pid: ProcessId(current_pid),
@@ -301,30 +339,26 @@ impl AncestryAnalyzer {
                 cmdline,
             });
 
-            // Check for supervisor match (skip the first entry - that's the process itself)
-            if depth > 0 {
-                let matches = self.config.database.find_matches(&comm);
-                if let Some(pattern) = matches.first() {
-                    // Found a supervisor in ancestry
-                    let evidence = vec![SupervisionEvidence {
-                        evidence_type: EvidenceType::Ancestry,
-                        description: format!(
-                            "Ancestor PID {} ({}) matches supervisor pattern '{}'",
-                            current_pid, comm, pattern.name
-                        ),
-                        weight: pattern.confidence_weight,
-                    }];
-
-                    return Ok(SupervisionResult::supervised_by_ancestry(
-                        pattern.category,
-                        pattern.name.clone(),
-                        ProcessId(current_pid),
-                        depth,
-                        pattern.confidence_weight,
-                        evidence,
-                        ancestry_chain,
-                    ));
-                }
+            if let Some(pattern) = supervisor {
+                // Found a supervisor in ancestry
+                let evidence = vec![SupervisionEvidence {
+                    evidence_type: EvidenceType::Ancestry,
+                    description: format!(
+                        "Ancestor PID {} ({}) matches supervisor pattern '{}'",
+                        current_pid, comm, pattern.name
+                    ),
+                    weight: pattern.confidence_weight,
+                }];
+
+                return Ok(SupervisionResult::supervised_by_ancestry(
+                    pattern.category,
+                    pattern.name.clone(),
+                    ProcessId(current_pid),
+                    depth,
+                    pattern.confidence_weight,
+                    evidence,
+                    ancestry_chain,
+                ));
             }
 
             // Move to parent
@@ -449,6 +483,72 @@ mod tests {
         assert!(cache.comm_map.is_empty());
     }
 
+    fn record(pid: u32, ppid: u32, comm: &str, cmd: &str) -> ProcessRecord {
+        use crate::collect::ProcessState;
+        use pt_common::StartId;
+        ProcessRecord {
+            pid: ProcessId(pid),
+            ppid: ProcessId(ppid),
+            uid: 1000,
+            user: "dev".to_string(),
+            pgid: None,
+            sid: None,
+            start_id: StartId("test:0:0".to_string()),
+            comm: comm.to_string(),
+            cmd: cmd.to_string(),
+            state: ProcessState::Sleeping,
+            cpu_percent: 0.0,
+            rss_bytes: 0,
+            vsz_bytes: 0,
+            tty: None,
+            start_time_unix: 0,
+            elapsed: std::time::Duration::from_secs(0),
+            source: "test".to_string(),
+            container_info: None,
+            io_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_process_table_finds_ide_helper_supervisor() {
+        use crate::supervision::SupervisorCategory;
+
+        let server = "/home/dev/.vscode-server/bin/abc123/node";
+        let table = vec![
+            record(1, 0, "init", "/sbin/init"),
+            record(
+                4_100_010,
+                1,
+                "node",
+                &format!("{server} /home/dev/.vscode-server/bin/abc123/out/server-main.js"),
+            ),
+            record(
+                4_100_011,
+                4_100_010,
+                "node",
+                &format!("{server} --type=extensionHost"),
+            ),
+            record(
+                4_100_012,
+                4_100_011,
+                "node",
+                "/usr/bin/node /home/dev/proj/node_modules/typescript/lib/tsserver.js",
+            ),
+            record(4_100_020, 1, "node", "node server.js"),
+        ];
+        let mut analyzer = AncestryAnalyzer::with_process_table(&table);
+
+        let result = analyzer.analyze(4_100_012).unwrap();
+        assert!(result.is_supervised);
+        assert_eq!(result.supervisor_type, Some(SupervisorCategory::Ide));
+        assert_eq!(result.supervisor_name.as_deref(), Some("vscode-server"));
+        assert_eq!(result.supervisor_pid, Some(ProcessId(4_100_011)));
+
+        let result = analyzer.analyze(4_100_020).unwrap();
+        assert!(!result.is_supervised);
+        assert_eq!(result.ancestry_chain.len(), 2);
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_analyze_current_process() {
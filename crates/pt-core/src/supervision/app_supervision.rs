@@ -187,7 +187,7 @@ pub enum AppActionType {
 }
 
 /// Escape a string for safe use in shell commands.
-fn shell_escape(s: &str) -> String {
+pub(super) fn shell_escape(s: &str) -> String {
     if s.is_empty() {
         return "''".to_string();
     }
@@ -3,12 +3,19 @@
 //! Estimates the downstream impact of killing a process by examining:
 //! - Child process subtree (PPID descendants)
 //! - Listening ports that would become unavailable
+//! - Downstream clients connected to those ports
 //! - Open write file handles (locks, WAL/journals, databases)
 //!
 //! Produces a structured `BlastRadius` with a cumulative risk score
-//! and human-readable summary.
+//! and human-readable summary. The policy enforcer's data-loss gates read it
+//! (`block_if_serving_clients`, `max_blast_radius_score`, and the write-FD and
+//! lock gates when those were not measured directly).
 
+#[cfg(target_os = "linux")]
+use crate::collect::{FdInfo, FdType, NetworkInfo, TcpState};
 use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 
 // ---------------------------------------------------------------------------
@@ -29,6 +36,7 @@ pub struct RiskFactor {
 pub enum RiskCategory {
     Children,
     ListenPort,
+    ClientConnection,
     WriteHandle,
     Database,
     Lock,
@@ -40,6 +48,7 @@ impl RiskCategory {
         match self {
             Self::Children => "child processes",
             Self::ListenPort => "listening port",
+            Self::ClientConnection => "client connection",
             Self::WriteHandle => "write handle",
             Self::Database => "database file",
             Self::Lock => "lock file",
@@ -65,6 +74,14 @@ pub struct ListeningPort {
     pub address: String,
 }
 
+/// An established client connection to one of the target's listening ports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientConnection {
+    pub local_port: u16,
+    pub remote_addr: String,
+    pub remote_port: u16,
+}
+
 /// Information about an open write handle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WriteHandle {
@@ -79,6 +96,8 @@ pub struct BlastRadius {
     pub target_pid: u32,
     pub children: Vec<ChildProcess>,
     pub listen_ports: Vec<ListeningPort>,
+    #[serde(default)]
+    pub client_connections: Vec<ClientConnection>,
     pub write_handles: Vec<WriteHandle>,
     pub risk_factors: Vec<RiskFactor>,
     /// Cumulative risk score (0.0 = safe, higher = riskier).
@@ -87,6 +106,19 @@ pub struct BlastRadius {
     pub summary: String,
 }
 
+impl BlastRadius {
+    /// Whether the process writes to a database or lock file.
+    pub fn holds_locks(&self) -> bool {
+        self.risk_factors
+            .iter()
+            .any(|f| matches!(f.category, RiskCategory::Database | RiskCategory::Lock))
+    }
+
+    pub fn risk_level(&self) -> &'static str {
+        risk_level(self.risk_score)
+    }
+}
+
 /// Input data for blast radius computation (avoids direct /proc access).
 #[derive(Debug, Clone, Default)]
 pub struct BlastRadiusInput {
@@ -96,12 +128,67 @@ pub struct BlastRadiusInput {
     pub process_table: HashMap<u32, (String, u32)>,
     /// Listening ports held by the target process.
     pub listen_ports: Vec<ListeningPort>,
+    /// Established client connections to those ports.
+    pub client_connections: Vec<ClientConnection>,
     /// Open files with write mode for the target process.
     pub open_write_files: Vec<(u32, String)>,
     /// Paths flagged as critical writes.
     pub critical_paths: Vec<String>,
 }
 
+impl BlastRadiusInput {
+    /// Replace the target's write handles, ports and clients with
+    /// already-collected FD and socket information.
+    ///
+    /// Write handles are regular files open for writing; critical writes
+    /// detected by the FD scan are flagged. Client connections are established
+    /// TCP connections whose local port is one the target listens on.
+    #[cfg(target_os = "linux")]
+    pub fn load_collected(&mut self, fd: Option<&FdInfo>, net: Option<&NetworkInfo>) {
+        self.open_write_files.clear();
+        self.critical_paths.clear();
+        self.listen_ports.clear();
+        self.client_connections.clear();
+        if let Some(fd) = fd {
+            self.open_write_files = fd
+                .open_files
+                .iter()
+                .filter(|f| f.mode.write && f.fd_type == FdType::File)
+                .map(|f| (f.fd, f.path.clone()))
+                .collect();
+            self.critical_paths = fd.critical_writes.iter().map(|c| c.path.clone()).collect();
+        }
+        if let Some(net) = net {
+            let mut seen = BTreeSet::new();
+            for port in &net.listen_ports {
+                if seen.insert((port.protocol.clone(), port.port)) {
+                    self.listen_ports.push(ListeningPort {
+                        port: port.port,
+                        protocol: port.protocol.clone(),
+                        address: port.address.clone(),
+                    });
+                }
+            }
+            let tcp_ports: BTreeSet<u16> = net
+                .listen_ports
+                .iter()
+                .filter(|p| p.protocol.starts_with("tcp"))
+                .map(|p| p.port)
+                .collect();
+            self.client_connections = net
+                .tcp_connections
+                .iter()
+                .filter(|c| c.state == TcpState::Established && tcp_ports.contains(&c.local_port))
+                .map(|c| ClientConnection {
+                    local_port: c.local_port,
+                    remote_addr: c.remote_addr.clone(),
+                    remote_port: c.remote_port,
+                })
+                .collect();
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Analysis
 // ---------------------------------------------------------------------------
@@ -136,6 +223,20 @@ pub fn compute_blast_radius(input: &BlastRadiusInput) -> BlastRadius {
         });
     }
 
+    // Downstream client risk: every connected client sees a reset.
+    if !input.client_connections.is_empty() {
+        let client_weight = (input.client_connections.len() as f64).ln_1p();
+        risk_score += client_weight;
+        risk_factors.push(RiskFactor {
+            category: RiskCategory::ClientConnection,
+            description: format!(
+                "{} connected client(s) would be dropped",
+                input.client_connections.len()
+            ),
+            weight: client_weight,
+        });
+    }
+
     // Write handle risk.
     for wh in &write_handles {
         let (category, weight) = categorize_write_handle(&wh.path, wh.is_critical);
@@ -147,12 +248,19 @@ pub fn compute_blast_radius(input: &BlastRadiusInput) -> BlastRadius {
         });
     }
 
-    let summary = build_summary(&children, &input.listen_ports, &write_handles, risk_score);
+    let summary = build_summary(
+        &children,
+        &input.listen_ports,
+        &input.client_connections,
+        &write_handles,
+        risk_score,
+    );
 
     BlastRadius {
         target_pid: input.target_pid,
         children,
         listen_ports: input.listen_ports.clone(),
+        client_connections: input.client_connections.clone(),
         write_handles,
         risk_factors,
         risk_score,
@@ -242,6 +350,7 @@ fn categorize_write_handle(path: &str, is_critical: bool) -> (RiskCategory, f64)
 fn build_summary(
     children: &[ChildProcess],
     ports: &[ListeningPort],
+    clients: &[ClientConnection],
     writes: &[WriteHandle],
     risk_score: f64,
 ) -> String {
@@ -259,26 +368,33 @@ fn build_summary(
         parts.push(format!("serves port {}", port.port));
     }
 
+    if !clients.is_empty() {
+        parts.push(format!("drops {} client connection(s)", clients.len()));
+    }
+
     if parts.is_empty() {
         return format!("Minimal blast radius (risk score: {:.1}).", risk_score);
     }
 
-    let risk_label = if risk_score > 5.0 {
-        "HIGH"
-    } else if risk_score > 2.0 {
-        "MEDIUM"
-    } else {
-        "LOW"
-    };
-
     format!(
         "{}. Risk: {} ({:.1}).",
         capitalise_first(&parts.join("; ")),
-        risk_label,
+        risk_level(risk_score).to_uppercase(),
         risk_score,
     )
 }
 
+/// Risk level for a score: `low`, `medium` (> 2) or `high` (> 5).
+pub fn risk_level(risk_score: f64) -> &'static str {
+    if risk_score > 5.0 {
+        "high"
+    } else if risk_score > 2.0 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
 fn capitalise_first(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
@@ -438,6 +554,88 @@ mod tests {
         assert_eq!(restored.children.len(), 1);
     }
 
+    #[test]
+    fn test_client_connection_risk() {
+        let client = |port: u16| ClientConnection {
+            local_port: 5432,
+            remote_addr: "10.0.0.7".to_string(),
+            remote_port: port,
+        };
+        let input = BlastRadiusInput {
+            target_pid: 100,
+            target_comm: "postgres".to_string(),
+            process_table: make_table(&[(100, "postgres", 1)]),
+            client_connections: vec![client(40000), client(40001), client(40002)],
+            open_write_files: vec![(7, "/var/lib/pg/pg.lock".to_string())],
+            ..Default::default()
+        };
+        let br = compute_blast_radius(&input);
+        // 3 clients: ln(4) ≈ 1.386, lock: 2.0
+        assert!((br.risk_score - (4.0f64.ln() + 2.0)).abs() < 1e-9);
+        assert!(br.summary.starts_with("Drops 3 client connection(s)"));
+        assert!(br.holds_locks());
+        assert_eq!(br.risk_level(), "medium");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_load_collected() {
+        use crate::collect::{ListenPort, OpenFile, OpenMode, SocketCounts, TcpConnection};
+
+        let file = |fd: u32, path: &str, write: bool| OpenFile {
+            fd,
+            path: path.to_string(),
+            fd_type: FdType::File,
+            mode: OpenMode { read: true, write },
+        };
+        let fd = FdInfo {
+            open_files: vec![
+                file(3, "/srv/app.log", true),
+                file(4, "/etc/app.conf", false),
+            ],
+            ..Default::default()
+        };
+        let conn = |local_port: u16, remote_port: u16, state: TcpState| TcpConnection {
+            local_addr: "0.0.0.0".to_string(),
+            local_port,
+            remote_addr: "10.0.0.7".to_string(),
+            remote_port,
+            state,
+            inode: 0,
+            is_ipv6: false,
+        };
+        let net = NetworkInfo {
+            socket_counts: SocketCounts::default(),
+            tcp_connections: vec![
+                conn(8080, 0, TcpState::Listen),
+                conn(8080, 51000, TcpState::Established),
+                conn(8080, 51001, TcpState::TimeWait),
+                // Outbound connection, not a client of ours.
+                conn(42000, 5432, TcpState::Established),
+            ],
+            udp_sockets: Vec::new(),
+            listen_ports: vec![ListenPort {
+                protocol: "tcp".to_string(),
+                port: 8080,
+                address: "0.0.0.0".to_string(),
+                inode: 0,
+            }],
+            unix_sockets: Vec::new(),
+        };
+        let mut input = BlastRadiusInput {
+            open_write_files: vec![(9, "/stale".to_string())],
+            ..Default::default()
+        };
+        input.load_collected(Some(&fd), Some(&net));
+        assert_eq!(
+            input.open_write_files,
+            vec![(3, "/srv/app.log".to_string())]
+        );
+        assert_eq!(input.listen_ports.len(), 1);
+        assert_eq!(input.client_connections.len(), 1);
+        assert_eq!(input.client_connections[0].remote_port, 51000);
+    }
+
     #[test]
     fn test_summary_format() {
         let input = BlastRadiusInput {
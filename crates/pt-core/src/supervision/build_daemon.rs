@@ -0,0 +1,550 @@
+//! Build-daemon detection.
+//!
+//! Build tools keep long-lived servers around to make the next build fast:
+//! Gradle and Kotlin compile daemons, the Bazel server, the sccache server and
+//! `cargo watch` loops. They idle by design, so idleness alone says little.
+//! What marks one as left behind is that nothing can use it any more: its
+//! Gradle distribution or Bazel workspace was deleted, another sccache server
+//! took over the port, or the terminal that ran `cargo watch` is gone.
+//!
+//! Detection works on the scanned process table (command lines and parent
+//! links), so it runs on every platform. When the tool has a graceful shutdown
+//! command, the result carries it; `pt` only reports it, never runs it.
+
+use super::app_supervision::shell_escape;
+use crate::collect::ProcessRecord;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Kind of build daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildDaemonKind {
+    /// Gradle build daemon (`org.gradle.launcher.daemon.bootstrap.GradleDaemon`).
+    GradleDaemon,
+    /// Kotlin compile daemon started by Gradle or an IDE.
+    KotlinDaemon,
+    /// Bazel server (`A-server.jar`).
+    BazelServer,
+    /// sccache compilation cache server.
+    SccacheServer,
+    /// `cargo watch` file-watch loop.
+    CargoWatch,
+}
+
+impl std::fmt::Display for BuildDaemonKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            BuildDaemonKind::GradleDaemon => "gradle_daemon",
+            BuildDaemonKind::KotlinDaemon => "kotlin_daemon",
+            BuildDaemonKind::BazelServer => "bazel_server",
+            BuildDaemonKind::SccacheServer => "sccache_server",
+            BuildDaemonKind::CargoWatch => "cargo_watch",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Whether a build daemon can still be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildDaemonState {
+    /// A client (build tool or IDE) is running.
+    Active,
+    /// No client right now, but the next build will reuse it.
+    Idle,
+    /// Nothing can reach it again: its installation, workspace, clients or
+    /// terminal are gone.
+    Orphaned,
+    /// Defunct, or shadowed by a newer instance of the same server.
+    Zombie,
+}
+
+impl BuildDaemonState {
+    /// Whether the daemon can be shut down without slowing any future build.
+    pub fn is_reclaimable(self) -> bool {
+        matches!(self, BuildDaemonState::Orphaned | BuildDaemonState::Zombie)
+    }
+}
+
+/// A detected build daemon and what to do about it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildDaemonInfo {
+    /// Kind of daemon.
+    pub kind: BuildDaemonKind,
+    /// Usability of the daemon.
+    pub state: BuildDaemonState,
+    /// Tool version, when the command line names it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Installation or workspace the daemon serves (Gradle home, Bazel workspace).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Why the state was chosen.
+    pub reasons: Vec<String>,
+    /// Graceful shutdown command, when the tool has one that reaches this
+    /// instance (for reference, NOT auto-executed).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shutdown_command: Option<String>,
+}
+
+impl BuildDaemonInfo {
+    /// Recommended action label: `shutdown` when a graceful command exists,
+    /// otherwise `kill` (SIGTERM lets all of these exit cleanly).
+    pub fn recommended_action(&self) -> &'static str {
+        if self.shutdown_command.is_some() {
+            "shutdown"
+        } else {
+            "kill"
+        }
+    }
+
+    /// One-line description for rationales.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} {}", state_label(self.state), kind_label(self.kind));
+        if let Some(version) = &self.version {
+            summary.push_str(&format!(" {}", version));
+        }
+        if let Some(reason) = self.reasons.first() {
+            summary.push_str(&format!(" ({})", reason));
+        }
+        summary
+    }
+}
+
+fn kind_label(kind: BuildDaemonKind) -> &'static str {
+    match kind {
+        BuildDaemonKind::GradleDaemon => "Gradle daemon",
+        BuildDaemonKind::KotlinDaemon => "Kotlin compile daemon",
+        BuildDaemonKind::BazelServer => "Bazel server",
+        BuildDaemonKind::SccacheServer => "sccache server",
+        BuildDaemonKind::CargoWatch => "cargo watch loop",
+    }
+}
+
+fn state_label(state: BuildDaemonState) -> &'static str {
+    match state {
+        BuildDaemonState::Active => "active",
+        BuildDaemonState::Idle => "idle",
+        BuildDaemonState::Orphaned => "orphaned",
+        BuildDaemonState::Zombie => "zombie",
+    }
+}
+
+/// Classify a process as a build daemon from its comm and command line.
+pub fn build_daemon_kind(comm: &str, cmd: &str) -> Option<BuildDaemonKind> {
+    let mut args = cmd.split_whitespace();
+    let argv0 = args.next().unwrap_or_default();
+    if cmd.contains("org.gradle.launcher.daemon.bootstrap.GradleDaemon") {
+        Some(BuildDaemonKind::GradleDaemon)
+    } else if cmd.contains("org.jetbrains.kotlin.daemon.KotlinCompileDaemon") {
+        Some(BuildDaemonKind::KotlinDaemon)
+    } else if argv0.starts_with("bazel(") || cmd.contains("/A-server.jar") {
+        Some(BuildDaemonKind::BazelServer)
+    } else if comm == "sccache" && args.next().is_none() {
+        // Clients run `sccache <compiler> ...`; the server has no arguments.
+        Some(BuildDaemonKind::SccacheServer)
+    } else if comm == "cargo-watch" {
+        Some(BuildDaemonKind::CargoWatch)
+    } else {
+        None
+    }
+}
+
+/// Value of a `--flag=value` or `--flag value` argument.
+fn flag_value<'a>(cmd: &'a str, flag: &str) -> Option<&'a str> {
+    let mut args = cmd.split_whitespace();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix(flag) {
+            if let Some(value) = value.strip_prefix('=') {
+                return Some(value);
+            }
+            if value.is_empty() {
+                return args.next();
+            }
+        }
+    }
+    None
+}
+
+/// Gradle home and version of a daemon, from its launcher classpath entry
+/// (`<home>/lib/gradle-launcher-8.5.jar`, or `gradle-daemon-main` since 8.8).
+fn gradle_home_and_version(cmd: &str) -> (Option<String>, Option<String>) {
+    let re = regex::Regex::new(r"([^\s:]+)/lib/gradle-(?:launcher|daemon-main)-(\d[^/\s]*)\.jar")
+        .expect("valid regex");
+    let home = re.captures(cmd).map(|c| c[1].to_string());
+    // The daemon's version is also its first argument.
+    let version = cmd
+        .split_whitespace()
+        .skip_while(|arg| *arg != "org.gradle.launcher.daemon.bootstrap.GradleDaemon")
+        .nth(1)
+        .map(String::from)
+        .or_else(|| re.captures(cmd).map(|c| c[2].to_string()));
+    (home, version)
+}
+
+/// Build-daemon analyzer over a scanned process table.
+pub struct BuildDaemonAnalyzer<'a> {
+    processes: &'a [ProcessRecord],
+}
+
+impl<'a> BuildDaemonAnalyzer<'a> {
+    /// Create an analyzer over `processes` (the whole scan, not just candidates).
+    pub fn new(processes: &'a [ProcessRecord]) -> Self {
+        Self { processes }
+    }
+
+    /// Detect and assess `proc` as a build daemon.
+    pub fn analyze(&self, proc: &ProcessRecord) -> Option<BuildDaemonInfo> {
+        self.analyze_with(proc, |path| path.exists())
+    }
+
+    /// Like [`analyze`](Self::analyze), with `exists` checking paths.
+    pub fn analyze_with(
+        &self,
+        proc: &ProcessRecord,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Option<BuildDaemonInfo> {
+        let kind = build_daemon_kind(&proc.comm, &proc.cmd)?;
+        let mut info = BuildDaemonInfo {
+            kind,
+            state: BuildDaemonState::Idle,
+            version: None,
+            workspace: None,
+            reasons: vec![],
+            shutdown_command: None,
+        };
+        if proc.state.is_zombie() {
+            info.state = BuildDaemonState::Zombie;
+            info.reasons.push("process is defunct".to_string());
+            return Some(info);
+        }
+        match kind {
+            BuildDaemonKind::GradleDaemon => self.assess_gradle(proc, &mut info, &exists),
+            BuildDaemonKind::KotlinDaemon => self.assess_kotlin(&mut info),
+            BuildDaemonKind::BazelServer => self.assess_bazel(proc, &mut info, &exists),
+            BuildDaemonKind::SccacheServer => self.assess_sccache(proc, &mut info),
+            BuildDaemonKind::CargoWatch => self.assess_cargo_watch(proc, &mut info),
+        }
+        Some(info)
+    }
+
+    fn others(&self, proc: &ProcessRecord) -> impl Iterator<Item = &'a ProcessRecord> + '_ {
+        let pid = proc.pid;
+        self.processes.iter().filter(move |p| p.pid != pid)
+    }
+
+    fn any_running(&self, pred: impl Fn(&ProcessRecord) -> bool) -> bool {
+        self.processes
+            .iter()
+            .any(|p| !p.state.is_zombie() && pred(p))
+    }
+
+    fn assess_gradle(
+        &self,
+        proc: &ProcessRecord,
+        info: &mut BuildDaemonInfo,
+        exists: &impl Fn(&Path) -> bool,
+    ) {
+        let (home, version) = gradle_home_and_version(&proc.cmd);
+        info.version = version;
+        info.workspace = home.clone();
+        if let Some(home) = home.as_deref().filter(|home| !exists(Path::new(home))) {
+            // `gradle --stop` of another distribution only stops its own version.
+            info.state = BuildDaemonState::Orphaned;
+            info.reasons
+                .push(format!("Gradle distribution {} was removed", home));
+            return;
+        }
+        info.shutdown_command = Some(match &home {
+            Some(home) => format!("{} --stop", shell_escape(&format!("{}/bin/gradle", home))),
+            None => "gradle --stop".to_string(),
+        });
+        if self.any_running(|p| is_gradle_client(p) || is_jvm_ide(p)) {
+            info.state = BuildDaemonState::Active;
+            info.reasons
+                .push("a Gradle client or IDE is running".to_string());
+        } else {
+            info.reasons
+                .push("no Gradle client or IDE is running".to_string());
+        }
+    }
+
+    fn assess_kotlin(&self, info: &mut BuildDaemonInfo) {
+        // Its clients are Gradle daemons and IDEs; without them it only waits
+        // for its own idle timeout.
+        if self.any_running(|p| {
+            build_daemon_kind(&p.comm, &p.cmd) == Some(BuildDaemonKind::GradleDaemon)
+                || is_gradle_client(p)
+                || is_jvm_ide(p)
+        }) {
+            info.state = BuildDaemonState::Active;
+            info.reasons
+                .push("a Gradle daemon or IDE is running".to_string());
+        } else {
+            info.state = BuildDaemonState::Orphaned;
+            info.reasons
+                .push("no Gradle daemon or IDE is running".to_string());
+        }
+    }
+
+    fn assess_bazel(
+        &self,
+        proc: &ProcessRecord,
+        info: &mut BuildDaemonInfo,
+        exists: &impl Fn(&Path) -> bool,
+    ) {
+        let workspace = flag_value(&proc.cmd, "--workspace_directory");
+        let output_base = flag_value(&proc.cmd, "--output_base");
+        info.workspace = workspace.map(String::from);
+        if let Some(workspace) = workspace.filter(|ws| !exists(Path::new(ws))) {
+            info.state = BuildDaemonState::Orphaned;
+            info.reasons
+                .push(format!("workspace {} was removed", workspace));
+            return;
+        }
+        if let Some(output_base) = output_base.filter(|ob| !exists(Path::new(ob))) {
+            info.state = BuildDaemonState::Orphaned;
+            info.reasons
+                .push(format!("output base {} was removed", output_base));
+            return;
+        }
+        if let Some(workspace) = workspace {
+            info.shutdown_command =
+                Some(format!("cd {} && bazel shutdown", shell_escape(workspace)));
+        }
+        if self.any_running(|p| matches!(p.comm.as_str(), "bazel" | "bazelisk")) {
+            info.state = BuildDaemonState::Active;
+            info.reasons.push("a bazel client is running".to_string());
+        } else {
+            info.reasons.push("no bazel client is running".to_string());
+        }
+    }
+
+    fn assess_sccache(&self, proc: &ProcessRecord, info: &mut BuildDaemonInfo) {
+        // Only one server per user can hold the port; an older one left behind
+        // after a newer one started serves nobody.
+        let newer = self.others(proc).find(|p| {
+            p.uid == proc.uid
+                && !p.state.is_zombie()
+                && build_daemon_kind(&p.comm, &p.cmd) == Some(BuildDaemonKind::SccacheServer)
+                && p.start_time_unix > proc.start_time_unix
+        });
+        if let Some(newer) = newer {
+            info.state = BuildDaemonState::Zombie;
+            info.reasons
+                .push(format!("shadowed by newer sccache server {}", newer.pid.0));
+            return;
+        }
+        info.shutdown_command = Some("sccache --stop-server".to_string());
+        if self.any_running(|p| p.comm == "sccache" && p.cmd.split_whitespace().nth(1).is_some()) {
+            info.state = BuildDaemonState::Active;
+            info.reasons
+                .push("compilations are running through it".to_string());
+        } else {
+            info.reasons
+                .push("no compilation is running through it".to_string());
+        }
+    }
+
+    fn assess_cargo_watch(&self, proc: &ProcessRecord, info: &mut BuildDaemonInfo) {
+        let parent_alive = self.processes.iter().any(|p| p.pid == proc.ppid);
+        if proc.is_orphan() || !parent_alive || !proc.has_tty() {
+            info.state = BuildDaemonState::Orphaned;
+            info.reasons
+                .push("the terminal that started it is gone".to_string());
+        } else if self.others(proc).any(|p| p.ppid == proc.pid) {
+            info.state = BuildDaemonState::Active;
+            info.reasons
+                .push("a watched command is running".to_string());
+        } else {
+            info.reasons.push("waiting for file changes".to_string());
+        }
+    }
+}
+
+fn is_gradle_client(p: &ProcessRecord) -> bool {
+    matches!(p.comm.as_str(), "gradle" | "gradlew")
+        || p.cmd.contains("org.gradle.wrapper.GradleWrapperMain")
+        || p.cmd.contains("org.gradle.launcher.GradleMain")
+}
+
+fn is_jvm_ide(p: &ProcessRecord) -> bool {
+    matches!(p.comm.as_str(), "idea" | "studio" | "android-studio")
+        || p.cmd.contains("com.intellij.idea.Main")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect::ProcessState;
+    use pt_common::{ProcessId, StartId};
+    use std::time::Duration;
+
+    fn record(pid: u32, ppid: u32, comm: &str, cmd: &str) -> ProcessRecord {
+        ProcessRecord {
+            pid: ProcessId(pid),
+            ppid: ProcessId(ppid),
+            uid: 1000,
+            user: "dev".to_string(),
+            pgid: None,
+            sid: None,
+            start_id: StartId("test:0:0".to_string()),
+            comm: comm.to_string(),
+            cmd: cmd.to_string(),
+            state: ProcessState::Sleeping,
+            cpu_percent: 0.0,
+            rss_bytes: 0,
+            vsz_bytes: 0,
+            tty: None,
+            start_time_unix: 1_000,
+            elapsed: Duration::from_secs(3600),
+            source: "test".to_string(),
+            container_info: None,
+            io_rate: None,
+        }
+    }
+
+    const GRADLE_DAEMON: &str = "/usr/lib/jvm/java-17/bin/java -Xmx2g -cp /home/dev/.gradle/wrapper/dists/gradle-8.5-bin/abc/gradle-8.5/lib/gradle-launcher-8.5.jar org.gradle.launcher.daemon.bootstrap.GradleDaemon 8.5";
+
+    #[test]
+    fn classifies_build_daemons() {
+        assert_eq!(
+            build_daemon_kind("java", GRADLE_DAEMON),
+            Some(BuildDaemonKind::GradleDaemon)
+        );
+        assert_eq!(
+            build_daemon_kind(
+                "java",
+                "bazel(proj) -Xverify:none -jar /home/dev/.cache/bazel/_bazel_dev/install/0f/A-server.jar --max_idle_secs=10800"
+            ),
+            Some(BuildDaemonKind::BazelServer)
+        );
+        assert_eq!(
+            build_daemon_kind("sccache", "/home/dev/.cargo/bin/sccache"),
+            Some(BuildDaemonKind::SccacheServer)
+        );
+        assert_eq!(
+            build_daemon_kind("sccache", "sccache rustc --crate-name foo"),
+            None
+        );
+        assert_eq!(
+            build_daemon_kind("cargo-watch", "cargo-watch watch -x test"),
+            Some(BuildDaemonKind::CargoWatch)
+        );
+        assert_eq!(build_daemon_kind("java", "java -jar app.jar"), None);
+    }
+
+    #[test]
+    fn gradle_daemon_with_removed_distribution_is_orphaned() {
+        let daemon = record(100, 1, "java", GRADLE_DAEMON);
+        let table = vec![daemon.clone()];
+        let analyzer = BuildDaemonAnalyzer::new(&table);
+
+        let idle = analyzer.analyze_with(&daemon, |_| true).unwrap();
+        assert_eq!(idle.state, BuildDaemonState::Idle);
+        assert_eq!(idle.version.as_deref(), Some("8.5"));
+        assert_eq!(
+            idle.shutdown_command.as_deref(),
+            Some("/home/dev/.gradle/wrapper/dists/gradle-8.5-bin/abc/gradle-8.5/bin/gradle --stop")
+        );
+        assert_eq!(idle.recommended_action(), "shutdown");
+        assert!(!idle.state.is_reclaimable());
+
+        let orphaned = analyzer.analyze_with(&daemon, |_| false).unwrap();
+        assert_eq!(orphaned.state, BuildDaemonState::Orphaned);
+        assert!(orphaned.state.is_reclaimable());
+        assert_eq!(orphaned.shutdown_command, None);
+        assert_eq!(orphaned.recommended_action(), "kill");
+        assert!(orphaned
+            .summary()
+            .starts_with("orphaned Gradle daemon 8.5 (Gradle distribution"));
+
+        let with_client = vec![
+            daemon.clone(),
+            record(
+                200,
+                50,
+                "java",
+                "java -cp gradle-wrapper.jar org.gradle.wrapper.GradleWrapperMain build",
+            ),
+        ];
+        let active = BuildDaemonAnalyzer::new(&with_client)
+            .analyze_with(&daemon, |_| true)
+            .unwrap();
+        assert_eq!(active.state, BuildDaemonState::Active);
+    }
+
+    #[test]
+    fn bazel_server_of_deleted_workspace_is_orphaned() {
+        let server = record(
+            300,
+            1,
+            "java",
+            "bazel(proj) -jar /home/dev/.cache/bazel/_bazel_dev/install/0f/A-server.jar --output_base=/home/dev/.cache/bazel/_bazel_dev/9a --workspace_directory=/home/dev/proj",
+        );
+        let table = vec![server.clone()];
+        let analyzer = BuildDaemonAnalyzer::new(&table);
+
+        let idle = analyzer.analyze_with(&server, |_| true).unwrap();
+        assert_eq!(idle.state, BuildDaemonState::Idle);
+        assert_eq!(idle.workspace.as_deref(), Some("/home/dev/proj"));
+        assert_eq!(
+            idle.shutdown_command.as_deref(),
+            Some("cd /home/dev/proj && bazel shutdown")
+        );
+
+        let stale = analyzer
+            .analyze_with(&server, |path| path != Path::new("/home/dev/proj"))
+            .unwrap();
+        assert_eq!(stale.state, BuildDaemonState::Orphaned);
+        assert_eq!(stale.shutdown_command, None);
+    }
+
+    #[test]
+    fn older_sccache_server_is_a_zombie() {
+        let old = record(400, 1, "sccache", "sccache");
+        let mut new = record(401, 1, "sccache", "sccache");
+        new.start_time_unix = 2_000;
+        let table = vec![old.clone(), new.clone()];
+        let analyzer = BuildDaemonAnalyzer::new(&table);
+
+        let shadowed = analyzer.analyze(&old).unwrap();
+        assert_eq!(shadowed.state, BuildDaemonState::Zombie);
+        assert_eq!(shadowed.shutdown_command, None);
+
+        let current = analyzer.analyze(&new).unwrap();
+        assert_eq!(current.state, BuildDaemonState::Idle);
+        assert_eq!(
+            current.shutdown_command.as_deref(),
+            Some("sccache --stop-server")
+        );
+
+        let mut defunct = old.clone();
+        defunct.state = ProcessState::Zombie;
+        assert_eq!(
+            analyzer.analyze(&defunct).unwrap().state,
+            BuildDaemonState::Zombie
+        );
+    }
+
+    #[test]
+    fn cargo_watch_without_its_terminal_is_orphaned() {
+        let shell = record(500, 1, "bash", "-bash");
+        let mut watch = record(501, 500, "cargo-watch", "cargo-watch watch -x test");
+        watch.tty = Some("pts/3".to_string());
+        let table = vec![shell.clone(), watch.clone()];
+        let analyzer = BuildDaemonAnalyzer::new(&table);
+        assert_eq!(
+            analyzer.analyze(&watch).unwrap().state,
+            BuildDaemonState::Idle
+        );
+
+        let mut detached = watch.clone();
+        detached.ppid = ProcessId(1);
+        detached.tty = None;
+        let info = analyzer.analyze(&detached).unwrap();
+        assert_eq!(info.state, BuildDaemonState::Orphaned);
+        assert_eq!(info.recommended_action(), "kill");
+    }
+}
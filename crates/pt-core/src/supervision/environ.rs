@@ -160,6 +160,12 @@ impl EnvironDatabase {
             EnvPattern::new("vscode", SupervisorCategory::Ide, "TERM_PROGRAM", 0.80)
                 .with_value("vscode"),
         );
+        self.add(EnvPattern::new(
+            "vscode-server",
+            SupervisorCategory::Ide,
+            "VSCODE_AGENT_FOLDER",
+            0.90,
+        ));
 
         // Claude
         self.add(EnvPattern::new(
@@ -180,6 +186,11 @@ impl EnvironDatabase {
             "CLAUDE_ENTRYPOINT",
             0.90,
         ));
+        // Set for every command the agent runs
+        self.add(
+            EnvPattern::new("claude", SupervisorCategory::Agent, "CLAUDECODE", 0.90)
+                .with_value("1"),
+        );
 
         // Codex
         self.add(EnvPattern::new(
@@ -195,6 +206,14 @@ impl EnvironDatabase {
             0.95,
         ));
 
+        // Gemini CLI
+        self.add(EnvPattern::new(
+            "gemini-cli",
+            SupervisorCategory::Agent,
+            "GEMINI_CLI",
+            0.90,
+        ));
+
         // Cursor
         self.add(EnvPattern::new(
             "cursor",
@@ -299,6 +318,15 @@ impl EnvironDatabase {
             "PYCHARM_VM_OPTIONS",
             0.85,
         ));
+        self.add(
+            EnvPattern::new(
+                "jetbrains",
+                SupervisorCategory::Ide,
+                "TERMINAL_EMULATOR",
+                0.80,
+            )
+            .with_value("JetBrains-JediTerm"),
+        );
 
         // macOS launchd (Orchestrator)
         // XPC_SERVICE_NAME is set for XPC services managed by launchd
@@ -532,6 +560,30 @@ mod tests {
         assert_eq!(result.category, Some(SupervisorCategory::Ci));
     }
 
+    #[test]
+    fn test_environ_analyzer_agent_and_ide_workers() {
+        let analyzer = EnvironAnalyzer::new();
+        let detect = |var: &str, value: &str| {
+            let env = HashMap::from([(var.to_string(), value.to_string())]);
+            let result = analyzer.analyze_env(&env);
+            result.supervisor_name.zip(result.category)
+        };
+
+        assert_eq!(
+            detect("CLAUDECODE", "1"),
+            Some(("claude".to_string(), SupervisorCategory::Agent))
+        );
+        assert_eq!(
+            detect("VSCODE_AGENT_FOLDER", "/home/dev/.vscode-server"),
+            Some(("vscode-server".to_string(), SupervisorCategory::Ide))
+        );
+        assert_eq!(
+            detect("TERMINAL_EMULATOR", "JetBrains-JediTerm"),
+            Some(("jetbrains".to_string(), SupervisorCategory::Ide))
+        );
+        assert_eq!(detect("TERMINAL_EMULATOR", "xterm"), None);
+    }
+
     #[test]
     fn test_environ_result_not_supervised() {
         let result = EnvironResult::not_supervised();
@@ -0,0 +1,923 @@
+//! Launch-origin attribution: who or what started a process.
+//!
+//! Killing a stray process rarely fixes the problem when something keeps
+//! launching it. This pass walks the ancestry, cgroup and environment of a
+//! process and names its origin:
+//!
+//! - **Container**: the cgroup belongs to a container runtime
+//! - **Cron**: a cron daemon is an ancestor, matched to a crontab entry
+//! - **Systemd unit**: the process runs in a service cgroup (plus its timer)
+//! - **SSH**: an interactive ssh session (`sshd` ancestor or `SSH_CONNECTION`)
+//! - **Desktop session**: a graphical session or desktop app scope
+//! - **Parent**: the nearest ancestor that is neither a shell nor init
+//!
+//! Each attribution carries the evidence used and remediation hints aimed at
+//! the origin (edit the crontab entry, stop the unit) rather than the process.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::session::SshConnectionInfo;
+use crate::collect::container::{
+    detect_container_from_cgroup, detect_kubernetes_from_env, ContainerRuntime,
+};
+use crate::collect::ProcessRecord;
+
+/// Cron daemon process names.
+const CRON_DAEMONS: &[&str] = &["cron", "crond", "anacron", "fcron", "busybox-crond"];
+
+/// Systemd units that run cron jobs.
+const CRON_UNITS: &[&str] = &[
+    "cron.service",
+    "crond.service",
+    "cronie.service",
+    "anacron.service",
+];
+
+/// Processes that only relay a launch (shells and exec wrappers).
+const PASSTHROUGH: &[&str] = &[
+    "sh", "bash", "dash", "zsh", "fish", "ksh", "mksh", "tcsh", "csh", "env", "sudo", "su",
+    "runuser", "nohup", "setsid", "timeout", "nice", "ionice", "stdbuf", "flock", "xargs",
+];
+
+/// Terminal emulators and multiplexers hosting interactive shells.
+const TERMINALS: &[&str] = &[
+    "screen",
+    "SCREEN",
+    "gnome-terminal-",
+    "konsole",
+    "xterm",
+    "alacritty",
+    "kitty",
+    "wezterm-gui",
+    "foot",
+    "tilix",
+    "terminator",
+    "xfce4-terminal",
+];
+
+/// Init processes; being parented by one says nothing about the origin.
+const INIT: &[&str] = &["systemd", "init", "launchd", "tini", "dumb-init"];
+
+/// Desktop session managers and shells.
+const DESKTOP_SESSIONS: &[&str] = &[
+    "gnome-session",
+    "gnome-session-b",
+    "gnome-shell",
+    "plasmashell",
+    "ksmserver",
+    "kwin_x11",
+    "kwin_wayland",
+    "xfce4-session",
+    "mate-session",
+    "cinnamon-sessio",
+    "lxsession",
+    "lxqt-session",
+    "sway",
+    "Hyprland",
+];
+
+/// Directories holding system unit files, searched for a matching timer.
+const SYSTEM_UNIT_DIRS: &[&str] = &[
+    "/etc/systemd/system",
+    "/run/systemd/system",
+    "/usr/lib/systemd/system",
+    "/lib/systemd/system",
+];
+
+/// Where a process was launched from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LaunchOrigin {
+    /// A systemd service or transient unit.
+    SystemdUnit {
+        unit: String,
+        /// Unit of the per-user manager (`systemctl --user`).
+        user_manager: bool,
+        /// Timer unit that activates the service, if one is installed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timer: Option<String>,
+    },
+    /// A cron job, with the crontab entry when one matched.
+    Cron {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        entry: Option<CronEntry>,
+    },
+    /// An interactive ssh session.
+    Ssh {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client: Option<String>,
+    },
+    /// A container runtime.
+    Container {
+        runtime: ContainerRuntime,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pod: Option<String>,
+    },
+    /// A graphical desktop session.
+    DesktopSession {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session: Option<String>,
+    },
+    /// Another traced parent process.
+    Parent { pid: u32, comm: String },
+    /// Nothing identifiable (e.g. reparented to init).
+    Unknown,
+}
+
+impl LaunchOrigin {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LaunchOrigin::SystemdUnit { .. } => "systemd_unit",
+            LaunchOrigin::Cron { .. } => "cron",
+            LaunchOrigin::Ssh { .. } => "ssh",
+            LaunchOrigin::Container { .. } => "container",
+            LaunchOrigin::DesktopSession { .. } => "desktop_session",
+            LaunchOrigin::Parent { .. } => "parent",
+            LaunchOrigin::Unknown => "unknown",
+        }
+    }
+}
+
+/// Launch origin of a process with the evidence behind it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchAttribution {
+    #[serde(flatten)]
+    pub origin: LaunchOrigin,
+    /// Observations that led to the origin.
+    pub evidence: Vec<String>,
+    /// Suggested fixes at the origin, so the process is not relaunched.
+    pub remediation: Vec<String>,
+}
+
+/// One crontab line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CronEntry {
+    /// Crontab file.
+    pub source: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// User the job runs as, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Schedule fields or `@` keyword.
+    pub schedule: String,
+    pub command: String,
+}
+
+/// Parsed crontabs of the host.
+#[derive(Debug, Clone, Default)]
+pub struct CronTable {
+    pub entries: Vec<CronEntry>,
+}
+
+impl CronTable {
+    /// Read the system crontab, `/etc/cron.d` and the per-user spools.
+    ///
+    /// Unreadable files (user spools need root) are skipped.
+    pub fn load() -> Self {
+        let mut table = Self::default();
+        table.add_file("/etc/crontab", true, None);
+        for path in sorted_files("/etc/cron.d") {
+            table.add_file(&path, true, None);
+        }
+        for dir in ["/var/spool/cron/crontabs", "/var/spool/cron"] {
+            for path in sorted_files(dir) {
+                let user = path.rsplit('/').next().map(str::to_string);
+                table.add_file(&path, false, user);
+            }
+        }
+        table
+    }
+
+    fn add_file(&mut self, path: &str, has_user_field: bool, user: Option<String>) {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            self.add(path, &content, has_user_field, user);
+        }
+    }
+
+    /// Parse crontab `content`. System crontabs (`has_user_field`) name the
+    /// user after the schedule; user crontabs run as `user`.
+    pub fn add(&mut self, source: &str, content: &str, has_user_field: bool, user: Option<String>) {
+        for (idx, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') || is_env_assignment(line) {
+                continue;
+            }
+            let schedule_fields = if line.starts_with('@') { 1 } else { 5 };
+            let mut rest = line;
+            let mut schedule = Vec::new();
+            for _ in 0..schedule_fields {
+                let Some((field, tail)) = split_field(rest) else {
+                    break;
+                };
+                schedule.push(field);
+                rest = tail;
+            }
+            if schedule.len() < schedule_fields {
+                continue;
+            }
+            let entry_user = if has_user_field {
+                let Some((field, tail)) = split_field(rest) else {
+                    continue;
+                };
+                rest = tail;
+                Some(field.to_string())
+            } else {
+                user.clone()
+            };
+            let command = rest.trim();
+            if command.is_empty() {
+                continue;
+            }
+            self.entries.push(CronEntry {
+                source: source.to_string(),
+                line: idx + 1,
+                user: entry_user,
+                schedule: schedule.join(" "),
+                command: command.to_string(),
+            });
+        }
+    }
+
+    /// Best entry for a job, given the command lines of the process and its
+    /// ancestors up to the cron daemon.
+    pub fn find_match(&self, cmdlines: &[&str], user: &str) -> Option<&CronEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.user.as_deref().is_none_or(|u| u == user))
+            .filter_map(|entry| {
+                let score = cmdlines
+                    .iter()
+                    .map(|cmd| cron_match_score(&entry.command, cmd))
+                    .max()
+                    .unwrap_or(0);
+                (score > 0).then_some((score, entry))
+            })
+            .max_by_key(|(score, entry)| (*score, std::cmp::Reverse(entry.line)))
+            .map(|(_, entry)| entry)
+    }
+}
+
+fn sorted_files(dir: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|e| e.path().display().to_string())
+        .filter(|p| !p.ends_with('~') && !p.contains("/."))
+        .collect();
+    files.sort();
+    files
+}
+
+fn split_field(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    if s.is_empty() {
+        return None;
+    }
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    Some((&s[..end], &s[end..]))
+}
+
+fn is_env_assignment(line: &str) -> bool {
+    match line.split_once('=') {
+        Some((name, _)) => {
+            let name = name.trim();
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// How well a crontab command explains a process command line: 3 for the
+/// whole command, 2 for a path it runs, 1 for the executable name.
+fn cron_match_score(entry_command: &str, cmdline: &str) -> u8 {
+    let cmdline = cmdline.trim();
+    if cmdline.is_empty() {
+        return 0;
+    }
+    // Cron runs `sh -c '<command>'`; `%` starts stdin in crontab syntax.
+    let command = entry_command.split('%').next().unwrap_or("").trim();
+    if !command.is_empty() && cmdline.contains(command) {
+        return 3;
+    }
+    let Some(exe) = cmdline.split_whitespace().next() else {
+        return 0;
+    };
+    let tokens: Vec<&str> = command
+        .split(|c: char| c.is_whitespace() || ";&|()<>`'\"".contains(c))
+        .filter(|t| !t.is_empty())
+        .collect();
+    // Interpreted jobs run as `python3 /path/to/job.py`, so any script path counts.
+    if cmdline
+        .split_whitespace()
+        .filter(|t| t.contains('/') && !t.starts_with("/dev/"))
+        .any(|t| tokens.contains(&t))
+    {
+        return 2;
+    }
+    let name = exe.rsplit('/').next().unwrap_or(exe);
+    if PASSTHROUGH.contains(&name) {
+        return 0;
+    }
+    if tokens.iter().any(|t| t.rsplit('/').next() == Some(name)) {
+        return 1;
+    }
+    0
+}
+
+/// An ancestor of the attributed process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ancestor {
+    pub pid: u32,
+    pub comm: String,
+    pub cmd: String,
+}
+
+/// Everything attribution looks at for one process.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchInput {
+    pub pid: u32,
+    pub user: String,
+    pub cmd: String,
+    /// Cgroup path (unified, or the systemd v1 path).
+    pub cgroup: Option<String>,
+    pub env: HashMap<String, String>,
+    /// Ancestors, parent first.
+    pub ancestors: Vec<Ancestor>,
+}
+
+/// Attribute a launch origin from gathered input.
+///
+/// `timer_for` looks up an installed timer unit for a system service.
+pub fn attribute(
+    input: &LaunchInput,
+    crontab: &CronTable,
+    timer_for: &dyn Fn(&str) -> Option<String>,
+) -> LaunchAttribution {
+    let mut evidence = Vec::new();
+    let unit = input.cgroup.as_deref().and_then(cgroup_unit);
+
+    // Container: the whole cgroup belongs to the runtime.
+    if let Some(cgroup) = input.cgroup.as_deref() {
+        let info = detect_container_from_cgroup(cgroup);
+        if info.in_container {
+            evidence.push(format!("cgroup {} belongs to a container", cgroup));
+            let pod = info.kubernetes.as_ref().and_then(|k| {
+                k.pod_name
+                    .clone()
+                    .or_else(|| detect_kubernetes_from_env(&input.env)?.pod_name)
+            });
+            let origin = LaunchOrigin::Container {
+                runtime: info.runtime,
+                id: info.container_id_short,
+                pod,
+            };
+            return finish(origin, evidence);
+        }
+    }
+
+    // Cron: a cron daemon among the ancestors, or the cron service cgroup.
+    let cron_ancestor = input
+        .ancestors
+        .iter()
+        .position(|a| CRON_DAEMONS.contains(&a.comm.to_ascii_lowercase().as_str()));
+    let cron_unit = unit
+        .as_deref()
+        .filter(|u| CRON_UNITS.contains(u))
+        .map(str::to_string);
+    if cron_ancestor.is_some() || cron_unit.is_some() {
+        let upto = cron_ancestor.unwrap_or(input.ancestors.len());
+        match (cron_ancestor, &cron_unit) {
+            (Some(idx), _) => {
+                let a = &input.ancestors[idx];
+                evidence.push(format!(
+                    "ancestor PID {} ({}) is a cron daemon",
+                    a.pid, a.comm
+                ));
+            }
+            (None, Some(unit)) => evidence.push(format!("runs in {}", unit)),
+            (None, None) => {}
+        }
+        let mut cmdlines = vec![input.cmd.as_str()];
+        cmdlines.extend(input.ancestors[..upto].iter().map(|a| a.cmd.as_str()));
+        let entry = crontab.find_match(&cmdlines, &input.user).cloned();
+        if let Some(entry) = &entry {
+            evidence.push(format!("matches {}:{}", entry.source, entry.line));
+        }
+        return finish(LaunchOrigin::Cron { entry }, evidence);
+    }
+
+    // Systemd: a service (or transient) unit owns the cgroup.
+    if let Some(unit) = unit.filter(|u| is_launching_unit(u)) {
+        let user_manager = input
+            .cgroup
+            .as_deref()
+            .is_some_and(|c| c.contains("/user@"));
+        evidence.push(format!("cgroup owned by {}", unit));
+        let timer = if user_manager { None } else { timer_for(&unit) };
+        if let Some(timer) = &timer {
+            evidence.push(format!("activated by {}", timer));
+        }
+        return finish(
+            LaunchOrigin::SystemdUnit {
+                unit,
+                user_manager,
+                timer,
+            },
+            evidence,
+        );
+    }
+
+    // Interactive sessions: whoever launched it from inside the session is
+    // more specific than the session itself.
+    let parent = input.ancestors.iter().find(|a| !is_relay(&a.comm));
+    let sshd = input.ancestors.iter().find(|a| is_sshd(&a.comm));
+    let connection = input
+        .env
+        .get("SSH_CONNECTION")
+        .and_then(|v| SshConnectionInfo::from_ssh_connection(v))
+        .or_else(|| {
+            input
+                .env
+                .get("SSH_CLIENT")
+                .and_then(|v| SshConnectionInfo::from_ssh_client(v))
+        });
+    let app_scope = input
+        .cgroup
+        .as_deref()
+        .and_then(|c| c.split('/').find(|part| part.starts_with("app-")));
+    let session_ancestor = input
+        .ancestors
+        .iter()
+        .find(|a| DESKTOP_SESSIONS.contains(&a.comm.as_str()));
+    let graphical = matches!(
+        input.env.get("XDG_SESSION_TYPE").map(String::as_str),
+        Some("x11") | Some("wayland")
+    ) || input.env.contains_key("WAYLAND_DISPLAY")
+        || input.env.contains_key("DISPLAY");
+
+    if let Some(a) =
+        parent.filter(|a| !is_sshd(&a.comm) && !DESKTOP_SESSIONS.contains(&a.comm.as_str()))
+    {
+        evidence.push(format!(
+            "nearest non-shell ancestor PID {} ({})",
+            a.pid, a.comm
+        ));
+        if sshd.is_some() || connection.is_some() {
+            evidence.push("inside an ssh session".to_string());
+        } else if app_scope.is_some() || session_ancestor.is_some() || graphical {
+            evidence.push("inside a desktop session".to_string());
+        }
+        let origin = LaunchOrigin::Parent {
+            pid: a.pid,
+            comm: a.comm.clone(),
+        };
+        return finish(origin, evidence);
+    }
+
+    // SSH: sshd ancestor, or the connection recorded in the environment.
+    if sshd.is_some() || connection.is_some() {
+        if let Some(a) = sshd {
+            evidence.push(format!("ancestor PID {} ({})", a.pid, a.comm));
+        }
+        if let Some(conn) = &connection {
+            evidence.push(format!("SSH_CONNECTION from {}", conn.client_ip));
+        }
+        let client = connection.map(|c| c.client_ip);
+        return finish(LaunchOrigin::Ssh { client }, evidence);
+    }
+
+    // Desktop: app scope, session manager ancestor, or graphical session env.
+    if app_scope.is_some() || session_ancestor.is_some() || graphical {
+        if let Some(scope) = app_scope {
+            evidence.push(format!("desktop app cgroup {}", scope));
+        }
+        if let Some(a) = session_ancestor {
+            evidence.push(format!("ancestor PID {} ({})", a.pid, a.comm));
+        }
+        if graphical {
+            evidence.push("graphical session environment".to_string());
+        }
+        let session = input
+            .env
+            .get("XDG_CURRENT_DESKTOP")
+            .or_else(|| input.env.get("XDG_SESSION_DESKTOP"))
+            .cloned()
+            .or_else(|| session_ancestor.map(|a| a.comm.clone()));
+        return finish(LaunchOrigin::DesktopSession { session }, evidence);
+    }
+
+    if input
+        .ancestors
+        .first()
+        .is_some_and(|a| INIT.contains(&a.comm.as_str()))
+    {
+        evidence.push("reparented to init; original launcher exited".to_string());
+    }
+    finish(LaunchOrigin::Unknown, evidence)
+}
+
+/// Shells, exec wrappers, terminals and init only relay a launch.
+fn is_relay(comm: &str) -> bool {
+    PASSTHROUGH.contains(&comm)
+        || INIT.contains(&comm)
+        || TERMINALS.contains(&comm)
+        || comm.starts_with("tmux")
+}
+
+fn is_sshd(comm: &str) -> bool {
+    comm == "sshd" || comm.starts_with("sshd-")
+}
+
+/// Most specific service or scope in a cgroup path.
+fn cgroup_unit(cgroup: &str) -> Option<String> {
+    cgroup
+        .split('/')
+        .rfind(|part| part.ends_with(".service") || part.ends_with(".scope"))
+        .map(str::to_string)
+}
+
+/// Whether a unit launched the process, as opposed to a login session or
+/// desktop app scope that merely contains it.
+fn is_launching_unit(unit: &str) -> bool {
+    let interactive = unit.starts_with("session-")
+        || unit.starts_with("app-")
+        || unit.starts_with("user@")
+        || unit.starts_with("tmux-spawn-")
+        || unit.starts_with("vte-spawn-")
+        || unit == "init.scope";
+    !interactive
+}
+
+fn finish(origin: LaunchOrigin, evidence: Vec<String>) -> LaunchAttribution {
+    let remediation = remediation(&origin);
+    LaunchAttribution {
+        origin,
+        evidence,
+        remediation,
+    }
+}
+
+fn remediation(origin: &LaunchOrigin) -> Vec<String> {
+    match origin {
+        LaunchOrigin::SystemdUnit {
+            unit,
+            user_manager,
+            timer,
+        } => {
+            let ctl = if *user_manager {
+                "systemctl --user"
+            } else {
+                "systemctl"
+            };
+            let mut steps = Vec::new();
+            if let Some(timer) = timer {
+                steps.push(format!("{} disable --now {}", ctl, timer));
+            }
+            steps.push(format!("{} stop {}", ctl, unit));
+            steps.push(format!(
+                "{} disable {} (keeps it from starting again)",
+                ctl, unit
+            ));
+            steps
+        }
+        LaunchOrigin::Cron { entry: Some(entry) } => vec![
+            format!(
+                "fix or remove the cron job at {}:{} ({} {})",
+                entry.source, entry.line, entry.schedule, entry.command
+            ),
+            match &entry.user {
+                Some(user) if entry.source.contains("/spool/") => {
+                    format!("crontab -e -u {}", user)
+                }
+                _ => format!("edit {}", entry.source),
+            },
+        ],
+        LaunchOrigin::Cron { entry: None } => vec![
+            "find the job with crontab -l (and /etc/crontab, /etc/cron.d) and fix its schedule or command"
+                .to_string(),
+        ],
+        LaunchOrigin::Ssh { client } => vec![match client {
+            Some(client) => format!(
+                "started interactively over ssh from {}; ask the session owner before killing",
+                client
+            ),
+            None => "started interactively over ssh; ask the session owner before killing"
+                .to_string(),
+        }],
+        LaunchOrigin::Container { runtime, id, pod } => {
+            let mut steps = Vec::new();
+            if let Some(pod) = pod {
+                steps.push(format!(
+                    "scale down or fix the workload owning pod {} (killing restarts it)",
+                    pod
+                ));
+            }
+            if let Some(id) = id {
+                let cli = match runtime {
+                    ContainerRuntime::Podman => Some("podman"),
+                    ContainerRuntime::Docker => Some("docker"),
+                    ContainerRuntime::Containerd => Some("nerdctl"),
+                    ContainerRuntime::Crio => Some("crictl"),
+                    _ => None,
+                };
+                if let Some(cli) = cli {
+                    steps.push(format!("{} stop {}", cli, id));
+                }
+            }
+            if steps.is_empty() {
+                steps.push("stop the container through its runtime".to_string());
+            }
+            steps
+        }
+        LaunchOrigin::DesktopSession { .. } => vec![
+            "close the application from the desktop session, or remove it from autostart"
+                .to_string(),
+        ],
+        LaunchOrigin::Parent { pid, comm } => vec![format!(
+            "stop or reconfigure the parent {} (PID {}), which will otherwise relaunch it",
+            comm, pid
+        )],
+        LaunchOrigin::Unknown => Vec::new(),
+    }
+}
+
+/// Installed timer unit for a system service (`foo.service` -> `foo.timer`).
+pub fn find_system_timer(unit: &str) -> Option<String> {
+    let stem = unit.strip_suffix(".service")?;
+    let timer = format!("{}.timer", stem);
+    SYSTEM_UNIT_DIRS
+        .iter()
+        .any(|dir| std::path::Path::new(dir).join(&timer).exists())
+        .then_some(timer)
+}
+
+/// Attributes live processes, sharing crontabs and the process table.
+pub struct LaunchAttributor {
+    crontab: CronTable,
+    known: HashMap<u32, (u32, String, String)>,
+}
+
+impl LaunchAttributor {
+    /// Attributor over a scan; ancestors outside it are read from /proc.
+    pub fn new(processes: &[ProcessRecord]) -> Self {
+        let known = processes
+            .iter()
+            .map(|p| (p.pid.0, (p.ppid.0, p.comm.clone(), p.cmd.clone())))
+            .collect();
+        Self {
+            crontab: CronTable::load(),
+            known,
+        }
+    }
+
+    /// Attribute a scanned process.
+    pub fn attribute(&self, proc: &ProcessRecord) -> LaunchAttribution {
+        let input = LaunchInput {
+            pid: proc.pid.0,
+            user: proc.user.clone(),
+            cmd: proc.cmd.clone(),
+            cgroup: read_cgroup(proc.pid.0),
+            env: crate::collect::parse_environ(proc.pid.0).unwrap_or_default(),
+            ancestors: self.ancestors(proc.ppid.0),
+        };
+        attribute(&input, &self.crontab, &find_system_timer)
+    }
+
+    fn ancestors(&self, mut pid: u32) -> Vec<Ancestor> {
+        let mut chain = Vec::new();
+        while pid > 0 && chain.len() < 32 {
+            let Some((ppid, comm, cmd)) = self.lookup(pid) else {
+                break;
+            };
+            chain.push(Ancestor { pid, comm, cmd });
+            if ppid == pid {
+                break;
+            }
+            pid = ppid;
+        }
+        chain
+    }
+
+    fn lookup(&self, pid: u32) -> Option<(u32, String, String)> {
+        if let Some(known) = self.known.get(&pid) {
+            return Some(known.clone());
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let stat = crate::collect::parse_proc_stat(pid)?;
+            let cmd = std::fs::read(format!("/proc/{}/cmdline", pid))
+                .map(|raw| {
+                    String::from_utf8_lossy(&raw)
+                        .replace('\0', " ")
+                        .trim()
+                        .to_string()
+                })
+                .unwrap_or_default();
+            Some((stat.ppid, stat.comm, cmd))
+        }
+        #[cfg(not(target_os = "linux"))]
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_cgroup(pid: u32) -> Option<String> {
+    let info = crate::collect::parse_cgroup(pid)?;
+    info.unified
+        .filter(|path| path != "/")
+        .or_else(|| info.v1_paths.get("name=systemd").cloned())
+        .or_else(|| info.v1_paths.get("pids").cloned())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cgroup(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ancestor(pid: u32, comm: &str, cmd: &str) -> Ancestor {
+        Ancestor {
+            pid,
+            comm: comm.to_string(),
+            cmd: cmd.to_string(),
+        }
+    }
+
+    fn no_timer(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn parses_system_and_user_crontabs() {
+        let mut table = CronTable::default();
+        table.add(
+            "/etc/crontab",
+            "SHELL=/bin/sh\n# m h dom mon dow user command\n17 * * * * root cd / && run-parts --report /etc/cron.hourly\n@reboot backup /opt/backup/start.sh --daemon\n",
+            true,
+            None,
+        );
+        table.add(
+            "/var/spool/cron/crontabs/alice",
+            "*/5 * * * * /home/alice/bin/sync.py --quiet > /dev/null 2>&1\n",
+            false,
+            Some("alice".to_string()),
+        );
+        assert_eq!(table.entries.len(), 3);
+        assert_eq!(table.entries[0].user.as_deref(), Some("root"));
+        assert_eq!(table.entries[0].schedule, "17 * * * *");
+        assert_eq!(table.entries[1].schedule, "@reboot");
+        assert_eq!(table.entries[1].command, "/opt/backup/start.sh --daemon");
+        assert_eq!(table.entries[2].line, 1);
+
+        let entry = table
+            .find_match(
+                &["/usr/bin/python3 /home/alice/bin/sync.py --quiet"],
+                "alice",
+            )
+            .unwrap();
+        assert_eq!(entry.source, "/var/spool/cron/crontabs/alice");
+        // Jobs of other users never match.
+        assert!(table
+            .find_match(&["/usr/bin/python3 /home/alice/bin/sync.py --quiet"], "bob")
+            .is_none());
+    }
+
+    #[test]
+    fn attributes_cron_job_with_entry() {
+        let mut table = CronTable::default();
+        table.add(
+            "/etc/cron.d/reports",
+            "0 3 * * * www /srv/app/bin/report --nightly\n",
+            true,
+            None,
+        );
+        let input = LaunchInput {
+            pid: 900,
+            user: "www".to_string(),
+            cmd: "/srv/app/bin/report --nightly".to_string(),
+            cgroup: Some("/system.slice/cron.service".to_string()),
+            ancestors: vec![
+                ancestor(899, "sh", "/bin/sh -c /srv/app/bin/report --nightly"),
+                ancestor(850, "cron", "/usr/sbin/CRON -f"),
+                ancestor(1, "systemd", "/sbin/init"),
+            ],
+            ..Default::default()
+        };
+        let attribution = attribute(&input, &table, &no_timer);
+        let LaunchOrigin::Cron { entry: Some(entry) } = &attribution.origin else {
+            panic!("expected cron, got {:?}", attribution.origin);
+        };
+        assert_eq!(entry.line, 1);
+        assert!(attribution.remediation[0].contains("/etc/cron.d/reports:1"));
+    }
+
+    #[test]
+    fn attributes_systemd_service_and_timer() {
+        let input = LaunchInput {
+            pid: 42,
+            cgroup: Some("/system.slice/logrotate.service".to_string()),
+            ancestors: vec![ancestor(1, "systemd", "/sbin/init")],
+            ..Default::default()
+        };
+        let attribution = attribute(&input, &CronTable::default(), &|unit| {
+            (unit == "logrotate.service").then(|| "logrotate.timer".to_string())
+        });
+        assert_eq!(
+            attribution.origin,
+            LaunchOrigin::SystemdUnit {
+                unit: "logrotate.service".to_string(),
+                user_manager: false,
+                timer: Some("logrotate.timer".to_string()),
+            }
+        );
+        assert_eq!(
+            attribution.remediation[0],
+            "systemctl disable --now logrotate.timer"
+        );
+
+        let json = serde_json::to_value(&attribution).unwrap();
+        assert_eq!(json["kind"], "systemd_unit");
+        assert_eq!(json["unit"], "logrotate.service");
+    }
+
+    #[test]
+    fn session_scopes_fall_through_to_ssh_and_parent() {
+        let mut env = HashMap::new();
+        env.insert(
+            "SSH_CONNECTION".to_string(),
+            "203.0.113.7 50022 10.0.0.5 22".to_string(),
+        );
+        let ssh = LaunchInput {
+            pid: 10,
+            cgroup: Some("/user.slice/user-1000.slice/session-4.scope".to_string()),
+            env,
+            ancestors: vec![
+                ancestor(9, "bash", "-bash"),
+                ancestor(8, "sshd", "sshd: alice@pts/0"),
+            ],
+            ..Default::default()
+        };
+        let attribution = attribute(&ssh, &CronTable::default(), &no_timer);
+        assert_eq!(
+            attribution.origin,
+            LaunchOrigin::Ssh {
+                client: Some("203.0.113.7".to_string())
+            }
+        );
+
+        let child = LaunchInput {
+            pid: 20,
+            ancestors: vec![
+                ancestor(19, "sh", "sh -c node server.js"),
+                ancestor(18, "node", "node /usr/lib/node_modules/pm2/bin/pm2"),
+                ancestor(1, "systemd", "/sbin/init"),
+            ],
+            ..Default::default()
+        };
+        let attribution = attribute(&child, &CronTable::default(), &no_timer);
+        assert_eq!(
+            attribution.origin,
+            LaunchOrigin::Parent {
+                pid: 18,
+                comm: "node".to_string()
+            }
+        );
+
+        let orphan = LaunchInput {
+            pid: 30,
+            ancestors: vec![ancestor(1, "systemd", "/sbin/init")],
+            ..Default::default()
+        };
+        let attribution = attribute(&orphan, &CronTable::default(), &no_timer);
+        assert_eq!(attribution.origin, LaunchOrigin::Unknown);
+        assert_eq!(attribution.evidence.len(), 1);
+    }
+
+    #[test]
+    fn attributes_containers_first() {
+        let id = "4f5e6d7c8b9a0123456789abcdef0123456789abcdef0123456789abcdef0123";
+        let input = LaunchInput {
+            pid: 5,
+            cgroup: Some(format!("/system.slice/docker-{}.scope", id)),
+            ancestors: vec![ancestor(4, "containerd-shim", "containerd-shim -id x")],
+            ..Default::default()
+        };
+        let attribution = attribute(&input, &CronTable::default(), &no_timer);
+        assert_eq!(attribution.origin.kind(), "container");
+        assert!(attribution
+            .remediation
+            .iter()
+            .any(|s| s.contains("docker stop")));
+    }
+}
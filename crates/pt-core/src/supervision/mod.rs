@@ -12,12 +12,23 @@
 //!
 //! # Detection Methods
 //!
-//! - **Ancestry**: Parent or ancestor matches supervisor pattern
+//! - **Ancestry**: Parent or ancestor matches supervisor pattern (by name or command line)
 //! - **Environment**: Variables like CLAUDE_SESSION_ID, VSCODE_PID, etc.
 //! - **Sockets**: Connected to known supervisor IPC paths
 //! - **Locks**: PID files in known automation directories (future)
 //! - **TTY**: Terminal attribution for tmux/screen sessions (future)
 //!
+//! [`launch`] attributes where a process was started from (systemd unit, cron
+//! job, ssh or desktop session, container, parent) for remediation hints.
+//!
+//! [`BuildDaemonAnalyzer`] recognizes Gradle, Kotlin, Bazel, sccache and
+//! `cargo watch` daemons that no build can use any more, with the tool's own
+//! shutdown command when there is one.
+//!
+//! [`signature_pack`] fetches, verifies and installs signed community
+//! signature packs. [`signature_suggest`] proposes user signatures for
+//! command patterns the user keeps killing or sparing.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -45,17 +56,22 @@
 mod ancestry;
 mod app_supervision;
 pub mod blast_radius;
+mod build_daemon;
 #[cfg(target_os = "linux")]
 mod container_supervision;
 mod environ;
 mod ipc;
+pub mod launch;
 pub mod narrative;
 mod nohup;
 mod orphan;
 pub mod pattern_learning;
 pub mod pattern_persistence;
+pub mod rollout;
 pub mod session;
 pub mod signature;
+pub mod signature_pack;
+pub mod signature_suggest;
 #[cfg(test)]
 mod supervision_tests;
 mod types;
@@ -68,6 +84,9 @@ pub use app_supervision::{
     detect_app_supervision, AlternativeAction, AppActionType, AppSupervisionAnalyzer,
     AppSupervisionError, AppSupervisionResult, AppSupervisorAction, AppSupervisorType,
 };
+pub use build_daemon::{
+    build_daemon_kind, BuildDaemonAnalyzer, BuildDaemonInfo, BuildDaemonKind, BuildDaemonState,
+};
 #[cfg(target_os = "linux")]
 pub use container_supervision::{
     detect_container_supervision, detect_container_supervision_with_actions, ContainerAction,
@@ -79,6 +98,7 @@ pub use environ::{
     EnvironError, EnvironResult,
 };
 pub use ipc::{detect_ipc_supervision, IpcAnalyzer, IpcDatabase, IpcError, IpcPattern, IpcResult};
+pub use launch::{CronEntry, CronTable, LaunchAttribution, LaunchAttributor, LaunchOrigin};
 pub use nohup::{
     check_signal_mask, detect_disown, detect_nohup, read_fd_info, read_signal_mask,
     BackgroundIntent, FdInfo, NohupAnalyzer, NohupError, NohupOutputActivity, NohupResult,
@@ -97,14 +117,22 @@ pub use pattern_persistence::{
     ImportConflict, ImportResult, PatternLibrary, PatternLifecycle, PatternSource, PatternStats,
     PersistedPattern, PersistedSchema, PersistenceError, SchemaMetadata,
 };
+pub use rollout::{RolloutCheck, RolloutEvidence, RolloutPolicy, StageRequirements};
 pub use session::{
     check_session_protection, is_in_protected_session, ScreenInfo, SessionAnalyzer, SessionConfig,
     SessionError, SessionEvidence, SessionProtectionType, SessionResult, SshConnectionInfo,
     TmuxInfo,
 };
 pub use signature::{
-    SignatureDatabase, SignatureError, SignatureMetadata, SignaturePatterns, SignatureSchema,
-    SupervisorSignature, SCHEMA_VERSION,
+    RolloutStage, SignatureDatabase, SignatureError, SignatureMetadata, SignaturePatterns,
+    SignatureSchema, SupervisorSignature, SCHEMA_VERSION,
+};
+pub use signature_pack::{
+    PackDiff, PackInfo, SignaturePackError, SignaturePackStore, VerifiedPack,
+    DEFAULT_SIGNATURE_PACK_URL,
+};
+pub use signature_suggest::{
+    suggest_signatures, DecisionRecord, SignatureSuggestion, SuggestionError, SuggestionStore,
 };
 pub use types::{
     AncestryEntry, EvidenceType, SupervisionEvidence, SupervisionResult, SupervisorCategory,
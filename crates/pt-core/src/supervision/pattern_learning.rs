@@ -56,6 +56,7 @@ use super::pattern_persistence::{PatternLibrary, PersistenceError};
 use super::signature::{SignaturePatterns, SupervisorSignature};
 use super::types::SupervisorCategory;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -438,7 +439,8 @@ impl CommandNormalizer {
 }
 
 /// Action type for pattern learning.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DecisionAction {
     /// User killed the process.
     Kill,
@@ -654,6 +656,8 @@ impl<'a> PatternLearner<'a> {
             priors: Default::default(),
             expectations: Default::default(),
             priority: 100 + candidate.level.priority_offset(),
+            rollout: Default::default(),
+            graceful_stop: None,
         };
 
         // Add to library
@@ -1129,6 +1129,8 @@ mod tests {
             priors: Default::default(),
             expectations: Default::default(),
             priority: 100,
+            rollout: Default::default(),
+            graceful_stop: None,
         }
     }
 
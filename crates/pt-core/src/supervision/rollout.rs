@@ -0,0 +1,237 @@
+//! Rollout gating for staged signatures.
+//!
+//! A signature is promoted observe → review → auto. Each promotion needs
+//! enough observed matches (fleet-wide hits plus local matches); reaching
+//! `auto` additionally needs enough reviewed matches and a precision whose
+//! lower credible bound clears a floor. Precision is the share of reviewed
+//! matches the user accepted, with a uniform Beta prior. Demotion is always
+//! allowed.
+
+use pt_math::beta_inv_cdf;
+use serde::{Deserialize, Serialize};
+
+use super::pattern_persistence::PatternStats;
+use super::signature::RolloutStage;
+
+/// Evidence gathered for a signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RolloutEvidence {
+    /// Matches observed (fleet hits plus local matches).
+    pub matches: u64,
+    /// Reviewed matches the user accepted.
+    pub accepts: u32,
+    /// Reviewed matches the user rejected.
+    pub rejects: u32,
+}
+
+impl RolloutEvidence {
+    /// Evidence from fleet hits and local pattern statistics.
+    pub fn new(fleet_matches: u64, local: Option<&PatternStats>) -> Self {
+        let local = local.cloned().unwrap_or_default();
+        Self {
+            matches: fleet_matches + u64::from(local.match_count),
+            accepts: local.accept_count,
+            rejects: local.reject_count,
+        }
+    }
+
+    /// Reviewed matches.
+    pub fn reviewed(&self) -> u32 {
+        self.accepts + self.rejects
+    }
+
+    /// Posterior mean precision; None before any review.
+    pub fn precision(&self) -> Option<f64> {
+        (self.reviewed() > 0).then(|| (self.accepts as f64 + 1.0) / (self.reviewed() as f64 + 2.0))
+    }
+
+    /// Lower bound of the one-sided credible interval on precision.
+    pub fn precision_lower_bound(&self, mass: f64) -> f64 {
+        beta_inv_cdf(
+            1.0 - mass,
+            self.accepts as f64 + 1.0,
+            self.rejects as f64 + 1.0,
+        )
+    }
+}
+
+/// What a signature must show to enter a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StageRequirements {
+    /// Minimum observed matches.
+    pub min_matches: u64,
+    /// Minimum reviewed matches.
+    pub min_reviewed: u32,
+    /// Floor on the precision lower bound.
+    pub min_precision: f64,
+}
+
+/// Promotion requirements per stage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RolloutPolicy {
+    pub review: StageRequirements,
+    pub auto: StageRequirements,
+    /// Mass of the one-sided precision credible interval.
+    pub credible_mass: f64,
+}
+
+impl Default for RolloutPolicy {
+    fn default() -> Self {
+        Self {
+            review: StageRequirements {
+                min_matches: 20,
+                min_reviewed: 0,
+                min_precision: 0.0,
+            },
+            auto: StageRequirements {
+                min_matches: 100,
+                min_reviewed: 10,
+                min_precision: 0.8,
+            },
+            credible_mass: 0.95,
+        }
+    }
+}
+
+/// Outcome of a transition check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RolloutCheck {
+    pub from: RolloutStage,
+    pub to: RolloutStage,
+    pub allowed: bool,
+    /// Unmet requirements, empty when allowed.
+    pub unmet: Vec<String>,
+    pub evidence: RolloutEvidence,
+    pub precision: Option<f64>,
+    pub precision_lower_bound: Option<f64>,
+}
+
+impl RolloutPolicy {
+    /// Requirements for entering `stage`; None for observe.
+    pub fn requirements(&self, stage: RolloutStage) -> Option<&StageRequirements> {
+        match stage {
+            RolloutStage::Observe => None,
+            RolloutStage::Review => Some(&self.review),
+            RolloutStage::Auto => Some(&self.auto),
+        }
+    }
+
+    /// Check moving a signature from `from` to `to`.
+    pub fn check(
+        &self,
+        from: RolloutStage,
+        to: RolloutStage,
+        evidence: &RolloutEvidence,
+    ) -> RolloutCheck {
+        let lower_bound =
+            (evidence.reviewed() > 0).then(|| evidence.precision_lower_bound(self.credible_mass));
+        let mut unmet = Vec::new();
+        if let Some(req) = self.requirements(to).filter(|_| to > from) {
+            if evidence.matches < req.min_matches {
+                unmet.push(format!(
+                    "{} matches observed, {} required",
+                    evidence.matches, req.min_matches
+                ));
+            }
+            if evidence.reviewed() < req.min_reviewed {
+                unmet.push(format!(
+                    "{} matches reviewed, {} required",
+                    evidence.reviewed(),
+                    req.min_reviewed
+                ));
+            }
+            if req.min_precision > 0.0 && lower_bound.unwrap_or(0.0) < req.min_precision {
+                unmet.push(format!(
+                    "precision lower bound {:.3} below {:.3}",
+                    lower_bound.unwrap_or(0.0),
+                    req.min_precision
+                ));
+            }
+        }
+        RolloutCheck {
+            from,
+            to,
+            allowed: unmet.is_empty(),
+            unmet,
+            evidence: *evidence,
+            precision: evidence.precision(),
+            precision_lower_bound: lower_bound,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evidence(matches: u64, accepts: u32, rejects: u32) -> RolloutEvidence {
+        RolloutEvidence {
+            matches,
+            accepts,
+            rejects,
+        }
+    }
+
+    #[test]
+    fn review_needs_matches_only() {
+        let policy = RolloutPolicy::default();
+        let check = policy.check(
+            RolloutStage::Observe,
+            RolloutStage::Review,
+            &evidence(5, 0, 0),
+        );
+        assert!(!check.allowed);
+        assert_eq!(check.unmet.len(), 1);
+        let check = policy.check(
+            RolloutStage::Observe,
+            RolloutStage::Review,
+            &evidence(20, 0, 0),
+        );
+        assert!(check.allowed);
+    }
+
+    #[test]
+    fn auto_needs_reviewed_precision() {
+        let policy = RolloutPolicy::default();
+        // Plenty of matches, but 8/10 accepted is not enough at 95%.
+        let check = policy.check(
+            RolloutStage::Review,
+            RolloutStage::Auto,
+            &evidence(500, 8, 2),
+        );
+        assert!(!check.allowed);
+        assert!(check.unmet[0].contains("precision"));
+        let check = policy.check(
+            RolloutStage::Review,
+            RolloutStage::Auto,
+            &evidence(500, 40, 0),
+        );
+        assert!(check.allowed, "{:?}", check.unmet);
+        assert!(check.precision_lower_bound.unwrap() > 0.9);
+    }
+
+    #[test]
+    fn demotion_is_always_allowed() {
+        let policy = RolloutPolicy::default();
+        let check = policy.check(
+            RolloutStage::Auto,
+            RolloutStage::Observe,
+            &RolloutEvidence::default(),
+        );
+        assert!(check.allowed);
+    }
+
+    #[test]
+    fn evidence_combines_fleet_and_local() {
+        let local = PatternStats {
+            match_count: 7,
+            accept_count: 5,
+            reject_count: 2,
+            ..Default::default()
+        };
+        let ev = RolloutEvidence::new(30, Some(&local));
+        assert_eq!(ev.matches, 37);
+        assert_eq!(ev.reviewed(), 7);
+        assert!((ev.precision().unwrap() - 6.0 / 9.0).abs() < 1e-12);
+    }
+}
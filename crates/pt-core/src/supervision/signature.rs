@@ -38,6 +38,7 @@ use super::environ::EnvPattern;
 use super::ipc::IpcPattern;
 use super::types::{SupervisorCategory, SupervisorPattern};
 pub use crate::config::priors::BetaParams;
+use crate::plan::GracefulStopTemplate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -123,6 +124,16 @@ impl SignaturePriors {
         }
     }
 
+    /// Create priors for build daemons, which idle between builds by design
+    /// but are often left behind once the project or toolchain moves on.
+    pub fn build_daemon() -> Self {
+        Self {
+            useful: Some(BetaParams::new(2.0, 3.0)),    // ~40% useful
+            abandoned: Some(BetaParams::new(3.0, 2.0)), // ~60% abandoned
+            ..Default::default()
+        }
+    }
+
     /// Check if any priors are set.
     pub fn is_empty(&self) -> bool {
         self.abandoned.is_none()
@@ -298,6 +309,64 @@ pub struct SupervisorSignature {
         skip_serializing_if = "is_default_priority"
     )]
     pub priority: u32,
+
+    /// Rollout stage: how much the signature may influence inference.
+    #[serde(default, skip_serializing_if = "RolloutStage::is_auto")]
+    pub rollout: RolloutStage,
+
+    /// Shutdown command run before signals when a matched process is killed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graceful_stop: Option<GracefulStopTemplate>,
+}
+
+/// Staged rollout of a signature.
+///
+/// New or aggressive signatures can be observed fleet-wide before they are
+/// allowed to act. Promotions are gated by [`crate::supervision::rollout`].
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutStage {
+    /// Matches are counted but do not influence inference.
+    Observe,
+    /// Signature priors apply; the fast path is not taken.
+    Review,
+    /// Full effect, including the signature fast path.
+    #[default]
+    Auto,
+}
+
+impl RolloutStage {
+    pub fn is_auto(&self) -> bool {
+        *self == RolloutStage::Auto
+    }
+
+    /// Whether matches may change priors or classification.
+    pub fn applies_priors(&self) -> bool {
+        *self != RolloutStage::Observe
+    }
+
+    /// Parse a stage name (observe, review, auto).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "observe" | "observe-only" | "observe_only" => Some(RolloutStage::Observe),
+            "review" => Some(RolloutStage::Review),
+            "auto" => Some(RolloutStage::Auto),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RolloutStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RolloutStage::Observe => "observe",
+            RolloutStage::Review => "review",
+            RolloutStage::Auto => "auto",
+        };
+        f.write_str(name)
+    }
 }
 
 fn default_priority() -> u32 {
@@ -376,6 +445,8 @@ impl SupervisorSignature {
             priors: SignaturePriors::default(),
             expectations: ProcessExpectations::default(),
             priority: default_priority(),
+            rollout: RolloutStage::default(),
+            graceful_stop: None,
         }
     }
 
@@ -451,6 +522,12 @@ impl SupervisorSignature {
         self
     }
 
+    /// Stop matched processes with their own CLI before signalling them.
+    pub fn with_graceful_stop(mut self, template: GracefulStopTemplate) -> Self {
+        self.graceful_stop = Some(template);
+        self
+    }
+
     /// Set match priority (higher = more specific).
     pub fn with_priority(mut self, priority: u32) -> Self {
         self.priority = priority;
@@ -519,6 +596,10 @@ impl SupervisorSignature {
         // Validate expectations
         self.expectations.validate()?;
 
+        if let Some(graceful_stop) = &self.graceful_stop {
+            graceful_stop.validate().map_err(SignatureError::Invalid)?;
+        }
+
         Ok(())
     }
 
@@ -1335,6 +1416,35 @@ impl SignatureDatabase {
                 .as_builtin(),
         );
 
+        // IDE helpers: idle for hours between requests, yet in use while the
+        // editor is connected.
+        let _ = self.add(
+            SupervisorSignature::new("vscode-server", SupervisorCategory::Ide)
+                .with_confidence(0.90)
+                .with_notes("VS Code remote/tunnel server, extension hosts and language servers")
+                .with_process_patterns(vec![r"^code-tunnel$"])
+                .with_arg_patterns(vec![
+                    r"/\.(vscode-server(-insiders)?|vscode-remote|cursor-server|windsurf-server)/",
+                ])
+                .with_env_patterns(HashMap::from([("VSCODE_AGENT_FOLDER".into(), ".*".into())]))
+                .with_priors(SignaturePriors::likely_useful())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("jetbrains-daemon", SupervisorCategory::Ide)
+                .with_confidence(0.85)
+                .with_notes("JetBrains remote-dev backend, file watcher and compile daemons")
+                .with_process_patterns(vec![r"^fsnotifier$", r"^remote-dev-serv"])
+                .with_arg_patterns(vec![
+                    r"(/RemoteDev/dist/|remote-dev-server|com\.intellij\.idea\.Main)",
+                ])
+                .with_priors(SignaturePriors::likely_useful())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
         // CI/CD Systems
         let _ = self.add(
             SupervisorSignature::new("github-actions", SupervisorCategory::Ci)
@@ -1527,6 +1637,37 @@ impl SignatureDatabase {
                 .as_builtin(),
         );
 
+        let _ = self.add(
+            SupervisorSignature::new("copilot-language-server", SupervisorCategory::Agent)
+                .with_confidence(0.90)
+                .with_notes("GitHub Copilot language server run by an editor")
+                .with_process_patterns(vec![r"^copilot-languag"])
+                .with_arg_patterns(vec![
+                    r"(copilot-language-server|/github\.copilot(-chat)?-[0-9]|@github/copilot)",
+                ])
+                .with_priors(SignaturePriors::likely_useful())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("gemini-cli", SupervisorCategory::Agent)
+                .with_confidence(0.90)
+                .with_notes("Google Gemini CLI agent")
+                .with_process_patterns(vec![r"^gemini$"])
+                .with_arg_patterns(vec![r"@google/gemini-cli/"])
+                .with_env_patterns(HashMap::from([("GEMINI_CLI".into(), ".*".into())]))
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("opencode", SupervisorCategory::Agent)
+                .with_confidence(0.85)
+                .with_notes("opencode terminal agent")
+                .with_process_patterns(vec![r"^opencode$"])
+                .as_builtin(),
+        );
+
         let _ = self.add(
             SupervisorSignature::new("codeium", SupervisorCategory::Agent)
                 .with_confidence(0.85)
@@ -1870,6 +2011,72 @@ impl SignatureDatabase {
                 .as_builtin(),
         );
 
+        // Build daemons: idle between builds by design; BuildDaemonAnalyzer
+        // tells the reusable ones from those left behind.
+        let _ = self.add(
+            SupervisorSignature::new("gradle-daemon", SupervisorCategory::Other)
+                .with_confidence(0.90)
+                .with_notes("Gradle build daemon")
+                .with_arg_patterns(vec![
+                    r"org\.gradle\.launcher\.daemon\.bootstrap\.GradleDaemon",
+                ])
+                .with_graceful_stop(GracefulStopTemplate::new(vec!["gradle", "--stop"]))
+                .with_priors(SignaturePriors::build_daemon())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("kotlin-daemon", SupervisorCategory::Other)
+                .with_confidence(0.90)
+                .with_notes("Kotlin compile daemon")
+                .with_arg_patterns(vec![r"org\.jetbrains\.kotlin\.daemon\.KotlinCompileDaemon"])
+                .with_priors(SignaturePriors::build_daemon())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("bazel-server", SupervisorCategory::Other)
+                .with_confidence(0.90)
+                .with_notes("Bazel server")
+                .with_arg_patterns(vec![r"(^bazel\(|/A-server\.jar)"])
+                .with_graceful_stop(
+                    GracefulStopTemplate::new(vec![
+                        "bazel",
+                        "--output_base={flag:output_base}",
+                        "shutdown",
+                    ])
+                    .with_cwd("{flag:workspace_directory}"),
+                )
+                .with_priors(SignaturePriors::build_daemon())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("sccache-server", SupervisorCategory::Other)
+                .with_confidence(0.85)
+                .with_notes("sccache compilation cache server")
+                .with_process_patterns(vec![r"^sccache$"])
+                .with_arg_patterns(vec![r"^\S*sccache$"])
+                .with_min_matches(2)
+                .with_graceful_stop(GracefulStopTemplate::new(vec!["sccache", "--stop-server"]))
+                .with_priors(SignaturePriors::build_daemon())
+                .with_expectations(ProcessExpectations::daemon())
+                .as_builtin(),
+        );
+
+        let _ = self.add(
+            SupervisorSignature::new("cargo-watch", SupervisorCategory::Other)
+                .with_confidence(0.85)
+                .with_notes("cargo watch file-watch loop")
+                .with_process_patterns(vec![r"^cargo-watch$"])
+                .with_priors(SignaturePriors::build_daemon())
+                .with_expectations(ProcessExpectations::dev_server())
+                .as_builtin(),
+        );
+
         // Databases
         let _ = self.add(
             SupervisorSignature::new("postgres", SupervisorCategory::Other)
@@ -2623,6 +2830,53 @@ mod tests {
         assert!(best.is_none());
     }
 
+    #[test]
+    fn test_best_match_ide_and_agent_helpers() {
+        let db = SignatureDatabase::with_defaults();
+        let best = |comm: &str, cmdline: &str| {
+            db.best_match(&ProcessMatchContext::with_comm(comm).cmdline(cmdline))
+                .map(|m| m.signature.name.clone())
+        };
+
+        assert_eq!(
+            best(
+                "node",
+                "/home/dev/.vscode-server/bin/abc123/node --max-old-space-size=3072 /home/dev/.vscode-server/extensions/ms-python.vscode-pylance-2024.8.1/dist/server.bundle.js --stdio"
+            )
+            .as_deref(),
+            Some("vscode-server")
+        );
+        assert_eq!(
+            best(
+                "java",
+                "/opt/jbr/bin/java -cp kotlin-compiler.jar org.jetbrains.kotlin.daemon.KotlinCompileDaemon --daemon-runFilesPath /tmp"
+            )
+            .as_deref(),
+            Some("kotlin-daemon")
+        );
+        assert_eq!(
+            best("fsnotifier", "/opt/idea/bin/fsnotifier").as_deref(),
+            Some("jetbrains-daemon")
+        );
+        assert_eq!(
+            best(
+                "node",
+                "/usr/bin/node /home/dev/.vscode/extensions/github.copilot-1.250.0/dist/language-server.js --stdio"
+            )
+            .as_deref(),
+            Some("copilot-language-server")
+        );
+        assert_eq!(best("node", "node dist/server.js").as_deref(), None);
+
+        // IDE helpers carry priors toward useful.
+        let sig = db
+            .signatures()
+            .iter()
+            .find(|s| s.name == "vscode-server")
+            .unwrap();
+        assert!(!sig.priors.is_empty());
+    }
+
     #[test]
     fn test_signature_with_priors_and_expectations() {
         let sig = SupervisorSignature::new("test-server", SupervisorCategory::Ide)
@@ -2756,6 +3010,42 @@ mod tests {
         assert!(useful.mean() > 0.8);
     }
 
+    #[test]
+    fn test_graceful_stop_roundtrip_and_validation() {
+        let mut schema = SignatureSchema::new();
+        schema.add(
+            SupervisorSignature::new("tool-server", SupervisorCategory::Other)
+                .with_process_patterns(vec![r"^tool-server$"])
+                .with_graceful_stop(
+                    GracefulStopTemplate::new(vec!["tool", "stop", "--socket={flag:socket}"])
+                        .with_cwd("{cwd}"),
+                ),
+        );
+        let json = schema.to_json().expect("should serialize to JSON");
+        let loaded = SignatureSchema::from_json(&json).expect("should parse JSON");
+        let stop = loaded.signatures[0].graceful_stop.as_ref().unwrap();
+        assert_eq!(stop.argv, vec!["tool", "stop", "--socket={flag:socket}"]);
+        assert_eq!(stop.cwd.as_deref(), Some("{cwd}"));
+
+        let bad = SupervisorSignature::new("bad", SupervisorCategory::Other)
+            .with_process_patterns(vec![r"^bad$"])
+            .with_graceful_stop(GracefulStopTemplate::new(vec!["tool", "{home}"]));
+        assert!(matches!(bad.validate(), Err(SignatureError::Invalid(_))));
+
+        let mut db = SignatureDatabase::new();
+        db.add_default_signatures();
+        let with_stop: Vec<&str> = db
+            .signatures()
+            .iter()
+            .filter(|sig| sig.graceful_stop.is_some())
+            .map(|sig| sig.name.as_str())
+            .collect();
+        assert_eq!(
+            with_stop,
+            vec!["gradle-daemon", "bazel-server", "sccache-server"]
+        );
+    }
+
     #[test]
     fn test_schema_v2_toml_roundtrip() {
         let mut schema = SignatureSchema::new();
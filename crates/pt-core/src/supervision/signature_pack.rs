@@ -0,0 +1,436 @@
+//! Signed community signature packs.
+//!
+//! A pack is a [`SignatureSchema`] document published with a detached ECDSA
+//! P-256 signature at `<url>.sig` (DER, or base64 DER). `pt-core signature
+//! update` fetches both, verifies the signature against pinned keys, shows
+//! what changes and installs the pack, so new signatures ship without a new
+//! binary. Pack signatures load after the built-ins and before user
+//! signatures; they are never treated as built-in.
+//!
+//! The store keeps the installed pack in `current/` and the one it replaced
+//! in `previous/`; a rollback swaps the two.
+
+use super::signature::{SignatureError, SignatureSchema};
+use crate::collect::tool_runner::{ToolConfig, ToolRunner, ToolSpec};
+use crate::install::signature::{SignatureError as KeyError, SignatureVerifier};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Where `signature update` looks for the community pack by default.
+pub const DEFAULT_SIGNATURE_PACK_URL: &str =
+    "https://github.com/Dicklesworthstone/process_triage/releases/latest/download/signature-pack.json";
+
+/// Largest pack accepted.
+pub const MAX_PACK_BYTES: usize = 4 * 1024 * 1024;
+
+const FETCH_TIMEOUT_SECS: u64 = 60;
+
+/// Errors from fetching, verifying or installing a pack.
+#[derive(Debug, Error)]
+pub enum SignaturePackError {
+    #[error("failed to fetch {url}: {reason}")]
+    Fetch { url: String, reason: String },
+
+    #[error("pack signature: {0}")]
+    Verify(#[from] KeyError),
+
+    #[error("invalid pack: {0}")]
+    Invalid(#[from] SignatureError),
+
+    #[error("no previous pack to roll back to")]
+    NoPrevious,
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Provenance of an installed pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackInfo {
+    /// Where the pack was fetched from.
+    pub url: String,
+    /// SHA-256 of the pack as fetched (hex).
+    pub sha256: String,
+    /// Fingerprint of the pinned key that verified it.
+    pub key_fingerprint: String,
+    /// Number of signatures in the pack.
+    pub signatures: usize,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// A pack whose signature checked out.
+#[derive(Debug, Clone)]
+pub struct VerifiedPack {
+    pub schema: SignatureSchema,
+    pub info: PackInfo,
+    /// Pack bytes as fetched, installed verbatim.
+    pub bytes: Vec<u8>,
+}
+
+/// Verify `bytes` against `sig` and parse the pack.
+///
+/// `sig` may be raw DER or base64 DER. Every signature in the pack must
+/// validate, and none is allowed to claim to be built-in.
+pub fn verify_pack(
+    url: &str,
+    bytes: Vec<u8>,
+    sig: &[u8],
+    verifier: &SignatureVerifier,
+) -> Result<VerifiedPack, SignaturePackError> {
+    let key_fingerprint = match (verifier.verify_der(&bytes, sig), std::str::from_utf8(sig)) {
+        (Err(KeyError::InvalidSignature(_)), Ok(b64)) => verifier.verify_base64(&bytes, b64)?,
+        (result, _) => result?,
+    };
+    let mut schema: SignatureSchema =
+        serde_json::from_slice(&bytes).map_err(SignatureError::from)?;
+    schema.validate()?;
+    for signature in &mut schema.signatures {
+        signature.builtin = false;
+    }
+    let info = PackInfo {
+        url: url.to_string(),
+        sha256: hex::encode(Sha256::digest(&bytes)),
+        key_fingerprint,
+        signatures: schema.signatures.len(),
+        fetched_at: Utc::now(),
+    };
+    Ok(VerifiedPack {
+        schema,
+        info,
+        bytes,
+    })
+}
+
+/// Fetch a pack and its `.sig` from `url` and verify it.
+///
+/// `https://` URLs are fetched with `curl`; `file://` URLs and bare paths
+/// are read from disk.
+pub fn fetch_pack(
+    url: &str,
+    verifier: &SignatureVerifier,
+) -> Result<VerifiedPack, SignaturePackError> {
+    let bytes = fetch(url)?;
+    let sig = fetch(&format!("{}.sig", url))?;
+    verify_pack(url, bytes, &sig, verifier)
+}
+
+fn fetch(url: &str) -> Result<Vec<u8>, SignaturePackError> {
+    let fetch_error = |reason: String| SignaturePackError::Fetch {
+        url: url.to_string(),
+        reason,
+    };
+    if url.starts_with("http://") {
+        return Err(fetch_error("only https:// URLs are fetched".to_string()));
+    }
+    if !url.starts_with("https://") {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        let bytes = fs::read(path).map_err(|e| fetch_error(e.to_string()))?;
+        if bytes.len() > MAX_PACK_BYTES {
+            return Err(fetch_error(format!("larger than {} bytes", MAX_PACK_BYTES)));
+        }
+        return Ok(bytes);
+    }
+
+    let timeout = Duration::from_secs(FETCH_TIMEOUT_SECS);
+    let runner = ToolRunner::new(ToolConfig {
+        default_timeout: timeout,
+        budget_ms: FETCH_TIMEOUT_SECS * 1000,
+        use_nice: false,
+        #[cfg(target_os = "linux")]
+        use_ionice: false,
+        ..ToolConfig::default()
+    });
+    let spec = ToolSpec::new(
+        "curl",
+        vec![
+            "-fsSL".to_string(),
+            "--proto".to_string(),
+            "=https".to_string(),
+            "--max-time".to_string(),
+            FETCH_TIMEOUT_SECS.to_string(),
+            url.to_string(),
+        ],
+    )
+    .with_timeout(timeout)
+    .with_max_output(MAX_PACK_BYTES + 1);
+    let output = runner.run(&spec).map_err(|e| fetch_error(e.to_string()))?;
+    if !output.success() {
+        return Err(fetch_error(output.stderr_str().trim().to_string()));
+    }
+    if output.truncated || output.stdout.len() > MAX_PACK_BYTES {
+        return Err(fetch_error(format!("larger than {} bytes", MAX_PACK_BYTES)));
+    }
+    Ok(output.stdout)
+}
+
+/// Signatures added, removed and changed by installing a pack.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PackDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl PackDiff {
+    /// Differences going from `old` (none installed when `None`) to `new`.
+    pub fn between(old: Option<&SignatureSchema>, new: &SignatureSchema) -> Self {
+        let old_signatures = old.map(|s| s.signatures.as_slice()).unwrap_or_default();
+        let mut diff = Self::default();
+        for signature in &new.signatures {
+            match old_signatures.iter().find(|s| s.name == signature.name) {
+                None => diff.added.push(signature.name.clone()),
+                Some(previous) if previous != signature => {
+                    diff.changed.push(signature.name.clone())
+                }
+                Some(_) => diff.unchanged += 1,
+            }
+        }
+        diff.removed = old_signatures
+            .iter()
+            .filter(|s| !new.signatures.iter().any(|n| n.name == s.name))
+            .map(|s| s.name.clone())
+            .collect();
+        diff
+    }
+
+    /// Whether installing changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// On-disk home of the installed pack, its predecessor and the pinned keys.
+#[derive(Debug, Clone)]
+pub struct SignaturePackStore {
+    dir: PathBuf,
+}
+
+impl SignaturePackStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// File of pinned public keys: one base64 SEC1 key per line, `#` comments.
+    pub fn trusted_keys_path(&self) -> PathBuf {
+        self.dir.join("trusted_keys")
+    }
+
+    /// Verifier for the pinned keys plus `extra_keys` (base64 SEC1).
+    pub fn verifier(&self, extra_keys: &[String]) -> Result<SignatureVerifier, SignaturePackError> {
+        let mut verifier = SignatureVerifier::new();
+        match fs::read_to_string(self.trusted_keys_path()) {
+            Ok(content) => {
+                for line in content.lines().map(str::trim) {
+                    if !line.is_empty() && !line.starts_with('#') {
+                        verifier.add_base64_key(line)?;
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        for key in extra_keys {
+            verifier.add_base64_key(key)?;
+        }
+        Ok(verifier)
+    }
+
+    /// The installed pack, if any.
+    pub fn current(&self) -> Result<Option<(SignatureSchema, PackInfo)>, SignaturePackError> {
+        Self::read_slot(&self.dir.join("current"))
+    }
+
+    /// The pack a rollback would restore, if any.
+    pub fn previous(&self) -> Result<Option<(SignatureSchema, PackInfo)>, SignaturePackError> {
+        Self::read_slot(&self.dir.join("previous"))
+    }
+
+    /// Install `pack`, keeping the current one for rollback.
+    pub fn install(&self, pack: &VerifiedPack) -> Result<(), SignaturePackError> {
+        let staging = self.dir.join("staging");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+        fs::write(staging.join("pack.json"), &pack.bytes)?;
+        let info = serde_json::to_vec_pretty(&pack.info).map_err(SignatureError::from)?;
+        fs::write(staging.join("info.json"), info)?;
+
+        let current = self.dir.join("current");
+        let previous = self.dir.join("previous");
+        if current.exists() {
+            if previous.exists() {
+                fs::remove_dir_all(&previous)?;
+            }
+            fs::rename(&current, &previous)?;
+        }
+        fs::rename(&staging, &current)?;
+        Ok(())
+    }
+
+    /// Swap the installed pack with the previous one; returns what is now
+    /// installed.
+    pub fn rollback(&self) -> Result<PackInfo, SignaturePackError> {
+        let current = self.dir.join("current");
+        let previous = self.dir.join("previous");
+        let (_, info) = self.previous()?.ok_or(SignaturePackError::NoPrevious)?;
+        let swap = self.dir.join("rollback");
+        if swap.exists() {
+            fs::remove_dir_all(&swap)?;
+        }
+        if current.exists() {
+            fs::rename(&current, &swap)?;
+        }
+        fs::rename(&previous, &current)?;
+        if swap.exists() {
+            fs::rename(&swap, &previous)?;
+        }
+        Ok(info)
+    }
+
+    fn read_slot(slot: &Path) -> Result<Option<(SignatureSchema, PackInfo)>, SignaturePackError> {
+        let pack = slot.join("pack.json");
+        if !pack.exists() {
+            return Ok(None);
+        }
+        let mut schema: SignatureSchema =
+            serde_json::from_slice(&fs::read(pack)?).map_err(SignatureError::from)?;
+        for signature in &mut schema.signatures {
+            signature.builtin = false;
+        }
+        let info: PackInfo = serde_json::from_slice(&fs::read(slot.join("info.json"))?)
+            .map_err(SignatureError::from)?;
+        Ok(Some((schema, info)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::signature::{generate_keypair, sign_bytes};
+    use crate::supervision::signature::SupervisorSignature;
+    use crate::supervision::SupervisorCategory;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use p256::ecdsa::SigningKey;
+    use tempfile::TempDir;
+
+    fn keys() -> (SigningKey, String) {
+        let (sk, vk) = generate_keypair();
+        (
+            SigningKey::from_bytes(sk.as_slice().into()).unwrap(),
+            BASE64.encode(vk),
+        )
+    }
+
+    fn pack(names: &[(&str, u32)]) -> Vec<u8> {
+        let mut schema = SignatureSchema::new();
+        for &(name, priority) in names {
+            schema.add(
+                SupervisorSignature::new(name, SupervisorCategory::Other)
+                    .with_process_patterns(vec![name])
+                    .with_priority(priority),
+            );
+        }
+        serde_json::to_vec(&schema).unwrap()
+    }
+
+    fn verifier(key: &str) -> SignatureVerifier {
+        SignatureVerifier::from_base64(key).unwrap()
+    }
+
+    #[test]
+    fn verifies_der_and_base64_signatures() {
+        let (sk, key) = keys();
+        let bytes = pack(&[("foo", 100)]);
+        let sig = sign_bytes(&bytes, &sk);
+
+        let verified = verify_pack("file:///p", bytes.clone(), &sig, &verifier(&key)).unwrap();
+        assert_eq!(verified.info.signatures, 1);
+        assert_eq!(verified.info.sha256, hex::encode(Sha256::digest(&bytes)));
+        assert!(!verified.schema.signatures[0].builtin);
+
+        let b64 = BASE64.encode(&sig);
+        assert!(verify_pack("file:///p", bytes, b64.as_bytes(), &verifier(&key)).is_ok());
+    }
+
+    #[test]
+    fn rejects_tampered_packs_and_unpinned_keys() {
+        let (sk, key) = keys();
+        let bytes = pack(&[("foo", 100)]);
+        let sig = sign_bytes(&bytes, &sk);
+
+        let tampered = pack(&[("foo", 900)]);
+        assert!(matches!(
+            verify_pack("u", tampered, &sig, &verifier(&key)),
+            Err(SignaturePackError::Verify(
+                KeyError::VerificationFailed { .. }
+            ))
+        ));
+        let (_, other) = keys();
+        assert!(verify_pack("u", bytes.clone(), &sig, &verifier(&other)).is_err());
+        assert!(matches!(
+            verify_pack("u", bytes, &sig, &SignatureVerifier::new()),
+            Err(SignaturePackError::Verify(KeyError::NoKeys))
+        ));
+    }
+
+    #[test]
+    fn diffs_packs() {
+        let old: SignatureSchema =
+            serde_json::from_slice(&pack(&[("a", 1), ("b", 1), ("c", 1)])).unwrap();
+        let new: SignatureSchema =
+            serde_json::from_slice(&pack(&[("a", 1), ("b", 2), ("d", 1)])).unwrap();
+        let diff = PackDiff::between(Some(&old), &new);
+        assert_eq!(diff.added, vec!["d"]);
+        assert_eq!(diff.removed, vec!["c"]);
+        assert_eq!(diff.changed, vec!["b"]);
+        assert_eq!(diff.unchanged, 1);
+        assert!(PackDiff::between(Some(&new), &new).is_empty());
+        assert_eq!(PackDiff::between(None, &new).added.len(), 3);
+    }
+
+    #[test]
+    fn installs_and_rolls_back() {
+        let dir = TempDir::new().unwrap();
+        let store = SignaturePackStore::new(dir.path());
+        let (sk, key) = keys();
+        fs::write(store.trusted_keys_path(), format!("# community\n{}\n", key)).unwrap();
+        let verifier = store.verifier(&[]).unwrap();
+        assert_eq!(verifier.key_count(), 1);
+
+        let first = pack(&[("a", 1)]);
+        let second = pack(&[("a", 1), ("b", 1)]);
+        for bytes in [&first, &second] {
+            let path = dir.path().join("pack.json");
+            fs::write(&path, bytes).unwrap();
+            fs::write(dir.path().join("pack.json.sig"), sign_bytes(bytes, &sk)).unwrap();
+            let verified = fetch_pack(path.to_str().unwrap(), &verifier).unwrap();
+            store.install(&verified).unwrap();
+        }
+        assert_eq!(store.current().unwrap().unwrap().1.signatures, 2);
+        assert_eq!(store.previous().unwrap().unwrap().1.signatures, 1);
+
+        assert_eq!(store.rollback().unwrap().signatures, 1);
+        assert_eq!(store.current().unwrap().unwrap().0.signatures.len(), 1);
+        // Rolling back again re-applies the newer pack.
+        assert_eq!(store.rollback().unwrap().signatures, 2);
+    }
+
+    #[test]
+    fn rollback_without_previous_fails() {
+        let dir = TempDir::new().unwrap();
+        let store = SignaturePackStore::new(dir.path());
+        assert!(store.current().unwrap().is_none());
+        assert!(matches!(
+            store.rollback(),
+            Err(SignaturePackError::NoPrevious)
+        ));
+        assert!(fetch("http://example.com/pack.json").is_err());
+    }
+}
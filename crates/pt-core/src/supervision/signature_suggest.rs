@@ -0,0 +1,486 @@
+//! Signature suggestions from repeated user decisions.
+//!
+//! Every kill applied and every process spared is logged with its command
+//! line. Decisions are grouped by the standard-level pattern of
+//! [`CommandNormalizer`] (process name plus key flags and subcommands); once a
+//! pattern has been decided the same way often enough, it becomes a
+//! [`SignatureSuggestion`]: a user signature whose priors lean the way the
+//! user decided, with the statistics behind it. Suggestions wait in the store
+//! until accepted into the user signatures or dismissed; a dismissed pattern
+//! is not suggested again.
+
+use super::pattern_learning::{CommandNormalizer, DecisionAction, SpecificityLevel};
+use super::signature::{BetaParams, SignaturePatterns, SignaturePriors, SupervisorSignature};
+use super::types::SupervisorCategory;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Decisions about one pattern needed before it is suggested.
+pub const DEFAULT_MIN_DECISIONS: usize = 3;
+
+/// Share of those decisions that must agree.
+pub const DEFAULT_MIN_AGREEMENT: f64 = 0.8;
+
+/// Name prefix of suggested signatures.
+pub const SUGGESTED_SIGNATURE_PREFIX: &str = "learned-";
+
+/// Decisions kept in the log; older ones are dropped.
+const MAX_LOGGED_DECISIONS: usize = 1000;
+
+/// Example command lines kept per suggestion.
+const MAX_EXAMPLES: usize = 3;
+
+const DECISIONS_FILE: &str = "decisions.jsonl";
+const SUGGESTIONS_FILE: &str = "suggestions.json";
+
+/// Errors from the suggestion store.
+#[derive(Debug, Error)]
+pub enum SuggestionError {
+    #[error("I/O error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("no pending signature suggestion named '{0}'")]
+    NotFound(String),
+}
+
+/// A kill or spare the user made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub comm: String,
+    pub cmdline: String,
+    pub action: DecisionAction,
+    pub at: DateTime<Utc>,
+}
+
+impl DecisionRecord {
+    pub fn new(comm: &str, cmdline: &str, action: DecisionAction) -> Self {
+        Self {
+            comm: comm.to_string(),
+            cmdline: cmdline.trim().to_string(),
+            action,
+            at: Utc::now(),
+        }
+    }
+}
+
+/// A proposed user signature for a pattern the user keeps deciding the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignatureSuggestion {
+    /// Name of the proposed signature.
+    pub name: String,
+    /// What the user usually did with matching processes.
+    pub action: DecisionAction,
+    pub kills: u32,
+    pub spares: u32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Recent matching command lines.
+    pub examples: Vec<String>,
+    /// The pattern in readable form.
+    pub pattern: String,
+    pub signature: SupervisorSignature,
+}
+
+impl SignatureSuggestion {
+    /// Decisions agreeing with [`Self::action`].
+    pub fn agreeing(&self) -> u32 {
+        match self.action {
+            DecisionAction::Kill => self.kills,
+            DecisionAction::Spare => self.spares,
+        }
+    }
+
+    /// Question put to the user in the inbox.
+    pub fn prompt(&self) -> String {
+        let verb = match self.action {
+            DecisionAction::Kill => "killed",
+            DecisionAction::Spare => "spared",
+        };
+        format!(
+            "You {} `{}` {} of {} times; would you like pt to remember this?",
+            verb,
+            self.pattern,
+            self.agreeing(),
+            self.kills + self.spares
+        )
+    }
+}
+
+/// Suggestions for every pattern decided at least `min_decisions` times with
+/// at least `min_agreement` of the decisions agreeing.
+pub fn suggest_signatures(
+    decisions: &[DecisionRecord],
+    min_decisions: usize,
+    min_agreement: f64,
+) -> Vec<SignatureSuggestion> {
+    let normalizer = CommandNormalizer::new();
+    let mut groups: BTreeMap<(String, Vec<String>), Vec<&DecisionRecord>> = BTreeMap::new();
+    for decision in decisions {
+        let Some(candidate) = normalizer
+            .generate_candidates(&decision.comm, &decision.cmdline)
+            .into_iter()
+            .find(|c| c.level == SpecificityLevel::Standard)
+        else {
+            continue;
+        };
+        groups
+            .entry((candidate.process_pattern, candidate.arg_patterns))
+            .or_default()
+            .push(decision);
+    }
+
+    let mut suggestions = Vec::new();
+    for ((process_pattern, arg_patterns), group) in groups {
+        if group.len() < min_decisions.max(1) {
+            continue;
+        }
+        let kills = group
+            .iter()
+            .filter(|d| d.action == DecisionAction::Kill)
+            .count() as u32;
+        let spares = group.len() as u32 - kills;
+        let action = if kills >= spares {
+            DecisionAction::Kill
+        } else {
+            DecisionAction::Spare
+        };
+        if f64::from(kills.max(spares)) / group.len() as f64 + 1e-9 < min_agreement {
+            continue;
+        }
+
+        let digest = Sha256::digest(format!("{}\0{}", process_pattern, arg_patterns.join("\0")));
+        let name = format!(
+            "{}{}-{}",
+            SUGGESTED_SIGNATURE_PREFIX,
+            process_pattern.trim_end_matches(".*"),
+            hex::encode(&digest[..4])
+        );
+        let pattern = std::iter::once(process_pattern.as_str())
+            .chain(arg_patterns.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let first_seen = group.iter().map(|d| d.at).min().unwrap_or_else(Utc::now);
+        let last_seen = group.iter().map(|d| d.at).max().unwrap_or_else(Utc::now);
+        let mut examples: Vec<String> = Vec::new();
+        for decision in group.iter().rev() {
+            if examples.len() < MAX_EXAMPLES && !examples.contains(&decision.cmdline) {
+                examples.push(decision.cmdline.clone());
+            }
+        }
+
+        // Spares count for "useful", kills for "abandoned".
+        let (useful, abandoned) = (
+            BetaParams::new(f64::from(spares) + 1.0, f64::from(kills) + 1.0),
+            BetaParams::new(f64::from(kills) + 1.0, f64::from(spares) + 1.0),
+        );
+        let signature = SupervisorSignature {
+            name: name.clone(),
+            category: SupervisorCategory::Other,
+            patterns: SignaturePatterns {
+                process_names: vec![format!("^(?:{})$", process_pattern)],
+                min_matches: if arg_patterns.is_empty() { 1 } else { 2 },
+                arg_patterns,
+                ..Default::default()
+            },
+            confidence_weight: (0.5 + 0.1 * (group.len() as f64).min(5.0)).min(0.95),
+            notes: Some(format!(
+                "Suggested from {} decisions ({} kills, {} spares) between {} and {}",
+                group.len(),
+                kills,
+                spares,
+                first_seen.format("%Y-%m-%d"),
+                last_seen.format("%Y-%m-%d")
+            )),
+            builtin: false,
+            priors: SignaturePriors {
+                useful: Some(useful),
+                abandoned: Some(abandoned),
+                ..Default::default()
+            },
+            expectations: Default::default(),
+            priority: 100 + SpecificityLevel::Standard.priority_offset(),
+            rollout: Default::default(),
+            graceful_stop: None,
+        };
+        if signature.validate().is_err() {
+            continue;
+        }
+
+        suggestions.push(SignatureSuggestion {
+            name,
+            action,
+            kills,
+            spares,
+            first_seen,
+            last_seen,
+            examples,
+            pattern,
+            signature,
+        });
+    }
+    suggestions
+}
+
+/// Pending and dismissed suggestions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SuggestionState {
+    #[serde(default)]
+    pub pending: Vec<SignatureSuggestion>,
+    /// Names of dismissed suggestions.
+    #[serde(default)]
+    pub dismissed: Vec<String>,
+}
+
+/// Decision log and suggestion state on disk.
+#[derive(Debug, Clone)]
+pub struct SuggestionStore {
+    dir: PathBuf,
+}
+
+impl SuggestionStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn io_error(path: PathBuf) -> impl FnOnce(std::io::Error) -> SuggestionError {
+        move |source| SuggestionError::Io { path, source }
+    }
+
+    /// Logged decisions, oldest first.
+    pub fn decisions(&self) -> Result<Vec<DecisionRecord>, SuggestionError> {
+        let path = self.dir.join(DECISIONS_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Self::io_error(path)(e)),
+        };
+        // A torn last line from an interrupted append is skipped.
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Pending and dismissed suggestions.
+    pub fn state(&self) -> Result<SuggestionState, SuggestionError> {
+        let path = self.dir.join(SUGGESTIONS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|source| SuggestionError::Json { path, source }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(SuggestionState::default()),
+            Err(e) => Err(Self::io_error(path)(e)),
+        }
+    }
+
+    fn save_state(&self, state: &SuggestionState) -> Result<(), SuggestionError> {
+        let path = self.dir.join(SUGGESTIONS_FILE);
+        fs::create_dir_all(&self.dir).map_err(Self::io_error(self.dir.clone()))?;
+        let content =
+            serde_json::to_string_pretty(state).map_err(|source| SuggestionError::Json {
+                path: path.clone(),
+                source,
+            })?;
+        fs::write(&path, content).map_err(Self::io_error(path))
+    }
+
+    /// Log `decisions` and refresh the suggestions.
+    ///
+    /// Returns suggestions raised for the first time. Suggestions already
+    /// pending get fresh statistics; dismissed ones and names in `known`
+    /// (existing user signatures) are not raised.
+    pub fn record(
+        &self,
+        decisions: &[DecisionRecord],
+        known: &HashSet<String>,
+    ) -> Result<Vec<SignatureSuggestion>, SuggestionError> {
+        if decisions.is_empty() {
+            return Ok(Vec::new());
+        }
+        let path = self.dir.join(DECISIONS_FILE);
+        fs::create_dir_all(&self.dir).map_err(Self::io_error(self.dir.clone()))?;
+        let mut all = self.decisions()?;
+        all.extend(decisions.iter().cloned());
+        if all.len() > MAX_LOGGED_DECISIONS {
+            all.drain(..all.len() - MAX_LOGGED_DECISIONS);
+            let mut content = String::new();
+            for decision in &all {
+                content.push_str(&serde_json::to_string(decision).unwrap_or_default());
+                content.push('\n');
+            }
+            fs::write(&path, content).map_err(Self::io_error(path.clone()))?;
+        } else {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(Self::io_error(path.clone()))?;
+            for decision in decisions {
+                let line = serde_json::to_string(decision).unwrap_or_default();
+                writeln!(file, "{}", line).map_err(Self::io_error(path.clone()))?;
+            }
+        }
+
+        let mut state = self.state()?;
+        let mut raised = Vec::new();
+        for suggestion in suggest_signatures(&all, DEFAULT_MIN_DECISIONS, DEFAULT_MIN_AGREEMENT) {
+            if let Some(pending) = state.pending.iter_mut().find(|p| p.name == suggestion.name) {
+                *pending = suggestion;
+            } else if !state.dismissed.contains(&suggestion.name)
+                && !known.contains(&suggestion.name)
+            {
+                state.pending.push(suggestion.clone());
+                raised.push(suggestion);
+            }
+        }
+        self.save_state(&state)?;
+        Ok(raised)
+    }
+
+    /// Remove a pending suggestion so it can be added to the user signatures.
+    pub fn accept(&self, name: &str) -> Result<SignatureSuggestion, SuggestionError> {
+        let mut state = self.state()?;
+        let index = state
+            .pending
+            .iter()
+            .position(|s| s.name == name)
+            .ok_or_else(|| SuggestionError::NotFound(name.to_string()))?;
+        let suggestion = state.pending.remove(index);
+        self.save_state(&state)?;
+        Ok(suggestion)
+    }
+
+    /// Drop a pending suggestion and never raise it again.
+    pub fn dismiss(&self, name: &str) -> Result<SignatureSuggestion, SuggestionError> {
+        let mut state = self.state()?;
+        let index = state
+            .pending
+            .iter()
+            .position(|s| s.name == name)
+            .ok_or_else(|| SuggestionError::NotFound(name.to_string()))?;
+        let suggestion = state.pending.remove(index);
+        state.dismissed.push(suggestion.name.clone());
+        self.save_state(&state)?;
+        Ok(suggestion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supervision::signature::{ProcessMatchContext, SignatureDatabase};
+    use tempfile::TempDir;
+
+    const JEST: &str = "/usr/bin/node /home/u/app/node_modules/.bin/jest --watch tests/";
+
+    fn decisions(cmdline: &str, kills: usize, spares: usize) -> Vec<DecisionRecord> {
+        let mut out = Vec::new();
+        for _ in 0..kills {
+            out.push(DecisionRecord::new("node", cmdline, DecisionAction::Kill));
+        }
+        for _ in 0..spares {
+            out.push(DecisionRecord::new("node", cmdline, DecisionAction::Spare));
+        }
+        out
+    }
+
+    #[test]
+    fn suggests_consistently_decided_patterns() {
+        let suggestions = suggest_signatures(
+            &decisions(JEST, 0, 3),
+            DEFAULT_MIN_DECISIONS,
+            DEFAULT_MIN_AGREEMENT,
+        );
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.action, DecisionAction::Spare);
+        assert_eq!((suggestion.kills, suggestion.spares), (0, 3));
+        assert_eq!(suggestion.examples, vec![JEST.to_string()]);
+        assert!(suggestion.name.starts_with("learned-node-"));
+        assert!(suggestion.prompt().contains("spared"));
+        let priors = &suggestion.signature.priors;
+        assert!(priors.useful.as_ref().unwrap().alpha > priors.abandoned.as_ref().unwrap().alpha);
+
+        // The signature matches the command it was learned from.
+        let mut db = SignatureDatabase::new();
+        db.add(suggestion.signature.clone()).unwrap();
+        let ctx = ProcessMatchContext::with_comm("node").cmdline(JEST);
+        assert_eq!(db.match_process(&ctx).len(), 1);
+    }
+
+    #[test]
+    fn skips_rare_and_mixed_patterns() {
+        assert!(suggest_signatures(&decisions(JEST, 2, 0), 3, 0.8).is_empty());
+        assert!(suggest_signatures(&decisions(JEST, 2, 2), 3, 0.8).is_empty());
+        let suggestions = suggest_signatures(&decisions(JEST, 4, 1), 3, 0.8);
+        assert_eq!(suggestions[0].action, DecisionAction::Kill);
+    }
+
+    #[test]
+    fn store_raises_each_suggestion_once() {
+        let dir = TempDir::new().unwrap();
+        let store = SuggestionStore::new(dir.path());
+        let known = HashSet::new();
+
+        assert!(store
+            .record(&decisions(JEST, 0, 2), &known)
+            .unwrap()
+            .is_empty());
+        let raised = store.record(&decisions(JEST, 0, 1), &known).unwrap();
+        assert_eq!(raised.len(), 1);
+        assert!(store
+            .record(&decisions(JEST, 0, 1), &known)
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.state().unwrap().pending[0].spares, 4);
+        assert_eq!(store.decisions().unwrap().len(), 4);
+
+        let name = raised[0].name.clone();
+        store.dismiss(&name).unwrap();
+        assert!(store
+            .record(&decisions(JEST, 0, 3), &known)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            store.accept(&name),
+            Err(SuggestionError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn accepted_suggestions_leave_the_store() {
+        let dir = TempDir::new().unwrap();
+        let store = SuggestionStore::new(dir.path());
+        let raised = store
+            .record(&decisions(JEST, 3, 0), &HashSet::new())
+            .unwrap();
+        let accepted = store.accept(&raised[0].name).unwrap();
+        assert_eq!(accepted.action, DecisionAction::Kill);
+        assert!(store.state().unwrap().pending.is_empty());
+
+        // Known user signatures are not suggested again.
+        let known: HashSet<String> = [accepted.name].into_iter().collect();
+        let other = TempDir::new().unwrap();
+        let store = SuggestionStore::new(other.path());
+        assert!(store
+            .record(&decisions(JEST, 3, 0), &known)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -178,6 +178,11 @@ pub struct SupervisorPattern {
     pub category: SupervisorCategory,
     /// Process name patterns (regex).
     pub process_patterns: Vec<String>,
+    /// Command-line patterns (regex); any match counts. Catches helpers whose
+    /// comm is a generic runtime (`node`, `java`) but whose path or arguments
+    /// name the tool, e.g. `~/.vscode-server/...`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cmdline_patterns: Vec<String>,
     /// Weight for confidence calculation.
     pub confidence_weight: f64,
     /// Notes about this supervisor.
@@ -197,6 +202,7 @@ impl SupervisorPattern {
             name: name.into(),
             category,
             process_patterns: patterns.into_iter().map(String::from).collect(),
+            cmdline_patterns: vec![],
             confidence_weight: weight,
             notes: None,
         }
@@ -207,6 +213,25 @@ impl SupervisorPattern {
         self.notes = Some(notes.into());
         self
     }
+
+    /// Add command-line patterns to this pattern.
+    pub fn with_cmdline_patterns(mut self, patterns: Vec<&str>) -> Self {
+        self.cmdline_patterns = patterns.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Whether a process with this comm and command line matches.
+    pub fn matches(&self, comm: &str, cmdline: Option<&str>) -> bool {
+        let any_match = |patterns: &[String], text: &str| {
+            patterns.iter().any(|pattern| {
+                regex::Regex::new(pattern)
+                    .map(|re| re.is_match(text))
+                    .unwrap_or(false)
+            })
+        };
+        any_match(&self.process_patterns, comm)
+            || cmdline.is_some_and(|cmdline| any_match(&self.cmdline_patterns, cmdline))
+    }
 }
 
 /// Collection of supervisor patterns.
@@ -244,6 +269,7 @@ impl SupervisorDatabase {
                 vec![r"^claude$", r"^claude-code$", r"^claude-cli$"],
                 0.95,
             )
+            .with_cmdline_patterns(vec![r"@anthropic-ai/claude-code/", r"/\.claude/local/"])
             .with_notes("Anthropic Claude AI agent"),
         );
 
@@ -254,6 +280,7 @@ impl SupervisorDatabase {
                 vec![r"^codex$", r"^codex-cli$"],
                 0.95,
             )
+            .with_cmdline_patterns(vec![r"@openai/codex/"])
             .with_notes("OpenAI Codex CLI agent"),
         );
 
@@ -264,6 +291,7 @@ impl SupervisorDatabase {
                 vec![r"^aider$", r"^aider-chat$"],
                 0.90,
             )
+            .with_cmdline_patterns(vec![r"/bin/aider(\s|$)"])
             .with_notes("Aider AI pair programming"),
         );
 
@@ -271,23 +299,90 @@ impl SupervisorDatabase {
             SupervisorPattern::new(
                 "cursor",
                 SupervisorCategory::Agent,
-                vec![r"^cursor$", r"^Cursor$", r"^cursor-agent$"],
+                vec![
+                    r"^cursor$",
+                    r"^Cursor$",
+                    r"^cursor-agent$",
+                    r"^Cursor Helper",
+                ],
                 0.90,
             )
+            .with_cmdline_patterns(vec![r"/\.cursor-server/"])
             .with_notes("Cursor IDE with AI"),
         );
 
+        self.add(
+            SupervisorPattern::new(
+                "gemini-cli",
+                SupervisorCategory::Agent,
+                vec![r"^gemini$"],
+                0.90,
+            )
+            .with_cmdline_patterns(vec![r"@google/gemini-cli/"])
+            .with_notes("Google Gemini CLI agent"),
+        );
+
+        self.add(
+            SupervisorPattern::new(
+                "opencode",
+                SupervisorCategory::Agent,
+                vec![r"^opencode$"],
+                0.85,
+            )
+            .with_notes("opencode terminal agent"),
+        );
+
+        // Copilot's language server runs under an editor; its comm is cut to 15 chars.
+        self.add(
+            SupervisorPattern::new(
+                "copilot",
+                SupervisorCategory::Agent,
+                vec![r"^copilot$", r"^copilot-agent$", r"^copilot-languag"],
+                0.90,
+            )
+            .with_cmdline_patterns(vec![
+                r"copilot-language-server",
+                r"/github\.copilot(-chat)?-[0-9]",
+                r"@github/copilot",
+            ])
+            .with_notes("GitHub Copilot agent and language server"),
+        );
+
         // IDEs
         self.add(
             SupervisorPattern::new(
                 "vscode",
                 SupervisorCategory::Ide,
-                vec![r"^code$", r"^code-server$", r"^Code$", r"^code-oss$"],
+                vec![
+                    r"^code$",
+                    r"^code-server$",
+                    r"^Code$",
+                    r"^code-oss$",
+                    r"^Code Helper",
+                ],
                 0.85,
             )
+            .with_cmdline_patterns(vec![r"/Visual Studio Code\.app/"])
             .with_notes("Visual Studio Code"),
         );
 
+        // Remote servers run as `node` from the install directory, so only the
+        // command line identifies them and the extension hosts they fork.
+        self.add(
+            SupervisorPattern::new(
+                "vscode-server",
+                SupervisorCategory::Ide,
+                vec![r"^code-tunnel$"],
+                0.90,
+            )
+            .with_cmdline_patterns(vec![
+                r"/\.vscode-server(-insiders)?/",
+                r"/\.vscode-remote/",
+                r"/\.windsurf-server/",
+            ])
+            .with_notes("VS Code remote/tunnel server and extension hosts"),
+        );
+
         self.add(
             SupervisorPattern::new(
                 "jetbrains",
@@ -301,10 +396,14 @@ impl SupervisorDatabase {
                     r"^rider$",
                     r"^rubymine$",
                     r"^phpstorm$",
+                    r"^rustrover$",
+                    r"^fsnotifier$",
+                    r"^remote-dev-serv",
                 ],
                 0.85,
             )
-            .with_notes("JetBrains IDEs"),
+            .with_cmdline_patterns(vec![r"com\.intellij\.idea\.Main", r"/RemoteDev/dist/"])
+            .with_notes("JetBrains IDEs, remote-dev backends and daemons"),
         );
 
         self.add(
@@ -414,15 +513,18 @@ impl SupervisorDatabase {
 
     /// Find matching patterns for a process name.
     pub fn find_matches(&self, comm: &str) -> Vec<&SupervisorPattern> {
+        self.find_matches_with_cmdline(comm, None)
+    }
+
+    /// Find matching patterns for a process name and command line.
+    pub fn find_matches_with_cmdline(
+        &self,
+        comm: &str,
+        cmdline: Option<&str>,
+    ) -> Vec<&SupervisorPattern> {
         self.patterns
             .iter()
-            .filter(|p| {
-                p.process_patterns.iter().any(|pattern| {
-                    regex::Regex::new(pattern)
-                        .map(|re| re.is_match(comm))
-                        .unwrap_or(false)
-                })
-            })
+            .filter(|p| p.matches(comm, cmdline))
             .collect()
     }
 }
@@ -484,6 +586,47 @@ mod tests {
         assert!(matches.is_empty());
     }
 
+    #[test]
+    fn test_supervisor_database_matches_helper_cmdlines() {
+        let db = SupervisorDatabase::with_defaults();
+        let first = |comm: &str, cmdline: &str| {
+            db.find_matches_with_cmdline(comm, Some(cmdline))
+                .first()
+                .map(|p| (p.name.clone(), p.category))
+        };
+
+        assert_eq!(
+            first(
+                "node",
+                "/home/dev/.vscode-server/bin/abc123/node /home/dev/.vscode-server/bin/abc123/out/bootstrap-fork --type=extensionHost"
+            ),
+            Some(("vscode-server".to_string(), SupervisorCategory::Ide))
+        );
+        assert_eq!(
+            first(
+                "java",
+                "/opt/idea/jbr/bin/java -Xmx2048m -cp /opt/idea/lib/app.jar com.intellij.idea.Main"
+            ),
+            Some(("jetbrains".to_string(), SupervisorCategory::Ide))
+        );
+        assert_eq!(
+            first("copilot-languag", "copilot-language-server --stdio"),
+            Some(("copilot".to_string(), SupervisorCategory::Agent))
+        );
+        assert_eq!(
+            first(
+                "node",
+                "node /usr/lib/node_modules/@anthropic-ai/claude-code/cli.js"
+            ),
+            Some(("claude".to_string(), SupervisorCategory::Agent))
+        );
+
+        // A plain node process is not a supervisor, and without a command
+        // line only the comm is checked.
+        assert_eq!(first("node", "node server.js"), None);
+        assert!(db.find_matches("node").is_empty());
+    }
+
     // ── SupervisorCategory ──────────────────────────────────────
 
     #[test]
@@ -0,0 +1,385 @@
+//! Live candidate board for `pt-core top`.
+//!
+//! Each refresh scores every scanned process with the inference engine and
+//! [`TopBoard::update`] turns the scores into a [`TopFrame`]: the
+//! highest-posterior candidates with their change since the previous refresh
+//! and a sparkline of recent scores. Score history is keyed by PID and start
+//! ID, so a reused PID starts a fresh series, and is dropped as soon as the
+//! process is gone.
+//!
+//! [`render_frame`] draws a frame as plain text for an in-place terminal
+//! redraw; frames also serialize to JSON for `--format jsonl`.
+
+use crate::output::sparkline_with_max;
+use chrono::{DateTime, Utc};
+use pt_common::units::{format_duration, format_size};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Scores kept per process.
+pub const TOP_HISTORY: usize = 30;
+
+/// Sparkline width in the rendered table.
+const TREND_WIDTH: usize = 16;
+
+/// Score changes smaller than this render as unchanged.
+const DELTA_EPSILON: f64 = 0.005;
+
+/// One scored process from a refresh.
+#[derive(Debug, Clone)]
+pub struct TopSample {
+    pub pid: u32,
+    pub start_id: String,
+    pub command: String,
+    /// Recommended action: `kill`, `spare` or `review`.
+    pub classification: String,
+    /// Posterior that the process is abandoned or a zombie.
+    pub score: f64,
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    pub age_secs: u64,
+}
+
+/// A listed candidate.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopEntry {
+    pub pid: u32,
+    pub start_id: String,
+    pub command: String,
+    pub classification: String,
+    pub score: f64,
+    /// Change since the previous refresh; `None` on first sight.
+    pub delta: Option<f64>,
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    pub age_secs: u64,
+    /// Recent scores, oldest first, ending with `score`.
+    pub history: Vec<f64>,
+}
+
+/// One refresh of the board.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopFrame {
+    pub frame: u64,
+    pub timestamp: DateTime<Utc>,
+    /// Processes scored this refresh.
+    pub scanned: usize,
+    /// Processes at or above the score floor, before the row limit.
+    pub candidates: usize,
+    pub entries: Vec<TopEntry>,
+}
+
+/// Score history across refreshes.
+#[derive(Debug, Default)]
+pub struct TopBoard {
+    history: HashMap<(u32, String), VecDeque<f64>>,
+    frames: u64,
+}
+
+impl TopBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold a refresh into the history and build the frame: processes
+    /// scoring at least `min_score`, highest first, at most `limit` rows.
+    pub fn update(&mut self, samples: Vec<TopSample>, limit: usize, min_score: f64) -> TopFrame {
+        self.frames += 1;
+        let scanned = samples.len();
+        let mut live = HashSet::with_capacity(scanned);
+        let mut entries = Vec::new();
+
+        for sample in samples {
+            let key = (sample.pid, sample.start_id.clone());
+            let series = self.history.entry(key.clone()).or_default();
+            let delta = series.back().map(|prev| sample.score - prev);
+            if series.len() == TOP_HISTORY {
+                series.pop_front();
+            }
+            series.push_back(sample.score);
+            live.insert(key);
+
+            if sample.score < min_score {
+                continue;
+            }
+            entries.push(TopEntry {
+                pid: sample.pid,
+                start_id: sample.start_id,
+                command: sample.command,
+                classification: sample.classification,
+                score: sample.score,
+                delta,
+                cpu_percent: sample.cpu_percent,
+                rss_bytes: sample.rss_bytes,
+                age_secs: sample.age_secs,
+                history: Vec::new(),
+            });
+        }
+        self.history.retain(|key, _| live.contains(key));
+
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.pid.cmp(&b.pid)));
+        let candidates = entries.len();
+        entries.truncate(limit);
+        for entry in &mut entries {
+            if let Some(series) = self.history.get(&(entry.pid, entry.start_id.clone())) {
+                entry.history = series.iter().copied().collect();
+            }
+        }
+
+        TopFrame {
+            frame: self.frames,
+            timestamp: Utc::now(),
+            scanned,
+            candidates,
+            entries,
+        }
+    }
+}
+
+/// Draw a frame as plain text, `width` columns wide.
+pub fn render_frame(frame: &TopFrame, interval: Duration, width: usize) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "pt top  {}  every {}  scanned {}  candidates {}\n",
+        frame
+            .timestamp
+            .with_timezone(&chrono::Local)
+            .format("%H:%M:%S"),
+        format_duration(interval),
+        frame.scanned,
+        frame.candidates,
+    ));
+    out.push_str("keys: [q] quit  [r] run  [space] refresh\n\n");
+
+    let header = format!(
+        "{:>7} {:>5} {:>6} {:<trend$} {:<6} {:>6} {:>10} {:>11}  COMMAND",
+        "PID",
+        "SCORE",
+        "DELTA",
+        "TREND",
+        "ACTION",
+        "CPU%",
+        "RSS",
+        "AGE",
+        trend = TREND_WIDTH,
+    );
+    let command_width = width.saturating_sub(header.chars().count() - "COMMAND".len());
+    out.push_str(&header);
+    out.push('\n');
+
+    if frame.entries.is_empty() {
+        out.push_str("  (no candidates)\n");
+    }
+    for entry in &frame.entries {
+        let delta = match entry.delta {
+            None => "new".to_string(),
+            Some(d) if d.abs() < DELTA_EPSILON => "=".to_string(),
+            Some(d) => format!("{:+.2}", d),
+        };
+        out.push_str(&format!(
+            "{:>7} {:>5.2} {:>6} {:<trend$} {:<6} {:>6.1} {:>10} {:>11}  {}\n",
+            entry.pid,
+            entry.score,
+            delta,
+            sparkline_with_max(&entry.history, TREND_WIDTH, 1.0),
+            entry.classification,
+            entry.cpu_percent,
+            format_size(entry.rss_bytes),
+            format_duration(Duration::from_secs(entry.age_secs)),
+            truncate_chars(&entry.command, command_width.max(10)),
+            trend = TREND_WIDTH,
+        ));
+    }
+    out
+}
+
+fn truncate_chars(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+    let mut truncated: String = value.chars().take(max.saturating_sub(1)).collect();
+    truncated.push('\u{2026}');
+    truncated
+}
+
+/// Terminal width in columns: the tty size, then `$COLUMNS`, then 120.
+pub fn terminal_width() -> usize {
+    #[cfg(unix)]
+    {
+        // SAFETY: TIOCGWINSZ only writes into the provided winsize struct.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+            && size.ws_col > 0
+        {
+            return size.ws_col as usize;
+        }
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|cols: &usize| *cols > 0)
+        .unwrap_or(120)
+}
+
+/// Unbuffered, no-echo stdin for single-key commands; restored on drop.
+///
+/// Output processing is left alone, so `\n` still returns the cursor. Signal
+/// keys are delivered as bytes (Ctrl-C reads as `0x03`) so the caller can
+/// exit through the normal path and the terminal is always restored.
+#[cfg(unix)]
+pub struct RawInput {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawInput {
+    /// Switch stdin to single-key mode, or `None` when it is not a terminal.
+    pub fn enable() -> Option<Self> {
+        use std::io::IsTerminal;
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        // SAFETY: termios is plain data filled in by tcgetattr.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return None;
+        }
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return None;
+        }
+        Some(Self { original })
+    }
+
+    /// Wait up to `timeout` for a key press.
+    pub fn read_key(&self, timeout: Duration) -> Option<u8> {
+        let mut fds = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+        // SAFETY: one valid pollfd; read writes at most one byte into `key`.
+        if unsafe { libc::poll(&mut fds, 1, millis) } <= 0 {
+            return None;
+        }
+        let mut key = 0u8;
+        let n = unsafe { libc::read(libc::STDIN_FILENO, (&mut key as *mut u8).cast(), 1) };
+        (n == 1).then_some(key)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawInput {
+    fn drop(&mut self) {
+        // SAFETY: restores the attributes read in `enable`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Single-key input is only available on Unix terminals.
+#[cfg(not(unix))]
+pub struct RawInput;
+
+#[cfg(not(unix))]
+impl RawInput {
+    pub fn enable() -> Option<Self> {
+        None
+    }
+
+    pub fn read_key(&self, timeout: Duration) -> Option<u8> {
+        std::thread::sleep(timeout);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(pid: u32, start_id: &str, score: f64) -> TopSample {
+        TopSample {
+            pid,
+            start_id: start_id.to_string(),
+            command: format!("proc-{}", pid),
+            classification: "review".to_string(),
+            score,
+            cpu_percent: 0.0,
+            rss_bytes: 1024,
+            age_secs: 3600,
+        }
+    }
+
+    #[test]
+    fn frames_rank_by_score_and_track_deltas() {
+        let mut board = TopBoard::new();
+        let first = board.update(
+            vec![
+                sample(10, "a", 0.4),
+                sample(11, "b", 0.9),
+                sample(12, "c", 0.1),
+            ],
+            2,
+            0.0,
+        );
+        assert_eq!(first.frame, 1);
+        assert_eq!(first.scanned, 3);
+        assert_eq!(first.candidates, 3);
+        let pids: Vec<u32> = first.entries.iter().map(|e| e.pid).collect();
+        assert_eq!(pids, vec![11, 10]);
+        assert!(first.entries.iter().all(|e| e.delta.is_none()));
+
+        let second = board.update(vec![sample(10, "a", 0.95), sample(11, "b", 0.9)], 2, 0.0);
+        assert_eq!(second.entries[0].pid, 10);
+        assert!((second.entries[0].delta.unwrap() - 0.55).abs() < 1e-9);
+        assert_eq!(second.entries[0].history, vec![0.4, 0.95]);
+        assert_eq!(second.entries[1].delta, Some(0.0));
+    }
+
+    #[test]
+    fn reused_pid_starts_fresh_history_and_gone_processes_are_dropped() {
+        let mut board = TopBoard::new();
+        board.update(vec![sample(10, "a", 0.5), sample(12, "c", 0.5)], 10, 0.0);
+        let frame = board.update(vec![sample(10, "z", 0.7)], 10, 0.0);
+        assert_eq!(frame.entries[0].delta, None);
+        assert_eq!(frame.entries[0].history, vec![0.7]);
+        assert_eq!(board.history.len(), 1);
+    }
+
+    #[test]
+    fn score_floor_filters_rows_but_keeps_history() {
+        let mut board = TopBoard::new();
+        let frame = board.update(vec![sample(10, "a", 0.2), sample(11, "b", 0.8)], 10, 0.5);
+        assert_eq!(frame.candidates, 1);
+        assert_eq!(frame.entries[0].pid, 11);
+
+        let frame = board.update(vec![sample(10, "a", 0.6)], 10, 0.5);
+        assert_eq!(frame.entries[0].history, vec![0.2, 0.6]);
+    }
+
+    #[test]
+    fn render_shows_header_rows_and_truncated_commands() {
+        let mut board = TopBoard::new();
+        board.update(vec![sample(42, "a", 0.5)], 10, 0.0);
+        let mut long = sample(42, "a", 0.8);
+        long.command = "x".repeat(300);
+        let frame = board.update(vec![long], 10, 0.0);
+
+        let text = render_frame(&frame, Duration::from_secs(2), 100);
+        assert!(text.starts_with("pt top"));
+        assert!(text.contains("scanned 1  candidates 1"));
+        assert!(text.contains("+0.30"));
+        let row = text.lines().find(|l| l.contains("   42 ")).unwrap();
+        assert!(row.ends_with('\u{2026}'));
+        assert!(row.chars().count() <= 100);
+
+        let empty = board.update(Vec::new(), 10, 0.0);
+        assert!(render_frame(&empty, Duration::from_secs(2), 80).contains("(no candidates)"));
+    }
+}
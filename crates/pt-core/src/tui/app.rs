@@ -11,8 +11,8 @@
 //! - `view(frame)` renders state into a frame (pure w.r.t. input state)
 //! - `subscriptions()` registers periodic ticks and other streams
 //!
-//! Async work (refresh, execute, evidence export) is injected via closures and executed via
-//! `Cmd::task`, returning completion messages back into `update()`.
+//! Async work (refresh, execute, evidence export, telemetry sampling) is injected via
+//! closures and executed via `Cmd::task`, returning completion messages back into `update()`.
 //!
 //! ## Running
 //!
@@ -36,12 +36,13 @@ use ftui::{
 
 use super::events::KeyBindings;
 use super::layout::{Breakpoint, LayoutState, ResponsiveLayout};
-use super::msg::{ExecutionOutcome, Msg};
+use super::msg::{ExecutionOutcome, Msg, StageSignal};
+use super::telemetry::{TelemetrySample, TelemetryStore};
 use super::theme::Theme;
 use super::widgets::{
     AuxPanel, ConfirmChoice, ConfirmDialog, ConfirmDialogState, DetailView, HelpOverlay,
     ProcessDetail, ProcessRow, ProcessTable, ProcessTableState, SearchInput, SearchInputState,
-    StatusBar, StatusMode,
+    SortOrder, StatusBar, StatusMode,
 };
 use super::{TuiError, TuiResult};
 
@@ -72,6 +73,26 @@ pub enum AppState {
 
 type RefreshOp = Arc<dyn Fn() -> Result<Vec<ProcessRow>, String> + Send + Sync>;
 type ExecuteOp = Arc<dyn Fn(Vec<u32>) -> Result<ExecutionOutcome, String> + Send + Sync>;
+type SnoozeOp = Arc<dyn Fn(u32, String) -> Result<String, String> + Send + Sync>;
+type RememberOp = Arc<dyn Fn(u32, String) -> Result<String, String> + Send + Sync>;
+type SelectionOp = Arc<dyn Fn(Vec<u32>) + Send + Sync>;
+type SampleOp = Arc<dyn Fn(Vec<u32>) -> Vec<TelemetrySample> + Send + Sync>;
+type StageOp = Arc<dyn Fn(Vec<u32>, StageSignal) -> Result<Vec<u32>, String> + Send + Sync>;
+
+/// Interval between telemetry samples for the listed processes.
+const TELEMETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Refresh rate of the staged-kill countdown.
+const STAGE_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A staged kill waiting out its grace period.
+#[derive(Debug, Clone)]
+struct StagedKill {
+    /// Stopped PIDs, killed when the grace period ends.
+    pids: Vec<u32>,
+    /// When the kill proceeds unless undone.
+    deadline: Instant,
+}
 
 /// Main TUI application.
 pub struct App {
@@ -111,6 +132,30 @@ pub struct App {
     /// Injected execute operation for ftui Cmd::task (Send + 'static).
     /// Takes selected PIDs, returns execution outcome.
     execute_op: Option<ExecuteOp>,
+    /// Injected snooze operation for ftui Cmd::task (Send + 'static).
+    /// Takes (pid, command), returns a status message describing the snooze.
+    snooze_op: Option<SnoozeOp>,
+    /// Injected "never suggest again" operation for ftui Cmd::task.
+    /// Takes (pid, command), writes a protecting user signature and returns
+    /// a status message naming it.
+    remember_op: Option<RememberOp>,
+    /// Injected selection observer, called with the sorted selected PIDs
+    /// whenever the selection changes (used to persist state for resume).
+    selection_op: Option<SelectionOp>,
+    /// Selection last reported to `selection_op`.
+    synced_selection: Vec<u32>,
+    /// Injected telemetry sampler for ftui Cmd::task (Send + 'static).
+    /// Takes the listed PIDs, returns one CPU/RSS sample per live PID.
+    sample_op: Option<SampleOp>,
+    /// CPU/RSS series sampled over the session, shown in the detail pane.
+    telemetry: TelemetryStore,
+    /// Injected stop/continue operation for staged kills (Send + 'static).
+    /// Takes PIDs and the signal, returns the PIDs actually signalled.
+    stage_op: Option<StageOp>,
+    /// Time a staged kill waits for an undo before proceeding.
+    stage_grace: Duration,
+    /// Staged kill currently counting down.
+    staged: Option<StagedKill>,
     /// Toast notification queue for async operation feedback.
     notifications: NotificationQueue,
     /// Command palette for fuzzy action discovery and execution.
@@ -164,6 +209,15 @@ impl App {
             goal_summary: None,
             refresh_op: None,
             execute_op: None,
+            snooze_op: None,
+            remember_op: None,
+            selection_op: None,
+            synced_selection: Vec::new(),
+            sample_op: None,
+            telemetry: TelemetryStore::new(),
+            stage_op: None,
+            stage_grace: Duration::ZERO,
+            staged: None,
             notifications: NotificationQueue::new(QueueConfig {
                 max_visible: 3,
                 max_queued: 10,
@@ -246,6 +300,26 @@ impl App {
                 .with_description("Switch process table between score and goal ordering")
                 .with_tags(&["goal", "view", "sort"])
                 .with_category("Views"),
+            ActionItem::new("action.undo_staged", "Undo staged kill  [U]")
+                .with_description("Resume processes paused by a staged kill before it proceeds")
+                .with_tags(&["undo", "resume", "staged"])
+                .with_category("Actions"),
+            ActionItem::new("action.remember", "Never suggest this again  [Z]")
+                .with_description("Save a user signature that protects the current command")
+                .with_tags(&["spare", "whitelist", "remember", "signature"])
+                .with_category("Actions"),
+            ActionItem::new("view.sort", "Cycle sort column  [o]")
+                .with_description("Sort the process table by score, memory or runtime")
+                .with_tags(&["sort", "memory", "runtime", "score"])
+                .with_category("Views"),
+            ActionItem::new("view.sort_reverse", "Reverse sort order  [O]")
+                .with_description("Flip between ascending and descending order")
+                .with_tags(&["sort", "reverse", "order"])
+                .with_category("Views"),
+            ActionItem::new("view.class_filter", "Cycle classification filter  [c]")
+                .with_description("Show only KILL, REVIEW or SPARE rows, or all")
+                .with_tags(&["filter", "class", "kill", "review", "spare"])
+                .with_category("Views"),
             ActionItem::new("view.help", "Show keyboard shortcuts  [?]")
                 .with_description("Open the full help overlay")
                 .with_tags(&["help", "shortcuts"])
@@ -338,6 +412,88 @@ impl App {
         self.execute_op = Some(op);
     }
 
+    /// Set the async snooze operation for ftui Cmd::task.
+    pub fn set_snooze_op(&mut self, op: SnoozeOp) {
+        self.snooze_op = Some(op);
+    }
+
+    /// Set the async "never suggest again" operation for ftui Cmd::task.
+    pub fn set_remember_op(&mut self, op: RememberOp) {
+        self.remember_op = Some(op);
+    }
+
+    /// Set the observer notified when the process selection changes.
+    ///
+    /// The current selection is treated as already synced.
+    pub fn set_selection_op(&mut self, op: SelectionOp) {
+        self.synced_selection = self.sorted_selection();
+        self.selection_op = Some(op);
+    }
+
+    /// Set the telemetry sampler behind the detail pane sparklines.
+    ///
+    /// Sampling runs every few seconds for all listed processes, so the
+    /// history is already there when a process is opened.
+    pub fn set_sample_op(&mut self, op: SampleOp) {
+        self.sample_op = Some(op);
+    }
+
+    /// Enable staged kills.
+    ///
+    /// Confirmed targets are stopped (SIGSTOP) instead of killed. `U` undoes
+    /// the stop during `grace`; after that the execute operation runs on the
+    /// still-stopped targets as usual.
+    pub fn set_stage_op(&mut self, op: StageOp, grace: Duration) {
+        self.stage_op = Some(op);
+        self.stage_grace = grace;
+    }
+
+    /// PIDs of the staged kill counting down, if any.
+    pub fn staged_pids(&self) -> Option<&[u32]> {
+        self.staged.as_ref().map(|staged| staged.pids.as_slice())
+    }
+
+    /// Resume staged targets before exiting so no process is left stopped.
+    fn release_staged(&mut self) {
+        if let (Some(staged), Some(stage)) = (self.staged.take(), self.stage_op.as_ref()) {
+            if let Err(error) = stage(staged.pids, StageSignal::Continue) {
+                tracing::warn!(target: "tui.staged", error = %error, "Failed to resume staged processes on exit");
+            }
+        }
+    }
+
+    fn staged_countdown_status(&self, staged: &StagedKill) -> String {
+        let remaining = staged.deadline.saturating_duration_since(Instant::now());
+        format!(
+            "Killing {} paused process(es) in {}s \u{2022} press U to undo",
+            staged.pids.len(),
+            remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+        )
+    }
+
+    /// CPU/RSS series sampled so far.
+    pub fn telemetry(&self) -> &TelemetryStore {
+        &self.telemetry
+    }
+
+    fn sorted_selection(&self) -> Vec<u32> {
+        let mut selected = self.process_table.get_selected();
+        selected.sort_unstable();
+        selected
+    }
+
+    /// Report the selection to `selection_op` if it changed since the last sync.
+    fn sync_selection(&mut self) {
+        let Some(op) = self.selection_op.clone() else {
+            return;
+        };
+        let selected = self.sorted_selection();
+        if selected != self.synced_selection {
+            self.synced_selection = selected.clone();
+            op(selected);
+        }
+    }
+
     /// Set a status message.
     pub fn set_status(&mut self, message: impl Into<String>) {
         self.status_message = Some(message.into());
@@ -441,6 +597,8 @@ impl App {
         match action_id {
             "action.execute" => "Execute selected",
             "action.refresh" => "Refresh processes",
+            "action.undo_staged" => "Undo staged kill",
+            "action.remember" => "Never suggest this again",
             "navigation.first" => "Go to first process",
             "navigation.last" => "Go to last process",
             "navigation.page_down" => "Page down",
@@ -455,6 +613,9 @@ impl App {
             "view.genealogy" => "Show genealogy detail",
             "view.galaxy" => "Toggle galaxy brain detail",
             "view.goal" => "Toggle goal view",
+            "view.sort" => "Cycle sort column",
+            "view.sort_reverse" => "Reverse sort order",
+            "view.class_filter" => "Cycle classification filter",
             "view.help" => "Show keyboard shortcuts",
             "settings.theme.dark" => "Switch theme dark",
             "settings.theme.light" => "Switch theme light",
@@ -472,6 +633,8 @@ impl App {
         match action_id {
             "action.execute" => self.show_execute_confirmation(),
             "action.refresh" => return FtuiCmd::msg(Msg::RequestRefresh),
+            "action.undo_staged" => return FtuiCmd::msg(Msg::UndoStaged),
+            "action.remember" => return FtuiCmd::msg(Msg::RequestRemember),
 
             "navigation.first" => self.process_table.cursor_home(),
             "navigation.last" => self.process_table.cursor_end(),
@@ -509,6 +672,9 @@ impl App {
                     self.set_status("Goal view unavailable");
                 }
             }
+            "view.sort" => self.cycle_sort(),
+            "view.sort_reverse" => self.reverse_sort(),
+            "view.class_filter" => self.cycle_class_filter(),
             "view.help" => self.state = AppState::Help,
 
             "settings.theme.dark" => self.theme = Theme::dark(),
@@ -583,6 +749,32 @@ impl App {
         }
     }
 
+    fn cycle_sort(&mut self) {
+        self.process_table.cycle_sort();
+        self.set_status(format!("Sort: {}", self.process_table.sort_label()));
+    }
+
+    fn reverse_sort(&mut self) {
+        self.process_table.reverse_sort();
+        let order = match self.process_table.sort_order {
+            SortOrder::Ascending => "ascending",
+            SortOrder::Descending => "descending",
+        };
+        self.set_status(format!(
+            "Sort: {} ({})",
+            self.process_table.sort_label(),
+            order
+        ));
+    }
+
+    fn cycle_class_filter(&mut self) {
+        self.process_table.cycle_class_filter();
+        self.set_status(format!(
+            "Class filter: {}",
+            self.process_table.class_filter.unwrap_or("all")
+        ));
+    }
+
     /// Apply the current search filter to the process table.
     fn apply_search_filter(&mut self) {
         let query = self.search.value().to_lowercase();
@@ -617,6 +809,45 @@ impl App {
         self.state = AppState::Normal;
     }
 
+    /// Run the execute operation on `selected_pids`.
+    fn execute_pids(&mut self, selected_pids: Vec<u32>) -> FtuiCmd<Msg> {
+        let selected_count = selected_pids.len();
+        if let Some(execute) = self.execute_op.clone() {
+            self.set_status(format!(
+                "Executing actions on {} process(es)...",
+                selected_count
+            ));
+            FtuiCmd::sequence(vec![
+                FtuiCmd::log(format!(
+                    "execute: starting (selected_count={})",
+                    selected_count
+                )),
+                FtuiCmd::task_named("execute-selected", move || {
+                    Msg::ExecutionComplete(execute(selected_pids))
+                }),
+            ])
+        } else {
+            self.set_status(format!(
+                "Execution requested for {} process(es) (skeleton mode)",
+                selected_count
+            ));
+            FtuiCmd::sequence(vec![
+                FtuiCmd::log(format!(
+                    "execute: skeleton mode (selected_count={})",
+                    selected_count
+                )),
+                FtuiCmd::task_named("execute-selected", move || {
+                    Msg::ExecutionComplete(Ok(ExecutionOutcome {
+                        mode: Some("skeleton".to_string()),
+                        attempted: selected_count,
+                        succeeded: 0,
+                        failed: 0,
+                    }))
+                }),
+            ])
+        }
+    }
+
     /// Check if the application should quit.
     pub fn should_quit(&self) -> bool {
         self.state == AppState::Quitting
@@ -789,40 +1020,82 @@ impl App {
                     selected_count,
                     "Execution requested"
                 );
-                if let Some(execute) = self.execute_op.clone() {
-                    self.set_status(format!(
-                        "Executing actions on {} process(es)...",
-                        selected_count
-                    ));
-                    FtuiCmd::sequence(vec![
-                        FtuiCmd::log(format!(
-                            "execute: starting (selected_count={})",
-                            selected_count
-                        )),
-                        FtuiCmd::task_named("execute-selected", move || {
-                            Msg::ExecutionComplete(execute(selected_pids))
-                        }),
-                    ])
-                } else {
-                    self.set_status(format!(
-                        "Execution requested for {} process(es) (skeleton mode)",
-                        selected_count
-                    ));
-                    FtuiCmd::sequence(vec![
-                        FtuiCmd::log(format!(
-                            "execute: skeleton mode (selected_count={})",
-                            selected_count
-                        )),
-                        FtuiCmd::task_named("execute-selected", move || {
-                            Msg::ExecutionComplete(Ok(ExecutionOutcome {
-                                mode: Some("skeleton".to_string()),
-                                attempted: selected_count,
-                                succeeded: 0,
-                                failed: 0,
-                            }))
-                        }),
-                    ])
+                if self.staged.is_some() {
+                    self.set_status("A staged kill is pending \u{2022} press U to undo it first");
+                    return FtuiCmd::none();
                 }
+                if let Some(stage) = self.stage_op.clone() {
+                    self.set_status(format!("Pausing {} process(es)...", selected_count));
+                    return FtuiCmd::task_named("stage-selected", move || {
+                        Msg::StagePaused(stage(selected_pids, StageSignal::Stop))
+                    });
+                }
+                self.execute_pids(selected_pids)
+            }
+            Msg::StagePaused(Ok(pids)) => {
+                if pids.is_empty() {
+                    self.set_status("No process could be paused; nothing staged");
+                    return FtuiCmd::none();
+                }
+                let staged = StagedKill {
+                    pids,
+                    deadline: Instant::now() + self.stage_grace,
+                };
+                let status = self.staged_countdown_status(&staged);
+                self.push_toast(status.clone(), ToastIcon::Warning, ToastStyle::Warning);
+                self.set_status(status);
+                self.staged = Some(staged);
+                FtuiCmd::log("execute: staged")
+            }
+            Msg::StagePaused(Err(error)) => {
+                self.set_status(format!("Staging failed: {}", error));
+                self.push_toast(
+                    format!("Staging failed: {}", error),
+                    ToastIcon::Error,
+                    ToastStyle::Error,
+                );
+                FtuiCmd::none()
+            }
+            Msg::StageTick => {
+                let Some(staged) = self.staged.as_ref() else {
+                    return FtuiCmd::none();
+                };
+                if Instant::now() < staged.deadline {
+                    let status = self.staged_countdown_status(staged);
+                    self.set_status(status);
+                    return FtuiCmd::none();
+                }
+                let pids = self.staged.take().map(|s| s.pids).unwrap_or_default();
+                self.execute_pids(pids)
+            }
+            Msg::UndoStaged => {
+                let Some(staged) = self.staged.take() else {
+                    self.set_status("Nothing to undo");
+                    return FtuiCmd::none();
+                };
+                tracing::info!(target: "tui.user_input", action = "undo_staged", count = staged.pids.len(), "Staged kill undone");
+                let Some(stage) = self.stage_op.clone() else {
+                    return FtuiCmd::none();
+                };
+                self.set_status(format!("Resuming {} process(es)...", staged.pids.len()));
+                FtuiCmd::task_named("undo-staged", move || {
+                    Msg::StageResumed(stage(staged.pids, StageSignal::Continue))
+                })
+            }
+            Msg::StageResumed(Ok(pids)) => {
+                let status = format!("Undone: resumed {} process(es)", pids.len());
+                self.push_toast(status.clone(), ToastIcon::Success, ToastStyle::Success);
+                self.set_status(status);
+                FtuiCmd::none()
+            }
+            Msg::StageResumed(Err(error)) => {
+                self.set_status(format!("Undo failed: {}", error));
+                self.push_toast(
+                    format!("Undo failed: {}", error),
+                    ToastIcon::Error,
+                    ToastStyle::Error,
+                );
+                FtuiCmd::none()
             }
             Msg::ConfirmExecute => {
                 self.handle_confirmation(ConfirmChoice::Yes);
@@ -853,10 +1126,61 @@ impl App {
                     ])
                 }
             }
+            Msg::RequestSnooze => {
+                let Some(row) = self.process_table.current_row() else {
+                    self.set_status("No process selected to snooze");
+                    return FtuiCmd::none();
+                };
+                let (pid, command) = (row.pid, row.command.clone());
+                tracing::info!(target: "tui.user_input", action = "snooze_requested", pid, "Snooze requested");
+                if let Some(snooze) = self.snooze_op.clone() {
+                    self.set_status(format!("Snoozing PID {}...", pid));
+                    FtuiCmd::task_named("snooze-candidate", move || {
+                        Msg::SnoozeComplete(snooze(pid, command))
+                    })
+                } else {
+                    self.set_status("Snooze is not available in this session");
+                    FtuiCmd::none()
+                }
+            }
+            Msg::RequestRemember => {
+                let Some(row) = self.process_table.current_row() else {
+                    self.set_status("No process selected to spare");
+                    return FtuiCmd::none();
+                };
+                let (pid, command) = (row.pid, row.command.clone());
+                tracing::info!(target: "tui.user_input", action = "remember_requested", pid, "Remember spare requested");
+                if let Some(remember) = self.remember_op.clone() {
+                    self.set_status(format!("Remembering PID {} as spared...", pid));
+                    FtuiCmd::task_named("remember-spare", move || {
+                        Msg::RememberComplete(remember(pid, command))
+                    })
+                } else {
+                    self.set_status("Remembering spares is not available in this session");
+                    FtuiCmd::none()
+                }
+            }
             Msg::ExportEvidenceLedger => {
                 self.set_status("Evidence ledger export is not wired yet");
                 FtuiCmd::none()
             }
+            Msg::SampleTelemetry => {
+                let Some(sample) = self.sample_op.clone() else {
+                    return FtuiCmd::none();
+                };
+                let pids: Vec<u32> = self.process_table.rows.iter().map(|r| r.pid).collect();
+                if pids.is_empty() {
+                    return FtuiCmd::none();
+                }
+                FtuiCmd::task_named("sample-telemetry", move || {
+                    Msg::TelemetrySampled(sample(pids))
+                })
+            }
+            Msg::TelemetrySampled(samples) => {
+                self.telemetry.record(&samples);
+                self.needs_redraw = true;
+                FtuiCmd::none()
+            }
 
             Msg::ProcessesScanned(rows) => {
                 self.process_table.set_rows(rows);
@@ -865,6 +1189,8 @@ impl App {
             }
             Msg::RefreshComplete(Ok(rows)) => {
                 let count = rows.len();
+                let pids: Vec<u32> = rows.iter().map(|r| r.pid).collect();
+                self.telemetry.retain_pids(&pids);
                 self.process_table.set_rows(rows);
                 self.set_status(format!("Process list refreshed ({})", count));
                 self.push_toast(
@@ -942,6 +1268,35 @@ impl App {
                 FtuiCmd::none()
             }
 
+            Msg::SnoozeComplete(Ok(message)) => {
+                self.set_status(message.clone());
+                self.push_toast(message, ToastIcon::Success, ToastStyle::Success);
+                FtuiCmd::msg(Msg::RequestRefresh)
+            }
+            Msg::SnoozeComplete(Err(error)) => {
+                self.set_status(format!("Snooze failed: {}", error));
+                self.push_toast(
+                    format!("Snooze failed: {}", error),
+                    ToastIcon::Error,
+                    ToastStyle::Error,
+                );
+                FtuiCmd::none()
+            }
+            Msg::RememberComplete(Ok(message)) => {
+                self.set_status(message.clone());
+                self.push_toast(message, ToastIcon::Success, ToastStyle::Success);
+                FtuiCmd::msg(Msg::RequestRefresh)
+            }
+            Msg::RememberComplete(Err(error)) => {
+                self.set_status(format!("Remember failed: {}", error));
+                self.push_toast(
+                    format!("Remember failed: {}", error),
+                    ToastIcon::Error,
+                    ToastStyle::Error,
+                );
+                FtuiCmd::none()
+            }
+
             Msg::SwitchTheme(name) => {
                 self.theme = match name.to_lowercase().as_str() {
                     "light" => Theme::light(),
@@ -958,6 +1313,7 @@ impl App {
             }
 
             Msg::Quit => {
+                self.release_staged();
                 self.state = AppState::Quitting;
                 FtuiCmd::quit()
             }
@@ -994,6 +1350,7 @@ impl App {
     fn handle_ftui_normal_key(&mut self, key: FtuiKeyEvent) -> FtuiCmd<Msg> {
         if matches!(key.code, FtuiKeyCode::Escape) || self.key_bindings.is_quit(&key) {
             tracing::info!(target: "tui.user_input", action = "quit", "Quit requested");
+            self.release_staged();
             self.state = AppState::Quitting;
             return FtuiCmd::quit();
         }
@@ -1051,6 +1408,8 @@ impl App {
             FtuiKeyCode::Char('x') => self.process_table.invert_selection(),
             FtuiKeyCode::Enter => self.toggle_detail_visibility(),
             FtuiKeyCode::Char('r') => return FtuiCmd::msg(Msg::RequestRefresh),
+            FtuiKeyCode::Char('z') => return FtuiCmd::msg(Msg::RequestSnooze),
+            FtuiKeyCode::Char('Z') => return FtuiCmd::msg(Msg::RequestRemember),
             FtuiKeyCode::Char('s') => self.set_detail_view(DetailView::Summary),
             FtuiKeyCode::Char('t') => self.set_detail_view(DetailView::Genealogy),
             FtuiKeyCode::Char('g') => {
@@ -1071,6 +1430,10 @@ impl App {
                     self.set_status("Goal view unavailable");
                 }
             }
+            FtuiKeyCode::Char('o') => self.cycle_sort(),
+            FtuiKeyCode::Char('O') => self.reverse_sort(),
+            FtuiKeyCode::Char('c') => self.cycle_class_filter(),
+            FtuiKeyCode::Char('U') => return FtuiCmd::msg(Msg::UndoStaged),
             _ => {}
         }
         FtuiCmd::none()
@@ -1150,7 +1513,9 @@ impl FtuiModel for App {
     }
 
     fn update(&mut self, msg: Self::Message) -> FtuiCmd<Self::Message> {
-        self.handle_msg(msg)
+        let cmd = self.handle_msg(msg);
+        self.sync_selection();
+        cmd
     }
 
     fn view(&self, frame: &mut FtuiFrame) {
@@ -1201,6 +1566,7 @@ impl FtuiModel for App {
                     .theme(&self.theme)
                     .row(current_row, selected)
                     .view(self.detail_view)
+                    .telemetry(current_row.and_then(|r| self.telemetry.get(r.pid)))
                     .render_ftui(detail_area, frame);
             }
         }
@@ -1271,17 +1637,33 @@ impl FtuiModel for App {
     }
 
     fn subscriptions(&self) -> Vec<Box<dyn Subscription<Self::Message>>> {
-        if self.reduce_motion {
-            // Skip periodic tick when motion is reduced; toasts use longer
-            // static durations and no stagger animation.
-            vec![]
-        } else {
-            vec![Box::new(Every::with_id(
+        let mut subscriptions: Vec<Box<dyn Subscription<Self::Message>>> = Vec::new();
+        // Skip periodic tick when motion is reduced; toasts use longer
+        // static durations and no stagger animation.
+        if !self.reduce_motion {
+            subscriptions.push(Box::new(Every::with_id(
                 0x5054_5449_434B,
                 Duration::from_secs(5),
                 || Msg::Tick,
-            ))]
+            )));
+        }
+        // Telemetry is data, not animation: sample regardless of motion settings.
+        if self.sample_op.is_some() {
+            subscriptions.push(Box::new(Every::with_id(
+                0x5054_5350_4B4C,
+                TELEMETRY_INTERVAL,
+                || Msg::SampleTelemetry,
+            )));
+        }
+        // The undo countdown must advance even with reduced motion.
+        if self.staged.is_some() {
+            subscriptions.push(Box::new(Every::with_id(
+                0x5054_5354_4745,
+                STAGE_TICK_INTERVAL,
+                || Msg::StageTick,
+            )));
         }
+        subscriptions
     }
 }
 
@@ -1320,6 +1702,7 @@ pub fn run_ftui(app: App, config: ProgramConfig) -> TuiResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tui::widgets::SortColumn;
 
     #[test]
     fn test_app_new() {
@@ -1550,6 +1933,13 @@ mod tests {
             top_evidence: vec![],
             confidence: None,
             plan_preview: vec![],
+            blast_radius: None,
+            io_rate: None,
+            user: None,
+            category: None,
+            runtime_secs: 0,
+            memory_bytes: 0,
+            snoozed: false,
         }
     }
 
@@ -1562,6 +1952,130 @@ mod tests {
         assert_eq!(app.process_table.rows[0].pid, 42);
     }
 
+    #[test]
+    fn test_telemetry_sampling_records_and_prunes() {
+        let mut app = App::new();
+        app.set_sample_op(Arc::new(|pids: Vec<u32>| {
+            pids.into_iter()
+                .map(|pid| TelemetrySample {
+                    pid,
+                    cpu_percent: 5.0,
+                    rss_bytes: 1024,
+                })
+                .collect()
+        }));
+        let subs = <App as FtuiModel>::subscriptions(&app);
+        assert!(subs.iter().any(|sub| sub.id() == 0x5054_5350_4B4C));
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::ProcessesScanned(vec![make_row(42), make_row(43)]),
+        );
+        let sample = |pid| TelemetrySample {
+            pid,
+            cpu_percent: 5.0,
+            rss_bytes: 1024,
+        };
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::TelemetrySampled(vec![sample(42), sample(43)]),
+        );
+        <App as FtuiModel>::update(&mut app, Msg::TelemetrySampled(vec![sample(42)]));
+        assert_eq!(app.telemetry().get(42).map(|t| t.len()), Some(2));
+        assert_eq!(app.telemetry().get(43).map(|t| t.len()), Some(1));
+
+        // History survives a refresh for processes still listed
+        <App as FtuiModel>::update(&mut app, Msg::RefreshComplete(Ok(vec![make_row(42)])));
+        assert_eq!(app.telemetry().get(42).map(|t| t.len()), Some(2));
+        assert!(app.telemetry().get(43).is_none());
+    }
+
+    #[test]
+    fn test_staged_kill_counts_down_then_executes() {
+        let mut app = App::new();
+        app.set_stage_op(Arc::new(|pids, _| Ok(pids)), Duration::ZERO);
+
+        <App as FtuiModel>::update(&mut app, Msg::StagePaused(Ok(vec![42, 43])));
+        assert_eq!(app.staged_pids(), Some(&[42, 43][..]));
+        let subs = <App as FtuiModel>::subscriptions(&app);
+        assert!(subs.iter().any(|sub| sub.id() == 0x5054_5354_4745));
+
+        // A second execute is refused while the first is pending
+        app.process_table.selected.insert(42);
+        <App as FtuiModel>::update(&mut app, Msg::RequestExecute);
+        assert!(app.staged_pids().is_some());
+
+        // Grace period over: the kill proceeds
+        <App as FtuiModel>::update(&mut app, Msg::StageTick);
+        assert!(app.staged_pids().is_none());
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|s| s.contains("2 process(es)")));
+    }
+
+    #[test]
+    fn test_staged_kill_undo_and_release_on_quit() {
+        use std::sync::Mutex;
+
+        let signals: Arc<Mutex<Vec<(Vec<u32>, StageSignal)>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&signals);
+        let mut app = App::new();
+        app.set_stage_op(
+            Arc::new(move |pids: Vec<u32>, signal| {
+                recorded.lock().unwrap().push((pids.clone(), signal));
+                Ok(pids)
+            }),
+            Duration::from_secs(60),
+        );
+
+        <App as FtuiModel>::update(&mut app, Msg::StagePaused(Ok(vec![7])));
+        <App as FtuiModel>::update(&mut app, Msg::StageTick);
+        assert_eq!(app.staged_pids(), Some(&[7][..]));
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|s| s.contains("U to undo")));
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::KeyPressed(FtuiKeyEvent::new(FtuiKeyCode::Char('U'))),
+        );
+        <App as FtuiModel>::update(&mut app, Msg::UndoStaged);
+        assert!(app.staged_pids().is_none());
+        <App as FtuiModel>::update(&mut app, Msg::StageResumed(Ok(vec![7])));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Undone: resumed 1 process(es)")
+        );
+
+        // Quitting with a kill pending resumes the targets synchronously
+        <App as FtuiModel>::update(&mut app, Msg::StagePaused(Ok(vec![8])));
+        <App as FtuiModel>::update(&mut app, Msg::Quit);
+        assert_eq!(
+            signals.lock().unwrap().as_slice(),
+            &[(vec![8], StageSignal::Continue)]
+        );
+    }
+
+    #[test]
+    fn test_selection_op_reports_changes_only() {
+        use std::sync::Mutex;
+
+        let mut app = App::new();
+        app.process_table.set_rows(vec![make_row(3), make_row(1)]);
+        let seen: Arc<Mutex<Vec<Vec<u32>>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        app.set_selection_op(Arc::new(move |pids| sink.lock().unwrap().push(pids)));
+
+        <App as FtuiModel>::update(&mut app, Msg::SelectAll);
+        <App as FtuiModel>::update(&mut app, Msg::Noop);
+        <App as FtuiModel>::update(&mut app, Msg::DeselectAll);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(*seen, vec![vec![1, 3], vec![]]);
+    }
+
     #[test]
     fn test_refresh_complete_ok() {
         let mut app = App::new();
@@ -1581,6 +2095,61 @@ mod tests {
         assert!(app.status_message.as_deref().unwrap().contains("failed"));
     }
 
+    #[test]
+    fn test_request_snooze_without_op_reports_unavailable() {
+        let mut app = App::new();
+        app.process_table.set_rows(vec![make_row(7)]);
+        <App as FtuiModel>::update(&mut app, Msg::RequestSnooze);
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .contains("not available"));
+    }
+
+    #[test]
+    fn test_snooze_complete_updates_status() {
+        let mut app = App::new();
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::SnoozeComplete(Ok("Snoozed PID 7".to_string())),
+        );
+        assert_eq!(app.status_message.as_deref(), Some("Snoozed PID 7"));
+
+        <App as FtuiModel>::update(&mut app, Msg::SnoozeComplete(Err("disk full".to_string())));
+        assert!(app.status_message.as_deref().unwrap().contains("disk full"));
+    }
+
+    #[test]
+    fn test_remember_key_uses_remember_op() {
+        let mut app = App::new();
+        app.process_table.set_rows(vec![make_row(9)]);
+        <App as FtuiModel>::update(&mut app, Msg::RequestRemember);
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .contains("not available"));
+
+        app.set_remember_op(Arc::new(|pid, _| Ok(format!("Spared PID {}", pid))));
+        <App as FtuiModel>::update(&mut app, Msg::RequestRemember);
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .contains("Remembering PID 9"));
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::RememberComplete(Err("read-only config".to_string())),
+        );
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .contains("read-only config"));
+    }
+
     #[test]
     fn test_execution_complete_ok_real_mode() {
         let mut app = App::new();
@@ -1683,6 +2252,39 @@ mod tests {
         assert_eq!(app.state, AppState::Normal);
     }
 
+    #[test]
+    fn test_sort_and_class_filter_keys() {
+        let mut app = App::new();
+        let mut small = make_row(1);
+        small.classification = "KILL".to_string();
+        small.memory_bytes = 10;
+        let mut large = make_row(2);
+        large.memory_bytes = 1000;
+        app.process_table.set_rows(vec![small, large]);
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::KeyPressed(FtuiKeyEvent::new(FtuiKeyCode::Char('o'))),
+        );
+        assert_eq!(app.process_table.sort_column, SortColumn::Memory);
+        assert_eq!(app.process_table.rows[0].pid, 2);
+        assert_eq!(app.status_message.as_deref(), Some("Sort: memory"));
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::KeyPressed(FtuiKeyEvent::new(FtuiKeyCode::Char('O'))),
+        );
+        assert_eq!(app.process_table.rows[0].pid, 1);
+
+        <App as FtuiModel>::update(
+            &mut app,
+            Msg::KeyPressed(FtuiKeyEvent::new(FtuiKeyCode::Char('c'))),
+        );
+        assert_eq!(app.process_table.class_filter, Some("KILL"));
+        assert_eq!(app.process_table.visible_rows().len(), 1);
+        assert_eq!(app.status_message.as_deref(), Some("Class filter: KILL"));
+    }
+
     #[test]
     fn test_key_event_help_escape_exits() {
         let mut app = App::new();
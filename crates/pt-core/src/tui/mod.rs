@@ -22,7 +22,9 @@
 //! - Search input with live filtering
 //! - Configuration editing via TUI forms
 //! - Evidence ledger visualization
+//! - Process detail pane with live CPU/RSS sparklines
 //! - Action confirmation dialogs
+//! - Staged kills: targets are stopped first and can be undone during a grace period
 //!
 //! # Module Structure
 //!
@@ -30,11 +32,13 @@
 //! - `widgets`: Custom widgets for the TUI
 //! - `theme`: Color schemes and styling
 //! - `events`: Event handling and key bindings
+//! - `telemetry`: Live CPU/RSS series for the detail pane
 
 mod app;
 mod events;
 pub mod layout;
 mod msg;
+pub mod telemetry;
 mod theme;
 pub mod widgets;
 
@@ -43,7 +47,8 @@ pub use events::{handle_event, AppAction, KeyBindings};
 pub use layout::{
     Breakpoint, DetailAreas, GalaxyBrainAreas, LayoutState, MainAreas, ResponsiveLayout,
 };
-pub use msg::{ExecutionOutcome, Msg};
+pub use msg::{ExecutionOutcome, Msg, StageSignal};
+pub use telemetry::{ProcessTelemetry, TelemetrySample, TelemetryStore};
 pub use theme::{Theme, ThemeMode};
 
 use thiserror::Error;
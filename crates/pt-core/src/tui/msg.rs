@@ -13,6 +13,7 @@ use std::path::PathBuf;
 
 use ftui::{Event, KeyEvent};
 
+use super::telemetry::TelemetrySample;
 use super::widgets::{DetailView, ProcessRow};
 
 /// Async execution summary returned to the update loop.
@@ -26,15 +27,30 @@ pub struct ExecutionOutcome {
     pub failed: usize,
 }
 
+/// Signal sent to the processes of a staged kill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageSignal {
+    /// Freeze the targets for the grace period (SIGSTOP).
+    Stop,
+    /// Undo: let the targets run again (SIGCONT).
+    Continue,
+}
+
 /// Single message type used by the ftui model update loop.
 #[derive(Debug, Clone)]
 pub enum Msg {
     // Input messages
     KeyPressed(KeyEvent),
-    Resized { width: u16, height: u16 },
+    Resized {
+        width: u16,
+        height: u16,
+    },
     Tick,
     FocusChanged(bool),
-    PasteReceived { text: String, bracketed: bool },
+    PasteReceived {
+        text: String,
+        bracketed: bool,
+    },
     ClipboardReceived(String),
     Noop,
 
@@ -75,13 +91,25 @@ pub enum Msg {
     ConfirmExecute,
     CancelExecute,
     RequestRefresh,
+    RequestSnooze,
+    RequestRemember,
     ExportEvidenceLedger,
+    SampleTelemetry,
+    StageTick,
+    UndoStaged,
 
     // Async result messages
     ProcessesScanned(Vec<ProcessRow>),
     ExecutionComplete(Result<ExecutionOutcome, String>),
     RefreshComplete(Result<Vec<ProcessRow>, String>),
     LedgerExported(Result<PathBuf, String>),
+    SnoozeComplete(Result<String, String>),
+    RememberComplete(Result<String, String>),
+    TelemetrySampled(Vec<TelemetrySample>),
+    /// PIDs stopped for a staged kill.
+    StagePaused(Result<Vec<u32>, String>),
+    /// PIDs resumed after an undo.
+    StageResumed(Result<Vec<u32>, String>),
 
     // Theme messages
     SwitchTheme(String),
@@ -160,5 +188,7 @@ mod tests {
         assert_send_static::<Result<ExecutionOutcome, String>>();
         assert_send_static::<Result<Vec<ProcessRow>, String>>();
         assert_send_static::<Result<PathBuf, String>>();
+        assert_send_static::<Vec<TelemetrySample>>();
+        assert_send_static::<Result<Vec<u32>, String>>();
     }
 }
@@ -0,0 +1,140 @@
+//! Live per-process telemetry for the detail pane.
+//!
+//! CPU and RSS are sampled for the listed candidates while the TUI runs and
+//! kept as short rolling series, rendered as text sparklines in the detail
+//! pane. Samples survive refreshes for as long as the PID stays listed.
+
+use std::collections::{HashMap, VecDeque};
+
+pub use crate::output::sparkline;
+
+/// Samples kept per process (two minutes at the default interval).
+pub const TELEMETRY_HISTORY: usize = 60;
+
+/// One CPU/RSS reading for a process.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TelemetrySample {
+    pub pid: u32,
+    /// CPU usage in percent of one core.
+    pub cpu_percent: f64,
+    /// Resident set size in bytes.
+    pub rss_bytes: u64,
+}
+
+/// Rolling CPU/RSS series for one process.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTelemetry {
+    cpu: VecDeque<f64>,
+    rss: VecDeque<u64>,
+}
+
+impl ProcessTelemetry {
+    fn push(&mut self, sample: &TelemetrySample) {
+        if self.cpu.len() == TELEMETRY_HISTORY {
+            self.cpu.pop_front();
+            self.rss.pop_front();
+        }
+        self.cpu.push_back(sample.cpu_percent.max(0.0));
+        self.rss.push_back(sample.rss_bytes);
+    }
+
+    /// Number of samples held.
+    pub fn len(&self) -> usize {
+        self.cpu.len()
+    }
+
+    /// Whether no samples have been taken yet.
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_empty()
+    }
+
+    /// CPU series, oldest first.
+    pub fn cpu(&self) -> impl Iterator<Item = f64> + '_ {
+        self.cpu.iter().copied()
+    }
+
+    /// RSS series, oldest first.
+    pub fn rss(&self) -> impl Iterator<Item = u64> + '_ {
+        self.rss.iter().copied()
+    }
+
+    /// Most recent CPU reading.
+    pub fn last_cpu(&self) -> Option<f64> {
+        self.cpu.back().copied()
+    }
+
+    /// Most recent RSS reading.
+    pub fn last_rss(&self) -> Option<u64> {
+        self.rss.back().copied()
+    }
+}
+
+/// Telemetry for every listed process, keyed by PID.
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryStore {
+    series: HashMap<u32, ProcessTelemetry>,
+}
+
+impl TelemetryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a batch of samples.
+    pub fn record(&mut self, samples: &[TelemetrySample]) {
+        for sample in samples {
+            self.series.entry(sample.pid).or_default().push(sample);
+        }
+    }
+
+    /// Series for `pid`, if any samples were taken.
+    pub fn get(&self, pid: u32) -> Option<&ProcessTelemetry> {
+        self.series.get(&pid).filter(|series| !series.is_empty())
+    }
+
+    /// Drop series for processes no longer listed.
+    pub fn retain_pids(&mut self, pids: &[u32]) {
+        self.series.retain(|pid, _| pids.contains(pid));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(pid: u32, cpu_percent: f64, rss_bytes: u64) -> TelemetrySample {
+        TelemetrySample {
+            pid,
+            cpu_percent,
+            rss_bytes,
+        }
+    }
+
+    #[test]
+    fn store_keeps_bounded_history_per_pid() {
+        let mut store = TelemetryStore::new();
+        for i in 0..(TELEMETRY_HISTORY + 5) {
+            store.record(&[sample(1, i as f64, i as u64), sample(2, 1.0, 10)]);
+        }
+        let series = store.get(1).unwrap();
+        assert_eq!(series.len(), TELEMETRY_HISTORY);
+        assert_eq!(series.cpu().next(), Some(5.0));
+        assert_eq!(series.last_rss(), Some((TELEMETRY_HISTORY + 4) as u64));
+
+        store.retain_pids(&[2]);
+        assert!(store.get(1).is_none());
+        assert!(store.get(2).is_some());
+    }
+
+    #[test]
+    fn sparkline_scales_to_window_max() {
+        assert_eq!(
+            sparkline(&[0.0, 50.0, 100.0], 10),
+            "\u{2581}\u{2585}\u{2588}"
+        );
+        assert_eq!(sparkline(&[0.0, 0.0], 10), "\u{2581}\u{2581}");
+        // Only the most recent values fit
+        assert_eq!(sparkline(&[100.0, 1.0, 2.0], 2), "\u{2585}\u{2588}");
+        assert_eq!(sparkline(&[], 4), "");
+    }
+}
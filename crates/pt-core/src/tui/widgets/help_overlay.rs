@@ -120,6 +120,22 @@ const ACTIONS: &[Binding] = &[
         key: "v",
         desc: "Toggle goal view",
     },
+    Binding {
+        key: "o / O",
+        desc: "Cycle/reverse sort",
+    },
+    Binding {
+        key: "c",
+        desc: "Cycle class filter",
+    },
+    Binding {
+        key: "U",
+        desc: "Undo staged kill",
+    },
+    Binding {
+        key: "Z",
+        desc: "Never suggest this again",
+    },
 ];
 
 const GENERAL: &[Binding] = &[
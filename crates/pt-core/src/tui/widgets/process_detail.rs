@@ -1,6 +1,8 @@
 //! Detail pane widget for a selected process.
 //!
-//! Uses ftui's Paragraph and layout primitives for rendering.
+//! Uses ftui's Paragraph and layout primitives for rendering. When telemetry
+//! has been sampled for the process, the stats section grows CPU and RSS
+//! sparklines covering the session so far.
 
 use ftui::layout::Constraint as FtuiConstraint;
 use ftui::layout::Flex;
@@ -11,6 +13,7 @@ use ftui::widgets::Widget as FtuiWidget;
 use ftui::PackedRgba;
 use ftui::Style as FtuiStyle;
 
+use crate::tui::telemetry::{sparkline, ProcessTelemetry, TELEMETRY_HISTORY};
 use crate::tui::theme::Theme;
 use crate::tui::widgets::ProcessRow;
 
@@ -28,6 +31,7 @@ pub struct ProcessDetail<'a> {
     row: Option<&'a ProcessRow>,
     selected: bool,
     view: DetailView,
+    telemetry: Option<&'a ProcessTelemetry>,
 }
 
 impl<'a> Default for ProcessDetail<'a> {
@@ -44,6 +48,7 @@ impl<'a> ProcessDetail<'a> {
             row: None,
             selected: false,
             view: DetailView::Summary,
+            telemetry: None,
         }
     }
 
@@ -66,6 +71,12 @@ impl<'a> ProcessDetail<'a> {
         self
     }
 
+    /// Set the sampled CPU/RSS series for the selected process.
+    pub fn telemetry(mut self, telemetry: Option<&'a ProcessTelemetry>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
     // ── ftui style helpers ──────────────────────────────────────────
 
     fn classification_ftui_style(&self, classification: &str) -> FtuiStyle {
@@ -128,12 +139,17 @@ impl<'a> ProcessDetail<'a> {
             return;
         };
 
+        let stats_lines = self.build_stats_lines(row, inner.width as usize);
+        // Keep a blank separator below the stats; never shrink below the
+        // original two-line layout.
+        let stats_height = (stats_lines.len() as u16 + 1).max(4);
+
         let sections = Flex::vertical()
             .constraints([
-                FtuiConstraint::Fixed(4), // Header
-                FtuiConstraint::Fixed(4), // Stats
-                FtuiConstraint::Min(4),   // Evidence placeholder
-                FtuiConstraint::Fixed(3), // Action placeholder
+                FtuiConstraint::Fixed(4),            // Header
+                FtuiConstraint::Fixed(stats_height), // Stats
+                FtuiConstraint::Min(4),              // Evidence placeholder
+                FtuiConstraint::Fixed(3),            // Action placeholder
             ])
             .split(inner);
 
@@ -162,22 +178,6 @@ impl<'a> ProcessDetail<'a> {
             ]),
         ];
 
-        // ── Stats section ───────────────────────────────────────────
-
-        let stats_lines: Vec<FtuiLine> = vec![
-            FtuiLine::from_spans([
-                FtuiSpan::styled("Score: ", self.label_ftui_style()),
-                FtuiSpan::styled(row.score.to_string(), self.value_ftui_style()),
-                FtuiSpan::styled("  ", self.value_ftui_style()),
-                FtuiSpan::styled("Runtime: ", self.label_ftui_style()),
-                FtuiSpan::styled(row.runtime.clone(), self.value_ftui_style()),
-            ]),
-            FtuiLine::from_spans([
-                FtuiSpan::styled("Memory: ", self.label_ftui_style()),
-                FtuiSpan::styled(row.memory.clone(), self.value_ftui_style()),
-            ]),
-        ];
-
         // ── View-dependent sections ─────────────────────────────────
 
         let evidence_height = sections[2].height.max(1) as usize;
@@ -219,6 +219,62 @@ impl<'a> ProcessDetail<'a> {
         );
     }
 
+    /// Score/runtime, memory, blast radius, I/O rate and telemetry sparklines.
+    fn build_stats_lines(&self, row: &ProcessRow, width: usize) -> Vec<FtuiLine> {
+        let mut lines = vec![
+            FtuiLine::from_spans([
+                FtuiSpan::styled("Score: ", self.label_ftui_style()),
+                FtuiSpan::styled(row.score.to_string(), self.value_ftui_style()),
+                FtuiSpan::styled("  ", self.value_ftui_style()),
+                FtuiSpan::styled("Runtime: ", self.label_ftui_style()),
+                FtuiSpan::styled(row.runtime.clone(), self.value_ftui_style()),
+            ]),
+            FtuiLine::from_spans([
+                FtuiSpan::styled("Memory: ", self.label_ftui_style()),
+                FtuiSpan::styled(row.memory.clone(), self.value_ftui_style()),
+            ]),
+        ];
+
+        if let Some(blast) = row.blast_radius.as_ref() {
+            lines.push(FtuiLine::from_spans([
+                FtuiSpan::styled("Blast: ", self.label_ftui_style()),
+                FtuiSpan::styled(blast.clone(), self.value_ftui_style()),
+            ]));
+        }
+
+        if let Some(io_rate) = row.io_rate.as_ref() {
+            lines.push(FtuiLine::from_spans([
+                FtuiSpan::styled("I/O: ", self.label_ftui_style()),
+                FtuiSpan::styled(io_rate.clone(), self.value_ftui_style()),
+            ]));
+        }
+
+        if let Some(telemetry) = self.telemetry {
+            // "CPU " + sparkline + " 100.0%" / " 1023.9 MB"
+            let spark_width = width.saturating_sub(15).clamp(1, TELEMETRY_HISTORY);
+            let cpu: Vec<f64> = telemetry.cpu().collect();
+            let rss: Vec<f64> = telemetry.rss().map(|bytes| bytes as f64).collect();
+            lines.push(FtuiLine::from_spans([
+                FtuiSpan::styled("CPU ", self.label_ftui_style()),
+                FtuiSpan::styled(sparkline(&cpu, spark_width), self.value_ftui_style()),
+                FtuiSpan::styled(
+                    format!(" {:.1}%", telemetry.last_cpu().unwrap_or(0.0)),
+                    self.value_ftui_style(),
+                ),
+            ]));
+            lines.push(FtuiLine::from_spans([
+                FtuiSpan::styled("RSS ", self.label_ftui_style()),
+                FtuiSpan::styled(sparkline(&rss, spark_width), self.value_ftui_style()),
+                FtuiSpan::styled(
+                    format!(" {}", format_rss(telemetry.last_rss().unwrap_or(0))),
+                    self.value_ftui_style(),
+                ),
+            ]));
+        }
+
+        lines
+    }
+
     fn build_summary_sections(
         &self,
         row: &ProcessRow,
@@ -364,6 +420,17 @@ impl<'a> ProcessDetail<'a> {
     }
 }
 
+/// RSS in MB below 1 GiB, GB above.
+fn format_rss(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MIB;
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -389,6 +456,13 @@ mod tests {
             ],
             confidence: Some("high".to_string()),
             plan_preview: Vec::new(),
+            blast_radius: None,
+            io_rate: None,
+            user: None,
+            category: None,
+            runtime_secs: 0,
+            memory_bytes: 0,
+            snoozed: false,
         }
     }
 
@@ -542,6 +616,29 @@ mod tests {
         assert!(evidence.len() >= 3);
     }
 
+    #[test]
+    fn build_stats_lines_grow_with_telemetry() {
+        use crate::tui::telemetry::{TelemetrySample, TelemetryStore};
+
+        let mut row = sample_row();
+        let d = ProcessDetail::new();
+        assert_eq!(d.build_stats_lines(&row, 40).len(), 2);
+
+        row.blast_radius = Some("low risk; 2 children".to_string());
+        let mut store = TelemetryStore::new();
+        store.record(&[TelemetrySample {
+            pid: row.pid,
+            cpu_percent: 12.5,
+            rss_bytes: 1536 * 1024 * 1024,
+        }]);
+        let d = ProcessDetail::new().telemetry(store.get(row.pid));
+        assert_eq!(d.build_stats_lines(&row, 40).len(), 5);
+        row.io_rate = Some("read 0 B/s, write 1.0 MiB/s".to_string());
+        assert_eq!(d.build_stats_lines(&row, 40).len(), 6);
+        assert_eq!(format_rss(1536 * 1024 * 1024), "1.5 GB");
+        assert_eq!(format_rss(512 * 1024 * 1024), "512.0 MB");
+    }
+
     #[test]
     fn build_genealogy_sections() {
         let d = ProcessDetail::new();
@@ -57,6 +57,83 @@ pub enum ViewMode {
     GoalFirst,
 }
 
+/// Classifications cycled by the class filter, in order.
+const CLASS_FILTERS: [&str; 3] = ["KILL", "REVIEW", "SPARE"];
+
+/// Parsed filter query for the process table.
+///
+/// Whitespace-separated terms; `user:`, `cat:` and `class:` terms filter on
+/// those fields, anything else must match the PID, the classification, or
+/// fuzzily match the command (`ndsrv` matches `node dev server`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RowQuery {
+    /// Free-text terms (lowercase).
+    pub terms: Vec<String>,
+    /// User name substring.
+    pub user: Option<String>,
+    /// Category substring.
+    pub category: Option<String>,
+    /// Classification prefix.
+    pub class: Option<String>,
+}
+
+impl RowQuery {
+    /// Parse a filter query.
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for term in query.split_whitespace() {
+            let term = term.to_lowercase();
+            match term.split_once(':') {
+                Some(("user" | "u", value)) if !value.is_empty() => {
+                    parsed.user = Some(value.to_string())
+                }
+                Some(("cat" | "category", value)) if !value.is_empty() => {
+                    parsed.category = Some(value.to_string())
+                }
+                Some(("class", value)) if !value.is_empty() => {
+                    parsed.class = Some(value.to_string())
+                }
+                _ => parsed.terms.push(term),
+            }
+        }
+        parsed
+    }
+
+    /// Whether `row` satisfies every term.
+    pub fn matches(&self, row: &ProcessRow) -> bool {
+        let field_matches = |field: &Option<String>, needle: &Option<String>| match needle {
+            Some(needle) => field
+                .as_deref()
+                .is_some_and(|value| value.to_lowercase().contains(needle.as_str())),
+            None => true,
+        };
+        if !field_matches(&row.user, &self.user) || !field_matches(&row.category, &self.category) {
+            return false;
+        }
+        if let Some(class) = &self.class {
+            if !row
+                .classification
+                .to_lowercase()
+                .starts_with(class.as_str())
+            {
+                return false;
+            }
+        }
+        let command = row.command.to_lowercase();
+        self.terms.iter().all(|term| {
+            row.pid.to_string().contains(term.as_str())
+                || row.classification.to_lowercase().contains(term.as_str())
+                || fuzzy_match(term, &command)
+        })
+    }
+}
+
+/// Whether the characters of `needle` appear in `haystack` in order.
+pub fn fuzzy_match(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
 /// A process row for display in the table.
 #[derive(Debug, Clone)]
 pub struct ProcessRow {
@@ -84,6 +161,20 @@ pub struct ProcessRow {
     pub confidence: Option<String>,
     /// Preview lines for the planned actions (stage/prechecks/confidence).
     pub plan_preview: Vec<String>,
+    /// One-line blast radius summary (risk level, children, handles).
+    pub blast_radius: Option<String>,
+    /// Storage I/O rate (read / write per second), when sampled.
+    pub io_rate: Option<String>,
+    /// Owning user name.
+    pub user: Option<String>,
+    /// Command category (e.g. "test runner", "server").
+    pub category: Option<String>,
+    /// Runtime in seconds (sort key for `runtime`).
+    pub runtime_secs: u64,
+    /// Resident memory in bytes (sort key for `memory`).
+    pub memory_bytes: u64,
+    /// Whether the user snoozed or pinned this candidate (sorted last, never auto-selected).
+    pub snoozed: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -142,16 +233,20 @@ impl<'a> ProcessTable<'a> {
             ViewMode::SuspicionFirst => "score",
             ViewMode::GoalFirst => "goal",
         };
+        let class_label = state
+            .class_filter
+            .map(|class| format!(" [class: {}]", class))
+            .unwrap_or_default();
 
         if selected_count > 0 {
             format!(
-                " Processes [{}/{} selected] [view: {}] [Space: toggle, a: rec, A: all, u: clear, x: invert, e: execute] ",
-                selected_count, total_count, view_label
+                " Processes [{}/{} selected] [view: {}]{} [Space: toggle, a: rec, A: all, u: clear, x: invert, e: execute] ",
+                selected_count, total_count, view_label, class_label
             )
         } else {
             format!(
-                " Processes [{}] [view: {}] [Space: toggle, a: rec, A: all, u: clear, x: invert, e: execute] ",
-                total_count, view_label
+                " Processes [{}] [view: {}]{} [Space: toggle, a: rec, A: all, u: clear, x: invert, e: execute] ",
+                total_count, view_label, class_label
             )
         }
     }
@@ -327,9 +422,14 @@ impl<'a> ProcessTable<'a> {
                     cells.push(FtuiText::raw(row.score.to_string()));
                 }
 
-                // Classification (styled)
+                // Classification (styled); snoozed rows carry a "z" marker
+                let class_label = if row.snoozed {
+                    format!("{} z", row.classification)
+                } else {
+                    row.classification.clone()
+                };
                 cells.push(FtuiText::from_line(FtuiLine::from_spans([
-                    FtuiSpan::styled(row.classification.clone(), class_style),
+                    FtuiSpan::styled(class_label, class_style),
                 ])));
 
                 // Runtime
@@ -526,6 +626,10 @@ pub struct ProcessTableState {
     pub sort_order: SortOrder,
     /// Current filter query (lowercase).
     pub filter: Option<String>,
+    /// Parsed form of `filter`.
+    query: RowQuery,
+    /// Classification filter applied on top of the query (`c` cycles it).
+    pub class_filter: Option<&'static str>,
     /// Current view mode (score vs goal ordering).
     pub view_mode: ViewMode,
     /// Optional goal-based ordering (pid -> rank).
@@ -550,6 +654,8 @@ impl ProcessTableState {
             sort_column: SortColumn::Score,
             sort_order: SortOrder::Descending,
             filter: None,
+            query: RowQuery::default(),
+            class_filter: None,
             view_mode: ViewMode::SuspicionFirst,
             goal_rank: None,
         }
@@ -621,25 +727,36 @@ impl ProcessTableState {
 
     /// Set the filter query.
     pub fn set_filter(&mut self, filter: Option<String>) {
+        self.query = filter.as_deref().map(RowQuery::parse).unwrap_or_default();
         self.filter = filter;
         self.cursor = 0;
         self.scroll_offset = 0;
     }
 
+    /// Advance the classification filter: all → KILL → REVIEW → SPARE → all.
+    pub fn cycle_class_filter(&mut self) {
+        self.class_filter = match self.class_filter {
+            None => Some(CLASS_FILTERS[0]),
+            Some(current) => CLASS_FILTERS
+                .iter()
+                .position(|class| *class == current)
+                .and_then(|i| CLASS_FILTERS.get(i + 1))
+                .copied(),
+        };
+        self.cursor = 0;
+        self.scroll_offset = 0;
+    }
+
     /// Get visible rows (after filtering).
     pub fn visible_rows(&self) -> Vec<&ProcessRow> {
-        if let Some(ref filter) = self.filter {
-            self.rows
-                .iter()
-                .filter(|r| {
-                    r.command.to_lowercase().contains(filter)
-                        || r.classification.to_lowercase().contains(filter)
-                        || r.pid.to_string().contains(filter)
-                })
-                .collect()
-        } else {
-            self.rows.iter().collect()
-        }
+        self.rows
+            .iter()
+            .filter(|r| {
+                self.class_filter
+                    .is_none_or(|class| r.classification.eq_ignore_ascii_case(class))
+            })
+            .filter(|r| self.query.matches(r))
+            .collect()
     }
 
     /// Get the currently focused row (after filtering).
@@ -730,7 +847,7 @@ impl ProcessTableState {
         let pids: Vec<u32> = self
             .visible_rows()
             .iter()
-            .filter(|row| row.classification.eq_ignore_ascii_case("KILL"))
+            .filter(|row| !row.snoozed && row.classification.eq_ignore_ascii_case("KILL"))
             .map(|row| row.pid)
             .collect();
         for pid in pids {
@@ -791,10 +908,45 @@ impl ProcessTableState {
         self.sort();
     }
 
+    /// Advance the sort column: score → memory → runtime → score.
+    ///
+    /// Each column starts descending (highest score, most memory, longest
+    /// running first).
+    pub fn cycle_sort(&mut self) {
+        let next = match self.sort_column {
+            SortColumn::Score => SortColumn::Memory,
+            SortColumn::Memory => SortColumn::Runtime,
+            _ => SortColumn::Score,
+        };
+        self.set_sort(next, SortOrder::Descending);
+    }
+
+    /// Reverse the current sort order.
+    pub fn reverse_sort(&mut self) {
+        let column = self.sort_column;
+        self.toggle_sort(column);
+    }
+
+    /// Human-readable label for the current sort column.
+    pub fn sort_label(&self) -> &'static str {
+        match self.sort_column {
+            SortColumn::Pid => "pid",
+            SortColumn::Score => "score",
+            SortColumn::Classification => "class",
+            SortColumn::Runtime => "runtime",
+            SortColumn::Memory => "memory",
+            SortColumn::Command => "command",
+        }
+    }
+
     /// Sort rows by current column and order.
     fn sort(&mut self) {
         let order = self.sort_order;
         self.rows.sort_by(|a, b| {
+            // Snoozed rows always sink below active candidates.
+            if a.snoozed != b.snoozed {
+                return a.snoozed.cmp(&b.snoozed);
+            }
             if self.view_mode == ViewMode::GoalFirst {
                 if let Some(ranks) = self.goal_rank.as_ref() {
                     let ra = ranks.get(&a.pid).copied().unwrap_or(usize::MAX);
@@ -809,8 +961,8 @@ impl ProcessTableState {
                 SortColumn::Pid => a.pid.cmp(&b.pid),
                 SortColumn::Score => a.score.cmp(&b.score),
                 SortColumn::Classification => a.classification.cmp(&b.classification),
-                SortColumn::Runtime => a.runtime.cmp(&b.runtime),
-                SortColumn::Memory => a.memory.cmp(&b.memory),
+                SortColumn::Runtime => a.runtime_secs.cmp(&b.runtime_secs),
+                SortColumn::Memory => a.memory_bytes.cmp(&b.memory_bytes),
                 SortColumn::Command => a.command.cmp(&b.command),
             };
             match order {
@@ -873,6 +1025,9 @@ fn routing_label(routing: &ActionRouting) -> &'static str {
         ActionRouting::ZombieToSupervisor => "zombie_to_supervisor",
         ActionRouting::ZombieInvestigateOnly => "zombie_investigate_only",
         ActionRouting::DStateLowConfidence => "d_state_low_confidence",
+        ActionRouting::ProcessGroup => "process_group",
+        ActionRouting::Cascade => "cascade",
+        ActionRouting::SystemdUnit => "systemd_unit",
     }
 }
 
@@ -893,6 +1048,9 @@ fn precheck_label(check: &PreCheck) -> &'static str {
         PreCheck::CheckSupervisor => "check_supervisor",
         PreCheck::CheckAgentSupervision => "check_agent_supervision",
         PreCheck::VerifyProcessState => "verify_process_state",
+        PreCheck::CheckFreezeScope => "check_freeze_scope",
+        PreCheck::VerifyUnitMapping => "verify_unit_mapping",
+        PreCheck::CheckForegroundWork => "check_foreground_work",
     }
 }
 
@@ -919,6 +1077,13 @@ mod tests {
                 top_evidence: vec!["runtime (2.4 bits toward abandoned)".to_string()],
                 confidence: Some("high".to_string()),
                 plan_preview: Vec::new(),
+                blast_radius: None,
+                io_rate: None,
+                user: Some("alice".to_string()),
+                category: Some("test runner".to_string()),
+                runtime_secs: 9000,
+                memory_bytes: 512 * 1024 * 1024,
+                snoozed: false,
             },
             ProcessRow {
                 pid: 5678,
@@ -933,6 +1098,13 @@ mod tests {
                 top_evidence: Vec::new(),
                 confidence: Some("medium".to_string()),
                 plan_preview: Vec::new(),
+                blast_radius: None,
+                io_rate: None,
+                user: Some("bob".to_string()),
+                category: Some("server".to_string()),
+                runtime_secs: 4500,
+                memory_bytes: 256 * 1024 * 1024,
+                snoozed: false,
             },
             ProcessRow {
                 pid: 9012,
@@ -947,6 +1119,13 @@ mod tests {
                 top_evidence: Vec::new(),
                 confidence: Some("low".to_string()),
                 plan_preview: Vec::new(),
+                blast_radius: None,
+                io_rate: None,
+                user: Some("alice".to_string()),
+                category: Some("build".to_string()),
+                runtime_secs: 1800,
+                memory_bytes: 128 * 1024 * 1024,
+                snoozed: false,
             },
         ]
     }
@@ -1083,6 +1262,86 @@ mod tests {
         assert_eq!(state.rows[0].pid, 9012); // Score 15 now first
     }
 
+    #[test]
+    fn test_query_qualifiers_and_fuzzy_command() {
+        let mut state = ProcessTableState::new();
+        state.set_rows(sample_rows());
+
+        // Fuzzy: characters in order, not necessarily adjacent
+        state.set_filter(Some("jstwrk".to_string()));
+        assert_eq!(state.visible_rows().len(), 1);
+        assert_eq!(state.visible_rows()[0].pid, 1234);
+
+        state.set_filter(Some("user:alice".to_string()));
+        assert_eq!(state.visible_rows().len(), 2);
+
+        state.set_filter(Some("user:alice cat:build".to_string()));
+        assert_eq!(state.visible_rows().len(), 1);
+        assert_eq!(state.visible_rows()[0].pid, 9012);
+
+        state.set_filter(Some("class:rev".to_string()));
+        assert_eq!(state.visible_rows()[0].pid, 5678);
+        assert_eq!(state.visible_rows().len(), 1);
+
+        assert_eq!(
+            RowQuery::parse("u:bob node"),
+            RowQuery {
+                terms: vec!["node".to_string()],
+                user: Some("bob".to_string()),
+                ..RowQuery::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_class_filter_cycles_and_combines_with_query() {
+        let mut state = ProcessTableState::new();
+        state.set_rows(sample_rows());
+
+        state.cycle_class_filter();
+        assert_eq!(state.class_filter, Some("KILL"));
+        assert_eq!(state.visible_rows().len(), 1);
+
+        state.set_filter(Some("user:bob".to_string()));
+        assert!(state.visible_rows().is_empty());
+
+        state.cycle_class_filter();
+        assert_eq!(state.class_filter, Some("REVIEW"));
+        assert_eq!(state.visible_rows()[0].pid, 5678);
+
+        state.cycle_class_filter();
+        state.cycle_class_filter();
+        assert_eq!(state.class_filter, None);
+        assert_eq!(state.visible_rows().len(), 1);
+    }
+
+    #[test]
+    fn test_cycle_sort_uses_numeric_keys() {
+        let mut state = ProcessTableState::new();
+        let mut rows = sample_rows();
+        // "2 GB" sorts below "512 MB" as text
+        rows[2].memory = "2 GB".to_string();
+        rows[2].memory_bytes = 2 * 1024 * 1024 * 1024;
+        state.set_rows(rows);
+
+        state.cycle_sort();
+        assert_eq!(state.sort_column, SortColumn::Memory);
+        assert_eq!(state.sort_label(), "memory");
+        assert_eq!(state.rows[0].pid, 9012);
+
+        state.cycle_sort();
+        assert_eq!(state.sort_column, SortColumn::Runtime);
+        assert_eq!(state.rows[0].pid, 1234);
+
+        state.reverse_sort();
+        assert_eq!(state.sort_order, SortOrder::Ascending);
+        assert_eq!(state.rows[0].pid, 9012);
+
+        state.cycle_sort();
+        assert_eq!(state.sort_column, SortColumn::Score);
+        assert_eq!(state.sort_order, SortOrder::Descending);
+    }
+
     // ── Selection persistence tests ──────────────────────────────────
 
     #[test]
@@ -1208,6 +1467,18 @@ mod tests {
         assert!(!state.selected.contains(&9012));
     }
 
+    #[test]
+    fn test_snoozed_rows_sort_last_and_skip_recommended() {
+        let mut rows = sample_rows();
+        rows[0].snoozed = true;
+        let mut state = ProcessTableState::new();
+        state.set_rows(rows);
+
+        assert_eq!(state.rows.last().map(|r| r.pid), Some(1234));
+        state.select_recommended();
+        assert!(state.selected.is_empty());
+    }
+
     // ── Sort edge cases ───────────────────────────────────────────────
 
     #[test]
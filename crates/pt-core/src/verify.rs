@@ -3,7 +3,8 @@
 //! Verifies action outcomes by comparing plan candidates against a fresh scan.
 //! Intended for `pt-core agent verify`.
 
-use crate::collect::{ProcessRecord, ProcessState};
+use crate::collect::{LaunchdJob, ProcessRecord, ProcessState};
+use crate::decision::respawn_loop::{respawn_identity_key, RespawnEvent};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -21,9 +22,9 @@ pub struct AgentPlan {
 pub struct PlanCandidate {
     pub pid: u32,
     pub uid: u32,
-    #[serde(default)]
+    #[serde(default, alias = "command_short")]
     pub cmd_short: String,
-    #[serde(default, rename = "cmd_full")]
+    #[serde(default, rename = "cmd_full", alias = "command")]
     pub cmd_full: String,
     #[serde(default)]
     pub start_id: Option<String>,
@@ -31,6 +32,27 @@ pub struct PlanCandidate {
     pub recommended_action: String,
     #[serde(default)]
     pub blast_radius: Option<BlastRadius>,
+    #[serde(default)]
+    pub supervisor: Option<CandidateSupervisor>,
+    #[serde(default)]
+    pub signature: Option<CandidateSignature>,
+}
+
+/// Supervisor details recorded for a candidate at plan time.
+#[derive(Debug, Deserialize, Default)]
+pub struct CandidateSupervisor {
+    /// Supervisor unit, job or container the process runs under.
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub launchd: Option<LaunchdJob>,
+}
+
+/// Signature match recorded for a candidate at plan time.
+#[derive(Debug, Deserialize, Default)]
+pub struct CandidateSignature {
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -123,6 +145,16 @@ pub struct RespawnDetected {
     pub start_time_unix: Option<i64>,
 }
 
+/// A launchd job that is running a new instance after its planned kill.
+#[derive(Debug, Serialize)]
+pub struct LaunchdRespawn {
+    pub pid: u32,
+    pub new_pid: u32,
+    pub label: String,
+    pub domain: String,
+    pub keep_alive: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ResourceSummary {
     pub memory_freed_mb: f64,
@@ -357,6 +389,74 @@ pub fn verify_plan(
     }
 }
 
+/// Killed launchd jobs that launchd has started again.
+///
+/// `job_pid` returns the current PID of a job; a PID other than the
+/// planned target means launchd restarted it, whatever its command line.
+pub fn launchd_respawns(
+    plan: &AgentPlan,
+    job_pid: impl Fn(&LaunchdJob) -> Option<u32>,
+) -> Vec<LaunchdRespawn> {
+    plan.candidates
+        .iter()
+        .filter(|c| matches!(c.recommended_action.as_str(), "kill" | "terminate"))
+        .filter_map(|c| {
+            let job = c.supervisor.as_ref()?.launchd.as_ref()?;
+            let new_pid = job_pid(job).filter(|pid| *pid != c.pid)?;
+            Some(LaunchdRespawn {
+                pid: c.pid,
+                new_pid,
+                label: job.label.clone(),
+                domain: job.domain.clone(),
+                keep_alive: job.keep_alive,
+            })
+        })
+        .collect()
+}
+
+/// Respawn events for the kills in `report` that came back.
+///
+/// `kill_times` maps PIDs to when they were killed (epoch seconds); PIDs
+/// without an entry fall back to the plan's generation time.
+pub fn respawn_events(
+    plan: &AgentPlan,
+    report: &VerificationReport,
+    kill_times: &HashMap<u32, f64>,
+) -> Vec<RespawnEvent> {
+    let plan_ts = plan
+        .generated_at
+        .as_ref()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.timestamp() as f64);
+    report
+        .action_outcomes
+        .iter()
+        .filter_map(|outcome| {
+            let respawn = outcome.respawn_detected.as_ref()?;
+            let candidate = plan
+                .candidates
+                .iter()
+                .find(|c| c.pid == outcome.target.pid)?;
+            let kill_ts = kill_times.get(&candidate.pid).copied().or(plan_ts)?;
+            let respawn_ts = respawn.start_time_unix? as f64;
+            let supervisor = candidate.supervisor.as_ref().and_then(|s| s.unit.clone());
+            Some(RespawnEvent {
+                identity_key: respawn_identity_key(
+                    candidate.signature.as_ref().and_then(|s| s.name.as_deref()),
+                    candidate_command(candidate),
+                    supervisor.as_deref(),
+                ),
+                supervisor_unit: supervisor,
+                cgroup: None,
+                kill_ts,
+                respawn_ts,
+                respawn_delay_secs: (respawn_ts - kill_ts).max(0.0),
+                session_id: Some(plan.session_id.clone()),
+            })
+        })
+        .collect()
+}
+
 fn normalize_cmd(cmd: &str) -> String {
     cmd.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -398,6 +498,15 @@ fn parse_plan_start_id(raw: &str) -> PlanStartId {
     PlanStartId::Unknown
 }
 
+/// PID embedded in a plan `start_id`, if the start_id is in a format
+/// [`verify_plan`] can use to detect PID reuse.
+pub fn start_id_pid(raw: &str) -> Option<u32> {
+    match parse_plan_start_id(raw) {
+        PlanStartId::Legacy { pid, .. } | PlanStartId::Full { pid, .. } => Some(pid),
+        PlanStartId::Unknown => None,
+    }
+}
+
 fn start_id_matches(parsed: PlanStartId, proc: &ProcessRecord) -> bool {
     match parsed {
         PlanStartId::Legacy { pid, start_time } => {
@@ -476,6 +585,7 @@ mod tests {
             elapsed: Duration::from_secs(60),
             source: "test".to_string(),
             container_info: None,
+            io_rate: None,
         }
     }
 
@@ -508,6 +618,8 @@ mod tests {
                     memory_mb: 100.0,
                     cpu_pct: 1.0,
                 }),
+                supervisor: None,
+                signature: None,
             }],
         };
 
@@ -522,6 +634,39 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn respawn_events_from_agent_plan_json() {
+        let plan = parse_agent_plan(
+            r#"{
+                "session_id": "pt-20260115-000000-abcd",
+                "generated_at": "1970-01-01T00:00:10Z",
+                "candidates": [{
+                    "pid": 123, "uid": 1000,
+                    "command": "celery worker -A app", "command_short": "celery",
+                    "start_id": "123:5", "recommended_action": "kill",
+                    "signature": {"matched": true, "name": "celery-worker"},
+                    "supervisor": {"detected": true, "type": "systemd", "unit": "celery.service"}
+                }]
+            }"#,
+        )
+        .unwrap();
+        let current = vec![make_proc(
+            456,
+            1000,
+            "celery worker -A app",
+            14,
+            ProcessState::Running,
+        )];
+        let report = verify_plan(&plan, &current, Utc::now(), Utc::now());
+
+        let kill_times = HashMap::from([(123, 12.0)]);
+        let events = respawn_events(&plan, &report, &kill_times);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].identity_key, "sig:celery-worker@celery.service");
+        assert_eq!(events[0].supervisor_unit.as_deref(), Some("celery.service"));
+        assert_eq!(events[0].respawn_delay_secs, 2.0);
+    }
+
     #[test]
     fn verify_detects_pid_reuse_with_non_uuid_start_id() {
         let plan = AgentPlan {
@@ -535,6 +680,8 @@ mod tests {
                 start_id: Some("unknown:100:321".to_string()),
                 recommended_action: "kill".to_string(),
                 blast_radius: None,
+                supervisor: None,
+                signature: None,
             }],
         };
 
@@ -657,6 +804,8 @@ mod tests {
             start_id: None,
             recommended_action: "kill".to_string(),
             blast_radius: None,
+            supervisor: None,
+            signature: None,
         };
         assert_eq!(candidate_command(&c), "node server.js");
     }
@@ -671,6 +820,8 @@ mod tests {
             start_id: None,
             recommended_action: "kill".to_string(),
             blast_radius: None,
+            supervisor: None,
+            signature: None,
         };
         assert_eq!(candidate_command(&c), "node");
     }
@@ -685,6 +836,8 @@ mod tests {
             start_id: None,
             recommended_action: "kill".to_string(),
             blast_radius: None,
+            supervisor: None,
+            signature: None,
         };
         assert_eq!(candidate_command(&c), "");
     }
@@ -1070,9 +1223,53 @@ mod tests {
                 memory_mb: 100.0,
                 cpu_pct: 2.0,
             }),
+            supervisor: None,
+            signature: None,
         }
     }
 
+    #[test]
+    fn launchd_respawns_reports_restarted_jobs() {
+        let job = |label: &str| LaunchdJob {
+            label: label.to_string(),
+            domain: "gui/501".to_string(),
+            keep_alive: true,
+            ..LaunchdJob::default()
+        };
+        let mut restarted = make_candidate(10, 501, "kill");
+        restarted.supervisor = Some(CandidateSupervisor {
+            unit: None,
+            launchd: Some(job("com.example.restarted")),
+        });
+        let mut gone = make_candidate(11, 501, "kill");
+        gone.supervisor = Some(CandidateSupervisor {
+            unit: None,
+            launchd: Some(job("com.example.gone")),
+        });
+        let mut paused = make_candidate(12, 501, "pause");
+        paused.supervisor = Some(CandidateSupervisor {
+            unit: None,
+            launchd: Some(job("com.example.paused")),
+        });
+        let plan = make_plan(vec![
+            restarted,
+            gone,
+            paused,
+            make_candidate(13, 501, "kill"),
+        ]);
+
+        let respawns = launchd_respawns(&plan, |job| match job.label.as_str() {
+            "com.example.restarted" => Some(20),
+            "com.example.paused" => Some(21),
+            _ => None,
+        });
+        assert_eq!(respawns.len(), 1);
+        assert_eq!(respawns[0].pid, 10);
+        assert_eq!(respawns[0].new_pid, 20);
+        assert_eq!(respawns[0].label, "com.example.restarted");
+        assert!(respawns[0].keep_alive);
+    }
+
     #[test]
     fn verify_plan_keep_action_skipped() {
         let plan = make_plan(vec![make_candidate(1, 1000, "keep")]);
@@ -1375,6 +1572,8 @@ mod tests {
                 start_id: Some("boot:5:42".to_string()),
                 recommended_action: "kill".to_string(),
                 blast_radius: None,
+                supervisor: None,
+                signature: None,
             }],
         };
         let current = vec![make_proc_with_start_id(
@@ -1422,6 +1621,8 @@ mod tests {
             start_id: None,
             recommended_action: "kill".to_string(),
             blast_radius: None,
+            supervisor: None,
+            signature: None,
         }]);
         let current: Vec<ProcessRecord> = vec![];
         let report = verify_plan(&plan, &current, Utc::now(), Utc::now());
@@ -1472,6 +1673,8 @@ mod tests {
             start_id: Some("boot:5:1".to_string()),
             recommended_action: "kill".to_string(),
             blast_radius: None,
+            supervisor: None,
+            signature: None,
         }]);
         let current: Vec<ProcessRecord> = vec![];
         let report = verify_plan(&plan, &current, Utc::now(), Utc::now());
@@ -1518,6 +1721,8 @@ mod tests {
                 start_id: Some("123:5".to_string()),
                 recommended_action: "kill".to_string(),
                 blast_radius: None,
+                supervisor: None,
+                signature: None,
             }],
         };
         let current = vec![make_proc(
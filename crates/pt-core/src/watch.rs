@@ -0,0 +1,738 @@
+//! Structured rules for `agent watch`.
+//!
+//! A rules file replaces the single `--threshold` knob with a list of named
+//! rules. Each rule has match conditions over the watch evaluation of a
+//! process (posterior class, posterior probability, recommendation, age,
+//! resident memory and memory growth rate) and a list of actions to take
+//! when it matches:
+//!
+//! - `notify`: emit a `rule_matched` event through the watch notify hooks.
+//! - `auto_plan`: create an `agent plan` session for the matched processes.
+//! - `auto_apply`: plan, then apply the policy-approved recommendations.
+//!   The apply step goes through the normal policy and guardrail checks, so
+//!   a rule can never kill something the policy would block.
+//!
+//! Rules files are TOML (`.toml`) or JSON (anything else):
+//!
+//! ```toml
+//! [[rules]]
+//! name = "leaky-abandoned"
+//! actions = ["notify", "auto_plan"]
+//!
+//! [rules.when]
+//! categories = ["abandoned"]
+//! min_posterior = 0.8
+//! min_memory_growth_mb_per_hour = 100.0
+//! min_age_secs = 3600
+//! ```
+
+use crate::decision::causal_interventions::ProcessClass;
+use crate::dedupe::DetectionSource;
+use crate::inference::ClassScores;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Minimum sampling window before a memory growth rate is reported.
+///
+/// Shorter windows turn allocator noise into huge per-hour rates.
+const MIN_GROWTH_WINDOW_SECS: f64 = 30.0;
+
+/// Errors from loading or validating a rules file.
+#[derive(Debug, Error)]
+pub enum WatchRulesError {
+    #[error("I/O error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse rules file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("rules file defines no rules")]
+    Empty,
+
+    #[error("rule #{index}: name must not be empty")]
+    MissingName { index: usize },
+
+    #[error("duplicate rule name: {0}")]
+    DuplicateName(String),
+
+    #[error("rule {rule}: {message}")]
+    InvalidRule { rule: String, message: String },
+}
+
+/// Recommendation produced by the watch decision step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchRecommendation {
+    Kill,
+    Review,
+    Spare,
+}
+
+impl WatchRecommendation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WatchRecommendation::Kill => "kill",
+            WatchRecommendation::Review => "review",
+            WatchRecommendation::Spare => "spare",
+        }
+    }
+}
+
+/// Action taken when a rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Emit a `rule_matched` event (and run the notify hook, if any).
+    Notify,
+    /// Create a plan session covering the matched processes.
+    AutoPlan,
+    /// Plan, then apply recommendations the policy allows.
+    AutoApply,
+}
+
+/// Match conditions for a rule. Every condition that is set must hold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConditions {
+    /// Most likely posterior class must be one of these (empty = any).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<ProcessClass>,
+
+    /// Decision recommendation must be one of these (empty = any).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recommendations: Vec<WatchRecommendation>,
+
+    /// Minimum posterior probability of the most likely class.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_posterior: Option<f64>,
+
+    /// Minimum resident memory growth rate (MB per hour).
+    ///
+    /// Requires at least two observations of the same process, so a rule
+    /// using this condition cannot fire on the first watch iteration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory_growth_mb_per_hour: Option<f64>,
+
+    /// Minimum resident memory (MB).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rss_mb: Option<f64>,
+
+    /// Minimum process age (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_age_secs: Option<u64>,
+
+    /// Maximum process age (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+
+    /// Regex matched against the full command line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_pattern: Option<String>,
+}
+
+/// A single named watch rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WatchRule {
+    /// Unique rule name (reported in events).
+    pub name: String,
+
+    /// Optional human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Disabled rules are parsed and validated but never evaluated.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Match conditions.
+    #[serde(default)]
+    pub when: RuleConditions,
+
+    /// Actions to run when the rule matches.
+    pub actions: Vec<RuleAction>,
+
+    /// Re-fire for the same process after this many seconds.
+    ///
+    /// When unset, a rule fires at most once per process incarnation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_secs: Option<u64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Top-level rules file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WatchRules {
+    /// Rules, evaluated in order.
+    pub rules: Vec<WatchRule>,
+}
+
+impl WatchRules {
+    /// Load rules from a TOML (`.toml`) or JSON file and validate them.
+    pub fn load(path: &Path) -> Result<Self, WatchRulesError> {
+        let content = fs::read_to_string(path).map_err(|source| WatchRulesError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let is_toml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let rules = if is_toml {
+            Self::from_toml_str(&content)
+        } else {
+            Self::from_json_str(&content)
+        }
+        .map_err(|message| WatchRulesError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    fn from_toml_str(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+
+    fn from_json_str(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| e.to_string())
+    }
+
+    /// Semantic validation beyond what the schema enforces.
+    pub fn validate(&self) -> Result<(), WatchRulesError> {
+        if self.rules.is_empty() {
+            return Err(WatchRulesError::Empty);
+        }
+        let mut names = HashSet::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let name = rule.name.trim();
+            if name.is_empty() {
+                return Err(WatchRulesError::MissingName { index });
+            }
+            if !names.insert(name.to_string()) {
+                return Err(WatchRulesError::DuplicateName(name.to_string()));
+            }
+            rule.validate()
+                .map_err(|message| WatchRulesError::InvalidRule {
+                    rule: name.to_string(),
+                    message,
+                })?;
+        }
+        Ok(())
+    }
+}
+
+impl WatchRule {
+    fn validate(&self) -> Result<(), String> {
+        if self.actions.is_empty() {
+            return Err("at least one action is required".to_string());
+        }
+        let when = &self.when;
+        if let Some(p) = when.min_posterior {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(format!("min_posterior must be in [0, 1], got {}", p));
+            }
+        }
+        if let Some(rate) = when.min_memory_growth_mb_per_hour {
+            if !rate.is_finite() {
+                return Err("min_memory_growth_mb_per_hour must be finite".to_string());
+            }
+        }
+        if let Some(rss) = when.min_rss_mb {
+            if !rss.is_finite() || rss < 0.0 {
+                return Err(format!("min_rss_mb must be >= 0, got {}", rss));
+            }
+        }
+        if let (Some(min), Some(max)) = (when.min_age_secs, when.max_age_secs) {
+            if min > max {
+                return Err(format!(
+                    "min_age_secs ({}) exceeds max_age_secs ({})",
+                    min, max
+                ));
+            }
+        }
+        if let Some(pattern) = &when.command_pattern {
+            regex::Regex::new(pattern).map_err(|e| format!("invalid command_pattern: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// What the engine needs to know about one process in one watch iteration.
+#[derive(Debug, Clone)]
+pub struct ProcessObservation<'a> {
+    pub pid: u32,
+    pub start_id: &'a str,
+    pub command: &'a str,
+    pub age_secs: u64,
+    pub rss_bytes: u64,
+    pub posterior: ClassScores,
+    pub recommendation: WatchRecommendation,
+}
+
+impl ProcessObservation<'_> {
+    /// Most likely posterior class and its probability.
+    pub fn map_class(&self) -> (ProcessClass, f64) {
+        let p = &self.posterior;
+        [
+            (ProcessClass::Useful, p.useful),
+            (ProcessClass::UsefulBad, p.useful_bad),
+            (ProcessClass::Abandoned, p.abandoned),
+            (ProcessClass::Zombie, p.zombie),
+        ]
+        .into_iter()
+        .fold((ProcessClass::Useful, f64::NEG_INFINITY), |best, cur| {
+            if cur.1 > best.1 {
+                cur
+            } else {
+                best
+            }
+        })
+    }
+}
+
+/// A rule that fired for a process.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub actions: Vec<RuleAction>,
+    pub category: ProcessClass,
+    pub posterior: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_growth_mb_per_hour: Option<f64>,
+}
+
+/// One JSONL line emitted by `agent watch`.
+///
+/// The `event` tag doubles as the `PT_WATCH_EVENT` value passed to notify
+/// hooks; the full serialized event is passed as `PT_WATCH_EVENT_JSON`.
+///
+/// A process yields at most one process event per iteration: threshold and
+/// rule detections are merged, and `sources`/`rules` record which fired.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent {
+    /// A process crossed the watch threshold for the first time.
+    CandidateDetected {
+        timestamp: String,
+        pid: u32,
+        classification: String,
+        confidence: f64,
+        severity: String,
+        command: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        sources: Vec<DetectionSource>,
+        /// Notify rules that also matched.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rules: Vec<String>,
+    },
+    /// A known candidate moved to a higher severity band.
+    SeverityEscalated {
+        timestamp: String,
+        pid: u32,
+        classification: String,
+        prior_confidence: f64,
+        current_confidence: f64,
+        prior_severity: String,
+        current_severity: String,
+        command: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        sources: Vec<DetectionSource>,
+        /// Notify rules that also matched.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rules: Vec<String>,
+    },
+    /// A rules-file rule with the `notify` action matched a process.
+    RuleMatched {
+        timestamp: String,
+        pid: u32,
+        rule: String,
+        category: ProcessClass,
+        posterior: f64,
+        memory_growth_mb_per_hour: Option<f64>,
+        classification: String,
+        actions: Vec<RuleAction>,
+        command: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        sources: Vec<DetectionSource>,
+        /// Every notify rule that matched; `rule` is the first.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rules: Vec<String>,
+    },
+    /// An `auto_plan` / `auto_apply` rule created a plan session.
+    RulePlanCreated {
+        timestamp: String,
+        session_id: String,
+        pids: Vec<u32>,
+        planned_pids: Vec<u32>,
+    },
+    /// An `auto_apply` rule finished applying; `result` is the `agent apply` output.
+    RuleApplyCompleted {
+        timestamp: String,
+        session_id: String,
+        pids: Vec<u32>,
+        result: serde_json::Value,
+    },
+    /// A rule action could not be carried out.
+    RuleActionFailed {
+        timestamp: String,
+        action: RuleAction,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        pids: Vec<u32>,
+        error: String,
+    },
+    /// A `--goal-*` target is not met.
+    GoalViolated {
+        timestamp: String,
+        goal: String,
+        current: String,
+    },
+    /// A system metric drifted away from the `--baseline` snapshot.
+    BaselineAnomaly {
+        timestamp: String,
+        metric: String,
+        baseline: String,
+        current: String,
+    },
+}
+
+impl WatchEvent {
+    /// The `event` tag value.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CandidateDetected { .. } => "candidate_detected",
+            Self::SeverityEscalated { .. } => "severity_escalated",
+            Self::RuleMatched { .. } => "rule_matched",
+            Self::RulePlanCreated { .. } => "rule_plan_created",
+            Self::RuleApplyCompleted { .. } => "rule_apply_completed",
+            Self::RuleActionFailed { .. } => "rule_action_failed",
+            Self::GoalViolated { .. } => "goal_violated",
+            Self::BaselineAnomaly { .. } => "baseline_anomaly",
+        }
+    }
+
+    /// The process the event is about, if it concerns a single process.
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            Self::CandidateDetected { pid, .. }
+            | Self::SeverityEscalated { pid, .. }
+            | Self::RuleMatched { pid, .. } => Some(*pid),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RssSample {
+    first_rss: u64,
+    first_at: DateTime<Utc>,
+}
+
+type ProcessKey = (u32, String);
+
+/// Evaluates [`WatchRules`] against watch observations.
+///
+/// The engine is stateful across iterations: it remembers the first RSS
+/// sample of each process incarnation (for growth rates) and when each rule
+/// last fired for it (for cooldowns). Call [`RuleEngine::retain`] at the end
+/// of every iteration to forget processes that have exited.
+#[derive(Debug)]
+pub struct RuleEngine {
+    rules: Vec<WatchRule>,
+    patterns: Vec<Option<regex::Regex>>,
+    samples: HashMap<ProcessKey, RssSample>,
+    fired: HashMap<(usize, ProcessKey), DateTime<Utc>>,
+}
+
+impl RuleEngine {
+    /// Build an engine from validated rules. Disabled rules are dropped.
+    pub fn new(rules: WatchRules) -> Result<Self, WatchRulesError> {
+        rules.validate()?;
+        let rules: Vec<WatchRule> = rules.rules.into_iter().filter(|r| r.enabled).collect();
+        let patterns = rules
+            .iter()
+            .map(|rule| {
+                rule.when
+                    .command_pattern
+                    .as_deref()
+                    .and_then(|p| regex::Regex::new(p).ok())
+            })
+            .collect();
+        Ok(Self {
+            rules,
+            patterns,
+            samples: HashMap::new(),
+            fired: HashMap::new(),
+        })
+    }
+
+    /// Enabled rules, in evaluation order.
+    pub fn rules(&self) -> &[WatchRule] {
+        &self.rules
+    }
+
+    /// Record an observation and return the rules that fire for it.
+    pub fn evaluate(&mut self, obs: &ProcessObservation<'_>, now: DateTime<Utc>) -> Vec<RuleMatch> {
+        let key: ProcessKey = (obs.pid, obs.start_id.to_string());
+        let growth = self.memory_growth(&key, obs.rss_bytes, now);
+        let (category, posterior) = obs.map_class();
+
+        let mut matches = Vec::new();
+        for (idx, rule) in self.rules.iter().enumerate() {
+            let when = &rule.when;
+            if !when.categories.is_empty() && !when.categories.contains(&category) {
+                continue;
+            }
+            if !when.recommendations.is_empty()
+                && !when.recommendations.contains(&obs.recommendation)
+            {
+                continue;
+            }
+            if when.min_posterior.is_some_and(|min| posterior < min) {
+                continue;
+            }
+            if when.min_age_secs.is_some_and(|min| obs.age_secs < min) {
+                continue;
+            }
+            if when.max_age_secs.is_some_and(|max| obs.age_secs > max) {
+                continue;
+            }
+            if when
+                .min_rss_mb
+                .is_some_and(|min| (obs.rss_bytes as f64 / (1024.0 * 1024.0)) < min)
+            {
+                continue;
+            }
+            if let Some(min) = when.min_memory_growth_mb_per_hour {
+                match growth {
+                    Some(rate) if rate >= min => {}
+                    _ => continue,
+                }
+            }
+            if let Some(re) = &self.patterns[idx] {
+                if !re.is_match(obs.command) {
+                    continue;
+                }
+            }
+
+            let fired_key = (idx, key.clone());
+            if let Some(last) = self.fired.get(&fired_key) {
+                let Some(cooldown) = rule.cooldown_secs else {
+                    continue;
+                };
+                if (now - *last).num_seconds() < cooldown as i64 {
+                    continue;
+                }
+            }
+            self.fired.insert(fired_key, now);
+
+            matches.push(RuleMatch {
+                rule: rule.name.clone(),
+                actions: rule.actions.clone(),
+                category,
+                posterior,
+                memory_growth_mb_per_hour: growth,
+            });
+        }
+        matches
+    }
+
+    /// Forget state for processes not in `seen` (pid, start_id).
+    pub fn retain(&mut self, seen: &HashSet<(u32, String)>) {
+        self.samples.retain(|key, _| seen.contains(key));
+        self.fired.retain(|(_, key), _| seen.contains(key));
+    }
+
+    fn memory_growth(&mut self, key: &ProcessKey, rss: u64, now: DateTime<Utc>) -> Option<f64> {
+        let sample = self.samples.entry(key.clone()).or_insert(RssSample {
+            first_rss: rss,
+            first_at: now,
+        });
+        let elapsed = (now - sample.first_at).num_milliseconds() as f64 / 1000.0;
+        if elapsed < MIN_GROWTH_WINDOW_SECS {
+            return None;
+        }
+        let delta_mb = (rss as f64 - sample.first_rss as f64) / (1024.0 * 1024.0);
+        Some(delta_mb * 3600.0 / elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn observation(rss_mb: u64, abandoned: f64) -> ProcessObservation<'static> {
+        ProcessObservation {
+            pid: 4242,
+            start_id: "boot:1:4242",
+            command: "node /srv/app/server.js",
+            age_secs: 7200,
+            rss_bytes: rss_mb * 1024 * 1024,
+            posterior: ClassScores {
+                useful: 1.0 - abandoned,
+                useful_bad: 0.0,
+                abandoned,
+                zombie: 0.0,
+            },
+            recommendation: WatchRecommendation::Kill,
+        }
+    }
+
+    fn rules_from_toml(raw: &str) -> WatchRules {
+        let rules = WatchRules::from_toml_str(raw).expect("parse toml");
+        rules.validate().expect("valid rules");
+        rules
+    }
+
+    #[test]
+    fn parses_toml_and_json_equivalently() {
+        let toml_rules = rules_from_toml(
+            r#"
+            [[rules]]
+            name = "abandoned"
+            actions = ["notify", "auto_plan"]
+            [rules.when]
+            categories = ["abandoned"]
+            min_posterior = 0.8
+            "#,
+        );
+        let json_rules = WatchRules::from_json_str(
+            r#"{"rules":[{"name":"abandoned","actions":["notify","auto_plan"],
+                "when":{"categories":["abandoned"],"min_posterior":0.8}}]}"#,
+        )
+        .expect("parse json");
+        assert_eq!(toml_rules, json_rules);
+        assert!(toml_rules.rules[0].enabled);
+    }
+
+    #[test]
+    fn validation_rejects_bad_rules() {
+        let err = WatchRules::from_json_str(r#"{"rules":[{"name":"x","actions":[],"bogus":1}]}"#);
+        assert!(err.is_err(), "unknown fields are rejected");
+
+        let rules = WatchRules::from_json_str(r#"{"rules":[{"name":"x","actions":[]}]}"#).unwrap();
+        assert!(matches!(
+            rules.validate(),
+            Err(WatchRulesError::InvalidRule { .. })
+        ));
+
+        let rules = WatchRules::from_json_str(
+            r#"{"rules":[{"name":"x","actions":["notify"],"when":{"min_posterior":1.5}}]}"#,
+        )
+        .unwrap();
+        assert!(rules.validate().is_err());
+
+        let rules = WatchRules::from_json_str(
+            r#"{"rules":[{"name":"x","actions":["notify"]},{"name":"x","actions":["notify"]}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            rules.validate(),
+            Err(WatchRulesError::DuplicateName(_))
+        ));
+
+        assert!(matches!(
+            WatchRules::default().validate(),
+            Err(WatchRulesError::Empty)
+        ));
+    }
+
+    #[test]
+    fn matches_category_posterior_and_fires_once() {
+        let rules = rules_from_toml(
+            r#"
+            [[rules]]
+            name = "abandoned"
+            actions = ["notify"]
+            [rules.when]
+            categories = ["abandoned"]
+            min_posterior = 0.8
+            min_age_secs = 3600
+            "#,
+        );
+        let mut engine = RuleEngine::new(rules).unwrap();
+        let now = Utc::now();
+
+        assert!(engine.evaluate(&observation(100, 0.6), now).is_empty());
+        let hits = engine.evaluate(&observation(100, 0.9), now);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].category, ProcessClass::Abandoned);
+        assert!(engine
+            .evaluate(&observation(100, 0.9), now + Duration::seconds(60))
+            .is_empty());
+    }
+
+    #[test]
+    fn memory_growth_and_cooldown() {
+        let rules = rules_from_toml(
+            r#"
+            [[rules]]
+            name = "leak"
+            actions = ["auto_apply"]
+            cooldown_secs = 600
+            [rules.when]
+            min_memory_growth_mb_per_hour = 100.0
+            "#,
+        );
+        let mut engine = RuleEngine::new(rules).unwrap();
+        let t0 = Utc::now();
+
+        // First sample only establishes the baseline.
+        assert!(engine.evaluate(&observation(100, 0.9), t0).is_empty());
+        // +10 MB in 60s = 600 MB/h.
+        let hits = engine.evaluate(&observation(110, 0.9), t0 + Duration::seconds(60));
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].memory_growth_mb_per_hour.unwrap() > 500.0);
+        // Within cooldown.
+        assert!(engine
+            .evaluate(&observation(120, 0.9), t0 + Duration::seconds(120))
+            .is_empty());
+        // After cooldown.
+        assert_eq!(
+            engine
+                .evaluate(&observation(200, 0.9), t0 + Duration::seconds(720))
+                .len(),
+            1
+        );
+
+        engine.retain(&HashSet::new());
+        assert!(engine
+            .evaluate(&observation(300, 0.9), t0 + Duration::seconds(780))
+            .is_empty());
+    }
+
+    #[test]
+    fn watch_event_serializes_with_event_tag() {
+        let event = WatchEvent::RuleActionFailed {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            action: RuleAction::AutoPlan,
+            session_id: None,
+            pids: vec![1, 2],
+            error: "boom".to_string(),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["event"], event.name());
+        assert_eq!(value["action"], "auto_plan");
+        assert!(value.get("session_id").is_none());
+        assert_eq!(event.pid(), None);
+
+        let parsed: WatchEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
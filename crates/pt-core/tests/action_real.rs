@@ -59,6 +59,9 @@ fn test_signal_kill_real() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Execute kill
@@ -114,6 +117,9 @@ fn test_signal_pause_resume_real() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Pause
@@ -206,6 +212,9 @@ fn test_process_group_pause_resume_real() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Pause the entire group
@@ -246,6 +255,9 @@ fn test_process_group_pause_resume_real() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Resume the entire group
@@ -311,6 +323,9 @@ fn test_zombie_verification_real() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Execute kill on zombie should succeed (no-op or ignored signal)
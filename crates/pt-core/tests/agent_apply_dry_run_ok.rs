@@ -127,6 +127,9 @@ fn agent_apply_dry_run_returns_actions_ok() {
                 confidence: ActionConfidence::Normal,
                 original_zombie_target: None,
                 d_state_diagnostics: None,
+                cost_benefit: None,
+                unit_action: None,
+                command_action: None,
             }],
             pre_toggled: Vec::new(),
             gates_summary: GatesSummary {
@@ -134,6 +137,7 @@ fn agent_apply_dry_run_returns_actions_ok() {
                 blocked_candidates: 0,
                 pre_toggled_actions: 0,
             },
+            stages: Vec::new(),
         };
 
         let decision_dir = handle.dir.join("decision");
@@ -106,6 +106,9 @@ fn agent_apply_returns_nothing_to_do_when_no_actions_match() {
                 confidence: ActionConfidence::Normal,
                 original_zombie_target: None,
                 d_state_diagnostics: None,
+                cost_benefit: None,
+                unit_action: None,
+                command_action: None,
             }],
             pre_toggled: Vec::new(),
             gates_summary: GatesSummary {
@@ -113,6 +116,7 @@ fn agent_apply_returns_nothing_to_do_when_no_actions_match() {
                 blocked_candidates: 0,
                 pre_toggled_actions: 0,
             },
+            stages: Vec::new(),
         };
 
         let decision_dir = handle.dir.join("decision");
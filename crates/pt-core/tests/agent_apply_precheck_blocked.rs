@@ -153,6 +153,9 @@ fn agent_apply_returns_policy_blocked_for_precheck_block() {
                 confidence: ActionConfidence::Normal,
                 original_zombie_target: None,
                 d_state_diagnostics: None,
+                cost_benefit: None,
+                unit_action: None,
+                command_action: None,
             }],
             pre_toggled: Vec::new(),
             gates_summary: GatesSummary {
@@ -160,6 +163,7 @@ fn agent_apply_returns_policy_blocked_for_precheck_block() {
                 blocked_candidates: 0,
                 pre_toggled_actions: 0,
             },
+            stages: Vec::new(),
         };
 
         let decision_dir = handle.dir.join("decision");
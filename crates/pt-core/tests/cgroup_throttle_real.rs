@@ -232,6 +232,9 @@ fn test_throttle_spawned_process() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // Execute throttle
@@ -325,6 +328,9 @@ fn test_throttle_permission_denied() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     // This should fail (either permission denied or protected)
@@ -366,6 +372,9 @@ fn test_throttle_nonexistent_process() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     let result = runner.execute(&action);
@@ -408,6 +417,9 @@ fn test_throttle_wrong_action_type() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     let result = runner.execute(&action);
@@ -445,6 +457,9 @@ fn test_throttle_keep_action_noop() {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     };
 
     let result = runner.execute(&action);
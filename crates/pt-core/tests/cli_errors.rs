@@ -245,6 +245,21 @@ mod numeric_errors {
             .failure()
             .stderr(predicate::str::contains("error"));
     }
+
+    #[test]
+    fn report_session_conflicts_with_bundle() {
+        pt_core()
+            .args([
+                "report",
+                "--session",
+                "pt-20260101-000000-abcd",
+                "--bundle",
+                "x.ptb",
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
 }
 
 // ============================================================================
@@ -532,12 +532,14 @@ fn make_test_snapshot() -> GpuSnapshot {
                 gpu_index: 0,
                 used_gpu_memory_mib: Some(1024),
                 gpu_process_type: Some("C".into()),
+                utilization_percent: None,
             },
             ProcessGpuUsage {
                 pid: 1234,
                 gpu_index: 1,
                 used_gpu_memory_mib: Some(2048),
                 gpu_process_type: Some("C".into()),
+                utilization_percent: None,
             },
         ],
     );
@@ -548,6 +550,7 @@ fn make_test_snapshot() -> GpuSnapshot {
             gpu_index: 0,
             used_gpu_memory_mib: Some(512),
             gpu_process_type: Some("G".into()),
+            utilization_percent: None,
         }],
     );
 
@@ -651,6 +654,7 @@ fn gpu_collect_snapshot_no_gpu_tools() {
     assert!(
         snapshot.provenance.source == GpuDetectionSource::NvidiaSmi
             || snapshot.provenance.source == GpuDetectionSource::RocmSmi
+            || snapshot.provenance.source == GpuDetectionSource::DrmFdinfo
             || snapshot.provenance.source == GpuDetectionSource::None
     );
 }
@@ -83,6 +83,8 @@ fn test_policy_load_and_enforce_real() {
         process_state: None,
         wchan: None,
         critical_files: Vec::new(),
+        privileges: None,
+        blast_radius: None,
     };
 
     let result = enforcer.check_action(&candidate, Action::Kill, true); // robot_mode=true
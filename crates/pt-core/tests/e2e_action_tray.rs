@@ -217,6 +217,9 @@ fn make_pause_action(pid: u32, pgid: Option<u32>, action_id: &str) -> PlanAction
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     }
 }
 
@@ -244,6 +247,9 @@ fn make_resume_action(pid: u32, pgid: Option<u32>, action_id: &str) -> PlanActio
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     }
 }
 
@@ -271,6 +277,9 @@ fn make_renice_action(pid: u32, action_id: &str) -> PlanAction {
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     }
 }
 
@@ -302,6 +311,9 @@ fn make_kill_action(pid: u32, action_id: &str, pre_checks: Vec<PreCheck>) -> Pla
         confidence: ActionConfidence::Normal,
         original_zombie_target: None,
         d_state_diagnostics: None,
+        cost_benefit: None,
+        unit_action: None,
+        command_action: None,
     }
 }
 
@@ -319,6 +331,7 @@ fn make_test_plan_from_actions(actions: Vec<PlanAction>) -> Plan {
             blocked_candidates: 0,
             pre_toggled_actions: 0,
         },
+        stages: vec![],
     }
 }
 
@@ -1166,6 +1179,9 @@ mod cgroup_throttle_action {
             confidence: ActionConfidence::Normal,
             original_zombie_target: None,
             d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
         }
     }
 
@@ -1419,6 +1435,9 @@ mod cgroup_freeze_action {
             confidence: ActionConfidence::Normal,
             original_zombie_target: None,
             d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
         }
     }
 
@@ -1446,6 +1465,9 @@ mod cgroup_freeze_action {
             confidence: ActionConfidence::Normal,
             original_zombie_target: None,
             d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
         }
     }
 
@@ -7,6 +7,7 @@ use assert_cmd::cargo::cargo_bin_cmd;
 use assert_cmd::Command;
 use pt_common::SessionId;
 use pt_core::exit_codes::ExitCode;
+use pt_core::output::envelopes::AgentDiffOutput;
 use pt_core::session::{SessionContext, SessionManifest, SessionMode, SessionStore};
 use serde_json::Value;
 use std::env;
@@ -205,6 +206,8 @@ fn diff_two_sessions_produces_json() {
         assert!(json.get("comparison").is_some(), "should have comparison");
         assert!(json.get("delta").is_some(), "should have delta");
         assert!(json.get("summary").is_some(), "should have summary");
+        serde_json::from_value::<AgentDiffOutput>(json.clone())
+            .expect("diff output should match AgentDiffOutput");
 
         let comparison = &json["comparison"];
         assert_eq!(comparison["prior_session"].as_str().unwrap(), base.0);
@@ -8,6 +8,7 @@ use assert_cmd::Command;
 use pt_common::{IdentityQuality, ProcessId, ProcessIdentity, SessionId, StartId};
 use pt_core::decision::Action;
 use pt_core::exit_codes::ExitCode;
+use pt_core::output::envelopes::AgentExplainOutput;
 use pt_core::plan::{
     ActionConfidence, ActionHook, ActionRationale, ActionRouting, ActionTimeouts, GatesSummary,
     Plan, PlanAction,
@@ -97,6 +98,9 @@ fn create_session_with_plan(_dir: &TempDir, identity: ProcessIdentity, blocked:
             confidence: ActionConfidence::Normal,
             original_zombie_target: None,
             d_state_diagnostics: None,
+            cost_benefit: None,
+            unit_action: None,
+            command_action: None,
         }],
         pre_toggled: Vec::new(),
         gates_summary: GatesSummary {
@@ -104,6 +108,7 @@ fn create_session_with_plan(_dir: &TempDir, identity: ProcessIdentity, blocked:
             blocked_candidates: if blocked { 1 } else { 0 },
             pre_toggled_actions: 0,
         },
+        stages: Vec::new(),
     };
 
     let decision_dir = handle.dir.join("decision");
@@ -249,6 +254,8 @@ fn explain_live_process_returns_explanation() {
             json.get("session_id").and_then(|v| v.as_str()),
             Some(session_id.0.as_str()),
         );
+        serde_json::from_value::<AgentExplainOutput>(json.clone())
+            .expect("explain output should match AgentExplainOutput");
 
         let explanations = json
             .get("explanations")
@@ -0,0 +1,183 @@
+//! E2E tests for `agent fleet plan --from-session`.
+//!
+//! Re-plans a fleet session from its cached host scans without SSH.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use pt_common::SessionId;
+use pt_core::fleet::agentless::parse_payload_output;
+use pt_core::fleet::scan_artifacts::{read_host_scans, write_host_scans};
+use pt_core::fleet::ssh_scan::HostScanResult;
+use pt_core::session::fleet::create_fleet_session;
+use pt_core::session::{SessionManifest, SessionMode, SessionStore};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tempfile::TempDir;
+
+static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+const AGENTLESS_OUTPUT: &str = "@@pt-agentless 1
+@@uname
+Linux
+@@boot_id
+8d1c7a52-4bb6-4a4e-9d33-5e1f0b6c2a10
+@@now
+1767225600
+@@ps
+    1     0     0 root         1     1 S  0.0 12000 170000 ?        Thu Jan  1 00:00:00 2026 86400 systemd /sbin/init
+  812     1  1000 alice      812   812 T  0.0 204800 409600 ?       Thu Jan  1 00:00:00 2026 86400 node node /srv/app/server.js
+  913   812  1000 alice      812   812 Z  0.0     0      0 ?        Thu Jan  1 12:00:00 2026 43200 worker [worker] <defunct>
+@@stat
+1 (systemd) S 0 1 1 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 1 0 42 170000 3000
+812 (node) T 1 812 812 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 11 0 93500 409600 51200
+@@end
+";
+
+fn with_temp_dirs<T>(f: impl FnOnce(&TempDir) -> T) -> T {
+    let _guard = ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let old_data = env::var("PROCESS_TRIAGE_DATA").ok();
+    let old_config = env::var("PROCESS_TRIAGE_CONFIG").ok();
+    let data_dir = TempDir::new().expect("create temp data dir");
+    let config_dir = TempDir::new().expect("create temp config dir");
+    env::set_var("PROCESS_TRIAGE_DATA", data_dir.path());
+    env::set_var("PROCESS_TRIAGE_CONFIG", config_dir.path());
+
+    let result = f(&data_dir);
+
+    match old_data {
+        Some(val) => env::set_var("PROCESS_TRIAGE_DATA", val),
+        None => env::remove_var("PROCESS_TRIAGE_DATA"),
+    }
+    match old_config {
+        Some(val) => env::set_var("PROCESS_TRIAGE_CONFIG", val),
+        None => env::remove_var("PROCESS_TRIAGE_CONFIG"),
+    }
+
+    result
+}
+
+fn pt_core_fast() -> Command {
+    let mut cmd = cargo_bin_cmd!("pt-core");
+    cmd.timeout(Duration::from_secs(120));
+    cmd.env("PT_SKIP_GLOBAL_LOCK", "1");
+    cmd
+}
+
+/// Persist an (empty) fleet session and return its id and directory.
+fn persist_fleet_session() -> (String, PathBuf) {
+    let session_id = SessionId::new();
+    let store = SessionStore::from_env().expect("session store");
+    let manifest = SessionManifest::new(
+        &session_id,
+        None,
+        SessionMode::RobotPlan,
+        Some("fleet-plan-fixture".to_string()),
+    );
+    let handle = store.create(&manifest).expect("create session");
+
+    let fleet = create_fleet_session(&session_id.0, Some("fixture"), &[], 0.05);
+    let payload = serde_json::to_string_pretty(&fleet).expect("serialize fleet");
+    fs::write(handle.dir.join("fleet.json"), payload).expect("write fleet session");
+
+    (session_id.0, handle.dir)
+}
+
+fn cached_results() -> Vec<HostScanResult> {
+    vec![
+        HostScanResult {
+            host: "alpha.internal".to_string(),
+            success: true,
+            scan: Some(parse_payload_output(AGENTLESS_OUTPUT).expect("payload parses")),
+            error: None,
+            duration_ms: 850,
+        },
+        HostScanResult {
+            host: "beta.internal".to_string(),
+            success: false,
+            scan: None,
+            error: Some("connection refused".to_string()),
+            duration_ms: 30,
+        },
+    ]
+}
+
+fn replan(source: &str, data_dir: &Path) -> Value {
+    let output = pt_core_fast()
+        .env("PROCESS_TRIAGE_DATA", data_dir)
+        .args([
+            "--format",
+            "json",
+            "agent",
+            "fleet",
+            "plan",
+            "--from-session",
+            source,
+            "--max-fdr",
+            "0.2",
+        ])
+        .output()
+        .expect("run pt-core");
+    serde_json::from_slice(&output.stdout).expect("valid json")
+}
+
+#[test]
+fn fleet_plan_replans_from_cached_scans() {
+    with_temp_dirs(|data_dir| {
+        let (source_id, source_dir) = persist_fleet_session();
+        let host_groups = HashMap::from([("alpha.internal".to_string(), "web".to_string())]);
+        write_host_scans(
+            &source_dir,
+            &cached_results(),
+            &host_groups,
+            &HashMap::new(),
+            true,
+        )
+        .expect("write scans");
+
+        let json = replan(&source_id, data_dir.path());
+        assert_eq!(json["status"], "partial");
+        assert_eq!(json["inventory_source"], "fleet_session");
+        assert_eq!(json["inputs"]["from_session"], source_id.as_str());
+        assert_eq!(json["inputs"]["max_fdr"], 0.2);
+        assert_eq!(json["scan_summary"]["total_hosts"], 2);
+        assert_eq!(json["scan_summary"]["successful"], 1);
+        assert_eq!(json["scan_summary"]["failed"], 1);
+
+        let new_id = json["fleet_session_id"].as_str().expect("fleet session id");
+        assert_ne!(new_id, source_id);
+        let hosts = json["fleet_session"]["hosts"].as_array().expect("hosts");
+        let alpha = hosts
+            .iter()
+            .find(|h| h["host_id"] == "alpha.internal")
+            .expect("alpha host");
+        assert_eq!(alpha["group"], "web");
+
+        // The new session keeps the scans, so it can be re-planned again.
+        let new_dir = json["session_dir"].as_str().expect("session dir");
+        let cached = read_host_scans(Path::new(new_dir)).expect("scans cached");
+        assert!(cached.index.agentless);
+        assert_eq!(cached.results.len(), 2);
+        assert_eq!(cached.host_groups(), host_groups);
+    });
+}
+
+#[test]
+fn fleet_plan_from_session_without_scans_fails() {
+    with_temp_dirs(|data_dir| {
+        let (source_id, _) = persist_fleet_session();
+
+        let json = replan(&source_id, data_dir.path());
+        assert_eq!(json["status"], "error");
+        let error = json["error"].as_str().expect("error message");
+        assert!(error.contains("no cached scans"), "error: {}", error);
+    });
+}
@@ -5,6 +5,7 @@
 use assert_cmd::cargo::cargo_bin_cmd;
 use assert_cmd::Command;
 use pt_common::SessionId;
+use pt_core::output::envelopes::FleetReportOutput;
 use pt_core::session::fleet::{create_fleet_session, CandidateInfo, HostInput};
 use pt_core::session::{SessionManifest, SessionMode, SessionStore};
 use serde_json::Value;
@@ -51,6 +52,7 @@ fn kill_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "kill".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
@@ -62,6 +64,7 @@ fn review_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "review".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
@@ -73,20 +76,23 @@ fn spare_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "spare".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
 fn host_input(id: &str, candidates: Vec<CandidateInfo>) -> HostInput {
     HostInput {
         host_id: id.to_string(),
+        group: None,
         session_id: format!("session-{}", id),
         scanned_at: "2026-02-08T12:00:00Z".to_string(),
         total_processes: 250 + candidates.len() as u32,
         candidates,
+        signature_hits: Default::default(),
     }
 }
 
-fn create_fixture_fleet_session() -> String {
+fn persist_fleet_session(inputs: &[HostInput]) -> String {
     let session_id = SessionId::new();
     let store = SessionStore::from_env().expect("session store");
     let manifest = SessionManifest::new(
@@ -97,6 +103,15 @@ fn create_fixture_fleet_session() -> String {
     );
     let handle = store.create(&manifest).expect("create session");
 
+    let fleet = create_fleet_session(&session_id.0, Some("fixture"), inputs, 0.05);
+    let fleet_path = handle.dir.join("fleet.json");
+    let payload = serde_json::to_string_pretty(&fleet).expect("serialize fleet");
+    fs::write(&fleet_path, payload).expect("write fleet session");
+
+    session_id.0
+}
+
+fn create_fixture_fleet_session() -> String {
     let inputs = vec![
         host_input(
             "alpha.internal",
@@ -124,12 +139,7 @@ fn create_fixture_fleet_session() -> String {
         ),
     ];
 
-    let fleet = create_fleet_session(&session_id.0, Some("fixture"), &inputs, 0.05);
-    let fleet_path = handle.dir.join("fleet.json");
-    let payload = serde_json::to_string_pretty(&fleet).expect("serialize fleet");
-    fs::write(&fleet_path, payload).expect("write fleet session");
-
-    session_id.0
+    persist_fleet_session(&inputs)
 }
 
 #[test]
@@ -154,6 +164,8 @@ fn fleet_report_json_contains_expected_sections() {
             .clone();
 
         let json: Value = serde_json::from_slice(&output).expect("valid json");
+        serde_json::from_value::<FleetReportOutput>(json.clone())
+            .expect("fleet report should match FleetReportOutput");
         let report = json.get("report").expect("report section");
 
         assert_eq!(
@@ -188,6 +200,71 @@ fn fleet_report_json_contains_expected_sections() {
     });
 }
 
+#[test]
+fn fleet_report_includes_host_group_sections() {
+    with_temp_data_dir(|data_dir| {
+        let grouped = |id: &str, group: &str, candidates| HostInput {
+            group: Some(group.to_string()),
+            ..host_input(id, candidates)
+        };
+        let fleet_session_id = persist_fleet_session(&[
+            grouped(
+                "ci-1.internal",
+                "ci",
+                vec![
+                    kill_candidate(1001, "stale_pytest_worker", 0.99),
+                    review_candidate(1002, "stale_pytest_worker", 0.8),
+                ],
+            ),
+            grouped(
+                "ci-2.internal",
+                "ci",
+                vec![kill_candidate(2001, "stale_pytest_worker", 0.97)],
+            ),
+            grouped(
+                "db-1.internal",
+                "db",
+                vec![spare_candidate(3001, "shell_idle", 0.11)],
+            ),
+        ]);
+        let output = pt_core_fast()
+            .env("PROCESS_TRIAGE_DATA", data_dir.path())
+            .args([
+                "--format",
+                "json",
+                "agent",
+                "fleet",
+                "report",
+                "--fleet-session",
+                &fleet_session_id,
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+
+        let json: Value = serde_json::from_slice(&output).expect("valid json");
+        let groups = json["report"]["groups"].as_array().expect("groups section");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0]["group"], "ci");
+        assert_eq!(groups[0]["rank"], 1);
+        assert_eq!(groups[0]["host_count"], 2);
+        assert_eq!(groups[0]["candidate_count"], 3);
+        assert_eq!(
+            groups[0]["top_offenders"][0]["signature"],
+            "stale_pytest_worker"
+        );
+        assert_eq!(groups[0]["top_offenders"][0]["total_instances"], 3);
+        assert_eq!(groups[1]["group"], "db");
+        assert!(json["report"]["host_comparison"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|host| host["group"].is_string()));
+    });
+}
+
 #[test]
 fn fleet_report_minimal_profile_redacts_hosts_and_signatures() {
     with_temp_data_dir(|data_dir| {
@@ -379,6 +379,8 @@ fn test_capabilities_supervisors_structure() {
         "docker_daemon",
         "podman",
         "kubernetes",
+        "systemd_oomd",
+        "earlyoom",
     ];
 
     for field in required {
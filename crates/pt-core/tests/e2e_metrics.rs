@@ -58,6 +58,7 @@ fn start_test_server(offset: u16, metrics: DaemonMetrics) -> MetricsServer {
         port,
         path: "/metrics".to_string(),
         rate_limit_per_sec: 100,
+        calibration_refresh_secs: 600,
     };
     let server = MetricsServer::start(&config, metrics)
         .unwrap_or_else(|e| panic!("Failed to start metrics server on port {}: {}", port, e));
@@ -82,6 +83,37 @@ mod prometheus_format {
         server.shutdown();
     }
 
+    #[test]
+    fn openmetrics_negotiated_via_accept_header() {
+        let metrics = DaemonMetrics::new().unwrap();
+        metrics.record_scan("success", 1.0);
+        let server = start_test_server(53, metrics);
+
+        let mut stream = TcpStream::connect(server.addr()).expect("connect failed");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream
+            .write_all(
+                b"GET /metrics HTTP/1.0\r\nHost: localhost\r\n\
+                  Accept: application/openmetrics-text; version=1.0.0\r\n\r\n",
+            )
+            .expect("write failed");
+        let mut resp = String::new();
+        let _ = stream.read_to_string(&mut resp);
+
+        let headers = resp.split("\r\n\r\n").next().unwrap_or("");
+        assert!(
+            headers.contains("application/openmetrics-text"),
+            "Content-Type should be OpenMetrics, got headers: {}",
+            headers
+        );
+        let body = extract_body(&resp);
+        assert!(body.contains("# TYPE pt_scans counter"));
+        assert!(body.trim_end().ends_with("# EOF"));
+        server.shutdown();
+    }
+
     #[test]
     fn content_type_is_prometheus_text() {
         let metrics = DaemonMetrics::new().unwrap();
@@ -208,6 +240,7 @@ mod metric_values {
             swap_used_mb: 200,
             process_count: 450,
             orphan_count: 12,
+            psi: None,
         };
         let state = DaemonState {
             started_at: "2026-01-15T00:00:00Z".to_string(),
@@ -217,6 +250,7 @@ mod metric_values {
             escalation_count: 7,
             deferred_count: 3,
             recent_events: std::collections::VecDeque::new(),
+            schedule: Default::default(),
         };
         metrics.update_from_tick(&tick, &state);
 
@@ -301,6 +335,7 @@ mod metric_values {
             swap_used_mb: 0,
             process_count: 100,
             orphan_count: 0,
+            psi: None,
         };
         let state = DaemonState::new();
         metrics.update_from_tick(&tick, &state);
@@ -467,6 +502,7 @@ mod configuration {
             port,
             path: "/custom/prometheus".to_string(),
             rate_limit_per_sec: 100,
+            calibration_refresh_secs: 600,
         };
         let server = MetricsServer::start(&config, metrics).unwrap();
         std::thread::sleep(Duration::from_millis(100));
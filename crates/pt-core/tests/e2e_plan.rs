@@ -98,6 +98,10 @@ mod plan_basic {
         );
         assert!(json.get("session_id").is_some(), "Missing session_id");
         assert!(json.get("generated_at").is_some(), "Missing generated_at");
+
+        // Output must satisfy the published AgentPlanOutput schema
+        serde_json::from_value::<pt_core::output::envelopes::AgentPlanOutput>(json)
+            .expect("plan output should match AgentPlanOutput");
     }
 
     #[test]
@@ -0,0 +1,135 @@
+//! E2E tests for `redact dictionary export` / `redact dictionary show`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use pt_common::SessionId;
+use pt_core::exit_codes::ExitCode;
+use pt_core::redact::{dictionary, KEY_FILE};
+use pt_core::session::{SessionManifest, SessionMode, SessionStore};
+use pt_redact::KeyManager;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tempfile::TempDir;
+
+static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+fn with_temp_data_dir<T>(f: impl FnOnce(&TempDir) -> T) -> T {
+    let _guard = ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let old = env::var("PROCESS_TRIAGE_DATA").ok();
+    let dir = TempDir::new().expect("create temp data dir");
+    env::set_var("PROCESS_TRIAGE_DATA", dir.path());
+
+    let result = f(&dir);
+
+    match old {
+        Some(val) => env::set_var("PROCESS_TRIAGE_DATA", val),
+        None => env::remove_var("PROCESS_TRIAGE_DATA"),
+    }
+
+    result
+}
+
+fn pt_core_fast(config: &TempDir) -> Command {
+    let mut cmd = cargo_bin_cmd!("pt-core");
+    cmd.timeout(Duration::from_secs(120));
+    cmd.env("PT_SKIP_GLOBAL_LOCK", "1");
+    cmd.env_remove("PT_BUNDLE_PASSPHRASE");
+    cmd.arg("--config").arg(config.path());
+    cmd
+}
+
+fn create_session() -> (SessionId, std::path::PathBuf) {
+    let store = SessionStore::from_env().expect("session store from env");
+    let session_id = SessionId::new();
+    let manifest = SessionManifest::new(&session_id, None, SessionMode::RobotPlan, None);
+    let handle = store.create(&manifest).expect("create session");
+    (session_id, handle.dir)
+}
+
+/// Record a dictionary for the session under the config dir's redaction key.
+fn record_tokens(config: &TempDir, session_dir: &std::path::Path) -> String {
+    let keys = KeyManager::new().unwrap();
+    keys.save(config.path().join(KEY_FILE)).unwrap();
+    let key = keys.active_key().unwrap();
+    let token = key.hash("deploy --host build-host-7", 8);
+    let entries: BTreeMap<String, String> =
+        [(token.clone(), "deploy --host build-host-7".to_string())].into();
+    dictionary::record(session_dir, &key, &entries).unwrap();
+    token
+}
+
+#[test]
+fn export_then_show_recovers_originals() {
+    with_temp_data_dir(|_| {
+        let config = TempDir::new().unwrap();
+        let (session_id, session_dir) = create_session();
+        let token = record_tokens(&config, &session_dir);
+        let out = config.path().join("export.enc");
+
+        let output = pt_core_fast(&config)
+            .args(["--format", "json", "redact", "dictionary", "export"])
+            .args(["--session", &session_id.0, "--passphrase", "responder"])
+            .arg("--out")
+            .arg(&out)
+            .output()
+            .expect("run dictionary export");
+        assert_eq!(output.status.code(), Some(ExitCode::Clean.as_i32()));
+        let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+        assert_eq!(json["command"], "redact dictionary export");
+        assert_eq!(json["dictionary"]["entries"], 1);
+        assert_eq!(json["dictionary"]["key_ids"][0], "k1");
+
+        let raw = std::fs::read(&out).unwrap();
+        assert!(pt_bundle::is_encrypted(&raw));
+        assert!(!String::from_utf8_lossy(&raw).contains("build-host-7"));
+
+        let output = pt_core_fast(&config)
+            .args(["--format", "json", "redact", "dictionary", "show"])
+            .arg(&out)
+            .args(["--passphrase", "responder", &token, "[HASH:k9:00]"])
+            .output()
+            .expect("run dictionary show");
+        assert_eq!(output.status.code(), Some(ExitCode::Clean.as_i32()));
+        let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+        assert_eq!(json["session_id"], session_id.0);
+        assert_eq!(json["entries"][&token], "deploy --host build-host-7");
+        assert_eq!(json["missing"][0], "[HASH:k9:00]");
+
+        pt_core_fast(&config)
+            .args(["redact", "dictionary", "show"])
+            .arg(&out)
+            .args(["--passphrase", "wrong"])
+            .assert()
+            .code(ExitCode::ArgsError.as_i32());
+    });
+}
+
+#[test]
+fn export_requires_a_passphrase_and_a_dictionary() {
+    with_temp_data_dir(|_| {
+        let config = TempDir::new().unwrap();
+        let (session_id, _) = create_session();
+
+        pt_core_fast(&config)
+            .args(["redact", "dictionary", "export", "--session", &session_id.0])
+            .assert()
+            .code(ExitCode::ArgsError.as_i32());
+
+        KeyManager::new()
+            .unwrap()
+            .save(config.path().join(KEY_FILE))
+            .unwrap();
+        pt_core_fast(&config)
+            .args(["redact", "dictionary", "export", "--session", &session_id.0])
+            .args(["--passphrase", "responder"])
+            .assert()
+            .code(ExitCode::ArgsError.as_i32());
+    });
+}
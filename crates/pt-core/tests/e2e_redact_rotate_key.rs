@@ -0,0 +1,158 @@
+//! E2E tests for `redact rotate-key`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use pt_common::SessionId;
+use pt_core::exit_codes::ExitCode;
+use pt_core::redact::rotation::{read_rotations, ROTATION_LOG};
+use pt_core::redact::{dictionary, KEY_FILE};
+use pt_core::session::{SessionManifest, SessionMode, SessionStore};
+use pt_redact::{FieldClass, KeyManager, RedactionEngine, RedactionPolicy};
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tempfile::TempDir;
+
+static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+fn with_temp_data_dir<T>(f: impl FnOnce(&TempDir) -> T) -> T {
+    let _guard = ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let old = env::var("PROCESS_TRIAGE_DATA").ok();
+    let dir = TempDir::new().expect("create temp data dir");
+    env::set_var("PROCESS_TRIAGE_DATA", dir.path());
+
+    let result = f(&dir);
+
+    match old {
+        Some(val) => env::set_var("PROCESS_TRIAGE_DATA", val),
+        None => env::remove_var("PROCESS_TRIAGE_DATA"),
+    }
+
+    result
+}
+
+fn pt_core_fast(config: &TempDir) -> Command {
+    let mut cmd = cargo_bin_cmd!("pt-core");
+    cmd.timeout(Duration::from_secs(120));
+    cmd.env("PT_SKIP_GLOBAL_LOCK", "1");
+    cmd.arg("--config").arg(config.path());
+    cmd
+}
+
+fn plan_path(session_dir: &Path) -> PathBuf {
+    session_dir.join("decision").join("plan.json")
+}
+
+/// Create an open session whose plan holds a hash token, with its dictionary.
+fn create_hashed_session(config: &TempDir) -> (SessionId, PathBuf, String) {
+    let keys = KeyManager::new().unwrap();
+    keys.save(config.path().join(KEY_FILE)).unwrap();
+    let engine = RedactionEngine::with_key_manager(RedactionPolicy::default(), &keys)
+        .unwrap()
+        .with_dictionary();
+    let token = engine.redact("build-host-7", FieldClass::Hostname).output;
+
+    let store = SessionStore::from_env().expect("session store from env");
+    let session_id = SessionId::new();
+    let manifest = SessionManifest::new(&session_id, None, SessionMode::RobotPlan, None);
+    let handle = store.create(&manifest).expect("create session");
+    dictionary::record(
+        &handle.dir,
+        &keys.active_key().unwrap(),
+        &engine.dictionary(),
+    )
+    .unwrap();
+    fs::create_dir_all(handle.dir.join("decision")).unwrap();
+    fs::write(
+        plan_path(&handle.dir),
+        json!({"session_id": session_id.0, "host": token}).to_string(),
+    )
+    .unwrap();
+    (session_id, handle.dir, token)
+}
+
+#[test]
+fn rotate_key_rehashes_open_sessions_and_marks_telemetry() {
+    with_temp_data_dir(|_| {
+        let config = TempDir::new().unwrap();
+        let telemetry = TempDir::new().unwrap();
+        let (session_id, session_dir, token) = create_hashed_session(&config);
+
+        let output = pt_core_fast(&config)
+            .args(["--format", "json", "redact", "rotate-key"])
+            .args(["--reason", "scheduled"])
+            .arg("--telemetry-dir")
+            .arg(telemetry.path())
+            .output()
+            .expect("run redact rotate-key");
+        assert_eq!(output.status.code(), Some(ExitCode::Clean.as_i32()));
+
+        let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+        assert_eq!(json["command"], "redact rotate-key");
+        assert_eq!(json["rotation"]["previous_key_id"], "k1");
+        assert_eq!(json["rotation"]["key_id"], "k2");
+        assert_eq!(json["rotation"]["reason"], "scheduled");
+        assert_eq!(json["rotation"]["sessions"][0]["session_id"], session_id.0);
+        assert_eq!(json["rotation"]["sessions"][0]["tokens"], 1);
+        let marker = json["telemetry_marker"].as_str().expect("marker path");
+        assert!(marker.contains("audit"));
+        assert!(Path::new(marker).exists());
+
+        let keys = KeyManager::load(config.path().join(KEY_FILE)).unwrap();
+        assert_eq!(keys.active_key_id, "k2");
+        let fresh = RedactionEngine::with_key_manager(RedactionPolicy::default(), &keys).unwrap();
+        let plan: Value =
+            serde_json::from_str(&fs::read_to_string(plan_path(&session_dir)).unwrap()).unwrap();
+        assert_ne!(plan["host"], token);
+        assert_eq!(
+            plan["host"],
+            fresh.redact("build-host-7", FieldClass::Hostname).output
+        );
+
+        assert!(config.path().join(ROTATION_LOG).exists());
+        let rotations = read_rotations(config.path()).unwrap();
+        assert_eq!(rotations.len(), 1);
+        assert_eq!(rotations[0].key_id, "k2");
+    });
+}
+
+#[test]
+fn rotate_key_without_rehash_leaves_sessions_alone() {
+    with_temp_data_dir(|_| {
+        let config = TempDir::new().unwrap();
+        let telemetry = TempDir::new().unwrap();
+        let (_, session_dir, token) = create_hashed_session(&config);
+
+        pt_core_fast(&config)
+            .args(["redact", "rotate-key", "--no-rehash"])
+            .arg("--telemetry-dir")
+            .arg(telemetry.path())
+            .assert()
+            .code(ExitCode::Clean.as_i32());
+
+        let plan: Value =
+            serde_json::from_str(&fs::read_to_string(plan_path(&session_dir)).unwrap()).unwrap();
+        assert_eq!(plan["host"], token);
+    });
+}
+
+#[test]
+fn rotate_key_requires_an_existing_key() {
+    with_temp_data_dir(|_| {
+        let config = TempDir::new().unwrap();
+        let telemetry = TempDir::new().unwrap();
+        pt_core_fast(&config)
+            .args(["redact", "rotate-key", "--telemetry-dir"])
+            .arg(telemetry.path())
+            .assert()
+            .code(ExitCode::ArgsError.as_i32());
+        assert!(!config.path().join(KEY_FILE).exists());
+    });
+}
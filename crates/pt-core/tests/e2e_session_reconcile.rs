@@ -0,0 +1,154 @@
+//! E2E tests for `session reconcile`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use pt_common::SessionId;
+use pt_core::exit_codes::ExitCode;
+use pt_core::session::{SessionContext, SessionManifest, SessionMode, SessionStore};
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tempfile::TempDir;
+
+static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+fn with_temp_data_dir<T>(f: impl FnOnce(&TempDir) -> T) -> T {
+    let _guard = ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let old = env::var("PROCESS_TRIAGE_DATA").ok();
+    let dir = TempDir::new().expect("create temp data dir");
+    env::set_var("PROCESS_TRIAGE_DATA", dir.path());
+
+    let result = f(&dir);
+
+    match old {
+        Some(val) => env::set_var("PROCESS_TRIAGE_DATA", val),
+        None => env::remove_var("PROCESS_TRIAGE_DATA"),
+    }
+
+    result
+}
+
+fn pt_core_fast() -> Command {
+    let mut cmd = cargo_bin_cmd!("pt-core");
+    cmd.timeout(Duration::from_secs(120));
+    cmd.env("PT_SKIP_GLOBAL_LOCK", "1");
+    cmd
+}
+
+/// Create a session holding an applied and verified plan.
+fn create_applied_session() -> (SessionId, PathBuf) {
+    let store = SessionStore::from_env().expect("session store from env");
+    let session_id = SessionId::new();
+    let manifest = SessionManifest::new(&session_id, None, SessionMode::RobotPlan, None);
+    let handle = store.create(&manifest).expect("create session");
+    let ctx = SessionContext::new(
+        &session_id,
+        "host-test".to_string(),
+        "run-test".to_string(),
+        None,
+    );
+    handle.write_context(&ctx).expect("write context");
+
+    let plan = json!({
+        "session_id": session_id.0,
+        "generated_at": "2026-01-05T12:00:00Z",
+        "candidates": [
+            {"pid": 4101, "start_id": "4101:100", "command_short": "node",
+             "recommended_action": "kill", "blast_radius": {"memory_mb": 512.0}},
+            {"pid": 4102, "start_id": "4102:100", "command_short": "jest",
+             "recommended_action": "kill", "blast_radius": {"memory_mb": 256.0}},
+            {"pid": 4103, "command_short": "bash", "recommended_action": "keep"},
+        ]
+    });
+    let decision = handle.dir.join("decision");
+    fs::create_dir_all(&decision).unwrap();
+    fs::write(decision.join("plan.json"), plan.to_string()).unwrap();
+
+    let action = handle.dir.join("action");
+    fs::create_dir_all(&action).unwrap();
+    fs::write(
+        action.join("outcomes.jsonl"),
+        "{\"pid\":4101,\"status\":\"success\",\"time_ms\":12}\n{\"pid\":4102,\"status\":\"success\"}\n",
+    )
+    .unwrap();
+    let verifications = json!({
+        "action_outcomes": [
+            {"target": {"pid": 4101}, "action": "kill", "outcome": "confirmed_dead",
+             "resources_freed": {"memory_mb": 512.0}},
+            {"target": {"pid": 4102}, "action": "kill", "outcome": "respawned",
+             "respawn_detected": {"pid": 5200}},
+        ]
+    });
+    fs::write(action.join("verifications.json"), verifications.to_string()).unwrap();
+
+    (session_id, handle.dir)
+}
+
+#[test]
+fn reconcile_joins_plan_outcomes_and_verification() {
+    with_temp_data_dir(|_| {
+        let (session_id, dir) = create_applied_session();
+        let output = pt_core_fast()
+            .args(["--format", "json", "session", "reconcile", "--session"])
+            .arg(&session_id.0)
+            .output()
+            .expect("run session reconcile");
+        assert!(output.status.success(), "reconcile failed: {:?}", output);
+
+        let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+        assert_eq!(json["command"], "session reconcile");
+        assert_eq!(json["session_id"], session_id.0);
+        assert_eq!(json["verified"], true);
+        assert_eq!(json["summary"]["planned"], 2);
+        assert_eq!(json["summary"]["verified"], 1);
+        assert_eq!(json["summary"]["respawned"], 1);
+        assert_eq!(json["summary"]["predicted_memory_mb"], 768.0);
+        assert_eq!(json["summary"]["recovered_memory_mb"], 512.0);
+        assert_eq!(json["actions"][0]["status"], "verified");
+        assert_eq!(json["actions"][1]["respawn_pid"], 5200);
+
+        let artifact = dir.join("action").join("reconciliation.json");
+        assert_eq!(json["artifact"], artifact.display().to_string());
+        let written: Value = serde_json::from_str(&fs::read_to_string(artifact).unwrap()).unwrap();
+        assert_eq!(written["summary"], json["summary"]);
+    });
+}
+
+#[test]
+fn reconcile_defaults_to_latest_session() {
+    with_temp_data_dir(|_| {
+        let (session_id, _) = create_applied_session();
+        let output = pt_core_fast()
+            .args(["--format", "md", "session", "reconcile"])
+            .output()
+            .expect("run session reconcile");
+        assert!(output.status.success(), "reconcile failed: {:?}", output);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(&session_id.0));
+        assert!(stdout.contains("respawned as 5200"));
+    });
+}
+
+#[test]
+fn reconcile_requires_a_plan() {
+    with_temp_data_dir(|_| {
+        let store = SessionStore::from_env().expect("session store from env");
+        let session_id = SessionId::new();
+        let manifest = SessionManifest::new(&session_id, None, SessionMode::RobotPlan, None);
+        store.create(&manifest).expect("create session");
+
+        pt_core_fast()
+            .args(["session", "reconcile", "--session"])
+            .arg(&session_id.0)
+            .assert()
+            .code(ExitCode::ArgsError.as_i32());
+    });
+}
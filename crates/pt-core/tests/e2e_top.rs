@@ -0,0 +1,80 @@
+//! E2E tests for `pt-core top`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use serde_json::Value;
+use std::time::Duration;
+
+fn pt_core_fast() -> Command {
+    let mut cmd = cargo_bin_cmd!("pt-core");
+    cmd.timeout(Duration::from_secs(120));
+    cmd.env("PT_SKIP_GLOBAL_LOCK", "1");
+    cmd
+}
+
+#[test]
+fn top_jsonl_streams_one_frame_per_refresh() {
+    let output = pt_core_fast()
+        .args([
+            "--format",
+            "jsonl",
+            "top",
+            "--iterations",
+            "2",
+            "--interval",
+            "1",
+            "--limit",
+            "5",
+        ])
+        .output()
+        .expect("run top");
+    assert!(output.status.success(), "top failed: {:?}", output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let frames: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("frame is JSON"))
+        .collect();
+    assert_eq!(frames.len(), 2);
+    for (i, frame) in frames.iter().enumerate() {
+        assert_eq!(frame["frame"], (i + 1) as u64);
+        assert!(frame["scanned"].as_u64().is_some());
+        let entries = frame["entries"].as_array().expect("entries");
+        assert!(entries.len() <= 5);
+        for entry in entries {
+            let score = entry["score"].as_f64().unwrap();
+            assert!((0.0..=1.0).contains(&score));
+            assert!(!entry["history"].as_array().unwrap().is_empty());
+        }
+    }
+
+    // Processes still listed on the second refresh carry a delta.
+    for entry in frames[1]["entries"].as_array().unwrap() {
+        if entry["history"].as_array().unwrap().len() > 1 {
+            assert!(entry["delta"].as_f64().is_some());
+        }
+    }
+}
+
+#[test]
+fn top_text_draws_header_and_columns() {
+    let output = pt_core_fast()
+        .args(["top", "--iterations", "1"])
+        .output()
+        .expect("run top");
+    assert!(output.status.success(), "top failed: {:?}", output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pt top"));
+    assert!(stdout.contains("keys: [q] quit"));
+    assert!(stdout.contains("SCORE"));
+    assert!(stdout.contains("TREND"));
+}
+
+#[test]
+fn top_rejects_out_of_range_min_score() {
+    pt_core_fast()
+        .args(["top", "--iterations", "1", "--min-score", "1.5"])
+        .assert()
+        .code(10);
+}
@@ -20,10 +20,12 @@ use pt_core::session::fleet::{
 fn host_input(id: &str, candidates: Vec<CandidateInfo>) -> HostInput {
     HostInput {
         host_id: id.to_string(),
+        group: None,
         session_id: format!("session-{}", id),
         scanned_at: "2026-02-01T12:00:00Z".to_string(),
         total_processes: 200 + candidates.len() as u32,
         candidates,
+        signature_hits: Default::default(),
     }
 }
 
@@ -35,6 +37,7 @@ fn kill_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "kill".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
@@ -46,6 +49,7 @@ fn kill_candidate_with_evalue(pid: u32, sig: &str, score: f64, e: f64) -> Candid
         recommended_action: "kill".to_string(),
         score,
         e_value: Some(e),
+        workload: None,
     }
 }
 
@@ -57,6 +61,7 @@ fn spare_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "spare".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
@@ -68,6 +73,7 @@ fn review_candidate(pid: u32, sig: &str, score: f64) -> CandidateInfo {
         recommended_action: "review".to_string(),
         score,
         e_value: None,
+        workload: None,
     }
 }
 
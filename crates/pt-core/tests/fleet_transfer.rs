@@ -51,6 +51,8 @@ fn make_sig(name: &str, confidence: f64) -> SupervisorSignature {
         priors: Default::default(),
         expectations: Default::default(),
         priority: 100,
+        rollout: Default::default(),
+        graceful_stop: None,
     }
 }
 
@@ -0,0 +1,113 @@
+//! Checks that fuzz corpus seeds stay valid inputs for the code they seed.
+//!
+//! Seeds that no longer parse only exercise the early-error path, so a format
+//! change would silently weaken the fuzz targets. The replay seeds are the
+//! built-in scenarios; regenerate them with
+//! `UPDATE_FUZZ_SEEDS=1 cargo test -p pt-core --test fuzz_corpus_seeds`.
+
+use pt_bundle::BundleManifest;
+use pt_core::decision::goal_parser::parse_goal;
+use pt_core::fleet::transfer::{validate_bundle, TransferBundle};
+use pt_core::replay::{self, ReplaySnapshot};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn corpus_dir(target: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("fuzz")
+        .join("corpus")
+        .join(target)
+}
+
+fn seed_files(target: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(corpus_dir(target))
+        .expect("read corpus dir")
+        .map(|entry| entry.expect("corpus entry").path())
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no seeds for {}", target);
+    files
+}
+
+fn builtin_scenarios() -> Vec<ReplaySnapshot> {
+    vec![
+        replay::stuck_tests(),
+        replay::memory_leak(),
+        replay::zombie_tree(),
+        replay::ci_build(),
+        replay::dev_machine(),
+        replay::mixed_workload(),
+    ]
+}
+
+#[test]
+fn replay_seeds_match_builtin_scenarios() {
+    let dir = corpus_dir("replay_snapshot");
+
+    if std::env::var("UPDATE_FUZZ_SEEDS").is_ok() {
+        for scenario in builtin_scenarios() {
+            scenario
+                .save(&dir.join(format!("scenario_{}", scenario.name)))
+                .expect("write replay seed");
+        }
+        return;
+    }
+
+    for scenario in builtin_scenarios() {
+        let path = dir.join(format!("scenario_{}", scenario.name));
+        let contents = fs::read_to_string(&path).expect("read replay seed");
+        let seed: ReplaySnapshot = serde_json::from_str(&contents).expect("parse replay seed");
+
+        assert_eq!(seed.name, scenario.name);
+        let seed_pids: Vec<u32> = seed.processes.iter().map(|p| p.pid.0).collect();
+        let scenario_pids: Vec<u32> = scenario.processes.iter().map(|p| p.pid.0).collect();
+        assert_eq!(seed_pids, scenario_pids, "{} seed is stale", scenario.name);
+        assert_eq!(seed.deep_signals.len(), scenario.deep_signals.len());
+    }
+}
+
+#[test]
+fn goal_seeds_parse() {
+    for path in seed_files("goal_parser") {
+        let goal = fs::read_to_string(&path).expect("read goal seed");
+        assert!(
+            parse_goal(&goal).is_ok(),
+            "{} does not parse",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn bundle_manifest_seeds_validate() {
+    for path in seed_files("bundle_manifest") {
+        let contents = fs::read(&path).expect("read manifest seed");
+        let manifest: BundleManifest =
+            serde_json::from_slice(&contents).expect("parse manifest seed");
+        manifest.validate().expect("manifest seed validates");
+    }
+}
+
+#[test]
+fn transfer_bundle_seeds_validate() {
+    for path in seed_files("transfer_bundle") {
+        let contents = fs::read(&path).expect("read transfer seed");
+        let bundle: TransferBundle =
+            serde_json::from_slice(&contents).expect("parse transfer seed");
+        validate_bundle(&bundle).expect("transfer seed validates");
+    }
+}
+
+#[test]
+fn toon_seeds_are_json() {
+    for path in seed_files("toon_roundtrip") {
+        let contents = fs::read(&path).expect("read toon seed");
+        assert!(
+            serde_json::from_slice::<serde_json::Value>(&contents).is_ok(),
+            "{} is not JSON",
+            path.display()
+        );
+    }
+}
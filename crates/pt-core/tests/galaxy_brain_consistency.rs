@@ -59,6 +59,14 @@ fn create_test_evidence_abandoned() -> Evidence {
         io_active: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -72,6 +80,14 @@ fn create_test_evidence_useful() -> Evidence {
         io_active: Some(true),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -1246,6 +1262,14 @@ fn test_galaxy_brain_multiple_scenarios_consistency() {
                 io_active: Some(false),
                 state_flag: None,
                 command_category: None,
+                gpu: None,
+                runtime_surprise: None,
+                net_recent_activity: None,
+                behavior_change: None,
+                swap_pressure: None,
+                io_rate: None,
+                fd_leak: None,
+                session_attachment: None,
             },
         ),
         (
@@ -1259,6 +1283,14 @@ fn test_galaxy_brain_multiple_scenarios_consistency() {
                 io_active: Some(true),
                 state_flag: None,
                 command_category: None,
+                gpu: None,
+                runtime_surprise: None,
+                net_recent_activity: None,
+                behavior_change: None,
+                swap_pressure: None,
+                io_rate: None,
+                fd_leak: None,
+                session_attachment: None,
             },
         ),
         (
@@ -1272,6 +1304,14 @@ fn test_galaxy_brain_multiple_scenarios_consistency() {
                 io_active: Some(false),
                 state_flag: None,
                 command_category: None,
+                gpu: None,
+                runtime_surprise: None,
+                net_recent_activity: None,
+                behavior_change: None,
+                swap_pressure: None,
+                io_rate: None,
+                fd_leak: None,
+                session_attachment: None,
             },
         ),
     ];
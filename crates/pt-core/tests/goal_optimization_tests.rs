@@ -1,7 +1,7 @@
 use pt_core::decision::goal_optimizer::{
     optimize_dp, optimize_greedy, optimize_greedy_with_preferences, optimize_ilp,
-    reoptimize_on_change, AlternativePlan, GoalAchievement, OptCandidate, PreferenceModel,
-    ResourceGoal,
+    optimize_multi_objective, reoptimize_on_change, AlternativePlan, GoalAchievement, OptCandidate,
+    PreferenceModel, ResourceGoal,
 };
 use pt_core::decision::goal_parser::{parse_goal, Goal};
 use pt_core::decision::goal_plan::{optimize_goal_plan, PlanCandidate, PlanConstraints};
@@ -716,6 +716,7 @@ fn preference_update_moves_toward_choice() {
     let alternatives = vec![
         AlternativePlan {
             description: "low".to_string(),
+            ids: vec![],
             action_count: 1,
             total_loss: 1.0,
             goal_achievement: vec![GoalAchievement {
@@ -728,6 +729,7 @@ fn preference_update_moves_toward_choice() {
         },
         AlternativePlan {
             description: "high".to_string(),
+            ids: vec![],
             action_count: 1,
             total_loss: 3.0,
             goal_achievement: vec![GoalAchievement {
@@ -782,3 +784,94 @@ fn preference_influences_selection() {
     assert_eq!(result_aggressive.selected[0].id, "A");
     assert_eq!(result_conservative.selected[0].id, "B");
 }
+
+fn mem_cpu_candidate(id: &str, loss: f64, memory_mb: f64, cpu_pct: f64) -> OptCandidate {
+    OptCandidate {
+        id: id.to_string(),
+        expected_loss: loss,
+        contributions: vec![memory_mb, cpu_pct],
+        blocked: false,
+        block_reason: None,
+    }
+}
+
+fn mem_cpu_goals(memory_mb: f64, cpu_pct: f64) -> Vec<ResourceGoal> {
+    vec![
+        ResourceGoal {
+            resource: "memory_mb".to_string(),
+            target: memory_mb,
+            weight: 1.0,
+        },
+        ResourceGoal {
+            resource: "cpu_pct".to_string(),
+            target: cpu_pct,
+            weight: 1.0,
+        },
+    ]
+}
+
+#[test]
+fn multi_objective_picks_cheapest_set_meeting_both_goals() {
+    // Greedy by efficiency takes C first and then needs two more kills; the
+    // exact frontier finds A+B.
+    let candidates = vec![
+        mem_cpu_candidate("A", 1.0, 3000.0, 5.0),
+        mem_cpu_candidate("B", 1.0, 1500.0, 20.0),
+        mem_cpu_candidate("C", 0.2, 1000.0, 0.0),
+        mem_cpu_candidate("D", 1.5, 1000.0, 10.0),
+    ];
+    let goals = mem_cpu_goals(4000.0, 20.0);
+
+    let result = optimize_multi_objective(&candidates, &goals);
+    assert_eq!(result.algorithm, "pareto_exact");
+    assert!(result.feasible);
+    let mut ids: Vec<&str> = result.selected.iter().map(|s| s.id.as_str()).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec!["A", "B"]);
+    assert!((result.total_loss - 2.0).abs() < 1e-9);
+
+    // Alternatives are other frontier points: cheaper ones miss a goal,
+    // dearer ones free more.
+    assert!(!result.alternatives.is_empty());
+    for alt in &result.alternatives {
+        assert_eq!(alt.ids.len(), alt.action_count);
+        assert!(alt
+            .description
+            .starts_with(&format!("Kill {}:", alt.action_count)));
+        if alt.total_loss < result.total_loss {
+            assert!(alt.goal_achievement.iter().any(|g| !g.met));
+        }
+    }
+    assert!(result.log_events.iter().any(|e| e.event == "pareto_point"));
+}
+
+#[test]
+fn multi_objective_reports_best_effort_when_infeasible() {
+    let candidates = vec![
+        mem_cpu_candidate("A", 1.0, 1000.0, 5.0),
+        mem_cpu_candidate("B", 2.0, 500.0, 5.0),
+    ];
+    let goals = mem_cpu_goals(10_000.0, 50.0);
+
+    let result = optimize_multi_objective(&candidates, &goals);
+    assert!(!result.feasible);
+    // Nothing meets the goals, so the plan maximizes achievement.
+    assert_eq!(result.selected.len(), 2);
+    assert!(result.goal_achievement.iter().all(|g| g.shortfall > 0.0));
+}
+
+#[test]
+fn multi_objective_large_sets_use_weighted_sum_sweep() {
+    let mut candidates: Vec<OptCandidate> = (0..30)
+        .map(|i| mem_cpu_candidate(&format!("P{i}"), 1.0 + i as f64 * 0.1, 100.0, 1.0))
+        .collect();
+    candidates.push(mem_cpu_candidate("big", 0.5, 2000.0, 10.0));
+    let goals = mem_cpu_goals(2500.0, 15.0);
+
+    let result = optimize_multi_objective(&candidates, &goals);
+    assert_eq!(result.algorithm, "pareto_weighted_sum");
+    assert!(result.feasible);
+    assert!(result.selected.iter().any(|s| s.id == "big"));
+    // big plus five of the cheapest small ones.
+    assert_eq!(result.selected.len(), 6);
+}
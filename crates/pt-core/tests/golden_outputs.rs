@@ -0,0 +1,98 @@
+//! Golden-output snapshot tests.
+//!
+//! Renders every canned command in every output format and compares the
+//! normalized output with the snapshots in `tests/golden/`. Also enforces the
+//! schema stability contract against `tests/golden/schemas.baseline.json`.
+//!
+//! Accept intentional changes with `PT_UPDATE_GOLDEN=1 cargo test --features
+//! test-utils --test golden_outputs`. See `docs/AGENT_CLI_CONTRACT.md`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use pt_core::golden::{
+    all_formats, canned_cases, check_golden_in, golden_dir, snapshot_name, UPDATE_ENV,
+};
+use pt_core::schema::{compare_schema_sets, generate_all_schemas, ChangeSeverity};
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+#[test]
+fn canned_commands_match_golden_output_in_every_format() {
+    let data_dir = TempDir::new().expect("tempdir");
+    let config_dir = TempDir::new().expect("tempdir");
+    let mut failures = Vec::new();
+
+    for case in canned_cases() {
+        for format in all_formats() {
+            let format_name = format.to_string();
+            let output = cargo_bin_cmd!("pt-core")
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env("PROCESS_TRIAGE_DATA", data_dir.path())
+                .env("PT_CONFIG_DIR", config_dir.path())
+                .env("PT_SKIP_GLOBAL_LOCK", "1")
+                .env("NO_COLOR", "1")
+                .args(["--format", format_name.as_str()])
+                .args(case.args)
+                .output()
+                .expect("run pt-core");
+
+            let rendered = format!(
+                "exit: {}\n{}",
+                output.status.code().unwrap_or(-1),
+                String::from_utf8_lossy(&output.stdout)
+            );
+            let name = snapshot_name(case, *format);
+            if let Err(msg) = check_golden_in(&golden_dir(), &name, &rendered) {
+                failures.push(msg);
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} golden snapshot(s) failed:\n\n{}",
+        failures.len(),
+        failures.join("\n\n")
+    );
+}
+
+#[test]
+fn schemas_have_no_breaking_changes_against_baseline() {
+    let baseline_path = golden_dir().join("schemas.baseline.json");
+    let current = generate_all_schemas();
+    let update = std::env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+    let write_baseline = || {
+        std::fs::create_dir_all(golden_dir()).expect("create golden dir");
+        let json = serde_json::to_string_pretty(&current).expect("serialize schemas");
+        std::fs::write(&baseline_path, json + "\n").expect("write schema baseline");
+    };
+
+    let Ok(raw) = std::fs::read_to_string(&baseline_path) else {
+        assert!(
+            std::env::var_os("CI").is_none() || update,
+            "missing schema baseline {}",
+            baseline_path.display()
+        );
+        write_baseline();
+        return;
+    };
+    let baseline: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(&raw).expect("parse schema baseline");
+
+    let changes = compare_schema_sets(&baseline, &current);
+    let breaking: Vec<String> = changes
+        .iter()
+        .filter(|c| c.severity == ChangeSeverity::Breaking)
+        .map(|c| format!("{}{}: {}", c.schema, c.path, c.description))
+        .collect();
+
+    if update && !changes.is_empty() {
+        write_baseline();
+        return;
+    }
+    assert!(
+        breaking.is_empty(),
+        "breaking schema changes (bump the MAJOR schema_version and re-run with {}=1):\n  {}",
+        UPDATE_ENV,
+        breaking.join("\n  ")
+    );
+}
@@ -131,6 +131,14 @@ fn to_evidence(fix: &EvidenceFixture) -> Evidence {
         io_active: fix.io_active,
         state_flag: fix.state_flag,
         command_category: fix.command_category,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -234,6 +242,7 @@ fn make_process(cpu_percent: f64) -> ProcessRecord {
         elapsed: std::time::Duration::from_secs(3600),
         source: "mock".to_string(),
         container_info: None,
+        io_rate: None,
     }
 }
 
@@ -322,6 +331,14 @@ fn test_monotonic_runtime_increases_abandoned() {
         io_active: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let long = Evidence {
@@ -41,6 +41,14 @@ fn evidence_strategy() -> impl Strategy<Value = Evidence> {
             io_active,
             state_flag: None,
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         },
     )
 }
@@ -35,6 +35,7 @@ fn make_record(
         elapsed: Duration::from_secs(3600),
         source: "test".to_string(),
         container_info: None,
+        io_rate: None,
     }
 }
 
@@ -50,6 +51,7 @@ fn make_scan(processes: Vec<ProcessRecord>) -> ScanResult {
             duration_ms: 0,
             process_count: count,
             warnings: vec![],
+            visibility: Default::default(),
         },
     }
 }
@@ -165,6 +167,14 @@ fn candidates_sorted_by_posterior_not_pid_order() {
             io_active: Some(false),
             state_flag: state_flag(proc.state),
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
         let posterior = compute_posterior(&priors, &evidence)
             .expect("posterior computation failed")
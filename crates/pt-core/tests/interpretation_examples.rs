@@ -77,6 +77,14 @@ fn example_1_bun_test_high_cpu_short_runtime() {
         io_active: Some(true),              // Active I/O
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Test)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -133,6 +141,14 @@ fn example_1_bun_test_stalled_signals_shift_posterior() {
         io_active: Some(false),              // No I/O
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Test)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -187,6 +203,14 @@ fn example_2_gemini_worker_moderate_cpu_normal_runtime() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -227,6 +251,14 @@ fn example_2_gemini_worker_long_runtime_but_active() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -271,6 +303,14 @@ fn example_3_gunicorn_server_normal_operation() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Server)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -312,6 +352,14 @@ fn example_3_gunicorn_server_even_with_ambiguous_signals() {
         io_active: Some(false),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Server)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -357,6 +405,14 @@ fn example_4_claude_process_normal_operation() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -396,6 +452,14 @@ fn example_4_claude_process_very_high_cpu() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -439,6 +503,14 @@ fn example_4_claude_process_stalled() {
         io_active: Some(false),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -481,6 +553,14 @@ fn regression_ppid1_alone_is_weak_signal() {
         io_active: Some(true),
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Agent)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -510,6 +590,14 @@ fn regression_high_cpu_is_not_abandoned() {
         io_active: Some(true),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -545,6 +633,14 @@ fn regression_daemon_category_protects_against_kill() {
         io_active: Some(false),                     // Might be idle
         state_flag: None,
         command_category: Some(category_index(CommandCategory::Daemon)),
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -105,7 +105,7 @@ fn jsonrpc_method_not_found() {
 
 #[test]
 fn jsonrpc_notification_no_response() {
-    let mut s = server();
+    let s = server();
     // Notifications have no id
     let resp = s.handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#);
     assert!(
@@ -208,8 +208,8 @@ fn tools_list_returns_all_tools() {
     let tools = result["tools"].as_array().unwrap();
     assert_eq!(
         tools.len(),
-        5,
-        "expected 5 tools (scan, explain, history, signatures, capabilities)"
+        9,
+        "expected 9 tools (scan, plan, explain, apply, sessions, history, signatures, capabilities, feedback)"
     );
 }
 
@@ -279,6 +279,9 @@ fn tools_list_includes_expected_tools() {
     assert!(names.contains(&"pt_history"));
     assert!(names.contains(&"pt_signatures"));
     assert!(names.contains(&"pt_capabilities"));
+    assert!(names.contains(&"pt_plan"));
+    assert!(names.contains(&"pt_apply"));
+    assert!(names.contains(&"pt_sessions"));
 }
 
 // ===========================================================================
@@ -696,7 +699,7 @@ fn full_mcp_conversation_flow() {
     let resp = send_rpc(&mut s, 2, "tools/list", serde_json::json!({}));
     let result = assert_success(&resp);
     let tools = result["tools"].as_array().unwrap();
-    assert_eq!(tools.len(), 5);
+    assert_eq!(tools.len(), 9);
 
     // 4. List resources
     let resp = send_rpc(&mut s, 3, "resources/list", serde_json::json!({}));
@@ -734,7 +737,7 @@ fn full_mcp_conversation_flow() {
 
 #[test]
 fn security_malformed_json_handled_gracefully() {
-    let mut s = server();
+    let s = server();
     let cases = vec![
         "",
         "null",
@@ -923,6 +926,56 @@ fn tool_capabilities_schema_is_empty() {
     );
 }
 
+#[test]
+fn tool_apply_schema_requires_session_and_has_confirm() {
+    let mut s = server();
+    let resp = send_rpc(&mut s, 1, "tools/list", serde_json::json!({}));
+    let result = assert_success(&resp);
+    let apply = result["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "pt_apply")
+        .expect("pt_apply tool not found");
+
+    let props = &apply["inputSchema"]["properties"];
+    assert!(props.get("confirm").is_some());
+    assert!(props.get("pids").is_some());
+    let required = apply["inputSchema"]["required"].as_array().unwrap();
+    assert!(required.contains(&serde_json::json!("session_id")));
+}
+
+#[test]
+fn tools_call_apply_without_targets_is_refused() {
+    let mut s = server();
+    let resp = send_rpc(
+        &mut s,
+        1,
+        "tools/call",
+        serde_json::json!({
+            "name": "pt_apply",
+            "arguments": {"session_id": "pt-20260115-120000-mcpz", "confirm": true}
+        }),
+    );
+    let result = assert_success(&resp);
+    assert_eq!(result["isError"], true);
+    let text = result["content"][0]["text"].as_str().unwrap();
+    assert!(text.contains("targets"), "unexpected message: {}", text);
+}
+
+#[test]
+fn tools_call_apply_missing_session_is_refused() {
+    let mut s = server();
+    let resp = send_rpc(
+        &mut s,
+        1,
+        "tools/call",
+        serde_json::json!({"name": "pt_apply", "arguments": {"recommended": true}}),
+    );
+    let result = assert_success(&resp);
+    assert_eq!(result["isError"], true);
+}
+
 // ===========================================================================
 // 16. Multiple Sequential Requests
 // ===========================================================================
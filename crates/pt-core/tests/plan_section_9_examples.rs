@@ -61,6 +61,14 @@ fn scenario_1_bun_test_high_cpu_18min_is_not_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None, // Would be "test" if categories were configured
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -106,6 +114,14 @@ fn scenario_1b_bun_test_stalled_shifts_toward_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -124,6 +140,14 @@ fn scenario_1b_bun_test_stalled_shifts_toward_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
     let baseline = compute_posterior(&priors, &baseline_evidence)
         .expect("baseline computation should succeed")
@@ -157,6 +181,14 @@ fn scenario_2_gemini_agent_moderate_runtime_not_abandoned() {
         net: Some(true), // Likely has network activity
         state_flag: None,
         command_category: None, // Would be "agent" if configured
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -198,6 +230,14 @@ fn scenario_2b_gemini_agent_long_orphaned_shifts_toward_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -233,6 +273,14 @@ fn scenario_3_gunicorn_server_is_useful() {
         net: Some(true), // Serving web requests
         state_flag: None,
         command_category: None, // Would be "server" if configured
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -282,6 +330,14 @@ fn scenario_4_claude_agent_high_cpu_is_useful() {
         net: Some(true), // Making API calls
         state_flag: None,
         command_category: None, // Would be "agent" if configured
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -320,6 +376,14 @@ fn scenario_4b_claude_orphaned_no_tty_shifts_toward_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
@@ -337,6 +401,14 @@ fn scenario_4b_claude_orphaned_no_tty_shifts_toward_abandoned() {
         net: Some(true),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
     let baseline = compute_posterior(&priors, &baseline_evidence)
         .expect("baseline should succeed")
@@ -368,6 +440,14 @@ fn orphan_alone_is_weak_signal() {
         net: Some(true),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     // Same process but not orphaned
@@ -415,6 +495,14 @@ fn high_cpu_alone_is_not_abandoned() {
         net: Some(false),
         state_flag: None,
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result =
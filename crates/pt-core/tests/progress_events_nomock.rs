@@ -64,6 +64,7 @@ fn validate_progress_event_schema(json: &str) -> Result<(), String> {
             "verify",
             "report",
             "bundle",
+            "export",
         ];
         if !valid_phases.contains(&phase) {
             return Err(format!("Unknown phase: {}", phase));
@@ -181,6 +182,7 @@ fn test_progress_event_all_phases() {
         (Phase::Verify, "verify"),
         (Phase::Report, "report"),
         (Phase::Bundle, "bundle"),
+        (Phase::Export, "export"),
     ];
 
     for (phase, expected_str) in phases {
@@ -43,6 +43,8 @@ fn killable_candidate() -> ProcessCandidate {
         process_state: None,
         wchan: None,
         critical_files: Vec::new(),
+        privileges: None,
+        blast_radius: None,
     }
 }
 
@@ -59,6 +59,7 @@ fn make_test_record(
         elapsed: Duration::from_secs(3600),
         source: "test".to_string(),
         container_info: None,
+        io_rate: None,
     }
 }
 
@@ -109,6 +110,10 @@ fn make_test_plan(pid: u32, uid: u32, pre_checks: Vec<PreCheck>) -> Plan {
             process_state: None,
             parent_identity: None,
             d_state_diagnostics: None,
+            impact: None,
+            systemd_unit: None,
+            zombie_reap: None,
+            graceful_stop: None,
         }],
         generated_at: Some("2026-01-15T12:00:00Z".to_string()),
     };
@@ -916,6 +921,7 @@ mod protected_processes {
                 duration_ms: 100,
                 process_count: 3,
                 warnings: vec![],
+                visibility: Default::default(),
             },
         };
 
@@ -1080,6 +1086,8 @@ mod session_safety {
             protect_ssh_chains: true,
             protect_multiplexers: true,
             protect_parent_shells: true,
+            protect_foreground_work: false,
+            foreground_idle_seconds: 300,
         };
 
         let provider = LivePreCheckProvider::new(None, config).expect("create provider");
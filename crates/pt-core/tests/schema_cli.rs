@@ -178,3 +178,62 @@ fn schema_unknown_type_returns_error_with_hint() {
         ))
         .stderr(predicate::str::contains("pt schema --list"));
 }
+
+#[test]
+fn schema_all_out_dir_writes_versioned_files() {
+    let dir = tempfile::TempDir::new().expect("tempdir");
+    let output = pt_core()
+        .args(["--format", "json", "schema", "--all", "--out-dir"])
+        .arg(dir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&output).expect("summary should be JSON");
+    let version = json["schema_version"].as_str().expect("schema_version");
+    let versioned = dir.path().join(format!("v{}", version));
+
+    let index: Value = serde_json::from_str(
+        &std::fs::read_to_string(versioned.join("index.json")).expect("index.json"),
+    )
+    .expect("index.json should be valid JSON");
+    let entries = index["schemas"].as_array().expect("schemas array");
+    for surface in [
+        "AgentPlanOutput",
+        "AgentExplainOutput",
+        "AgentDiffOutput",
+        "FleetReportOutput",
+        "ValidationReport",
+        "BundleManifest",
+        "WatchEvent",
+    ] {
+        assert!(
+            entries.iter().any(|e| e["type"] == surface),
+            "index should list {}",
+            surface
+        );
+        let schema: Value = serde_json::from_str(
+            &std::fs::read_to_string(versioned.join(format!("{}.schema.json", surface)))
+                .expect("schema file"),
+        )
+        .expect("schema file should be valid JSON");
+        assert!(
+            schema["$id"]
+                .as_str()
+                .unwrap_or("")
+                .ends_with(&format!("/v{}/{}.schema.json", version, surface)),
+            "{} should carry a versioned $id",
+            surface
+        );
+    }
+}
+
+#[test]
+fn schema_out_dir_requires_all() {
+    pt_core()
+        .args(["schema", "Plan", "--out-dir", "/tmp/pt-schema-out"])
+        .assert()
+        .failure();
+}
@@ -54,6 +54,8 @@ fn scan_pid(pid: u32) -> Vec<ProcessRecord> {
         include_kernel_threads: false,
         timeout: Some(Duration::from_secs(2)),
         progress: None,
+        incremental: None,
+        io_rate_window: None,
     };
     match quick_scan(&options) {
         Ok(result) => result.processes,
@@ -195,6 +197,10 @@ fn test_session_lifecycle_persistence_nomock() {
                 process_state: None,
                 parent_identity: None,
                 d_state_diagnostics: None,
+                impact: None,
+                systemd_unit: None,
+                zombie_reap: None,
+                graceful_stop: None,
             }],
             generated_at: Some(Utc::now().to_rfc3339()),
         };
@@ -238,6 +244,8 @@ fn test_verify_plan_with_real_process_nomock() {
                 memory_mb: 0.0,
                 cpu_pct: 0.0,
             }),
+            supervisor: None,
+            signature: None,
         }],
     };
 
@@ -264,6 +272,8 @@ fn test_verify_plan_with_real_process_nomock() {
                 memory_mb: 0.0,
                 cpu_pct: 0.0,
             }),
+            supervisor: None,
+            signature: None,
         }],
     };
 
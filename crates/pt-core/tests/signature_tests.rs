@@ -1034,6 +1034,8 @@ mod fast_path_tests {
             priors,
             expectations: Default::default(),
             priority: 100,
+            rollout: Default::default(),
+            graceful_stop: None,
         }
     }
 
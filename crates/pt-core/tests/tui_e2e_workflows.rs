@@ -63,6 +63,13 @@ fn make_row(
         top_evidence: vec![],
         confidence: None,
         plan_preview: vec![],
+        blast_radius: None,
+        io_rate: None,
+        user: None,
+        category: None,
+        runtime_secs: 0,
+        memory_bytes: 0,
+        snoozed: false,
     }
 }
 
@@ -46,6 +46,7 @@ fn sample_posterior() -> PosteriorResult {
                     abandoned: -0.6,
                     zombie: -2.4,
                 },
+                weight: None,
             },
             EvidenceTerm {
                 feature: "cpu_idle".to_string(),
@@ -55,6 +56,7 @@ fn sample_posterior() -> PosteriorResult {
                     abandoned: -0.7,
                     zombie: -2.0,
                 },
+                weight: None,
             },
         ],
     }
@@ -85,6 +87,13 @@ fn sample_row(trace: Option<String>) -> ProcessRow {
         top_evidence: vec!["PPID=1".to_string(), "Idle>2h".to_string()],
         confidence: Some("high".to_string()),
         plan_preview: vec!["SIGTERM -> SIGKILL".to_string()],
+        blast_radius: None,
+        io_rate: None,
+        user: None,
+        category: None,
+        runtime_secs: 0,
+        memory_bytes: 0,
+        snoozed: false,
     }
 }
 
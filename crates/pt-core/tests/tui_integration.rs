@@ -28,6 +28,13 @@ fn sample_row() -> ProcessRow {
         top_evidence: vec!["PPID=1".to_string(), "Idle>2h".to_string()],
         confidence: Some("high".to_string()),
         plan_preview: vec!["SIGTERM -> SIGKILL".to_string()],
+        blast_radius: None,
+        io_rate: None,
+        user: None,
+        category: None,
+        runtime_secs: 0,
+        memory_bytes: 0,
+        snoozed: false,
     }
 }
 
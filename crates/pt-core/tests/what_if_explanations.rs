@@ -26,8 +26,11 @@ fn uniform_priors() -> Priors {
         tty_beta: BetaParams::new(1.0, 1.0),
         net_beta: BetaParams::new(1.0, 1.0),
         io_active_beta: Some(BetaParams::new(1.0, 1.0)),
+        gpu_beta: None,
+        gpu_util_beta: None,
         hazard_gamma: None,
         competing_hazards: None,
+        runtime_surprise_gamma: None,
     };
     Priors {
         schema_version: "1.0.0".to_string(),
@@ -51,6 +54,13 @@ fn uniform_priors() -> Priors {
         robust_bayes: None,
         error_rate: None,
         bocpd: None,
+        evidence_weights: None,
+        net_recency: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     }
 }
 
@@ -151,6 +161,14 @@ mod evidence_contribution {
             io_active: Some(false),
             state_flag: None,
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
 
         let result = compute_posterior(&priors, &evidence).expect("posterior");
@@ -965,6 +983,14 @@ mod integration {
             io_active: Some(false),
             state_flag: None,
             command_category: None,
+            gpu: None,
+            runtime_surprise: None,
+            net_recent_activity: None,
+            behavior_change: None,
+            swap_pressure: None,
+            io_rate: None,
+            fd_leak: None,
+            session_attachment: None,
         };
 
         let result = compute_posterior(&priors, &evidence).expect("posterior");
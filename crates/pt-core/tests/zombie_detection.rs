@@ -34,6 +34,14 @@ fn zombie_state_flag_drives_zombie_posterior() {
         io_active: Some(false),
         state_flag: Some(3), // Z state
         command_category: None,
+        gpu: None,
+        runtime_surprise: None,
+        net_recent_activity: None,
+        behavior_change: None,
+        swap_pressure: None,
+        io_rate: None,
+        fd_leak: None,
+        session_attachment: None,
     };
 
     let result = compute_posterior(&priors, &evidence).expect("posterior computation failed");
@@ -0,0 +1,311 @@
+//! Following a JSONL file as it grows.
+//!
+//! [`JsonlFollower`] reads the lines already in a file (catch-up), then the
+//! lines appended after it. It copes with the ways log files get rotated:
+//!
+//! - The path is renamed away and a new file created: the rest of the old
+//!   file is read first, then the new file from its start.
+//! - The file is truncated in place (copy-truncate): reading restarts at the
+//!   beginning.
+//! - The file does not exist yet: lines are read once it appears.
+//!
+//! A final line without its newline is held back until the writer finishes
+//! it, so a reader never sees half an event.
+
+use serde::de::DeserializeOwned;
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use thiserror::Error;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Errors returned while following an event stream.
+#[derive(Debug, Error)]
+pub enum FollowError {
+    #[error("I/O error following {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("malformed event line: {source}")]
+    Parse {
+        line: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl FollowError {
+    /// A single line failed to parse; the stream itself is fine and the
+    /// caller may continue with the next line.
+    pub fn is_parse(&self) -> bool {
+        matches!(self, FollowError::Parse { .. })
+    }
+}
+
+/// Device and inode of an open file, to notice when the path is replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileId {
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileId {
+        dev: meta.dev(),
+        ino: meta.ino(),
+    })
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &Metadata) -> Option<FileId> {
+    None
+}
+
+/// Tails a JSONL file across rotation and truncation.
+#[derive(Debug)]
+pub struct JsonlFollower {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    file_id: Option<FileId>,
+    /// Bytes consumed from the open file.
+    offset: u64,
+    /// Bytes of a line whose newline has not been written yet.
+    partial: Vec<u8>,
+    start_at_end: bool,
+    poll_interval: Duration,
+}
+
+impl JsonlFollower {
+    /// Follow `path` from its first line.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            reader: None,
+            file_id: None,
+            offset: 0,
+            partial: Vec::new(),
+            start_at_end: false,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Skip the lines already in the file when it is first opened.
+    pub fn from_end(mut self) -> Self {
+        self.start_at_end = true;
+        self
+    }
+
+    /// How long the blocking reads sleep between polls (default 250 ms).
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Next complete line, if one is available now. Blank lines are skipped.
+    pub fn try_next_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if self.reader.is_none() && !self.open()? {
+                return Ok(None);
+            }
+            if let Some(line) = self.read_line()? {
+                return Ok(Some(line));
+            }
+            if !self.reopen_if_rotated()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Next complete line, waiting for one to be written.
+    pub fn next_line(&mut self) -> io::Result<String> {
+        loop {
+            if let Some(line) = self.try_next_line()? {
+                return Ok(line);
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+
+    /// Next event, if a line is available now.
+    pub fn try_next_event<T: DeserializeOwned>(&mut self) -> Result<Option<T>, FollowError> {
+        match self.try_next_line().map_err(|e| self.io_error(e))? {
+            Some(line) => parse_line(line).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Next event, waiting for one to be written.
+    pub fn next_event<T: DeserializeOwned>(&mut self) -> Result<T, FollowError> {
+        let line = self.next_line().map_err(|e| self.io_error(e))?;
+        parse_line(line)
+    }
+
+    fn io_error(&self, source: io::Error) -> FollowError {
+        FollowError::Io {
+            path: self.path.clone(),
+            source,
+        }
+    }
+
+    /// Open the file, returning `false` while it does not exist.
+    fn open(&mut self) -> io::Result<bool> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        self.file_id = file_id(&file.metadata()?);
+        let mut reader = BufReader::new(file);
+        self.offset = 0;
+        if self.start_at_end {
+            self.offset = reader.seek(SeekFrom::End(0))?;
+            self.start_at_end = false;
+        }
+        self.partial.clear();
+        self.reader = Some(reader);
+        Ok(true)
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let Some(reader) = self.reader.as_mut() else {
+            return Ok(None);
+        };
+        loop {
+            let n = reader.read_until(b'\n', &mut self.partial)?;
+            self.offset += n as u64;
+            if n == 0 || self.partial.last() != Some(&b'\n') {
+                return Ok(None);
+            }
+            let bytes = std::mem::take(&mut self.partial);
+            let line = String::from_utf8_lossy(&bytes);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                return Ok(Some(line.to_string()));
+            }
+        }
+    }
+
+    /// At the end of the open file: switch to the file now at the path if it
+    /// was replaced or truncated. Returns `true` when reading should resume.
+    fn reopen_if_rotated(&mut self) -> io::Result<bool> {
+        let meta = match fs::metadata(&self.path) {
+            Ok(meta) => meta,
+            // Renamed away and not recreated yet; keep the old handle.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        let current = file_id(&meta);
+        let replaced = current.is_some() && current != self.file_id;
+        let truncated = meta.len() < self.offset;
+        if !replaced && !truncated {
+            return Ok(false);
+        }
+        self.reader = None;
+        self.open()
+    }
+}
+
+fn parse_line<T: DeserializeOwned>(line: String) -> Result<T, FollowError> {
+    serde_json::from_str(&line).map_err(|source| FollowError::Parse { line, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event_names, Phase, ProgressEvent};
+    use std::io::Write;
+
+    fn append(path: &Path, text: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn drain(follower: &mut JsonlFollower) -> Vec<String> {
+        std::iter::from_fn(|| follower.try_next_line().unwrap()).collect()
+    }
+
+    #[test]
+    fn catches_up_then_follows_and_holds_partial_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let mut follower = JsonlFollower::new(&path);
+        assert!(drain(&mut follower).is_empty());
+
+        append(&path, "{\"n\":1}\n\n{\"n\":2}\n{\"n\":");
+        assert_eq!(drain(&mut follower), vec!["{\"n\":1}", "{\"n\":2}"]);
+        append(&path, "3}\n");
+        assert_eq!(drain(&mut follower), vec!["{\"n\":3}"]);
+    }
+
+    #[test]
+    fn from_end_skips_existing_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        append(&path, "{\"n\":1}\n");
+        let mut follower = JsonlFollower::new(&path).from_end();
+        assert!(drain(&mut follower).is_empty());
+        append(&path, "{\"n\":2}\n");
+        assert_eq!(drain(&mut follower), vec!["{\"n\":2}"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_rename_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        append(&path, "{\"n\":1}\n");
+        let mut follower = JsonlFollower::new(&path);
+        assert_eq!(drain(&mut follower), vec!["{\"n\":1}"]);
+
+        // Writer finishes the old file after the rename, then starts anew.
+        fs::rename(&path, dir.path().join("session.jsonl.1")).unwrap();
+        append(&dir.path().join("session.jsonl.1"), "{\"n\":2}\n");
+        append(&path, "{\"n\":3}\n");
+        assert_eq!(drain(&mut follower), vec!["{\"n\":2}", "{\"n\":3}"]);
+    }
+
+    #[test]
+    fn restarts_after_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        append(&path, "{\"n\":1}\n{\"n\":2}\n");
+        let mut follower = JsonlFollower::new(&path);
+        assert_eq!(drain(&mut follower).len(), 2);
+
+        fs::write(&path, "{\"n\":3}\n").unwrap();
+        assert_eq!(drain(&mut follower), vec!["{\"n\":3}"]);
+    }
+
+    #[test]
+    fn typed_events_and_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let ended = ProgressEvent::new(event_names::SESSION_ENDED, Phase::Session);
+        append(&path, &format!("not json\n{}\n", ended.to_jsonl()));
+
+        let mut follower = JsonlFollower::new(&path);
+        let err = follower.try_next_event::<ProgressEvent>().unwrap_err();
+        assert!(err.is_parse());
+        let event: ProgressEvent = follower.try_next_event().unwrap().unwrap();
+        assert!(event.is_session_end());
+        assert!(follower
+            .try_next_event::<ProgressEvent>()
+            .unwrap()
+            .is_none());
+    }
+}
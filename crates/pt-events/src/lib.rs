@@ -0,0 +1,47 @@
+//! Typed event stream for process triage.
+//!
+//! `pt-core` reports progress as JSONL (on stderr with `--format json`, and in
+//! each session's `logs/session.jsonl`, which `agent tail` streams) and keeps
+//! an append-only lifecycle log (`events.jsonl`). This crate owns the types of
+//! both streams, so the emitter and downstream tools deserialize the same
+//! schema:
+//!
+//! - [`ProgressEvent`] / [`Phase`]: pipeline progress events.
+//! - [`SessionEvent`] / [`SessionEventKind`]: session lifecycle events.
+//! - [`JsonlFollower`]: follows a JSONL file across rotation and truncation,
+//!   catching up on existing lines first.
+//!
+//! # Schema evolution
+//!
+//! Streams only grow: new fields are optional and new phases or event kinds
+//! may appear at any time. Consumers built against an older version keep
+//! working:
+//!
+//! - Unknown top-level fields of a progress event are kept in
+//!   [`ProgressEvent::extra`].
+//! - Unknown phases parse as [`Phase::Unknown`] and unknown session events as
+//!   [`SessionEventKind::Unknown`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use pt_events::{JsonlFollower, ProgressEvent};
+//!
+//! let mut follower = JsonlFollower::new("/path/to/session/logs/session.jsonl");
+//! loop {
+//!     match follower.next_event::<ProgressEvent>() {
+//!         Ok(event) if event.is_session_end() => break,
+//!         Ok(event) => println!("{:?} {}", event.phase, event.event),
+//!         Err(err) if err.is_parse() => continue,
+//!         Err(err) => panic!("{}", err),
+//!     }
+//! }
+//! ```
+
+pub mod follow;
+pub mod progress;
+pub mod session;
+
+pub use follow::{FollowError, JsonlFollower};
+pub use progress::{event_names, Phase, Progress, ProgressEvent};
+pub use session::{SessionEvent, SessionEventKind, SessionMode, SessionState};
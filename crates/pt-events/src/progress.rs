@@ -0,0 +1,170 @@
+//! Pipeline progress events.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Standard progress event names.
+pub mod event_names {
+    pub const SESSION_STARTED: &str = "session_started";
+    pub const SESSION_ENDED: &str = "session_ended";
+
+    pub const QUICK_SCAN_STARTED: &str = "quick_scan_started";
+    pub const QUICK_SCAN_PROGRESS: &str = "quick_scan_progress";
+    pub const QUICK_SCAN_COMPLETE: &str = "quick_scan_complete";
+
+    pub const DEEP_SCAN_STARTED: &str = "deep_scan_started";
+    pub const DEEP_SCAN_PROGRESS: &str = "deep_scan_progress";
+    pub const DEEP_SCAN_COMPLETE: &str = "deep_scan_complete";
+
+    pub const INFERENCE_STARTED: &str = "inference_started";
+    pub const INFERENCE_PROGRESS: &str = "inference_progress";
+    pub const INFERENCE_COMPLETE: &str = "inference_complete";
+
+    pub const DECISION_STARTED: &str = "decision_started";
+    pub const DECISION_COMPLETE: &str = "decision_complete";
+
+    pub const ACTION_STARTED: &str = "action_started";
+    pub const ACTION_COMPLETE: &str = "action_complete";
+    pub const ACTION_FAILED: &str = "action_failed";
+
+    pub const PLAN_READY: &str = "plan_ready";
+
+    pub const EXPORT_STARTED: &str = "export_started";
+    pub const EXPORT_PROGRESS: &str = "export_progress";
+    pub const EXPORT_COMPLETE: &str = "export_complete";
+}
+
+/// High-level pipeline phase for a progress event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Session,
+    QuickScan,
+    DeepScan,
+    Infer,
+    Decide,
+    Plan,
+    Apply,
+    Ui,
+    Verify,
+    Report,
+    Bundle,
+    Export,
+    /// A phase added after this version of the crate. Never emitted.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Progress counters for a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    pub current: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+/// Structured progress event for CLI/TUI consumers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressEvent {
+    pub event: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub phase: Phase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub details: HashMap<String, Value>,
+    /// Top-level fields this version does not know, kept as read.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ProgressEvent {
+    pub fn new(event: impl Into<String>, phase: Phase) -> Self {
+        Self {
+            event: event.into(),
+            timestamp: Utc::now(),
+            session_id: None,
+            phase,
+            progress: None,
+            elapsed_ms: None,
+            details: HashMap::new(),
+            extra: Map::new(),
+        }
+    }
+
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_progress(mut self, current: u64, total: Option<u64>) -> Self {
+        self.progress = Some(Progress { current, total });
+        self
+    }
+
+    pub fn with_elapsed_ms(mut self, elapsed_ms: u64) -> Self {
+        self.elapsed_ms = Some(elapsed_ms);
+        self
+    }
+
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        if let Ok(v) = serde_json::to_value(value) {
+            self.details.insert(key.into(), v);
+        }
+        self
+    }
+
+    /// Whether this event closes the session's progress stream.
+    pub fn is_session_end(&self) -> bool {
+        self.event == event_names::SESSION_ENDED
+    }
+
+    pub fn to_jsonl(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| {
+            format!(
+                r#"{{"error":"serialization_failed","event":"{}"}}"#,
+                self.event
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrips_through_jsonl() {
+        let event = ProgressEvent::new(event_names::QUICK_SCAN_PROGRESS, Phase::QuickScan)
+            .with_session_id("pt-20260115-120000-abcd")
+            .with_progress(40, Some(120))
+            .with_detail("pids_scanned", 40);
+        let parsed: ProgressEvent = serde_json::from_str(&event.to_jsonl()).unwrap();
+        assert_eq!(parsed.event, event.event);
+        assert_eq!(parsed.phase, Phase::QuickScan);
+        assert_eq!(parsed.progress, event.progress);
+        assert_eq!(parsed.details["pids_scanned"], 40);
+        assert!(parsed.extra.is_empty());
+    }
+
+    #[test]
+    fn tolerates_newer_emitters() {
+        let line = r#"{"event":"gpu_scan_started","timestamp":"2026-01-15T12:00:00Z",
+            "phase":"gpu_scan","host":"node-a","details":{"devices":2}}"#;
+        let event: ProgressEvent = serde_json::from_str(line).unwrap();
+        assert_eq!(event.phase, Phase::Unknown);
+        assert_eq!(event.extra["host"], "node-a");
+        assert_eq!(event.details["devices"], 2);
+        assert!(!event.is_session_end());
+
+        // Unknown fields survive re-serialization.
+        let again: Value = serde_json::from_str(&event.to_jsonl()).unwrap();
+        assert_eq!(again["host"], "node-a");
+    }
+}
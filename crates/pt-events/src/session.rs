@@ -0,0 +1,148 @@
+//! Session lifecycle events (`events.jsonl`).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    Created,
+    Scanning,
+    Planned,
+    Executing,
+    Completed,
+    Cancelled,
+    Failed,
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMode {
+    Interactive,
+    RobotPlan,
+    RobotApply,
+    DaemonAlert,
+    ScanOnly,
+    Export,
+}
+
+/// One entry in a session's event log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionEvent {
+    /// Position in the log, starting at 1.
+    pub seq: u64,
+    /// RFC 3339 timestamp when the event was recorded.
+    pub ts: String,
+    #[serde(flatten)]
+    pub kind: SessionEventKind,
+}
+
+/// What happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SessionEventKind {
+    /// Session directory and manifest were created.
+    Created {
+        mode: SessionMode,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_session_id: Option<String>,
+    },
+    /// Explicit lifecycle transition (scanning, executing, completed, ...).
+    StateChanged { state: SessionState },
+    /// A process scan finished.
+    Scanned { process_count: usize },
+    /// A plan was written; moves the session to `planned`.
+    Planned {
+        candidate_count: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        plan_id: Option<String>,
+    },
+    /// An action was attempted during apply.
+    ActionExecuted {
+        action_id: String,
+        pid: u32,
+        /// Outcome status as reported by apply (e.g. `success`, `identity_mismatch`).
+        status: String,
+    },
+    /// A verification outcome was recorded for an action target.
+    Verified {
+        pid: u32,
+        action: String,
+        outcome: String,
+        verified: bool,
+    },
+    /// The session was (re)labeled.
+    Labeled { label: String },
+    /// An event kind added after this version of the crate. Never emitted.
+    #[serde(other)]
+    Unknown,
+}
+
+impl SessionEventKind {
+    /// Lifecycle state this event moves the session to, if any.
+    pub fn implied_state(&self) -> Option<SessionState> {
+        match self {
+            SessionEventKind::Created { .. } => Some(SessionState::Created),
+            SessionEventKind::StateChanged { state } => Some(*state),
+            SessionEventKind::Planned { .. } => Some(SessionState::Planned),
+            SessionEventKind::Scanned { .. }
+            | SessionEventKind::ActionExecuted { .. }
+            | SessionEventKind::Verified { .. }
+            | SessionEventKind::Labeled { .. }
+            | SessionEventKind::Unknown => None,
+        }
+    }
+
+    /// One-line description for timelines.
+    pub fn summary(&self) -> String {
+        match self {
+            SessionEventKind::Created { mode, .. } => format!("created ({:?})", mode),
+            SessionEventKind::StateChanged { state } => format!("state -> {:?}", state),
+            SessionEventKind::Scanned { process_count } => {
+                format!("scanned {} processes", process_count)
+            }
+            SessionEventKind::Planned {
+                candidate_count, ..
+            } => format!("planned {} candidates", candidate_count),
+            SessionEventKind::ActionExecuted {
+                action_id,
+                pid,
+                status,
+            } => format!("action {} on pid {}: {}", action_id, pid, status),
+            SessionEventKind::Verified {
+                pid,
+                action,
+                outcome,
+                ..
+            } => format!("verified {} on pid {}: {}", action, pid, outcome),
+            SessionEventKind::Labeled { label } => format!("labeled \"{}\"", label),
+            SessionEventKind::Unknown => "unknown event".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_flat_tagged_lines() {
+        let line = r#"{"seq":2,"ts":"2026-01-15T12:00:05Z","event":"scanned","process_count":312}"#;
+        let event: SessionEvent = serde_json::from_str(line).unwrap();
+        assert_eq!(event.seq, 2);
+        assert_eq!(event.kind, SessionEventKind::Scanned { process_count: 312 });
+        assert_eq!(serde_json::to_string(&event).unwrap(), line);
+    }
+
+    #[test]
+    fn unknown_event_kinds_do_not_break_replay() {
+        let line =
+            r#"{"seq":7,"ts":"2026-01-15T12:06:00Z","event":"snapshot_pruned","bytes":4096}"#;
+        let event: SessionEvent = serde_json::from_str(line).unwrap();
+        assert_eq!(event.kind, SessionEventKind::Unknown);
+        assert_eq!(event.kind.implied_state(), None);
+        assert_eq!(event.seq, 7);
+    }
+}
@@ -8,5 +8,9 @@ pub use math::beta::*;
 pub use math::binomial;
 pub use math::dirichlet;
 pub use math::gamma::*;
+pub use math::hierarchical;
+pub use math::hurdle;
 pub use math::posterior::*;
+pub use math::sprt;
 pub use math::stable::*;
+pub use math::weibull;
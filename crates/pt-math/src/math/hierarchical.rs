@@ -0,0 +1,241 @@
+//! Hierarchical priors with partial pooling across host profiles.
+//!
+//! Hosts of the same kind (laptops, CI runners, k8s nodes) behave alike, so a
+//! host's priors are modelled as draws from a group-level hyperprior:
+//!
+//! - Group: `θ_g` estimated from the priors of every host in the profile
+//! - Host: `θ_h | θ_g ~ Beta(κ·μ_g, κ·(1 - μ_g))` (or the Gamma analogue)
+//!
+//! Shrinking a host toward its group treats the host's own parameters as
+//! pseudo-observations and the group mean as a prior worth `κ` of them:
+//!
+//! - Beta: `Beta(α_h + κ·μ_g, β_h + κ·(1 - μ_g))`
+//! - Gamma: mean `w·m_h + (1 - w)·m_g` with `w = a_h / (a_h + κ)`, shape `a_h + κ`
+//!
+//! A host with little evidence (small `α + β` or shape) lands near the group;
+//! a well-observed host keeps most of its own estimate. `κ` defaults per
+//! profile: homogeneous fleets (CI runners, k8s nodes) pool harder than
+//! laptops, whose workloads vary by owner.
+
+use serde::{Deserialize, Serialize};
+
+use super::bernoulli::BetaParams;
+
+/// Host profile that defines a pooling group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostProfile {
+    /// Developer laptop or workstation.
+    Laptop,
+    /// Ephemeral CI/CD build runner.
+    CiRunner,
+    /// Kubernetes worker node.
+    K8sNode,
+    /// Long-lived general-purpose server.
+    Server,
+}
+
+impl HostProfile {
+    /// All profiles, in a stable order.
+    pub const ALL: [HostProfile; 4] = [
+        HostProfile::Laptop,
+        HostProfile::CiRunner,
+        HostProfile::K8sNode,
+        HostProfile::Server,
+    ];
+
+    /// Stable snake_case name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HostProfile::Laptop => "laptop",
+            HostProfile::CiRunner => "ci_runner",
+            HostProfile::K8sNode => "k8s_node",
+            HostProfile::Server => "server",
+        }
+    }
+
+    /// Parse a profile name, accepting `-` or `_` separators.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "laptop" | "workstation" | "desktop" => Some(HostProfile::Laptop),
+            "ci_runner" | "ci" | "runner" => Some(HostProfile::CiRunner),
+            "k8s_node" | "k8s" | "kubernetes" => Some(HostProfile::K8sNode),
+            "server" => Some(HostProfile::Server),
+            _ => None,
+        }
+    }
+
+    /// Default pooling strength `κ` (pseudo-observations granted to the group).
+    pub fn default_strength(&self) -> f64 {
+        match self {
+            HostProfile::Laptop => 5.0,
+            HostProfile::CiRunner => 30.0,
+            HostProfile::K8sNode => 20.0,
+            HostProfile::Server => 10.0,
+        }
+    }
+}
+
+impl std::fmt::Display for HostProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Weight kept by the host estimate: `n / (n + κ)`.
+///
+/// Returns NAN for NaN or negative inputs.
+pub fn pooling_weight(effective_n: f64, strength: f64) -> f64 {
+    if effective_n.is_nan() || strength.is_nan() || effective_n < 0.0 || strength < 0.0 {
+        return f64::NAN;
+    }
+    if effective_n + strength == 0.0 {
+        return 1.0;
+    }
+    effective_n / (effective_n + strength)
+}
+
+/// Shrink a host Beta toward the group mean with strength `κ`.
+///
+/// # Example
+/// ```
+/// use pt_math::bernoulli::BetaParams;
+/// use pt_math::hierarchical::pool_beta;
+///
+/// let host = BetaParams::new(1.0, 1.0).unwrap();
+/// let group = BetaParams::new(1.0, 9.0).unwrap();
+/// let pooled = pool_beta(&host, &group, 10.0).unwrap();
+/// assert!((pooled.alpha - 2.0).abs() < 1e-12);
+/// assert!((pooled.beta - 10.0).abs() < 1e-12);
+/// ```
+pub fn pool_beta(host: &BetaParams, group: &BetaParams, strength: f64) -> Option<BetaParams> {
+    if strength.is_nan() || strength < 0.0 {
+        return None;
+    }
+    let mu = group.alpha / (group.alpha + group.beta);
+    BetaParams::new(host.alpha + strength * mu, host.beta + strength * (1.0 - mu))
+}
+
+/// Shrink a host Gamma(shape, rate) toward the group mean with strength `κ`.
+///
+/// The host shape acts as its effective sample size. Returns `(shape, rate)`,
+/// or None for invalid parameters.
+pub fn pool_gamma(
+    host_shape: f64,
+    host_rate: f64,
+    group_shape: f64,
+    group_rate: f64,
+    strength: f64,
+) -> Option<(f64, f64)> {
+    let params = [host_shape, host_rate, group_shape, group_rate];
+    if params.iter().any(|p| p.is_nan() || *p <= 0.0) || strength.is_nan() || strength < 0.0 {
+        return None;
+    }
+    let w = pooling_weight(host_shape, strength);
+    let mean = w * (host_shape / host_rate) + (1.0 - w) * (group_shape / group_rate);
+    let shape = host_shape + strength;
+    Some((shape, shape / mean))
+}
+
+/// Shrink a host probability toward the group probability.
+///
+/// `effective_n` is how many observations back the host value.
+pub fn pool_probability(host: f64, group: f64, effective_n: f64, strength: f64) -> f64 {
+    let w = pooling_weight(effective_n, strength);
+    w * host + (1.0 - w) * group
+}
+
+/// Estimate the group-level Beta from member hosts by method of moments.
+///
+/// The group mean is the average member mean. The concentration comes from
+/// the spread of member means (`μ(1-μ)/v - 1`); when the members agree
+/// exactly it falls back to their average `α + β`. Returns None for an empty
+/// group.
+pub fn fit_group_beta(members: &[BetaParams]) -> Option<BetaParams> {
+    if members.is_empty() {
+        return None;
+    }
+    let n = members.len() as f64;
+    let means: Vec<f64> = members.iter().map(|m| m.alpha / (m.alpha + m.beta)).collect();
+    let mu = means.iter().sum::<f64>() / n;
+    let var = means.iter().map(|m| (m - mu).powi(2)).sum::<f64>() / n;
+    let spread = mu * (1.0 - mu);
+
+    let concentration = if var > 0.0 && var < spread {
+        spread / var - 1.0
+    } else {
+        members.iter().map(|m| m.alpha + m.beta).sum::<f64>() / n
+    };
+    BetaParams::new(mu * concentration, (1.0 - mu) * concentration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(alpha: f64, beta: f64) -> BetaParams {
+        BetaParams::new(alpha, beta).unwrap()
+    }
+
+    #[test]
+    fn profile_names_round_trip() {
+        for profile in HostProfile::ALL {
+            assert_eq!(HostProfile::parse(profile.as_str()), Some(profile));
+        }
+        assert_eq!(HostProfile::parse("CI-Runner"), Some(HostProfile::CiRunner));
+        assert_eq!(HostProfile::parse("mainframe"), None);
+    }
+
+    #[test]
+    fn sparse_host_shrinks_toward_group() {
+        let group = params(2.0, 18.0);
+        let sparse = pool_beta(&params(1.0, 1.0), &group, 20.0).unwrap();
+        let dense = pool_beta(&params(100.0, 100.0), &group, 20.0).unwrap();
+
+        let sparse_mean = sparse.alpha / (sparse.alpha + sparse.beta);
+        let dense_mean = dense.alpha / (dense.alpha + dense.beta);
+        assert!((sparse_mean - 0.1).abs() < (dense_mean - 0.1).abs());
+        assert!(dense_mean > 0.45);
+    }
+
+    #[test]
+    fn zero_strength_keeps_host() {
+        let host = params(3.0, 4.0);
+        assert_eq!(pool_beta(&host, &params(1.0, 9.0), 0.0), Some(host));
+        assert_eq!(pool_beta(&host, &params(1.0, 9.0), -1.0), None);
+
+        let (shape, rate) = pool_gamma(2.0, 0.5, 8.0, 1.0, 0.0).unwrap();
+        assert!((shape - 2.0).abs() < 1e-12);
+        assert!((rate - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn gamma_mean_moves_by_weight() {
+        // Host mean 4, group mean 8, w = 2 / (2 + 2) = 0.5 -> mean 6.
+        let (shape, rate) = pool_gamma(2.0, 0.5, 8.0, 1.0, 2.0).unwrap();
+        assert!((shape - 4.0).abs() < 1e-12);
+        assert!((shape / rate - 6.0).abs() < 1e-12);
+        assert!(pool_gamma(0.0, 1.0, 1.0, 1.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn probability_pooling_interpolates() {
+        assert!((pool_probability(0.4, 0.2, 10.0, 10.0) - 0.3).abs() < 1e-12);
+        assert_eq!(pooling_weight(0.0, 0.0), 1.0);
+        assert!(pooling_weight(f64::NAN, 1.0).is_nan());
+    }
+
+    #[test]
+    fn fit_group_uses_member_spread() {
+        assert!(fit_group_beta(&[]).is_none());
+
+        let identical = fit_group_beta(&[params(2.0, 8.0), params(2.0, 8.0)]).unwrap();
+        assert!((identical.alpha - 2.0).abs() < 1e-12);
+        assert!((identical.beta - 8.0).abs() < 1e-12);
+
+        // Means 0.1 and 0.3: mu = 0.2, var = 0.01 -> concentration 15.
+        let spread = fit_group_beta(&[params(1.0, 9.0), params(3.0, 7.0)]).unwrap();
+        assert!((spread.alpha - 3.0).abs() < 1e-9);
+        assert!((spread.beta - 12.0).abs() < 1e-9);
+    }
+}
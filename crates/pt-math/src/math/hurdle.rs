@@ -0,0 +1,125 @@
+//! Hurdle Beta model for gated occupancy evidence.
+//!
+//! Some resources are either not held at all, or held with some level of
+//! activity: a process has no GPU context, or it has one and keeps the device
+//! busy some fraction of the time. The hurdle model splits the likelihood into
+//! two parts:
+//!
+//! - Gate: `held ~ Bernoulli(p)`, `p ~ Beta(α_g, β_g)`, scored with the
+//!   Beta-Bernoulli predictive `α_g / (α_g + β_g)`
+//! - Level: `u | held ~ Beta(α_u, β_u)` for the activity fraction `u ∈ [0, 1]`
+//!
+//! The level term only applies when the resource is held and the activity was
+//! measured. Activity is clamped to `[ACTIVITY_EPS, 1 - ACTIVITY_EPS]` so an
+//! idle holder (u = 0) gets a finite density instead of ±∞.
+
+use super::bernoulli::{log_predictive, BetaParams};
+use super::beta::log_beta_pdf;
+
+/// Clamp applied to activity fractions before evaluating the level density.
+pub const ACTIVITY_EPS: f64 = 1e-3;
+
+/// Log-probability of the gate outcome under a Beta-Bernoulli predictive.
+pub fn log_gate(gate: &BetaParams, held: bool) -> f64 {
+    log_predictive(gate, u8::from(held))
+}
+
+/// Log-density of an activity fraction given that the resource is held.
+///
+/// Returns NAN for NaN input.
+pub fn log_level(level: &BetaParams, activity: f64) -> f64 {
+    if activity.is_nan() {
+        return f64::NAN;
+    }
+    let u = activity.clamp(ACTIVITY_EPS, 1.0 - ACTIVITY_EPS);
+    log_beta_pdf(u, level.alpha, level.beta)
+}
+
+/// Full hurdle log-likelihood.
+///
+/// # Arguments
+/// * `gate` - Beta prior over the probability that the resource is held
+/// * `level` - Beta distribution of activity when held (None skips the level term)
+/// * `held` - Whether the resource is held
+/// * `activity` - Measured activity fraction, if available
+///
+/// # Example
+/// ```
+/// use pt_math::bernoulli::BetaParams;
+/// use pt_math::hurdle::log_likelihood;
+///
+/// let gate = BetaParams::new(1.0, 9.0).unwrap();
+/// let idle = BetaParams::new(1.0, 8.0).unwrap();
+/// // Not held: only the gate contributes, ln(9/10).
+/// assert!((log_likelihood(&gate, Some(&idle), false, None) - 0.9f64.ln()).abs() < 1e-12);
+/// ```
+pub fn log_likelihood(
+    gate: &BetaParams,
+    level: Option<&BetaParams>,
+    held: bool,
+    activity: Option<f64>,
+) -> f64 {
+    let gate_term = log_gate(gate, held);
+    if !held {
+        return gate_term;
+    }
+    match (level, activity) {
+        (Some(level), Some(u)) => gate_term + log_level(level, u),
+        _ => gate_term,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(alpha: f64, beta: f64) -> BetaParams {
+        BetaParams::new(alpha, beta).unwrap()
+    }
+
+    #[test]
+    fn gate_matches_predictive() {
+        let gate = params(2.0, 6.0);
+        assert!((log_gate(&gate, true) - 0.25f64.ln()).abs() < 1e-12);
+        assert!((log_gate(&gate, false) - 0.75f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn idle_holder_is_finite() {
+        let level = params(1.0, 8.0);
+        let ll = log_level(&level, 0.0);
+        assert!(ll.is_finite());
+        let ll = log_level(&params(3.0, 2.0), 1.0);
+        assert!(ll.is_finite());
+        assert!(log_level(&level, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn idle_holder_favours_idle_level() {
+        let gate = params(1.0, 10.0);
+        let idle = params(1.0, 8.0);
+        let busy = params(3.0, 2.0);
+
+        let ll_idle = log_likelihood(&gate, Some(&idle), true, Some(0.02));
+        let ll_busy = log_likelihood(&gate, Some(&busy), true, Some(0.02));
+        assert!(ll_idle > ll_busy);
+
+        let ll_idle = log_likelihood(&gate, Some(&idle), true, Some(0.8));
+        let ll_busy = log_likelihood(&gate, Some(&busy), true, Some(0.8));
+        assert!(ll_busy > ll_idle);
+    }
+
+    #[test]
+    fn missing_activity_uses_gate_only() {
+        let gate = params(1.0, 3.0);
+        let level = params(1.0, 8.0);
+        assert_eq!(
+            log_likelihood(&gate, Some(&level), true, None),
+            log_gate(&gate, true)
+        );
+        assert_eq!(
+            log_likelihood(&gate, None, true, Some(0.5)),
+            log_gate(&gate, true)
+        );
+    }
+}
@@ -6,5 +6,9 @@ pub mod beta;
 pub mod binomial;
 pub mod dirichlet;
 pub mod gamma;
+pub mod hierarchical;
+pub mod hurdle;
 pub mod posterior;
+pub mod sprt;
 pub mod stable;
+pub mod weibull;
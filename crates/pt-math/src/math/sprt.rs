@@ -0,0 +1,163 @@
+//! Wald sequential probability ratio test (SPRT).
+//!
+//! Observations `x_1, x_2, …` arrive one at a time. The test accumulates the
+//! log-likelihood ratio
+//!
+//! ```text
+//! log Λ_n = Σ_{i=1}^n [log p(x_i | H1) - log p(x_i | H0)]
+//! ```
+//!
+//! and stops as soon as it leaves the continuation region `(A, B)`:
+//!
+//! - `log Λ_n ≥ B = ln((1-β)/α)`: accept H1
+//! - `log Λ_n ≤ A = ln(β/(1-α))`: accept H0
+//!
+//! where `α` is the target Type I error (accepting H1 when H0 holds) and `β`
+//! the target Type II error. Wald's boundaries are conservative: the realized
+//! error rates are at most `α/(1-β)` and `β/(1-α)`.
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of an SPRT step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SprtDecision {
+    /// Still inside the continuation region; keep sampling.
+    Continue,
+    /// Upper boundary crossed.
+    AcceptH1,
+    /// Lower boundary crossed.
+    AcceptH0,
+}
+
+/// Log-scale decision boundaries of a Wald SPRT.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WaldBoundaries {
+    /// Lower boundary `A = ln(β/(1-α))` (negative).
+    pub lower: f64,
+    /// Upper boundary `B = ln((1-β)/α)` (positive).
+    pub upper: f64,
+}
+
+impl WaldBoundaries {
+    /// Boundaries from target error rates.
+    ///
+    /// Returns None unless both rates are in `(0, 0.5)`.
+    ///
+    /// # Example
+    /// ```
+    /// use pt_math::sprt::WaldBoundaries;
+    ///
+    /// let b = WaldBoundaries::from_error_rates(0.05, 0.05).unwrap();
+    /// assert!((b.upper - 19.0f64.ln()).abs() < 1e-12);
+    /// assert!((b.lower + 19.0f64.ln()).abs() < 1e-12);
+    /// ```
+    pub fn from_error_rates(alpha: f64, beta: f64) -> Option<Self> {
+        let valid = |p: f64| p > 0.0 && p < 0.5;
+        if !valid(alpha) || !valid(beta) {
+            return None;
+        }
+        Some(Self {
+            lower: (beta / (1.0 - alpha)).ln(),
+            upper: ((1.0 - beta) / alpha).ln(),
+        })
+    }
+
+    /// Decision for an accumulated log-likelihood ratio.
+    ///
+    /// NaN stays in the continuation region.
+    pub fn decide(&self, log_lambda: f64) -> SprtDecision {
+        if log_lambda >= self.upper {
+            SprtDecision::AcceptH1
+        } else if log_lambda <= self.lower {
+            SprtDecision::AcceptH0
+        } else {
+            SprtDecision::Continue
+        }
+    }
+}
+
+/// Running SPRT statistic.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SprtState {
+    /// Accumulated log-likelihood ratio.
+    pub log_lambda: f64,
+    /// Observations folded in.
+    pub observations: u64,
+}
+
+impl SprtState {
+    /// Fold in one observation's log-likelihood ratio and return the decision.
+    ///
+    /// Non-finite increments are ignored.
+    pub fn update(&mut self, log_lr: f64, boundaries: &WaldBoundaries) -> SprtDecision {
+        if log_lr.is_finite() {
+            self.log_lambda += log_lr;
+            self.observations += 1;
+        }
+        boundaries.decide(self.log_lambda)
+    }
+}
+
+/// Approximate expected sample size under H1 (Wald).
+///
+/// `E[N | H1] ≈ [(1-β)·B + β·A] / μ1`, where `μ1 > 0` is the mean per-sample
+/// log-likelihood ratio under H1. Returns None for non-positive `μ1`.
+pub fn expected_samples_h1(
+    boundaries: &WaldBoundaries,
+    beta: f64,
+    mean_log_lr_h1: f64,
+) -> Option<f64> {
+    if mean_log_lr_h1.is_nan() || mean_log_lr_h1 <= 0.0 {
+        return None;
+    }
+    Some(((1.0 - beta) * boundaries.upper + beta * boundaries.lower) / mean_log_lr_h1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries_reject_invalid_rates() {
+        assert!(WaldBoundaries::from_error_rates(0.0, 0.1).is_none());
+        assert!(WaldBoundaries::from_error_rates(0.1, 0.5).is_none());
+        assert!(WaldBoundaries::from_error_rates(f64::NAN, 0.1).is_none());
+    }
+
+    #[test]
+    fn asymmetric_rates_shift_boundaries() {
+        let b = WaldBoundaries::from_error_rates(0.01, 0.1).unwrap();
+        assert!((b.upper - 90.0f64.ln()).abs() < 1e-12);
+        assert!((b.lower - (0.1f64 / 0.99).ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn state_crosses_upper_after_enough_evidence() {
+        let b = WaldBoundaries::from_error_rates(0.05, 0.05).unwrap();
+        let mut state = SprtState::default();
+        assert_eq!(state.update(1.0, &b), SprtDecision::Continue);
+        assert_eq!(state.update(1.0, &b), SprtDecision::Continue);
+        assert_eq!(state.update(1.0, &b), SprtDecision::AcceptH1);
+        assert_eq!(state.observations, 3);
+    }
+
+    #[test]
+    fn state_crosses_lower_and_ignores_non_finite() {
+        let b = WaldBoundaries::from_error_rates(0.05, 0.05).unwrap();
+        let mut state = SprtState::default();
+        assert_eq!(state.update(f64::NAN, &b), SprtDecision::Continue);
+        assert_eq!(state.update(f64::INFINITY, &b), SprtDecision::Continue);
+        assert_eq!(state.observations, 0);
+        assert_eq!(state.update(-3.0, &b), SprtDecision::AcceptH0);
+    }
+
+    #[test]
+    fn expected_samples_scales_inversely_with_drift() {
+        let b = WaldBoundaries::from_error_rates(0.05, 0.05).unwrap();
+        let slow = expected_samples_h1(&b, 0.05, 0.5).unwrap();
+        let fast = expected_samples_h1(&b, 0.05, 1.0).unwrap();
+        assert!((slow / fast - 2.0).abs() < 1e-12);
+        assert!(expected_samples_h1(&b, 0.05, 0.0).is_none());
+    }
+}
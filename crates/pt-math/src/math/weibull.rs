@@ -0,0 +1,257 @@
+//! Weibull survival with a conjugate Gamma prior on the rate.
+//!
+//! Event times follow a Weibull distribution with shape `k` and rate
+//! `θ = λ^{-k}`:
+//!
+//! ```text
+//! S(t) = exp(-θ t^k)        H(t) = θ t^k
+//! ```
+//!
+//! For fixed `k` the Gamma prior `θ ~ Gamma(α0, β0)` is conjugate to
+//! right-censored data: with `d` events and exposure `E = Σ t_i^k` over all
+//! subjects (events and censored alike) the posterior is
+//! `θ | data ~ Gamma(α0 + d, β0 + E)`.
+//!
+//! Integrating `θ` out gives a closed-form posterior predictive for the
+//! residual time of a subject that has survived to age `a`:
+//!
+//! ```text
+//! P(T > a + s | T > a) = (β / (β + (a+s)^k - a^k))^α
+//! ```
+//!
+//! so credible intervals on the remaining time need no sampling. The shape is
+//! chosen by profile maximum likelihood ([`fit_weibull_shape`]).
+
+use serde::{Deserialize, Serialize};
+
+/// One subject's follow-up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalTime {
+    /// Time to the event, or to the end of follow-up if censored.
+    pub time: f64,
+    /// Whether the event was observed (false = right-censored).
+    pub event: bool,
+}
+
+impl SurvivalTime {
+    pub fn event(time: f64) -> Self {
+        Self { time, event: true }
+    }
+
+    pub fn censored(time: f64) -> Self {
+        Self { time, event: false }
+    }
+}
+
+/// Search range for the shape parameter.
+const MIN_SHAPE: f64 = 0.1;
+const MAX_SHAPE: f64 = 10.0;
+
+/// Profile maximum-likelihood estimate of the Weibull shape.
+///
+/// Maximizes `d ln k - d ln Σ t_i^k + (k-1) Σ_events ln t_i` over
+/// `k ∈ [0.1, 10]`. Returns None without at least two events at distinct
+/// positive times, where the shape is not identified.
+///
+/// # Example
+/// ```
+/// use pt_math::weibull::{fit_weibull_shape, SurvivalTime};
+///
+/// // Exact exponential quantiles: shape 1.
+/// let data: Vec<_> = (1..=200)
+///     .map(|i| SurvivalTime::event(-(1.0 - (i as f64 - 0.5) / 200.0).ln()))
+///     .collect();
+/// let k = fit_weibull_shape(&data).unwrap();
+/// assert!((k - 1.0).abs() < 0.1);
+/// ```
+pub fn fit_weibull_shape(data: &[SurvivalTime]) -> Option<f64> {
+    let data: Vec<SurvivalTime> = data
+        .iter()
+        .copied()
+        .filter(|s| s.time.is_finite() && s.time > 0.0)
+        .collect();
+    let mut event_times: Vec<f64> = data.iter().filter(|s| s.event).map(|s| s.time).collect();
+    event_times.sort_by(f64::total_cmp);
+    event_times.dedup();
+    if event_times.len() < 2 {
+        return None;
+    }
+
+    // Work in units of the longest follow-up so t^k stays well scaled.
+    let scale = data.iter().map(|s| s.time).fold(0.0, f64::max);
+    let events = data.iter().filter(|s| s.event).count() as f64;
+    let sum_log_events: f64 = data
+        .iter()
+        .filter(|s| s.event)
+        .map(|s| (s.time / scale).ln())
+        .sum();
+    let profile = |log_k: f64| {
+        let k = log_k.exp();
+        let exposure: f64 = data.iter().map(|s| (s.time / scale).powf(k)).sum();
+        events * k.ln() - events * exposure.ln() + (k - 1.0) * sum_log_events
+    };
+
+    // Golden-section search on ln k; the profile is unimodal in k.
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (MIN_SHAPE.ln(), MAX_SHAPE.ln());
+    let mut a = hi - inv_phi * (hi - lo);
+    let mut b = lo + inv_phi * (hi - lo);
+    let (mut fa, mut fb) = (profile(a), profile(b));
+    for _ in 0..100 {
+        if fa < fb {
+            lo = a;
+            a = b;
+            fa = fb;
+            b = lo + inv_phi * (hi - lo);
+            fb = profile(b);
+        } else {
+            hi = b;
+            b = a;
+            fb = fa;
+            a = hi - inv_phi * (hi - lo);
+            fa = profile(a);
+        }
+        if hi - lo < 1e-9 {
+            break;
+        }
+    }
+    Some(((lo + hi) / 2.0).exp())
+}
+
+/// Posterior over the Weibull rate for a fixed shape.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeibullGammaPosterior {
+    /// Weibull shape `k`.
+    pub shape: f64,
+    /// Gamma shape `α0 + d`.
+    pub alpha: f64,
+    /// Gamma rate `β0 + Σ t_i^k`.
+    pub beta: f64,
+}
+
+impl WeibullGammaPosterior {
+    /// Condition a `Gamma(prior_alpha, prior_beta)` prior on the data.
+    ///
+    /// Non-finite or negative times are ignored.
+    pub fn fit(data: &[SurvivalTime], shape: f64, prior_alpha: f64, prior_beta: f64) -> Self {
+        let mut alpha = prior_alpha;
+        let mut beta = prior_beta;
+        for s in data {
+            if !s.time.is_finite() || s.time < 0.0 {
+                continue;
+            }
+            beta += s.time.powf(shape);
+            if s.event {
+                alpha += 1.0;
+            }
+        }
+        Self { shape, alpha, beta }
+    }
+
+    /// Posterior-mean Weibull scale `λ = E[θ]^{-1/k}`.
+    pub fn scale(&self) -> f64 {
+        (self.alpha / self.beta).powf(-1.0 / self.shape)
+    }
+
+    /// Predictive probability of surviving `horizon` more time units, given
+    /// survival to `age`.
+    pub fn residual_survival(&self, age: f64, horizon: f64) -> f64 {
+        let age = age.max(0.0);
+        let k = self.shape;
+        let delta = (age + horizon.max(0.0)).powf(k) - age.powf(k);
+        (self.beta / (self.beta + delta)).powf(self.alpha)
+    }
+
+    /// Predictive `q`-quantile of the remaining time, given survival to `age`.
+    ///
+    /// Inverts [`Self::residual_survival`]; `q` is clamped to `[0, 1)`.
+    pub fn residual_quantile(&self, age: f64, q: f64) -> f64 {
+        let age = age.max(0.0);
+        let q = q.clamp(0.0, 1.0 - 1e-12);
+        let k = self.shape;
+        let delta = self.beta * ((1.0 - q).powf(-1.0 / self.alpha) - 1.0);
+        ((age.powf(k) + delta).powf(1.0 / k) - age).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weibull_quantiles(shape: f64, scale: f64, n: usize) -> Vec<SurvivalTime> {
+        (1..=n)
+            .map(|i| {
+                let u = (i as f64 - 0.5) / n as f64;
+                SurvivalTime::event(scale * (-(1.0 - u).ln()).powf(1.0 / shape))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shape_recovered_from_quantiles() {
+        let k = fit_weibull_shape(&weibull_quantiles(2.5, 3600.0, 400)).unwrap();
+        assert!((k - 2.5).abs() < 0.15, "k = {}", k);
+        let k = fit_weibull_shape(&weibull_quantiles(0.6, 60.0, 400)).unwrap();
+        assert!((k - 0.6).abs() < 0.05, "k = {}", k);
+    }
+
+    #[test]
+    fn shape_needs_two_distinct_events() {
+        assert!(fit_weibull_shape(&[]).is_none());
+        let data = [
+            SurvivalTime::event(10.0),
+            SurvivalTime::event(10.0),
+            SurvivalTime::censored(50.0),
+        ];
+        assert!(fit_weibull_shape(&data).is_none());
+    }
+
+    #[test]
+    fn posterior_counts_events_and_exposure() {
+        let data = [
+            SurvivalTime::event(2.0),
+            SurvivalTime::censored(3.0),
+            SurvivalTime::event(f64::NAN),
+        ];
+        let post = WeibullGammaPosterior::fit(&data, 2.0, 1.0, 1.0);
+        assert_eq!(post.alpha, 2.0);
+        assert_eq!(post.beta, 1.0 + 4.0 + 9.0);
+    }
+
+    #[test]
+    fn censoring_lengthens_predictions() {
+        let events = weibull_quantiles(1.5, 100.0, 50);
+        let mut with_censored = events.clone();
+        with_censored.extend((0..50).map(|_| SurvivalTime::censored(200.0)));
+        let a = WeibullGammaPosterior::fit(&events, 1.5, 1.0, 1.0);
+        let b = WeibullGammaPosterior::fit(&with_censored, 1.5, 1.0, 1.0);
+        assert!(b.residual_quantile(0.0, 0.5) > a.residual_quantile(0.0, 0.5));
+    }
+
+    #[test]
+    fn quantile_inverts_residual_survival() {
+        let post = WeibullGammaPosterior {
+            shape: 1.7,
+            alpha: 12.0,
+            beta: 4.0e5,
+        };
+        for &age in &[0.0, 50.0, 500.0] {
+            for &q in &[0.05, 0.5, 0.95] {
+                let s = post.residual_quantile(age, q);
+                let surv = post.residual_survival(age, s);
+                assert!((surv - (1.0 - q)).abs() < 1e-9, "age {} q {}", age, q);
+            }
+        }
+    }
+
+    #[test]
+    fn increasing_hazard_shortens_residual_with_age() {
+        let post = WeibullGammaPosterior {
+            shape: 3.0,
+            alpha: 50.0,
+            beta: 50.0 * 1000f64.powi(3),
+        };
+        assert!(post.residual_quantile(900.0, 0.5) < post.residual_quantile(100.0, 0.5));
+        assert!((post.scale() - 1000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,159 @@
+//! Raw-capture allowlist for forensic exports.
+//!
+//! The forensic profile exists for support tickets, where a reviewer needs
+//! some raw evidence. Rather than capturing everything, a bundle declares up
+//! front which environment variable names and which command line arguments
+//! may pass through unredacted. Everything else still goes through the normal
+//! redaction rules, so the allowlist is an upper bound on the raw data a
+//! bundle can contain.
+
+use crate::{ExportProfile, RedactionError, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Declared set of values that may be captured raw under the forensic profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CaptureAllowlist {
+    /// Environment variable names whose values may be captured raw (exact match).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_names: Vec<String>,
+
+    /// Regex patterns; command line arguments matching one are captured raw.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cmdline_patterns: Vec<String>,
+}
+
+impl CaptureAllowlist {
+    /// Create an empty allowlist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add environment variable names.
+    pub fn with_env_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.env_names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add command line argument patterns.
+    pub fn with_cmdline_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.cmdline_patterns
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether nothing is allowlisted.
+    pub fn is_empty(&self) -> bool {
+        self.env_names.is_empty() && self.cmdline_patterns.is_empty()
+    }
+
+    /// Check that the allowlist is well-formed for the given export profile.
+    ///
+    /// Only the forensic profile may carry a non-empty allowlist, env names
+    /// must be valid variable names, and every pattern must compile.
+    pub fn validate(&self, profile: ExportProfile) -> Result<()> {
+        if !self.is_empty() && profile != ExportProfile::Forensic {
+            return Err(RedactionError::PolicyError(format!(
+                "capture allowlist requires the forensic profile (got {})",
+                profile
+            )));
+        }
+        for name in &self.env_names {
+            if name.is_empty() || name.contains('=') || name.contains('\0') {
+                return Err(RedactionError::PolicyError(format!(
+                    "invalid environment variable name in allowlist: {:?}",
+                    name
+                )));
+            }
+        }
+        self.compile().map(|_| ())
+    }
+
+    /// Compile into a matcher.
+    pub fn compile(&self) -> Result<CompiledAllowlist> {
+        let patterns = self
+            .cmdline_patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p).map_err(|e| {
+                    RedactionError::PatternError(format!("allowlist pattern {:?}: {}", p, e))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(CompiledAllowlist {
+            env_names: self.env_names.clone(),
+            patterns,
+        })
+    }
+}
+
+/// Compiled form of a [`CaptureAllowlist`].
+#[derive(Debug, Clone, Default)]
+pub struct CompiledAllowlist {
+    env_names: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl CompiledAllowlist {
+    /// Whether the value of this environment variable may be captured raw.
+    pub fn allows_env(&self, name: &str) -> bool {
+        self.env_names.iter().any(|n| n == name)
+    }
+
+    /// Whether this command line argument may be captured raw.
+    pub fn allows_arg(&self, arg: &str) -> bool {
+        self.patterns.iter().any(|re| re.is_match(arg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        let allowlist = CaptureAllowlist::new()
+            .with_env_names(["RUST_LOG", "NODE_ENV"])
+            .with_cmdline_patterns(["^--port=\\d+$"]);
+        let compiled = allowlist.compile().unwrap();
+
+        assert!(compiled.allows_env("RUST_LOG"));
+        assert!(!compiled.allows_env("rust_log"));
+        assert!(!compiled.allows_env("AWS_SECRET_ACCESS_KEY"));
+        assert!(compiled.allows_arg("--port=8080"));
+        assert!(!compiled.allows_arg("--token=abc"));
+    }
+
+    #[test]
+    fn test_validate_requires_forensic() {
+        let allowlist = CaptureAllowlist::new().with_env_names(["RUST_LOG"]);
+        assert!(allowlist.validate(ExportProfile::Forensic).is_ok());
+        assert!(allowlist.validate(ExportProfile::Safe).is_err());
+
+        // An empty allowlist is valid everywhere.
+        assert!(CaptureAllowlist::new()
+            .validate(ExportProfile::Minimal)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_entries() {
+        let bad_name = CaptureAllowlist::new().with_env_names(["A=B"]);
+        assert!(bad_name.validate(ExportProfile::Forensic).is_err());
+
+        let bad_pattern = CaptureAllowlist::new().with_cmdline_patterns(["("]);
+        assert!(matches!(
+            bad_pattern.validate(ExportProfile::Forensic),
+            Err(RedactionError::PatternError(_))
+        ));
+    }
+}
@@ -190,6 +190,16 @@ impl KeyManager {
         KeyMaterial::from_base64(&entry.key_material, &self.active_key_id)
     }
 
+    /// Get the material of a key by ID, active or deprecated.
+    pub fn key(&self, key_id: &str) -> Result<KeyMaterial> {
+        let entry = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| RedactionError::KeyError(format!("key '{}' not found", key_id)))?;
+
+        KeyMaterial::from_base64(&entry.key_material, key_id)
+    }
+
     /// Rotate to a new key.
     pub fn rotate(&mut self) -> Result<()> {
         // Mark current key as deprecated
@@ -267,4 +277,15 @@ mod tests {
         let restored = KeyMaterial::from_base64(&encoded, "test").unwrap();
         assert_eq!(original.hash("test", 8), restored.hash("test", 8));
     }
+
+    #[test]
+    fn test_key_lookup_after_rotation() {
+        let mut manager = KeyManager::new().unwrap();
+        let first = manager.active_key().unwrap().hash("test", 8);
+        manager.rotate().unwrap();
+
+        assert_eq!(manager.key("k1").unwrap().hash("test", 8), first);
+        assert_eq!(manager.key("k2").unwrap().key_id, "k2");
+        assert!(manager.key("k3").is_err());
+    }
 }
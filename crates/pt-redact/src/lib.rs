@@ -30,6 +30,7 @@
 //! ```
 
 pub mod action;
+pub mod allowlist;
 pub mod canonicalize;
 pub mod detect;
 pub mod engine;
@@ -39,6 +40,7 @@ pub mod hash;
 pub mod policy;
 
 pub use action::Action;
+pub use allowlist::{CaptureAllowlist, CompiledAllowlist};
 pub use canonicalize::{Canonicalizer, CANONICALIZATION_VERSION};
 pub use detect::{SecretDetector, SecretType};
 pub use engine::{RedactedValue, RedactionEngine};
@@ -4,6 +4,7 @@
 //! including custom rules and detection patterns.
 
 use crate::{Action, FieldClass};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
@@ -68,7 +69,7 @@ fn default_entropy_threshold() -> f64 {
 }
 
 /// Export profile for controlling redaction level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ExportProfile {
     /// Aggregate stats only - for public sharing.
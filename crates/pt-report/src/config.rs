@@ -77,6 +77,15 @@ pub struct ReportSections {
     /// Actions and outcomes section.
     #[serde(default = "default_true")]
     pub actions: bool,
+    /// Process dependency graph section.
+    #[serde(default = "default_true")]
+    pub dependencies: bool,
+    /// Projected before/after system state section.
+    #[serde(default = "default_true")]
+    pub simulation: bool,
+    /// File-descriptor leak trends section.
+    #[serde(default = "default_true")]
+    pub fd_leaks: bool,
     /// Telemetry charts section.
     #[serde(default = "default_true")]
     pub telemetry: bool,
@@ -96,6 +105,9 @@ impl Default for ReportSections {
             candidates: true,
             evidence: true,
             actions: true,
+            dependencies: true,
+            simulation: true,
+            fd_leaks: true,
             telemetry: true,
             galaxy_brain: false,
         }
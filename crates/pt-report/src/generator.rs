@@ -26,6 +26,15 @@ pub struct ReportData {
     pub evidence: Option<EvidenceSection>,
     /// Actions section.
     pub actions: Option<ActionsSection>,
+    /// Dependencies section.
+    #[serde(default)]
+    pub dependencies: Option<DependenciesSection>,
+    /// Simulation section.
+    #[serde(default)]
+    pub simulation: Option<SimulationSection>,
+    /// FD leaks section.
+    #[serde(default)]
+    pub fd_leaks: Option<FdLeaksSection>,
     /// Galaxy-brain section.
     pub galaxy_brain: Option<GalaxyBrainSection>,
 }
@@ -91,6 +100,9 @@ impl ReportGenerator {
             candidates: None, // Would be populated from telemetry
             evidence: None,
             actions: None,
+            dependencies: None,
+            simulation: None,
+            fd_leaks: None,
             galaxy_brain: if self.config.galaxy_brain {
                 Some(GalaxyBrainSection::default())
             } else {
@@ -435,6 +447,50 @@ impl ReportGenerator {
             window.addEventListener('resize', () => scoreChart.resize());
         }}
 
+        // Dependency graph (force layout; candidates highlighted)
+        if (typeof echarts !== 'undefined' && REPORT_DATA.dependencies) {{
+            const deps = REPORT_DATA.dependencies;
+            const edgeStyles = {{
+                parent: {{ type: 'solid', color: '#6b7280' }},
+                process_group: {{ type: 'dashed', color: '#3b82f6' }},
+                session: {{ type: 'dotted', color: '#8b5cf6' }},
+                unix_socket: {{ type: 'solid', color: '#f59e0b', width: 2 }},
+            }};
+            const depChart = echarts.init(document.getElementById('dependency-graph'));
+            depChart.setOption({{
+                tooltip: {{
+                    formatter: p => p.dataType === 'edge'
+                        ? p.data.kind + (p.data.label ? ': ' + p.data.label : '')
+                        : p.data.name + ' (' + p.data.state + ', ' + formatMem(p.data.rss_bytes / 1048576) + ')'
+                }},
+                series: [{{
+                    type: 'graph',
+                    layout: 'force',
+                    roam: true,
+                    draggable: true,
+                    force: {{ repulsion: 200, edgeLength: 80 }},
+                    edgeSymbol: ['none', 'arrow'],
+                    label: {{ show: true, position: 'right', formatter: p => p.data.name }},
+                    data: deps.nodes.map(n => ({{
+                        id: String(n.pid),
+                        name: n.comm + ' ' + n.pid,
+                        state: n.state,
+                        rss_bytes: n.rss_bytes,
+                        symbolSize: n.candidate ? 24 : 14,
+                        itemStyle: {{ color: n.candidate ? '#ef4444' : '#9ca3af' }},
+                    }})),
+                    links: deps.edges.map(e => ({{
+                        source: String(e.from),
+                        target: String(e.to),
+                        kind: e.kind,
+                        label: e.label,
+                        lineStyle: edgeStyles[e.kind] || {{}},
+                    }})),
+                }}]
+            }});
+            window.addEventListener('resize', () => depChart.resize());
+        }}
+
         // Initialize KaTeX if available
         if (typeof katex !== 'undefined') {{
             document.querySelectorAll('.math').forEach(el => {{
@@ -486,6 +542,16 @@ impl ReportGenerator {
         if sections.actions && data.actions.is_some() {
             buttons.push(r#"<button class="tab-btn" data-tab="actions">Actions</button>"#);
         }
+        if sections.dependencies && data.dependencies.is_some() {
+            buttons
+                .push(r#"<button class="tab-btn" data-tab="dependencies">Dependencies</button>"#);
+        }
+        if sections.simulation && data.simulation.is_some() {
+            buttons.push(r#"<button class="tab-btn" data-tab="simulation">Simulation</button>"#);
+        }
+        if sections.fd_leaks && data.fd_leaks.is_some() {
+            buttons.push(r#"<button class="tab-btn" data-tab="fd-leaks">FD Leaks</button>"#);
+        }
         if sections.galaxy_brain && data.galaxy_brain.is_some() {
             buttons
                 .push(r#"<button class="tab-btn" data-tab="galaxy-brain">Galaxy Brain</button>"#);
@@ -518,6 +584,21 @@ impl ReportGenerator {
                 contents.push(self.generate_actions_tab(actions));
             }
         }
+        if sections.dependencies {
+            if let Some(ref deps) = data.dependencies {
+                contents.push(self.generate_dependencies_tab(deps));
+            }
+        }
+        if sections.simulation {
+            if let Some(ref sim) = data.simulation {
+                contents.push(self.generate_simulation_tab(sim));
+            }
+        }
+        if sections.fd_leaks {
+            if let Some(ref leaks) = data.fd_leaks {
+                contents.push(self.generate_fd_leaks_tab(leaks));
+            }
+        }
         if sections.galaxy_brain {
             if let Some(ref gb) = data.galaxy_brain {
                 contents.push(self.generate_galaxy_brain_tab(gb));
@@ -774,6 +855,7 @@ impl ReportGenerator {
                         <td class="px-4 py-2"><span class="badge {}">{}</span></td>
                         <td class="px-4 py-2">{}</td>
                         <td class="px-4 py-2">{}</td>
+                        <td class="px-4 py-2">{}</td>
                     </tr>"#,
                     a.timestamp.format("%H:%M:%S"),
                     a.pid,
@@ -782,6 +864,7 @@ impl ReportGenerator {
                     html_escape(&a.recommendation),
                     a.status_class(),
                     a.status_text(),
+                    a.memory_predicted_formatted().unwrap_or_default(),
                     a.memory_freed_formatted().unwrap_or_default(),
                     a.user_feedback.as_deref().unwrap_or("-"),
                 )
@@ -805,7 +888,7 @@ impl ReportGenerator {
         </div>
         <div class="card stat-card">
             <div class="stat-value">{memory_freed}</div>
-            <div class="stat-label">Memory Freed</div>
+            <div class="stat-label">Memory Freed{predicted_note}</div>
         </div>
     </div>
 
@@ -818,6 +901,7 @@ impl ReportGenerator {
                     <th class="px-4 py-2 text-left">Command</th>
                     <th class="px-4 py-2 text-left">Recommendation</th>
                     <th class="px-4 py-2 text-left">Status</th>
+                    <th class="px-4 py-2 text-left">Predicted</th>
                     <th class="px-4 py-2 text-left">Memory Freed</th>
                     <th class="px-4 py-2 text-left">Feedback</th>
                 </tr>
@@ -832,10 +916,215 @@ impl ReportGenerator {
             successful = actions.summary.successful,
             failed = actions.summary.failed,
             memory_freed = actions.summary.memory_freed_formatted(),
+            predicted_note = if actions.summary.total_memory_predicted > 0 {
+                format!(
+                    " of {} predicted",
+                    actions.summary.memory_predicted_formatted()
+                )
+            } else {
+                String::new()
+            },
             rows_html = rows_html,
         )
     }
 
+    fn generate_dependencies_tab(&self, deps: &DependenciesSection) -> String {
+        format!(
+            r##"<section id="tab-dependencies" class="tab-content">
+    <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-6">
+        <div class="card stat-card">
+            <div class="stat-value">{nodes}</div>
+            <div class="stat-label">Processes</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value">{parent}</div>
+            <div class="stat-label">Parent/Child Links</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value">{membership}</div>
+            <div class="stat-label">Group/Session Links</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value">{unix}</div>
+            <div class="stat-label">Unix Socket Links</div>
+        </div>
+    </div>
+
+    <div class="card">
+        <div class="flex justify-between items-center mb-4">
+            <h3 class="text-lg font-semibold">Process Dependencies</h3>
+            {truncation_notice}
+        </div>
+        <div id="dependency-graph" style="height: 600px;"></div>
+    </div>
+</section>"##,
+            nodes = deps.nodes.len(),
+            parent = deps.edge_count("parent"),
+            membership = deps.edge_count("process_group") + deps.edge_count("session"),
+            unix = deps.edge_count("unix_socket"),
+            truncation_notice = if deps.truncated {
+                r#"<span class="text-sm" style="color: var(--text-secondary)">Graph truncated at node limit</span>"#
+            } else {
+                ""
+            },
+        )
+    }
+
+    fn generate_simulation_tab(&self, sim: &SimulationSection) -> String {
+        let row = |label: &str, before: String, after: String| {
+            format!(
+                r#"<tr><td>{}</td><td class="font-mono">{}</td><td class="font-mono">{}</td></tr>"#,
+                html_escape(label),
+                html_escape(&before),
+                html_escape(&after)
+            )
+        };
+        let mut rows = vec![
+            row(
+                "Available memory",
+                format!(
+                    "{:.0} MB ({:.1}%)",
+                    sim.before.memory_available_mb, sim.before.memory_available_pct
+                ),
+                format!(
+                    "{:.0} MB ({:.1}%)",
+                    sim.after.memory_available_mb, sim.after.memory_available_pct
+                ),
+            ),
+            row(
+                "Swap used",
+                format!(
+                    "{:.0} MB ({})",
+                    sim.before.swap_used_mb, sim.before.swap_pressure
+                ),
+                format!(
+                    "{:.0} MB ({})",
+                    sim.after.swap_used_mb, sim.after.swap_pressure
+                ),
+            ),
+            row(
+                "Load (1 min)",
+                format!(
+                    "{:.2} ({:.2}/core)",
+                    sim.before.load1, sim.before.load_per_core
+                ),
+                format!(
+                    "{:.2} ({:.2}/core)",
+                    sim.after.load1, sim.after.load_per_core
+                ),
+            ),
+        ];
+        let mb =
+            |v: Option<f64>| v.map_or_else(|| "unlimited".to_string(), |v| format!("{:.0} MB", v));
+        for cg in &sim.cgroups {
+            rows.push(row(
+                &format!("Headroom {}", cg.cgroup),
+                mb(cg.headroom_before_mb),
+                mb(cg.headroom_after_mb),
+            ));
+        }
+        let assumptions: String = sim
+            .assumptions
+            .iter()
+            .map(|a| format!("<li>{}</li>", html_escape(a)))
+            .collect();
+
+        format!(
+            r##"<section id="tab-simulation" class="tab-content">
+    <div class="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6">
+        <div class="card stat-card">
+            <div class="stat-value">{targets}</div>
+            <div class="stat-label">Processes in Kill Set</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value text-green-500">{freed:.0} MB</div>
+            <div class="stat-label">Projected Memory Freed</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value">{goal}</div>
+            <div class="stat-label">Goal</div>
+        </div>
+    </div>
+
+    <div class="card">
+        <h3 class="text-lg font-semibold mb-4">Before / After</h3>
+        <table class="w-full text-sm">
+            <thead><tr><th></th><th>Before</th><th>After</th></tr></thead>
+            <tbody>
+                {rows}
+            </tbody>
+        </table>
+    </div>
+
+    <div class="card mt-4">
+        <h3 class="text-lg font-semibold mb-4">Assumptions</h3>
+        <ul class="text-sm" style="color: var(--text-secondary)">{assumptions}</ul>
+    </div>
+</section>"##,
+            targets = sim.targets,
+            freed = sim.memory_freed_mb(),
+            goal = match sim.goal_achievable() {
+                Some(true) => "Achievable",
+                Some(false) => "Not achievable",
+                None => "N/A",
+            },
+            rows = rows.join("\n                "),
+            assumptions = assumptions,
+        )
+    }
+
+    fn generate_fd_leaks_tab(&self, section: &FdLeaksSection) -> String {
+        let rows: String = section
+            .leaks
+            .iter()
+            .map(|leak| {
+                format!(
+                    r#"<tr><td class="font-mono">{}</td><td>{}</td><td class="font-mono">{} &rarr; {}</td><td class="font-mono">{:+.1}</td><td class="font-mono">{:.2}</td><td class="font-mono">{:.2}</td><td>{}</td></tr>"#,
+                    leak.pid,
+                    html_escape(&leak.command),
+                    leak.first_fd_count,
+                    leak.last_fd_count,
+                    leak.slope_per_hour,
+                    leak.r_squared,
+                    leak.confidence,
+                    if leak.likely_leak {
+                        "likely leak"
+                    } else {
+                        "possible"
+                    }
+                )
+            })
+            .collect();
+
+        format!(
+            r##"<section id="tab-fd-leaks" class="tab-content">
+    <div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+        <div class="card stat-card">
+            <div class="stat-value text-red-500">{likely}</div>
+            <div class="stat-label">Likely FD Leaks</div>
+        </div>
+        <div class="card stat-card">
+            <div class="stat-value">{total}</div>
+            <div class="stat-label">Growing FD Trends</div>
+        </div>
+    </div>
+
+    <div class="card">
+        <h3 class="text-lg font-semibold mb-4">Open FD Trends Across Shadow Runs</h3>
+        <table class="w-full text-sm">
+            <thead><tr><th>PID</th><th>Command</th><th>FDs</th><th>Per hour</th><th>R&sup2;</th><th>Confidence</th><th>Verdict</th></tr></thead>
+            <tbody>
+                {rows}
+            </tbody>
+        </table>
+    </div>
+</section>"##,
+            likely = section.likely_count(),
+            total = section.leaks.len(),
+            rows = rows,
+        )
+    }
+
     fn generate_galaxy_brain_tab(&self, gb: &GalaxyBrainSection) -> String {
         let factors_html: String = gb
             .factors
@@ -1016,6 +1305,9 @@ mod tests {
             candidates: None,
             evidence: None,
             actions: None,
+            dependencies: None,
+            simulation: None,
+            fd_leaks: None,
             galaxy_brain: None,
         };
         let html = generator.generate(data).unwrap();
@@ -1057,6 +1349,9 @@ mod tests {
             candidates: None,
             evidence: None,
             actions: None,
+            dependencies: None,
+            simulation: None,
+            fd_leaks: None,
             galaxy_brain: None,
         };
         let html = generator.generate(data).unwrap();
@@ -1076,10 +1371,121 @@ mod tests {
             candidates: None,
             evidence: None,
             actions: None,
+            dependencies: None,
+            simulation: None,
+            fd_leaks: None,
             galaxy_brain: Some(GalaxyBrainSection::default()),
         };
         let html = generator.generate(data).unwrap();
         assert!(html.contains("Galaxy Brain"));
         assert!(html.contains("Bayesian"));
     }
+
+    #[test]
+    fn test_dependencies_section() {
+        let generator = ReportGenerator::default_config();
+        let deps = DependenciesSection {
+            roots: vec![200],
+            nodes: vec![DependencyNode {
+                pid: 200,
+                ppid: 1,
+                comm: "make".to_string(),
+                state: "S".to_string(),
+                rss_bytes: 1024,
+                candidate: true,
+            }],
+            edges: vec![],
+            truncated: true,
+        };
+        let data = ReportData {
+            config: ReportConfig::default(),
+            generated_at: Utc::now(),
+            generator_version: "test".to_string(),
+            overview: None,
+            candidates: None,
+            evidence: None,
+            actions: None,
+            dependencies: Some(deps),
+            simulation: None,
+            fd_leaks: None,
+            galaxy_brain: None,
+        };
+        let html = generator.generate(data).unwrap();
+        assert!(html.contains(r#"data-tab="dependencies""#));
+        assert!(html.contains("dependency-graph"));
+        assert!(html.contains("Graph truncated"));
+    }
+
+    #[test]
+    fn test_simulation_section() {
+        let generator = ReportGenerator::default_config();
+        let sim = SimulationSection {
+            targets: 1,
+            before: SimulatedState {
+                memory_available_mb: 1024.0,
+                swap_pressure: "high".to_string(),
+                ..Default::default()
+            },
+            after: SimulatedState {
+                memory_available_mb: 3072.0,
+                swap_pressure: "low".to_string(),
+                ..Default::default()
+            },
+            assumptions: vec!["shared pages <counted>".to_string()],
+            ..Default::default()
+        };
+        let data = ReportData {
+            config: ReportConfig::default(),
+            generated_at: Utc::now(),
+            generator_version: "test".to_string(),
+            overview: None,
+            candidates: None,
+            evidence: None,
+            actions: None,
+            dependencies: None,
+            simulation: Some(sim),
+            fd_leaks: None,
+            galaxy_brain: None,
+        };
+        let html = generator.generate(data).unwrap();
+        assert!(html.contains(r#"data-tab="simulation""#));
+        assert!(html.contains("2048 MB"));
+        assert!(html.contains("&lt;counted&gt;"));
+    }
+
+    #[test]
+    fn test_fd_leaks_section() {
+        let generator = ReportGenerator::default_config();
+        let leaks = FdLeaksSection {
+            leaks: vec![FdLeakRow {
+                pid: 4242,
+                command: "<leaky>".to_string(),
+                samples: 8,
+                first_fd_count: 40,
+                last_fd_count: 124,
+                slope_per_hour: 24.0,
+                confidence: 0.74,
+                likely_leak: true,
+                ..Default::default()
+            }],
+        };
+        let data = ReportData {
+            config: ReportConfig::default(),
+            generated_at: Utc::now(),
+            generator_version: "test".to_string(),
+            overview: None,
+            candidates: None,
+            evidence: None,
+            actions: None,
+            dependencies: None,
+            simulation: None,
+            fd_leaks: Some(leaks),
+            galaxy_brain: None,
+        };
+        let html = generator.generate(data).unwrap();
+        assert!(html.contains(r#"data-tab="fd-leaks""#));
+        assert!(html.contains("40 &rarr; 124"));
+        assert!(html.contains("&lt;leaky&gt;"));
+        assert!(html.contains("likely leak"));
+    }
 }
@@ -16,6 +16,9 @@
 //! - Candidates: Interactive table of candidate processes
 //! - Evidence: Expandable evidence ledgers with factor weights
 //! - Actions: Timeline of actions taken and outcomes
+//! - Dependencies: Interactive process dependency graph around candidates
+//! - Simulation: Projected system state before/after the plan
+//! - FD leaks: Candidates whose open-fd count keeps growing across runs
 //! - Telemetry: Interactive charts of resource usage
 //! - Galaxy-brain: Mathematical derivation of Bayesian inference
 //!
@@ -34,6 +34,12 @@ pub struct ActionRow {
     pub process_state_after: Option<String>,
     /// Memory freed in bytes.
     pub memory_freed_bytes: Option<u64>,
+    /// Memory the plan predicted the action would free, in bytes.
+    #[serde(default)]
+    pub memory_predicted_bytes: Option<u64>,
+    /// Whether the target came back after the action.
+    #[serde(default)]
+    pub respawned: bool,
     /// Error message if failed.
     pub error_message: Option<String>,
     /// User feedback label.
@@ -47,7 +53,9 @@ pub struct ActionRow {
 impl ActionRow {
     /// Get status badge text.
     pub fn status_text(&self) -> &'static str {
-        if !self.action_attempted {
+        if self.respawned {
+            "Respawned"
+        } else if !self.action_attempted {
             "Skipped"
         } else if self.action_successful {
             "Success"
@@ -58,7 +66,9 @@ impl ActionRow {
 
     /// Get CSS class for status badge.
     pub fn status_class(&self) -> &'static str {
-        if !self.action_attempted {
+        if self.respawned {
+            "bg-yellow-100 text-yellow-800 dark:bg-yellow-900 dark:text-yellow-200"
+        } else if !self.action_attempted {
             "bg-gray-100 text-gray-800 dark:bg-gray-700 dark:text-gray-200"
         } else if self.action_successful {
             "bg-green-100 text-green-800 dark:bg-green-900 dark:text-green-200"
@@ -82,17 +92,12 @@ impl ActionRow {
 
     /// Format memory freed.
     pub fn memory_freed_formatted(&self) -> Option<String> {
-        self.memory_freed_bytes.map(|bytes| {
-            if bytes >= 1_073_741_824 {
-                format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
-            } else if bytes >= 1_048_576 {
-                format!("{:.0} MB", bytes as f64 / 1_048_576.0)
-            } else if bytes >= 1024 {
-                format!("{:.0} KB", bytes as f64 / 1024.0)
-            } else {
-                format!("{} B", bytes)
-            }
-        })
+        self.memory_freed_bytes.map(format_bytes)
+    }
+
+    /// Format predicted memory.
+    pub fn memory_predicted_formatted(&self) -> Option<String> {
+        self.memory_predicted_bytes.map(format_bytes)
     }
 }
 
@@ -118,6 +123,12 @@ pub struct ActionsSummary {
     pub skipped: usize,
     /// Total memory freed in bytes.
     pub total_memory_freed: u64,
+    /// Total memory the plan predicted, in bytes.
+    #[serde(default)]
+    pub total_memory_predicted: u64,
+    /// Actions whose target respawned.
+    #[serde(default)]
+    pub respawned: usize,
     /// Actions with user feedback.
     pub with_feedback: usize,
     /// Correct feedback count.
@@ -133,6 +144,62 @@ impl ActionsSection {
         Self { actions, summary }
     }
 
+    /// Build from a session's `action/reconciliation.json` document.
+    ///
+    /// Each reconciled action becomes a row; respawned targets count as
+    /// attempted but not successful.
+    pub fn from_reconciliation(doc: &serde_json::Value) -> Option<Self> {
+        let timestamp = doc
+            .get("generated_at")
+            .and_then(|v| v.as_str())
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now);
+        let mb_to_bytes = |mb: f64| (mb.max(0.0) * 1_048_576.0) as u64;
+
+        let rows = doc
+            .get("actions")?
+            .as_array()?
+            .iter()
+            .filter_map(|a| {
+                let str_field = |key: &str| a.get(key).and_then(|v| v.as_str()).map(String::from);
+                let status = a.get("status")?.as_str()?;
+                let action = str_field("action").unwrap_or_default();
+                let predicted = a
+                    .get("predicted_memory_mb")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                let recovered = a
+                    .get("recovered_memory_mb")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                Some(ActionRow {
+                    timestamp,
+                    pid: a.get("pid")?.as_u64()? as u32,
+                    start_id: str_field("start_id").unwrap_or_default(),
+                    cmd: str_field("command").unwrap_or_default(),
+                    recommendation: action.clone(),
+                    decision: action.clone(),
+                    decision_source: "plan".to_string(),
+                    action_type: Some(action),
+                    action_attempted: !matches!(status, "blocked" | "not_applied"),
+                    action_successful: matches!(status, "verified" | "applied"),
+                    signal_sent: None,
+                    signal_response: str_field("outcome"),
+                    process_state_after: str_field("verification"),
+                    memory_freed_bytes: (recovered > 0.0).then(|| mb_to_bytes(recovered)),
+                    memory_predicted_bytes: (predicted > 0.0).then(|| mb_to_bytes(predicted)),
+                    respawned: status == "respawned",
+                    error_message: str_field("reason"),
+                    user_feedback: None,
+                    feedback_ts: None,
+                    score: 0.0,
+                })
+            })
+            .collect();
+        Some(Self::new(rows))
+    }
+
     /// Get actions filtered by status.
     pub fn successful_actions(&self) -> Vec<&ActionRow> {
         self.actions
@@ -159,12 +226,18 @@ impl ActionsSummary {
             failed: 0,
             skipped: 0,
             total_memory_freed: 0,
+            total_memory_predicted: 0,
+            respawned: 0,
             with_feedback: 0,
             correct_feedback: 0,
             incorrect_feedback: 0,
         };
 
         for action in actions {
+            summary.total_memory_predicted += action.memory_predicted_bytes.unwrap_or(0);
+            if action.respawned {
+                summary.respawned += 1;
+            }
             if !action.action_attempted {
                 summary.skipped += 1;
             } else if action.action_successful {
@@ -210,15 +283,61 @@ impl ActionsSummary {
 
     /// Format total memory freed.
     pub fn memory_freed_formatted(&self) -> String {
-        let bytes = self.total_memory_freed;
-        if bytes >= 1_073_741_824 {
-            format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
-        } else if bytes >= 1_048_576 {
-            format!("{:.0} MB", bytes as f64 / 1_048_576.0)
-        } else if bytes >= 1024 {
-            format!("{:.0} KB", bytes as f64 / 1024.0)
-        } else {
-            format!("{} B", bytes)
-        }
+        format_bytes(self.total_memory_freed)
+    }
+
+    /// Format total predicted memory.
+    pub fn memory_predicted_formatted(&self) -> String {
+        format_bytes(self.total_memory_predicted)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1_073_741_824 {
+        format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
+    } else if bytes >= 1_048_576 {
+        format!("{:.0} MB", bytes as f64 / 1_048_576.0)
+    } else if bytes >= 1024 {
+        format!("{:.0} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_rows_from_reconciliation() {
+        let doc = serde_json::json!({
+            "generated_at": "2026-01-05T12:00:00Z",
+            "actions": [
+                {"pid": 10, "command": "node", "action": "kill", "status": "verified",
+                 "outcome": "success", "verification": "confirmed_dead",
+                 "predicted_memory_mb": 512.0, "recovered_memory_mb": 512.0},
+                {"pid": 11, "command": "jest", "action": "kill", "status": "respawned",
+                 "predicted_memory_mb": 256.0, "recovered_memory_mb": 0.0},
+                {"pid": 12, "command": "vim", "action": "pause", "status": "blocked",
+                 "reason": "has tty", "predicted_memory_mb": 0.0, "recovered_memory_mb": 0.0},
+            ]
+        });
+        let section = ActionsSection::from_reconciliation(&doc).unwrap();
+        assert_eq!(section.actions.len(), 3);
+        assert_eq!(section.actions[0].status_text(), "Success");
+        assert_eq!(section.actions[1].status_text(), "Respawned");
+        assert_eq!(section.actions[2].status_text(), "Skipped");
+        assert_eq!(section.actions[2].error_message.as_deref(), Some("has tty"));
+
+        let summary = &section.summary;
+        assert_eq!(
+            (summary.successful, summary.failed, summary.skipped),
+            (1, 1, 1)
+        );
+        assert_eq!(summary.respawned, 1);
+        assert_eq!(summary.total_memory_freed, 512 * 1_048_576);
+        assert_eq!(summary.memory_predicted_formatted(), "768 MB");
+
+        assert!(ActionsSection::from_reconciliation(&serde_json::json!({})).is_none());
     }
 }
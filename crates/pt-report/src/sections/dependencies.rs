@@ -0,0 +1,95 @@
+//! Dependencies section data.
+//!
+//! Mirrors the `inference/dependency_graph.json` session artifact written by
+//! `pt agent explain --show-dependencies`.
+
+use serde::{Deserialize, Serialize};
+
+/// Process node in the dependency graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyNode {
+    /// Process ID.
+    pub pid: u32,
+    /// Parent process ID.
+    pub ppid: u32,
+    /// Command name.
+    pub comm: String,
+    /// Process state code.
+    #[serde(default)]
+    pub state: String,
+    /// Resident set size in bytes.
+    #[serde(default)]
+    pub rss_bytes: u64,
+    /// Whether this process is a triage candidate.
+    #[serde(default)]
+    pub candidate: bool,
+}
+
+/// Relationship between two processes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Source PID.
+    pub from: u32,
+    /// Target PID.
+    pub to: u32,
+    /// Edge kind (parent, process_group, session, unix_socket).
+    pub kind: String,
+    /// Socket path or inode for unix-socket edges.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Dependencies section: process subtree and IPC edges around candidates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependenciesSection {
+    /// Candidate PIDs the graph was built around.
+    #[serde(default)]
+    pub roots: Vec<u32>,
+    /// Graph nodes.
+    pub nodes: Vec<DependencyNode>,
+    /// Graph edges.
+    pub edges: Vec<DependencyEdge>,
+    /// Whether the graph was truncated at the node limit.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl DependenciesSection {
+    /// Parse from the dependency graph artifact JSON.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Number of edges of a given kind.
+    pub fn edge_count(&self, kind: &str) -> usize {
+        self.edges.iter().filter(|e| e.kind == kind).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_artifact_and_counts_edges() {
+        let json = r#"{
+            "schema_version": "1.0.0",
+            "roots": [200],
+            "nodes": [
+                {"pid": 100, "ppid": 1, "comm": "bash", "state": "S", "rss_bytes": 1024, "cpu_percent": 0.0, "candidate": false},
+                {"pid": 200, "ppid": 100, "comm": "make", "state": "S", "rss_bytes": 2048, "cpu_percent": 0.0, "candidate": true}
+            ],
+            "edges": [
+                {"from": 100, "to": 200, "kind": "parent"},
+                {"from": 200, "to": 100, "kind": "session"}
+            ],
+            "truncated": false
+        }"#;
+        let section = DependenciesSection::from_json(json).unwrap();
+        assert_eq!(section.roots, vec![200]);
+        assert_eq!(section.nodes.len(), 2);
+        assert!(section.nodes[1].candidate);
+        assert_eq!(section.edge_count("parent"), 1);
+        assert_eq!(section.edge_count("unix_socket"), 0);
+    }
+}
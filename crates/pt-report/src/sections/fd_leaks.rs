@@ -0,0 +1,108 @@
+//! File-descriptor leak section data.
+//!
+//! Collects the `fd_leak` objects that `pt agent plan` attaches to candidates
+//! whose open-fd count has been tracked across shadow observations.
+
+use serde::{Deserialize, Serialize};
+
+/// Fitted fd-count trend of one candidate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FdLeakRow {
+    /// Process ID.
+    pub pid: u32,
+    /// Short command name.
+    #[serde(default)]
+    pub command: String,
+    /// Samples in the fit.
+    pub samples: usize,
+    /// Time covered by the samples, in hours.
+    pub span_hours: f64,
+    /// Open fds at the first sample.
+    pub first_fd_count: u32,
+    /// Open fds at the last sample.
+    pub last_fd_count: u32,
+    /// Fitted growth in fds per hour.
+    pub slope_per_hour: f64,
+    /// Goodness of the linear fit.
+    pub r_squared: f64,
+    /// Leak confidence in [0, 1].
+    pub confidence: f64,
+    /// Whether the trend is reported as a likely leak.
+    pub likely_leak: bool,
+    /// Priors bucket name (none, possible, likely).
+    #[serde(default)]
+    pub bucket: Option<String>,
+}
+
+/// FD leak section: candidates with a growing fd count.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FdLeaksSection {
+    /// Trends with a non-zero leak confidence, most confident first.
+    pub leaks: Vec<FdLeakRow>,
+}
+
+impl FdLeaksSection {
+    /// Extract from a plan document, if any candidate's trend looks like a leak.
+    pub fn from_plan(plan: &serde_json::Value) -> Option<Self> {
+        let mut leaks: Vec<FdLeakRow> = plan
+            .get("candidates")?
+            .as_array()?
+            .iter()
+            .filter_map(|candidate| {
+                let mut row: FdLeakRow =
+                    serde_json::from_value(candidate.get("fd_leak")?.clone()).ok()?;
+                row.pid = candidate.get("pid")?.as_u64()? as u32;
+                row.command = candidate
+                    .get("command_short")
+                    .and_then(|c| c.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                Some(row)
+            })
+            .filter(|row| row.confidence > 0.0)
+            .collect();
+        if leaks.is_empty() {
+            return None;
+        }
+        leaks.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        Some(Self { leaks })
+    }
+
+    /// Number of likely leaks.
+    pub fn likely_count(&self) -> usize {
+        self.leaks.iter().filter(|l| l.likely_leak).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plan_fd_leaks() {
+        let trend = |confidence: f64, likely: bool| {
+            serde_json::json!({
+                "samples": 8, "span_hours": 3.5, "first_fd_count": 40,
+                "last_fd_count": 124, "slope_per_hour": 24.0, "r_squared": 0.99,
+                "monotonic_fraction": 1.0, "confidence": confidence,
+                "likely_leak": likely, "bucket": "likely", "summary": "likely fd leak"
+            })
+        };
+        let plan = serde_json::json!({
+            "candidates": [
+                {"pid": 10, "command_short": "steady", "fd_leak": trend(0.0, false)},
+                {"pid": 11, "command_short": "maybe", "fd_leak": trend(0.4, false)},
+                {"pid": 12, "command_short": "leaky", "fd_leak": trend(0.8, true)},
+                {"pid": 13, "command_short": "untracked"}
+            ]
+        });
+        let section = FdLeaksSection::from_plan(&plan).unwrap();
+        assert_eq!(section.leaks.len(), 2);
+        assert_eq!(section.leaks[0].pid, 12);
+        assert_eq!(section.leaks[0].command, "leaky");
+        assert_eq!(section.likely_count(), 1);
+
+        let quiet = serde_json::json!({"candidates": [{"pid": 10, "fd_leak": trend(0.0, false)}]});
+        assert!(FdLeaksSection::from_plan(&quiet).is_none());
+    }
+}
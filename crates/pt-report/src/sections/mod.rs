@@ -2,12 +2,18 @@
 
 pub mod actions;
 pub mod candidates;
+pub mod dependencies;
 pub mod evidence;
+pub mod fd_leaks;
 pub mod galaxy_brain;
 pub mod overview;
+pub mod simulation;
 
 pub use actions::{ActionRow, ActionsSection};
 pub use candidates::{CandidateRow, CandidatesSection};
+pub use dependencies::{DependenciesSection, DependencyEdge, DependencyNode};
 pub use evidence::{EvidenceFactor, EvidenceLedger, EvidenceSection};
+pub use fd_leaks::{FdLeakRow, FdLeaksSection};
 pub use galaxy_brain::GalaxyBrainSection;
 pub use overview::OverviewSection;
+pub use simulation::{CgroupHeadroom, SimulatedState, SimulationSection};
@@ -0,0 +1,125 @@
+//! Simulation section data.
+//!
+//! Mirrors the `simulation` object that `pt agent plan --simulate` writes to
+//! `decision/plan.json`: projected host state before and after the kill set.
+
+use serde::{Deserialize, Serialize};
+
+/// Host-level figures on one side of the simulation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulatedState {
+    /// Available memory in MB.
+    pub memory_available_mb: f64,
+    /// Available memory as a percentage of total.
+    #[serde(default)]
+    pub memory_available_pct: f64,
+    /// Swap in use in MB.
+    #[serde(default)]
+    pub swap_used_mb: f64,
+    /// Swap pressure level (none, low, moderate, high).
+    #[serde(default)]
+    pub swap_pressure: String,
+    /// 1-minute load average.
+    #[serde(default)]
+    pub load1: f64,
+    /// Load average per core.
+    #[serde(default)]
+    pub load_per_core: f64,
+}
+
+/// Before/after memory of one cgroup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CgroupHeadroom {
+    /// Cgroup path.
+    pub cgroup: String,
+    /// Memory limit in MB (None when unlimited).
+    #[serde(default)]
+    pub limit_mb: Option<f64>,
+    /// Memory usage before, in MB.
+    pub usage_before_mb: f64,
+    /// Memory usage after, in MB.
+    pub usage_after_mb: f64,
+    /// Headroom before, in MB.
+    #[serde(default)]
+    pub headroom_before_mb: Option<f64>,
+    /// Headroom after, in MB.
+    #[serde(default)]
+    pub headroom_after_mb: Option<f64>,
+    /// Kill-set processes in this cgroup.
+    #[serde(default)]
+    pub processes: usize,
+}
+
+/// Simulation section: projected system state after the plan is applied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationSection {
+    /// Processes in the kill set.
+    #[serde(default)]
+    pub targets: usize,
+    /// State before the plan.
+    pub before: SimulatedState,
+    /// Projected state after the plan.
+    pub after: SimulatedState,
+    /// Cgroups the kill set lives in.
+    #[serde(default)]
+    pub cgroups: Vec<CgroupHeadroom>,
+    /// Goal optimizer outcome, when the plan had a goal.
+    #[serde(default)]
+    pub goal: Option<serde_json::Value>,
+    /// Caveats of the projection.
+    #[serde(default)]
+    pub assumptions: Vec<String>,
+}
+
+impl SimulationSection {
+    /// Extract from a plan document, if it was built with `--simulate`.
+    pub fn from_plan(plan: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(plan.get("simulation")?.clone()).ok()
+    }
+
+    /// Memory freed by the plan, in MB.
+    pub fn memory_freed_mb(&self) -> f64 {
+        self.after.memory_available_mb - self.before.memory_available_mb
+    }
+
+    /// Whether the plan's goal is achievable (None without a goal).
+    pub fn goal_achievable(&self) -> Option<bool> {
+        self.goal.as_ref()?.get("achievable")?.as_bool()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plan_simulation() {
+        let plan = serde_json::json!({
+            "candidates": [],
+            "simulation": {
+                "targets": 2,
+                "before": {"memory_available_mb": 1024.0, "memory_available_pct": 6.3,
+                           "swap_used_mb": 3072.0, "swap_pressure": "high",
+                           "load1": 3.5, "load_per_core": 0.88},
+                "after": {"memory_available_mb": 3584.0, "memory_available_pct": 21.9,
+                          "swap_used_mb": 2048.0, "swap_pressure": "moderate",
+                          "load1": 1.5, "load_per_core": 0.38},
+                "delta": {"memory_freed_mb": 2560.0},
+                "cgroups": [{"cgroup": "/system.slice/ci.service", "limit_mb": 4096.0,
+                             "usage_before_mb": 4000.0, "usage_after_mb": 3000.0,
+                             "headroom_before_mb": 96.0, "headroom_after_mb": 1096.0,
+                             "processes": 1}],
+                "goal": {"goal": "free 2GB RAM", "achievable": true},
+                "assumptions": ["supervised processes are not respawned"]
+            }
+        });
+        let section = SimulationSection::from_plan(&plan).unwrap();
+        assert_eq!(section.targets, 2);
+        assert_eq!(section.memory_freed_mb(), 2560.0);
+        assert_eq!(section.after.swap_pressure, "moderate");
+        assert_eq!(section.cgroups[0].headroom_after_mb, Some(1096.0));
+        assert_eq!(section.goal_achievable(), Some(true));
+
+        assert!(SimulationSection::from_plan(&serde_json::json!({"candidates": []})).is_none());
+    }
+}
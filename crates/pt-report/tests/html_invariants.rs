@@ -67,6 +67,9 @@ fn full_test_report_data(config: ReportConfig) -> ReportData {
         candidates: Some(test_candidates()),
         evidence: Some(test_evidence()),
         actions: Some(test_actions()),
+        dependencies: None,
+        simulation: None,
+        fd_leaks: None,
         galaxy_brain: if config.galaxy_brain {
             Some(GalaxyBrainSection::default())
         } else {
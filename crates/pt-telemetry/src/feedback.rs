@@ -0,0 +1,404 @@
+//! Feedback labels on past decisions.
+//!
+//! A feedback label says a decision was wrong: a process was killed (or
+//! recommended for killing) that should have been left alone, a *false
+//! positive*, or a process that should have been flagged was missed, a
+//! *false negative*.
+//!
+//! Labels are rows of the `outcomes` table with `decision_source =
+//! "feedback"`, `user_feedback = "incorrect"` and the `feedback_kind` /
+//! `feedback_actor` columns set. Each label is written to its own Parquet
+//! file so that repeated feedback against one session never replaces an
+//! earlier file, and [`read_feedback`] scans the table back for the
+//! calibration and prior-update pipelines.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, BooleanArray, Float32Array, Int32Array, Int64Array, RecordBatch, StringArray,
+    TimestampMicrosecondArray,
+};
+use chrono::{DateTime, Utc};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::schema::{outcomes_schema, TableName};
+use crate::writer::{BatchedWriter, WriteError, WriterConfig};
+
+/// `decision_source` value of feedback rows in the `outcomes` table.
+pub const FEEDBACK_DECISION_SOURCE: &str = "feedback";
+
+/// Errors from feedback storage.
+#[derive(Error, Debug)]
+pub enum FeedbackError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+
+    #[error("write error: {0}")]
+    Write(#[from] WriteError),
+}
+
+/// What was wrong about a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackKind {
+    /// The process was killed or flagged but was still useful.
+    FalsePositive,
+    /// The process was left alone but should have been flagged.
+    FalseNegative,
+}
+
+impl FeedbackKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedbackKind::FalsePositive => "false_positive",
+            FeedbackKind::FalseNegative => "false_negative",
+        }
+    }
+
+    /// Parse a kind name, accepting the spellings used on the command line.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "false_positive" | "fp" | "wrong_kill" => Some(FeedbackKind::FalsePositive),
+            "false_negative" | "fn" | "missed" => Some(FeedbackKind::FalseNegative),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FeedbackKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One feedback label against a session and PID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackRecord {
+    pub session_id: String,
+    pub pid: u32,
+    pub start_id: String,
+    pub kind: FeedbackKind,
+    /// Who gave the feedback: `user` or `agent`.
+    pub actor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    /// Command of the labelled process, as recorded in the session.
+    pub cmd: String,
+    /// Recommendation the session made for the process (`kill`, `keep`, ...).
+    pub recommendation: String,
+    /// Posterior probability of abandonment at plan time, if recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_abandoned: Option<f64>,
+    pub score: f64,
+    pub proc_type: String,
+}
+
+impl FeedbackRecord {
+    /// Whether the label says the process was actually abandoned.
+    pub fn actually_abandoned(&self) -> bool {
+        self.kind == FeedbackKind::FalseNegative
+    }
+}
+
+/// Build an `outcomes` table batch from feedback records.
+pub fn feedback_batch(records: &[FeedbackRecord]) -> Result<RecordBatch, FeedbackError> {
+    let ts: Vec<i64> = records
+        .iter()
+        .map(|r| r.recorded_at.timestamp_micros())
+        .collect();
+    let decisions: Vec<&str> = records
+        .iter()
+        .map(|r| match r.kind {
+            FeedbackKind::FalsePositive => "kill",
+            FeedbackKind::FalseNegative => "keep",
+        })
+        .collect();
+    let nulls_str = || StringArray::from(vec![None::<&str>; records.len()]);
+    let nulls_bool = || BooleanArray::from(vec![None::<bool>; records.len()]);
+
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.session_id.as_str()),
+        )),
+        Arc::new(TimestampMicrosecondArray::from(ts.clone()).with_timezone("UTC")),
+        Arc::new(Int32Array::from_iter_values(
+            records.iter().map(|r| r.pid as i32),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.start_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.recommendation.as_str()),
+        )),
+        Arc::new(StringArray::from(decisions)),
+        Arc::new(StringArray::from(vec![
+            FEEDBACK_DECISION_SOURCE;
+            records.len()
+        ])),
+        // Action
+        Arc::new(nulls_str()),
+        Arc::new(BooleanArray::from(
+            records
+                .iter()
+                .map(|r| r.kind == FeedbackKind::FalsePositive)
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(nulls_bool()),
+        Arc::new(nulls_str()),
+        Arc::new(nulls_str()),
+        // Verification
+        Arc::new(nulls_bool()),
+        Arc::new(Int32Array::from(vec![None::<i32>; records.len()])),
+        Arc::new(nulls_bool()),
+        // Result
+        Arc::new(nulls_str()),
+        Arc::new(Int64Array::from(vec![None::<i64>; records.len()])),
+        Arc::new(nulls_str()),
+        // Feedback
+        Arc::new(StringArray::from(vec!["incorrect"; records.len()])),
+        Arc::new(TimestampMicrosecondArray::from(ts).with_timezone("UTC")),
+        Arc::new(StringArray::from(
+            records
+                .iter()
+                .map(|r| r.note.as_deref())
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.kind.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.actor.as_str()),
+        )),
+        Arc::new(Float32Array::from(
+            records
+                .iter()
+                .map(|r| r.p_abandoned.map(|p| p as f32))
+                .collect::<Vec<_>>(),
+        )),
+        // Context
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.cmd.as_str()),
+        )),
+        Arc::new(nulls_str()),
+        Arc::new(Float32Array::from_iter_values(
+            records.iter().map(|r| r.score as f32),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.proc_type.as_str()),
+        )),
+    ];
+
+    Ok(RecordBatch::try_new(Arc::new(outcomes_schema()), columns)?)
+}
+
+/// Write one feedback label to the `outcomes` table under `base_dir`.
+pub fn write_feedback(
+    base_dir: &Path,
+    host_id: &str,
+    record: &FeedbackRecord,
+) -> Result<PathBuf, FeedbackError> {
+    // The writer names files after the last `-` segment of the session ID;
+    // a per-label suffix keeps every label in its own file.
+    let file_key = format!(
+        "{}-fb{}",
+        record.session_id,
+        record.recorded_at.timestamp_micros()
+    );
+    let config = WriterConfig::new(base_dir.to_path_buf(), file_key, host_id.to_string());
+    let mut writer = BatchedWriter::new(TableName::Outcomes, Arc::new(outcomes_schema()), config);
+    writer.write(feedback_batch(std::slice::from_ref(record))?)?;
+    Ok(writer.close()?)
+}
+
+/// Read every feedback label from the `outcomes` table under `base_dir`,
+/// oldest first.
+///
+/// Outcome files without feedback columns (older schema) are skipped.
+pub fn read_feedback(base_dir: &Path) -> Result<Vec<FeedbackRecord>, FeedbackError> {
+    let mut files = Vec::new();
+    collect_parquet_files(&base_dir.join(TableName::Outcomes.as_str()), &mut files)?;
+
+    let mut records = Vec::new();
+    for path in files {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path)?)?.build()?;
+        for batch in reader {
+            records.extend(records_from_batch(&batch?));
+        }
+    }
+    records.sort_by_key(|r| r.recorded_at);
+    Ok(records)
+}
+
+fn collect_parquet_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), std::io::Error> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_parquet_files(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext == "parquet") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn records_from_batch(batch: &RecordBatch) -> Vec<FeedbackRecord> {
+    fn strings<'a>(batch: &'a RecordBatch, name: &str) -> Option<&'a StringArray> {
+        batch.column_by_name(name)?.as_any().downcast_ref()
+    }
+    fn floats<'a>(batch: &'a RecordBatch, name: &str) -> Option<&'a Float32Array> {
+        batch.column_by_name(name)?.as_any().downcast_ref()
+    }
+
+    let (
+        Some(kind),
+        Some(actor),
+        Some(session_id),
+        Some(start_id),
+        Some(cmd),
+        Some(recommendation),
+        Some(proc_type),
+        Some(note),
+        Some(p_abandoned),
+        Some(score),
+    ) = (
+        strings(batch, "feedback_kind"),
+        strings(batch, "feedback_actor"),
+        strings(batch, "session_id"),
+        strings(batch, "start_id"),
+        strings(batch, "cmd"),
+        strings(batch, "recommendation"),
+        strings(batch, "proc_type"),
+        strings(batch, "feedback_note"),
+        floats(batch, "p_abandoned"),
+        floats(batch, "score"),
+    )
+    else {
+        return Vec::new();
+    };
+    let Some(pid) = batch
+        .column_by_name("pid")
+        .and_then(|c| c.as_any().downcast_ref::<Int32Array>())
+    else {
+        return Vec::new();
+    };
+    let Some(ts) = batch
+        .column_by_name("outcome_ts")
+        .and_then(|c| c.as_any().downcast_ref::<TimestampMicrosecondArray>())
+    else {
+        return Vec::new();
+    };
+
+    (0..batch.num_rows())
+        .filter(|&i| !kind.is_null(i))
+        .filter_map(|i| {
+            Some(FeedbackRecord {
+                session_id: session_id.value(i).to_string(),
+                pid: pid.value(i) as u32,
+                start_id: start_id.value(i).to_string(),
+                kind: FeedbackKind::parse(kind.value(i))?,
+                actor: if actor.is_null(i) {
+                    "user".to_string()
+                } else {
+                    actor.value(i).to_string()
+                },
+                note: (!note.is_null(i)).then(|| note.value(i).to_string()),
+                recorded_at: DateTime::from_timestamp_micros(ts.value(i))?,
+                cmd: cmd.value(i).to_string(),
+                recommendation: recommendation.value(i).to_string(),
+                p_abandoned: (!p_abandoned.is_null(i)).then(|| p_abandoned.value(i) as f64),
+                score: score.value(i) as f64,
+                proc_type: proc_type.value(i).to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(pid: u32, kind: FeedbackKind, micros: i64) -> FeedbackRecord {
+        FeedbackRecord {
+            session_id: "pt-20260115-143022-abcd".to_string(),
+            pid,
+            start_id: format!("{}:1700000000", pid),
+            kind,
+            actor: "agent".to_string(),
+            note: Some("still serving requests".to_string()),
+            recorded_at: DateTime::from_timestamp_micros(micros).unwrap(),
+            cmd: "node server.js".to_string(),
+            recommendation: "kill".to_string(),
+            p_abandoned: Some(0.9),
+            score: 87.0,
+            proc_type: "dev_server".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_kind_parse_accepts_cli_spellings() {
+        assert_eq!(
+            FeedbackKind::parse("false-positive"),
+            Some(FeedbackKind::FalsePositive)
+        );
+        assert_eq!(
+            FeedbackKind::parse("wrong-kill"),
+            Some(FeedbackKind::FalsePositive)
+        );
+        assert_eq!(
+            FeedbackKind::parse("missed"),
+            Some(FeedbackKind::FalseNegative)
+        );
+        assert_eq!(FeedbackKind::parse("maybe"), None);
+    }
+
+    #[test]
+    fn test_feedback_batch_matches_outcomes_schema() {
+        let batch = feedback_batch(&[record(42, FeedbackKind::FalsePositive, 1)]).unwrap();
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.schema().as_ref(), &outcomes_schema());
+    }
+
+    #[test]
+    fn test_write_and_read_feedback_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = record(42, FeedbackKind::FalsePositive, 1_700_000_000_000_000);
+        let mut second = record(43, FeedbackKind::FalseNegative, 1_700_000_000_000_001);
+        second.note = None;
+        second.p_abandoned = None;
+
+        let a = write_feedback(temp_dir.path(), "host", &first).unwrap();
+        let b = write_feedback(temp_dir.path(), "host", &second).unwrap();
+        assert_ne!(a, b);
+
+        let read = read_feedback(temp_dir.path()).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].pid, 42);
+        assert_eq!(read[0].kind, FeedbackKind::FalsePositive);
+        assert_eq!(read[0].note.as_deref(), Some("still serving requests"));
+        assert!((read[0].p_abandoned.unwrap() - 0.9).abs() < 1e-6);
+        assert_eq!(read[1].kind, FeedbackKind::FalseNegative);
+        assert!(read[1].actually_abandoned());
+        assert_eq!(read[1].p_abandoned, None);
+    }
+
+    #[test]
+    fn test_read_feedback_missing_dir_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(read_feedback(temp_dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+//! Redaction key rotation markers in the `audit` table.
+//!
+//! Exports hash identifying values as `[HASH:<key_id>:…]`, so after a key
+//! rotation the same value gets a different token. Each rotation is written
+//! as an `audit` row with `event_type = "redaction_key_rotated"` and the
+//! previous and new key IDs in `details_json`, which lets queries tell the
+//! token epochs apart:
+//!
+//! ```sql
+//! SELECT audit_ts, details_json FROM audit
+//! WHERE event_type = 'redaction_key_rotated' ORDER BY audit_ts
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::array::{Array, Int32Array, RecordBatch, StringArray, TimestampMicrosecondArray};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::{audit_schema, TableName};
+use crate::writer::{BatchedWriter, WriteError, WriterConfig};
+
+/// `event_type` of key rotation rows in the `audit` table.
+pub const KEY_ROTATION_EVENT: &str = "redaction_key_rotated";
+
+/// A redaction key rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotationEvent {
+    pub session_id: String,
+    pub rotated_at: DateTime<Utc>,
+    pub actor: String,
+    /// Key whose tokens end with this rotation.
+    pub previous_key_id: String,
+    /// Key producing tokens from now on.
+    pub key_id: String,
+    pub reason: Option<String>,
+}
+
+/// Build an `audit` table batch for a key rotation.
+pub fn key_rotation_batch(
+    event: &KeyRotationEvent,
+    host_id: &str,
+) -> Result<RecordBatch, WriteError> {
+    let message = format!(
+        "redaction key rotated: {} -> {}",
+        event.previous_key_id, event.key_id
+    );
+    let details = serde_json::json!({
+        "previous_key_id": event.previous_key_id,
+        "key_id": event.key_id,
+        "reason": event.reason,
+    });
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(
+            TimestampMicrosecondArray::from(vec![event.rotated_at.timestamp_micros()])
+                .with_timezone("UTC"),
+        ),
+        Arc::new(StringArray::from(vec![event.session_id.as_str()])),
+        Arc::new(StringArray::from(vec![KEY_ROTATION_EVENT])),
+        Arc::new(StringArray::from(vec!["info"])),
+        Arc::new(StringArray::from(vec![event.actor.as_str()])),
+        Arc::new(Int32Array::from(vec![None::<i32>])),
+        Arc::new(StringArray::from(vec![None::<&str>])),
+        Arc::new(StringArray::from(vec![message])),
+        Arc::new(StringArray::from(vec![details.to_string()])),
+        Arc::new(StringArray::from(vec![host_id])),
+    ];
+    Ok(RecordBatch::try_new(Arc::new(audit_schema()), columns)?)
+}
+
+/// Write a key rotation marker to the `audit` table under `base_dir`.
+pub fn write_key_rotation(
+    base_dir: &Path,
+    host_id: &str,
+    event: &KeyRotationEvent,
+) -> Result<PathBuf, WriteError> {
+    let batch = key_rotation_batch(event, host_id)?;
+    // The writer names files after the last `-` segment of the session ID;
+    // a per-rotation suffix keeps every marker in its own file.
+    let file_key = format!(
+        "{}-kr{}",
+        event.session_id,
+        event.rotated_at.timestamp_micros()
+    );
+    let config = WriterConfig::new(base_dir.to_path_buf(), file_key, host_id.to_string());
+    let mut writer = BatchedWriter::new(TableName::Audit, Arc::new(audit_schema()), config);
+    writer.write(batch)?;
+    writer.close()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rotation_is_written_as_an_audit_row() {
+        let dir = TempDir::new().unwrap();
+        let event = KeyRotationEvent {
+            session_id: "pt-20260115-143022-abcd".to_string(),
+            rotated_at: DateTime::from_timestamp_micros(1_768_487_422_000_000).unwrap(),
+            actor: "user".to_string(),
+            previous_key_id: "k1".to_string(),
+            key_id: "k2".to_string(),
+            reason: Some("scheduled".to_string()),
+        };
+        let path = write_key_rotation(dir.path(), "host123", &event).unwrap();
+        assert!(path.to_string_lossy().contains("audit/year="));
+
+        let mut reader =
+            ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+                .unwrap()
+                .build()
+                .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .value(0)
+                .to_string()
+        };
+        assert_eq!(column("event_type"), KEY_ROTATION_EVENT);
+        assert_eq!(column("host_id"), "host123");
+        let details: serde_json::Value = serde_json::from_str(&column("details_json")).unwrap();
+        assert_eq!(details["previous_key_id"], "k1");
+        assert_eq!(details["key_id"], "k2");
+        assert_eq!(details["reason"], "scheduled");
+    }
+}
@@ -3,14 +3,37 @@
 //! This crate provides:
 //! - Arrow schema definitions for telemetry tables
 //! - Batched Parquet writer with compression
+//! - Redacted export of the tables to CSV, JSON or Parquet
 //! - Path layout and partitioning helpers
 //! - Shadow mode observation storage with tiered retention
+//! - Long-term daily trend rollups
+//! - Feedback labels on past decisions (false positives/negatives)
+//! - Redaction key rotation markers, to tell hash token epochs apart
+//! - Disk-pressure guard that degrades to minimal telemetry near a full disk
+//! - SQL queries over the Parquet tables (`sql` feature)
 
+pub mod export;
+pub mod feedback;
+pub mod key_rotation;
+pub mod quota;
 pub mod retention;
 pub mod schema;
 pub mod shadow;
+#[cfg(feature = "sql")]
+pub mod sql;
+pub mod trends;
 pub mod writer;
 
+pub use export::{
+    export_tables, ExportError, ExportFormat, ExportOptions, ExportProgress, ExportSummary,
+    TableExport,
+};
+pub use feedback::{read_feedback, write_feedback, FeedbackError, FeedbackKind, FeedbackRecord};
+pub use key_rotation::{write_key_rotation, KeyRotationEvent, KEY_ROTATION_EVENT};
+pub use quota::{
+    DiskQuotaGuard, DiskReserve, DiskSpace, QuotaDecision, QuotaEvent, QuotaEventKind, SpaceProbe,
+    TelemetryMode, WritePriority,
+};
 pub use schema::{
     audit_schema, outcomes_schema, proc_features_schema, proc_inference_schema,
     proc_samples_schema, runs_schema, TableName, TelemetrySchema,
@@ -20,6 +43,10 @@ pub use shadow::{
     ObservationSummary, ProcessEvent, RetentionTier, ScoreResult, ShadowStorage,
     ShadowStorageConfig, ShadowStorageError, StateSnapshot, StorageStats,
 };
+pub use trends::{
+    SessionRollup, TrendCounts, TrendPoint, TrendQuery, TrendRollup, TrendSeries, TrendsError,
+    TrendsTable,
+};
 pub use writer::{BatchedWriter, WriteError, WriterConfig};
 
 /// Schema version for telemetry tables.
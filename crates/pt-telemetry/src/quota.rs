@@ -0,0 +1,477 @@
+//! Disk-pressure aware telemetry and session writes.
+//!
+//! A full disk must not fail an apply halfway through. Callers ask a
+//! [`DiskQuotaGuard`] before each write. The guard compares the free space
+//! on the target filesystem with a configurable [`DiskReserve`]:
+//!
+//! 1. If the write fits above the reserve, it goes ahead.
+//! 2. Otherwise the guard prunes telemetry in `pruning_priority` order,
+//!    oldest first (only when the telemetry lives on the same filesystem).
+//! 3. If space is still short, the guard switches to
+//!    [`TelemetryMode::Minimal`] and records a [`QuotaEvent`]. In minimal
+//!    mode optional writes (samples, shadow observations, ledgers) are
+//!    skipped; essential writes (action outcomes, audit) still go ahead as
+//!    long as the data itself fits on disk.
+//!
+//! Full mode is restored once free space climbs back above twice the reserve.
+//! Every mode change and pressure prune is appended to
+//! `quota_events.jsonl` in the telemetry directory.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::retention::{RetentionConfig, RetentionEnforcer};
+
+/// File name of the quota event log inside the telemetry directory.
+pub const QUOTA_EVENT_LOG: &str = "quota_events.jsonl";
+
+/// Free space to keep on any filesystem that telemetry or sessions write to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskReserve {
+    /// Absolute reserve in bytes.
+    #[serde(default = "default_min_free_bytes")]
+    pub min_free_bytes: u64,
+
+    /// Reserve as a percentage of the filesystem size. The larger of the
+    /// two reserves applies.
+    #[serde(default)]
+    pub min_free_pct: f64,
+}
+
+fn default_min_free_bytes() -> u64 {
+    512 * 1024 * 1024 // 512 MB
+}
+
+impl Default for DiskReserve {
+    fn default() -> Self {
+        DiskReserve {
+            min_free_bytes: default_min_free_bytes(),
+            min_free_pct: 0.0,
+        }
+    }
+}
+
+impl DiskReserve {
+    /// Effective reserve in bytes for a filesystem of `total_bytes`.
+    pub fn reserve_for(&self, total_bytes: u64) -> u64 {
+        let pct = (total_bytes as f64 * self.min_free_pct.clamp(0.0, 100.0) / 100.0) as u64;
+        self.min_free_bytes.max(pct)
+    }
+}
+
+/// Size and free space of a filesystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskSpace {
+    pub total_bytes: u64,
+    /// Bytes available to unprivileged writers.
+    pub available_bytes: u64,
+}
+
+/// Free space on the filesystem holding `path`.
+///
+/// `path` need not exist yet; its nearest existing ancestor is queried.
+#[cfg(unix)]
+pub fn disk_space(path: &Path) -> io::Result<DiskSpace> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = existing_ancestor(path)?;
+    let c_path = CString::new(existing.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid out pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let frsize = if stat.f_frsize > 0 {
+        stat.f_frsize as u64
+    } else {
+        stat.f_bsize as u64
+    };
+    Ok(DiskSpace {
+        total_bytes: (stat.f_blocks as u64).saturating_mul(frsize),
+        available_bytes: (stat.f_bavail as u64).saturating_mul(frsize),
+    })
+}
+
+/// Free space on the filesystem holding `path`.
+#[cfg(not(unix))]
+pub fn disk_space(_path: &Path) -> io::Result<DiskSpace> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "free-space checks are only supported on unix",
+    ))
+}
+
+fn existing_ancestor(path: &Path) -> io::Result<&Path> {
+    path.ancestors()
+        .find(|p| !p.as_os_str().is_empty() && p.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+}
+
+#[cfg(unix)]
+fn same_filesystem(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let dev = |p: &Path| {
+        existing_ancestor(p)
+            .and_then(fs::metadata)
+            .map(|m| m.dev())
+            .ok()
+    };
+    matches!((dev(a), dev(b)), (Some(x), Some(y)) if x == y)
+}
+
+#[cfg(not(unix))]
+fn same_filesystem(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// How important a write is when disk space runs short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WritePriority {
+    /// Written whenever the data fits, even below the reserve (outcomes, audit).
+    Essential,
+    /// Skipped in minimal mode (samples, shadow observations, ledgers).
+    Optional,
+}
+
+/// Current telemetry level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryMode {
+    #[default]
+    Full,
+    Minimal,
+}
+
+/// Outcome of a quota check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaDecision {
+    Write,
+    Skip { reason: String },
+}
+
+impl QuotaDecision {
+    pub fn allowed(&self) -> bool {
+        matches!(self, QuotaDecision::Write)
+    }
+}
+
+/// What happened under disk pressure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum QuotaEventKind {
+    /// Telemetry was pruned to make room.
+    RetentionPruned { files: usize, freed_bytes: u64 },
+    /// Optional writes are now skipped.
+    MinimalModeEntered,
+    /// Free space recovered; all writes resume.
+    FullModeRestored,
+}
+
+/// A disk-pressure event, persisted to [`QUOTA_EVENT_LOG`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaEvent {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: QuotaEventKind,
+    /// Path whose write triggered the event.
+    pub path: String,
+    pub available_bytes: u64,
+    pub reserve_bytes: u64,
+    pub requested_bytes: u64,
+}
+
+/// Free-space probe used by [`DiskQuotaGuard`]; [`disk_space`] by default.
+pub type SpaceProbe = Box<dyn Fn(&Path) -> io::Result<DiskSpace> + Send + Sync>;
+
+/// Gatekeeper for writes that may run into a full disk.
+pub struct DiskQuotaGuard {
+    telemetry_dir: PathBuf,
+    retention: RetentionConfig,
+    mode: TelemetryMode,
+    events: Vec<QuotaEvent>,
+    skipped_writes: u64,
+    probe: SpaceProbe,
+}
+
+impl DiskQuotaGuard {
+    /// Guard writes using the reserve and pruning order from `retention`.
+    pub fn new(telemetry_dir: PathBuf, retention: RetentionConfig) -> Self {
+        Self::with_probe(telemetry_dir, retention, Box::new(disk_space))
+    }
+
+    /// Guard writes with a custom free-space probe.
+    pub fn with_probe(
+        telemetry_dir: PathBuf,
+        retention: RetentionConfig,
+        probe: SpaceProbe,
+    ) -> Self {
+        Self {
+            telemetry_dir,
+            retention,
+            mode: TelemetryMode::Full,
+            events: Vec::new(),
+            skipped_writes: 0,
+            probe,
+        }
+    }
+
+    pub fn mode(&self) -> TelemetryMode {
+        self.mode
+    }
+
+    /// Events recorded by this guard, oldest first.
+    pub fn events(&self) -> &[QuotaEvent] {
+        &self.events
+    }
+
+    /// Number of writes skipped because of disk pressure.
+    pub fn skipped_writes(&self) -> u64 {
+        self.skipped_writes
+    }
+
+    /// Decide whether `requested_bytes` may be written to `path`.
+    ///
+    /// When the probe fails (unsupported platform, missing mount) the write
+    /// is allowed; the guard never blocks on an unknown.
+    pub fn check(
+        &mut self,
+        path: &Path,
+        requested_bytes: u64,
+        priority: WritePriority,
+    ) -> QuotaDecision {
+        let Ok(mut space) = (self.probe)(path) else {
+            return QuotaDecision::Write;
+        };
+        let reserve = self.retention.disk_reserve.reserve_for(space.total_bytes);
+
+        if self.mode == TelemetryMode::Minimal
+            && space.available_bytes >= reserve.saturating_mul(2).saturating_add(requested_bytes)
+        {
+            self.mode = TelemetryMode::Full;
+            info!(path = %path.display(), "disk space recovered; full telemetry restored");
+            self.record(
+                QuotaEventKind::FullModeRestored,
+                path,
+                space,
+                reserve,
+                requested_bytes,
+            );
+        }
+
+        if self.mode == TelemetryMode::Full {
+            if fits(space, requested_bytes, reserve) {
+                return QuotaDecision::Write;
+            }
+            if let Some(after) = self.prune_for(path, space, reserve, requested_bytes) {
+                space = after;
+                if fits(space, requested_bytes, reserve) {
+                    return QuotaDecision::Write;
+                }
+            }
+            self.mode = TelemetryMode::Minimal;
+            warn!(
+                path = %path.display(),
+                available_bytes = space.available_bytes,
+                reserve_bytes = reserve,
+                "disk below reserve; switching to minimal telemetry"
+            );
+            self.record(
+                QuotaEventKind::MinimalModeEntered,
+                path,
+                space,
+                reserve,
+                requested_bytes,
+            );
+        }
+
+        match priority {
+            WritePriority::Essential if space.available_bytes >= requested_bytes => {
+                QuotaDecision::Write
+            }
+            WritePriority::Essential => {
+                self.skipped_writes += 1;
+                QuotaDecision::Skip {
+                    reason: format!(
+                        "only {} bytes free, {} needed",
+                        space.available_bytes, requested_bytes
+                    ),
+                }
+            }
+            WritePriority::Optional => {
+                self.skipped_writes += 1;
+                QuotaDecision::Skip {
+                    reason: format!(
+                        "minimal telemetry mode: {} bytes free, reserve is {}",
+                        space.available_bytes, reserve
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Prune telemetry to make room, returning the free space afterwards.
+    fn prune_for(
+        &mut self,
+        path: &Path,
+        space: DiskSpace,
+        reserve: u64,
+        requested_bytes: u64,
+    ) -> Option<DiskSpace> {
+        if self.retention.keep_everything || !same_filesystem(path, &self.telemetry_dir) {
+            return None;
+        }
+        let needed = reserve
+            .saturating_add(requested_bytes)
+            .saturating_sub(space.available_bytes);
+        let mut enforcer =
+            RetentionEnforcer::new(self.telemetry_dir.clone(), self.retention.clone());
+        let pruned = match enforcer.free_space(needed, space.available_bytes, reserve) {
+            Ok(pruned) => pruned,
+            Err(e) => {
+                warn!(error = %e, "retention under disk pressure failed");
+                return None;
+            }
+        };
+        if pruned.is_empty() {
+            return None;
+        }
+        let freed_bytes = pruned.iter().map(|e| e.size_bytes).sum();
+        self.record(
+            QuotaEventKind::RetentionPruned {
+                files: pruned.len(),
+                freed_bytes,
+            },
+            path,
+            space,
+            reserve,
+            requested_bytes,
+        );
+        (self.probe)(path).ok()
+    }
+
+    fn record(
+        &mut self,
+        kind: QuotaEventKind,
+        path: &Path,
+        space: DiskSpace,
+        reserve_bytes: u64,
+        requested_bytes: u64,
+    ) {
+        let event = QuotaEvent {
+            timestamp: Utc::now(),
+            kind,
+            path: path.display().to_string(),
+            available_bytes: space.available_bytes,
+            reserve_bytes,
+            requested_bytes,
+        };
+        if let Err(e) = self.persist(&event) {
+            warn!(error = %e, "failed to persist quota event");
+        }
+        self.events.push(event);
+    }
+
+    /// Append an event to the log. Events are tiny, so this is attempted even
+    /// under pressure; failure is logged, never fatal.
+    fn persist(&self, event: &QuotaEvent) -> io::Result<()> {
+        fs::create_dir_all(&self.telemetry_dir)?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.telemetry_dir.join(QUOTA_EVENT_LOG))?;
+        let line = serde_json::to_string(event).map_err(io::Error::other)?;
+        writeln!(file, "{}", line)
+    }
+}
+
+fn fits(space: DiskSpace, requested_bytes: u64, reserve: u64) -> bool {
+    space.available_bytes >= reserve.saturating_add(requested_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn guard_with_free(dir: &Path, free: Arc<AtomicU64>) -> DiskQuotaGuard {
+        let retention = RetentionConfig {
+            disk_reserve: DiskReserve {
+                min_free_bytes: 100 * MB,
+                min_free_pct: 0.0,
+            },
+            ..Default::default()
+        };
+        DiskQuotaGuard::with_probe(
+            dir.to_path_buf(),
+            retention,
+            Box::new(move |_| {
+                Ok(DiskSpace {
+                    total_bytes: 10_000 * MB,
+                    available_bytes: free.load(Ordering::SeqCst),
+                })
+            }),
+        )
+    }
+
+    #[test]
+    fn test_reserve_uses_larger_of_bytes_and_pct() {
+        let reserve = DiskReserve {
+            min_free_bytes: 100,
+            min_free_pct: 5.0,
+        };
+        assert_eq!(reserve.reserve_for(10_000), 500);
+        assert_eq!(reserve.reserve_for(1_000), 100);
+    }
+
+    #[test]
+    fn test_writes_above_reserve_pass() {
+        let dir = tempdir().unwrap();
+        let free = Arc::new(AtomicU64::new(500 * MB));
+        let mut guard = guard_with_free(dir.path(), free);
+        let path = dir.path().join("outcomes.jsonl");
+        assert!(guard.check(&path, MB, WritePriority::Optional).allowed());
+        assert_eq!(guard.mode(), TelemetryMode::Full);
+        assert!(guard.events().is_empty());
+    }
+
+    #[test]
+    fn test_minimal_mode_skips_optional_and_keeps_essential() {
+        let dir = tempdir().unwrap();
+        let free = Arc::new(AtomicU64::new(50 * MB));
+        let mut guard = guard_with_free(dir.path(), free.clone());
+        let path = dir.path().join("session.json");
+
+        assert!(!guard.check(&path, MB, WritePriority::Optional).allowed());
+        assert_eq!(guard.mode(), TelemetryMode::Minimal);
+        assert!(guard.check(&path, MB, WritePriority::Essential).allowed());
+        assert!(!guard
+            .check(&path, 60 * MB, WritePriority::Essential)
+            .allowed());
+        assert_eq!(guard.skipped_writes(), 2);
+        assert_eq!(guard.events()[0].kind, QuotaEventKind::MinimalModeEntered);
+
+        // Above the reserve but below the hysteresis band: still minimal.
+        free.store(150 * MB, Ordering::SeqCst);
+        assert!(!guard.check(&path, MB, WritePriority::Optional).allowed());
+
+        free.store(300 * MB, Ordering::SeqCst);
+        assert!(guard.check(&path, MB, WritePriority::Optional).allowed());
+        assert_eq!(guard.mode(), TelemetryMode::Full);
+
+        let log = fs::read_to_string(dir.path().join(QUOTA_EVENT_LOG)).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.contains("\"event\":\"minimal_mode_entered\""));
+        assert!(log.contains("\"event\":\"full_mode_restored\""));
+    }
+}
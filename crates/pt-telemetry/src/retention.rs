@@ -3,6 +3,7 @@
 //! This module enforces retention policies for telemetry data:
 //! - Per-table TTL (time-to-live) enforcement
 //! - Disk budget constraints (global and per-table)
+//! - Pressure pruning when the filesystem nears its free-space reserve
 //! - Explicit retention event logging (no silent deletions)
 //! - Dry-run mode for previewing pruning actions
 //!
@@ -47,6 +48,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
+use crate::quota::DiskReserve;
 use crate::schema::TableName;
 
 /// Errors from retention operations.
@@ -96,6 +98,10 @@ pub struct RetentionConfig {
     /// Output directory for retention event logs.
     #[serde(default)]
     pub event_log_dir: Option<PathBuf>,
+
+    /// Free space to keep on the filesystem before writing more telemetry.
+    #[serde(default)]
+    pub disk_reserve: DiskReserve,
 }
 
 fn default_pruning_priority() -> Vec<String> {
@@ -123,6 +129,7 @@ impl Default for RetentionConfig {
             pruning_priority: default_pruning_priority(),
             min_free_after_bytes: default_min_free_after(),
             event_log_dir: None,
+            disk_reserve: DiskReserve::default(),
         }
     }
 }
@@ -162,6 +169,13 @@ impl RetentionConfig {
             }
         }
 
+        if !(0.0..=100.0).contains(&self.disk_reserve.min_free_pct) {
+            return Err(RetentionError::InvalidConfig(format!(
+                "disk_reserve.min_free_pct must be within 0-100, got {}",
+                self.disk_reserve.min_free_pct
+            )));
+        }
+
         Ok(())
     }
 }
@@ -232,6 +246,12 @@ pub enum RetentionReason {
 
     /// Compaction replaced this file.
     Compacted { new_file: String },
+
+    /// Filesystem free space fell below the reserve.
+    DiskPressure {
+        available_bytes: u64,
+        reserve_bytes: u64,
+    },
 }
 
 /// A candidate file for pruning.
@@ -535,21 +555,8 @@ impl RetentionEnforcer {
 
             // Sort by priority order then by age (oldest first)
             remaining.sort_by(|a, b| {
-                let a_priority = self
-                    .config
-                    .pruning_priority
-                    .iter()
-                    .position(|t| t == a.table.as_str())
-                    .unwrap_or(usize::MAX);
-                let b_priority = self
-                    .config
-                    .pruning_priority
-                    .iter()
-                    .position(|t| t == b.table.as_str())
-                    .unwrap_or(usize::MAX);
-
-                a_priority
-                    .cmp(&b_priority)
+                self.priority_index(a.table)
+                    .cmp(&self.priority_index(b.table))
                     .then_with(|| b.age().cmp(&a.age()))
             });
 
@@ -626,6 +633,68 @@ impl RetentionEnforcer {
         self.enforce_preview(&preview, true)
     }
 
+    /// Prune until at least `bytes_needed` is freed because the filesystem is
+    /// running out of space.
+    ///
+    /// Files go in `pruning_priority` order, oldest first within a table.
+    /// Honors `keep_everything`; each deletion is logged like any other
+    /// retention event.
+    pub fn free_space(
+        &mut self,
+        bytes_needed: u64,
+        available_bytes: u64,
+        reserve_bytes: u64,
+    ) -> Result<Vec<RetentionEvent>, RetentionError> {
+        if self.config.keep_everything || bytes_needed == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut all_candidates = self.scan_all_files()?;
+        let current_usage: u64 = all_candidates.iter().map(|c| c.size_bytes).sum();
+        all_candidates.sort_by(|a, b| {
+            self.priority_index(a.table)
+                .cmp(&self.priority_index(b.table))
+                .then_with(|| b.age().cmp(&a.age()))
+        });
+
+        let mut candidates = Vec::new();
+        let mut freed = 0u64;
+        for candidate in all_candidates {
+            if freed >= bytes_needed {
+                break;
+            }
+            freed += candidate.size_bytes;
+            candidates.push(PruneCandidatePreview {
+                age_days: candidate.age_days(),
+                file_path: candidate.relative_path,
+                table: candidate.table.as_str().to_string(),
+                size_bytes: candidate.size_bytes,
+                reason: RetentionReason::DiskPressure {
+                    available_bytes,
+                    reserve_bytes,
+                },
+            });
+        }
+
+        let preview = RetentionPreview {
+            files_to_prune: candidates.len(),
+            bytes_to_free: freed,
+            by_table: HashMap::new(),
+            current_usage_bytes: current_usage,
+            projected_usage_bytes: current_usage.saturating_sub(freed),
+            candidates,
+        };
+        self.enforce_preview(&preview, false)
+    }
+
+    fn priority_index(&self, table: TableName) -> usize {
+        self.config
+            .pruning_priority
+            .iter()
+            .position(|t| t == table.as_str())
+            .unwrap_or(usize::MAX)
+    }
+
     /// Enforce a specific preview (used by both enforce and dry_run).
     fn enforce_preview(
         &mut self,
@@ -1262,6 +1331,48 @@ mod tests {
         assert_eq!(pruned.table, "proc_samples");
     }
 
+    #[test]
+    fn test_free_space_prunes_by_priority_then_age() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        let files = [
+            ("outcomes", "outcomes.parquet", 1024 * 1024, 20),
+            ("proc_samples", "new.parquet", 1024 * 1024, 1),
+            ("proc_samples", "old.parquet", 1024 * 1024, 3),
+        ];
+        for (table, name, size, age) in &files {
+            let path = root.join(format!(
+                "{}/year=2025/month=01/day=15/host_id=test/{}",
+                table, name
+            ));
+            create_fake_parquet(&path, *size, *age).unwrap();
+        }
+
+        let mut enforcer = RetentionEnforcer::with_host_id(
+            root.to_path_buf(),
+            RetentionConfig::default(),
+            "test-host".to_string(),
+        );
+        let events = enforcer.free_space(1024 * 1024, 0, 512).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert!(events[0].file_path.ends_with("old.parquet"));
+        assert!(matches!(
+            events[0].reason,
+            RetentionReason::DiskPressure {
+                available_bytes: 0,
+                reserve_bytes: 512
+            }
+        ));
+        assert!(!root
+            .join("proc_samples/year=2025/month=01/day=15/host_id=test/old.parquet")
+            .exists());
+        assert!(root
+            .join("outcomes/year=2025/month=01/day=15/host_id=test/outcomes.parquet")
+            .exists());
+    }
+
     #[test]
     fn test_retention_event_log_format() {
         let dir = tempdir().unwrap();
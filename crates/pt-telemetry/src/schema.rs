@@ -24,6 +24,22 @@ pub enum TableName {
 }
 
 impl TableName {
+    /// Every table, in write order.
+    pub const ALL: [TableName; 7] = [
+        TableName::Runs,
+        TableName::ProcSamples,
+        TableName::ProcFeatures,
+        TableName::ProcInference,
+        TableName::Outcomes,
+        TableName::Audit,
+        TableName::SignatureMatches,
+    ];
+
+    /// Parse a table from its directory name.
+    pub fn parse_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.as_str() == s)
+    }
+
     /// Get the string name for directory layout.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -370,6 +386,9 @@ pub fn outcomes_schema() -> Schema {
         string_field("user_feedback", true),
         timestamp_field("feedback_ts", true),
         string_field("feedback_note", true),
+        string_field("feedback_kind", true), // false_positive, false_negative
+        string_field("feedback_actor", true), // user, agent
+        Field::new("p_abandoned", DataType::Float32, true),
         // Context
         string_field("cmd", false),
         string_field("cmdline_hash", true),
@@ -485,6 +504,7 @@ mod tests {
         assert!(schema.field_with_name("decision").is_ok());
         assert!(schema.field_with_name("action_successful").is_ok());
         assert!(schema.field_with_name("user_feedback").is_ok());
+        assert!(schema.field_with_name("feedback_kind").is_ok());
     }
 
     #[test]
@@ -503,6 +523,14 @@ mod tests {
         assert_eq!(TableName::SignatureMatches.as_str(), "signature_matches");
     }
 
+    #[test]
+    fn test_table_name_parse() {
+        for table in TableName::ALL {
+            assert_eq!(TableName::parse_str(table.as_str()), Some(table));
+        }
+        assert_eq!(TableName::parse_str("nope"), None);
+    }
+
     #[test]
     fn test_telemetry_schema_get() {
         let schemas = TelemetrySchema::new();
@@ -0,0 +1,282 @@
+//! SQL over the telemetry Parquet directories.
+//!
+//! Each table directory under the telemetry root (`proc_samples/`,
+//! `audit/`, ...) is registered with DataFusion as a table of the same name,
+//! covering every Parquet file of every partition. Queries are read-only:
+//! DDL, DML and statements such as `COPY` or `SET` are rejected.
+//!
+//! ```sql
+//! SELECT cmd, max(rss_bytes) FROM proc_samples
+//! WHERE sample_ts > now() - interval '1 day' GROUP BY cmd
+//! ```
+
+use crate::schema::TableName;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::error::ArrowError;
+use datafusion::arrow::json::writer::JsonArray;
+use datafusion::arrow::json::WriterBuilder;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::pretty::pretty_format_batches;
+use datafusion::error::DataFusionError;
+use datafusion::execution::context::SQLOptions;
+use datafusion::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Tables that can be registered, in registration order.
+pub const QUERYABLE_TABLES: &[TableName] = &[
+    TableName::Runs,
+    TableName::ProcSamples,
+    TableName::ProcFeatures,
+    TableName::ProcInference,
+    TableName::Outcomes,
+    TableName::Audit,
+    TableName::SignatureMatches,
+];
+
+#[derive(Debug, Error)]
+pub enum SqlError {
+    #[error("no telemetry tables with Parquet files under {0}")]
+    NoTables(PathBuf),
+
+    #[error("query error: {0}")]
+    DataFusion(#[from] DataFusionError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("JSON conversion error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("failed to start query runtime: {0}")]
+    Runtime(std::io::Error),
+}
+
+/// A result column.
+#[derive(Debug, Clone, Serialize)]
+pub struct SqlColumn {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+}
+
+/// Result of a telemetry SQL query.
+#[derive(Debug, Clone)]
+pub struct SqlResult {
+    pub schema: SchemaRef,
+    pub batches: Vec<RecordBatch>,
+}
+
+impl SqlResult {
+    pub fn columns(&self) -> Vec<SqlColumn> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|f| SqlColumn {
+                name: f.name().clone(),
+                data_type: f.data_type().to_string(),
+                nullable: f.is_nullable(),
+            })
+            .collect()
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.batches.iter().map(|b| b.num_rows()).sum()
+    }
+
+    /// Rows as JSON objects, with explicit nulls.
+    pub fn to_json_rows(&self) -> Result<Vec<serde_json::Value>, SqlError> {
+        if self.row_count() == 0 {
+            return Ok(Vec::new());
+        }
+        let mut writer = WriterBuilder::new()
+            .with_explicit_nulls(true)
+            .build::<_, JsonArray>(Vec::new());
+        let batches: Vec<&RecordBatch> = self.batches.iter().collect();
+        writer.write_batches(&batches)?;
+        writer.finish()?;
+        Ok(serde_json::from_slice(&writer.into_inner())?)
+    }
+
+    /// ASCII table of the rows.
+    pub fn to_table(&self) -> Result<String, SqlError> {
+        Ok(pretty_format_batches(&self.batches)?.to_string())
+    }
+}
+
+/// DataFusion context with the telemetry tables registered.
+pub struct TelemetrySql {
+    ctx: SessionContext,
+    tables: Vec<String>,
+}
+
+impl TelemetrySql {
+    /// Register every table directory under `telemetry_dir` that holds Parquet files.
+    ///
+    /// `information_schema` is enabled, so `SHOW TABLES` and
+    /// `SHOW COLUMNS FROM <table>` work.
+    pub async fn open(telemetry_dir: &Path) -> Result<Self, SqlError> {
+        let ctx =
+            SessionContext::new_with_config(SessionConfig::new().with_information_schema(true));
+        let mut tables = Vec::new();
+        for table in QUERYABLE_TABLES {
+            let dir = telemetry_dir.join(table.as_str());
+            if !has_parquet_files(&dir) {
+                continue;
+            }
+            ctx.register_parquet(
+                table.as_str(),
+                &format!("{}/", dir.display()),
+                ParquetReadOptions::default(),
+            )
+            .await?;
+            tables.push(table.as_str().to_string());
+        }
+        if tables.is_empty() {
+            return Err(SqlError::NoTables(telemetry_dir.to_path_buf()));
+        }
+        Ok(Self { ctx, tables })
+    }
+
+    /// Names of the registered tables.
+    pub fn tables(&self) -> &[String] {
+        &self.tables
+    }
+
+    /// Run a read-only query.
+    pub async fn query(&self, sql: &str) -> Result<SqlResult, SqlError> {
+        let options = SQLOptions::new()
+            .with_allow_ddl(false)
+            .with_allow_dml(false)
+            .with_allow_statements(false);
+        let df = self.ctx.sql_with_options(sql, options).await?;
+        let schema: SchemaRef = df.schema().inner().clone();
+        let batches = df.collect().await?;
+        Ok(SqlResult { schema, batches })
+    }
+}
+
+/// Run `sql` against `telemetry_dir` on a private single-threaded runtime.
+pub fn run_query(telemetry_dir: &Path, sql: &str) -> Result<(Vec<String>, SqlResult), SqlError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(SqlError::Runtime)?;
+    runtime.block_on(async {
+        let engine = TelemetrySql::open(telemetry_dir).await?;
+        let result = engine.query(sql).await?;
+        Ok((engine.tables, result))
+    })
+}
+
+fn has_parquet_files(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            has_parquet_files(&path)
+        } else {
+            path.extension().is_some_and(|ext| ext == "parquet")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::audit_schema;
+    use crate::writer::{BatchedWriter, WriterConfig};
+    use datafusion::arrow::array::{Int32Array, StringArray, TimestampMicrosecondArray};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn write_audit(dir: &Path, events: &[&str]) {
+        let schema = Arc::new(audit_schema());
+        let n = events.len();
+        let now = chrono::Utc::now().timestamp_micros();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampMicrosecondArray::from(vec![now; n]).with_timezone("UTC")),
+                Arc::new(StringArray::from(vec!["pt-20260115-143022-test"; n])),
+                Arc::new(StringArray::from(events.to_vec())),
+                Arc::new(StringArray::from(vec!["info"; n])),
+                Arc::new(StringArray::from(vec!["system"; n])),
+                Arc::new(Int32Array::from(vec![None::<i32>; n])),
+                Arc::new(StringArray::from(vec![None::<&str>; n])),
+                Arc::new(StringArray::from(vec!["message"; n])),
+                Arc::new(StringArray::from(vec![None::<&str>; n])),
+                Arc::new(StringArray::from(vec!["test-host"; n])),
+            ],
+        )
+        .unwrap();
+        let config = WriterConfig::new(
+            dir.to_path_buf(),
+            "pt-20260115-143022-test".to_string(),
+            "test-host".to_string(),
+        );
+        let mut writer = BatchedWriter::new(TableName::Audit, schema, config);
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn aggregates_over_partitioned_parquet() {
+        let dir = TempDir::new().unwrap();
+        write_audit(dir.path(), &["kill", "kill", "pause"]);
+
+        let (tables, result) = run_query(
+            dir.path(),
+            "SELECT event_type, count(*) AS n FROM audit \
+             WHERE audit_ts > now() - interval '1 day' \
+             GROUP BY event_type ORDER BY event_type",
+        )
+        .unwrap();
+        assert_eq!(tables, vec!["audit".to_string()]);
+        let names: Vec<String> = result.columns().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["event_type", "n"]);
+        let rows = result.to_json_rows().unwrap();
+        assert_eq!(rows[0]["event_type"], "kill");
+        assert_eq!(rows[0]["n"], 2);
+        assert_eq!(rows[1]["event_type"], "pause");
+        assert!(result.to_table().unwrap().contains("pause"));
+    }
+
+    #[test]
+    fn writes_and_ddl_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        write_audit(dir.path(), &["kill"]);
+        for sql in [
+            "CREATE TABLE t AS SELECT 1",
+            "COPY audit TO '/tmp/pt-sql-copy.parquet'",
+            "INSERT INTO audit SELECT * FROM audit",
+        ] {
+            assert!(run_query(dir.path(), sql).is_err(), "accepted: {sql}");
+        }
+    }
+
+    #[test]
+    fn information_schema_lists_columns() {
+        let dir = TempDir::new().unwrap();
+        write_audit(dir.path(), &["kill"]);
+        let (_, result) = run_query(
+            dir.path(),
+            "SELECT column_name FROM information_schema.columns \
+             WHERE table_name = 'audit' ORDER BY ordinal_position",
+        )
+        .unwrap();
+        let rows = result.to_json_rows().unwrap();
+        assert_eq!(rows.len(), audit_schema().fields().len());
+        assert_eq!(rows[0]["column_name"], "audit_ts");
+    }
+
+    #[test]
+    fn empty_dir_has_no_tables() {
+        let dir = TempDir::new().unwrap();
+        let err = run_query(dir.path(), "SELECT 1").unwrap_err();
+        assert!(matches!(err, SqlError::NoTables(_)));
+    }
+}
@@ -0,0 +1,404 @@
+//! Long-term trend rollups.
+//!
+//! Per-session artifacts are pruned by retention long before anyone asks how
+//! the tool performed over a quarter. This module keeps a compact table of
+//! daily rollups keyed by (day, host, category) that survives session cleanup
+//! and answers plotting queries without touching the session store.
+//!
+//! The table is a single JSON file under the telemetry directory. Each
+//! session is folded in at most once; the set of ingested session IDs is
+//! stored alongside the rollups so repeated ingestion is idempotent.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, NaiveDate};
+use pt_common::units::{parse_duration, DurationUnit};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// File name of the trends table inside the telemetry directory.
+pub const TRENDS_FILE: &str = "trends.json";
+
+/// Errors from trends table operations.
+#[derive(Error, Debug)]
+pub enum TrendsError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid range: {0}")]
+    InvalidRange(String),
+}
+
+/// Counters tracked per rollup bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrendCounts {
+    /// Candidates surfaced by plans.
+    pub candidates: u64,
+
+    /// Actions attempted against candidates.
+    pub actions: u64,
+
+    /// Actions verified to have stopped or killed the target.
+    pub recoveries: u64,
+
+    /// Actions labelled as false positives (target respawned after action).
+    pub false_positives: u64,
+
+    /// Memory freed by recoveries, in MB.
+    pub memory_recovered_mb: f64,
+}
+
+impl TrendCounts {
+    /// Add another set of counters into this one.
+    pub fn add(&mut self, other: &TrendCounts) {
+        self.candidates += other.candidates;
+        self.actions += other.actions;
+        self.recoveries += other.recoveries;
+        self.false_positives += other.false_positives;
+        self.memory_recovered_mb += other.memory_recovered_mb;
+    }
+
+    /// Whether all counters are zero.
+    pub fn is_empty(&self) -> bool {
+        *self == TrendCounts::default()
+    }
+}
+
+/// One daily rollup bucket.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendRollup {
+    pub day: NaiveDate,
+    pub host_id: String,
+    pub category: String,
+    #[serde(flatten)]
+    pub counts: TrendCounts,
+}
+
+/// Per-category counters contributed by a single session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionRollup {
+    pub session_id: String,
+    pub day: NaiveDate,
+    pub host_id: String,
+    pub categories: Vec<(String, TrendCounts)>,
+}
+
+/// Filter for [`TrendsTable::series`].
+#[derive(Debug, Clone)]
+pub struct TrendQuery {
+    /// Restrict to one category (all categories when `None`).
+    pub category: Option<String>,
+
+    /// Restrict to one host (all hosts when `None`).
+    pub host_id: Option<String>,
+
+    /// First day of the series (inclusive).
+    pub start: NaiveDate,
+
+    /// Last day of the series (inclusive).
+    pub end: NaiveDate,
+}
+
+impl TrendQuery {
+    /// Query the `days` days ending at `end` (inclusive).
+    pub fn last_days(end: NaiveDate, days: u32) -> Self {
+        let span = i64::from(days.max(1)) - 1;
+        Self {
+            category: None,
+            host_id: None,
+            start: end - Duration::days(span),
+            end,
+        }
+    }
+
+    /// Restrict to one category.
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Restrict to one host.
+    pub fn with_host(mut self, host_id: impl Into<String>) -> Self {
+        self.host_id = Some(host_id.into());
+        self
+    }
+
+    fn matches(&self, rollup: &TrendRollup) -> bool {
+        rollup.day >= self.start
+            && rollup.day <= self.end
+            && self.category.as_ref().is_none_or(|c| *c == rollup.category)
+            && self.host_id.as_ref().is_none_or(|h| *h == rollup.host_id)
+    }
+}
+
+/// A single point of a trend series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub day: NaiveDate,
+    #[serde(flatten)]
+    pub counts: TrendCounts,
+}
+
+/// Daily series returned by a trends query.
+///
+/// Days without data are present with zero counters so the series can be
+/// plotted directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendSeries {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub points: Vec<TrendPoint>,
+    pub totals: TrendCounts,
+}
+
+/// Compact table of daily rollups.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrendsTable {
+    #[serde(default)]
+    ingested_sessions: BTreeSet<String>,
+    #[serde(default)]
+    rollups: Vec<TrendRollup>,
+}
+
+impl TrendsTable {
+    /// Load a table from disk; a missing file yields an empty table.
+    pub fn load(path: &Path) -> Result<Self, TrendsError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the table to disk atomically.
+    pub fn save(&self, path: &Path) -> Result<(), TrendsError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// All rollup buckets, sorted by day, host, then category.
+    pub fn rollups(&self) -> &[TrendRollup] {
+        &self.rollups
+    }
+
+    /// Whether a session has already been folded into the table.
+    pub fn is_ingested(&self, session_id: &str) -> bool {
+        self.ingested_sessions.contains(session_id)
+    }
+
+    /// Number of sessions folded into the table.
+    pub fn ingested_count(&self) -> usize {
+        self.ingested_sessions.len()
+    }
+
+    /// Fold a session into the table.
+    ///
+    /// Returns `false` (and changes nothing) if the session was already
+    /// ingested.
+    pub fn ingest(&mut self, session: &SessionRollup) -> bool {
+        if !self.ingested_sessions.insert(session.session_id.clone()) {
+            return false;
+        }
+        for (category, counts) in &session.categories {
+            self.record(session.day, &session.host_id, category, counts);
+        }
+        true
+    }
+
+    /// Add counters to the bucket for (day, host, category).
+    pub fn record(&mut self, day: NaiveDate, host_id: &str, category: &str, counts: &TrendCounts) {
+        let key = (day, host_id, category);
+        match self
+            .rollups
+            .binary_search_by(|r| (r.day, r.host_id.as_str(), r.category.as_str()).cmp(&key))
+        {
+            Ok(idx) => self.rollups[idx].counts.add(counts),
+            Err(idx) => self.rollups.insert(
+                idx,
+                TrendRollup {
+                    day,
+                    host_id: host_id.to_string(),
+                    category: category.to_string(),
+                    counts: *counts,
+                },
+            ),
+        }
+    }
+
+    /// Distinct categories present in the table.
+    pub fn categories(&self) -> BTreeSet<&str> {
+        self.rollups.iter().map(|r| r.category.as_str()).collect()
+    }
+
+    /// Build a zero-filled daily series for the query.
+    pub fn series(&self, query: &TrendQuery) -> TrendSeries {
+        let mut points = Vec::new();
+        let mut day = query.start;
+        while day <= query.end {
+            points.push(TrendPoint {
+                day,
+                counts: TrendCounts::default(),
+            });
+            day += Duration::days(1);
+        }
+
+        let mut totals = TrendCounts::default();
+        for rollup in self.rollups.iter().filter(|r| query.matches(r)) {
+            let idx = (rollup.day - query.start).num_days() as usize;
+            points[idx].counts.add(&rollup.counts);
+            totals.add(&rollup.counts);
+        }
+
+        TrendSeries {
+            start: query.start,
+            end: query.end,
+            points,
+            totals,
+        }
+    }
+}
+
+/// Default location of the trends table for a telemetry directory.
+pub fn trends_path(telemetry_dir: &Path) -> PathBuf {
+    telemetry_dir.join(TRENDS_FILE)
+}
+
+/// Parse a range like `90d`, `12w` or `30` (days) into a whole number of days.
+pub fn parse_range_days(range: &str) -> Result<u32, TrendsError> {
+    let invalid = || TrendsError::InvalidRange(range.to_string());
+    let secs = parse_duration(range, DurationUnit::Days)
+        .map_err(|_| invalid())?
+        .as_secs();
+    if secs == 0 || secs % 86_400 != 0 {
+        return Err(invalid());
+    }
+    u32::try_from(secs / 86_400).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn counts(candidates: u64, recoveries: u64, false_positives: u64) -> TrendCounts {
+        TrendCounts {
+            candidates,
+            actions: recoveries + false_positives,
+            recoveries,
+            false_positives,
+            memory_recovered_mb: recoveries as f64 * 10.0,
+        }
+    }
+
+    fn session(id: &str, d: &str, host: &str, cats: &[(&str, TrendCounts)]) -> SessionRollup {
+        SessionRollup {
+            session_id: id.to_string(),
+            day: day(d),
+            host_id: host.to_string(),
+            categories: cats.iter().map(|(c, n)| (c.to_string(), *n)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_ingest_is_idempotent_and_merges_buckets() {
+        let mut table = TrendsTable::default();
+        let s1 = session(
+            "pt-1",
+            "2026-01-05",
+            "host-a",
+            &[("test_runner", counts(3, 2, 0))],
+        );
+        let s2 = session(
+            "pt-2",
+            "2026-01-05",
+            "host-a",
+            &[("test_runner", counts(1, 0, 1)), ("agent", counts(2, 1, 0))],
+        );
+
+        assert!(table.ingest(&s1));
+        assert!(!table.ingest(&s1));
+        assert!(table.ingest(&s2));
+
+        assert_eq!(table.ingested_count(), 2);
+        assert_eq!(table.rollups().len(), 2);
+        let runner = table
+            .rollups()
+            .iter()
+            .find(|r| r.category == "test_runner")
+            .unwrap();
+        assert_eq!(runner.counts.candidates, 4);
+        assert_eq!(runner.counts.recoveries, 2);
+        assert_eq!(runner.counts.false_positives, 1);
+    }
+
+    #[test]
+    fn test_series_is_zero_filled_and_filtered() {
+        let mut table = TrendsTable::default();
+        table.record(day("2026-01-01"), "host-a", "agent", &counts(2, 1, 0));
+        table.record(day("2026-01-03"), "host-a", "agent", &counts(1, 1, 0));
+        table.record(day("2026-01-03"), "host-b", "agent", &counts(5, 0, 2));
+        table.record(day("2026-01-03"), "host-a", "build", &counts(7, 7, 0));
+        // Outside the range.
+        table.record(day("2025-12-01"), "host-a", "agent", &counts(9, 9, 9));
+
+        let query = TrendQuery::last_days(day("2026-01-04"), 4)
+            .with_category("agent")
+            .with_host("host-a");
+        let series = table.series(&query);
+
+        assert_eq!(series.start, day("2026-01-01"));
+        assert_eq!(series.points.len(), 4);
+        assert_eq!(series.points[0].counts.candidates, 2);
+        assert!(series.points[1].counts.is_empty());
+        assert_eq!(series.points[2].counts.candidates, 1);
+        assert_eq!(series.totals.recoveries, 2);
+        assert_eq!(series.totals.false_positives, 0);
+
+        let all_hosts = table.series(&TrendQuery::last_days(day("2026-01-04"), 4));
+        assert_eq!(all_hosts.totals.candidates, 15);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = trends_path(dir.path());
+
+        assert!(TrendsTable::load(&path).unwrap().rollups().is_empty());
+
+        let mut table = TrendsTable::default();
+        table.ingest(&session(
+            "pt-1",
+            "2026-02-10",
+            "host-a",
+            &[("agent", counts(4, 3, 1))],
+        ));
+        table.save(&path).unwrap();
+
+        let loaded = TrendsTable::load(&path).unwrap();
+        assert!(loaded.is_ingested("pt-1"));
+        assert_eq!(loaded.rollups(), table.rollups());
+    }
+
+    #[test]
+    fn test_parse_range_days() {
+        assert_eq!(parse_range_days("90d").unwrap(), 90);
+        assert_eq!(parse_range_days("2w").unwrap(), 14);
+        assert_eq!(parse_range_days("30").unwrap(), 30);
+        assert!(parse_range_days("0d").is_err());
+        assert!(parse_range_days("3h").is_err());
+    }
+}
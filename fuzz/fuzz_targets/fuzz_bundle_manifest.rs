@@ -0,0 +1,21 @@
+//! Fuzz target for bundle manifest parsing and validation.
+//!
+//! Tests that `manifest.json` deserialization and `BundleManifest::validate`
+//! handle arbitrary input without panicking. The manifest is the first thing
+//! read from an untrusted bundle and drives every later file lookup.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pt_bundle::BundleManifest;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(manifest) = serde_json::from_slice::<BundleManifest>(data) {
+        let _ = manifest.validate();
+        let _ = manifest.compute_self_checksum();
+        let _ = manifest.total_bytes();
+        for entry in &manifest.files {
+            let _ = manifest.find_file(&entry.path);
+        }
+    }
+});
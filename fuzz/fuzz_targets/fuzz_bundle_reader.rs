@@ -11,5 +11,15 @@ use pt_bundle::BundleReader;
 fuzz_target!(|data: &[u8]| {
     // Try to parse as a bundle - should never panic, only return an error
     // The bundle reader expects ZIP format, so most random data will fail quickly
-    let _ = BundleReader::from_bytes(data.to_vec());
+    let Ok(mut reader) = BundleReader::from_bytes(data.to_vec()) else {
+        return;
+    };
+
+    // Inputs that get past the ZIP and manifest checks exercise the
+    // manifest-driven file lookups and checksum verification
+    let paths: Vec<String> = reader.files().iter().map(|f| f.path.clone()).collect();
+    for path in &paths {
+        let _ = reader.read_verified(path);
+    }
+    let _ = reader.verify_all();
 });
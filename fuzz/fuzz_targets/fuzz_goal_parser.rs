@@ -0,0 +1,20 @@
+//! Fuzz target for goal string parsing.
+//!
+//! Tests that `parse_goal` handles arbitrary input without panicking, and that
+//! the canonical form of any accepted goal parses back to the same goal.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pt_core::decision::goal_parser::parse_goal;
+
+fuzz_target!(|data: &str| {
+    // Goal strings come straight from the CLI (--goal), so any input must be
+    // rejected cleanly rather than panic
+    if let Ok(goal) = parse_goal(data) {
+        let canonical = goal.canonical();
+        if let Ok(reparsed) = parse_goal(&canonical) {
+            assert_eq!(reparsed.canonical(), canonical);
+        }
+    }
+});
@@ -0,0 +1,17 @@
+//! Fuzz target for replay snapshot loading.
+//!
+//! Tests that recorded snapshot JSON handles arbitrary input without
+//! panicking, including conversion back into a scan result and anonymization.
+//! Snapshots are shared for bug reproduction and may come from other hosts.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pt_core::replay::ReplaySnapshot;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(mut snapshot) = serde_json::from_slice::<ReplaySnapshot>(data) {
+        let _ = snapshot.to_scan_result();
+        snapshot.anonymize();
+    }
+});
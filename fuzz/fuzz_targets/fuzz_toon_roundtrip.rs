@@ -0,0 +1,31 @@
+//! Fuzz target for TOON encoding round-trips.
+//!
+//! Tests that any JSON value encodes to TOON without panicking and that the
+//! encoding decodes again. Values without floating-point numbers must
+//! round-trip exactly; floats may legitimately change representation.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pt_core::output::encode_toon_value;
+use serde_json::Value;
+
+fn has_float(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.is_f64(),
+        Value::Array(items) => items.iter().any(has_float),
+        Value::Object(map) => map.values().any(has_float),
+        _ => false,
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = serde_json::from_slice::<Value>(data) else {
+        return;
+    };
+    let encoded = encode_toon_value(&value);
+    let decoded = toon::try_decode(&encoded, None).expect("TOON output must decode");
+    if !has_float(&value) {
+        assert_eq!(decoded, value.into());
+    }
+});
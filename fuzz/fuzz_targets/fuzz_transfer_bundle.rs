@@ -0,0 +1,36 @@
+//! Fuzz target for fleet transfer bundle JSON.
+//!
+//! Tests that transfer bundle parsing, validation, and diffing handle
+//! arbitrary input without panicking. Transfer bundles are imported from
+//! other hosts and must be treated as untrusted.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pt_config::priors::Priors;
+use pt_core::fleet::transfer::{
+    compute_diff, export_bundle, normalize_baseline, validate_bundle, TransferBundle,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(bundle) = serde_json::from_slice::<TransferBundle>(data) else {
+        return;
+    };
+    let _ = validate_bundle(&bundle);
+    let _ = compute_diff(Some(&Priors::default()), None, &bundle);
+
+    // Re-seal with a correct checksum so validation reaches the content checks
+    if let Ok(resealed) = export_bundle(
+        bundle.priors.as_ref(),
+        bundle.signatures.as_ref(),
+        bundle.baseline_stats.as_ref(),
+        &bundle.source_host_id,
+        bundle.source_host_profile.as_deref(),
+    ) {
+        let _ = validate_bundle(&resealed);
+    }
+
+    if let (Some(mut priors), Some(stats)) = (bundle.priors, bundle.baseline_stats.as_ref()) {
+        normalize_baseline(&mut priors, stats, stats);
+    }
+});